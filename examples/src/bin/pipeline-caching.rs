@@ -14,23 +14,14 @@
 // return that pipeline directly or insert that new pipeline into the
 // cache.
 //
-// You can retrieve the data in the cache as a `Vec<u8>` and
-// save that to a binary file. Later you can load that file and build a
-// PipelineCache with the given data. Be aware that the Vulkan
-// implementation does not check if the data is valid and vulkano
-// currently does not either. Invalid data can lead to driver crashes
-// or worse. Using the same cache data with a different GPU probably
-// won't work, a simple driver update can lead to invalid data as well.
-// To check if your data is valid you can find inspiration here:
-//      https://zeux.io/2019/07/17/serializing-pipeline-cache/
-//
-// In the future, vulkano might implement those safety checks, but for
-// now, you would have to do that yourself or trust the data and the user.
+// You can save the data in the cache to a binary file. Later you can load
+// that file and build a PipelineCache with the given data. When loading,
+// vulkano checks the header of the data, and discards it if it was created
+// by a different GPU or driver version. Beyond that, the Vulkan
+// implementation does not check if the data is valid. Invalid data can lead
+// to driver crashes or worse, so you should only load files that you have
+// written yourself.
 
-use std::{
-    fs::{remove_file, rename, File},
-    io::{Read, Write},
-};
 use vulkano::{
     device::{
         physical::PhysicalDeviceType, Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo,
@@ -147,45 +138,19 @@ fn main() {
     // The cache works the same for a GraphicsPipeline, a ComputePipeline is just simpler to
     // build.
     //
-    // We are now going to retrieve the cache data into a Vec<u8> and save that to a file on
-    // our disk.
-
-    if let Ok(data) = pipeline_cache.get_data() {
-        if let Ok(mut file) = File::create("pipeline_cache.bin.tmp") {
-            if file.write_all(&data).is_ok() {
-                let _ = rename("pipeline_cache.bin.tmp", "pipeline_cache.bin");
-            } else {
-                let _ = remove_file("pipeline_cache.bin.tmp");
-            }
-        }
-    }
+    // We are now going to save the cache data to a file on our disk.
+    // If an error happens (eg. no permission for the file) we simply skip storing the cache.
+    let _ = pipeline_cache.save_to_file("pipeline_cache.bin");
 
     // The PipelineCache is now saved to disk and can be loaded the next time the application
     // is started. This way, the pipelines do not have to be rebuild and pipelines that might
     // exist in the cache can be build far quicker.
     //
-    // To load the cache from the file, we just need to load the data into a Vec<u8> and build
-    // the PipelineCache from that. Note that this function is currently unsafe as there are
-    // no checks, as it was mentioned at the start of this example.
-    let data = {
-        if let Ok(mut file) = File::open("pipeline_cache.bin") {
-            let mut data = Vec::new();
-            if file.read_to_end(&mut data).is_ok() {
-                Some(data)
-            } else {
-                None
-            }
-        } else {
-            None
-        }
-    };
-
-    let second_cache = if let Some(data) = data {
-        // This is unsafe because there is no way to be sure that the file contains valid data.
-        unsafe { PipelineCache::with_data(device, &data).unwrap() }
-    } else {
-        PipelineCache::empty(device).unwrap()
-    };
+    // If the file doesn't exist, or was created by a different GPU or driver version, an empty
+    // cache is created instead. Note that this function is unsafe, as the data beyond the header
+    // is not checked.
+    let second_cache =
+        unsafe { PipelineCache::load_from_file(device, "pipeline_cache.bin").unwrap() };
 
     // As the PipelineCache of the Vulkan implementation saves an opaque blob of data,
     // there is no real way to know if the data is correct. There might be differences
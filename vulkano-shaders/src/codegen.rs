@@ -9,9 +9,10 @@
 
 use crate::{entry_point, read_file_to_string, structs, RegisteredType, TypesMeta};
 use ahash::HashMap;
+use heck::{ToSnakeCase, ToUpperCamelCase};
 use proc_macro2::TokenStream;
-pub use shaderc::{CompilationArtifact, IncludeType, ResolvedInclude, ShaderKind};
-use shaderc::{CompileOptions, Compiler, EnvVersion, SpirvVersion, TargetEnv};
+pub use shaderc::{CompilationArtifact, IncludeType, ResolvedInclude, ShaderKind, SourceLanguage};
+use shaderc::{CompileOptions, Compiler, EnvVersion, OptimizationLevel, SpirvVersion, TargetEnv};
 use std::{
     cell::{RefCell, RefMut},
     io::Error as IoError,
@@ -58,7 +59,7 @@ fn include_callback(
                     .to_string_lossy();
 
                 return Err(format!(
-                    "Usage of relative paths in imports in embedded GLSL is not \
+                    "Usage of relative paths in imports in embedded shader source is not \
                                     allowed, try using `#include <{}>` and adding the directory \
                                     `{}` to the `include` array in your `shader!` macro call \
                                     instead.",
@@ -134,7 +135,10 @@ fn include_callback(
         )
     })?;
 
-    includes_tracker.push(file_to_include_string.clone());
+    // A file that is included more than once only needs to be tracked once.
+    if !includes_tracker.contains(&file_to_include_string) {
+        includes_tracker.push(file_to_include_string.clone());
+    }
 
     Ok(ResolvedInclude {
         resolved_name: file_to_include_string,
@@ -148,10 +152,13 @@ pub fn compile(
     base_path: &impl AsRef<Path>,
     code: &str,
     ty: ShaderKind,
+    source_language: SourceLanguage,
+    entry_point_name: &str,
     include_directories: &[impl AsRef<Path>],
     macro_defines: &[(impl AsRef<str>, impl AsRef<str>)],
     vulkan_version: Option<EnvVersion>,
     spirv_version: Option<SpirvVersion>,
+    optimization_level: Option<OptimizationLevel>,
 ) -> Result<(CompilationArtifact, Vec<String>), String> {
     let includes_tracker = RefCell::new(Vec::new());
     let compiler = Compiler::new().ok_or("failed to create GLSL compiler")?;
@@ -166,10 +173,27 @@ pub fn compile(
         compile_options.set_target_spirv(spirv_version);
     }
 
+    if let Some(optimization_level) = optimization_level {
+        compile_options.set_optimization_level(optimization_level);
+    }
+
+    compile_options.set_source_language(source_language);
+
+    if source_language == SourceLanguage::HLSL {
+        // HLSL has no `layout` qualifiers. Inputs and outputs with a user-defined semantic are
+        // given locations in the order they are declared, unless they have a `[[vk::location]]`
+        // attribute. Resources are bound according to their `register` and `space`.
+        compile_options.set_auto_map_locations(true);
+        compile_options.set_auto_bind_uniforms(true);
+        compile_options.set_hlsl_io_mapping(true);
+    }
+
     let root_source_path = if let &Some(ref path) = &path {
         path
-    } else {
+    } else if source_language == SourceLanguage::HLSL {
         // An arbitrary placeholder file name for embedded shaders
+        "shader.hlsl"
+    } else {
         "shader.glsl"
     };
 
@@ -197,7 +221,13 @@ pub fn compile(
     compile_options.set_generate_debug_info();
 
     let content = compiler
-        .compile_into_spirv(code, ty, root_source_path, "main", Some(&compile_options))
+        .compile_into_spirv(
+            code,
+            ty,
+            root_source_path,
+            entry_point_name,
+            Some(&compile_options),
+        )
         .map_err(|e| e.to_string())?;
 
     let includes = includes_tracker.borrow().clone();
@@ -240,8 +270,15 @@ pub(super) fn reflect<'a>(
         quote! { &::vulkano::shader::spirv::Capability::#name }
     });
     let spirv_extensions = reflect::spirv_extensions(&spirv);
-    let entry_points = reflect::entry_points(&spirv)
-        .map(|(name, model, info)| entry_point::write_entry_point(&name, model, &info));
+    let entry_points: Vec<_> = reflect::entry_points(&spirv).collect();
+    let entry_point_accessors = entry_point::write_entry_point_accessors(
+        entry_points
+            .iter()
+            .map(|(name, model, _)| (name.as_str(), *model)),
+    );
+    let entry_points = entry_points
+        .iter()
+        .map(|(name, model, info)| entry_point::write_entry_point(name, *model, info));
 
     let specialization_constants =
         structs::write_specialization_constants(prefix, &spirv, shared_constants, types_registry);
@@ -278,6 +315,35 @@ pub(super) fn reflect<'a>(
         #specialization_constants
     };
 
+    // When there are multiple shaders, each one also gets a module of its own with the same
+    // contents as the module generated for a single shader.
+    let shader_code = if prefix.is_empty() {
+        quote! {
+            #shader_code
+
+            #entry_point_accessors
+        }
+    } else {
+        let module_name = format_ident!("{}", prefix.to_snake_case());
+        let specialization_constants_name = if shared_constants {
+            format_ident!("SpecializationConstants")
+        } else {
+            format_ident!("{}SpecializationConstants", prefix.to_upper_camel_case())
+        };
+
+        quote! {
+            #shader_code
+
+            pub mod #module_name {
+                pub use super::#load_name as load;
+                pub use super::#specialization_constants_name as SpecializationConstants;
+                pub use super::ty;
+
+                #entry_point_accessors
+            }
+        }
+    };
+
     let structs = structs::write_structs(prefix, &spirv, types_meta, types_registry);
 
     Ok((shader_code, structs))
@@ -305,9 +371,12 @@ impl From<SpirvError> for Error {
 mod tests {
     use super::*;
     use crate::codegen::compile;
-    use shaderc::ShaderKind;
+    use shaderc::{ShaderKind, SourceLanguage};
     use std::path::{Path, PathBuf};
-    use vulkano::shader::{reflect, spirv::Spirv};
+    use vulkano::shader::{
+        reflect,
+        spirv::{Capability, Spirv},
+    };
 
     #[cfg(not(target_os = "windows"))]
     pub fn path_separator() -> &'static str {
@@ -363,10 +432,13 @@ mod tests {
         void main() {}
         ",
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &includes,
             &defines,
             None,
             None,
+            None,
         )
         .unwrap();
         let spirv = Spirv::new(comp.as_binary()).unwrap();
@@ -393,10 +465,13 @@ mod tests {
         void main() {}
         ",
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &includes,
             &defines,
             None,
             None,
+            None,
         )
         .unwrap();
         let spirv = Spirv::new(comp.as_binary()).unwrap();
@@ -425,13 +500,99 @@ mod tests {
         void main() {}
         ",
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+        structs::write_structs("", &spirv, &TypesMeta::default(), &mut HashMap::default());
+    }
+
+    #[test]
+    fn test_struct_tail_padding() {
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        #version 450
+        struct MyStruct {
+            double d;
+            float f;
+        };
+        layout(set = 0, binding = 0) buffer Data {
+            MyStruct s;
+            float after;
+        };
+        void main() {}
+        ",
+            ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+        let structs =
+            structs::write_structs("", &spirv, &TypesMeta::default(), &mut HashMap::default())
+                .to_string();
+
+        // `MyStruct` is padded to a multiple of the alignment of `f64`, so that `Data` has no
+        // implicit padding before `after`.
+        assert!(structs.contains("_dummy0 : [u8 ; 4usize]"));
+        assert!(structs.contains("size_of :: < MyStruct > () == 16usize"));
+        assert!(structs.contains("size_of :: < Data > () == 24usize"));
+    }
+
+    #[test]
+    fn test_buffer_reference() {
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        #version 450
+        #extension GL_EXT_buffer_reference : require
+
+        layout(buffer_reference, std430) buffer Vertices {
+            vec4 positions[];
+        };
+
+        layout(push_constant) uniform PushConstants {
+            Vertices vertices;
+            uint index;
+        };
+
+        void main() {
+            gl_Position = vertices.positions[index];
+        }
+        ",
+            ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &includes,
             &defines,
             None,
             None,
+            None,
         )
         .unwrap();
         let spirv = Spirv::new(comp.as_binary()).unwrap();
+
+        let capabilities: Vec<_> = reflect::spirv_capabilities(&spirv).collect();
+        assert!(capabilities.contains(&&Capability::PhysicalStorageBufferAddresses));
+
         structs::write_structs("", &spirv, &TypesMeta::default(), &mut HashMap::default());
     }
 
@@ -450,10 +611,13 @@ mod tests {
         void main() {}
         ",
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &empty_includes,
             &defines,
             None,
             None,
+            None,
         )
         .expect("Cannot resolve include files");
 
@@ -467,6 +631,8 @@ mod tests {
         void main() {}
         ",
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &[
                 root_path.join("tests").join("include_dir_a"),
                 root_path.join("tests").join("include_dir_b"),
@@ -474,6 +640,7 @@ mod tests {
             &defines,
             None,
             None,
+            None,
         )
         .expect("Cannot resolve include files");
         assert_eq!(
@@ -497,10 +664,13 @@ mod tests {
         void main() {}
         ",
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &[root_path.join("tests").join("include_dir_a")],
             &defines,
             None,
             None,
+            None,
         )
         .expect("Cannot resolve include files");
         assert_eq!(
@@ -534,10 +704,13 @@ mod tests {
                 absolute_path_str
             ),
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &empty_includes,
             &defines,
             None,
             None,
+            None,
         )
         .expect("Cannot resolve include files");
         assert_eq!(
@@ -557,6 +730,8 @@ mod tests {
         void main() {}
         ",
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &[
                 root_path.join("tests").join("include_dir_b"),
                 root_path.join("tests").join("include_dir_c"),
@@ -564,6 +739,7 @@ mod tests {
             &defines,
             None,
             None,
+            None,
         )
         .expect("Cannot resolve include files");
         assert_eq!(
@@ -596,10 +772,13 @@ mod tests {
             &Path::new(""),
             need_defines,
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &empty_includes,
             &no_defines,
             None,
             None,
+            None,
         );
         assert!(compile_no_defines.is_err());
 
@@ -608,10 +787,13 @@ mod tests {
             &Path::new(""),
             need_defines,
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &empty_includes,
             &defines,
             None,
             None,
+            None,
         );
         compile_defines.expect("Setting shader macros did not work");
     }
@@ -754,10 +936,13 @@ mod tests {
         }
         ",
             ShaderKind::Vertex,
+            SourceLanguage::GLSL,
+            "main",
             &includes,
             &defines,
             None,
             None,
+            None,
         )
         .unwrap();
         let spirv = Spirv::new(comp.as_binary()).unwrap();
@@ -777,4 +962,306 @@ mod tests {
         }
         panic!("Could not find entrypoint");
     }
+
+    #[test]
+    fn test_debug_printf() {
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        #version 450
+        #extension GL_EXT_debug_printf : enable
+
+        layout(set = 0, binding = 0) buffer Buffer {
+            uint data[];
+        } bo;
+
+        void main() {
+            debugPrintfEXT(\"data[0] = %u\", bo.data[0]);
+        }
+        ",
+            ShaderKind::Compute,
+            SourceLanguage::GLSL,
+            "main",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+
+        assert!(reflect::spirv_extensions(&spirv).any(|ext| ext == "SPV_KHR_non_semantic_info"));
+
+        if let Some((_, _, info)) = reflect::entry_points(&spirv).next() {
+            let bindings: Vec<_> = info.descriptor_binding_requirements.into_keys().collect();
+            assert_eq!(bindings, [(0, 0)]);
+
+            return;
+        }
+        panic!("Could not find entrypoint");
+    }
+
+    #[test]
+    fn test_descriptor_indexing() {
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        #version 450
+        #extension GL_EXT_nonuniform_qualifier : enable
+
+        layout(location = 0) in flat uint index;
+        layout(location = 1) in vec2 uv;
+        layout(location = 0) out vec4 color;
+
+        layout(set = 0, binding = 0) uniform sampler2D textures[];
+
+        void main() {
+            color = texture(textures[nonuniformEXT(index)], uv);
+        }
+        ",
+            ShaderKind::Fragment,
+            SourceLanguage::GLSL,
+            "main",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+
+        let capabilities: Vec<_> = reflect::spirv_capabilities(&spirv).collect();
+        assert!(capabilities.contains(&&Capability::RuntimeDescriptorArray));
+        assert!(capabilities.contains(&&Capability::ShaderNonUniform));
+        assert!(capabilities.contains(&&Capability::SampledImageArrayNonUniformIndexing));
+
+        if let Some((_, _, info)) = reflect::entry_points(&spirv).next() {
+            let reqs = &info.descriptor_binding_requirements[&(0, 0)];
+            assert_eq!(reqs.descriptor_count, None);
+
+            return;
+        }
+        panic!("Could not find entrypoint");
+    }
+
+    #[test]
+    fn test_hlsl() {
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        Texture2D tex : register(t0);
+        SamplerState samp : register(s1, space2);
+
+        float4 PSMain(float4 color : COLOR0, float2 uv : TEXCOORD0) : SV_Target {
+            return color * tex.Sample(samp, uv);
+        }
+        ",
+            ShaderKind::Fragment,
+            SourceLanguage::HLSL,
+            "PSMain",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+
+        if let Some((name, _, info)) = reflect::entry_points(&spirv).next() {
+            assert_eq!(name, "PSMain");
+
+            let mut bindings: Vec<_> = info.descriptor_binding_requirements.into_keys().collect();
+            bindings.sort_unstable();
+            assert_eq!(bindings, [(0, 0), (2, 1)]);
+
+            let mut locations: Vec<_> = info
+                .input_interface
+                .elements()
+                .iter()
+                .map(|entry| entry.location)
+                .collect();
+            locations.sort_unstable();
+            assert_eq!(locations, [0, 1]);
+
+            return;
+        }
+        panic!("Could not find entrypoint");
+    }
+
+    #[test]
+    fn test_rust_idents() {
+        use crate::structs::to_rust_ident;
+
+        assert_eq!(to_rust_ident("Uniforms"), "Uniforms");
+        assert_eq!(to_rust_ident("my_shader::Uniforms"), "Uniforms");
+        assert_eq!(to_rust_ident("Light<f32>"), "Light");
+        assert_eq!(to_rust_ident("0"), "_0");
+        assert_eq!(to_rust_ident("type"), "type_");
+        assert_eq!(to_rust_ident("a.b"), "a_b");
+    }
+
+    #[test]
+    fn test_float16_int8_structs() {
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        #version 450
+        #extension GL_EXT_shader_explicit_arithmetic_types_float16 : require
+        #extension GL_EXT_shader_explicit_arithmetic_types_int8 : require
+        layout(local_size_x = 64) in;
+        layout(binding = 0) buffer Data {
+            f16vec4 halves;
+            int8_t bytes[4];
+        };
+        void main() {
+            halves *= float16_t(2.0);
+            bytes[0] += int8_t(1);
+        }
+        ",
+            ShaderKind::Compute,
+            SourceLanguage::GLSL,
+            "main",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+        let structs =
+            structs::write_structs("", &spirv, &TypesMeta::default(), &mut HashMap::default())
+                .to_string();
+        assert!(structs.contains("f16"));
+        assert!(structs.contains("i8"));
+    }
+
+    #[test]
+    fn test_fragment_shader_interlock() {
+        use vulkano::shader::{FragmentShaderExecution, FragmentShaderInterlock, ShaderExecution};
+
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        #version 450
+        #extension GL_ARB_fragment_shader_interlock : require
+        layout(pixel_interlock_ordered) in;
+        layout(set = 0, binding = 0, rgba8) uniform coherent image2D img;
+        void main() {
+            beginInvocationInterlockARB();
+            ivec2 coord = ivec2(gl_FragCoord.xy);
+            imageStore(img, coord, imageLoad(img, coord) * 0.5);
+            endInvocationInterlockARB();
+        }
+        ",
+            ShaderKind::Fragment,
+            SourceLanguage::GLSL,
+            "main",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+
+        let capabilities: Vec<_> = reflect::spirv_capabilities(&spirv).collect();
+        assert!(capabilities.contains(&&Capability::FragmentShaderPixelInterlockEXT));
+
+        let (_, _, info) = reflect::entry_points(&spirv).next().unwrap();
+        assert_eq!(
+            info.execution,
+            ShaderExecution::Fragment(FragmentShaderExecution {
+                interlock: Some(FragmentShaderInterlock::PixelOrdered),
+            }),
+        );
+    }
+
+    #[test]
+    fn test_tessellation_shader_execution() {
+        use vulkano::shader::{
+            ShaderExecution, TessellationShaderExecution, TessellationShaderSubdivision,
+        };
+
+        let includes: [PathBuf; 0] = [];
+        let defines: [(String, String); 0] = [];
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        #version 450
+        layout(vertices = 3) out;
+        void main() {
+            gl_out[gl_InvocationID].gl_Position = gl_in[gl_InvocationID].gl_Position;
+        }
+        ",
+            ShaderKind::TessControl,
+            SourceLanguage::GLSL,
+            "main",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+        let (_, _, info) = reflect::entry_points(&spirv).next().unwrap();
+        assert_eq!(
+            info.execution,
+            ShaderExecution::TessellationControl(TessellationShaderExecution {
+                output_vertices: Some(3),
+                subdivision: None,
+            }),
+        );
+
+        let (comp, _) = compile(
+            None,
+            &Path::new(""),
+            "
+        #version 450
+        layout(quads, equal_spacing, cw) in;
+        void main() {
+            gl_Position = gl_in[0].gl_Position;
+        }
+        ",
+            ShaderKind::TessEvaluation,
+            SourceLanguage::GLSL,
+            "main",
+            &includes,
+            &defines,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let spirv = Spirv::new(comp.as_binary()).unwrap();
+        let (_, _, info) = reflect::entry_points(&spirv).next().unwrap();
+        assert_eq!(
+            info.execution,
+            ShaderExecution::TessellationEvaluation(TessellationShaderExecution {
+                output_vertices: None,
+                subdivision: Some(TessellationShaderSubdivision::Quads),
+            }),
+        );
+    }
 }
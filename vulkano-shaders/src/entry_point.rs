@@ -7,15 +7,17 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use ahash::HashMap;
+use crate::structs::to_rust_ident;
+use ahash::{HashMap, HashSet};
+use heck::ToSnakeCase;
 use proc_macro2::TokenStream;
 use vulkano::{
     pipeline::layout::PushConstantRange,
     shader::{
         spirv::ExecutionModel, DescriptorBindingRequirements, DescriptorIdentifier,
         DescriptorRequirements, EntryPointInfo, ShaderExecution, ShaderInterface,
-        ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderStages,
-        SpecializationConstantRequirements,
+        ShaderInterfaceEntry, ShaderInterfaceEntryType, ShaderStages, SpecializationConstant,
+        SpecializationConstantRequirements, TessellationShaderExecution,
     },
 };
 
@@ -55,14 +57,77 @@ pub(super) fn write_entry_point(
     }
 }
 
+/// Writes a module with a function for each entry point, which looks it up in a loaded shader
+/// module.
+pub(super) fn write_entry_point_accessors<'a>(
+    entry_points: impl IntoIterator<Item = (&'a str, ExecutionModel)>,
+) -> TokenStream {
+    let entry_points: Vec<_> = entry_points.into_iter().collect();
+    let mut used_names = HashSet::default();
+
+    let accessors = entry_points.iter().map(|&(name, model)| {
+        let mut fn_name = to_rust_ident(&name.to_snake_case());
+
+        // SPIR-V allows several entry points with the same name, if their execution models
+        // differ.
+        if entry_points
+            .iter()
+            .filter(|&&(other_name, _)| other_name == name)
+            .count()
+            > 1
+        {
+            fn_name = format!("{}_{}", fn_name, format!("{:?}", model).to_snake_case());
+        }
+
+        if !used_names.insert(fn_name.clone()) {
+            panic!(
+                "Entry point {:?} has the same Rust name as another entry point, `{}`",
+                name, fn_name,
+            );
+        }
+
+        let fn_name = format_ident!("{}", fn_name);
+        let doc = format!(" Returns the `{}` entry point of the shader module.", name);
+        let model = syn::parse_str::<syn::Path>(&format!(
+            "::vulkano::shader::spirv::ExecutionModel::{:?}",
+            model
+        ))
+        .unwrap();
+
+        quote! {
+            #[doc = #doc]
+            ///
+            /// # Panics
+            ///
+            /// - Panics if `module` was not loaded from this shader.
+            #[inline]
+            pub fn #fn_name(
+                module: &::vulkano::shader::ShaderModule,
+            ) -> ::vulkano::shader::EntryPoint<'_> {
+                module
+                    .entry_point_with_execution(#name, #model)
+                    .expect("the shader module does not contain the entry point")
+            }
+        }
+    });
+
+    quote! {
+        pub mod entry_points {
+            #( #accessors )*
+        }
+    }
+}
+
 fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
     match execution {
         ShaderExecution::Vertex => quote! { ::vulkano::shader::ShaderExecution::Vertex },
-        ShaderExecution::TessellationControl => {
-            quote! { ::vulkano::shader::ShaderExecution::TessellationControl }
+        ShaderExecution::TessellationControl(execution) => {
+            let execution = write_tessellation_shader_execution(execution);
+            quote! { ::vulkano::shader::ShaderExecution::TessellationControl(#execution) }
         }
-        ShaderExecution::TessellationEvaluation => {
-            quote! { ::vulkano::shader::ShaderExecution::TessellationEvaluation }
+        ShaderExecution::TessellationEvaluation(execution) => {
+            let execution = write_tessellation_shader_execution(execution);
+            quote! { ::vulkano::shader::ShaderExecution::TessellationEvaluation(#execution) }
         }
         ShaderExecution::Geometry(::vulkano::shader::GeometryShaderExecution { input }) => {
             let input = format_ident!("{}", format!("{:?}", input));
@@ -74,7 +139,22 @@ fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
                 )
             }
         }
-        ShaderExecution::Fragment => quote! { ::vulkano::shader::ShaderExecution::Fragment },
+        ShaderExecution::Fragment(::vulkano::shader::FragmentShaderExecution { interlock }) => {
+            let interlock = match interlock {
+                Some(interlock) => {
+                    let interlock = format_ident!("{}", format!("{:?}", interlock));
+                    quote! { Some(::vulkano::shader::FragmentShaderInterlock::#interlock) }
+                }
+                None => quote! { None },
+            };
+            quote! {
+                ::vulkano::shader::ShaderExecution::Fragment(
+                    ::vulkano::shader::FragmentShaderExecution {
+                        interlock: #interlock,
+                    }
+                )
+            }
+        }
         ShaderExecution::Compute => quote! { ::vulkano::shader::ShaderExecution::Compute },
         ShaderExecution::RayGeneration => {
             quote! { ::vulkano::shader::ShaderExecution::RayGeneration }
@@ -94,6 +174,32 @@ fn write_shader_execution(execution: &ShaderExecution) -> TokenStream {
     }
 }
 
+fn write_tessellation_shader_execution(execution: &TessellationShaderExecution) -> TokenStream {
+    let &TessellationShaderExecution {
+        output_vertices,
+        subdivision,
+    } = execution;
+
+    let output_vertices = match output_vertices {
+        Some(output_vertices) => quote! { Some(#output_vertices) },
+        None => quote! { None },
+    };
+    let subdivision = match subdivision {
+        Some(subdivision) => {
+            let subdivision = format_ident!("{}", format!("{:?}", subdivision));
+            quote! { Some(::vulkano::shader::TessellationShaderSubdivision::#subdivision) }
+        }
+        None => quote! { None },
+    };
+
+    quote! {
+        ::vulkano::shader::TessellationShaderExecution {
+            output_vertices: #output_vertices,
+            subdivision: #subdivision,
+        }
+    }
+}
+
 fn write_descriptor_binding_requirements(
     descriptor_binding_requirements: &HashMap<(u32, u32), DescriptorBindingRequirements>,
 ) -> TokenStream {
@@ -239,6 +345,55 @@ fn write_push_constant_requirements(
     }
 }
 
+fn write_specialization_constant(value: &SpecializationConstant) -> TokenStream {
+    match *value {
+        SpecializationConstant::Bool(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::Bool(#value) }
+        }
+        SpecializationConstant::I8(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::I8(#value) }
+        }
+        SpecializationConstant::I16(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::I16(#value) }
+        }
+        SpecializationConstant::I32(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::I32(#value) }
+        }
+        SpecializationConstant::I64(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::I64(#value) }
+        }
+        SpecializationConstant::U8(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::U8(#value) }
+        }
+        SpecializationConstant::U16(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::U16(#value) }
+        }
+        SpecializationConstant::U32(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::U32(#value) }
+        }
+        SpecializationConstant::U64(value) => {
+            quote! { ::vulkano::shader::SpecializationConstant::U64(#value) }
+        }
+        // Floats are written as their bits, so that NaN and infinity are preserved.
+        SpecializationConstant::F16(value) => {
+            let bits = value.to_bits();
+            quote! {
+                ::vulkano::shader::SpecializationConstant::F16(
+                    ::vulkano::half::f16::from_bits(#bits)
+                )
+            }
+        }
+        SpecializationConstant::F32(value) => {
+            let bits = value.to_bits();
+            quote! { ::vulkano::shader::SpecializationConstant::F32(f32::from_bits(#bits)) }
+        }
+        SpecializationConstant::F64(value) => {
+            let bits = value.to_bits();
+            quote! { ::vulkano::shader::SpecializationConstant::F64(f64::from_bits(#bits)) }
+        }
+    }
+}
+
 fn write_specialization_constant_requirements(
     specialization_constant_requirements: &HashMap<u32, SpecializationConstantRequirements>,
 ) -> TokenStream {
@@ -246,12 +401,23 @@ fn write_specialization_constant_requirements(
         specialization_constant_requirements
             .iter()
             .map(|(&constant_id, reqs)| {
-                let SpecializationConstantRequirements { size } = reqs;
+                let SpecializationConstantRequirements {
+                    size,
+                    default_value,
+                } = reqs;
+                let default_value = match default_value {
+                    Some(default_value) => {
+                        let default_value = write_specialization_constant(default_value);
+                        quote! { Some(#default_value) }
+                    }
+                    None => quote! { None },
+                };
                 quote! {
                     (
                         #constant_id,
                         ::vulkano::shader::SpecializationConstantRequirements {
                             size: #size,
+                            default_value: #default_value,
                         },
                     ),
                 }
@@ -1,5 +1,5 @@
 //! The procedural macro for vulkano's shader system.
-//! Manages the compile-time compilation of GLSL into SPIR-V and generation of assosciated rust code.
+//! Manages the compile-time compilation of GLSL or HLSL into SPIR-V and generation of assosciated rust code.
 //!
 //! # Basic usage
 //!
@@ -41,11 +41,22 @@
 //! data, verifying that the passed-in `Device` has the appropriate features
 //! enabled.
 //! * If the `shaders` option is used, then instead of one `load` constructor, there is one for each
-//! shader. They are named based on the provided names, `load_first`, `load_second` etc.
+//! shader. They are named based on the provided names, `load_first`, `load_second` etc. Each
+//! shader also gets a module, `first`, `second` etc., that contains its `load` constructor and
+//! `SpecializationConstants` struct under the same names as for a single shader.
+//! * The `entry_points` module, which contains a function for each entry point of the shader,
+//! named after the entry point in snake case. It takes the loaded `ShaderModule` and returns the
+//! [`EntryPoint`][EntryPoint], so that modules with several entry points don't need to look them
+//! up by name. With the `shaders` option, each shader's module has its own `entry_points` module.
 //! * A Rust struct translated from each struct contained in the shader data.
-//! By default each structure has a `Clone` and a `Copy` implementations. This
-//! behavior could be customized through the `types_meta` macro option(see below
-//! for details).
+//! By default each structure has `Clone` and `Copy` implementations, as well as
+//! [`Zeroable`][Zeroable] and [`Pod`][Pod] implementations so that it can be
+//! used as buffer contents. This behavior could be customized through the
+//! `types_meta` macro option(see below for details).
+//! The structs match the `std140` or `std430` layout of the shader, with any
+//! padding added as explicit `_dummyX` members. Each struct is accompanied by a
+//! compile-time assertion that its size, and therefore the offsets of all its
+//! members, matches the shader.
 //! * The `SpecializationConstants` struct. This contains a field for every
 //! specialization constant found in the shader data. Implementations of
 //! `Default` and [`SpecializationConstants`][SpecializationConstants] are also
@@ -95,7 +106,9 @@
 //!
 //! ## `ty: "..."`
 //!
-//! This defines what shader type the given GLSL source will be compiled into.
+//! This defines what shader type the given GLSL source will be compiled into. It is not needed
+//! with `bytes`, because precompiled SPIR-V already specifies the execution model of each entry
+//! point.
 //! The type can be any of the following:
 //!
 //! * `vertex`
@@ -125,6 +138,97 @@
 //! **Note**: If your shader contains multiple entrypoints with different
 //! descriptor sets, you may also need to enable `exact_entrypoint_interface`.
 //!
+//! Instead of a string literal, the path can be given as `env!("NAME")`, in which case it is read
+//! from the environment variable `NAME` when the macro is expanded. The path is then usually
+//! absolute. This is intended for shaders that are compiled by a build script, which sets the
+//! variable with `cargo:rustc-env`.
+//!
+//! ## Shaders written in Rust
+//!
+//! Shader crates written with [rust-gpu] are compiled to SPIR-V by a build script using
+//! `spirv-builder`, which prints the path of the compiled module as a `cargo:rustc-env` variable
+//! named after the shader crate. That variable can be passed to `bytes`:
+//!
+//! ```ignore
+//! // build.rs, with `spirv-builder` as a build dependency.
+//! use spirv_builder::{MetadataPrintout, SpirvBuilder};
+//!
+//! fn main() {
+//!     SpirvBuilder::new("my-shaders", "spirv-unknown-vulkan1.1")
+//!         .print_metadata(MetadataPrintout::Full)
+//!         .build()
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! ```ignore
+//! mod shaders {
+//!     vulkano_shaders::shader!{
+//!         bytes: env!("my_shaders.spv"),
+//!     }
+//! }
+//!
+//! let module = shaders::load(device.clone())?;
+//! let vs = shaders::entry_points::main_vs(&module);
+//! let fs = shaders::entry_points::main_fs(&module);
+//! ```
+//!
+//! The module contains all the entry points of the shader crate, for all shader stages, and
+//! requires the SPIR-V version that corresponds to the target, SPIR-V 1.3 for
+//! `spirv-unknown-vulkan1.1`. The capabilities and extensions that are given with the builder's
+//! `capability` and `extension` methods are checked against the device when the module is loaded,
+//! like those of any other shader.
+//!
+//! rust-gpu names types after their Rust path, and the members of tuple structs after their
+//! index. The generated structs use the last segment of the path, without generic arguments, and
+//! members such as `0` become `_0`.
+//!
+//! ## `lang: "..."`
+//!
+//! Sets the language of the shader source, which can be `glsl` (the default) or `hlsl`. This
+//! applies to all shaders of the macro invocation, and is ignored for `bytes`.
+//!
+//! HLSL has no `layout` qualifiers, so the interface of an HLSL shader is mapped as follows:
+//!
+//! * Inputs and outputs with a system-value semantic, such as `SV_Position`, become the
+//!   corresponding built-in variables.
+//! * Inputs and outputs with any other semantic, such as `TEXCOORD0`, are given locations in the
+//!   order in which they are declared. A location can also be given explicitly with the
+//!   `[[vk::location(N)]]` attribute.
+//! * Resources are bound according to their `register` binding and `space`. For example
+//!   `register(t1, space2)` becomes binding 1 of descriptor set 2. The `[[vk::binding(N, M)]]`
+//!   attribute can be used instead.
+//!
+//! The generated descriptor requirements and Rust structs are then derived from the compiled
+//! SPIR-V in the same way as for GLSL.
+//!
+//! ```
+//! mod ps {
+//!     vulkano_shaders::shader!{
+//!         ty: "fragment",
+//!         lang: "hlsl",
+//!         entry_point: "PSMain",
+//!         src: "
+//! Texture2D tex : register(t0);
+//! SamplerState samp : register(s1);
+//!
+//! float4 PSMain(float2 uv : TEXCOORD0) : SV_Target {
+//!     return tex.Sample(samp, uv);
+//! }"
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! ## `entry_point: "..."`
+//!
+//! The name of the entry point of the shader in the generated shader module. For HLSL, this is
+//! also the name of the entry point function in the source code. GLSL shaders must always use
+//! `main` in the source code, which is then renamed. This applies to all shaders of the macro
+//! invocation, and is ignored for `bytes`.
+//!
+//! The default value is `main`.
+//!
 //! ## `shaders: { First: {src: "...", ty: "..."}, ... }`
 //!
 //! With these options the user can compile several shaders at a single macro invocation.
@@ -136,6 +240,56 @@
 //!
 //! Each entry values expecting `src`, `path`, `bytes`, and `ty` pairs same as above.
 //!
+//! In addition, a module named after each entry key in snake case is generated, which re-exports
+//! the `load` function and `SpecializationConstants` struct of that shader without the prefix, as
+//! well as the shared `ty` module. This makes it possible to use several shaders from one
+//! invocation in the same way as shaders from separate invocations:
+//!
+//! ```
+//! mod shaders {
+//!     vulkano_shaders::shader!{
+//!         shaders: {
+//!             vs: {
+//!                 ty: "vertex",
+//!                 src: "
+//! #version 450
+//!
+//! layout(push_constant) uniform Data {
+//!     vec4 color;
+//! } data;
+//!
+//! layout(location = 0) in vec2 position;
+//!
+//! void main() {
+//!     gl_Position = vec4(position, 0.0, 1.0);
+//! }"
+//!             },
+//!             fs: {
+//!                 ty: "fragment",
+//!                 src: "
+//! #version 450
+//!
+//! layout(push_constant) uniform Data {
+//!     vec4 color;
+//! } data;
+//!
+//! layout(location = 0) out vec4 f_color;
+//!
+//! void main() {
+//!     f_color = data.color;
+//! }"
+//!             },
+//!         },
+//!     }
+//! }
+//!
+//! // Both shaders use the same `shaders::ty::Data` struct.
+//! use shaders::{fs, vs};
+//! let _load_vs = vs::load;
+//! let _load_fs = fs::load;
+//! let _data = fs::ty::Data { color: [1.0; 4] };
+//! ```
+//!
 //! Also `SpecializationConstants` can all be shared between shaders by specifying
 //! `shared_constants: true,` entry-flag of the `shaders` map. This feature is turned-off by
 //! default.
@@ -149,6 +303,10 @@
 //! to specify one or more standard include directories. Relative paths are relative to the
 //! directory, which contains the source file the `#include "..."` directive is declared in.
 //!
+//! Every file that is included, directly or through another included file, is tracked by Cargo in
+//! the same way as the file given with `path` or `bytes`. Modifying any of these files causes the
+//! crate to be recompiled, so that the generated code always matches the shader sources.
+//!
 //! ## `define: [("NAME", "VALUE"), ...]`
 //!
 //! Adds the given macro definitions to the pre-processor. This is equivalent to passing `-DNAME=VALUE`
@@ -164,13 +322,37 @@
 //! The generated code must be supported by the device at runtime. If not, then an error will be
 //! returned when calling `Shader::load`.
 //!
+//! Shaders that use `debugPrintfEXT` (from the `GL_EXT_debug_printf` GLSL extension) require the
+//! `SPV_KHR_non_semantic_info` SPIR-V extension, which means that the
+//! [`khr_shader_non_semantic_info`](vulkano::device::DeviceExtensions::khr_shader_non_semantic_info)
+//! device extension must be enabled, or the device must support Vulkan 1.3. See
+//! [`Message::debug_printf_payload`](vulkano::instance::debug::Message::debug_printf_payload)
+//! for how to receive the printed messages.
+//!
+//! ## `optimize: "..."`
+//!
+//! Runs the SPIR-V optimizer on the compiled shaders. The value can be `none`, `size` to make the
+//! shaders as small as possible, or `performance` to make them run as fast as possible. Both
+//! `size` and `performance` also remove dead code. Smaller shaders make the binary smaller and
+//! take less time to be compiled by the driver.
+//!
+//! If this option is not specified, the optimizer does not run.
+//!
+//! Optimizing removes information from the shader, such as the names of unused variables. This
+//! can change the generated code, for example by removing structs that are only used by unused
+//! variables.
+//!
 //! ## `types_meta: { use a::b; #[derive(Clone, Default, PartialEq ...)] impl Eq }`
 //!
 //! Extends implementations of Rust structs that represent Shader structs.
 //!
-//! By default each generated struct has a `Clone` and a `Copy` implementations
-//! only. If the struct has unsized members none of derives or impls applied on
-//! this struct.
+//! By default each generated struct has `Clone`, `Copy`, `Zeroable` and `Pod`
+//! implementations only. If the struct has unsized members none of derives or
+//! impls applied on this struct.
+//!
+//! The `Zeroable` and `Pod` implementations are still generated when `types_meta`
+//! is specified, unless they are derived in the block. `Pod` is only implemented
+//! if `Copy` is derived.
 //!
 //! The block may have as many `use`, `derive` or `impl` statements as needed
 //! and in any order.
@@ -212,6 +394,10 @@
 //! [descriptor]: https://docs.rs/vulkano/*/vulkano/descriptor/index.html
 //! [ShaderStages]: https://docs.rs/vulkano/*/vulkano/descriptor/descriptor/struct.ShaderStages.html
 //! [SpecializationConstants]: https://docs.rs/vulkano/*/vulkano/pipeline/shader/trait.SpecializationConstants.html
+//! [Zeroable]: https://docs.rs/bytemuck/*/bytemuck/trait.Zeroable.html
+//! [Pod]: https://docs.rs/bytemuck/*/bytemuck/trait.Pod.html
+//! [EntryPoint]: https://docs.rs/vulkano/*/vulkano/shader/struct.EntryPoint.html
+//! [rust-gpu]: https://github.com/EmbarkStudios/rust-gpu
 //! [pipeline]: https://docs.rs/vulkano/*/vulkano/pipeline/index.html
 //! [descriptor_sets]: https://github.com/vulkano-rs/vulkano/blob/master/vulkano-shaders/src/descriptor_sets.rs#L142
 
@@ -224,21 +410,22 @@ extern crate quote;
 #[macro_use]
 extern crate syn;
 
-use crate::codegen::ShaderKind;
+use crate::codegen::{ShaderKind, SourceLanguage};
 use ahash::HashMap;
-use shaderc::{EnvVersion, SpirvVersion};
+use heck::ToSnakeCase;
+use shaderc::{EnvVersion, OptimizationLevel, SpirvVersion};
 use std::{
     borrow::Cow,
     env, fs,
     fs::File,
     io::{Read, Result as IoResult},
-    iter::empty,
     path::Path,
     slice::from_raw_parts,
 };
 use syn::{
     parse::{Parse, ParseStream, Result},
-    Ident, ItemUse, LitBool, LitStr, Meta, MetaList, NestedMeta, Path as SynPath, TypeImplTrait,
+    Ident, ItemUse, LitBool, LitStr, Macro, Meta, MetaList, NestedMeta, Path as SynPath,
+    TypeImplTrait,
 };
 
 mod codegen;
@@ -259,6 +446,8 @@ struct TypesMeta {
     debug: bool,
     default: bool,
     partial_eq: bool,
+    pod: bool,
+    zeroable: bool,
     uses: Vec<ItemUse>,
     impls: Vec<TypeImplTrait>,
 }
@@ -274,6 +463,8 @@ impl Default for TypesMeta {
             debug: false,
             display: false,
             default: false,
+            pod: true,
+            zeroable: true,
             uses: Vec::new(),
             impls: Vec::new(),
         }
@@ -291,6 +482,8 @@ impl TypesMeta {
             debug: false,
             display: false,
             default: false,
+            pod: false,
+            zeroable: false,
             uses: Vec::new(),
             impls: Vec::new(),
         }
@@ -359,10 +552,13 @@ impl RegisteredType {
 
 struct MacroInput {
     dump: bool,
+    entry_point: String,
     include_directories: Vec<String>,
     macro_defines: Vec<(String, String)>,
+    optimization_level: Option<OptimizationLevel>,
     shared_constants: bool,
     shaders: HashMap<String, (ShaderKind, SourceKind)>,
+    source_language: SourceLanguage,
     spirv_version: Option<SpirvVersion>,
     types_meta: TypesMeta,
     vulkan_version: Option<EnvVersion>,
@@ -371,11 +567,14 @@ struct MacroInput {
 impl Parse for MacroInput {
     fn parse(input: ParseStream<'_>) -> Result<Self> {
         let mut dump = None;
+        let mut entry_point = None;
         let mut exact_entrypoint_interface = None;
         let mut include_directories = Vec::new();
         let mut macro_defines = Vec::new();
+        let mut optimization_level = None;
         let mut shared_constants = None;
         let mut shaders = HashMap::default();
+        let mut source_language = None;
         let mut spirv_version = None;
         let mut types_meta = None;
         let mut vulkan_version = None;
@@ -419,8 +618,28 @@ impl Parse for MacroInput {
                         )
                     }
 
-                    let path: LitStr = input.parse()?;
-                    output.1 = Some(SourceKind::Bytes(path.value()));
+                    // `env!("NAME")` reads the path from an environment variable, for example one
+                    // that was set by a build script with `cargo:rustc-env`.
+                    let path = if input.peek(LitStr) {
+                        let path: LitStr = input.parse()?;
+                        path.value()
+                    } else {
+                        let mac: Macro = input.parse()?;
+
+                        if !mac.path.is_ident("env") {
+                            panic!("Expected a string literal or `env!(\"...\")` for `bytes`");
+                        }
+
+                        let name: LitStr = mac.parse_body()?;
+                        env::var(name.value()).unwrap_or_else(|_| {
+                            panic!(
+                                "Environment variable {:?} is not set; it must be set by a build \
+                                script with `cargo:rustc-env`",
+                                name.value(),
+                            )
+                        })
+                    };
+                    output.1 = Some(SourceKind::Bytes(path));
                 }
 
                 "path" => {
@@ -500,6 +719,10 @@ impl Parse for MacroInput {
                             panic!("Shader entry {:?} already defined", prefix);
                         }
 
+                        if prefix.to_snake_case() == "ty" {
+                            panic!("Shader entry {:?} conflicts with the `ty` module", prefix);
+                        }
+
                         in_braces.parse::<Token![:]>()?;
 
                         let in_shader_definition;
@@ -534,6 +757,7 @@ impl Parse for MacroInput {
                         }
 
                         match shaders.get(&prefix).unwrap() {
+                            (None, Some(SourceKind::Bytes(_))) => (),
                             (None, _) => panic!("Please specify shader's {} type e.g. `ty: \"vertex\"`", prefix),
                             (_, None) => panic!("Please specify shader's {} source e.g. `path: \"entry_point.glsl\"`", prefix),
                             _ => ()
@@ -569,6 +793,13 @@ impl Parse for MacroInput {
                     let dump_lit: LitBool = input.parse()?;
                     dump = Some(dump_lit.value);
                 }
+                "entry_point" => {
+                    if entry_point.is_some() {
+                        panic!("Only one `entry_point` can be defined")
+                    }
+                    let name: LitStr = input.parse()?;
+                    entry_point = Some(name.value());
+                }
                 "exact_entrypoint_interface" => {
                     if exact_entrypoint_interface.is_some() {
                         panic!("Only one `dump` can be defined")
@@ -590,6 +821,31 @@ impl Parse for MacroInput {
                         }
                     }
                 }
+                "lang" => {
+                    if source_language.is_some() {
+                        panic!("Only one `lang` can be defined")
+                    }
+                    let lang: LitStr = input.parse()?;
+                    source_language = Some(match lang.value().as_ref() {
+                        "glsl" => SourceLanguage::GLSL,
+                        "hlsl" => SourceLanguage::HLSL,
+                        _ => panic!("Unexpected shader language, valid values: glsl, hlsl"),
+                    });
+                }
+                "optimize" => {
+                    if optimization_level.is_some() {
+                        panic!("Only one `optimize` can be defined")
+                    }
+                    let level: LitStr = input.parse()?;
+                    optimization_level = Some(match level.value().as_ref() {
+                        "none" => OptimizationLevel::Zero,
+                        "size" => OptimizationLevel::Size,
+                        "performance" => OptimizationLevel::Performance,
+                        _ => panic!(
+                            "Unexpected optimization level, valid values: none, size, performance"
+                        ),
+                    });
+                }
                 "spirv_version" => {
                     let version: LitStr = input.parse()?;
                     spirv_version = Some(match version.value().as_ref() {
@@ -739,6 +995,19 @@ impl Parse for MacroInput {
                         return Err(in_braces.error("Type meta must by \"use a::b::c\", \"#[derive(Type1, Type2, ..)]\" or \"impl Type\""));
                     }
 
+                    // Implement the bytemuck traits, unless they are already derived.
+                    let is_derived = |name: &str| {
+                        meta.custom_derives.iter().any(|path| {
+                            path.segments
+                                .last()
+                                .map_or(false, |segment| segment.ident == name)
+                        })
+                    };
+                    let zeroable = !is_derived("Zeroable");
+                    let pod = meta.copy && !is_derived("Pod");
+                    meta.zeroable = zeroable;
+                    meta.pod = pod;
+
                     types_meta = Some(meta);
                 }
                 "vulkan_version" => {
@@ -763,6 +1032,7 @@ impl Parse for MacroInput {
         }
 
         match shaders.get("") {
+            Some((None, Some(SourceKind::Bytes(_)))) => (),
             Some((None, _)) => panic!("Please specify shader's type e.g. `ty: \"vertex\"`"),
             Some((_, None)) => {
                 panic!("Please specify shader's source e.g. `src: \"glsl source code\"`")
@@ -772,15 +1042,25 @@ impl Parse for MacroInput {
 
         Ok(Self {
             dump: dump.unwrap_or(false),
+            entry_point: entry_point.unwrap_or_else(|| "main".to_owned()),
             include_directories,
             macro_defines,
+            optimization_level,
             shared_constants: shared_constants.unwrap_or(false),
             shaders: shaders
                 .into_iter()
                 .map(|(key, (shader_kind, shader_source))| {
-                    (key, (shader_kind.unwrap(), shader_source.unwrap()))
+                    // The type is only used when compiling, so precompiled shaders don't need it.
+                    (
+                        key,
+                        (
+                            shader_kind.unwrap_or(ShaderKind::InferFromSource),
+                            shader_source.unwrap(),
+                        ),
+                    )
                 })
                 .collect(),
+            source_language: source_language.unwrap_or(SourceLanguage::GLSL),
             spirv_version,
             types_meta: types_meta.unwrap_or_default(),
             vulkan_version,
@@ -811,7 +1091,7 @@ pub fn shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
             let full_path = root_path.join(&path);
 
             let bytes = if full_path.is_file() {
-                fs::read(full_path)
+                fs::read(&full_path)
                     .unwrap_or_else(|_| panic!("Error reading source from {:?}", path))
             } else {
                 panic!(
@@ -827,7 +1107,7 @@ pub fn shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 prefix.as_str(),
                 unsafe { from_raw_parts(bytes.as_slice().as_ptr() as *const u32, bytes.len() / 4) },
                 &input.types_meta,
-                empty(),
+                [codegen::path_to_str(&full_path)],
                 input.shared_constants,
                 &mut types_registry,
             )
@@ -865,10 +1145,13 @@ pub fn shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
                 &root_path,
                 &source_code,
                 shader_kind,
+                input.source_language,
+                &input.entry_point,
                 &include_paths,
                 &input.macro_defines,
                 input.vulkan_version,
                 input.spirv_version,
+                input.optimization_level,
             ) {
                 Ok(ok) => ok,
                 Err(e) => {
@@ -13,7 +13,7 @@ use heck::ToUpperCamelCase;
 use proc_macro2::{Span, TokenStream};
 use std::{borrow::Cow, mem};
 use syn::{Ident, LitStr};
-use vulkano::shader::spirv::{Decoration, Id, Instruction, Spirv};
+use vulkano::shader::spirv::{Decoration, Id, Instruction, Spirv, StorageClass};
 
 /// Translates all the structs that are contained in the SPIR-V document as Rust structs.
 pub(super) fn write_structs<'a>(
@@ -33,16 +33,17 @@ pub(super) fn write_structs<'a>(
         })
         .filter(|&(struct_id, _member_types)| has_defined_layout(spirv, struct_id))
         .filter_map(|(struct_id, member_types)| {
-            let (rust_members, is_sized) = write_struct_members(spirv, struct_id, member_types);
+            let (rust_members, rust_size) = write_struct_members(spirv, struct_id, member_types);
 
             let struct_name = spirv
                 .id(struct_id)
                 .iter_name()
                 .find_map(|instruction| match instruction {
-                    Instruction::Name { name, .. } => Some(name.as_str()),
+                    Instruction::Name { name, .. } => Some(to_rust_ident(name)),
                     _ => None,
                 })
-                .unwrap_or("__unnamed");
+                .unwrap_or_else(|| "__unnamed".to_owned());
+            let struct_name = struct_name.as_str();
 
             // Register the type if needed
             if !register_struct(types_registry, shader, &rust_members, struct_name) {
@@ -62,13 +63,15 @@ pub(super) fn write_structs<'a>(
                 }
             };
 
-            Some(if is_sized {
+            Some(if let Some(rust_size) = rust_size {
                 let derives = write_derives(types_meta);
                 let impls = write_impls(types_meta, struct_name, &rust_members);
+                let layout_assertion = write_layout_assertion(struct_name, rust_size);
                 quote! {
                     #derives
                     #struct_body
                     #(#impls)*
+                    #layout_assertion
                 }
             } else {
                 struct_body
@@ -85,7 +88,15 @@ struct Member {
     signature: Cow<'static, str>,
 }
 
-fn write_struct_members(spirv: &Spirv, struct_id: Id, members: &[Id]) -> (Vec<Member>, bool) {
+/// Returns the members of the Rust struct, and the size of the struct if it is sized.
+///
+/// All padding is added as explicit dummy members, so that the Rust struct has no implicit
+/// padding. The offset of each member is then the sum of the sizes of the members before it.
+fn write_struct_members(
+    spirv: &Spirv,
+    struct_id: Id,
+    members: &[Id],
+) -> (Vec<Member>, Option<usize>) {
     let mut rust_members = Vec::with_capacity(members.len());
 
     // Dummy members will be named `_dummyN` where `N` is determined by this variable.
@@ -95,6 +106,9 @@ fn write_struct_members(spirv: &Spirv, struct_id: Id, members: &[Id]) -> (Vec<Me
     // Equals to `None` if there's a runtime-sized field in there.
     let mut current_rust_offset = Some(0);
 
+    // The alignment of the Rust struct.
+    let mut struct_align = 1;
+
     for (member_index, (&member, member_info)) in members
         .iter()
         .zip(spirv.id(struct_id).iter_members())
@@ -102,10 +116,11 @@ fn write_struct_members(spirv: &Spirv, struct_id: Id, members: &[Id]) -> (Vec<Me
     {
         // Compute infos about the member.
         let (ty, signature, rust_size, rust_align) = type_from_id(spirv, member);
+        struct_align = struct_align.max(rust_align);
         let member_name = member_info
             .iter_name()
             .find_map(|instruction| match instruction {
-                Instruction::MemberName { name, .. } => Some(Cow::from(name.as_str())),
+                Instruction::MemberName { name, .. } => Some(Cow::from(to_rust_ident(name))),
                 _ => None,
             })
             .unwrap_or_else(|| Cow::from(format!("__unnamed{}", member_index)));
@@ -128,15 +143,26 @@ fn write_struct_members(spirv: &Spirv, struct_id: Id, members: &[Id]) -> (Vec<Me
                 .as_mut()
                 .expect("Found runtime-sized member in non-final position");
 
-            // Updating current_rust_offset to take the alignment of the next field into account
-            *current_rust_offset = if *current_rust_offset == 0 {
-                0
-            } else {
-                (1 + (*current_rust_offset - 1) / rust_align) * rust_align
-            };
+            if spirv_offset % rust_align != 0 {
+                panic!(
+                    "Not possible to generate a Rust struct with the correct layout, because the \
+                    member `{}` has an offset of {}, which is not a multiple of the alignment of \
+                    its Rust type, {}",
+                    member_name, spirv_offset, rust_align,
+                );
+            }
 
             if spirv_offset != *current_rust_offset {
-                let diff = spirv_offset.checked_sub(*current_rust_offset).unwrap();
+                let diff = spirv_offset
+                    .checked_sub(*current_rust_offset)
+                    .unwrap_or_else(|| {
+                        panic!(
+                        "Not possible to generate a Rust struct with the correct layout, because \
+                        the member `{}` has an offset of {}, which overlaps with the previous \
+                        member",
+                        member_name, spirv_offset,
+                    )
+                    });
                 rust_members.push(Member {
                     name: format_ident!("_dummy{}", next_dummy_num.to_string()),
                     is_dummy: true,
@@ -164,23 +190,57 @@ fn write_struct_members(spirv: &Spirv, struct_id: Id, members: &[Id]) -> (Vec<Me
     }
 
     // Adding the final padding members, if the struct is sized.
-    if let Some(cur_size) = current_rust_offset {
-        // Try to determine the total size of the struct.
-        if let Some(req_size) = struct_size_from_array_stride(spirv, struct_id) {
-            let diff = req_size.checked_sub(cur_size as u32).unwrap();
-
-            if diff >= 1 {
-                rust_members.push(Member {
-                    name: Ident::new(&format!("_dummy{}", next_dummy_num), Span::call_site()),
-                    is_dummy: true,
-                    ty: quote! { [u8; #diff as usize] },
-                    signature: Cow::from(format!("[u8; {}]", diff)),
-                });
-            }
+    let rust_size = current_rust_offset.map(|cur_size| {
+        // Try to determine the total size of the struct. Rust rounds the size up to the
+        // alignment of the struct, so this is padded explicitly as well.
+        let req_size = struct_size_from_array_stride(spirv, struct_id)
+            .map_or(cur_size, |req_size| req_size as usize);
+        let req_size = round_up(req_size, struct_align);
+        let diff = req_size.checked_sub(cur_size).unwrap();
+
+        if diff >= 1 {
+            rust_members.push(Member {
+                name: Ident::new(&format!("_dummy{}", next_dummy_num), Span::call_site()),
+                is_dummy: true,
+                ty: quote! { [u8; #diff] },
+                signature: Cow::from(format!("[u8; {}]", diff)),
+            });
         }
+
+        req_size
+    });
+
+    (rust_members, rust_size)
+}
+
+fn round_up(size: usize, align: usize) -> usize {
+    (size + align - 1) / align * align
+}
+
+/// Turns a name from the SPIR-V code into a valid Rust identifier.
+///
+/// Compilers other than glslang, such as rust-gpu, name types after their path in the source
+/// language, for example `my_shader::Uniforms` or `Light<f32>`, and tuple struct members are
+/// named `0`, `1`, and so on. Only the last path segment is kept, generic arguments are dropped,
+/// and any other character that is not allowed in an identifier is replaced with `_`.
+pub(super) fn to_rust_ident(name: &str) -> String {
+    let name = name.split('<').next().unwrap();
+    let name = name.rsplit("::").next().unwrap();
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
     }
 
-    (rust_members, current_rust_offset.is_some())
+    // Keywords can't be used as identifiers.
+    if syn::parse_str::<Ident>(&ident).is_err() {
+        ident.push('_');
+    }
+
+    ident
 }
 
 fn register_struct(
@@ -239,6 +299,23 @@ fn write_derives(types_meta: &TypesMeta) -> TokenStream {
     }
 }
 
+/// Writes an assertion that fails to compile if the size of the Rust struct does not match the
+/// size that was calculated from the shader.
+///
+/// Because the struct has no implicit padding, this also guarantees that the offsets of all members
+/// match the offsets in the shader.
+fn write_layout_assertion(struct_name: &str, rust_size: usize) -> TokenStream {
+    let struct_ident = format_ident!("{}", struct_name);
+    let message = format!(
+        "the layout of the generated struct `{}` does not match the layout in the shader",
+        struct_name,
+    );
+
+    quote! {
+        const _: () = assert!(::std::mem::size_of::<#struct_ident>() == #rust_size, #message);
+    }
+}
+
 fn write_impls<'a>(
     types_meta: &'a TypesMeta,
     struct_name: &'a str,
@@ -321,6 +398,16 @@ fn write_impls<'a>(
             }
         }
     }))
+    .chain(types_meta.zeroable.then(|| {
+        quote! {
+            unsafe impl ::vulkano::bytemuck::Zeroable for #struct_ident {}
+        }
+    }))
+    .chain(types_meta.pod.then(|| {
+        quote! {
+            unsafe impl ::vulkano::bytemuck::Pod for #struct_ident {}
+        }
+    }))
     .chain(
         types_meta
             .impls
@@ -517,6 +604,20 @@ pub(super) fn type_from_id(
             _ => panic!("No Rust equivalent for an integer of width {}", width),
         },
         Instruction::TypeFloat { width, .. } => match width {
+            16 => {
+                // `half::f16` is a transparent wrapper around `u16`.
+                #[repr(C)]
+                struct Foo {
+                    data: u16,
+                    after: u8,
+                }
+                (
+                    quote! {::vulkano::half::f16},
+                    Cow::from("f16"),
+                    Some(std::mem::size_of::<u16>()),
+                    mem::align_of::<Foo>(),
+                )
+            }
             32 => {
                 #[repr(C)]
                 struct Foo {
@@ -613,6 +714,12 @@ pub(super) fn type_from_id(
                             (e.g. increase a vec3 to a vec4)")
             }
 
+            if (stride as usize) < element_size {
+                panic!("Not possible to generate a rust array with the correct alignment since the SPIR-V \
+                            ArrayStride is smaller than the size of the array element in rust, which is \
+                            padded to a multiple of its alignment")
+            }
+
             (
                 quote! { [#element_type; #array_length] },
                 Cow::from(format!("[{}; {}]", element_type_string, array_length)),
@@ -672,10 +779,13 @@ pub(super) fn type_from_id(
                 .max()
                 .unwrap_or(1);
 
+            // The generated struct is padded to a multiple of its alignment.
+            let size = size.map(|size| round_up(size, align));
+
             let name_string = id_info
                 .iter_name()
                 .find_map(|instruction| match instruction {
-                    Instruction::Name { name, .. } => Some(Cow::from(name.clone())),
+                    Instruction::Name { name, .. } => Some(Cow::from(to_rust_ident(name))),
                     _ => None,
                 })
                 .unwrap_or_else(|| Cow::from("__unnamed"));
@@ -686,6 +796,23 @@ pub(super) fn type_from_id(
 
             (name, name_string, size, align)
         }
+        Instruction::TypePointer {
+            storage_class: StorageClass::PhysicalStorageBuffer,
+            ..
+        } => {
+            // A pointer into a buffer, which is represented by the buffer's device address.
+            #[repr(C)]
+            struct Foo {
+                data: u64,
+                after: u8,
+            }
+            (
+                quote! {u64},
+                Cow::from("u64"),
+                Some(std::mem::size_of::<u64>()),
+                mem::align_of::<Foo>(),
+            )
+        }
         _ => panic!("Type #{} not found", type_id),
     }
 }
@@ -783,7 +910,7 @@ pub(super) fn write_specialization_constants<'a>(
                     Instruction::Name { name, .. } => Some(name.as_str()),
                     _ => None,
                 }) {
-                Some(name) => name.to_owned(),
+                Some(name) => to_rust_ident(name),
                 None => format!("constant_{}", constant_id),
             };
 
@@ -253,6 +253,38 @@ impl VulkanoContext {
         (device, gfx_queue, compute_queue)
     }
 
+    /// Returns whether the device has been lost.
+    ///
+    /// When this happens, the device and everything created from it can no longer be used for
+    /// any work, and must be recreated with [`recreate_device`](Self::recreate_device).
+    #[inline]
+    pub fn is_device_lost(&self) -> bool {
+        self.device.is_lost()
+    }
+
+    /// Recreates the device, the queues and the memory allocator after the device has been lost.
+    ///
+    /// The new device is created from the same physical device, with the same extensions and
+    /// features. Anything that was created from the old device, such as buffers, images and
+    /// pipelines, must be recreated from the new one. The window renderers can be recreated with
+    /// [`VulkanoWindows::recreate_device`](crate::window::VulkanoWindows::recreate_device).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the device could not be recreated.
+    pub fn recreate_device(&mut self) {
+        let (device, graphics_queue, compute_queue) = Self::create_device(
+            self.device.physical_device().clone(),
+            *self.device.enabled_extensions(),
+            *self.device.enabled_features(),
+        );
+
+        self.memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        self.device = device;
+        self.graphics_queue = graphics_queue;
+        self.compute_queue = compute_queue;
+    }
+
     /// Returns the name of the device.
     #[inline]
     pub fn device_name(&self) -> &str {
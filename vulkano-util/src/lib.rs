@@ -11,5 +11,6 @@
 #![warn(rust_2018_idioms, rust_2021_compatibility)]
 
 pub mod context;
+pub mod profiler;
 pub mod renderer;
 pub mod window;
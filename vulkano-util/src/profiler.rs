@@ -0,0 +1,450 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Measuring how long parts of a frame take on the GPU.
+//!
+//! A [`GpuProfiler`] records timestamp queries around named scopes in command buffers, and
+//! collects them into a [`ProfilerReport`] once the GPU has finished executing them:
+//!
+//! ```ignore
+//! let mut profiler = GpuProfiler::new(device.clone(), GpuProfilerConfig::default());
+//!
+//! loop {
+//!     let mut builder = AutoCommandBufferBuilder::primary(/* ... */).unwrap();
+//!
+//!     // Safety: the fence of the frame that last used this slot has been waited on.
+//!     if let Some(report) = unsafe { profiler.begin_frame(&mut builder) } {
+//!         println!("{:#?}", report);
+//!     }
+//!
+//!     {
+//!         let mut frame = profiler.scope(&mut builder, "frame");
+//!
+//!         {
+//!             let mut shadows = frame.scope("shadows");
+//!             shadows.draw(/* ... */).unwrap();
+//!         }
+//!
+//!         frame.dispatch(/* ... */).unwrap();
+//!     }
+//!
+//!     // Build, submit, present...
+//! }
+//! ```
+
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+    time::Duration,
+};
+use vulkano::{
+    command_buffer::{allocator::CommandBufferAllocator, AutoCommandBufferBuilder},
+    device::Device,
+    instance::debug::DebugUtilsLabel,
+    query::{QueryPool, QueryPoolCreateInfo, QueryResultFlags, QueryType},
+    sync::PipelineStage,
+};
+
+/// A configuration struct to pass various creation options to create [`GpuProfiler`].
+pub struct GpuProfilerConfig {
+    /// The number of frames that can be executing on the GPU at the same time. A separate query
+    /// pool is kept for each of them, so that the results of a frame can be read while the
+    /// next ones are being recorded.
+    pub frames_in_flight: usize,
+
+    /// The maximum number of scopes that can be timed in a single frame. Scopes beyond this are
+    /// still labeled, but are left out of the report.
+    pub max_scopes_per_frame: u32,
+
+    /// Whether to also open a debug label region for each scope, so that the scopes show up in
+    /// graphics debuggers. This has no effect if the
+    /// [`ext_debug_utils`](vulkano::instance::InstanceExtensions::ext_debug_utils) extension is
+    /// not enabled on the instance.
+    pub debug_labels: bool,
+}
+
+impl Default for GpuProfilerConfig {
+    #[inline]
+    fn default() -> Self {
+        GpuProfilerConfig {
+            frames_in_flight: 2,
+            max_scopes_per_frame: 256,
+            debug_labels: true,
+        }
+    }
+}
+
+/// Times named scopes of command buffers on the GPU, using timestamp queries.
+///
+/// Call [`begin_frame`](Self::begin_frame) once per frame, then open scopes with
+/// [`scope`](Self::scope). The scopes of a frame are reported by the `begin_frame` call that
+/// reuses its query pool, `frames_in_flight` frames later.
+pub struct GpuProfiler {
+    frames: Vec<ProfilerFrame>,
+    current_frame: Option<usize>,
+    // The number of queries between the start and end timestamps of a scope. With multiview,
+    // writing a timestamp inside a render pass writes one query for each view.
+    view_stride: u32,
+    timestamp_period: f32,
+    debug_labels: bool,
+}
+
+struct ProfilerFrame {
+    query_pool: Arc<QueryPool>,
+    scopes: Vec<ScopeRecord>,
+    next_query: u32,
+}
+
+struct ScopeRecord {
+    name: String,
+    parent: Option<usize>,
+    start_query: Option<u32>,
+    ended: bool,
+}
+
+impl GpuProfiler {
+    /// Creates a new [`GpuProfiler`] and its query pools.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `config.frames_in_flight` or `config.max_scopes_per_frame` is `0`.
+    pub fn new(device: Arc<Device>, config: GpuProfilerConfig) -> GpuProfiler {
+        assert!(config.frames_in_flight != 0);
+        assert!(config.max_scopes_per_frame != 0);
+
+        let properties = device.physical_device().properties();
+        let view_stride = if device.enabled_features().multiview {
+            properties.max_multiview_view_count.unwrap_or(1)
+        } else {
+            1
+        };
+
+        let frames = (0..config.frames_in_flight)
+            .map(|_| ProfilerFrame {
+                query_pool: QueryPool::new(
+                    device.clone(),
+                    QueryPoolCreateInfo {
+                        query_count: config.max_scopes_per_frame * 2 * view_stride,
+                        ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+                    },
+                )
+                .expect("Failed to create query pool"),
+                scopes: Vec::new(),
+                next_query: 0,
+            })
+            .collect();
+
+        GpuProfiler {
+            frames,
+            current_frame: None,
+            view_stride,
+            timestamp_period: properties.timestamp_period,
+            debug_labels: config.debug_labels
+                && device.instance().enabled_extensions().ext_debug_utils,
+        }
+    }
+
+    /// Starts a new frame, and returns the report of the frame that previously used the same
+    /// query pool, if there was one and its results are available.
+    ///
+    /// This records a reset of the query pool of the new frame into `builder`, which must be
+    /// outside a render pass.
+    ///
+    /// # Safety
+    ///
+    /// - The command buffers containing scopes of the frame that was begun `frames_in_flight`
+    ///   calls ago must have completed execution.
+    /// - The command buffer recorded with `builder` must be executed before any other command
+    ///   buffer containing scopes of the new frame.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `builder` is inside a render pass, or its queue family doesn't support
+    ///   resetting queries.
+    pub unsafe fn begin_frame<L, A>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+    ) -> Option<ProfilerReport>
+    where
+        A: CommandBufferAllocator,
+    {
+        let index = self
+            .current_frame
+            .map_or(0, |index| (index + 1) % self.frames.len());
+        self.current_frame = Some(index);
+
+        let frame = &mut self.frames[index];
+        let report = frame.report(self.view_stride, self.timestamp_period);
+
+        frame.scopes.clear();
+        frame.next_query = 0;
+        builder
+            .reset_query_pool(frame.query_pool.clone(), 0..frame.query_pool.query_count())
+            .expect("Failed to reset query pool");
+
+        report
+    }
+
+    /// Opens a scope with the given name, which is timed until the returned guard is dropped.
+    ///
+    /// Commands that are recorded through the guard are part of the scope, and nested scopes
+    /// can be opened with [`ProfilerScope::scope`].
+    ///
+    /// The scope is not timed if no frame has been begun yet, if the frame already has
+    /// `max_scopes_per_frame` scopes, or if the queue family of `builder` doesn't support
+    /// timestamps.
+    pub fn scope<'a, L, A>(
+        &'a mut self,
+        builder: &'a mut AutoCommandBufferBuilder<L, A>,
+        name: impl Into<String>,
+    ) -> ProfilerScope<'a, L, A>
+    where
+        A: CommandBufferAllocator,
+    {
+        ProfilerScope::new(self, builder, name.into(), None)
+    }
+}
+
+impl ProfilerFrame {
+    fn report(&self, view_stride: u32, timestamp_period: f32) -> Option<ProfilerReport> {
+        if self.scopes.is_empty() {
+            return None;
+        }
+
+        // The start and end timestamps of each timed scope, in ticks.
+        let mut timestamps = Vec::with_capacity(self.scopes.len());
+
+        for scope in &self.scopes {
+            let start_query = match scope.start_query {
+                Some(start_query) if scope.ended => start_query,
+                _ => {
+                    timestamps.push(None);
+                    continue;
+                }
+            };
+
+            let end_query = start_query + view_stride;
+            let mut start = [0u64];
+            let mut end = [0u64];
+
+            let available = self
+                .query_pool
+                .queries_range(start_query..start_query + 1)
+                .unwrap()
+                .get_results(&mut start, QueryResultFlags::empty())
+                .expect("Failed to get query results")
+                && self
+                    .query_pool
+                    .queries_range(end_query..end_query + 1)
+                    .unwrap()
+                    .get_results(&mut end, QueryResultFlags::empty())
+                    .expect("Failed to get query results");
+
+            if !available {
+                return None;
+            }
+
+            timestamps.push(Some((start[0], end[0])));
+        }
+
+        let frame_start = timestamps.iter().flatten().map(|&(start, _)| start).min()?;
+        let to_duration =
+            |ticks: u64| Duration::from_nanos((ticks as f64 * timestamp_period as f64) as u64);
+
+        let mut reports: Vec<_> = (self.scopes.iter())
+            .zip(timestamps)
+            .map(|(scope, timestamps)| {
+                timestamps.map(|(start, end)| ProfilerScopeReport {
+                    name: scope.name.clone(),
+                    start: to_duration(start.saturating_sub(frame_start)),
+                    duration: to_duration(end.saturating_sub(start)),
+                    children: Vec::new(),
+                })
+            })
+            .collect();
+
+        // Children are always recorded after their parent, so going backwards moves every
+        // subtree into its parent before the parent itself is moved.
+        let mut scopes = Vec::new();
+
+        for index in (0..reports.len()).rev() {
+            let report = match reports[index].take() {
+                Some(report) => report,
+                None => continue,
+            };
+
+            match self.scopes[index].parent {
+                Some(parent) => {
+                    // If the parent was not timed, the subtree is left out.
+                    if let Some(parent) = &mut reports[parent] {
+                        parent.children.insert(0, report);
+                    }
+                }
+                None => scopes.insert(0, report),
+            }
+        }
+
+        Some(ProfilerReport { scopes })
+    }
+}
+
+/// A scope that is being timed by a [`GpuProfiler`]. The scope ends when this is dropped.
+///
+/// This dereferences to the command buffer builder, so that commands can be recorded in the
+/// scope.
+pub struct ProfilerScope<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    profiler: &'a mut GpuProfiler,
+    builder: &'a mut AutoCommandBufferBuilder<L, A>,
+    index: Option<usize>,
+    labeled: bool,
+}
+
+impl<'a, L, A> ProfilerScope<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    fn new(
+        profiler: &'a mut GpuProfiler,
+        builder: &'a mut AutoCommandBufferBuilder<L, A>,
+        name: String,
+        parent: Option<usize>,
+    ) -> Self {
+        let labeled = profiler.debug_labels
+            && builder
+                .begin_debug_utils_label(DebugUtilsLabel {
+                    label_name: name.clone(),
+                    ..Default::default()
+                })
+                .is_ok();
+
+        let view_stride = profiler.view_stride;
+        let index = profiler.current_frame.map(|current_frame| {
+            let frame = &mut profiler.frames[current_frame];
+            let query = frame.next_query;
+
+            // Safety: the queries of the frame were reset by `begin_frame`, and each query is
+            // only written once per frame.
+            let start_query = (query + 2 * view_stride <= frame.query_pool.query_count()
+                && unsafe {
+                    builder
+                        .write_timestamp(frame.query_pool.clone(), query, PipelineStage::TopOfPipe)
+                        .is_ok()
+                })
+            .then(|| {
+                frame.next_query += 2 * view_stride;
+                query
+            });
+
+            frame.scopes.push(ScopeRecord {
+                name,
+                parent,
+                start_query,
+                ended: false,
+            });
+
+            frame.scopes.len() - 1
+        });
+
+        ProfilerScope {
+            profiler,
+            builder,
+            index,
+            labeled,
+        }
+    }
+
+    /// Opens a scope nested in this one, which is timed until the returned guard is dropped.
+    pub fn scope(&mut self, name: impl Into<String>) -> ProfilerScope<'_, L, A> {
+        ProfilerScope::new(self.profiler, self.builder, name.into(), self.index)
+    }
+}
+
+impl<'a, L, A> Deref for ProfilerScope<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    type Target = AutoCommandBufferBuilder<L, A>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.builder
+    }
+}
+
+impl<'a, L, A> DerefMut for ProfilerScope<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.builder
+    }
+}
+
+impl<'a, L, A> Drop for ProfilerScope<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    fn drop(&mut self) {
+        if let (Some(index), Some(current_frame)) = (self.index, self.profiler.current_frame) {
+            let view_stride = self.profiler.view_stride;
+            let frame = &mut self.profiler.frames[current_frame];
+            let scope = &mut frame.scopes[index];
+
+            if let Some(start_query) = scope.start_query {
+                // Safety: the end query of the scope was reset by `begin_frame`, and is only
+                // written here.
+                scope.ended = unsafe {
+                    self.builder
+                        .write_timestamp(
+                            frame.query_pool.clone(),
+                            start_query + view_stride,
+                            PipelineStage::BottomOfPipe,
+                        )
+                        .is_ok()
+                };
+            }
+        }
+
+        if self.labeled {
+            // Safety: the label region was opened when the scope was created, and all regions
+            // opened since then have been closed by the nested scopes.
+            unsafe {
+                self.builder.end_debug_utils_label().unwrap();
+            }
+        }
+    }
+}
+
+/// The timings of the scopes of a frame, as returned by [`GpuProfiler::begin_frame`].
+#[derive(Clone, Debug)]
+pub struct ProfilerReport {
+    /// The top-level scopes of the frame, in the order they were opened.
+    pub scopes: Vec<ProfilerScopeReport>,
+}
+
+/// The timing of a single scope in a [`ProfilerReport`].
+#[derive(Clone, Debug)]
+pub struct ProfilerScopeReport {
+    /// The name of the scope.
+    pub name: String,
+
+    /// The time at which the scope started, relative to the start of the earliest scope of the
+    /// frame.
+    pub start: Duration,
+
+    /// The time that the GPU spent executing the scope.
+    pub duration: Duration,
+
+    /// The scopes that were nested in this one, in the order they were opened.
+    pub children: Vec<ProfilerScopeReport>,
+}
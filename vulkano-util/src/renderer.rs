@@ -11,15 +11,15 @@ use crate::{context::VulkanoContext, window::WindowDescriptor};
 use ahash::HashMap;
 use std::sync::Arc;
 use vulkano::{
-    device::{Device, Queue},
+    device::{physical::PhysicalDeviceError, Device, Queue},
     format::Format,
     image::{
         view::ImageView, ImageAccess, ImageUsage, ImageViewAbstract, StorageImage, SwapchainImage,
     },
     memory::allocator::StandardMemoryAllocator,
     swapchain::{
-        self, AcquireError, Surface, Swapchain, SwapchainCreateInfo, SwapchainCreationError,
-        SwapchainPresentInfo,
+        self, AcquireError, RectangleLayer, Surface, SurfaceTransform, Swapchain,
+        SwapchainCreateInfo, SwapchainCreationError, SwapchainPresentInfo,
     },
     sync::{self, FlushError, GpuFuture},
 };
@@ -55,6 +55,7 @@ pub struct VulkanoWindowRenderer {
     previous_frame_end: Option<Box<dyn GpuFuture>>,
     image_index: u32,
     present_mode: vulkano::swapchain::PresentMode,
+    pre_rotate: bool,
 }
 
 impl VulkanoWindowRenderer {
@@ -94,6 +95,7 @@ impl VulkanoWindowRenderer {
             previous_frame_end,
             image_index: 0,
             present_mode: descriptor.present_mode,
+            pre_rotate: descriptor.pre_rotate,
         }
     }
 
@@ -117,13 +119,19 @@ impl VulkanoWindowRenderer {
                 .0,
         );
         let window = surface.object().unwrap().downcast_ref::<Window>().unwrap();
-        let image_extent = window.inner_size().into();
+        let (pre_transform, image_extent) = Self::pre_transform_and_extent(
+            &device,
+            &surface,
+            window_descriptor.pre_rotate,
+            window.inner_size().into(),
+        );
         let (swapchain, images) = Swapchain::new(device, surface, {
             let mut create_info = SwapchainCreateInfo {
                 min_image_count: surface_capabilities.min_image_count,
                 image_format,
                 image_extent,
                 image_usage: ImageUsage::COLOR_ATTACHMENT,
+                pre_transform,
                 composite_alpha: surface_capabilities
                     .supported_composite_alpha
                     .into_iter()
@@ -144,13 +152,153 @@ impl VulkanoWindowRenderer {
         (swapchain, images)
     }
 
+    /// Returns the pre-transform and the image extent to use for the swapchain of a window of
+    /// the given size. If `pre_rotate` is true, the current transform of the surface is used, and
+    /// the width and height are swapped if it rotates the images.
+    fn pre_transform_and_extent(
+        device: &Device,
+        surface: &Surface,
+        pre_rotate: bool,
+        window_size: [u32; 2],
+    ) -> (SurfaceTransform, [u32; 2]) {
+        if !pre_rotate {
+            return (SurfaceTransform::Identity, window_size);
+        }
+
+        let pre_transform = device
+            .physical_device()
+            .surface_capabilities(surface, Default::default())
+            .unwrap()
+            .current_transform;
+
+        if pre_transform.swaps_extent() {
+            (pre_transform, [window_size[1], window_size[0]])
+        } else {
+            (pre_transform, window_size)
+        }
+    }
+
+    /// Recreates the swapchain and the image views of this window for the new device of
+    /// `vulkano_context`, after the device has been lost and
+    /// [`VulkanoContext::recreate_device`] has been called. The window and its surface are kept.
+    ///
+    /// The additional image views are recreated with the same keys, formats and usages, but their
+    /// contents are lost.
+    pub fn recreate_device(self, vulkano_context: &VulkanoContext) -> VulkanoWindowRenderer {
+        let (pre_transform, image_extent) = Self::pre_transform_and_extent(
+            vulkano_context.device(),
+            &self.surface,
+            self.pre_rotate,
+            self.window().inner_size().into(),
+        );
+        let swapchain_create_info = SwapchainCreateInfo {
+            image_extent,
+            present_mode: self.present_mode,
+            pre_transform,
+            ..self.swapchain.create_info()
+        };
+        let additional_image_views = self
+            .additional_image_views
+            .iter()
+            .map(|(&key, view)| (key, view.format().unwrap(), view.usage()))
+            .collect::<Vec<_>>();
+        let surface = self.surface.clone();
+        let present_mode = self.present_mode;
+        let pre_rotate = self.pre_rotate;
+
+        // The surface can only be used for a new swapchain once the old one is destroyed.
+        drop(self);
+
+        let (swapchain, images) = Swapchain::new(
+            vulkano_context.device().clone(),
+            surface.clone(),
+            swapchain_create_info,
+        )
+        .unwrap();
+        let final_views = images
+            .into_iter()
+            .map(|image| ImageView::new_default(image).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut renderer = VulkanoWindowRenderer {
+            surface,
+            graphics_queue: vulkano_context.graphics_queue().clone(),
+            compute_queue: vulkano_context.compute_queue().clone(),
+            swapchain,
+            final_views,
+            memory_allocator: vulkano_context.memory_allocator().clone(),
+            additional_image_views: HashMap::default(),
+            recreate_swapchain: false,
+            previous_frame_end: Some(sync::now(vulkano_context.device().clone()).boxed()),
+            image_index: 0,
+            present_mode,
+            pre_rotate,
+        };
+
+        for (key, format, usage) in additional_image_views {
+            renderer.add_additional_image_view(key, format, usage);
+        }
+
+        renderer
+    }
+
     /// Set window renderer present mode. This triggers a swapchain recreation.
+    ///
+    /// Returns `false` and keeps the current present mode if `present_mode` is not supported by
+    /// the surface. [`PresentMode::Fifo`](vulkano::swapchain::PresentMode::Fifo) is always
+    /// supported. Returns an error if the supported present modes could not be queried.
     #[inline]
-    pub fn set_present_mode(&mut self, present_mode: vulkano::swapchain::PresentMode) {
+    pub fn set_present_mode(
+        &mut self,
+        present_mode: vulkano::swapchain::PresentMode,
+    ) -> Result<bool, PhysicalDeviceError> {
         if self.present_mode != present_mode {
+            if !self.supported_present_modes()?.contains(&present_mode) {
+                return Ok(false);
+            }
+
             self.present_mode = present_mode;
             self.recreate_swapchain = true;
         }
+
+        Ok(true)
+    }
+
+    /// Return the present modes that the surface supports, for example to offer a VSync toggle.
+    #[inline]
+    pub fn supported_present_modes(
+        &self,
+    ) -> Result<Vec<vulkano::swapchain::PresentMode>, PhysicalDeviceError> {
+        self.graphics_queue
+            .device()
+            .physical_device()
+            .surface_present_modes(&self.surface)
+            .map(Iterator::collect)
+    }
+
+    /// Return the present mode of the swapchain.
+    #[inline]
+    pub fn present_mode(&self) -> vulkano::swapchain::PresentMode {
+        self.present_mode
+    }
+
+    /// Return the pre-transform of the swapchain. This is always
+    /// [`SurfaceTransform::Identity`] unless [`WindowDescriptor::pre_rotate`] is set.
+    #[inline]
+    pub fn pre_transform(&self) -> SurfaceTransform {
+        self.swapchain.pre_transform()
+    }
+
+    /// Return the matrix to multiply with the projection matrix when rendering to the swapchain
+    /// images, so that the rendering matches the [`pre_transform`](Self::pre_transform) of the
+    /// swapchain. This is the identity matrix unless [`WindowDescriptor::pre_rotate`] is set.
+    ///
+    /// The matrix is in column-major order. Note that when the pre-transform rotates the images
+    /// by 90 or 270 degrees, the [`swapchain_image_size`](Self::swapchain_image_size) has its
+    /// width and height swapped compared to the [`window_size`](Self::window_size).
+    #[inline]
+    pub fn pre_rotation_matrix(&self) -> [[f32; 4]; 4] {
+        self.swapchain.pre_transform().pre_rotation_matrix()
     }
 
     /// Return swapchain image format.
@@ -266,8 +414,16 @@ impl VulkanoWindowRenderer {
     /// swapchain image has been acquired and previous frame ended.
     /// Execute your command buffers after calling this function and finish rendering by calling
     /// [`VulkanoWindowRenderer::present`].
+    ///
+    /// Returns [`AcquireError::DeviceLost`] if the device has been lost. In that case, recreate
+    /// the device with [`VulkanoContext::recreate_device`] and the window renderers with
+    /// [`VulkanoWindows::recreate_device`](crate::window::VulkanoWindows::recreate_device).
     #[inline]
     pub fn acquire(&mut self) -> std::result::Result<Box<dyn GpuFuture>, AcquireError> {
+        if self.graphics_queue.device().is_lost() {
+            return Err(AcquireError::DeviceLost);
+        }
+
         // Recreate swap chain if needed (when resizing of window occurs or swapchain is outdated)
         // Also resize render views if needed
         if self.recreate_swapchain {
@@ -282,6 +438,7 @@ impl VulkanoWindowRenderer {
                     self.recreate_swapchain = true;
                     return Err(AcquireError::OutOfDate);
                 }
+                Err(AcquireError::DeviceLost) => return Err(AcquireError::DeviceLost),
                 Err(e) => panic!("Failed to acquire next image: {:?}", e),
             };
         if suboptimal {
@@ -303,13 +460,31 @@ impl VulkanoWindowRenderer {
     /// on.
     #[inline]
     pub fn present(&mut self, after_future: Box<dyn GpuFuture>, wait_future: bool) {
+        self.present_with_regions(after_future, Vec::new(), wait_future)
+    }
+
+    /// Finishes rendering like [`VulkanoWindowRenderer::present`], but hints that only `regions`
+    /// of the swapchain image have changed since it was last presented.
+    ///
+    /// The hint is only used if the `khr_incremental_present` device extension is enabled. An
+    /// empty `regions` means that the whole image has changed.
+    #[inline]
+    pub fn present_with_regions(
+        &mut self,
+        after_future: Box<dyn GpuFuture>,
+        regions: Vec<RectangleLayer>,
+        wait_future: bool,
+    ) {
         let future = after_future
             .then_swapchain_present(
                 self.graphics_queue.clone(),
-                SwapchainPresentInfo::swapchain_image_index(
-                    self.swapchain.clone(),
-                    self.image_index,
-                ),
+                SwapchainPresentInfo {
+                    present_regions: regions,
+                    ..SwapchainPresentInfo::swapchain_image_index(
+                        self.swapchain.clone(),
+                        self.image_index,
+                    )
+                },
             )
             .then_signal_fence_and_flush();
         match future {
@@ -324,6 +499,10 @@ impl VulkanoWindowRenderer {
                     future.cleanup_finished();
                 }
 
+                if self.swapchain.is_suboptimal() {
+                    self.recreate_swapchain = true;
+                }
+
                 self.previous_frame_end = Some(future.boxed());
             }
             Err(FlushError::OutOfDate) => {
@@ -342,14 +521,34 @@ impl VulkanoWindowRenderer {
     /// Recreates swapchain images and image views which follow the window size.
     fn recreate_swapchain_and_views(&mut self) {
         let dimensions: [u32; 2] = self.window().inner_size().into();
-        let (new_swapchain, new_images) = match self.swapchain.recreate(SwapchainCreateInfo {
-            image_extent: dimensions,
-            // Use present mode from current state
-            present_mode: self.present_mode,
-            ..self.swapchain.create_info()
-        }) {
+        let result = if self.pre_rotate {
+            // The current transform changes when the device is rotated.
+            let (pre_transform, image_extent) = Self::pre_transform_and_extent(
+                self.graphics_queue.device(),
+                &self.surface,
+                true,
+                dimensions,
+            );
+            self.swapchain.recreate(SwapchainCreateInfo {
+                image_extent,
+                present_mode: self.present_mode,
+                pre_transform,
+                ..self.swapchain.create_info()
+            })
+        } else if self.present_mode != self.swapchain.present_mode() {
+            self.swapchain.recreate(SwapchainCreateInfo {
+                image_extent: dimensions,
+                // Use present mode from current state
+                present_mode: self.present_mode,
+                ..self.swapchain.create_info()
+            })
+        } else {
+            self.swapchain.recreate_with_extent(dimensions)
+        };
+        let (new_swapchain, new_images) = match result {
             Ok(r) => r,
-            Err(SwapchainCreationError::ImageExtentNotSupported { .. }) => return,
+            Err(SwapchainCreationError::ImageExtentNotSupported { .. })
+            | Err(SwapchainCreationError::ImageExtentZeroLengthDimensions) => return,
             Err(e) => panic!("Failed to recreate swapchain: {:?}", e),
         };
 
@@ -242,6 +242,16 @@ impl VulkanoWindows {
         }
     }
 
+    /// Recreates the window renderers for the new device of `vulkano_context`, after the device
+    /// has been lost and [`VulkanoContext::recreate_device`] has been called.
+    pub fn recreate_device(&mut self, vulkano_context: &VulkanoContext) {
+        self.windows = self
+            .windows
+            .drain()
+            .map(|(id, renderer)| (id, renderer.recreate_device(vulkano_context)))
+            .collect();
+    }
+
     /// Return iterator over window renderers.
     #[inline]
     pub fn iter(&self) -> Iter<'_, WindowId, VulkanoWindowRenderer> {
@@ -361,6 +371,15 @@ pub struct WindowDescriptor {
     pub mode: WindowMode,
     /// Sets whether the background of the window should be transparent.
     pub transparent: bool,
+    /// Sets whether the swapchain is created with the current transform of the surface as its
+    /// pre-transform, so that the presentation engine does not need to rotate the images.
+    ///
+    /// This avoids a costly composition pass on rotated Android devices, but the application must
+    /// then apply [`VulkanoWindowRenderer::pre_rotation_matrix`] when rendering to the swapchain
+    /// images.
+    ///
+    /// [`VulkanoWindowRenderer::pre_rotation_matrix`]: crate::renderer::VulkanoWindowRenderer::pre_rotation_matrix
+    pub pre_rotate: bool,
 }
 
 impl Default for WindowDescriptor {
@@ -380,6 +399,7 @@ impl Default for WindowDescriptor {
             cursor_visible: true,
             mode: WindowMode::Windowed,
             transparent: false,
+            pre_rotate: false,
         }
     }
 }
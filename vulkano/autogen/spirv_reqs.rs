@@ -222,7 +222,7 @@ fn make_enable(enable: &vk_parse::Enable) -> Option<(Enable, String)> {
         vk_parse::Enable::Version(version) => {
             let captures = VK_API_VERSION.captures(version).unwrap();
             let major = captures.get(1).unwrap().as_str();
-            let minor = captures.get(1).unwrap().as_str();
+            let minor = captures.get(2).unwrap().as_str();
 
             (
                 Enable::Core((major.parse().unwrap(), minor.parse().unwrap())),
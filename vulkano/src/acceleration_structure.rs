@@ -0,0 +1,1684 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! An opaque data structure that is used to accelerate spatial queries on geometry data.
+//!
+//! Acceleration structures contain geometry data, arranged in such a way that the device can
+//! easily search through the data and check for intersections between the geometry and rays
+//! (lines). The geometry data can consist of either triangles, or axis-aligned bounding boxes
+//! (AABBs).
+//!
+//! Acceleration structures come in two forms: top-level and bottom-level. A bottom-level
+//! acceleration structure holds the actual geometry data, while a top-level structure contains
+//! instances of (references to) one or more bottom-level structures. A top-level structure is
+//! intended to contain the whole rendered scene (or the relevant parts of it), while a
+//! bottom-level structure may contain individual objects within the scene. This two-level
+//! arrangement allows you to easily rearrange the scene, adding and removing parts of it as
+//! needed.
+//!
+//! # Building an acceleration structure
+//!
+//! An acceleration structure is stored inside a buffer, which must be created with the
+//! [`ACCELERATION_STRUCTURE_STORAGE`] usage. Creating an `AccelerationStructure` object does not
+//! yet put any data in it; it must first be built on the device. This happens in several steps:
+//!
+//! 1. Describe the geometry with an [`AccelerationStructureBuildGeometryInfo`]. For a size query,
+//!    the buffers in the description do not need to be provided yet.
+//! 2. Call [`Device::acceleration_structure_build_sizes`] to query the size that the acceleration
+//!    structure needs, and the size of the scratch buffer that is used during the build.
+//! 3. Create a buffer of the queried size, and create an `AccelerationStructure` inside it.
+//!    Also create a scratch buffer with the [`STORAGE_BUFFER`] and [`SHADER_DEVICE_ADDRESS`]
+//!    usages.
+//! 4. Fill in the geometry buffers, the destination acceleration structure and the scratch
+//!    buffer in the build info, and record a [`build_acceleration_structure`] command.
+//!
+//! Bottom-level acceleration structures must be built before a top-level acceleration structure
+//! that references them.
+//!
+//! # Updating an acceleration structure
+//!
+//! If an acceleration structure was built with the [`ALLOW_UPDATE`] flag, it can later be
+//! updated (refitted) with new geometry data, as long as the number of geometries and
+//! primitives stays the same. This is much faster than a full build, but the resulting
+//! acceleration structure may be less efficient to trace against as the geometry moves further
+//! away from its original positions.
+//!
+//! A common pattern for scenes with moving objects is to rebuild the top-level acceleration
+//! structure every frame:
+//!
+//! 1. Build it once with the [`ALLOW_UPDATE`] flag, sizing the scratch buffer for
+//!    [`build_scratch_size`] and [`update_scratch_size`], whichever is larger.
+//! 2. Each frame, write the new instance transforms into an instance buffer that is not in use
+//!    by the device, and record a build using the same build info with the new instance data,
+//!    turned into an in-place update with
+//!    [`AccelerationStructureBuildGeometryInfo::into_update`].
+//! 3. When instances are added or removed, do a full build instead.
+//!
+//! # Compacting an acceleration structure
+//!
+//! After building, an acceleration structure often uses less memory than the size returned by
+//! [`Device::acceleration_structure_build_sizes`]. If it was built with the
+//! [`ALLOW_COMPACTION`] flag, it can be copied into a smaller acceleration structure:
+//!
+//! 1. Create a query pool with the [`AccelerationStructureCompactedSize`] query type, and record
+//!    a [`write_acceleration_structures_properties`] command after the build.
+//! 2. Once the command buffer has finished executing, read the compacted size from the query
+//!    results.
+//! 3. Create a new acceleration structure of that size, and record a
+//!    [`copy_acceleration_structure`] command with [`CopyAccelerationStructureMode::Compact`].
+//!    The original acceleration structure can be dropped afterwards.
+//!
+//! [`ACCELERATION_STRUCTURE_STORAGE`]: BufferUsage::ACCELERATION_STRUCTURE_STORAGE
+//! [`STORAGE_BUFFER`]: BufferUsage::STORAGE_BUFFER
+//! [`SHADER_DEVICE_ADDRESS`]: BufferUsage::SHADER_DEVICE_ADDRESS
+//! [`build_acceleration_structure`]: crate::command_buffer::AutoCommandBufferBuilder::build_acceleration_structure
+//! [`ALLOW_UPDATE`]: BuildAccelerationStructureFlags::ALLOW_UPDATE
+//! [`build_scratch_size`]: AccelerationStructureBuildSizesInfo::build_scratch_size
+//! [`update_scratch_size`]: AccelerationStructureBuildSizesInfo::update_scratch_size
+//! [`ALLOW_COMPACTION`]: BuildAccelerationStructureFlags::ALLOW_COMPACTION
+//! [`AccelerationStructureCompactedSize`]: crate::query::QueryType::AccelerationStructureCompactedSize
+//! [`write_acceleration_structures_properties`]: crate::command_buffer::AutoCommandBufferBuilder::write_acceleration_structures_properties
+//! [`copy_acceleration_structure`]: crate::command_buffer::AutoCommandBufferBuilder::copy_acceleration_structure
+
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    device::{Device, DeviceOwned},
+    format::{Format, FormatFeatures},
+    macros::{vulkan_bitflags, vulkan_enum},
+    pipeline::graphics::input_assembly::IndexType,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
+};
+use bytemuck::{Pod, Zeroable};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::Arc,
+};
+
+/// An opaque data structure that is used to accelerate spatial queries on geometry data.
+#[derive(Debug)]
+pub struct AccelerationStructure {
+    handle: ash::vk::AccelerationStructureKHR,
+    device: Arc<Device>,
+    id: NonZeroU64,
+
+    create_flags: AccelerationStructureCreateFlags,
+    buffer: Arc<dyn BufferAccess>,
+    ty: AccelerationStructureType,
+}
+
+impl AccelerationStructure {
+    /// Creates a new `AccelerationStructure`.
+    ///
+    /// The [`acceleration_structure`] feature must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - `create_info.buffer` (and any subbuffer it overlaps with) must not be accessed
+    ///   while it is bound to the acceleration structure.
+    ///
+    /// [`acceleration_structure`]: crate::device::Features::acceleration_structure
+    #[inline]
+    pub unsafe fn new(
+        device: Arc<Device>,
+        create_info: AccelerationStructureCreateInfo,
+    ) -> Result<Arc<Self>, AccelerationStructureError> {
+        Self::validate_new(&device, &create_info)?;
+
+        Ok(Self::new_unchecked(device, create_info)?)
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &AccelerationStructureCreateInfo,
+    ) -> Result<(), AccelerationStructureError> {
+        // VUID-vkCreateAccelerationStructureKHR-accelerationStructure-03611
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureError::RequirementNotMet {
+                required_for: "`AccelerationStructure::new`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let &AccelerationStructureCreateInfo {
+            create_flags,
+            ref buffer,
+            ty,
+            _ne: _,
+        } = create_info;
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-commonparent
+        assert_eq!(device, buffer.device().as_ref());
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-createFlags-parameter
+        create_flags.validate_device(device)?;
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-type-parameter
+        ty.validate_device(device)?;
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-createFlags-03613
+        if create_flags.intersects(AccelerationStructureCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY)
+            && !device
+                .enabled_features()
+                .acceleration_structure_capture_replay
+        {
+            return Err(AccelerationStructureError::RequirementNotMet {
+                required_for: "`create_info.create_flags` contains \
+                    `AccelerationStructureCreateFlags::DEVICE_ADDRESS_CAPTURE_REPLAY`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure_capture_replay"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-buffer-03614
+        if !buffer
+            .usage()
+            .intersects(BufferUsage::ACCELERATION_STRUCTURE_STORAGE)
+        {
+            return Err(AccelerationStructureError::BufferMissingUsage);
+        }
+
+        // VUID-VkAccelerationStructureCreateInfoKHR-offset-03734
+        let offset = buffer.inner().offset;
+
+        if offset % 256 != 0 {
+            return Err(AccelerationStructureError::BufferOffsetNotAligned {
+                offset,
+                required_alignment: 256,
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        create_info: AccelerationStructureCreateInfo,
+    ) -> Result<Arc<Self>, VulkanError> {
+        let &AccelerationStructureCreateInfo {
+            create_flags,
+            ref buffer,
+            ty,
+            _ne: _,
+        } = &create_info;
+
+        let buffer_inner = buffer.inner();
+
+        let create_info_vk = ash::vk::AccelerationStructureCreateInfoKHR {
+            create_flags: create_flags.into(),
+            buffer: buffer_inner.buffer.handle(),
+            offset: buffer_inner.offset,
+            size: buffer.size(),
+            ty: ty.into(),
+            device_address: 0, // TODO: allow user to specify
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_acceleration_structure
+                .create_acceleration_structure_khr)(
+                device.handle(),
+                &create_info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        Ok(Self::from_handle(device, handle, create_info))
+    }
+
+    /// Creates a new `AccelerationStructure` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `create_info` must match the info used to create the object.
+    #[inline]
+    pub unsafe fn from_handle(
+        device: Arc<Device>,
+        handle: ash::vk::AccelerationStructureKHR,
+        create_info: AccelerationStructureCreateInfo,
+    ) -> Arc<Self> {
+        let AccelerationStructureCreateInfo {
+            create_flags,
+            buffer,
+            ty,
+            _ne: _,
+        } = create_info;
+
+        Arc::new(AccelerationStructure {
+            handle,
+            device,
+            id: Self::next_id(),
+            create_flags,
+            buffer,
+            ty,
+        })
+    }
+
+    /// Returns the flags the acceleration structure was created with.
+    #[inline]
+    pub fn create_flags(&self) -> AccelerationStructureCreateFlags {
+        self.create_flags
+    }
+
+    /// Returns the buffer that the acceleration structure is stored in.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<dyn BufferAccess> {
+        &self.buffer
+    }
+
+    /// Returns the size of the acceleration structure.
+    #[inline]
+    pub fn size(&self) -> DeviceSize {
+        self.buffer.size()
+    }
+
+    /// Returns the type of the acceleration structure.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        self.ty
+    }
+
+    /// Returns the device address of the acceleration structure.
+    ///
+    /// The device address of a bottom-level acceleration structure is used to refer to it from
+    /// an [`AccelerationStructureInstance`] in a top-level acceleration structure.
+    #[inline]
+    pub fn device_address(&self) -> NonZeroU64 {
+        let info_vk = ash::vk::AccelerationStructureDeviceAddressInfoKHR {
+            acceleration_structure: self.handle,
+            ..Default::default()
+        };
+
+        let ptr = unsafe {
+            let fns = self.device.fns();
+            (fns.khr_acceleration_structure
+                .get_acceleration_structure_device_address_khr)(
+                self.device.handle(), &info_vk
+            )
+        };
+
+        NonZeroU64::new(ptr)
+            .expect("got null ptr from a valid GetAccelerationStructureDeviceAddressKHR call")
+    }
+}
+
+impl Drop for AccelerationStructure {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.khr_acceleration_structure
+                .destroy_acceleration_structure_khr)(
+                self.device.handle(),
+                self.handle,
+                ptr::null(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for AccelerationStructure {
+    type Handle = ash::vk::AccelerationStructureKHR;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for AccelerationStructure {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+crate::impl_id_counter!(AccelerationStructure);
+
+/// Parameters to create a new `AccelerationStructure`.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureCreateInfo {
+    /// Specifies how to create the acceleration structure.
+    ///
+    /// The default value is empty.
+    pub create_flags: AccelerationStructureCreateFlags,
+
+    /// The buffer to store the acceleration structure in.
+    ///
+    /// The buffer must have been created with the
+    /// [`ACCELERATION_STRUCTURE_STORAGE`](BufferUsage::ACCELERATION_STRUCTURE_STORAGE) usage,
+    /// and its offset within the underlying buffer must be a multiple of 256. The size of the
+    /// acceleration structure is the size of the buffer, which should be at least the
+    /// [`acceleration_structure_size`] returned by
+    /// [`Device::acceleration_structure_build_sizes`].
+    ///
+    /// There is no default value.
+    ///
+    /// [`acceleration_structure_size`]: AccelerationStructureBuildSizesInfo::acceleration_structure_size
+    pub buffer: Arc<dyn BufferAccess>,
+
+    /// The type of acceleration structure to create.
+    ///
+    /// The default value is [`AccelerationStructureType::Generic`].
+    pub ty: AccelerationStructureType,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureCreateInfo {
+    /// Returns a `AccelerationStructureCreateInfo` with the specified `buffer`.
+    #[inline]
+    pub fn buffer(buffer: Arc<dyn BufferAccess>) -> Self {
+        Self {
+            create_flags: AccelerationStructureCreateFlags::empty(),
+            buffer,
+            ty: AccelerationStructureType::Generic,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control how an acceleration structure is created.
+    AccelerationStructureCreateFlags = AccelerationStructureCreateFlagsKHR(u32);
+
+    /// The acceleration structure can be recreated with the same device address on a
+    /// subsequent run of the application, for capture and replay tools.
+    DEVICE_ADDRESS_CAPTURE_REPLAY = DEVICE_ADDRESS_CAPTURE_REPLAY,
+
+    /* TODO: enable
+    // TODO: document
+    DESCRIPTOR_BUFFER_CAPTURE_REPLAY = DESCRIPTOR_BUFFER_CAPTURE_REPLAY_EXT {
+        device_extensions: [ext_descriptor_buffer],
+    },*/
+
+    /* TODO: enable
+    // TODO: document
+    MOTION = MOTION_NV {
+        device_extensions: [nv_ray_tracing_motion_blur],
+    },*/
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type of an acceleration structure.
+    AccelerationStructureType = AccelerationStructureTypeKHR(i32);
+
+    /// Contains instances of (references to) bottom-level acceleration structures. This type
+    /// can be bound to a descriptor.
+    TopLevel = TOP_LEVEL,
+
+    /// Contains AABBs or geometry to be intersected.
+    BottomLevel = BOTTOM_LEVEL,
+
+    /// The type is determined at build time.
+    ///
+    /// Use of this type is discouraged, it is preferred to specify the type at create time.
+    Generic = GENERIC,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// Where the building of an acceleration structure will take place.
+    AccelerationStructureBuildType = AccelerationStructureBuildTypeKHR(i32);
+
+    /// Building will take place on the host.
+    Host = HOST,
+
+    /// Building will take place on the device.
+    Device = DEVICE,
+
+    /// Building will take place on either the host or the device.
+    HostOrDevice = HOST_OR_DEVICE,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags to control how an acceleration structure should be built.
+    BuildAccelerationStructureFlags = BuildAccelerationStructureFlagsKHR(u32);
+
+    /// The acceleration structure can be updated later, with
+    /// [`BuildAccelerationStructureMode::Update`].
+    ALLOW_UPDATE = ALLOW_UPDATE,
+
+    /// The acceleration structure can be compacted when it is copied.
+    ALLOW_COMPACTION = ALLOW_COMPACTION,
+
+    /// Prioritize for best trace performance, with possibly longer build times.
+    PREFER_FAST_TRACE = PREFER_FAST_TRACE,
+
+    /// Prioritize for shorter build time, with possibly suboptimal trace performance.
+    PREFER_FAST_BUILD = PREFER_FAST_BUILD,
+
+    /// Prioritize low acceleration structure and scratch memory size, with possibly longer build
+    /// times or suboptimal trace performance.
+    LOW_MEMORY = LOW_MEMORY,
+
+    /* TODO: enable
+    // TODO: document
+    MOTION = MOTION_NV {
+        device_extensions: [nv_ray_tracing_motion_blur],
+    },*/
+}
+
+/// What mode an acceleration structure build command should operate in.
+#[derive(Clone, Debug)]
+pub enum BuildAccelerationStructureMode {
+    /// Build a new acceleration structure from scratch.
+    Build,
+
+    /// Update a previously built source acceleration structure with new data, storing the
+    /// updated structure in the destination. The source and destination acceleration structures
+    /// may be the same, which will do the update in-place.
+    ///
+    /// The source acceleration structure must have been built with the
+    /// [`BuildAccelerationStructureFlags::ALLOW_UPDATE`] flag.
+    Update(Arc<AccelerationStructure>),
+}
+
+impl From<&BuildAccelerationStructureMode> for ash::vk::BuildAccelerationStructureModeKHR {
+    #[inline]
+    fn from(val: &BuildAccelerationStructureMode) -> Self {
+        match val {
+            BuildAccelerationStructureMode::Build => Self::BUILD,
+            BuildAccelerationStructureMode::Update(_) => Self::UPDATE,
+        }
+    }
+}
+
+/// Parameters for building an acceleration structure, or for querying the sizes that are needed
+/// for it.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureBuildGeometryInfo {
+    /// Specifies how to build the acceleration structure.
+    ///
+    /// The default value is empty.
+    pub flags: BuildAccelerationStructureFlags,
+
+    /// The mode that the build command should operate in.
+    ///
+    /// This is ignored when querying the build sizes.
+    ///
+    /// The default value is [`BuildAccelerationStructureMode::Build`].
+    pub mode: BuildAccelerationStructureMode,
+
+    /// The acceleration structure to build or update.
+    ///
+    /// This can be `None` when querying the build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    pub dst_acceleration_structure: Option<Arc<AccelerationStructure>>,
+
+    /// The geometries that will be built into `dst_acceleration_structure`.
+    ///
+    /// Triangles and AABBs produce a bottom-level acceleration structure, instances produce a
+    /// top-level acceleration structure.
+    ///
+    /// There is no default value.
+    pub geometries: AccelerationStructureGeometries,
+
+    /// The buffer that is used for temporary storage while building. It must have been created
+    /// with the [`STORAGE_BUFFER`] and [`SHADER_DEVICE_ADDRESS`] usages, and be at least as large
+    /// as the size returned by [`Device::acceleration_structure_build_sizes`].
+    ///
+    /// This can be `None` when querying the build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`STORAGE_BUFFER`]: BufferUsage::STORAGE_BUFFER
+    /// [`SHADER_DEVICE_ADDRESS`]: BufferUsage::SHADER_DEVICE_ADDRESS
+    pub scratch_data: Option<Arc<dyn BufferAccess>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureBuildGeometryInfo {
+    /// Returns a `AccelerationStructureBuildGeometryInfo` with the specified `geometries`.
+    #[inline]
+    pub fn geometries(geometries: AccelerationStructureGeometries) -> Self {
+        Self {
+            flags: BuildAccelerationStructureFlags::empty(),
+            mode: BuildAccelerationStructureMode::Build,
+            dst_acceleration_structure: None,
+            geometries,
+            scratch_data: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    /// Turns `self` into an in-place update of `dst_acceleration_structure`, by setting `mode`
+    /// to [`BuildAccelerationStructureMode::Update`] with `dst_acceleration_structure` as the
+    /// source.
+    ///
+    /// This is useful to refit an acceleration structure every frame, for example a top-level
+    /// acceleration structure whose instances have moved. The acceleration structure must
+    /// have been originally built with the [`BuildAccelerationStructureFlags::ALLOW_UPDATE`]
+    /// flag, which `self.flags` must still contain.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `self.dst_acceleration_structure` is `None`.
+    #[inline]
+    pub fn into_update(self) -> Self {
+        let src_acceleration_structure = self
+            .dst_acceleration_structure
+            .clone()
+            .expect("`dst_acceleration_structure` must be `Some` to update it in place");
+
+        Self {
+            mode: BuildAccelerationStructureMode::Update(src_acceleration_structure),
+            ..self
+        }
+    }
+
+    /// Returns the type of acceleration structure that is produced by `geometries`.
+    #[inline]
+    pub fn ty(&self) -> AccelerationStructureType {
+        match self.geometries {
+            AccelerationStructureGeometries::Triangles(_)
+            | AccelerationStructureGeometries::Aabbs(_) => AccelerationStructureType::BottomLevel,
+            AccelerationStructureGeometries::Instances(_) => AccelerationStructureType::TopLevel,
+        }
+    }
+
+    pub(crate) fn validate(
+        &self,
+        device: &Device,
+        max_primitive_counts: &[u32],
+    ) -> Result<(), AccelerationStructureError> {
+        let &AccelerationStructureBuildGeometryInfo {
+            flags,
+            mode: _,
+            dst_acceleration_structure: _,
+            ref geometries,
+            scratch_data: _,
+            _ne: _,
+        } = self;
+
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-flags-parameter
+        flags.validate_device(device)?;
+
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-flags-03796
+        if flags.contains(
+            BuildAccelerationStructureFlags::PREFER_FAST_TRACE
+                | BuildAccelerationStructureFlags::PREFER_FAST_BUILD,
+        ) {
+            return Err(AccelerationStructureError::BuildFlagsPreferFastTraceAndFastBuild);
+        }
+
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-pBuildInfo-03619
+        // VUID-vkCmdBuildAccelerationStructuresKHR-ppBuildRangeInfos-03676
+        if max_primitive_counts.len() != geometries.len() {
+            return Err(AccelerationStructureError::PrimitiveCountsCountMismatch {
+                provided: max_primitive_counts.len() as u32,
+                required: geometries.len() as u32,
+            });
+        }
+
+        let properties = device.physical_device().properties();
+
+        match geometries {
+            AccelerationStructureGeometries::Triangles(geometries) => {
+                // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03793
+                let max_geometry_count = properties.max_geometry_count.unwrap_or(0);
+
+                if geometries.len() as u64 > max_geometry_count {
+                    return Err(AccelerationStructureError::MaxGeometryCountExceeded {
+                        count: geometries.len() as u64,
+                        max: max_geometry_count,
+                    });
+                }
+
+                for (geometry_index, triangles_data) in geometries.iter().enumerate() {
+                    let &AccelerationStructureGeometryTrianglesData {
+                        flags,
+                        vertex_format,
+                        vertex_data: _,
+                        vertex_stride,
+                        max_vertex: _,
+                        ref index_data,
+                        index_type,
+                        transform_data: _,
+                        _ne: _,
+                    } = triangles_data;
+
+                    // VUID-VkAccelerationStructureGeometryKHR-flags-parameter
+                    flags.validate_device(device)?;
+
+                    // VUID-VkAccelerationStructureGeometryTrianglesDataKHR-vertexFormat-parameter
+                    vertex_format.validate_device(device)?;
+
+                    // VUID-VkAccelerationStructureGeometryTrianglesDataKHR-vertexFormat-03797
+                    if !unsafe {
+                        device
+                            .physical_device()
+                            .format_properties_unchecked(vertex_format)
+                            .buffer_features
+                    }
+                    .intersects(FormatFeatures::ACCELERATION_STRUCTURE_VERTEX_BUFFER)
+                    {
+                        return Err(AccelerationStructureError::VertexFormatNotSupported {
+                            geometry_index: geometry_index as u32,
+                            format: vertex_format,
+                        });
+                    }
+
+                    // VUID-VkAccelerationStructureGeometryTrianglesDataKHR-vertexStride-03735
+                    let smallest_component_size = vertex_format_component_size(vertex_format);
+
+                    if vertex_stride as DeviceSize % smallest_component_size != 0 {
+                        return Err(AccelerationStructureError::VertexStrideNotAligned {
+                            geometry_index: geometry_index as u32,
+                            stride: vertex_stride,
+                            required_alignment: smallest_component_size,
+                        });
+                    }
+
+                    if index_data.is_some() {
+                        // VUID-VkAccelerationStructureGeometryTrianglesDataKHR-indexType-parameter
+                        index_type.validate_device(device)?;
+
+                        // VUID-VkAccelerationStructureGeometryTrianglesDataKHR-indexType-03798
+                        if !matches!(index_type, IndexType::U16 | IndexType::U32) {
+                            return Err(AccelerationStructureError::IndexTypeNotSupported {
+                                geometry_index: geometry_index as u32,
+                                index_type,
+                            });
+                        }
+                    }
+                }
+
+                // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03795
+                let max_primitive_count = properties.max_primitive_count.unwrap_or(0);
+                let primitive_count: u64 = max_primitive_counts.iter().map(|&c| c as u64).sum();
+
+                if primitive_count > max_primitive_count {
+                    return Err(AccelerationStructureError::MaxPrimitiveCountExceeded {
+                        count: primitive_count,
+                        max: max_primitive_count,
+                    });
+                }
+            }
+            AccelerationStructureGeometries::Aabbs(geometries) => {
+                // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03793
+                let max_geometry_count = properties.max_geometry_count.unwrap_or(0);
+
+                if geometries.len() as u64 > max_geometry_count {
+                    return Err(AccelerationStructureError::MaxGeometryCountExceeded {
+                        count: geometries.len() as u64,
+                        max: max_geometry_count,
+                    });
+                }
+
+                for (geometry_index, aabbs_data) in geometries.iter().enumerate() {
+                    let &AccelerationStructureGeometryAabbsData {
+                        flags,
+                        data: _,
+                        stride,
+                        _ne: _,
+                    } = aabbs_data;
+
+                    // VUID-VkAccelerationStructureGeometryKHR-flags-parameter
+                    flags.validate_device(device)?;
+
+                    // VUID-VkAccelerationStructureGeometryAabbsDataKHR-stride-03545
+                    if stride % 8 != 0 {
+                        return Err(AccelerationStructureError::AabbStrideNotAligned {
+                            geometry_index: geometry_index as u32,
+                            stride,
+                        });
+                    }
+                }
+
+                // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03794
+                let max_primitive_count = properties.max_primitive_count.unwrap_or(0);
+                let primitive_count: u64 = max_primitive_counts.iter().map(|&c| c as u64).sum();
+
+                if primitive_count > max_primitive_count {
+                    return Err(AccelerationStructureError::MaxPrimitiveCountExceeded {
+                        count: primitive_count,
+                        max: max_primitive_count,
+                    });
+                }
+            }
+            AccelerationStructureGeometries::Instances(instances_data) => {
+                let &AccelerationStructureGeometryInstancesData {
+                    flags,
+                    data: _,
+                    _ne: _,
+                } = instances_data;
+
+                // VUID-VkAccelerationStructureGeometryKHR-flags-parameter
+                flags.validate_device(device)?;
+
+                // VUID-VkAccelerationStructureBuildGeometryInfoKHR-type-03801
+                let max_instance_count = properties.max_instance_count.unwrap_or(0);
+                let instance_count = max_primitive_counts[0] as u64;
+
+                if instance_count > max_instance_count {
+                    return Err(AccelerationStructureError::MaxInstanceCountExceeded {
+                        count: instance_count,
+                        max: max_instance_count,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the Vulkan build info, and the geometries that `p_geometries` must point to.
+    /// `p_geometries` is not set by this function, as it must point to the returned `SmallVec`.
+    pub(crate) fn to_vulkan(
+        &self,
+    ) -> (
+        ash::vk::AccelerationStructureBuildGeometryInfoKHR,
+        SmallVec<[ash::vk::AccelerationStructureGeometryKHR; 8]>,
+    ) {
+        let &AccelerationStructureBuildGeometryInfo {
+            flags,
+            ref mode,
+            ref dst_acceleration_structure,
+            ref geometries,
+            ref scratch_data,
+            _ne: _,
+        } = self;
+
+        let geometries_vk: SmallVec<[_; 8]> = match geometries {
+            AccelerationStructureGeometries::Triangles(geometries) => geometries
+                .iter()
+                .map(|triangles_data| {
+                    let &AccelerationStructureGeometryTrianglesData {
+                        flags,
+                        vertex_format,
+                        ref vertex_data,
+                        vertex_stride,
+                        max_vertex,
+                        ref index_data,
+                        index_type,
+                        ref transform_data,
+                        _ne: _,
+                    } = triangles_data;
+
+                    ash::vk::AccelerationStructureGeometryKHR {
+                        geometry_type: ash::vk::GeometryTypeKHR::TRIANGLES,
+                        geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                            triangles: ash::vk::AccelerationStructureGeometryTrianglesDataKHR {
+                                vertex_format: vertex_format.into(),
+                                vertex_data: device_address_const(vertex_data.as_ref()),
+                                vertex_stride: vertex_stride as DeviceSize,
+                                max_vertex,
+                                index_type: if index_data.is_some() {
+                                    index_type.into()
+                                } else {
+                                    ash::vk::IndexType::NONE_KHR
+                                },
+                                index_data: device_address_const(index_data.as_ref()),
+                                transform_data: device_address_const(transform_data.as_ref()),
+                                ..Default::default()
+                            },
+                        },
+                        flags: flags.into(),
+                        ..Default::default()
+                    }
+                })
+                .collect(),
+            AccelerationStructureGeometries::Aabbs(geometries) => geometries
+                .iter()
+                .map(|aabbs_data| {
+                    let &AccelerationStructureGeometryAabbsData {
+                        flags,
+                        ref data,
+                        stride,
+                        _ne: _,
+                    } = aabbs_data;
+
+                    ash::vk::AccelerationStructureGeometryKHR {
+                        geometry_type: ash::vk::GeometryTypeKHR::AABBS,
+                        geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                            aabbs: ash::vk::AccelerationStructureGeometryAabbsDataKHR {
+                                data: device_address_const(data.as_ref()),
+                                stride: stride as DeviceSize,
+                                ..Default::default()
+                            },
+                        },
+                        flags: flags.into(),
+                        ..Default::default()
+                    }
+                })
+                .collect(),
+            AccelerationStructureGeometries::Instances(instances_data) => {
+                let &AccelerationStructureGeometryInstancesData {
+                    flags,
+                    ref data,
+                    _ne: _,
+                } = instances_data;
+
+                [ash::vk::AccelerationStructureGeometryKHR {
+                    geometry_type: ash::vk::GeometryTypeKHR::INSTANCES,
+                    geometry: ash::vk::AccelerationStructureGeometryDataKHR {
+                        instances: ash::vk::AccelerationStructureGeometryInstancesDataKHR {
+                            array_of_pointers: ash::vk::FALSE,
+                            data: device_address_const(data.as_ref()),
+                            ..Default::default()
+                        },
+                    },
+                    flags: flags.into(),
+                    ..Default::default()
+                }]
+                .into_iter()
+                .collect()
+            }
+        };
+
+        let info_vk = ash::vk::AccelerationStructureBuildGeometryInfoKHR {
+            ty: self.ty().into(),
+            flags: flags.into(),
+            mode: mode.into(),
+            src_acceleration_structure: match mode {
+                BuildAccelerationStructureMode::Build => Default::default(),
+                BuildAccelerationStructureMode::Update(src_acceleration_structure) => {
+                    src_acceleration_structure.handle()
+                }
+            },
+            dst_acceleration_structure: dst_acceleration_structure
+                .as_ref()
+                .map_or_else(Default::default, VulkanObject::handle),
+            geometry_count: geometries_vk.len() as u32,
+            p_geometries: ptr::null(),
+            pp_geometries: ptr::null(),
+            scratch_data: ash::vk::DeviceOrHostAddressKHR {
+                device_address: device_address(scratch_data.as_ref()),
+            },
+            ..Default::default()
+        };
+
+        (info_vk, geometries_vk)
+    }
+}
+
+// Returns the device address of a buffer, or 0 if there is no buffer. Validation must have
+// already ensured that the address can be retrieved, if it is needed.
+fn device_address(buffer: Option<&Arc<dyn BufferAccess>>) -> ash::vk::DeviceAddress {
+    buffer
+        .and_then(|buffer| buffer.raw_device_address().ok())
+        .map_or(0, NonZeroU64::get)
+}
+
+fn device_address_const(
+    buffer: Option<&Arc<dyn BufferAccess>>,
+) -> ash::vk::DeviceOrHostAddressConstKHR {
+    ash::vk::DeviceOrHostAddressConstKHR {
+        device_address: device_address(buffer),
+    }
+}
+
+// Returns the size in bytes of the smallest component of a vertex format.
+pub(crate) fn vertex_format_component_size(format: Format) -> DeviceSize {
+    format
+        .components()
+        .into_iter()
+        .filter(|&bits| bits != 0)
+        .min()
+        .map_or(1, |bits| (bits as DeviceSize + 7) / 8)
+}
+
+/// The geometries that are built into an acceleration structure.
+#[derive(Clone, Debug)]
+pub enum AccelerationStructureGeometries {
+    /// The geometries are triangles. This produces a bottom-level acceleration structure.
+    Triangles(Vec<AccelerationStructureGeometryTrianglesData>),
+
+    /// The geometries are axis-aligned bounding boxes. This produces a bottom-level acceleration
+    /// structure.
+    Aabbs(Vec<AccelerationStructureGeometryAabbsData>),
+
+    /// The geometry is a list of instances of bottom-level acceleration structures. This
+    /// produces a top-level acceleration structure.
+    Instances(AccelerationStructureGeometryInstancesData),
+}
+
+impl AccelerationStructureGeometries {
+    /// Returns the number of geometries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            AccelerationStructureGeometries::Triangles(geometries) => geometries.len(),
+            AccelerationStructureGeometries::Aabbs(geometries) => geometries.len(),
+            AccelerationStructureGeometries::Instances(_) => 1,
+        }
+    }
+
+    /// Returns whether there are no geometries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl From<Vec<AccelerationStructureGeometryTrianglesData>> for AccelerationStructureGeometries {
+    #[inline]
+    fn from(val: Vec<AccelerationStructureGeometryTrianglesData>) -> Self {
+        Self::Triangles(val)
+    }
+}
+
+impl From<Vec<AccelerationStructureGeometryAabbsData>> for AccelerationStructureGeometries {
+    #[inline]
+    fn from(val: Vec<AccelerationStructureGeometryAabbsData>) -> Self {
+        Self::Aabbs(val)
+    }
+}
+
+impl From<AccelerationStructureGeometryInstancesData> for AccelerationStructureGeometries {
+    #[inline]
+    fn from(val: AccelerationStructureGeometryInstancesData) -> Self {
+        Self::Instances(val)
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags to control how the geometry of an acceleration structure is traced.
+    GeometryFlags = GeometryFlagsKHR(u32);
+
+    /// The geometry does not invoke the any-hit shaders, even if it is present in a hit group.
+    OPAQUE = OPAQUE,
+
+    /// The any-hit shader will never be called more than once for each primitive in the
+    /// geometry.
+    NO_DUPLICATE_ANY_HIT_INVOCATION = NO_DUPLICATE_ANY_HIT_INVOCATION,
+}
+
+/// A bottom-level geometry consisting of triangles.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryTrianglesData {
+    /// Specifies how the geometry will be traced.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The format of each vertex in `vertex_data`.
+    ///
+    /// This works in the same way as formats for vertex buffers. The format must support the
+    /// [`FormatFeatures::ACCELERATION_STRUCTURE_VERTEX_BUFFER`] feature for buffers.
+    ///
+    /// There is no default value.
+    pub vertex_format: Format,
+
+    /// The buffer containing the vertex data.
+    ///
+    /// The buffer must have been created with the
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`] and [`SHADER_DEVICE_ADDRESS`] usages.
+    ///
+    /// This can be `None` when querying the build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`]: BufferUsage::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY
+    /// [`SHADER_DEVICE_ADDRESS`]: BufferUsage::SHADER_DEVICE_ADDRESS
+    pub vertex_data: Option<Arc<dyn BufferAccess>>,
+
+    /// The number of bytes between the start of successive elements in `vertex_data`.
+    ///
+    /// This must be a multiple of the smallest component size (in bytes) of `vertex_format`.
+    ///
+    /// The default value is 0, which must be overridden.
+    pub vertex_stride: u32,
+
+    /// The highest vertex index that may be read from `vertex_data`.
+    ///
+    /// The default value is 0, which must be overridden.
+    pub max_vertex: u32,
+
+    /// If indices are used, the buffer containing the index data.
+    ///
+    /// The buffer must have been created with the
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`] and [`SHADER_DEVICE_ADDRESS`] usages.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`]: BufferUsage::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY
+    /// [`SHADER_DEVICE_ADDRESS`]: BufferUsage::SHADER_DEVICE_ADDRESS
+    pub index_data: Option<Arc<dyn BufferAccess>>,
+
+    /// The type of the indices in `index_data`, if it is `Some`.
+    ///
+    /// Only [`IndexType::U16`] and [`IndexType::U32`] are allowed.
+    ///
+    /// The default value is [`IndexType::U32`].
+    pub index_type: IndexType,
+
+    /// Optionally, a buffer containing a [`TransformMatrix`] that will be applied to the
+    /// vertices before building.
+    ///
+    /// The buffer must have been created with the
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`] and [`SHADER_DEVICE_ADDRESS`] usages.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`]: BufferUsage::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY
+    /// [`SHADER_DEVICE_ADDRESS`]: BufferUsage::SHADER_DEVICE_ADDRESS
+    pub transform_data: Option<Arc<dyn BufferAccess>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl AccelerationStructureGeometryTrianglesData {
+    /// Returns a `AccelerationStructureGeometryTrianglesData` with the specified
+    /// `vertex_format`.
+    #[inline]
+    pub fn vertex_format(vertex_format: Format) -> Self {
+        Self {
+            flags: GeometryFlags::empty(),
+            vertex_format,
+            vertex_data: None,
+            vertex_stride: 0,
+            max_vertex: 0,
+            index_data: None,
+            index_type: IndexType::U32,
+            transform_data: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A 3x4 transformation matrix, in row-major order, that is applied to the geometry of a
+/// bottom-level acceleration structure.
+#[derive(Clone, Copy, Debug, PartialEq, Zeroable, Pod)]
+#[repr(C)]
+pub struct TransformMatrix(pub [[f32; 4]; 3]);
+
+impl Default for TransformMatrix {
+    #[inline]
+    fn default() -> Self {
+        Self([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+        ])
+    }
+}
+
+/// A bottom-level geometry consisting of axis-aligned bounding boxes.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryAabbsData {
+    /// Specifies how the geometry will be traced.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The buffer containing the [`AabbPositions`] of each AABB.
+    ///
+    /// The buffer must have been created with the
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`] and [`SHADER_DEVICE_ADDRESS`] usages.
+    ///
+    /// This can be `None` when querying the build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`]: BufferUsage::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY
+    /// [`SHADER_DEVICE_ADDRESS`]: BufferUsage::SHADER_DEVICE_ADDRESS
+    pub data: Option<Arc<dyn BufferAccess>>,
+
+    /// The number of bytes between the start of successive elements in `data`.
+    ///
+    /// This must be a multiple of 8.
+    ///
+    /// The default value is 24, the size of [`AabbPositions`].
+    pub stride: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AccelerationStructureGeometryAabbsData {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: GeometryFlags::empty(),
+            data: None,
+            stride: 24,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The minimum and maximum corners of an axis-aligned bounding box.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Zeroable, Pod)]
+#[repr(C)]
+pub struct AabbPositions {
+    /// The minimum of the box, on each axis.
+    pub min: [f32; 3],
+
+    /// The maximum of the box, on each axis.
+    pub max: [f32; 3],
+}
+
+/// A top-level geometry consisting of instances of bottom-level acceleration structures.
+#[derive(Clone, Debug)]
+pub struct AccelerationStructureGeometryInstancesData {
+    /// Specifies how the geometry will be traced.
+    ///
+    /// The default value is empty.
+    pub flags: GeometryFlags,
+
+    /// The buffer containing an array of [`AccelerationStructureInstance`] values.
+    ///
+    /// The buffer must have been created with the
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`] and [`SHADER_DEVICE_ADDRESS`] usages.
+    ///
+    /// This can be `None` when querying the build sizes, but must be `Some` when building.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY`]: BufferUsage::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY
+    /// [`SHADER_DEVICE_ADDRESS`]: BufferUsage::SHADER_DEVICE_ADDRESS
+    pub data: Option<Arc<dyn BufferAccess>>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AccelerationStructureGeometryInstancesData {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: GeometryFlags::empty(),
+            data: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// An instance of a bottom-level acceleration structure, as it is stored in the buffer of
+/// [`AccelerationStructureGeometryInstancesData`].
+#[derive(Clone, Copy, Debug, PartialEq, Zeroable, Pod)]
+#[repr(C)]
+pub struct AccelerationStructureInstance {
+    /// The transformation that is applied to the bottom-level acceleration structure.
+    ///
+    /// The default value is the identity matrix.
+    pub transform: TransformMatrix,
+
+    /// The low 24 bits are a custom index that is made available to shaders, the high 8 bits
+    /// are a visibility mask that is compared against the mask of a ray.
+    ///
+    /// The default value is `Packed24_8::new(0, 0xff)`.
+    pub instance_custom_index_and_mask: Packed24_8,
+
+    /// The low 24 bits are an offset into the shader binding table, the high 8 bits are a
+    /// value of [`GeometryInstanceFlags`].
+    ///
+    /// The default value is `Packed24_8::new(0, 0)`.
+    pub instance_shader_binding_table_record_offset_and_flags: Packed24_8,
+
+    /// The device address of the bottom-level acceleration structure, as returned by
+    /// [`AccelerationStructure::device_address`].
+    ///
+    /// The default value is 0, which must be overridden.
+    pub acceleration_structure_reference: DeviceSize,
+}
+
+impl Default for AccelerationStructureInstance {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            transform: Default::default(),
+            instance_custom_index_and_mask: Packed24_8::new(0, 0xff),
+            instance_shader_binding_table_record_offset_and_flags: Packed24_8::new(0, 0),
+            acceleration_structure_reference: 0,
+        }
+    }
+}
+
+/// A 24-bit value and an 8-bit value, packed together into a `u32`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Zeroable, Pod)]
+#[repr(transparent)]
+pub struct Packed24_8(u32);
+
+impl Packed24_8 {
+    /// Returns a new `Packed24_8` value.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `low_24` does not fit in 24 bits.
+    #[inline]
+    pub fn new(low_24: u32, high_8: u8) -> Self {
+        assert!(low_24 <= 0xff_ffff);
+
+        Self((high_8 as u32) << 24 | low_24)
+    }
+
+    /// Returns the low 24 bits.
+    #[inline]
+    pub fn low_24(&self) -> u32 {
+        self.0 & 0xff_ffff
+    }
+
+    /// Returns the high 8 bits.
+    #[inline]
+    pub fn high_8(&self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags for an instance in a top-level acceleration structure.
+    GeometryInstanceFlags = GeometryInstanceFlagsKHR(u32);
+
+    /// Disable face culling for the instance.
+    TRIANGLE_FACING_CULL_DISABLE = TRIANGLE_FACING_CULL_DISABLE,
+
+    /// Flip the facing (front vs back) of triangles.
+    TRIANGLE_FLIP_FACING = TRIANGLE_FLIP_FACING,
+
+    /// Geometries in this instance will act as if [`GeometryFlags::OPAQUE`] were specified.
+    FORCE_OPAQUE = FORCE_OPAQUE,
+
+    /// Geometries in this instance will act as if [`GeometryFlags::OPAQUE`] were not
+    /// specified.
+    FORCE_NO_OPAQUE = FORCE_NO_OPAQUE,
+}
+
+impl From<GeometryInstanceFlags> for u8 {
+    #[inline]
+    fn from(value: GeometryInstanceFlags) -> Self {
+        ash::vk::GeometryInstanceFlagsKHR::from(value).as_raw() as u8
+    }
+}
+
+/// Counts and offsets for an acceleration structure build operation, one for each geometry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AccelerationStructureBuildRangeInfo {
+    /// The number of primitives (triangles, AABBs or instances) to build.
+    pub primitive_count: u32,
+
+    /// The offset in bytes into the buffer of the geometry data, where the primitives start.
+    ///
+    /// For triangles, this is an offset into the index data if indices are used, and an offset
+    /// into the vertex data otherwise.
+    pub primitive_offset: u32,
+
+    /// For triangles, the index of the first vertex to build from.
+    ///
+    /// This is ignored for AABBs and instances.
+    pub first_vertex: u32,
+
+    /// For triangles, the offset in bytes into the transform data, where the
+    /// [`TransformMatrix`] is read from.
+    ///
+    /// This is ignored for AABBs and instances.
+    pub transform_offset: u32,
+}
+
+impl From<AccelerationStructureBuildRangeInfo> for ash::vk::AccelerationStructureBuildRangeInfoKHR {
+    #[inline]
+    fn from(val: AccelerationStructureBuildRangeInfo) -> Self {
+        Self {
+            primitive_count: val.primitive_count,
+            primitive_offset: val.primitive_offset,
+            first_vertex: val.first_vertex,
+            transform_offset: val.transform_offset,
+        }
+    }
+}
+
+/// The sizes that are needed to build an acceleration structure.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct AccelerationStructureBuildSizesInfo {
+    /// The minimum size of the buffer of the acceleration structure.
+    pub acceleration_structure_size: DeviceSize,
+
+    /// The minimum size of the scratch buffer, when the build mode is
+    /// [`BuildAccelerationStructureMode::Update`].
+    pub update_scratch_size: DeviceSize,
+
+    /// The minimum size of the scratch buffer, when the build mode is
+    /// [`BuildAccelerationStructureMode::Build`].
+    pub build_scratch_size: DeviceSize,
+}
+
+/// Parameters for copying an acceleration structure into another.
+#[derive(Clone, Debug)]
+pub struct CopyAccelerationStructureInfo {
+    /// The acceleration structure to copy from.
+    ///
+    /// There is no default value.
+    pub src: Arc<AccelerationStructure>,
+
+    /// The acceleration structure to copy into.
+    ///
+    /// There is no default value.
+    pub dst: Arc<AccelerationStructure>,
+
+    /// How to perform the copy.
+    ///
+    /// The default value is [`CopyAccelerationStructureMode::Clone`].
+    pub mode: CopyAccelerationStructureMode,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl CopyAccelerationStructureInfo {
+    /// Returns a `CopyAccelerationStructureInfo` with the specified `src` and `dst`.
+    #[inline]
+    pub fn acceleration_structures(
+        src: Arc<AccelerationStructure>,
+        dst: Arc<AccelerationStructure>,
+    ) -> Self {
+        Self {
+            src,
+            dst,
+            mode: CopyAccelerationStructureMode::Clone,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn to_vulkan(&self) -> ash::vk::CopyAccelerationStructureInfoKHR {
+        let &Self {
+            ref src,
+            ref dst,
+            mode,
+            _ne: _,
+        } = self;
+
+        ash::vk::CopyAccelerationStructureInfoKHR {
+            src: src.handle(),
+            dst: dst.handle(),
+            mode: mode.into(),
+            ..Default::default()
+        }
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// What mode an acceleration structure copy command should operate in.
+    CopyAccelerationStructureMode = CopyAccelerationStructureModeKHR(i32);
+
+    /// Copy the source into the destination. The destination must be at least as large as the
+    /// source.
+    Clone = CLONE,
+
+    /// Copy the source into the destination, removing any unused space. The source must have
+    /// been built with the [`BuildAccelerationStructureFlags::ALLOW_COMPACTION`] flag, and the
+    /// destination must be at least as large as the compacted size of the source, which can be
+    /// queried with a [`QueryType::AccelerationStructureCompactedSize`] query.
+    ///
+    /// [`QueryType::AccelerationStructureCompactedSize`]: crate::query::QueryType::AccelerationStructureCompactedSize
+    Compact = COMPACT,
+
+    /* TODO: enable
+    // TODO: document
+    Serialize = SERIALIZE,*/
+
+    /* TODO: enable
+    // TODO: document
+    Deserialize = DESERIALIZE,*/
+}
+
+/// Error that can happen when creating or building an acceleration structure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccelerationStructureError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The stride of the AABB data of a geometry is not a multiple of 8.
+    AabbStrideNotAligned { geometry_index: u32, stride: u32 },
+
+    /// The buffer was not created with the `acceleration_structure_storage` usage.
+    BufferMissingUsage,
+
+    /// The offset of the buffer is not a multiple of the required alignment.
+    BufferOffsetNotAligned {
+        offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// Both the `PREFER_FAST_TRACE` and `PREFER_FAST_BUILD` build flags were set.
+    BuildFlagsPreferFastTraceAndFastBuild,
+
+    /// The index type of a geometry is not supported for acceleration structures.
+    IndexTypeNotSupported {
+        geometry_index: u32,
+        index_type: IndexType,
+    },
+
+    /// The number of geometries exceeds the `max_geometry_count` limit.
+    MaxGeometryCountExceeded { count: u64, max: u64 },
+
+    /// The number of instances exceeds the `max_instance_count` limit.
+    MaxInstanceCountExceeded { count: u64, max: u64 },
+
+    /// The total number of primitives exceeds the `max_primitive_count` limit.
+    MaxPrimitiveCountExceeded { count: u64, max: u64 },
+
+    /// The number of provided primitive counts does not equal the number of geometries.
+    PrimitiveCountsCountMismatch { provided: u32, required: u32 },
+
+    /// The vertex format of a geometry does not support the
+    /// `acceleration_structure_vertex_buffer` format feature.
+    VertexFormatNotSupported { geometry_index: u32, format: Format },
+
+    /// The vertex stride of a geometry is not a multiple of the size of the smallest component
+    /// of the vertex format.
+    VertexStrideNotAligned {
+        geometry_index: u32,
+        stride: u32,
+        required_alignment: DeviceSize,
+    },
+}
+
+impl Error for AccelerationStructureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AccelerationStructureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::AabbStrideNotAligned {
+                geometry_index,
+                stride,
+            } => write!(
+                f,
+                "the stride ({}) of the AABB data of geometry {} is not a multiple of 8",
+                stride, geometry_index,
+            ),
+            Self::BufferMissingUsage => write!(
+                f,
+                "the buffer was not created with the `acceleration_structure_storage` usage",
+            ),
+            Self::BufferOffsetNotAligned {
+                offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the offset of the buffer ({}) is not a multiple of the required alignment ({})",
+                offset, required_alignment,
+            ),
+            Self::BuildFlagsPreferFastTraceAndFastBuild => write!(
+                f,
+                "both the `PREFER_FAST_TRACE` and `PREFER_FAST_BUILD` build flags were set",
+            ),
+            Self::IndexTypeNotSupported {
+                geometry_index,
+                index_type,
+            } => write!(
+                f,
+                "the index type ({:?}) of geometry {} is not supported for acceleration \
+                structures",
+                index_type, geometry_index,
+            ),
+            Self::MaxGeometryCountExceeded { count, max } => write!(
+                f,
+                "the number of geometries ({}) exceeds the `max_geometry_count` limit ({})",
+                count, max,
+            ),
+            Self::MaxInstanceCountExceeded { count, max } => write!(
+                f,
+                "the number of instances ({}) exceeds the `max_instance_count` limit ({})",
+                count, max,
+            ),
+            Self::MaxPrimitiveCountExceeded { count, max } => write!(
+                f,
+                "the total number of primitives ({}) exceeds the `max_primitive_count` limit \
+                ({})",
+                count, max,
+            ),
+            Self::PrimitiveCountsCountMismatch { provided, required } => write!(
+                f,
+                "the number of provided primitive counts ({}) does not equal the number of \
+                geometries ({})",
+                provided, required,
+            ),
+            Self::VertexFormatNotSupported {
+                geometry_index,
+                format,
+            } => write!(
+                f,
+                "the vertex format ({:?}) of geometry {} does not support the \
+                `acceleration_structure_vertex_buffer` format feature",
+                format, geometry_index,
+            ),
+            Self::VertexStrideNotAligned {
+                geometry_index,
+                stride,
+                required_alignment,
+            } => write!(
+                f,
+                "the vertex stride ({}) of geometry {} is not a multiple of the size of the \
+                smallest component of the vertex format ({})",
+                stride, geometry_index, required_alignment,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for AccelerationStructureError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<VulkanError> for AccelerationStructureError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            err @ VulkanError::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ VulkanError::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for AccelerationStructureError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AccelerationStructure, AccelerationStructureBuildGeometryInfo,
+        AccelerationStructureBuildType, AccelerationStructureCreateInfo,
+        AccelerationStructureError, AccelerationStructureGeometryInstancesData,
+    };
+    use crate::{
+        buffer::{BufferUsage, CpuAccessibleBuffer},
+        memory::allocator::StandardMemoryAllocator,
+    };
+
+    #[test]
+    fn create_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+
+        let buffer = CpuAccessibleBuffer::from_iter(
+            &memory_allocator,
+            BufferUsage::STORAGE_BUFFER,
+            false,
+            [0u8; 256],
+        )
+        .unwrap();
+
+        match unsafe {
+            AccelerationStructure::new(device, AccelerationStructureCreateInfo::buffer(buffer))
+        } {
+            Err(AccelerationStructureError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn build_sizes_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let build_info = AccelerationStructureBuildGeometryInfo::geometries(
+            AccelerationStructureGeometryInstancesData::default().into(),
+        );
+
+        match device.acceleration_structure_build_sizes(
+            AccelerationStructureBuildType::Device,
+            &build_info,
+            &[1],
+        ) {
+            Err(AccelerationStructureError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -31,7 +31,7 @@ use crate::{
         DedicatedAllocation,
     },
     sync::Sharing,
-    DeviceSize,
+    DeviceSize, VulkanObject,
 };
 use smallvec::SmallVec;
 use std::{
@@ -391,6 +391,18 @@ where
     type Content = T;
 }
 
+unsafe impl<T> VulkanObject for CpuAccessibleBuffer<T>
+where
+    T: BufferContents + ?Sized,
+{
+    type Handle = ash::vk::Buffer;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.inner.handle()
+    }
+}
+
 unsafe impl<T> DeviceOwned for CpuAccessibleBuffer<T>
 where
     T: BufferContents + ?Sized,
@@ -32,7 +32,7 @@ use crate::{
         ExternalMemoryHandleTypes,
     },
     sync::Sharing,
-    DeviceSize,
+    DeviceSize, VulkanObject,
 };
 use smallvec::SmallVec;
 use std::{
@@ -457,6 +457,18 @@ where
     }
 }
 
+unsafe impl<T> VulkanObject for DeviceLocalBuffer<T>
+where
+    T: BufferContents + ?Sized,
+{
+    type Handle = ash::vk::Buffer;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.inner.handle()
+    }
+}
+
 unsafe impl<T> DeviceOwned for DeviceLocalBuffer<T>
 where
     T: BufferContents + ?Sized,
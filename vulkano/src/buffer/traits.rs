@@ -8,7 +8,7 @@
 // according to those terms.
 
 use super::{sys::Buffer, BufferContents, BufferSlice, BufferUsage};
-use crate::{device::DeviceOwned, DeviceSize, RequiresOneOf, SafeDeref, Version, VulkanObject};
+use crate::{device::DeviceOwned, DeviceSize, RequiresOneOf, SafeDeref};
 use std::{
     error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
@@ -67,7 +67,8 @@ pub unsafe trait BufferAccess: DeviceOwned + Send + Sync {
         BufferSlice::index(&self.into_buffer_slice(), index)
     }
 
-    /// Gets the device address for this buffer.
+    /// Gets the device address for this buffer, including the offset of the buffer within its
+    /// underlying [`Buffer`]. See [`Buffer::device_address`] for more.
     ///
     /// # Safety
     ///
@@ -76,49 +77,9 @@ pub unsafe trait BufferAccess: DeviceOwned + Send + Sync {
     /// value in a shader.
     fn raw_device_address(&self) -> Result<NonZeroU64, BufferDeviceAddressError> {
         let inner = self.inner();
-        let device = self.device();
-
-        // VUID-vkGetBufferDeviceAddress-bufferDeviceAddress-03324
-        if !device.enabled_features().buffer_device_address {
-            return Err(BufferDeviceAddressError::RequirementNotMet {
-                required_for: "`BufferAccess::raw_device_address`",
-                requires_one_of: RequiresOneOf {
-                    features: &["buffer_device_address"],
-                    ..Default::default()
-                },
-            });
-        }
-
-        // VUID-VkBufferDeviceAddressInfo-buffer-02601
-        if !inner
-            .buffer
-            .usage()
-            .intersects(BufferUsage::SHADER_DEVICE_ADDRESS)
-        {
-            return Err(BufferDeviceAddressError::BufferMissingUsage);
-        }
+        let address = inner.buffer.device_address()?;
 
-        unsafe {
-            let info = ash::vk::BufferDeviceAddressInfo {
-                buffer: inner.buffer.handle(),
-                ..Default::default()
-            };
-            let fns = device.fns();
-            let f = if device.api_version() >= Version::V1_2 {
-                fns.v1_2.get_buffer_device_address
-            } else if device.enabled_extensions().khr_buffer_device_address {
-                fns.khr_buffer_device_address.get_buffer_device_address_khr
-            } else {
-                fns.ext_buffer_device_address.get_buffer_device_address_ext
-            };
-            let ptr = f(device.handle(), &info);
-
-            if ptr == 0 {
-                panic!("got null ptr from a valid GetBufferDeviceAddress call");
-            }
-
-            Ok(NonZeroU64::new_unchecked(ptr + inner.offset))
-        }
+        Ok(NonZeroU64::new(address.get() + inner.offset).unwrap())
     }
 }
 
@@ -57,83 +57,79 @@ vulkan_bitflags! {
         device_extensions: [khr_buffer_device_address, ext_buffer_device_address],
     },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as the source of the bitstream of a video decode operation.
     VIDEO_DECODE_SRC = VIDEO_DECODE_SRC_KHR {
         device_extensions: [khr_video_decode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// Reserved for future use by video decode operations.
     VIDEO_DECODE_DST = VIDEO_DECODE_DST_KHR {
         device_extensions: [khr_video_decode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a transform feedback buffer with
+    /// [`bind_transform_feedback_buffers`](crate::command_buffer::AutoCommandBufferBuilder::bind_transform_feedback_buffers).
     TRANSFORM_FEEDBACK_BUFFER = TRANSFORM_FEEDBACK_BUFFER_EXT {
         device_extensions: [ext_transform_feedback],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as a counter buffer of
+    /// [`begin_transform_feedback`](crate::command_buffer::AutoCommandBufferBuilder::begin_transform_feedback).
     TRANSFORM_FEEDBACK_COUNTER_BUFFER = TRANSFORM_FEEDBACK_COUNTER_BUFFER_EXT {
         device_extensions: [ext_transform_feedback],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as the source of the predicate value of
+    /// [`begin_conditional_rendering`](crate::command_buffer::AutoCommandBufferBuilder::begin_conditional_rendering).
     CONDITIONAL_RENDERING = CONDITIONAL_RENDERING_EXT {
         device_extensions: [ext_conditional_rendering],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
-    ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR = ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR {
+    /// The buffer can be used as a read-only input to an acceleration structure build, such as
+    /// vertex, index, transform, AABB or instance data.
+    ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY = ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR {
         device_extensions: [khr_acceleration_structure],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as the backing storage of an
+    /// [`AccelerationStructure`](crate::acceleration_structure::AccelerationStructure).
     ACCELERATION_STRUCTURE_STORAGE = ACCELERATION_STRUCTURE_STORAGE_KHR {
         device_extensions: [khr_acceleration_structure],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as a
+    /// [`ShaderBindingTable`](crate::pipeline::ray_tracing::ShaderBindingTable).
     SHADER_BINDING_TABLE = SHADER_BINDING_TABLE_KHR {
         device_extensions: [khr_ray_tracing_pipeline, nv_ray_tracing],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as the destination of the bitstream of a video encode operation.
     VIDEO_ENCODE_DST = VIDEO_ENCODE_DST_KHR {
         device_extensions: [khr_video_encode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// Reserved for future use by video encode operations.
     VIDEO_ENCODE_SRC = VIDEO_ENCODE_SRC_KHR {
         device_extensions: [khr_video_encode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a descriptor buffer that contains sampler and combined
+    /// image sampler descriptors.
     SAMPLER_DESCRIPTOR_BUFFER = SAMPLER_DESCRIPTOR_BUFFER_EXT {
         device_extensions: [ext_descriptor_buffer],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a descriptor buffer that contains descriptors other than
+    /// samplers and combined image samplers.
     RESOURCE_DESCRIPTOR_BUFFER = RESOURCE_DESCRIPTOR_BUFFER_EXT {
         device_extensions: [ext_descriptor_buffer],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a descriptor buffer, and the implementation may store push
+    /// descriptor data in it.
     PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER = PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER_EXT {
         device_extensions: [ext_descriptor_buffer],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
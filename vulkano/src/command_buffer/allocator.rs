@@ -135,8 +135,17 @@ pub struct StandardCommandBufferAllocator {
 
 impl StandardCommandBufferAllocator {
     /// Creates a new `StandardCommandBufferAllocator`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.protected` is `true` and the
+    ///   [`protected_memory`](crate::device::Features::protected_memory) feature is not enabled
+    ///   on the device.
     #[inline]
     pub fn new(device: Arc<Device>, create_info: StandardCommandBufferAllocatorCreateInfo) -> Self {
+        // VUID-VkCommandPoolCreateInfo-flags-02860
+        assert!(!create_info.protected || device.enabled_features().protected_memory);
+
         StandardCommandBufferAllocator {
             device,
             pools: ThreadLocal::new(),
@@ -356,11 +365,14 @@ impl Pool {
             device,
             CommandPoolCreateInfo {
                 queue_family_index,
+                protected: create_info.protected,
                 ..Default::default()
             },
         )
         .map_err(|err| match err {
             CommandPoolCreationError::OomError(err) => err,
+            // We check that the `protected_memory` feature is enabled when creating the allocator.
+            CommandPoolCreationError::RequirementNotMet { .. } => unreachable!(),
             // We check that the provided queue family index is active on the device, so it can't
             // be out of range.
             CommandPoolCreationError::QueueFamilyIndexOutOfRange { .. } => unreachable!(),
@@ -532,6 +544,20 @@ pub struct StandardCommandBufferAllocatorCreateInfo {
     /// The default value is `256`.
     pub secondary_buffer_count: usize,
 
+    /// Whether the command buffers that are allocated are protected.
+    ///
+    /// Protected command buffers can access protected resources, and must be submitted to a
+    /// queue that was created with [`QueueCreateFlags::PROTECTED`]. If you need both protected
+    /// and unprotected command buffers, use a separate allocator for each.
+    ///
+    /// If set to `true`, the [`protected_memory`](crate::device::Features::protected_memory)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`QueueCreateFlags::PROTECTED`]: crate::device::QueueCreateFlags::PROTECTED
+    pub protected: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -541,6 +567,7 @@ impl Default for StandardCommandBufferAllocatorCreateInfo {
         StandardCommandBufferAllocatorCreateInfo {
             primary_buffer_count: 256,
             secondary_buffer_count: 256,
+            protected: false,
             _ne: crate::NonExhaustive(()),
         }
     }
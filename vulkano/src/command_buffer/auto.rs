@@ -20,16 +20,20 @@ use super::{
     SecondaryCommandBufferAbstract, SecondaryCommandBufferResourcesUsage, SubpassContents,
 };
 use crate::{
+    buffer::BufferAccess,
     command_buffer::CommandBufferInheritanceRenderingInfo,
     device::{Device, DeviceOwned, QueueFamilyProperties},
     format::{Format, FormatFeatures},
-    image::ImageAspects,
+    image::{ImageAspects, ImageViewAbstract},
     query::{QueryControlFlags, QueryType},
-    render_pass::{Framebuffer, Subpass},
+    render_pass::{Framebuffer, FramebufferCreateFlags, Subpass},
+    sync::{AccessFlags, PipelineStages},
+    video::VideoSession,
     OomError, RequirementNotMet, RequiresOneOf, VulkanObject,
 };
 use ahash::HashMap;
 use parking_lot::{Mutex, MutexGuard};
+use smallvec::SmallVec;
 use std::{
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
@@ -52,7 +56,7 @@ where
     builder_alloc: A::Builder, // Safety: must be dropped after `inner`
 
     // The index of the queue family that this command buffer is being created for.
-    queue_family_index: u32,
+    pub(super) queue_family_index: u32,
 
     // The inheritance for secondary command buffers.
     // Must be `None` in a primary command buffer and `Some` in a secondary command buffer.
@@ -67,6 +71,21 @@ where
     // If any queries are active, this hashmap contains their state.
     pub(super) query_state: HashMap<ash::vk::QueryType, QueryState>,
 
+    // If conditional rendering is active, contains its state.
+    pub(super) conditional_rendering_state: Option<ConditionalRenderingState>,
+
+    // If transform feedback is active, contains its state.
+    pub(super) transform_feedback_state: Option<TransformFeedbackState>,
+
+    // The number of debug label regions that were begun in this command buffer and not yet ended.
+    pub(super) debug_label_depth: u32,
+
+    // The events that were set in this command buffer and not yet reset.
+    pub(super) event_state: HashMap<ash::vk::Event, EventState>,
+
+    // If a video coding scope is active, contains its state.
+    pub(super) video_coding_state: Option<VideoCodingState>,
+
     _data: PhantomData<L>,
 }
 
@@ -99,6 +118,8 @@ impl From<BeginRenderingState> for RenderPassStateType {
 pub(super) struct BeginRenderPassState {
     pub(super) subpass: Subpass,
     pub(super) framebuffer: Option<Arc<Framebuffer>>,
+    // The image views that are attached, if they are known.
+    pub(super) attachments: Option<Vec<Arc<dyn ImageViewAbstract>>>,
 }
 
 pub(super) struct BeginRenderingState {
@@ -124,6 +145,33 @@ pub(super) struct QueryState {
     pub(super) in_subpass: bool,
 }
 
+// The state of an active conditional rendering block.
+pub(super) struct ConditionalRenderingState {
+    pub(super) in_subpass: bool,
+}
+
+// The state of an active video coding scope.
+pub(super) struct VideoCodingState {
+    pub(super) video_session: Arc<VideoSession>,
+    // The image views and array layers of the picture resources that were bound to DPB slots when
+    // the scope was begun.
+    pub(super) bound_picture_resources: SmallVec<[(ash::vk::ImageView, u32); 8]>,
+}
+
+// The state of active transform feedback.
+pub(super) struct TransformFeedbackState {
+    pub(super) first_counter_buffer: u32,
+    pub(super) counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+}
+
+// The state of an event that was set in this command buffer.
+pub(super) struct EventState {
+    // The `src_stages`, `src_access`, `dst_stages` and `dst_access` of each memory barrier that
+    // the event was set with.
+    pub(super) memory_barriers:
+        SmallVec<[(PipelineStages, AccessFlags, PipelineStages, AccessFlags); 2]>,
+}
+
 impl<A> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, A>
 where
     A: CommandBufferAllocator,
@@ -235,6 +283,15 @@ where
                             render_pass: BeginRenderPassState {
                                 subpass: info.subpass.clone(),
                                 framebuffer: info.framebuffer.clone(),
+                                attachments: info
+                                    .framebuffer
+                                    .as_ref()
+                                    .filter(|framebuffer| {
+                                        !framebuffer
+                                            .flags()
+                                            .intersects(FramebufferCreateFlags::IMAGELESS)
+                                    })
+                                    .map(|framebuffer| framebuffer.attachments().to_vec()),
                             }
                             .into(),
                             view_mask: info.subpass.subpass_desc().view_mask,
@@ -273,6 +330,11 @@ where
             queue_family_index,
             render_pass_state,
             query_state: HashMap::default(),
+            conditional_rendering_state: None,
+            transform_feedback_state: None,
+            debug_label_depth: 0,
+            event_state: HashMap::default(),
+            video_coding_state: None,
             inheritance_info,
             usage,
             _data: PhantomData,
@@ -619,6 +681,16 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        // VUID-vkEndCommandBuffer-None-01978
+        if self.conditional_rendering_state.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkEndCommandBuffer-None-06991
+        if self.video_coding_state.is_some() {
+            return Err(BuildError::VideoCodingActive);
+        }
+
         Ok(PrimaryAutoCommandBuffer {
             inner: self.inner.build()?,
             _alloc: self.builder_alloc.into_alloc(),
@@ -639,6 +711,16 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        // VUID-vkEndCommandBuffer-None-01978
+        if self.conditional_rendering_state.is_some() {
+            return Err(BuildError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkEndCommandBuffer-commandBuffer-01815
+        if self.debug_label_depth != 0 {
+            return Err(BuildError::DebugLabelActive);
+        }
+
         let submit_state = match self.usage {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -669,6 +751,15 @@ pub enum BuildError {
 
     /// A query is still active on the command buffer.
     QueryActive,
+
+    /// A debug label region begun in this secondary command buffer has not been ended.
+    DebugLabelActive,
+
+    /// Conditional rendering is still active on the command buffer.
+    ConditionalRenderingActive,
+
+    /// A video coding scope is still active on the command buffer.
+    VideoCodingActive,
 }
 
 impl Error for BuildError {
@@ -688,6 +779,22 @@ impl Display for BuildError {
                 write!(f, "a render pass is still active on the command buffer")
             }
             Self::QueryActive => write!(f, "a query is still active on the command buffer"),
+            Self::DebugLabelActive => write!(
+                f,
+                "a debug label region begun in this secondary command buffer has not been ended",
+            ),
+            Self::ConditionalRenderingActive => {
+                write!(
+                    f,
+                    "conditional rendering is still active on the command buffer"
+                )
+            }
+            Self::VideoCodingActive => {
+                write!(
+                    f,
+                    "a video coding scope is still active on the command buffer"
+                )
+            }
         }
     }
 }
@@ -751,6 +858,10 @@ where
         self.usage
     }
 
+    fn protected(&self) -> bool {
+        self._alloc.inner().protected()
+    }
+
     fn state(&self) -> MutexGuard<'_, CommandBufferState> {
         self.state.lock()
     }
@@ -866,11 +977,11 @@ mod tests {
         buffer::{BufferUsage, CpuAccessibleBuffer},
         command_buffer::{
             synced::SyncCommandBufferBuilderError, BufferCopy, CopyBufferInfoTyped, CopyError,
-            ExecuteCommandsError,
+            EventCommandError, ExecuteCommandsError,
         },
         device::{DeviceCreateInfo, QueueCreateInfo},
         memory::allocator::StandardMemoryAllocator,
-        sync::GpuFuture,
+        sync::{event::Event, DependencyInfo, GpuFuture, MemoryBarrier},
     };
 
     #[test]
@@ -1107,4 +1218,57 @@ mod tests {
             })
         ));
     }
+
+    #[test]
+    fn event_split_barrier() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        let event = Arc::new(Event::from_pool(device).unwrap());
+        let dependency_info = || DependencyInfo {
+            memory_barriers: [MemoryBarrier {
+                src_stages: PipelineStages::ALL_TRANSFER,
+                src_access: AccessFlags::TRANSFER_WRITE,
+                dst_stages: PipelineStages::COMPUTE_SHADER,
+                dst_access: AccessFlags::SHADER_READ,
+                ..Default::default()
+            }]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        unsafe {
+            assert!(matches!(
+                cbb.wait_events([(event.clone(), dependency_info())]),
+                Err(EventCommandError::EventNotSet { event_index: 0 })
+            ));
+
+            cbb.set_event(event.clone(), dependency_info()).unwrap();
+
+            assert!(matches!(
+                cbb.wait_events([(event.clone(), DependencyInfo::default())]),
+                Err(EventCommandError::EventDependencyInfoMismatch { event_index: 0 })
+            ));
+
+            cbb.wait_events([(event.clone(), dependency_info())])
+                .unwrap();
+            cbb.reset_event(event.clone(), PipelineStages::COMPUTE_SHADER)
+                .unwrap();
+
+            assert!(matches!(
+                cbb.wait_events([(event, dependency_info())]),
+                Err(EventCommandError::EventNotSet { event_index: 0 })
+            ));
+        }
+
+        cbb.build().unwrap();
+    }
 }
@@ -0,0 +1,1177 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    acceleration_structure::{
+        vertex_format_component_size, AccelerationStructure,
+        AccelerationStructureBuildGeometryInfo, AccelerationStructureBuildRangeInfo,
+        AccelerationStructureBuildType, AccelerationStructureError,
+        AccelerationStructureGeometries, AccelerationStructureGeometryAabbsData,
+        AccelerationStructureGeometryInstancesData, AccelerationStructureGeometryTrianglesData,
+        AccelerationStructureType, BuildAccelerationStructureFlags, BuildAccelerationStructureMode,
+        CopyAccelerationStructureInfo, CopyAccelerationStructureMode,
+    },
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    query::{QueryPool, QueryType},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize, RequirementNotMet, RequiresOneOf, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// # Commands to build, copy and query acceleration structures.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Builds or updates an acceleration structure.
+    ///
+    /// `build_range_infos` must contain one element for each geometry in `info.geometries`.
+    ///
+    /// # Safety
+    ///
+    /// - If `info.mode` is [`BuildAccelerationStructureMode::Update`], then the source
+    ///   acceleration structure must have been previously built with the
+    ///   [`ALLOW_UPDATE`] flag, and with the same geometries and primitive counts as are given
+    ///   now.
+    /// - The geometry data buffers must contain valid data in the ranges given by
+    ///   `build_range_infos`. In particular, all indices must be no greater than `max_vertex`.
+    /// - For instance geometry, every [`AccelerationStructureInstance`] must refer to a valid
+    ///   bottom-level acceleration structure that has been built before the command is
+    ///   executed, and that is kept alive while the top-level acceleration structure is used.
+    ///
+    /// [`ALLOW_UPDATE`]: crate::acceleration_structure::BuildAccelerationStructureFlags::ALLOW_UPDATE
+    /// [`AccelerationStructureInstance`]: crate::acceleration_structure::AccelerationStructureInstance
+    #[inline]
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: AccelerationStructureBuildGeometryInfo,
+        build_range_infos: SmallVec<[AccelerationStructureBuildRangeInfo; 8]>,
+    ) -> Result<&mut Self, AccelerationStructureCommandError> {
+        self.validate_build_acceleration_structure(&info, &build_range_infos)?;
+
+        self.inner
+            .build_acceleration_structure(info, build_range_infos)?;
+
+        Ok(self)
+    }
+
+    fn validate_build_acceleration_structure(
+        &self,
+        info: &AccelerationStructureBuildGeometryInfo,
+        build_range_infos: &[AccelerationStructureBuildRangeInfo],
+    ) -> Result<(), AccelerationStructureCommandError> {
+        let device = self.device();
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-accelerationStructure-08923
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureCommandError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::build_acceleration_structure`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(AccelerationStructureCommandError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(AccelerationStructureCommandError::NotSupportedByQueueFamily);
+        }
+
+        let primitive_counts: SmallVec<[_; 8]> = build_range_infos
+            .iter()
+            .map(|build_range_info| build_range_info.primitive_count)
+            .collect();
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-parameter
+        // VUID-vkCmdBuildAccelerationStructuresKHR-ppBuildRangeInfos-03676
+        info.validate(device, &primitive_counts)?;
+
+        let &AccelerationStructureBuildGeometryInfo {
+            flags,
+            ref mode,
+            ref dst_acceleration_structure,
+            ref geometries,
+            ref scratch_data,
+            _ne: _,
+        } = info;
+
+        // VUID-VkAccelerationStructureBuildGeometryInfoKHR-mode-04628
+        let dst_acceleration_structure = dst_acceleration_structure
+            .as_ref()
+            .ok_or(AccelerationStructureCommandError::DstAccelerationStructureMissing)?;
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-commonparent
+        assert_eq!(device, dst_acceleration_structure.device());
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03700
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03699
+        if !matches!(
+            dst_acceleration_structure.ty(),
+            AccelerationStructureType::Generic
+        ) && dst_acceleration_structure.ty() != info.ty()
+        {
+            return Err(
+                AccelerationStructureCommandError::AccelerationStructureTypeMismatch {
+                    provided: dst_acceleration_structure.ty(),
+                    required: info.ty(),
+                },
+            );
+        }
+
+        if let BuildAccelerationStructureMode::Update(src_acceleration_structure) = mode {
+            // VUID-vkCmdBuildAccelerationStructuresKHR-commonparent
+            assert_eq!(device, src_acceleration_structure.device());
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03667
+            if !flags.intersects(BuildAccelerationStructureFlags::ALLOW_UPDATE) {
+                return Err(AccelerationStructureCommandError::UpdateNotAllowed);
+            }
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03758
+            if !matches!(
+                src_acceleration_structure.ty(),
+                AccelerationStructureType::Generic
+            ) && src_acceleration_structure.ty() != info.ty()
+            {
+                return Err(
+                    AccelerationStructureCommandError::AccelerationStructureTypeMismatch {
+                        provided: src_acceleration_structure.ty(),
+                        required: info.ty(),
+                    },
+                );
+            }
+        }
+
+        let build_sizes_info = unsafe {
+            device.acceleration_structure_build_sizes_unchecked(
+                AccelerationStructureBuildType::Device,
+                info,
+                &primitive_counts,
+            )
+        };
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03675
+        if dst_acceleration_structure.size() < build_sizes_info.acceleration_structure_size {
+            return Err(
+                AccelerationStructureCommandError::AccelerationStructureTooSmall {
+                    size: dst_acceleration_structure.size(),
+                    required_size: build_sizes_info.acceleration_structure_size,
+                },
+            );
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03802
+        let scratch_data = scratch_data
+            .as_ref()
+            .ok_or(AccelerationStructureCommandError::ScratchDataMissing)?;
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-commonparent
+        assert_eq!(device, scratch_data.device());
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03674
+        if !scratch_data
+            .usage()
+            .contains(BufferUsage::STORAGE_BUFFER | BufferUsage::SHADER_DEVICE_ADDRESS)
+        {
+            return Err(AccelerationStructureCommandError::MissingUsage {
+                resource: ResourceInCommand::ScratchData,
+                usage: "storage_buffer and shader_device_address",
+            });
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03671
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03672
+        let required_scratch_size = match mode {
+            BuildAccelerationStructureMode::Build => build_sizes_info.build_scratch_size,
+            BuildAccelerationStructureMode::Update(_) => build_sizes_info.update_scratch_size,
+        };
+
+        if scratch_data.size() < required_scratch_size {
+            return Err(AccelerationStructureCommandError::ScratchDataTooSmall {
+                size: scratch_data.size(),
+                required_size: required_scratch_size,
+            });
+        }
+
+        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03710
+        let required_alignment = device
+            .physical_device()
+            .properties()
+            .min_acceleration_structure_scratch_offset_alignment
+            .unwrap_or(1) as DeviceSize;
+        let offset = scratch_data.inner().offset;
+
+        if offset % required_alignment != 0 {
+            return Err(AccelerationStructureCommandError::DataNotAligned {
+                resource: ResourceInCommand::ScratchData,
+                offset,
+                required_alignment,
+            });
+        }
+
+        let check_geometry_data = |index: u32,
+                                   data: &Option<Arc<dyn BufferAccess>>,
+                                   offset: DeviceSize,
+                                   required_alignment: DeviceSize|
+         -> Result<(), AccelerationStructureCommandError> {
+            let resource = ResourceInCommand::GeometryData { index };
+
+            let data = data
+                .as_ref()
+                .ok_or(AccelerationStructureCommandError::DataMissing { resource })?;
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-commonparent
+            assert_eq!(device, data.device());
+
+            // VUID-vkCmdBuildAccelerationStructuresKHR-geometry-03673
+            if !data.usage().contains(
+                BufferUsage::ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY
+                    | BufferUsage::SHADER_DEVICE_ADDRESS,
+            ) {
+                return Err(AccelerationStructureCommandError::MissingUsage {
+                    resource,
+                    usage: "acceleration_structure_build_input_read_only and \
+                        shader_device_address",
+                });
+            }
+
+            let offset = data.inner().offset + offset;
+
+            if offset % required_alignment != 0 {
+                return Err(AccelerationStructureCommandError::DataNotAligned {
+                    resource,
+                    offset,
+                    required_alignment,
+                });
+            }
+
+            Ok(())
+        };
+
+        match geometries {
+            AccelerationStructureGeometries::Triangles(geometries) => {
+                for (geometry_index, (triangles_data, build_range_info)) in
+                    geometries.iter().zip(build_range_infos).enumerate()
+                {
+                    let &AccelerationStructureGeometryTrianglesData {
+                        flags: _,
+                        vertex_format,
+                        ref vertex_data,
+                        vertex_stride: _,
+                        max_vertex: _,
+                        ref index_data,
+                        index_type,
+                        ref transform_data,
+                        _ne: _,
+                    } = triangles_data;
+
+                    let geometry_index = geometry_index as u32;
+
+                    if index_data.is_some() {
+                        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03711
+                        check_geometry_data(
+                            geometry_index,
+                            vertex_data,
+                            0,
+                            vertex_format_component_size(vertex_format),
+                        )?;
+
+                        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03712
+                        // VUID-VkAccelerationStructureBuildRangeInfoKHR-primitiveOffset-03656
+                        check_geometry_data(
+                            geometry_index,
+                            index_data,
+                            build_range_info.primitive_offset as DeviceSize,
+                            index_type.size(),
+                        )?;
+                    } else {
+                        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03711
+                        // VUID-VkAccelerationStructureBuildRangeInfoKHR-primitiveOffset-03657
+                        check_geometry_data(
+                            geometry_index,
+                            vertex_data,
+                            build_range_info.primitive_offset as DeviceSize,
+                            vertex_format_component_size(vertex_format),
+                        )?;
+                    }
+
+                    if transform_data.is_some() {
+                        // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03810
+                        // VUID-VkAccelerationStructureBuildRangeInfoKHR-transformOffset-03658
+                        check_geometry_data(
+                            geometry_index,
+                            transform_data,
+                            build_range_info.transform_offset as DeviceSize,
+                            16,
+                        )?;
+                    }
+                }
+            }
+            AccelerationStructureGeometries::Aabbs(geometries) => {
+                for (geometry_index, (aabbs_data, build_range_info)) in
+                    geometries.iter().zip(build_range_infos).enumerate()
+                {
+                    let AccelerationStructureGeometryAabbsData { data, .. } = aabbs_data;
+
+                    // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03714
+                    // VUID-VkAccelerationStructureBuildRangeInfoKHR-primitiveOffset-03659
+                    check_geometry_data(
+                        geometry_index as u32,
+                        data,
+                        build_range_info.primitive_offset as DeviceSize,
+                        8,
+                    )?;
+                }
+            }
+            AccelerationStructureGeometries::Instances(instances_data) => {
+                let AccelerationStructureGeometryInstancesData { data, .. } = instances_data;
+
+                // VUID-vkCmdBuildAccelerationStructuresKHR-pInfos-03715
+                // VUID-VkAccelerationStructureBuildRangeInfoKHR-primitiveOffset-03660
+                check_geometry_data(
+                    0,
+                    data,
+                    build_range_infos[0].primitive_offset as DeviceSize,
+                    16,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Copies an acceleration structure into another, optionally compacting it.
+    ///
+    /// # Safety
+    ///
+    /// - `copy_info.src` must have been built before the command is executed.
+    /// - If `copy_info.mode` is [`CopyAccelerationStructureMode::Compact`], then `copy_info.src`
+    ///   must have been built with the [`ALLOW_COMPACTION`] flag, and `copy_info.dst` must be at
+    ///   least as large as the compacted size of `copy_info.src`.
+    ///
+    /// [`ALLOW_COMPACTION`]: BuildAccelerationStructureFlags::ALLOW_COMPACTION
+    #[inline]
+    pub unsafe fn copy_acceleration_structure(
+        &mut self,
+        copy_info: CopyAccelerationStructureInfo,
+    ) -> Result<&mut Self, AccelerationStructureCommandError> {
+        self.validate_copy_acceleration_structure(&copy_info)?;
+
+        self.inner.copy_acceleration_structure(copy_info)?;
+
+        Ok(self)
+    }
+
+    fn validate_copy_acceleration_structure(
+        &self,
+        copy_info: &CopyAccelerationStructureInfo,
+    ) -> Result<(), AccelerationStructureCommandError> {
+        let device = self.device();
+
+        // VUID-vkCmdCopyAccelerationStructureKHR-accelerationStructure-08925
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureCommandError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::copy_acceleration_structure`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdCopyAccelerationStructureKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(AccelerationStructureCommandError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdCopyAccelerationStructureKHR-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(AccelerationStructureCommandError::NotSupportedByQueueFamily);
+        }
+
+        let &CopyAccelerationStructureInfo {
+            ref src,
+            ref dst,
+            mode,
+            _ne: _,
+        } = copy_info;
+
+        // VUID-VkCopyAccelerationStructureInfoKHR-mode-parameter
+        mode.validate_device(device)?;
+
+        // VUID-vkCmdCopyAccelerationStructureKHR-commonparent
+        assert_eq!(device, src.device());
+        assert_eq!(device, dst.device());
+
+        // VUID-VkCopyAccelerationStructureInfoKHR-src-04963
+        if !matches!(src.ty(), AccelerationStructureType::Generic)
+            && !matches!(dst.ty(), AccelerationStructureType::Generic)
+            && src.ty() != dst.ty()
+        {
+            return Err(
+                AccelerationStructureCommandError::AccelerationStructureTypeMismatch {
+                    provided: dst.ty(),
+                    required: src.ty(),
+                },
+            );
+        }
+
+        // VUID-VkCopyAccelerationStructureInfoKHR-src-03411
+        if matches!(mode, CopyAccelerationStructureMode::Clone) && dst.size() < src.size() {
+            return Err(
+                AccelerationStructureCommandError::AccelerationStructureTooSmall {
+                    size: dst.size(),
+                    required_size: src.size(),
+                },
+            );
+        }
+
+        // VUID-VkCopyAccelerationStructureInfoKHR-dst-07791
+        let src_inner = src.buffer().inner();
+        let dst_inner = dst.buffer().inner();
+
+        if src_inner.buffer == dst_inner.buffer {
+            let src_range = src_inner.offset..src_inner.offset + src.size();
+            let dst_range = dst_inner.offset..dst_inner.offset + dst.size();
+
+            if src_range.start < dst_range.end && dst_range.start < src_range.end {
+                return Err(AccelerationStructureCommandError::OverlappingAccelerationStructures);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes properties of acceleration structures into queries of a query pool, starting at
+    /// `first_query`.
+    ///
+    /// The type of `query_pool` determines which property is written. Currently, only
+    /// [`QueryType::AccelerationStructureCompactedSize`] is supported, which is used to
+    /// find the size of an acceleration structure after compaction.
+    ///
+    /// # Safety
+    ///
+    /// - The queries must be unavailable, ensured by calling [`reset_query_pool`].
+    /// - The acceleration structures must have been built before the command is executed.
+    /// - For [`QueryType::AccelerationStructureCompactedSize`] queries, the acceleration
+    ///   structures must have been built with the [`ALLOW_COMPACTION`] flag.
+    ///
+    /// [`reset_query_pool`]: Self::reset_query_pool
+    /// [`ALLOW_COMPACTION`]: BuildAccelerationStructureFlags::ALLOW_COMPACTION
+    #[inline]
+    pub unsafe fn write_acceleration_structures_properties(
+        &mut self,
+        acceleration_structures: SmallVec<[Arc<AccelerationStructure>; 4]>,
+        query_pool: Arc<QueryPool>,
+        first_query: u32,
+    ) -> Result<&mut Self, AccelerationStructureCommandError> {
+        self.validate_write_acceleration_structures_properties(
+            &acceleration_structures,
+            &query_pool,
+            first_query,
+        )?;
+
+        self.inner.write_acceleration_structures_properties(
+            acceleration_structures,
+            query_pool,
+            first_query,
+        )?;
+
+        Ok(self)
+    }
+
+    fn validate_write_acceleration_structures_properties(
+        &self,
+        acceleration_structures: &[Arc<AccelerationStructure>],
+        query_pool: &QueryPool,
+        first_query: u32,
+    ) -> Result<(), AccelerationStructureCommandError> {
+        let device = self.device();
+
+        // VUID-vkCmdWriteAccelerationStructuresPropertiesKHR-accelerationStructure-08924
+        if !device.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureCommandError::RequirementNotMet {
+                required_for:
+                    "`AutoCommandBufferBuilder::write_acceleration_structures_properties`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdWriteAccelerationStructuresPropertiesKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(AccelerationStructureCommandError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdWriteAccelerationStructuresPropertiesKHR-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(AccelerationStructureCommandError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdWriteAccelerationStructuresPropertiesKHR-commonparent
+        assert_eq!(device, query_pool.device());
+
+        // VUID-vkCmdWriteAccelerationStructuresPropertiesKHR-queryType-06742
+        if !matches!(
+            query_pool.query_type(),
+            QueryType::AccelerationStructureCompactedSize
+        ) {
+            return Err(AccelerationStructureCommandError::QueryTypeNotSupported {
+                query_type: query_pool.query_type(),
+            });
+        }
+
+        // VUID-vkCmdWriteAccelerationStructuresPropertiesKHR-query-04880
+        if first_query as u64 + acceleration_structures.len() as u64
+            > query_pool.query_count() as u64
+        {
+            return Err(AccelerationStructureCommandError::QueryOutOfRange);
+        }
+
+        for acceleration_structure in acceleration_structures {
+            // VUID-vkCmdWriteAccelerationStructuresPropertiesKHR-commonparent
+            assert_eq!(device, acceleration_structure.device());
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder.
+    #[inline]
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: AccelerationStructureBuildGeometryInfo,
+        build_range_infos: SmallVec<[AccelerationStructureBuildRangeInfo; 8]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            info: AccelerationStructureBuildGeometryInfo,
+            build_range_infos: SmallVec<[AccelerationStructureBuildRangeInfo; 8]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "build_acceleration_structure"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.build_acceleration_structure(&self.info, &self.build_range_infos);
+            }
+        }
+
+        let AccelerationStructureBuildGeometryInfo {
+            mode,
+            dst_acceleration_structure,
+            geometries,
+            scratch_data,
+            ..
+        } = &info;
+
+        let command_index = self.commands.len();
+        let command_name = "build_acceleration_structure";
+        let mut resources: SmallVec<[_; 8]> = SmallVec::new();
+
+        let mut add_buffer = |resource_in_command: ResourceInCommand,
+                              buffer: &Arc<dyn BufferAccess>,
+                              access: AccessFlags,
+                              exclusive: bool| {
+            resources.push((
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command,
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: buffer.clone(),
+                    range: 0..buffer.size(),
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+                        access,
+                        exclusive,
+                    },
+                },
+            ));
+        };
+
+        if let Some(dst_acceleration_structure) = dst_acceleration_structure {
+            match mode {
+                BuildAccelerationStructureMode::Update(src_acceleration_structure)
+                    if src_acceleration_structure == dst_acceleration_structure =>
+                {
+                    add_buffer(
+                        ResourceInCommand::Destination,
+                        dst_acceleration_structure.buffer(),
+                        AccessFlags::ACCELERATION_STRUCTURE_READ
+                            | AccessFlags::ACCELERATION_STRUCTURE_WRITE,
+                        true,
+                    );
+                }
+                BuildAccelerationStructureMode::Update(src_acceleration_structure) => {
+                    add_buffer(
+                        ResourceInCommand::Source,
+                        src_acceleration_structure.buffer(),
+                        AccessFlags::ACCELERATION_STRUCTURE_READ,
+                        false,
+                    );
+                    add_buffer(
+                        ResourceInCommand::Destination,
+                        dst_acceleration_structure.buffer(),
+                        AccessFlags::ACCELERATION_STRUCTURE_WRITE,
+                        true,
+                    );
+                }
+                BuildAccelerationStructureMode::Build => {
+                    add_buffer(
+                        ResourceInCommand::Destination,
+                        dst_acceleration_structure.buffer(),
+                        AccessFlags::ACCELERATION_STRUCTURE_WRITE,
+                        true,
+                    );
+                }
+            }
+        }
+
+        if let Some(scratch_data) = scratch_data {
+            add_buffer(
+                ResourceInCommand::ScratchData,
+                scratch_data,
+                AccessFlags::ACCELERATION_STRUCTURE_READ
+                    | AccessFlags::ACCELERATION_STRUCTURE_WRITE,
+                true,
+            );
+        }
+
+        match geometries {
+            AccelerationStructureGeometries::Triangles(geometries) => {
+                for (index, triangles_data) in geometries.iter().enumerate() {
+                    let AccelerationStructureGeometryTrianglesData {
+                        vertex_data,
+                        index_data,
+                        transform_data,
+                        ..
+                    } = triangles_data;
+
+                    for data in [vertex_data, index_data, transform_data]
+                        .into_iter()
+                        .flatten()
+                    {
+                        add_buffer(
+                            ResourceInCommand::GeometryData {
+                                index: index as u32,
+                            },
+                            data,
+                            AccessFlags::SHADER_READ,
+                            false,
+                        );
+                    }
+                }
+            }
+            AccelerationStructureGeometries::Aabbs(geometries) => {
+                for (index, aabbs_data) in geometries.iter().enumerate() {
+                    if let Some(data) = &aabbs_data.data {
+                        add_buffer(
+                            ResourceInCommand::GeometryData {
+                                index: index as u32,
+                            },
+                            data,
+                            AccessFlags::SHADER_READ,
+                            false,
+                        );
+                    }
+                }
+            }
+            AccelerationStructureGeometries::Instances(instances_data) => {
+                if let Some(data) = &instances_data.data {
+                    add_buffer(
+                        ResourceInCommand::GeometryData { index: 0 },
+                        data,
+                        AccessFlags::SHADER_READ,
+                        false,
+                    );
+                }
+            }
+        }
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            info,
+            build_range_infos,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdCopyAccelerationStructureKHR` on the builder.
+    #[inline]
+    pub unsafe fn copy_acceleration_structure(
+        &mut self,
+        copy_info: CopyAccelerationStructureInfo,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            copy_info: CopyAccelerationStructureInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "copy_acceleration_structure"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.copy_acceleration_structure(&self.copy_info);
+            }
+        }
+
+        let CopyAccelerationStructureInfo { src, dst, .. } = &copy_info;
+
+        let command_index = self.commands.len();
+        let command_name = "copy_acceleration_structure";
+        let resources = [
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::Source,
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: src.buffer().clone(),
+                    range: 0..src.size(),
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+                        access: AccessFlags::ACCELERATION_STRUCTURE_READ,
+                        exclusive: false,
+                    },
+                },
+            ),
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::Destination,
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: dst.buffer().clone(),
+                    range: 0..dst.size(),
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+                        access: AccessFlags::ACCELERATION_STRUCTURE_WRITE,
+                        exclusive: true,
+                    },
+                },
+            ),
+        ];
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd { copy_info }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdWriteAccelerationStructuresPropertiesKHR` on the builder.
+    #[inline]
+    pub unsafe fn write_acceleration_structures_properties(
+        &mut self,
+        acceleration_structures: SmallVec<[Arc<AccelerationStructure>; 4]>,
+        query_pool: Arc<QueryPool>,
+        first_query: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            acceleration_structures: SmallVec<[Arc<AccelerationStructure>; 4]>,
+            query_pool: Arc<QueryPool>,
+            first_query: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "write_acceleration_structures_properties"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.write_acceleration_structures_properties(
+                    &self.acceleration_structures,
+                    &self.query_pool,
+                    self.first_query,
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "write_acceleration_structures_properties";
+        let resources: SmallVec<[_; 4]> = acceleration_structures
+            .iter()
+            .enumerate()
+            .map(|(index, acceleration_structure)| {
+                (
+                    ResourceUseRef {
+                        command_index,
+                        command_name,
+                        resource_in_command: ResourceInCommand::AccelerationStructure {
+                            index: index as u32,
+                        },
+                        secondary_use_ref: None,
+                    },
+                    Resource::Buffer {
+                        buffer: acceleration_structure.buffer().clone(),
+                        range: 0..acceleration_structure.size(),
+                        memory: PipelineMemoryAccess {
+                            stages: PipelineStages::ACCELERATION_STRUCTURE_BUILD,
+                            access: AccessFlags::ACCELERATION_STRUCTURE_READ,
+                            exclusive: false,
+                        },
+                    },
+                )
+            })
+            .collect();
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            acceleration_structures,
+            query_pool,
+            first_query,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBuildAccelerationStructuresKHR` on the builder.
+    #[inline]
+    pub unsafe fn build_acceleration_structure(
+        &mut self,
+        info: &AccelerationStructureBuildGeometryInfo,
+        build_range_infos: &[AccelerationStructureBuildRangeInfo],
+    ) {
+        let (mut info_vk, geometries_vk) = info.to_vulkan();
+        info_vk.p_geometries = geometries_vk.as_ptr();
+
+        let build_range_infos_vk: SmallVec<[_; 8]> = build_range_infos
+            .iter()
+            .copied()
+            .map(ash::vk::AccelerationStructureBuildRangeInfoKHR::from)
+            .collect();
+
+        let fns = self.device.fns();
+        (fns.khr_acceleration_structure
+            .cmd_build_acceleration_structures_khr)(
+            self.handle,
+            1,
+            &info_vk,
+            &build_range_infos_vk.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdCopyAccelerationStructureKHR` on the builder.
+    #[inline]
+    pub unsafe fn copy_acceleration_structure(
+        &mut self,
+        copy_info: &CopyAccelerationStructureInfo,
+    ) {
+        let copy_info_vk = copy_info.to_vulkan();
+
+        let fns = self.device.fns();
+        (fns.khr_acceleration_structure
+            .cmd_copy_acceleration_structure_khr)(self.handle, &copy_info_vk);
+    }
+
+    /// Calls `vkCmdWriteAccelerationStructuresPropertiesKHR` on the builder.
+    #[inline]
+    pub unsafe fn write_acceleration_structures_properties(
+        &mut self,
+        acceleration_structures: &[Arc<AccelerationStructure>],
+        query_pool: &QueryPool,
+        first_query: u32,
+    ) {
+        if acceleration_structures.is_empty() {
+            return;
+        }
+
+        let acceleration_structures_vk: SmallVec<[_; 4]> = acceleration_structures
+            .iter()
+            .map(|acceleration_structure| acceleration_structure.handle())
+            .collect();
+
+        let fns = self.device.fns();
+        (fns.khr_acceleration_structure
+            .cmd_write_acceleration_structures_properties_khr)(
+            self.handle,
+            acceleration_structures_vk.len() as u32,
+            acceleration_structures_vk.as_ptr(),
+            query_pool.query_type().into(),
+            query_pool.handle(),
+            first_query,
+        );
+    }
+}
+
+/// Error that can happen when recording an acceleration structure command.
+#[derive(Clone, Debug)]
+pub enum AccelerationStructureCommandError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The build info of the acceleration structure is not valid.
+    AccelerationStructureError(AccelerationStructureError),
+
+    /// The destination acceleration structure is smaller than the size required for the build.
+    AccelerationStructureTooSmall {
+        size: DeviceSize,
+        required_size: DeviceSize,
+    },
+
+    /// The type of an acceleration structure does not match the type of the geometries.
+    AccelerationStructureTypeMismatch {
+        provided: AccelerationStructureType,
+        required: AccelerationStructureType,
+    },
+
+    /// A buffer that is required for the operation was not provided.
+    DataMissing {
+        resource: ResourceInCommand,
+    },
+
+    /// The device address of a buffer, plus any offset, is not a multiple of the required
+    /// alignment.
+    DataNotAligned {
+        resource: ResourceInCommand,
+        offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// No destination acceleration structure was provided.
+    DstAccelerationStructureMissing,
+
+    /// Operation forbidden inside of a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// A buffer is missing a usage that is required for the operation.
+    MissingUsage {
+        resource: ResourceInCommand,
+        usage: &'static str,
+    },
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The source and destination acceleration structures overlap in memory.
+    OverlappingAccelerationStructures,
+
+    /// The range of queries to write is outside the query pool.
+    QueryOutOfRange,
+
+    /// The type of the query pool is not supported by the operation.
+    QueryTypeNotSupported {
+        query_type: QueryType,
+    },
+
+    /// No scratch buffer was provided.
+    ScratchDataMissing,
+
+    /// The scratch buffer is smaller than the size required for the build.
+    ScratchDataTooSmall {
+        size: DeviceSize,
+        required_size: DeviceSize,
+    },
+
+    /// The build mode is [`BuildAccelerationStructureMode::Update`], but the build flags do not
+    /// contain [`BuildAccelerationStructureFlags::ALLOW_UPDATE`].
+    UpdateNotAllowed,
+}
+
+impl Error for AccelerationStructureCommandError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            Self::AccelerationStructureError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for AccelerationStructureCommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::AccelerationStructureError(_) => {
+                write!(
+                    f,
+                    "the build info of the acceleration structure is not valid"
+                )
+            }
+            Self::AccelerationStructureTooSmall {
+                size,
+                required_size,
+            } => write!(
+                f,
+                "the destination acceleration structure size ({}) is smaller than the size \
+                required for the build ({})",
+                size, required_size,
+            ),
+            Self::AccelerationStructureTypeMismatch { provided, required } => write!(
+                f,
+                "the type of an acceleration structure ({:?}) does not match the type of the \
+                geometries ({:?})",
+                provided, required,
+            ),
+            Self::DataMissing { resource } => write!(
+                f,
+                "the buffer for {:?} is required for the operation but was not provided",
+                resource,
+            ),
+            Self::DataNotAligned {
+                resource,
+                offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the offset ({}) of the buffer for {:?} is not a multiple of the required \
+                alignment ({})",
+                offset, resource, required_alignment,
+            ),
+            Self::DstAccelerationStructureMissing => {
+                write!(f, "no destination acceleration structure was provided")
+            }
+            Self::ForbiddenInsideRenderPass => {
+                write!(f, "operation forbidden inside of a render pass")
+            }
+            Self::MissingUsage { resource, usage } => write!(
+                f,
+                "the buffer for {:?} is missing the usage required for the operation: {}",
+                resource, usage,
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::OverlappingAccelerationStructures => write!(
+                f,
+                "the source and destination acceleration structures overlap in memory",
+            ),
+            Self::QueryOutOfRange => {
+                write!(f, "the range of queries to write is outside the query pool",)
+            }
+            Self::QueryTypeNotSupported { query_type } => write!(
+                f,
+                "the type of the query pool ({:?}) is not supported by the operation",
+                query_type,
+            ),
+            Self::ScratchDataMissing => write!(f, "no scratch buffer was provided"),
+            Self::ScratchDataTooSmall {
+                size,
+                required_size,
+            } => write!(
+                f,
+                "the scratch buffer size ({}) is smaller than the size required for the build \
+                ({})",
+                size, required_size,
+            ),
+            Self::UpdateNotAllowed => write!(
+                f,
+                "the build mode is `Update`, but the build flags do not contain `ALLOW_UPDATE`",
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for AccelerationStructureCommandError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+impl From<AccelerationStructureError> for AccelerationStructureCommandError {
+    fn from(err: AccelerationStructureError) -> Self {
+        match err {
+            AccelerationStructureError::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            },
+            err => Self::AccelerationStructureError(err),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for AccelerationStructureCommandError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
@@ -8,7 +8,9 @@
 // according to those terms.
 
 use crate::{
-    buffer::{BufferAccess, BufferContents, BufferUsage, TypedBufferAccess},
+    buffer::{
+        BufferAccess, BufferContents, BufferDeviceAddressError, BufferUsage, TypedBufferAccess,
+    },
     command_buffer::{
         allocator::CommandBufferAllocator,
         auto::RenderPassStateType,
@@ -28,7 +30,7 @@ use crate::{
             render_pass::PipelineRenderPassType,
             vertex_input::VertexBuffersCollection,
         },
-        ComputePipeline, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
+        ComputePipeline, GraphicsPipeline, PipelineBindPoint, PipelineLayout, RayTracingPipeline,
     },
     shader::ShaderStages,
     DeviceSize, RequirementNotMet, RequiresOneOf, VulkanObject,
@@ -38,8 +40,8 @@ use smallvec::SmallVec;
 use std::{
     error,
     fmt::{Display, Error as FmtError, Formatter},
-    mem::{size_of, size_of_val},
-    ptr, slice,
+    mem::size_of_val,
+    ptr,
     sync::Arc,
 };
 
@@ -50,6 +52,165 @@ impl<L, A> AutoCommandBufferBuilder<L, A>
 where
     A: CommandBufferAllocator,
 {
+    /// Binds descriptor buffers, for use with [`set_descriptor_buffer_offsets`].
+    ///
+    /// The buffers are bound to consecutive buffer indices starting at 0, replacing all
+    /// previously bound descriptor buffers.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics or compute
+    ///   operations.
+    /// - Panics if the [`descriptor_buffer`] feature is not enabled on the device.
+    /// - Panics if `self` and any element of `descriptor_buffers` do not belong to the same
+    ///   device.
+    /// - Panics if any element of `descriptor_buffers` does not have one of the
+    ///   [`BufferUsage::SAMPLER_DESCRIPTOR_BUFFER`] or [`BufferUsage::RESOURCE_DESCRIPTOR_BUFFER`]
+    ///   usages, or does not have the [`BufferUsage::SHADER_DEVICE_ADDRESS`] usage.
+    /// - Panics if the device address of any element of `descriptor_buffers` is not a multiple of
+    ///   the [`descriptor_buffer_offset_alignment`] device property.
+    /// - Panics if the number of descriptor buffers exceeds the
+    ///   [`max_descriptor_buffer_bindings`], [`max_sampler_descriptor_buffer_bindings`] or
+    ///   [`max_resource_descriptor_buffer_bindings`] device properties.
+    ///
+    /// [`set_descriptor_buffer_offsets`]: Self::set_descriptor_buffer_offsets
+    /// [`descriptor_buffer`]: crate::device::Features::descriptor_buffer
+    /// [`descriptor_buffer_offset_alignment`]: crate::device::Properties::descriptor_buffer_offset_alignment
+    /// [`max_descriptor_buffer_bindings`]: crate::device::Properties::max_descriptor_buffer_bindings
+    /// [`max_sampler_descriptor_buffer_bindings`]: crate::device::Properties::max_sampler_descriptor_buffer_bindings
+    /// [`max_resource_descriptor_buffer_bindings`]: crate::device::Properties::max_resource_descriptor_buffer_bindings
+    pub fn bind_descriptor_buffers(
+        &mut self,
+        descriptor_buffers: impl IntoIterator<Item = Arc<dyn BufferAccess>>,
+    ) -> &mut Self {
+        let descriptor_buffers: SmallVec<[_; 3]> = descriptor_buffers.into_iter().collect();
+        self.validate_bind_descriptor_buffers(&descriptor_buffers)
+            .unwrap();
+
+        unsafe {
+            self.inner.bind_descriptor_buffers(descriptor_buffers);
+        }
+
+        self
+    }
+
+    fn validate_bind_descriptor_buffers(
+        &self,
+        descriptor_buffers: &[Arc<dyn BufferAccess>],
+    ) -> Result<(), BindPushError> {
+        let device = self.device();
+
+        // VUID-vkCmdBindDescriptorBuffersEXT-None-08047
+        if !device.enabled_features().descriptor_buffer {
+            return Err(BindPushError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::bind_descriptor_buffers`",
+                requires_one_of: RequiresOneOf {
+                    features: &["descriptor_buffer"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdBindDescriptorBuffersEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(BindPushError::NotSupportedByQueueFamily);
+        }
+
+        let properties = device.physical_device().properties();
+        let required_alignment = properties.descriptor_buffer_offset_alignment.unwrap();
+        let mut sampler_count = 0;
+        let mut resource_count = 0;
+        let mut push_descriptors_count = 0;
+
+        for (index, buffer) in descriptor_buffers.iter().enumerate() {
+            let index = index as u32;
+
+            // VUID-vkCmdBindDescriptorBuffersEXT-commonparent
+            assert_eq!(device, buffer.device());
+
+            let usage = buffer.usage();
+
+            // VUID-VkDescriptorBufferBindingInfoEXT-usage-08122
+            // VUID-VkDescriptorBufferBindingInfoEXT-usage-08123
+            // VUID-VkDescriptorBufferBindingInfoEXT-usage-08124
+            if !usage.intersects(
+                BufferUsage::SAMPLER_DESCRIPTOR_BUFFER | BufferUsage::RESOURCE_DESCRIPTOR_BUFFER,
+            ) {
+                return Err(BindPushError::DescriptorBufferMissingUsage { index });
+            }
+
+            if usage.intersects(BufferUsage::SAMPLER_DESCRIPTOR_BUFFER) {
+                sampler_count += 1;
+            }
+
+            if usage.intersects(BufferUsage::RESOURCE_DESCRIPTOR_BUFFER) {
+                resource_count += 1;
+            }
+
+            if usage.intersects(BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER) {
+                push_descriptors_count += 1;
+            }
+
+            let address = buffer.raw_device_address().map_err(|err| match err {
+                BufferDeviceAddressError::RequirementNotMet {
+                    required_for,
+                    requires_one_of,
+                } => BindPushError::RequirementNotMet {
+                    required_for,
+                    requires_one_of,
+                },
+                BufferDeviceAddressError::BufferMissingUsage => {
+                    BindPushError::DescriptorBufferMissingUsage { index }
+                }
+            })?;
+
+            // VUID-VkDescriptorBufferBindingInfoEXT-address-08057
+            if address.get() % required_alignment != 0 {
+                return Err(BindPushError::DescriptorBufferAddressNotAligned {
+                    index,
+                    address: address.get(),
+                    required_alignment,
+                });
+            }
+        }
+
+        // VUID-vkCmdBindDescriptorBuffersEXT-bufferCount-08051
+        if descriptor_buffers.len() as u32 > properties.max_descriptor_buffer_bindings.unwrap() {
+            return Err(BindPushError::MaxDescriptorBufferBindingsExceeded {
+                provided: descriptor_buffers.len() as u32,
+                max_supported: properties.max_descriptor_buffer_bindings.unwrap(),
+            });
+        }
+
+        // VUID-vkCmdBindDescriptorBuffersEXT-maxSamplerDescriptorBufferBindings-08048
+        if sampler_count > properties.max_sampler_descriptor_buffer_bindings.unwrap() {
+            return Err(BindPushError::MaxSamplerDescriptorBufferBindingsExceeded {
+                provided: sampler_count,
+                max_supported: properties.max_sampler_descriptor_buffer_bindings.unwrap(),
+            });
+        }
+
+        // VUID-vkCmdBindDescriptorBuffersEXT-maxResourceDescriptorBufferBindings-08049
+        if resource_count > properties.max_resource_descriptor_buffer_bindings.unwrap() {
+            return Err(BindPushError::MaxResourceDescriptorBufferBindingsExceeded {
+                provided: resource_count,
+                max_supported: properties.max_resource_descriptor_buffer_bindings.unwrap(),
+            });
+        }
+
+        // VUID-vkCmdBindDescriptorBuffersEXT-None-08050
+        if push_descriptors_count > 1 {
+            return Err(BindPushError::DescriptorBufferPushDescriptorsMultiple);
+        }
+
+        Ok(())
+    }
+
     /// Binds descriptor sets for future dispatch or draw calls.
     ///
     /// # Panics
@@ -103,7 +264,7 @@ where
         // VUID-vkCmdBindDescriptorSets-commandBuffer-cmdpool
         // VUID-vkCmdBindDescriptorSets-pipelineBindPoint-00361
         match pipeline_bind_point {
-            PipelineBindPoint::Compute => {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::COMPUTE)
@@ -288,6 +449,11 @@ where
         // VUID-vkCmdBindPipeline-commonparent
         assert_eq!(self.device(), pipeline.device());
 
+        // VUID-vkCmdBindPipeline-pipeline-03382
+        if !pipeline.library_flags().is_empty() {
+            return Err(BindPushError::PipelineIsLibrary);
+        }
+
         if let Some(last_pipeline) = self
             .render_pass_state
             .as_ref()
@@ -333,6 +499,42 @@ where
         Ok(())
     }
 
+    /// Binds a ray tracing pipeline for future trace rays calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support compute operations.
+    /// - Panics if `self` and `pipeline` do not belong to the same device.
+    pub fn bind_pipeline_ray_tracing(&mut self, pipeline: Arc<RayTracingPipeline>) -> &mut Self {
+        self.validate_bind_pipeline_ray_tracing(&pipeline).unwrap();
+
+        unsafe {
+            self.inner.bind_pipeline_ray_tracing(pipeline);
+        }
+
+        self
+    }
+
+    fn validate_bind_pipeline_ray_tracing(
+        &self,
+        pipeline: &RayTracingPipeline,
+    ) -> Result<(), BindPushError> {
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdBindPipeline-pipelineBindPoint-02391
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(BindPushError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBindPipeline-commonparent
+        assert_eq!(self.device(), pipeline.device());
+
+        Ok(())
+    }
+
     /// Binds vertex buffers for future draw calls.
     ///
     /// # Panics
@@ -419,6 +621,8 @@ where
     ///
     /// - Panics if `offset` is not a multiple of 4.
     /// - Panics if the size of `push_constants` is not a multiple of 4.
+    /// - Panics if `offset` plus the size of `push_constants` exceeds the
+    ///   [`max_push_constants_size`](crate::device::Properties::max_push_constants_size) limit.
     /// - Panics if any of the bytes in `push_constants` do not fall within any of the pipeline
     ///   layout's push constant ranges.
     pub fn push_constants<Pc>(
@@ -430,18 +634,36 @@ where
     where
         Pc: BufferContents,
     {
-        let size = size_of::<Pc>() as u32;
+        self.push_constants_bytes(pipeline_layout, offset, push_constants.as_bytes())
+    }
+
+    /// Sets push constants for future dispatch or draw calls, from raw bytes.
+    ///
+    /// Only the bytes from `offset` to `offset + push_constants.len()` are updated, so this can be
+    /// used to update the push constant range of one shader stage, without touching the ranges of
+    /// the other stages. The ranges of an entry point can be found with
+    /// [`EntryPoint::push_constant_requirements`](crate::shader::EntryPoint::push_constant_requirements).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `offset` is not a multiple of 4.
+    /// - Panics if the length of `push_constants` is not a multiple of 4.
+    /// - Panics if `offset` plus the length of `push_constants` exceeds the
+    ///   [`max_push_constants_size`](crate::device::Properties::max_push_constants_size) limit.
+    /// - Panics if any of the bytes in `push_constants` do not fall within any of the pipeline
+    ///   layout's push constant ranges.
+    pub fn push_constants_bytes(
+        &mut self,
+        pipeline_layout: Arc<PipelineLayout>,
+        offset: u32,
+        push_constants: &[u8],
+    ) -> &mut Self {
+        let size = push_constants.len() as u32;
 
         if size == 0 {
             return self;
         }
 
-        // SAFETY: `&push_constants` is a valid pointer, and the size of the struct is `size`,
-        //         thus, getting a slice of the whole struct is safe if its not modified.
-        let push_constants = unsafe {
-            slice::from_raw_parts(&push_constants as *const Pc as *const u8, size as usize)
-        };
-
         self.validate_push_constants(&pipeline_layout, offset, push_constants)
             .unwrap();
 
@@ -489,14 +711,32 @@ where
         offset: u32,
         push_constants: &[u8],
     ) -> Result<(), BindPushError> {
+        // VUID-vkCmdPushConstants-offset-00370
         if offset % 4 != 0 {
             return Err(BindPushError::PushConstantsOffsetNotAligned);
         }
 
+        // VUID-vkCmdPushConstants-size-00371
         if push_constants.len() % 4 != 0 {
             return Err(BindPushError::PushConstantsSizeNotAligned);
         }
 
+        let max_push_constants_size = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_push_constants_size;
+
+        // VUID-vkCmdPushConstants-offset-00372
+        // VUID-vkCmdPushConstants-size-00373
+        if offset as u64 + push_constants.len() as u64 > max_push_constants_size as u64 {
+            return Err(BindPushError::MaxPushConstantsSizeExceeded {
+                offset,
+                size: push_constants.len() as u32,
+                max_supported: max_push_constants_size,
+            });
+        }
+
         let mut current_offset = offset;
         let mut remaining_size = push_constants.len() as u32;
         for range in pipeline_layout
@@ -592,7 +832,7 @@ where
         // VUID-vkCmdPushDescriptorSetKHR-commandBuffer-cmdpool
         // VUID-vkCmdPushDescriptorSetKHR-pipelineBindPoint-00363
         match pipeline_bind_point {
-            PipelineBindPoint::Compute => {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::COMPUTE)
@@ -614,7 +854,7 @@ where
         assert_eq!(self.device(), pipeline_layout.device());
 
         // VUID-vkCmdPushDescriptorSetKHR-set-00364
-        if set_num as usize > pipeline_layout.set_layouts().len() {
+        if set_num as usize >= pipeline_layout.set_layouts().len() {
             return Err(BindPushError::DescriptorSetOutOfRange {
                 set_num,
                 pipeline_layout_set_count: pipeline_layout.set_layouts().len() as u32,
@@ -634,9 +874,197 @@ where
 
         Ok(())
     }
+
+    /// Sets descriptor sets for future dispatch or draw calls, by pointing them to offsets within
+    /// the descriptor buffers that were bound with [`bind_descriptor_buffers`].
+    ///
+    /// Each element of `offsets` holds the index of a bound descriptor buffer and the offset in
+    /// bytes within that buffer, and is applied to consecutive set numbers starting at
+    /// `first_set`. The set layouts of `pipeline_layout` must have been created with
+    /// [`descriptor_buffer`](crate::descriptor_set::layout::DescriptorSetLayoutCreateInfo::descriptor_buffer)
+    /// enabled. Descriptors are written to the buffer with
+    /// [`DescriptorSetLayout::write_descriptor_buffer`].
+    ///
+    /// # Safety
+    ///
+    /// - The descriptor set data in the descriptor buffers must be valid for the set layouts of
+    ///   `pipeline_layout` when it is accessed by a dispatch or draw call.
+    /// - The resources referred to by the descriptors are not kept alive or synchronized by
+    ///   vulkano. They must remain alive, and must not be accessed in a conflicting way, for as
+    ///   long as they may be accessed through the descriptor buffers.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support `pipeline_bind_point`.
+    /// - Panics if the [`descriptor_buffer`] feature is not enabled on the device.
+    /// - Panics if the highest descriptor set slot being set is not less than the number of sets
+    ///   in `pipeline_layout`.
+    /// - Panics if the set layouts of `pipeline_layout` do not have `descriptor_buffer` enabled.
+    /// - Panics if a buffer index is not less than the number of bound descriptor buffers.
+    /// - Panics if an offset is not a multiple of the [`descriptor_buffer_offset_alignment`]
+    ///   device property, or if the descriptor set does not fit in the buffer at that offset.
+    ///
+    /// [`bind_descriptor_buffers`]: Self::bind_descriptor_buffers
+    /// [`DescriptorSetLayout::write_descriptor_buffer`]: crate::descriptor_set::layout::DescriptorSetLayout::write_descriptor_buffer
+    /// [`descriptor_buffer`]: crate::device::Features::descriptor_buffer
+    /// [`descriptor_buffer_offset_alignment`]: crate::device::Properties::descriptor_buffer_offset_alignment
+    pub unsafe fn set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: Arc<PipelineLayout>,
+        first_set: u32,
+        offsets: impl IntoIterator<Item = (u32, DeviceSize)>,
+    ) -> &mut Self {
+        let offsets: SmallVec<[_; 4]> = offsets.into_iter().collect();
+        self.validate_set_descriptor_buffer_offsets(
+            pipeline_bind_point,
+            &pipeline_layout,
+            first_set,
+            &offsets,
+        )
+        .unwrap();
+
+        self.inner.set_descriptor_buffer_offsets(
+            pipeline_bind_point,
+            pipeline_layout,
+            first_set,
+            offsets,
+        );
+
+        self
+    }
+
+    fn validate_set_descriptor_buffer_offsets(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: &PipelineLayout,
+        first_set: u32,
+        offsets: &[(u32, DeviceSize)],
+    ) -> Result<(), BindPushError> {
+        let device = self.device();
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-None-08060
+        if !device.enabled_features().descriptor_buffer {
+            return Err(BindPushError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_descriptor_buffer_offsets`",
+                requires_one_of: RequiresOneOf {
+                    features: &["descriptor_buffer"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pipelineBindPoint-parameter
+        pipeline_bind_point.validate_device(device)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-commandBuffer-cmdpool
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pipelineBindPoint-08067
+        match pipeline_bind_point {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::COMPUTE)
+                {
+                    return Err(BindPushError::NotSupportedByQueueFamily);
+                }
+            }
+            PipelineBindPoint::Graphics => {
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(BindPushError::NotSupportedByQueueFamily);
+                }
+            }
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-commonparent
+        assert_eq!(device, pipeline_layout.device());
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-firstSet-08066
+        if first_set + offsets.len() as u32 > pipeline_layout.set_layouts().len() as u32 {
+            return Err(BindPushError::DescriptorSetOutOfRange {
+                set_num: first_set + offsets.len() as u32,
+                pipeline_layout_set_count: pipeline_layout.set_layouts().len() as u32,
+            });
+        }
+
+        let required_alignment = device
+            .physical_device()
+            .properties()
+            .descriptor_buffer_offset_alignment
+            .unwrap();
+        let descriptor_buffers = self.inner.state().descriptor_buffers();
+
+        for (i, &(buffer_index, offset)) in offsets.iter().enumerate() {
+            let set_num = first_set + i as u32;
+            let set_layout = &pipeline_layout.set_layouts()[set_num as usize];
+
+            // VUID?
+            if !set_layout.descriptor_buffer() {
+                return Err(BindPushError::DescriptorSetNotDescriptorBuffer { set_num });
+            }
+
+            // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08061
+            if offset % required_alignment != 0 {
+                return Err(BindPushError::DescriptorBufferOffsetNotAligned {
+                    set_num,
+                    offset,
+                    required_alignment,
+                });
+            }
+
+            // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pBufferIndices-08064
+            let buffer = descriptor_buffers.get(buffer_index as usize).ok_or(
+                BindPushError::DescriptorBufferIndexOutOfRange {
+                    set_num,
+                    buffer_index,
+                    descriptor_buffer_count: descriptor_buffers.len() as u32,
+                },
+            )?;
+
+            // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08063
+            if offset + set_layout.descriptor_buffer_size() > buffer.size() {
+                return Err(BindPushError::DescriptorBufferOffsetOutOfRange { set_num });
+            }
+
+            // TODO:
+            // VUID-vkCmdSetDescriptorBufferOffsetsEXT-pBufferIndices-08065
+        }
+
+        Ok(())
+    }
 }
 
 impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBindDescriptorBuffersEXT` on the builder.
+    #[inline]
+    pub unsafe fn bind_descriptor_buffers(
+        &mut self,
+        descriptor_buffers: SmallVec<[Arc<dyn BufferAccess>; 3]>,
+    ) {
+        struct Cmd {
+            descriptor_buffers: SmallVec<[Arc<dyn BufferAccess>; 3]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "bind_descriptor_buffers"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_descriptor_buffers(
+                    self.descriptor_buffers.iter().map(|buffer| buffer.as_ref()),
+                );
+            }
+        }
+
+        self.current_state.descriptor_buffers = descriptor_buffers.clone();
+        self.commands.push(Box::new(Cmd { descriptor_buffers }));
+    }
+
     /// Starts the process of binding descriptor sets. Returns an intermediate struct which can be
     /// used to add the sets.
     #[inline]
@@ -723,6 +1151,27 @@ impl SyncCommandBufferBuilder {
         self.commands.push(Box::new(Cmd { pipeline }));
     }
 
+    /// Calls `vkCmdBindPipeline` on the builder with a ray tracing pipeline.
+    #[inline]
+    pub unsafe fn bind_pipeline_ray_tracing(&mut self, pipeline: Arc<RayTracingPipeline>) {
+        struct Cmd {
+            pipeline: Arc<RayTracingPipeline>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "bind_pipeline_ray_tracing"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_pipeline_ray_tracing(&self.pipeline);
+            }
+        }
+
+        self.current_state.pipeline_ray_tracing = Some(pipeline.clone());
+        self.commands.push(Box::new(Cmd { pipeline }));
+    }
+
     /// Starts the process of binding vertex buffers. Returns an intermediate struct which can be
     /// used to add the buffers.
     #[inline]
@@ -839,6 +1288,7 @@ impl SyncCommandBufferBuilder {
         );
         let layout = state.pipeline_layout.set_layouts()[set_num as usize].as_ref();
         debug_assert!(layout.push_descriptor());
+        state.descriptor_buffer_offsets.remove(&set_num);
 
         let set_resources = match state
             .descriptor_sets
@@ -860,6 +1310,61 @@ impl SyncCommandBufferBuilder {
             descriptor_writes,
         }));
     }
+
+    /// Calls `vkCmdSetDescriptorBufferOffsetsEXT` on the builder.
+    pub unsafe fn set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: Arc<PipelineLayout>,
+        first_set: u32,
+        offsets: SmallVec<[(u32, DeviceSize); 4]>,
+    ) {
+        struct Cmd {
+            pipeline_bind_point: PipelineBindPoint,
+            pipeline_layout: Arc<PipelineLayout>,
+            first_set: u32,
+            offsets: SmallVec<[(u32, DeviceSize); 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_descriptor_buffer_offsets"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_descriptor_buffer_offsets(
+                    self.pipeline_bind_point,
+                    &self.pipeline_layout,
+                    self.first_set,
+                    self.offsets.iter().copied(),
+                );
+            }
+        }
+
+        if offsets.is_empty() {
+            return;
+        }
+
+        let state = self.current_state.invalidate_descriptor_sets(
+            pipeline_bind_point,
+            pipeline_layout.clone(),
+            first_set,
+            offsets.len() as u32,
+        );
+
+        for (set_num, &offset) in offsets.iter().enumerate() {
+            let set_num = first_set + set_num as u32;
+            state.descriptor_sets.remove(&set_num);
+            state.descriptor_buffer_offsets.insert(set_num, offset);
+        }
+
+        self.commands.push(Box::new(Cmd {
+            pipeline_bind_point,
+            pipeline_layout,
+            first_set,
+            offsets,
+        }));
+    }
 }
 
 pub struct SyncCommandBufferBuilderBindDescriptorSets<'b> {
@@ -921,9 +1426,11 @@ impl<'b> SyncCommandBufferBuilderBindDescriptorSets<'b> {
         );
 
         for (set_num, set) in self.descriptor_sets.iter().enumerate() {
+            let set_num = first_set + set_num as u32;
+            state.descriptor_buffer_offsets.remove(&set_num);
             state
                 .descriptor_sets
-                .insert(first_set + set_num as u32, SetOrPush::Set(set.clone()));
+                .insert(set_num, SetOrPush::Set(set.clone()));
         }
 
         self.builder.commands.push(Box::new(Cmd {
@@ -984,6 +1491,69 @@ impl<'a> SyncCommandBufferBuilderBindVertexBuffer<'a> {
 }
 
 impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBindDescriptorBuffersEXT` on the builder.
+    pub unsafe fn bind_descriptor_buffers<'a>(
+        &mut self,
+        descriptor_buffers: impl IntoIterator<Item = &'a dyn BufferAccess>,
+    ) {
+        let bufferless_push_descriptors = self
+            .device
+            .physical_device()
+            .properties()
+            .bufferless_push_descriptors
+            .unwrap_or(true);
+
+        let descriptor_buffers: SmallVec<[_; 3]> = descriptor_buffers.into_iter().collect();
+        let mut push_descriptor_buffer_handle_vk = None;
+
+        let mut binding_infos_vk: SmallVec<[_; 3]> = descriptor_buffers
+            .iter()
+            .map(|buffer| {
+                debug_assert!(buffer.usage().intersects(
+                    BufferUsage::SAMPLER_DESCRIPTOR_BUFFER
+                        | BufferUsage::RESOURCE_DESCRIPTOR_BUFFER
+                ));
+
+                if !bufferless_push_descriptors
+                    && buffer
+                        .usage()
+                        .intersects(BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER)
+                {
+                    push_descriptor_buffer_handle_vk = Some(
+                        ash::vk::DescriptorBufferBindingPushDescriptorBufferHandleEXT {
+                            buffer: buffer.inner().buffer.handle(),
+                            ..Default::default()
+                        },
+                    );
+                }
+
+                ash::vk::DescriptorBufferBindingInfoEXT {
+                    address: buffer.raw_device_address().unwrap().get(),
+                    usage: (*buffer.usage()).into(),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        if let Some(push_descriptor_buffer_handle_vk) = push_descriptor_buffer_handle_vk.as_mut() {
+            for (binding_info_vk, buffer) in binding_infos_vk.iter_mut().zip(&descriptor_buffers) {
+                if buffer
+                    .usage()
+                    .intersects(BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER)
+                {
+                    binding_info_vk.p_next = push_descriptor_buffer_handle_vk as *mut _ as *mut _;
+                }
+            }
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_descriptor_buffer.cmd_bind_descriptor_buffers_ext)(
+            self.handle,
+            binding_infos_vk.len() as u32,
+            binding_infos_vk.as_ptr(),
+        );
+    }
+
     /// Calls `vkCmdBindDescriptorSets` on the builder.
     ///
     /// Does nothing if the list of descriptor sets is empty, as it would be a no-op and isn't a
@@ -1059,6 +1629,17 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdBindPipeline` on the builder with a ray tracing pipeline.
+    #[inline]
+    pub unsafe fn bind_pipeline_ray_tracing(&mut self, pipeline: &RayTracingPipeline) {
+        let fns = self.device.fns();
+        (fns.v1_0.cmd_bind_pipeline)(
+            self.handle,
+            ash::vk::PipelineBindPoint::RAY_TRACING_KHR,
+            pipeline.handle(),
+        );
+    }
+
     /// Calls `vkCmdBindVertexBuffers` on the builder.
     ///
     /// Does nothing if the list of buffers is empty, as it would be a no-op and isn't a valid
@@ -1156,8 +1737,22 @@ impl UnsafeCommandBufferBuilder {
             return;
         }
 
+        let acceleration_structure_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_acceleration_structure)
+            .collect();
+        let inline_uniform_block_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_inline_uniform_block)
+            .collect();
+
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        for (((info, write), acceleration_structure_info), inline_uniform_block_info) in infos
+            .iter()
+            .zip(writes.iter_mut())
+            .zip(&acceleration_structure_infos)
+            .zip(&inline_uniform_block_infos)
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -1171,6 +1766,14 @@ impl UnsafeCommandBufferBuilder {
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::AccelerationStructure(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_next = acceleration_structure_info as *const _ as *const _;
+                }
+                DescriptorWriteInfo::InlineUniformBlock(data) => {
+                    write.descriptor_count = data.len() as u32;
+                    write.p_next = inline_uniform_block_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
@@ -1187,6 +1790,41 @@ impl UnsafeCommandBufferBuilder {
             writes.as_ptr(),
         );
     }
+
+    /// Calls `vkCmdSetDescriptorBufferOffsetsEXT` on the builder.
+    ///
+    /// Does nothing if the list of offsets is empty, as it would be a no-op and isn't a valid
+    /// usage of the command anyway.
+    pub unsafe fn set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: &PipelineLayout,
+        first_set: u32,
+        offsets: impl IntoIterator<Item = (u32, DeviceSize)>,
+    ) {
+        let (buffer_indices, offsets): (SmallVec<[u32; 4]>, SmallVec<[DeviceSize; 4]>) =
+            offsets.into_iter().unzip();
+
+        if offsets.is_empty() {
+            return;
+        }
+
+        debug_assert!(
+            first_set + offsets.len() as u32 <= pipeline_layout.set_layouts().len() as u32
+        );
+
+        let fns = self.device.fns();
+        (fns.ext_descriptor_buffer
+            .cmd_set_descriptor_buffer_offsets_ext)(
+            self.handle,
+            pipeline_bind_point.into(),
+            pipeline_layout.handle(),
+            first_set,
+            offsets.len() as u32,
+            buffer_indices.as_ptr(),
+            offsets.as_ptr(),
+        );
+    }
 }
 
 /// Prototype for a `vkCmdBindVertexBuffers`.
@@ -1227,12 +1865,56 @@ pub(in super::super) enum BindPushError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// The device address of a descriptor buffer is not a multiple of the
+    /// `descriptor_buffer_offset_alignment` device property.
+    DescriptorBufferAddressNotAligned {
+        index: u32,
+        address: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// The buffer index for a descriptor set is not less than the number of bound descriptor
+    /// buffers.
+    DescriptorBufferIndexOutOfRange {
+        set_num: u32,
+        buffer_index: u32,
+        descriptor_buffer_count: u32,
+    },
+
+    /// A descriptor buffer is missing a descriptor buffer usage, or the `shader_device_address`
+    /// usage.
+    DescriptorBufferMissingUsage {
+        index: u32,
+    },
+
+    /// The offset for a descriptor set is not a multiple of the
+    /// `descriptor_buffer_offset_alignment` device property.
+    DescriptorBufferOffsetNotAligned {
+        set_num: u32,
+        offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// The descriptor set does not fit in the descriptor buffer at the given offset.
+    DescriptorBufferOffsetOutOfRange {
+        set_num: u32,
+    },
+
+    /// More than one descriptor buffer has the `push_descriptors_descriptor_buffer` usage.
+    DescriptorBufferPushDescriptorsMultiple,
+
     /// The element of `descriptor_sets` being bound to a slot is not compatible with the
     /// corresponding slot in `pipeline_layout`.
     DescriptorSetNotCompatible {
         set_num: u32,
     },
 
+    /// The set layout of a descriptor set number being set from a descriptor buffer does not
+    /// have `descriptor_buffer` enabled.
+    DescriptorSetNotDescriptorBuffer {
+        set_num: u32,
+    },
+
     /// The descriptor set number being pushed is not defined for push descriptor sets in the
     /// pipeline layout.
     DescriptorSetNotPush {
@@ -1249,6 +1931,31 @@ pub(in super::super) enum BindPushError {
     /// An index buffer is missing the `index_buffer` usage.
     IndexBufferMissingUsage,
 
+    /// The `max_descriptor_buffer_bindings` limit has been exceeded.
+    MaxDescriptorBufferBindingsExceeded {
+        provided: u32,
+        max_supported: u32,
+    },
+
+    /// The `max_resource_descriptor_buffer_bindings` limit has been exceeded.
+    MaxResourceDescriptorBufferBindingsExceeded {
+        provided: u32,
+        max_supported: u32,
+    },
+
+    /// The `max_sampler_descriptor_buffer_bindings` limit has been exceeded.
+    MaxSamplerDescriptorBufferBindingsExceeded {
+        provided: u32,
+        max_supported: u32,
+    },
+
+    /// The push constants to be written extend beyond the `max_push_constants_size` limit.
+    MaxPushConstantsSizeExceeded {
+        offset: u32,
+        size: u32,
+        max_supported: u32,
+    },
+
     /// The `max_vertex_input_bindings` limit has been exceeded.
     MaxVertexInputBindingsExceeded {
         _binding_count: u32,
@@ -1258,6 +1965,9 @@ pub(in super::super) enum BindPushError {
     /// The queue family doesn't allow this operation.
     NotSupportedByQueueFamily,
 
+    /// The pipeline was created as a pipeline library.
+    PipelineIsLibrary,
+
     /// The newly set pipeline has color attachment formats that do not match the
     /// previously used pipeline.
     PreviousPipelineColorAttachmentFormatMismatch,
@@ -1307,12 +2017,64 @@ impl Display for BindPushError {
                 required_for, requires_one_of,
             ),
             Self::DescriptorSetUpdateError(_) => write!(f, "a DescriptorSetUpdateError"),
+            Self::DescriptorBufferAddressNotAligned {
+                index,
+                address,
+                required_alignment,
+            } => write!(
+                f,
+                "the device address ({}) of descriptor buffer {} is not a multiple of the \
+                `descriptor_buffer_offset_alignment` device property ({})",
+                address, index, required_alignment,
+            ),
+            Self::DescriptorBufferIndexOutOfRange {
+                set_num,
+                buffer_index,
+                descriptor_buffer_count,
+            } => write!(
+                f,
+                "the buffer index ({}) for descriptor set {} is not less than the number of bound \
+                descriptor buffers ({})",
+                buffer_index, set_num, descriptor_buffer_count,
+            ),
+            Self::DescriptorBufferMissingUsage { index } => write!(
+                f,
+                "descriptor buffer {} is missing a descriptor buffer usage, or the \
+                `shader_device_address` usage",
+                index,
+            ),
+            Self::DescriptorBufferOffsetNotAligned {
+                set_num,
+                offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the offset ({}) for descriptor set {} is not a multiple of the \
+                `descriptor_buffer_offset_alignment` device property ({})",
+                offset, set_num, required_alignment,
+            ),
+            Self::DescriptorBufferOffsetOutOfRange { set_num } => write!(
+                f,
+                "descriptor set {} does not fit in the descriptor buffer at the given offset",
+                set_num,
+            ),
+            Self::DescriptorBufferPushDescriptorsMultiple => write!(
+                f,
+                "more than one descriptor buffer has the `push_descriptors_descriptor_buffer` \
+                usage",
+            ),
             Self::DescriptorSetNotCompatible { set_num } => write!(
                 f,
                 "the element of `descriptor_sets` being bound to slot {} is not compatible with \
                 the corresponding slot in `pipeline_layout`",
                 set_num,
             ),
+            Self::DescriptorSetNotDescriptorBuffer { set_num } => write!(
+                f,
+                "the set layout of descriptor set number {} does not have `descriptor_buffer` \
+                enabled",
+                set_num,
+            ),
             Self::DescriptorSetNotPush { set_num } => write!(
                 f,
                 "the descriptor set number being pushed ({}) is not defined for push descriptor \
@@ -1331,12 +2093,52 @@ impl Display for BindPushError {
             Self::IndexBufferMissingUsage => {
                 write!(f, "an index buffer is missing the `index_buffer` usage")
             }
+            Self::MaxDescriptorBufferBindingsExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the number of descriptor buffers ({}) exceeds the \
+                `max_descriptor_buffer_bindings` limit ({})",
+                provided, max_supported,
+            ),
+            Self::MaxResourceDescriptorBufferBindingsExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the number of resource descriptor buffers ({}) exceeds the \
+                `max_resource_descriptor_buffer_bindings` limit ({})",
+                provided, max_supported,
+            ),
+            Self::MaxSamplerDescriptorBufferBindingsExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the number of sampler descriptor buffers ({}) exceeds the \
+                `max_sampler_descriptor_buffer_bindings` limit ({})",
+                provided, max_supported,
+            ),
+            Self::MaxPushConstantsSizeExceeded {
+                offset,
+                size,
+                max_supported,
+            } => write!(
+                f,
+                "the push constants to be written at offset {} with size {} extend beyond the \
+                `max_push_constants_size` limit ({})",
+                offset, size, max_supported,
+            ),
             Self::MaxVertexInputBindingsExceeded { .. } => {
                 write!(f, "the `max_vertex_input_bindings` limit has been exceeded")
             }
             Self::NotSupportedByQueueFamily => {
                 write!(f, "the queue family doesn't allow this operation")
             }
+            Self::PipelineIsLibrary => {
+                write!(f, "the pipeline was created as a pipeline library")
+            }
             Self::PreviousPipelineColorAttachmentFormatMismatch => write!(
                 f,
                 "the newly set pipeline has color attachment formats that do not match the \
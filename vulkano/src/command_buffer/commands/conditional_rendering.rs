@@ -0,0 +1,508 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::ConditionalRenderingState,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize, RequiresOneOf, VulkanObject,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// # Commands for conditional rendering.
+///
+/// These commands require the
+/// [`ext_conditional_rendering`](crate::device::DeviceExtensions::ext_conditional_rendering)
+/// extension and the
+/// [`conditional_rendering`](crate::device::Features::conditional_rendering) feature.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins a conditional rendering block.
+    ///
+    /// A 32-bit predicate value is read from `buffer` at `offset` when the command buffer is
+    /// executed. If the value is zero, draw, dispatch and clear commands recorded until
+    /// [`end_conditional_rendering`](Self::end_conditional_rendering) is called are discarded.
+    /// If `inverted` is `true`, the commands are discarded if the value is nonzero instead.
+    ///
+    /// Because the predicate is read on the device, this can be used to skip work based on the
+    /// results of earlier commands, such as occlusion query results copied into a buffer, without
+    /// reading them back on the host.
+    pub fn begin_conditional_rendering(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        offset: DeviceSize,
+        inverted: bool,
+    ) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_begin_conditional_rendering(buffer.as_ref(), offset)?;
+
+        unsafe {
+            self.inner
+                .begin_conditional_rendering(buffer, offset, inverted)?;
+        }
+
+        self.conditional_rendering_state = Some(ConditionalRenderingState {
+            in_subpass: self.render_pass_state.is_some(),
+        });
+
+        Ok(self)
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        buffer: &dyn BufferAccess,
+        offset: DeviceSize,
+    ) -> Result<(), ConditionalRenderingError> {
+        let device = self.device();
+
+        if !device.enabled_extensions().ext_conditional_rendering {
+            return Err(ConditionalRenderingError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_conditional_rendering`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_conditional_rendering"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID?
+        if !device.enabled_features().conditional_rendering {
+            return Err(ConditionalRenderingError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_conditional_rendering`",
+                requires_one_of: RequiresOneOf {
+                    features: &["conditional_rendering"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginConditionalRenderingEXT-None-01980
+        if self.conditional_rendering_state.is_some() {
+            return Err(ConditionalRenderingError::AlreadyActive);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-parameter
+        assert_eq!(device, buffer.device());
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-01982
+        if !buffer
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING)
+        {
+            return Err(ConditionalRenderingError::BufferMissingUsage);
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01984
+        if offset % 4 != 0 {
+            return Err(ConditionalRenderingError::OffsetNotAligned { offset });
+        }
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-offset-01983
+        if offset + 4 > buffer.size() {
+            return Err(ConditionalRenderingError::OffsetOutOfRange {
+                offset,
+                buffer_size: buffer.size(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Ends a conditional rendering block.
+    ///
+    /// If the block was begun inside a render subpass, it must be ended in the same subpass. If it
+    /// was begun outside a render pass, it must be ended outside a render pass.
+    pub fn end_conditional_rendering(&mut self) -> Result<&mut Self, ConditionalRenderingError> {
+        self.validate_end_conditional_rendering()?;
+
+        unsafe {
+            self.inner.end_conditional_rendering();
+        }
+
+        self.conditional_rendering_state = None;
+
+        Ok(self)
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), ConditionalRenderingError> {
+        // VUID-vkCmdEndConditionalRenderingEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(ConditionalRenderingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01985
+        let state = self
+            .conditional_rendering_state
+            .as_ref()
+            .ok_or(ConditionalRenderingError::NotActive)?;
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01986
+        if !state.in_subpass && self.render_pass_state.is_some() {
+            return Err(ConditionalRenderingError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        // Ensured by the checks in `next_subpass`, `end_render_pass` and `end_rendering`.
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: Arc<dyn BufferAccess>,
+        offset: DeviceSize,
+        inverted: bool,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            buffer: Arc<dyn BufferAccess>,
+            offset: DeviceSize,
+            inverted: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "begin_conditional_rendering"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_conditional_rendering(self.buffer.as_ref(), self.offset, self.inverted);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "begin_conditional_rendering";
+        let resources = [(
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::ConditionalRenderingBuffer,
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: buffer.clone(),
+                range: offset..offset + 4,
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::CONDITIONAL_RENDERING,
+                    access: AccessFlags::CONDITIONAL_RENDERING_READ,
+                    exclusive: false,
+                },
+            },
+        )];
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            buffer,
+            offset,
+            inverted,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        struct Cmd;
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "end_conditional_rendering"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_conditional_rendering();
+            }
+        }
+
+        self.commands.push(Box::new(Cmd));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBeginConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &dyn BufferAccess,
+        offset: DeviceSize,
+        inverted: bool,
+    ) {
+        let inner = buffer.inner();
+        debug_assert!(inner
+            .buffer
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING));
+
+        let mut flags = ash::vk::ConditionalRenderingFlagsEXT::empty();
+
+        if inverted {
+            flags |= ash::vk::ConditionalRenderingFlagsEXT::INVERTED;
+        }
+
+        let begin_info = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: inner.buffer.handle(),
+            offset: inner.offset + offset,
+            flags,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext)(self.handle, &begin_info);
+    }
+
+    /// Calls `vkCmdEndConditionalRenderingEXT` on the builder.
+    #[inline]
+    pub unsafe fn end_conditional_rendering(&mut self) {
+        let fns = self.device.fns();
+        (fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext)(self.handle);
+    }
+}
+
+/// Error that can happen when recording a conditional rendering command.
+#[derive(Clone, Debug)]
+pub enum ConditionalRenderingError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// Conditional rendering is already active.
+    AlreadyActive,
+
+    /// The predicate buffer is missing the `conditional_rendering` usage.
+    BufferMissingUsage,
+
+    /// Conditional rendering was made active outside a render pass, but this operation is
+    /// recorded inside a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// Conditional rendering is not active.
+    NotActive,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The offset is not a multiple of 4.
+    OffsetNotAligned {
+        offset: DeviceSize,
+    },
+
+    /// The offset plus the size of the predicate value is greater than the size of the buffer.
+    OffsetOutOfRange {
+        offset: DeviceSize,
+        buffer_size: DeviceSize,
+    },
+}
+
+impl Error for ConditionalRenderingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ConditionalRenderingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::AlreadyActive => write!(f, "conditional rendering is already active"),
+            Self::BufferMissingUsage => write!(
+                f,
+                "the predicate buffer is missing the `conditional_rendering` usage",
+            ),
+            Self::ForbiddenInsideRenderPass => write!(
+                f,
+                "conditional rendering was made active outside a render pass, but this operation \
+                is recorded inside a render pass",
+            ),
+            Self::NotActive => write!(f, "conditional rendering is not active"),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::OffsetNotAligned { offset } => {
+                write!(f, "the offset ({}) is not a multiple of 4", offset)
+            }
+            Self::OffsetOutOfRange {
+                offset,
+                buffer_size,
+            } => write!(
+                f,
+                "the offset ({}) plus the size of the predicate value (4) is greater than the size \
+                of the buffer ({})",
+                offset, buffer_size,
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for ConditionalRenderingError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::CpuAccessibleBuffer,
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, CommandBufferUsage, PrimaryAutoCommandBuffer,
+        },
+        device::Device,
+        memory::allocator::StandardMemoryAllocator,
+    };
+
+    fn predicate_buffer(device: Arc<Device>, usage: BufferUsage) -> Arc<dyn BufferAccess> {
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        CpuAccessibleBuffer::from_iter(&memory_allocator, usage, false, [0u32; 4]).unwrap()
+    }
+
+    fn command_buffer_builder(
+        allocator: &StandardCommandBufferAllocator,
+        queue_family_index: u32,
+    ) -> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandBufferAllocator> {
+        AutoCommandBufferBuilder::primary(
+            allocator,
+            queue_family_index,
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn requires_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let buffer = predicate_buffer(device, BufferUsage::UNIFORM_BUFFER);
+
+        match cbb.begin_conditional_rendering(buffer, 0, false) {
+            Err(ConditionalRenderingError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn begin_end() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_conditional_rendering],
+            features: [conditional_rendering]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let buffer = predicate_buffer(device, BufferUsage::CONDITIONAL_RENDERING);
+
+        match cbb.end_conditional_rendering() {
+            Err(ConditionalRenderingError::NotActive) => (),
+            _ => panic!(),
+        }
+
+        cbb.begin_conditional_rendering(buffer.clone(), 4, true)
+            .unwrap();
+
+        match cbb.begin_conditional_rendering(buffer, 0, false) {
+            Err(ConditionalRenderingError::AlreadyActive) => (),
+            _ => panic!(),
+        }
+
+        cbb.end_conditional_rendering().unwrap();
+        cbb.build().unwrap();
+    }
+
+    #[test]
+    fn buffer_missing_usage() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_conditional_rendering],
+            features: [conditional_rendering]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let buffer = predicate_buffer(device, BufferUsage::UNIFORM_BUFFER);
+
+        match cbb.begin_conditional_rendering(buffer, 0, false) {
+            Err(ConditionalRenderingError::BufferMissingUsage) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_offset() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_conditional_rendering],
+            features: [conditional_rendering]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let buffer = predicate_buffer(device, BufferUsage::CONDITIONAL_RENDERING);
+
+        match cbb.begin_conditional_rendering(buffer.clone(), 2, false) {
+            Err(ConditionalRenderingError::OffsetNotAligned { offset: 2 }) => (),
+            _ => panic!(),
+        }
+
+        match cbb.begin_conditional_rendering(buffer, 16, false) {
+            Err(ConditionalRenderingError::OffsetOutOfRange {
+                offset: 16,
+                buffer_size: 16,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+}
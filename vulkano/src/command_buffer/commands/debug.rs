@@ -44,6 +44,8 @@ where
             self.inner.begin_debug_utils_label(label_info);
         }
 
+        self.debug_label_depth += 1;
+
         Ok(self)
     }
 
@@ -81,6 +83,10 @@ where
 
     /// Closes a command buffer debug label region.
     ///
+    /// In a secondary command buffer, the region must have been begun within the same command
+    /// buffer. This is checked, and every region begun in a secondary command buffer must also
+    /// be ended before it is built.
+    ///
     /// # Safety
     ///
     /// - When submitting the command buffer, there must be an outstanding command buffer label
@@ -90,6 +96,7 @@ where
         self.validate_end_debug_utils_label()?;
 
         self.inner.end_debug_utils_label();
+        self.debug_label_depth = self.debug_label_depth.saturating_sub(1);
 
         Ok(self)
     }
@@ -124,7 +131,9 @@ where
         // TODO: not checked, so unsafe for now
 
         // VUID-vkCmdEndDebugUtilsLabelEXT-commandBuffer-01913
-        // TODO: not checked, so unsafe for now
+        if self.inheritance_info.is_some() && self.debug_label_depth == 0 {
+            return Err(DebugUtilsError::DebugLabelNotActive);
+        }
 
         Ok(())
     }
@@ -322,6 +331,9 @@ pub enum DebugUtilsError {
 
     /// The queue family doesn't allow this operation.
     NotSupportedByQueueFamily,
+
+    /// There is no debug label region begun in this secondary command buffer that could be ended.
+    DebugLabelNotActive,
 }
 
 impl Error for DebugUtilsError {}
@@ -340,6 +352,11 @@ impl Display for DebugUtilsError {
             Self::NotSupportedByQueueFamily => {
                 write!(f, "the queue family doesn't allow this operation")
             }
+            Self::DebugLabelNotActive => write!(
+                f,
+                "there is no debug label region begun in this secondary command buffer that could \
+                be ended",
+            ),
         }
     }
 }
@@ -0,0 +1,566 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStage, PipelineStages},
+    DeviceSize, RequirementNotMet, RequiresOneOf, VulkanObject,
+};
+use parking_lot::{const_mutex, Mutex};
+use std::{
+    error::Error,
+    ffi::c_void,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// # Commands for diagnosing device loss.
+///
+/// When the device is lost, for example because a command buffer took too long to execute, the
+/// markers written by these commands can be used to find out how far the execution of each
+/// queue got. Checkpoints require the [`nv_device_diagnostic_checkpoints`] extension, and are
+/// retrieved after device loss with [`QueueGuard::checkpoint_data`]. Buffer markers require the
+/// [`amd_buffer_marker`] extension, and are written to a buffer that can be read by the host.
+///
+/// [`nv_device_diagnostic_checkpoints`]: crate::device::DeviceExtensions::nv_device_diagnostic_checkpoints
+/// [`QueueGuard::checkpoint_data`]: crate::device::QueueGuard::checkpoint_data
+/// [`amd_buffer_marker`]: crate::device::DeviceExtensions::amd_buffer_marker
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Inserts a checkpoint with the given marker.
+    ///
+    /// The highest bit of `marker` is reserved for automatic checkpoints, and must not be set.
+    pub fn set_checkpoint(&mut self, marker: usize) -> Result<&mut Self, DiagnosticsError> {
+        self.validate_set_checkpoint(marker)?;
+
+        unsafe {
+            self.inner.set_checkpoint(marker);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_set_checkpoint(&self, marker: usize) -> Result<(), DiagnosticsError> {
+        self.validate_checkpoints("`AutoCommandBufferBuilder::set_checkpoint`")?;
+
+        if marker & AUTOMATIC_CHECKPOINT_BIT != 0 {
+            return Err(DiagnosticsError::MarkerReserved);
+        }
+
+        Ok(())
+    }
+
+    /// Enables or disables automatic checkpoints.
+    ///
+    /// While automatic checkpoints are enabled, a checkpoint is inserted before every command
+    /// that is recorded. After device loss, the checkpoint reports the name of the command, as
+    /// [`CheckpointMarker::Command`]. This makes it possible to find the command that the device
+    /// was executing when it was lost, at the cost of some performance.
+    pub fn set_automatic_checkpoints(
+        &mut self,
+        enabled: bool,
+    ) -> Result<&mut Self, DiagnosticsError> {
+        self.validate_checkpoints("`AutoCommandBufferBuilder::set_automatic_checkpoints`")?;
+
+        unsafe {
+            self.inner.set_automatic_checkpoints(enabled);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_checkpoints(&self, required_for: &'static str) -> Result<(), DiagnosticsError> {
+        if !self
+            .device()
+            .enabled_extensions()
+            .nv_device_diagnostic_checkpoints
+        {
+            return Err(DiagnosticsError::RequirementNotMet {
+                required_for,
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["nv_device_diagnostic_checkpoints"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetCheckpointNV-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::TRANSFER | QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(DiagnosticsError::NotSupportedByQueueFamily);
+        }
+
+        Ok(())
+    }
+
+    /// Writes `marker` to `dst_buffer` when all previous commands have completed `stage`.
+    ///
+    /// If the implementation can't write the marker at `stage`, it may write it at a later stage
+    /// instead. To be able to read the markers after device loss, the buffer should be in
+    /// host-coherent memory.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `dst_buffer` was not created from the same device as `self`.
+    pub fn write_buffer_marker(
+        &mut self,
+        stage: PipelineStage,
+        dst_buffer: Arc<dyn BufferAccess>,
+        marker: u32,
+    ) -> Result<&mut Self, DiagnosticsError> {
+        self.validate_write_buffer_marker(stage, dst_buffer.as_ref(), marker)?;
+
+        unsafe {
+            self.inner.write_buffer_marker(stage, dst_buffer, marker)?;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_write_buffer_marker(
+        &self,
+        stage: PipelineStage,
+        dst_buffer: &dyn BufferAccess,
+        _marker: u32,
+    ) -> Result<(), DiagnosticsError> {
+        let device = self.device();
+
+        if !device.enabled_extensions().amd_buffer_marker {
+            return Err(DiagnosticsError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::write_buffer_marker`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["amd_buffer_marker"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        if !device.enabled_features().synchronization2 && PipelineStages::from(stage).is_2() {
+            return Err(DiagnosticsError::RequirementNotMet {
+                required_for: "`stage` has flags set from `VkPipelineStageFlagBits2`",
+                requires_one_of: RequiresOneOf {
+                    features: &["synchronization2"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdWriteBufferMarker2AMD-stage-parameter
+        stage.validate_device(device)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdWriteBufferMarkerAMD-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::TRANSFER | QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(DiagnosticsError::NotSupportedByQueueFamily);
+        }
+
+        // VUID?
+        if !PipelineStages::from(queue_family_properties.queue_flags).contains_enum(stage) {
+            return Err(DiagnosticsError::StageNotSupported);
+        }
+
+        match stage {
+            PipelineStage::GeometryShader => {
+                // VUID-vkCmdWriteBufferMarker2AMD-stage-03929
+                if !device.enabled_features().geometry_shader {
+                    return Err(DiagnosticsError::RequirementNotMet {
+                        required_for: "`stage` is `PipelineStage::GeometryShader`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["geometry_shader"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+            PipelineStage::TessellationControlShader
+            | PipelineStage::TessellationEvaluationShader => {
+                // VUID-vkCmdWriteBufferMarker2AMD-stage-03930
+                if !device.enabled_features().tessellation_shader {
+                    return Err(DiagnosticsError::RequirementNotMet {
+                        required_for: "`stage` is `PipelineStage::TessellationControlShader` or \
+                            `PipelineStage::TessellationEvaluationShader`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["tessellation_shader"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+            PipelineStage::MeshShader => {
+                // VUID-vkCmdWriteBufferMarker2AMD-stage-03934
+                if !device.enabled_features().mesh_shader {
+                    return Err(DiagnosticsError::RequirementNotMet {
+                        required_for: "`stage` is `PipelineStage::MeshShader`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["mesh_shader"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+            PipelineStage::TaskShader => {
+                // VUID-vkCmdWriteBufferMarker2AMD-stage-03935
+                if !device.enabled_features().task_shader {
+                    return Err(DiagnosticsError::RequirementNotMet {
+                        required_for: "`stage` is `PipelineStage::TaskShader`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["task_shader"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+            _ => (),
+        }
+
+        // VUID-vkCmdWriteBufferMarkerAMD-commonparent
+        assert_eq!(device, dst_buffer.device());
+
+        // VUID-vkCmdWriteBufferMarkerAMD-dstBuffer-01799
+        if !dst_buffer.usage().intersects(BufferUsage::TRANSFER_DST) {
+            return Err(DiagnosticsError::MissingUsage {
+                usage: "transfer_dst",
+            });
+        }
+
+        // VUID-vkCmdWriteBufferMarkerAMD-dstOffset-01798
+        if dst_buffer.size() < 4 {
+            return Err(DiagnosticsError::OutOfBufferBounds {
+                offset_range_end: 4,
+                buffer_size: dst_buffer.size(),
+            });
+        }
+
+        // VUID-vkCmdWriteBufferMarkerAMD-dstOffset-01801
+        if dst_buffer.inner().offset % 4 != 0 {
+            return Err(DiagnosticsError::OffsetNotAligned {
+                offset: dst_buffer.inner().offset,
+                required_alignment: 4,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdSetCheckpointNV` on the builder.
+    #[inline]
+    pub unsafe fn set_checkpoint(&mut self, marker: usize) {
+        struct Cmd {
+            marker: usize,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_checkpoint"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_checkpoint(self.marker);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { marker }));
+    }
+
+    /// Calls `vkCmdWriteBufferMarkerAMD` on the builder.
+    #[inline]
+    pub unsafe fn write_buffer_marker(
+        &mut self,
+        stage: PipelineStage,
+        dst_buffer: Arc<dyn BufferAccess>,
+        marker: u32,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            stage: PipelineStage,
+            dst_buffer: Arc<dyn BufferAccess>,
+            marker: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "write_buffer_marker"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.write_buffer_marker(self.stage, self.dst_buffer.as_ref(), self.marker);
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "write_buffer_marker";
+        let resources = [(
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::Destination,
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: dst_buffer.clone(),
+                range: 0..4,
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::ALL_TRANSFER | stage.into(),
+                    access: AccessFlags::TRANSFER_WRITE,
+                    exclusive: true,
+                },
+            },
+        )];
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            stage,
+            dst_buffer,
+            marker,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdSetCheckpointNV` on the builder.
+    #[inline]
+    pub unsafe fn set_checkpoint(&mut self, marker: usize) {
+        let fns = self.device.fns();
+        (fns.nv_device_diagnostic_checkpoints.cmd_set_checkpoint_nv)(
+            self.handle,
+            marker as *const c_void,
+        );
+    }
+
+    /// Calls `vkCmdWriteBufferMarkerAMD` on the builder.
+    #[inline]
+    pub unsafe fn write_buffer_marker(
+        &mut self,
+        stage: PipelineStage,
+        dst_buffer: &dyn BufferAccess,
+        marker: u32,
+    ) {
+        let dst_buffer_inner = dst_buffer.inner();
+
+        let fns = self.device.fns();
+
+        if self.device.enabled_features().synchronization2 {
+            (fns.khr_synchronization2.cmd_write_buffer_marker2_amd)(
+                self.handle,
+                stage.into(),
+                dst_buffer_inner.buffer.handle(),
+                dst_buffer_inner.offset,
+                marker,
+            );
+        } else {
+            (fns.amd_buffer_marker.cmd_write_buffer_marker_amd)(
+                self.handle,
+                stage.into(),
+                dst_buffer_inner.buffer.handle(),
+                dst_buffer_inner.offset,
+                marker,
+            );
+        }
+    }
+}
+
+/// The highest bit of a checkpoint marker, which is set for automatic checkpoints.
+const AUTOMATIC_CHECKPOINT_BIT: usize = 1 << (usize::BITS - 1);
+
+/// The names of the commands that automatic checkpoints have been inserted for. The marker of
+/// an automatic checkpoint is the index into this list, with `AUTOMATIC_CHECKPOINT_BIT` set.
+static AUTOMATIC_CHECKPOINT_NAMES: Mutex<Vec<&'static str>> = const_mutex(Vec::new());
+
+/// Returns the marker of an automatic checkpoint that is inserted before the command named
+/// `command_name`.
+pub(in crate::command_buffer) fn automatic_checkpoint_marker(command_name: &'static str) -> usize {
+    let mut names = AUTOMATIC_CHECKPOINT_NAMES.lock();
+    let index = names
+        .iter()
+        .position(|&name| name == command_name)
+        .unwrap_or_else(|| {
+            names.push(command_name);
+            names.len() - 1
+        });
+
+    index | AUTOMATIC_CHECKPOINT_BIT
+}
+
+/// A checkpoint that was reached by a queue, as returned by [`QueueGuard::checkpoint_data`].
+///
+/// [`QueueGuard::checkpoint_data`]: crate::device::QueueGuard::checkpoint_data
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The pipeline stages that the checkpoint was reached in.
+    pub stages: PipelineStages,
+
+    /// The marker of the checkpoint.
+    pub marker: CheckpointMarker,
+}
+
+/// The marker of a [`Checkpoint`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckpointMarker {
+    /// A checkpoint inserted with [`AutoCommandBufferBuilder::set_checkpoint`], with the marker
+    /// that was provided.
+    Explicit(usize),
+
+    /// An automatic checkpoint, inserted before the command with the given name.
+    Command(&'static str),
+}
+
+impl CheckpointMarker {
+    pub(crate) fn from_vulkan(marker: *mut c_void) -> Self {
+        let marker = marker as usize;
+
+        if marker & AUTOMATIC_CHECKPOINT_BIT != 0 {
+            let names = AUTOMATIC_CHECKPOINT_NAMES.lock();
+
+            if let Some(&name) = names.get(marker & !AUTOMATIC_CHECKPOINT_BIT) {
+                return Self::Command(name);
+            }
+        }
+
+        Self::Explicit(marker)
+    }
+}
+
+/// Error that can happen when recording a diagnostics command.
+#[derive(Clone, Debug)]
+pub enum DiagnosticsError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The highest bit of the checkpoint marker is set, but it is reserved for automatic
+    /// checkpoints.
+    MarkerReserved,
+
+    /// The provided stage is not supported by the queue family.
+    StageNotSupported,
+
+    /// The destination buffer is missing a required usage.
+    MissingUsage {
+        usage: &'static str,
+    },
+
+    /// The offset of the destination buffer is not a multiple of the required alignment.
+    OffsetNotAligned {
+        offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// The marker would be written outside the destination buffer.
+    OutOfBufferBounds {
+        offset_range_end: DeviceSize,
+        buffer_size: DeviceSize,
+    },
+}
+
+impl Error for DiagnosticsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DiagnosticsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::MarkerReserved => write!(
+                f,
+                "the highest bit of the checkpoint marker is set, but it is reserved for \
+                automatic checkpoints",
+            ),
+            Self::StageNotSupported => {
+                write!(f, "the provided stage is not supported by the queue family")
+            }
+            Self::MissingUsage { usage } => write!(
+                f,
+                "the destination buffer is missing the required usage ({})",
+                usage,
+            ),
+            Self::OffsetNotAligned {
+                offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the offset of the destination buffer ({}) is not a multiple of the required \
+                alignment ({})",
+                offset, required_alignment,
+            ),
+            Self::OutOfBufferBounds {
+                offset_range_end,
+                buffer_size,
+            } => write!(
+                f,
+                "the end of the marker ({}) is greater than the size of the destination buffer \
+                ({})",
+                offset_range_end, buffer_size,
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for DiagnosticsError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+impl From<RequirementNotMet> for DiagnosticsError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
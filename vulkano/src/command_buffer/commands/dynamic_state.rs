@@ -15,12 +15,15 @@ use crate::{
         AutoCommandBufferBuilder,
     },
     device::{DeviceOwned, QueueFlags},
+    image::SampleCount,
     pipeline::{
         graphics::{
             color_blend::LogicOp,
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
+            fragment_shading_rate::{FragmentShadingRate, FragmentShadingRateCombinerOp},
             input_assembly::PrimitiveTopology,
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            multisample::SampleLocationsInfo,
+            rasterization::{CullMode, DepthBias, FrontFace, LineStipple, PolygonMode},
             viewport::{Scissor, Viewport},
         },
         DynamicState,
@@ -57,6 +60,122 @@ where
         Ok(())
     }
 
+    /// Sets whether dynamic alpha-to-coverage is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_alpha_to_coverage_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_coverage_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    pub fn set_alpha_to_coverage_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_alpha_to_coverage_enable(enable).unwrap();
+
+        unsafe {
+            self.inner.set_alpha_to_coverage_enable(enable);
+        }
+
+        self
+    }
+
+    fn validate_set_alpha_to_coverage_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::AlphaToCoverageEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetAlphaToCoverageEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetAlphaToCoverageEnableEXT-extendedDynamicState3AlphaToCoverageEnable-07343
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_coverage_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_alpha_to_coverage_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_alpha_to_coverage_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sets whether dynamic alpha-to-one is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_alpha_to_one_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_one_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - If the [`alpha_to_one`](crate::device::Features::alpha_to_one) feature is not enabled
+    ///   on the device, panics if `enable` is `true`.
+    pub fn set_alpha_to_one_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_alpha_to_one_enable(enable).unwrap();
+
+        unsafe {
+            self.inner.set_alpha_to_one_enable(enable);
+        }
+
+        self
+    }
+
+    fn validate_set_alpha_to_one_enable(&self, enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::AlphaToOneEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-extendedDynamicState3AlphaToOneEnable-07345
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_one_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_alpha_to_one_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_alpha_to_one_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-alphaToOne-07607
+        if enable && !self.device().enabled_features().alpha_to_one {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`enable` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["alpha_to_one"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic blend constants for future draw calls.
     ///
     /// # Panics
@@ -432,6 +551,69 @@ where
         Ok(())
     }
 
+    /// Sets whether dynamic depth clamping is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_depth_clamp_enable`](crate::device::Features::extended_dynamic_state3_depth_clamp_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - If the [`depth_clamp`](crate::device::Features::depth_clamp) feature is not enabled
+    ///   on the device, panics if `enable` is `true`.
+    pub fn set_depth_clamp_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_depth_clamp_enable(enable).unwrap();
+
+        unsafe {
+            self.inner.set_depth_clamp_enable(enable);
+        }
+
+        self
+    }
+
+    fn validate_set_depth_clamp_enable(&self, enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::DepthClampEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetDepthClampEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetDepthClampEnableEXT-extendedDynamicState3DepthClampEnable-07448
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_depth_clamp_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_depth_clamp_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_depth_clamp_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetDepthClampEnableEXT-depthClamp-07449
+        if enable && !self.device().enabled_features().depth_clamp {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`enable` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["depth_clamp"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic depth compare op for future draw calls.
     ///
     /// # Panics
@@ -585,6 +767,72 @@ where
         Ok(())
     }
 
+    /// Sets the device mask for future commands, which determines the physical devices of the
+    /// device group that execute them.
+    ///
+    /// Bit `n` of `device_mask` corresponds to the physical device at index `n` of
+    /// [`Device::physical_devices`](crate::device::Device::physical_devices).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics, compute or
+    ///   transfer operations.
+    /// - Panics if the device API version is less than 1.1 and the
+    ///   [`khr_device_group`](crate::device::DeviceExtensions::khr_device_group) extension is not
+    ///   enabled on the device.
+    /// - Panics if `device_mask` is zero, or contains bits for nonexistent physical devices.
+    pub fn set_device_mask(&mut self, device_mask: u32) -> &mut Self {
+        self.validate_set_device_mask(device_mask).unwrap();
+
+        unsafe {
+            self.inner.set_device_mask(device_mask);
+        }
+
+        self
+    }
+
+    fn validate_set_device_mask(&self, device_mask: u32) -> Result<(), SetDynamicStateError> {
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetDeviceMask-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE | QueueFlags::TRANSFER)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        if !(self.device().api_version() >= Version::V1_1
+            || self.device().enabled_extensions().khr_device_group)
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_device_mask`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_1),
+                    device_extensions: &["khr_device_group"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let physical_device_count = self.device().physical_devices().len() as u32;
+
+        // VUID-vkCmdSetDeviceMask-deviceMask-00108
+        // VUID-vkCmdSetDeviceMask-deviceMask-00109
+        if device_mask == 0 || device_mask as u64 >> physical_device_count != 0 {
+            return Err(SetDynamicStateError::DeviceMaskInvalid {
+                provided: device_mask,
+            });
+        }
+
+        // VUID-vkCmdSetDeviceMask-deviceMask-00110
+        // VUID-vkCmdSetDeviceMask-deviceMask-00111
+        // The initial device mask of the command buffer and the device mask of render passes
+        // always include all physical devices, so these are always met.
+
+        Ok(())
+    }
+
     /// Sets the dynamic discard rectangles for future draw calls.
     ///
     /// # Panics
@@ -664,6 +912,160 @@ where
         Ok(())
     }
 
+    /// Sets the dynamic fragment shading rate for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if none of the
+    ///   [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate),
+    ///   [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    ///   or [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    ///   features are enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the elements of `fragment_size` are not 1, 2 or 4.
+    /// - Panics if `fragment_size` is not `[1, 1]` and the `pipeline_fragment_shading_rate`
+    ///   feature is not enabled on the device.
+    /// - Panics if `combiner_ops[0]` is not [`FragmentShadingRateCombinerOp::Keep`] and the
+    ///   `primitive_fragment_shading_rate` feature is not enabled on the device.
+    /// - Panics if `combiner_ops[1]` is not [`FragmentShadingRateCombinerOp::Keep`] and the
+    ///   `attachment_fragment_shading_rate` feature is not enabled on the device.
+    /// - Panics if an element of `combiner_ops` is not `Keep` or `Replace` and the
+    ///   [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    ///   device property is not `true`.
+    pub fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        self.validate_set_fragment_shading_rate(fragment_size, combiner_ops)
+            .unwrap();
+
+        unsafe {
+            self.inner
+                .set_fragment_shading_rate(fragment_size, combiner_ops);
+        }
+
+        self
+    }
+
+    fn validate_set_fragment_shading_rate(
+        &self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::FragmentShadingRate)?;
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-combinerOps-parameter
+        for combiner_op in combiner_ops {
+            combiner_op.validate_device(self.device())?;
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        let device = self.device();
+        let features = device.enabled_features();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04509
+        if !(features.pipeline_fragment_shading_rate
+            || features.primitive_fragment_shading_rate
+            || features.attachment_fragment_shading_rate)
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_fragment_shading_rate`",
+                requires_one_of: RequiresOneOf {
+                    features: &[
+                        "pipeline_fragment_shading_rate",
+                        "primitive_fragment_shading_rate",
+                        "attachment_fragment_shading_rate",
+                    ],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04513
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04514
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04515
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04516
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04517
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04518
+        if !fragment_size
+            .iter()
+            .all(|&size| size.is_power_of_two() && size <= 4)
+        {
+            return Err(
+                SetDynamicStateError::FragmentShadingRateFragmentSizeInvalid {
+                    provided: fragment_size,
+                },
+            );
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04507
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04508
+        if fragment_size != [1, 1] && !features.pipeline_fragment_shading_rate {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`fragment_size` is not `[1, 1]`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pipeline_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-primitiveFragmentShadingRate-04510
+        if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+            && !features.primitive_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`combiner_ops[0]` is not `FragmentShadingRateCombinerOp::Keep`",
+                requires_one_of: RequiresOneOf {
+                    features: &["primitive_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-attachmentFragmentShadingRate-04511
+        if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+            && !features.attachment_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`combiner_ops[1]` is not `FragmentShadingRateCombinerOp::Keep`",
+                requires_one_of: RequiresOneOf {
+                    features: &["attachment_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-fragmentSizeNonTrivialCombinerOps-04512
+        if !device
+            .physical_device()
+            .properties()
+            .fragment_shading_rate_non_trivial_combiner_ops
+            .unwrap_or(false)
+            && combiner_ops.iter().any(|combiner_op| {
+                !matches!(
+                    combiner_op,
+                    FragmentShadingRateCombinerOp::Keep | FragmentShadingRateCombinerOp::Replace
+                )
+            })
+        {
+            return Err(SetDynamicStateError::FragmentShadingRateNonTrivialCombinerOpsNotSupported);
+        }
+
+        Ok(())
+    }
+
     /// Sets the dynamic front face for future draw calls.
     ///
     /// # Panics
@@ -943,6 +1345,77 @@ where
         Ok(())
     }
 
+    /// Sets the dynamic polygon mode for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_polygon_mode`](crate::device::Features::extended_dynamic_state3_polygon_mode)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - If the [`fill_mode_non_solid`](crate::device::Features::fill_mode_non_solid) feature is
+    ///   not enabled on the device, panics if `polygon_mode` is not `PolygonMode::Fill`.
+    pub fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        self.validate_set_polygon_mode(polygon_mode).unwrap();
+
+        unsafe {
+            self.inner.set_polygon_mode(polygon_mode);
+        }
+
+        self
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::PolygonMode)?;
+
+        // VUID-vkCmdSetPolygonModeEXT-polygonMode-parameter
+        polygon_mode.validate_device(self.device())?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetPolygonModeEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetPolygonModeEXT-extendedDynamicState3PolygonMode-07422
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_polygon_mode
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_polygon_mode`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_polygon_mode"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetPolygonModeEXT-fillModeNonSolid-07424
+        if polygon_mode != PolygonMode::Fill
+            && !self.device().enabled_features().fill_mode_non_solid
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`polygon_mode` is not `PolygonMode::Fill`",
+                requires_one_of: RequiresOneOf {
+                    features: &["fill_mode_non_solid"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
     /// Sets whether dynamic primitive restart is enabled for future draw calls.
     ///
     /// # Panics
@@ -1100,34 +1573,92 @@ where
         Ok(())
     }
 
-    /// Sets whether dynamic rasterizer discard is enabled for future draw calls.
+    /// Sets whether dynamic rasterizer discard is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the device API version is less than 1.3 and the
+    ///   [`extended_dynamic_state2`](crate::device::Features::extended_dynamic_state2) feature is
+    ///   not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    pub fn set_rasterizer_discard_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_rasterizer_discard_enable(enable).unwrap();
+
+        unsafe {
+            self.inner.set_rasterizer_discard_enable(enable);
+        }
+
+        self
+    }
+
+    fn validate_set_rasterizer_discard_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::RasterizerDiscardEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetRasterizerDiscardEnable-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetRasterizerDiscardEnable-None-04871
+        if !(self.device().api_version() >= Version::V1_3
+            || self.device().enabled_features().extended_dynamic_state2)
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::set_rasterizer_discard_enable`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_3),
+                    features: &["extended_dynamic_state2"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Sets the dynamic sample locations for future draw calls.
     ///
     /// # Panics
     ///
     /// - Panics if the queue family of the command buffer does not support graphics operations.
-    /// - Panics if the device API version is less than 1.3 and the
-    ///   [`extended_dynamic_state2`](crate::device::Features::extended_dynamic_state2) feature is
-    ///   not enabled on the device.
+    /// - Panics if the
+    ///   [`ext_sample_locations`](crate::device::DeviceExtensions::ext_sample_locations)
+    ///   extension is not enabled on the device.
     /// - Panics if the currently bound graphics pipeline already contains this state internally.
-    pub fn set_rasterizer_discard_enable(&mut self, enable: bool) -> &mut Self {
-        self.validate_set_rasterizer_discard_enable(enable).unwrap();
+    /// - Panics if `sample_locations.sample_locations_per_pixel` is not included in the
+    ///   [`sample_location_sample_counts`](crate::device::Properties::sample_location_sample_counts)
+    ///   device property.
+    /// - Panics if the number of elements in `sample_locations.sample_locations` does not match
+    ///   the sample count and grid size.
+    pub fn set_sample_locations(&mut self, sample_locations: SampleLocationsInfo) -> &mut Self {
+        self.validate_set_sample_locations(&sample_locations)
+            .unwrap();
 
         unsafe {
-            self.inner.set_rasterizer_discard_enable(enable);
+            self.inner.set_sample_locations(sample_locations);
         }
 
         self
     }
 
-    fn validate_set_rasterizer_discard_enable(
+    fn validate_set_sample_locations(
         &self,
-        _enable: bool,
+        sample_locations: &SampleLocationsInfo,
     ) -> Result<(), SetDynamicStateError> {
-        self.validate_pipeline_fixed_state(DynamicState::RasterizerDiscardEnable)?;
+        self.validate_pipeline_fixed_state(DynamicState::SampleLocations)?;
 
         let queue_family_properties = self.queue_family_properties();
 
-        // VUID-vkCmdSetRasterizerDiscardEnable-commandBuffer-cmdpool
+        // VUID-vkCmdSetSampleLocationsEXT-commandBuffer-cmdpool
         if !queue_family_properties
             .queue_flags
             .intersects(QueueFlags::GRAPHICS)
@@ -1135,20 +1666,54 @@ where
             return Err(SetDynamicStateError::NotSupportedByQueueFamily);
         }
 
-        // VUID-vkCmdSetRasterizerDiscardEnable-None-04871
-        if !(self.device().api_version() >= Version::V1_3
-            || self.device().enabled_features().extended_dynamic_state2)
-        {
+        if !self.device().enabled_extensions().ext_sample_locations {
             return Err(SetDynamicStateError::RequirementNotMet {
-                required_for: "`AutoCommandBufferBuilder::set_rasterizer_discard_enable`",
+                required_for: "`AutoCommandBufferBuilder::set_sample_locations`",
                 requires_one_of: RequiresOneOf {
-                    api_version: Some(Version::V1_3),
-                    features: &["extended_dynamic_state2"],
+                    device_extensions: &["ext_sample_locations"],
                     ..Default::default()
                 },
             });
         }
 
+        let &SampleLocationsInfo {
+            sample_locations_per_pixel,
+            sample_location_grid_size,
+            ref sample_locations,
+        } = sample_locations;
+
+        // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-parameter
+        sample_locations_per_pixel.validate_device(self.device())?;
+
+        // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-01526
+        if !self
+            .device()
+            .physical_device()
+            .properties()
+            .sample_location_sample_counts
+            .unwrap_or_default()
+            .contains_enum(sample_locations_per_pixel)
+        {
+            return Err(SetDynamicStateError::SampleLocationsPerPixelNotSupported {
+                provided: sample_locations_per_pixel,
+            });
+        }
+
+        // VUID-VkSampleLocationsInfoEXT-sampleLocationsCount-01527
+        let required = sample_locations_per_pixel as u32
+            * sample_location_grid_size[0]
+            * sample_location_grid_size[1];
+
+        if sample_locations.len() as u32 != required {
+            return Err(SetDynamicStateError::SampleLocationsCountMismatch {
+                provided: sample_locations.len() as u32,
+                required,
+            });
+        }
+
+        // TODO:
+        // VUID-vkCmdSetSampleLocationsEXT-variableSampleLocations-01530
+
         Ok(())
     }
 
@@ -1724,6 +2289,48 @@ where
 }
 
 impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdSetAlphaToCoverageEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_alpha_to_coverage_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_alpha_to_coverage_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_alpha_to_coverage_enable(self.enable);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { enable }));
+        self.current_state.alpha_to_coverage_enable = Some(enable);
+    }
+
+    /// Calls `vkCmdSetAlphaToOneEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_alpha_to_one_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_alpha_to_one_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_alpha_to_one_enable(self.enable);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { enable }));
+        self.current_state.alpha_to_one_enable = Some(enable);
+    }
+
     /// Calls `vkCmdSetBlendConstants` on the builder.
     #[inline]
     pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
@@ -1890,6 +2497,27 @@ impl SyncCommandBufferBuilder {
         self.current_state.depth_bounds_test_enable = Some(enable);
     }
 
+    /// Calls `vkCmdSetDepthClampEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_clamp_enable(&mut self, enable: bool) {
+        struct Cmd {
+            enable: bool,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_depth_clamp_enable"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_depth_clamp_enable(self.enable);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { enable }));
+        self.current_state.depth_clamp_enable = Some(enable);
+    }
+
     /// Calls `vkCmdSetDepthCompareOpEXT` on the builder.
     #[inline]
     pub unsafe fn set_depth_compare_op(&mut self, compare_op: CompareOp) {
@@ -1953,6 +2581,26 @@ impl SyncCommandBufferBuilder {
         self.current_state.depth_write_enable = Some(enable);
     }
 
+    /// Calls `vkCmdSetDeviceMask` on the builder.
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, device_mask: u32) {
+        struct Cmd {
+            device_mask: u32,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_device_mask"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_device_mask(self.device_mask);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { device_mask }));
+    }
+
     /// Calls `vkCmdSetDiscardRectangle` on the builder.
     ///
     /// If the list is empty then the command is automatically ignored.
@@ -1989,6 +2637,38 @@ impl SyncCommandBufferBuilder {
         }));
     }
 
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) {
+        struct Cmd {
+            fragment_size: [u32; 2],
+            combiner_ops: [FragmentShadingRateCombinerOp; 2],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_fragment_shading_rate"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_fragment_shading_rate(self.fragment_size, self.combiner_ops);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            fragment_size,
+            combiner_ops,
+        }));
+        self.current_state.fragment_shading_rate = Some(FragmentShadingRate {
+            fragment_size,
+            combiner_ops,
+        });
+    }
+
     /// Calls `vkCmdSetFrontFaceEXT` on the builder.
     #[inline]
     pub unsafe fn set_front_face(&mut self, face: FrontFace) {
@@ -2095,6 +2775,27 @@ impl SyncCommandBufferBuilder {
         self.current_state.patch_control_points = Some(num);
     }
 
+    /// Calls `vkCmdSetPolygonModeEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) {
+        struct Cmd {
+            polygon_mode: PolygonMode,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_polygon_mode"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_polygon_mode(self.polygon_mode);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { polygon_mode }));
+        self.current_state.polygon_mode = Some(polygon_mode);
+    }
+
     /// Calls `vkCmdSetPrimitiveRestartEnableEXT` on the builder.
     #[inline]
     pub unsafe fn set_primitive_restart_enable(&mut self, enable: bool) {
@@ -2158,6 +2859,27 @@ impl SyncCommandBufferBuilder {
         self.current_state.rasterizer_discard_enable = Some(enable);
     }
 
+    /// Calls `vkCmdSetSampleLocationsEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_sample_locations(&mut self, sample_locations: SampleLocationsInfo) {
+        struct Cmd {
+            sample_locations: SampleLocationsInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "set_sample_locations"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.set_sample_locations(&self.sample_locations);
+            }
+        }
+
+        self.current_state.sample_locations = Some(sample_locations.clone());
+        self.commands.push(Box::new(Cmd { sample_locations }));
+    }
+
     /// Calls `vkCmdSetStencilCompareMask` on the builder.
     #[inline]
     pub unsafe fn set_stencil_compare_mask(&mut self, faces: StencilFaces, compare_mask: u32) {
@@ -2465,6 +3187,36 @@ impl SyncCommandBufferBuilder {
 }
 
 impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdSetAlphaToCoverageEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_alpha_to_coverage_enable(&mut self, enable: bool) {
+        debug_assert!(self.device.enabled_extensions().ext_extended_dynamic_state3);
+        debug_assert!(
+            self.device
+                .enabled_features()
+                .extended_dynamic_state3_alpha_to_coverage_enable
+        );
+        let fns = self.device.fns();
+
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_alpha_to_coverage_enable_ext)(self.handle, enable.into());
+    }
+
+    /// Calls `vkCmdSetAlphaToOneEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_alpha_to_one_enable(&mut self, enable: bool) {
+        debug_assert!(self.device.enabled_extensions().ext_extended_dynamic_state3);
+        debug_assert!(
+            self.device
+                .enabled_features()
+                .extended_dynamic_state3_alpha_to_one_enable
+        );
+        let fns = self.device.fns();
+
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_alpha_to_one_enable_ext)(self.handle, enable.into());
+    }
+
     /// Calls `vkCmdSetBlendConstants` on the builder.
     #[inline]
     pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
@@ -2549,6 +3301,21 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdSetDepthClampEnableEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_depth_clamp_enable(&mut self, enable: bool) {
+        debug_assert!(self.device.enabled_extensions().ext_extended_dynamic_state3);
+        debug_assert!(
+            self.device
+                .enabled_features()
+                .extended_dynamic_state3_depth_clamp_enable
+        );
+        let fns = self.device.fns();
+
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_depth_clamp_enable_ext)(self.handle, enable.into());
+    }
+
     /// Calls `vkCmdSetDepthCompareOpEXT` on the builder.
     #[inline]
     pub unsafe fn set_depth_compare_op(&mut self, compare_op: CompareOp) {
@@ -2595,6 +3362,19 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdSetDeviceMask` on the builder.
+    #[inline]
+    pub unsafe fn set_device_mask(&mut self, device_mask: u32) {
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_1 {
+            (fns.v1_1.cmd_set_device_mask)(self.handle, device_mask);
+        } else {
+            debug_assert!(self.device.enabled_extensions().khr_device_group);
+            (fns.khr_device_group.cmd_set_device_mask_khr)(self.handle, device_mask);
+        }
+    }
+
     /// Calls `vkCmdSetDiscardRectangleEXT` on the builder.
     ///
     /// If the list is empty then the command is automatically ignored.
@@ -2632,6 +3412,29 @@ impl UnsafeCommandBufferBuilder {
         );
     }
 
+    /// Calls `vkCmdSetFragmentShadingRateKHR` on the builder.
+    #[inline]
+    pub unsafe fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) {
+        debug_assert!(self.device.enabled_extensions().khr_fragment_shading_rate);
+
+        let fragment_size_vk = ash::vk::Extent2D {
+            width: fragment_size[0],
+            height: fragment_size[1],
+        };
+        let combiner_ops_vk: [ash::vk::FragmentShadingRateCombinerOpKHR; 2] =
+            combiner_ops.map(Into::into);
+
+        let fns = self.device.fns();
+        (fns.khr_fragment_shading_rate
+            .cmd_set_fragment_shading_rate_khr)(
+            self.handle, &fragment_size_vk, &combiner_ops_vk
+        );
+    }
+
     /// Calls `vkCmdSetFrontFaceEXT` on the builder.
     #[inline]
     pub unsafe fn set_front_face(&mut self, face: FrontFace) {
@@ -2683,6 +3486,23 @@ impl UnsafeCommandBufferBuilder {
             .cmd_set_patch_control_points_ext)(self.handle, num);
     }
 
+    /// Calls `vkCmdSetPolygonModeEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) {
+        debug_assert!(self.device.enabled_extensions().ext_extended_dynamic_state3);
+        debug_assert!(
+            self.device
+                .enabled_features()
+                .extended_dynamic_state3_polygon_mode
+        );
+        let fns = self.device.fns();
+
+        (fns.ext_extended_dynamic_state3.cmd_set_polygon_mode_ext)(
+            self.handle,
+            polygon_mode.into(),
+        );
+    }
+
     /// Calls `vkCmdSetPrimitiveRestartEnableEXT` on the builder.
     #[inline]
     pub unsafe fn set_primitive_restart_enable(&mut self, enable: bool) {
@@ -2725,6 +3545,40 @@ impl UnsafeCommandBufferBuilder {
         }
     }
 
+    /// Calls `vkCmdSetSampleLocationsEXT` on the builder.
+    #[inline]
+    pub unsafe fn set_sample_locations(&mut self, sample_locations: &SampleLocationsInfo) {
+        debug_assert!(self.device.enabled_extensions().ext_sample_locations);
+
+        let &SampleLocationsInfo {
+            sample_locations_per_pixel,
+            sample_location_grid_size,
+            ref sample_locations,
+        } = sample_locations;
+
+        let sample_locations_vk: SmallVec<[_; 4]> = sample_locations
+            .iter()
+            .map(|&[x, y]| ash::vk::SampleLocationEXT { x, y })
+            .collect();
+
+        let sample_locations_info = ash::vk::SampleLocationsInfoEXT {
+            sample_locations_per_pixel: sample_locations_per_pixel.into(),
+            sample_location_grid_size: ash::vk::Extent2D {
+                width: sample_location_grid_size[0],
+                height: sample_location_grid_size[1],
+            },
+            sample_locations_count: sample_locations_vk.len() as u32,
+            p_sample_locations: sample_locations_vk.as_ptr(),
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.ext_sample_locations.cmd_set_sample_locations_ext)(
+            self.handle,
+            &sample_locations_info,
+        );
+    }
+
     /// Calls `vkCmdSetStencilCompareMask` on the builder.
     #[inline]
     pub unsafe fn set_stencil_compare_mask(&mut self, face_mask: StencilFaces, compare_mask: u32) {
@@ -2918,9 +3772,21 @@ pub(in super::super) enum SetDynamicStateError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// The provided `device_mask` is zero, or contains bits for physical devices that are not
+    /// part of the device.
+    DeviceMaskInvalid { provided: u32 },
+
     /// The provided `factor` is not between 1 and 256 inclusive.
     FactorOutOfRange,
 
+    /// The provided `fragment_size` does not have elements that are 1, 2 or 4.
+    FragmentShadingRateFragmentSizeInvalid { provided: [u32; 2] },
+
+    /// A provided combiner operation is not `Keep` or `Replace`, but the
+    /// [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    /// device property is not `true`.
+    FragmentShadingRateNonTrivialCombinerOpsNotSupported,
+
     /// The [`max_discard_rectangles`](crate::device::Properties::max_discard_rectangles)
     /// limit has been exceeded.
     MaxDiscardRectanglesExceeded { provided: u32, max: u32 },
@@ -2946,6 +3812,15 @@ pub(in super::super) enum SetDynamicStateError {
     /// The currently bound pipeline contains this state as internally fixed state, which cannot be
     /// overridden with dynamic state.
     PipelineHasFixedState,
+
+    /// The number of provided sample locations does not equal the number required by the sample
+    /// count and grid size.
+    SampleLocationsCountMismatch { provided: u32, required: u32 },
+
+    /// The provided sample count is not included in the
+    /// [`sample_location_sample_counts`](crate::device::Properties::sample_location_sample_counts)
+    /// device property.
+    SampleLocationsPerPixelNotSupported { provided: SampleCount },
 }
 
 impl Error for SetDynamicStateError {}
@@ -2961,10 +3836,26 @@ impl Display for SetDynamicStateError {
                 "a requirement was not met for: {}; requires one of: {}",
                 required_for, requires_one_of,
             ),
+            Self::DeviceMaskInvalid { provided } => write!(
+                f,
+                "the provided `device_mask` ({:#b}) is zero, or contains bits for physical devices \
+                that are not part of the device",
+                provided,
+            ),
             Self::FactorOutOfRange => write!(
                 f,
                 "the provided `factor` is not between 1 and 256 inclusive",
             ),
+            Self::FragmentShadingRateFragmentSizeInvalid { provided } => write!(
+                f,
+                "the provided `fragment_size` ({:?}) does not have elements that are 1, 2 or 4",
+                provided,
+            ),
+            Self::FragmentShadingRateNonTrivialCombinerOpsNotSupported => write!(
+                f,
+                "a provided combiner operation is not `Keep` or `Replace`, but the \
+                `fragment_shading_rate_non_trivial_combiner_ops` device property is not `true`",
+            ),
             Self::MaxDiscardRectanglesExceeded { .. } => {
                 write!(f, "the `max_discard_rectangles` limit has been exceeded")
             }
@@ -2992,6 +3883,18 @@ impl Display for SetDynamicStateError {
                 "the currently bound pipeline contains this state as internally fixed state, which \
                 cannot be overridden with dynamic state",
             ),
+            Self::SampleLocationsCountMismatch { provided, required } => write!(
+                f,
+                "the number of provided sample locations ({}) does not equal the number required \
+                by the sample count and grid size ({})",
+                provided, required,
+            ),
+            Self::SampleLocationsPerPixelNotSupported { provided } => write!(
+                f,
+                "the provided sample count ({:?}) is not included in the \
+                `sample_location_sample_counts` device property",
+                provided,
+            ),
         }
     }
 }
@@ -3004,3 +3907,128 @@ impl From<RequirementNotMet> for SetDynamicStateError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_buffer::{
+        allocator::StandardCommandBufferAllocator, CommandBufferUsage, PrimaryAutoCommandBuffer,
+    };
+
+    fn command_buffer_builder(
+        allocator: &StandardCommandBufferAllocator,
+        queue_family_index: u32,
+    ) -> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandBufferAllocator> {
+        AutoCommandBufferBuilder::primary(
+            allocator,
+            queue_family_index,
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn set_depth_clamp_enable_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+
+        match cbb.validate_set_depth_clamp_enable(false) {
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"extended_dynamic_state3_depth_clamp_enable") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn set_depth_clamp_enable_requires_depth_clamp() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_extended_dynamic_state3],
+            features: [extended_dynamic_state3_depth_clamp_enable]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+
+        assert!(cbb.validate_set_depth_clamp_enable(false).is_ok());
+
+        match cbb.validate_set_depth_clamp_enable(true) {
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"depth_clamp") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn set_polygon_mode_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+
+        match cbb.validate_set_polygon_mode(PolygonMode::Fill) {
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"extended_dynamic_state3_polygon_mode") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn set_polygon_mode_requires_fill_mode_non_solid() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_extended_dynamic_state3],
+            features: [extended_dynamic_state3_polygon_mode]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+
+        assert!(cbb.validate_set_polygon_mode(PolygonMode::Fill).is_ok());
+
+        match cbb.validate_set_polygon_mode(PolygonMode::Line) {
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"fill_mode_non_solid") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn set_alpha_to_coverage_enable_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+
+        match cbb.validate_set_alpha_to_coverage_enable(true) {
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"extended_dynamic_state3_alpha_to_coverage_enable") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn set_alpha_to_one_enable_requires_alpha_to_one() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_extended_dynamic_state3],
+            features: [extended_dynamic_state3_alpha_to_one_enable]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+
+        assert!(cbb.validate_set_alpha_to_one_enable(false).is_ok());
+
+        match cbb.validate_set_alpha_to_one_enable(true) {
+            Err(SetDynamicStateError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"alpha_to_one") => (),
+            _ => panic!(),
+        }
+    }
+}
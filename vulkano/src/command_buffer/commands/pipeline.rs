@@ -30,6 +30,7 @@ use crate::{
             render_pass::PipelineRenderPassType,
             vertex_input::VertexInputRate,
         },
+        ray_tracing::{ShaderBindingTable, StridedDeviceAddressRegion},
         DynamicState, GraphicsPipeline, PartialStateMode, Pipeline, PipelineLayout,
     },
     sampler::{Sampler, SamplerImageViewIncompatibleError},
@@ -519,6 +520,99 @@ where
         Ok(())
     }
 
+    /// Traces rays using a ray tracing pipeline, with one ray generation shader invocation for
+    /// each element of `dimensions`.
+    ///
+    /// A ray tracing pipeline must have been bound using
+    /// [`bind_pipeline_ray_tracing`](Self::bind_pipeline_ray_tracing), and `shader_binding_table`
+    /// must have been created for that pipeline. Any resources used by the ray tracing pipeline,
+    /// such as descriptor sets, must have been set beforehand.
+    pub fn trace_rays(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) -> Result<&mut Self, PipelineExecutionError> {
+        self.validate_trace_rays(shader_binding_table, dimensions)?;
+
+        unsafe {
+            self.inner.trace_rays(shader_binding_table, dimensions)?;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_trace_rays(
+        &self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) -> Result<(), PipelineExecutionError> {
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdTraceRaysKHR-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(PipelineExecutionError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdTraceRaysKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(PipelineExecutionError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdTraceRaysKHR-None-02700
+        let pipeline = match self.state().pipeline_ray_tracing() {
+            Some(x) => x,
+            None => return Err(PipelineExecutionError::PipelineNotBound),
+        };
+
+        // VUID-vkCmdTraceRaysKHR-pRayGenShaderBindingTable-03681
+        // VUID-vkCmdTraceRaysKHR-pMissShaderBindingTable-03683
+        // VUID-vkCmdTraceRaysKHR-pHitShaderBindingTable-03687
+        // VUID-vkCmdTraceRaysKHR-pCallableShaderBindingTable-03691
+        if shader_binding_table.pipeline() != pipeline {
+            return Err(PipelineExecutionError::ShaderBindingTablePipelineMismatch);
+        }
+
+        self.validate_pipeline_descriptor_sets(pipeline.as_ref())?;
+        self.validate_pipeline_push_constants(pipeline.layout())?;
+
+        let properties = self.device().physical_device().properties();
+        let max_count = properties.max_compute_work_group_count;
+        let max_size = properties.max_compute_work_group_size;
+        let max = [
+            max_count[0].saturating_mul(max_size[0]),
+            max_count[1].saturating_mul(max_size[1]),
+            max_count[2].saturating_mul(max_size[2]),
+        ];
+
+        // VUID-vkCmdTraceRaysKHR-width-03638
+        // VUID-vkCmdTraceRaysKHR-height-03639
+        // VUID-vkCmdTraceRaysKHR-depth-03640
+        if dimensions[0] > max[0] || dimensions[1] > max[1] || dimensions[2] > max[2] {
+            return Err(PipelineExecutionError::MaxRayDispatchSizeExceeded {
+                requested: dimensions,
+                max,
+            });
+        }
+
+        let invocation_count = dimensions.iter().map(|&d| d as u64).product::<u64>();
+        let max_invocation_count = properties.max_ray_dispatch_invocation_count.unwrap_or(0);
+
+        // VUID-vkCmdTraceRaysKHR-width-03641
+        if invocation_count > max_invocation_count as u64 {
+            return Err(
+                PipelineExecutionError::MaxRayDispatchInvocationCountExceeded {
+                    requested: invocation_count,
+                    max: max_invocation_count,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
     fn validate_index_buffer(
         &self,
         indices: Option<(u32, u32)>,
@@ -572,6 +666,7 @@ where
             set_num: u32,
             binding_num: u32,
             binding_reqs: &DescriptorBindingRequirements,
+            partially_bound: bool,
             elements: &[Option<T>],
             mut extra_check: impl FnMut(u32, &T) -> Result<(), DescriptorResourceInvalidError>,
         ) -> Result<(), PipelineExecutionError> {
@@ -599,6 +694,9 @@ where
                 // VUID-vkCmdDispatch-None-02699
                 let element = match element {
                     Some(x) => x,
+                    // With a partially bound binding, only descriptors that are dynamically used
+                    // must be valid, which can't be known here.
+                    None if partially_bound => continue,
                     None => {
                         return Err(PipelineExecutionError::DescriptorResourceInvalid {
                             set_num,
@@ -644,8 +742,21 @@ where
         }
 
         for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
-            let layout_binding =
-                &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
+            let set_layout = &pipeline.layout().set_layouts()[set_num as usize];
+            let layout_binding = &set_layout.bindings()[&binding_num];
+
+            // The contents of descriptor buffers are not known, so only check that an offset has
+            // been set.
+            if set_layout.descriptor_buffer() {
+                if current_state
+                    .descriptor_buffer_offset(pipeline.bind_point(), set_num)
+                    .is_none()
+                {
+                    return Err(PipelineExecutionError::DescriptorSetNotBound { set_num });
+                }
+
+                continue;
+            }
 
             let check_buffer = |_index: u32, _buffer: &Arc<dyn BufferAccess>| Ok(());
 
@@ -874,15 +985,18 @@ where
                     // If the image view isn't actually present in the resources, then just skip it.
                     // It will be caught later by check_resources.
                     let iter = desc_reqs.sampler_with_images.iter().filter_map(|id| {
-                        current_state
-                            .descriptor_set(pipeline.bind_point(), id.set)
-                            .and_then(|set| set.resources().binding(id.binding))
-                            .and_then(|res| match res {
-                                DescriptorBindingResources::ImageView(elements) => elements
-                                    .get(id.index as usize)
-                                    .and_then(|opt| opt.as_ref().map(|opt| (id, opt))),
-                                _ => None,
-                            })
+                        let set_resources = current_state
+                            .descriptor_set(pipeline.bind_point(), id.set)?
+                            .resources();
+
+                        let image_view = match set_resources.binding(id.binding)? {
+                            DescriptorBindingResources::ImageView(elements) => {
+                                elements.get(id.index as usize)?.clone()
+                            }
+                            _ => None,
+                        };
+
+                        image_view.map(|image_view| (id, image_view))
                     });
 
                     for (id, image_view) in iter {
@@ -919,16 +1033,31 @@ where
 
             match binding_resources {
                 DescriptorBindingResources::None(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_none)?;
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        check_none,
+                    )?;
                 }
                 DescriptorBindingResources::Buffer(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_buffer)?;
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        check_buffer,
+                    )?;
                 }
                 DescriptorBindingResources::BufferView(elements) => {
                     validate_resources(
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_buffer_view,
                     )?;
@@ -938,6 +1067,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_image_view,
                     )?;
@@ -947,6 +1077,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_image_view_sampler,
                     )?;
@@ -956,10 +1087,21 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_sampler,
                     )?;
                 }
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        |_, _| Ok(()),
+                    )?;
+                }
             }
         }
 
@@ -1020,6 +1162,18 @@ where
             .map(|(s, _)| s)
         {
             match dynamic_state {
+                DynamicState::AlphaToCoverageEnable => {
+                    // VUID-vkCmdDraw-None-07624
+                    if current_state.alpha_to_coverage_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::AlphaToOneEnable => {
+                    // VUID-vkCmdDraw-None-07625
+                    if current_state.alpha_to_one_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::BlendConstants => {
                     // VUID?
                     if current_state.blend_constants().is_none() {
@@ -1078,6 +1232,12 @@ where
                         return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                     }
                 }
+                DynamicState::DepthClampEnable => {
+                    // VUID-vkCmdDraw-None-07620
+                    if current_state.depth_clamp_enable().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::DepthCompareOp => {
                     // VUID?
                     if current_state.depth_compare_op().is_none() {
@@ -1113,7 +1273,12 @@ where
                     }
                 }
                 DynamicState::ExclusiveScissor => todo!(),
-                DynamicState::FragmentShadingRate => todo!(),
+                DynamicState::FragmentShadingRate => {
+                    // VUID?
+                    if current_state.fragment_shading_rate().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::FrontFace => {
                     // VUID?
                     if current_state.front_face().is_none() {
@@ -1144,6 +1309,12 @@ where
                         return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                     }
                 }
+                DynamicState::PolygonMode => {
+                    // VUID-vkCmdDraw-None-07621
+                    if current_state.polygon_mode().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::PrimitiveRestartEnable => {
                     // VUID-vkCmdDraw-None-04879
                     let primitive_restart_enable =
@@ -1259,7 +1430,12 @@ where
                 DynamicState::RayTracingPipelineStackSize => unreachable!(
                     "RayTracingPipelineStackSize dynamic state should not occur on a graphics pipeline"
                 ),
-                DynamicState::SampleLocations => todo!(),
+                DynamicState::SampleLocations => {
+                    // VUID?
+                    if current_state.sample_locations().is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::Scissor => {
                     for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
                         // VUID?
@@ -1389,12 +1565,8 @@ where
                 }
                 DynamicState::ViewportWScaling => todo!(),
                 DynamicState::TessellationDomainOrigin => todo!(),
-                DynamicState::DepthClampEnable => todo!(),
-                DynamicState::PolygonMode => todo!(),
                 DynamicState::RasterizationSamples => todo!(),
                 DynamicState::SampleMask => todo!(),
-                DynamicState::AlphaToCoverageEnable => todo!(),
-                DynamicState::AlphaToOneEnable => todo!(),
                 DynamicState::LogicOpEnable => todo!(),
                 DynamicState::ColorBlendEnable => todo!(),
                 DynamicState::ColorBlendEquation => todo!(),
@@ -2018,6 +2190,86 @@ impl SyncCommandBufferBuilder {
         Ok(())
     }
 
+    /// Calls `vkCmdTraceRaysKHR` on the builder.
+    #[inline]
+    pub unsafe fn trace_rays(
+        &mut self,
+        shader_binding_table: &ShaderBindingTable,
+        dimensions: [u32; 3],
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            raygen: StridedDeviceAddressRegion,
+            miss: StridedDeviceAddressRegion,
+            hit: StridedDeviceAddressRegion,
+            callable: StridedDeviceAddressRegion,
+            dimensions: [u32; 3],
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "trace_rays"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.trace_rays(
+                    &self.raygen,
+                    &self.miss,
+                    &self.hit,
+                    &self.callable,
+                    self.dimensions,
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "trace_rays";
+        let pipeline = self
+            .current_state
+            .pipeline_ray_tracing
+            .as_ref()
+            .unwrap()
+            .as_ref();
+        let buffer: Arc<dyn BufferAccess> = shader_binding_table.buffer().clone();
+
+        let mut resources = Vec::new();
+        self.add_descriptor_sets(&mut resources, command_index, command_name, pipeline);
+        resources.push((
+            ResourceUseRef {
+                command_index,
+                command_name,
+                resource_in_command: ResourceInCommand::ShaderBindingTable,
+                secondary_use_ref: None,
+            },
+            Resource::Buffer {
+                buffer: buffer.clone(),
+                range: 0..buffer.size(),
+                memory: PipelineMemoryAccess {
+                    stages: PipelineStages::RAY_TRACING_SHADER,
+                    access: AccessFlags::SHADER_READ,
+                    exclusive: false,
+                },
+            },
+        ));
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            raygen: *shader_binding_table.raygen(),
+            miss: *shader_binding_table.miss(),
+            hit: *shader_binding_table.hit(),
+            callable: *shader_binding_table.callable(),
+            dimensions,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
     fn add_descriptor_sets<Pl: Pipeline>(
         &self,
         resources: &mut Vec<(ResourceUseRef, Resource)>,
@@ -2034,14 +2286,34 @@ impl SyncCommandBufferBuilder {
             None => return,
         };
 
+        // The shader stages that read from each bound descriptor buffer.
+        let mut descriptor_buffer_stages =
+            vec![PipelineStages::empty(); self.current_state.descriptor_buffers.len()];
+
         for (&(set, binding), binding_reqs) in pipeline.descriptor_binding_requirements() {
             // TODO: Can things be refactored so that the pipeline layout isn't needed at all?
-            let descriptor_type = descriptor_sets_state.pipeline_layout.set_layouts()[set as usize]
-                .bindings()[&binding]
-                .descriptor_type;
+            let set_layout = &descriptor_sets_state.pipeline_layout.set_layouts()[set as usize];
+            let descriptor_type = set_layout.bindings()[&binding].descriptor_type;
+
+            // The resources referred to by a descriptor buffer are not tracked, but the buffer
+            // itself is read.
+            if set_layout.descriptor_buffer() {
+                if let Some(stages) = descriptor_sets_state
+                    .descriptor_buffer_offsets
+                    .get(&set)
+                    .and_then(|&(buffer_index, _)| {
+                        descriptor_buffer_stages.get_mut(buffer_index as usize)
+                    })
+                {
+                    *stages |= binding_reqs.stages.into();
+                }
+
+                continue;
+            }
 
             let (access_read, access_write) = match descriptor_type {
-                DescriptorType::Sampler => continue,
+                // Inline uniform blocks are stored in the descriptor set itself.
+                DescriptorType::Sampler | DescriptorType::InlineUniformBlock => continue,
                 DescriptorType::InputAttachment => {
                     // FIXME: This is tricky. Since we read from the input attachment
                     // and this input attachment is being written in an earlier pass,
@@ -2063,6 +2335,9 @@ impl SyncCommandBufferBuilder {
                 DescriptorType::UniformBuffer | DescriptorType::UniformBufferDynamic => {
                     (Some(AccessFlags::UNIFORM_READ), None)
                 }
+                DescriptorType::AccelerationStructure => {
+                    (Some(AccessFlags::ACCELERATION_STRUCTURE_READ), None)
+                }
             };
 
             let memory_iter = move |index: u32| {
@@ -2207,8 +2482,48 @@ impl SyncCommandBufferBuilder {
                     );
                 }
                 DescriptorBindingResources::Sampler(_) => (),
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    resources.extend(
+                        (elements.iter().enumerate())
+                            .filter_map(|(index, element)| {
+                                element.as_ref().map(|acceleration_structure| {
+                                    let buffer = acceleration_structure.buffer();
+                                    (index as u32, buffer.clone(), 0..buffer.size())
+                                })
+                            })
+                            .flat_map(buffer_resource),
+                    );
+                }
             }
         }
+
+        resources.extend(
+            (self.current_state.descriptor_buffers.iter())
+                .zip(descriptor_buffer_stages)
+                .enumerate()
+                .filter(|(_, (_, stages))| !stages.is_empty())
+                .map(|(index, (descriptor_buffer, stages))| {
+                    (
+                        ResourceUseRef {
+                            command_index,
+                            command_name,
+                            resource_in_command: ResourceInCommand::DescriptorBuffer {
+                                index: index as u32,
+                            },
+                            secondary_use_ref: None,
+                        },
+                        Resource::Buffer {
+                            buffer: descriptor_buffer.clone(),
+                            range: 0..descriptor_buffer.size(),
+                            memory: PipelineMemoryAccess {
+                                stages,
+                                access: AccessFlags::DESCRIPTOR_BUFFER_READ,
+                                exclusive: false,
+                            },
+                        },
+                    )
+                }),
+        );
     }
 
     fn add_vertex_buffers(
@@ -2436,6 +2751,29 @@ impl UnsafeCommandBufferBuilder {
             stride,
         );
     }
+
+    /// Calls `vkCmdTraceRaysKHR` on the builder.
+    #[inline]
+    pub unsafe fn trace_rays(
+        &mut self,
+        raygen: &StridedDeviceAddressRegion,
+        miss: &StridedDeviceAddressRegion,
+        hit: &StridedDeviceAddressRegion,
+        callable: &StridedDeviceAddressRegion,
+        dimensions: [u32; 3],
+    ) {
+        let fns = self.device.fns();
+        (fns.khr_ray_tracing_pipeline.cmd_trace_rays_khr)(
+            self.handle,
+            &ash::vk::StridedDeviceAddressRegionKHR::from(raygen),
+            &ash::vk::StridedDeviceAddressRegionKHR::from(miss),
+            &ash::vk::StridedDeviceAddressRegionKHR::from(hit),
+            &ash::vk::StridedDeviceAddressRegionKHR::from(callable),
+            dimensions[0],
+            dimensions[1],
+            dimensions[2],
+        );
+    }
 }
 
 /// Error that can happen when recording a bound pipeline execution command.
@@ -2536,6 +2874,19 @@ pub enum PipelineExecutionError {
         max: u32,
     },
 
+    /// The `max_ray_dispatch_invocation_count` limit has been exceeded.
+    MaxRayDispatchInvocationCountExceeded {
+        requested: u64,
+        max: u32,
+    },
+
+    /// The product of the `max_compute_work_group_count` and `max_compute_work_group_size`
+    /// limits has been exceeded.
+    MaxRayDispatchSizeExceeded {
+        requested: [u32; 3],
+        max: [u32; 3],
+    },
+
     /// The queue family doesn't allow this operation.
     NotSupportedByQueueFamily,
 
@@ -2601,6 +2952,9 @@ pub enum PipelineExecutionError {
     /// Not all push constants used by the pipeline have been set.
     PushConstantsMissing,
 
+    /// The shader binding table was not created for the bound ray tracing pipeline.
+    ShaderBindingTablePipelineMismatch,
+
     /// The bound graphics pipeline requires a vertex buffer bound to a binding number, but none
     /// was bound.
     VertexBufferNotBound {
@@ -2739,6 +3093,15 @@ impl Display for PipelineExecutionError {
                 f,
                 "the `max_multiview_instance_index` limit has been exceeded",
             ),
+            Self::MaxRayDispatchInvocationCountExceeded { .. } => write!(
+                f,
+                "the `max_ray_dispatch_invocation_count` limit has been exceeded",
+            ),
+            Self::MaxRayDispatchSizeExceeded { .. } => write!(
+                f,
+                "the product of the `max_compute_work_group_count` and \
+                `max_compute_work_group_size` limits has been exceeded",
+            ),
             Self::NotSupportedByQueueFamily => {
                 write!(f, "the queue family doesn't allow this operation")
             }
@@ -2821,6 +3184,10 @@ impl Display for PipelineExecutionError {
                 f,
                 "not all push constants used by the pipeline have been set",
             ),
+            Self::ShaderBindingTablePipelineMismatch => write!(
+                f,
+                "the shader binding table was not created for the bound ray tracing pipeline",
+            ),
             Self::VertexBufferNotBound { binding_num } => write!(
                 f,
                 "the bound graphics pipeline requires a vertex buffer bound to binding number {}, \
@@ -45,6 +45,12 @@ where
     ///
     /// The query must be unavailable, ensured by calling
     /// [`reset_query_pool`](Self::reset_query_pool).
+    ///
+    /// For performance queries, the profiling lock must be held while the command buffer is
+    /// recorded, see [`Device::acquire_profiling_lock`]. The query must also be begun and ended
+    /// as required by the [scope](crate::query::PerformanceCounterScope) of its counters.
+    ///
+    /// [`Device::acquire_profiling_lock`]: crate::device::Device::acquire_profiling_lock
     pub unsafe fn begin_query(
         &mut self,
         query_pool: Arc<QueryPool>,
@@ -145,6 +151,77 @@ where
             }
             // VUID-vkCmdBeginQuery-queryType-02804
             QueryType::Timestamp => return Err(QueryError::NotPermitted),
+            // VUID-vkCmdBeginQuery-queryType-04728
+            QueryType::AccelerationStructureCompactedSize => return Err(QueryError::NotPermitted),
+            QueryType::TransformFeedbackStream => {
+                // VUID-vkCmdBeginQuery-commandBuffer-cmdpool
+                // VUID-vkCmdBeginQuery-queryType-02327
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(QueryError::NotSupportedByQueueFamily);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-02328
+                if !device
+                    .physical_device()
+                    .properties()
+                    .transform_feedback_queries
+                    .unwrap_or(false)
+                {
+                    return Err(QueryError::TransformFeedbackQueriesNotSupported);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-00800
+                if flags.intersects(QueryControlFlags::PRECISE) {
+                    return Err(QueryError::InvalidFlags);
+                }
+            }
+            QueryType::PrimitivesGenerated => {
+                // VUID-vkCmdBeginQuery-commandBuffer-cmdpool
+                // VUID-vkCmdBeginQuery-queryType-06687
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(QueryError::NotSupportedByQueueFamily);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-06688
+                if !device.enabled_features().primitives_generated_query {
+                    return Err(QueryError::RequirementNotMet {
+                        required_for: "`query_pool.query_type()` is \
+                            `QueryType::PrimitivesGenerated`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["primitives_generated_query"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-00800
+                if flags.intersects(QueryControlFlags::PRECISE) {
+                    return Err(QueryError::InvalidFlags);
+                }
+            }
+            QueryType::Performance => {
+                // VUID-vkCmdBeginQuery-queryPool-03224
+                if query_pool.performance_queue_family_index() != self.queue_family_index {
+                    return Err(QueryError::PerformanceQueueFamilyMismatch);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-00800
+                if flags.intersects(QueryControlFlags::PRECISE) {
+                    return Err(QueryError::InvalidFlags);
+                }
+
+                // VUID-vkCmdBeginQuery-queryPool-03223
+                // VUID-vkCmdBeginQuery-queryPool-03225
+                // VUID-vkCmdBeginQuery-queryPool-03226
+                // VUID-vkCmdBeginQuery-queryPool-03227
+                // Ensured by the safety requirements.
+            }
         }
 
         // VUID-vkCmdBeginQuery-queryPool-01922
@@ -530,6 +607,13 @@ where
             .queries_range(queries.clone())
             .ok_or(QueryError::OutOfRange)?;
 
+        // VUID-vkCmdCopyQueryPoolResults-queryType-03232
+        // VUID-vkCmdCopyQueryPoolResults-queryType-03233
+        // Performance query results are only retrieved on the host.
+        if let QueryType::Performance = query_pool.query_type() {
+            return Err(QueryError::NotPermitted);
+        }
+
         let count = queries.end - queries.start;
         let per_query_len = query_pool.query_type().result_len()
             + flags.intersects(QueryResultFlags::WITH_AVAILABILITY) as DeviceSize;
@@ -567,6 +651,8 @@ where
     /// The affected queries will be marked as "unavailable" after this command runs, and will no
     /// longer return any results. They will be ready to have new results recorded for them.
     ///
+    /// Queries can also be reset from the host, with [`QueriesRange::reset`].
+    ///
     /// # Safety
     /// The queries in the specified range must not be active in another command buffer.
     // TODO: Do other command buffers actually matter here? Not sure on the Vulkan spec.
@@ -960,6 +1046,10 @@ pub enum QueryError {
     /// than the number of queries in the pool.
     OutOfRangeMultiview,
 
+    /// The performance query pool was created for a different queue family than the one of the
+    /// command buffer.
+    PerformanceQueueFamilyMismatch,
+
     /// A query is active that conflicts with the current operation.
     QueryIsActive,
 
@@ -968,6 +1058,10 @@ pub enum QueryError {
 
     /// The provided stage is not supported by the queue family.
     StageNotSupported,
+
+    /// A transform feedback stream query was begun, but the device does not support transform
+    /// feedback queries.
+    TransformFeedbackQueriesNotSupported,
 }
 
 impl Error for QueryError {}
@@ -1011,6 +1105,11 @@ impl Display for QueryError {
                 "the provided query index plus the number of views in the current render subpass \
                 is greater than the number of queries in the pool",
             ),
+            Self::PerformanceQueueFamilyMismatch => write!(
+                f,
+                "the performance query pool was created for a different queue family than the \
+                one of the command buffer",
+            ),
             Self::QueryIsActive => write!(
                 f,
                 "a query is active that conflicts with the current operation"
@@ -1019,6 +1118,11 @@ impl Display for QueryError {
             Self::StageNotSupported => {
                 write!(f, "the provided stage is not supported by the queue family")
             }
+            Self::TransformFeedbackQueriesNotSupported => write!(
+                f,
+                "a transform feedback stream query was begun, but the device does not support \
+                transform feedback queries",
+            ),
         }
     }
 }
@@ -21,9 +21,10 @@ use crate::{
     device::{DeviceOwned, QueueFlags},
     format::{ClearColorValue, ClearValue, Format, NumericType},
     image::{ImageAspects, ImageLayout, ImageUsage, ImageViewAbstract, SampleCount},
+    pipeline::graphics::multisample::SampleLocationsInfo,
     render_pass::{
-        AttachmentDescription, Framebuffer, LoadOp, RenderPass, ResolveMode, StoreOp,
-        SubpassDescription,
+        AttachmentDescription, Framebuffer, FramebufferAttachmentImageInfo, FramebufferCreateFlags,
+        LoadOp, RenderPass, ResolveMode, StoreOp, SubpassDescription,
     },
     sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
     RequirementNotMet, RequiresOneOf, Version, VulkanObject,
@@ -61,9 +62,12 @@ where
             let RenderPassBeginInfo {
                 ref render_pass,
                 ref framebuffer,
+                attachments: _,
                 render_area_offset,
                 render_area_extent,
                 clear_values: _,
+                attachment_initial_sample_locations: _,
+                post_subpass_sample_locations: _,
                 _ne: _,
             } = render_pass_begin_info;
 
@@ -77,6 +81,7 @@ where
                 render_pass: BeginRenderPassState {
                     subpass,
                     framebuffer: Some(framebuffer.clone()),
+                    attachments: Some(render_pass_begin_info.attachment_image_views().to_vec()),
                 }
                 .into(),
                 view_mask,
@@ -118,9 +123,12 @@ where
         let RenderPassBeginInfo {
             render_pass,
             framebuffer,
+            attachments,
             render_area_offset,
             render_area_extent,
             clear_values,
+            attachment_initial_sample_locations,
+            post_subpass_sample_locations,
             _ne: _,
         } = render_pass_begin_info;
 
@@ -141,10 +149,83 @@ where
             }
         }
 
+        let attachment_image_views = if framebuffer
+            .flags()
+            .intersects(FramebufferCreateFlags::IMAGELESS)
+        {
+            // VUID-VkRenderPassBeginInfo-framebuffer-03208
+            if attachments.len() != framebuffer.attachment_image_infos().len() {
+                return Err(RenderPassError::AttachmentCountMismatch {
+                    provided: attachments.len() as u32,
+                    required: framebuffer.attachment_image_infos().len() as u32,
+                });
+            }
+
+            for (attachment_index, ((image_view, image_info), attachment_desc)) in attachments
+                .iter()
+                .zip(framebuffer.attachment_image_infos())
+                .zip(render_pass.attachments())
+                .enumerate()
+            {
+                let attachment_index = attachment_index as u32;
+                let &FramebufferAttachmentImageInfo {
+                    flags,
+                    usage,
+                    extent,
+                    array_layers,
+                    ref view_formats,
+                    _ne: _,
+                } = image_info;
+
+                // VUID-VkRenderPassBeginInfo-framebuffer-02780
+                assert_eq!(device, image_view.device());
+
+                let subresource_range = image_view.subresource_range();
+
+                // VUID-VkRenderPassBeginInfo-framebuffer-03209
+                // VUID-VkRenderPassBeginInfo-framebuffer-04627
+                // VUID-VkRenderPassBeginInfo-framebuffer-03211
+                // VUID-VkRenderPassBeginInfo-framebuffer-03212
+                // VUID-VkRenderPassBeginInfo-framebuffer-03213
+                // VUID-VkRenderPassBeginInfo-framebuffer-03218
+                if image_view.image().inner().image.flags() != flags
+                    || image_view.usage() != usage
+                    || image_view.image().dimensions().width_height() != extent
+                    || subresource_range.array_layers.end - subresource_range.array_layers.start
+                        != array_layers
+                    || !image_view
+                        .format()
+                        .map_or(false, |format| view_formats.contains(&format))
+                {
+                    return Err(RenderPassError::AttachmentImageNotCompatible { attachment_index });
+                }
+
+                // VUID-VkRenderPassBeginInfo-framebuffer-03216
+                // VUID-VkRenderPassBeginInfo-framebuffer-09047
+                if image_view.format() != attachment_desc.format
+                    || image_view.image().samples() != attachment_desc.samples
+                {
+                    return Err(RenderPassError::AttachmentImageNotCompatible { attachment_index });
+                }
+            }
+
+            attachments.as_slice()
+        } else {
+            // VUID-VkRenderPassBeginInfo-framebuffer-03207
+            if !attachments.is_empty() {
+                return Err(RenderPassError::AttachmentCountMismatch {
+                    provided: attachments.len() as u32,
+                    required: 0,
+                });
+            }
+
+            framebuffer.attachments()
+        };
+
         for (attachment_index, (attachment_desc, image_view)) in render_pass
             .attachments()
             .iter()
-            .zip(framebuffer.attachments())
+            .zip(attachment_image_views)
             .enumerate()
         {
             let attachment_index = attachment_index as u32;
@@ -222,17 +303,21 @@ where
                 color_attachments,
                 resolve_attachments,
                 depth_stencil_attachment,
+                depth_stencil_resolve_attachment,
+                depth_resolve_mode: _,
+                stencil_resolve_mode: _,
                 preserve_attachments: _,
+                fragment_shading_rate_attachment: _,
                 _ne: _,
             } = subpass_desc;
 
             for atch_ref in (input_attachments.iter())
                 .chain(color_attachments)
                 .chain(resolve_attachments)
-                .chain([depth_stencil_attachment])
+                .chain([depth_stencil_attachment, depth_stencil_resolve_attachment])
                 .flatten()
             {
-                let image_view = &framebuffer.attachments()[atch_ref.attachment as usize];
+                let image_view = &attachment_image_views[atch_ref.attachment as usize];
 
                 match atch_ref.layout {
                     ImageLayout::ColorAttachmentOptimal => {
@@ -393,6 +478,77 @@ where
             }
         }
 
+        if !(attachment_initial_sample_locations.is_empty()
+            && post_subpass_sample_locations.is_empty())
+            && !device.enabled_extensions().ext_sample_locations
+        {
+            return Err(RenderPassError::RequirementNotMet {
+                required_for: "`render_pass_begin_info.attachment_initial_sample_locations` or \
+                    `render_pass_begin_info.post_subpass_sample_locations` are not empty",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_sample_locations"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        for &(attachment_index, _) in attachment_initial_sample_locations.iter() {
+            // VUID-VkAttachmentSampleLocationsEXT-attachmentIndex-01531
+            if attachment_index as usize >= render_pass.attachments().len() {
+                return Err(RenderPassError::SampleLocationsAttachmentIndexOutOfRange {
+                    attachment_index,
+                });
+            }
+        }
+
+        for &(subpass_index, _) in post_subpass_sample_locations.iter() {
+            // VUID-VkSubpassSampleLocationsEXT-subpassIndex-01532
+            if subpass_index as usize >= render_pass.subpasses().len() {
+                return Err(RenderPassError::SampleLocationsSubpassIndexOutOfRange {
+                    subpass_index,
+                });
+            }
+        }
+
+        for (_, sample_locations) in attachment_initial_sample_locations
+            .iter()
+            .chain(post_subpass_sample_locations.iter())
+        {
+            let &SampleLocationsInfo {
+                sample_locations_per_pixel,
+                sample_location_grid_size,
+                ref sample_locations,
+            } = sample_locations;
+
+            // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-parameter
+            sample_locations_per_pixel.validate_device(device)?;
+
+            // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-01526
+            if !device
+                .physical_device()
+                .properties()
+                .sample_location_sample_counts
+                .unwrap_or_default()
+                .contains_enum(sample_locations_per_pixel)
+            {
+                return Err(RenderPassError::SampleLocationsPerPixelNotSupported {
+                    provided: sample_locations_per_pixel,
+                });
+            }
+
+            // VUID-VkSampleLocationsInfoEXT-sampleLocationsCount-01527
+            let required = sample_locations_per_pixel as u32
+                * sample_location_grid_size[0]
+                * sample_location_grid_size[1];
+
+            if sample_locations.len() as u32 != required {
+                return Err(RenderPassError::SampleLocationsCountMismatch {
+                    provided: sample_locations.len() as u32,
+                    required,
+                });
+            }
+        }
+
         // VUID-vkCmdBeginRenderPass2-initialLayout-03100
         // TODO:
 
@@ -470,6 +626,20 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .conditional_rendering_state
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdNextSubpass2-None-02350
+        if self.transform_feedback_state.is_some() {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdNextSubpass2-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -528,6 +698,20 @@ where
             return Err(RenderPassError::QueryIsActive);
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .conditional_rendering_state
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdEndRenderPass2-None-02352
+        if self.transform_feedback_state.is_some() {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdEndRenderPass2-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -1193,6 +1377,20 @@ where
             RenderPassStateType::BeginRendering(_) => (),
         }
 
+        // VUID-vkCmdEndConditionalRenderingEXT-None-01987
+        if self
+            .conditional_rendering_state
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(RenderPassError::ConditionalRenderingActive);
+        }
+
+        // VUID-vkCmdEndRendering-None-06781
+        if self.transform_feedback_state.is_some() {
+            return Err(RenderPassError::TransformFeedbackActive);
+        }
+
         // VUID-vkCmdEndRendering-commandBuffer-cmdpool
         debug_assert!(self
             .queue_family_properties()
@@ -1309,14 +1507,14 @@ where
                     }
 
                     let image_view = match &render_pass_state.render_pass {
-                        RenderPassStateType::BeginRenderPass(state) => (state.framebuffer.as_ref())
+                        RenderPassStateType::BeginRenderPass(state) => (state.attachments.as_ref())
                             .zip(
                                 state.subpass.subpass_desc().color_attachments
                                     [color_attachment as usize]
                                     .as_ref(),
                             )
-                            .map(|(framebuffer, atch_ref)| {
-                                &framebuffer.attachments()[atch_ref.attachment as usize]
+                            .map(|(attachments, atch_ref)| {
+                                &attachments[atch_ref.attachment as usize]
                             }),
                         RenderPassStateType::BeginRendering(state) => state
                             .attachments
@@ -1382,7 +1580,7 @@ where
                     }
 
                     let image_view = match &render_pass_state.render_pass {
-                        RenderPassStateType::BeginRenderPass(state) => (state.framebuffer.as_ref())
+                        RenderPassStateType::BeginRenderPass(state) => (state.attachments.as_ref())
                             .zip(
                                 state
                                     .subpass
@@ -1390,8 +1588,8 @@ where
                                     .depth_stencil_attachment
                                     .as_ref(),
                             )
-                            .map(|(framebuffer, atch_ref)| {
-                                &framebuffer.attachments()[atch_ref.attachment as usize]
+                            .map(|(attachments, atch_ref)| {
+                                &attachments[atch_ref.attachment as usize]
                             }),
                         RenderPassStateType::BeginRendering(state) => state
                             .attachments
@@ -1482,14 +1680,8 @@ impl SyncCommandBufferBuilder {
             }
         }
 
-        let RenderPassBeginInfo {
-            render_pass,
-            framebuffer,
-            render_area_offset: _,
-            render_area_extent: _,
-            clear_values: _,
-            _ne: _,
-        } = &render_pass_begin_info;
+        let render_pass = &render_pass_begin_info.render_pass;
+        let attachment_image_views = render_pass_begin_info.attachment_image_views();
 
         let command_index = self.commands.len();
         let command_name = "begin_render_pass";
@@ -1498,8 +1690,25 @@ impl SyncCommandBufferBuilder {
             .iter()
             .enumerate()
             .map(|(index, desc)| {
-                let image_view = &framebuffer.attachments()[index];
+                let image_view = &attachment_image_views[index];
                 let index = index as u32;
+                let mut extra_access = AccessFlags::empty();
+
+                if render_pass.subpasses().iter().any(|subpass| {
+                    subpass
+                        .fragment_shading_rate_attachment
+                        .as_ref()
+                        .map_or(false, |info| info.attachment.attachment == index)
+                }) {
+                    extra_access |= AccessFlags::FRAGMENT_SHADING_RATE_ATTACHMENT_READ;
+                }
+
+                if render_pass
+                    .fragment_density_map_attachment()
+                    .map_or(false, |atch_ref| atch_ref.attachment == index)
+                {
+                    extra_access |= AccessFlags::FRAGMENT_DENSITY_MAP_READ;
+                }
 
                 (
                     ResourceUseRef {
@@ -1517,7 +1726,8 @@ impl SyncCommandBufferBuilder {
                                 | AccessFlags::COLOR_ATTACHMENT_READ
                                 | AccessFlags::COLOR_ATTACHMENT_WRITE
                                 | AccessFlags::DEPTH_STENCIL_ATTACHMENT_READ
-                                | AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE, // TODO: suboptimal
+                                | AccessFlags::DEPTH_STENCIL_ATTACHMENT_WRITE
+                                | extra_access, // TODO: suboptimal
                             exclusive: true, // TODO: suboptimal ; note: remember to always pass true if desc.initial_layout != desc.final_layout
                         },
                         start_layout: desc.initial_layout,
@@ -1899,9 +2109,12 @@ impl UnsafeCommandBufferBuilder {
         let &RenderPassBeginInfo {
             ref render_pass,
             ref framebuffer,
+            ref attachments,
             render_area_offset,
             render_area_extent,
             ref clear_values,
+            ref attachment_initial_sample_locations,
+            ref post_subpass_sample_locations,
             _ne: _,
         } = render_pass_begin_info;
 
@@ -1911,7 +2124,12 @@ impl UnsafeCommandBufferBuilder {
             .map(|clear_value| clear_value.map(Into::into).unwrap_or_default())
             .collect();
 
-        let render_pass_begin_info = ash::vk::RenderPassBeginInfo {
+        let attachments_vk: SmallVec<[_; 4]> = attachments
+            .iter()
+            .map(|image_view| image_view.handle())
+            .collect();
+
+        let mut render_pass_begin_info = ash::vk::RenderPassBeginInfo {
             render_pass: render_pass.handle(),
             framebuffer: framebuffer.handle(),
             render_area: ash::vk::Rect2D {
@@ -1929,6 +2147,92 @@ impl UnsafeCommandBufferBuilder {
             ..Default::default()
         };
 
+        let mut attachment_begin_info_vk = None;
+
+        if framebuffer
+            .flags()
+            .intersects(FramebufferCreateFlags::IMAGELESS)
+        {
+            let next = attachment_begin_info_vk.insert(ash::vk::RenderPassAttachmentBeginInfo {
+                attachment_count: attachments_vk.len() as u32,
+                p_attachments: attachments_vk.as_ptr(),
+                ..Default::default()
+            });
+
+            render_pass_begin_info.p_next = next as *const _ as *const _;
+        }
+
+        let sample_locations_vk: SmallVec<[_; 8]> = attachment_initial_sample_locations
+            .iter()
+            .chain(post_subpass_sample_locations.iter())
+            .flat_map(|(_, sample_locations)| &sample_locations.sample_locations)
+            .map(|&[x, y]| ash::vk::SampleLocationEXT { x, y })
+            .collect();
+        let mut sample_locations_offset = 0;
+        let mut sample_locations_info_vk = |sample_locations: &SampleLocationsInfo| {
+            let &SampleLocationsInfo {
+                sample_locations_per_pixel,
+                sample_location_grid_size,
+                ref sample_locations,
+            } = sample_locations;
+
+            let sample_locations_info = ash::vk::SampleLocationsInfoEXT {
+                sample_locations_per_pixel: sample_locations_per_pixel.into(),
+                sample_location_grid_size: ash::vk::Extent2D {
+                    width: sample_location_grid_size[0],
+                    height: sample_location_grid_size[1],
+                },
+                sample_locations_count: sample_locations.len() as u32,
+                p_sample_locations: sample_locations_vk[sample_locations_offset..].as_ptr(),
+                ..Default::default()
+            };
+            sample_locations_offset += sample_locations.len();
+
+            sample_locations_info
+        };
+
+        let attachment_initial_sample_locations_vk: SmallVec<[_; 4]> =
+            attachment_initial_sample_locations
+                .iter()
+                .map(
+                    |(attachment_index, sample_locations)| ash::vk::AttachmentSampleLocationsEXT {
+                        attachment_index: *attachment_index,
+                        sample_locations_info: sample_locations_info_vk(sample_locations),
+                    },
+                )
+                .collect();
+        let post_subpass_sample_locations_vk: SmallVec<[_; 4]> = post_subpass_sample_locations
+            .iter()
+            .map(
+                |(subpass_index, sample_locations)| ash::vk::SubpassSampleLocationsEXT {
+                    subpass_index: *subpass_index,
+                    sample_locations_info: sample_locations_info_vk(sample_locations),
+                },
+            )
+            .collect();
+
+        let mut sample_locations_begin_info_vk = None;
+
+        if !(attachment_initial_sample_locations_vk.is_empty()
+            && post_subpass_sample_locations_vk.is_empty())
+        {
+            let next = sample_locations_begin_info_vk.insert(
+                ash::vk::RenderPassSampleLocationsBeginInfoEXT {
+                    attachment_initial_sample_locations_count:
+                        attachment_initial_sample_locations_vk.len() as u32,
+                    p_attachment_initial_sample_locations: attachment_initial_sample_locations_vk
+                        .as_ptr(),
+                    post_subpass_sample_locations_count: post_subpass_sample_locations_vk.len()
+                        as u32,
+                    p_post_subpass_sample_locations: post_subpass_sample_locations_vk.as_ptr(),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = render_pass_begin_info.p_next;
+            render_pass_begin_info.p_next = next as *const _ as *const _;
+        }
+
         let subpass_begin_info = ash::vk::SubpassBeginInfo {
             contents: contents.into(),
             ..Default::default()
@@ -2191,6 +2495,18 @@ pub struct RenderPassBeginInfo {
     /// There is no default value.
     pub framebuffer: Arc<Framebuffer>,
 
+    /// If `framebuffer` was created with [`FramebufferCreateFlags::IMAGELESS`], the image views
+    /// to use as attachments. There must be exactly as many as there are attachments in
+    /// `render_pass`, and each image view must match the corresponding element of
+    /// [`framebuffer.attachment_image_infos()`].
+    ///
+    /// If `framebuffer` is not imageless, this must be empty.
+    ///
+    /// The default value is empty.
+    ///
+    /// [`framebuffer.attachment_image_infos()`]: Framebuffer::attachment_image_infos
+    pub attachments: Vec<Arc<dyn ImageViewAbstract>>,
+
     /// The offset from the top left corner of the framebuffer that will be rendered to.
     ///
     /// The default value is `[0, 0]`.
@@ -2213,6 +2529,32 @@ pub struct RenderPassBeginInfo {
     /// The default value is empty, which must be overridden if the framebuffer has attachments.
     pub clear_values: Vec<Option<ClearValue>>,
 
+    /// Pairs of an attachment index and the sample locations that should be used for the layout
+    /// transition of that attachment at the start of the render pass. This is only used for
+    /// depth/stencil attachments whose image was created with
+    /// [`ImageCreateFlags::SAMPLE_LOCATIONS_COMPATIBLE_DEPTH`].
+    ///
+    /// If not empty, the
+    /// [`ext_sample_locations`](crate::device::DeviceExtensions::ext_sample_locations)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is empty.
+    ///
+    /// [`ImageCreateFlags::SAMPLE_LOCATIONS_COMPATIBLE_DEPTH`]: crate::image::ImageCreateFlags::SAMPLE_LOCATIONS_COMPATIBLE_DEPTH
+    pub attachment_initial_sample_locations: Vec<(u32, SampleLocationsInfo)>,
+
+    /// Pairs of a subpass index and the sample locations that are used for depth/stencil
+    /// attachments at the end of that subpass, when they are transitioned to another layout by
+    /// the render pass. The sample locations must match those used while rendering in the
+    /// subpass.
+    ///
+    /// If not empty, the
+    /// [`ext_sample_locations`](crate::device::DeviceExtensions::ext_sample_locations)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is empty.
+    pub post_subpass_sample_locations: Vec<(u32, SampleLocationsInfo)>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -2224,12 +2566,29 @@ impl RenderPassBeginInfo {
         Self {
             render_pass: framebuffer.render_pass().clone(),
             framebuffer,
+            attachments: Vec::new(),
             render_area_offset: [0, 0],
             render_area_extent,
             clear_values: Vec::new(),
+            attachment_initial_sample_locations: Vec::new(),
+            post_subpass_sample_locations: Vec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
+
+    /// Returns the image views that are used as attachments: `attachments` if `framebuffer` is
+    /// imageless, or the attachments of `framebuffer` otherwise.
+    pub(crate) fn attachment_image_views(&self) -> &[Arc<dyn ImageViewAbstract>] {
+        if self
+            .framebuffer
+            .flags()
+            .intersects(FramebufferCreateFlags::IMAGELESS)
+        {
+            &self.attachments
+        } else {
+            self.framebuffer.attachments()
+        }
+    }
 }
 
 /// Parameters to begin rendering.
@@ -2606,6 +2965,19 @@ pub enum RenderPassError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// The number of attachment image views doesn't match the number expected by the
+    /// framebuffer.
+    AttachmentCountMismatch {
+        provided: u32,
+        required: u32,
+    },
+
+    /// An attachment image view doesn't match the corresponding attachment image info of the
+    /// imageless framebuffer, or the corresponding attachment of the render pass.
+    AttachmentImageNotCompatible {
+        attachment_index: u32,
+    },
+
     /// A framebuffer image did not have the required usage enabled.
     AttachmentImageMissingUsage {
         attachment_index: u32,
@@ -2689,6 +3061,9 @@ pub enum RenderPassError {
         attachment_index: u32,
     },
 
+    /// Conditional rendering was made active within the current subpass, and has not been ended.
+    ConditionalRenderingActive,
+
     /// The contents `SubpassContents::SecondaryCommandBuffers` is not allowed inside a secondary
     /// command buffer.
     ContentsForbiddenInSecondaryCommandBuffer,
@@ -2812,6 +3187,30 @@ pub enum RenderPassError {
     /// The render area's `offset` and `extent` are outside the extent of the framebuffer.
     RenderAreaOutOfBounds,
 
+    /// An attachment index in `attachment_initial_sample_locations` is out of range.
+    SampleLocationsAttachmentIndexOutOfRange {
+        attachment_index: u32,
+    },
+
+    /// The number of provided sample locations does not equal the number required by the sample
+    /// count and grid size.
+    SampleLocationsCountMismatch {
+        provided: u32,
+        required: u32,
+    },
+
+    /// The provided sample count is not included in the
+    /// [`sample_location_sample_counts`](crate::device::Properties::sample_location_sample_counts)
+    /// device property.
+    SampleLocationsPerPixelNotSupported {
+        provided: SampleCount,
+    },
+
+    /// A subpass index in `post_subpass_sample_locations` is out of range.
+    SampleLocationsSubpassIndexOutOfRange {
+        subpass_index: u32,
+    },
+
     /// The stencil attachment has a format that does not support that usage.
     StencilAttachmentFormatUsageNotSupported,
 
@@ -2847,6 +3246,9 @@ pub enum RenderPassError {
         current_subpass: u32,
         remaining_subpasses: u32,
     },
+
+    /// Transform feedback is active, and has not been ended.
+    TransformFeedbackActive,
 }
 
 impl Error for RenderPassError {
@@ -2870,6 +3272,19 @@ impl Display for RenderPassError {
                 "a requirement was not met for: {}; requires one of: {}",
                 required_for, requires_one_of,
             ),
+            Self::AttachmentCountMismatch { provided, required } => write!(
+                f,
+                "the number of attachment image views ({}) doesn't match the number expected by \
+                the framebuffer ({})",
+                provided, required,
+            ),
+            Self::AttachmentImageNotCompatible { attachment_index } => write!(
+                f,
+                "the attachment image view at index {} doesn't match the corresponding \
+                attachment image info of the imageless framebuffer, or the corresponding \
+                attachment of the render pass",
+                attachment_index,
+            ),
             Self::AttachmentImageMissingUsage {
                 attachment_index,
                 usage,
@@ -2966,6 +3381,11 @@ impl Display for RenderPassError {
                 `SampleCount::Sample1`",
                 attachment_index,
             ),
+            Self::ConditionalRenderingActive => write!(
+                f,
+                "conditional rendering was made active within the current subpass, and has not \
+                been ended",
+            ),
             Self::ContentsForbiddenInSecondaryCommandBuffer => write!(
                 f,
                 "the contents `SubpassContents::SecondaryCommandBuffers` is not allowed inside a \
@@ -3106,6 +3526,28 @@ impl Display for RenderPassError {
                 f,
                 "the render area's `offset` and `extent` are outside the extent of the framebuffer",
             ),
+            Self::SampleLocationsAttachmentIndexOutOfRange { attachment_index } => write!(
+                f,
+                "attachment index {} in `attachment_initial_sample_locations` is out of range",
+                attachment_index,
+            ),
+            Self::SampleLocationsCountMismatch { provided, required } => write!(
+                f,
+                "the number of provided sample locations ({}) does not equal the number required \
+                by the sample count and grid size ({})",
+                provided, required,
+            ),
+            Self::SampleLocationsPerPixelNotSupported { provided } => write!(
+                f,
+                "the provided sample count ({:?}) is not included in the \
+                `sample_location_sample_counts` device property",
+                provided,
+            ),
+            Self::SampleLocationsSubpassIndexOutOfRange { subpass_index } => write!(
+                f,
+                "subpass index {} in `post_subpass_sample_locations` is out of range",
+                subpass_index,
+            ),
             Self::StencilAttachmentFormatUsageNotSupported => write!(
                 f,
                 "the stencil attachment has a format that does not support that usage",
@@ -3155,6 +3597,9 @@ impl Display for RenderPassError {
                 the render pass",
                 current_subpass, remaining_subpasses,
             ),
+            Self::TransformFeedbackActive => {
+                write!(f, "transform feedback is active, and has not been ended")
+            }
         }
     }
 }
@@ -374,7 +374,11 @@ where
                         );
                     }
                 }
-                QueryType::Timestamp => (),
+                QueryType::Timestamp
+                | QueryType::AccelerationStructureCompactedSize
+                | QueryType::TransformFeedbackStream
+                | QueryType::PrimitivesGenerated
+                | QueryType::Performance => (),
             }
         }
 
@@ -9,18 +9,445 @@
 
 use crate::{
     command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::EventState,
         synced::{Command, SyncCommandBufferBuilder},
         sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder,
     },
+    device::{Device, DeviceOwned, QueueFlags},
     image::ImageLayout,
     sync::{
         event::Event, AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo,
         ImageMemoryBarrier, MemoryBarrier, PipelineStages,
     },
-    Version, VulkanObject,
+    RequirementNotMet, RequiresOneOf, Version, VulkanObject,
 };
 use smallvec::SmallVec;
-use std::{ptr, sync::Arc};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    ptr,
+    sync::Arc,
+};
+
+/// # Commands related to synchronization.
+///
+/// These commands are used to create split barriers with [`Event`]s. Commands recorded between
+/// [`set_event`] and [`wait_events`] are not included in either synchronization scope, so they
+/// are free to overlap with the work that comes before and after.
+///
+/// Synchronization of buffers and images that are used by other commands is still handled
+/// automatically by the builder, so only global memory barriers can be used with these commands.
+///
+/// [`set_event`]: Self::set_event
+/// [`wait_events`]: Self::wait_events
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Signals an [`Event`] from the device, once the source scope of `dependency_info` has
+    /// completed.
+    ///
+    /// `dependency_info.buffer_memory_barriers` and `dependency_info.image_memory_barriers` must
+    /// be empty.
+    pub fn set_event(
+        &mut self,
+        event: Arc<Event>,
+        dependency_info: DependencyInfo,
+    ) -> Result<&mut Self, EventCommandError> {
+        self.validate_set_event(&event, &dependency_info)?;
+
+        let handle = event.handle();
+        let memory_barriers = dependency_info
+            .memory_barriers
+            .iter()
+            .map(|barrier| {
+                (
+                    barrier.src_stages,
+                    barrier.src_access,
+                    barrier.dst_stages,
+                    barrier.dst_access,
+                )
+            })
+            .collect();
+
+        unsafe {
+            self.inner.set_event(event, dependency_info);
+        }
+
+        self.event_state
+            .insert(handle, EventState { memory_barriers });
+
+        Ok(self)
+    }
+
+    fn validate_set_event(
+        &self,
+        event: &Event,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), EventCommandError> {
+        // VUID-vkCmdSetEvent2-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(EventCommandError::ForbiddenInsideRenderPass);
+        }
+
+        let device = self.device();
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetEvent2-commandBuffer-cmdpool
+        if !queue_family_properties.queue_flags.intersects(
+            QueueFlags::GRAPHICS
+                | QueueFlags::COMPUTE
+                | QueueFlags::VIDEO_DECODE
+                | QueueFlags::VIDEO_ENCODE,
+        ) {
+            return Err(EventCommandError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetEvent2-commonparent
+        assert_eq!(device, event.device());
+
+        // VUID-VkDependencyInfo-dependencyFlags-parameter
+        dependency_info.dependency_flags.validate_device(device)?;
+
+        // VUID-vkCmdSetEvent2-dependencyFlags-03825
+        assert!(dependency_info.dependency_flags.is_empty());
+
+        self.validate_event_dependency_info(dependency_info)
+    }
+
+    /// Waits for [`Event`]s to be signaled, before executing the destination scope of each
+    /// corresponding `DependencyInfo`.
+    ///
+    /// Each event must have been set with [`set_event`](Self::set_event) earlier in this
+    /// command buffer, without being reset since, and the `DependencyInfo` must be equal to the
+    /// one that was given to `set_event`.
+    ///
+    /// # Safety
+    ///
+    /// - Between the `set_event` command and this command, the events must not be reset by the
+    ///   host, or by a command buffer that executes concurrently with this one.
+    pub unsafe fn wait_events(
+        &mut self,
+        events: impl IntoIterator<Item = (Arc<Event>, DependencyInfo)>,
+    ) -> Result<&mut Self, EventCommandError> {
+        let events: SmallVec<[(Arc<Event>, DependencyInfo); 4]> = events.into_iter().collect();
+        self.validate_wait_events(&events)?;
+
+        if !events.is_empty() {
+            self.inner.wait_events(events);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_wait_events(
+        &self,
+        events: &[(Arc<Event>, DependencyInfo)],
+    ) -> Result<(), EventCommandError> {
+        // Waiting inside a render pass requires a matching subpass self-dependency, which is not
+        // tracked by this builder.
+        if self.render_pass_state.is_some() {
+            return Err(EventCommandError::ForbiddenInsideRenderPass);
+        }
+
+        let device = self.device();
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdWaitEvents2-commandBuffer-cmdpool
+        if !queue_family_properties.queue_flags.intersects(
+            QueueFlags::GRAPHICS
+                | QueueFlags::COMPUTE
+                | QueueFlags::VIDEO_DECODE
+                | QueueFlags::VIDEO_ENCODE,
+        ) {
+            return Err(EventCommandError::NotSupportedByQueueFamily);
+        }
+
+        for (event_index, (event, dependency_info)) in events.iter().enumerate() {
+            // VUID-vkCmdWaitEvents2-commonparent
+            assert_eq!(device, event.device());
+
+            // VUID-VkDependencyInfo-dependencyFlags-parameter
+            dependency_info.dependency_flags.validate_device(device)?;
+
+            self.validate_event_dependency_info(dependency_info)?;
+
+            let event_state = self
+                .event_state
+                .get(&event.handle())
+                .ok_or(EventCommandError::EventNotSet { event_index })?;
+
+            // VUID-vkCmdWaitEvents2-pEvents-03837
+            if !dependency_info.dependency_flags.is_empty()
+                || dependency_info.memory_barriers.len() != event_state.memory_barriers.len()
+                || !dependency_info
+                    .memory_barriers
+                    .iter()
+                    .zip(&event_state.memory_barriers)
+                    .all(
+                        |(barrier, &(src_stages, src_access, dst_stages, dst_access))| {
+                            barrier.src_stages == src_stages
+                                && barrier.src_access == src_access
+                                && barrier.dst_stages == dst_stages
+                                && barrier.dst_access == dst_access
+                        },
+                    )
+            {
+                return Err(EventCommandError::EventDependencyInfoMismatch { event_index });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Unsignals an [`Event`] from the device, once `stages` of all previous commands have
+    /// completed.
+    pub fn reset_event(
+        &mut self,
+        event: Arc<Event>,
+        stages: PipelineStages,
+    ) -> Result<&mut Self, EventCommandError> {
+        self.validate_reset_event(&event, stages)?;
+
+        let handle = event.handle();
+
+        unsafe {
+            self.inner.reset_event(event, stages);
+        }
+
+        self.event_state.remove(&handle);
+
+        Ok(self)
+    }
+
+    fn validate_reset_event(
+        &self,
+        event: &Event,
+        stages: PipelineStages,
+    ) -> Result<(), EventCommandError> {
+        // VUID-vkCmdResetEvent2-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(EventCommandError::ForbiddenInsideRenderPass);
+        }
+
+        let device = self.device();
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdResetEvent2-commandBuffer-cmdpool
+        if !queue_family_properties.queue_flags.intersects(
+            QueueFlags::GRAPHICS
+                | QueueFlags::COMPUTE
+                | QueueFlags::VIDEO_DECODE
+                | QueueFlags::VIDEO_ENCODE,
+        ) {
+            return Err(EventCommandError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdResetEvent2-commonparent
+        assert_eq!(device, event.device());
+
+        validate_stages(device, stages)?;
+
+        // VUID-vkCmdResetEvent2-stageMask-03830
+        if stages.intersects(PipelineStages::HOST) {
+            return Err(EventCommandError::HostStageForbidden);
+        }
+
+        if !PipelineStages::from(queue_family_properties.queue_flags).contains(stages) {
+            return Err(EventCommandError::StageNotSupported);
+        }
+
+        Ok(())
+    }
+
+    fn validate_event_dependency_info(
+        &self,
+        dependency_info: &DependencyInfo,
+    ) -> Result<(), EventCommandError> {
+        let device = self.device();
+        let queue_family_properties = self.queue_family_properties();
+
+        let DependencyInfo {
+            dependency_flags: _,
+            memory_barriers,
+            buffer_memory_barriers,
+            image_memory_barriers,
+            _ne: _,
+        } = dependency_info;
+
+        if !buffer_memory_barriers.is_empty() || !image_memory_barriers.is_empty() {
+            return Err(EventCommandError::BufferOrImageMemoryBarrierNotSupported);
+        }
+
+        for (barrier_index, barrier) in memory_barriers.iter().enumerate() {
+            let &MemoryBarrier {
+                src_stages,
+                src_access,
+                dst_stages,
+                dst_access,
+                _ne: _,
+            } = barrier;
+
+            for (stages, access) in [(src_stages, src_access), (dst_stages, dst_access)] {
+                // VUID-VkMemoryBarrier2-srcStageMask-parameter
+                // VUID-VkMemoryBarrier2-dstStageMask-parameter
+                validate_stages(device, stages)?;
+
+                // VUID-VkMemoryBarrier2-srcAccessMask-parameter
+                // VUID-VkMemoryBarrier2-dstAccessMask-parameter
+                access.validate_device(device)?;
+
+                // VUID-vkCmdSetEvent2-synchronization2-03824
+                // VUID-vkCmdWaitEvents2-synchronization2-03836
+                if access.is_2() && !device.enabled_features().synchronization2 {
+                    return Err(EventCommandError::RequirementNotMet {
+                        required_for: "One of `dependency_info.memory_barriers` has an element \
+                            where `src_access` or `dst_access` contains flags from \
+                            `VkAccessFlagBits2`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["synchronization2"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-vkCmdSetEvent2-srcStageMask-03827
+                // VUID-vkCmdSetEvent2-dstStageMask-03828
+                if !PipelineStages::from(queue_family_properties.queue_flags).contains(stages) {
+                    return Err(EventCommandError::MemoryBarrierStageNotSupported {
+                        barrier_index,
+                    });
+                }
+
+                // VUID-VkMemoryBarrier2-srcAccessMask-03900
+                // ..
+                // VUID-VkMemoryBarrier2-dstAccessMask-07458
+                if !AccessFlags::from(stages).contains(access) {
+                    return Err(EventCommandError::MemoryBarrierAccessNotSupportedByStages {
+                        barrier_index,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Checks the requirements that apply to any pipeline stage mask of an event command.
+fn validate_stages(device: &Device, stages: PipelineStages) -> Result<(), EventCommandError> {
+    stages.validate_device(device)?;
+
+    let enabled_features = device.enabled_features();
+
+    if !enabled_features.synchronization2 {
+        if stages.is_2() {
+            return Err(EventCommandError::RequirementNotMet {
+                required_for: "a pipeline stage mask contains flags from \
+                    `VkPipelineStageFlagBits2`",
+                requires_one_of: RequiresOneOf {
+                    features: &["synchronization2"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        if stages.is_empty() {
+            return Err(EventCommandError::RequirementNotMet {
+                required_for: "a pipeline stage mask is empty",
+                requires_one_of: RequiresOneOf {
+                    features: &["synchronization2"],
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    let stage_features: [(PipelineStages, &'static str, bool, &'static [&'static str]); 10] = [
+        (
+            PipelineStages::GEOMETRY_SHADER,
+            "a pipeline stage mask contains `PipelineStages::GEOMETRY_SHADER`",
+            enabled_features.geometry_shader,
+            &["geometry_shader"],
+        ),
+        (
+            PipelineStages::TESSELLATION_CONTROL_SHADER
+                | PipelineStages::TESSELLATION_EVALUATION_SHADER,
+            "a pipeline stage mask contains `PipelineStages::TESSELLATION_CONTROL_SHADER` or \
+                `PipelineStages::TESSELLATION_EVALUATION_SHADER`",
+            enabled_features.tessellation_shader,
+            &["tessellation_shader"],
+        ),
+        (
+            PipelineStages::CONDITIONAL_RENDERING,
+            "a pipeline stage mask contains `PipelineStages::CONDITIONAL_RENDERING`",
+            enabled_features.conditional_rendering,
+            &["conditional_rendering"],
+        ),
+        (
+            PipelineStages::FRAGMENT_DENSITY_PROCESS,
+            "a pipeline stage mask contains `PipelineStages::FRAGMENT_DENSITY_PROCESS`",
+            enabled_features.fragment_density_map,
+            &["fragment_density_map"],
+        ),
+        (
+            PipelineStages::TRANSFORM_FEEDBACK,
+            "a pipeline stage mask contains `PipelineStages::TRANSFORM_FEEDBACK`",
+            enabled_features.transform_feedback,
+            &["transform_feedback"],
+        ),
+        (
+            PipelineStages::MESH_SHADER,
+            "a pipeline stage mask contains `PipelineStages::MESH_SHADER`",
+            enabled_features.mesh_shader,
+            &["mesh_shader"],
+        ),
+        (
+            PipelineStages::TASK_SHADER,
+            "a pipeline stage mask contains `PipelineStages::TASK_SHADER`",
+            enabled_features.task_shader,
+            &["task_shader"],
+        ),
+        (
+            PipelineStages::FRAGMENT_SHADING_RATE_ATTACHMENT,
+            "a pipeline stage mask contains `PipelineStages::FRAGMENT_SHADING_RATE_ATTACHMENT`",
+            enabled_features.attachment_fragment_shading_rate
+                || enabled_features.shading_rate_image,
+            &["attachment_fragment_shading_rate", "shading_rate_image"],
+        ),
+        (
+            PipelineStages::SUBPASS_SHADING,
+            "a pipeline stage mask contains `PipelineStages::SUBPASS_SHADING`",
+            enabled_features.subpass_shading,
+            &["subpass_shading"],
+        ),
+        (
+            PipelineStages::INVOCATION_MASK,
+            "a pipeline stage mask contains `PipelineStages::INVOCATION_MASK`",
+            enabled_features.invocation_mask,
+            &["invocation_mask"],
+        ),
+    ];
+
+    // VUID-VkMemoryBarrier2-srcStageMask-03929
+    // ..
+    // VUID-VkMemoryBarrier2-dstStageMask-04995
+    for (stage, required_for, enabled, features) in stage_features {
+        if stages.intersects(stage) && !enabled {
+            return Err(EventCommandError::RequirementNotMet {
+                required_for,
+                requires_one_of: RequiresOneOf {
+                    features,
+                    ..Default::default()
+                },
+            });
+        }
+    }
+
+    Ok(())
+}
 
 impl SyncCommandBufferBuilder {
     /// Calls `vkCmdSetEvent` on the builder.
@@ -260,6 +687,9 @@ impl UnsafeCommandBufferBuilder {
                 );
             }
         } else {
+            // Stages and accesses that only exist in `VkPipelineStageFlagBits2` and
+            // `VkAccessFlagBits2`, such as the ones used for video coding, are replaced with
+            // broader ones that `vkCmdPipelineBarrier` can express.
             let mut src_stage_mask = ash::vk::PipelineStageFlags::empty();
             let mut dst_stage_mask = ash::vk::PipelineStageFlags::empty();
 
@@ -277,12 +707,12 @@ impl UnsafeCommandBufferBuilder {
                     debug_assert!(AccessFlags::from(src_stages).contains(src_access));
                     debug_assert!(AccessFlags::from(dst_stages).contains(dst_access));
 
-                    src_stage_mask |= src_stages.into();
-                    dst_stage_mask |= dst_stages.into();
+                    src_stage_mask |= src_stages.into_legacy().into();
+                    dst_stage_mask |= dst_stages.into_legacy().into();
 
                     ash::vk::MemoryBarrier {
-                        src_access_mask: src_access.into(),
-                        dst_access_mask: dst_access.into(),
+                        src_access_mask: src_access.into_legacy().into(),
+                        dst_access_mask: dst_access.into_legacy().into(),
                         ..Default::default()
                     }
                 })
@@ -307,8 +737,8 @@ impl UnsafeCommandBufferBuilder {
                     debug_assert!(!range.is_empty());
                     debug_assert!(range.end <= buffer.size());
 
-                    src_stage_mask |= src_stages.into();
-                    dst_stage_mask |= dst_stages.into();
+                    src_stage_mask |= src_stages.into_legacy().into();
+                    dst_stage_mask |= dst_stages.into_legacy().into();
 
                     let (src_queue_family_index, dst_queue_family_index) =
                         queue_family_ownership_transfer.map_or(
@@ -317,8 +747,8 @@ impl UnsafeCommandBufferBuilder {
                         );
 
                     ash::vk::BufferMemoryBarrier {
-                        src_access_mask: src_access.into(),
-                        dst_access_mask: dst_access.into(),
+                        src_access_mask: src_access.into_legacy().into(),
+                        dst_access_mask: dst_access.into_legacy().into(),
                         src_queue_family_index,
                         dst_queue_family_index,
                         buffer: buffer.handle(),
@@ -363,8 +793,8 @@ impl UnsafeCommandBufferBuilder {
                         subresource_range.array_layers.end <= image.dimensions().array_layers()
                     );
 
-                    src_stage_mask |= src_stages.into();
-                    dst_stage_mask |= dst_stages.into();
+                    src_stage_mask |= src_stages.into_legacy().into();
+                    dst_stage_mask |= dst_stages.into_legacy().into();
 
                     let (src_queue_family_index, dst_queue_family_index) =
                         queue_family_ownership_transfer.map_or(
@@ -373,8 +803,8 @@ impl UnsafeCommandBufferBuilder {
                         );
 
                     ash::vk::ImageMemoryBarrier {
-                        src_access_mask: src_access.into(),
-                        dst_access_mask: dst_access.into(),
+                        src_access_mask: src_access.into_legacy().into(),
+                        dst_access_mask: dst_access.into_legacy().into(),
                         old_layout: old_layout.into(),
                         new_layout: new_layout.into(),
                         src_queue_family_index,
@@ -554,15 +984,15 @@ impl UnsafeCommandBufferBuilder {
             let mut stage_mask = ash::vk::PipelineStageFlags::empty();
 
             for barrier in memory_barriers {
-                stage_mask |= barrier.src_stages.into();
+                stage_mask |= barrier.src_stages.into_legacy().into();
             }
 
             for barrier in buffer_memory_barriers {
-                stage_mask |= barrier.src_stages.into();
+                stage_mask |= barrier.src_stages.into_legacy().into();
             }
 
             for barrier in image_memory_barriers {
-                stage_mask |= barrier.src_stages.into();
+                stage_mask |= barrier.src_stages.into_legacy().into();
             }
 
             if stage_mask.is_empty() {
@@ -784,12 +1214,12 @@ impl UnsafeCommandBufferBuilder {
                             _ne: _,
                         } = barrier;
 
-                        src_stage_mask |= src_stages.into();
-                        dst_stage_mask |= dst_stages.into();
+                        src_stage_mask |= src_stages.into_legacy().into();
+                        dst_stage_mask |= dst_stages.into_legacy().into();
 
                         ash::vk::MemoryBarrier {
-                            src_access_mask: src_access.into(),
-                            dst_access_mask: dst_access.into(),
+                            src_access_mask: src_access.into_legacy().into(),
+                            dst_access_mask: dst_access.into_legacy().into(),
                             ..Default::default()
                         }
                     })
@@ -809,8 +1239,8 @@ impl UnsafeCommandBufferBuilder {
                             _ne: _,
                         } = barrier;
 
-                        src_stage_mask |= src_stages.into();
-                        dst_stage_mask |= dst_stages.into();
+                        src_stage_mask |= src_stages.into_legacy().into();
+                        dst_stage_mask |= dst_stages.into_legacy().into();
 
                         let (src_queue_family_index, dst_queue_family_index) =
                             queue_family_ownership_transfer.map_or(
@@ -819,8 +1249,8 @@ impl UnsafeCommandBufferBuilder {
                             );
 
                         ash::vk::BufferMemoryBarrier {
-                            src_access_mask: src_access.into(),
-                            dst_access_mask: dst_access.into(),
+                            src_access_mask: src_access.into_legacy().into(),
+                            dst_access_mask: dst_access.into_legacy().into(),
                             src_queue_family_index,
                             dst_queue_family_index,
                             buffer: buffer.handle(),
@@ -847,8 +1277,8 @@ impl UnsafeCommandBufferBuilder {
                             _ne: _,
                         } = barrier;
 
-                        src_stage_mask |= src_stages.into();
-                        dst_stage_mask |= dst_stages.into();
+                        src_stage_mask |= src_stages.into_legacy().into();
+                        dst_stage_mask |= dst_stages.into_legacy().into();
 
                         let (src_queue_family_index, dst_queue_family_index) =
                             queue_family_ownership_transfer.map_or(
@@ -857,8 +1287,8 @@ impl UnsafeCommandBufferBuilder {
                             );
 
                         ash::vk::ImageMemoryBarrier {
-                            src_access_mask: src_access.into(),
-                            dst_access_mask: dst_access.into(),
+                            src_access_mask: src_access.into_legacy().into(),
+                            dst_access_mask: dst_access.into_legacy().into(),
                             old_layout: old_layout.into(),
                             new_layout: new_layout.into(),
                             src_queue_family_index,
@@ -925,3 +1355,171 @@ impl UnsafeCommandBufferBuilder {
 
     // TODO: wait_event
 }
+
+/// Error that can happen when recording an event command.
+#[derive(Clone, Debug)]
+pub enum EventCommandError {
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// Buffer or image memory barriers were provided, but only global memory barriers are
+    /// supported.
+    BufferOrImageMemoryBarrierNotSupported,
+
+    /// The `DependencyInfo` of an event does not match the one that it was set with.
+    EventDependencyInfoMismatch { event_index: usize },
+
+    /// An event was not set earlier in this command buffer, or was reset since.
+    EventNotSet { event_index: usize },
+
+    /// Operation forbidden inside of a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// The provided stages contain [`HOST`].
+    ///
+    /// [`HOST`]: crate::sync::PipelineStages::HOST
+    HostStageForbidden,
+
+    /// One or more accesses of a memory barrier are not supported by the corresponding
+    /// pipeline stages.
+    MemoryBarrierAccessNotSupportedByStages { barrier_index: usize },
+
+    /// One or more pipeline stages of a memory barrier are not supported by the queue
+    /// family of the command buffer.
+    MemoryBarrierStageNotSupported { barrier_index: usize },
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// One or more of the provided stages are not supported by the queue family of the command
+    /// buffer.
+    StageNotSupported,
+}
+
+impl Error for EventCommandError {}
+
+impl Display for EventCommandError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::BufferOrImageMemoryBarrierNotSupported => write!(
+                f,
+                "buffer or image memory barriers were provided, but only global memory barriers \
+                are supported",
+            ),
+            Self::EventDependencyInfoMismatch { event_index } => write!(
+                f,
+                "the `DependencyInfo` of event {} does not match the one that it was set with",
+                event_index,
+            ),
+            Self::EventNotSet { event_index } => write!(
+                f,
+                "event {} was not set earlier in this command buffer, or was reset since",
+                event_index,
+            ),
+            Self::ForbiddenInsideRenderPass => {
+                write!(f, "operation forbidden inside of a render pass")
+            }
+            Self::HostStageForbidden => write!(f, "the provided stages contain `HOST`"),
+            Self::MemoryBarrierAccessNotSupportedByStages { barrier_index } => write!(
+                f,
+                "one or more accesses of memory barrier {} are not supported by the \
+                corresponding pipeline stages",
+                barrier_index,
+            ),
+            Self::MemoryBarrierStageNotSupported { barrier_index } => write!(
+                f,
+                "one or more pipeline stages of memory barrier {} are not supported by the \
+                queue family of the command buffer",
+                barrier_index,
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::StageNotSupported => write!(
+                f,
+                "one or more of the provided stages are not supported by the queue family of the \
+                command buffer",
+            ),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for EventCommandError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_buffer::{
+        allocator::{CommandBufferBuilderAlloc, StandardCommandBufferAllocator},
+        sys::CommandBufferBeginInfo,
+        CommandBufferLevel, CommandBufferUsage,
+    };
+    use smallvec::smallvec;
+
+    // Records a barrier whose stages and accesses are only available in the `*2` flag types.
+    fn record_barrier_2(device: Arc<Device>, queue_family_index: u32) {
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let builder_alloc = allocator
+            .allocate(queue_family_index, CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap();
+
+        unsafe {
+            let mut builder = UnsafeCommandBufferBuilder::new(
+                builder_alloc.inner(),
+                CommandBufferBeginInfo {
+                    usage: CommandBufferUsage::OneTimeSubmit,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            builder.pipeline_barrier(&DependencyInfo {
+                memory_barriers: smallvec![MemoryBarrier {
+                    src_stages: PipelineStages::COPY,
+                    src_access: AccessFlags::TRANSFER_WRITE,
+                    dst_stages: PipelineStages::VERTEX_ATTRIBUTE_INPUT
+                        | PipelineStages::FRAGMENT_SHADER,
+                    dst_access: AccessFlags::VERTEX_ATTRIBUTE_READ
+                        | AccessFlags::SHADER_SAMPLED_READ,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            });
+            builder.build().unwrap();
+        }
+    }
+
+    #[test]
+    fn pipeline_barrier_synchronization2() {
+        let (device, queue) = gfx_dev_and_queue!(synchronization2);
+        assert!(device.enabled_features().synchronization2);
+
+        record_barrier_2(device, queue.queue_family_index());
+    }
+
+    #[test]
+    fn pipeline_barrier_legacy() {
+        let (device, queue) = gfx_dev_and_queue!();
+        assert!(!device.enabled_features().synchronization2);
+
+        record_barrier_2(device, queue.queue_family_index());
+    }
+}
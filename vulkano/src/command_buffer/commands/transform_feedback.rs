@@ -0,0 +1,882 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::TransformFeedbackState,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    DeviceSize, RequiresOneOf, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// # Commands for transform feedback.
+///
+/// These commands require the
+/// [`ext_transform_feedback`](crate::device::DeviceExtensions::ext_transform_feedback)
+/// extension and the [`transform_feedback`](crate::device::Features::transform_feedback) feature.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Binds buffers to receive the outputs of the last vertex processing shader stage while
+    /// transform feedback is active.
+    ///
+    /// The buffers are bound to consecutive binding slots, starting at `first_binding`. Each
+    /// buffer is captured to in its entirety; to capture to only part of a buffer, bind a slice
+    /// of it.
+    pub fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: impl IntoIterator<Item = Arc<dyn BufferAccess>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let transform_feedback_buffers: SmallVec<[_; 4]> =
+            transform_feedback_buffers.into_iter().collect();
+        self.validate_bind_transform_feedback_buffers(first_binding, &transform_feedback_buffers)?;
+
+        unsafe {
+            self.inner
+                .bind_transform_feedback_buffers(first_binding, transform_feedback_buffers);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_bind_transform_feedback_buffers(
+        &self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Arc<dyn BufferAccess>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        if !device.enabled_extensions().ext_transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::bind_transform_feedback_buffers`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-transformFeedback-02355
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::bind_transform_feedback_buffers`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-None-02365
+        if self.transform_feedback_state.is_some() {
+            return Err(TransformFeedbackError::TransformFeedbackActive);
+        }
+
+        let properties = device.physical_device().properties();
+        let max_buffers = properties.max_transform_feedback_buffers.unwrap_or(0);
+
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02356
+        // VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02357
+        if first_binding + transform_feedback_buffers.len() as u32 > max_buffers {
+            return Err(
+                TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                    buffer_count: first_binding + transform_feedback_buffers.len() as u32,
+                    max: max_buffers,
+                },
+            );
+        }
+
+        let max_buffer_size = properties.max_transform_feedback_buffer_size.unwrap_or(0);
+
+        for (index, buffer) in transform_feedback_buffers.iter().enumerate() {
+            let index = index as u32;
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-commonparent
+            assert_eq!(device, buffer.device());
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pBuffers-02360
+            if !buffer
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_BUFFER)
+            {
+                return Err(TransformFeedbackError::BufferMissingUsage { index });
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pOffsets-02359
+            if buffer.inner().offset % 4 != 0 {
+                return Err(TransformFeedbackError::BufferOffsetNotAligned { index });
+            }
+
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-pSize-02361
+            if buffer.size() > max_buffer_size {
+                return Err(
+                    TransformFeedbackError::MaxTransformFeedbackBufferSizeExceeded {
+                        index,
+                        size: buffer.size(),
+                        max: max_buffer_size,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Makes transform feedback active.
+    ///
+    /// While transform feedback is active, the outputs of the last vertex processing shader stage
+    /// of the bound graphics pipeline are written to the buffers that were bound with
+    /// [`bind_transform_feedback_buffers`](Self::bind_transform_feedback_buffers).
+    ///
+    /// `counter_buffers` provides a counter buffer for each transform feedback buffer starting at
+    /// `first_counter_buffer`. If a counter buffer is provided, then capturing resumes at the byte
+    /// offset that was stored in it by a previous
+    /// [`end_transform_feedback`](Self::end_transform_feedback), and the new byte offset is
+    /// written back to it when transform feedback is ended. If `None` is provided, capturing
+    /// starts at the beginning of the transform feedback buffer.
+    ///
+    /// Transform feedback must be ended in the same subpass it was made active in.
+    ///
+    /// # Safety
+    ///
+    /// - The last vertex processing shader stage of the currently bound graphics pipeline must
+    ///   declare the `Xfb` execution mode.
+    /// - Counter buffers that are provided must contain a valid byte offset, written by a
+    ///   previous `end_transform_feedback` or by the host.
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<Arc<dyn BufferAccess>>>,
+    ) -> Result<&mut Self, TransformFeedbackError> {
+        let counter_buffers: SmallVec<[_; 4]> = counter_buffers.into_iter().collect();
+        self.validate_begin_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        self.inner
+            .begin_transform_feedback(first_counter_buffer, counter_buffers.clone())?;
+        self.transform_feedback_state = Some(TransformFeedbackState {
+            first_counter_buffer,
+            counter_buffers,
+        });
+
+        Ok(self)
+    }
+
+    fn validate_begin_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Arc<dyn BufferAccess>>],
+    ) -> Result<(), TransformFeedbackError> {
+        let device = self.device();
+
+        if !device.enabled_extensions().ext_transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_transform_feedback`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-transformFeedback-02366
+        if !device.enabled_features().transform_feedback {
+            return Err(TransformFeedbackError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_transform_feedback`",
+                requires_one_of: RequiresOneOf {
+                    features: &["transform_feedback"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-renderpass
+        let render_pass_state = self
+            .render_pass_state
+            .as_ref()
+            .ok_or(TransformFeedbackError::ForbiddenOutsideRenderPass)?;
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-02373
+        if render_pass_state.view_mask != 0 {
+            return Err(TransformFeedbackError::MultiviewEnabled);
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-02367
+        if self.transform_feedback_state.is_some() {
+            return Err(TransformFeedbackError::TransformFeedbackActive);
+        }
+
+        let max_buffers = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap_or(0);
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02368
+        // VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02369
+        if first_counter_buffer + counter_buffers.len() as u32 > max_buffers {
+            return Err(
+                TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                    buffer_count: first_counter_buffer + counter_buffers.len() as u32,
+                    max: max_buffers,
+                },
+            );
+        }
+
+        for (index, counter_buffer) in counter_buffers.iter().enumerate() {
+            let index = index as u32;
+
+            let counter_buffer = match counter_buffer {
+                Some(counter_buffer) => counter_buffer,
+                None => continue,
+            };
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-commonparent
+            assert_eq!(device, counter_buffer.device());
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-pCounterBuffers-02372
+            if !counter_buffer
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER)
+            {
+                return Err(TransformFeedbackError::CounterBufferMissingUsage { index });
+            }
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-pCounterBufferOffsets-02370
+            if counter_buffer.size() < 4 {
+                return Err(TransformFeedbackError::CounterBufferTooSmall { index });
+            }
+
+            // VUID?
+            if counter_buffer.inner().offset % 4 != 0 {
+                return Err(TransformFeedbackError::CounterBufferOffsetNotAligned { index });
+            }
+        }
+
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-04128
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-06233
+        // Not checked, therefore unsafe.
+        // TODO: add check.
+
+        Ok(())
+    }
+
+    /// Ends transform feedback that was made active with
+    /// [`begin_transform_feedback`](Self::begin_transform_feedback).
+    ///
+    /// The current byte offsets of the transform feedback buffers are written to the counter
+    /// buffers that were provided to `begin_transform_feedback`.
+    pub fn end_transform_feedback(&mut self) -> Result<&mut Self, TransformFeedbackError> {
+        self.validate_end_transform_feedback()?;
+
+        unsafe {
+            let TransformFeedbackState {
+                first_counter_buffer,
+                counter_buffers,
+            } = self.transform_feedback_state.take().unwrap();
+            self.inner
+                .end_transform_feedback(first_counter_buffer, counter_buffers);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_end_transform_feedback(&self) -> Result<(), TransformFeedbackError> {
+        // VUID-vkCmdEndTransformFeedbackEXT-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(TransformFeedbackError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndTransformFeedbackEXT-renderpass
+        if self.render_pass_state.is_none() {
+            return Err(TransformFeedbackError::ForbiddenOutsideRenderPass);
+        }
+
+        // VUID-vkCmdEndTransformFeedbackEXT-None-02375
+        if self.transform_feedback_state.is_none() {
+            return Err(TransformFeedbackError::TransformFeedbackNotActive);
+        }
+
+        Ok(())
+    }
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    #[inline]
+    pub unsafe fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+    ) {
+        struct Cmd {
+            first_binding: u32,
+            transform_feedback_buffers: SmallVec<[Arc<dyn BufferAccess>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "bind_transform_feedback_buffers"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.bind_transform_feedback_buffers(
+                    self.first_binding,
+                    self.transform_feedback_buffers
+                        .iter()
+                        .map(|buffer| buffer.as_ref()),
+                );
+            }
+        }
+
+        for (index, buffer) in transform_feedback_buffers.iter().enumerate() {
+            self.current_state
+                .transform_feedback_buffers
+                .insert(first_binding + index as u32, buffer.clone());
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_binding,
+            transform_feedback_buffers,
+        }));
+    }
+
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    ///
+    /// All transform feedback buffers that are currently bound, and the provided counter buffers,
+    /// are added as resources of this command. They can't be accessed by any other command until
+    /// transform feedback is ended, so this covers all the accesses made while it's active.
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            first_counter_buffer: u32,
+            counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "begin_transform_feedback"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_transform_feedback(
+                    self.first_counter_buffer,
+                    self.counter_buffers
+                        .iter()
+                        .map(|buffer| buffer.as_ref().map(|buffer| buffer.as_ref())),
+                );
+            }
+        }
+
+        let command_index = self.commands.len();
+        let command_name = "begin_transform_feedback";
+        let mut resources: Vec<_> = self
+            .current_state
+            .transform_feedback_buffers
+            .iter()
+            .map(|(&binding, buffer)| {
+                (
+                    ResourceUseRef {
+                        command_index,
+                        command_name,
+                        resource_in_command: ResourceInCommand::TransformFeedbackBuffer { binding },
+                        secondary_use_ref: None,
+                    },
+                    Resource::Buffer {
+                        buffer: buffer.clone(),
+                        range: 0..buffer.size(),
+                        memory: PipelineMemoryAccess {
+                            stages: PipelineStages::TRANSFORM_FEEDBACK,
+                            access: AccessFlags::TRANSFORM_FEEDBACK_WRITE,
+                            exclusive: true,
+                        },
+                    },
+                )
+            })
+            .collect();
+        resources.extend(
+            counter_buffers
+                .iter()
+                .enumerate()
+                .filter_map(|(index, buffer)| {
+                    buffer.as_ref().map(|buffer| {
+                        (
+                            ResourceUseRef {
+                                command_index,
+                                command_name,
+                                resource_in_command:
+                                    ResourceInCommand::TransformFeedbackCounterBuffer {
+                                        index: first_counter_buffer + index as u32,
+                                    },
+                                secondary_use_ref: None,
+                            },
+                            Resource::Buffer {
+                                buffer: buffer.clone(),
+                                range: 0..4,
+                                memory: PipelineMemoryAccess {
+                                    stages: PipelineStages::DRAW_INDIRECT
+                                        | PipelineStages::TRANSFORM_FEEDBACK,
+                                    access: AccessFlags::TRANSFORM_FEEDBACK_COUNTER_READ
+                                        | AccessFlags::TRANSFORM_FEEDBACK_COUNTER_WRITE,
+                                    exclusive: true,
+                                },
+                            },
+                        )
+                    })
+                }),
+        );
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_counter_buffer,
+            counter_buffers,
+        }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    ///
+    /// The counter buffers must be the same as those that were provided to
+    /// `begin_transform_feedback`, which have already been added as resources.
+    #[inline]
+    pub unsafe fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+    ) {
+        struct Cmd {
+            first_counter_buffer: u32,
+            counter_buffers: SmallVec<[Option<Arc<dyn BufferAccess>>; 4]>,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "end_transform_feedback"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_transform_feedback(
+                    self.first_counter_buffer,
+                    self.counter_buffers
+                        .iter()
+                        .map(|buffer| buffer.as_ref().map(|buffer| buffer.as_ref())),
+                );
+            }
+        }
+
+        self.commands.push(Box::new(Cmd {
+            first_counter_buffer,
+            counter_buffers,
+        }));
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBindTransformFeedbackBuffersEXT` on the builder.
+    pub unsafe fn bind_transform_feedback_buffers<'a>(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: impl IntoIterator<Item = &'a dyn BufferAccess>,
+    ) {
+        let mut buffers_vk: SmallVec<[_; 4]> = SmallVec::new();
+        let mut offsets_vk: SmallVec<[_; 4]> = SmallVec::new();
+        let mut sizes_vk: SmallVec<[_; 4]> = SmallVec::new();
+
+        for buffer in transform_feedback_buffers {
+            let inner = buffer.inner();
+            debug_assert!(inner
+                .buffer
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_BUFFER));
+
+            buffers_vk.push(inner.buffer.handle());
+            offsets_vk.push(inner.offset);
+            sizes_vk.push(buffer.size());
+        }
+
+        if buffers_vk.is_empty() {
+            return;
+        }
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback
+            .cmd_bind_transform_feedback_buffers_ext)(
+            self.handle,
+            first_binding,
+            buffers_vk.len() as u32,
+            buffers_vk.as_ptr(),
+            offsets_vk.as_ptr(),
+            sizes_vk.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdBeginTransformFeedbackEXT` on the builder.
+    pub unsafe fn begin_transform_feedback<'a>(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<&'a dyn BufferAccess>>,
+    ) {
+        let (counter_buffers_vk, counter_buffer_offsets_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
+            counter_buffers
+                .into_iter()
+                .map(|buffer| match buffer {
+                    Some(buffer) => {
+                        let inner = buffer.inner();
+                        (inner.buffer.handle(), inner.offset)
+                    }
+                    None => (ash::vk::Buffer::null(), 0),
+                })
+                .unzip();
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback.cmd_begin_transform_feedback_ext)(
+            self.handle,
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+    }
+
+    /// Calls `vkCmdEndTransformFeedbackEXT` on the builder.
+    pub unsafe fn end_transform_feedback<'a>(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: impl IntoIterator<Item = Option<&'a dyn BufferAccess>>,
+    ) {
+        let (counter_buffers_vk, counter_buffer_offsets_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
+            counter_buffers
+                .into_iter()
+                .map(|buffer| match buffer {
+                    Some(buffer) => {
+                        let inner = buffer.inner();
+                        (inner.buffer.handle(), inner.offset)
+                    }
+                    None => (ash::vk::Buffer::null(), 0),
+                })
+                .unzip();
+
+        let fns = self.device.fns();
+        (fns.ext_transform_feedback.cmd_end_transform_feedback_ext)(
+            self.handle,
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+    }
+}
+
+/// Error that can happen when recording a transform feedback command.
+#[derive(Clone, Debug)]
+pub enum TransformFeedbackError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// A transform feedback buffer is missing the `transform_feedback_buffer` usage.
+    BufferMissingUsage {
+        index: u32,
+    },
+
+    /// The offset of a transform feedback buffer is not a multiple of 4.
+    BufferOffsetNotAligned {
+        index: u32,
+    },
+
+    /// A counter buffer is missing the `transform_feedback_counter_buffer` usage.
+    CounterBufferMissingUsage {
+        index: u32,
+    },
+
+    /// The offset of a counter buffer is not a multiple of 4.
+    CounterBufferOffsetNotAligned {
+        index: u32,
+    },
+
+    /// A counter buffer is smaller than 4 bytes.
+    CounterBufferTooSmall {
+        index: u32,
+    },
+
+    /// Operation forbidden outside a render pass.
+    ForbiddenOutsideRenderPass,
+
+    /// The `max_transform_feedback_buffers` limit has been exceeded.
+    MaxTransformFeedbackBuffersExceeded {
+        buffer_count: u32,
+        max: u32,
+    },
+
+    /// The `max_transform_feedback_buffer_size` limit has been exceeded.
+    MaxTransformFeedbackBufferSizeExceeded {
+        index: u32,
+        size: DeviceSize,
+        max: DeviceSize,
+    },
+
+    /// The current render pass instance has multiview enabled.
+    MultiviewEnabled,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// Transform feedback is active.
+    TransformFeedbackActive,
+
+    /// Transform feedback is not active.
+    TransformFeedbackNotActive,
+}
+
+impl Error for TransformFeedbackError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for TransformFeedbackError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::BufferMissingUsage { index } => write!(
+                f,
+                "transform feedback buffer {} is missing the `transform_feedback_buffer` usage",
+                index,
+            ),
+            Self::BufferOffsetNotAligned { index } => write!(
+                f,
+                "the offset of transform feedback buffer {} is not a multiple of 4",
+                index,
+            ),
+            Self::CounterBufferMissingUsage { index } => write!(
+                f,
+                "counter buffer {} is missing the `transform_feedback_counter_buffer` usage",
+                index,
+            ),
+            Self::CounterBufferOffsetNotAligned { index } => write!(
+                f,
+                "the offset of counter buffer {} is not a multiple of 4",
+                index,
+            ),
+            Self::CounterBufferTooSmall { index } => {
+                write!(f, "counter buffer {} is smaller than 4 bytes", index)
+            }
+            Self::ForbiddenOutsideRenderPass => {
+                write!(f, "operation forbidden outside a render pass")
+            }
+            Self::MaxTransformFeedbackBuffersExceeded { .. } => write!(
+                f,
+                "the `max_transform_feedback_buffers` limit has been exceeded",
+            ),
+            Self::MaxTransformFeedbackBufferSizeExceeded { .. } => write!(
+                f,
+                "the `max_transform_feedback_buffer_size` limit has been exceeded",
+            ),
+            Self::MultiviewEnabled => {
+                write!(f, "the current render pass instance has multiview enabled",)
+            }
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::TransformFeedbackActive => write!(f, "transform feedback is active"),
+            Self::TransformFeedbackNotActive => write!(f, "transform feedback is not active"),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for TransformFeedbackError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::CpuAccessibleBuffer,
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, CommandBufferUsage, PrimaryAutoCommandBuffer,
+        },
+        device::Device,
+        memory::allocator::StandardMemoryAllocator,
+    };
+
+    fn buffer(device: Arc<Device>, usage: BufferUsage) -> Arc<dyn BufferAccess> {
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        CpuAccessibleBuffer::from_iter(&memory_allocator, usage, false, [0u32; 16]).unwrap()
+    }
+
+    fn command_buffer_builder(
+        allocator: &StandardCommandBufferAllocator,
+        queue_family_index: u32,
+    ) -> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandBufferAllocator> {
+        AutoCommandBufferBuilder::primary(
+            allocator,
+            queue_family_index,
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn requires_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let buffer = buffer(device, BufferUsage::VERTEX_BUFFER);
+
+        match cbb.bind_transform_feedback_buffers(0, [buffer]) {
+            Err(TransformFeedbackError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn bind_buffers() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_transform_feedback],
+            features: [transform_feedback]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let buffer = buffer(device, BufferUsage::TRANSFORM_FEEDBACK_BUFFER);
+
+        cbb.bind_transform_feedback_buffers(0, [buffer]).unwrap();
+        cbb.build().unwrap();
+    }
+
+    #[test]
+    fn bind_buffers_missing_usage() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_transform_feedback],
+            features: [transform_feedback]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let buffers = [
+            buffer(device.clone(), BufferUsage::TRANSFORM_FEEDBACK_BUFFER),
+            buffer(device, BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER),
+        ];
+
+        match cbb.bind_transform_feedback_buffers(0, buffers) {
+            Err(TransformFeedbackError::BufferMissingUsage { index: 1 }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn bind_buffers_exceeds_max() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_transform_feedback],
+            features: [transform_feedback]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let max = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap();
+        let buffer = buffer(device, BufferUsage::TRANSFORM_FEEDBACK_BUFFER);
+
+        match cbb.bind_transform_feedback_buffers(max, [buffer]) {
+            Err(TransformFeedbackError::MaxTransformFeedbackBuffersExceeded {
+                buffer_count,
+                max: limit,
+            }) => {
+                assert_eq!(buffer_count, max + 1);
+                assert_eq!(limit, max);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn begin_end_outside_render_pass() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_transform_feedback],
+            features: [transform_feedback]
+        );
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = command_buffer_builder(&allocator, queue.queue_family_index());
+        let counter_buffer = buffer(device, BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER);
+
+        match unsafe { cbb.begin_transform_feedback(0, [Some(counter_buffer)]) } {
+            Err(TransformFeedbackError::ForbiddenOutsideRenderPass) => (),
+            _ => panic!(),
+        }
+
+        match cbb.end_transform_feedback() {
+            Err(TransformFeedbackError::ForbiddenOutsideRenderPass) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,2787 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferAccess, BufferUsage},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::VideoCodingState,
+        synced::{Command, Resource, SyncCommandBufferBuilder, SyncCommandBufferBuilderError},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand, ResourceUseRef,
+    },
+    device::{DeviceOwned, QueueFlags},
+    image::{ImageLayout, ImageSubresourceRange, ImageUsage},
+    sync::{AccessFlags, PipelineMemoryAccess, PipelineStages},
+    video::{
+        VideoBeginCodingInfo, VideoCodecOperation, VideoCodingControlFlags, VideoCodingControlInfo,
+        VideoDecodeCapabilityFlags, VideoDecodeCodecPictureInfo, VideoDecodeCodecReferenceInfo,
+        VideoDecodeInfo, VideoEncodeCapabilityFlags, VideoEncodeCodecPictureInfo,
+        VideoEncodeH264DpbSlotInfo, VideoEncodeH264ReferenceListsInfo, VideoEncodeH265DpbSlotInfo,
+        VideoEncodeH265ReferenceListsInfo, VideoEncodeInfo, VideoEncodeRateControlInfo,
+        VideoEncodeRateControlMode, VideoEncodeRateControlModes, VideoPictureResourceInfo,
+        VideoReferenceSlotInfo,
+    },
+    DeviceSize, RequirementNotMet, RequiresOneOf, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    ptr,
+};
+
+/// # Commands for video coding.
+///
+/// These commands require the
+/// [`khr_video_queue`](crate::device::DeviceExtensions::khr_video_queue) extension, and can only
+/// be recorded in primary command buffers for queue families that support video decode or video
+/// encode operations. See the [`video`](crate::video) module for an overview.
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins a video coding scope.
+    ///
+    /// The video session, its parameters and the DPB slots given in `begin_info` are used by the
+    /// video coding commands that are recorded until
+    /// [`end_video_coding`](Self::end_video_coding) is called.
+    pub fn begin_video_coding(
+        &mut self,
+        begin_info: VideoBeginCodingInfo,
+    ) -> Result<&mut Self, VideoCodingError> {
+        self.validate_begin_video_coding(&begin_info)?;
+
+        let video_coding_state = VideoCodingState {
+            video_session: begin_info.video_session.clone(),
+            bound_picture_resources: begin_info
+                .reference_slots
+                .iter()
+                .filter_map(|slot| slot.picture_resource.as_ref())
+                .map(|picture_resource| {
+                    (
+                        picture_resource.image_view.handle(),
+                        picture_resource.base_array_layer,
+                    )
+                })
+                .collect(),
+        };
+
+        unsafe {
+            self.inner.begin_video_coding(begin_info);
+        }
+
+        self.video_coding_state = Some(video_coding_state);
+
+        Ok(self)
+    }
+
+    fn validate_begin_video_coding(
+        &self,
+        begin_info: &VideoBeginCodingInfo,
+    ) -> Result<(), VideoCodingError> {
+        let device = self.device();
+
+        if !device.enabled_extensions().khr_video_queue {
+            return Err(VideoCodingError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::begin_video_coding`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_video_queue"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdBeginVideoCodingKHR-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::VIDEO_DECODE | QueueFlags::VIDEO_ENCODE)
+        {
+            return Err(VideoCodingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdBeginVideoCodingKHR-bufferlevel
+        if self.inheritance_info.is_some() {
+            return Err(VideoCodingError::ForbiddenInSecondaryCommandBuffer);
+        }
+
+        // VUID-vkCmdBeginVideoCodingKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(VideoCodingError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdBeginVideoCodingKHR-videocoding
+        if self.video_coding_state.is_some() {
+            return Err(VideoCodingError::AlreadyActive);
+        }
+
+        let &VideoBeginCodingInfo {
+            ref video_session,
+            ref video_session_parameters,
+            ref reference_slots,
+            _ne: _,
+        } = begin_info;
+
+        // VUID-vkCmdBeginVideoCodingKHR-commonparent
+        assert_eq!(device, video_session.device());
+
+        // VUID-vkCmdBeginVideoCodingKHR-commandBuffer-07231
+        if video_session.queue_family_index() != self.queue_family_index {
+            return Err(VideoCodingError::VideoSessionQueueFamilyMismatch);
+        }
+
+        match video_session_parameters {
+            Some(video_session_parameters) => {
+                // VUID-VkVideoBeginCodingInfoKHR-videoSessionParameters-04857
+                if video_session_parameters.video_session().handle() != video_session.handle() {
+                    return Err(VideoCodingError::VideoSessionParametersMismatch);
+                }
+            }
+            None => {
+                // VUID-VkVideoBeginCodingInfoKHR-videoSession-07247
+                // VUID-VkVideoBeginCodingInfoKHR-videoSession-07248
+                // VUID-VkVideoBeginCodingInfoKHR-videoSession-07249
+                // VUID-VkVideoBeginCodingInfoKHR-videoSession-07250
+                if matches!(
+                    video_session.video_profile().video_codec_operation(),
+                    VideoCodecOperation::DecodeH264
+                        | VideoCodecOperation::DecodeH265
+                        | VideoCodecOperation::EncodeH264
+                        | VideoCodecOperation::EncodeH265
+                ) {
+                    return Err(VideoCodingError::VideoSessionParametersMissing);
+                }
+            }
+        }
+
+        let dpb_usage = dpb_usage(video_session.video_profile().video_codec_operation());
+
+        let mut slot_indices: SmallVec<[u32; 8]> = SmallVec::new();
+
+        for (index, reference_slot) in reference_slots.iter().enumerate() {
+            let &VideoReferenceSlotInfo {
+                slot_index,
+                ref picture_resource,
+                codec_reference_info: _,
+                _ne: _,
+            } = reference_slot;
+
+            if let Some(slot_index) = slot_index {
+                // VUID-VkVideoBeginCodingInfoKHR-slotIndex-04856
+                if slot_index >= video_session.max_dpb_slots() {
+                    return Err(VideoCodingError::SlotIndexOutOfRange {
+                        slot_index,
+                        max_dpb_slots: video_session.max_dpb_slots(),
+                    });
+                }
+
+                // VUID-VkVideoBeginCodingInfoKHR-slotIndex-04856
+                if slot_indices.contains(&slot_index) {
+                    return Err(VideoCodingError::SlotIndexNotUnique { slot_index });
+                }
+
+                slot_indices.push(slot_index);
+            }
+
+            if let Some(picture_resource) = picture_resource {
+                self.validate_picture_resource(
+                    picture_resource,
+                    dpb_usage,
+                    ResourceInCommand::VideoReferencePicture {
+                        index: index as u32,
+                    },
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ends the current video coding scope.
+    pub fn end_video_coding(&mut self) -> Result<&mut Self, VideoCodingError> {
+        self.validate_end_video_coding()?;
+
+        unsafe {
+            self.inner.end_video_coding();
+        }
+
+        self.video_coding_state = None;
+
+        Ok(self)
+    }
+
+    fn validate_end_video_coding(&self) -> Result<(), VideoCodingError> {
+        // VUID-vkCmdEndVideoCodingKHR-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::VIDEO_DECODE | QueueFlags::VIDEO_ENCODE)
+        {
+            return Err(VideoCodingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEndVideoCodingKHR-videocoding
+        if self.video_coding_state.is_none() {
+            return Err(VideoCodingError::NotActive);
+        }
+
+        // VUID-vkCmdEndVideoCodingKHR-None-07251
+        if !self.query_state.is_empty() {
+            return Err(VideoCodingError::QueryActive);
+        }
+
+        Ok(())
+    }
+
+    /// Controls the state of the video session of the current video coding scope.
+    ///
+    /// A newly created video session must be reset with [`VideoCodingControlFlags::RESET`] before
+    /// it is used to decode.
+    ///
+    /// [`VideoCodingControlFlags::RESET`]: crate::video::VideoCodingControlFlags::RESET
+    pub fn control_video_coding(
+        &mut self,
+        control_info: VideoCodingControlInfo,
+    ) -> Result<&mut Self, VideoCodingError> {
+        self.validate_control_video_coding(&control_info)?;
+
+        unsafe {
+            self.inner.control_video_coding(control_info);
+        }
+
+        Ok(self)
+    }
+
+    fn validate_control_video_coding(
+        &self,
+        control_info: &VideoCodingControlInfo,
+    ) -> Result<(), VideoCodingError> {
+        // VUID-vkCmdControlVideoCodingKHR-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::VIDEO_DECODE | QueueFlags::VIDEO_ENCODE)
+        {
+            return Err(VideoCodingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdControlVideoCodingKHR-videocoding
+        let video_coding_state = self
+            .video_coding_state
+            .as_ref()
+            .ok_or(VideoCodingError::NotActive)?;
+
+        let &VideoCodingControlInfo {
+            flags,
+            ref encode_rate_control,
+            _ne: _,
+        } = control_info;
+
+        // VUID-VkVideoCodingControlInfoKHR-flags-parameter
+        flags.validate_device(self.device())?;
+
+        // VUID-VkVideoCodingControlInfoKHR-flags-requiredbitmask
+        assert!(!flags.is_empty());
+
+        // VUID-VkVideoCodingControlInfoKHR-flags-07017
+        assert_eq!(
+            flags.intersects(VideoCodingControlFlags::ENCODE_RATE_CONTROL),
+            encode_rate_control.is_some(),
+            "`encode_rate_control` must be `Some` if and only if `flags` contains \
+            `VideoCodingControlFlags::ENCODE_RATE_CONTROL`",
+        );
+
+        if let Some(encode_rate_control) = encode_rate_control {
+            self.validate_encode_rate_control(encode_rate_control, video_coding_state)?;
+        }
+
+        Ok(())
+    }
+
+    fn validate_encode_rate_control(
+        &self,
+        encode_rate_control: &VideoEncodeRateControlInfo,
+        video_coding_state: &VideoCodingState,
+    ) -> Result<(), VideoCodingError> {
+        let video_session = &video_coding_state.video_session;
+        let video_capabilities = video_session.video_capabilities();
+        let video_codec_operation = video_session.video_profile().video_codec_operation();
+
+        // VUID-vkCmdControlVideoCodingKHR-pCodingControlInfo-07018
+        if !video_codec_operation.is_encode() {
+            return Err(VideoCodingError::NotEncodeVideoSession);
+        }
+
+        let &VideoEncodeRateControlInfo {
+            rate_control_mode,
+            ref layers,
+            ref codec_rate_control_info,
+            _ne: _,
+        } = encode_rate_control;
+
+        // VUID-VkVideoEncodeRateControlInfoKHR-rateControlMode-parameter
+        rate_control_mode.validate_device(self.device())?;
+
+        let required_mode = match rate_control_mode {
+            VideoEncodeRateControlMode::None => VideoEncodeRateControlModes::empty(),
+            VideoEncodeRateControlMode::Cbr => VideoEncodeRateControlModes::CBR,
+            VideoEncodeRateControlMode::Vbr => VideoEncodeRateControlModes::VBR,
+        };
+
+        // VUID?
+        if !video_capabilities
+            .encode_rate_control_modes
+            .contains(required_mode)
+        {
+            return Err(VideoCodingError::RateControlModeNotSupported { rate_control_mode });
+        }
+
+        if rate_control_mode == VideoEncodeRateControlMode::None {
+            // VUID-VkVideoEncodeRateControlInfoKHR-rateControlMode-07022
+            if !layers.is_empty() {
+                return Err(VideoCodingError::RateControlLayersNotAllowed);
+            }
+        } else {
+            // VUID-VkVideoEncodeRateControlInfoKHR-layerCount-07024
+            if layers.len() as u32 > video_capabilities.encode_rate_control_layer_count {
+                return Err(VideoCodingError::MaxRateControlLayersExceeded {
+                    provided: layers.len() as u32,
+                    max_supported: video_capabilities.encode_rate_control_layer_count,
+                });
+            }
+        }
+
+        for layer in layers {
+            // VUID-VkVideoEncodeRateControlLayerInfoKHR-frameRateDenominator-07033
+            assert!(
+                layer.frame_rate[1] != 0,
+                "the denominator of `frame_rate` must not be zero",
+            );
+
+            if let Some(codec_layer_info) = &layer.codec_layer_info {
+                if codec_layer_info.video_codec_operation() != video_codec_operation {
+                    return Err(VideoCodingError::CodecMismatch);
+                }
+            }
+
+            // VUID-VkVideoEncodeRateControlLayerInfoKHR-pNext-07031
+            if rate_control_mode == VideoEncodeRateControlMode::Cbr
+                && layer.max_bitrate != layer.average_bitrate
+            {
+                return Err(VideoCodingError::CbrBitrateMismatch);
+            }
+        }
+
+        if let Some(codec_rate_control_info) = codec_rate_control_info {
+            if codec_rate_control_info.video_codec_operation() != video_codec_operation {
+                return Err(VideoCodingError::CodecMismatch);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes a picture, in the current video coding scope.
+    ///
+    /// The bitstream of the picture is read from `decode_info.src_buffer`, and the decoded
+    /// picture is written to `decode_info.dst_picture_resource`. If
+    /// `decode_info.setup_reference_slot` is `Some`, the reconstructed picture is also written to
+    /// that DPB slot, so that it can be used as a reference by later pictures.
+    pub fn decode_video(
+        &mut self,
+        decode_info: VideoDecodeInfo,
+    ) -> Result<&mut Self, VideoCodingError> {
+        self.validate_decode_video(&decode_info)?;
+
+        unsafe {
+            self.inner.decode_video(decode_info)?;
+        }
+
+        Ok(self)
+    }
+
+    fn validate_decode_video(&self, decode_info: &VideoDecodeInfo) -> Result<(), VideoCodingError> {
+        let device = self.device();
+
+        if !device.enabled_extensions().khr_video_decode_queue {
+            return Err(VideoCodingError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::decode_video`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_video_decode_queue"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdDecodeVideoKHR-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::VIDEO_DECODE)
+        {
+            return Err(VideoCodingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdDecodeVideoKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(VideoCodingError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdDecodeVideoKHR-videocoding
+        let video_coding_state = self
+            .video_coding_state
+            .as_ref()
+            .ok_or(VideoCodingError::NotActive)?;
+        let video_session = &video_coding_state.video_session;
+        let video_capabilities = video_session.video_capabilities();
+        let video_codec_operation = video_session.video_profile().video_codec_operation();
+
+        let &VideoDecodeInfo {
+            ref src_buffer,
+            src_buffer_offset,
+            src_buffer_range,
+            ref dst_picture_resource,
+            ref setup_reference_slot,
+            ref reference_slots,
+            ref codec_picture_info,
+            _ne: _,
+        } = decode_info;
+
+        // VUID-vkCmdDecodeVideoKHR-pNext-07152
+        // VUID-vkCmdDecodeVideoKHR-pNext-07157
+        if codec_picture_info.video_codec_operation() != video_codec_operation {
+            return Err(VideoCodingError::CodecMismatch);
+        }
+
+        /* Source buffer */
+
+        // VUID-VkVideoDecodeInfoKHR-srcBuffer-parameter
+        assert_eq!(device, src_buffer.device());
+
+        // VUID-VkVideoDecodeInfoKHR-srcBuffer-07165
+        if !src_buffer.usage().intersects(BufferUsage::VIDEO_DECODE_SRC) {
+            return Err(VideoCodingError::BufferMissingUsage {
+                required_usage: BufferUsage::VIDEO_DECODE_SRC,
+            });
+        }
+
+        // VUID-VkVideoDecodeInfoKHR-srcBufferOffset-07166
+        // VUID-VkVideoDecodeInfoKHR-srcBufferRange-07167
+        if src_buffer_offset
+            .checked_add(src_buffer_range)
+            .map_or(true, |end| end > src_buffer.size())
+        {
+            return Err(VideoCodingError::BitstreamRangeOutOfBounds {
+                offset: src_buffer_offset,
+                range: src_buffer_range,
+                buffer_size: src_buffer.size(),
+            });
+        }
+
+        // VUID-vkCmdDecodeVideoKHR-srcBufferOffset-07131
+        if src_buffer_offset % video_capabilities.min_bitstream_buffer_offset_alignment != 0 {
+            return Err(VideoCodingError::BitstreamOffsetNotAligned {
+                offset: src_buffer_offset,
+                required_alignment: video_capabilities.min_bitstream_buffer_offset_alignment,
+            });
+        }
+
+        // VUID-vkCmdDecodeVideoKHR-srcBufferRange-07133
+        if src_buffer_range % video_capabilities.min_bitstream_buffer_size_alignment != 0 {
+            return Err(VideoCodingError::BitstreamRangeNotAligned {
+                range: src_buffer_range,
+                required_alignment: video_capabilities.min_bitstream_buffer_size_alignment,
+            });
+        }
+
+        /* Destination picture resource */
+
+        // VUID-vkCmdDecodeVideoKHR-pDecodeInfo-07139
+        self.validate_picture_resource(
+            dst_picture_resource,
+            ImageUsage::VIDEO_DECODE_DST,
+            ResourceInCommand::VideoDecodeDestination,
+        )?;
+
+        /* Setup reference slot */
+
+        if let Some(setup_reference_slot) = setup_reference_slot {
+            let picture_resource = self.validate_decode_reference_slot(
+                setup_reference_slot,
+                video_coding_state,
+                video_codec_operation,
+                ResourceInCommand::VideoSetupReferencePicture,
+            )?;
+
+            let coincide = is_same_picture(picture_resource, dst_picture_resource);
+
+            if coincide {
+                // VUID-vkCmdDecodeVideoKHR-None-07140
+                if !video_capabilities
+                    .decode_capability_flags
+                    .intersects(VideoDecodeCapabilityFlags::DPB_AND_OUTPUT_COINCIDE)
+                {
+                    return Err(VideoCodingError::DpbAndOutputCoincideNotSupported);
+                }
+            } else {
+                // VUID-vkCmdDecodeVideoKHR-None-07141
+                if !video_capabilities
+                    .decode_capability_flags
+                    .intersects(VideoDecodeCapabilityFlags::DPB_AND_OUTPUT_DISTINCT)
+                {
+                    return Err(VideoCodingError::DpbAndOutputDistinctNotSupported);
+                }
+            }
+        }
+
+        /* Reference slots */
+
+        // VUID-vkCmdDecodeVideoKHR-activeReferencePictureCount-07150
+        if reference_slots.len() as u32 > video_session.max_active_reference_pictures() {
+            return Err(VideoCodingError::MaxActiveReferencePicturesExceeded {
+                provided: reference_slots.len() as u32,
+                max_supported: video_session.max_active_reference_pictures(),
+            });
+        }
+
+        let mut slot_indices: SmallVec<[u32; 8]> = SmallVec::new();
+
+        for (index, reference_slot) in reference_slots.iter().enumerate() {
+            self.validate_decode_reference_slot(
+                reference_slot,
+                video_coding_state,
+                video_codec_operation,
+                ResourceInCommand::VideoReferencePicture {
+                    index: index as u32,
+                },
+            )?;
+
+            // Checked to be `Some` above.
+            let slot_index = reference_slot.slot_index.unwrap();
+
+            // VUID-vkCmdDecodeVideoKHR-slotIndex-07256
+            if slot_indices.contains(&slot_index) {
+                return Err(VideoCodingError::SlotIndexNotUnique { slot_index });
+            }
+
+            slot_indices.push(slot_index);
+        }
+
+        Ok(())
+    }
+
+    /// Encodes a picture, in the current video coding scope.
+    ///
+    /// The picture is read from `encode_info.src_picture_resource`, and the bitstream of the
+    /// encoded picture is written to `encode_info.dst_buffer`. If
+    /// `encode_info.setup_reference_slot` is `Some`, the reconstructed picture is also written to
+    /// that DPB slot, so that it can be used as a reference by later pictures.
+    ///
+    /// # Safety
+    ///
+    /// - The codec Std structures in `encode_info.codec_picture_info` must be valid, and any
+    ///   pointers that they contain must remain valid until the command buffer is built.
+    pub unsafe fn encode_video(
+        &mut self,
+        encode_info: VideoEncodeInfo,
+    ) -> Result<&mut Self, VideoCodingError> {
+        self.validate_encode_video(&encode_info)?;
+
+        self.inner.encode_video(encode_info)?;
+
+        Ok(self)
+    }
+
+    fn validate_encode_video(&self, encode_info: &VideoEncodeInfo) -> Result<(), VideoCodingError> {
+        let device = self.device();
+
+        if !device.enabled_extensions().khr_video_encode_queue {
+            return Err(VideoCodingError::RequirementNotMet {
+                required_for: "`AutoCommandBufferBuilder::encode_video`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_video_encode_queue"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdEncodeVideoKHR-commandBuffer-cmdpool
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::VIDEO_ENCODE)
+        {
+            return Err(VideoCodingError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdEncodeVideoKHR-renderpass
+        if self.render_pass_state.is_some() {
+            return Err(VideoCodingError::ForbiddenInsideRenderPass);
+        }
+
+        // VUID-vkCmdEncodeVideoKHR-videocoding
+        let video_coding_state = self
+            .video_coding_state
+            .as_ref()
+            .ok_or(VideoCodingError::NotActive)?;
+        let video_session = &video_coding_state.video_session;
+        let video_capabilities = video_session.video_capabilities();
+        let video_codec_operation = video_session.video_profile().video_codec_operation();
+
+        let &VideoEncodeInfo {
+            ref dst_buffer,
+            dst_buffer_offset,
+            dst_buffer_max_range,
+            ref src_picture_resource,
+            ref setup_reference_slot,
+            ref reference_slots,
+            quality_level,
+            preceding_externally_encoded_bytes,
+            ref codec_picture_info,
+            _ne: _,
+        } = encode_info;
+
+        // VUID-vkCmdEncodeVideoKHR-pNext-07213
+        // VUID-vkCmdEncodeVideoKHR-pNext-07214
+        if codec_picture_info.video_codec_operation() != video_codec_operation {
+            return Err(VideoCodingError::CodecMismatch);
+        }
+
+        /* Destination buffer */
+
+        // VUID-VkVideoEncodeInfoKHR-dstBitstreamBuffer-parameter
+        assert_eq!(device, dst_buffer.device());
+
+        // VUID-VkVideoEncodeInfoKHR-dstBitstreamBuffer-07197
+        if !dst_buffer.usage().intersects(BufferUsage::VIDEO_ENCODE_DST) {
+            return Err(VideoCodingError::BufferMissingUsage {
+                required_usage: BufferUsage::VIDEO_ENCODE_DST,
+            });
+        }
+
+        // VUID-VkVideoEncodeInfoKHR-dstBitstreamBufferOffset-07198
+        // VUID-VkVideoEncodeInfoKHR-dstBitstreamBufferMaxRange-07199
+        if dst_buffer_offset
+            .checked_add(dst_buffer_max_range)
+            .map_or(true, |end| end > dst_buffer.size())
+        {
+            return Err(VideoCodingError::BitstreamRangeOutOfBounds {
+                offset: dst_buffer_offset,
+                range: dst_buffer_max_range,
+                buffer_size: dst_buffer.size(),
+            });
+        }
+
+        // VUID-vkCmdEncodeVideoKHR-dstBitstreamBufferOffset-07215
+        if dst_buffer_offset % video_capabilities.min_bitstream_buffer_offset_alignment != 0 {
+            return Err(VideoCodingError::BitstreamOffsetNotAligned {
+                offset: dst_buffer_offset,
+                required_alignment: video_capabilities.min_bitstream_buffer_offset_alignment,
+            });
+        }
+
+        // VUID-vkCmdEncodeVideoKHR-dstBitstreamBufferMaxRange-07216
+        if dst_buffer_max_range % video_capabilities.min_bitstream_buffer_size_alignment != 0 {
+            return Err(VideoCodingError::BitstreamRangeNotAligned {
+                range: dst_buffer_max_range,
+                required_alignment: video_capabilities.min_bitstream_buffer_size_alignment,
+            });
+        }
+
+        /* Source picture resource */
+
+        // VUID-vkCmdEncodeVideoKHR-pEncodeInfo-07207
+        self.validate_picture_resource(
+            src_picture_resource,
+            ImageUsage::VIDEO_ENCODE_SRC,
+            ResourceInCommand::VideoEncodeSource,
+        )?;
+
+        // VUID?
+        if quality_level >= video_capabilities.encode_quality_level_count {
+            return Err(VideoCodingError::QualityLevelOutOfRange {
+                quality_level,
+                quality_level_count: video_capabilities.encode_quality_level_count,
+            });
+        }
+
+        // VUID-VkVideoEncodeInfoKHR-precedingExternallyEncodedBytes-07200
+        if preceding_externally_encoded_bytes != 0
+            && !video_capabilities
+                .encode_capability_flags
+                .intersects(VideoEncodeCapabilityFlags::PRECEDING_EXTERNALLY_ENCODED_BYTES)
+        {
+            return Err(VideoCodingError::PrecedingExternallyEncodedBytesNotSupported);
+        }
+
+        /* Setup reference slot */
+
+        if let Some(setup_reference_slot) = setup_reference_slot {
+            self.validate_bound_reference_slot(
+                setup_reference_slot,
+                video_coding_state,
+                ImageUsage::VIDEO_ENCODE_DPB,
+                ResourceInCommand::VideoSetupReferencePicture,
+            )?;
+        }
+
+        /* Reference slots */
+
+        // VUID-vkCmdEncodeVideoKHR-activeReferencePictureCount-07211
+        if reference_slots.len() as u32 > video_session.max_active_reference_pictures() {
+            return Err(VideoCodingError::MaxActiveReferencePicturesExceeded {
+                provided: reference_slots.len() as u32,
+                max_supported: video_session.max_active_reference_pictures(),
+            });
+        }
+
+        let mut slot_indices: SmallVec<[u32; 8]> = SmallVec::new();
+
+        for (index, reference_slot) in reference_slots.iter().enumerate() {
+            let (slot_index, _) = self.validate_bound_reference_slot(
+                reference_slot,
+                video_coding_state,
+                ImageUsage::VIDEO_ENCODE_DPB,
+                ResourceInCommand::VideoReferencePicture {
+                    index: index as u32,
+                },
+            )?;
+
+            // VUID-vkCmdEncodeVideoKHR-slotIndex-07217
+            if slot_indices.contains(&slot_index) {
+                return Err(VideoCodingError::SlotIndexNotUnique { slot_index });
+            }
+
+            slot_indices.push(slot_index);
+        }
+
+        /* Codec-specific picture information */
+
+        // The reference lists must only refer to the DPB slots of the reference pictures.
+        let check_slot_index = |slot_index: u32| {
+            if slot_indices.contains(&slot_index) {
+                Ok(())
+            } else {
+                Err(VideoCodingError::ReferenceListSlotNotActive { slot_index })
+            }
+        };
+
+        match codec_picture_info {
+            VideoEncodeCodecPictureInfo::H264 {
+                std_picture_info: _,
+                reference_final_lists,
+                nalu_slices,
+                emit_picture_parameters: _,
+            } => {
+                // VUID-VkVideoEncodeH264VclFrameInfoEXT-naluSliceEntryCount-arraylength
+                assert!(!nalu_slices.is_empty());
+
+                for reference_lists in reference_final_lists
+                    .iter()
+                    .chain(nalu_slices.iter().flat_map(|s| &s.reference_final_lists))
+                {
+                    for entry in (reference_lists.reference_list0.iter())
+                        .chain(&reference_lists.reference_list1)
+                    {
+                        check_slot_index(entry.slot_index)?;
+                    }
+                }
+            }
+            VideoEncodeCodecPictureInfo::H265 {
+                std_picture_info: _,
+                reference_final_lists,
+                nalu_slice_segments,
+                emit_picture_parameters: _,
+            } => {
+                // VUID-VkVideoEncodeH265VclFrameInfoEXT-naluSliceSegmentEntryCount-arraylength
+                assert!(!nalu_slice_segments.is_empty());
+
+                for reference_lists in reference_final_lists.iter().chain(
+                    nalu_slice_segments
+                        .iter()
+                        .flat_map(|s| &s.reference_final_lists),
+                ) {
+                    for entry in (reference_lists.reference_list0.iter())
+                        .chain(&reference_lists.reference_list1)
+                    {
+                        check_slot_index(entry.slot_index)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_picture_resource(
+        &self,
+        picture_resource: &VideoPictureResourceInfo,
+        required_usage: ImageUsage,
+        resource_in_command: ResourceInCommand,
+    ) -> Result<(), VideoCodingError> {
+        let &VideoPictureResourceInfo {
+            ref image_view,
+            base_array_layer,
+            coded_offset: _,
+            coded_extent,
+            _ne: _,
+        } = picture_resource;
+
+        // VUID-VkVideoPictureResourceInfoKHR-imageViewBinding-parameter
+        assert_eq!(self.device(), image_view.device());
+
+        // VUID-vkCmdBeginVideoCodingKHR-slotIndex-07239
+        // VUID-vkCmdDecodeVideoKHR-pDecodeInfo-07146
+        if !image_view.usage().intersects(required_usage) {
+            return Err(VideoCodingError::ImageMissingUsage {
+                resource_in_command,
+                required_usage,
+            });
+        }
+
+        // VUID-VkVideoPictureResourceInfoKHR-baseArrayLayer-07175
+        if base_array_layer
+            >= image_view.subresource_range().array_layers.end
+                - image_view.subresource_range().array_layers.start
+        {
+            return Err(VideoCodingError::PictureResourceArrayLayerOutOfRange {
+                resource_in_command,
+            });
+        }
+
+        // VUID-VkVideoPictureResourceInfoKHR-codedExtent-07174
+        let [width, height, _] = image_view.dimensions().width_height_depth();
+        if coded_extent[0] == 0
+            || coded_extent[1] == 0
+            || coded_extent[0] > width
+            || coded_extent[1] > height
+        {
+            return Err(VideoCodingError::PictureResourceExtentOutOfRange {
+                resource_in_command,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn validate_decode_reference_slot<'a>(
+        &self,
+        reference_slot: &'a VideoReferenceSlotInfo,
+        video_coding_state: &VideoCodingState,
+        video_codec_operation: VideoCodecOperation,
+        resource_in_command: ResourceInCommand,
+    ) -> Result<&'a VideoPictureResourceInfo, VideoCodingError> {
+        // VUID-VkVideoDecodeInfoKHR-pSetupReferenceSlot-07168
+        // VUID-VkVideoDecodeInfoKHR-slotIndex-07171
+        let codec_reference_info = reference_slot.codec_reference_info.as_ref().ok_or(
+            VideoCodingError::ReferenceSlotIncomplete {
+                resource_in_command,
+            },
+        )?;
+
+        // VUID-vkCmdDecodeVideoKHR-pNext-07154
+        // VUID-vkCmdDecodeVideoKHR-pNext-07158
+        if codec_reference_info.video_codec_operation() != video_codec_operation {
+            return Err(VideoCodingError::CodecMismatch);
+        }
+
+        let (_, picture_resource) = self.validate_bound_reference_slot(
+            reference_slot,
+            video_coding_state,
+            ImageUsage::VIDEO_DECODE_DPB,
+            resource_in_command,
+        )?;
+
+        Ok(picture_resource)
+    }
+
+    fn validate_bound_reference_slot<'a>(
+        &self,
+        reference_slot: &'a VideoReferenceSlotInfo,
+        video_coding_state: &VideoCodingState,
+        dpb_usage: ImageUsage,
+        resource_in_command: ResourceInCommand,
+    ) -> Result<(u32, &'a VideoPictureResourceInfo), VideoCodingError> {
+        let &VideoReferenceSlotInfo {
+            slot_index,
+            ref picture_resource,
+            codec_reference_info: _,
+            _ne: _,
+        } = reference_slot;
+
+        // VUID-VkVideoDecodeInfoKHR-pSetupReferenceSlot-07168
+        // VUID-VkVideoEncodeInfoKHR-pSetupReferenceSlot-07165
+        let (slot_index, picture_resource) = match (slot_index, picture_resource) {
+            (Some(slot_index), Some(picture_resource)) => (slot_index, picture_resource),
+            _ => {
+                return Err(VideoCodingError::ReferenceSlotIncomplete {
+                    resource_in_command,
+                })
+            }
+        };
+
+        // VUID-vkCmdDecodeVideoKHR-pDecodeInfo-07170
+        // VUID-vkCmdEncodeVideoKHR-pEncodeInfo-07212
+        if slot_index >= video_coding_state.video_session.max_dpb_slots() {
+            return Err(VideoCodingError::SlotIndexOutOfRange {
+                slot_index,
+                max_dpb_slots: video_coding_state.video_session.max_dpb_slots(),
+            });
+        }
+
+        self.validate_picture_resource(picture_resource, dpb_usage, resource_in_command)?;
+
+        // VUID-vkCmdDecodeVideoKHR-pPictureResource-07149
+        // VUID-vkCmdDecodeVideoKHR-pDecodeInfo-07151
+        // VUID-vkCmdEncodeVideoKHR-pPictureResource-07209
+        if !video_coding_state.bound_picture_resources.contains(&(
+            picture_resource.image_view.handle(),
+            picture_resource.base_array_layer,
+        )) {
+            return Err(VideoCodingError::PictureResourceNotBound {
+                resource_in_command,
+            });
+        }
+
+        Ok((slot_index, picture_resource))
+    }
+}
+
+fn dpb_usage(video_codec_operation: VideoCodecOperation) -> ImageUsage {
+    if video_codec_operation.is_encode() {
+        ImageUsage::VIDEO_ENCODE_DPB
+    } else {
+        ImageUsage::VIDEO_DECODE_DPB
+    }
+}
+
+fn is_same_picture(a: &VideoPictureResourceInfo, b: &VideoPictureResourceInfo) -> bool {
+    a.image_view.handle() == b.image_view.handle() && a.base_array_layer == b.base_array_layer
+}
+
+fn picture_subresource_range(picture_resource: &VideoPictureResourceInfo) -> ImageSubresourceRange {
+    let mut subresource_range = picture_resource.image_view.subresource_range().clone();
+    let array_layer = subresource_range.array_layers.start + picture_resource.base_array_layer;
+    subresource_range.array_layers = array_layer..array_layer + 1;
+
+    subresource_range
+}
+
+impl SyncCommandBufferBuilder {
+    /// Calls `vkCmdBeginVideoCodingKHR` on the builder.
+    #[inline]
+    pub unsafe fn begin_video_coding(&mut self, begin_info: VideoBeginCodingInfo) {
+        struct Cmd {
+            begin_info: VideoBeginCodingInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "begin_video_coding"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.begin_video_coding(&self.begin_info);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { begin_info }));
+    }
+
+    /// Calls `vkCmdEndVideoCodingKHR` on the builder.
+    #[inline]
+    pub unsafe fn end_video_coding(&mut self) {
+        struct Cmd;
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "end_video_coding"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.end_video_coding();
+            }
+        }
+
+        self.commands.push(Box::new(Cmd));
+    }
+
+    /// Calls `vkCmdControlVideoCodingKHR` on the builder.
+    #[inline]
+    pub unsafe fn control_video_coding(&mut self, control_info: VideoCodingControlInfo) {
+        struct Cmd {
+            control_info: VideoCodingControlInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "control_video_coding"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.control_video_coding(&self.control_info);
+            }
+        }
+
+        self.commands.push(Box::new(Cmd { control_info }));
+    }
+
+    /// Calls `vkCmdDecodeVideoKHR` on the builder.
+    pub unsafe fn decode_video(
+        &mut self,
+        decode_info: VideoDecodeInfo,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            decode_info: VideoDecodeInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "decode_video"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.decode_video(&self.decode_info);
+            }
+        }
+
+        let VideoDecodeInfo {
+            src_buffer,
+            src_buffer_offset,
+            src_buffer_range,
+            dst_picture_resource,
+            setup_reference_slot,
+            reference_slots,
+            codec_picture_info: _,
+            _ne: _,
+        } = &decode_info;
+
+        let setup_picture_resource = setup_reference_slot
+            .as_ref()
+            .and_then(|slot| slot.picture_resource.as_ref());
+
+        // If the output picture coincides with the setup reference picture, it is written in the
+        // DPB layout.
+        let dst_layout = if setup_picture_resource.map_or(false, |setup_picture_resource| {
+            is_same_picture(setup_picture_resource, dst_picture_resource)
+        }) {
+            ImageLayout::VideoDecodeDpb
+        } else {
+            ImageLayout::VideoDecodeDst
+        };
+
+        let command_index = self.commands.len();
+        let command_name = "decode_video";
+        let resources: SmallVec<[_; 8]> = [
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::VideoDecodeSource,
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: src_buffer.clone(),
+                    range: *src_buffer_offset..*src_buffer_offset + *src_buffer_range,
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::VIDEO_DECODE,
+                        access: AccessFlags::VIDEO_DECODE_READ,
+                        exclusive: false,
+                    },
+                },
+            ),
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::VideoDecodeDestination,
+                    secondary_use_ref: None,
+                },
+                Resource::Image {
+                    image: dst_picture_resource.image_view.image(),
+                    subresource_range: picture_subresource_range(dst_picture_resource),
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::VIDEO_DECODE,
+                        access: AccessFlags::VIDEO_DECODE_WRITE,
+                        exclusive: true,
+                    },
+                    start_layout: dst_layout,
+                    end_layout: dst_layout,
+                },
+            ),
+        ]
+        .into_iter()
+        .chain(
+            setup_picture_resource
+                .filter(|setup_picture_resource| {
+                    dst_layout != ImageLayout::VideoDecodeDpb
+                        || !is_same_picture(setup_picture_resource, dst_picture_resource)
+                })
+                .map(|setup_picture_resource| {
+                    (
+                        ResourceUseRef {
+                            command_index,
+                            command_name,
+                            resource_in_command: ResourceInCommand::VideoSetupReferencePicture,
+                            secondary_use_ref: None,
+                        },
+                        Resource::Image {
+                            image: setup_picture_resource.image_view.image(),
+                            subresource_range: picture_subresource_range(setup_picture_resource),
+                            memory: PipelineMemoryAccess {
+                                stages: PipelineStages::VIDEO_DECODE,
+                                access: AccessFlags::VIDEO_DECODE_WRITE,
+                                exclusive: true,
+                            },
+                            start_layout: ImageLayout::VideoDecodeDpb,
+                            end_layout: ImageLayout::VideoDecodeDpb,
+                        },
+                    )
+                }),
+        )
+        .chain(
+            reference_slots
+                .iter()
+                .enumerate()
+                .filter_map(|(index, slot)| Some((index, slot.picture_resource.as_ref()?)))
+                .map(|(index, picture_resource)| {
+                    (
+                        ResourceUseRef {
+                            command_index,
+                            command_name,
+                            resource_in_command: ResourceInCommand::VideoReferencePicture {
+                                index: index as u32,
+                            },
+                            secondary_use_ref: None,
+                        },
+                        Resource::Image {
+                            image: picture_resource.image_view.image(),
+                            subresource_range: picture_subresource_range(picture_resource),
+                            memory: PipelineMemoryAccess {
+                                stages: PipelineStages::VIDEO_DECODE,
+                                access: AccessFlags::VIDEO_DECODE_READ,
+                                exclusive: false,
+                            },
+                            start_layout: ImageLayout::VideoDecodeDpb,
+                            end_layout: ImageLayout::VideoDecodeDpb,
+                        },
+                    )
+                }),
+        )
+        .collect();
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd { decode_info }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+
+    /// Calls `vkCmdEncodeVideoKHR` on the builder.
+    pub unsafe fn encode_video(
+        &mut self,
+        encode_info: VideoEncodeInfo,
+    ) -> Result<(), SyncCommandBufferBuilderError> {
+        struct Cmd {
+            encode_info: VideoEncodeInfo,
+        }
+
+        impl Command for Cmd {
+            fn name(&self) -> &'static str {
+                "encode_video"
+            }
+
+            unsafe fn send(&self, out: &mut UnsafeCommandBufferBuilder) {
+                out.encode_video(&self.encode_info);
+            }
+        }
+
+        let VideoEncodeInfo {
+            dst_buffer,
+            dst_buffer_offset,
+            dst_buffer_max_range,
+            src_picture_resource,
+            setup_reference_slot,
+            reference_slots,
+            quality_level: _,
+            preceding_externally_encoded_bytes: _,
+            codec_picture_info: _,
+            _ne: _,
+        } = &encode_info;
+
+        let command_index = self.commands.len();
+        let command_name = "encode_video";
+        let resources: SmallVec<[_; 8]> = [
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::VideoEncodeDestination,
+                    secondary_use_ref: None,
+                },
+                Resource::Buffer {
+                    buffer: dst_buffer.clone(),
+                    range: *dst_buffer_offset..*dst_buffer_offset + *dst_buffer_max_range,
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::VIDEO_ENCODE,
+                        access: AccessFlags::VIDEO_ENCODE_WRITE,
+                        exclusive: true,
+                    },
+                },
+            ),
+            (
+                ResourceUseRef {
+                    command_index,
+                    command_name,
+                    resource_in_command: ResourceInCommand::VideoEncodeSource,
+                    secondary_use_ref: None,
+                },
+                Resource::Image {
+                    image: src_picture_resource.image_view.image(),
+                    subresource_range: picture_subresource_range(src_picture_resource),
+                    memory: PipelineMemoryAccess {
+                        stages: PipelineStages::VIDEO_ENCODE,
+                        access: AccessFlags::VIDEO_ENCODE_READ,
+                        exclusive: false,
+                    },
+                    start_layout: ImageLayout::VideoEncodeSrc,
+                    end_layout: ImageLayout::VideoEncodeSrc,
+                },
+            ),
+        ]
+        .into_iter()
+        .chain(
+            setup_reference_slot
+                .as_ref()
+                .and_then(|slot| slot.picture_resource.as_ref())
+                .map(|setup_picture_resource| {
+                    (
+                        ResourceUseRef {
+                            command_index,
+                            command_name,
+                            resource_in_command: ResourceInCommand::VideoSetupReferencePicture,
+                            secondary_use_ref: None,
+                        },
+                        Resource::Image {
+                            image: setup_picture_resource.image_view.image(),
+                            subresource_range: picture_subresource_range(setup_picture_resource),
+                            memory: PipelineMemoryAccess {
+                                stages: PipelineStages::VIDEO_ENCODE,
+                                access: AccessFlags::VIDEO_ENCODE_WRITE,
+                                exclusive: true,
+                            },
+                            start_layout: ImageLayout::VideoEncodeDpb,
+                            end_layout: ImageLayout::VideoEncodeDpb,
+                        },
+                    )
+                }),
+        )
+        .chain(
+            reference_slots
+                .iter()
+                .enumerate()
+                .filter_map(|(index, slot)| Some((index, slot.picture_resource.as_ref()?)))
+                .map(|(index, picture_resource)| {
+                    (
+                        ResourceUseRef {
+                            command_index,
+                            command_name,
+                            resource_in_command: ResourceInCommand::VideoReferencePicture {
+                                index: index as u32,
+                            },
+                            secondary_use_ref: None,
+                        },
+                        Resource::Image {
+                            image: picture_resource.image_view.image(),
+                            subresource_range: picture_subresource_range(picture_resource),
+                            memory: PipelineMemoryAccess {
+                                stages: PipelineStages::VIDEO_ENCODE,
+                                access: AccessFlags::VIDEO_ENCODE_READ,
+                                exclusive: false,
+                            },
+                            start_layout: ImageLayout::VideoEncodeDpb,
+                            end_layout: ImageLayout::VideoEncodeDpb,
+                        },
+                    )
+                }),
+        )
+        .collect();
+
+        for resource in &resources {
+            self.check_resource_conflicts(resource)?;
+        }
+
+        self.commands.push(Box::new(Cmd { encode_info }));
+
+        for resource in resources {
+            self.add_resource(resource);
+        }
+
+        Ok(())
+    }
+}
+
+impl UnsafeCommandBufferBuilder {
+    /// Calls `vkCmdBeginVideoCodingKHR` on the builder.
+    #[inline]
+    pub unsafe fn begin_video_coding(&mut self, begin_info: &VideoBeginCodingInfo) {
+        let &VideoBeginCodingInfo {
+            ref video_session,
+            ref video_session_parameters,
+            ref reference_slots,
+            _ne: _,
+        } = begin_info;
+
+        let picture_resources_vk: SmallVec<[_; 8]> = reference_slots
+            .iter()
+            .map(|slot| {
+                slot.picture_resource
+                    .as_ref()
+                    .map(VideoPictureResourceInfo::to_vulkan)
+            })
+            .collect();
+        let reference_slots_vk: SmallVec<[_; 8]> = reference_slots
+            .iter()
+            .zip(&picture_resources_vk)
+            .map(
+                |(slot, picture_resource_vk)| ash::vk::VideoReferenceSlotInfoKHR {
+                    slot_index: slot.slot_index.map_or(-1, |slot_index| slot_index as i32),
+                    p_picture_resource: picture_resource_vk
+                        .as_ref()
+                        .map_or(ptr::null(), |picture_resource_vk| {
+                            picture_resource_vk as *const _
+                        }),
+                    ..Default::default()
+                },
+            )
+            .collect();
+
+        let begin_info_vk = ash::vk::VideoBeginCodingInfoKHR {
+            flags: ash::vk::VideoBeginCodingFlagsKHR::empty(),
+            video_session: video_session.handle(),
+            video_session_parameters: video_session_parameters
+                .as_ref()
+                .map_or_else(Default::default, VulkanObject::handle),
+            reference_slot_count: reference_slots_vk.len() as u32,
+            p_reference_slots: reference_slots_vk.as_ptr(),
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.khr_video_queue.cmd_begin_video_coding_khr)(self.handle, &begin_info_vk);
+    }
+
+    /// Calls `vkCmdEndVideoCodingKHR` on the builder.
+    #[inline]
+    pub unsafe fn end_video_coding(&mut self) {
+        let end_info_vk = ash::vk::VideoEndCodingInfoKHR::default();
+
+        let fns = self.device.fns();
+        (fns.khr_video_queue.cmd_end_video_coding_khr)(self.handle, &end_info_vk);
+    }
+
+    /// Calls `vkCmdControlVideoCodingKHR` on the builder.
+    #[inline]
+    pub unsafe fn control_video_coding(&mut self, control_info: &VideoCodingControlInfo) {
+        let &VideoCodingControlInfo {
+            flags,
+            ref encode_rate_control,
+            _ne: _,
+        } = control_info;
+
+        let mut control_info_vk = ash::vk::VideoCodingControlInfoKHR {
+            flags: flags.into(),
+            ..Default::default()
+        };
+        let encode_rate_control_vk = encode_rate_control
+            .as_ref()
+            .map(VideoEncodeRateControlInfo::to_vulkan);
+
+        if let Some(encode_rate_control_vk) = &encode_rate_control_vk {
+            control_info_vk.p_next =
+                &encode_rate_control_vk.rate_control_info as *const _ as *const _;
+        }
+
+        let fns = self.device.fns();
+        (fns.khr_video_queue.cmd_control_video_coding_khr)(self.handle, &control_info_vk);
+    }
+
+    /// Calls `vkCmdDecodeVideoKHR` on the builder.
+    pub unsafe fn decode_video(&mut self, decode_info: &VideoDecodeInfo) {
+        let &VideoDecodeInfo {
+            ref src_buffer,
+            src_buffer_offset,
+            src_buffer_range,
+            ref dst_picture_resource,
+            ref setup_reference_slot,
+            ref reference_slots,
+            ref codec_picture_info,
+            _ne: _,
+        } = decode_info;
+
+        // The setup reference slot, if any, is first.
+        let slots: SmallVec<[&VideoReferenceSlotInfo; 9]> =
+            setup_reference_slot.iter().chain(reference_slots).collect();
+
+        let picture_resources_vk: SmallVec<[_; 9]> = slots
+            .iter()
+            .map(|slot| {
+                slot.picture_resource
+                    .as_ref()
+                    .map(VideoPictureResourceInfo::to_vulkan)
+            })
+            .collect();
+        let h264_dpb_slot_infos_vk: SmallVec<[_; 9]> = slots
+            .iter()
+            .map(|slot| match &slot.codec_reference_info {
+                Some(VideoDecodeCodecReferenceInfo::H264(std_reference_info)) => {
+                    Some(ash::vk::VideoDecodeH264DpbSlotInfoEXT {
+                        p_std_reference_info: std_reference_info,
+                        ..Default::default()
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        let h265_dpb_slot_infos_vk: SmallVec<[_; 9]> = slots
+            .iter()
+            .map(|slot| match &slot.codec_reference_info {
+                Some(VideoDecodeCodecReferenceInfo::H265(std_reference_info)) => {
+                    Some(ash::vk::VideoDecodeH265DpbSlotInfoEXT {
+                        p_std_reference_info: std_reference_info,
+                        ..Default::default()
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        let slots_vk: SmallVec<[_; 9]> = slots
+            .iter()
+            .zip(&picture_resources_vk)
+            .zip(h264_dpb_slot_infos_vk.iter().zip(&h265_dpb_slot_infos_vk))
+            .map(|((slot, picture_resource_vk), dpb_slot_info_vk)| {
+                let mut slot_vk = ash::vk::VideoReferenceSlotInfoKHR {
+                    slot_index: slot.slot_index.map_or(-1, |slot_index| slot_index as i32),
+                    p_picture_resource: picture_resource_vk
+                        .as_ref()
+                        .map_or(ptr::null(), |picture_resource_vk| {
+                            picture_resource_vk as *const _
+                        }),
+                    ..Default::default()
+                };
+
+                match dpb_slot_info_vk {
+                    (Some(next), _) => slot_vk.p_next = next as *const _ as *const _,
+                    (_, Some(next)) => slot_vk.p_next = next as *const _ as *const _,
+                    (None, None) => (),
+                }
+
+                slot_vk
+            })
+            .collect();
+        let (setup_slot_vk, reference_slots_vk) =
+            slots_vk.split_at(setup_reference_slot.is_some() as usize);
+
+        let src_buffer_inner = src_buffer.inner();
+
+        let mut decode_info_vk = ash::vk::VideoDecodeInfoKHR {
+            flags: ash::vk::VideoDecodeFlagsKHR::empty(),
+            src_buffer: src_buffer_inner.buffer.handle(),
+            src_buffer_offset: src_buffer_inner.offset + src_buffer_offset,
+            src_buffer_range,
+            dst_picture_resource: dst_picture_resource.to_vulkan(),
+            p_setup_reference_slot: setup_slot_vk
+                .first()
+                .map_or(ptr::null(), |slot_vk| slot_vk as *const _),
+            reference_slot_count: reference_slots_vk.len() as u32,
+            p_reference_slots: reference_slots_vk.as_ptr(),
+            ..Default::default()
+        };
+        let mut h264_picture_info_vk = None;
+        let mut h265_picture_info_vk = None;
+
+        match codec_picture_info {
+            VideoDecodeCodecPictureInfo::H264 {
+                std_picture_info,
+                slice_offsets,
+            } => {
+                let next = h264_picture_info_vk.insert(ash::vk::VideoDecodeH264PictureInfoEXT {
+                    p_std_picture_info: std_picture_info,
+                    slice_count: slice_offsets.len() as u32,
+                    p_slice_offsets: slice_offsets.as_ptr(),
+                    ..Default::default()
+                });
+
+                next.p_next = decode_info_vk.p_next;
+                decode_info_vk.p_next = next as *const _ as *const _;
+            }
+            VideoDecodeCodecPictureInfo::H265 {
+                std_picture_info,
+                slice_offsets,
+            } => {
+                let next = h265_picture_info_vk.insert(ash::vk::VideoDecodeH265PictureInfoEXT {
+                    // The pointer is not const in the headers, but the structure is not written
+                    // to.
+                    p_std_picture_info: std_picture_info as *const _ as *mut _,
+                    slice_count: slice_offsets.len() as u32,
+                    p_slice_offsets: slice_offsets.as_ptr(),
+                    ..Default::default()
+                });
+
+                next.p_next = decode_info_vk.p_next;
+                decode_info_vk.p_next = next as *const _ as *const _;
+            }
+        }
+
+        let fns = self.device.fns();
+        (fns.khr_video_decode_queue.cmd_decode_video_khr)(self.handle, &decode_info_vk);
+    }
+
+    /// Calls `vkCmdEncodeVideoKHR` on the builder.
+    pub unsafe fn encode_video(&mut self, encode_info: &VideoEncodeInfo) {
+        let &VideoEncodeInfo {
+            ref dst_buffer,
+            dst_buffer_offset,
+            dst_buffer_max_range,
+            ref src_picture_resource,
+            ref setup_reference_slot,
+            ref reference_slots,
+            quality_level,
+            preceding_externally_encoded_bytes,
+            ref codec_picture_info,
+            _ne: _,
+        } = encode_info;
+
+        // The setup reference slot, if any, is first.
+        let slots: SmallVec<[&VideoReferenceSlotInfo; 9]> =
+            setup_reference_slot.iter().chain(reference_slots).collect();
+
+        let picture_resources_vk: SmallVec<[_; 9]> = slots
+            .iter()
+            .map(|slot| {
+                slot.picture_resource
+                    .as_ref()
+                    .map(VideoPictureResourceInfo::to_vulkan)
+            })
+            .collect();
+        let slots_vk: SmallVec<[_; 9]> = slots
+            .iter()
+            .zip(&picture_resources_vk)
+            .map(
+                |(slot, picture_resource_vk)| ash::vk::VideoReferenceSlotInfoKHR {
+                    slot_index: slot.slot_index.map_or(-1, |slot_index| slot_index as i32),
+                    p_picture_resource: picture_resource_vk
+                        .as_ref()
+                        .map_or(ptr::null(), |picture_resource_vk| {
+                            picture_resource_vk as *const _
+                        }),
+                    ..Default::default()
+                },
+            )
+            .collect();
+        let (setup_slot_vk, reference_slots_vk) =
+            slots_vk.split_at(setup_reference_slot.is_some() as usize);
+
+        let dst_buffer_inner = dst_buffer.inner();
+
+        let mut encode_info_vk = ash::vk::VideoEncodeInfoKHR {
+            flags: ash::vk::VideoEncodeFlagsKHR::empty(),
+            quality_level,
+            dst_bitstream_buffer: dst_buffer_inner.buffer.handle(),
+            dst_bitstream_buffer_offset: dst_buffer_inner.offset + dst_buffer_offset,
+            dst_bitstream_buffer_max_range: dst_buffer_max_range,
+            src_picture_resource: src_picture_resource.to_vulkan(),
+            p_setup_reference_slot: setup_slot_vk
+                .first()
+                .map_or(ptr::null(), |slot_vk| slot_vk as *const _),
+            reference_slot_count: reference_slots_vk.len() as u32,
+            p_reference_slots: reference_slots_vk.as_ptr(),
+            preceding_externally_encoded_bytes,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+
+        // The codec-specific structures borrow from each other, so the command is called from
+        // within each match arm.
+        match codec_picture_info {
+            VideoEncodeCodecPictureInfo::H264 {
+                std_picture_info,
+                reference_final_lists,
+                nalu_slices,
+                emit_picture_parameters,
+            } => {
+                // The reference lists of the picture, if any, are first.
+                let reference_lists: SmallVec<[&VideoEncodeH264ReferenceListsInfo; 4]> =
+                    reference_final_lists
+                        .iter()
+                        .chain(
+                            nalu_slices
+                                .iter()
+                                .filter_map(|slice| slice.reference_final_lists.as_ref()),
+                        )
+                        .collect();
+                let reference_list_entries_vk: SmallVec<[_; 4]> = reference_lists
+                    .iter()
+                    .map(|lists| {
+                        let entries_vk =
+                            |entries: &[VideoEncodeH264DpbSlotInfo]| -> SmallVec<[_; 8]> {
+                                entries
+                                    .iter()
+                                    .map(|entry| ash::vk::VideoEncodeH264DpbSlotInfoEXT {
+                                        slot_index: entry.slot_index as i8,
+                                        p_std_reference_info: &entry.std_reference_info,
+                                        ..Default::default()
+                                    })
+                                    .collect()
+                            };
+
+                        (
+                            entries_vk(&lists.reference_list0),
+                            entries_vk(&lists.reference_list1),
+                        )
+                    })
+                    .collect();
+                let reference_lists_vk: SmallVec<[_; 4]> = reference_lists
+                    .iter()
+                    .zip(&reference_list_entries_vk)
+                    .map(|(lists, (list0_vk, list1_vk))| {
+                        ash::vk::VideoEncodeH264ReferenceListsInfoEXT {
+                            reference_list0_entry_count: list0_vk.len() as u8,
+                            p_reference_list0_entries: list0_vk.as_ptr(),
+                            reference_list1_entry_count: list1_vk.len() as u8,
+                            p_reference_list1_entries: list1_vk.as_ptr(),
+                            p_mem_mgmt_ctrl_operations: lists
+                                .std_mem_mgmt_ctrl_operations
+                                .as_ref()
+                                .map_or(ptr::null(), |operations| operations as *const _),
+                            ..Default::default()
+                        }
+                    })
+                    .collect();
+                let (picture_lists_vk, slice_lists_vk) =
+                    reference_lists_vk.split_at(reference_final_lists.is_some() as usize);
+                let mut slice_lists_vk = slice_lists_vk.iter();
+
+                let nalu_slices_vk: SmallVec<[_; 4]> = nalu_slices
+                    .iter()
+                    .map(|slice| ash::vk::VideoEncodeH264NaluSliceInfoEXT {
+                        mb_count: slice.mb_count,
+                        p_reference_final_lists: if slice.reference_final_lists.is_some() {
+                            slice_lists_vk.next().unwrap()
+                        } else {
+                            ptr::null()
+                        },
+                        p_slice_header_std: &slice.std_slice_header,
+                        ..Default::default()
+                    })
+                    .collect();
+
+                let mut vcl_frame_info_vk = ash::vk::VideoEncodeH264VclFrameInfoEXT {
+                    p_reference_final_lists: picture_lists_vk
+                        .first()
+                        .map_or(ptr::null(), |lists_vk| lists_vk as *const _),
+                    nalu_slice_entry_count: nalu_slices_vk.len() as u32,
+                    p_nalu_slice_entries: nalu_slices_vk.as_ptr(),
+                    p_current_picture_info: std_picture_info,
+                    ..Default::default()
+                };
+                let emit_picture_parameters_vk =
+                    emit_picture_parameters
+                        .as_ref()
+                        .map(|emit_picture_parameters| {
+                            ash::vk::VideoEncodeH264EmitPictureParametersInfoEXT {
+                                sps_id: emit_picture_parameters.sps_id,
+                                emit_sps_enable: emit_picture_parameters.emit_sps
+                                    as ash::vk::Bool32,
+                                pps_id_entry_count: emit_picture_parameters.pps_ids.len() as u32,
+                                pps_id_entries: emit_picture_parameters.pps_ids.as_ptr(),
+                                ..Default::default()
+                            }
+                        });
+
+                if let Some(next) = &emit_picture_parameters_vk {
+                    vcl_frame_info_vk.p_next = next as *const _ as *const _;
+                }
+
+                encode_info_vk.p_next = &vcl_frame_info_vk as *const _ as *const _;
+
+                (fns.khr_video_encode_queue.cmd_encode_video_khr)(self.handle, &encode_info_vk);
+            }
+            VideoEncodeCodecPictureInfo::H265 {
+                std_picture_info,
+                reference_final_lists,
+                nalu_slice_segments,
+                emit_picture_parameters,
+            } => {
+                // The reference lists of the picture, if any, are first.
+                let reference_lists: SmallVec<[&VideoEncodeH265ReferenceListsInfo; 4]> =
+                    reference_final_lists
+                        .iter()
+                        .chain(
+                            nalu_slice_segments
+                                .iter()
+                                .filter_map(|segment| segment.reference_final_lists.as_ref()),
+                        )
+                        .collect();
+                let reference_list_entries_vk: SmallVec<[_; 4]> = reference_lists
+                    .iter()
+                    .map(|lists| {
+                        let entries_vk =
+                            |entries: &[VideoEncodeH265DpbSlotInfo]| -> SmallVec<[_; 8]> {
+                                entries
+                                    .iter()
+                                    .map(|entry| ash::vk::VideoEncodeH265DpbSlotInfoEXT {
+                                        slot_index: entry.slot_index as i8,
+                                        p_std_reference_info: &entry.std_reference_info,
+                                        ..Default::default()
+                                    })
+                                    .collect()
+                            };
+
+                        (
+                            entries_vk(&lists.reference_list0),
+                            entries_vk(&lists.reference_list1),
+                        )
+                    })
+                    .collect();
+                let reference_lists_vk: SmallVec<[_; 4]> = reference_lists
+                    .iter()
+                    .zip(&reference_list_entries_vk)
+                    .map(|(lists, (list0_vk, list1_vk))| {
+                        ash::vk::VideoEncodeH265ReferenceListsInfoEXT {
+                            reference_list0_entry_count: list0_vk.len() as u8,
+                            p_reference_list0_entries: list0_vk.as_ptr(),
+                            reference_list1_entry_count: list1_vk.len() as u8,
+                            p_reference_list1_entries: list1_vk.as_ptr(),
+                            p_reference_modifications: lists
+                                .std_reference_modifications
+                                .as_ref()
+                                .map_or(ptr::null(), |modifications| modifications as *const _),
+                            ..Default::default()
+                        }
+                    })
+                    .collect();
+                let (picture_lists_vk, segment_lists_vk) =
+                    reference_lists_vk.split_at(reference_final_lists.is_some() as usize);
+                let mut segment_lists_vk = segment_lists_vk.iter();
+
+                let nalu_slice_segments_vk: SmallVec<[_; 4]> = nalu_slice_segments
+                    .iter()
+                    .map(|segment| ash::vk::VideoEncodeH265NaluSliceSegmentInfoEXT {
+                        ctb_count: segment.ctb_count,
+                        p_reference_final_lists: if segment.reference_final_lists.is_some() {
+                            segment_lists_vk.next().unwrap()
+                        } else {
+                            ptr::null()
+                        },
+                        p_slice_segment_header_std: &segment.std_slice_segment_header,
+                        ..Default::default()
+                    })
+                    .collect();
+
+                let mut vcl_frame_info_vk = ash::vk::VideoEncodeH265VclFrameInfoEXT {
+                    p_reference_final_lists: picture_lists_vk
+                        .first()
+                        .map_or(ptr::null(), |lists_vk| lists_vk as *const _),
+                    nalu_slice_segment_entry_count: nalu_slice_segments_vk.len() as u32,
+                    p_nalu_slice_segment_entries: nalu_slice_segments_vk.as_ptr(),
+                    p_current_picture_info: std_picture_info,
+                    ..Default::default()
+                };
+                let emit_picture_parameters_vk =
+                    emit_picture_parameters
+                        .as_ref()
+                        .map(|emit_picture_parameters| {
+                            ash::vk::VideoEncodeH265EmitPictureParametersInfoEXT {
+                                vps_id: emit_picture_parameters.vps_id,
+                                sps_id: emit_picture_parameters.sps_id,
+                                emit_vps_enable: emit_picture_parameters.emit_vps
+                                    as ash::vk::Bool32,
+                                emit_sps_enable: emit_picture_parameters.emit_sps
+                                    as ash::vk::Bool32,
+                                pps_id_entry_count: emit_picture_parameters.pps_ids.len() as u32,
+                                pps_id_entries: emit_picture_parameters.pps_ids.as_ptr(),
+                                ..Default::default()
+                            }
+                        });
+
+                if let Some(next) = &emit_picture_parameters_vk {
+                    vcl_frame_info_vk.p_next = next as *const _ as *const _;
+                }
+
+                encode_info_vk.p_next = &vcl_frame_info_vk as *const _ as *const _;
+
+                (fns.khr_video_encode_queue.cmd_encode_video_khr)(self.handle, &encode_info_vk);
+            }
+        }
+    }
+}
+
+/// Error that can happen when recording a video coding command.
+#[derive(Clone, Debug)]
+pub enum VideoCodingError {
+    SyncCommandBufferBuilderError(SyncCommandBufferBuilderError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// A video coding scope is already active.
+    AlreadyActive,
+
+    /// The offset of the bitstream is not a multiple of the required alignment.
+    BitstreamOffsetNotAligned {
+        offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// The size of the bitstream is not a multiple of the required alignment.
+    BitstreamRangeNotAligned {
+        range: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// The bitstream range is not within its buffer.
+    BitstreamRangeOutOfBounds {
+        offset: DeviceSize,
+        range: DeviceSize,
+        buffer_size: DeviceSize,
+    },
+
+    /// The bitstream buffer is missing a required usage.
+    BufferMissingUsage {
+        required_usage: BufferUsage,
+    },
+
+    /// The rate control mode is constant bitrate, but the maximum bitrate of a layer is not
+    /// equal to its average bitrate.
+    CbrBitrateMismatch,
+
+    /// The codec of the provided codec-specific information does not match the codec of the
+    /// video session.
+    CodecMismatch,
+
+    /// The output picture and the setup reference picture are the same, but the video profile
+    /// does not support this.
+    DpbAndOutputCoincideNotSupported,
+
+    /// The output picture and the setup reference picture are different, but the video profile
+    /// does not support this.
+    DpbAndOutputDistinctNotSupported,
+
+    /// Video coding commands can't be recorded in a secondary command buffer.
+    ForbiddenInSecondaryCommandBuffer,
+
+    /// Video coding commands can't be recorded inside a render pass.
+    ForbiddenInsideRenderPass,
+
+    /// The image view of a picture resource is missing a required usage.
+    ImageMissingUsage {
+        resource_in_command: ResourceInCommand,
+        required_usage: ImageUsage,
+    },
+
+    /// More reference pictures were provided than the maximum number of active reference
+    /// pictures of the video session.
+    MaxActiveReferencePicturesExceeded {
+        provided: u32,
+        max_supported: u32,
+    },
+
+    /// More rate control layers were provided than are supported by the video profile.
+    MaxRateControlLayersExceeded {
+        provided: u32,
+        max_supported: u32,
+    },
+
+    /// A video coding scope is not active.
+    NotActive,
+
+    /// The operation requires a video session with an encode profile.
+    NotEncodeVideoSession,
+
+    /// The queue family doesn't allow this operation.
+    NotSupportedByQueueFamily,
+
+    /// The array layer of a picture resource is not less than the number of array layers of its
+    /// image view.
+    PictureResourceArrayLayerOutOfRange {
+        resource_in_command: ResourceInCommand,
+    },
+
+    /// The coded extent of a picture resource is zero, or larger than its image view.
+    PictureResourceExtentOutOfRange {
+        resource_in_command: ResourceInCommand,
+    },
+
+    /// The picture resource of a reference slot was not bound to a DPB slot when the video coding
+    /// scope was begun.
+    PictureResourceNotBound {
+        resource_in_command: ResourceInCommand,
+    },
+
+    /// The number of bytes encoded by the application was not zero, but the video profile does
+    /// not support this.
+    PrecedingExternallyEncodedBytesNotSupported,
+
+    /// The quality level is not less than the number of quality levels of the video profile.
+    QualityLevelOutOfRange {
+        quality_level: u32,
+        quality_level_count: u32,
+    },
+
+    /// A query is still active.
+    QueryActive,
+
+    /// Rate control layers were provided, but the rate control mode is
+    /// [`VideoEncodeRateControlMode::None`].
+    RateControlLayersNotAllowed,
+
+    /// The rate control mode is not supported by the video profile.
+    RateControlModeNotSupported {
+        rate_control_mode: VideoEncodeRateControlMode,
+    },
+
+    /// An entry of a reference list refers to a DPB slot that is not one of the reference slots
+    /// of the encode operation.
+    ReferenceListSlotNotActive {
+        slot_index: u32,
+    },
+
+    /// A reference slot used by a decode or encode operation is missing its slot index, picture
+    /// resource, or for decode operations, codec-specific reference information.
+    ReferenceSlotIncomplete {
+        resource_in_command: ResourceInCommand,
+    },
+
+    /// The same DPB slot index was used more than once.
+    SlotIndexNotUnique {
+        slot_index: u32,
+    },
+
+    /// A DPB slot index is not less than the number of DPB slots of the video session.
+    SlotIndexOutOfRange {
+        slot_index: u32,
+        max_dpb_slots: u32,
+    },
+
+    /// The video session was created for a different queue family than the command buffer.
+    VideoSessionQueueFamilyMismatch,
+
+    /// The video session parameters were created for a different video session.
+    VideoSessionParametersMismatch,
+
+    /// The codec of the video session requires video session parameters, but none were
+    /// provided.
+    VideoSessionParametersMissing,
+}
+
+impl Error for VideoCodingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SyncCommandBufferBuilderError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for VideoCodingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::SyncCommandBufferBuilderError(_) => write!(f, "a SyncCommandBufferBuilderError"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::AlreadyActive => write!(f, "a video coding scope is already active"),
+            Self::BitstreamOffsetNotAligned {
+                offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the offset of the bitstream ({}) is not a multiple of the required alignment \
+                ({})",
+                offset, required_alignment,
+            ),
+            Self::BitstreamRangeNotAligned {
+                range,
+                required_alignment,
+            } => write!(
+                f,
+                "the size of the bitstream ({}) is not a multiple of the required alignment ({})",
+                range, required_alignment,
+            ),
+            Self::BitstreamRangeOutOfBounds {
+                offset,
+                range,
+                buffer_size,
+            } => write!(
+                f,
+                "the bitstream range (offset {}, size {}) is not within its buffer (size {})",
+                offset, range, buffer_size,
+            ),
+            Self::BufferMissingUsage { required_usage } => write!(
+                f,
+                "the bitstream buffer is missing the usage {:?}",
+                required_usage,
+            ),
+            Self::CbrBitrateMismatch => write!(
+                f,
+                "the rate control mode is constant bitrate, but the maximum bitrate of a layer is \
+                not equal to its average bitrate",
+            ),
+            Self::CodecMismatch => write!(
+                f,
+                "the codec of the provided codec-specific information does not match the codec \
+                of the video session",
+            ),
+            Self::DpbAndOutputCoincideNotSupported => write!(
+                f,
+                "the output picture and the setup reference picture are the same, but the video \
+                profile does not support this",
+            ),
+            Self::DpbAndOutputDistinctNotSupported => write!(
+                f,
+                "the output picture and the setup reference picture are different, but the video \
+                profile does not support this",
+            ),
+            Self::ForbiddenInSecondaryCommandBuffer => write!(
+                f,
+                "video coding commands can't be recorded in a secondary command buffer",
+            ),
+            Self::ForbiddenInsideRenderPass => write!(
+                f,
+                "video coding commands can't be recorded inside a render pass",
+            ),
+            Self::ImageMissingUsage {
+                resource_in_command,
+                required_usage,
+            } => write!(
+                f,
+                "the image view of the picture resource {:?} is missing the usage {:?}",
+                resource_in_command, required_usage,
+            ),
+            Self::MaxActiveReferencePicturesExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "more reference pictures ({}) were provided than the maximum number of active \
+                reference pictures of the video session ({})",
+                provided, max_supported,
+            ),
+            Self::MaxRateControlLayersExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "more rate control layers ({}) were provided than are supported by the video \
+                profile ({})",
+                provided, max_supported,
+            ),
+            Self::NotActive => write!(f, "a video coding scope is not active"),
+            Self::NotEncodeVideoSession => write!(
+                f,
+                "the operation requires a video session with an encode profile",
+            ),
+            Self::NotSupportedByQueueFamily => {
+                write!(f, "the queue family doesn't allow this operation")
+            }
+            Self::PictureResourceArrayLayerOutOfRange {
+                resource_in_command,
+            } => write!(
+                f,
+                "the array layer of the picture resource {:?} is not less than the number of \
+                array layers of its image view",
+                resource_in_command,
+            ),
+            Self::PictureResourceExtentOutOfRange {
+                resource_in_command,
+            } => write!(
+                f,
+                "the coded extent of the picture resource {:?} is zero, or larger than its image \
+                view",
+                resource_in_command,
+            ),
+            Self::PictureResourceNotBound {
+                resource_in_command,
+            } => write!(
+                f,
+                "the picture resource {:?} was not bound to a DPB slot when the video coding \
+                scope was begun",
+                resource_in_command,
+            ),
+            Self::PrecedingExternallyEncodedBytesNotSupported => write!(
+                f,
+                "the number of bytes encoded by the application was not zero, but the video \
+                profile does not support this",
+            ),
+            Self::QualityLevelOutOfRange {
+                quality_level,
+                quality_level_count,
+            } => write!(
+                f,
+                "the quality level ({}) is not less than the number of quality levels of the \
+                video profile ({})",
+                quality_level, quality_level_count,
+            ),
+            Self::QueryActive => write!(f, "a query is still active"),
+            Self::RateControlLayersNotAllowed => write!(
+                f,
+                "rate control layers were provided, but the rate control mode is \
+                `VideoEncodeRateControlMode::None`",
+            ),
+            Self::RateControlModeNotSupported { rate_control_mode } => write!(
+                f,
+                "the rate control mode {:?} is not supported by the video profile",
+                rate_control_mode,
+            ),
+            Self::ReferenceListSlotNotActive { slot_index } => write!(
+                f,
+                "an entry of a reference list refers to the DPB slot {}, which is not one of the \
+                reference slots of the encode operation",
+                slot_index,
+            ),
+            Self::ReferenceSlotIncomplete {
+                resource_in_command,
+            } => write!(
+                f,
+                "the reference slot {:?} is missing its slot index, picture resource, or for \
+                decode operations, codec-specific reference information",
+                resource_in_command,
+            ),
+            Self::SlotIndexNotUnique { slot_index } => write!(
+                f,
+                "the DPB slot index {} was used more than once",
+                slot_index,
+            ),
+            Self::SlotIndexOutOfRange {
+                slot_index,
+                max_dpb_slots,
+            } => write!(
+                f,
+                "the DPB slot index {} is not less than the number of DPB slots of the video \
+                session ({})",
+                slot_index, max_dpb_slots,
+            ),
+            Self::VideoSessionQueueFamilyMismatch => write!(
+                f,
+                "the video session was created for a different queue family than the command \
+                buffer",
+            ),
+            Self::VideoSessionParametersMismatch => write!(
+                f,
+                "the video session parameters were created for a different video session",
+            ),
+            Self::VideoSessionParametersMissing => write!(
+                f,
+                "the codec of the video session requires video session parameters, but none were \
+                provided",
+            ),
+        }
+    }
+}
+
+impl From<SyncCommandBufferBuilderError> for VideoCodingError {
+    fn from(err: SyncCommandBufferBuilderError) -> Self {
+        Self::SyncCommandBufferBuilderError(err)
+    }
+}
+
+impl From<RequirementNotMet> for VideoCodingError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        buffer::CpuAccessibleBuffer,
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, CommandBufferUsage, PrimaryAutoCommandBuffer,
+        },
+        device::Queue,
+        format::Format,
+        image::{view::ImageView, AttachmentImage},
+        memory::allocator::StandardMemoryAllocator,
+        video::{
+            H264ProfileIdc, VideoCodecOperations, VideoCodecProfileInfo,
+            VideoEncodeCodecRateControlInfo, VideoEncodeH265RateControlStructure,
+            VideoEncodeRateControlLayerInfo, VideoProfileInfo, VideoSession,
+            VideoSessionCreateInfo, VideoSessionParameters, VideoSessionParametersCodecCreateInfo,
+            VideoSessionParametersCreateInfo,
+        },
+    };
+    use std::{mem, sync::Arc};
+
+    /// Creates a device and a queue for H.264 encode operations, and an encode video session with
+    /// empty session parameters, or returns if that is not supported.
+    macro_rules! encode_h264_dev_and_session {
+        () => {{
+            use crate::device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo};
+
+            let instance = instance!();
+            let enabled_extensions = DeviceExtensions {
+                khr_synchronization2: true,
+                khr_video_queue: true,
+                khr_video_encode_queue: true,
+                ext_video_encode_h264: true,
+                ..DeviceExtensions::empty()
+            };
+            let video_profile = VideoProfileInfo::new(VideoCodecProfileInfo::EncodeH264 {
+                std_profile_idc: H264ProfileIdc::Main,
+            });
+
+            let select = match instance.enumerate_physical_devices() {
+                Ok(x) => x,
+                Err(_) => return,
+            }
+            .filter(|p| p.supported_extensions().contains(&enabled_extensions))
+            .find_map(|p| {
+                p.queue_family_properties()
+                    .iter()
+                    .position(|q| {
+                        q.video_codec_operations
+                            .intersects(VideoCodecOperations::ENCODE_H264)
+                    })
+                    .map(|i| (p, i as u32))
+            });
+
+            let (physical_device, queue_family_index) = match select {
+                Some(x) => x,
+                None => return,
+            };
+
+            let video_capabilities = match physical_device.video_capabilities(&video_profile) {
+                Ok(x) => x,
+                Err(_) => return,
+            };
+
+            let (device, mut queues) = match Device::new(
+                physical_device,
+                DeviceCreateInfo {
+                    queue_create_infos: vec![QueueCreateInfo {
+                        queue_family_index,
+                        ..Default::default()
+                    }],
+                    enabled_extensions,
+                    ..Default::default()
+                },
+            ) {
+                Ok(r) => r,
+                Err(_) => return,
+            };
+
+            let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+            let video_session = VideoSession::new(
+                &memory_allocator,
+                VideoSessionCreateInfo {
+                    queue_family_index,
+                    max_coded_extent: video_capabilities.min_coded_extent,
+                    ..VideoSessionCreateInfo::video_profile(video_profile)
+                },
+            )
+            .unwrap();
+            let video_session_parameters = unsafe {
+                VideoSessionParameters::new(
+                    video_session.clone(),
+                    VideoSessionParametersCreateInfo::codec(
+                        VideoSessionParametersCodecCreateInfo::EncodeH264 {
+                            max_std_sps_count: 1,
+                            max_std_pps_count: 1,
+                            std_spss: Vec::new(),
+                            std_ppss: Vec::new(),
+                        },
+                    ),
+                )
+            }
+            .unwrap();
+
+            (
+                device,
+                queues.next().unwrap(),
+                video_session,
+                video_session_parameters,
+            )
+        }};
+    }
+
+    /// Creates a primary command buffer builder for `queue` that is inside a video coding scope
+    /// for `video_session`.
+    fn encode_command_buffer_builder(
+        cb_allocator: &StandardCommandBufferAllocator,
+        queue: &Queue,
+        video_session: Arc<VideoSession>,
+        video_session_parameters: Arc<VideoSessionParameters>,
+    ) -> AutoCommandBufferBuilder<PrimaryAutoCommandBuffer, StandardCommandBufferAllocator> {
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.begin_video_coding(VideoBeginCodingInfo {
+            video_session_parameters: Some(video_session_parameters),
+            ..VideoBeginCodingInfo::video_session(video_session)
+        })
+        .unwrap();
+
+        cbb
+    }
+
+    #[test]
+    fn decode_video_requires_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let src_buffer = CpuAccessibleBuffer::from_iter(
+            &memory_allocator,
+            BufferUsage::TRANSFER_SRC,
+            false,
+            [0u8; 64],
+        )
+        .unwrap();
+        let dst_image = ImageView::new_default(
+            AttachmentImage::new(&memory_allocator, [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match cbb.decode_video(VideoDecodeInfo::new(
+            src_buffer,
+            VideoPictureResourceInfo::image_view(dst_image),
+            VideoDecodeCodecPictureInfo::H264 {
+                std_picture_info: unsafe { mem::zeroed() },
+                slice_offsets: vec![0],
+            },
+        )) {
+            Err(VideoCodingError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"khr_video_decode_queue") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn encode_video_requires_extension() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let dst_buffer = CpuAccessibleBuffer::from_iter(
+            &memory_allocator,
+            BufferUsage::TRANSFER_DST,
+            false,
+            [0u8; 64],
+        )
+        .unwrap();
+        let src_image = ImageView::new_default(
+            AttachmentImage::new(&memory_allocator, [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match unsafe {
+            cbb.encode_video(VideoEncodeInfo::new(
+                dst_buffer,
+                VideoPictureResourceInfo::image_view(src_image),
+                VideoEncodeCodecPictureInfo::H264 {
+                    std_picture_info: mem::zeroed(),
+                    reference_final_lists: None,
+                    nalu_slices: Vec::new(),
+                    emit_picture_parameters: None,
+                },
+            ))
+        } {
+            Err(VideoCodingError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"khr_video_encode_queue") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn control_video_coding_not_supported_by_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        if queue.device().physical_device().queue_family_properties()
+            [queue.queue_family_index() as usize]
+            .queue_flags
+            .intersects(QueueFlags::VIDEO_DECODE | QueueFlags::VIDEO_ENCODE)
+        {
+            return;
+        }
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            cbb.control_video_coding(VideoCodingControlInfo {
+                flags: VideoCodingControlFlags::RESET,
+                ..Default::default()
+            }),
+            Err(VideoCodingError::NotSupportedByQueueFamily),
+        ));
+    }
+
+    #[test]
+    fn end_video_coding_not_supported_by_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        // The queue of `gfx_dev_and_queue!` is chosen for graphics, skip if it can also do video.
+        if queue.device().physical_device().queue_family_properties()
+            [queue.queue_family_index() as usize]
+            .queue_flags
+            .intersects(QueueFlags::VIDEO_DECODE | QueueFlags::VIDEO_ENCODE)
+        {
+            return;
+        }
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            cbb.end_video_coding(),
+            Err(VideoCodingError::NotSupportedByQueueFamily),
+        ));
+    }
+
+    #[test]
+    fn encode_rate_control_layers_not_allowed() {
+        let (device, queue, video_session, video_session_parameters) =
+            encode_h264_dev_and_session!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = encode_command_buffer_builder(
+            &cb_allocator,
+            &queue,
+            video_session,
+            video_session_parameters,
+        );
+
+        assert!(matches!(
+            cbb.control_video_coding(VideoCodingControlInfo {
+                flags: VideoCodingControlFlags::RESET
+                    | VideoCodingControlFlags::ENCODE_RATE_CONTROL,
+                encode_rate_control: Some(VideoEncodeRateControlInfo {
+                    rate_control_mode: VideoEncodeRateControlMode::None,
+                    layers: vec![Default::default()],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Err(VideoCodingError::RateControlLayersNotAllowed),
+        ));
+    }
+
+    #[test]
+    fn encode_rate_control_max_layers_exceeded() {
+        let (device, queue, video_session, video_session_parameters) =
+            encode_h264_dev_and_session!();
+
+        let video_capabilities = video_session.video_capabilities();
+        let rate_control_mode = if video_capabilities
+            .encode_rate_control_modes
+            .intersects(VideoEncodeRateControlModes::VBR)
+        {
+            VideoEncodeRateControlMode::Vbr
+        } else if video_capabilities
+            .encode_rate_control_modes
+            .intersects(VideoEncodeRateControlModes::CBR)
+        {
+            VideoEncodeRateControlMode::Cbr
+        } else {
+            return;
+        };
+        let layer_count = video_capabilities.encode_rate_control_layer_count + 1;
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = encode_command_buffer_builder(
+            &cb_allocator,
+            &queue,
+            video_session,
+            video_session_parameters,
+        );
+
+        match cbb.control_video_coding(VideoCodingControlInfo {
+            flags: VideoCodingControlFlags::RESET | VideoCodingControlFlags::ENCODE_RATE_CONTROL,
+            encode_rate_control: Some(VideoEncodeRateControlInfo {
+                rate_control_mode,
+                layers: (0..layer_count)
+                    .map(|_| VideoEncodeRateControlLayerInfo {
+                        average_bitrate: 1_000_000,
+                        max_bitrate: 1_000_000,
+                        frame_rate: [30, 1],
+                        ..Default::default()
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }) {
+            Err(VideoCodingError::MaxRateControlLayersExceeded { provided, .. })
+                if provided == layer_count => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn encode_rate_control_cbr_bitrate_mismatch() {
+        let (device, queue, video_session, video_session_parameters) =
+            encode_h264_dev_and_session!();
+
+        let video_capabilities = video_session.video_capabilities();
+
+        if !video_capabilities
+            .encode_rate_control_modes
+            .intersects(VideoEncodeRateControlModes::CBR)
+            || video_capabilities.encode_rate_control_layer_count == 0
+        {
+            return;
+        }
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = encode_command_buffer_builder(
+            &cb_allocator,
+            &queue,
+            video_session,
+            video_session_parameters,
+        );
+
+        assert!(matches!(
+            cbb.control_video_coding(VideoCodingControlInfo {
+                flags: VideoCodingControlFlags::RESET
+                    | VideoCodingControlFlags::ENCODE_RATE_CONTROL,
+                encode_rate_control: Some(VideoEncodeRateControlInfo {
+                    rate_control_mode: VideoEncodeRateControlMode::Cbr,
+                    layers: vec![VideoEncodeRateControlLayerInfo {
+                        average_bitrate: 1_000_000,
+                        max_bitrate: 2_000_000,
+                        frame_rate: [30, 1],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Err(VideoCodingError::CbrBitrateMismatch),
+        ));
+    }
+
+    #[test]
+    fn encode_rate_control_zero_frame_rate_denominator() {
+        let (device, queue, video_session, video_session_parameters) =
+            encode_h264_dev_and_session!();
+
+        let video_capabilities = video_session.video_capabilities();
+
+        if !video_capabilities
+            .encode_rate_control_modes
+            .intersects(VideoEncodeRateControlModes::VBR)
+            || video_capabilities.encode_rate_control_layer_count == 0
+        {
+            return;
+        }
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = encode_command_buffer_builder(
+            &cb_allocator,
+            &queue,
+            video_session,
+            video_session_parameters,
+        );
+
+        assert_should_panic!("the denominator of `frame_rate` must not be zero", {
+            let _ = cbb.control_video_coding(VideoCodingControlInfo {
+                flags: VideoCodingControlFlags::RESET
+                    | VideoCodingControlFlags::ENCODE_RATE_CONTROL,
+                encode_rate_control: Some(VideoEncodeRateControlInfo {
+                    rate_control_mode: VideoEncodeRateControlMode::Vbr,
+                    layers: vec![VideoEncodeRateControlLayerInfo {
+                        average_bitrate: 1_000_000,
+                        max_bitrate: 2_000_000,
+                        frame_rate: [30, 0],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }),
+                ..Default::default()
+            });
+        });
+    }
+
+    #[test]
+    fn encode_rate_control_codec_mismatch() {
+        let (device, queue, video_session, video_session_parameters) =
+            encode_h264_dev_and_session!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = encode_command_buffer_builder(
+            &cb_allocator,
+            &queue,
+            video_session,
+            video_session_parameters,
+        );
+
+        assert!(matches!(
+            cbb.control_video_coding(VideoCodingControlInfo {
+                flags: VideoCodingControlFlags::RESET
+                    | VideoCodingControlFlags::ENCODE_RATE_CONTROL,
+                encode_rate_control: Some(VideoEncodeRateControlInfo {
+                    codec_rate_control_info: Some(VideoEncodeCodecRateControlInfo::H265 {
+                        gop_frame_count: 0,
+                        idr_period: 0,
+                        consecutive_b_frame_count: 0,
+                        rate_control_structure: VideoEncodeH265RateControlStructure::Unknown,
+                        sub_layer_count: 1,
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            Err(VideoCodingError::CodecMismatch),
+        ));
+    }
+
+    #[test]
+    fn encode_video_codec_mismatch() {
+        let (device, queue, video_session, video_session_parameters) =
+            encode_h264_dev_and_session!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let dst_buffer = CpuAccessibleBuffer::from_iter(
+            &memory_allocator,
+            BufferUsage::TRANSFER_DST,
+            false,
+            [0u8; 64],
+        )
+        .unwrap();
+        let src_image = ImageView::new_default(
+            AttachmentImage::new(&memory_allocator, [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = encode_command_buffer_builder(
+            &cb_allocator,
+            &queue,
+            video_session,
+            video_session_parameters,
+        );
+
+        assert!(matches!(
+            unsafe {
+                cbb.encode_video(VideoEncodeInfo::new(
+                    dst_buffer,
+                    VideoPictureResourceInfo::image_view(src_image),
+                    VideoEncodeCodecPictureInfo::H265 {
+                        std_picture_info: mem::zeroed(),
+                        reference_final_lists: None,
+                        nalu_slice_segments: Vec::new(),
+                        emit_picture_parameters: None,
+                    },
+                ))
+            },
+            Err(VideoCodingError::CodecMismatch),
+        ));
+    }
+
+    #[test]
+    fn encode_video_buffer_missing_usage() {
+        let (device, queue, video_session, video_session_parameters) =
+            encode_h264_dev_and_session!();
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+        let dst_buffer = CpuAccessibleBuffer::from_iter(
+            &memory_allocator,
+            BufferUsage::TRANSFER_DST,
+            false,
+            [0u8; 64],
+        )
+        .unwrap();
+        let src_image = ImageView::new_default(
+            AttachmentImage::new(&memory_allocator, [64, 64], Format::R8G8B8A8_UNORM).unwrap(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = encode_command_buffer_builder(
+            &cb_allocator,
+            &queue,
+            video_session,
+            video_session_parameters,
+        );
+
+        match unsafe {
+            cbb.encode_video(VideoEncodeInfo::new(
+                dst_buffer,
+                VideoPictureResourceInfo::image_view(src_image),
+                VideoEncodeCodecPictureInfo::H264 {
+                    std_picture_info: mem::zeroed(),
+                    reference_final_lists: None,
+                    nalu_slices: Vec::new(),
+                    emit_picture_parameters: None,
+                },
+            ))
+        } {
+            Err(VideoCodingError::BufferMissingUsage { required_usage })
+                if required_usage == BufferUsage::VIDEO_ENCODE_DST => {}
+            _ => panic!(),
+        }
+    }
+}
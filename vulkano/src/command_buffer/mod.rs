@@ -106,19 +106,23 @@
 
 #[doc(no_inline)]
 pub use self::standard::{CommandBufferBuilder, PrimaryCommandBuffer, SecondaryCommandBuffer};
+pub(crate) use self::traits::execute_batch_after;
 pub use self::{
     auto::{
         AutoCommandBufferBuilder, BuildError, CommandBufferBeginError, PrimaryAutoCommandBuffer,
         SecondaryAutoCommandBuffer,
     },
     commands::{
+        acceleration_structure::AccelerationStructureCommandError,
         clear::{ClearColorImageInfo, ClearDepthStencilImageInfo, ClearError, FillBufferInfo},
+        conditional_rendering::ConditionalRenderingError,
         copy::{
             BlitImageInfo, BufferCopy, BufferImageCopy, CopyBufferInfo, CopyBufferInfoTyped,
             CopyBufferToImageInfo, CopyError, CopyErrorResource, CopyImageInfo,
             CopyImageToBufferInfo, ImageBlit, ImageCopy, ImageResolve, ResolveImageInfo,
         },
         debug::DebugUtilsError,
+        diagnostics::{Checkpoint, CheckpointMarker, DiagnosticsError},
         pipeline::PipelineExecutionError,
         query::QueryError,
         render_pass::{
@@ -126,6 +130,9 @@ pub use self::{
             RenderingAttachmentInfo, RenderingAttachmentResolveInfo, RenderingInfo,
         },
         secondary::ExecuteCommandsError,
+        sync::EventCommandError,
+        transform_feedback::TransformFeedbackError,
+        video::VideoCodingError,
     },
     traits::{
         CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract,
@@ -440,6 +447,32 @@ pub struct SubmitInfo {
     /// The default value is empty.
     pub signal_semaphores: Vec<SemaphoreSubmitInfo>,
 
+    /// Whether this is a protected submission.
+    ///
+    /// If set to `true`, all elements of `command_buffers` must be [protected], and the queue must
+    /// have been created with [`QueueCreateFlags::PROTECTED`]. If set to `false`, none of the
+    /// elements of `command_buffers` may be protected.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [protected]: PrimaryCommandBufferAbstract::protected
+    /// [`QueueCreateFlags::PROTECTED`]: crate::device::QueueCreateFlags::PROTECTED
+    pub protected: bool,
+
+    /// The counter pass to use for performance queries in `command_buffers`.
+    ///
+    /// Reading some sets of performance counters takes more than one pass. In that case, the
+    /// command buffers must be submitted once for each pass index, from `0` up to the number
+    /// returned by [`PhysicalDevice::queue_family_performance_query_passes`].
+    ///
+    /// If not `0`, the [`khr_performance_query`] extension must be enabled on the device.
+    ///
+    /// The default value is `0`.
+    ///
+    /// [`PhysicalDevice::queue_family_performance_query_passes`]: crate::device::physical::PhysicalDevice::queue_family_performance_query_passes
+    /// [`khr_performance_query`]: crate::device::DeviceExtensions::khr_performance_query
+    pub performance_counter_pass_index: u32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -450,6 +483,8 @@ impl Default for SubmitInfo {
             wait_semaphores: Vec::new(),
             command_buffers: Vec::new(),
             signal_semaphores: Vec::new(),
+            protected: false,
+            performance_counter_pass_index: 0,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -468,8 +503,11 @@ pub struct SemaphoreSubmitInfo {
     /// For a semaphore signal operation, specifies the pipeline stages in the first synchronization
     /// scope: stages of queue operations preceding the signal operation that must complete before
     /// the semaphore is signalled.
-    /// If this value does not equal [`ALL_COMMANDS`], then the [`synchronization2`] feature must
-    /// be enabled on the device.
+    ///
+    /// If the [`synchronization2`] feature is not enabled on the device, then the stages of a
+    /// signal operation are ignored and [`ALL_COMMANDS`] is used instead. The same happens for a
+    /// wait operation if the value contains stages that are only available with
+    /// [`synchronization2`].
     ///
     /// The default value is [`ALL_COMMANDS`].
     ///
@@ -477,6 +515,15 @@ pub struct SemaphoreSubmitInfo {
     /// [`synchronization2`]: crate::device::Features::synchronization2
     pub stages: PipelineStages,
 
+    /// If `semaphore` is a timeline semaphore, specifies the value that the counter of the
+    /// semaphore must reach for a wait operation, or the value to set the counter to for a
+    /// signal operation.
+    ///
+    /// This is ignored for binary semaphores.
+    ///
+    /// The default value is 0.
+    pub value: u64,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -487,6 +534,7 @@ impl SemaphoreSubmitInfo {
         Self {
             semaphore,
             stages: PipelineStages::ALL_COMMANDS,
+            value: 0,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -590,20 +638,34 @@ impl From<ResourceUseRef> for SecondaryResourceUseRef {
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum ResourceInCommand {
+    AccelerationStructure { index: u32 },
     ColorAttachment { index: u32 },
     ColorResolveAttachment { index: u32 },
+    ConditionalRenderingBuffer,
     DepthAttachment,
     DepthResolveAttachment,
+    DescriptorBuffer { index: u32 },
     DescriptorSet { set: u32, binding: u32, index: u32 },
     Destination,
     FramebufferAttachment { index: u32 },
+    GeometryData { index: u32 },
     IndexBuffer,
     IndirectBuffer,
+    ScratchData,
     SecondaryCommandBuffer { index: u32 },
+    ShaderBindingTable,
     Source,
     StencilAttachment,
     StencilResolveAttachment,
+    TransformFeedbackBuffer { binding: u32 },
+    TransformFeedbackCounterBuffer { index: u32 },
     VertexBuffer { binding: u32 },
+    VideoDecodeDestination,
+    VideoDecodeSource,
+    VideoEncodeDestination,
+    VideoEncodeSource,
+    VideoReferencePicture { index: u32 },
+    VideoSetupReferencePicture,
 }
 
 #[doc(hidden)]
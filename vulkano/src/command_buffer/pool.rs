@@ -48,6 +48,7 @@ pub struct CommandPool {
     queue_family_index: u32,
     _transient: bool,
     _reset_command_buffer: bool,
+    protected: bool,
     // Unimplement `Sync`, as Vulkan command pools are not thread-safe.
     _marker: PhantomData<Cell<ash::vk::CommandPool>>,
 }
@@ -65,6 +66,7 @@ impl CommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -75,6 +77,7 @@ impl CommandPool {
             queue_family_index,
             _transient: transient,
             _reset_command_buffer: reset_command_buffer,
+            protected,
             _marker: PhantomData,
         })
     }
@@ -95,6 +98,7 @@ impl CommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -105,6 +109,7 @@ impl CommandPool {
             queue_family_index,
             _transient: transient,
             _reset_command_buffer: reset_command_buffer,
+            protected,
             _marker: PhantomData,
         }
     }
@@ -117,6 +122,7 @@ impl CommandPool {
             queue_family_index,
             transient: _,
             reset_command_buffer: _,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -128,6 +134,17 @@ impl CommandPool {
             });
         }
 
+        // VUID-VkCommandPoolCreateInfo-flags-02860
+        if protected && !device.enabled_features().protected_memory {
+            return Err(CommandPoolCreationError::RequirementNotMet {
+                required_for: "`create_info.protected` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["protected_memory"],
+                    ..Default::default()
+                },
+            });
+        }
+
         Ok(())
     }
 
@@ -139,6 +156,7 @@ impl CommandPool {
             queue_family_index,
             transient,
             reset_command_buffer,
+            protected,
             _ne: _,
         } = create_info;
 
@@ -152,6 +170,10 @@ impl CommandPool {
             flags |= ash::vk::CommandPoolCreateFlags::RESET_COMMAND_BUFFER;
         }
 
+        if protected {
+            flags |= ash::vk::CommandPoolCreateFlags::PROTECTED;
+        }
+
         let create_info = ash::vk::CommandPoolCreateInfo {
             flags,
             queue_family_index,
@@ -238,12 +260,14 @@ impl CommandPool {
         };
 
         let device = self.device.clone();
+        let protected = self.protected;
 
         Ok(out.into_iter().map(move |command_buffer| CommandPoolAlloc {
             handle: command_buffer,
             device: device.clone(),
             id: CommandPoolAlloc::next_id(),
             level,
+            protected,
         }))
     }
 
@@ -319,6 +343,12 @@ impl CommandPool {
     pub fn queue_family_index(&self) -> u32 {
         self.queue_family_index
     }
+
+    /// Returns whether the command buffers allocated from this pool are protected.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
 }
 
 impl Drop for CommandPool {
@@ -355,6 +385,11 @@ pub enum CommandPoolCreationError {
     /// Not enough memory.
     OomError(OomError),
 
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
     /// The provided `queue_family_index` was not less than the number of queue families in the
     /// physical device.
     QueueFamilyIndexOutOfRange {
@@ -376,6 +411,14 @@ impl Display for CommandPoolCreationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
             Self::OomError(_) => write!(f, "not enough memory",),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
             Self::QueueFamilyIndexOutOfRange {
                 queue_family_index,
                 queue_family_count,
@@ -418,6 +461,15 @@ pub struct CommandPoolCreateInfo {
     /// The default value is `false`.
     pub reset_command_buffer: bool,
 
+    /// Whether the command buffers allocated from this pool are protected, so that they can
+    /// access protected resources and be submitted in a protected submission.
+    ///
+    /// If set to `true`, the [`protected_memory`](crate::device::Features::protected_memory)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub protected: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -428,6 +480,7 @@ impl Default for CommandPoolCreateInfo {
             queue_family_index: u32::MAX,
             transient: false,
             reset_command_buffer: false,
+            protected: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -467,6 +520,7 @@ pub struct CommandPoolAlloc {
     device: Arc<Device>,
     id: NonZeroU64,
     level: CommandBufferLevel,
+    protected: bool,
 }
 
 impl CommandPoolAlloc {
@@ -475,6 +529,13 @@ impl CommandPoolAlloc {
     pub fn level(&self) -> CommandBufferLevel {
         self.level
     }
+
+    /// Returns whether the command buffer is protected, because it was allocated from a
+    /// protected command pool.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
 }
 
 unsafe impl VulkanObject for CommandPoolAlloc {
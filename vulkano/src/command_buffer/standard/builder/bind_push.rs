@@ -82,7 +82,7 @@ where
         // VUID-vkCmdBindDescriptorSets-commandBuffer-cmdpool
         // VUID-vkCmdBindDescriptorSets-pipelineBindPoint-00361
         match pipeline_bind_point {
-            PipelineBindPoint::Compute => {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::COMPUTE)
@@ -356,6 +356,11 @@ where
         // VUID-vkCmdBindPipeline-commonparent
         assert_eq!(self.device(), pipeline.device());
 
+        // VUID-vkCmdBindPipeline-pipeline-03382
+        if !pipeline.library_flags().is_empty() {
+            return Err(BindPushError::PipelineIsLibrary);
+        }
+
         if let Some(last_pipeline) =
             self.current_state
                 .render_pass
@@ -543,17 +548,23 @@ where
 
     /// Sets push constants for future dispatch or draw calls.
     ///
+    /// Only the bytes from `offset` to `offset` plus the size of `push_constants` are updated.
+    /// `push_constants` can be a slice such as `&[u8]`, so that the push constant range of one
+    /// shader stage can be updated without touching the ranges of the other stages.
+    ///
     /// # Panics
     ///
     /// - Panics if `offset` is not a multiple of 4.
     /// - Panics if the size of `push_constants` is not a multiple of 4.
+    /// - Panics if `offset` plus the size of `push_constants` exceeds the
+    ///   [`max_push_constants_size`](crate::device::Properties::max_push_constants_size) limit.
     /// - Panics if any of the bytes in `push_constants` do not fall within any of the pipeline
     ///   layout's push constant ranges.
     pub fn push_constants(
         &mut self,
         pipeline_layout: Arc<PipelineLayout>,
         offset: u32,
-        push_constants: &impl BufferContents,
+        push_constants: &(impl BufferContents + ?Sized),
     ) -> &mut Self {
         let push_constants = push_constants.as_bytes();
 
@@ -573,14 +584,32 @@ where
         offset: u32,
         push_constants: &[u8],
     ) -> Result<(), BindPushError> {
+        // VUID-vkCmdPushConstants-offset-00370
         if offset % 4 != 0 {
             return Err(BindPushError::PushConstantsOffsetNotAligned);
         }
 
+        // VUID-vkCmdPushConstants-size-00371
         if push_constants.len() % 4 != 0 {
             return Err(BindPushError::PushConstantsSizeNotAligned);
         }
 
+        let max_push_constants_size = self
+            .device()
+            .physical_device()
+            .properties()
+            .max_push_constants_size;
+
+        // VUID-vkCmdPushConstants-offset-00372
+        // VUID-vkCmdPushConstants-size-00373
+        if offset as u64 + push_constants.len() as u64 > max_push_constants_size as u64 {
+            return Err(BindPushError::MaxPushConstantsSizeExceeded {
+                offset,
+                size: push_constants.len() as u32,
+                max_supported: max_push_constants_size,
+            });
+        }
+
         let mut current_offset = offset;
         let mut remaining_size = push_constants.len() as u32;
 
@@ -738,7 +767,7 @@ where
         // VUID-vkCmdPushDescriptorSetKHR-commandBuffer-cmdpool
         // VUID-vkCmdPushDescriptorSetKHR-pipelineBindPoint-00363
         match pipeline_bind_point {
-            PipelineBindPoint::Compute => {
+            PipelineBindPoint::Compute | PipelineBindPoint::RayTracing => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::COMPUTE)
@@ -760,7 +789,7 @@ where
         assert_eq!(self.device(), pipeline_layout.device());
 
         // VUID-vkCmdPushDescriptorSetKHR-set-00364
-        if set_num as usize > pipeline_layout.set_layouts().len() {
+        if set_num as usize >= pipeline_layout.set_layouts().len() {
             return Err(BindPushError::DescriptorSetOutOfRange {
                 set_num,
                 pipeline_layout_set_count: pipeline_layout.set_layouts().len() as u32,
@@ -811,8 +840,22 @@ where
             return self;
         }
 
+        let acceleration_structure_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_acceleration_structure)
+            .collect();
+        let inline_uniform_block_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_inline_uniform_block)
+            .collect();
+
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        for (((info, write), acceleration_structure_info), inline_uniform_block_info) in infos
+            .iter()
+            .zip(writes.iter_mut())
+            .zip(&acceleration_structure_infos)
+            .zip(&inline_uniform_block_infos)
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -826,6 +869,14 @@ where
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::AccelerationStructure(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_next = acceleration_structure_info as *const _ as *const _;
+                }
+                DescriptorWriteInfo::InlineUniformBlock(data) => {
+                    write.descriptor_count = data.len() as u32;
+                    write.p_next = inline_uniform_block_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
@@ -84,6 +84,8 @@ where
         let fns = self.device().instance().fns();
         (fns.ext_debug_utils.cmd_begin_debug_utils_label_ext)(self.handle(), &label_info);
 
+        self.current_state.debug_label_depth += 1;
+
         self
     }
 
@@ -131,7 +133,9 @@ where
         // TODO: not checked, so unsafe for now
 
         // VUID-vkCmdEndDebugUtilsLabelEXT-commandBuffer-01913
-        // TODO: not checked, so unsafe for now
+        if self.inheritance_info.is_some() && self.current_state.debug_label_depth == 0 {
+            return Err(DebugUtilsError::DebugLabelNotActive);
+        }
 
         Ok(())
     }
@@ -141,6 +145,9 @@ where
         let fns = self.device().instance().fns();
         (fns.ext_debug_utils.cmd_end_debug_utils_label_ext)(self.handle());
 
+        self.current_state.debug_label_depth =
+            self.current_state.debug_label_depth.saturating_sub(1);
+
         self
     }
 
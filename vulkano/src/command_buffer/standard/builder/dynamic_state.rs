@@ -17,8 +17,10 @@ use crate::{
         graphics::{
             color_blend::LogicOp,
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
+            fragment_shading_rate::{FragmentShadingRate, FragmentShadingRateCombinerOp},
             input_assembly::PrimitiveTopology,
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            multisample::SampleLocationsInfo,
+            rasterization::{CullMode, DepthBias, FrontFace, LineStipple, PolygonMode},
             viewport::{Scissor, Viewport},
         },
         DynamicState,
@@ -53,6 +55,158 @@ where
         Ok(())
     }
 
+    /// Sets whether dynamic alpha-to-coverage is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_alpha_to_coverage_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_coverage_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    #[inline]
+    pub fn set_alpha_to_coverage_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_alpha_to_coverage_enable(enable).unwrap();
+
+        unsafe { self.set_alpha_to_coverage_enable_unchecked(enable) }
+    }
+
+    fn validate_set_alpha_to_coverage_enable(
+        &self,
+        _enable: bool,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::AlphaToCoverageEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetAlphaToCoverageEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetAlphaToCoverageEnableEXT-extendedDynamicState3AlphaToCoverageEnable-07343
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_coverage_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_alpha_to_coverage_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_alpha_to_coverage_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_coverage_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        debug_assert!(
+            self.device()
+                .enabled_extensions()
+                .ext_extended_dynamic_state3
+        );
+        debug_assert!(
+            self.device()
+                .enabled_features()
+                .extended_dynamic_state3_alpha_to_coverage_enable
+        );
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_alpha_to_coverage_enable_ext)(self.handle(), enable.into());
+
+        self.current_state.alpha_to_coverage_enable = Some(enable);
+
+        self
+    }
+
+    /// Sets whether dynamic alpha-to-one is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_alpha_to_one_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_one_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - If the [`alpha_to_one`](crate::device::Features::alpha_to_one) feature is not enabled
+    ///   on the device, panics if `enable` is `true`.
+    #[inline]
+    pub fn set_alpha_to_one_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_alpha_to_one_enable(enable).unwrap();
+
+        unsafe { self.set_alpha_to_one_enable_unchecked(enable) }
+    }
+
+    fn validate_set_alpha_to_one_enable(&self, enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::AlphaToOneEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-extendedDynamicState3AlphaToOneEnable-07345
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_alpha_to_one_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_alpha_to_one_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_alpha_to_one_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetAlphaToOneEnableEXT-alphaToOne-07607
+        if enable && !self.device().enabled_features().alpha_to_one {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`enable` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["alpha_to_one"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_alpha_to_one_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        debug_assert!(
+            self.device()
+                .enabled_extensions()
+                .ext_extended_dynamic_state3
+        );
+        debug_assert!(
+            self.device()
+                .enabled_features()
+                .extended_dynamic_state3_alpha_to_one_enable
+        );
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_alpha_to_one_enable_ext)(self.handle(), enable.into());
+
+        self.current_state.alpha_to_one_enable = Some(enable);
+
+        self
+    }
+
     /// Sets the dynamic blend constants for future draw calls.
     ///
     /// # Panics
@@ -543,6 +697,87 @@ where
         self
     }
 
+    /// Sets whether dynamic depth clamping is enabled for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_depth_clamp_enable`](crate::device::Features::extended_dynamic_state3_depth_clamp_enable)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - If the [`depth_clamp`](crate::device::Features::depth_clamp) feature is not enabled
+    ///   on the device, panics if `enable` is `true`.
+    #[inline]
+    pub fn set_depth_clamp_enable(&mut self, enable: bool) -> &mut Self {
+        self.validate_set_depth_clamp_enable(enable).unwrap();
+
+        unsafe { self.set_depth_clamp_enable_unchecked(enable) }
+    }
+
+    fn validate_set_depth_clamp_enable(&self, enable: bool) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::DepthClampEnable)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetDepthClampEnableEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetDepthClampEnableEXT-extendedDynamicState3DepthClampEnable-07448
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_depth_clamp_enable
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_depth_clamp_enable`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_depth_clamp_enable"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetDepthClampEnableEXT-depthClamp-07449
+        if enable && !self.device().enabled_features().depth_clamp {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`enable` is `true`",
+                requires_one_of: RequiresOneOf {
+                    features: &["depth_clamp"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_depth_clamp_enable_unchecked(&mut self, enable: bool) -> &mut Self {
+        debug_assert!(
+            self.device()
+                .enabled_extensions()
+                .ext_extended_dynamic_state3
+        );
+        debug_assert!(
+            self.device()
+                .enabled_features()
+                .extended_dynamic_state3_depth_clamp_enable
+        );
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_depth_clamp_enable_ext)(self.handle(), enable.into());
+
+        self.current_state.depth_clamp_enable = Some(enable);
+
+        self
+    }
+
     /// Sets the dynamic depth compare op for future draw calls.
     ///
     /// # Panics
@@ -754,6 +989,83 @@ where
         self
     }
 
+    /// Sets the device mask for future commands, which determines the physical devices of the
+    /// device group that execute them.
+    ///
+    /// Bit `n` of `device_mask` corresponds to the physical device at index `n` of
+    /// [`Device::physical_devices`](crate::device::Device::physical_devices).
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics, compute or
+    ///   transfer operations.
+    /// - Panics if the device API version is less than 1.1 and the
+    ///   [`khr_device_group`](crate::device::DeviceExtensions::khr_device_group) extension is not
+    ///   enabled on the device.
+    /// - Panics if `device_mask` is zero, or contains bits for nonexistent physical devices.
+    #[inline]
+    pub fn set_device_mask(&mut self, device_mask: u32) -> &mut Self {
+        self.validate_set_device_mask(device_mask).unwrap();
+
+        unsafe { self.set_device_mask_unchecked(device_mask) }
+    }
+
+    fn validate_set_device_mask(&self, device_mask: u32) -> Result<(), SetDynamicStateError> {
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetDeviceMask-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE | QueueFlags::TRANSFER)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        if !(self.device().api_version() >= Version::V1_1
+            || self.device().enabled_extensions().khr_device_group)
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_device_mask`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_1),
+                    device_extensions: &["khr_device_group"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let physical_device_count = self.device().physical_devices().len() as u32;
+
+        // VUID-vkCmdSetDeviceMask-deviceMask-00108
+        // VUID-vkCmdSetDeviceMask-deviceMask-00109
+        if device_mask == 0 || device_mask as u64 >> physical_device_count != 0 {
+            return Err(SetDynamicStateError::DeviceMaskInvalid {
+                provided: device_mask,
+            });
+        }
+
+        // VUID-vkCmdSetDeviceMask-deviceMask-00110
+        // VUID-vkCmdSetDeviceMask-deviceMask-00111
+        // The initial device mask of the command buffer and the device mask of render passes
+        // always include all physical devices, so these are always met.
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_device_mask_unchecked(&mut self, device_mask: u32) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_1 {
+            (fns.v1_1.cmd_set_device_mask)(self.handle(), device_mask);
+        } else {
+            debug_assert!(self.device().enabled_extensions().khr_device_group);
+            (fns.khr_device_group.cmd_set_device_mask_khr)(self.handle(), device_mask);
+        }
+
+        self
+    }
+
     /// Sets the dynamic discard rectangles for future draw calls.
     ///
     /// # Panics
@@ -878,6 +1190,183 @@ where
         self
     }
 
+    /// Sets the dynamic fragment shading rate for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if none of the
+    ///   [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate),
+    ///   [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    ///   or [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    ///   features are enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if the elements of `fragment_size` are not 1, 2 or 4.
+    /// - Panics if `fragment_size` is not `[1, 1]` and the `pipeline_fragment_shading_rate`
+    ///   feature is not enabled on the device.
+    /// - Panics if `combiner_ops[0]` is not [`FragmentShadingRateCombinerOp::Keep`] and the
+    ///   `primitive_fragment_shading_rate` feature is not enabled on the device.
+    /// - Panics if `combiner_ops[1]` is not [`FragmentShadingRateCombinerOp::Keep`] and the
+    ///   `attachment_fragment_shading_rate` feature is not enabled on the device.
+    /// - Panics if an element of `combiner_ops` is not `Keep` or `Replace` and the
+    ///   [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    ///   device property is not `true`.
+    #[inline]
+    pub fn set_fragment_shading_rate(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        self.validate_set_fragment_shading_rate(fragment_size, combiner_ops)
+            .unwrap();
+
+        unsafe { self.set_fragment_shading_rate_unchecked(fragment_size, combiner_ops) }
+    }
+
+    fn validate_set_fragment_shading_rate(
+        &self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::FragmentShadingRate)?;
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-combinerOps-parameter
+        for combiner_op in combiner_ops {
+            combiner_op.validate_device(self.device())?;
+        }
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        let device = self.device();
+        let features = device.enabled_features();
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04509
+        if !(features.pipeline_fragment_shading_rate
+            || features.primitive_fragment_shading_rate
+            || features.attachment_fragment_shading_rate)
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_fragment_shading_rate`",
+                requires_one_of: RequiresOneOf {
+                    features: &[
+                        "pipeline_fragment_shading_rate",
+                        "primitive_fragment_shading_rate",
+                        "attachment_fragment_shading_rate",
+                    ],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04513
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04514
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04515
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04516
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04517
+        // VUID-vkCmdSetFragmentShadingRateKHR-pFragmentSize-04518
+        if !fragment_size
+            .iter()
+            .all(|&size| size.is_power_of_two() && size <= 4)
+        {
+            return Err(
+                SetDynamicStateError::FragmentShadingRateFragmentSizeInvalid {
+                    provided: fragment_size,
+                },
+            );
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04507
+        // VUID-vkCmdSetFragmentShadingRateKHR-pipelineFragmentShadingRate-04508
+        if fragment_size != [1, 1] && !features.pipeline_fragment_shading_rate {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`fragment_size` is not `[1, 1]`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pipeline_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-primitiveFragmentShadingRate-04510
+        if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+            && !features.primitive_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`combiner_ops[0]` is not `FragmentShadingRateCombinerOp::Keep`",
+                requires_one_of: RequiresOneOf {
+                    features: &["primitive_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-attachmentFragmentShadingRate-04511
+        if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+            && !features.attachment_fragment_shading_rate
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`combiner_ops[1]` is not `FragmentShadingRateCombinerOp::Keep`",
+                requires_one_of: RequiresOneOf {
+                    features: &["attachment_fragment_shading_rate"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetFragmentShadingRateKHR-fragmentSizeNonTrivialCombinerOps-04512
+        if !device
+            .physical_device()
+            .properties()
+            .fragment_shading_rate_non_trivial_combiner_ops
+            .unwrap_or(false)
+            && combiner_ops.iter().any(|combiner_op| {
+                !matches!(
+                    combiner_op,
+                    FragmentShadingRateCombinerOp::Keep | FragmentShadingRateCombinerOp::Replace
+                )
+            })
+        {
+            return Err(SetDynamicStateError::FragmentShadingRateNonTrivialCombinerOpsNotSupported);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_fragment_shading_rate_unchecked(
+        &mut self,
+        fragment_size: [u32; 2],
+        combiner_ops: [FragmentShadingRateCombinerOp; 2],
+    ) -> &mut Self {
+        let fragment_size_vk = ash::vk::Extent2D {
+            width: fragment_size[0],
+            height: fragment_size[1],
+        };
+        let combiner_ops_vk: [ash::vk::FragmentShadingRateCombinerOpKHR; 2] =
+            combiner_ops.map(Into::into);
+
+        let fns = self.device().fns();
+        (fns.khr_fragment_shading_rate
+            .cmd_set_fragment_shading_rate_khr)(
+            self.handle(), &fragment_size_vk, &combiner_ops_vk
+        );
+
+        self.current_state.fragment_shading_rate = Some(FragmentShadingRate {
+            fragment_size,
+            combiner_ops,
+        });
+
+        self
+    }
+
     /// Sets the dynamic front face for future draw calls.
     ///
     /// # Panics
@@ -1218,6 +1707,97 @@ where
         self
     }
 
+    /// Sets the dynamic polygon mode for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`extended_dynamic_state3_polygon_mode`](crate::device::Features::extended_dynamic_state3_polygon_mode)
+    ///   feature is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - If the [`fill_mode_non_solid`](crate::device::Features::fill_mode_non_solid) feature is
+    ///   not enabled on the device, panics if `polygon_mode` is not `PolygonMode::Fill`.
+    #[inline]
+    pub fn set_polygon_mode(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        self.validate_set_polygon_mode(polygon_mode).unwrap();
+
+        unsafe { self.set_polygon_mode_unchecked(polygon_mode) }
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::PolygonMode)?;
+
+        // VUID-vkCmdSetPolygonModeEXT-polygonMode-parameter
+        polygon_mode.validate_device(self.device())?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetPolygonModeEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        // VUID-vkCmdSetPolygonModeEXT-extendedDynamicState3PolygonMode-07422
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_polygon_mode
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_polygon_mode`",
+                requires_one_of: RequiresOneOf {
+                    features: &["extended_dynamic_state3_polygon_mode"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkCmdSetPolygonModeEXT-fillModeNonSolid-07424
+        if polygon_mode != PolygonMode::Fill
+            && !self.device().enabled_features().fill_mode_non_solid
+        {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`polygon_mode` is not `PolygonMode::Fill`",
+                requires_one_of: RequiresOneOf {
+                    features: &["fill_mode_non_solid"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        debug_assert!(
+            self.device()
+                .enabled_extensions()
+                .ext_extended_dynamic_state3
+        );
+        debug_assert!(
+            self.device()
+                .enabled_features()
+                .extended_dynamic_state3_polygon_mode
+        );
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_polygon_mode_ext)(
+            self.handle(),
+            polygon_mode.into(),
+        );
+
+        self.current_state.polygon_mode = Some(polygon_mode);
+
+        self
+    }
+
     /// Sets whether dynamic primitive restart is enabled for future draw calls.
     ///
     /// # Panics
@@ -1484,6 +2064,133 @@ where
         self
     }
 
+    /// Sets the dynamic sample locations for future draw calls.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the queue family of the command buffer does not support graphics operations.
+    /// - Panics if the
+    ///   [`ext_sample_locations`](crate::device::DeviceExtensions::ext_sample_locations)
+    ///   extension is not enabled on the device.
+    /// - Panics if the currently bound graphics pipeline already contains this state internally.
+    /// - Panics if `sample_locations.sample_locations_per_pixel` is not included in the
+    ///   [`sample_location_sample_counts`](crate::device::Properties::sample_location_sample_counts)
+    ///   device property.
+    /// - Panics if the number of elements in `sample_locations.sample_locations` does not match
+    ///   the sample count and grid size.
+    #[inline]
+    pub fn set_sample_locations(&mut self, sample_locations: SampleLocationsInfo) -> &mut Self {
+        self.validate_set_sample_locations(&sample_locations)
+            .unwrap();
+
+        unsafe { self.set_sample_locations_unchecked(sample_locations) }
+    }
+
+    fn validate_set_sample_locations(
+        &self,
+        sample_locations: &SampleLocationsInfo,
+    ) -> Result<(), SetDynamicStateError> {
+        self.validate_pipeline_fixed_state(DynamicState::SampleLocations)?;
+
+        let queue_family_properties = self.queue_family_properties();
+
+        // VUID-vkCmdSetSampleLocationsEXT-commandBuffer-cmdpool
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(SetDynamicStateError::NotSupportedByQueueFamily);
+        }
+
+        if !self.device().enabled_extensions().ext_sample_locations {
+            return Err(SetDynamicStateError::RequirementNotMet {
+                required_for: "`CommandBufferBuilder::set_sample_locations`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_sample_locations"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let &SampleLocationsInfo {
+            sample_locations_per_pixel,
+            sample_location_grid_size,
+            ref sample_locations,
+        } = sample_locations;
+
+        // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-parameter
+        sample_locations_per_pixel.validate_device(self.device())?;
+
+        // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-01526
+        if !self
+            .device()
+            .physical_device()
+            .properties()
+            .sample_location_sample_counts
+            .unwrap_or_default()
+            .contains_enum(sample_locations_per_pixel)
+        {
+            return Err(SetDynamicStateError::SampleLocationsPerPixelNotSupported {
+                provided: sample_locations_per_pixel,
+            });
+        }
+
+        // VUID-VkSampleLocationsInfoEXT-sampleLocationsCount-01527
+        let required = sample_locations_per_pixel as u32
+            * sample_location_grid_size[0]
+            * sample_location_grid_size[1];
+
+        if sample_locations.len() as u32 != required {
+            return Err(SetDynamicStateError::SampleLocationsCountMismatch {
+                provided: sample_locations.len() as u32,
+                required,
+            });
+        }
+
+        // TODO:
+        // VUID-vkCmdSetSampleLocationsEXT-variableSampleLocations-01530
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_sample_locations_unchecked(
+        &mut self,
+        sample_locations: SampleLocationsInfo,
+    ) -> &mut Self {
+        let SampleLocationsInfo {
+            sample_locations_per_pixel,
+            sample_location_grid_size,
+            sample_locations: ref sample_locations_vec,
+        } = sample_locations;
+
+        let sample_locations_vk: SmallVec<[_; 4]> = sample_locations_vec
+            .iter()
+            .map(|&[x, y]| ash::vk::SampleLocationEXT { x, y })
+            .collect();
+
+        let sample_locations_info = ash::vk::SampleLocationsInfoEXT {
+            sample_locations_per_pixel: sample_locations_per_pixel.into(),
+            sample_location_grid_size: ash::vk::Extent2D {
+                width: sample_location_grid_size[0],
+                height: sample_location_grid_size[1],
+            },
+            sample_locations_count: sample_locations_vk.len() as u32,
+            p_sample_locations: sample_locations_vk.as_ptr(),
+            ..Default::default()
+        };
+
+        let fns = self.device().fns();
+        (fns.ext_sample_locations.cmd_set_sample_locations_ext)(
+            self.handle(),
+            &sample_locations_info,
+        );
+
+        self.current_state.sample_locations = Some(sample_locations);
+
+        self
+    }
+
     /// Sets the dynamic scissors for future draw calls.
     ///
     /// # Panics
@@ -35,29 +35,31 @@ use crate::{
     descriptor_set::{DescriptorSetResources, DescriptorSetWithOffsets},
     device::{Device, DeviceOwned, QueueFamilyProperties},
     format::{Format, FormatFeatures},
-    image::ImageAspects,
+    image::{ImageAspects, ImageViewAbstract},
     pipeline::{
         graphics::{
             color_blend::LogicOp,
             depth_stencil::{CompareOp, StencilOps},
+            fragment_shading_rate::FragmentShadingRate,
             input_assembly::{IndexType, PrimitiveTopology},
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            multisample::SampleLocationsInfo,
+            rasterization::{CullMode, DepthBias, FrontFace, LineStipple, PolygonMode},
             viewport::{Scissor, Viewport},
         },
         ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
     },
     query::{QueryControlFlags, QueryType},
     range_set::RangeSet,
-    render_pass::{Framebuffer, Subpass},
+    render_pass::{Framebuffer, FramebufferCreateFlags, Subpass},
     OomError, RequiresOneOf, VulkanError, VulkanObject,
 };
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLockReadGuard};
 use smallvec::SmallVec;
 use std::{
     any::Any,
     collections::{hash_map::Entry, HashMap},
     marker::PhantomData,
-    ops::RangeInclusive,
+    ops::{Deref, RangeInclusive},
     ptr,
     sync::{atomic::AtomicBool, Arc},
 };
@@ -619,6 +621,11 @@ where
             return Err(BuildError::QueryActive);
         }
 
+        // VUID-vkEndCommandBuffer-commandBuffer-01815
+        if self.current_state.debug_label_depth != 0 {
+            return Err(BuildError::DebugLabelActive);
+        }
+
         Ok(unsafe { self.build_unchecked()? })
     }
 
@@ -667,6 +674,8 @@ struct CurrentState {
     push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
 
     // Dynamic state
+    alpha_to_coverage_enable: Option<bool>,
+    alpha_to_one_enable: Option<bool>,
     blend_constants: Option<[f32; 4]>,
     color_write_enable: Option<SmallVec<[bool; 4]>>,
     cull_mode: Option<CullMode>,
@@ -674,18 +683,22 @@ struct CurrentState {
     depth_bias_enable: Option<bool>,
     depth_bounds: Option<RangeInclusive<f32>>,
     depth_bounds_test_enable: Option<bool>,
+    depth_clamp_enable: Option<bool>,
     depth_compare_op: Option<CompareOp>,
     depth_test_enable: Option<bool>,
     depth_write_enable: Option<bool>,
     discard_rectangle: HashMap<u32, Scissor>,
+    fragment_shading_rate: Option<FragmentShadingRate>,
     front_face: Option<FrontFace>,
     line_stipple: Option<LineStipple>,
     line_width: Option<f32>,
     logic_op: Option<LogicOp>,
     patch_control_points: Option<u32>,
+    polygon_mode: Option<PolygonMode>,
     primitive_restart_enable: Option<bool>,
     primitive_topology: Option<PrimitiveTopology>,
     rasterizer_discard_enable: Option<bool>,
+    sample_locations: Option<SampleLocationsInfo>,
     scissor: HashMap<u32, Scissor>,
     scissor_with_count: Option<SmallVec<[Scissor; 2]>>,
     stencil_compare_mask: StencilStateDynamic,
@@ -698,12 +711,17 @@ struct CurrentState {
 
     // Active queries
     queries: HashMap<ash::vk::QueryType, QueryState>,
+
+    // Debug label regions begun in this command buffer and not yet ended
+    debug_label_depth: u32,
 }
 
 impl CurrentState {
     fn reset_dynamic_states(&mut self, states: impl IntoIterator<Item = DynamicState>) {
         for state in states {
             match state {
+                DynamicState::AlphaToCoverageEnable => self.alpha_to_coverage_enable = None,
+                DynamicState::AlphaToOneEnable => self.alpha_to_one_enable = None,
                 DynamicState::BlendConstants => self.blend_constants = None,
                 DynamicState::ColorWriteEnable => self.color_write_enable = None,
                 DynamicState::CullMode => self.cull_mode = None,
@@ -711,22 +729,24 @@ impl CurrentState {
                 DynamicState::DepthBiasEnable => self.depth_bias_enable = None,
                 DynamicState::DepthBounds => self.depth_bounds = None,
                 DynamicState::DepthBoundsTestEnable => self.depth_bounds_test_enable = None,
+                DynamicState::DepthClampEnable => self.depth_clamp_enable = None,
                 DynamicState::DepthCompareOp => self.depth_compare_op = None,
                 DynamicState::DepthTestEnable => self.depth_test_enable = None,
                 DynamicState::DepthWriteEnable => self.depth_write_enable = None,
                 DynamicState::DiscardRectangle => self.discard_rectangle.clear(),
                 DynamicState::ExclusiveScissor => (), // TODO;
-                DynamicState::FragmentShadingRate => (), // TODO:
+                DynamicState::FragmentShadingRate => self.fragment_shading_rate = None,
                 DynamicState::FrontFace => self.front_face = None,
                 DynamicState::LineStipple => self.line_stipple = None,
                 DynamicState::LineWidth => self.line_width = None,
                 DynamicState::LogicOp => self.logic_op = None,
                 DynamicState::PatchControlPoints => self.patch_control_points = None,
+                DynamicState::PolygonMode => self.polygon_mode = None,
                 DynamicState::PrimitiveRestartEnable => self.primitive_restart_enable = None,
                 DynamicState::PrimitiveTopology => self.primitive_topology = None,
                 DynamicState::RasterizerDiscardEnable => self.rasterizer_discard_enable = None,
                 DynamicState::RayTracingPipelineStackSize => (), // TODO:
-                DynamicState::SampleLocations => (),             // TODO:
+                DynamicState::SampleLocations => self.sample_locations = None,
                 DynamicState::Scissor => self.scissor.clear(),
                 DynamicState::ScissorWithCount => self.scissor_with_count = None,
                 DynamicState::StencilCompareMask => self.stencil_compare_mask = Default::default(),
@@ -742,12 +762,8 @@ impl CurrentState {
                 DynamicState::ViewportWScaling => (),          // TODO:
                 DynamicState::ViewportWithCount => self.viewport_with_count = None,
                 DynamicState::TessellationDomainOrigin => (), // TODO:
-                DynamicState::DepthClampEnable => (),         // TODO:
-                DynamicState::PolygonMode => (),              // TODO:
                 DynamicState::RasterizationSamples => (),     // TODO:
                 DynamicState::SampleMask => (),               // TODO:
-                DynamicState::AlphaToCoverageEnable => (),    // TODO:
-                DynamicState::AlphaToOneEnable => (),         // TODO:
                 DynamicState::LogicOpEnable => (),            // TODO:
                 DynamicState::ColorBlendEnable => (),         // TODO:
                 DynamicState::ColorBlendEquation => (),       // TODO:
@@ -862,6 +878,15 @@ impl RenderPassState {
                     render_pass: BeginRenderPassState {
                         subpass: info.subpass.clone(),
                         framebuffer: info.framebuffer.clone(),
+                        attachments: info
+                            .framebuffer
+                            .as_ref()
+                            .filter(|framebuffer| {
+                                !framebuffer
+                                    .flags()
+                                    .intersects(FramebufferCreateFlags::IMAGELESS)
+                            })
+                            .map(|framebuffer| framebuffer.attachments().to_vec()),
                     }
                     .into(),
                     view_mask: info.subpass.subpass_desc().view_mask,
@@ -907,6 +932,8 @@ impl From<BeginRenderingState> for RenderPassStateType {
 struct BeginRenderPassState {
     subpass: Subpass,
     framebuffer: Option<Arc<Framebuffer>>,
+    // The image views that are attached, if they are known.
+    attachments: Option<Vec<Arc<dyn ImageViewAbstract>>>,
 }
 
 struct BeginRenderingState {
@@ -935,9 +962,25 @@ enum SetOrPush {
 }
 
 impl SetOrPush {
-    pub fn resources(&self) -> &DescriptorSetResources {
+    pub fn resources(&self) -> SetOrPushResources<'_> {
+        match self {
+            Self::Set(set) => SetOrPushResources::Set(set.as_ref().0.resources()),
+            Self::Push(resources) => SetOrPushResources::Push(resources),
+        }
+    }
+}
+
+enum SetOrPushResources<'a> {
+    Set(RwLockReadGuard<'a, DescriptorSetResources>),
+    Push(&'a DescriptorSetResources),
+}
+
+impl Deref for SetOrPushResources<'_> {
+    type Target = DescriptorSetResources;
+
+    fn deref(&self) -> &Self::Target {
         match self {
-            Self::Set(set) => set.as_ref().0.resources(),
+            Self::Set(resources) => resources,
             Self::Push(resources) => resources,
         }
     }
@@ -731,6 +731,7 @@ where
             set_num: u32,
             binding_num: u32,
             binding_reqs: &DescriptorBindingRequirements,
+            partially_bound: bool,
             elements: &[Option<T>],
             mut extra_check: impl FnMut(u32, &T) -> Result<(), DescriptorResourceInvalidError>,
         ) -> Result<(), PipelineExecutionError> {
@@ -758,6 +759,9 @@ where
                 // VUID-vkCmdDispatch-None-02699
                 let element = match element {
                     Some(x) => x,
+                    // With a partially bound binding, only descriptors that are dynamically used
+                    // must be valid, which can't be known here.
+                    None if partially_bound => continue,
                     None => {
                         return Err(PipelineExecutionError::DescriptorResourceInvalid {
                             set_num,
@@ -1031,16 +1035,19 @@ where
                     // If the image view isn't actually present in the resources, then just skip it.
                     // It will be caught later by check_resources.
                     let iter = desc_reqs.sampler_with_images.iter().filter_map(|id| {
-                        descriptor_set_state
+                        let set_resources = descriptor_set_state
                             .descriptor_sets
-                            .get(&id.set)
-                            .and_then(|set| set.resources().binding(id.binding))
-                            .and_then(|res| match res {
-                                DescriptorBindingResources::ImageView(elements) => elements
-                                    .get(id.index as usize)
-                                    .and_then(|opt| opt.as_ref().map(|opt| (id, opt))),
-                                _ => None,
-                            })
+                            .get(&id.set)?
+                            .resources();
+
+                        let image_view = match set_resources.binding(id.binding)? {
+                            DescriptorBindingResources::ImageView(elements) => {
+                                elements.get(id.index as usize)?.clone()
+                            }
+                            _ => None,
+                        };
+
+                        image_view.map(|image_view| (id, image_view))
                     });
 
                     for (id, image_view) in iter {
@@ -1078,16 +1085,31 @@ where
 
             match binding_resources {
                 DescriptorBindingResources::None(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_none)?;
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        check_none,
+                    )?;
                 }
                 DescriptorBindingResources::Buffer(elements) => {
-                    validate_resources(set_num, binding_num, binding_reqs, elements, check_buffer)?;
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        check_buffer,
+                    )?;
                 }
                 DescriptorBindingResources::BufferView(elements) => {
                     validate_resources(
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_buffer_view,
                     )?;
@@ -1097,6 +1119,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_image_view,
                     )?;
@@ -1106,6 +1129,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_image_view_sampler,
                     )?;
@@ -1115,10 +1139,21 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        layout_binding.partially_bound,
                         elements,
                         check_sampler,
                     )?;
                 }
+                DescriptorBindingResources::AccelerationStructure(elements) => {
+                    validate_resources(
+                        set_num,
+                        binding_num,
+                        binding_reqs,
+                        layout_binding.partially_bound,
+                        elements,
+                        |_, _| Ok(()),
+                    )?;
+                }
             }
         }
 
@@ -1177,6 +1212,18 @@ where
             .map(|(s, _)| s)
         {
             match dynamic_state {
+                DynamicState::AlphaToCoverageEnable => {
+                    // VUID-vkCmdDraw-None-07624
+                    if self.current_state.alpha_to_coverage_enable.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
+                DynamicState::AlphaToOneEnable => {
+                    // VUID-vkCmdDraw-None-07625
+                    if self.current_state.alpha_to_one_enable.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::BlendConstants => {
                     // VUID?
                     if self.current_state.blend_constants.is_none() {
@@ -1231,6 +1278,12 @@ where
                         return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                     }
                 }
+                DynamicState::DepthClampEnable => {
+                    // VUID-vkCmdDraw-None-07620
+                    if self.current_state.depth_clamp_enable.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::DepthCompareOp => {
                     // VUID?
                     if self.current_state.depth_compare_op.is_none() {
@@ -1266,7 +1319,12 @@ where
                     }
                 }
                 DynamicState::ExclusiveScissor => todo!(),
-                DynamicState::FragmentShadingRate => todo!(),
+                DynamicState::FragmentShadingRate => {
+                    // VUID?
+                    if self.current_state.fragment_shading_rate.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::FrontFace => {
                     // VUID?
                     if self.current_state.front_face.is_none() {
@@ -1297,6 +1355,12 @@ where
                         return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
                     }
                 }
+                DynamicState::PolygonMode => {
+                    // VUID-vkCmdDraw-None-07621
+                    if self.current_state.polygon_mode.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::PrimitiveRestartEnable => {
                     // VUID-vkCmdDraw-None-04879
                     let primitive_restart_enable =
@@ -1412,7 +1476,12 @@ where
                 DynamicState::RayTracingPipelineStackSize => unreachable!(
                     "RayTracingPipelineStackSize dynamic state should not occur on a graphics pipeline"
                 ),
-                DynamicState::SampleLocations => todo!(),
+                DynamicState::SampleLocations => {
+                    // VUID?
+                    if self.current_state.sample_locations.is_none() {
+                        return Err(PipelineExecutionError::DynamicStateNotSet { dynamic_state });
+                    }
+                }
                 DynamicState::Scissor => {
                     for num in 0..pipeline.viewport_state().unwrap().count().unwrap() {
                         // VUID?
@@ -1530,12 +1599,8 @@ where
                 }
                 DynamicState::ViewportWScaling => todo!(),
                 DynamicState::TessellationDomainOrigin => todo!(),
-                DynamicState::DepthClampEnable => todo!(),
-                DynamicState::PolygonMode => todo!(),
                 DynamicState::RasterizationSamples => todo!(),
                 DynamicState::SampleMask => todo!(),
-                DynamicState::AlphaToCoverageEnable => todo!(),
-                DynamicState::AlphaToOneEnable => todo!(),
                 DynamicState::LogicOpEnable => todo!(),
                 DynamicState::ColorBlendEnable => todo!(),
                 DynamicState::ColorBlendEquation => todo!(),
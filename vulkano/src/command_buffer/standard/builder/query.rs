@@ -29,9 +29,15 @@ where
     /// # Safety
     ///
     /// - The query must be unavailable, ensured by calling [`reset_query_pool`].
+    /// - For performance queries, the profiling lock must be held while the command buffer is
+    ///   recorded, see [`Device::acquire_profiling_lock`].
+    /// - For performance queries, the query must be begun and ended as required by the
+    ///   [scope] of its counters.
     ///
     /// [`end_query`]: Self::end_query
     /// [`reset_query_pool`]: Self::reset_query_pool
+    /// [`Device::acquire_profiling_lock`]: crate::device::Device::acquire_profiling_lock
+    /// [scope]: crate::query::PerformanceCounterScope
     #[inline]
     pub unsafe fn begin_query(
         &mut self,
@@ -118,6 +124,77 @@ where
             }
             // VUID-vkCmdBeginQuery-queryType-02804
             QueryType::Timestamp => return Err(QueryError::NotPermitted),
+            // VUID-vkCmdBeginQuery-queryType-04728
+            QueryType::AccelerationStructureCompactedSize => return Err(QueryError::NotPermitted),
+            QueryType::TransformFeedbackStream => {
+                // VUID-vkCmdBeginQuery-commandBuffer-cmdpool
+                // VUID-vkCmdBeginQuery-queryType-02327
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(QueryError::NotSupportedByQueueFamily);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-02328
+                if !device
+                    .physical_device()
+                    .properties()
+                    .transform_feedback_queries
+                    .unwrap_or(false)
+                {
+                    return Err(QueryError::TransformFeedbackQueriesNotSupported);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-00800
+                if flags.intersects(QueryControlFlags::PRECISE) {
+                    return Err(QueryError::InvalidFlags);
+                }
+            }
+            QueryType::PrimitivesGenerated => {
+                // VUID-vkCmdBeginQuery-commandBuffer-cmdpool
+                // VUID-vkCmdBeginQuery-queryType-06687
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(QueryError::NotSupportedByQueueFamily);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-06688
+                if !device.enabled_features().primitives_generated_query {
+                    return Err(QueryError::RequirementNotMet {
+                        required_for: "`query_pool.query_type()` is \
+                            `QueryType::PrimitivesGenerated`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["primitives_generated_query"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-00800
+                if flags.intersects(QueryControlFlags::PRECISE) {
+                    return Err(QueryError::InvalidFlags);
+                }
+            }
+            QueryType::Performance => {
+                // VUID-vkCmdBeginQuery-queryPool-03224
+                if query_pool.performance_queue_family_index() != self.queue_family_index {
+                    return Err(QueryError::PerformanceQueueFamilyMismatch);
+                }
+
+                // VUID-vkCmdBeginQuery-queryType-00800
+                if flags.intersects(QueryControlFlags::PRECISE) {
+                    return Err(QueryError::InvalidFlags);
+                }
+
+                // VUID-vkCmdBeginQuery-queryPool-03223
+                // VUID-vkCmdBeginQuery-queryPool-03225
+                // VUID-vkCmdBeginQuery-queryPool-03226
+                // VUID-vkCmdBeginQuery-queryPool-03227
+                // Ensured by the safety requirements.
+            }
         }
 
         // VUID-vkCmdBeginQuery-queryPool-01922
@@ -587,6 +664,13 @@ where
             .queries_range(queries.clone())
             .ok_or(QueryError::OutOfRange)?;
 
+        // VUID-vkCmdCopyQueryPoolResults-queryType-03232
+        // VUID-vkCmdCopyQueryPoolResults-queryType-03233
+        // Performance query results are only retrieved on the host.
+        if let QueryType::Performance = query_pool.query_type() {
+            return Err(QueryError::NotPermitted);
+        }
+
         let count = queries.end - queries.start;
         let per_query_len = query_pool.query_type().result_len()
             + flags.intersects(QueryResultFlags::WITH_AVAILABILITY) as DeviceSize;
@@ -661,6 +745,9 @@ where
     /// The affected queries will be marked as "unavailable" after this command runs, and will no
     /// longer return any results. They will be ready to have new results recorded for them.
     ///
+    /// Queries can also be reset from the host, with
+    /// [`QueriesRange::reset`](crate::query::QueriesRange::reset).
+    ///
     /// # Safety
     /// The queries in the specified range must not be active in another command buffer.
     // TODO: Do other command buffers actually matter here? Not sure on the Vulkan spec.
@@ -17,7 +17,11 @@ use crate::{
     device::{DeviceOwned, QueueFlags},
     format::{ClearColorValue, ClearValue, NumericType},
     image::{ImageAspects, ImageLayout, ImageUsage, SampleCount},
-    render_pass::{AttachmentDescription, LoadOp, ResolveMode, SubpassDescription},
+    pipeline::graphics::multisample::SampleLocationsInfo,
+    render_pass::{
+        AttachmentDescription, FramebufferAttachmentImageInfo, FramebufferCreateFlags, LoadOp,
+        ResolveMode, SubpassDescription,
+    },
     RequiresOneOf, Version, VulkanObject,
 };
 use smallvec::SmallVec;
@@ -78,9 +82,12 @@ where
         let RenderPassBeginInfo {
             render_pass,
             framebuffer,
+            attachments,
             render_area_offset,
             render_area_extent,
             clear_values,
+            attachment_initial_sample_locations,
+            post_subpass_sample_locations,
             _ne: _,
         } = render_pass_begin_info;
 
@@ -101,10 +108,83 @@ where
             }
         }
 
+        let attachment_image_views = if framebuffer
+            .flags()
+            .intersects(FramebufferCreateFlags::IMAGELESS)
+        {
+            // VUID-VkRenderPassBeginInfo-framebuffer-03208
+            if attachments.len() != framebuffer.attachment_image_infos().len() {
+                return Err(RenderPassError::AttachmentCountMismatch {
+                    provided: attachments.len() as u32,
+                    required: framebuffer.attachment_image_infos().len() as u32,
+                });
+            }
+
+            for (attachment_index, ((image_view, image_info), attachment_desc)) in attachments
+                .iter()
+                .zip(framebuffer.attachment_image_infos())
+                .zip(render_pass.attachments())
+                .enumerate()
+            {
+                let attachment_index = attachment_index as u32;
+                let &FramebufferAttachmentImageInfo {
+                    flags,
+                    usage,
+                    extent,
+                    array_layers,
+                    ref view_formats,
+                    _ne: _,
+                } = image_info;
+
+                // VUID-VkRenderPassBeginInfo-framebuffer-02780
+                assert_eq!(device, image_view.device());
+
+                let subresource_range = image_view.subresource_range();
+
+                // VUID-VkRenderPassBeginInfo-framebuffer-03209
+                // VUID-VkRenderPassBeginInfo-framebuffer-04627
+                // VUID-VkRenderPassBeginInfo-framebuffer-03211
+                // VUID-VkRenderPassBeginInfo-framebuffer-03212
+                // VUID-VkRenderPassBeginInfo-framebuffer-03213
+                // VUID-VkRenderPassBeginInfo-framebuffer-03218
+                if image_view.image().inner().image.flags() != flags
+                    || image_view.usage() != usage
+                    || image_view.image().dimensions().width_height() != extent
+                    || subresource_range.array_layers.end - subresource_range.array_layers.start
+                        != array_layers
+                    || !image_view
+                        .format()
+                        .map_or(false, |format| view_formats.contains(&format))
+                {
+                    return Err(RenderPassError::AttachmentImageNotCompatible { attachment_index });
+                }
+
+                // VUID-VkRenderPassBeginInfo-framebuffer-03216
+                // VUID-VkRenderPassBeginInfo-framebuffer-09047
+                if image_view.format() != attachment_desc.format
+                    || image_view.image().samples() != attachment_desc.samples
+                {
+                    return Err(RenderPassError::AttachmentImageNotCompatible { attachment_index });
+                }
+            }
+
+            attachments.as_slice()
+        } else {
+            // VUID-VkRenderPassBeginInfo-framebuffer-03207
+            if !attachments.is_empty() {
+                return Err(RenderPassError::AttachmentCountMismatch {
+                    provided: attachments.len() as u32,
+                    required: 0,
+                });
+            }
+
+            framebuffer.attachments()
+        };
+
         for (attachment_index, (attachment_desc, image_view)) in render_pass
             .attachments()
             .iter()
-            .zip(framebuffer.attachments())
+            .zip(attachment_image_views)
             .enumerate()
         {
             let attachment_index = attachment_index as u32;
@@ -182,17 +262,21 @@ where
                 color_attachments,
                 resolve_attachments,
                 depth_stencil_attachment,
+                depth_stencil_resolve_attachment,
+                depth_resolve_mode: _,
+                stencil_resolve_mode: _,
                 preserve_attachments: _,
+                fragment_shading_rate_attachment: _,
                 _ne: _,
             } = subpass_desc;
 
             for atch_ref in (input_attachments.iter())
                 .chain(color_attachments)
                 .chain(resolve_attachments)
-                .chain([depth_stencil_attachment])
+                .chain([depth_stencil_attachment, depth_stencil_resolve_attachment])
                 .flatten()
             {
-                let image_view = &framebuffer.attachments()[atch_ref.attachment as usize];
+                let image_view = &attachment_image_views[atch_ref.attachment as usize];
 
                 match atch_ref.layout {
                     ImageLayout::ColorAttachmentOptimal => {
@@ -353,6 +437,77 @@ where
             }
         }
 
+        if !(attachment_initial_sample_locations.is_empty()
+            && post_subpass_sample_locations.is_empty())
+            && !device.enabled_extensions().ext_sample_locations
+        {
+            return Err(RenderPassError::RequirementNotMet {
+                required_for: "`render_pass_begin_info.attachment_initial_sample_locations` or \
+                    `render_pass_begin_info.post_subpass_sample_locations` are not empty",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_sample_locations"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        for &(attachment_index, _) in attachment_initial_sample_locations.iter() {
+            // VUID-VkAttachmentSampleLocationsEXT-attachmentIndex-01531
+            if attachment_index as usize >= render_pass.attachments().len() {
+                return Err(RenderPassError::SampleLocationsAttachmentIndexOutOfRange {
+                    attachment_index,
+                });
+            }
+        }
+
+        for &(subpass_index, _) in post_subpass_sample_locations.iter() {
+            // VUID-VkSubpassSampleLocationsEXT-subpassIndex-01532
+            if subpass_index as usize >= render_pass.subpasses().len() {
+                return Err(RenderPassError::SampleLocationsSubpassIndexOutOfRange {
+                    subpass_index,
+                });
+            }
+        }
+
+        for (_, sample_locations) in attachment_initial_sample_locations
+            .iter()
+            .chain(post_subpass_sample_locations.iter())
+        {
+            let &SampleLocationsInfo {
+                sample_locations_per_pixel,
+                sample_location_grid_size,
+                ref sample_locations,
+            } = sample_locations;
+
+            // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-parameter
+            sample_locations_per_pixel.validate_device(device)?;
+
+            // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-01526
+            if !device
+                .physical_device()
+                .properties()
+                .sample_location_sample_counts
+                .unwrap_or_default()
+                .contains_enum(sample_locations_per_pixel)
+            {
+                return Err(RenderPassError::SampleLocationsPerPixelNotSupported {
+                    provided: sample_locations_per_pixel,
+                });
+            }
+
+            // VUID-VkSampleLocationsInfoEXT-sampleLocationsCount-01527
+            let required = sample_locations_per_pixel as u32
+                * sample_location_grid_size[0]
+                * sample_location_grid_size[1];
+
+            if sample_locations.len() as u32 != required {
+                return Err(RenderPassError::SampleLocationsCountMismatch {
+                    provided: sample_locations.len() as u32,
+                    required,
+                });
+            }
+        }
+
         // VUID-vkCmdBeginRenderPass2-initialLayout-03100
         // TODO:
 
@@ -377,12 +532,16 @@ where
         render_pass_begin_info: RenderPassBeginInfo,
         contents: SubpassContents,
     ) -> &mut Self {
+        let attachment_image_views = render_pass_begin_info.attachment_image_views().to_vec();
         let RenderPassBeginInfo {
             render_pass,
             framebuffer,
+            attachments,
             render_area_offset,
             render_area_extent,
             clear_values,
+            attachment_initial_sample_locations,
+            post_subpass_sample_locations,
             _ne: _,
         } = render_pass_begin_info;
 
@@ -392,7 +551,12 @@ where
             .map(|clear_value| clear_value.map(Into::into).unwrap_or_default())
             .collect();
 
-        let render_pass_begin_info = ash::vk::RenderPassBeginInfo {
+        let attachments_vk: SmallVec<[_; 4]> = attachments
+            .iter()
+            .map(|image_view| image_view.handle())
+            .collect();
+
+        let mut render_pass_begin_info = ash::vk::RenderPassBeginInfo {
             render_pass: render_pass.handle(),
             framebuffer: framebuffer.handle(),
             render_area: ash::vk::Rect2D {
@@ -410,6 +574,92 @@ where
             ..Default::default()
         };
 
+        let mut attachment_begin_info_vk = None;
+
+        if framebuffer
+            .flags()
+            .intersects(FramebufferCreateFlags::IMAGELESS)
+        {
+            let next = attachment_begin_info_vk.insert(ash::vk::RenderPassAttachmentBeginInfo {
+                attachment_count: attachments_vk.len() as u32,
+                p_attachments: attachments_vk.as_ptr(),
+                ..Default::default()
+            });
+
+            render_pass_begin_info.p_next = next as *const _ as *const _;
+        }
+
+        let sample_locations_vk: SmallVec<[_; 8]> = attachment_initial_sample_locations
+            .iter()
+            .chain(post_subpass_sample_locations.iter())
+            .flat_map(|(_, sample_locations)| &sample_locations.sample_locations)
+            .map(|&[x, y]| ash::vk::SampleLocationEXT { x, y })
+            .collect();
+        let mut sample_locations_offset = 0;
+        let mut sample_locations_info_vk = |sample_locations: &SampleLocationsInfo| {
+            let &SampleLocationsInfo {
+                sample_locations_per_pixel,
+                sample_location_grid_size,
+                ref sample_locations,
+            } = sample_locations;
+
+            let sample_locations_info = ash::vk::SampleLocationsInfoEXT {
+                sample_locations_per_pixel: sample_locations_per_pixel.into(),
+                sample_location_grid_size: ash::vk::Extent2D {
+                    width: sample_location_grid_size[0],
+                    height: sample_location_grid_size[1],
+                },
+                sample_locations_count: sample_locations.len() as u32,
+                p_sample_locations: sample_locations_vk[sample_locations_offset..].as_ptr(),
+                ..Default::default()
+            };
+            sample_locations_offset += sample_locations.len();
+
+            sample_locations_info
+        };
+
+        let attachment_initial_sample_locations_vk: SmallVec<[_; 4]> =
+            attachment_initial_sample_locations
+                .iter()
+                .map(
+                    |(attachment_index, sample_locations)| ash::vk::AttachmentSampleLocationsEXT {
+                        attachment_index: *attachment_index,
+                        sample_locations_info: sample_locations_info_vk(sample_locations),
+                    },
+                )
+                .collect();
+        let post_subpass_sample_locations_vk: SmallVec<[_; 4]> = post_subpass_sample_locations
+            .iter()
+            .map(
+                |(subpass_index, sample_locations)| ash::vk::SubpassSampleLocationsEXT {
+                    subpass_index: *subpass_index,
+                    sample_locations_info: sample_locations_info_vk(sample_locations),
+                },
+            )
+            .collect();
+
+        let mut sample_locations_begin_info_vk = None;
+
+        if !(attachment_initial_sample_locations_vk.is_empty()
+            && post_subpass_sample_locations_vk.is_empty())
+        {
+            let next = sample_locations_begin_info_vk.insert(
+                ash::vk::RenderPassSampleLocationsBeginInfoEXT {
+                    attachment_initial_sample_locations_count:
+                        attachment_initial_sample_locations_vk.len() as u32,
+                    p_attachment_initial_sample_locations: attachment_initial_sample_locations_vk
+                        .as_ptr(),
+                    post_subpass_sample_locations_count: post_subpass_sample_locations_vk.len()
+                        as u32,
+                    p_post_subpass_sample_locations: post_subpass_sample_locations_vk.as_ptr(),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = render_pass_begin_info.p_next;
+            render_pass_begin_info.p_next = next as *const _ as *const _;
+        }
+
         let subpass_begin_info = ash::vk::SubpassBeginInfo {
             contents: contents.into(),
             ..Default::default()
@@ -453,6 +703,7 @@ where
             render_pass: BeginRenderPassState {
                 subpass,
                 framebuffer: Some(framebuffer.clone()),
+                attachments: Some(attachment_image_views),
             }
             .into(),
             view_mask,
@@ -460,6 +711,7 @@ where
 
         self.resources.push(Box::new(render_pass));
         self.resources.push(Box::new(framebuffer));
+        self.resources.push(Box::new(attachments));
 
         // TODO: sync state update
 
@@ -1641,14 +1893,14 @@ where
                     }
 
                     let image_view = match &render_pass_state.render_pass {
-                        RenderPassStateType::BeginRenderPass(state) => (state.framebuffer.as_ref())
+                        RenderPassStateType::BeginRenderPass(state) => (state.attachments.as_ref())
                             .zip(
                                 state.subpass.subpass_desc().color_attachments
                                     [color_attachment as usize]
                                     .as_ref(),
                             )
-                            .map(|(framebuffer, atch_ref)| {
-                                &framebuffer.attachments()[atch_ref.attachment as usize]
+                            .map(|(attachments, atch_ref)| {
+                                &attachments[atch_ref.attachment as usize]
                             }),
                         RenderPassStateType::BeginRendering(state) => state
                             .attachments
@@ -1714,7 +1966,7 @@ where
                     }
 
                     let image_view = match &render_pass_state.render_pass {
-                        RenderPassStateType::BeginRenderPass(state) => (state.framebuffer.as_ref())
+                        RenderPassStateType::BeginRenderPass(state) => (state.attachments.as_ref())
                             .zip(
                                 state
                                     .subpass
@@ -1722,8 +1974,8 @@ where
                                     .depth_stencil_attachment
                                     .as_ref(),
                             )
-                            .map(|(framebuffer, atch_ref)| {
-                                &framebuffer.attachments()[atch_ref.attachment as usize]
+                            .map(|(attachments, atch_ref)| {
+                                &attachments[atch_ref.attachment as usize]
                             }),
                         RenderPassStateType::BeginRendering(state) => state
                             .attachments
@@ -312,7 +312,11 @@ where
                         );
                     }
                 }
-                QueryType::Timestamp => (),
+                QueryType::Timestamp
+                | QueryType::AccelerationStructureCompactedSize
+                | QueryType::TransformFeedbackStream
+                | QueryType::PrimitivesGenerated
+                | QueryType::Performance => (),
             }
         }
 
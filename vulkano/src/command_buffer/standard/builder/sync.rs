@@ -880,12 +880,11 @@ where
                             | ImageUsage::SAMPLED
                             | ImageUsage::INPUT_ATTACHMENT
                     }
-
+                     */
                     // VUID-VkImageMemoryBarrier2-oldLayout-02088
                     ImageLayout::FragmentShadingRateAttachmentOptimal => {
                         ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT
                     }
-                     */
                     _ => continue,
                 };
 
@@ -1030,8 +1029,8 @@ where
                 // TODO: How are you supposed to verify this in secondary command buffers,
                 // when there is no inherited framebuffer?
                 // The image is not known until you execute it in a primary command buffer.
-                if let Some(framebuffer) = &begin_render_pass_state.framebuffer {
-                    let attachment_index = (framebuffer.attachments().iter())
+                if let Some(attachments) = &begin_render_pass_state.attachments {
+                    let attachment_index = (attachments.iter())
                         .position(|attachment| attachment.image().inner().image == &barrier.image)
                         .ok_or(SynchronizationError::ImageMemoryBarrierNotInputAttachment {
                             barrier_index,
@@ -1228,12 +1227,12 @@ where
                         _ne: _,
                     } = barrier;
 
-                    src_stage_mask |= src_stages.into();
-                    dst_stage_mask |= dst_stages.into();
+                    src_stage_mask |= src_stages.into_legacy().into();
+                    dst_stage_mask |= dst_stages.into_legacy().into();
 
                     ash::vk::MemoryBarrier {
-                        src_access_mask: src_access.into(),
-                        dst_access_mask: dst_access.into(),
+                        src_access_mask: src_access.into_legacy().into(),
+                        dst_access_mask: dst_access.into_legacy().into(),
                         ..Default::default()
                     }
                 })
@@ -1253,8 +1252,8 @@ where
                         _ne: _,
                     } = barrier;
 
-                    src_stage_mask |= src_stages.into();
-                    dst_stage_mask |= dst_stages.into();
+                    src_stage_mask |= src_stages.into_legacy().into();
+                    dst_stage_mask |= dst_stages.into_legacy().into();
 
                     let (src_queue_family_index, dst_queue_family_index) =
                         queue_family_ownership_transfer.map_or(
@@ -1263,8 +1262,8 @@ where
                         );
 
                     ash::vk::BufferMemoryBarrier {
-                        src_access_mask: src_access.into(),
-                        dst_access_mask: dst_access.into(),
+                        src_access_mask: src_access.into_legacy().into(),
+                        dst_access_mask: dst_access.into_legacy().into(),
                         src_queue_family_index,
                         dst_queue_family_index,
                         buffer: buffer.handle(),
@@ -1291,8 +1290,8 @@ where
                         _ne: _,
                     } = barrier;
 
-                    src_stage_mask |= src_stages.into();
-                    dst_stage_mask |= dst_stages.into();
+                    src_stage_mask |= src_stages.into_legacy().into();
+                    dst_stage_mask |= dst_stages.into_legacy().into();
 
                     let (src_queue_family_index, dst_queue_family_index) =
                         queue_family_ownership_transfer.map_or(
@@ -1301,8 +1300,8 @@ where
                         );
 
                     ash::vk::ImageMemoryBarrier {
-                        src_access_mask: src_access.into(),
-                        dst_access_mask: dst_access.into(),
+                        src_access_mask: src_access.into_legacy().into(),
+                        dst_access_mask: dst_access.into_legacy().into(),
                         old_layout: old_layout.into(),
                         new_layout: new_layout.into(),
                         src_queue_family_index,
@@ -2226,12 +2225,11 @@ where
                             | ImageUsage::SAMPLED
                             | ImageUsage::INPUT_ATTACHMENT
                     }
-
+                     */
                     // VUID-VkImageMemoryBarrier2-oldLayout-02088
                     ImageLayout::FragmentShadingRateAttachmentOptimal => {
                         ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT
                     }
-                     */
                     _ => continue,
                 };
 
@@ -2484,15 +2482,15 @@ where
             let mut stage_mask = ash::vk::PipelineStageFlags::empty();
 
             for barrier in memory_barriers {
-                stage_mask |= barrier.src_stages.into();
+                stage_mask |= barrier.src_stages.into_legacy().into();
             }
 
             for barrier in buffer_memory_barriers {
-                stage_mask |= barrier.src_stages.into();
+                stage_mask |= barrier.src_stages.into_legacy().into();
             }
 
             for barrier in image_memory_barriers {
-                stage_mask |= barrier.src_stages.into();
+                stage_mask |= barrier.src_stages.into_legacy().into();
             }
 
             if stage_mask.is_empty() {
@@ -3381,12 +3379,11 @@ where
                                 | ImageUsage::SAMPLED
                                 | ImageUsage::INPUT_ATTACHMENT
                         }
-
+                         */
                         // VUID-VkImageMemoryBarrier2-oldLayout-02088
                         ImageLayout::FragmentShadingRateAttachmentOptimal => {
                             ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT
                         }
-                         */
                         _ => continue,
                     };
 
@@ -3711,12 +3708,12 @@ where
                             _ne: _,
                         } = barrier;
 
-                        src_stage_mask |= src_stages.into();
-                        dst_stage_mask |= dst_stages.into();
+                        src_stage_mask |= src_stages.into_legacy().into();
+                        dst_stage_mask |= dst_stages.into_legacy().into();
 
                         ash::vk::MemoryBarrier {
-                            src_access_mask: src_access.into(),
-                            dst_access_mask: dst_access.into(),
+                            src_access_mask: src_access.into_legacy().into(),
+                            dst_access_mask: dst_access.into_legacy().into(),
                             ..Default::default()
                         }
                     })
@@ -3736,8 +3733,8 @@ where
                             _ne: _,
                         } = barrier;
 
-                        src_stage_mask |= src_stages.into();
-                        dst_stage_mask |= dst_stages.into();
+                        src_stage_mask |= src_stages.into_legacy().into();
+                        dst_stage_mask |= dst_stages.into_legacy().into();
 
                         let (src_queue_family_index, dst_queue_family_index) =
                             queue_family_ownership_transfer.map_or(
@@ -3746,8 +3743,8 @@ where
                             );
 
                         ash::vk::BufferMemoryBarrier {
-                            src_access_mask: src_access.into(),
-                            dst_access_mask: dst_access.into(),
+                            src_access_mask: src_access.into_legacy().into(),
+                            dst_access_mask: dst_access.into_legacy().into(),
                             src_queue_family_index,
                             dst_queue_family_index,
                             buffer: buffer.handle(),
@@ -3774,8 +3771,8 @@ where
                             _ne: _,
                         } = barrier;
 
-                        src_stage_mask |= src_stages.into();
-                        dst_stage_mask |= dst_stages.into();
+                        src_stage_mask |= src_stages.into_legacy().into();
+                        dst_stage_mask |= dst_stages.into_legacy().into();
 
                         let (src_queue_family_index, dst_queue_family_index) =
                             queue_family_ownership_transfer.map_or(
@@ -3784,8 +3781,8 @@ where
                             );
 
                         ash::vk::ImageMemoryBarrier {
-                            src_access_mask: src_access.into(),
-                            dst_access_mask: dst_access.into(),
+                            src_access_mask: src_access.into_legacy().into(),
+                            dst_access_mask: dst_access.into_legacy().into(),
                             old_layout: old_layout.into(),
                             new_layout: new_layout.into(),
                             src_queue_family_index,
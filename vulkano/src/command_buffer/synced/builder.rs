@@ -17,6 +17,7 @@ pub use crate::command_buffer::commands::{
 use crate::{
     buffer::{sys::Buffer, BufferAccess},
     command_buffer::{
+        commands::diagnostics::automatic_checkpoint_marker,
         pool::CommandPoolAlloc,
         sys::{CommandBufferBeginInfo, UnsafeCommandBufferBuilder},
         CommandBufferBufferRangeUsage, CommandBufferBufferUsage, CommandBufferExecError,
@@ -31,11 +32,14 @@ use crate::{
         graphics::{
             color_blend::LogicOp,
             depth_stencil::{CompareOp, StencilOps},
+            fragment_shading_rate::FragmentShadingRate,
             input_assembly::{IndexType, PrimitiveTopology},
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            multisample::SampleLocationsInfo,
+            rasterization::{CullMode, DepthBias, FrontFace, LineStipple, PolygonMode},
             viewport::{Scissor, Viewport},
         },
         ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
+        RayTracingPipeline,
     },
     range_map::RangeMap,
     range_set::RangeSet,
@@ -46,12 +50,13 @@ use crate::{
     DeviceSize, OomError, VulkanObject,
 };
 use ahash::HashMap;
+use parking_lot::RwLockReadGuard;
 use smallvec::SmallVec;
 use std::{
     collections::hash_map::Entry,
     error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
-    ops::{Range, RangeInclusive},
+    ops::{Deref, Range, RangeInclusive},
     sync::Arc,
 };
 
@@ -100,6 +105,10 @@ pub struct SyncCommandBufferBuilder {
 
     // Current binding/setting state.
     pub(in crate::command_buffer) current_state: CurrentState,
+
+    // Ranges of commands that get an automatic checkpoint inserted before them. While automatic
+    // checkpoints are enabled, the last range ends at `usize::MAX`.
+    automatic_checkpoints: Vec<Range<usize>>,
 }
 
 impl SyncCommandBufferBuilder {
@@ -160,6 +169,7 @@ impl SyncCommandBufferBuilder {
             images2: HashMap::default(),
             secondary_resources_usage: Default::default(),
             current_state: Default::default(),
+            automatic_checkpoints: Vec::new(),
         }
     }
 
@@ -180,6 +190,28 @@ impl SyncCommandBufferBuilder {
         self.current_state = Default::default();
     }
 
+    /// Enables or disables inserting a checkpoint before every command that is added from now on.
+    ///
+    /// # Safety
+    ///
+    /// - The `nv_device_diagnostic_checkpoints` extension must be enabled on the device.
+    /// - The command pool that this command buffer was allocated from must support graphics,
+    ///   compute or transfer operations.
+    #[inline]
+    pub unsafe fn set_automatic_checkpoints(&mut self, enabled: bool) {
+        let command_index = self.commands.len();
+        let is_enabled = self
+            .automatic_checkpoints
+            .last()
+            .map_or(false, |range| range.end == usize::MAX);
+
+        match (is_enabled, enabled) {
+            (false, true) => self.automatic_checkpoints.push(command_index..usize::MAX),
+            (true, false) => self.automatic_checkpoints.last_mut().unwrap().end = command_index,
+            _ => (),
+        }
+    }
+
     pub(in crate::command_buffer) fn check_resource_conflicts(
         &self,
         resource: &(ResourceUseRef, Resource),
@@ -471,10 +503,13 @@ impl SyncCommandBufferBuilder {
                             self.pending_barrier.clear();
                             self.barriers.push(self.first_unflushed); // Track inserted barriers
 
-                            for command in
-                                &mut self.commands[self.first_unflushed..last_allowed_barrier_index]
-                            {
-                                command.send(&mut self.inner);
+                            for command_index in self.first_unflushed..last_allowed_barrier_index {
+                                Self::send_command(
+                                    &self.commands,
+                                    &self.automatic_checkpoints,
+                                    &mut self.inner,
+                                    command_index,
+                                );
                             }
 
                             self.first_unflushed = last_allowed_barrier_index;
@@ -688,10 +723,15 @@ impl SyncCommandBufferBuilder {
                                 self.pending_barrier.clear();
                                 self.barriers.push(self.first_unflushed); // Track inserted barriers
 
-                                for command in &mut self.commands
-                                    [self.first_unflushed..last_allowed_barrier_index]
+                                for command_index in
+                                    self.first_unflushed..last_allowed_barrier_index
                                 {
-                                    command.send(&mut self.inner);
+                                    Self::send_command(
+                                        &self.commands,
+                                        &self.automatic_checkpoints,
+                                        &mut self.inner,
+                                        command_index,
+                                    );
                                 }
                                 self.first_unflushed = last_allowed_barrier_index;
                             }
@@ -732,6 +772,28 @@ impl SyncCommandBufferBuilder {
         }
     }
 
+    /// Sends a command to the inner builder, preceded by an automatic checkpoint if enabled.
+    ///
+    /// This takes the fields it needs separately, so that it can be called while other fields of
+    /// the builder are borrowed.
+    unsafe fn send_command(
+        commands: &[Box<dyn Command>],
+        automatic_checkpoints: &[Range<usize>],
+        inner: &mut UnsafeCommandBufferBuilder,
+        command_index: usize,
+    ) {
+        let command = &commands[command_index];
+
+        if automatic_checkpoints
+            .iter()
+            .any(|range| range.contains(&command_index))
+        {
+            inner.set_checkpoint(automatic_checkpoint_marker(command.name()));
+        }
+
+        command.send(inner);
+    }
+
     /// Builds the command buffer and turns it into a `SyncCommandBuffer`.
     #[inline]
     pub fn build(mut self) -> Result<SyncCommandBuffer, OomError> {
@@ -744,8 +806,13 @@ impl SyncCommandBufferBuilder {
             let start = self.first_unflushed;
             self.barriers.push(start); // Track inserted barriers
 
-            for command in &mut self.commands[start..] {
-                command.send(&mut self.inner);
+            for command_index in start..self.commands.len() {
+                Self::send_command(
+                    &self.commands,
+                    &self.automatic_checkpoints,
+                    &mut self.inner,
+                    command_index,
+                );
             }
         }
 
@@ -942,15 +1009,20 @@ struct ImageState {
 /// Holds the current binding and setting state.
 #[derive(Default)]
 pub(in crate::command_buffer) struct CurrentState {
+    pub(in crate::command_buffer) descriptor_buffers: SmallVec<[Arc<dyn BufferAccess>; 3]>,
     pub(in crate::command_buffer) descriptor_sets: HashMap<PipelineBindPoint, DescriptorSetState>,
     pub(in crate::command_buffer) index_buffer: Option<(Arc<dyn BufferAccess>, IndexType)>,
     pub(in crate::command_buffer) pipeline_compute: Option<Arc<ComputePipeline>>,
     pub(in crate::command_buffer) pipeline_graphics: Option<Arc<GraphicsPipeline>>,
+    pub(in crate::command_buffer) pipeline_ray_tracing: Option<Arc<RayTracingPipeline>>,
+    pub(in crate::command_buffer) transform_feedback_buffers: HashMap<u32, Arc<dyn BufferAccess>>,
     pub(in crate::command_buffer) vertex_buffers: HashMap<u32, Arc<dyn BufferAccess>>,
 
     pub(in crate::command_buffer) push_constants: RangeSet<u32>,
     pub(in crate::command_buffer) push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
 
+    pub(in crate::command_buffer) alpha_to_coverage_enable: Option<bool>,
+    pub(in crate::command_buffer) alpha_to_one_enable: Option<bool>,
     pub(in crate::command_buffer) blend_constants: Option<[f32; 4]>,
     pub(in crate::command_buffer) color_write_enable: Option<SmallVec<[bool; 4]>>,
     pub(in crate::command_buffer) cull_mode: Option<CullMode>,
@@ -958,18 +1030,22 @@ pub(in crate::command_buffer) struct CurrentState {
     pub(in crate::command_buffer) depth_bias_enable: Option<bool>,
     pub(in crate::command_buffer) depth_bounds: Option<RangeInclusive<f32>>,
     pub(in crate::command_buffer) depth_bounds_test_enable: Option<bool>,
+    pub(in crate::command_buffer) depth_clamp_enable: Option<bool>,
     pub(in crate::command_buffer) depth_compare_op: Option<CompareOp>,
     pub(in crate::command_buffer) depth_test_enable: Option<bool>,
     pub(in crate::command_buffer) depth_write_enable: Option<bool>,
     pub(in crate::command_buffer) discard_rectangle: HashMap<u32, Scissor>,
+    pub(in crate::command_buffer) fragment_shading_rate: Option<FragmentShadingRate>,
     pub(in crate::command_buffer) front_face: Option<FrontFace>,
     pub(in crate::command_buffer) line_stipple: Option<LineStipple>,
     pub(in crate::command_buffer) line_width: Option<f32>,
     pub(in crate::command_buffer) logic_op: Option<LogicOp>,
     pub(in crate::command_buffer) patch_control_points: Option<u32>,
+    pub(in crate::command_buffer) polygon_mode: Option<PolygonMode>,
     pub(in crate::command_buffer) primitive_restart_enable: Option<bool>,
     pub(in crate::command_buffer) primitive_topology: Option<PrimitiveTopology>,
     pub(in crate::command_buffer) rasterizer_discard_enable: Option<bool>,
+    pub(in crate::command_buffer) sample_locations: Option<SampleLocationsInfo>,
     pub(in crate::command_buffer) scissor: HashMap<u32, Scissor>,
     pub(in crate::command_buffer) scissor_with_count: Option<SmallVec<[Scissor; 2]>>,
     pub(in crate::command_buffer) stencil_compare_mask: StencilStateDynamic,
@@ -988,6 +1064,8 @@ impl CurrentState {
     ) {
         for state in states {
             match state {
+                DynamicState::AlphaToCoverageEnable => self.alpha_to_coverage_enable = None,
+                DynamicState::AlphaToOneEnable => self.alpha_to_one_enable = None,
                 DynamicState::BlendConstants => self.blend_constants = None,
                 DynamicState::ColorWriteEnable => self.color_write_enable = None,
                 DynamicState::CullMode => self.cull_mode = None,
@@ -995,22 +1073,24 @@ impl CurrentState {
                 DynamicState::DepthBiasEnable => self.depth_bias_enable = None,
                 DynamicState::DepthBounds => self.depth_bounds = None,
                 DynamicState::DepthBoundsTestEnable => self.depth_bounds_test_enable = None,
+                DynamicState::DepthClampEnable => self.depth_clamp_enable = None,
                 DynamicState::DepthCompareOp => self.depth_compare_op = None,
                 DynamicState::DepthTestEnable => self.depth_test_enable = None,
                 DynamicState::DepthWriteEnable => self.depth_write_enable = None,
                 DynamicState::DiscardRectangle => self.discard_rectangle.clear(),
                 DynamicState::ExclusiveScissor => (), // TODO;
-                DynamicState::FragmentShadingRate => (), // TODO:
+                DynamicState::FragmentShadingRate => self.fragment_shading_rate = None,
                 DynamicState::FrontFace => self.front_face = None,
                 DynamicState::LineStipple => self.line_stipple = None,
                 DynamicState::LineWidth => self.line_width = None,
                 DynamicState::LogicOp => self.logic_op = None,
                 DynamicState::PatchControlPoints => self.patch_control_points = None,
+                DynamicState::PolygonMode => self.polygon_mode = None,
                 DynamicState::PrimitiveRestartEnable => self.primitive_restart_enable = None,
                 DynamicState::PrimitiveTopology => self.primitive_topology = None,
                 DynamicState::RasterizerDiscardEnable => self.rasterizer_discard_enable = None,
                 DynamicState::RayTracingPipelineStackSize => (), // TODO:
-                DynamicState::SampleLocations => (),             // TODO:
+                DynamicState::SampleLocations => self.sample_locations = None,
                 DynamicState::Scissor => self.scissor.clear(),
                 DynamicState::ScissorWithCount => self.scissor_with_count = None,
                 DynamicState::StencilCompareMask => self.stencil_compare_mask = Default::default(),
@@ -1026,12 +1106,8 @@ impl CurrentState {
                 DynamicState::ViewportWScaling => (),          // TODO:
                 DynamicState::ViewportWithCount => self.viewport_with_count = None,
                 DynamicState::TessellationDomainOrigin => (), // TODO:
-                DynamicState::DepthClampEnable => (),         // TODO:
-                DynamicState::PolygonMode => (),              // TODO:
                 DynamicState::RasterizationSamples => (),     // TODO:
                 DynamicState::SampleMask => (),               // TODO:
-                DynamicState::AlphaToCoverageEnable => (),    // TODO:
-                DynamicState::AlphaToOneEnable => (),         // TODO:
                 DynamicState::LogicOpEnable => (),            // TODO:
                 DynamicState::ColorBlendEnable => (),         // TODO:
                 DynamicState::ColorBlendEquation => (),       // TODO:
@@ -1070,6 +1146,7 @@ impl CurrentState {
         match self.descriptor_sets.entry(pipeline_bind_point) {
             Entry::Vacant(entry) => entry.insert(DescriptorSetState {
                 descriptor_sets: Default::default(),
+                descriptor_buffer_offsets: Default::default(),
                 pipeline_layout,
             }),
             Entry::Occupied(entry) => {
@@ -1104,6 +1181,9 @@ impl CurrentState {
                     state
                         .descriptor_sets
                         .retain(|&num, _| num < invalidate_from);
+                    state
+                        .descriptor_buffer_offsets
+                        .retain(|&num, _| num < invalidate_from);
                     state.pipeline_layout = pipeline_layout;
                 } else if (first_set + num_descriptor_sets) as usize
                     >= state.pipeline_layout.set_layouts().len()
@@ -1120,6 +1200,8 @@ impl CurrentState {
 
 pub(in crate::command_buffer) struct DescriptorSetState {
     pub(in crate::command_buffer) descriptor_sets: HashMap<u32, SetOrPush>,
+    // Index of the bound descriptor buffer, and offset into it.
+    pub(in crate::command_buffer) descriptor_buffer_offsets: HashMap<u32, (u32, DeviceSize)>,
     pub(in crate::command_buffer) pipeline_layout: Arc<PipelineLayout>,
 }
 
@@ -1131,9 +1213,27 @@ pub enum SetOrPush {
 
 impl SetOrPush {
     #[inline]
-    pub fn resources(&self) -> &DescriptorSetResources {
+    pub fn resources(&self) -> SetOrPushResources<'_> {
         match self {
-            Self::Set(set) => set.as_ref().0.resources(),
+            Self::Set(set) => SetOrPushResources::Set(set.as_ref().0.resources()),
+            Self::Push(resources) => SetOrPushResources::Push(resources),
+        }
+    }
+}
+
+/// The resources of a [`SetOrPush`].
+pub enum SetOrPushResources<'a> {
+    Set(RwLockReadGuard<'a, DescriptorSetResources>),
+    Push(&'a DescriptorSetResources),
+}
+
+impl Deref for SetOrPushResources<'_> {
+    type Target = DescriptorSetResources;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Set(resources) => resources,
             Self::Push(resources) => resources,
         }
     }
@@ -1146,6 +1246,27 @@ pub struct CommandBufferBuilderState<'a> {
 }
 
 impl<'a> CommandBufferBuilderState<'a> {
+    /// Returns the descriptor buffers currently bound, indexed by buffer index.
+    #[inline]
+    pub fn descriptor_buffers(&self) -> &'a [Arc<dyn BufferAccess>] {
+        &self.current_state.descriptor_buffers
+    }
+
+    /// Returns the descriptor buffer index and offset that are currently set for a given set
+    /// number, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn descriptor_buffer_offset(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+        set_num: u32,
+    ) -> Option<(u32, DeviceSize)> {
+        self.current_state
+            .descriptor_sets
+            .get(&pipeline_bind_point)
+            .and_then(|state| state.descriptor_buffer_offsets.get(&set_num))
+            .copied()
+    }
+
     /// Returns the descriptor set currently bound to a given set number, or `None` if nothing has
     /// been bound yet.
     #[inline]
@@ -1196,6 +1317,22 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.pipeline_graphics.as_ref()
     }
 
+    /// Returns the ray tracing pipeline currently bound, or `None` if nothing has been bound
+    /// yet.
+    #[inline]
+    pub fn pipeline_ray_tracing(&self) -> Option<&'a Arc<RayTracingPipeline>> {
+        self.current_state.pipeline_ray_tracing.as_ref()
+    }
+
+    /// Returns the transform feedback buffer currently bound to a given binding slot number, or
+    /// `None` if nothing has been bound yet.
+    #[inline]
+    pub fn transform_feedback_buffer(&self, binding_num: u32) -> Option<&'a Arc<dyn BufferAccess>> {
+        self.current_state
+            .transform_feedback_buffers
+            .get(&binding_num)
+    }
+
     /// Returns the vertex buffer currently bound to a given binding slot number, or `None` if
     /// nothing has been bound yet.
     #[inline]
@@ -1217,6 +1354,18 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.push_constants_pipeline_layout.as_ref()
     }
 
+    /// Returns whether alpha-to-coverage is enabled, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn alpha_to_coverage_enable(&self) -> Option<bool> {
+        self.current_state.alpha_to_coverage_enable
+    }
+
+    /// Returns whether alpha-to-one is enabled, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn alpha_to_one_enable(&self) -> Option<bool> {
+        self.current_state.alpha_to_one_enable
+    }
+
     /// Returns the current blend constants, or `None` if nothing has been set yet.
     #[inline]
     pub fn blend_constants(&self) -> Option<[f32; 4]> {
@@ -1262,6 +1411,12 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.depth_bias_enable
     }
 
+    /// Returns whether depth clamping is enabled, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn depth_clamp_enable(&self) -> Option<bool> {
+        self.current_state.depth_clamp_enable
+    }
+
     /// Returns the current depth compare op, or `None` if nothing has been set yet.
     #[inline]
     pub fn depth_compare_op(&self) -> Option<CompareOp> {
@@ -1286,6 +1441,12 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.discard_rectangle.get(&num)
     }
 
+    /// Returns the current fragment shading rate, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn fragment_shading_rate(&self) -> Option<FragmentShadingRate> {
+        self.current_state.fragment_shading_rate
+    }
+
     /// Returns the current front face, or `None` if nothing has been set yet.
     #[inline]
     pub fn front_face(&self) -> Option<FrontFace> {
@@ -1316,6 +1477,12 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.patch_control_points
     }
 
+    /// Returns the current polygon mode, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn polygon_mode(&self) -> Option<PolygonMode> {
+        self.current_state.polygon_mode
+    }
+
     /// Returns whether primitive restart is enabled, or `None` if nothing has been set yet.
     #[inline]
     pub fn primitive_restart_enable(&self) -> Option<bool> {
@@ -1334,6 +1501,12 @@ impl<'a> CommandBufferBuilderState<'a> {
         self.current_state.rasterizer_discard_enable
     }
 
+    /// Returns the current sample locations, or `None` if nothing has been set yet.
+    #[inline]
+    pub fn sample_locations(&self) -> Option<&'a SampleLocationsInfo> {
+        self.current_state.sample_locations.as_ref()
+    }
+
     /// Returns the current scissor for a given viewport slot, or `None` if nothing has been set
     /// yet.
     #[inline]
@@ -65,8 +65,8 @@
 //! queue with a fresh new barrier prototype.
 
 pub use self::builder::{
-    CommandBufferBuilderState, SetOrPush, StencilOpStateDynamic, StencilStateDynamic,
-    SyncCommandBufferBuilder, SyncCommandBufferBuilderBindDescriptorSets,
+    CommandBufferBuilderState, SetOrPush, SetOrPushResources, StencilOpStateDynamic,
+    StencilStateDynamic, SyncCommandBufferBuilder, SyncCommandBufferBuilderBindDescriptorSets,
     SyncCommandBufferBuilderBindVertexBuffer, SyncCommandBufferBuilderError,
     SyncCommandBufferBuilderExecuteCommands,
 };
@@ -13,13 +13,14 @@ use super::{
 };
 use crate::{
     buffer::sys::Buffer,
-    device::{Device, DeviceOwned, Queue},
+    device::{Device, DeviceOwned, Queue, QueueCreateFlags},
     image::{sys::Image, ImageLayout},
     swapchain::Swapchain,
     sync::{
         future::{
             now, AccessCheckError, AccessError, FlushError, GpuFuture, NowFuture, SubmitAnyBuilder,
         },
+        semaphore::Semaphore,
         PipelineStages,
     },
     DeviceSize, SafeDeref, VulkanObject,
@@ -42,6 +43,19 @@ pub unsafe trait PrimaryCommandBufferAbstract:
     /// Returns the usage of this command buffer.
     fn usage(&self) -> CommandBufferUsage;
 
+    /// Returns whether this command buffer was allocated from a protected command pool.
+    ///
+    /// A protected command buffer can only be executed in a protected submission, on a queue that
+    /// was created with [`QueueCreateFlags::PROTECTED`].
+    ///
+    /// The default implementation returns `false`.
+    ///
+    /// [`QueueCreateFlags::PROTECTED`]: crate::device::QueueCreateFlags::PROTECTED
+    #[inline]
+    fn protected(&self) -> bool {
+        false
+    }
+
     /// Executes this command buffer on a queue.
     ///
     /// This function returns an object that implements the `GpuFuture` trait. See the
@@ -103,19 +117,7 @@ pub unsafe trait PrimaryCommandBufferAbstract:
         Self: Sized + 'static,
         F: GpuFuture,
     {
-        assert_eq!(self.device().handle(), future.device().handle());
-
-        if !future.queue_change_allowed() {
-            assert!(future.queue().unwrap() == queue);
-        }
-
-        Ok(CommandBufferExecFuture {
-            previous: future,
-            command_buffer: Arc::new(self),
-            queue,
-            submitted: Mutex::new(false),
-            finished: AtomicBool::new(false),
-        })
+        execute_batch_after(future, queue, [Arc::new(self) as _])
     }
 
     #[doc(hidden)]
@@ -140,6 +142,10 @@ where
         (**self).usage()
     }
 
+    fn protected(&self) -> bool {
+        (**self).protected()
+    }
+
     fn state(&self) -> MutexGuard<'_, CommandBufferState> {
         (**self).state()
     }
@@ -202,8 +208,53 @@ where
     }
 }
 
-/// Represents a command buffer being executed by the GPU and the moment when the execution
-/// finishes.
+// Executes the command buffers after an existing future, in a single batch.
+pub(crate) fn execute_batch_after<F>(
+    future: F,
+    queue: Arc<Queue>,
+    command_buffers: impl IntoIterator<Item = Arc<dyn PrimaryCommandBufferAbstract>>,
+) -> Result<CommandBufferExecFuture<F>, CommandBufferExecError>
+where
+    F: GpuFuture,
+{
+    let command_buffers: Vec<_> = command_buffers.into_iter().collect();
+    assert!(!command_buffers.is_empty());
+
+    for command_buffer in &command_buffers {
+        assert_eq!(command_buffer.device().handle(), future.device().handle());
+    }
+
+    if !future.queue_change_allowed() {
+        assert!(future.queue().unwrap() == queue);
+    }
+
+    let protected = command_buffers[0].protected();
+
+    // VUID-VkSubmitInfo-pNext-04120
+    // VUID-VkSubmitInfo-pNext-04148
+    if command_buffers
+        .iter()
+        .any(|command_buffer| command_buffer.protected() != protected)
+    {
+        return Err(CommandBufferExecError::ProtectedMismatch);
+    }
+
+    // VUID-vkQueueSubmit-queue-06448
+    if protected && !queue.flags().intersects(QueueCreateFlags::PROTECTED) {
+        return Err(CommandBufferExecError::QueueNotProtected);
+    }
+
+    Ok(CommandBufferExecFuture {
+        previous: future,
+        command_buffers,
+        queue,
+        submitted: Mutex::new(false),
+        finished: AtomicBool::new(false),
+    })
+}
+
+/// Represents one or more command buffers being executed by the GPU and the moment when the
+/// execution finishes.
 #[derive(Debug)]
 #[must_use = "Dropping this object will immediately block the thread until the GPU has finished processing the submission"]
 pub struct CommandBufferExecFuture<F>
@@ -211,7 +262,8 @@ where
     F: GpuFuture,
 {
     previous: F,
-    command_buffer: Arc<dyn PrimaryCommandBufferAbstract>,
+    // The command buffers to execute, in submission order. Never empty.
+    command_buffers: Vec<Arc<dyn PrimaryCommandBufferAbstract>>,
     queue: Arc<Queue>,
     // True if the command buffer has already been submitted.
     // If flush is called multiple times, we want to block so that only one flushing is executed.
@@ -227,10 +279,13 @@ where
     // Implementation of `build_submission`. Doesn't check whenever the future was already flushed.
     // You must make sure to not submit same command buffer multiple times.
     unsafe fn build_submission_impl(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        let protected = self.command_buffers[0].protected();
+
         Ok(match self.previous.build_submission()? {
             SubmitAnyBuilder::Empty => SubmitAnyBuilder::CommandBuffer(
                 SubmitInfo {
-                    command_buffers: vec![self.command_buffer.clone()],
+                    command_buffers: self.command_buffers.clone(),
+                    protected,
                     ..Default::default()
                 },
                 None,
@@ -248,7 +303,36 @@ where
                                 }
                             })
                             .collect(),
-                        command_buffers: vec![self.command_buffer.clone()],
+                        command_buffers: self.command_buffers.clone(),
+                        protected,
+                        ..Default::default()
+                    },
+                    None,
+                )
+            }
+            SubmitAnyBuilder::CommandBuffer(mut submit_info, fence)
+                if submit_info.protected != protected =>
+            {
+                // Protected and unprotected command buffers can't be in the same submission, so
+                // submit the previous one separately and wait for it with a semaphore.
+                let semaphore =
+                    Arc::new(Semaphore::from_pool(self.queue.device().clone()).unwrap());
+                submit_info
+                    .signal_semaphores
+                    .push(SemaphoreSubmitInfo::semaphore(semaphore.clone()));
+
+                self.queue.with(|mut q| {
+                    q.submit_with_future(submit_info, fence, &self.previous, &self.queue)
+                })?;
+
+                SubmitAnyBuilder::CommandBuffer(
+                    SubmitInfo {
+                        wait_semaphores: vec![SemaphoreSubmitInfo {
+                            stages: PipelineStages::ALL_COMMANDS,
+                            ..SemaphoreSubmitInfo::semaphore(semaphore)
+                        }],
+                        command_buffers: self.command_buffers.clone(),
+                        protected,
                         ..Default::default()
                     },
                     None,
@@ -258,10 +342,37 @@ where
                 // FIXME: add pipeline barrier
                 submit_info
                     .command_buffers
-                    .push(self.command_buffer.clone());
+                    .extend(self.command_buffers.iter().cloned());
                 SubmitAnyBuilder::CommandBuffer(submit_info, fence)
             }
-            SubmitAnyBuilder::QueuePresent(_) | SubmitAnyBuilder::BindSparse(_, _) => {
+            SubmitAnyBuilder::BindSparse(mut bind_infos, fence) => {
+                // Sparse binding operations are not ordered with command buffer submissions, so
+                // signal a semaphore at the end and wait for it before executing.
+                let semaphore =
+                    Arc::new(Semaphore::from_pool(self.queue.device().clone()).unwrap());
+                bind_infos
+                    .last_mut()
+                    .unwrap()
+                    .signal_semaphores
+                    .push(semaphore.clone());
+
+                self.queue
+                    .with(|mut q| q.bind_sparse_unchecked(bind_infos, fence))?;
+
+                SubmitAnyBuilder::CommandBuffer(
+                    SubmitInfo {
+                        wait_semaphores: vec![SemaphoreSubmitInfo {
+                            stages: PipelineStages::ALL_COMMANDS,
+                            ..SemaphoreSubmitInfo::semaphore(semaphore)
+                        }],
+                        command_buffers: self.command_buffers.clone(),
+                        protected,
+                        ..Default::default()
+                    },
+                    None,
+                )
+            }
+            SubmitAnyBuilder::QueuePresent(_) => {
                 unimplemented!() // TODO:
                                  /*present.submit();     // TODO: wrong
                                  let mut builder = SubmitCommandBufferBuilder::new();
@@ -331,32 +442,43 @@ where
         exclusive: bool,
         queue: &Queue,
     ) -> Result<(), AccessCheckError> {
-        let resources_usage = self.command_buffer.resources_usage();
-        let usage = match resources_usage.buffer_indices.get(buffer) {
-            Some(&index) => &resources_usage.buffers[index],
-            None => return Err(AccessCheckError::Unknown),
-        };
-
-        // TODO: check the queue family
-
-        let result = usage
-            .ranges
-            .range(&range)
-            .try_fold((), |_, (_range, range_usage)| {
-                if !range_usage.mutable && exclusive {
-                    Err(AccessCheckError::Unknown)
-                } else {
-                    Ok(())
-                }
-            });
-
-        match result {
-            Ok(()) => Ok(()),
-            Err(AccessCheckError::Denied(err)) => Err(AccessCheckError::Denied(err)),
-            Err(AccessCheckError::Unknown) => self
-                .previous
-                .check_buffer_access(buffer, range, exclusive, queue),
+        let mut is_used = false;
+
+        // Check the last command buffer that uses the buffer first.
+        for command_buffer in self.command_buffers.iter().rev() {
+            let resources_usage = command_buffer.resources_usage();
+            let usage = match resources_usage.buffer_indices.get(buffer) {
+                Some(&index) => &resources_usage.buffers[index],
+                None => continue,
+            };
+            is_used = true;
+
+            // TODO: check the queue family
+
+            let result = usage
+                .ranges
+                .range(&range)
+                .try_fold((), |_, (_range, range_usage)| {
+                    if !range_usage.mutable && exclusive {
+                        Err(AccessCheckError::Unknown)
+                    } else {
+                        Ok(())
+                    }
+                });
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err(AccessCheckError::Denied(err)) => return Err(AccessCheckError::Denied(err)),
+                Err(AccessCheckError::Unknown) => (),
+            }
+        }
+
+        if !is_used {
+            return Err(AccessCheckError::Unknown);
         }
+
+        self.previous
+            .check_buffer_access(buffer, range, exclusive, queue)
     }
 
     fn check_image_access(
@@ -367,44 +489,54 @@ where
         expected_layout: ImageLayout,
         queue: &Queue,
     ) -> Result<(), AccessCheckError> {
-        let resources_usage = self.command_buffer.resources_usage();
-        let usage = match resources_usage.image_indices.get(image) {
-            Some(&index) => &resources_usage.images[index],
-            None => return Err(AccessCheckError::Unknown),
-        };
-
-        // TODO: check the queue family
-
-        let result = usage
-            .ranges
-            .range(&range)
-            .try_fold((), |_, (_range, range_usage)| {
-                if expected_layout != ImageLayout::Undefined
-                    && range_usage.final_layout != expected_layout
-                {
-                    return Err(AccessCheckError::Denied(
-                        AccessError::UnexpectedImageLayout {
-                            allowed: range_usage.final_layout,
-                            requested: expected_layout,
-                        },
-                    ));
-                }
-
-                if !range_usage.mutable && exclusive {
-                    Err(AccessCheckError::Unknown)
-                } else {
-                    Ok(())
-                }
-            });
-
-        match result {
-            Ok(()) => Ok(()),
-            Err(AccessCheckError::Denied(err)) => Err(AccessCheckError::Denied(err)),
-            Err(AccessCheckError::Unknown) => {
-                self.previous
-                    .check_image_access(image, range, exclusive, expected_layout, queue)
+        let mut is_used = false;
+
+        // Check the last command buffer that uses the image first.
+        for command_buffer in self.command_buffers.iter().rev() {
+            let resources_usage = command_buffer.resources_usage();
+            let usage = match resources_usage.image_indices.get(image) {
+                Some(&index) => &resources_usage.images[index],
+                None => continue,
+            };
+            is_used = true;
+
+            // TODO: check the queue family
+
+            let result = usage
+                .ranges
+                .range(&range)
+                .try_fold((), |_, (_range, range_usage)| {
+                    if expected_layout != ImageLayout::Undefined
+                        && range_usage.final_layout != expected_layout
+                    {
+                        return Err(AccessCheckError::Denied(
+                            AccessError::UnexpectedImageLayout {
+                                allowed: range_usage.final_layout,
+                                requested: expected_layout,
+                            },
+                        ));
+                    }
+
+                    if !range_usage.mutable && exclusive {
+                        Err(AccessCheckError::Unknown)
+                    } else {
+                        Ok(())
+                    }
+                });
+
+            match result {
+                Ok(()) => return Ok(()),
+                Err(AccessCheckError::Denied(err)) => return Err(AccessCheckError::Denied(err)),
+                Err(AccessCheckError::Unknown) => (),
             }
         }
+
+        if !is_used {
+            return Err(AccessCheckError::Unknown);
+        }
+
+        self.previous
+            .check_image_access(image, range, exclusive, expected_layout, queue)
     }
 
     #[inline]
@@ -424,7 +556,7 @@ where
     F: GpuFuture,
 {
     fn device(&self) -> &Arc<Device> {
-        self.command_buffer.device()
+        self.command_buffers[0].device()
     }
 }
 
@@ -435,10 +567,17 @@ where
     fn drop(&mut self) {
         unsafe {
             if !*self.finished.get_mut() {
-                // TODO: handle errors?
-                self.flush().unwrap();
+                // If the device was lost, nothing more will be executed on it, so the resources
+                // can be released anyway.
+                if let Err(err) = self.flush() {
+                    assert!(self.device().is_lost(), "{:?}", err);
+                }
+
                 // Block until the queue finished.
-                self.queue.with(|mut q| q.wait_idle()).unwrap();
+                if let Err(err) = self.queue.with(|mut q| q.wait_idle()) {
+                    assert!(self.device().is_lost(), "{:?}", err);
+                }
+
                 self.previous.signal_finished();
             }
         }
@@ -463,6 +602,13 @@ pub enum CommandBufferExecError {
     /// The command buffer or one of the secondary command buffers it executes is already in use by
     /// the GPU and was not created with the "concurrent" flag.
     ExclusiveAlreadyInUse,
+
+    /// Some of the command buffers are protected and others are not.
+    ProtectedMismatch,
+
+    /// The command buffers are protected, but the queue was not created with
+    /// [`QueueCreateFlags::PROTECTED`].
+    QueueNotProtected,
     // TODO: missing entries (eg. wrong queue family, secondary command buffer)
 }
 
@@ -492,6 +638,13 @@ impl Display for CommandBufferExecError {
                     "the command buffer or one of the secondary command buffers it executes is \
                     already in use was not created with the \"concurrent\" flag"
                 }
+                CommandBufferExecError::ProtectedMismatch => {
+                    "some of the command buffers are protected and others are not"
+                }
+                CommandBufferExecError::QueueNotProtected => {
+                    "the command buffers are protected, but the queue was not created with the \
+                    `protected` flag"
+                }
             }
         )
     }
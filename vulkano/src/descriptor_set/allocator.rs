@@ -18,7 +18,7 @@
 
 use self::sorted_map::SortedMap;
 use super::{
-    layout::DescriptorSetLayout,
+    layout::{DescriptorSetLayout, DescriptorType},
     pool::{
         DescriptorPool, DescriptorPoolAllocError, DescriptorPoolCreateInfo,
         DescriptorSetAllocateInfo,
@@ -266,6 +266,9 @@ impl FixedPool {
                     .iter()
                     .map(|(&ty, &count)| (ty, count * set_count as u32))
                     .collect(),
+                update_after_bind: layout.update_after_bind_pool(),
+                max_inline_uniform_block_bindings: inline_uniform_block_bindings(layout)
+                    * set_count as u32,
                 ..Default::default()
             },
         )?;
@@ -402,6 +405,9 @@ impl VariablePool {
                     .iter()
                     .map(|(&ty, &count)| (ty, count * MAX_SETS as u32))
                     .collect(),
+                update_after_bind: layout.update_after_bind_pool(),
+                max_inline_uniform_block_bindings: inline_uniform_block_bindings(layout)
+                    * MAX_SETS as u32,
                 ..Default::default()
             },
         )
@@ -474,6 +480,16 @@ impl Drop for StandardDescriptorSetAlloc {
     }
 }
 
+/// Returns the number of inline uniform block bindings in `layout`, which pools must reserve
+/// separately from the descriptor counts.
+fn inline_uniform_block_bindings(layout: &DescriptorSetLayout) -> u32 {
+    layout
+        .bindings()
+        .values()
+        .filter(|binding| binding.descriptor_type == DescriptorType::InlineUniformBlock)
+        .count() as u32
+}
+
 mod sorted_map {
     use smallvec::SmallVec;
 
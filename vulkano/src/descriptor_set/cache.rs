@@ -0,0 +1,388 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reuse descriptor sets that were created with the same layout and resources.
+//!
+//! In dynamic scenes, the resources that are used by a draw or dispatch call often change from
+//! one frame to the next, but many combinations of resources reappear frame after frame. Creating
+//! a new [`PersistentDescriptorSet`] for each of them every frame can take a significant amount of
+//! CPU time. A [`DescriptorSetCache`] remembers the descriptor sets that it has created, keyed by
+//! their layout and the resources that were written to them, and returns an existing set when the
+//! same combination is requested again.
+//!
+//! Descriptor sets that have not been requested for a number of frames are evicted from the
+//! cache when [`DescriptorSetCache::next_frame`] is called.
+
+use super::{
+    allocator::{DescriptorSetAllocator, StandardDescriptorSetAllocator},
+    layout::DescriptorSetLayout,
+    DescriptorSetCreationError, PersistentDescriptorSet, WriteDescriptorSet,
+    WriteDescriptorSetElements,
+};
+use crate::{
+    device::{Device, DeviceOwned},
+    DeviceSize, VulkanObject,
+};
+use ahash::HashMap;
+use parking_lot::Mutex;
+use smallvec::SmallVec;
+use std::sync::Arc;
+
+/// Cache of descriptor sets, keyed by their layout and the resources written to them.
+///
+/// See [the documentation of the module](crate::descriptor_set::cache) for more info.
+///
+/// The descriptor sets that are returned by the cache are shared between all users that request
+/// the same resources, so they must not be modified with [`PersistentDescriptorSet::update`].
+pub struct DescriptorSetCache<A = StandardDescriptorSetAllocator>
+where
+    A: DescriptorSetAllocator,
+{
+    allocator: A,
+    max_unused_frames: u32,
+    state: Mutex<CacheState<A::Alloc>>,
+}
+
+struct CacheState<P> {
+    frame: u64,
+    sets: HashMap<CacheKey, CacheEntry<P>>,
+}
+
+struct CacheEntry<P> {
+    descriptor_set: Arc<PersistentDescriptorSet<P>>,
+    last_used_frame: u64,
+}
+
+impl<A> DescriptorSetCache<A>
+where
+    A: DescriptorSetAllocator,
+{
+    /// Creates a new `DescriptorSetCache`, which allocates new descriptor sets from `allocator`.
+    #[inline]
+    pub fn new(allocator: A, create_info: DescriptorSetCacheCreateInfo) -> Self {
+        let DescriptorSetCacheCreateInfo {
+            max_unused_frames,
+            _ne: _,
+        } = create_info;
+
+        DescriptorSetCache {
+            allocator,
+            max_unused_frames,
+            state: Mutex::new(CacheState {
+                frame: 0,
+                sets: HashMap::default(),
+            }),
+        }
+    }
+
+    /// Returns the allocator that new descriptor sets are allocated from.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+
+    /// Returns the number of frames that a descriptor set can go unused before it is evicted.
+    #[inline]
+    pub fn max_unused_frames(&self) -> u32 {
+        self.max_unused_frames
+    }
+
+    /// Returns a descriptor set with a variable descriptor count of 0 and the given descriptors.
+    ///
+    /// See `get_or_create_variable` for more.
+    #[inline]
+    pub fn get_or_create(
+        &self,
+        layout: Arc<DescriptorSetLayout>,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<Arc<PersistentDescriptorSet<A::Alloc>>, DescriptorSetCreationError> {
+        self.get_or_create_variable(layout, 0, descriptor_writes)
+    }
+
+    /// Returns a descriptor set with the given variable descriptor count and descriptors.
+    ///
+    /// If a descriptor set was previously created by the cache with the same layout, variable
+    /// descriptor count and sequence of descriptor writes, and it has not been evicted since, then
+    /// that descriptor set is returned. Otherwise, a new descriptor set is created with
+    /// [`PersistentDescriptorSet::new_variable`] and added to the cache.
+    ///
+    /// Resources are compared by identity, not by content. Writing a different buffer with the
+    /// same contents results in a different descriptor set.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `layout` was created for push descriptors rather than descriptor sets.
+    /// - Panics if `variable_descriptor_count` is too large for the given `layout`.
+    pub fn get_or_create_variable(
+        &self,
+        layout: Arc<DescriptorSetLayout>,
+        variable_descriptor_count: u32,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<Arc<PersistentDescriptorSet<A::Alloc>>, DescriptorSetCreationError> {
+        let descriptor_writes: SmallVec<[_; 8]> = descriptor_writes.into_iter().collect();
+        let key = CacheKey {
+            layout: layout.handle(),
+            variable_descriptor_count,
+            writes: descriptor_writes.iter().map(WriteKey::new).collect(),
+        };
+
+        let mut state = self.state.lock();
+        let frame = state.frame;
+
+        if let Some(entry) = state.sets.get_mut(&key) {
+            entry.last_used_frame = frame;
+            return Ok(entry.descriptor_set.clone());
+        }
+
+        let descriptor_set = PersistentDescriptorSet::new_variable(
+            &self.allocator,
+            layout,
+            variable_descriptor_count,
+            descriptor_writes,
+        )?;
+        state.sets.insert(
+            key,
+            CacheEntry {
+                descriptor_set: descriptor_set.clone(),
+                last_used_frame: frame,
+            },
+        );
+
+        Ok(descriptor_set)
+    }
+
+    /// Advances the cache to the next frame, and evicts the descriptor sets that have not been
+    /// requested during the last `max_unused_frames` frames.
+    ///
+    /// This should be called once per frame, for example after waiting for the fence of a
+    /// previous frame. Evicting a descriptor set only removes the reference that the cache holds
+    /// to it. Command buffers that use the descriptor set keep it alive until they are dropped,
+    /// which for a submitted command buffer happens once its fence has been signaled and
+    /// cleaned up, so a set is never freed while the device may still be using it.
+    pub fn next_frame(&self) {
+        let mut state = self.state.lock();
+        state.frame += 1;
+
+        let frame = state.frame;
+        let max_unused_frames = self.max_unused_frames as u64;
+        state
+            .sets
+            .retain(|_, entry| frame - entry.last_used_frame <= max_unused_frames);
+    }
+
+    /// Returns the number of descriptor sets currently in the cache.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.state.lock().sets.len()
+    }
+
+    /// Returns whether the cache is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.state.lock().sets.is_empty()
+    }
+
+    /// Evicts all descriptor sets from the cache.
+    #[inline]
+    pub fn clear(&self) {
+        self.state.lock().sets.clear();
+    }
+}
+
+unsafe impl<A> DeviceOwned for DescriptorSetCache<A>
+where
+    A: DescriptorSetAllocator,
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.allocator.device()
+    }
+}
+
+/// Parameters to create a new [`DescriptorSetCache`].
+#[derive(Clone, Debug)]
+pub struct DescriptorSetCacheCreateInfo {
+    /// The number of calls to [`DescriptorSetCache::next_frame`] that a descriptor set can go
+    /// without being requested before it is evicted from the cache.
+    ///
+    /// A value of `0` evicts every descriptor set that was not requested since the previous call
+    /// to `next_frame`.
+    ///
+    /// The default value is `3`.
+    pub max_unused_frames: u32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for DescriptorSetCacheCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_unused_frames: 3,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+// The handles of the layout and resources uniquely identify them, because the cached descriptor
+// set keeps all of them alive for as long as the key exists.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    layout: ash::vk::DescriptorSetLayout,
+    variable_descriptor_count: u32,
+    writes: SmallVec<[WriteKey; 8]>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct WriteKey {
+    binding: u32,
+    first_array_element: u32,
+    elements: ElementsKey,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum ElementsKey {
+    None(u32),
+    Buffer(SmallVec<[(ash::vk::Buffer, DeviceSize, DeviceSize); 1]>),
+    BufferView(SmallVec<[ash::vk::BufferView; 1]>),
+    ImageView(SmallVec<[ash::vk::ImageView; 1]>),
+    ImageViewSampler(SmallVec<[(ash::vk::ImageView, ash::vk::Sampler); 1]>),
+    Sampler(SmallVec<[ash::vk::Sampler; 1]>),
+    AccelerationStructure(SmallVec<[ash::vk::AccelerationStructureKHR; 1]>),
+    InlineUniformBlock(Vec<u8>),
+}
+
+impl WriteKey {
+    fn new(write: &WriteDescriptorSet) -> Self {
+        let elements = match write.elements() {
+            WriteDescriptorSetElements::None(num_elements) => ElementsKey::None(*num_elements),
+            WriteDescriptorSetElements::Buffer(elements) => ElementsKey::Buffer(
+                elements
+                    .iter()
+                    .map(|buffer| {
+                        let inner = buffer.inner();
+                        (inner.buffer.handle(), inner.offset, buffer.size())
+                    })
+                    .collect(),
+            ),
+            WriteDescriptorSetElements::BufferView(elements) => ElementsKey::BufferView(
+                elements
+                    .iter()
+                    .map(|buffer_view| buffer_view.handle())
+                    .collect(),
+            ),
+            WriteDescriptorSetElements::ImageView(elements) => ElementsKey::ImageView(
+                elements
+                    .iter()
+                    .map(|image_view| image_view.handle())
+                    .collect(),
+            ),
+            WriteDescriptorSetElements::ImageViewSampler(elements) => {
+                ElementsKey::ImageViewSampler(
+                    elements
+                        .iter()
+                        .map(|(image_view, sampler)| (image_view.handle(), sampler.handle()))
+                        .collect(),
+                )
+            }
+            WriteDescriptorSetElements::Sampler(elements) => {
+                ElementsKey::Sampler(elements.iter().map(|sampler| sampler.handle()).collect())
+            }
+            WriteDescriptorSetElements::AccelerationStructure(elements) => {
+                ElementsKey::AccelerationStructure(
+                    elements
+                        .iter()
+                        .map(|acceleration_structure| acceleration_structure.handle())
+                        .collect(),
+                )
+            }
+            WriteDescriptorSetElements::InlineUniformBlock(data) => {
+                ElementsKey::InlineUniformBlock(data.clone())
+            }
+        };
+
+        WriteKey {
+            binding: write.binding(),
+            first_array_element: write.first_array_element(),
+            elements,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        descriptor_set::layout::{
+            DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo, DescriptorType,
+        },
+        sampler::{Sampler, SamplerCreateInfo},
+        shader::ShaderStages,
+    };
+
+    #[test]
+    fn reuse_and_evict() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::FRAGMENT,
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let sampler1 =
+            Sampler::new(device.clone(), SamplerCreateInfo::simple_repeat_linear()).unwrap();
+        let sampler2 =
+            Sampler::new(device.clone(), SamplerCreateInfo::simple_repeat_linear()).unwrap();
+
+        let cache = DescriptorSetCache::new(
+            StandardDescriptorSetAllocator::new(device),
+            DescriptorSetCacheCreateInfo {
+                max_unused_frames: 1,
+                ..Default::default()
+            },
+        );
+
+        let set1 = cache
+            .get_or_create(
+                layout.clone(),
+                [WriteDescriptorSet::sampler(0, sampler1.clone())],
+            )
+            .unwrap();
+        let set2 = cache
+            .get_or_create(
+                layout.clone(),
+                [WriteDescriptorSet::sampler(0, sampler1.clone())],
+            )
+            .unwrap();
+        let set3 = cache
+            .get_or_create(layout.clone(), [WriteDescriptorSet::sampler(0, sampler2)])
+            .unwrap();
+        assert!(Arc::ptr_eq(&set1, &set2));
+        assert!(!Arc::ptr_eq(&set1, &set3));
+        assert_eq!(cache.len(), 2);
+
+        cache.next_frame();
+        cache
+            .get_or_create(layout, [WriteDescriptorSet::sampler(0, sampler1)])
+            .unwrap();
+        cache.next_frame();
+        assert_eq!(cache.len(), 1);
+    }
+}
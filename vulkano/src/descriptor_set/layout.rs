@@ -10,13 +10,22 @@
 //! Describes the layout of all descriptors within a descriptor set.
 //!
 //! When creating a new descriptor set, you must provide a *layout* object to create it from.
-
+//!
+//! A layout can be derived from the reflection data of shaders with
+//! [`DescriptorSetLayoutCreateInfo::from_requirements`], but it can also be described entirely at
+//! runtime by filling in [`DescriptorSetLayoutCreateInfo::bindings`]. See
+//! [the `pipeline::layout` module](crate::pipeline::layout#creating-pipeline-layouts) for an
+//! example.
+
+use super::update::{
+    check_descriptor_write, write_descriptor_buffer, DescriptorSetUpdateError, WriteDescriptorSet,
+};
 use crate::{
     device::{Device, DeviceOwned},
     macros::vulkan_enum,
     sampler::Sampler,
     shader::{DescriptorBindingRequirements, ShaderStages},
-    OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use std::{
@@ -38,6 +47,8 @@ pub struct DescriptorSetLayout {
 
     bindings: BTreeMap<u32, DescriptorSetLayoutBinding>,
     push_descriptor: bool,
+    update_after_bind_pool: bool,
+    descriptor_buffer: bool,
 
     descriptor_counts: HashMap<DescriptorType, u32>,
 }
@@ -55,6 +66,8 @@ impl DescriptorSetLayout {
         let DescriptorSetLayoutCreateInfo {
             bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             _ne: _,
         } = create_info;
 
@@ -64,6 +77,8 @@ impl DescriptorSetLayout {
             id: Self::next_id(),
             bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             descriptor_counts,
         }))
     }
@@ -83,6 +98,8 @@ impl DescriptorSetLayout {
         let DescriptorSetLayoutCreateInfo {
             bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             _ne: _,
         } = create_info;
 
@@ -101,6 +118,8 @@ impl DescriptorSetLayout {
             id: Self::next_id(),
             bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             descriptor_counts,
         })
     }
@@ -112,6 +131,8 @@ impl DescriptorSetLayout {
         let &mut DescriptorSetLayoutCreateInfo {
             ref bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             _ne: _,
         } = create_info;
 
@@ -129,6 +150,44 @@ impl DescriptorSetLayout {
             }
         }
 
+        if update_after_bind_pool {
+            if !(device.api_version() >= Version::V1_2
+                || device.enabled_extensions().ext_descriptor_indexing)
+            {
+                return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                    required_for: "`create_info.update_after_bind_pool` is set",
+                    requires_one_of: RequiresOneOf {
+                        api_version: Some(Version::V1_2),
+                        device_extensions: &["ext_descriptor_indexing"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID?
+            if push_descriptor {
+                return Err(DescriptorSetLayoutCreationError::PushDescriptorUpdateAfterBindPool);
+            }
+        }
+
+        if descriptor_buffer {
+            // VUID?
+            if !device.enabled_features().descriptor_buffer {
+                return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                    required_for: "`create_info.descriptor_buffer` is set",
+                    requires_one_of: RequiresOneOf {
+                        features: &["descriptor_buffer"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkDescriptorSetLayoutCreateInfo-flags-08002
+            if update_after_bind_pool {
+                return Err(DescriptorSetLayoutCreationError::DescriptorBufferUpdateAfterBindPool);
+            }
+        }
+
         let highest_binding_num = bindings.keys().copied().next_back();
 
         for (&binding_num, binding) in bindings.iter() {
@@ -136,6 +195,9 @@ impl DescriptorSetLayout {
                 descriptor_type,
                 descriptor_count,
                 variable_descriptor_count,
+                update_after_bind,
+                update_unused_while_pending,
+                partially_bound,
                 stages,
                 ref immutable_samplers,
                 _ne: _,
@@ -151,6 +213,60 @@ impl DescriptorSetLayout {
                 *descriptor_counts.entry(descriptor_type).or_default() += descriptor_count;
             }
 
+            if descriptor_type == DescriptorType::InlineUniformBlock {
+                // VUID-VkDescriptorSetLayoutBinding-descriptorType-04604
+                if !device.enabled_features().inline_uniform_block {
+                    return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                        required_for: "`create_info.bindings` has an element where \
+                            `descriptor_type` is `DescriptorType::InlineUniformBlock`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["inline_uniform_block"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkDescriptorSetLayoutBinding-descriptorType-02209
+                if descriptor_count % 4 != 0 {
+                    return Err(
+                        DescriptorSetLayoutCreationError::InlineUniformBlockSizeNotMultipleOf4 {
+                            binding_num,
+                        },
+                    );
+                }
+
+                // VUID-VkDescriptorSetLayoutBinding-descriptorType-02210
+                let max_inline_uniform_block_size = device
+                    .physical_device()
+                    .properties()
+                    .max_inline_uniform_block_size
+                    .unwrap_or(0);
+
+                if descriptor_count > max_inline_uniform_block_size {
+                    return Err(
+                        DescriptorSetLayoutCreationError::MaxInlineUniformBlockSizeExceeded {
+                            binding_num,
+                            provided: descriptor_count,
+                            max_supported: max_inline_uniform_block_size,
+                        },
+                    );
+                }
+            }
+
+            if descriptor_buffer {
+                // VUID-VkDescriptorSetLayoutCreateInfo-flags-08000
+                if matches!(
+                    descriptor_type,
+                    DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic
+                ) {
+                    return Err(
+                        DescriptorSetLayoutCreationError::DescriptorBufferDescriptorTypeIncompatible {
+                            binding_num,
+                        },
+                    );
+                }
+            }
+
             if push_descriptor {
                 // VUID-VkDescriptorSetLayoutCreateInfo-flags-00280
                 if matches!(
@@ -172,6 +288,15 @@ impl DescriptorSetLayout {
                         },
                     );
                 }
+
+                // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-flags-03003
+                if update_after_bind || update_unused_while_pending {
+                    return Err(
+                        DescriptorSetLayoutCreationError::PushDescriptorUpdateAfterBind {
+                            binding_num,
+                        },
+                    );
+                }
             }
 
             if !immutable_samplers.is_empty() {
@@ -252,6 +377,147 @@ impl DescriptorSetLayout {
                     );
                 }
             }
+
+            if update_after_bind {
+                let (feature_enabled, required_feature): (bool, &'static [&'static str]) =
+                    match descriptor_type {
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUniformBufferUpdateAfterBind-03005
+                        DescriptorType::UniformBuffer => (
+                            device
+                                .enabled_features()
+                                .descriptor_binding_uniform_buffer_update_after_bind,
+                            &["descriptor_binding_uniform_buffer_update_after_bind"],
+                        ),
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingSampledImageUpdateAfterBind-03006
+                        DescriptorType::Sampler
+                        | DescriptorType::CombinedImageSampler
+                        | DescriptorType::SampledImage => (
+                            device
+                                .enabled_features()
+                                .descriptor_binding_sampled_image_update_after_bind,
+                            &["descriptor_binding_sampled_image_update_after_bind"],
+                        ),
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageImageUpdateAfterBind-03007
+                        DescriptorType::StorageImage => (
+                            device
+                                .enabled_features()
+                                .descriptor_binding_storage_image_update_after_bind,
+                            &["descriptor_binding_storage_image_update_after_bind"],
+                        ),
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageBufferUpdateAfterBind-03008
+                        DescriptorType::StorageBuffer => (
+                            device
+                                .enabled_features()
+                                .descriptor_binding_storage_buffer_update_after_bind,
+                            &["descriptor_binding_storage_buffer_update_after_bind"],
+                        ),
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUniformTexelBufferUpdateAfterBind-03009
+                        DescriptorType::UniformTexelBuffer => (
+                            device
+                                .enabled_features()
+                                .descriptor_binding_uniform_texel_buffer_update_after_bind,
+                            &["descriptor_binding_uniform_texel_buffer_update_after_bind"],
+                        ),
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageTexelBufferUpdateAfterBind-03010
+                        DescriptorType::StorageTexelBuffer => (
+                            device
+                                .enabled_features()
+                                .descriptor_binding_storage_texel_buffer_update_after_bind,
+                            &["descriptor_binding_storage_texel_buffer_update_after_bind"],
+                        ),
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingInlineUniformBlockUpdateAfterBind-02211
+                        DescriptorType::InlineUniformBlock => (
+                            device
+                                .enabled_features()
+                                .descriptor_binding_inline_uniform_block_update_after_bind,
+                            &["descriptor_binding_inline_uniform_block_update_after_bind"],
+                        ),
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingAccelerationStructureUpdateAfterBind-03570
+                        DescriptorType::AccelerationStructure => (
+                            device
+                                .enabled_features()
+                                .descriptor_binding_acceleration_structure_update_after_bind,
+                            &["descriptor_binding_acceleration_structure_update_after_bind"],
+                        ),
+                        // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-None-03011
+                        DescriptorType::UniformBufferDynamic
+                        | DescriptorType::StorageBufferDynamic
+                        | DescriptorType::InputAttachment => {
+                            return Err(
+                                DescriptorSetLayoutCreationError::UpdateAfterBindDescriptorTypeIncompatible {
+                                    binding_num,
+                                },
+                            );
+                        }
+                    };
+
+                if !feature_enabled {
+                    return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                        required_for: "`create_info.bindings` has an element where \
+                            `update_after_bind` is set",
+                        requires_one_of: RequiresOneOf {
+                            features: required_feature,
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkDescriptorSetLayoutCreateInfo-flags-03000
+                if !update_after_bind_pool {
+                    return Err(
+                        DescriptorSetLayoutCreationError::UpdateAfterBindPoolNotEnabled {
+                            binding_num,
+                        },
+                    );
+                }
+            }
+
+            if update_unused_while_pending {
+                // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUpdateUnusedWhilePending-03012
+                if !device
+                    .enabled_features()
+                    .descriptor_binding_update_unused_while_pending
+                {
+                    return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                        required_for: "`create_info.bindings` has an element where \
+                            `update_unused_while_pending` is set",
+                        requires_one_of: RequiresOneOf {
+                            features: &["descriptor_binding_update_unused_while_pending"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+
+            if partially_bound {
+                // VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingPartiallyBound-03013
+                if !device.enabled_features().descriptor_binding_partially_bound {
+                    return Err(DescriptorSetLayoutCreationError::RequirementNotMet {
+                        required_for: "`create_info.bindings` has an element where \
+                            `partially_bound` is set",
+                        requires_one_of: RequiresOneOf {
+                            features: &["descriptor_binding_partially_bound"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+        }
+
+        // VUID-VkDescriptorSetLayoutCreateInfo-descriptorType-03001
+        if bindings.values().any(|binding| binding.update_after_bind) {
+            if let Some((&binding_num, _)) = bindings.iter().find(|(_, binding)| {
+                matches!(
+                    binding.descriptor_type,
+                    DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic
+                )
+            }) {
+                return Err(
+                    DescriptorSetLayoutCreationError::UpdateAfterBindDescriptorTypeIncompatible {
+                        binding_num,
+                    },
+                );
+            }
         }
 
         // VUID-VkDescriptorSetLayoutCreateInfo-flags-00281
@@ -285,6 +551,8 @@ impl DescriptorSetLayout {
         let &DescriptorSetLayoutCreateInfo {
             ref bindings,
             push_descriptor,
+            update_after_bind_pool,
+            descriptor_buffer,
             _ne: _,
         } = create_info;
 
@@ -297,6 +565,14 @@ impl DescriptorSetLayout {
             flags |= ash::vk::DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR_KHR;
         }
 
+        if update_after_bind_pool {
+            flags |= ash::vk::DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL;
+        }
+
+        if descriptor_buffer {
+            flags |= ash::vk::DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER_EXT;
+        }
+
         for (&binding_num, binding) in bindings.iter() {
             let mut binding_flags = ash::vk::DescriptorBindingFlags::empty();
 
@@ -319,6 +595,18 @@ impl DescriptorSetLayout {
                 binding_flags |= ash::vk::DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT;
             }
 
+            if binding.update_after_bind {
+                binding_flags |= ash::vk::DescriptorBindingFlags::UPDATE_AFTER_BIND;
+            }
+
+            if binding.update_unused_while_pending {
+                binding_flags |= ash::vk::DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING;
+            }
+
+            if binding.partially_bound {
+                binding_flags |= ash::vk::DescriptorBindingFlags::PARTIALLY_BOUND;
+            }
+
             // VUID-VkDescriptorSetLayoutCreateInfo-binding-00279
             // Guaranteed by BTreeMap
             bindings_vk.push(ash::vk::DescriptorSetLayoutBinding {
@@ -390,6 +678,105 @@ impl DescriptorSetLayout {
         self.push_descriptor
     }
 
+    /// Returns whether descriptor sets with this layout must be allocated from a pool that was
+    /// created with `update_after_bind` enabled.
+    #[inline]
+    pub fn update_after_bind_pool(&self) -> bool {
+        self.update_after_bind_pool
+    }
+
+    /// Returns whether descriptor sets with this layout are stored in descriptor buffers, rather
+    /// than allocated from a descriptor pool.
+    #[inline]
+    pub fn descriptor_buffer(&self) -> bool {
+        self.descriptor_buffer
+    }
+
+    /// Returns the number of bytes that a descriptor set with this layout occupies in a
+    /// descriptor buffer.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the layout was not created with `descriptor_buffer` enabled.
+    #[inline]
+    pub fn descriptor_buffer_size(&self) -> DeviceSize {
+        // VUID-vkGetDescriptorSetLayoutSizeEXT-layout-08012
+        assert!(self.descriptor_buffer);
+
+        unsafe {
+            let fns = self.device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.ext_descriptor_buffer.get_descriptor_set_layout_size_ext)(
+                self.device.handle(),
+                self.handle,
+                output.as_mut_ptr(),
+            );
+            output.assume_init()
+        }
+    }
+
+    /// Returns the offset in bytes of `binding` from the start of a descriptor set with this
+    /// layout, when it is stored in a descriptor buffer.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the layout was not created with `descriptor_buffer` enabled.
+    /// - Panics if `binding` does not exist in the layout.
+    #[inline]
+    pub fn descriptor_buffer_binding_offset(&self, binding: u32) -> DeviceSize {
+        // VUID-vkGetDescriptorSetLayoutBindingOffsetEXT-layout-08014
+        assert!(self.descriptor_buffer);
+        assert!(self.bindings.contains_key(&binding));
+
+        unsafe {
+            let fns = self.device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.ext_descriptor_buffer
+                .get_descriptor_set_layout_binding_offset_ext)(
+                self.device.handle(),
+                self.handle,
+                binding,
+                output.as_mut_ptr(),
+            );
+            output.assume_init()
+        }
+    }
+
+    /// Writes descriptors into `data`, which holds the contents of a descriptor set with this
+    /// layout in a descriptor buffer.
+    ///
+    /// `data` must start at the beginning of the descriptor set, and would usually be obtained by
+    /// writing to a buffer that has the [`SAMPLER_DESCRIPTOR_BUFFER`] or
+    /// [`RESOURCE_DESCRIPTOR_BUFFER`] usage. Descriptors of buffers are written using their
+    /// device address, so these buffers must have the [`SHADER_DEVICE_ADDRESS`] usage.
+    ///
+    /// Unlike with regular descriptor sets, the resources that are written are not kept alive and
+    /// their use is not tracked by command buffers.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the layout was not created with `descriptor_buffer` enabled.
+    /// - Panics if `data` is too small to hold the descriptors being written.
+    ///
+    /// [`SAMPLER_DESCRIPTOR_BUFFER`]: crate::buffer::BufferUsage::SAMPLER_DESCRIPTOR_BUFFER
+    /// [`RESOURCE_DESCRIPTOR_BUFFER`]: crate::buffer::BufferUsage::RESOURCE_DESCRIPTOR_BUFFER
+    /// [`SHADER_DEVICE_ADDRESS`]: crate::buffer::BufferUsage::SHADER_DEVICE_ADDRESS
+    pub fn write_descriptor_buffer(
+        &self,
+        data: &mut [u8],
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<(), DescriptorSetUpdateError> {
+        assert!(self.descriptor_buffer);
+
+        for write in descriptor_writes {
+            let layout_binding =
+                check_descriptor_write(&write, self, self.variable_descriptor_count())?;
+            write_descriptor_buffer(self, layout_binding, &write, data)?;
+        }
+
+        Ok(())
+    }
+
     /// Returns the number of descriptors of each type.
     ///
     /// The map is guaranteed to not contain any elements with a count of `0`.
@@ -423,7 +810,10 @@ impl DescriptorSetLayout {
     #[inline]
     pub fn is_compatible_with(&self, other: &DescriptorSetLayout) -> bool {
         self == other
-            || (self.bindings == other.bindings && self.push_descriptor == other.push_descriptor)
+            || (self.bindings == other.bindings
+                && self.push_descriptor == other.push_descriptor
+                && self.update_after_bind_pool == other.update_after_bind_pool
+                && self.descriptor_buffer == other.descriptor_buffer)
     }
 }
 
@@ -477,9 +867,30 @@ pub enum DescriptorSetLayoutCreationError {
         descriptor_count: u32,
     },
 
+    /// `descriptor_buffer` is enabled, but a binding has a `descriptor_type` that is not
+    /// compatible with descriptor buffers.
+    DescriptorBufferDescriptorTypeIncompatible { binding_num: u32 },
+
+    /// `descriptor_buffer` and `update_after_bind_pool` are both enabled.
+    DescriptorBufferUpdateAfterBindPool,
+
     /// A binding includes immutable samplers but it has an incompatible `descriptor_type`.
     ImmutableSamplersDescriptorTypeIncompatible { binding_num: u32 },
 
+    /// A binding has a `descriptor_type` of [`DescriptorType::InlineUniformBlock`], but its
+    /// `descriptor_count` is not a multiple of 4.
+    InlineUniformBlockSizeNotMultipleOf4 { binding_num: u32 },
+
+    /// A binding has a `descriptor_type` of [`DescriptorType::InlineUniformBlock`], but its
+    /// `descriptor_count` is greater than the
+    /// [`max_inline_uniform_block_size`](crate::device::Properties::max_inline_uniform_block_size)
+    /// limit.
+    MaxInlineUniformBlockSizeExceeded {
+        binding_num: u32,
+        provided: u32,
+        max_supported: u32,
+    },
+
     /// More descriptors were provided in all bindings than the
     /// [`max_push_descriptors`](crate::device::Properties::max_push_descriptors) limit.
     MaxPushDescriptorsExceeded { provided: u32, max_supported: u32 },
@@ -487,9 +898,23 @@ pub enum DescriptorSetLayoutCreationError {
     /// `push_descriptor` is enabled, but a binding has an incompatible `descriptor_type`.
     PushDescriptorDescriptorTypeIncompatible { binding_num: u32 },
 
+    /// `push_descriptor` is enabled, but a binding has `update_after_bind` or
+    /// `update_unused_while_pending` enabled.
+    PushDescriptorUpdateAfterBind { binding_num: u32 },
+
+    /// `push_descriptor` and `update_after_bind_pool` are both enabled.
+    PushDescriptorUpdateAfterBindPool,
+
     /// `push_descriptor` is enabled, but a binding has `variable_descriptor_count` enabled.
     PushDescriptorVariableDescriptorCount { binding_num: u32 },
 
+    /// A binding has `update_after_bind` enabled, but it has an incompatible `descriptor_type`,
+    /// or there is another binding with a dynamic buffer `descriptor_type`.
+    UpdateAfterBindDescriptorTypeIncompatible { binding_num: u32 },
+
+    /// A binding has `update_after_bind` enabled, but `update_after_bind_pool` is not enabled.
+    UpdateAfterBindPoolNotEnabled { binding_num: u32 },
+
     /// A binding has `variable_descriptor_count` enabled, but it is not the highest-numbered
     /// binding.
     VariableDescriptorCountBindingNotHighest {
@@ -534,12 +959,39 @@ impl Display for DescriptorSetLayoutCreationError {
                 `descriptor_count` ({})",
                 binding_num, sampler_count, descriptor_count,
             ),
+            Self::DescriptorBufferDescriptorTypeIncompatible { binding_num } => write!(
+                f,
+                "`descriptor_buffer` is enabled, but binding {} has a `descriptor_type` that is \
+                not compatible with descriptor buffers",
+                binding_num,
+            ),
+            Self::DescriptorBufferUpdateAfterBindPool => write!(
+                f,
+                "`descriptor_buffer` and `update_after_bind_pool` are both enabled",
+            ),
             Self::ImmutableSamplersDescriptorTypeIncompatible { binding_num } => write!(
                 f,
                 "binding {} includes immutable samplers but it has an incompatible \
                 `descriptor_type`",
                 binding_num,
             ),
+            Self::InlineUniformBlockSizeNotMultipleOf4 { binding_num } => write!(
+                f,
+                "binding {} has a `descriptor_type` of `DescriptorType::InlineUniformBlock`, but \
+                its `descriptor_count` is not a multiple of 4",
+                binding_num,
+            ),
+            Self::MaxInlineUniformBlockSizeExceeded {
+                binding_num,
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "binding {} has a `descriptor_type` of `DescriptorType::InlineUniformBlock`, but \
+                its `descriptor_count` ({}) is greater than the `max_inline_uniform_block_size` \
+                limit ({})",
+                binding_num, provided, max_supported,
+            ),
             Self::MaxPushDescriptorsExceeded {
                 provided,
                 max_supported,
@@ -555,12 +1007,35 @@ impl Display for DescriptorSetLayoutCreationError {
                 `descriptor_type`",
                 binding_num,
             ),
+            Self::PushDescriptorUpdateAfterBind { binding_num } => write!(
+                f,
+                "`push_descriptor` is enabled, but binding {} has `update_after_bind` or \
+                `update_unused_while_pending` enabled",
+                binding_num,
+            ),
+            Self::PushDescriptorUpdateAfterBindPool => write!(
+                f,
+                "`push_descriptor` and `update_after_bind_pool` are both enabled",
+            ),
             Self::PushDescriptorVariableDescriptorCount { binding_num } => write!(
                 f,
                 "`push_descriptor` is enabled, but binding {} has `variable_descriptor_count` \
                 enabled",
                 binding_num,
             ),
+            Self::UpdateAfterBindDescriptorTypeIncompatible { binding_num } => write!(
+                f,
+                "binding {} has `update_after_bind` enabled, but it has an incompatible \
+                `descriptor_type`, or there is another binding with a dynamic buffer \
+                `descriptor_type`",
+                binding_num,
+            ),
+            Self::UpdateAfterBindPoolNotEnabled { binding_num } => write!(
+                f,
+                "binding {} has `update_after_bind` enabled, but `update_after_bind_pool` is not \
+                enabled",
+                binding_num,
+            ),
             Self::VariableDescriptorCountBindingNotHighest {
                 binding_num,
                 highest_binding_num,
@@ -617,6 +1092,37 @@ pub struct DescriptorSetLayoutCreateInfo {
     /// The default value is `false`.
     pub push_descriptor: bool,
 
+    /// Whether descriptor sets with this layout must be allocated from a descriptor pool that was
+    /// created with [`update_after_bind`] enabled.
+    ///
+    /// This must be `true` if any binding has `update_after_bind` enabled. If set to `true`, the
+    /// device API version must be at least 1.2, or the [`ext_descriptor_indexing`] extension must
+    /// be enabled on the device, and `push_descriptor` must be `false`.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`update_after_bind`]: crate::descriptor_set::pool::DescriptorPoolCreateInfo::update_after_bind
+    /// [`ext_descriptor_indexing`]: crate::device::DeviceExtensions::ext_descriptor_indexing
+    pub update_after_bind_pool: bool,
+
+    /// Whether descriptor sets with this layout are stored in descriptor buffers, rather than
+    /// allocated from a descriptor pool.
+    ///
+    /// Descriptor data is written into a descriptor buffer with
+    /// [`DescriptorSetLayout::write_descriptor_buffer`], and bound with
+    /// [`set_descriptor_buffer_offsets`]. If any set layout of a pipeline layout has this enabled,
+    /// then all of them must.
+    ///
+    /// If set to `true`, the [`descriptor_buffer`] feature must be enabled on the device,
+    /// `update_after_bind_pool` must be `false`, and there must be no bindings with a type of
+    /// [`DescriptorType::UniformBufferDynamic`] or [`DescriptorType::StorageBufferDynamic`].
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`set_descriptor_buffer_offsets`]: crate::command_buffer::AutoCommandBufferBuilder::set_descriptor_buffer_offsets
+    /// [`descriptor_buffer`]: crate::device::Features::descriptor_buffer
+    pub descriptor_buffer: bool,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -626,6 +1132,8 @@ impl Default for DescriptorSetLayoutCreateInfo {
         Self {
             bindings: BTreeMap::new(),
             push_descriptor: false,
+            update_after_bind_pool: false,
+            descriptor_buffer: false,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -668,6 +1176,9 @@ pub struct DescriptorSetLayoutBinding {
     ///
     /// If the binding is a single element rather than an array, then you must specify `1`.
     ///
+    /// If `descriptor_type` is [`DescriptorType::InlineUniformBlock`], then this is instead the
+    /// size of the block in bytes, which must be a multiple of 4.
+    ///
     /// The default value is `1`.
     pub descriptor_count: u32,
 
@@ -686,6 +1197,40 @@ pub struct DescriptorSetLayoutBinding {
     /// [`descriptor_binding_variable_descriptor_count`]: crate::device::Features::descriptor_binding_variable_descriptor_count
     pub variable_descriptor_count: bool,
 
+    /// Whether the descriptors in this binding can be updated after a descriptor set has been
+    /// bound in a command buffer, without invalidating the command buffer. The command buffer
+    /// will use the most recent descriptors when it is submitted.
+    ///
+    /// If set to `true`, the layout must have `update_after_bind_pool` enabled, and the
+    /// `descriptor_binding_*_update_after_bind` feature corresponding to `descriptor_type` must be
+    /// enabled. The `descriptor_type` must not be [`DescriptorType::UniformBufferDynamic`],
+    /// [`DescriptorType::StorageBufferDynamic`] or [`DescriptorType::InputAttachment`], and no
+    /// other binding in the layout may have a dynamic buffer type.
+    ///
+    /// The default value is `false`.
+    pub update_after_bind: bool,
+
+    /// Whether the descriptors in this binding that are not used by a command buffer can be
+    /// updated while that command buffer is pending execution.
+    ///
+    /// If set to `true`, the [`descriptor_binding_update_unused_while_pending`] feature must be
+    /// enabled.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`descriptor_binding_update_unused_while_pending`]: crate::device::Features::descriptor_binding_update_unused_while_pending
+    pub update_unused_while_pending: bool,
+
+    /// Whether descriptors in this binding that are not dynamically used by a shader are allowed
+    /// to be left unwritten.
+    ///
+    /// If set to `true`, the [`descriptor_binding_partially_bound`] feature must be enabled.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`descriptor_binding_partially_bound`]: crate::device::Features::descriptor_binding_partially_bound
+    pub partially_bound: bool,
+
     /// Which shader stages are going to access the descriptors in this binding.
     ///
     /// The default value is [`ShaderStages::empty()`], which must be overridden.
@@ -713,6 +1258,9 @@ impl DescriptorSetLayoutBinding {
             descriptor_type,
             descriptor_count: 1,
             variable_descriptor_count: false,
+            update_after_bind: false,
+            update_unused_while_pending: false,
+            partially_bound: false,
             stages: ShaderStages::empty(),
             immutable_samplers: Vec::new(),
             _ne: crate::NonExhaustive(()),
@@ -771,6 +1319,9 @@ impl From<&DescriptorBindingRequirements> for DescriptorSetLayoutBinding {
             descriptor_type: reqs.descriptor_types[0],
             descriptor_count: reqs.descriptor_count.unwrap_or(0),
             variable_descriptor_count: false,
+            update_after_bind: false,
+            update_unused_while_pending: false,
+            partially_bound: false,
             stages: reqs.stages,
             immutable_samplers: Vec::new(),
             _ne: crate::NonExhaustive(()),
@@ -866,18 +1417,22 @@ vulkan_enum! {
     /// pixel that is currently being processed by the fragment shader.
     InputAttachment = INPUT_ATTACHMENT,
 
-    /* TODO: enable
-    // TODO: document
+    /// Gives read-only access to data that is stored directly in the descriptor set, rather than
+    /// in a separate buffer. The data is accessed in a shader like a uniform buffer.
+    ///
+    /// For this descriptor type, the `descriptor_count` of the binding is the size of the data in
+    /// bytes, rather than a number of descriptors.
     InlineUniformBlock = INLINE_UNIFORM_BLOCK {
         api_version: V1_3,
         device_extensions: [ext_inline_uniform_block],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// Gives access to an
+    /// [`AccelerationStructure`](crate::acceleration_structure::AccelerationStructure), which can
+    /// be used to trace rays with ray queries or a ray tracing pipeline.
     AccelerationStructure = ACCELERATION_STRUCTURE_KHR {
         device_extensions: [khr_acceleration_structure],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -909,7 +1464,7 @@ mod tests {
     use crate::{
         descriptor_set::layout::{
             DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
-            DescriptorType,
+            DescriptorSetLayoutCreationError, DescriptorType,
         },
         shader::ShaderStages,
     };
@@ -948,4 +1503,99 @@ mod tests {
                 .collect::<HashMap<_, _>>(),
         );
     }
+
+    #[test]
+    fn update_after_bind_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device
+            .enabled_features()
+            .descriptor_binding_sampled_image_update_after_bind
+        {
+            return;
+        }
+
+        assert!(matches!(
+            DescriptorSetLayout::new(
+                device,
+                DescriptorSetLayoutCreateInfo {
+                    bindings: [(
+                        0,
+                        DescriptorSetLayoutBinding {
+                            descriptor_count: 16,
+                            update_after_bind: true,
+                            stages: ShaderStages::FRAGMENT,
+                            ..DescriptorSetLayoutBinding::descriptor_type(
+                                DescriptorType::SampledImage
+                            )
+                        },
+                    )]
+                    .into(),
+                    update_after_bind_pool: true,
+                    ..Default::default()
+                },
+            ),
+            Err(DescriptorSetLayoutCreationError::RequirementNotMet { .. })
+        ));
+    }
+
+    #[test]
+    fn inline_uniform_block_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.enabled_features().inline_uniform_block {
+            return;
+        }
+
+        assert!(matches!(
+            DescriptorSetLayout::new(
+                device,
+                DescriptorSetLayoutCreateInfo {
+                    bindings: [(
+                        0,
+                        DescriptorSetLayoutBinding {
+                            descriptor_count: 64,
+                            stages: ShaderStages::FRAGMENT,
+                            ..DescriptorSetLayoutBinding::descriptor_type(
+                                DescriptorType::InlineUniformBlock
+                            )
+                        },
+                    )]
+                    .into(),
+                    ..Default::default()
+                },
+            ),
+            Err(DescriptorSetLayoutCreationError::RequirementNotMet { .. })
+        ));
+    }
+
+    #[test]
+    fn descriptor_buffer_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.enabled_features().descriptor_buffer {
+            return;
+        }
+
+        assert!(matches!(
+            DescriptorSetLayout::new(
+                device,
+                DescriptorSetLayoutCreateInfo {
+                    bindings: [(
+                        0,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::FRAGMENT,
+                            ..DescriptorSetLayoutBinding::descriptor_type(
+                                DescriptorType::UniformBuffer
+                            )
+                        },
+                    )]
+                    .into(),
+                    descriptor_buffer: true,
+                    ..Default::default()
+                },
+            ),
+            Err(DescriptorSetLayoutCreationError::RequirementNotMet { .. })
+        ));
+    }
 }
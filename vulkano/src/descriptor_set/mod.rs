@@ -73,6 +73,8 @@
 //!   them.
 //! - The [`DescriptorSetsCollection`] trait is implemented on collections of types that implement
 //!   [`DescriptorSet`]. It is what you pass to the draw functions.
+//! - The [`DescriptorSetCache`] type returns existing descriptor sets when the same layout and
+//!   resources are requested again, instead of creating a new set every time.
 //!
 //! [`DescriptorPool`]: pool::DescriptorPool
 //! [`DescriptorSetAllocator`]: allocator::DescriptorSetAllocator
@@ -80,12 +82,14 @@
 
 pub(crate) use self::update::{check_descriptor_write, DescriptorWriteInfo};
 pub use self::{
+    cache::DescriptorSetCache,
     collection::DescriptorSetsCollection,
     persistent::PersistentDescriptorSet,
     update::{DescriptorSetUpdateError, WriteDescriptorSet, WriteDescriptorSetElements},
 };
 use self::{layout::DescriptorSetLayout, sys::UnsafeDescriptorSet};
 use crate::{
+    acceleration_structure::AccelerationStructure,
     buffer::{view::BufferViewAbstract, BufferAccess},
     descriptor_set::layout::DescriptorType,
     device::DeviceOwned,
@@ -94,6 +98,7 @@ use crate::{
     OomError, VulkanObject,
 };
 use ahash::HashMap;
+use parking_lot::{RwLock, RwLockReadGuard};
 use smallvec::{smallvec, SmallVec};
 use std::{
     error::Error,
@@ -104,6 +109,7 @@ use std::{
 };
 
 pub mod allocator;
+pub mod cache;
 mod collection;
 pub mod layout;
 pub mod persistent;
@@ -133,7 +139,7 @@ pub unsafe trait DescriptorSet: DeviceOwned + Send + Sync {
     }
 
     /// Returns the resources bound to this descriptor set.
-    fn resources(&self) -> &DescriptorSetResources;
+    fn resources(&self) -> RwLockReadGuard<'_, DescriptorSetResources>;
 }
 
 impl PartialEq for dyn DescriptorSet {
@@ -153,7 +159,8 @@ impl Hash for dyn DescriptorSet {
 
 pub(crate) struct DescriptorSetInner {
     layout: Arc<DescriptorSetLayout>,
-    resources: DescriptorSetResources,
+    variable_descriptor_count: u32,
+    resources: RwLock<DescriptorSetResources>,
 }
 
 impl DescriptorSetInner {
@@ -179,65 +186,112 @@ impl DescriptorSetInner {
             max_count,
         );
 
-        let mut resources = DescriptorSetResources::new(&layout, variable_descriptor_count);
+        let resources = DescriptorSetResources::new(&layout, variable_descriptor_count);
+        let inner = DescriptorSetInner {
+            layout,
+            variable_descriptor_count,
+            resources: RwLock::new(resources),
+        };
+
+        // Safe because the descriptor set was just allocated, so it can't be in use.
+        unsafe { inner.update(handle, descriptor_writes)? };
 
-        let descriptor_writes = descriptor_writes.into_iter();
-        let (lower_size_bound, _) = descriptor_writes.size_hint();
-        let mut descriptor_write_info: SmallVec<[_; 8]> = SmallVec::with_capacity(lower_size_bound);
-        let mut write_descriptor_set: SmallVec<[_; 8]> = SmallVec::with_capacity(lower_size_bound);
+        Ok(inner)
+    }
 
-        for write in descriptor_writes {
+    /// Writes to the descriptor set with the given `handle`, after checking all the writes.
+    ///
+    /// # Safety
+    ///
+    /// - See [`PersistentDescriptorSet::update`].
+    pub(crate) unsafe fn update(
+        &self,
+        handle: ash::vk::DescriptorSet,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<(), DescriptorSetUpdateError> {
+        let descriptor_writes: SmallVec<[_; 8]> = descriptor_writes.into_iter().collect();
+        let mut descriptor_write_info: SmallVec<[_; 8]> =
+            SmallVec::with_capacity(descriptor_writes.len());
+        let mut write_descriptor_set: SmallVec<[_; 8]> =
+            SmallVec::with_capacity(descriptor_writes.len());
+
+        for write in &descriptor_writes {
             let layout_binding =
-                check_descriptor_write(&write, &layout, variable_descriptor_count)?;
+                check_descriptor_write(write, &self.layout, self.variable_descriptor_count)?;
 
-            resources.update(&write);
             descriptor_write_info.push(write.to_vulkan_info(layout_binding.descriptor_type));
             write_descriptor_set.push(write.to_vulkan(handle, layout_binding.descriptor_type));
         }
 
-        if !write_descriptor_set.is_empty() {
-            for (info, write) in descriptor_write_info
+        if write_descriptor_set.is_empty() {
+            return Ok(());
+        }
+
+        let acceleration_structure_infos: SmallVec<[_; 8]> = descriptor_write_info
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_acceleration_structure)
+            .collect();
+        let inline_uniform_block_infos: SmallVec<[_; 8]> = descriptor_write_info
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_inline_uniform_block)
+            .collect();
+
+        for (((info, write), acceleration_structure_info), inline_uniform_block_info) in
+            descriptor_write_info
                 .iter()
                 .zip(write_descriptor_set.iter_mut())
-            {
-                match info {
-                    DescriptorWriteInfo::Image(info) => {
-                        write.descriptor_count = info.len() as u32;
-                        write.p_image_info = info.as_ptr();
-                    }
-                    DescriptorWriteInfo::Buffer(info) => {
-                        write.descriptor_count = info.len() as u32;
-                        write.p_buffer_info = info.as_ptr();
-                    }
-                    DescriptorWriteInfo::BufferView(info) => {
-                        write.descriptor_count = info.len() as u32;
-                        write.p_texel_buffer_view = info.as_ptr();
-                    }
+                .zip(&acceleration_structure_infos)
+                .zip(&inline_uniform_block_infos)
+        {
+            match info {
+                DescriptorWriteInfo::Image(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_image_info = info.as_ptr();
+                }
+                DescriptorWriteInfo::Buffer(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_buffer_info = info.as_ptr();
+                }
+                DescriptorWriteInfo::BufferView(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_texel_buffer_view = info.as_ptr();
+                }
+                DescriptorWriteInfo::AccelerationStructure(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_next = acceleration_structure_info as *const _ as *const _;
+                }
+                DescriptorWriteInfo::InlineUniformBlock(data) => {
+                    write.descriptor_count = data.len() as u32;
+                    write.p_next = inline_uniform_block_info as *const _ as *const _;
                 }
             }
         }
 
-        unsafe {
-            let fns = layout.device().fns();
+        // Hold the lock while writing, so that the resources always match what was written.
+        let mut resources = self.resources.write();
 
-            (fns.v1_0.update_descriptor_sets)(
-                layout.device().handle(),
-                write_descriptor_set.len() as u32,
-                write_descriptor_set.as_ptr(),
-                0,
-                ptr::null(),
-            );
+        let fns = self.layout.device().fns();
+        (fns.v1_0.update_descriptor_sets)(
+            self.layout.device().handle(),
+            write_descriptor_set.len() as u32,
+            write_descriptor_set.as_ptr(),
+            0,
+            ptr::null(),
+        );
+
+        for write in &descriptor_writes {
+            resources.update(write);
         }
 
-        Ok(DescriptorSetInner { layout, resources })
+        Ok(())
     }
 
     pub(crate) fn layout(&self) -> &Arc<DescriptorSetLayout> {
         &self.layout
     }
 
-    pub(crate) fn resources(&self) -> &DescriptorSetResources {
-        &self.resources
+    pub(crate) fn resources(&self) -> RwLockReadGuard<'_, DescriptorSetResources> {
+        self.resources.read()
     }
 }
 
@@ -299,6 +353,14 @@ impl DescriptorSetResources {
                             DescriptorBindingResources::None(smallvec![Some(()); count])
                         }
                     }
+                    DescriptorType::AccelerationStructure => {
+                        DescriptorBindingResources::AccelerationStructure(smallvec![None; count])
+                    }
+                    DescriptorType::InlineUniformBlock => {
+                        // The data of an inline uniform block is merely undefined if it hasn't
+                        // been written, so it is considered valid from the start.
+                        DescriptorBindingResources::None(smallvec![Some(()); count])
+                    }
                 };
                 (binding_num, binding_resources)
             })
@@ -338,6 +400,7 @@ pub enum DescriptorBindingResources {
     ImageView(Elements<Arc<dyn ImageViewAbstract>>),
     ImageViewSampler(Elements<(Arc<dyn ImageViewAbstract>, Arc<Sampler>)>),
     Sampler(Elements<Arc<Sampler>>),
+    AccelerationStructure(Elements<Arc<AccelerationStructure>>),
 }
 
 type Elements<T> = SmallVec<[Option<T>; 1]>;
@@ -397,6 +460,14 @@ impl DescriptorBindingResources {
                 DescriptorBindingResources::Sampler(resources),
                 WriteDescriptorSetElements::Sampler(elements),
             ) => write_resources(first, resources, elements),
+            (
+                DescriptorBindingResources::AccelerationStructure(resources),
+                WriteDescriptorSetElements::AccelerationStructure(elements),
+            ) => write_resources(first, resources, elements),
+            (
+                DescriptorBindingResources::None(_),
+                WriteDescriptorSetElements::InlineUniformBlock(_),
+            ) => (),
             _ => panic!(
                 "descriptor write for binding {} has wrong resource type",
                 write.binding(),
@@ -26,11 +26,12 @@ use crate::{
         allocator::{DescriptorSetAlloc, DescriptorSetAllocator, StandardDescriptorSetAlloc},
         update::WriteDescriptorSet,
         DescriptorSet, DescriptorSetCreationError, DescriptorSetInner, DescriptorSetLayout,
-        DescriptorSetResources, UnsafeDescriptorSet,
+        DescriptorSetResources, DescriptorSetUpdateError, UnsafeDescriptorSet,
     },
     device::{Device, DeviceOwned},
     VulkanObject,
 };
+use parking_lot::RwLockReadGuard;
 use std::{
     hash::{Hash, Hasher},
     sync::Arc,
@@ -102,6 +103,37 @@ impl PersistentDescriptorSet {
     }
 }
 
+impl<P> PersistentDescriptorSet<P>
+where
+    P: DescriptorSetAlloc,
+{
+    /// Writes to the descriptor set after it has been created.
+    ///
+    /// This is mainly useful for large arrays of descriptors, such as the ones used for bindless
+    /// rendering, where only a few elements change at a time. Bindings whose layout has
+    /// `update_after_bind` or `update_unused_while_pending` enabled can be updated while the
+    /// descriptor set is bound in a command buffer, or while such a command buffer is executing.
+    ///
+    /// # Safety
+    ///
+    /// - If a binding that is written doesn't have `update_after_bind` or
+    ///   `update_unused_while_pending` enabled in the layout, then the descriptor set must not be
+    ///   bound in any command buffer that is in the recording or executable state, or that is
+    ///   pending execution.
+    /// - If the descriptor set is bound in a command buffer that is pending execution, then the
+    ///   descriptors that are written must not be used by that command buffer.
+    /// - Command buffers that were recorded before this call do not keep track of the resources
+    ///   that are written here. You must make sure that these resources are properly
+    ///   synchronized with any command buffer that may access them.
+    pub unsafe fn update(
+        &self,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<(), DescriptorSetUpdateError> {
+        self.inner
+            .update(self.alloc.inner().handle(), descriptor_writes)
+    }
+}
+
 unsafe impl<P> DescriptorSet for PersistentDescriptorSet<P>
 where
     P: DescriptorSetAlloc,
@@ -114,11 +146,23 @@ where
         self.inner.layout()
     }
 
-    fn resources(&self) -> &DescriptorSetResources {
+    fn resources(&self) -> RwLockReadGuard<'_, DescriptorSetResources> {
         self.inner.resources()
     }
 }
 
+unsafe impl<P> VulkanObject for PersistentDescriptorSet<P>
+where
+    P: DescriptorSetAlloc,
+{
+    type Handle = ash::vk::DescriptorSet;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.alloc.inner().handle()
+    }
+}
+
 unsafe impl<P> DeviceOwned for PersistentDescriptorSet<P>
 where
     P: DescriptorSetAlloc,
@@ -41,6 +41,8 @@ pub struct DescriptorPool {
     max_sets: u32,
     pool_sizes: HashMap<DescriptorType, u32>,
     can_free_descriptor_sets: bool,
+    update_after_bind: bool,
+    max_inline_uniform_block_bindings: u32,
     // Unimplement `Sync`, as Vulkan descriptor pools are not thread safe.
     _marker: PhantomData<Cell<ash::vk::DescriptorPool>>,
 }
@@ -53,6 +55,13 @@ impl DescriptorPool {
     /// - Panics if `create_info.max_sets` is `0`.
     /// - Panics if `create_info.pool_sizes` is empty.
     /// - Panics if `create_info.pool_sizes` contains a descriptor type with a count of `0`.
+    /// - Panics if `create_info.update_after_bind` is `true`, but the device API version is less
+    ///   than 1.2 and the [`ext_descriptor_indexing`] extension is not enabled.
+    /// - Panics if `create_info.max_inline_uniform_block_bindings` is not `0`, but the device API
+    ///   version is less than 1.3 and the [`ext_inline_uniform_block`] extension is not enabled.
+    ///
+    /// [`ext_descriptor_indexing`]: crate::device::DeviceExtensions::ext_descriptor_indexing
+    /// [`ext_inline_uniform_block`]: crate::device::DeviceExtensions::ext_inline_uniform_block
     pub fn new(
         device: Arc<Device>,
         create_info: DescriptorPoolCreateInfo,
@@ -61,6 +70,8 @@ impl DescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
+            max_inline_uniform_block_bindings,
             _ne: _,
         } = create_info;
 
@@ -70,6 +81,24 @@ impl DescriptorPool {
         // VUID-VkDescriptorPoolCreateInfo-poolSizeCount-arraylength
         assert!(!pool_sizes.is_empty());
 
+        // VUID-VkDescriptorPoolCreateInfo-flags-parameter
+        assert!(
+            !update_after_bind
+                || device.api_version() >= Version::V1_2
+                || device.enabled_extensions().ext_descriptor_indexing,
+            "`create_info.update_after_bind` was set, but the device API version is less than 1.2 \
+            and the `ext_descriptor_indexing` extension is not enabled",
+        );
+
+        // VUID-VkDescriptorPoolCreateInfo-pNext-pNext
+        assert!(
+            max_inline_uniform_block_bindings == 0
+                || device.api_version() >= Version::V1_3
+                || device.enabled_extensions().ext_inline_uniform_block,
+            "`create_info.max_inline_uniform_block_bindings` was not 0, but the device API version \
+            is less than 1.3 and the `ext_inline_uniform_block` extension is not enabled",
+        );
+
         let handle = {
             let pool_sizes: SmallVec<[_; 8]> = pool_sizes
                 .iter()
@@ -90,20 +119,37 @@ impl DescriptorPool {
                 flags |= ash::vk::DescriptorPoolCreateFlags::FREE_DESCRIPTOR_SET;
             }
 
-            let create_info = ash::vk::DescriptorPoolCreateInfo {
+            if update_after_bind {
+                flags |= ash::vk::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND;
+            }
+
+            let mut create_info_vk = ash::vk::DescriptorPoolCreateInfo {
                 flags,
                 max_sets,
                 pool_size_count: pool_sizes.len() as u32,
                 p_pool_sizes: pool_sizes.as_ptr(),
                 ..Default::default()
             };
+            let mut inline_uniform_block_create_info_vk = None;
+
+            if max_inline_uniform_block_bindings != 0 {
+                let next = inline_uniform_block_create_info_vk.insert(
+                    ash::vk::DescriptorPoolInlineUniformBlockCreateInfo {
+                        max_inline_uniform_block_bindings,
+                        ..Default::default()
+                    },
+                );
+
+                next.p_next = create_info_vk.p_next;
+                create_info_vk.p_next = next as *const _ as *const _;
+            }
 
             unsafe {
                 let fns = device.fns();
                 let mut output = MaybeUninit::uninit();
                 (fns.v1_0.create_descriptor_pool)(
                     device.handle(),
-                    &create_info,
+                    &create_info_vk,
                     ptr::null(),
                     output.as_mut_ptr(),
                 )
@@ -120,6 +166,8 @@ impl DescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
+            max_inline_uniform_block_bindings,
             _marker: PhantomData,
         })
     }
@@ -140,6 +188,8 @@ impl DescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
+            max_inline_uniform_block_bindings,
             _ne: _,
         } = create_info;
 
@@ -150,6 +200,8 @@ impl DescriptorPool {
             max_sets,
             pool_sizes,
             can_free_descriptor_sets,
+            update_after_bind,
+            max_inline_uniform_block_bindings,
             _marker: PhantomData,
         }
     }
@@ -172,6 +224,20 @@ impl DescriptorPool {
         self.can_free_descriptor_sets
     }
 
+    /// Returns whether descriptor sets with a layout that has `update_after_bind_pool` enabled
+    /// can be allocated from the pool.
+    #[inline]
+    pub fn update_after_bind(&self) -> bool {
+        self.update_after_bind
+    }
+
+    /// Returns the maximum number of inline uniform block bindings that descriptor sets allocated
+    /// from the pool can contain in total.
+    #[inline]
+    pub fn max_inline_uniform_block_bindings(&self) -> u32 {
+        self.max_inline_uniform_block_bindings
+    }
+
     /// Allocates descriptor sets from the pool, one for each element in `create_info`.
     /// Returns an iterator to the allocated sets, or an error.
     ///
@@ -181,6 +247,9 @@ impl DescriptorPool {
     /// # Panics
     ///
     /// - Panics if one of the layouts wasn't created with the same device as the pool.
+    /// - Panics if one of the layouts has `update_after_bind_pool` enabled, but the pool was not
+    ///   created with `update_after_bind` enabled.
+    /// - Panics if one of the layouts has `descriptor_buffer` enabled.
     ///
     /// # Safety
     ///
@@ -199,6 +268,10 @@ impl DescriptorPool {
                 .into_iter()
                 .map(|info| {
                     assert_eq!(self.device.handle(), info.layout.device().handle(),);
+                    // VUID-VkDescriptorSetAllocateInfo-pSetLayouts-03044
+                    assert!(!info.layout.update_after_bind_pool() || self.update_after_bind);
+                    // VUID-VkDescriptorSetAllocateInfo-pSetLayouts-08009
+                    assert!(!info.layout.descriptor_buffer());
                     debug_assert!(!info.layout.push_descriptor());
                     debug_assert!(
                         info.variable_descriptor_count <= info.layout.variable_descriptor_count()
@@ -367,6 +440,30 @@ pub struct DescriptorPoolCreateInfo {
     /// The default value is `false`.
     pub can_free_descriptor_sets: bool,
 
+    /// Whether descriptor sets can be allocated from the pool with a layout that has
+    /// [`update_after_bind_pool`] enabled.
+    ///
+    /// If set to `true`, the device API version must be at least 1.2, or the
+    /// [`ext_descriptor_indexing`] extension must be enabled on the device.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`update_after_bind_pool`]: crate::descriptor_set::layout::DescriptorSetLayoutCreateInfo::update_after_bind_pool
+    /// [`ext_descriptor_indexing`]: crate::device::DeviceExtensions::ext_descriptor_indexing
+    pub update_after_bind: bool,
+
+    /// The maximum number of [`DescriptorType::InlineUniformBlock`] bindings that can be
+    /// allocated from the pool, across all descriptor sets. The total size in bytes of the inline
+    /// uniform blocks is given in `pool_sizes` instead.
+    ///
+    /// If not `0`, the device API version must be at least 1.3, or the
+    /// [`ext_inline_uniform_block`] extension must be enabled on the device.
+    ///
+    /// The default value is `0`.
+    ///
+    /// [`ext_inline_uniform_block`]: crate::device::DeviceExtensions::ext_inline_uniform_block
+    pub max_inline_uniform_block_bindings: u32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -377,6 +474,8 @@ impl Default for DescriptorPoolCreateInfo {
             max_sets: 0,
             pool_sizes: HashMap::default(),
             can_free_descriptor_sets: false,
+            update_after_bind: false,
+            max_inline_uniform_block_bindings: 0,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -78,8 +78,22 @@ impl UnsafeDescriptorSet {
             return;
         }
 
+        let acceleration_structure_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_acceleration_structure)
+            .collect();
+        let inline_uniform_block_infos: SmallVec<[_; 8]> = infos
+            .iter()
+            .map(DescriptorWriteInfo::to_vulkan_inline_uniform_block)
+            .collect();
+
         // Set the info pointers separately.
-        for (info, write) in infos.iter().zip(writes.iter_mut()) {
+        for (((info, write), acceleration_structure_info), inline_uniform_block_info) in infos
+            .iter()
+            .zip(writes.iter_mut())
+            .zip(&acceleration_structure_infos)
+            .zip(&inline_uniform_block_infos)
+        {
             match info {
                 DescriptorWriteInfo::Image(info) => {
                     write.descriptor_count = info.len() as u32;
@@ -93,6 +107,14 @@ impl UnsafeDescriptorSet {
                     write.descriptor_count = info.len() as u32;
                     write.p_texel_buffer_view = info.as_ptr();
                 }
+                DescriptorWriteInfo::AccelerationStructure(info) => {
+                    write.descriptor_count = info.len() as u32;
+                    write.p_next = acceleration_structure_info as *const _ as *const _;
+                }
+                DescriptorWriteInfo::InlineUniformBlock(data) => {
+                    write.descriptor_count = data.len() as u32;
+                    write.p_next = inline_uniform_block_info as *const _ as *const _;
+                }
             }
 
             debug_assert!(write.descriptor_count != 0);
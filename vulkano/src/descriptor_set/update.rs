@@ -9,7 +9,10 @@
 
 use super::layout::{DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorType};
 use crate::{
-    buffer::{view::BufferViewAbstract, BufferAccess, BufferInner, BufferUsage},
+    acceleration_structure::{AccelerationStructure, AccelerationStructureType},
+    buffer::{
+        view::BufferViewAbstract, BufferAccess, BufferDeviceAddressError, BufferInner, BufferUsage,
+    },
     device::DeviceOwned,
     image::{view::ImageViewType, ImageAspects, ImageType, ImageUsage, ImageViewAbstract},
     sampler::{Sampler, SamplerImageViewIncompatibleError},
@@ -19,6 +22,7 @@ use smallvec::SmallVec;
 use std::{
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
+    num::NonZeroU64,
     ptr,
     sync::Arc,
 };
@@ -174,6 +178,47 @@ impl WriteDescriptorSet {
         }
     }
 
+    /// Write a single acceleration structure to array element 0.
+    #[inline]
+    pub fn acceleration_structure(
+        binding: u32,
+        acceleration_structure: Arc<AccelerationStructure>,
+    ) -> Self {
+        Self::acceleration_structure_array(binding, 0, [acceleration_structure])
+    }
+
+    /// Write a number of consecutive acceleration structure elements.
+    pub fn acceleration_structure_array(
+        binding: u32,
+        first_array_element: u32,
+        elements: impl IntoIterator<Item = Arc<AccelerationStructure>>,
+    ) -> Self {
+        let elements: SmallVec<_> = elements.into_iter().collect();
+        assert!(!elements.is_empty());
+        Self {
+            binding,
+            first_array_element,
+            elements: WriteDescriptorSetElements::AccelerationStructure(elements),
+        }
+    }
+
+    /// Write data to an inline uniform block.
+    ///
+    /// `offset` is the byte offset within the block to start writing at. Both `offset` and the
+    /// length of `data` must be a multiple of 4.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `data` is empty.
+    pub fn inline_uniform_block(binding: u32, offset: u32, data: Vec<u8>) -> Self {
+        assert!(!data.is_empty());
+        Self {
+            binding,
+            first_array_element: offset,
+            elements: WriteDescriptorSetElements::InlineUniformBlock(data),
+        }
+    }
+
     /// Returns the binding number that is updated by this descriptor write.
     #[inline]
     pub fn binding(&self) -> u32 {
@@ -318,6 +363,25 @@ impl WriteDescriptorSet {
                         .collect(),
                 )
             }
+            WriteDescriptorSetElements::AccelerationStructure(elements) => {
+                debug_assert!(matches!(
+                    descriptor_type,
+                    DescriptorType::AccelerationStructure
+                ));
+                DescriptorWriteInfo::AccelerationStructure(
+                    elements
+                        .iter()
+                        .map(|acceleration_structure| acceleration_structure.handle())
+                        .collect(),
+                )
+            }
+            WriteDescriptorSetElements::InlineUniformBlock(data) => {
+                debug_assert!(matches!(
+                    descriptor_type,
+                    DescriptorType::InlineUniformBlock
+                ));
+                DescriptorWriteInfo::InlineUniformBlock(data.clone())
+            }
         }
     }
 
@@ -348,10 +412,14 @@ pub enum WriteDescriptorSetElements {
     ImageView(SmallVec<[Arc<dyn ImageViewAbstract>; 1]>),
     ImageViewSampler(SmallVec<[(Arc<dyn ImageViewAbstract>, Arc<Sampler>); 1]>),
     Sampler(SmallVec<[Arc<Sampler>; 1]>),
+    AccelerationStructure(SmallVec<[Arc<AccelerationStructure>; 1]>),
+    InlineUniformBlock(Vec<u8>),
 }
 
 impl WriteDescriptorSetElements {
     /// Returns the number of elements.
+    ///
+    /// For an inline uniform block, this is the number of bytes.
     #[inline]
     pub fn len(&self) -> u32 {
         match self {
@@ -361,6 +429,8 @@ impl WriteDescriptorSetElements {
             Self::ImageView(elements) => elements.len() as u32,
             Self::ImageViewSampler(elements) => elements.len() as u32,
             Self::Sampler(elements) => elements.len() as u32,
+            Self::AccelerationStructure(elements) => elements.len() as u32,
+            Self::InlineUniformBlock(data) => data.len() as u32,
         }
     }
 }
@@ -370,6 +440,44 @@ pub(crate) enum DescriptorWriteInfo {
     Image(SmallVec<[ash::vk::DescriptorImageInfo; 1]>),
     Buffer(SmallVec<[ash::vk::DescriptorBufferInfo; 1]>),
     BufferView(SmallVec<[ash::vk::BufferView; 1]>),
+    AccelerationStructure(SmallVec<[ash::vk::AccelerationStructureKHR; 1]>),
+    InlineUniformBlock(Vec<u8>),
+}
+
+impl DescriptorWriteInfo {
+    /// Returns the extension struct that must be chained to the `WriteDescriptorSet` for this
+    /// info, or a default value if none is needed.
+    pub(crate) fn to_vulkan_acceleration_structure(
+        &self,
+    ) -> ash::vk::WriteDescriptorSetAccelerationStructureKHR {
+        match self {
+            DescriptorWriteInfo::AccelerationStructure(info) => {
+                ash::vk::WriteDescriptorSetAccelerationStructureKHR {
+                    acceleration_structure_count: info.len() as u32,
+                    p_acceleration_structures: info.as_ptr(),
+                    ..Default::default()
+                }
+            }
+            _ => Default::default(),
+        }
+    }
+
+    /// Returns the extension struct that must be chained to the `WriteDescriptorSet` for an
+    /// inline uniform block write, or a default value if this is not one.
+    pub(crate) fn to_vulkan_inline_uniform_block(
+        &self,
+    ) -> ash::vk::WriteDescriptorSetInlineUniformBlock {
+        match self {
+            DescriptorWriteInfo::InlineUniformBlock(data) => {
+                ash::vk::WriteDescriptorSetInlineUniformBlock {
+                    data_size: data.len() as u32,
+                    p_data: data.as_ptr() as *const _,
+                    ..Default::default()
+                }
+            }
+            _ => Default::default(),
+        }
+    }
 }
 
 pub(crate) fn check_descriptor_write<'a>(
@@ -862,11 +970,296 @@ pub(crate) fn check_descriptor_write<'a>(
                 })
             }
         },
+        WriteDescriptorSetElements::AccelerationStructure(elements) => {
+            match layout_binding.descriptor_type {
+                DescriptorType::AccelerationStructure => {
+                    for (index, acceleration_structure) in elements.iter().enumerate() {
+                        assert_eq!(device, acceleration_structure.device());
+
+                        // VUID-VkWriteDescriptorSetAccelerationStructureKHR-pAccelerationStructures-03579
+                        if !matches!(
+                            acceleration_structure.ty(),
+                            AccelerationStructureType::TopLevel
+                                | AccelerationStructureType::Generic
+                        ) {
+                            return Err(
+                                DescriptorSetUpdateError::AccelerationStructureNotTopLevel {
+                                    binding: write.binding(),
+                                    index: descriptor_range_start + index as u32,
+                                },
+                            );
+                        }
+                    }
+                }
+                _ => {
+                    return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
+                        binding: write.binding(),
+                    })
+                }
+            }
+        }
+        WriteDescriptorSetElements::InlineUniformBlock(_) => {
+            match layout_binding.descriptor_type {
+                DescriptorType::InlineUniformBlock => {
+                    // VUID-VkWriteDescriptorSet-descriptorType-02219
+                    if descriptor_range_start % 4 != 0 {
+                        return Err(
+                            DescriptorSetUpdateError::InlineUniformBlockOffsetNotAligned {
+                                binding: write.binding(),
+                                offset: descriptor_range_start,
+                            },
+                        );
+                    }
+
+                    // VUID-VkWriteDescriptorSet-descriptorType-02220
+                    if num_elements % 4 != 0 {
+                        return Err(DescriptorSetUpdateError::InlineUniformBlockSizeNotAligned {
+                            binding: write.binding(),
+                            size: num_elements,
+                        });
+                    }
+                }
+                _ => {
+                    return Err(DescriptorSetUpdateError::IncompatibleDescriptorType {
+                        binding: write.binding(),
+                    })
+                }
+            }
+        }
     }
 
     Ok(layout_binding)
 }
 
+/// Writes the descriptors of `write` into `data`, which holds the contents of a descriptor set in
+/// a descriptor buffer. The write must already have been checked with `check_descriptor_write`.
+pub(crate) fn write_descriptor_buffer(
+    layout: &DescriptorSetLayout,
+    layout_binding: &DescriptorSetLayoutBinding,
+    write: &WriteDescriptorSet,
+    data: &mut [u8],
+) -> Result<(), DescriptorSetUpdateError> {
+    let device = layout.device();
+    let properties = device.physical_device().properties();
+    let robust = device.enabled_features().robust_buffer_access;
+    let binding = write.binding();
+    let first = write.first_array_element();
+    let binding_offset = layout.descriptor_buffer_binding_offset(binding) as usize;
+
+    if let WriteDescriptorSetElements::InlineUniformBlock(bytes) = write.elements() {
+        // For inline uniform blocks, `first_array_element` is a byte offset.
+        let offset = binding_offset + first as usize;
+        data[offset..offset + bytes.len()].copy_from_slice(bytes);
+        return Ok(());
+    }
+
+    let descriptor_type = layout_binding.descriptor_type;
+    let descriptor_size = match descriptor_type {
+        DescriptorType::Sampler => properties.sampler_descriptor_size,
+        DescriptorType::CombinedImageSampler => properties.combined_image_sampler_descriptor_size,
+        DescriptorType::SampledImage => properties.sampled_image_descriptor_size,
+        DescriptorType::StorageImage => properties.storage_image_descriptor_size,
+        DescriptorType::UniformTexelBuffer if robust => {
+            properties.robust_uniform_texel_buffer_descriptor_size
+        }
+        DescriptorType::UniformTexelBuffer => properties.uniform_texel_buffer_descriptor_size,
+        DescriptorType::StorageTexelBuffer if robust => {
+            properties.robust_storage_texel_buffer_descriptor_size
+        }
+        DescriptorType::StorageTexelBuffer => properties.storage_texel_buffer_descriptor_size,
+        DescriptorType::UniformBuffer if robust => properties.robust_uniform_buffer_descriptor_size,
+        DescriptorType::UniformBuffer => properties.uniform_buffer_descriptor_size,
+        DescriptorType::StorageBuffer if robust => properties.robust_storage_buffer_descriptor_size,
+        DescriptorType::StorageBuffer => properties.storage_buffer_descriptor_size,
+        DescriptorType::InputAttachment => properties.input_attachment_descriptor_size,
+        DescriptorType::AccelerationStructure => properties.acceleration_structure_descriptor_size,
+        DescriptorType::UniformBufferDynamic
+        | DescriptorType::StorageBufferDynamic
+        | DescriptorType::InlineUniformBlock => unreachable!(),
+    }
+    .unwrap();
+
+    // If the implementation does not store combined image samplers as a single array, then the
+    // image parts of all the descriptors come first, followed by the sampler parts.
+    let split_combined_image_sampler = descriptor_type == DescriptorType::CombinedImageSampler
+        && !properties
+            .combined_image_sampler_descriptor_single_array
+            .unwrap_or(true);
+    let sampled_image_size = properties.sampled_image_descriptor_size.unwrap();
+    let sampler_size = properties.sampler_descriptor_size.unwrap();
+
+    let address_info = |address: Result<NonZeroU64, BufferDeviceAddressError>,
+                        range: DeviceSize,
+                        format: ash::vk::Format,
+                        index: u32|
+     -> Result<ash::vk::DescriptorAddressInfoEXT, DescriptorSetUpdateError> {
+        let address = address.map_err(|err| match err {
+            BufferDeviceAddressError::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => DescriptorSetUpdateError::RequirementNotMet {
+                binding,
+                index,
+                required_for,
+                requires_one_of,
+            },
+            BufferDeviceAddressError::BufferMissingUsage => {
+                DescriptorSetUpdateError::MissingUsage {
+                    binding,
+                    index,
+                    usage: "shader_device_address",
+                }
+            }
+        })?;
+
+        Ok(ash::vk::DescriptorAddressInfoEXT {
+            address: address.get(),
+            range,
+            format,
+            ..Default::default()
+        })
+    };
+
+    let mut image_infos: SmallVec<[ash::vk::DescriptorImageInfo; 1]> = SmallVec::new();
+    let mut address_infos: SmallVec<[ash::vk::DescriptorAddressInfoEXT; 1]> = SmallVec::new();
+    let mut samplers: SmallVec<[ash::vk::Sampler; 1]> = SmallVec::new();
+    let mut acceleration_structures: SmallVec<[ash::vk::DeviceAddress; 1]> = SmallVec::new();
+
+    match write.elements() {
+        WriteDescriptorSetElements::None(_) | WriteDescriptorSetElements::InlineUniformBlock(_) => {
+            return Ok(())
+        }
+        WriteDescriptorSetElements::Buffer(elements) => {
+            for (index, buffer) in (first..).zip(elements) {
+                address_infos.push(address_info(
+                    buffer.raw_device_address(),
+                    buffer.size(),
+                    ash::vk::Format::UNDEFINED,
+                    index,
+                )?);
+            }
+        }
+        WriteDescriptorSetElements::BufferView(elements) => {
+            for (index, buffer_view) in (first..).zip(elements) {
+                let range = buffer_view.range();
+                address_infos.push(address_info(
+                    buffer_view
+                        .buffer()
+                        .raw_device_address()
+                        .map(|address| NonZeroU64::new(address.get() + range.start).unwrap()),
+                    range.end - range.start,
+                    buffer_view
+                        .format()
+                        .map_or(ash::vk::Format::UNDEFINED, Into::into),
+                    index,
+                )?);
+            }
+        }
+        WriteDescriptorSetElements::ImageView(_)
+        | WriteDescriptorSetElements::ImageViewSampler(_) => {
+            if let DescriptorWriteInfo::Image(infos) = write.to_vulkan_info(descriptor_type) {
+                image_infos = infos;
+            }
+
+            // Immutable samplers must still be provided when getting the descriptor.
+            if descriptor_type == DescriptorType::CombinedImageSampler
+                && !layout_binding.immutable_samplers.is_empty()
+            {
+                for (info, sampler) in image_infos.iter_mut().zip(
+                    layout_binding
+                        .immutable_samplers
+                        .iter()
+                        .skip(first as usize),
+                ) {
+                    info.sampler = sampler.handle();
+                }
+            }
+        }
+        WriteDescriptorSetElements::Sampler(elements) => {
+            samplers.extend(elements.iter().map(|sampler| sampler.handle()));
+        }
+        WriteDescriptorSetElements::AccelerationStructure(elements) => {
+            acceleration_structures.extend(
+                elements
+                    .iter()
+                    .map(|acceleration_structure| acceleration_structure.device_address().get()),
+            );
+        }
+    }
+
+    let fns = device.fns();
+    let mut descriptor = vec![0u8; descriptor_size];
+
+    for i in 0..write.elements().len() as usize {
+        let data_vk = match descriptor_type {
+            DescriptorType::Sampler => ash::vk::DescriptorDataEXT {
+                p_sampler: &samplers[i],
+            },
+            DescriptorType::CombinedImageSampler => ash::vk::DescriptorDataEXT {
+                p_combined_image_sampler: &image_infos[i],
+            },
+            DescriptorType::SampledImage => ash::vk::DescriptorDataEXT {
+                p_sampled_image: &image_infos[i],
+            },
+            DescriptorType::StorageImage => ash::vk::DescriptorDataEXT {
+                p_storage_image: &image_infos[i],
+            },
+            DescriptorType::InputAttachment => ash::vk::DescriptorDataEXT {
+                p_input_attachment_image: &image_infos[i],
+            },
+            DescriptorType::UniformTexelBuffer => ash::vk::DescriptorDataEXT {
+                p_uniform_texel_buffer: &address_infos[i],
+            },
+            DescriptorType::StorageTexelBuffer => ash::vk::DescriptorDataEXT {
+                p_storage_texel_buffer: &address_infos[i],
+            },
+            DescriptorType::UniformBuffer => ash::vk::DescriptorDataEXT {
+                p_uniform_buffer: &address_infos[i],
+            },
+            DescriptorType::StorageBuffer => ash::vk::DescriptorDataEXT {
+                p_storage_buffer: &address_infos[i],
+            },
+            DescriptorType::AccelerationStructure => ash::vk::DescriptorDataEXT {
+                acceleration_structure: acceleration_structures[i],
+            },
+            DescriptorType::UniformBufferDynamic
+            | DescriptorType::StorageBufferDynamic
+            | DescriptorType::InlineUniformBlock => unreachable!(),
+        };
+        let info_vk = ash::vk::DescriptorGetInfoEXT {
+            ty: descriptor_type.into(),
+            data: data_vk,
+            ..Default::default()
+        };
+
+        unsafe {
+            (fns.ext_descriptor_buffer.get_descriptor_ext)(
+                device.handle(),
+                &info_vk,
+                descriptor_size,
+                descriptor.as_mut_ptr() as *mut _,
+            );
+        }
+
+        let array_index = first as usize + i;
+
+        if split_combined_image_sampler {
+            let image_offset = binding_offset + array_index * sampled_image_size;
+            let sampler_offset = binding_offset
+                + layout_binding.descriptor_count as usize * sampled_image_size
+                + array_index * sampler_size;
+            let (image_part, sampler_part) = descriptor.split_at(sampled_image_size);
+            data[image_offset..image_offset + sampled_image_size].copy_from_slice(image_part);
+            data[sampler_offset..sampler_offset + sampler_part.len()].copy_from_slice(sampler_part);
+        } else {
+            let offset = binding_offset + array_index * descriptor_size;
+            data[offset..offset + descriptor_size].copy_from_slice(&descriptor);
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Copy, Debug)]
 pub enum DescriptorSetUpdateError {
     RequirementNotMet {
@@ -886,6 +1279,9 @@ pub enum DescriptorSetUpdateError {
         written_count: u32,
     },
 
+    /// Tried to write a bottom-level acceleration structure to a binding.
+    AccelerationStructureNotTopLevel { binding: u32, index: u32 },
+
     /// Tried to write an image view with a 2D type and a 3D underlying image.
     ImageView2dFrom3d { binding: u32, index: u32 },
 
@@ -916,6 +1312,12 @@ pub enum DescriptorSetUpdateError {
     /// layout.
     IncompatibleDescriptorType { binding: u32 },
 
+    /// Tried to write to an inline uniform block at an offset that is not a multiple of 4.
+    InlineUniformBlockOffsetNotAligned { binding: u32, offset: u32 },
+
+    /// Tried to write data to an inline uniform block with a size that is not a multiple of 4.
+    InlineUniformBlockSizeNotAligned { binding: u32, size: u32 },
+
     /// Tried to write to a nonexistent binding.
     InvalidBinding { binding: u32 },
 
@@ -966,6 +1368,11 @@ impl Display for DescriptorSetUpdateError {
                 available",
                 written_count, binding, available_count,
             ),
+            Self::AccelerationStructureNotTopLevel { binding, index } => write!(
+                f,
+                "tried to write a bottom-level acceleration structure to binding {} index {}",
+                binding, index,
+            ),
             Self::ImageView2dFrom3d { binding, index } => write!(
                 f,
                 "tried to write an image view to binding {} index {} with a 2D type and a 3D \
@@ -1008,6 +1415,18 @@ impl Display for DescriptorSetUpdateError {
                 descriptor type",
                 binding,
             ),
+            Self::InlineUniformBlockOffsetNotAligned { binding, offset } => write!(
+                f,
+                "tried to write to the inline uniform block in binding {} at offset {}, which is \
+                not a multiple of 4",
+                binding, offset,
+            ),
+            Self::InlineUniformBlockSizeNotAligned { binding, size } => write!(
+                f,
+                "tried to write {} bytes to the inline uniform block in binding {}, which is not \
+                a multiple of 4",
+                size, binding,
+            ),
             Self::InvalidBinding { binding } => {
                 write!(f, "tried to write to a nonexistent binding {}", binding,)
             }
@@ -101,38 +101,56 @@
 //!
 //! TODO: write
 
-use self::physical::PhysicalDevice;
+use self::physical::{PhysicalDevice, TimeDomain};
 pub(crate) use self::{features::FeaturesFfi, properties::PropertiesFfi};
 pub use self::{
     features::{FeatureRestriction, FeatureRestrictionError, Features},
     properties::Properties,
-    queue::{Queue, QueueError, QueueFamilyProperties, QueueFlags, QueueGuard},
+    queue::{BindSparseError, Queue, QueueError, QueueFamilyProperties, QueueFlags, QueueGuard},
+};
+use crate::{
+    acceleration_structure::{
+        AccelerationStructureBuildGeometryInfo, AccelerationStructureBuildSizesInfo,
+        AccelerationStructureBuildType, AccelerationStructureError,
+    },
+    format::{Format, FormatFeatures},
+    instance::Instance,
+    macros::{vulkan_bitflags, vulkan_enum},
+    memory::{
+        report::{
+            device_memory_report_trampoline, DeviceMemoryReportCallback, MemoryReport,
+            MemoryTracker,
+        },
+        ExternalMemoryHandleType, PeerMemoryFeatures,
+    },
+    sampler::{
+        ycbcr::{ChromaLocation, SamplerYcbcrModelConversion, SamplerYcbcrRange},
+        ComponentMapping,
+    },
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 pub use crate::{
     device::extensions::DeviceExtensions,
     extensions::{ExtensionRestriction, ExtensionRestrictionError},
     fns::DeviceFunctions,
 };
-use crate::{
-    instance::Instance, memory::ExternalMemoryHandleType, OomError, RequirementNotMet,
-    RequiresOneOf, Version, VulkanError, VulkanObject,
-};
 use ash::vk::Handle;
 use parking_lot::Mutex;
-use smallvec::SmallVec;
+use smallvec::{smallvec, SmallVec};
 use std::{
     error::Error,
-    ffi::CString,
-    fmt::{Display, Error as FmtError, Formatter},
+    ffi::{c_void, CStr, CString},
+    fmt::{Debug, Display, Error as FmtError, Formatter},
     fs::File,
     mem::MaybeUninit,
     num::NonZeroU64,
     ops::Deref,
     ptr,
     sync::{
-        atomic::{AtomicU32, Ordering},
+        atomic::{AtomicBool, AtomicU32, Ordering},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
 pub(crate) mod extensions;
@@ -142,10 +160,10 @@ pub(crate) mod properties;
 mod queue;
 
 /// Represents a Vulkan context.
-#[derive(Debug)]
 pub struct Device {
     handle: ash::vk::Device,
     physical_device: Arc<PhysicalDevice>,
+    physical_devices: SmallVec<[Arc<PhysicalDevice>; 2]>,
     id: NonZeroU64,
 
     // The highest version that is supported for this device.
@@ -159,9 +177,13 @@ pub struct Device {
     // This is required for validation in `memory::device_memory`, the count must only be modified
     // in that module.
     pub(crate) allocation_count: AtomicU32,
+    // This is updated by `memory::device_memory` as well.
+    pub(crate) memory_tracker: MemoryTracker,
     fence_pool: Mutex<Vec<ash::vk::Fence>>,
     semaphore_pool: Mutex<Vec<ash::vk::Semaphore>>,
     event_pool: Mutex<Vec<ash::vk::Event>>,
+    _device_memory_report_callback: Option<Box<DeviceMemoryReportCallback>>,
+    lost: AtomicBool,
 }
 
 impl Device {
@@ -176,14 +198,41 @@ impl Device {
     /// - Panics if `create_info.queues` contains an element where `queues` is empty.
     /// - Panics if `create_info.queues` contains an element where `queues` contains a value that is
     ///   not between 0.0 and 1.0 inclusive.
+    /// - Panics if `create_info.physical_devices` is not empty, and it contains duplicate elements,
+    ///   elements that belong to a different instance or group, or does not contain
+    ///   `physical_device`.
+    #[inline]
     pub fn new(
         physical_device: Arc<PhysicalDevice>,
         create_info: DeviceCreateInfo,
+    ) -> Result<(Arc<Device>, impl ExactSizeIterator<Item = Arc<Queue>>), DeviceCreationError> {
+        unsafe { Self::with_device_memory_report_callback(physical_device, create_info, None) }
+    }
+
+    /// Creates a new `Device`, with a callback that the implementation calls to report device
+    /// memory events.
+    ///
+    /// If `device_memory_report_callback` is `Some`, the
+    /// [`device_memory_report`](Features::device_memory_report) feature must be enabled in
+    /// `create_info`. The callback stays registered for as long as the device is alive.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info` is invalid, as described for [`Device::new`].
+    ///
+    /// # Safety
+    ///
+    /// - `device_memory_report_callback` must not make any calls to the Vulkan API.
+    pub unsafe fn with_device_memory_report_callback(
+        physical_device: Arc<PhysicalDevice>,
+        create_info: DeviceCreateInfo,
+        device_memory_report_callback: Option<DeviceMemoryReportCallback>,
     ) -> Result<(Arc<Device>, impl ExactSizeIterator<Item = Arc<Queue>>), DeviceCreationError> {
         let DeviceCreateInfo {
             mut enabled_extensions,
             mut enabled_features,
             queue_create_infos,
+            physical_devices,
             _ne: _,
         } = create_info;
 
@@ -197,6 +246,7 @@ impl Device {
 
         struct QueueToGet {
             queue_family_index: u32,
+            flags: QueueCreateFlags,
             id: u32,
         }
 
@@ -211,6 +261,7 @@ impl Device {
 
         for queue_create_info in &queue_create_infos {
             let &QueueCreateInfo {
+                flags,
                 queue_family_index,
                 ref queues,
                 _ne: _,
@@ -225,11 +276,34 @@ impl Device {
             assert!(
                 queue_create_infos
                     .iter()
-                    .filter(|qc2| qc2.queue_family_index == queue_family_index)
+                    .filter(|qc2| qc2.queue_family_index == queue_family_index
+                        && qc2.flags == flags)
                     .count()
                     == 1
             );
 
+            // VUID-VkDeviceQueueCreateInfo-flags-parameter
+            flags.validate_physical_device(&physical_device)?;
+
+            if flags.intersects(QueueCreateFlags::PROTECTED) {
+                // VUID-VkDeviceQueueCreateInfo-flags-02861
+                if !enabled_features.protected_memory {
+                    return Err(DeviceCreationError::RequirementNotMet {
+                        required_for: "`create_info.queue_create_infos` has an element where \
+                            `flags` contains `QueueCreateFlags::PROTECTED`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["protected_memory"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkDeviceQueueCreateInfo-flags-06449
+                assert!(queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::PROTECTED));
+            }
+
             // VUID-VkDeviceQueueCreateInfo-queueCount-arraylength
             assert!(!queues.is_empty());
 
@@ -243,7 +317,7 @@ impl Device {
             }
 
             queue_create_infos_vk.push(ash::vk::DeviceQueueCreateInfo {
-                flags: ash::vk::DeviceQueueCreateFlags::empty(),
+                flags: flags.into(),
                 queue_family_index,
                 queue_count: queues.len() as u32,
                 p_queue_priorities: queues.as_ptr(), // borrows from queue_create
@@ -252,6 +326,7 @@ impl Device {
             active_queue_family_indices.push(queue_family_index);
             queues_to_get.extend((0..queues.len() as u32).map(move |id| QueueToGet {
                 queue_family_index,
+                flags,
                 id,
             }));
         }
@@ -358,6 +433,51 @@ impl Device {
             .map(|layer| layer.as_ptr())
             .collect::<SmallVec<[_; 2]>>();
 
+        /*
+            Device group
+        */
+
+        if !physical_devices.is_empty() {
+            if !(instance.api_version() >= Version::V1_1
+                || instance.enabled_extensions().khr_device_group_creation)
+            {
+                return Err(DeviceCreationError::RequirementNotMet {
+                    required_for: "`create_info.physical_devices` is not empty",
+                    requires_one_of: RequiresOneOf {
+                        api_version: Some(Version::V1_1),
+                        instance_extensions: &["khr_device_group_creation"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            for (index, other) in physical_devices.iter().enumerate() {
+                assert_eq!(other.instance(), instance);
+
+                // VUID-VkDeviceGroupDeviceCreateInfo-pPhysicalDevices-00375
+                assert!(physical_devices[..index]
+                    .iter()
+                    .all(|previous| previous.handle() != other.handle()));
+            }
+
+            // VUID-VkDeviceGroupDeviceCreateInfo-physicalDeviceCount-00377
+            assert!(physical_devices
+                .iter()
+                .any(|other| other.handle() == physical_device.handle()));
+
+            // VUID-VkDeviceGroupDeviceCreateInfo-pPhysicalDevices-00376
+            let is_same_group = unsafe { instance.enumerate_physical_device_groups_unchecked() }?
+                .any(|group| {
+                    physical_devices.iter().all(|other| {
+                        group
+                            .physical_devices
+                            .iter()
+                            .any(|member| member.handle() == other.handle())
+                    })
+                });
+            assert!(is_same_group);
+        }
+
         /*
             Create the device
         */
@@ -385,6 +505,52 @@ impl Device {
             create_info.p_enabled_features = &features_ffi.head_as_ref().features;
         }
 
+        let physical_devices_vk: SmallVec<[_; 2]> = physical_devices
+            .iter()
+            .map(|physical_device| physical_device.handle())
+            .collect();
+        let mut device_group_create_info = None;
+
+        if !physical_devices_vk.is_empty() {
+            let info = device_group_create_info.insert(ash::vk::DeviceGroupDeviceCreateInfo {
+                physical_device_count: physical_devices_vk.len() as u32,
+                p_physical_devices: physical_devices_vk.as_ptr(),
+                ..Default::default()
+            });
+
+            info.p_next = create_info.p_next;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
+        // Box the callback so that the pointer to it stays valid when the device is moved.
+        let device_memory_report_callback = device_memory_report_callback.map(Box::new);
+        let mut device_memory_report_create_info = None;
+
+        if let Some(user_callback) = device_memory_report_callback.as_ref() {
+            if !enabled_features.device_memory_report {
+                return Err(DeviceCreationError::RequirementNotMet {
+                    required_for: "`device_memory_report_callback` is `Some`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["device_memory_report"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            let info = device_memory_report_create_info.insert(
+                ash::vk::DeviceDeviceMemoryReportCreateInfoEXT {
+                    flags: ash::vk::DeviceMemoryReportFlagsEXT::empty(),
+                    pfn_user_callback: Some(device_memory_report_trampoline),
+                    p_user_data: user_callback.as_ref() as *const DeviceMemoryReportCallback
+                        as *const c_void as *mut _,
+                    ..Default::default()
+                },
+            );
+
+            info.p_next = create_info.p_next;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
         let handle = unsafe {
             let mut output = MaybeUninit::uninit();
             (fns_i.v1_0.create_device)(
@@ -404,9 +570,18 @@ impl Device {
                 .map_or(ptr::null(), |func| func as _)
         });
 
+        let memory_heap_count = physical_device.memory_properties().memory_heaps.len();
+
+        let physical_devices = if physical_devices.is_empty() {
+            smallvec![physical_device.clone()]
+        } else {
+            physical_devices
+        };
+
         let device = Arc::new(Device {
             handle,
             physical_device,
+            physical_devices,
             id: Self::next_id(),
             api_version,
             fns,
@@ -414,9 +589,12 @@ impl Device {
             enabled_features,
             active_queue_family_indices,
             allocation_count: AtomicU32::new(0),
+            memory_tracker: MemoryTracker::new(memory_heap_count),
             fence_pool: Mutex::new(Vec::new()),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
+            _device_memory_report_callback: device_memory_report_callback,
+            lost: AtomicBool::new(false),
         });
 
         // Iterator to return the queues
@@ -425,18 +603,37 @@ impl Device {
             queues_to_get.into_iter().map(
                 move |QueueToGet {
                           queue_family_index,
+                          flags,
                           id,
                       }| unsafe {
                     let fns = device.fns();
                     let mut output = MaybeUninit::uninit();
-                    (fns.v1_0.get_device_queue)(
-                        handle,
+
+                    if flags.is_empty() {
+                        (fns.v1_0.get_device_queue)(
+                            handle,
+                            queue_family_index,
+                            id,
+                            output.as_mut_ptr(),
+                        );
+                    } else {
+                        // VUID-vkGetDeviceQueue-flags-01841
+                        let queue_info = ash::vk::DeviceQueueInfo2 {
+                            flags: flags.into(),
+                            queue_family_index,
+                            queue_index: id,
+                            ..Default::default()
+                        };
+                        (fns.v1_1.get_device_queue2)(handle, &queue_info, output.as_mut_ptr());
+                    }
+
+                    Queue::from_handle(
+                        device.clone(),
+                        output.assume_init(),
+                        flags,
                         queue_family_index,
                         id,
-                        output.as_mut_ptr(),
-                    );
-
-                    Queue::from_handle(device.clone(), output.assume_init(), queue_family_index, id)
+                    )
                 },
             )
         };
@@ -466,6 +663,19 @@ impl Device {
         &self.physical_device
     }
 
+    /// Returns the physical devices that the device spans.
+    ///
+    /// If [`DeviceCreateInfo::physical_devices`] was empty when the device was created, this
+    /// contains only the [physical device] that was used to create the device. The index of a
+    /// physical device in this list is its device index, which corresponds to a bit in a device
+    /// mask.
+    ///
+    /// [physical device]: Self::physical_device
+    #[inline]
+    pub fn physical_devices(&self) -> &[Arc<PhysicalDevice>] {
+        &self.physical_devices
+    }
+
     /// Returns the instance used to create this device.
     #[inline]
     pub fn instance(&self) -> &Arc<Instance> {
@@ -498,6 +708,492 @@ impl Device {
         self.allocation_count.load(Ordering::Acquire)
     }
 
+    /// Queries the current memory budget and usage of each memory heap of the physical device.
+    ///
+    /// The returned values are only a snapshot, and can change at any time, as other processes
+    /// and the implementation itself also allocate memory. The budget is how much memory this
+    /// process can allocate from a heap before allocations are likely to fail or cause
+    /// performance degradation, and is a better indicator than the size of the heap. The
+    /// returned `Vec` has one element for each element of
+    /// [`MemoryProperties::memory_heaps`](crate::memory::MemoryProperties::memory_heaps).
+    ///
+    /// The [`ext_memory_budget`](DeviceExtensions::ext_memory_budget) extension must be enabled on
+    /// the device.
+    #[inline]
+    pub fn memory_budget(&self) -> Result<Vec<MemoryHeapBudget>, MemoryBudgetError> {
+        self.validate_memory_budget()?;
+
+        unsafe { Ok(self.memory_budget_unchecked()) }
+    }
+
+    fn validate_memory_budget(&self) -> Result<(), MemoryBudgetError> {
+        if !self.enabled_extensions().ext_memory_budget {
+            return Err(MemoryBudgetError::RequirementNotMet {
+                required_for: "`Device::memory_budget`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_memory_budget"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn memory_budget_unchecked(&self) -> Vec<MemoryHeapBudget> {
+        let mut memory_budget_properties =
+            ash::vk::PhysicalDeviceMemoryBudgetPropertiesEXT::default();
+        let mut memory_properties2 = ash::vk::PhysicalDeviceMemoryProperties2 {
+            p_next: &mut memory_budget_properties as *mut _ as *mut _,
+            ..Default::default()
+        };
+
+        let instance = self.instance();
+        let fns = instance.fns();
+
+        if instance.api_version() >= Version::V1_1 {
+            (fns.v1_1.get_physical_device_memory_properties2)(
+                self.physical_device.handle(),
+                &mut memory_properties2,
+            );
+        } else {
+            (fns.khr_get_physical_device_properties2
+                .get_physical_device_memory_properties2_khr)(
+                self.physical_device.handle(),
+                &mut memory_properties2,
+            );
+        }
+
+        let memory_heap_count = memory_properties2.memory_properties.memory_heap_count as usize;
+
+        (memory_budget_properties.heap_budget[..memory_heap_count].iter())
+            .zip(&memory_budget_properties.heap_usage[..memory_heap_count])
+            .map(|(&budget, &usage)| MemoryHeapBudget { budget, usage })
+            .collect()
+    }
+
+    /// Returns a report of the [`DeviceMemory`] allocations of the device, with the totals and
+    /// high-water marks of each memory heap and the allocations that are still alive.
+    ///
+    /// See [the `report` module](crate::memory::report) for more information.
+    ///
+    /// [`DeviceMemory`]: crate::memory::DeviceMemory
+    #[inline]
+    pub fn memory_report(&self) -> MemoryReport {
+        self.memory_tracker.report()
+    }
+
+    /// Queries how memory that is allocated from a heap on one physical device of the device
+    /// group can be accessed by another physical device of the group.
+    ///
+    /// `local_device_index` is the index of the physical device that performs the access, and
+    /// `remote_device_index` is the index of the physical device that the memory is allocated on.
+    /// Both are indices into [`physical_devices`](Self::physical_devices).
+    ///
+    /// The device API version must be at least 1.1, or the
+    /// [`khr_device_group`](DeviceExtensions::khr_device_group) extension must be enabled on the
+    /// device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `heap_index` is not less than the number of memory heaps of the physical
+    ///   device.
+    /// - Panics if `local_device_index` or `remote_device_index` is not less than the number of
+    ///   physical devices of the device.
+    /// - Panics if `local_device_index` and `remote_device_index` are equal.
+    #[inline]
+    pub fn group_peer_memory_features(
+        &self,
+        heap_index: u32,
+        local_device_index: u32,
+        remote_device_index: u32,
+    ) -> Result<PeerMemoryFeatures, PeerMemoryFeaturesError> {
+        self.validate_group_peer_memory_features(
+            heap_index,
+            local_device_index,
+            remote_device_index,
+        )?;
+
+        unsafe {
+            Ok(self.group_peer_memory_features_unchecked(
+                heap_index,
+                local_device_index,
+                remote_device_index,
+            ))
+        }
+    }
+
+    fn validate_group_peer_memory_features(
+        &self,
+        heap_index: u32,
+        local_device_index: u32,
+        remote_device_index: u32,
+    ) -> Result<(), PeerMemoryFeaturesError> {
+        if !(self.api_version() >= Version::V1_1 || self.enabled_extensions().khr_device_group) {
+            return Err(PeerMemoryFeaturesError::RequirementNotMet {
+                required_for: "`Device::group_peer_memory_features`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_1),
+                    device_extensions: &["khr_device_group"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetDeviceGroupPeerMemoryFeatures-heapIndex-00691
+        assert!(
+            (heap_index as usize) < self.physical_device.memory_properties().memory_heaps.len()
+        );
+
+        // VUID-vkGetDeviceGroupPeerMemoryFeatures-localDeviceIndex-00692
+        assert!((local_device_index as usize) < self.physical_devices.len());
+
+        // VUID-vkGetDeviceGroupPeerMemoryFeatures-remoteDeviceIndex-00693
+        assert!((remote_device_index as usize) < self.physical_devices.len());
+
+        // VUID-vkGetDeviceGroupPeerMemoryFeatures-localDeviceIndex-00694
+        assert_ne!(local_device_index, remote_device_index);
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn group_peer_memory_features_unchecked(
+        &self,
+        heap_index: u32,
+        local_device_index: u32,
+        remote_device_index: u32,
+    ) -> PeerMemoryFeatures {
+        let fns = self.fns();
+        let mut output = MaybeUninit::uninit();
+
+        if self.api_version() >= Version::V1_1 {
+            (fns.v1_1.get_device_group_peer_memory_features)(
+                self.handle,
+                heap_index,
+                local_device_index,
+                remote_device_index,
+                output.as_mut_ptr(),
+            );
+        } else {
+            (fns.khr_device_group
+                .get_device_group_peer_memory_features_khr)(
+                self.handle,
+                heap_index,
+                local_device_index,
+                remote_device_index,
+                output.as_mut_ptr(),
+            );
+        }
+
+        output.assume_init().into()
+    }
+
+    /// Samples timestamps from multiple time domains at the same moment, so that timestamps from
+    /// one time domain can be correlated with timestamps from another.
+    ///
+    /// This is mainly used to correlate the results of timestamp queries, which are in the
+    /// [`TimeDomain::Device`] time domain, with a clock of the host. See [`CalibratedTimestamps`]
+    /// for how to convert between them.
+    ///
+    /// Each element of `time_domains` must be one of the
+    /// [`calibrateable_time_domains`](PhysicalDevice::calibrateable_time_domains) of the physical
+    /// device, and must not occur more than once.
+    ///
+    /// The [`ext_calibrated_timestamps`](DeviceExtensions::ext_calibrated_timestamps) extension
+    /// must be enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `time_domains` is empty.
+    #[inline]
+    pub fn calibrate_timestamps(
+        &self,
+        time_domains: &[TimeDomain],
+    ) -> Result<CalibratedTimestamps, CalibrateTimestampsError> {
+        self.validate_calibrate_timestamps(time_domains)?;
+
+        unsafe { Ok(self.calibrate_timestamps_unchecked(time_domains)?) }
+    }
+
+    fn validate_calibrate_timestamps(
+        &self,
+        time_domains: &[TimeDomain],
+    ) -> Result<(), CalibrateTimestampsError> {
+        if !self.enabled_extensions().ext_calibrated_timestamps {
+            return Err(CalibrateTimestampsError::RequirementNotMet {
+                required_for: "`Device::calibrate_timestamps`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_calibrated_timestamps"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetCalibratedTimestampsEXT-timestampCount-arraylength
+        assert!(!time_domains.is_empty());
+
+        let supported_time_domains = unsafe {
+            self.physical_device
+                .calibrateable_time_domains_unchecked()?
+        };
+
+        for (index, &time_domain) in time_domains.iter().enumerate() {
+            // VUID-VkCalibratedTimestampInfoEXT-timeDomain-parameter
+            time_domain.validate_device(self)?;
+
+            // VUID-VkCalibratedTimestampInfoEXT-timeDomain-02354
+            if !supported_time_domains.contains(&time_domain) {
+                return Err(CalibrateTimestampsError::TimeDomainNotSupported { time_domain });
+            }
+
+            // VUID?
+            if time_domains[..index].contains(&time_domain) {
+                return Err(CalibrateTimestampsError::TimeDomainNotUnique { time_domain });
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn calibrate_timestamps_unchecked(
+        &self,
+        time_domains: &[TimeDomain],
+    ) -> Result<CalibratedTimestamps, VulkanError> {
+        let timestamp_infos_vk: SmallVec<[_; 4]> = time_domains
+            .iter()
+            .map(|&time_domain| ash::vk::CalibratedTimestampInfoEXT {
+                time_domain: time_domain.into(),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut timestamps: SmallVec<[u64; 4]> = smallvec![0; time_domains.len()];
+        let mut max_deviation = 0;
+
+        let fns = self.fns();
+        (fns.ext_calibrated_timestamps.get_calibrated_timestamps_ext)(
+            self.handle,
+            timestamp_infos_vk.len() as u32,
+            timestamp_infos_vk.as_ptr(),
+            timestamps.as_mut_ptr(),
+            &mut max_deviation,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let instant = Instant::now();
+
+        Ok(CalibratedTimestamps {
+            time_domains: time_domains.iter().copied().collect(),
+            timestamps,
+            max_deviation,
+            timestamp_period: self.physical_device.properties().timestamp_period,
+            instant,
+        })
+    }
+
+    /// Acquires the profiling lock of the device, which is needed to record command buffers that
+    /// use performance queries.
+    ///
+    /// The lock must be held for as long as such command buffers are being recorded, submitted
+    /// or executed. If `timeout` is `None`, this waits until the lock is acquired. Otherwise,
+    /// an error is returned if the lock could not be acquired in time.
+    ///
+    /// The [`khr_performance_query`](DeviceExtensions::khr_performance_query) extension must be
+    /// enabled on the device.
+    #[inline]
+    pub fn acquire_profiling_lock(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<(), ProfilingLockError> {
+        self.validate_profiling_lock()?;
+
+        unsafe { self.acquire_profiling_lock_unchecked(timeout) }
+    }
+
+    fn validate_profiling_lock(&self) -> Result<(), ProfilingLockError> {
+        if !self.enabled_extensions().khr_performance_query {
+            return Err(ProfilingLockError::RequirementNotMet {
+                required_for: "`Device::acquire_profiling_lock` or \
+                    `Device::release_profiling_lock`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_performance_query"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn acquire_profiling_lock_unchecked(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<(), ProfilingLockError> {
+        let timeout_ns = timeout.map_or(u64::MAX, |timeout| {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        });
+
+        let info = ash::vk::AcquireProfilingLockInfoKHR {
+            flags: ash::vk::AcquireProfilingLockFlagsKHR::empty(),
+            timeout: timeout_ns,
+            ..Default::default()
+        };
+
+        let fns = self.fns();
+        let result = (fns.khr_performance_query.acquire_profiling_lock_khr)(self.handle, &info);
+
+        match result {
+            ash::vk::Result::SUCCESS => Ok(()),
+            ash::vk::Result::TIMEOUT => Err(ProfilingLockError::Timeout),
+            err => Err(VulkanError::from(err).into()),
+        }
+    }
+
+    /// Releases the profiling lock of the device, that was previously acquired with
+    /// [`acquire_profiling_lock`](Self::acquire_profiling_lock).
+    ///
+    /// The [`khr_performance_query`](DeviceExtensions::khr_performance_query) extension must be
+    /// enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - The profiling lock must be currently held.
+    /// - No command buffers that use performance queries must be in the recording or pending
+    ///   state.
+    #[inline]
+    pub unsafe fn release_profiling_lock(&self) -> Result<(), ProfilingLockError> {
+        self.validate_profiling_lock()?;
+
+        self.release_profiling_lock_unchecked();
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn release_profiling_lock_unchecked(&self) {
+        let fns = self.fns();
+        (fns.khr_performance_query.release_profiling_lock_khr)(self.handle);
+    }
+
+    /// Retrieves information about the fault that caused the device to be lost.
+    ///
+    /// If the device has not been lost, the returned information is empty. Vulkano also calls
+    /// this automatically when a flush operation finds that the device has been lost, and then
+    /// returns [`FlushError::DeviceFault`] with the retrieved information.
+    ///
+    /// The [`device_fault`](Features::device_fault) feature must be enabled on the device.
+    ///
+    /// [`FlushError::DeviceFault`]: crate::sync::FlushError::DeviceFault
+    #[inline]
+    pub fn fault_info(&self) -> Result<DeviceFaultInfo, DeviceFaultError> {
+        self.validate_fault_info()?;
+
+        unsafe { Ok(self.fault_info_unchecked()?) }
+    }
+
+    fn validate_fault_info(&self) -> Result<(), DeviceFaultError> {
+        if !self.enabled_features().device_fault {
+            return Err(DeviceFaultError::RequirementNotMet {
+                required_for: "`Device::fault_info`",
+                requires_one_of: RequiresOneOf {
+                    features: &["device_fault"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn fault_info_unchecked(&self) -> Result<DeviceFaultInfo, VulkanError> {
+        let fns = self.fns();
+
+        loop {
+            let mut counts = ash::vk::DeviceFaultCountsEXT::default();
+            (fns.ext_device_fault.get_device_fault_info_ext)(
+                self.handle,
+                &mut counts,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            let mut address_infos = Vec::with_capacity(counts.address_info_count as usize);
+            let mut vendor_infos = Vec::with_capacity(counts.vendor_info_count as usize);
+            let mut vendor_binary_data: Vec<u8> =
+                Vec::with_capacity(counts.vendor_binary_size as usize);
+
+            let mut info_vk = ash::vk::DeviceFaultInfoEXT {
+                p_address_infos: address_infos.as_mut_ptr(),
+                p_vendor_infos: vendor_infos.as_mut_ptr(),
+                p_vendor_binary_data: if counts.vendor_binary_size != 0 {
+                    vendor_binary_data.as_mut_ptr() as *mut c_void
+                } else {
+                    ptr::null_mut()
+                },
+                ..Default::default()
+            };
+
+            let result = (fns.ext_device_fault.get_device_fault_info_ext)(
+                self.handle,
+                &mut counts,
+                &mut info_vk,
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => {
+                    address_infos.set_len(counts.address_info_count as usize);
+                    vendor_infos.set_len(counts.vendor_info_count as usize);
+                    vendor_binary_data.set_len(counts.vendor_binary_size as usize);
+
+                    break Ok(DeviceFaultInfo {
+                        description: CStr::from_ptr(info_vk.description.as_ptr())
+                            .to_string_lossy()
+                            .into_owned(),
+                        address_infos: address_infos
+                            .into_iter()
+                            .map(|address_info: ash::vk::DeviceFaultAddressInfoEXT| {
+                                DeviceFaultAddressInfo {
+                                    address_type: address_info.address_type.try_into().unwrap(),
+                                    reported_address: address_info.reported_address,
+                                    address_precision: address_info.address_precision,
+                                }
+                            })
+                            .collect(),
+                        vendor_infos: vendor_infos
+                            .into_iter()
+                            .map(|vendor_info: ash::vk::DeviceFaultVendorInfoEXT| {
+                                DeviceFaultVendorInfo {
+                                    description: CStr::from_ptr(vendor_info.description.as_ptr())
+                                        .to_string_lossy()
+                                        .into_owned(),
+                                    vendor_fault_code: vendor_info.vendor_fault_code,
+                                    vendor_fault_data: vendor_info.vendor_fault_data,
+                                }
+                            })
+                            .collect(),
+                        vendor_binary_data,
+                    });
+                }
+                ash::vk::Result::INCOMPLETE => (),
+                err => break Err(VulkanError::from(err)),
+            }
+        }
+    }
+
     pub(crate) fn fence_pool(&self) -> &Mutex<Vec<ash::vk::Fence>> {
         &self.fence_pool
     }
@@ -564,17 +1260,266 @@ impl Device {
         }
     }
 
-    /// Assigns a human-readable name to `object` for debugging purposes.
+    /// Retrieves the properties of a pointer to host memory when imported as a given external
+    /// handle type.
     ///
-    /// If `object_name` is `None`, a previously set object name is removed.
+    /// The [`ext_external_memory_host`](DeviceExtensions::ext_external_memory_host) extension
+    /// must be enabled on the device, and `handle_type` must be either
+    /// [`ExternalMemoryHandleType::HostAllocation`] or
+    /// [`ExternalMemoryHandleType::HostMappedForeignMemory`].
     ///
-    /// # Panics
-    /// - If `object` is not owned by this device.
+    /// # Safety
+    ///
+    /// - `host_pointer` must be a pointer to host memory of the kind indicated by `handle_type`.
+    #[inline]
+    pub unsafe fn memory_host_pointer_properties(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+        host_pointer: *const c_void,
+    ) -> Result<MemoryHostPointerProperties, MemoryFdPropertiesError> {
+        if !self.enabled_extensions().ext_external_memory_host {
+            return Err(MemoryFdPropertiesError::RequirementNotMet {
+                required_for: "`Device::memory_host_pointer_properties`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_external_memory_host"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetMemoryHostPointerPropertiesEXT-handleType-parameter
+        handle_type.validate_device(self)?;
+
+        // VUID-vkGetMemoryHostPointerPropertiesEXT-handleType-01752
+        if !matches!(
+            handle_type,
+            ExternalMemoryHandleType::HostAllocation
+                | ExternalMemoryHandleType::HostMappedForeignMemory
+        ) {
+            return Err(MemoryFdPropertiesError::InvalidExternalHandleType);
+        }
+
+        // VUID-vkGetMemoryHostPointerPropertiesEXT-pHostPointer-01753
+        // VUID-vkGetMemoryHostPointerPropertiesEXT-handleType-01754
+        // VUID-vkGetMemoryHostPointerPropertiesEXT-handleType-01755
+        // Can't validate, therefore unsafe
+
+        let mut memory_host_pointer_properties = ash::vk::MemoryHostPointerPropertiesEXT::default();
+
+        let fns = self.fns();
+        (fns.ext_external_memory_host
+            .get_memory_host_pointer_properties_ext)(
+            self.handle,
+            handle_type.into(),
+            host_pointer,
+            &mut memory_host_pointer_properties,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(MemoryHostPointerProperties {
+            memory_type_bits: memory_host_pointer_properties.memory_type_bits,
+        })
+    }
+
+    /// Retrieves the properties of an Android hardware buffer, which are needed to import it with
+    /// [`MemoryImportInfo::AndroidHardwareBuffer`].
+    ///
+    /// The [`android_external_memory_android_hardware_buffer`] extension must be enabled on the
+    /// device.
+    ///
+    /// # Safety
+    ///
+    /// - `buffer` must be a valid pointer to an `AHardwareBuffer`.
+    /// - `buffer` must have been allocated with at least one of the `AHARDWAREBUFFER_USAGE_GPU_*`
+    ///   usage flags.
+    ///
+    /// [`MemoryImportInfo::AndroidHardwareBuffer`]: crate::memory::MemoryImportInfo::AndroidHardwareBuffer
+    /// [`android_external_memory_android_hardware_buffer`]: DeviceExtensions::android_external_memory_android_hardware_buffer
+    #[inline]
+    pub unsafe fn android_hardware_buffer_properties(
+        &self,
+        buffer: *const ash::vk::AHardwareBuffer,
+    ) -> Result<AndroidHardwareBufferProperties, MemoryFdPropertiesError> {
+        if !self
+            .enabled_extensions()
+            .android_external_memory_android_hardware_buffer
+        {
+            return Err(MemoryFdPropertiesError::RequirementNotMet {
+                required_for: "`Device::android_hardware_buffer_properties`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["android_external_memory_android_hardware_buffer"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetAndroidHardwareBufferPropertiesANDROID-buffer-01884
+        // Can't validate, therefore unsafe
+
+        let mut format_properties_vk =
+            ash::vk::AndroidHardwareBufferFormatPropertiesANDROID::default();
+        let mut properties_vk = ash::vk::AndroidHardwareBufferPropertiesANDROID {
+            p_next: &mut format_properties_vk as *mut _ as *mut _,
+            ..Default::default()
+        };
+
+        let fns = self.fns();
+        (fns.android_external_memory_android_hardware_buffer
+            .get_android_hardware_buffer_properties_android)(
+            self.handle,
+            buffer,
+            &mut properties_vk,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let components = format_properties_vk.sampler_ycbcr_conversion_components;
+
+        Ok(AndroidHardwareBufferProperties {
+            allocation_size: properties_vk.allocation_size,
+            memory_type_bits: properties_vk.memory_type_bits,
+            format: format_properties_vk.format.try_into().ok(),
+            external_format: format_properties_vk.external_format,
+            format_features: format_properties_vk.format_features.into(),
+            sampler_ycbcr_conversion_components: ComponentMapping {
+                r: components.r.try_into().unwrap(),
+                g: components.g.try_into().unwrap(),
+                b: components.b.try_into().unwrap(),
+                a: components.a.try_into().unwrap(),
+            },
+            suggested_ycbcr_model: format_properties_vk
+                .suggested_ycbcr_model
+                .try_into()
+                .unwrap(),
+            suggested_ycbcr_range: format_properties_vk
+                .suggested_ycbcr_range
+                .try_into()
+                .unwrap(),
+            suggested_x_chroma_offset: format_properties_vk
+                .suggested_x_chroma_offset
+                .try_into()
+                .unwrap(),
+            suggested_y_chroma_offset: format_properties_vk
+                .suggested_y_chroma_offset
+                .try_into()
+                .unwrap(),
+        })
+    }
+
+    /// Returns the sizes that are needed to build an acceleration structure with `build_info`.
+    ///
+    /// `max_primitive_counts` must contain one element for each geometry in `build_info`,
+    /// specifying the maximum number of primitives that will be built for that geometry.
+    /// The buffers and acceleration structures in `build_info` are ignored, and may be `None`.
+    ///
+    /// The [`acceleration_structure`](Features::acceleration_structure) feature must be enabled
+    /// on the device.
+    #[inline]
+    pub fn acceleration_structure_build_sizes(
+        &self,
+        build_type: AccelerationStructureBuildType,
+        build_info: &AccelerationStructureBuildGeometryInfo,
+        max_primitive_counts: &[u32],
+    ) -> Result<AccelerationStructureBuildSizesInfo, AccelerationStructureError> {
+        self.validate_acceleration_structure_build_sizes(
+            build_type,
+            build_info,
+            max_primitive_counts,
+        )?;
+
+        unsafe {
+            Ok(self.acceleration_structure_build_sizes_unchecked(
+                build_type,
+                build_info,
+                max_primitive_counts,
+            ))
+        }
+    }
+
+    fn validate_acceleration_structure_build_sizes(
+        &self,
+        build_type: AccelerationStructureBuildType,
+        build_info: &AccelerationStructureBuildGeometryInfo,
+        max_primitive_counts: &[u32],
+    ) -> Result<(), AccelerationStructureError> {
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-accelerationStructure-08933
+        if !self.enabled_features().acceleration_structure {
+            return Err(AccelerationStructureError::RequirementNotMet {
+                required_for: "`Device::acceleration_structure_build_sizes`",
+                requires_one_of: RequiresOneOf {
+                    features: &["acceleration_structure"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-buildType-parameter
+        build_type.validate_device(self)?;
+
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-pBuildInfo-parameter
+        // VUID-vkGetAccelerationStructureBuildSizesKHR-pBuildInfo-03619
+        build_info.validate(self, max_primitive_counts)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn acceleration_structure_build_sizes_unchecked(
+        &self,
+        build_type: AccelerationStructureBuildType,
+        build_info: &AccelerationStructureBuildGeometryInfo,
+        max_primitive_counts: &[u32],
+    ) -> AccelerationStructureBuildSizesInfo {
+        let (mut build_info_vk, geometries_vk) = build_info.to_vulkan();
+        build_info_vk.p_geometries = geometries_vk.as_ptr();
+
+        let mut build_sizes_info_vk = ash::vk::AccelerationStructureBuildSizesInfoKHR::default();
+
+        let fns = self.fns();
+        (fns.khr_acceleration_structure
+            .get_acceleration_structure_build_sizes_khr)(
+            self.handle,
+            build_type.into(),
+            &build_info_vk,
+            max_primitive_counts.as_ptr(),
+            &mut build_sizes_info_vk,
+        );
+
+        AccelerationStructureBuildSizesInfo {
+            acceleration_structure_size: build_sizes_info_vk.acceleration_structure_size,
+            update_scratch_size: build_sizes_info_vk.update_scratch_size,
+            build_scratch_size: build_sizes_info_vk.build_scratch_size,
+        }
+    }
+
+    /// Assigns a human-readable name to `object` for debugging purposes.
+    ///
+    /// If `object_name` is `None`, a previously set object name is removed.
+    ///
+    /// The [`ext_debug_utils`](crate::instance::InstanceExtensions::ext_debug_utils) extension
+    /// must be enabled on the instance.
+    ///
+    /// # Panics
+    /// - If `object` is not owned by this device.
+    /// - If `object_name` contains a nul byte.
     pub fn set_debug_utils_object_name<T: VulkanObject + DeviceOwned>(
         &self,
         object: &T,
         object_name: Option<&str>,
-    ) -> Result<(), OomError> {
+    ) -> Result<(), DebugUtilsError> {
+        if !self.instance().enabled_extensions().ext_debug_utils {
+            return Err(DebugUtilsError::RequirementNotMet {
+                required_for: "`Device::set_debug_utils_object_name`",
+                requires_one_of: RequiresOneOf {
+                    instance_extensions: &["ext_debug_utils"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkDebugUtilsObjectNameInfoEXT-commonparent
         assert!(object.device().handle() == self.handle());
 
         let object_name_vk = object_name.map(|object_name| CString::new(object_name).unwrap());
@@ -592,6 +1537,13 @@ impl Device {
                 .map_err(VulkanError::from)?;
         }
 
+        if T::Handle::TYPE == ash::vk::ObjectType::DEVICE_MEMORY {
+            self.memory_tracker.record_name(
+                ash::vk::DeviceMemory::from_raw(object.handle().as_raw()),
+                object_name,
+            );
+        }
+
         Ok(())
     }
 
@@ -606,14 +1558,76 @@ impl Device {
     /// of the device (either explicitly or implicitly, for example with a future's destructor)
     /// while this function is waiting.
     #[inline]
-    pub unsafe fn wait_idle(&self) -> Result<(), OomError> {
+    pub unsafe fn wait_idle(&self) -> Result<(), VulkanError> {
         let fns = self.fns();
         (fns.v1_0.device_wait_idle)(self.handle)
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| self.check_lost(err.into()))?;
 
         Ok(())
     }
+
+    /// Returns whether the device has been lost.
+    ///
+    /// Once an operation on the device has returned [`VulkanError::DeviceLost`], or an error
+    /// type's equivalent, the device stays lost. From then on, all submissions to its queues fail
+    /// with `DeviceLost`, and all objects created from the device are poisoned: they can still
+    /// be dropped, but they can no longer be used for any work. To continue, the application
+    /// must create a new `Device`, and recreate all of its objects from it.
+    #[inline]
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Acquire)
+    }
+
+    /// Marks the device as lost if `err` is `VulkanError::DeviceLost`, and returns `err`.
+    pub(crate) fn check_lost(&self, err: VulkanError) -> VulkanError {
+        if matches!(err, VulkanError::DeviceLost) {
+            self.lost.store(true, Ordering::Release);
+        }
+
+        err
+    }
+}
+
+impl Debug for Device {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        let Self {
+            handle,
+            physical_device,
+            physical_devices,
+            id,
+            api_version,
+            fns,
+            enabled_extensions,
+            enabled_features,
+            active_queue_family_indices,
+            allocation_count,
+            memory_tracker,
+            fence_pool,
+            semaphore_pool,
+            event_pool,
+            _device_memory_report_callback: _,
+            lost,
+        } = self;
+
+        f.debug_struct("Device")
+            .field("handle", handle)
+            .field("physical_device", physical_device)
+            .field("physical_devices", physical_devices)
+            .field("id", id)
+            .field("api_version", api_version)
+            .field("fns", fns)
+            .field("enabled_extensions", enabled_extensions)
+            .field("enabled_features", enabled_features)
+            .field("active_queue_family_indices", active_queue_family_indices)
+            .field("allocation_count", allocation_count)
+            .field("memory_tracker", memory_tracker)
+            .field("fence_pool", fence_pool)
+            .field("semaphore_pool", semaphore_pool)
+            .field("event_pool", event_pool)
+            .field("lost", lost)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Drop for Device {
@@ -669,6 +1683,10 @@ pub enum DeviceCreationError {
     OutOfHostMemory,
     /// There is no memory available on the device (ie. video memory).
     OutOfDeviceMemory,
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
     /// A restriction for an extension was not met.
     ExtensionRestrictionNotMet(ExtensionRestrictionError),
     /// A restriction for a feature was not met.
@@ -709,8 +1727,16 @@ impl Display for DeviceCreationError {
                 "you have reached the limit to the number of devices that can be created from the \
                 same physical device",
             ),
-            Self::ExtensionRestrictionNotMet(err) => err.fmt(f),
-            Self::FeatureRestrictionNotMet(err) => err.fmt(f),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::ExtensionRestrictionNotMet(err) => Display::fmt(err, f),
+            Self::FeatureRestrictionNotMet(err) => Display::fmt(err, f),
         }
     }
 }
@@ -730,6 +1756,15 @@ impl From<VulkanError> for DeviceCreationError {
     }
 }
 
+impl From<RequirementNotMet> for DeviceCreationError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 impl From<ExtensionRestrictionError> for DeviceCreationError {
     fn from(err: ExtensionRestrictionError) -> Self {
         Self::ExtensionRestrictionNotMet(err)
@@ -766,6 +1801,19 @@ pub struct DeviceCreateInfo {
     /// The default value is empty, which must be overridden.
     pub queue_create_infos: Vec<QueueCreateInfo>,
 
+    /// The physical devices that the device will span, if it is created from a device group.
+    ///
+    /// If not empty, the physical devices must all belong to the same group, as returned by
+    /// [`Instance::enumerate_physical_device_groups`], and must include the physical device that
+    /// is passed to [`Device::new`]. The order of the elements determines the device index of
+    /// each physical device. Additionally, the instance API version must be at least 1.1, or the
+    /// [`khr_device_group_creation`](crate::instance::InstanceExtensions::khr_device_group_creation)
+    /// extension must be enabled on the instance.
+    ///
+    /// The default value is empty, which creates a device for only the physical device that is
+    /// passed to `Device::new`.
+    pub physical_devices: SmallVec<[Arc<PhysicalDevice>; 2]>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -776,6 +1824,7 @@ impl Default for DeviceCreateInfo {
             enabled_extensions: DeviceExtensions::empty(),
             enabled_features: Features::empty(),
             queue_create_infos: Vec::new(),
+            physical_devices: SmallVec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -784,6 +1833,14 @@ impl Default for DeviceCreateInfo {
 /// Parameters to create queues in a new `Device`.
 #[derive(Clone, Debug)]
 pub struct QueueCreateInfo {
+    /// Additional properties of the queues.
+    ///
+    /// Multiple elements of [`DeviceCreateInfo::queue_create_infos`] can have the same
+    /// `queue_family_index`, as long as their `flags` are different.
+    ///
+    /// The default value is empty.
+    pub flags: QueueCreateFlags,
+
     /// The index of the queue family to create queues for.
     ///
     /// The default value is `0`.
@@ -806,6 +1863,7 @@ impl Default for QueueCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            flags: QueueCreateFlags::empty(),
             queue_family_index: 0,
             queues: vec![0.5],
             _ne: crate::NonExhaustive(()),
@@ -813,6 +1871,23 @@ impl Default for QueueCreateInfo {
     }
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags specifying additional properties of a queue.
+    QueueCreateFlags = DeviceQueueCreateFlags(u32);
+
+    /// The queues are protected-capable, and can be used for [protected submissions].
+    ///
+    /// The [`protected_memory`](Features::protected_memory) feature must be enabled on the
+    /// device, and the queue family must support [`QueueFlags::PROTECTED`].
+    ///
+    /// [protected submissions]: crate::command_buffer::SubmitInfo::protected
+    PROTECTED = PROTECTED {
+        api_version: V1_1,
+    },
+}
+
 /// Implemented on objects that belong to a Vulkan device.
 ///
 /// # Safety
@@ -833,6 +1908,221 @@ where
     }
 }
 
+/// The current budget and usage of a memory heap, as returned by [`Device::memory_budget`].
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct MemoryHeapBudget {
+    /// An estimate of how much memory, in bytes, the current process can allocate from the heap
+    /// before allocations may fail or cause performance degradation.
+    pub budget: DeviceSize,
+
+    /// An estimate of how much memory, in bytes, the current process is using in the heap.
+    pub usage: DeviceSize,
+}
+
+/// Timestamps that were sampled at the same moment from multiple time domains, as returned by
+/// [`Device::calibrate_timestamps`].
+///
+/// If the [`TimeDomain::Device`] time domain was sampled, then device timestamps, such as the
+/// results of timestamp queries, can be converted to the other time domains that were sampled.
+/// Because the clocks drift apart over time, the conversion becomes less accurate the further
+/// the timestamp is from the moment of calibration, so long-running applications should
+/// recalibrate regularly.
+#[derive(Clone, Debug)]
+pub struct CalibratedTimestamps {
+    time_domains: SmallVec<[TimeDomain; 4]>,
+    timestamps: SmallVec<[u64; 4]>,
+    max_deviation: u64,
+    timestamp_period: f32,
+    instant: Instant,
+}
+
+impl CalibratedTimestamps {
+    /// Returns the timestamp that was sampled from `time_domain`, or `None` if `time_domain` was
+    /// not sampled.
+    #[inline]
+    pub fn timestamp(&self, time_domain: TimeDomain) -> Option<u64> {
+        self.time_domains
+            .iter()
+            .position(|&td| td == time_domain)
+            .map(|index| self.timestamps[index])
+    }
+
+    /// Returns the maximum deviation, in nanoseconds, between the moments that the timestamps
+    /// were sampled at.
+    #[inline]
+    pub fn max_deviation(&self) -> u64 {
+        self.max_deviation
+    }
+
+    /// Returns the [`Instant`] that was taken on the host right after the timestamps were sampled.
+    ///
+    /// This is not sampled together with the timestamps, so it is only as accurate as the time it
+    /// took to return from the Vulkan call.
+    #[inline]
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    /// Converts `device_timestamp`, which is in the [`TimeDomain::Device`] time domain, to the
+    /// time domain `time_domain`.
+    ///
+    /// Returns `None` if either time domain was not sampled, if the result does not fit in a
+    /// `u64`, or if `time_domain` is [`TimeDomain::QueryPerformanceCounter`], whose frequency is
+    /// not known to vulkano.
+    #[inline]
+    pub fn device_timestamp_to(
+        &self,
+        device_timestamp: u64,
+        time_domain: TimeDomain,
+    ) -> Option<u64> {
+        let elapsed_nanos = self.device_elapsed_nanos(device_timestamp)?;
+
+        match time_domain {
+            TimeDomain::Device => Some(device_timestamp),
+            TimeDomain::ClockMonotonic | TimeDomain::ClockMonotonicRaw => {
+                let timestamp = self.timestamp(time_domain)?;
+                u64::try_from(timestamp as i128 + elapsed_nanos).ok()
+            }
+            TimeDomain::QueryPerformanceCounter => None,
+        }
+    }
+
+    /// Converts `device_timestamp`, which is in the [`TimeDomain::Device`] time domain, to an
+    /// [`Instant`] on the host, relative to [`instant`](Self::instant).
+    ///
+    /// Returns `None` if [`TimeDomain::Device`] was not sampled, or if the result can not be
+    /// represented by an `Instant`.
+    #[inline]
+    pub fn device_timestamp_to_instant(&self, device_timestamp: u64) -> Option<Instant> {
+        let elapsed_nanos = self.device_elapsed_nanos(device_timestamp)?;
+
+        if elapsed_nanos >= 0 {
+            self.instant
+                .checked_add(Duration::from_nanos(u64::try_from(elapsed_nanos).ok()?))
+        } else {
+            self.instant
+                .checked_sub(Duration::from_nanos(u64::try_from(-elapsed_nanos).ok()?))
+        }
+    }
+
+    /// Returns the number of nanoseconds between the calibration and `device_timestamp`.
+    fn device_elapsed_nanos(&self, device_timestamp: u64) -> Option<i128> {
+        let calibrated = self.timestamp(TimeDomain::Device)?;
+        let elapsed_ticks = device_timestamp as i128 - calibrated as i128;
+
+        Some((elapsed_ticks as f64 * self.timestamp_period as f64) as i128)
+    }
+}
+
+/// Information about the fault that caused a device to be lost, as returned by
+/// [`Device::fault_info`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeviceFaultInfo {
+    /// A human-readable description of the fault.
+    pub description: String,
+
+    /// Addresses that are related to the fault.
+    pub address_infos: Vec<DeviceFaultAddressInfo>,
+
+    /// Vendor-specific information about the fault.
+    pub vendor_infos: Vec<DeviceFaultVendorInfo>,
+
+    /// Vendor-specific binary crash dump data, which can be passed to tools provided by the
+    /// vendor for analysis.
+    ///
+    /// This is empty if the [`device_fault_vendor_binary`](Features::device_fault_vendor_binary)
+    /// feature is not enabled.
+    pub vendor_binary_data: Vec<u8>,
+}
+
+impl Display for DeviceFaultInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "{}", self.description)?;
+
+        for address_info in &self.address_infos {
+            write!(
+                f,
+                "; {:?} at {:#x} (precision {:#x})",
+                address_info.address_type,
+                address_info.reported_address,
+                address_info.address_precision,
+            )?;
+        }
+
+        for vendor_info in &self.vendor_infos {
+            write!(
+                f,
+                "; {} (code {:#x}, data {:#x})",
+                vendor_info.description,
+                vendor_info.vendor_fault_code,
+                vendor_info.vendor_fault_data,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An address that is related to a device fault.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeviceFaultAddressInfo {
+    /// The kind of operation that was performed at the address.
+    pub address_type: DeviceFaultAddressType,
+
+    /// The address that was reported by the device.
+    pub reported_address: DeviceSize,
+
+    /// The precision of `reported_address`, which is a power of two. The actual address lies
+    /// within the block of this size that contains `reported_address`.
+    pub address_precision: DeviceSize,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The kind of operation that a device fault address is related to.
+    DeviceFaultAddressType = DeviceFaultAddressTypeEXT(i32);
+
+    /// No address is available.
+    None = NONE,
+
+    /// The address of an invalid read access.
+    ReadInvalid = READ_INVALID,
+
+    /// The address of an invalid write access.
+    WriteInvalid = WRITE_INVALID,
+
+    /// The address of an invalid instruction fetch.
+    ExecuteInvalid = EXECUTE_INVALID,
+
+    /// The value of an instruction pointer at the time of the fault, which may or may not be
+    /// related to the fault.
+    InstructionPointerUnknown = INSTRUCTION_POINTER_UNKNOWN,
+
+    /// The instruction pointer of an invalid instruction.
+    InstructionPointerInvalid = INSTRUCTION_POINTER_INVALID,
+
+    /// The instruction pointer of an instruction that caused the fault.
+    InstructionPointerFault = INSTRUCTION_POINTER_FAULT,
+}
+
+/// Vendor-specific information about a device fault.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DeviceFaultVendorInfo {
+    /// A human-readable description of the fault.
+    pub description: String,
+
+    /// A vendor-specific code that identifies the fault.
+    pub vendor_fault_code: u64,
+
+    /// Vendor-specific data associated with the fault.
+    pub vendor_fault_data: u64,
+}
+
 /// The properties of a Unix file descriptor when it is imported.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -841,7 +2131,54 @@ pub struct MemoryFdProperties {
     pub memory_type_bits: u32,
 }
 
-/// Error that can happen when calling `memory_fd_properties`.
+/// The properties of a pointer to host memory when it is imported.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct MemoryHostPointerProperties {
+    /// A bitmask of the indices of memory types that can be used with the host memory.
+    pub memory_type_bits: u32,
+}
+
+/// The properties of an Android hardware buffer when it is imported.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct AndroidHardwareBufferProperties {
+    /// The size of the memory allocation that must be used to import the buffer.
+    pub allocation_size: DeviceSize,
+
+    /// A bitmask of the indices of memory types that can be used with the buffer.
+    pub memory_type_bits: u32,
+
+    /// The Vulkan format that corresponds to the format of the buffer, or `None` if the buffer
+    /// has an implementation-defined external format.
+    pub format: Option<Format>,
+
+    /// An implementation-defined external format identifier, or 0 if there is none.
+    ///
+    /// This is typically used by YUV buffers produced by the camera or media codecs.
+    pub external_format: u64,
+
+    /// The features that are supported for an image created from the buffer.
+    pub format_features: FormatFeatures,
+
+    /// The component swizzle that should be used in a sampler YCbCr conversion for the buffer.
+    pub sampler_ycbcr_conversion_components: ComponentMapping,
+
+    /// The suggested color model for a sampler YCbCr conversion for the buffer.
+    pub suggested_ycbcr_model: SamplerYcbcrModelConversion,
+
+    /// The suggested numerical value range for a sampler YCbCr conversion for the buffer.
+    pub suggested_ycbcr_range: SamplerYcbcrRange,
+
+    /// The suggested X chroma offset for a sampler YCbCr conversion for the buffer.
+    pub suggested_x_chroma_offset: ChromaLocation,
+
+    /// The suggested Y chroma offset for a sampler YCbCr conversion for the buffer.
+    pub suggested_y_chroma_offset: ChromaLocation,
+}
+
+/// Error that can happen when calling `memory_fd_properties`,
+/// `memory_host_pointer_properties` or `android_hardware_buffer_properties`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum MemoryFdPropertiesError {
     /// No memory available on the host.
@@ -909,13 +2246,346 @@ impl From<RequirementNotMet> for MemoryFdPropertiesError {
     }
 }
 
+/// Error that can happen when calling [`Device::memory_budget`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MemoryBudgetError {
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for MemoryBudgetError {}
+
+impl Display for MemoryBudgetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for MemoryBudgetError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+/// Error that can happen when calling [`Device::calibrate_timestamps`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CalibrateTimestampsError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// A time domain is not one of the calibrateable time domains of the physical device.
+    TimeDomainNotSupported { time_domain: TimeDomain },
+
+    /// A time domain was provided more than once.
+    TimeDomainNotUnique { time_domain: TimeDomain },
+}
+
+impl Error for CalibrateTimestampsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CalibrateTimestampsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::TimeDomainNotSupported { time_domain } => write!(
+                f,
+                "the time domain {:?} is not one of the calibrateable time domains of the \
+                physical device",
+                time_domain,
+            ),
+            Self::TimeDomainNotUnique { time_domain } => write!(
+                f,
+                "the time domain {:?} was provided more than once",
+                time_domain,
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for CalibrateTimestampsError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            VulkanError::OutOfHostMemory | VulkanError::OutOfDeviceMemory => {
+                Self::OomError(OomError::from(err))
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for CalibrateTimestampsError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+/// Error that can happen when calling [`Device::fault_info`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeviceFaultError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for DeviceFaultError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DeviceFaultError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for DeviceFaultError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            VulkanError::OutOfHostMemory | VulkanError::OutOfDeviceMemory => {
+                Self::OomError(OomError::from(err))
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for DeviceFaultError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+/// Error that can happen when calling [`Device::acquire_profiling_lock`] or
+/// [`Device::release_profiling_lock`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfilingLockError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The profiling lock could not be acquired before the timeout expired.
+    Timeout,
+}
+
+impl Error for ProfilingLockError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ProfilingLockError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::Timeout => write!(
+                f,
+                "the profiling lock could not be acquired before the timeout expired",
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for ProfilingLockError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            VulkanError::OutOfHostMemory | VulkanError::OutOfDeviceMemory => {
+                Self::OomError(OomError::from(err))
+            }
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for ProfilingLockError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+/// Error that can happen when calling [`Device::group_peer_memory_features`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeerMemoryFeaturesError {
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for PeerMemoryFeaturesError {}
+
+impl Display for PeerMemoryFeaturesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for PeerMemoryFeaturesError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+/// Error that can happen when calling `set_debug_utils_object_name`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugUtilsError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for DebugUtilsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for DebugUtilsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for DebugUtilsError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            err @ VulkanError::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for DebugUtilsError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<RequirementNotMet> for DebugUtilsError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::device::{
-        Device, DeviceCreateInfo, DeviceCreationError, FeatureRestriction, FeatureRestrictionError,
-        Features, QueueCreateInfo,
+    use crate::{
+        device::{
+            physical::TimeDomain, CalibratedTimestamps, Device, DeviceCreateInfo,
+            DeviceCreationError, DeviceFaultError, FeatureRestriction, FeatureRestrictionError,
+            Features, QueueCreateInfo,
+        },
+        sync::FlushError,
+        RequiresOneOf,
     };
-    use std::sync::Arc;
+    use smallvec::smallvec;
+    use std::{sync::Arc, time::Instant};
 
     #[test]
     fn one_ref() {
@@ -923,6 +2593,30 @@ mod tests {
         assert!(Arc::get_mut(&mut device).is_some());
     }
 
+    #[test]
+    fn calibrated_timestamps_conversion() {
+        let calibrated_timestamps = CalibratedTimestamps {
+            time_domains: smallvec![TimeDomain::Device, TimeDomain::ClockMonotonic],
+            timestamps: smallvec![1000, 5_000_000],
+            max_deviation: 0,
+            timestamp_period: 2.0,
+            instant: Instant::now(),
+        };
+
+        assert_eq!(
+            calibrated_timestamps.device_timestamp_to(1500, TimeDomain::ClockMonotonic),
+            Some(5_001_000),
+        );
+        assert_eq!(
+            calibrated_timestamps.device_timestamp_to(500, TimeDomain::ClockMonotonic),
+            Some(4_999_000),
+        );
+        assert_eq!(
+            calibrated_timestamps.device_timestamp_to(500, TimeDomain::ClockMonotonicRaw),
+            None,
+        );
+    }
+
     #[test]
     fn too_many_queues() {
         let instance = instance!();
@@ -1023,4 +2717,31 @@ mod tests {
             )
         });
     }
+
+    #[test]
+    fn fault_info_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match device.fault_info() {
+            Err(DeviceFaultError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"device_fault") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn device_lost_without_device_fault() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(matches!(
+            FlushError::DeviceLost.with_device_fault(&device),
+            FlushError::DeviceLost,
+        ));
+        assert!(matches!(
+            FlushError::OutOfDate.with_device_fault(&device),
+            FlushError::OutOfDate,
+        ));
+    }
 }
@@ -12,21 +12,29 @@ use crate::{
     buffer::{ExternalBufferInfo, ExternalBufferProperties},
     cache::OnceCache,
     device::{properties::Properties, DeviceExtensions, Features, FeaturesFfi, PropertiesFfi},
-    format::{Format, FormatProperties},
+    format::{DrmFormatModifierProperties, Format, FormatProperties},
     image::{
-        ImageAspects, ImageFormatInfo, ImageFormatProperties, ImageUsage, SparseImageFormatInfo,
-        SparseImageFormatProperties,
+        ImageAspects, ImageFormatInfo, ImageFormatProperties, ImageTiling, ImageUsage,
+        SparseImageFormatInfo, SparseImageFormatProperties,
     },
     instance::Instance,
     macros::{vulkan_bitflags, vulkan_enum},
     memory::MemoryProperties,
+    query::PerformanceCounter,
+    sampler::ComponentMapping,
     swapchain::{
+        display::{self, DisplayError, DisplayPlane},
         ColorSpace, FullScreenExclusive, PresentMode, Surface, SurfaceApi, SurfaceCapabilities,
         SurfaceInfo, SurfaceTransforms,
     },
     sync::{
         fence::{ExternalFenceInfo, ExternalFenceProperties},
         semaphore::{ExternalSemaphoreInfo, ExternalSemaphoreProperties},
+        Sharing,
+    },
+    video::{
+        VideoCapabilities, VideoCodecCapabilities, VideoCodecOperation, VideoFormatInfo,
+        VideoFormatProperties, VideoProfileInfo, VideoProfileListInfoVk,
     },
     ExtensionProperties, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
@@ -84,6 +92,7 @@ pub struct PhysicalDevice {
     image_format_properties: OnceCache<ImageFormatInfo, Option<ImageFormatProperties>>,
     sparse_image_format_properties:
         OnceCache<SparseImageFormatInfo, Vec<SparseImageFormatProperties>>,
+    queue_family_performance_counters: OnceCache<u32, Vec<PerformanceCounter>>,
 }
 
 impl PhysicalDevice {
@@ -120,7 +129,8 @@ impl PhysicalDevice {
             properties =
                 Self::get_properties2(handle, &instance, api_version, &supported_extensions);
             memory_properties = Self::get_memory_properties2(handle, &instance);
-            queue_family_properties = Self::get_queue_family_properties2(handle, &instance);
+            queue_family_properties =
+                Self::get_queue_family_properties2(handle, &instance, &supported_extensions);
         } else {
             supported_features = Self::get_features(handle, &instance);
             properties =
@@ -146,6 +156,7 @@ impl PhysicalDevice {
             format_properties: OnceCache::new(),
             image_format_properties: OnceCache::new(),
             sparse_image_format_properties: OnceCache::new(),
+            queue_family_performance_counters: OnceCache::new(),
         }))
     }
 
@@ -324,6 +335,7 @@ impl PhysicalDevice {
     unsafe fn get_queue_family_properties2(
         handle: ash::vk::PhysicalDevice,
         instance: &Instance,
+        supported_extensions: &DeviceExtensions,
     ) -> Vec<QueueFamilyProperties> {
         let mut num = 0;
         let fns = instance.fns();
@@ -344,6 +356,19 @@ impl PhysicalDevice {
         }
 
         let mut output = vec![ash::vk::QueueFamilyProperties2::default(); num as usize];
+        let mut video_properties_vk = None;
+
+        if supported_extensions.khr_video_queue {
+            let video_properties_vk = video_properties_vk.insert(vec![
+                    ash::vk::QueueFamilyVideoPropertiesKHR::default();
+                    num as usize
+                ]);
+
+            for (output, video_properties_vk) in output.iter_mut().zip(video_properties_vk) {
+                video_properties_vk.p_next = output.p_next;
+                output.p_next = video_properties_vk as *mut _ as *mut _;
+            }
+        }
 
         if instance.api_version() >= Version::V1_1 {
             (fns.v1_1.get_physical_device_queue_family_properties2)(
@@ -362,7 +387,15 @@ impl PhysicalDevice {
 
         output
             .into_iter()
-            .map(|family| family.queue_family_properties.into())
+            .enumerate()
+            .map(|(index, family)| QueueFamilyProperties {
+                video_codec_operations: video_properties_vk
+                    .as_ref()
+                    .map_or_else(Default::default, |video_properties_vk| {
+                        video_properties_vk[index].video_codec_operations.into()
+                    }),
+                ..family.queue_family_properties.into()
+            })
             .collect()
     }
 
@@ -418,6 +451,168 @@ impl PhysicalDevice {
         &self.queue_family_properties
     }
 
+    /// Returns the time domains that can be sampled together with
+    /// [`Device::calibrate_timestamps`].
+    ///
+    /// The results of this function are not cached.
+    ///
+    /// The [`ext_calibrated_timestamps`](crate::device::DeviceExtensions::ext_calibrated_timestamps)
+    /// extension must be supported by the physical device.
+    ///
+    /// [`Device::calibrate_timestamps`]: crate::device::Device::calibrate_timestamps
+    #[inline]
+    pub fn calibrateable_time_domains(&self) -> Result<Vec<TimeDomain>, PhysicalDeviceError> {
+        self.validate_calibrateable_time_domains()?;
+
+        unsafe { Ok(self.calibrateable_time_domains_unchecked()?) }
+    }
+
+    fn validate_calibrateable_time_domains(&self) -> Result<(), PhysicalDeviceError> {
+        if !self.supported_extensions().ext_calibrated_timestamps {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::calibrateable_time_domains`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_calibrated_timestamps"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn calibrateable_time_domains_unchecked(
+        &self,
+    ) -> Result<Vec<TimeDomain>, VulkanError> {
+        let fns = self.instance.fns();
+
+        loop {
+            let mut count = 0;
+            (fns.ext_calibrated_timestamps
+                .get_physical_device_calibrateable_time_domains_ext)(
+                self.handle,
+                &mut count,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            let mut time_domains = Vec::with_capacity(count as usize);
+            let result = (fns
+                .ext_calibrated_timestamps
+                .get_physical_device_calibrateable_time_domains_ext)(
+                self.handle,
+                &mut count,
+                time_domains.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => {
+                    time_domains.set_len(count as usize);
+
+                    return Ok(time_domains
+                        .into_iter()
+                        .filter_map(|time_domain| time_domain.try_into().ok())
+                        .collect());
+                }
+                ash::vk::Result::INCOMPLETE => (),
+                err => return Err(VulkanError::from(err)),
+            }
+        }
+    }
+
+    /// Returns the combinations of matrix sizes and component types that are supported for
+    /// cooperative matrix operations in shaders.
+    ///
+    /// To use cooperative matrices in shaders, the
+    /// [`cooperative_matrix`](crate::device::Features::cooperative_matrix) feature must also be
+    /// enabled on the device. The shader stages that support them are given by the
+    /// [`cooperative_matrix_supported_stages`](crate::device::Properties::cooperative_matrix_supported_stages)
+    /// property.
+    ///
+    /// The results of this function are not cached.
+    ///
+    /// The [`nv_cooperative_matrix`](crate::device::DeviceExtensions::nv_cooperative_matrix)
+    /// extension must be supported by the physical device.
+    #[inline]
+    pub fn cooperative_matrix_properties(
+        &self,
+    ) -> Result<Vec<CooperativeMatrixProperties>, PhysicalDeviceError> {
+        self.validate_cooperative_matrix_properties()?;
+
+        unsafe { Ok(self.cooperative_matrix_properties_unchecked()?) }
+    }
+
+    fn validate_cooperative_matrix_properties(&self) -> Result<(), PhysicalDeviceError> {
+        if !self.supported_extensions().nv_cooperative_matrix {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::cooperative_matrix_properties`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["nv_cooperative_matrix"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn cooperative_matrix_properties_unchecked(
+        &self,
+    ) -> Result<Vec<CooperativeMatrixProperties>, VulkanError> {
+        let fns = self.instance.fns();
+
+        loop {
+            let mut count = 0;
+            (fns.nv_cooperative_matrix
+                .get_physical_device_cooperative_matrix_properties_nv)(
+                self.handle,
+                &mut count,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            let mut properties =
+                vec![ash::vk::CooperativeMatrixPropertiesNV::default(); count as usize];
+            let result = (fns
+                .nv_cooperative_matrix
+                .get_physical_device_cooperative_matrix_properties_nv)(
+                self.handle,
+                &mut count,
+                properties.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => {
+                    properties.truncate(count as usize);
+
+                    return Ok(properties
+                        .into_iter()
+                        .filter_map(|properties| {
+                            Some(CooperativeMatrixProperties {
+                                m_size: properties.m_size,
+                                n_size: properties.n_size,
+                                k_size: properties.k_size,
+                                a_type: properties.a_type.try_into().ok()?,
+                                b_type: properties.b_type.try_into().ok()?,
+                                c_type: properties.c_type.try_into().ok()?,
+                                d_type: properties.d_type.try_into().ok()?,
+                                scope: properties.scope.try_into().ok()?,
+                            })
+                        })
+                        .collect());
+                }
+                ash::vk::Result::INCOMPLETE => (),
+                err => return Err(VulkanError::from(err)),
+            }
+        }
+    }
+
     /// Queries whether the physical device supports presenting to DirectFB surfaces from queues of
     /// the given queue family.
     ///
@@ -480,6 +675,25 @@ impl PhysicalDevice {
         ) != 0
     }
 
+    /// Returns the display planes of the physical device, which can be used to create a
+    /// [`Surface`] directly on a display with [`Surface::from_display_plane`].
+    ///
+    /// The [`khr_display`](crate::instance::InstanceExtensions::khr_display) extension must be
+    /// enabled on the instance.
+    #[inline]
+    pub fn display_planes(self: &Arc<Self>) -> Result<Vec<DisplayPlane>, DisplayError> {
+        DisplayPlane::enumerate_raw(self.clone()).map(Iterator::collect)
+    }
+
+    /// Returns the displays that are connected to the physical device.
+    ///
+    /// The [`khr_display`](crate::instance::InstanceExtensions::khr_display) extension must be
+    /// enabled on the instance.
+    #[inline]
+    pub fn displays(self: &Arc<Self>) -> Result<Vec<display::Display>, DisplayError> {
+        display::Display::enumerate_raw(self.clone()).map(Iterator::collect)
+    }
+
     /// Retrieves the external memory properties supported for buffers with a given configuration.
     ///
     /// Instance API version must be at least 1.1, or the [`khr_external_memory_capabilities`]
@@ -901,6 +1115,113 @@ impl PhysicalDevice {
         })
     }
 
+    /// Retrieves the Linux DRM format modifiers that are supported for a format, and their
+    /// properties.
+    ///
+    /// The [`ext_image_drm_format_modifier`](crate::device::DeviceExtensions::ext_image_drm_format_modifier)
+    /// extension must be supported by the physical device.
+    #[inline]
+    pub fn format_drm_modifier_properties(
+        &self,
+        format: Format,
+    ) -> Result<Vec<DrmFormatModifierProperties>, PhysicalDeviceError> {
+        self.validate_format_drm_modifier_properties(format)?;
+
+        unsafe { Ok(self.format_drm_modifier_properties_unchecked(format)) }
+    }
+
+    fn validate_format_drm_modifier_properties(
+        &self,
+        format: Format,
+    ) -> Result<(), PhysicalDeviceError> {
+        if !self.supported_extensions().ext_image_drm_format_modifier {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::format_drm_modifier_properties`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_image_drm_format_modifier"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        if !(self.api_version() >= Version::V1_1
+            || self
+                .instance
+                .enabled_extensions()
+                .khr_get_physical_device_properties2)
+        {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::format_drm_modifier_properties`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_1),
+                    instance_extensions: &["khr_get_physical_device_properties2"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetPhysicalDeviceFormatProperties2-format-parameter
+        format.validate_physical_device(self)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn format_drm_modifier_properties_unchecked(
+        &self,
+        format: Format,
+    ) -> Vec<DrmFormatModifierProperties> {
+        let fns = self.instance.fns();
+
+        let call = |format_properties2: &mut ash::vk::FormatProperties2| {
+            if self.api_version() >= Version::V1_1 {
+                (fns.v1_1.get_physical_device_format_properties2)(
+                    self.handle,
+                    format.into(),
+                    format_properties2,
+                );
+            } else {
+                (fns.khr_get_physical_device_properties2
+                    .get_physical_device_format_properties2_khr)(
+                    self.handle,
+                    format.into(),
+                    format_properties2,
+                );
+            }
+        };
+
+        let mut list_vk = ash::vk::DrmFormatModifierPropertiesListEXT::default();
+        let mut format_properties2 = ash::vk::FormatProperties2 {
+            p_next: &mut list_vk as *mut _ as *mut _,
+            ..Default::default()
+        };
+        call(&mut format_properties2);
+
+        let mut properties_vk = Vec::with_capacity(list_vk.drm_format_modifier_count as usize);
+        let mut list_vk = ash::vk::DrmFormatModifierPropertiesListEXT {
+            drm_format_modifier_count: properties_vk.capacity() as u32,
+            p_drm_format_modifier_properties: properties_vk.as_mut_ptr(),
+            ..Default::default()
+        };
+        let mut format_properties2 = ash::vk::FormatProperties2 {
+            p_next: &mut list_vk as *mut _ as *mut _,
+            ..Default::default()
+        };
+        call(&mut format_properties2);
+        properties_vk.set_len(list_vk.drm_format_modifier_count as usize);
+
+        properties_vk
+            .into_iter()
+            .map(|properties_vk| DrmFormatModifierProperties {
+                drm_format_modifier: properties_vk.drm_format_modifier,
+                drm_format_modifier_plane_count: properties_vk.drm_format_modifier_plane_count,
+                drm_format_modifier_tiling_features: properties_vk
+                    .drm_format_modifier_tiling_features
+                    .into(),
+            })
+            .collect()
+    }
+
     /// Returns the properties supported for images with a given image configuration.
     ///
     /// `Some` is returned if the configuration is supported, `None` if it is not.
@@ -935,6 +1256,8 @@ impl PhysicalDevice {
             mut stencil_usage,
             external_memory_handle_type,
             image_view_type,
+            drm_format_modifier,
+            ref sharing,
             _ne: _,
         } = image_format_info;
 
@@ -959,6 +1282,27 @@ impl PhysicalDevice {
         // VUID-VkPhysicalDeviceImageFormatInfo2-tiling-parameter
         tiling.validate_physical_device(self)?;
 
+        // VUID-VkPhysicalDeviceImageFormatInfo2-tiling-02249
+        if (tiling == ImageTiling::DrmFormatModifier) != drm_format_modifier.is_some() {
+            return Err(PhysicalDeviceError::DrmFormatModifierTilingMismatch);
+        }
+
+        if drm_format_modifier.is_some() {
+            if let Sharing::Concurrent(queue_family_indices) = sharing {
+                let queue_family_count = self.queue_family_properties().len() as u32;
+
+                for &queue_family_index in queue_family_indices {
+                    // VUID?
+                    if queue_family_index >= queue_family_count {
+                        return Err(PhysicalDeviceError::QueueFamilyIndexOutOfRange {
+                            queue_family_index,
+                            queue_family_count,
+                        });
+                    }
+                }
+            }
+        }
+
         // VUID-VkPhysicalDeviceImageFormatInfo2-usage-parameter
         usage.validate_physical_device(self)?;
 
@@ -1061,6 +1405,8 @@ impl PhysicalDevice {
                     stencil_usage,
                     external_memory_handle_type,
                     image_view_type,
+                    drm_format_modifier,
+                    ref sharing,
                     _ne: _,
                 } = image_format_info;
 
@@ -1077,6 +1423,7 @@ impl PhysicalDevice {
                 let mut external_info_vk = None;
                 let mut image_view_info_vk = None;
                 let mut stencil_usage_info_vk = None;
+                let mut drm_format_modifier_info_vk = None;
 
                 if let Some(handle_type) = external_memory_handle_type {
                     let next =
@@ -1111,6 +1458,31 @@ impl PhysicalDevice {
                     info2_vk.p_next = next as *const _ as *const _;
                 }
 
+                if let Some(drm_format_modifier) = drm_format_modifier {
+                    let (sharing_mode, queue_family_index_count, p_queue_family_indices) =
+                        match sharing {
+                            Sharing::Exclusive => (ash::vk::SharingMode::EXCLUSIVE, 0, ptr::null()),
+                            Sharing::Concurrent(ids) => (
+                                ash::vk::SharingMode::CONCURRENT,
+                                ids.len() as u32,
+                                ids.as_ptr(),
+                            ),
+                        };
+
+                    let next = drm_format_modifier_info_vk.insert(
+                        ash::vk::PhysicalDeviceImageDrmFormatModifierInfoEXT {
+                            drm_format_modifier,
+                            sharing_mode,
+                            queue_family_index_count,
+                            p_queue_family_indices,
+                            ..Default::default()
+                        },
+                    );
+
+                    next.p_next = info2_vk.p_next as *mut _;
+                    info2_vk.p_next = next as *const _ as *const _;
+                }
+
                 /* Output */
 
                 let mut properties2_vk = ash::vk::ImageFormatProperties2::default();
@@ -1254,6 +1626,231 @@ impl PhysicalDevice {
         ) != 0
     }
 
+    /// Returns the performance counters that can be read by performance queries on queues of the
+    /// given queue family.
+    ///
+    /// The index of a counter in the returned list is the index that must be given in
+    /// [`QueryPoolCreateInfo::performance_counter_indices`].
+    ///
+    /// The results of this function are cached, so that future calls with the same arguments
+    /// do not need to make a call to the Vulkan API again.
+    ///
+    /// The [`khr_performance_query`](crate::device::DeviceExtensions::khr_performance_query)
+    /// extension must be supported by the physical device.
+    ///
+    /// [`QueryPoolCreateInfo::performance_counter_indices`]: crate::query::QueryPoolCreateInfo::performance_counter_indices
+    #[inline]
+    pub fn queue_family_performance_counters(
+        &self,
+        queue_family_index: u32,
+    ) -> Result<Vec<PerformanceCounter>, PhysicalDeviceError> {
+        self.validate_queue_family_performance_counters(queue_family_index)?;
+
+        unsafe { Ok(self.queue_family_performance_counters_unchecked(queue_family_index)?) }
+    }
+
+    fn validate_queue_family_performance_counters(
+        &self,
+        queue_family_index: u32,
+    ) -> Result<(), PhysicalDeviceError> {
+        if !self.supported_extensions().khr_performance_query {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::queue_family_performance_counters`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_performance_query"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID?
+        if queue_family_index >= self.queue_family_properties.len() as u32 {
+            return Err(PhysicalDeviceError::QueueFamilyIndexOutOfRange {
+                queue_family_index,
+                queue_family_count: self.queue_family_properties.len() as u32,
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn queue_family_performance_counters_unchecked(
+        &self,
+        queue_family_index: u32,
+    ) -> Result<Vec<PerformanceCounter>, VulkanError> {
+        self.queue_family_performance_counters.get_or_try_insert(
+            queue_family_index,
+            |&queue_family_index| {
+                let fns = self.instance.fns();
+
+                loop {
+                    let mut count = 0;
+                    (fns.khr_performance_query
+                        .enumerate_physical_device_queue_family_performance_query_counters_khr)(
+                        self.handle,
+                        queue_family_index,
+                        &mut count,
+                        ptr::null_mut(),
+                        ptr::null_mut(),
+                    )
+                    .result()
+                    .map_err(VulkanError::from)?;
+
+                    let mut counters =
+                        vec![ash::vk::PerformanceCounterKHR::default(); count as usize];
+                    let mut descriptions =
+                        vec![ash::vk::PerformanceCounterDescriptionKHR::default(); count as usize];
+                    let result = (fns
+                        .khr_performance_query
+                        .enumerate_physical_device_queue_family_performance_query_counters_khr)(
+                        self.handle,
+                        queue_family_index,
+                        &mut count,
+                        counters.as_mut_ptr(),
+                        descriptions.as_mut_ptr(),
+                    );
+
+                    match result {
+                        ash::vk::Result::SUCCESS => {
+                            counters.truncate(count as usize);
+                            descriptions.truncate(count as usize);
+
+                            return Ok(counters
+                                .into_iter()
+                                .zip(descriptions)
+                                .filter_map(|(counter, description)| {
+                                    Some(PerformanceCounter {
+                                        unit: counter.unit.try_into().ok()?,
+                                        scope: counter.scope.try_into().ok()?,
+                                        storage: counter.storage.try_into().ok()?,
+                                        uuid: counter.uuid,
+                                        flags: description.flags.into(),
+                                        name: {
+                                            let bytes = cast_slice(description.name.as_slice());
+                                            let end = bytes
+                                                .iter()
+                                                .position(|&b| b == 0)
+                                                .unwrap_or(bytes.len());
+                                            String::from_utf8_lossy(&bytes[0..end]).into()
+                                        },
+                                        category: {
+                                            let bytes = cast_slice(description.category.as_slice());
+                                            let end = bytes
+                                                .iter()
+                                                .position(|&b| b == 0)
+                                                .unwrap_or(bytes.len());
+                                            String::from_utf8_lossy(&bytes[0..end]).into()
+                                        },
+                                        description: {
+                                            let bytes =
+                                                cast_slice(description.description.as_slice());
+                                            let end = bytes
+                                                .iter()
+                                                .position(|&b| b == 0)
+                                                .unwrap_or(bytes.len());
+                                            String::from_utf8_lossy(&bytes[0..end]).into()
+                                        },
+                                    })
+                                })
+                                .collect());
+                        }
+                        ash::vk::Result::INCOMPLETE => (),
+                        err => return Err(VulkanError::from(err)),
+                    }
+                }
+            },
+        )
+    }
+
+    /// Returns the number of passes that are needed to read the given performance counters of
+    /// the given queue family in a single performance query.
+    ///
+    /// A performance query with these counters must be submitted this many times, each time
+    /// with a different [`SubmitInfo::performance_counter_pass_index`], before its results are
+    /// available.
+    ///
+    /// The results of this function are not cached.
+    ///
+    /// The [`khr_performance_query`](crate::device::DeviceExtensions::khr_performance_query)
+    /// extension must be supported by the physical device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `counter_indices` is empty.
+    ///
+    /// [`SubmitInfo::performance_counter_pass_index`]: crate::command_buffer::SubmitInfo::performance_counter_pass_index
+    #[inline]
+    pub fn queue_family_performance_query_passes(
+        &self,
+        queue_family_index: u32,
+        counter_indices: &[u32],
+    ) -> Result<u32, PhysicalDeviceError> {
+        self.validate_queue_family_performance_query_passes(queue_family_index, counter_indices)?;
+
+        unsafe {
+            Ok(self.queue_family_performance_query_passes_unchecked(
+                queue_family_index,
+                counter_indices,
+            ))
+        }
+    }
+
+    fn validate_queue_family_performance_query_passes(
+        &self,
+        queue_family_index: u32,
+        counter_indices: &[u32],
+    ) -> Result<(), PhysicalDeviceError> {
+        self.validate_queue_family_performance_counters(queue_family_index)?;
+
+        // VUID-VkQueryPoolPerformanceCreateInfoKHR-counterIndexCount-arraylength
+        assert!(!counter_indices.is_empty());
+
+        let counter_count = unsafe {
+            self.queue_family_performance_counters_unchecked(queue_family_index)?
+                .len() as u32
+        };
+
+        for &counter_index in counter_indices {
+            // VUID-VkQueryPoolPerformanceCreateInfoKHR-pCounterIndices-03321
+            if counter_index >= counter_count {
+                return Err(PhysicalDeviceError::PerformanceCounterIndexOutOfRange {
+                    counter_index,
+                    counter_count,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn queue_family_performance_query_passes_unchecked(
+        &self,
+        queue_family_index: u32,
+        counter_indices: &[u32],
+    ) -> u32 {
+        let performance_query_create_info = ash::vk::QueryPoolPerformanceCreateInfoKHR {
+            queue_family_index,
+            counter_index_count: counter_indices.len() as u32,
+            p_counter_indices: counter_indices.as_ptr(),
+            ..Default::default()
+        };
+
+        let mut num_passes = 0;
+        let fns = self.instance.fns();
+        (fns.khr_performance_query
+            .get_physical_device_queue_family_performance_query_passes_khr)(
+            self.handle,
+            &performance_query_create_info,
+            &mut num_passes,
+        );
+
+        num_passes
+    }
+
     /// Returns the properties of sparse images with a given image configuration.
     ///
     /// The results of this function are cached, so that future calls with the same arguments
@@ -1581,6 +2178,7 @@ impl PhysicalDevice {
         let mut capabilities_vk = ash::vk::SurfaceCapabilities2KHR::default();
         let mut capabilities_full_screen_exclusive_vk = None;
         let mut protected_capabilities_vk = None;
+        let mut shared_present_capabilities_vk = None;
 
         if full_screen_exclusive_info_vk.is_some() {
             let next = capabilities_full_screen_exclusive_vk
@@ -1602,6 +2200,19 @@ impl PhysicalDevice {
             capabilities_vk.p_next = next as *mut _ as *mut _;
         }
 
+        if self
+            .instance
+            .enabled_extensions()
+            .khr_get_surface_capabilities2
+            && self.supported_extensions().khr_shared_presentable_image
+        {
+            let next = shared_present_capabilities_vk
+                .insert(ash::vk::SharedPresentSurfaceCapabilitiesKHR::default());
+
+            next.p_next = capabilities_vk.p_next as *mut _;
+            capabilities_vk.p_next = next as *mut _ as *mut _;
+        }
+
         let fns = self.instance.fns();
 
         if self
@@ -1681,6 +2292,11 @@ impl PhysicalDevice {
 
             full_screen_exclusive_supported: capabilities_full_screen_exclusive_vk
                 .map_or(false, |c| c.full_screen_exclusive_supported != 0),
+
+            shared_present_supported_usage_flags: shared_present_capabilities_vk
+                .map_or_else(ImageUsage::empty, |c| {
+                    c.shared_present_supported_usage_flags.into()
+                }),
         })
     }
 
@@ -2176,6 +2792,310 @@ impl PhysicalDevice {
         }
     }
 
+    /// Returns the capabilities of the physical device for a video profile.
+    ///
+    /// The results of this function are not cached.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `video_profile` is not a valid video profile.
+    #[inline]
+    pub fn video_capabilities(
+        &self,
+        video_profile: &VideoProfileInfo,
+    ) -> Result<VideoCapabilities, PhysicalDeviceError> {
+        self.validate_video_capabilities(video_profile)?;
+
+        unsafe { Ok(self.video_capabilities_unchecked(video_profile)?) }
+    }
+
+    fn validate_video_capabilities(
+        &self,
+        video_profile: &VideoProfileInfo,
+    ) -> Result<(), PhysicalDeviceError> {
+        if !self.supported_extensions().khr_video_queue {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::video_capabilities`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_video_queue"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetPhysicalDeviceVideoCapabilitiesKHR-pVideoProfile-parameter
+        video_profile.validate(self)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn video_capabilities_unchecked(
+        &self,
+        video_profile: &VideoProfileInfo,
+    ) -> Result<VideoCapabilities, VulkanError> {
+        let video_profile_vk = video_profile.to_vulkan();
+
+        let mut capabilities_vk = ash::vk::VideoCapabilitiesKHR::default();
+        let mut decode_capabilities_vk = ash::vk::VideoDecodeCapabilitiesKHR::default();
+        let mut decode_h264_capabilities_vk = ash::vk::VideoDecodeH264CapabilitiesEXT::default();
+        let mut decode_h265_capabilities_vk = ash::vk::VideoDecodeH265CapabilitiesEXT::default();
+        let mut encode_capabilities_vk = ash::vk::VideoEncodeCapabilitiesKHR::default();
+        let mut encode_h264_capabilities_vk = ash::vk::VideoEncodeH264CapabilitiesEXT::default();
+        let mut encode_h265_capabilities_vk = ash::vk::VideoEncodeH265CapabilitiesEXT::default();
+
+        let video_codec_operation = video_profile.video_codec_operation();
+
+        if video_codec_operation.is_decode() {
+            decode_capabilities_vk.p_next = capabilities_vk.p_next;
+            capabilities_vk.p_next = &mut decode_capabilities_vk as *mut _ as *mut _;
+        }
+
+        if video_codec_operation.is_encode() {
+            encode_capabilities_vk.p_next = capabilities_vk.p_next;
+            capabilities_vk.p_next = &mut encode_capabilities_vk as *mut _ as *mut _;
+        }
+
+        match video_codec_operation {
+            VideoCodecOperation::DecodeH264 => {
+                decode_h264_capabilities_vk.p_next = capabilities_vk.p_next;
+                capabilities_vk.p_next = &mut decode_h264_capabilities_vk as *mut _ as *mut _;
+            }
+            VideoCodecOperation::DecodeH265 => {
+                decode_h265_capabilities_vk.p_next = capabilities_vk.p_next;
+                capabilities_vk.p_next = &mut decode_h265_capabilities_vk as *mut _ as *mut _;
+            }
+            VideoCodecOperation::EncodeH264 => {
+                encode_h264_capabilities_vk.p_next = capabilities_vk.p_next;
+                capabilities_vk.p_next = &mut encode_h264_capabilities_vk as *mut _ as *mut _;
+            }
+            VideoCodecOperation::EncodeH265 => {
+                encode_h265_capabilities_vk.p_next = capabilities_vk.p_next;
+                capabilities_vk.p_next = &mut encode_h265_capabilities_vk as *mut _ as *mut _;
+            }
+        }
+
+        let fns = self.instance.fns();
+        (fns.khr_video_queue
+            .get_physical_device_video_capabilities_khr)(
+            self.handle,
+            &video_profile_vk.profile_info,
+            &mut capabilities_vk,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(VideoCapabilities {
+            flags: capabilities_vk.flags.into(),
+            min_bitstream_buffer_offset_alignment: capabilities_vk
+                .min_bitstream_buffer_offset_alignment,
+            min_bitstream_buffer_size_alignment: capabilities_vk
+                .min_bitstream_buffer_size_alignment,
+            picture_access_granularity: [
+                capabilities_vk.picture_access_granularity.width,
+                capabilities_vk.picture_access_granularity.height,
+            ],
+            min_coded_extent: [
+                capabilities_vk.min_coded_extent.width,
+                capabilities_vk.min_coded_extent.height,
+            ],
+            max_coded_extent: [
+                capabilities_vk.max_coded_extent.width,
+                capabilities_vk.max_coded_extent.height,
+            ],
+            max_dpb_slots: capabilities_vk.max_dpb_slots,
+            max_active_reference_pictures: capabilities_vk.max_active_reference_pictures,
+            std_header_version: capabilities_vk.std_header_version.into(),
+            decode_capability_flags: decode_capabilities_vk.flags.into(),
+            encode_capability_flags: encode_capabilities_vk.flags.into(),
+            encode_rate_control_modes: encode_capabilities_vk.rate_control_modes.into(),
+            encode_rate_control_layer_count: encode_capabilities_vk.rate_control_layer_count as u32,
+            encode_quality_level_count: encode_capabilities_vk.quality_level_count as u32,
+            encode_input_image_data_fill_alignment: [
+                encode_capabilities_vk.input_image_data_fill_alignment.width,
+                encode_capabilities_vk
+                    .input_image_data_fill_alignment
+                    .height,
+            ],
+            codec_capabilities: match video_codec_operation {
+                VideoCodecOperation::DecodeH264 => VideoCodecCapabilities::DecodeH264 {
+                    max_level_idc: decode_h264_capabilities_vk.max_level_idc,
+                    field_offset_granularity: [
+                        decode_h264_capabilities_vk.field_offset_granularity.x,
+                        decode_h264_capabilities_vk.field_offset_granularity.y,
+                    ],
+                },
+                VideoCodecOperation::DecodeH265 => VideoCodecCapabilities::DecodeH265 {
+                    max_level_idc: decode_h265_capabilities_vk.max_level_idc,
+                },
+                VideoCodecOperation::EncodeH264 => VideoCodecCapabilities::EncodeH264 {
+                    max_p_picture_l0_reference_count: encode_h264_capabilities_vk
+                        .max_p_picture_l0_reference_count
+                        as u32,
+                    max_b_picture_l0_reference_count: encode_h264_capabilities_vk
+                        .max_b_picture_l0_reference_count
+                        as u32,
+                    max_l1_reference_count: encode_h264_capabilities_vk.max_l1_reference_count
+                        as u32,
+                    motion_vectors_over_pic_boundaries: encode_h264_capabilities_vk
+                        .motion_vectors_over_pic_boundaries_flag
+                        != ash::vk::FALSE,
+                },
+                VideoCodecOperation::EncodeH265 => VideoCodecCapabilities::EncodeH265 {
+                    max_p_picture_l0_reference_count: encode_h265_capabilities_vk
+                        .max_p_picture_l0_reference_count
+                        as u32,
+                    max_b_picture_l0_reference_count: encode_h265_capabilities_vk
+                        .max_b_picture_l0_reference_count
+                        as u32,
+                    max_l1_reference_count: encode_h265_capabilities_vk.max_l1_reference_count
+                        as u32,
+                    max_sub_layers_count: encode_h265_capabilities_vk.max_sub_layers_count as u32,
+                },
+            },
+        })
+    }
+
+    /// Returns the formats, and their properties, that can be used for images that are used with
+    /// the given video profiles.
+    ///
+    /// The results of this function are not cached.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `video_format_info.image_usage` does not contain one of
+    ///   [`ImageUsage::VIDEO_DECODE_DST`], [`ImageUsage::VIDEO_DECODE_DPB`],
+    ///   [`ImageUsage::VIDEO_ENCODE_SRC`] or [`ImageUsage::VIDEO_ENCODE_DPB`].
+    /// - Panics if `video_format_info.video_profiles` is empty, or contains a video profile that
+    ///   is not valid.
+    #[inline]
+    pub fn video_format_properties(
+        &self,
+        video_format_info: VideoFormatInfo,
+    ) -> Result<Vec<VideoFormatProperties>, PhysicalDeviceError> {
+        self.validate_video_format_properties(&video_format_info)?;
+
+        unsafe { Ok(self.video_format_properties_unchecked(video_format_info)?) }
+    }
+
+    fn validate_video_format_properties(
+        &self,
+        video_format_info: &VideoFormatInfo,
+    ) -> Result<(), PhysicalDeviceError> {
+        if !self.supported_extensions().khr_video_queue {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`PhysicalDevice::video_format_properties`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_video_queue"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let &VideoFormatInfo {
+            image_usage,
+            ref video_profiles,
+            _ne: _,
+        } = video_format_info;
+
+        // VUID-VkPhysicalDeviceVideoFormatInfoKHR-imageUsage-parameter
+        image_usage.validate_physical_device(self)?;
+
+        // VUID?
+        assert!(image_usage.intersects(
+            ImageUsage::VIDEO_DECODE_DST
+                | ImageUsage::VIDEO_DECODE_DPB
+                | ImageUsage::VIDEO_ENCODE_SRC
+                | ImageUsage::VIDEO_ENCODE_DPB
+        ));
+
+        // VUID-vkGetPhysicalDeviceVideoFormatPropertiesKHR-pNext-06812
+        assert!(!video_profiles.is_empty());
+
+        for video_profile in video_profiles {
+            // VUID-VkVideoProfileListInfoKHR-pProfiles-parameter
+            video_profile.validate(self)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn video_format_properties_unchecked(
+        &self,
+        video_format_info: VideoFormatInfo,
+    ) -> Result<Vec<VideoFormatProperties>, VulkanError> {
+        let VideoFormatInfo {
+            image_usage,
+            video_profiles,
+            _ne: _,
+        } = video_format_info;
+
+        let profile_list_info_vk = VideoProfileListInfoVk::new(&video_profiles);
+        let format_info_vk = ash::vk::PhysicalDeviceVideoFormatInfoKHR {
+            p_next: &profile_list_info_vk.profile_list_info as *const _ as *const _,
+            image_usage: image_usage.into(),
+            ..Default::default()
+        };
+
+        let fns = self.instance.fns();
+
+        loop {
+            let mut count = 0;
+            (fns.khr_video_queue
+                .get_physical_device_video_format_properties_khr)(
+                self.handle,
+                &format_info_vk,
+                &mut count,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            let mut format_properties =
+                vec![ash::vk::VideoFormatPropertiesKHR::default(); count as usize];
+            let result = (fns
+                .khr_video_queue
+                .get_physical_device_video_format_properties_khr)(
+                self.handle,
+                &format_info_vk,
+                &mut count,
+                format_properties.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::INCOMPLETE => (),
+                ash::vk::Result::SUCCESS => {
+                    format_properties.truncate(count as usize);
+
+                    return Ok(format_properties
+                        .into_iter()
+                        .filter_map(|format_properties| {
+                            let component_mapping = format_properties.component_mapping;
+
+                            Some(VideoFormatProperties {
+                                format: format_properties.format.try_into().ok()?,
+                                component_mapping: ComponentMapping {
+                                    r: component_mapping.r.try_into().ok()?,
+                                    g: component_mapping.g.try_into().ok()?,
+                                    b: component_mapping.b.try_into().ok()?,
+                                    a: component_mapping.a.try_into().ok()?,
+                                },
+                                image_create_flags: format_properties.image_create_flags.into(),
+                                image_type: format_properties.image_type.try_into().ok()?,
+                                image_tiling: format_properties.image_tiling.try_into().ok()?,
+                                image_usage_flags: format_properties.image_usage_flags.into(),
+                            })
+                        })
+                        .collect());
+                }
+                err => return Err(VulkanError::from(err)),
+            }
+        }
+    }
+
     /// Queries whether the physical device supports presenting to Wayland surfaces from queues of
     /// the given queue family.
     ///
@@ -2618,37 +3538,145 @@ vulkan_bitflags! {
     },
 }
 
+/// A combination of matrix sizes and component types that is supported for cooperative matrix
+/// operations, as returned by [`PhysicalDevice::cooperative_matrix_properties`].
+///
+/// A supported operation multiplies an `m_size`×`k_size` matrix A with a `k_size`×`n_size`
+/// matrix B, adds an `m_size`×`n_size` matrix C, and stores the result in an
+/// `m_size`×`n_size` matrix D.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct CooperativeMatrixProperties {
+    /// The number of rows in matrices A, C and D.
+    pub m_size: u32,
+
+    /// The number of columns in matrices B, C and D.
+    pub n_size: u32,
+
+    /// The number of columns in matrix A and rows in matrix B.
+    pub k_size: u32,
+
+    /// The component type of matrix A.
+    pub a_type: ComponentType,
+
+    /// The component type of matrix B.
+    pub b_type: ComponentType,
+
+    /// The component type of matrix C.
+    pub c_type: ComponentType,
+
+    /// The component type of matrix D.
+    pub d_type: ComponentType,
+
+    /// The scope of all the matrix types.
+    pub scope: Scope,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type of the components of a cooperative matrix.
+    ComponentType = ComponentTypeNV(i32);
+
+    /// 16-bit floating point.
+    Float16 = FLOAT16,
+
+    /// 32-bit floating point.
+    Float32 = FLOAT32,
+
+    /// 64-bit floating point.
+    Float64 = FLOAT64,
+
+    /// 8-bit signed integer.
+    SInt8 = SINT8,
+
+    /// 16-bit signed integer.
+    SInt16 = SINT16,
+
+    /// 32-bit signed integer.
+    SInt32 = SINT32,
+
+    /// 64-bit signed integer.
+    SInt64 = SINT64,
+
+    /// 8-bit unsigned integer.
+    UInt8 = UINT8,
+
+    /// 16-bit unsigned integer.
+    UInt16 = UINT16,
+
+    /// 32-bit unsigned integer.
+    UInt32 = UINT32,
+
+    /// 64-bit unsigned integer.
+    UInt64 = UINT64,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The set of shader invocations that a cooperative matrix is shared by.
+    Scope = ScopeNV(i32);
+
+    /// All invocations on the device.
+    Device = DEVICE,
+
+    /// The invocations of a local workgroup.
+    Workgroup = WORKGROUP,
+
+    /// The invocations of a subgroup.
+    Subgroup = SUBGROUP,
+
+    /// All invocations on queues of the same queue family.
+    QueueFamily = QUEUE_FAMILY,
+}
+
 vulkan_bitflags! {
     #[non_exhaustive]
 
     /// Specifies which subgroup operations are supported.
+    ///
+    /// The operations that are supported by a physical device are given by the
+    /// [`subgroup_supported_operations`](crate::device::Properties::subgroup_supported_operations)
+    /// property, and the shader stages they are supported in by the
+    /// [`subgroup_supported_stages`](crate::device::Properties::subgroup_supported_stages)
+    /// property.
     SubgroupFeatures = SubgroupFeatureFlags(u32);
 
-    // TODO: document
+    /// Basic operations, such as electing a single invocation of the subgroup and subgroup
+    /// barriers. Corresponds to the `GroupNonUniform` SPIR-V capability.
     BASIC = BASIC,
 
-    // TODO: document
+    /// Voting operations, such as checking whether a condition holds for all or any of the
+    /// invocations of the subgroup. Corresponds to the `GroupNonUniformVote` SPIR-V capability.
     VOTE = VOTE,
 
-    // TODO: document
+    /// Arithmetic operations, such as sums, minimums and maximums, over the invocations of the
+    /// subgroup. Corresponds to the `GroupNonUniformArithmetic` SPIR-V capability.
     ARITHMETIC = ARITHMETIC,
 
-    // TODO: document
+    /// Ballot operations, such as broadcasting a value from one invocation to the whole subgroup.
+    /// Corresponds to the `GroupNonUniformBallot` SPIR-V capability.
     BALLOT = BALLOT,
 
-    // TODO: document
+    /// Shuffle operations, which read a value from an arbitrary invocation of the subgroup.
+    /// Corresponds to the `GroupNonUniformShuffle` SPIR-V capability.
     SHUFFLE = SHUFFLE,
 
-    // TODO: document
+    /// Relative shuffle operations, which read a value from an invocation at an offset from the
+    /// current one. Corresponds to the `GroupNonUniformShuffleRelative` SPIR-V capability.
     SHUFFLE_RELATIVE = SHUFFLE_RELATIVE,
 
-    // TODO: document
+    /// Clustered operations, which perform arithmetic operations over partitions of the subgroup
+    /// with a fixed size. Corresponds to the `GroupNonUniformClustered` SPIR-V capability.
     CLUSTERED = CLUSTERED,
 
-    // TODO: document
+    /// Quad operations, which exchange values between groups of four invocations.
+    /// Corresponds to the `GroupNonUniformQuad` SPIR-V capability.
     QUAD = QUAD,
 
-    // TODO: document
+    /// Partitioned operations, which perform arithmetic operations over arbitrary partitions of
+    /// the subgroup. Corresponds to the `GroupNonUniformPartitionedNV` SPIR-V capability.
     PARTITIONED = PARTITIONED_NV {
         device_extensions: [nv_shader_subgroup_partitioned],
     },
@@ -2775,6 +3803,36 @@ vulkan_enum! {
     Reorder = REORDER,
 }
 
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// A source of time that timestamps can be sampled from.
+    TimeDomain = TimeDomainEXT(i32);
+
+    /// The time domain of the device, which is also used by timestamp queries.
+    ///
+    /// Timestamps are in units of
+    /// [`timestamp_period`](crate::device::Properties::timestamp_period) nanoseconds.
+    Device = DEVICE,
+
+    /// The `CLOCK_MONOTONIC` clock of POSIX platforms, which is also used by
+    /// [`Instant`](std::time::Instant) on most of them.
+    ///
+    /// Timestamps are in units of nanoseconds.
+    ClockMonotonic = CLOCK_MONOTONIC,
+
+    /// The `CLOCK_MONOTONIC_RAW` clock of Linux, which is not subject to NTP adjustments.
+    ///
+    /// Timestamps are in units of nanoseconds.
+    ClockMonotonicRaw = CLOCK_MONOTONIC_RAW,
+
+    /// The performance counter of Windows, as returned by `QueryPerformanceCounter`, which is
+    /// also used by [`Instant`](std::time::Instant) on Windows.
+    ///
+    /// Timestamps are in units of the frequency returned by `QueryPerformanceFrequency`.
+    QueryPerformanceCounter = QUERY_PERFORMANCE_COUNTER,
+}
+
 /// Error that can happen when using a physical device.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum PhysicalDeviceError {
@@ -2785,9 +3843,20 @@ pub enum PhysicalDeviceError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// `drm_format_modifier` was `Some` but `tiling` was not `ImageTiling::DrmFormatModifier`, or
+    /// vice versa.
+    DrmFormatModifierTilingMismatch,
+
     // The given `SurfaceInfo` values are not supported for the surface by the physical device.
     NotSupported,
 
+    /// A provided performance counter index was not less than the number of performance counters
+    /// of the queue family.
+    PerformanceCounterIndexOutOfRange {
+        counter_index: u32,
+        counter_count: u32,
+    },
+
     /// The provided `queue_family_index` was not less than the number of queue families in the
     /// physical device.
     QueueFamilyIndexOutOfRange {
@@ -2820,11 +3889,25 @@ impl Display for PhysicalDeviceError {
                 "a requirement was not met for: {}; requires one of: {}",
                 required_for, requires_one_of,
             ),
+            Self::DrmFormatModifierTilingMismatch => write!(
+                f,
+                "`drm_format_modifier` was `Some` but `tiling` was not \
+                `ImageTiling::DrmFormatModifier`, or vice versa",
+            ),
             Self::NotSupported => write!(
                 f,
                 "the given `SurfaceInfo` values are not supported for the surface by the physical \
                 device",
             ),
+            Self::PerformanceCounterIndexOutOfRange {
+                counter_index,
+                counter_count,
+            } => write!(
+                f,
+                "a provided performance counter index ({}) was not less than the number of \
+                performance counters of the queue family ({})",
+                counter_index, counter_count,
+            ),
             Self::QueueFamilyIndexOutOfRange {
                 queue_family_index,
                 queue_family_count,
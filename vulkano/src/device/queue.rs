@@ -7,26 +7,32 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use super::{Device, DeviceOwned};
+use super::{Device, DeviceOwned, QueueCreateFlags};
 use crate::{
-    buffer::{sys::BufferState, BufferAccess},
+    buffer::{sys::BufferState, BufferAccess, BufferCreateFlags},
     command_buffer::{
-        CommandBufferResourcesUsage, CommandBufferState, CommandBufferUsage, SemaphoreSubmitInfo,
-        SubmitInfo,
+        Checkpoint, CheckpointMarker, CommandBufferResourcesUsage, CommandBufferState,
+        CommandBufferUsage, SemaphoreSubmitInfo, SubmitInfo,
+    },
+    image::{
+        sys::{ImageMemory, ImageState},
+        ImageAccess, ImageAspects, ImageCreateFlags,
     },
-    image::{sys::ImageState, ImageAccess},
     instance::debug::DebugUtilsLabel,
     macros::vulkan_bitflags,
     memory::{
-        BindSparseInfo, SparseBufferMemoryBind, SparseImageMemoryBind, SparseImageOpaqueMemoryBind,
+        BindSparseInfo, DeviceMemory, MemoryPropertyFlags, MemoryRequirements,
+        SparseBufferMemoryBind, SparseImageMemoryBind, SparseImageOpaqueMemoryBind,
     },
     swapchain::{PresentInfo, SwapchainPresentInfo},
     sync::{
         fence::{Fence, FenceState},
         future::{AccessCheckError, FlushError, GpuFuture},
-        semaphore::SemaphoreState,
+        semaphore::{SemaphoreState, SemaphoreType},
+        PipelineStages,
     },
-    OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
+    video::VideoCodecOperations,
+    DeviceSize, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use parking_lot::{Mutex, MutexGuard};
@@ -48,6 +54,7 @@ use std::{
 pub struct Queue {
     handle: ash::vk::Queue,
     device: Arc<Device>,
+    flags: QueueCreateFlags,
     queue_family_index: u32,
     id: u32, // id within family
 
@@ -60,12 +67,14 @@ impl Queue {
     pub(super) fn from_handle(
         device: Arc<Device>,
         handle: ash::vk::Queue,
+        flags: QueueCreateFlags,
         queue_family_index: u32,
         id: u32,
     ) -> Arc<Self> {
         Arc::new(Queue {
             handle,
             device,
+            flags,
             queue_family_index,
             id,
             state: Mutex::new(Default::default()),
@@ -78,6 +87,12 @@ impl Queue {
         &self.device
     }
 
+    /// Returns the flags that the queue was created with.
+    #[inline]
+    pub fn flags(&self) -> QueueCreateFlags {
+        self.flags
+    }
+
     /// Returns the index of the queue family that this queue belongs to.
     #[inline]
     pub fn queue_family_index(&self) -> u32 {
@@ -162,13 +177,366 @@ impl<'a> QueueGuard<'a> {
     ///
     /// Just like [`Device::wait_idle`], you shouldn't have to call this function in a typical
     /// program.
+    ///
+    /// If the device has been lost, the resources are still released, and
+    /// [`VulkanError::DeviceLost`] is returned.
     #[inline]
-    pub fn wait_idle(&mut self) -> Result<(), OomError> {
+    pub fn wait_idle(&mut self) -> Result<(), VulkanError> {
         self.state.wait_idle(&self.queue.device, self.queue.handle)
     }
 
+    /// Binds or unbinds memory to parts of sparse buffers and images.
+    ///
+    /// The queue family must support [`QueueFlags::SPARSE_BINDING`].
+    ///
+    /// # Safety
+    ///
+    /// - Memory that is bound to a resource must be kept alive for as long as it is bound, and
+    ///   the resource is used by the device.
+    /// - The bound regions of a resource must not be in use by the device while the bind
+    ///   operation executes. Use the semaphores of `bind_infos` to order the bind operations
+    ///   with other work.
+    /// - The semaphores in `wait_semaphores` must have a signal operation pending, or already
+    ///   be signaled, and must not be waited on by any other operation.
+    /// - The semaphores in `signal_semaphores` must be unsignaled, and must not be signaled by
+    ///   any other pending operation.
+    #[inline]
+    pub unsafe fn bind_sparse(
+        &mut self,
+        bind_infos: impl IntoIterator<Item = BindSparseInfo>,
+        fence: Option<Arc<Fence>>,
+    ) -> Result<(), BindSparseError> {
+        let bind_infos: SmallVec<[_; 4]> = bind_infos.into_iter().collect();
+        self.validate_bind_sparse(&bind_infos, fence.as_ref())?;
+
+        Ok(self.bind_sparse_unchecked(bind_infos, fence)?)
+    }
+
+    pub(crate) fn validate_bind_sparse(
+        &self,
+        bind_infos: &[BindSparseInfo],
+        fence: Option<&Arc<Fence>>,
+    ) -> Result<(), BindSparseError> {
+        let device = &self.queue.device;
+
+        // VUID-vkQueueBindSparse-queuetype
+        if !device.physical_device().queue_family_properties()
+            [self.queue.queue_family_index as usize]
+            .queue_flags
+            .intersects(QueueFlags::SPARSE_BINDING)
+        {
+            return Err(BindSparseError::NotSupportedByQueueFamily);
+        }
+
+        if let Some(fence) = fence {
+            // VUID-vkQueueBindSparse-commonparent
+            assert_eq!(device, fence.device());
+
+            let fence_state = fence.state();
+
+            // VUID-vkQueueBindSparse-fence-01113
+            if fence_state.is_signaled() == Some(true) {
+                return Err(BindSparseError::FenceSignaled);
+            }
+
+            // VUID-vkQueueBindSparse-fence-01114
+            if fence_state.is_in_queue() {
+                return Err(BindSparseError::FenceInUse);
+            }
+        }
+
+        let check_memory = |memory: &Option<(Arc<DeviceMemory>, DeviceSize)>,
+                            size: DeviceSize,
+                            memory_requirements: &MemoryRequirements|
+         -> Result<(), BindSparseError> {
+            let (memory, memory_offset) = match memory {
+                Some((memory, memory_offset)) => (memory, *memory_offset),
+                None => return Ok(()),
+            };
+
+            // VUID-VkBindSparseInfo-commonparent
+            assert_eq!(device, memory.device());
+
+            let memory_type = &device.physical_device().memory_properties().memory_types
+                [memory.memory_type_index() as usize];
+
+            // VUID-VkSparseMemoryBind-memory-01096
+            // VUID-VkSparseImageMemoryBind-memory-01104
+            if memory_requirements.memory_type_bits & (1 << memory.memory_type_index()) == 0 {
+                return Err(BindSparseError::MemoryTypeNotAllowed {
+                    provided_memory_type_index: memory.memory_type_index(),
+                    allowed_memory_type_bits: memory_requirements.memory_type_bits,
+                });
+            }
+
+            // VUID-VkSparseMemoryBind-memory-01097
+            // VUID-VkSparseImageMemoryBind-memory-01105
+            if memory_type
+                .property_flags
+                .intersects(MemoryPropertyFlags::LAZILY_ALLOCATED)
+            {
+                return Err(BindSparseError::MemoryLazilyAllocated);
+            }
+
+            // VUID-VkSparseMemoryBind-memory-01096
+            // VUID-VkSparseImageMemoryBind-memory-01104
+            if memory_offset % memory_requirements.alignment != 0 {
+                return Err(BindSparseError::MemoryNotAligned {
+                    memory_offset,
+                    required_alignment: memory_requirements.alignment,
+                });
+            }
+
+            // VUID-VkSparseMemoryBind-memoryOffset-01101
+            // VUID-VkSparseMemoryBind-size-01102
+            if memory_offset >= memory.allocation_size()
+                || size > memory.allocation_size() - memory_offset
+            {
+                return Err(BindSparseError::MemoryOutOfRange {
+                    memory_offset,
+                    size,
+                    memory_size: memory.allocation_size(),
+                });
+            }
+
+            Ok(())
+        };
+
+        let check_resource_range = |offset: DeviceSize,
+                                    size: DeviceSize,
+                                    resource_size: DeviceSize|
+         -> Result<(), BindSparseError> {
+            // VUID-VkSparseMemoryBind-size-01098
+            assert!(size != 0);
+
+            // VUID-VkSparseMemoryBind-resourceOffset-01099
+            // VUID-VkSparseMemoryBind-size-01100
+            if offset >= resource_size || size > resource_size - offset {
+                return Err(BindSparseError::ResourceRangeOutOfBounds {
+                    offset,
+                    size,
+                    resource_size,
+                });
+            }
+
+            Ok(())
+        };
+
+        for bind_info in bind_infos {
+            let BindSparseInfo {
+                wait_semaphores,
+                buffer_binds,
+                image_opaque_binds,
+                image_binds,
+                signal_semaphores,
+                _ne: _,
+            } = bind_info;
+
+            for semaphore in wait_semaphores.iter().chain(signal_semaphores) {
+                // VUID-VkBindSparseInfo-commonparent
+                assert_eq!(device, semaphore.device());
+
+                // VUID-VkBindSparseInfo-pWaitSemaphores-03246
+                // VUID-VkBindSparseInfo-pSignalSemaphores-03247
+                if semaphore.semaphore_type() != SemaphoreType::Binary {
+                    return Err(BindSparseError::SemaphoreNotBinary);
+                }
+            }
+
+            for (buffer, memory_binds) in buffer_binds {
+                let buffer = &buffer.inner().buffer;
+
+                // VUID-VkSparseBufferMemoryBindInfo-buffer-parameter
+                assert_eq!(device, buffer.device());
+
+                if !buffer.flags().intersects(BufferCreateFlags::SPARSE_BINDING) {
+                    return Err(BindSparseError::BufferNotSparse);
+                }
+
+                let memory_requirements = buffer.memory_requirements();
+
+                for memory_bind in memory_binds {
+                    let &SparseBufferMemoryBind {
+                        offset,
+                        size,
+                        ref memory,
+                    } = memory_bind;
+
+                    check_resource_range(offset, size, buffer.size())?;
+                    check_memory(memory, size, memory_requirements)?;
+                }
+            }
+
+            for (image, memory_binds) in image_opaque_binds {
+                let image = &image.inner().image;
+
+                // VUID-VkSparseImageOpaqueMemoryBindInfo-image-parameter
+                assert_eq!(device, image.device());
+
+                if !image.flags().intersects(ImageCreateFlags::SPARSE_BINDING) {
+                    return Err(BindSparseError::ImageNotSparse);
+                }
+
+                let memory_requirements = &image.memory_requirements()[0];
+
+                for memory_bind in memory_binds {
+                    let &SparseImageOpaqueMemoryBind {
+                        offset,
+                        size,
+                        ref memory,
+                        metadata,
+                    } = memory_bind;
+
+                    check_resource_range(offset, size, memory_requirements.size)?;
+                    check_memory(memory, size, memory_requirements)?;
+
+                    if metadata {
+                        // VUID-VkSparseImageOpaqueMemoryBindInfo-pBinds-01103
+                        let is_in_mip_tail = match image.memory() {
+                            ImageMemory::Sparse(sparse_memory_requirements) => {
+                                sparse_memory_requirements.iter().any(|requirements| {
+                                    if !requirements
+                                        .format_properties
+                                        .aspects
+                                        .intersects(ImageAspects::METADATA)
+                                        || offset < requirements.image_mip_tail_offset
+                                    {
+                                        return false;
+                                    }
+
+                                    let mut relative_offset =
+                                        offset - requirements.image_mip_tail_offset;
+
+                                    if let Some(stride) = requirements.image_mip_tail_stride {
+                                        relative_offset %= stride;
+                                    }
+
+                                    relative_offset + size <= requirements.image_mip_tail_size
+                                })
+                            }
+                            _ => false,
+                        };
+
+                        if !is_in_mip_tail {
+                            return Err(BindSparseError::MetadataNotInMipTail);
+                        }
+                    }
+                }
+            }
+
+            for (image, memory_binds) in image_binds {
+                let image = &image.inner().image;
+
+                // VUID-VkSparseImageMemoryBindInfo-image-parameter
+                assert_eq!(device, image.device());
+
+                // VUID-VkSparseImageMemoryBindInfo-image-02901
+                let sparse_memory_requirements = match image.memory() {
+                    ImageMemory::Sparse(sparse_memory_requirements)
+                        if image.flags().intersects(ImageCreateFlags::SPARSE_RESIDENCY) =>
+                    {
+                        sparse_memory_requirements
+                    }
+                    _ => return Err(BindSparseError::ImageNotSparseResidency),
+                };
+
+                let memory_requirements = &image.memory_requirements()[0];
+
+                for memory_bind in memory_binds {
+                    let &SparseImageMemoryBind {
+                        aspects,
+                        mip_level,
+                        array_layer,
+                        offset,
+                        extent,
+                        ref memory,
+                    } = memory_bind;
+
+                    // VUID-VkSparseImageMemoryBindInfo-subresource-01722
+                    if mip_level >= image.mip_levels() {
+                        return Err(BindSparseError::ImageMipLevelOutOfRange {
+                            mip_level,
+                            mip_levels: image.mip_levels(),
+                        });
+                    }
+
+                    // VUID-VkSparseImageMemoryBindInfo-subresource-01723
+                    if array_layer >= image.dimensions().array_layers() {
+                        return Err(BindSparseError::ImageArrayLayerOutOfRange {
+                            array_layer,
+                            array_layers: image.dimensions().array_layers(),
+                        });
+                    }
+
+                    // VUID-VkImageSubresource-aspectMask-parameter
+                    aspects.validate_device(device)?;
+
+                    // VUID-VkImageSubresource-aspectMask-requiredbitmask
+                    assert!(!aspects.is_empty());
+
+                    // VUID-VkSparseImageMemoryBindInfo-subresource-01106
+                    let requirements = sparse_memory_requirements
+                        .iter()
+                        .find(|requirements| {
+                            requirements.format_properties.aspects.contains(aspects)
+                        })
+                        .ok_or(BindSparseError::ImageAspectsNotAllowed { aspects })?;
+
+                    // VUID?
+                    assert!(extent.iter().all(|&extent| extent != 0));
+
+                    let granularity = requirements.format_properties.image_granularity;
+                    let subresource_extent = image
+                        .dimensions()
+                        .mip_level_dimensions(mip_level)
+                        .unwrap()
+                        .width_height_depth();
+
+                    for i in 0..3 {
+                        // VUID-VkSparseImageMemoryBind-offset-01107
+                        // VUID-VkSparseImageMemoryBind-offset-01109
+                        // VUID-VkSparseImageMemoryBind-offset-01111
+                        // VUID-VkSparseImageMemoryBind-extent-01108
+                        // VUID-VkSparseImageMemoryBind-extent-01110
+                        // VUID-VkSparseImageMemoryBind-extent-01112
+                        if offset[i] % granularity[i] != 0
+                            || extent[i] % granularity[i] != 0
+                                && offset[i] + extent[i] != subresource_extent[i]
+                        {
+                            return Err(BindSparseError::ImageBindNotAligned {
+                                offset,
+                                extent,
+                                granularity,
+                            });
+                        }
+
+                        // VUID?
+                        if offset[i] + extent[i] > subresource_extent[i] {
+                            return Err(BindSparseError::ImageBindOutOfRange {
+                                offset,
+                                extent,
+                                subresource_extent,
+                            });
+                        }
+                    }
+
+                    let block_count: DeviceSize = (0..3)
+                        .map(|i| ((extent[i] + granularity[i] - 1) / granularity[i]) as DeviceSize)
+                        .product();
+                    check_memory(
+                        memory,
+                        block_count * memory_requirements.alignment,
+                        memory_requirements,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub(crate) unsafe fn bind_sparse_unchecked(
+    pub unsafe fn bind_sparse_unchecked(
         &mut self,
         bind_infos: impl IntoIterator<Item = BindSparseInfo>,
         fence: Option<Arc<Fence>>,
@@ -192,6 +560,12 @@ impl<'a> QueueGuard<'a> {
         fence: Option<(&Arc<Fence>, MutexGuard<'_, FenceState>)>,
         states: &mut States<'_>,
     ) -> Result<(), VulkanError> {
+        // Once the device is lost, it no longer executes anything, so fail consistently instead
+        // of relying on the implementation to report it.
+        if self.queue.device.is_lost() {
+            return Err(VulkanError::DeviceLost);
+        }
+
         struct PerBindSparseInfo {
             wait_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
             buffer_bind_infos_vk: SmallVec<[ash::vk::SparseBufferMemoryBindInfo; 4]>,
@@ -448,7 +822,7 @@ impl<'a> QueueGuard<'a> {
                 .map_or_else(Default::default, |(fence, _)| fence.handle()),
         )
         .result()
-        .map_err(VulkanError::from)?;
+        .map_err(|err| self.queue.device.check_lost(err.into()))?;
 
         for bind_info in bind_infos {
             let BindSparseInfo {
@@ -460,14 +834,15 @@ impl<'a> QueueGuard<'a> {
                 _ne: _,
             } = bind_info;
 
+            // Only binary semaphores can be used here, so there is no value to track.
             for semaphore in wait_semaphores {
                 let state = states.semaphores.get_mut(&semaphore.handle()).unwrap();
-                state.add_queue_wait(self.queue);
+                state.add_queue_wait(self.queue, 0);
             }
 
             for semaphore in signal_semaphores {
                 let state = states.semaphores.get_mut(&semaphore.handle()).unwrap();
-                state.add_queue_wait(self.queue);
+                state.add_queue_signal(self.queue, 0);
             }
         }
 
@@ -498,6 +873,11 @@ impl<'a> QueueGuard<'a> {
         present_info: &PresentInfo,
         states: &mut States<'_>,
     ) -> Result<impl ExactSizeIterator<Item = Result<bool, VulkanError>>, VulkanError> {
+        // See `bind_sparse_unchecked_locked`.
+        if self.queue.device.is_lost() {
+            return Err(VulkanError::DeviceLost);
+        }
+
         let PresentInfo {
             ref wait_semaphores,
             ref swapchain_infos,
@@ -515,9 +895,11 @@ impl<'a> QueueGuard<'a> {
         let mut present_regions_vk: SmallVec<[_; 4]> =
             SmallVec::with_capacity(swapchain_infos.len());
         let mut rectangles_vk: SmallVec<[_; 4]> = SmallVec::with_capacity(swapchain_infos.len());
+        let mut present_times_vk: SmallVec<[_; 4]> = SmallVec::with_capacity(swapchain_infos.len());
 
         let mut has_present_ids = false;
         let mut has_present_regions = false;
+        let mut has_present_times = false;
 
         for swapchain_info in swapchain_infos {
             let &SwapchainPresentInfo {
@@ -525,6 +907,7 @@ impl<'a> QueueGuard<'a> {
                 image_index,
                 present_id,
                 ref present_regions,
+                present_time,
                 _ne: _,
             } = swapchain_info;
 
@@ -543,9 +926,19 @@ impl<'a> QueueGuard<'a> {
                 has_present_ids = true;
             }
 
+            present_times_vk.push(
+                present_time
+                    .as_ref()
+                    .map_or_else(Default::default, ash::vk::PresentTimeGOOGLE::from),
+            );
+
             if !present_regions.is_empty() {
                 has_present_regions = true;
             }
+
+            if present_time.is_some() {
+                has_present_times = true;
+            }
         }
 
         let mut results = vec![ash::vk::Result::SUCCESS; swapchain_infos.len()];
@@ -560,6 +953,7 @@ impl<'a> QueueGuard<'a> {
         };
         let mut present_id_info_vk = None;
         let mut present_region_info_vk = None;
+        let mut present_times_info_vk = None;
 
         if has_present_ids {
             let next = present_id_info_vk.insert(ash::vk::PresentIdKHR {
@@ -592,6 +986,17 @@ impl<'a> QueueGuard<'a> {
             info_vk.p_next = next as *const _ as *const _;
         }
 
+        if has_present_times {
+            let next = present_times_info_vk.insert(ash::vk::PresentTimesInfoGOOGLE {
+                swapchain_count: present_times_vk.len() as u32,
+                p_times: present_times_vk.as_ptr(),
+                ..Default::default()
+            });
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        }
+
         let fns = self.queue.device().fns();
         let result = (fns.khr_swapchain.queue_present_khr)(self.queue.handle, &info_vk);
 
@@ -607,26 +1012,32 @@ impl<'a> QueueGuard<'a> {
                 | ash::vk::Result::ERROR_SURFACE_LOST_KHR
                 | ash::vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT,
         ) {
-            return Err(VulkanError::from(result));
+            return Err(self.queue.device.check_lost(VulkanError::from(result)));
         }
 
+        // Only binary semaphores can be used here, so there is no value to track.
         for semaphore in wait_semaphores {
             let state = states.semaphores.get_mut(&semaphore.handle()).unwrap();
-            state.add_queue_wait(self.queue);
+            state.add_queue_wait(self.queue, 0);
         }
 
         self.state
             .operations
             .push_back((present_info.clone().into(), None));
 
-        // If a presentation results in a loss of full-screen exclusive mode,
-        // signal that to the relevant swapchain.
+        // If a presentation results in a loss of full-screen exclusive mode, or reports the
+        // swapchain as suboptimal, signal that to the relevant swapchain.
         for (&result, swapchain_info) in results.iter().zip(&present_info.swapchain_infos) {
-            if result == ash::vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT {
-                swapchain_info
+            match result {
+                ash::vk::Result::ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => swapchain_info
                     .swapchain
                     .full_screen_exclusive_held()
-                    .store(false, Ordering::SeqCst);
+                    .store(false, Ordering::SeqCst),
+                ash::vk::Result::SUBOPTIMAL_KHR => swapchain_info
+                    .swapchain
+                    .suboptimal()
+                    .store(true, Ordering::SeqCst),
+                _ => (),
             }
         }
 
@@ -752,14 +1163,15 @@ impl<'a> QueueGuard<'a> {
             }
         }
 
-        Ok(self.submit_unchecked_locked(
+        self.submit_unchecked_locked(
             &submit_infos,
             fence.as_ref().map(|fence| {
                 let state = fence.state();
                 (fence, state)
             }),
             &mut states,
-        )?)
+        )
+        .map_err(|err| FlushError::from(err).with_device_fault(&self.queue.device))
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
@@ -787,11 +1199,19 @@ impl<'a> QueueGuard<'a> {
         fence: Option<(&Arc<Fence>, MutexGuard<'_, FenceState>)>,
         states: &mut States<'_>,
     ) -> Result<(), VulkanError> {
+        // See `bind_sparse_unchecked_locked`.
+        if self.queue.device.is_lost() {
+            return Err(VulkanError::DeviceLost);
+        }
+
+        let has_performance_query = self.queue.device.enabled_extensions().khr_performance_query;
+
         if self.queue.device.enabled_features().synchronization2 {
             struct PerSubmitInfo {
                 wait_semaphore_infos_vk: SmallVec<[ash::vk::SemaphoreSubmitInfo; 4]>,
                 command_buffer_infos_vk: SmallVec<[ash::vk::CommandBufferSubmitInfo; 4]>,
                 signal_semaphore_infos_vk: SmallVec<[ash::vk::SemaphoreSubmitInfo; 4]>,
+                performance_query_submit_info_vk: Option<ash::vk::PerformanceQuerySubmitInfoKHR>,
             }
 
             let (mut submit_info_vk, per_submit_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
@@ -802,6 +1222,8 @@ impl<'a> QueueGuard<'a> {
                             ref wait_semaphores,
                             ref command_buffers,
                             ref signal_semaphores,
+                            protected,
+                            performance_counter_pass_index,
                             _ne: _,
                         } = submit_info;
 
@@ -811,12 +1233,13 @@ impl<'a> QueueGuard<'a> {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
                                     stages,
+                                    value,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
                                 ash::vk::SemaphoreSubmitInfo {
                                     semaphore: semaphore.handle(),
-                                    value: 0, // TODO:
+                                    value,
                                     stage_mask: stages.into(),
                                     device_index: 0, // TODO:
                                     ..Default::default()
@@ -839,12 +1262,13 @@ impl<'a> QueueGuard<'a> {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
                                     stages,
+                                    value,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
                                 ash::vk::SemaphoreSubmitInfo {
                                     semaphore: semaphore.handle(),
-                                    value: 0, // TODO:
+                                    value,
                                     stage_mask: stages.into(),
                                     device_index: 0, // TODO:
                                     ..Default::default()
@@ -854,7 +1278,11 @@ impl<'a> QueueGuard<'a> {
 
                         (
                             ash::vk::SubmitInfo2 {
-                                flags: ash::vk::SubmitFlags::empty(), // TODO:
+                                flags: if protected {
+                                    ash::vk::SubmitFlags::PROTECTED
+                                } else {
+                                    ash::vk::SubmitFlags::empty()
+                                },
                                 wait_semaphore_info_count: 0,
                                 p_wait_semaphore_infos: ptr::null(),
                                 command_buffer_info_count: 0,
@@ -867,6 +1295,12 @@ impl<'a> QueueGuard<'a> {
                                 wait_semaphore_infos_vk,
                                 command_buffer_infos_vk,
                                 signal_semaphore_infos_vk,
+                                performance_query_submit_info_vk: has_performance_query.then(
+                                    || ash::vk::PerformanceQuerySubmitInfoKHR {
+                                        counter_pass_index: performance_counter_pass_index,
+                                        ..Default::default()
+                                    },
+                                ),
                             },
                         )
                     })
@@ -878,10 +1312,14 @@ impl<'a> QueueGuard<'a> {
                     wait_semaphore_infos_vk,
                     command_buffer_infos_vk,
                     signal_semaphore_infos_vk,
+                    performance_query_submit_info_vk,
                 },
             ) in (submit_info_vk.iter_mut()).zip(per_submit_vk.iter())
             {
                 *submit_info_vk = ash::vk::SubmitInfo2 {
+                    p_next: performance_query_submit_info_vk
+                        .as_ref()
+                        .map_or(ptr::null(), |info| info as *const _ as *const _),
                     wait_semaphore_info_count: wait_semaphore_infos_vk.len() as u32,
                     p_wait_semaphore_infos: wait_semaphore_infos_vk.as_ptr(),
                     command_buffer_info_count: command_buffer_infos_vk.len() as u32,
@@ -915,13 +1353,18 @@ impl<'a> QueueGuard<'a> {
                 )
             }
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| self.queue.device.check_lost(err.into()))?;
         } else {
             struct PerSubmitInfo {
                 wait_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
+                wait_semaphore_values_vk: SmallVec<[u64; 4]>,
                 wait_dst_stage_mask_vk: SmallVec<[ash::vk::PipelineStageFlags; 4]>,
                 command_buffers_vk: SmallVec<[ash::vk::CommandBuffer; 4]>,
                 signal_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
+                signal_semaphore_values_vk: SmallVec<[u64; 4]>,
+                has_timeline_semaphores: bool,
+                protected: bool,
+                performance_counter_pass_index: u32,
             }
 
             let (mut submit_info_vk, per_submit_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
@@ -932,37 +1375,61 @@ impl<'a> QueueGuard<'a> {
                             ref wait_semaphores,
                             ref command_buffers,
                             ref signal_semaphores,
+                            protected,
+                            performance_counter_pass_index,
                             _ne: _,
                         } = submit_info;
 
-                        let (wait_semaphores_vk, wait_dst_stage_mask_vk) = wait_semaphores
-                            .iter()
-                            .map(|semaphore_submit_info| {
-                                let &SemaphoreSubmitInfo {
-                                    ref semaphore,
-                                    stages,
-                                    _ne: _,
-                                } = semaphore_submit_info;
-
-                                (semaphore.handle(), stages.into())
-                            })
-                            .unzip();
+                        let mut wait_semaphores_vk = SmallVec::new();
+                        let mut wait_semaphore_values_vk = SmallVec::new();
+                        let mut wait_dst_stage_mask_vk = SmallVec::new();
+
+                        for semaphore_submit_info in wait_semaphores {
+                            let &SemaphoreSubmitInfo {
+                                ref semaphore,
+                                stages,
+                                value,
+                                _ne: _,
+                            } = semaphore_submit_info;
+
+                            // Stages that only exist in `VkPipelineStageFlagBits2` can't be
+                            // expressed without `synchronization2`, so fall back to waiting
+                            // before all commands instead.
+                            let stages = if stages.is_2() {
+                                PipelineStages::ALL_COMMANDS
+                            } else {
+                                stages
+                            };
+
+                            wait_semaphores_vk.push(semaphore.handle());
+                            wait_semaphore_values_vk.push(value);
+                            wait_dst_stage_mask_vk.push(stages.into());
+                        }
 
                         let command_buffers_vk =
                             command_buffers.iter().map(|cb| cb.handle()).collect();
 
-                        let signal_semaphores_vk = signal_semaphores
+                        let (signal_semaphores_vk, signal_semaphore_values_vk) = signal_semaphores
                             .iter()
                             .map(|semaphore_submit_info| {
                                 let &SemaphoreSubmitInfo {
                                     ref semaphore,
                                     stages: _,
+                                    value,
                                     _ne: _,
                                 } = semaphore_submit_info;
 
-                                semaphore.handle()
+                                (semaphore.handle(), value)
                             })
-                            .collect();
+                            .unzip();
+
+                        let has_timeline_semaphores = wait_semaphores
+                            .iter()
+                            .chain(signal_semaphores)
+                            .any(|semaphore_submit_info| {
+                                semaphore_submit_info.semaphore.semaphore_type()
+                                    == SemaphoreType::Timeline
+                            });
 
                         (
                             ash::vk::SubmitInfo {
@@ -977,25 +1444,125 @@ impl<'a> QueueGuard<'a> {
                             },
                             PerSubmitInfo {
                                 wait_semaphores_vk,
+                                wait_semaphore_values_vk,
                                 wait_dst_stage_mask_vk,
                                 command_buffers_vk,
                                 signal_semaphores_vk,
+                                signal_semaphore_values_vk,
+                                has_timeline_semaphores,
+                                protected,
+                                performance_counter_pass_index,
                             },
                         )
                     })
                     .unzip();
 
+            // The performance counter pass index is provided through a structure at the end of
+            // the `p_next` chain.
+            let performance_query_submit_infos_vk: SmallVec<[_; 4]> = per_submit_vk
+                .iter()
+                .map(|per_submit_info| {
+                    has_performance_query.then(|| ash::vk::PerformanceQuerySubmitInfoKHR {
+                        counter_pass_index: per_submit_info.performance_counter_pass_index,
+                        ..Default::default()
+                    })
+                })
+                .collect();
+
+            // Timeline semaphore values are provided through a separate structure in the `p_next`
+            // chain, which must outlive the submission.
+            let timeline_semaphore_submit_infos_vk: SmallVec<[_; 4]> = (per_submit_vk.iter())
+                .zip(performance_query_submit_infos_vk.iter())
+                .map(|(per_submit_info, performance_query_submit_info_vk)| {
+                    let PerSubmitInfo {
+                        wait_semaphore_values_vk,
+                        signal_semaphore_values_vk,
+                        has_timeline_semaphores,
+                        ..
+                    } = per_submit_info;
+
+                    has_timeline_semaphores.then(|| ash::vk::TimelineSemaphoreSubmitInfo {
+                        p_next: performance_query_submit_info_vk
+                            .as_ref()
+                            .map_or(ptr::null(), |info| info as *const _ as *const _),
+                        wait_semaphore_value_count: wait_semaphore_values_vk.len() as u32,
+                        p_wait_semaphore_values: wait_semaphore_values_vk.as_ptr(),
+                        signal_semaphore_value_count: signal_semaphore_values_vk.len() as u32,
+                        p_signal_semaphore_values: signal_semaphore_values_vk.as_ptr(),
+                        ..Default::default()
+                    })
+                })
+                .collect();
+
+            let protected_submit_infos_vk: SmallVec<[_; 4]> = (per_submit_vk.iter())
+                .zip(timeline_semaphore_submit_infos_vk.iter())
+                .zip(performance_query_submit_infos_vk.iter())
+                .map(
+                    |(
+                        (per_submit_info, timeline_semaphore_submit_info_vk),
+                        performance_query_submit_info_vk,
+                    )| {
+                        per_submit_info
+                            .protected
+                            .then(|| ash::vk::ProtectedSubmitInfo {
+                                p_next: match (
+                                    timeline_semaphore_submit_info_vk,
+                                    performance_query_submit_info_vk,
+                                ) {
+                                    (Some(info), _) => info as *const _ as *const _,
+                                    (None, Some(info)) => info as *const _ as *const _,
+                                    (None, None) => ptr::null(),
+                                },
+                                protected_submit: ash::vk::TRUE,
+                                ..Default::default()
+                            })
+                    },
+                )
+                .collect();
+
             for (
-                submit_info_vk,
-                PerSubmitInfo {
-                    wait_semaphores_vk,
-                    wait_dst_stage_mask_vk,
-                    command_buffers_vk,
-                    signal_semaphores_vk,
-                },
-            ) in (submit_info_vk.iter_mut()).zip(per_submit_vk.iter())
+                (
+                    (
+                        submit_info_vk,
+                        PerSubmitInfo {
+                            wait_semaphores_vk,
+                            wait_semaphore_values_vk: _,
+                            wait_dst_stage_mask_vk,
+                            command_buffers_vk,
+                            signal_semaphores_vk,
+                            signal_semaphore_values_vk: _,
+                            has_timeline_semaphores: _,
+                            protected: _,
+                            performance_counter_pass_index: _,
+                        },
+                    ),
+                    timeline_semaphore_submit_info_vk,
+                ),
+                (protected_submit_info_vk, performance_query_submit_info_vk),
+            ) in (submit_info_vk.iter_mut())
+                .zip(per_submit_vk.iter())
+                .zip(timeline_semaphore_submit_infos_vk.iter())
+                .zip(
+                    protected_submit_infos_vk
+                        .iter()
+                        .zip(&performance_query_submit_infos_vk),
+                )
             {
+                // The chain is, in order and for those that are present: the protected submit
+                // info, the timeline semaphore submit info, the performance query submit info.
+                let p_next = match (
+                    protected_submit_info_vk,
+                    timeline_semaphore_submit_info_vk,
+                    performance_query_submit_info_vk,
+                ) {
+                    (Some(info), _, _) => info as *const _ as *const _,
+                    (None, Some(info), _) => info as *const _ as *const _,
+                    (None, None, Some(info)) => info as *const _ as *const _,
+                    (None, None, None) => ptr::null(),
+                };
+
                 *submit_info_vk = ash::vk::SubmitInfo {
+                    p_next,
                     wait_semaphore_count: wait_semaphores_vk.len() as u32,
                     p_wait_semaphores: wait_semaphores_vk.as_ptr(),
                     p_wait_dst_stage_mask: wait_dst_stage_mask_vk.as_ptr(),
@@ -1017,7 +1584,7 @@ impl<'a> QueueGuard<'a> {
                     .map_or_else(Default::default, |(fence, _)| fence.handle()),
             )
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| self.queue.device.check_lost(err.into()))?;
         }
 
         for submit_info in submit_infos {
@@ -1025,6 +1592,8 @@ impl<'a> QueueGuard<'a> {
                 wait_semaphores,
                 command_buffers,
                 signal_semaphores,
+                protected: _,
+                performance_counter_pass_index: _,
                 _ne: _,
             } = submit_info;
 
@@ -1033,7 +1602,7 @@ impl<'a> QueueGuard<'a> {
                     .semaphores
                     .get_mut(&semaphore_submit_info.semaphore.handle())
                     .unwrap();
-                state.add_queue_wait(self.queue);
+                state.add_queue_wait(self.queue, semaphore_submit_info.value);
             }
 
             for command_buffer in command_buffers {
@@ -1080,7 +1649,7 @@ impl<'a> QueueGuard<'a> {
                     .semaphores
                     .get_mut(&semaphore_submit_info.semaphore.handle())
                     .unwrap();
-                state.add_queue_signal(self.queue);
+                state.add_queue_signal(self.queue, semaphore_submit_info.value);
             }
         }
 
@@ -1263,6 +1832,109 @@ impl<'a> QueueGuard<'a> {
         let fns = self.queue.device.instance().fns();
         (fns.ext_debug_utils.queue_insert_debug_utils_label_ext)(self.queue.handle, &label_info);
     }
+
+    /// Returns the last checkpoints that were reached by the queue, one for each pipeline stage
+    /// that checkpoints are reported for.
+    ///
+    /// This is meant to be called after an operation returned [`VulkanError::DeviceLost`], to
+    /// find out how far the execution of the queue got before the device was lost. Checkpoints
+    /// are inserted into command buffers with [`set_checkpoint`] or
+    /// [`set_automatic_checkpoints`].
+    ///
+    /// The [`nv_device_diagnostic_checkpoints`] extension must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - The device must be lost.
+    ///
+    /// [`set_checkpoint`]: crate::command_buffer::AutoCommandBufferBuilder::set_checkpoint
+    /// [`set_automatic_checkpoints`]: crate::command_buffer::AutoCommandBufferBuilder::set_automatic_checkpoints
+    /// [`nv_device_diagnostic_checkpoints`]: crate::device::DeviceExtensions::nv_device_diagnostic_checkpoints
+    #[inline]
+    pub unsafe fn checkpoint_data(&mut self) -> Result<Vec<Checkpoint>, QueueError> {
+        self.validate_checkpoint_data()?;
+
+        Ok(self.checkpoint_data_unchecked())
+    }
+
+    fn validate_checkpoint_data(&self) -> Result<(), QueueError> {
+        if !self
+            .queue
+            .device
+            .enabled_extensions()
+            .nv_device_diagnostic_checkpoints
+        {
+            return Err(QueueError::RequirementNotMet {
+                required_for: "`QueueGuard::checkpoint_data`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["nv_device_diagnostic_checkpoints"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkGetQueueCheckpointDataNV-queue-02025
+        // TODO: not checked, so unsafe for now
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn checkpoint_data_unchecked(&mut self) -> Vec<Checkpoint> {
+        let fns = self.queue.device.fns();
+
+        if self.queue.device.enabled_features().synchronization2 {
+            let mut count = 0;
+            (fns.khr_synchronization2.get_queue_checkpoint_data2_nv)(
+                self.queue.handle,
+                &mut count,
+                ptr::null_mut(),
+            );
+
+            let mut checkpoint_data = vec![ash::vk::CheckpointData2NV::default(); count as usize];
+            (fns.khr_synchronization2.get_queue_checkpoint_data2_nv)(
+                self.queue.handle,
+                &mut count,
+                checkpoint_data.as_mut_ptr(),
+            );
+            checkpoint_data.truncate(count as usize);
+
+            checkpoint_data
+                .into_iter()
+                .map(|checkpoint_data| Checkpoint {
+                    stages: checkpoint_data.stage.into(),
+                    marker: CheckpointMarker::from_vulkan(checkpoint_data.p_checkpoint_marker),
+                })
+                .collect()
+        } else {
+            let mut count = 0;
+            (fns.nv_device_diagnostic_checkpoints
+                .get_queue_checkpoint_data_nv)(
+                self.queue.handle, &mut count, ptr::null_mut()
+            );
+
+            let mut checkpoint_data = vec![ash::vk::CheckpointDataNV::default(); count as usize];
+            (fns.nv_device_diagnostic_checkpoints
+                .get_queue_checkpoint_data_nv)(
+                self.queue.handle,
+                &mut count,
+                checkpoint_data.as_mut_ptr(),
+            );
+            checkpoint_data.truncate(count as usize);
+
+            checkpoint_data
+                .into_iter()
+                .map(|checkpoint_data| Checkpoint {
+                    stages: ash::vk::PipelineStageFlags2::from_raw(
+                        checkpoint_data.stage.as_raw() as u64
+                    )
+                    .into(),
+                    marker: CheckpointMarker::from_vulkan(checkpoint_data.p_checkpoint_marker),
+                })
+                .collect()
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -1271,12 +1943,20 @@ struct QueueState {
 }
 
 impl QueueState {
-    fn wait_idle(&mut self, device: &Device, handle: ash::vk::Queue) -> Result<(), OomError> {
+    fn wait_idle(&mut self, device: &Device, handle: ash::vk::Queue) -> Result<(), VulkanError> {
         unsafe {
             let fns = device.fns();
-            (fns.v1_0.queue_wait_idle)(handle)
+            let result = (fns.v1_0.queue_wait_idle)(handle)
                 .result()
-                .map_err(VulkanError::from)?;
+                .map_err(|err| device.check_lost(err.into()));
+
+            match result {
+                Ok(()) => (),
+                // If the device was lost, the queue will never execute anything anymore,
+                // so it is also finished with all work.
+                Err(VulkanError::DeviceLost) => (),
+                Err(err) => return Err(err),
+            }
 
             // Since we now know that the queue is finished with all work,
             // we can safely release all resources.
@@ -1284,7 +1964,7 @@ impl QueueState {
                 operation.set_finished();
             }
 
-            Ok(())
+            result
         }
     }
 
@@ -1328,11 +2008,11 @@ impl QueueOperation {
             QueueOperation::BindSparse(bind_infos) => {
                 for bind_info in bind_infos {
                     for semaphore in bind_info.wait_semaphores {
-                        semaphore.state().set_wait_finished();
+                        semaphore.state().set_wait_finished(0);
                     }
 
                     for semaphore in bind_info.signal_semaphores {
-                        semaphore.state().set_signal_finished();
+                        semaphore.state().set_signal_finished(0);
                     }
                 }
 
@@ -1340,20 +2020,23 @@ impl QueueOperation {
             }
             QueueOperation::Present(present_info) => {
                 for semaphore in present_info.wait_semaphores {
-                    semaphore.state().set_wait_finished();
+                    semaphore.state().set_wait_finished(0);
                 }
             }
             QueueOperation::Submit(submit_infos) => {
                 for submit_info in submit_infos {
                     for semaphore_submit_info in submit_info.wait_semaphores {
-                        semaphore_submit_info.semaphore.state().set_wait_finished();
+                        semaphore_submit_info
+                            .semaphore
+                            .state()
+                            .set_wait_finished(semaphore_submit_info.value);
                     }
 
                     for semaphore_submit_info in submit_info.signal_semaphores {
                         semaphore_submit_info
                             .semaphore
                             .state()
-                            .set_signal_finished();
+                            .set_signal_finished(semaphore_submit_info.value);
                     }
 
                     for command_buffer in submit_info.command_buffers {
@@ -1514,6 +2197,8 @@ impl<'a> States<'a> {
                 wait_semaphores,
                 command_buffers: info_command_buffers,
                 signal_semaphores,
+                protected: _,
+                performance_counter_pass_index: _,
                 _ne: _,
             } = submit_info;
 
@@ -1588,6 +2273,14 @@ pub struct QueueFamilyProperties {
 
     /// The minimum granularity supported for image transfers, in terms of `[width, height, depth]`.
     pub min_image_transfer_granularity: [u32; 3],
+
+    /// The video coding operations that are supported by the queue family.
+    ///
+    /// This is always empty if the [`khr_video_queue`] extension is not supported by the
+    /// physical device.
+    ///
+    /// [`khr_video_queue`]: crate::device::DeviceExtensions::khr_video_queue
+    pub video_codec_operations: VideoCodecOperations,
 }
 
 impl From<ash::vk::QueueFamilyProperties> for QueueFamilyProperties {
@@ -1603,6 +2296,7 @@ impl From<ash::vk::QueueFamilyProperties> for QueueFamilyProperties {
                 val.min_image_transfer_granularity.height,
                 val.min_image_transfer_granularity.depth,
             ],
+            video_codec_operations: VideoCodecOperations::empty(),
         }
     }
 }
@@ -1697,11 +2391,296 @@ impl From<RequirementNotMet> for QueueError {
     }
 }
 
+/// Error that can happen when performing sparse bind operations on a queue.
+#[derive(Clone, Debug)]
+pub enum BindSparseError {
+    VulkanError(VulkanError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// A buffer was not created with the [`BufferCreateFlags::SPARSE_BINDING`] flag.
+    BufferNotSparse,
+
+    /// The fence is already in use by another queue operation.
+    FenceInUse,
+
+    /// The fence is already signaled.
+    FenceSignaled,
+
+    /// The array layer of an image bind is not less than the number of array layers in the
+    /// image.
+    ImageArrayLayerOutOfRange {
+        array_layer: u32,
+        array_layers: u32,
+    },
+
+    /// The aspects of an image bind are not all part of a single set of sparse memory
+    /// requirements of the image.
+    ImageAspectsNotAllowed {
+        aspects: ImageAspects,
+    },
+
+    /// The offset or extent of an image bind is not a multiple of the sparse image block size,
+    /// and the bind does not extend to the edge of the subresource.
+    ImageBindNotAligned {
+        offset: [u32; 3],
+        extent: [u32; 3],
+        granularity: [u32; 3],
+    },
+
+    /// The offset and extent of an image bind exceed the extent of the subresource.
+    ImageBindOutOfRange {
+        offset: [u32; 3],
+        extent: [u32; 3],
+        subresource_extent: [u32; 3],
+    },
+
+    /// The mip level of an image bind is not less than the number of mip levels in the image.
+    ImageMipLevelOutOfRange {
+        mip_level: u32,
+        mip_levels: u32,
+    },
+
+    /// An image was not created with the [`ImageCreateFlags::SPARSE_BINDING`] flag.
+    ImageNotSparse,
+
+    /// An image that was used in `image_binds` was not created with the
+    /// [`ImageCreateFlags::SPARSE_RESIDENCY`] flag.
+    ImageNotSparseResidency,
+
+    /// The memory of a bind has a memory type with the `lazily_allocated` property.
+    MemoryLazilyAllocated,
+
+    /// The memory offset of a bind does not have the required alignment.
+    MemoryNotAligned {
+        memory_offset: DeviceSize,
+        required_alignment: DeviceSize,
+    },
+
+    /// The memory offset and size of a bind exceed the size of the memory.
+    MemoryOutOfRange {
+        memory_offset: DeviceSize,
+        size: DeviceSize,
+        memory_size: DeviceSize,
+    },
+
+    /// The memory type of a bind is not one of the allowed memory types for the resource.
+    MemoryTypeNotAllowed {
+        provided_memory_type_index: u32,
+        allowed_memory_type_bits: u32,
+    },
+
+    /// A bind to the metadata aspect of an image is not within the mip tail region of the
+    /// metadata aspect.
+    MetadataNotInMipTail,
+
+    /// The queue family of the queue does not support sparse binding operations.
+    NotSupportedByQueueFamily,
+
+    /// The offset and size of a bind exceed the size of the resource.
+    ResourceRangeOutOfBounds {
+        offset: DeviceSize,
+        size: DeviceSize,
+        resource_size: DeviceSize,
+    },
+
+    /// A wait or signal semaphore is not a binary semaphore.
+    SemaphoreNotBinary,
+}
+
+impl Error for BindSparseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::VulkanError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for BindSparseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::VulkanError(_) => write!(f, "a runtime error occurred"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::BufferNotSparse => write!(
+                f,
+                "a buffer was not created with the `BufferCreateFlags::SPARSE_BINDING` flag",
+            ),
+            Self::FenceInUse => {
+                write!(f, "the fence is already in use by another queue operation",)
+            }
+            Self::FenceSignaled => write!(f, "the fence is already signaled"),
+            Self::ImageArrayLayerOutOfRange {
+                array_layer,
+                array_layers,
+            } => write!(
+                f,
+                "the array layer of an image bind ({}) is not less than the number of array \
+                layers in the image ({})",
+                array_layer, array_layers,
+            ),
+            Self::ImageAspectsNotAllowed { aspects } => write!(
+                f,
+                "the aspects of an image bind ({:?}) are not all part of a single set of sparse \
+                memory requirements of the image",
+                aspects,
+            ),
+            Self::ImageBindNotAligned {
+                offset,
+                extent,
+                granularity,
+            } => write!(
+                f,
+                "the offset ({:?}) or extent ({:?}) of an image bind is not a multiple of the \
+                sparse image block size ({:?}), and the bind does not extend to the edge of the \
+                subresource",
+                offset, extent, granularity,
+            ),
+            Self::ImageBindOutOfRange {
+                offset,
+                extent,
+                subresource_extent,
+            } => write!(
+                f,
+                "the offset ({:?}) and extent ({:?}) of an image bind exceed the extent of the \
+                subresource ({:?})",
+                offset, extent, subresource_extent,
+            ),
+            Self::ImageMipLevelOutOfRange {
+                mip_level,
+                mip_levels,
+            } => write!(
+                f,
+                "the mip level of an image bind ({}) is not less than the number of mip levels \
+                in the image ({})",
+                mip_level, mip_levels,
+            ),
+            Self::ImageNotSparse => write!(
+                f,
+                "an image was not created with the `ImageCreateFlags::SPARSE_BINDING` flag",
+            ),
+            Self::ImageNotSparseResidency => write!(
+                f,
+                "an image that was used in `image_binds` was not created with the \
+                `ImageCreateFlags::SPARSE_RESIDENCY` flag",
+            ),
+            Self::MemoryLazilyAllocated => write!(
+                f,
+                "the memory of a bind has a memory type with the `lazily_allocated` property",
+            ),
+            Self::MemoryNotAligned {
+                memory_offset,
+                required_alignment,
+            } => write!(
+                f,
+                "the memory offset of a bind ({}) does not have the required alignment ({})",
+                memory_offset, required_alignment,
+            ),
+            Self::MemoryOutOfRange {
+                memory_offset,
+                size,
+                memory_size,
+            } => write!(
+                f,
+                "the memory offset ({}) and size ({}) of a bind exceed the size of the memory \
+                ({})",
+                memory_offset, size, memory_size,
+            ),
+            Self::MemoryTypeNotAllowed {
+                provided_memory_type_index,
+                allowed_memory_type_bits,
+            } => write!(
+                f,
+                "the memory type of a bind ({}) is not one of the allowed memory types for the \
+                resource ({:#b})",
+                provided_memory_type_index, allowed_memory_type_bits,
+            ),
+            Self::MetadataNotInMipTail => write!(
+                f,
+                "a bind to the metadata aspect of an image is not within the mip tail region of \
+                the metadata aspect",
+            ),
+            Self::NotSupportedByQueueFamily => write!(
+                f,
+                "the queue family of the queue does not support sparse binding operations",
+            ),
+            Self::ResourceRangeOutOfBounds {
+                offset,
+                size,
+                resource_size,
+            } => write!(
+                f,
+                "the offset ({}) and size ({}) of a bind exceed the size of the resource ({})",
+                offset, size, resource_size,
+            ),
+            Self::SemaphoreNotBinary => {
+                write!(f, "a wait or signal semaphore is not a binary semaphore")
+            }
+        }
+    }
+}
+
+impl From<VulkanError> for BindSparseError {
+    fn from(err: VulkanError) -> Self {
+        Self::VulkanError(err)
+    }
+}
+
+impl From<RequirementNotMet> for BindSparseError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::sync::fence::Fence;
+    use super::BindSparseError;
+    use crate::{
+        buffer::{
+            sys::{Buffer, BufferCreateInfo, RawBuffer},
+            BufferAccess, BufferCreateFlags, BufferInner, BufferUsage, CpuAccessibleBuffer,
+        },
+        device::{Device, DeviceOwned, QueueFlags},
+        memory::{allocator::StandardMemoryAllocator, BindSparseInfo, SparseBufferMemoryBind},
+        sync::fence::{Fence, FenceCreateInfo},
+        DeviceSize,
+    };
     use std::{sync::Arc, time::Duration};
 
+    struct SparseBuffer(Arc<Buffer>);
+
+    unsafe impl BufferAccess for SparseBuffer {
+        fn inner(&self) -> BufferInner<'_> {
+            BufferInner {
+                buffer: &self.0,
+                offset: 0,
+            }
+        }
+
+        fn size(&self) -> DeviceSize {
+            self.0.size()
+        }
+    }
+
+    unsafe impl DeviceOwned for SparseBuffer {
+        fn device(&self) -> &Arc<Device> {
+            self.0.device()
+        }
+    }
+
     #[test]
     fn empty_submit() {
         let (_device, queue) = gfx_dev_and_queue!();
@@ -1727,4 +2706,138 @@ mod tests {
             assert!(fence.is_signaled().unwrap());
         }
     }
+
+    #[test]
+    fn bind_sparse_not_supported_by_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        if device.physical_device().queue_family_properties()[queue.queue_family_index() as usize]
+            .queue_flags
+            .intersects(QueueFlags::SPARSE_BINDING)
+        {
+            return;
+        }
+
+        assert!(matches!(
+            queue.with(|mut q| unsafe { q.bind_sparse([BindSparseInfo::default()], None) }),
+            Err(BindSparseError::NotSupportedByQueueFamily),
+        ));
+    }
+
+    #[test]
+    fn bind_sparse_fence_signaled() {
+        let (device, queue) = gfx_dev_and_queue!(sparse_binding);
+
+        if !device.physical_device().queue_family_properties()[queue.queue_family_index() as usize]
+            .queue_flags
+            .intersects(QueueFlags::SPARSE_BINDING)
+        {
+            return;
+        }
+
+        let fence = Arc::new(
+            Fence::new(
+                device,
+                FenceCreateInfo {
+                    signaled: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        );
+
+        assert!(matches!(
+            queue.with(|mut q| unsafe { q.bind_sparse([BindSparseInfo::default()], Some(fence)) }),
+            Err(BindSparseError::FenceSignaled),
+        ));
+    }
+
+    #[test]
+    fn bind_sparse_buffer_not_sparse() {
+        let (device, queue) = gfx_dev_and_queue!(sparse_binding);
+
+        if !device.physical_device().queue_family_properties()[queue.queue_family_index() as usize]
+            .queue_flags
+            .intersects(QueueFlags::SPARSE_BINDING)
+        {
+            return;
+        }
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let buffer = CpuAccessibleBuffer::from_iter(
+            &memory_allocator,
+            BufferUsage::TRANSFER_DST,
+            false,
+            [0u8; 128],
+        )
+        .unwrap();
+
+        assert!(matches!(
+            queue.with(|mut q| unsafe {
+                q.bind_sparse(
+                    [BindSparseInfo {
+                        buffer_binds: vec![(
+                            buffer,
+                            vec![SparseBufferMemoryBind {
+                                offset: 0,
+                                size: 128,
+                                memory: None,
+                            }],
+                        )],
+                        ..Default::default()
+                    }],
+                    None,
+                )
+            }),
+            Err(BindSparseError::BufferNotSparse),
+        ));
+    }
+
+    #[test]
+    fn bind_sparse_resource_range_out_of_bounds() {
+        let (device, queue) = gfx_dev_and_queue!(sparse_binding);
+
+        if !device.physical_device().queue_family_properties()[queue.queue_family_index() as usize]
+            .queue_flags
+            .intersects(QueueFlags::SPARSE_BINDING)
+        {
+            return;
+        }
+
+        let raw_buffer = RawBuffer::new(
+            device,
+            BufferCreateInfo {
+                size: 128,
+                flags: BufferCreateFlags::SPARSE_BINDING,
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let buffer = Arc::new(SparseBuffer(Arc::new(unsafe { raw_buffer.assume_bound() })));
+
+        match queue.with(|mut q| unsafe {
+            q.bind_sparse(
+                [BindSparseInfo {
+                    buffer_binds: vec![(
+                        buffer,
+                        vec![SparseBufferMemoryBind {
+                            offset: 64,
+                            size: 128,
+                            memory: None,
+                        }],
+                    )],
+                    ..Default::default()
+                }],
+                None,
+            )
+        }) {
+            Err(BindSparseError::ResourceRangeOutOfBounds {
+                offset: 64,
+                size: 128,
+                resource_size: 128,
+            }) => (),
+            _ => panic!(),
+        }
+    }
 }
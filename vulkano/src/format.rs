@@ -742,6 +742,23 @@ impl FormatProperties {
     }
 }
 
+/// The properties of a Linux DRM format modifier that is supported by a physical device for a
+/// particular format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DrmFormatModifierProperties {
+    /// The DRM format modifier.
+    pub drm_format_modifier: u64,
+
+    /// The number of memory planes of an image created with this modifier.
+    ///
+    /// This can differ from the number of planes of the format, as the modifier may add extra
+    /// planes for metadata such as compression.
+    pub drm_format_modifier_plane_count: u32,
+
+    /// Features available for images created with this modifier.
+    pub drm_format_modifier_tiling_features: FormatFeatures,
+}
+
 vulkan_bitflags! {
     #[non_exhaustive]
 
@@ -25,7 +25,7 @@ use crate::{
         DedicatedAllocation, DeviceMemoryError, ExternalMemoryHandleType,
         ExternalMemoryHandleTypes,
     },
-    DeviceSize,
+    DeviceSize, VulkanObject,
 };
 use std::{
     fs::File,
@@ -99,6 +99,30 @@ impl AttachmentImage {
         )
     }
 
+    /// Same as `new`, but creates an image with multiple layers.
+    ///
+    /// This is useful for rendering with multiview, where each view of a subpass is rendered to
+    /// a separate layer of the attachment.
+    ///
+    /// > **Note**: You can also use this function and pass `1` for the number of layers if you
+    /// > want a regular image.
+    #[inline]
+    pub fn with_layers(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        dimensions: [u32; 2],
+        array_layers: u32,
+        format: Format,
+    ) -> Result<Arc<AttachmentImage>, ImageError> {
+        AttachmentImage::new_impl(
+            allocator,
+            dimensions,
+            array_layers,
+            format,
+            ImageUsage::empty(),
+            SampleCount::Sample1,
+        )
+    }
+
     /// Same as `new`, but creates an image that can be used as an input attachment.
     ///
     /// > **Note**: This function is just a convenient shortcut for `with_usage`.
@@ -178,6 +202,28 @@ impl AttachmentImage {
         )
     }
 
+    /// Same as `with_usage`, but creates an image with multiple layers.
+    ///
+    /// > **Note**: You can also use this function and pass `1` for the number of layers if you
+    /// > want a regular image.
+    #[inline]
+    pub fn with_usage_with_layers(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        dimensions: [u32; 2],
+        array_layers: u32,
+        format: Format,
+        usage: ImageUsage,
+    ) -> Result<Arc<AttachmentImage>, ImageError> {
+        AttachmentImage::new_impl(
+            allocator,
+            dimensions,
+            array_layers,
+            format,
+            usage,
+            SampleCount::Sample1,
+        )
+    }
+
     /// Same as `with_usage`, but creates a multisampled image.
     ///
     /// > **Note**: You can also use this function and pass `1` for the number of samples if you
@@ -622,6 +668,15 @@ unsafe impl ImageAccess for AttachmentImage {
     }
 }
 
+unsafe impl VulkanObject for AttachmentImage {
+    type Handle = ash::vk::Image;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.inner.handle()
+    }
+}
+
 unsafe impl DeviceOwned for AttachmentImage {
     #[inline]
     fn device(&self) -> &Arc<Device> {
@@ -671,6 +726,16 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn create_layered() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+        let img =
+            AttachmentImage::with_layers(&memory_allocator, [32, 32], 2, Format::R8G8B8A8_UNORM)
+                .unwrap();
+        assert_eq!(img.dimensions().array_layers(), 2);
+    }
+
     #[test]
     fn d16_unorm_always_supported() {
         let (device, _) = gfx_dev_and_queue!();
@@ -0,0 +1,255 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Reading back the contents of images, for example to take screenshots.
+//!
+//! [`read_image_to_buffer`] records the commands needed to copy an image, such as a
+//! [`SwapchainImage`](super::SwapchainImage) or an [`AttachmentImage`](super::AttachmentImage),
+//! into a buffer that the host can read. The layout transitions of the image are handled by the
+//! command buffer builder.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use vulkano::command_buffer::{
+//! #     allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+//! #     CommandBufferUsage,
+//! # };
+//! # use vulkano::device::Queue;
+//! # use vulkano::image::ImageAccess;
+//! # use vulkano::memory::allocator::StandardMemoryAllocator;
+//! # use vulkano::sync::{self, GpuFuture};
+//! # let queue: Arc<Queue> = return;
+//! # let memory_allocator: StandardMemoryAllocator = return;
+//! # let command_buffer_allocator: StandardCommandBufferAllocator = return;
+//! # let image: Arc<dyn ImageAccess> = return;
+//! use vulkano::image::capture::read_image_to_buffer;
+//!
+//! let mut builder = AutoCommandBufferBuilder::primary(
+//!     &command_buffer_allocator,
+//!     queue.queue_family_index(),
+//!     CommandBufferUsage::OneTimeSubmit,
+//! )
+//! .unwrap();
+//! let captured_image = read_image_to_buffer(&memory_allocator, image, &mut builder).unwrap();
+//!
+//! sync::now(queue.device().clone())
+//!     .then_execute(queue.clone(), builder.build().unwrap())
+//!     .unwrap()
+//!     .then_signal_fence_and_flush()
+//!     .unwrap()
+//!     .wait(None)
+//!     .unwrap();
+//!
+//! let [width, height] = captured_image.extent();
+//! let rgba = captured_image.to_rgba8().unwrap();
+//! ```
+
+use super::{ImageAccess, ImageAspects};
+use crate::{
+    buffer::{cpu_access::ReadLockError, BufferUsage, CpuAccessibleBuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, BufferImageCopy, CopyError,
+        CopyImageToBufferInfo,
+    },
+    format::Format,
+    memory::allocator::{AllocationCreationError, MemoryAllocator},
+    DeviceSize,
+};
+use smallvec::smallvec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+/// Records the commands to copy the contents of `image` into a new buffer that can be read by the
+/// host.
+///
+/// Only the first mip level and the first array layer of the image are copied. The rows of the
+/// image are tightly packed in the buffer, so that the row pitch is
+/// [`CapturedImage::row_pitch`].
+///
+/// The image must have been created with the
+/// [`TRANSFER_SRC`](super::ImageUsage::TRANSFER_SRC) usage. For a swapchain image, this means
+/// that the usage must be included in the
+/// [`image_usage`](crate::swapchain::SwapchainCreateInfo::image_usage) of the swapchain, and the
+/// command buffer must be executed after the image has been acquired.
+///
+/// The contents of the buffer can only be read once the command buffer has finished executing.
+pub fn read_image_to_buffer<L, A>(
+    allocator: &(impl MemoryAllocator + ?Sized),
+    image: Arc<dyn ImageAccess>,
+    command_buffer_builder: &mut AutoCommandBufferBuilder<L, A>,
+) -> Result<CapturedImage, CaptureError>
+where
+    A: CommandBufferAllocator,
+{
+    let format = image.format();
+
+    // Depth/stencil and multi-planar images would need a separate copy for each aspect or plane.
+    if format.aspects() != ImageAspects::COLOR {
+        return Err(CaptureError::FormatNotSupported);
+    }
+
+    let [width, height, _] = image.dimensions().width_height_depth();
+    let region = BufferImageCopy {
+        image_subresource: {
+            let mut image_subresource = image.subresource_layers();
+            image_subresource.array_layers = 0..1;
+            image_subresource
+        },
+        image_extent: [width, height, 1],
+        ..Default::default()
+    };
+    let buffer_size = region.buffer_copy_size(format);
+
+    let buffer = unsafe {
+        CpuAccessibleBuffer::<[u8]>::uninitialized_array(
+            allocator,
+            buffer_size,
+            BufferUsage::TRANSFER_DST,
+            true,
+        )?
+    };
+
+    command_buffer_builder.copy_image_to_buffer(CopyImageToBufferInfo {
+        regions: smallvec![region],
+        ..CopyImageToBufferInfo::image_buffer(image, buffer.clone())
+    })?;
+
+    let block_extent = format.block_extent();
+    let row_pitch = ((width + block_extent[0] - 1) / block_extent[0]) as DeviceSize
+        * format.block_size().unwrap();
+
+    Ok(CapturedImage {
+        buffer,
+        format,
+        extent: [width, height],
+        row_pitch,
+    })
+}
+
+/// The contents of an image that were copied to a buffer by [`read_image_to_buffer`].
+#[derive(Debug)]
+pub struct CapturedImage {
+    buffer: Arc<CpuAccessibleBuffer<[u8]>>,
+    format: Format,
+    extent: [u32; 2],
+    row_pitch: DeviceSize,
+}
+
+impl CapturedImage {
+    /// Returns the buffer that the image is copied to.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<CpuAccessibleBuffer<[u8]>> {
+        &self.buffer
+    }
+
+    /// Returns the format of the image, which is also the format of the data in the buffer.
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the width and height of the image.
+    #[inline]
+    pub fn extent(&self) -> [u32; 2] {
+        self.extent
+    }
+
+    /// Returns the number of bytes between the starts of two successive rows in the buffer.
+    #[inline]
+    pub fn row_pitch(&self) -> DeviceSize {
+        self.row_pitch
+    }
+
+    /// Reads the buffer and returns its contents as 8-bit RGBA pixels, with the rows tightly
+    /// packed. Images with a BGRA format are swizzled to RGBA.
+    ///
+    /// Only 8-bit RGBA and BGRA formats are supported. The values are returned as they are
+    /// stored, so sRGB formats are not converted to linear.
+    pub fn to_rgba8(&self) -> Result<Vec<u8>, CaptureError> {
+        let bgra = match self.format {
+            Format::R8G8B8A8_UNORM | Format::R8G8B8A8_SRGB => false,
+            Format::B8G8R8A8_UNORM | Format::B8G8R8A8_SRGB => true,
+            _ => return Err(CaptureError::FormatNotSupported),
+        };
+
+        let mut data = self.buffer.read()?.to_vec();
+
+        if bgra {
+            for pixel in data.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Ok(data)
+    }
+}
+
+/// Error that can happen when reading back an image.
+#[derive(Clone, Debug)]
+pub enum CaptureError {
+    /// Allocating the buffer failed.
+    AllocError(AllocationCreationError),
+
+    /// Recording the copy command failed.
+    CopyError(CopyError),
+
+    /// The buffer could not be read, because the copy has not finished yet.
+    ReadLockError(ReadLockError),
+
+    /// The format of the image is not supported for this operation.
+    FormatNotSupported,
+}
+
+impl Error for CaptureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AllocError(err) => Some(err),
+            Self::CopyError(err) => Some(err),
+            Self::ReadLockError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for CaptureError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::AllocError(_) => write!(f, "allocating the buffer failed"),
+            Self::CopyError(_) => write!(f, "recording the copy command failed"),
+            Self::ReadLockError(_) => write!(f, "the buffer could not be read"),
+            Self::FormatNotSupported => {
+                write!(
+                    f,
+                    "the format of the image is not supported for this operation"
+                )
+            }
+        }
+    }
+}
+
+impl From<AllocationCreationError> for CaptureError {
+    fn from(err: AllocationCreationError) -> Self {
+        Self::AllocError(err)
+    }
+}
+
+impl From<CopyError> for CaptureError {
+    fn from(err: CopyError) -> Self {
+        Self::CopyError(err)
+    }
+}
+
+impl From<ReadLockError> for CaptureError {
+    fn from(err: ReadLockError) -> Self {
+        Self::ReadLockError(err)
+    }
+}
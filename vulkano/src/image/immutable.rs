@@ -31,7 +31,7 @@ use crate::{
     },
     sampler::Filter,
     sync::Sharing,
-    DeviceSize, VulkanError,
+    DeviceSize, VulkanError, VulkanObject,
 };
 use smallvec::{smallvec, SmallVec};
 use std::{
@@ -284,6 +284,15 @@ impl ImmutableImage {
     }
 }
 
+unsafe impl VulkanObject for ImmutableImage {
+    type Handle = ash::vk::Image;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.inner.handle()
+    }
+}
+
 unsafe impl DeviceOwned for ImmutableImage {
     #[inline]
     fn device(&self) -> &Arc<Device> {
@@ -138,23 +138,21 @@ vulkan_enum! {
         device_extensions: [khr_swapchain],
     },
 
-    /* TODO: enable
-    // TODO: document
+    /// The layout of an image that is the output picture of a video decode operation.
     VideoDecodeDst = VIDEO_DECODE_DST_KHR {
         device_extensions: [khr_video_decode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// Reserved for future use by video decode operations.
     VideoDecodeSrc = VIDEO_DECODE_SRC_KHR {
         device_extensions: [khr_video_decode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The layout of an image that is a reference picture in the decoded picture buffer of a video
+    /// decode operation.
     VideoDecodeDpb = VIDEO_DECODE_DPB_KHR {
         device_extensions: [khr_video_decode_queue],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -162,35 +160,31 @@ vulkan_enum! {
         device_extensions: [khr_shared_presentable_image],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The layout of an image that is used as a fragment density map attachment.
     FragmentDensityMapOptimal = FRAGMENT_DENSITY_MAP_OPTIMAL_EXT {
         device_extensions: [ext_fragment_density_map],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The layout of an image that is used as a fragment shading rate attachment.
     FragmentShadingRateAttachmentOptimal = FRAGMENT_SHADING_RATE_ATTACHMENT_OPTIMAL_KHR {
         device_extensions: [khr_fragment_shading_rate],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// Reserved for future use by video encode operations.
     VideoEncodeDst = VIDEO_ENCODE_DST_KHR {
         device_extensions: [khr_video_encode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The layout of an image that is the input picture of a video encode operation.
     VideoEncodeSrc = VIDEO_ENCODE_SRC_KHR {
         device_extensions: [khr_video_encode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The layout of an image that is a reference picture in the decoded picture buffer of a video
+    /// encode operation.
     VideoEncodeDpb = VIDEO_ENCODE_DPB_KHR {
         device_extensions: [khr_video_encode_queue],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
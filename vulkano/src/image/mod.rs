@@ -62,12 +62,15 @@ use crate::{
     format::Format,
     macros::{vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
     memory::{ExternalMemoryHandleType, ExternalMemoryProperties},
+    sync::Sharing,
     DeviceSize,
 };
+use smallvec::SmallVec;
 use std::{cmp, ops::Range};
 
 mod aspect;
 pub mod attachment; // TODO: make private
+pub mod capture;
 pub mod immutable; // TODO: make private
 mod layout;
 mod storage;
@@ -83,7 +86,6 @@ vulkan_bitflags! {
     /// Flags that can be set when creating a new image.
     ImageCreateFlags = ImageCreateFlags(u32);
 
-    /* TODO: enable
     /// The image will be backed by sparse memory binding (through queue commands) instead of
     /// regular binding (through [`bind_memory`]).
     ///
@@ -91,9 +93,8 @@ vulkan_bitflags! {
     ///
     /// [`bind_memory`]: sys::RawImage::bind_memory
     /// [`sparse_binding`]: crate::device::Features::sparse_binding
-    SPARSE_BINDING = SPARSE_BINDING,*/
+    SPARSE_BINDING = SPARSE_BINDING,
 
-    /* TODO: enable
     /// The image can be used without being fully resident in memory at the time of use.
     ///
     /// This requires the `sparse_binding` flag as well.
@@ -107,22 +108,21 @@ vulkan_bitflags! {
     ///
     /// [`sparse_binding`]: crate::device::Features::sparse_binding
     /// [`sparse_residency_image2_d`]: crate::device::Features::sparse_residency_image2_d
-    /// [`sparse_residency_image2_3`]: crate::device::Features::sparse_residency_image3_d
+    /// [`sparse_residency_image3_d`]: crate::device::Features::sparse_residency_image3_d
     /// [`sparse_residency2_samples`]: crate::device::Features::sparse_residency2_samples
     /// [`sparse_residency4_samples`]: crate::device::Features::sparse_residency4_samples
     /// [`sparse_residency8_samples`]: crate::device::Features::sparse_residency8_samples
     /// [`sparse_residency16_samples`]: crate::device::Features::sparse_residency16_samples
-    SPARSE_RESIDENCY = SPARSE_RESIDENCY,*/
+    SPARSE_RESIDENCY = SPARSE_RESIDENCY,
 
-    /* TODO: enable
-    /// The buffer's memory can alias with another image or a different part of the same image.
+    /// The image's memory can alias with another image or a different part of the same image.
     ///
     /// This requires the `sparse_binding` flag as well.
     ///
     /// The [`sparse_residency_aliased`] feature must be enabled on the device.
     ///
     /// [`sparse_residency_aliased`]: crate::device::Features::sparse_residency_aliased
-    SPARSE_ALIASED = SPARSE_ALIASED,*/
+    SPARSE_ALIASED = SPARSE_ALIASED,
 
     /// For non-multi-planar formats, whether an image view wrapping the image can have a
     /// different format.
@@ -184,11 +184,16 @@ vulkan_bitflags! {
         device_extensions: [khr_maintenance2],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The image is protected, and can only be used in combination with protected memory and other
+    /// protected objects.
+    ///
+    /// The device API version must be at least 1.1, and the [`protected_memory`] feature must be
+    /// enabled on the device. This flag can not be combined with the sparse flags.
+    ///
+    /// [`protected_memory`]: crate::device::Features::protected_memory
     PROTECTED = PROTECTED {
         api_version: V1_1,
-    },*/
+    },
 
     /// For images with a multi-planar format, whether each plane will have its memory bound
     /// separately, rather than having a single memory binding for the whole image.
@@ -203,17 +208,26 @@ vulkan_bitflags! {
         device_extensions: [nv_corner_sampled_image],
     },*/
 
-    /* TODO: enable
-    // TODO: document
+    /// For depth/stencil images, the image can be used with custom sample locations, by
+    /// providing the sample locations for the layout transitions of the image when beginning a
+    /// render pass.
+    ///
+    /// The image format must be a depth/stencil format.
     SAMPLE_LOCATIONS_COMPATIBLE_DEPTH = SAMPLE_LOCATIONS_COMPATIBLE_DEPTH_EXT {
         device_extensions: [ext_sample_locations],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as a subsampled image, when it is rendered to in a render pass that
+    /// has a fragment density map attachment. Subsampled images can only be sampled by samplers
+    /// created with [`SamplerCreateFlags::SUBSAMPLED`].
+    ///
+    /// The image must be 2D, have optimal tiling, have only one mip level, and must not be
+    /// cube-compatible.
+    ///
+    /// [`SamplerCreateFlags::SUBSAMPLED`]: crate::sampler::SamplerCreateFlags::SUBSAMPLED
     SUBSAMPLED = SUBSAMPLED_EXT {
         device_extensions: [ext_fragment_density_map],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -360,11 +374,16 @@ vulkan_enum! {
     // TODO: document
     Linear = LINEAR,
 
-    /* TODO: enable
-    // TODO: document
+    /// The memory layout of the image is defined by a Linux DRM format modifier. This is used to
+    /// share images with other APIs and processes, for example through dma-buf file descriptors.
+    ///
+    /// The modifiers that are supported for a format can be queried with
+    /// [`PhysicalDevice::format_drm_modifier_properties`].
+    ///
+    /// [`PhysicalDevice::format_drm_modifier_properties`]: crate::device::physical::PhysicalDevice::format_drm_modifier_properties
     DrmFormatModifier = DRM_FORMAT_MODIFIER_EXT {
         device_extensions: [ext_image_drm_format_modifier],
-    },*/
+    },
 }
 
 /// The dimensions of an image.
@@ -776,6 +795,20 @@ pub struct ImageFormatInfo {
     /// The default value is `None`.
     pub image_view_type: Option<ImageViewType>,
 
+    /// The Linux DRM format modifier that the image will have.
+    ///
+    /// This must be `Some` if and only if `tiling` is [`ImageTiling::DrmFormatModifier`].
+    ///
+    /// The default value is `None`.
+    pub drm_format_modifier: Option<u64>,
+
+    /// The sharing mode that the image will have.
+    ///
+    /// This is only used if `drm_format_modifier` is `Some`.
+    ///
+    /// The default value is [`Sharing::Exclusive`].
+    pub sharing: Sharing<SmallVec<[u32; 4]>>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -791,6 +824,8 @@ impl Default for ImageFormatInfo {
             stencil_usage: ImageUsage::empty(),
             external_memory_handle_type: None,
             image_view_type: None,
+            drm_format_modifier: None,
+            sharing: Sharing::Exclusive,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -26,7 +26,7 @@ use crate::{
         ExternalMemoryHandleTypes,
     },
     sync::Sharing,
-    DeviceSize,
+    DeviceSize, VulkanObject,
 };
 use smallvec::SmallVec;
 use std::{
@@ -270,6 +270,15 @@ impl StorageImage {
     }
 }
 
+unsafe impl VulkanObject for StorageImage {
+    type Handle = ash::vk::Image;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.inner.handle()
+    }
+}
+
 unsafe impl DeviceOwned for StorageImage {
     #[inline]
     fn device(&self) -> &Arc<Device> {
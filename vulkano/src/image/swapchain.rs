@@ -15,7 +15,7 @@ use super::{
 use crate::{
     device::{Device, DeviceOwned},
     swapchain::Swapchain,
-    OomError,
+    OomError, VulkanObject,
 };
 use std::{
     hash::{Hash, Hasher},
@@ -63,6 +63,15 @@ impl SwapchainImage {
     }
 }
 
+unsafe impl VulkanObject for SwapchainImage {
+    type Handle = ash::vk::Image;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.inner.handle()
+    }
+}
+
 unsafe impl DeviceOwned for SwapchainImage {
     fn device(&self) -> &Arc<Device> {
         self.inner.device()
@@ -22,7 +22,7 @@ use crate::{
     buffer::cpu_access::{ReadLockError, WriteLockError},
     cache::OnceCache,
     device::{Device, DeviceOwned},
-    format::{ChromaSampling, Format, FormatFeatures, NumericType},
+    format::{ChromaSampling, DrmFormatModifierProperties, Format, FormatFeatures, NumericType},
     image::{
         view::ImageViewCreationError, ImageFormatInfo, ImageFormatProperties, ImageType,
         SparseImageFormatProperties,
@@ -35,6 +35,7 @@ use crate::{
     range_map::RangeMap,
     swapchain::Swapchain,
     sync::{future::AccessError, CurrentAccess, Sharing},
+    video::{VideoFormatInfo, VideoProfileInfo, VideoProfileListInfoVk},
     DeviceSize, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use parking_lot::{Mutex, MutexGuard};
@@ -74,6 +75,7 @@ pub struct RawImage {
     sharing: Sharing<SmallVec<[u32; 4]>>,
     stencil_usage: ImageUsage,
     external_memory_handle_types: ExternalMemoryHandleTypes,
+    drm_format_modifier: Option<DrmFormatModifierProperties>,
 
     memory_requirements: SmallVec<[MemoryRequirements; 3]>,
     needs_destruction: bool, // `vkDestroyImage` is called only if true.
@@ -132,6 +134,9 @@ impl RawImage {
             ref sharing,
             initial_layout,
             external_memory_handle_types,
+            ref drm_format_modifiers,
+            ref drm_format_modifier_plane_layouts,
+            ref video_profiles,
             _ne: _,
         } = create_info;
 
@@ -205,6 +210,126 @@ impl RawImage {
                 || flags.intersects(ImageCreateFlags::MUTABLE_FORMAT)
         );
 
+        /* DRM format modifiers */
+
+        let mut drm_format_modifier_tiling_features = None;
+
+        if tiling == ImageTiling::DrmFormatModifier {
+            // VUID-VkImageCreateInfo-tiling-02261
+            if drm_format_modifiers.is_empty() {
+                return Err(ImageError::DrmFormatModifiersMissing);
+            }
+
+            // VUID-VkImageCreateInfo-tiling-02261
+            if !drm_format_modifier_plane_layouts.is_empty() && drm_format_modifiers.len() != 1 {
+                return Err(ImageError::DrmFormatModifierPlaneLayoutsMultipleModifiers);
+            }
+
+            // Use unchecked, because the extension is enabled, which was validated above.
+            let drm_format_modifier_properties =
+                unsafe { physical_device.format_drm_modifier_properties_unchecked(format) };
+
+            for &drm_format_modifier in drm_format_modifiers {
+                let properties = drm_format_modifier_properties
+                    .iter()
+                    .find(|properties| properties.drm_format_modifier == drm_format_modifier)
+                    .ok_or(ImageError::DrmFormatModifierNotSupported {
+                        drm_format_modifier,
+                    })?;
+
+                // If a list of modifiers is given, any of them may be chosen, so only the features
+                // that are supported by all of them can be relied on.
+                *drm_format_modifier_tiling_features
+                    .get_or_insert(properties.drm_format_modifier_tiling_features) &=
+                    properties.drm_format_modifier_tiling_features;
+
+                // VUID-VkImageDrmFormatModifierExplicitCreateInfoEXT-drmFormatModifierPlaneCount-02265
+                if !drm_format_modifier_plane_layouts.is_empty()
+                    && drm_format_modifier_plane_layouts.len() as u32
+                        != properties.drm_format_modifier_plane_count
+                {
+                    return Err(ImageError::DrmFormatModifierPlaneLayoutsCountMismatch {
+                        provided: drm_format_modifier_plane_layouts.len() as u32,
+                        required: properties.drm_format_modifier_plane_count,
+                    });
+                }
+            }
+
+            for (plane_index, plane_layout) in drm_format_modifier_plane_layouts.iter().enumerate()
+            {
+                // VUID-VkImageDrmFormatModifierExplicitCreateInfoEXT-size-02267
+                // VUID-VkImageDrmFormatModifierExplicitCreateInfoEXT-arrayPitch-02268
+                // VUID-VkImageDrmFormatModifierExplicitCreateInfoEXT-depthPitch-02269
+                if plane_layout.size != 0
+                    || plane_layout.array_pitch.is_some() && dimensions.array_layers() == 1
+                    || plane_layout.depth_pitch.is_some() && dimensions.depth() == 1
+                {
+                    return Err(ImageError::DrmFormatModifierPlaneLayoutInvalid {
+                        plane_index: plane_index as u32,
+                    });
+                }
+            }
+        } else {
+            // VUID-VkImageCreateInfo-pNext-02262
+            if !(drm_format_modifiers.is_empty() && drm_format_modifier_plane_layouts.is_empty()) {
+                return Err(ImageError::DrmFormatModifiersNotAllowed);
+            }
+        }
+
+        /* Video profiles */
+
+        let video_usage = usage
+            & (ImageUsage::VIDEO_DECODE_DST
+                | ImageUsage::VIDEO_DECODE_SRC
+                | ImageUsage::VIDEO_DECODE_DPB
+                | ImageUsage::VIDEO_ENCODE_SRC
+                | ImageUsage::VIDEO_ENCODE_DPB);
+
+        if !video_profiles.is_empty() {
+            if !device.enabled_extensions().khr_video_queue {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.video_profiles` is not empty",
+                    requires_one_of: RequiresOneOf {
+                        device_extensions: &["khr_video_queue"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            for video_profile in video_profiles {
+                // VUID-VkVideoProfileListInfoKHR-pProfiles-parameter
+                video_profile.validate(physical_device)?;
+            }
+
+            // VUID-VkImageCreateInfo-pNext-06811
+            if !video_usage.is_empty() {
+                // Use unchecked, because all validation has been done above.
+                let video_format_properties = unsafe {
+                    physical_device.video_format_properties_unchecked(VideoFormatInfo {
+                        image_usage: video_usage,
+                        video_profiles: video_profiles.clone(),
+                        ..Default::default()
+                    })?
+                };
+
+                if !video_format_properties
+                    .iter()
+                    .any(|properties| properties.format == format)
+                {
+                    return Err(ImageError::VideoFormatNotSupported);
+                }
+            }
+        } else if usage.intersects(
+            ImageUsage::VIDEO_DECODE_DST
+                | ImageUsage::VIDEO_DECODE_DPB
+                | ImageUsage::VIDEO_ENCODE_SRC
+                | ImageUsage::VIDEO_ENCODE_DPB,
+        ) {
+            // VUID-VkImageCreateInfo-usage-04815
+            // VUID-VkImageCreateInfo-usage-04816
+            return Err(ImageError::VideoProfilesMissing);
+        }
+
         // Get format features
         let format_features = {
             // Use unchecked, because all validation has been done above.
@@ -212,6 +337,7 @@ impl RawImage {
             match tiling {
                 ImageTiling::Linear => format_properties.linear_tiling_features,
                 ImageTiling::Optimal => format_properties.optimal_tiling_features,
+                ImageTiling::DrmFormatModifier => drm_format_modifier_tiling_features.unwrap(),
             }
         };
 
@@ -384,6 +510,22 @@ impl RawImage {
             });
         }
 
+        if combined_usage.intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+            && !format_features.intersects(FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT)
+        {
+            return Err(ImageError::FormatUsageNotSupported {
+                usage: "fragment_shading_rate_attachment",
+            });
+        }
+
+        if combined_usage.intersects(ImageUsage::FRAGMENT_DENSITY_MAP)
+            && !format_features.intersects(FormatFeatures::FRAGMENT_DENSITY_MAP)
+        {
+            return Err(ImageError::FormatUsageNotSupported {
+                usage: "fragment_density_map",
+            });
+        }
+
         if combined_usage.intersects(
             ImageUsage::COLOR_ATTACHMENT
                 | ImageUsage::DEPTH_STENCIL_ATTACHMENT
@@ -557,6 +699,171 @@ impl RawImage {
             }
         }
 
+        if flags.intersects(ImageCreateFlags::SAMPLE_LOCATIONS_COMPATIBLE_DEPTH) {
+            // VUID-VkImageCreateInfo-flags-01533
+            if !format
+                .aspects()
+                .intersects(ImageAspects::DEPTH | ImageAspects::STENCIL)
+            {
+                return Err(ImageError::SampleLocationsCompatibleDepthNotDepthStencil);
+            }
+        }
+
+        if flags.intersects(ImageCreateFlags::SUBSAMPLED) {
+            // VUID-VkImageCreateInfo-flags-02565
+            if tiling != ImageTiling::Optimal {
+                return Err(ImageError::SubsampledNotOptimalTiling);
+            }
+
+            // VUID-VkImageCreateInfo-flags-02566
+            if image_type != ImageType::Dim2d {
+                return Err(ImageError::SubsampledNot2d);
+            }
+
+            // VUID-VkImageCreateInfo-flags-02567
+            if flags.intersects(ImageCreateFlags::CUBE_COMPATIBLE) {
+                return Err(ImageError::SubsampledCubeCompatible);
+            }
+
+            // VUID-VkImageCreateInfo-flags-02568
+            if mip_levels != 1 {
+                return Err(ImageError::SubsampledMultipleMipLevels);
+            }
+        }
+
+        if flags.intersects(ImageCreateFlags::SPARSE_BINDING) {
+            // VUID-VkImageCreateInfo-flags-00969
+            if !device.enabled_features().sparse_binding {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains `ImageCreateFlags::SPARSE_BINDING`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["sparse_binding"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        if flags.intersects(ImageCreateFlags::SPARSE_RESIDENCY) {
+            // VUID-VkImageCreateInfo-tiling-04121
+            if tiling == ImageTiling::Linear {
+                return Err(ImageError::SparseResidencyLinearTiling);
+            }
+
+            match image_type {
+                // VUID-VkImageCreateInfo-imageType-00970
+                ImageType::Dim1d => return Err(ImageError::SparseResidency1d),
+                ImageType::Dim2d => {
+                    // VUID-VkImageCreateInfo-imageType-00971
+                    if !device.enabled_features().sparse_residency_image2_d {
+                        return Err(ImageError::RequirementNotMet {
+                            required_for: "`create_info.flags` contains \
+                                `ImageCreateFlags::SPARSE_RESIDENCY`, and \
+                                `create_info.dimensions` is `ImageDimensions::Dim2d`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["sparse_residency_image2_d"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+                ImageType::Dim3d => {
+                    // VUID-VkImageCreateInfo-imageType-00972
+                    if !device.enabled_features().sparse_residency_image3_d {
+                        return Err(ImageError::RequirementNotMet {
+                            required_for: "`create_info.flags` contains \
+                                `ImageCreateFlags::SPARSE_RESIDENCY`, and \
+                                `create_info.dimensions` is `ImageDimensions::Dim3d`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["sparse_residency_image3_d"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+            }
+
+            // VUID-VkImageCreateInfo-imageType-00973
+            // VUID-VkImageCreateInfo-imageType-00974
+            // VUID-VkImageCreateInfo-imageType-00975
+            // VUID-VkImageCreateInfo-imageType-00976
+            let (samples_supported, required_features): (bool, &'static [&'static str]) =
+                match samples {
+                    SampleCount::Sample1 => (true, &[]),
+                    SampleCount::Sample2 => (
+                        device.enabled_features().sparse_residency2_samples,
+                        &["sparse_residency2_samples"],
+                    ),
+                    SampleCount::Sample4 => (
+                        device.enabled_features().sparse_residency4_samples,
+                        &["sparse_residency4_samples"],
+                    ),
+                    SampleCount::Sample8 => (
+                        device.enabled_features().sparse_residency8_samples,
+                        &["sparse_residency8_samples"],
+                    ),
+                    SampleCount::Sample16 => (
+                        device.enabled_features().sparse_residency16_samples,
+                        &["sparse_residency16_samples"],
+                    ),
+                    SampleCount::Sample32 | SampleCount::Sample64 => (false, &[]),
+                };
+
+            if !samples_supported {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains \
+                        `ImageCreateFlags::SPARSE_RESIDENCY`, and `create_info.samples` is not \
+                        `SampleCount::Sample1`",
+                    requires_one_of: RequiresOneOf {
+                        features: required_features,
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        if flags.intersects(ImageCreateFlags::SPARSE_ALIASED) {
+            // VUID-VkImageCreateInfo-flags-01924
+            if !device.enabled_features().sparse_residency_aliased {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains `ImageCreateFlags::SPARSE_ALIASED`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["sparse_residency_aliased"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        // VUID-VkImageCreateInfo-flags-00987
+        if flags.intersects(ImageCreateFlags::SPARSE_RESIDENCY | ImageCreateFlags::SPARSE_ALIASED)
+            && !flags.intersects(ImageCreateFlags::SPARSE_BINDING)
+        {
+            return Err(ImageError::SparseFlagsWithoutSparseBinding);
+        }
+
+        if flags.intersects(ImageCreateFlags::PROTECTED) {
+            // VUID-VkImageCreateInfo-flags-01890
+            if !device.enabled_features().protected_memory {
+                return Err(ImageError::RequirementNotMet {
+                    required_for: "`create_info.flags` contains `ImageCreateFlags::PROTECTED`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["protected_memory"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkImageCreateInfo-None-01891
+            if flags.intersects(
+                ImageCreateFlags::SPARSE_BINDING
+                    | ImageCreateFlags::SPARSE_RESIDENCY
+                    | ImageCreateFlags::SPARSE_ALIASED,
+            ) {
+                return Err(ImageError::SparseFlagsWithProtected);
+            }
+        }
+
         /* Check sharing mode and queue families */
 
         match sharing {
@@ -765,7 +1072,13 @@ impl RawImage {
             || mip_levels_must_query()
             || array_layers_must_query()
             || samples_must_query()
-            || linear_must_query();
+            || linear_must_query()
+            || tiling == ImageTiling::DrmFormatModifier;
+
+        // The image format properties of images with a video usage depend on the video profiles,
+        // which can't be passed to the query. The format was checked against the video format
+        // properties instead.
+        let must_query_device = must_query_device && video_usage.is_empty();
 
         // We determined that we must query the device in order to be sure that the image
         // configuration is supported.
@@ -779,72 +1092,94 @@ impl RawImage {
                     smallvec![None]
                 };
 
-            for external_memory_handle_type in external_memory_handle_types {
-                // Use unchecked, because all validation has been done above.
-                let image_format_properties = unsafe {
-                    device
-                        .physical_device()
-                        .image_format_properties_unchecked(ImageFormatInfo {
-                            flags,
-                            format: Some(format),
-                            image_type,
-                            tiling,
-                            usage,
-                            external_memory_handle_type,
-                            ..Default::default()
-                        })?
+            let drm_format_modifiers: SmallVec<[Option<u64>; 4]> =
+                if tiling == ImageTiling::DrmFormatModifier {
+                    // The properties need to be queried individually for each modifier.
+                    drm_format_modifiers.iter().copied().map(Some).collect()
+                } else {
+                    smallvec![None]
                 };
 
-                let ImageFormatProperties {
-                    max_extent,
-                    max_mip_levels,
-                    max_array_layers,
-                    sample_counts,
-                    max_resource_size: _,
-                    ..
-                } = match image_format_properties {
-                    Some(x) => x,
-                    None => return Err(ImageError::ImageFormatPropertiesNotSupported),
-                };
+            for external_memory_handle_type in external_memory_handle_types {
+                let mut is_supported = false;
+
+                for &drm_format_modifier in &drm_format_modifiers {
+                    // Use unchecked, because all validation has been done above.
+                    let image_format_properties = unsafe {
+                        device.physical_device().image_format_properties_unchecked(
+                            ImageFormatInfo {
+                                flags,
+                                format: Some(format),
+                                image_type,
+                                tiling,
+                                usage,
+                                external_memory_handle_type,
+                                drm_format_modifier,
+                                sharing: sharing.clone(),
+                                ..Default::default()
+                            },
+                        )?
+                    };
+
+                    let ImageFormatProperties {
+                        max_extent,
+                        max_mip_levels,
+                        max_array_layers,
+                        sample_counts,
+                        max_resource_size: _,
+                        ..
+                    } = match image_format_properties {
+                        Some(x) => x,
+                        // If a list of DRM format modifiers is given, the implementation only
+                        // chooses from the modifiers that support the image configuration.
+                        None => continue,
+                    };
+
+                    // VUID-VkImageCreateInfo-extent-02252
+                    // VUID-VkImageCreateInfo-extent-02253
+                    // VUID-VkImageCreateInfo-extent-02254
+                    if extent[0] > max_extent[0]
+                        || extent[1] > max_extent[1]
+                        || extent[2] > max_extent[2]
+                    {
+                        return Err(ImageError::MaxDimensionsExceeded {
+                            extent,
+                            max: max_extent,
+                        });
+                    }
 
-                // VUID-VkImageCreateInfo-extent-02252
-                // VUID-VkImageCreateInfo-extent-02253
-                // VUID-VkImageCreateInfo-extent-02254
-                if extent[0] > max_extent[0]
-                    || extent[1] > max_extent[1]
-                    || extent[2] > max_extent[2]
-                {
-                    return Err(ImageError::MaxDimensionsExceeded {
-                        extent,
-                        max: max_extent,
-                    });
-                }
+                    // VUID-VkImageCreateInfo-mipLevels-02255
+                    if mip_levels > max_mip_levels {
+                        return Err(ImageError::MaxMipLevelsExceeded {
+                            mip_levels,
+                            max: max_mip_levels,
+                        });
+                    }
 
-                // VUID-VkImageCreateInfo-mipLevels-02255
-                if mip_levels > max_mip_levels {
-                    return Err(ImageError::MaxMipLevelsExceeded {
-                        mip_levels,
-                        max: max_mip_levels,
-                    });
-                }
+                    // VUID-VkImageCreateInfo-arrayLayers-02256
+                    if array_layers > max_array_layers {
+                        return Err(ImageError::MaxArrayLayersExceeded {
+                            array_layers,
+                            max: max_array_layers,
+                        });
+                    }
 
-                // VUID-VkImageCreateInfo-arrayLayers-02256
-                if array_layers > max_array_layers {
-                    return Err(ImageError::MaxArrayLayersExceeded {
-                        array_layers,
-                        max: max_array_layers,
-                    });
-                }
+                    // VUID-VkImageCreateInfo-samples-02258
+                    if !sample_counts.contains_enum(samples) {
+                        return Err(ImageError::SampleCountNotSupported {
+                            samples,
+                            supported: sample_counts,
+                        });
+                    }
 
-                // VUID-VkImageCreateInfo-samples-02258
-                if !sample_counts.contains_enum(samples) {
-                    return Err(ImageError::SampleCountNotSupported {
-                        samples,
-                        supported: sample_counts,
-                    });
+                    // TODO: check resource size?
+
+                    is_supported = true;
                 }
 
-                // TODO: check resource size?
+                if !is_supported {
+                    return Err(ImageError::ImageFormatPropertiesNotSupported);
+                }
             }
         }
 
@@ -869,6 +1204,9 @@ impl RawImage {
             ref sharing,
             initial_layout,
             external_memory_handle_types,
+            ref drm_format_modifiers,
+            ref drm_format_modifier_plane_layouts,
+            ref video_profiles,
             _ne: _,
         } = &create_info;
 
@@ -931,6 +1269,10 @@ impl RawImage {
         };
         let mut external_memory_info_vk = None;
         let mut stencil_usage_info_vk = None;
+        let mut drm_format_modifier_list_info_vk = None;
+        let mut drm_format_modifier_explicit_info_vk = None;
+        let drm_format_modifier_plane_layouts_vk: SmallVec<[_; 4]>;
+        let mut video_profile_list_info_vk = None;
 
         if !external_memory_handle_types.is_empty() {
             let next = external_memory_info_vk.insert(ash::vk::ExternalMemoryImageCreateInfo {
@@ -952,6 +1294,52 @@ impl RawImage {
             info_vk.p_next = next as *const _ as *const _;
         }
 
+        if !drm_format_modifier_plane_layouts.is_empty() {
+            drm_format_modifier_plane_layouts_vk = drm_format_modifier_plane_layouts
+                .iter()
+                .map(|plane_layout| ash::vk::SubresourceLayout {
+                    offset: plane_layout.offset,
+                    size: plane_layout.size,
+                    row_pitch: plane_layout.row_pitch,
+                    array_pitch: plane_layout.array_pitch.unwrap_or(0),
+                    depth_pitch: plane_layout.depth_pitch.unwrap_or(0),
+                })
+                .collect();
+
+            let next = drm_format_modifier_explicit_info_vk.insert(
+                ash::vk::ImageDrmFormatModifierExplicitCreateInfoEXT {
+                    drm_format_modifier: drm_format_modifiers[0],
+                    drm_format_modifier_plane_count: drm_format_modifier_plane_layouts_vk.len()
+                        as u32,
+                    p_plane_layouts: drm_format_modifier_plane_layouts_vk.as_ptr(),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        } else if !drm_format_modifiers.is_empty() {
+            let next = drm_format_modifier_list_info_vk.insert(
+                ash::vk::ImageDrmFormatModifierListCreateInfoEXT {
+                    drm_format_modifier_count: drm_format_modifiers.len() as u32,
+                    p_drm_format_modifiers: drm_format_modifiers.as_ptr(),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        }
+
+        if !video_profiles.is_empty() {
+            let next = &mut video_profile_list_info_vk
+                .insert(VideoProfileListInfoVk::new(video_profiles))
+                .profile_list_info;
+
+            next.p_next = info_vk.p_next;
+            info_vk.p_next = next as *const _ as *const _;
+        }
+
         let handle = {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -961,7 +1349,53 @@ impl RawImage {
             output.assume_init()
         };
 
-        Ok(Self::from_handle(device, handle, create_info))
+        let drm_format_modifier = if tiling == ImageTiling::DrmFormatModifier {
+            match Self::query_drm_format_modifier(&device, handle, format.unwrap()) {
+                Ok(drm_format_modifier) => Some(drm_format_modifier),
+                Err(err) => {
+                    let fns = device.fns();
+                    (fns.v1_0.destroy_image)(device.handle(), handle, ptr::null());
+                    return Err(err);
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(Self::from_handle_with_destruction(
+            device,
+            handle,
+            create_info,
+            drm_format_modifier,
+            true,
+        ))
+    }
+
+    /// Queries the DRM format modifier that the implementation chose for `handle`.
+    unsafe fn query_drm_format_modifier(
+        device: &Device,
+        handle: ash::vk::Image,
+        format: Format,
+    ) -> Result<DrmFormatModifierProperties, VulkanError> {
+        let fns = device.fns();
+        let mut properties_vk = ash::vk::ImageDrmFormatModifierPropertiesEXT::default();
+        (fns.ext_image_drm_format_modifier
+            .get_image_drm_format_modifier_properties_ext)(
+            device.handle(),
+            handle,
+            &mut properties_vk,
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        // The implementation only chooses from modifiers that were validated to be supported, so
+        // this is only reached if the implementation misbehaves.
+        device
+            .physical_device()
+            .format_drm_modifier_properties_unchecked(format)
+            .into_iter()
+            .find(|properties| properties.drm_format_modifier == properties_vk.drm_format_modifier)
+            .ok_or(VulkanError::FormatNotSupported)
     }
 
     /// Creates a new `RawImage` from a raw object handle.
@@ -971,19 +1405,35 @@ impl RawImage {
     /// - `handle` must be a valid Vulkan object handle created from `device`.
     /// - `handle` must refer to an image that has not yet had memory bound to it.
     /// - `create_info` must match the info used to create the object.
+    /// - If `create_info.tiling` is [`ImageTiling::DrmFormatModifier`], then
+    ///   `create_info.drm_format_modifiers` must contain exactly one element, which is the
+    ///   modifier that the image was created with.
     #[inline]
     pub unsafe fn from_handle(
         device: Arc<Device>,
         handle: ash::vk::Image,
         create_info: ImageCreateInfo,
     ) -> Self {
-        Self::from_handle_with_destruction(device, handle, create_info, true)
+        let drm_format_modifier = (create_info.tiling == ImageTiling::DrmFormatModifier)
+            .then(|| {
+                let &drm_format_modifier = create_info.drm_format_modifiers.first()?;
+
+                device
+                    .physical_device()
+                    .format_drm_modifier_properties_unchecked(create_info.format.unwrap())
+                    .into_iter()
+                    .find(|properties| properties.drm_format_modifier == drm_format_modifier)
+            })
+            .flatten();
+
+        Self::from_handle_with_destruction(device, handle, create_info, drm_format_modifier, true)
     }
 
     unsafe fn from_handle_with_destruction(
         device: Arc<Device>,
         handle: ash::vk::Image,
         create_info: ImageCreateInfo,
+        drm_format_modifier: Option<DrmFormatModifierProperties>,
         needs_destruction: bool,
     ) -> Self {
         let ImageCreateInfo {
@@ -998,6 +1448,9 @@ impl RawImage {
             sharing,
             initial_layout,
             external_memory_handle_types,
+            drm_format_modifiers: _,
+            drm_format_modifier_plane_layouts: _,
+            video_profiles: _,
             _ne: _,
         } = create_info;
 
@@ -1019,6 +1472,10 @@ impl RawImage {
             match tiling {
                 ImageTiling::Linear => format_properties.linear_tiling_features,
                 ImageTiling::Optimal => format_properties.optimal_tiling_features,
+                ImageTiling::DrmFormatModifier => drm_format_modifier
+                    .map_or_else(FormatFeatures::empty, |drm_format_modifier| {
+                        drm_format_modifier.drm_format_modifier_tiling_features
+                    }),
             }
         };
 
@@ -1046,6 +1503,7 @@ impl RawImage {
             stencil_usage,
             sharing,
             external_memory_handle_types,
+            drm_format_modifier,
             memory_requirements,
             needs_destruction,
             subresource_layout: OnceCache::new(),
@@ -1142,9 +1600,13 @@ impl RawImage {
         }
     }
 
+    /// Queries the memory requirements for binding memory to the image with sparse binding
+    /// operations.
+    ///
+    /// The returned list is empty if the image was not created with
+    /// [`ImageCreateFlags::SPARSE_RESIDENCY`].
     #[inline]
-    #[allow(dead_code)] // Remove when sparse memory is implemented
-    fn get_sparse_memory_requirements(&self) -> Vec<SparseImageMemoryRequirements> {
+    pub fn sparse_memory_requirements(&self) -> Vec<SparseImageMemoryRequirements> {
         let device = &self.device;
 
         unsafe {
@@ -1398,8 +1860,9 @@ impl RawImage {
             // Ensured by taking ownership of `RawImage`.
 
             // VUID-VkBindImageMemoryInfo-image-01045
-            // Currently ensured by not having sparse binding flags, but this needs to be checked
-            // once those are enabled.
+            if self.flags.intersects(ImageCreateFlags::SPARSE_BINDING) {
+                return Err(ImageError::BindMemorySparseBinding);
+            }
 
             // VUID-VkBindImageMemoryInfo-memoryOffset-01046
             // Assume that `allocation` was created correctly.
@@ -1419,14 +1882,17 @@ impl RawImage {
             }
 
             // VUID-VkBindImageMemoryInfo-None-01901
-            if memory_type
+            // VUID-VkBindImageMemoryInfo-None-01902
+            let image_protected = self.flags.intersects(ImageCreateFlags::PROTECTED);
+            let memory_protected = memory_type
                 .property_flags
-                .intersects(MemoryPropertyFlags::PROTECTED)
-            {
+                .intersects(MemoryPropertyFlags::PROTECTED);
+
+            if image_protected != memory_protected {
                 return Err(ImageError::MemoryProtectedMismatch {
                     allocations_index,
-                    image_protected: false,
-                    memory_protected: true,
+                    image_protected,
+                    memory_protected,
                 });
             }
 
@@ -1596,6 +2062,34 @@ impl RawImage {
         Ok(Image::from_raw(self, ImageMemory::Normal(allocations)))
     }
 
+    /// Converts a raw image that was created with [`ImageCreateFlags::SPARSE_BINDING`] into a
+    /// full image, without binding any memory.
+    ///
+    /// Memory must then be bound to the image with [`bind_sparse`] before it can be used.
+    ///
+    /// # Safety
+    ///
+    /// - If `self.flags()` does not contain [`ImageCreateFlags::SPARSE_RESIDENCY`], then the
+    ///   image must be fully bound with memory before its memory is accessed by the device.
+    /// - If `self.flags()` contains [`ImageCreateFlags::SPARSE_RESIDENCY`], then you must ensure
+    ///   that any reads from the image are prepared to handle unexpected or inconsistent values,
+    ///   as determined by the [`residency_non_resident_strict`] device property.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `self.flags()` does not contain [`ImageCreateFlags::SPARSE_BINDING`].
+    ///
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
+    /// [`residency_non_resident_strict`]: crate::device::Properties::residency_non_resident_strict
+    #[inline]
+    pub unsafe fn assume_bound(self) -> Image {
+        assert!(self.flags.intersects(ImageCreateFlags::SPARSE_BINDING));
+
+        let sparse_memory_requirements = self.sparse_memory_requirements();
+
+        Image::from_raw(self, ImageMemory::Sparse(sparse_memory_requirements))
+    }
+
     /// Returns the memory requirements for this image.
     ///
     /// - If `self.flags().disjoint` is not set, this returns a slice with a length of 1.
@@ -1678,6 +2172,18 @@ impl RawImage {
         self.external_memory_handle_types
     }
 
+    /// If `tiling` is [`ImageTiling::DrmFormatModifier`], returns the Linux DRM format modifier
+    /// of the image, and its properties.
+    ///
+    /// If the image was created with a list of modifiers, this is the modifier that was chosen
+    /// by the implementation. It can be shared with other APIs or processes together with the
+    /// layout of each memory plane, which can be queried with
+    /// [`subresource_layout`](Self::subresource_layout).
+    #[inline]
+    pub fn drm_format_modifier(&self) -> Option<DrmFormatModifierProperties> {
+        self.drm_format_modifier
+    }
+
     /// Returns an `ImageSubresourceLayers` covering the first mip level of the image. All aspects
     /// of the image are selected, or `plane0` if the image is multi-planar.
     #[inline]
@@ -1717,6 +2223,10 @@ impl RawImage {
     /// Multi-planar formats are supported, but you must specify one of the planes as the `aspect`,
     /// not [`ImageAspect::Color`].
     ///
+    /// Images with [`ImageTiling::DrmFormatModifier`] tiling are also supported, in which case
+    /// `aspect` must be one of the memory planes of the image's
+    /// [DRM format modifier](Self::drm_format_modifier).
+    ///
     /// The results of this function are cached, so that future calls with the same arguments
     /// do not need to make a call to the Vulkan API again.
     pub fn subresource_layout(
@@ -1744,7 +2254,10 @@ impl RawImage {
         // Ensured by use of enum `ImageAspect`.
 
         // VUID-vkGetImageSubresourceLayout-image-02270
-        if !matches!(self.tiling, ImageTiling::Linear) {
+        if !matches!(
+            self.tiling,
+            ImageTiling::Linear | ImageTiling::DrmFormatModifier
+        ) {
             return Err(ImageError::OptimalTilingNotSupported);
         }
 
@@ -1764,6 +2277,27 @@ impl RawImage {
             });
         }
 
+        // VUID-vkGetImageSubresourceLayout-tiling-02271
+        if let Some(drm_format_modifier) = self.drm_format_modifier {
+            let allowed_aspects = [
+                ImageAspects::MEMORY_PLANE_0,
+                ImageAspects::MEMORY_PLANE_1,
+                ImageAspects::MEMORY_PLANE_2,
+            ]
+            .into_iter()
+            .take(drm_format_modifier.drm_format_modifier_plane_count as usize)
+            .fold(ImageAspects::empty(), |total, aspect| total | aspect);
+
+            if !allowed_aspects.contains(aspect.into()) {
+                return Err(ImageError::AspectNotAllowed {
+                    provided_aspect: aspect,
+                    allowed_aspects,
+                });
+            }
+
+            return Ok(());
+        }
+
         let mut allowed_aspects = self.format.unwrap().aspects();
 
         // Follows from the combination of these three VUIDs. See:
@@ -1951,6 +2485,51 @@ pub struct ImageCreateInfo {
     /// The default value is [`ExternalMemoryHandleTypes::empty()`].
     pub external_memory_handle_types: ExternalMemoryHandleTypes,
 
+    /// The Linux DRM format modifiers that the image can be created with.
+    ///
+    /// If `tiling` is [`ImageTiling::DrmFormatModifier`], then this must not be empty, and each
+    /// modifier must be supported for `format`, as returned by
+    /// [`PhysicalDevice::format_drm_modifier_properties`]. If
+    /// `drm_format_modifier_plane_layouts` is empty, then the implementation chooses one of the
+    /// modifiers in the list, which can be queried afterwards with
+    /// [`RawImage::drm_format_modifier`]. Otherwise, this must contain exactly one modifier,
+    /// which is the one the image is created with.
+    ///
+    /// If `tiling` is not `DrmFormatModifier`, then this must be empty.
+    ///
+    /// The default value is empty.
+    ///
+    /// [`PhysicalDevice::format_drm_modifier_properties`]: crate::device::physical::PhysicalDevice::format_drm_modifier_properties
+    pub drm_format_modifiers: Vec<u64>,
+
+    /// The layout of each memory plane of the image, when creating an image with an explicit DRM
+    /// format modifier.
+    ///
+    /// This is used when importing an image whose memory layout was chosen by another API or
+    /// process, for example a dma-buf file descriptor along with its modifier, offsets and
+    /// pitches. If this is not empty, `tiling` must be [`ImageTiling::DrmFormatModifier`],
+    /// `drm_format_modifiers` must contain exactly one element, and the number of elements must
+    /// equal the number of memory planes of the modifier. The `size` of each element must be 0,
+    /// and `array_pitch` and `depth_pitch` must be `None` if the image has only one array layer
+    /// or one depth slice, respectively.
+    ///
+    /// The default value is empty.
+    pub drm_format_modifier_plane_layouts: Vec<SubresourceLayout>,
+
+    /// The video profiles that the image will be used with.
+    ///
+    /// If `usage` contains [`ImageUsage::VIDEO_DECODE_DST`], [`ImageUsage::VIDEO_DECODE_DPB`],
+    /// [`ImageUsage::VIDEO_ENCODE_SRC`] or [`ImageUsage::VIDEO_ENCODE_DPB`], then this must not
+    /// be empty, and `format` must be supported for the video profiles, as
+    /// returned by [`PhysicalDevice::video_format_properties`]. If this is not empty, the
+    /// [`khr_video_queue`] extension must be enabled on the device.
+    ///
+    /// The default value is empty.
+    ///
+    /// [`PhysicalDevice::video_format_properties`]: crate::device::physical::PhysicalDevice::video_format_properties
+    /// [`khr_video_queue`]: crate::device::DeviceExtensions::khr_video_queue
+    pub video_profiles: Vec<VideoProfileInfo>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1973,6 +2552,9 @@ impl Default for ImageCreateInfo {
             sharing: Sharing::Exclusive,
             initial_layout: ImageLayout::Undefined,
             external_memory_handle_types: ExternalMemoryHandleTypes::empty(),
+            drm_format_modifiers: Vec::new(),
+            drm_format_modifier_plane_layouts: Vec::new(),
+            video_profiles: Vec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -2040,7 +2622,11 @@ impl Image {
         image_index: u32,
     ) -> Self {
         let create_info = ImageCreateInfo {
-            flags: ImageCreateFlags::empty(),
+            flags: if swapchain.protected() {
+                ImageCreateFlags::PROTECTED
+            } else {
+                ImageCreateFlags::empty()
+            },
             dimensions: ImageDimensions::Dim2d {
                 width: swapchain.image_extent()[0],
                 height: swapchain.image_extent()[1],
@@ -2062,6 +2648,7 @@ impl Image {
                 swapchain.device().clone(),
                 handle,
                 create_info,
+                None,
                 false,
             ),
             ImageMemory::Swapchain {
@@ -2159,6 +2746,18 @@ impl Image {
         self.inner.external_memory_handle_types
     }
 
+    /// If `tiling` is [`ImageTiling::DrmFormatModifier`], returns the Linux DRM format modifier
+    /// of the image, and its properties.
+    ///
+    /// If the image was created with a list of modifiers, this is the modifier that was chosen
+    /// by the implementation. It can be shared with other APIs or processes together with the
+    /// layout of each memory plane, which can be queried with
+    /// [`subresource_layout`](Self::subresource_layout).
+    #[inline]
+    pub fn drm_format_modifier(&self) -> Option<DrmFormatModifierProperties> {
+        self.inner.drm_format_modifier
+    }
+
     /// Returns an `ImageSubresourceLayers` covering the first mip level of the image. All aspects
     /// of the image are selected, or `plane0` if the image is multi-planar.
     #[inline]
@@ -2181,6 +2780,10 @@ impl Image {
     /// Multi-planar formats are supported, but you must specify one of the planes as the `aspect`,
     /// not [`ImageAspect::Color`].
     ///
+    /// Images with [`ImageTiling::DrmFormatModifier`] tiling are also supported, in which case
+    /// `aspect` must be one of the memory planes of the image's
+    /// [DRM format modifier](Self::drm_format_modifier).
+    ///
     /// The layout is invariant for each image. However it is not cached, as this would waste
     /// memory in the case of non-linear-tiling images. You are encouraged to store the layout
     /// somewhere in order to avoid calling this semi-expensive function at every single memory
@@ -2775,6 +3378,10 @@ pub enum ImageError {
         allowed_aspects: ImageAspects,
     },
 
+    /// The image was created with the [`ImageCreateFlags::SPARSE_BINDING`] flag, so memory can
+    /// only be bound to it with sparse binding operations.
+    BindMemorySparseBinding,
+
     /// The `block_texel_view_compatible` flag was enabled, but the given format was not compressed.
     BlockTexelViewCompatibleNotCompressed,
 
@@ -2804,6 +3411,36 @@ pub enum ImageError {
     /// not support disjoint images.
     DisjointFormatNotSupported,
 
+    /// A DRM format modifier was provided that is not supported for the format by the physical
+    /// device.
+    DrmFormatModifierNotSupported {
+        drm_format_modifier: u64,
+    },
+
+    /// An element of `drm_format_modifier_plane_layouts` has a nonzero `size`, or a pitch that
+    /// must be `None` for the dimensions of the image.
+    DrmFormatModifierPlaneLayoutInvalid {
+        plane_index: u32,
+    },
+
+    /// The number of elements in `drm_format_modifier_plane_layouts` does not equal the number of
+    /// memory planes of the DRM format modifier.
+    DrmFormatModifierPlaneLayoutsCountMismatch {
+        provided: u32,
+        required: u32,
+    },
+
+    /// `drm_format_modifier_plane_layouts` is not empty, but `drm_format_modifiers` does not
+    /// contain exactly one element.
+    DrmFormatModifierPlaneLayoutsMultipleModifiers,
+
+    /// The tiling is `DrmFormatModifier`, but `drm_format_modifiers` is empty.
+    DrmFormatModifiersMissing,
+
+    /// `drm_format_modifiers` or `drm_format_modifier_plane_layouts` is not empty, but the tiling
+    /// is not `DrmFormatModifier`.
+    DrmFormatModifiersNotAllowed,
+
     /// One or more external memory handle types were provided, but the initial layout was not
     /// `Undefined`.
     ExternalMemoryInvalidInitialLayout,
@@ -2920,6 +3557,10 @@ pub enum ImageError {
         supported: SampleCounts,
     },
 
+    /// The `sample_locations_compatible_depth` flag was enabled, but the given format was not a
+    /// depth/stencil format.
+    SampleLocationsCompatibleDepthNotDepthStencil,
+
     /// The sharing mode was set to `Concurrent`, but one of the specified queue family indices was
     /// out of range.
     SharingQueueFamilyIndexOutOfRange {
@@ -2927,6 +3568,19 @@ pub enum ImageError {
         queue_family_count: u32,
     },
 
+    /// The [`ImageCreateFlags::SPARSE_RESIDENCY`] or [`ImageCreateFlags::SPARSE_ALIASED`] flag
+    /// was set, but not [`ImageCreateFlags::SPARSE_BINDING`].
+    SparseFlagsWithoutSparseBinding,
+
+    /// The [`ImageCreateFlags::PROTECTED`] flag was set together with one of the sparse flags.
+    SparseFlagsWithProtected,
+
+    /// The [`ImageCreateFlags::SPARSE_RESIDENCY`] flag was set, but the image type was 1D.
+    SparseResidency1d,
+
+    /// The [`ImageCreateFlags::SPARSE_RESIDENCY`] flag was set, but the tiling was `Linear`.
+    SparseResidencyLinearTiling,
+
     /// The provided `usage` and `stencil_usage` have different values for
     /// `depth_stencil_attachment` or `transient_attachment`.
     StencilUsageMismatch {
@@ -2934,6 +3588,25 @@ pub enum ImageError {
         stencil_usage: ImageUsage,
     },
 
+    /// The `subsampled` flag was enabled together with the `cube_compatible` flag.
+    SubsampledCubeCompatible,
+
+    /// The `subsampled` flag was enabled, and multiple mip levels were specified.
+    SubsampledMultipleMipLevels,
+
+    /// The `subsampled` flag was enabled, but the image type was not 2D.
+    SubsampledNot2d,
+
+    /// The `subsampled` flag was enabled, but the tiling was not `Optimal`.
+    SubsampledNotOptimalTiling,
+
+    /// The format is not supported for the video usages of the image with the provided video
+    /// profiles.
+    VideoFormatNotSupported,
+
+    /// The usage contained a video decode usage, but no video profiles were provided.
+    VideoProfilesMissing,
+
     /// A YCbCr format was given, but the specified width and/or height was not a multiple of 2
     /// as required by the format's chroma subsampling.
     YcbcrFormatInvalidDimensions,
@@ -2998,6 +3671,11 @@ impl Display for ImageError {
                 "the provided aspect ({:?}) is not present in the image, or is not allowed ({:?})",
                 provided_aspect, allowed_aspects,
             ),
+            Self::BindMemorySparseBinding => write!(
+                f,
+                "the image was created with the `ImageCreateFlags::SPARSE_BINDING` flag, so \
+                memory can only be bound to it with sparse binding operations",
+            ),
             Self::BlockTexelViewCompatibleNotCompressed => write!(
                 f,
                 "the `block_texel_view_compatible` flag was enabled, but the given format was not \
@@ -3038,6 +3716,40 @@ impl Display for ImageError {
                 "the `disjoint` flag was enabled, but the given format is either not multi-planar, \
                 or does not support disjoint images",
             ),
+            Self::DrmFormatModifierNotSupported {
+                drm_format_modifier,
+            } => write!(
+                f,
+                "the DRM format modifier {:#x} is not supported for the format by the physical \
+                device",
+                drm_format_modifier,
+            ),
+            Self::DrmFormatModifierPlaneLayoutInvalid { plane_index } => write!(
+                f,
+                "element {} of `drm_format_modifier_plane_layouts` has a nonzero `size`, or a \
+                pitch that must be `None` for the dimensions of the image",
+                plane_index,
+            ),
+            Self::DrmFormatModifierPlaneLayoutsCountMismatch { provided, required } => write!(
+                f,
+                "the number of elements in `drm_format_modifier_plane_layouts` ({}) does not \
+                equal the number of memory planes of the DRM format modifier ({})",
+                provided, required,
+            ),
+            Self::DrmFormatModifierPlaneLayoutsMultipleModifiers => write!(
+                f,
+                "`drm_format_modifier_plane_layouts` is not empty, but `drm_format_modifiers` does \
+                not contain exactly one element",
+            ),
+            Self::DrmFormatModifiersMissing => write!(
+                f,
+                "the tiling is `DrmFormatModifier`, but `drm_format_modifiers` is empty",
+            ),
+            Self::DrmFormatModifiersNotAllowed => write!(
+                f,
+                "`drm_format_modifiers` or `drm_format_modifier_plane_layouts` is not empty, but \
+                the tiling is not `DrmFormatModifier`",
+            ),
             Self::ExternalMemoryInvalidInitialLayout => write!(
                 f,
                 "one or more external memory handle types were provided, but the initial layout \
@@ -3178,11 +3890,35 @@ impl Display for ImageError {
                 f,
                 "the sample count is not supported by the device for this image configuration",
             ),
+            Self::SampleLocationsCompatibleDepthNotDepthStencil => write!(
+                f,
+                "the `sample_locations_compatible_depth` flag was enabled, but the given format \
+                was not a depth/stencil format",
+            ),
             Self::SharingQueueFamilyIndexOutOfRange { .. } => write!(
                 f,
                 "the sharing mode was set to `Concurrent`, but one of the specified queue family \
                 indices was out of range",
             ),
+            Self::SparseFlagsWithoutSparseBinding => write!(
+                f,
+                "the `ImageCreateFlags::SPARSE_RESIDENCY` or `ImageCreateFlags::SPARSE_ALIASED` \
+                flag was set, but not `ImageCreateFlags::SPARSE_BINDING`",
+            ),
+            Self::SparseFlagsWithProtected => write!(
+                f,
+                "the `ImageCreateFlags::PROTECTED` flag was set together with one of the sparse \
+                flags",
+            ),
+            Self::SparseResidency1d => write!(
+                f,
+                "the `ImageCreateFlags::SPARSE_RESIDENCY` flag was set, but the image type was 1D",
+            ),
+            Self::SparseResidencyLinearTiling => write!(
+                f,
+                "the `ImageCreateFlags::SPARSE_RESIDENCY` flag was set, but the tiling was \
+                `Linear`",
+            ),
             Self::StencilUsageMismatch {
                 usage: _,
                 stencil_usage: _,
@@ -3191,6 +3927,31 @@ impl Display for ImageError {
                 "the provided `usage` and `stencil_usage` have different values for \
                 `depth_stencil_attachment` or `transient_attachment`",
             ),
+            Self::SubsampledCubeCompatible => write!(
+                f,
+                "the `subsampled` flag was enabled together with the `cube_compatible` flag",
+            ),
+            Self::SubsampledMultipleMipLevels => write!(
+                f,
+                "the `subsampled` flag was enabled, and multiple mip levels were specified",
+            ),
+            Self::SubsampledNot2d => write!(
+                f,
+                "the `subsampled` flag was enabled, but the image type was not 2D",
+            ),
+            Self::SubsampledNotOptimalTiling => write!(
+                f,
+                "the `subsampled` flag was enabled, but the tiling was not `Optimal`",
+            ),
+            Self::VideoFormatNotSupported => write!(
+                f,
+                "the format is not supported for the video usages of the image with the \
+                provided video profiles",
+            ),
+            Self::VideoProfilesMissing => write!(
+                f,
+                "the usage contained a video decode usage, but no video profiles were provided",
+            ),
             Self::YcbcrFormatInvalidDimensions => write!(
                 f,
                 "a YCbCr format was given, but the specified width and/or height was not a \
@@ -3239,7 +4000,14 @@ mod tests {
         format::Format,
         image::{
             sys::SubresourceRangeIterator, ImageAspect, ImageAspects, ImageCreateFlags,
-            ImageDimensions, ImageSubresourceRange, SampleCount,
+            ImageDimensions, ImageSubresourceRange, ImageTiling, SampleCount,
+        },
+        memory::allocator::{
+            AllocationCreateInfo, AllocationType, MemoryAllocator, MemoryUsage,
+            StandardMemoryAllocator,
+        },
+        video::{
+            H264ProfileIdc, VideoCodecProfileInfo, VideoDecodeH264PictureLayout, VideoProfileInfo,
         },
         DeviceSize, RequiresOneOf,
     };
@@ -3361,6 +4129,82 @@ mod tests {
         };
     }
 
+    #[test]
+    fn video_format_not_supported() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+
+        let video_profile = VideoProfileInfo::new(VideoCodecProfileInfo::DecodeH264 {
+            std_profile_idc: H264ProfileIdc::Main,
+            picture_layout: VideoDecodeH264PictureLayout::Progressive,
+        });
+
+        if device
+            .physical_device()
+            .video_capabilities(&video_profile)
+            .is_err()
+        {
+            return;
+        }
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 64,
+                    height: 64,
+                    array_layers: 1,
+                },
+                format: Some(Format::R32G32B32A32_SFLOAT),
+                usage: ImageUsage::VIDEO_DECODE_DST,
+                video_profiles: vec![video_profile],
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::VideoFormatNotSupported) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn video_usage_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 64,
+                    height: 64,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::VIDEO_DECODE_DST,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"khr_video_decode_queue") => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     fn compressed_not_color_attachment() {
         let (device, _) = gfx_dev_and_queue!();
@@ -3435,6 +4279,169 @@ mod tests {
         };
     }
 
+    #[test]
+    fn drm_format_modifiers_tiling_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::SAMPLED,
+                drm_format_modifiers: vec![0],
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::DrmFormatModifiersNotAllowed) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn sparse_binding_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::SPARSE_BINDING,
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"sparse_binding") => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn sparse_residency_1d() {
+        let (device, _) = gfx_dev_and_queue!(sparse_binding);
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::SPARSE_BINDING | ImageCreateFlags::SPARSE_RESIDENCY,
+                dimensions: ImageDimensions::Dim1d {
+                    width: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::SparseResidency1d) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn sparse_residency_linear_tiling() {
+        let (device, _) = gfx_dev_and_queue!(sparse_binding, sparse_residency_image2_d);
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::SPARSE_BINDING | ImageCreateFlags::SPARSE_RESIDENCY,
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                tiling: ImageTiling::Linear,
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::SparseResidencyLinearTiling) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn sparse_residency_without_sparse_binding() {
+        let (device, _) = gfx_dev_and_queue!(sparse_residency_image2_d);
+
+        let res = RawImage::new(
+            device,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::SPARSE_RESIDENCY,
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        );
+
+        match res {
+            Err(ImageError::SparseFlagsWithoutSparseBinding) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn bind_memory_sparse_binding() {
+        let (device, _) = gfx_dev_and_queue!(sparse_binding);
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+
+        let raw_image = RawImage::new(
+            device,
+            ImageCreateInfo {
+                flags: ImageCreateFlags::SPARSE_BINDING,
+                dimensions: ImageDimensions::Dim2d {
+                    width: 32,
+                    height: 32,
+                    array_layers: 1,
+                },
+                format: Some(Format::R8G8B8A8_UNORM),
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let allocation = memory_allocator
+            .allocate(AllocationCreateInfo {
+                requirements: raw_image.memory_requirements()[0],
+                allocation_type: AllocationType::NonLinear,
+                usage: MemoryUsage::GpuOnly,
+                ..Default::default()
+            })
+            .unwrap();
+
+        match raw_image.bind_memory([allocation]) {
+            Err((ImageError::BindMemorySparseBinding, _, _)) => (),
+            _ => panic!(),
+        };
+    }
+
     #[test]
     #[allow(clippy::erasing_op, clippy::identity_op)]
     fn subresource_range_iterator() {
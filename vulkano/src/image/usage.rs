@@ -49,53 +49,47 @@ vulkan_bitflags! {
     /// The image can be used as an input attachment in a render pass/framebuffer.
     INPUT_ATTACHMENT = INPUT_ATTACHMENT,
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as the output picture of a video decode operation.
     VIDEO_DECODE_DST = VIDEO_DECODE_DST_KHR {
         device_extensions: [khr_video_decode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// Reserved for future use by video decode operations.
     VIDEO_DECODE_SRC = VIDEO_DECODE_SRC_KHR {
         device_extensions: [khr_video_decode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as a reference picture in the decoded picture buffer of a video
+    /// decode operation.
     VIDEO_DECODE_DPB = VIDEO_DECODE_DPB_KHR {
         device_extensions: [khr_video_decode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as a fragment density map attachment in a render pass/framebuffer.
     FRAGMENT_DENSITY_MAP = FRAGMENT_DENSITY_MAP_EXT {
         device_extensions: [ext_fragment_density_map],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as a fragment shading rate attachment in a render pass/framebuffer.
     FRAGMENT_SHADING_RATE_ATTACHMENT = FRAGMENT_SHADING_RATE_ATTACHMENT_KHR {
         device_extensions: [khr_fragment_shading_rate],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// Reserved for future use by video encode operations.
     VIDEO_ENCODE_DST = VIDEO_ENCODE_DST_KHR {
         device_extensions: [khr_video_encode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as the input picture of a video encode operation.
     VIDEO_ENCODE_SRC = VIDEO_ENCODE_SRC_KHR {
         device_extensions: [khr_video_encode_queue],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used as a reference picture in the decoded picture buffer of a video
+    /// encode operation.
     VIDEO_ENCODE_DPB = VIDEO_ENCODE_DPB_KHR {
         device_extensions: [khr_video_encode_queue],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
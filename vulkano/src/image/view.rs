@@ -726,6 +726,21 @@ where
             match image.tiling() {
                 ImageTiling::Optimal => format_properties.optimal_tiling_features,
                 ImageTiling::Linear => format_properties.linear_tiling_features,
+                ImageTiling::DrmFormatModifier => image
+                    .drm_format_modifier()
+                    .and_then(|image_drm_format_modifier| {
+                        device
+                            .physical_device()
+                            .format_drm_modifier_properties_unchecked(format)
+                            .into_iter()
+                            .find(|properties| {
+                                properties.drm_format_modifier
+                                    == image_drm_format_modifier.drm_format_modifier
+                            })
+                    })
+                    .map_or_else(FormatFeatures::empty, |properties| {
+                        properties.drm_format_modifier_tiling_features
+                    }),
             }
         } else {
             image.format_features()
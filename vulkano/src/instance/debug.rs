@@ -46,13 +46,16 @@ use crate::{
     macros::{vulkan_bitflags, vulkan_enum},
     RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
 };
+use ahash::HashMap;
+use parking_lot::Mutex;
 use std::{
+    borrow::Cow,
     error::Error,
     ffi::{c_void, CStr},
     fmt::{Debug, Display, Error as FmtError, Formatter},
     mem::MaybeUninit,
     panic::{catch_unwind, AssertUnwindSafe, RefUnwindSafe},
-    ptr,
+    ptr, slice,
     sync::Arc,
 };
 
@@ -92,6 +95,28 @@ impl DebugUtilsMessenger {
         })
     }
 
+    /// Initializes a debug callback that forwards all messages to the
+    /// [`log`](https://crates.io/crates/log) crate.
+    ///
+    /// See [`DebugUtilsMessengerCreateInfo::log`] for details.
+    #[cfg(feature = "log")]
+    #[inline]
+    pub fn to_log(instance: Arc<Instance>) -> Result<Self, DebugUtilsMessengerCreationError> {
+        // Safe because the callback doesn't make any calls to the Vulkan API.
+        unsafe { Self::new(instance, DebugUtilsMessengerCreateInfo::log()) }
+    }
+
+    /// Initializes a debug callback that forwards all messages to the
+    /// [`tracing`](https://crates.io/crates/tracing) crate.
+    ///
+    /// See [`DebugUtilsMessengerCreateInfo::tracing`] for details.
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn to_tracing(instance: Arc<Instance>) -> Result<Self, DebugUtilsMessengerCreationError> {
+        // Safe because the callback doesn't make any calls to the Vulkan API.
+        unsafe { Self::new(instance, DebugUtilsMessengerCreateInfo::tracing()) }
+    }
+
     fn validate_create(
         instance: &Instance,
         create_info: &mut DebugUtilsMessengerCreateInfo,
@@ -99,6 +124,8 @@ impl DebugUtilsMessenger {
         let &mut DebugUtilsMessengerCreateInfo {
             message_type,
             message_severity,
+            ignored_message_ids: _,
+            deduplicate_messages: _,
             user_callback: _,
             _ne: _,
         } = create_info;
@@ -141,10 +168,15 @@ impl DebugUtilsMessenger {
         let DebugUtilsMessengerCreateInfo {
             message_severity,
             message_type,
+            ignored_message_ids,
+            deduplicate_messages,
             user_callback,
             _ne: _,
         } = create_info;
 
+        let user_callback =
+            filtered_user_callback(user_callback, ignored_message_ids, deduplicate_messages);
+
         // Note that we need to double-box the callback, because a `*const Fn()` is a fat pointer
         // that can't be cast to a `*const c_void`.
         let user_callback = Box::new(user_callback);
@@ -235,7 +267,21 @@ pub(super) unsafe extern "system" fn trampoline(
             severity: message_severity.into(),
             ty: message_types.into(),
             layer_prefix,
+            id_number: (*callback_data).message_id_number,
             description,
+            repeat_count: 1,
+            queue_labels: MessageLabelIter(raw_slice(
+                (*callback_data).p_queue_labels,
+                (*callback_data).queue_label_count,
+            )),
+            cmd_buf_labels: MessageLabelIter(raw_slice(
+                (*callback_data).p_cmd_buf_labels,
+                (*callback_data).cmd_buf_label_count,
+            )),
+            objects: MessageObjectIter(raw_slice(
+                (*callback_data).p_objects,
+                (*callback_data).object_count,
+            )),
         };
 
         user_callback(&message);
@@ -244,6 +290,78 @@ pub(super) unsafe extern "system" fn trampoline(
     ash::vk::FALSE
 }
 
+// The maximum number of distinct messages that are remembered for deduplication.
+const MAX_DEDUPLICATED_MESSAGES: usize = 1024;
+
+/// Wraps `user_callback` so that messages matching `ignored_message_ids` are dropped, and
+/// repeated messages are deduplicated if `deduplicate_messages` is set.
+pub(super) fn filtered_user_callback(
+    user_callback: UserCallback,
+    ignored_message_ids: Vec<DebugUtilsMessageId>,
+    deduplicate_messages: bool,
+) -> UserCallback {
+    if ignored_message_ids.is_empty() && !deduplicate_messages {
+        return user_callback;
+    }
+
+    // For each message, the number of times it was received and when it was last received.
+    // Nothing can panic while the lock is held, so the map can't be observed in a broken state.
+    let repeat_counts: AssertUnwindSafe<Mutex<(HashMap<(i32, String), (u32, u64)>, u64)>> =
+        AssertUnwindSafe(Mutex::default());
+
+    Arc::new(move |message: &Message<'_>| {
+        if ignored_message_ids
+            .iter()
+            .any(|message_id| message_id.matches(message))
+        {
+            return;
+        }
+
+        if !deduplicate_messages {
+            user_callback(message);
+            return;
+        }
+
+        let repeat_count = {
+            let (repeat_counts, time) = &mut *repeat_counts.lock();
+            *time += 1;
+            let key = (message.id_number, message.description.to_owned());
+
+            if repeat_counts.len() >= MAX_DEDUPLICATED_MESSAGES && !repeat_counts.contains_key(&key)
+            {
+                // Forget the least recently received message.
+                let oldest = repeat_counts
+                    .iter()
+                    .min_by_key(|(_, &(_, last_time))| last_time)
+                    .map(|(key, _)| key.clone())
+                    .unwrap();
+                repeat_counts.remove(&oldest);
+            }
+
+            let (repeat_count, last_time) = repeat_counts.entry(key).or_insert((0, 0));
+            *repeat_count = repeat_count.saturating_add(1);
+            *last_time = *time;
+            *repeat_count
+        };
+
+        // Only forward the first occurrence, and then every time the count doubles.
+        if repeat_count.is_power_of_two() {
+            user_callback(&Message {
+                repeat_count,
+                ..message.clone()
+            });
+        }
+    })
+}
+
+unsafe fn raw_slice<'a, T>(ptr: *const T, len: u32) -> &'a [T] {
+    if ptr.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(ptr, len as usize)
+    }
+}
+
 /// Error that can happen when creating a `DebugUtilsMessenger`.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DebugUtilsMessengerCreationError {
@@ -302,6 +420,28 @@ pub struct DebugUtilsMessengerCreateInfo {
     /// The default value is `MessageType::general()`.
     pub message_type: DebugUtilsMessageType,
 
+    /// Message IDs that the callback should not be called for.
+    ///
+    /// This can be used to silence known messages, for example those emitted by third-party
+    /// layers.
+    ///
+    /// The default value is empty.
+    pub ignored_message_ids: Vec<DebugUtilsMessageId>,
+
+    /// Whether repeated messages should be deduplicated.
+    ///
+    /// If set to `true`, messages with the same ID number and description are only passed to the
+    /// callback the first time they are received, and then again each time the number of times
+    /// they were received doubles. The number of times is provided in
+    /// [`Message::repeat_count`].
+    ///
+    /// At most 1024 distinct messages are remembered. When more are received, the message that
+    /// was received least recently is forgotten, and its count starts over if it is received
+    /// again.
+    ///
+    /// The default value is `false`.
+    pub deduplicate_messages: bool,
+
     /// The closure that should be called.
     ///
     /// The closure must not make any calls to the Vulkan API.
@@ -321,10 +461,98 @@ impl DebugUtilsMessengerCreateInfo {
         Self {
             message_severity: DebugUtilsMessageSeverity::ERROR | DebugUtilsMessageSeverity::WARNING,
             message_type: DebugUtilsMessageType::GENERAL,
+            ignored_message_ids: Vec::new(),
+            deduplicate_messages: false,
             user_callback,
             _ne: crate::NonExhaustive(()),
         }
     }
+
+    /// Returns a `DebugUtilsMessengerCreateInfo` that forwards all messages to the
+    /// [`log`](https://crates.io/crates/log) crate.
+    ///
+    /// Messages are logged with the `vulkano::instance::debug` target, at a level that
+    /// corresponds to their severity. The message type, layer prefix and ID number are included
+    /// in the logged text.
+    #[cfg(feature = "log")]
+    #[inline]
+    pub fn log() -> Self {
+        Self {
+            message_severity: DebugUtilsMessageSeverity::ERROR
+                | DebugUtilsMessageSeverity::WARNING
+                | DebugUtilsMessageSeverity::INFO
+                | DebugUtilsMessageSeverity::VERBOSE,
+            message_type: DebugUtilsMessageType::GENERAL
+                | DebugUtilsMessageType::VALIDATION
+                | DebugUtilsMessageType::PERFORMANCE,
+            ..Self::user_callback(Arc::new(|msg: &Message<'_>| {
+                let level = if msg.severity.intersects(DebugUtilsMessageSeverity::ERROR) {
+                    log::Level::Error
+                } else if msg.severity.intersects(DebugUtilsMessageSeverity::WARNING) {
+                    log::Level::Warn
+                } else if msg.severity.intersects(DebugUtilsMessageSeverity::INFO) {
+                    log::Level::Info
+                } else {
+                    log::Level::Trace
+                };
+
+                log::log!(
+                    target: "vulkano::instance::debug",
+                    level,
+                    "[{:?}] [{}] [{:#x}] {}",
+                    msg.ty,
+                    msg.layer_prefix.unwrap_or("unknown"),
+                    msg.id_number,
+                    msg.description,
+                );
+            }))
+        }
+    }
+
+    /// Returns a `DebugUtilsMessengerCreateInfo` that forwards all messages to the
+    /// [`tracing`](https://crates.io/crates/tracing) crate.
+    ///
+    /// Messages are emitted as events with the `vulkano::instance::debug` target, at a level that
+    /// corresponds to their severity. The message type, layer prefix and ID number are recorded
+    /// as the `ty`, `layer_prefix` and `id_number` fields.
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn tracing() -> Self {
+        Self {
+            message_severity: DebugUtilsMessageSeverity::ERROR
+                | DebugUtilsMessageSeverity::WARNING
+                | DebugUtilsMessageSeverity::INFO
+                | DebugUtilsMessageSeverity::VERBOSE,
+            message_type: DebugUtilsMessageType::GENERAL
+                | DebugUtilsMessageType::VALIDATION
+                | DebugUtilsMessageType::PERFORMANCE,
+            ..Self::user_callback(Arc::new(|msg: &Message<'_>| {
+                macro_rules! event {
+                    ($level:expr) => {
+                        tracing::event!(
+                            target: "vulkano::instance::debug",
+                            $level,
+                            ty = ?msg.ty,
+                            layer_prefix = msg.layer_prefix,
+                            id_number = msg.id_number,
+                            "{}",
+                            msg.description,
+                        )
+                    };
+                }
+
+                if msg.severity.intersects(DebugUtilsMessageSeverity::ERROR) {
+                    event!(tracing::Level::ERROR);
+                } else if msg.severity.intersects(DebugUtilsMessageSeverity::WARNING) {
+                    event!(tracing::Level::WARN);
+                } else if msg.severity.intersects(DebugUtilsMessageSeverity::INFO) {
+                    event!(tracing::Level::INFO);
+                } else {
+                    event!(tracing::Level::TRACE);
+                }
+            }))
+        }
+    }
 }
 
 impl Debug for DebugUtilsMessengerCreateInfo {
@@ -332,6 +560,8 @@ impl Debug for DebugUtilsMessengerCreateInfo {
         let Self {
             message_severity,
             message_type,
+            ignored_message_ids,
+            deduplicate_messages,
             user_callback: _,
             _ne: _,
         } = self;
@@ -339,20 +569,241 @@ impl Debug for DebugUtilsMessengerCreateInfo {
         f.debug_struct("DebugUtilsMessengerCreateInfo")
             .field("message_severity", message_severity)
             .field("message_type", message_type)
+            .field("ignored_message_ids", ignored_message_ids)
+            .field("deduplicate_messages", deduplicate_messages)
             .finish_non_exhaustive()
     }
 }
 
+/// Identifies a message, to filter it out with
+/// [`DebugUtilsMessengerCreateInfo::ignored_message_ids`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DebugUtilsMessageId {
+    /// Matches messages whose [`layer_prefix`](Message::layer_prefix) is equal to this name,
+    /// such as the VUID of a validation rule.
+    Name(String),
+
+    /// Matches messages whose [`id_number`](Message::id_number) is equal to this number.
+    Number(i32),
+}
+
+impl DebugUtilsMessageId {
+    /// Returns whether `message` is identified by `self`.
+    #[inline]
+    pub fn matches(&self, message: &Message<'_>) -> bool {
+        match self {
+            Self::Name(name) => message.layer_prefix == Some(name.as_str()),
+            Self::Number(number) => message.id_number == *number,
+        }
+    }
+}
+
+impl From<String> for DebugUtilsMessageId {
+    #[inline]
+    fn from(val: String) -> Self {
+        Self::Name(val)
+    }
+}
+
+impl From<&str> for DebugUtilsMessageId {
+    #[inline]
+    fn from(val: &str) -> Self {
+        Self::Name(val.to_owned())
+    }
+}
+
+impl From<i32> for DebugUtilsMessageId {
+    #[inline]
+    fn from(val: i32) -> Self {
+        Self::Number(val)
+    }
+}
+
 /// A message received by the callback.
+#[derive(Clone)]
 pub struct Message<'a> {
     /// Severity of message.
     pub severity: DebugUtilsMessageSeverity,
     /// Type of message,
     pub ty: DebugUtilsMessageType,
     /// Prefix of the layer that reported this message or `None` if unknown.
+    ///
+    /// For the validation layers, this is the name of the message ID, such as the VUID of the
+    /// violated rule.
     pub layer_prefix: Option<&'a str>,
+    /// The ID number of the message, or 0 if the message has no ID.
+    pub id_number: i32,
     /// Description of the message.
     pub description: &'a str,
+    /// The number of times this message has been received, including this time.
+    ///
+    /// This is always 1, unless
+    /// [`deduplicate_messages`](DebugUtilsMessengerCreateInfo::deduplicate_messages) is enabled.
+    pub repeat_count: u32,
+    /// The labels that were active in the queue when the message was triggered,
+    /// most recent first.
+    pub queue_labels: MessageLabelIter<'a>,
+    /// The labels that were active in the command buffer when the message was triggered,
+    /// most recent first.
+    pub cmd_buf_labels: MessageLabelIter<'a>,
+    /// The objects that are related to the message.
+    pub objects: MessageObjectIter<'a>,
+}
+
+impl<'a> Message<'a> {
+    /// If this message was generated by a `debugPrintfEXT` call in a shader, returns the text
+    /// that was printed by the shader. Otherwise returns `None`.
+    ///
+    /// To receive these messages, the
+    /// [`DebugPrintf`](ValidationFeatureEnable::DebugPrintf) validation feature must be enabled
+    /// on the instance, and the messenger must include `DebugUtilsMessageSeverity::INFO` and
+    /// `DebugUtilsMessageType::VALIDATION`.
+    pub fn debug_printf_payload(&self) -> Option<&'a str> {
+        if !matches!(
+            self.layer_prefix,
+            Some("UNASSIGNED-DEBUG-PRINTF" | "WARNING-DEBUG-PRINTF")
+        ) {
+            return None;
+        }
+
+        // Some versions of the validation layers prefix the description with the message ID
+        // and object info, separated by `|`.
+        let payload = self
+            .description
+            .split_once("MessageID = ")
+            .and_then(|(_, rest)| rest.split_once(" | "))
+            .map_or(self.description, |(_, payload)| payload);
+
+        Some(payload)
+    }
+}
+
+/// An iterator over the labels of a [`Message`].
+#[derive(Clone, Debug)]
+pub struct MessageLabelIter<'a>(&'a [ash::vk::DebugUtilsLabelEXT]);
+
+impl<'a> Iterator for MessageLabelIter<'a> {
+    type Item = MessageLabel<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, rest) = self.0.split_first()?;
+        self.0 = rest;
+
+        let label_name = unsafe { CStr::from_ptr(first.p_label_name) }.to_string_lossy();
+
+        Some(MessageLabel {
+            label_name,
+            color: first.color,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<'a> ExactSizeIterator for MessageLabelIter<'a> {}
+
+/// A label that was active when a [`Message`] was triggered.
+#[derive(Clone, Debug)]
+pub struct MessageLabel<'a> {
+    /// The name of the label. Invalid UTF-8 sequences are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
+    pub label_name: Cow<'a, str>,
+    /// The color of the label, or `[0.0; 4]` if no color was given.
+    pub color: [f32; 4],
+}
+
+/// An iterator over the objects of a [`Message`].
+#[derive(Clone, Debug)]
+pub struct MessageObjectIter<'a>(&'a [ash::vk::DebugUtilsObjectNameInfoEXT]);
+
+impl<'a> Iterator for MessageObjectIter<'a> {
+    type Item = MessageObject<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, rest) = self.0.split_first()?;
+        self.0 = rest;
+
+        let object_name = unsafe { first.p_object_name.as_ref() }
+            .map(|object_name| unsafe { CStr::from_ptr(object_name) }.to_string_lossy());
+
+        Some(MessageObject {
+            object_type: first.object_type.try_into().unwrap_or(ObjectType::Unknown),
+            object_handle: first.object_handle,
+            object_name,
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.len(), Some(self.0.len()))
+    }
+}
+
+impl<'a> ExactSizeIterator for MessageObjectIter<'a> {}
+
+/// An object that is related to a [`Message`].
+#[derive(Clone, Debug)]
+pub struct MessageObject<'a> {
+    /// The type of the object.
+    pub object_type: ObjectType,
+    /// The raw Vulkan handle of the object.
+    pub object_handle: u64,
+    /// The name that was given to the object with
+    /// [`set_debug_utils_object_name`](crate::device::Device::set_debug_utils_object_name),
+    /// if any. Invalid UTF-8 sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+    pub object_name: Option<Cow<'a, str>>,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type of a Vulkan object.
+    ObjectType = ObjectType(i32);
+
+    /// The type of the object is not known.
+    Unknown = UNKNOWN,
+
+    Instance = INSTANCE,
+    PhysicalDevice = PHYSICAL_DEVICE,
+    Device = DEVICE,
+    Queue = QUEUE,
+    Semaphore = SEMAPHORE,
+    CommandBuffer = COMMAND_BUFFER,
+    Fence = FENCE,
+    DeviceMemory = DEVICE_MEMORY,
+    Buffer = BUFFER,
+    Image = IMAGE,
+    Event = EVENT,
+    QueryPool = QUERY_POOL,
+    BufferView = BUFFER_VIEW,
+    ImageView = IMAGE_VIEW,
+    ShaderModule = SHADER_MODULE,
+    PipelineCache = PIPELINE_CACHE,
+    PipelineLayout = PIPELINE_LAYOUT,
+    RenderPass = RENDER_PASS,
+    Pipeline = PIPELINE,
+    DescriptorSetLayout = DESCRIPTOR_SET_LAYOUT,
+    Sampler = SAMPLER,
+    DescriptorPool = DESCRIPTOR_POOL,
+    DescriptorSet = DESCRIPTOR_SET,
+    Framebuffer = FRAMEBUFFER,
+    CommandPool = COMMAND_POOL,
+    SamplerYcbcrConversion = SAMPLER_YCBCR_CONVERSION,
+    DescriptorUpdateTemplate = DESCRIPTOR_UPDATE_TEMPLATE,
+    PrivateDataSlot = PRIVATE_DATA_SLOT,
+    Surface = SURFACE_KHR,
+    Swapchain = SWAPCHAIN_KHR,
+    Display = DISPLAY_KHR,
+    DisplayMode = DISPLAY_MODE_KHR,
+    DebugUtilsMessenger = DEBUG_UTILS_MESSENGER_EXT,
+    AccelerationStructure = ACCELERATION_STRUCTURE_KHR,
+    ValidationCache = VALIDATION_CACHE_EXT,
+    DeferredOperation = DEFERRED_OPERATION_KHR,
 }
 
 vulkan_bitflags! {
@@ -447,7 +898,8 @@ vulkan_enum! {
     BestPractices = BEST_PRACTICES,
 
     /// The validation layer will process `debugPrintfEXT` operations in shaders, and send them
-    /// to the debug callback.
+    /// to the debug callback. The printed text can be retrieved with
+    /// [`Message::debug_printf_payload`].
     ///
     /// This must not be used together with `GpuAssisted`.
     DebugPrintf = DEBUG_PRINTF,
@@ -497,7 +949,7 @@ mod tests {
         instance::{InstanceCreateInfo, InstanceExtensions},
         VulkanLibrary,
     };
-    use std::thread;
+    use std::{sync::atomic::AtomicU32, sync::atomic::Ordering, thread};
 
     #[test]
     fn ensure_sendable() {
@@ -541,4 +993,84 @@ mod tests {
             drop(callback);
         });
     }
+
+    #[test]
+    fn debug_printf_payload() {
+        let message = |layer_prefix, description| Message {
+            severity: DebugUtilsMessageSeverity::INFO,
+            ty: DebugUtilsMessageType::VALIDATION,
+            layer_prefix,
+            id_number: 0,
+            description,
+            repeat_count: 1,
+            queue_labels: MessageLabelIter(&[]),
+            cmd_buf_labels: MessageLabelIter(&[]),
+            objects: MessageObjectIter(&[]),
+        };
+
+        assert_eq!(
+            message(Some("UNASSIGNED-DEBUG-PRINTF"), "x = 1").debug_printf_payload(),
+            Some("x = 1"),
+        );
+        assert_eq!(
+            message(
+                Some("WARNING-DEBUG-PRINTF"),
+                "Validation Information: [ WARNING-DEBUG-PRINTF ] | MessageID = 0x76589099 | x = 1",
+            )
+            .debug_printf_payload(),
+            Some("x = 1"),
+        );
+        assert_eq!(
+            message(Some("VUID-vkCmdDraw-None-02699"), "x = 1").debug_printf_payload(),
+            None,
+        );
+    }
+
+    #[test]
+    fn filter_messages() {
+        let message = |layer_prefix, id_number, description| Message {
+            severity: DebugUtilsMessageSeverity::WARNING,
+            ty: DebugUtilsMessageType::VALIDATION,
+            layer_prefix,
+            id_number,
+            description,
+            repeat_count: 1,
+            queue_labels: MessageLabelIter(&[]),
+            cmd_buf_labels: MessageLabelIter(&[]),
+            objects: MessageObjectIter(&[]),
+        };
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let last_repeat_count = Arc::new(AtomicU32::new(0));
+        let user_callback = filtered_user_callback(
+            Arc::new({
+                let calls = calls.clone();
+                let last_repeat_count = last_repeat_count.clone();
+                move |msg: &Message<'_>| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    last_repeat_count.store(msg.repeat_count, Ordering::Relaxed);
+                }
+            }),
+            vec!["VUID-noisy".into(), 42.into()],
+            true,
+        );
+
+        user_callback(&message(Some("VUID-noisy"), 1, "ignored by name"));
+        user_callback(&message(None, 42, "ignored by number"));
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+        for _ in 0..5 {
+            user_callback(&message(Some("VUID-other"), 7, "repeated"));
+        }
+        // Forwarded at repeat counts 1, 2 and 4.
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(last_repeat_count.load(Ordering::Relaxed), 4);
+
+        // Receiving more distinct messages than are remembered forgets the oldest one.
+        for id_number in 0..MAX_DEDUPLICATED_MESSAGES as i32 {
+            user_callback(&message(None, 1000 + id_number, "distinct"));
+        }
+        user_callback(&message(Some("VUID-other"), 7, "repeated"));
+        assert_eq!(last_repeat_count.load(Ordering::Relaxed), 1);
+    }
 }
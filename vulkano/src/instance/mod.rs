@@ -54,6 +54,42 @@
 //! Once you have chosen a physical device, you can create a `Device` object from it. See the
 //! `device` module for more info.
 //!
+//! # Debug messages during instance creation and destruction
+//!
+//! A [`DebugUtilsMessenger`](crate::instance::debug::DebugUtilsMessenger) can only be created
+//! once the instance exists, and is destroyed before the instance is, so it will miss any messages
+//! that are emitted while the instance itself is being created or destroyed. To receive these
+//! messages as well, pass the messenger parameters to [`Instance::with_debug_utils_messengers`]
+//! instead of [`Instance::new`]:
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use vulkano::{
+//!     instance::{
+//!         debug::DebugUtilsMessengerCreateInfo, Instance, InstanceCreateInfo, InstanceExtensions,
+//!     },
+//!     VulkanLibrary,
+//! };
+//!
+//! let library = VulkanLibrary::new().unwrap();
+//! let instance = unsafe {
+//!     Instance::with_debug_utils_messengers(
+//!         library,
+//!         InstanceCreateInfo {
+//!             enabled_extensions: InstanceExtensions {
+//!                 ext_debug_utils: true,
+//!                 ..InstanceExtensions::empty()
+//!             },
+//!             ..Default::default()
+//!         },
+//!         [DebugUtilsMessengerCreateInfo::user_callback(Arc::new(|msg| {
+//!             println!("Debug callback: {:?}", msg.description);
+//!         }))],
+//!     )
+//! }
+//! .unwrap();
+//! ```
+//!
 //! # Portability subset devices and the `enumerate_portability` flag
 //!
 //! Certain devices, currently those on MacOS and iOS systems, do not fully conform to the Vulkan
@@ -84,8 +120,9 @@ use self::debug::{
 };
 pub use self::{extensions::InstanceExtensions, layers::LayerProperties};
 use crate::{
-    device::physical::PhysicalDevice, instance::debug::trampoline, OomError, RequiresOneOf,
-    VulkanError, VulkanLibrary, VulkanObject,
+    device::physical::{PhysicalDevice, PhysicalDeviceError},
+    instance::debug::{filtered_user_callback, trampoline},
+    OomError, RequiresOneOf, VulkanError, VulkanLibrary, VulkanObject,
 };
 pub use crate::{
     extensions::{ExtensionRestriction, ExtensionRestrictionError},
@@ -277,11 +314,18 @@ impl RefUnwindSafe for Instance {}
 impl Instance {
     /// Creates a new `Instance`.
     ///
+    /// To receive debug messages that are emitted while the instance is being created or
+    /// destroyed, use [`Instance::with_debug_utils_messengers`] instead.
+    ///
     /// # Panics
     ///
     /// - Panics if any version numbers in `create_info` contain a field too large to be converted
     ///   into a Vulkan version number.
     /// - Panics if `create_info.max_api_version` is not at least `V1_0`.
+    /// - Panics if `create_info.enabled_validation_features` contains
+    ///   `GpuAssistedReserveBindingSlot` but not `GpuAssisted`.
+    /// - Panics if `create_info.enabled_validation_features` contains both `DebugPrintf` and
+    ///   `GpuAssisted`.
     pub fn new(
         library: Arc<VulkanLibrary>,
         create_info: InstanceCreateInfo,
@@ -301,6 +345,7 @@ impl Instance {
     ///
     /// # Panics
     ///
+    /// - Panics if any of the conditions listed for [`Instance::new`] are not met.
     /// - Panics if the `message_severity` or `message_type` members of any element of
     ///   `debug_utils_messengers` are empty.
     ///
@@ -458,6 +503,8 @@ impl Instance {
             let DebugUtilsMessengerCreateInfo {
                 message_type,
                 message_severity,
+                ignored_message_ids,
+                deduplicate_messages,
                 user_callback,
                 _ne: _,
             } = create_info;
@@ -488,7 +535,11 @@ impl Instance {
             // VUID-PFN_vkDebugUtilsMessengerCallbackEXT-None-04769
             // Can't be checked, creation is unsafe.
 
-            let user_callback = Box::new(user_callback);
+            let user_callback = Box::new(filtered_user_callback(
+                user_callback,
+                ignored_message_ids,
+                deduplicate_messages,
+            ));
             let create_info = ash::vk::DebugUtilsMessengerCreateInfoEXT {
                 flags: ash::vk::DebugUtilsMessengerCreateFlagsEXT::empty(),
                 message_severity: message_severity.into(),
@@ -583,6 +634,16 @@ impl Instance {
         &self.enabled_layers
     }
 
+    /// Returns whether the application is running under RenderDoc, so that captures can be
+    /// triggered with [`RenderDoc`](crate::renderdoc::RenderDoc).
+    ///
+    /// This is only available with the `renderdoc` feature.
+    #[cfg(feature = "renderdoc")]
+    #[inline]
+    pub fn is_renderdoc_attached(&self) -> bool {
+        crate::renderdoc::RenderDoc::new().is_ok()
+    }
+
     /// Returns an iterator that enumerates the physical devices available.
     ///
     /// # Examples
@@ -636,6 +697,100 @@ impl Instance {
             Ok(physical_devices.into_iter())
         }
     }
+
+    /// Returns an iterator that enumerates the groups of physical devices that can be used
+    /// together to create a single logical device.
+    ///
+    /// Every physical device is a member of exactly one group. Most systems only have groups that
+    /// contain a single physical device, but a system with multiple GPUs that are linked
+    /// together may report them as a single group. To create a device that spans all physical
+    /// devices of a group, set [`DeviceCreateInfo::physical_devices`].
+    ///
+    /// The instance API version must be at least 1.1, or the
+    /// [`khr_device_group_creation`](InstanceExtensions::khr_device_group_creation) extension
+    /// must be enabled on the instance.
+    ///
+    /// [`DeviceCreateInfo::physical_devices`]: crate::device::DeviceCreateInfo::physical_devices
+    pub fn enumerate_physical_device_groups(
+        self: &Arc<Self>,
+    ) -> Result<impl ExactSizeIterator<Item = PhysicalDeviceGroupProperties>, PhysicalDeviceError>
+    {
+        self.validate_enumerate_physical_device_groups()?;
+
+        unsafe { Ok(self.enumerate_physical_device_groups_unchecked()?) }
+    }
+
+    fn validate_enumerate_physical_device_groups(&self) -> Result<(), PhysicalDeviceError> {
+        if !(self.api_version() >= Version::V1_1
+            || self.enabled_extensions().khr_device_group_creation)
+        {
+            return Err(PhysicalDeviceError::RequirementNotMet {
+                required_for: "`Instance::enumerate_physical_device_groups`",
+                requires_one_of: RequiresOneOf {
+                    api_version: Some(Version::V1_1),
+                    instance_extensions: &["khr_device_group_creation"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn enumerate_physical_device_groups_unchecked(
+        self: &Arc<Self>,
+    ) -> Result<impl ExactSizeIterator<Item = PhysicalDeviceGroupProperties>, VulkanError> {
+        let fns = self.fns();
+        let enumerate_physical_device_groups = if self.api_version() >= Version::V1_1 {
+            fns.v1_1.enumerate_physical_device_groups
+        } else {
+            fns.khr_device_group_creation
+                .enumerate_physical_device_groups_khr
+        };
+
+        let properties_vk = loop {
+            let mut count = 0;
+            (enumerate_physical_device_groups)(self.handle, &mut count, ptr::null_mut())
+                .result()
+                .map_err(VulkanError::from)?;
+
+            let mut properties_vk =
+                vec![ash::vk::PhysicalDeviceGroupProperties::default(); count as usize];
+            let result = (enumerate_physical_device_groups)(
+                self.handle,
+                &mut count,
+                properties_vk.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => {
+                    properties_vk.set_len(count as usize);
+                    break properties_vk;
+                }
+                ash::vk::Result::INCOMPLETE => (),
+                err => return Err(VulkanError::from(err)),
+            }
+        };
+
+        let groups: SmallVec<[_; 4]> = properties_vk
+            .into_iter()
+            .map(|properties_vk| {
+                let physical_devices = properties_vk.physical_devices
+                    [..properties_vk.physical_device_count as usize]
+                    .iter()
+                    .map(|&handle| PhysicalDevice::from_handle(self.clone(), handle))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(PhysicalDeviceGroupProperties {
+                    physical_devices,
+                    subset_allocation: properties_vk.subset_allocation != ash::vk::FALSE,
+                })
+            })
+            .collect::<Result<_, VulkanError>>()?;
+
+        Ok(groups.into_iter())
+    }
 }
 
 impl Drop for Instance {
@@ -750,6 +905,11 @@ pub struct InstanceCreateInfo {
     /// If not empty, the
     /// [`ext_validation_features`](crate::instance::InstanceExtensions::ext_validation_features)
     /// extension must be enabled on the instance.
+    ///
+    /// If `GpuAssistedReserveBindingSlot` is included, then `GpuAssisted` must also be included.
+    /// `DebugPrintf` and `GpuAssisted` must not both be included.
+    ///
+    /// The default value is empty.
     pub enabled_validation_features: Vec<ValidationFeatureEnable>,
 
     /// Features of the validation layer to disable.
@@ -757,6 +917,8 @@ pub struct InstanceCreateInfo {
     /// If not empty, the
     /// [`ext_validation_features`](crate::instance::InstanceExtensions::ext_validation_features)
     /// extension must be enabled on the instance.
+    ///
+    /// The default value is empty.
     pub disabled_validation_features: Vec<ValidationFeatureDisable>,
 
     pub _ne: crate::NonExhaustive,
@@ -803,6 +965,22 @@ impl InstanceCreateInfo {
     }
 }
 
+/// Properties of a group of physical devices that can be used together to create a single logical
+/// device.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PhysicalDeviceGroupProperties {
+    /// The physical devices that belong to the group.
+    pub physical_devices: Vec<Arc<PhysicalDevice>>,
+
+    /// Whether memory can be allocated from a subset of the physical devices in the group, by
+    /// specifying a [`device_mask`] when allocating. If `false`, memory is always allocated on
+    /// all physical devices in the group.
+    ///
+    /// [`device_mask`]: crate::memory::MemoryAllocateInfo::device_mask
+    pub subset_allocation: bool,
+}
+
 /// Error that can happen when creating an instance.
 #[derive(Clone, Debug)]
 pub enum InstanceCreationError {
@@ -888,9 +1066,29 @@ impl From<VulkanError> for InstanceCreationError {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
 
     #[test]
     fn create_instance() {
         let _ = instance!();
     }
+
+    #[test]
+    fn validation_features_require_extension() {
+        let library = match VulkanLibrary::new() {
+            Ok(x) => x,
+            Err(_) => return,
+        };
+
+        match Instance::new(
+            library,
+            InstanceCreateInfo {
+                enabled_validation_features: vec![ValidationFeatureEnable::BestPractices],
+                ..Default::default()
+            },
+        ) {
+            Err(InstanceCreationError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
 }
@@ -87,6 +87,7 @@
 )]
 
 pub use ash::vk::Handle;
+pub use bytemuck;
 pub use half;
 pub use library::{LoadingError, VulkanLibrary};
 use std::{
@@ -101,6 +102,7 @@ pub use {extensions::ExtensionProperties, version::Version};
 mod tests;
 #[macro_use]
 mod extensions;
+pub mod acceleration_structure;
 pub mod buffer;
 pub mod command_buffer;
 pub mod descriptor_set;
@@ -120,10 +122,13 @@ pub mod pipeline;
 pub mod query;
 mod range_map;
 pub mod range_set;
+#[cfg(feature = "renderdoc")]
+pub mod renderdoc;
 pub mod sampler;
 pub mod shader;
 pub mod swapchain;
 pub mod sync;
+pub mod video;
 
 /// Represents memory size and offset values on a Vulkan device.
 /// Analogous to the Rust `usize` type on the host.
@@ -0,0 +1,552 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    align_down, align_up, AllocationCreateInfo, AllocationCreationError, AllocationType,
+    MemoryAlloc, MemoryAllocatePreference, MemoryAllocator, MemoryUsage,
+};
+use crate::{device::DeviceOwned, memory::MemoryRequirements, DeviceSize};
+use std::{cmp::Reverse, ops::Range, ptr};
+
+/// Parameters of a resource to allocate memory for with [`allocate_aliased`].
+#[derive(Clone, Debug)]
+pub struct AliasedAllocationCreateInfo {
+    /// Requirements of the resource you want to allocate memory for.
+    ///
+    /// This must correspond to the value returned by either [`RawBuffer::memory_requirements`] or
+    /// [`RawImage::memory_requirements`] for the respective buffer or image.
+    /// [`prefers_dedicated_allocation`] and [`requires_dedicated_allocation`] are ignored, the
+    /// latter must therefore be `false`.
+    ///
+    /// The default is all zeros, which must be overridden.
+    ///
+    /// [`RawBuffer::memory_requirements`]: crate::buffer::sys::RawBuffer::memory_requirements
+    /// [`RawImage::memory_requirements`]: crate::image::sys::RawImage::memory_requirements
+    /// [`prefers_dedicated_allocation`]: MemoryRequirements::prefers_dedicated_allocation
+    /// [`requires_dedicated_allocation`]: MemoryRequirements::requires_dedicated_allocation
+    pub requirements: MemoryRequirements,
+
+    /// What type of resource this allocation will be used for.
+    ///
+    /// This is used to keep resources of different types from sharing a page of
+    /// [buffer-image granularity] while they are both in use.
+    ///
+    /// The default value is [`AllocationType::Unknown`].
+    ///
+    /// [buffer-image granularity]: super#buffer-image-granularity
+    pub allocation_type: AllocationType,
+
+    /// The range of steps during which the resource is in use, where the end is exclusive.
+    ///
+    /// The steps are arbitrary and only need to be consistent between all resources that are
+    /// allocated together. For example, they could be the indices of the render passes in a frame
+    /// that the resource is first and last used in, plus one for the end. Resources whose
+    /// lifetimes don't overlap may share memory.
+    ///
+    /// The default value is `0..0`, which must be overridden.
+    pub lifetime: Range<u32>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for AliasedAllocationCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        AliasedAllocationCreateInfo {
+            requirements: MemoryRequirements {
+                size: 0,
+                alignment: 0,
+                memory_type_bits: 0,
+                prefers_dedicated_allocation: false,
+                requires_dedicated_allocation: false,
+            },
+            allocation_type: AllocationType::Unknown,
+            lifetime: 0..0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Allocates memory for multiple resources from a single block of [`DeviceMemory`], where
+/// resources whose lifetimes don't overlap are placed in the same memory.
+///
+/// This is mostly useful for transient attachments, such as the G-buffer targets of a deferred
+/// renderer, that are only used during part of a frame. The returned allocations are in the same
+/// order as `create_infos`, and allocations that alias each other are guaranteed to have
+/// disjoint lifetimes. The memory is freed once all the allocations have been dropped.
+///
+/// # Panics
+///
+/// - Panics if `create_infos` is empty.
+/// - Panics if the `requirements.size` of an element of `create_infos` is zero.
+/// - Panics if the `requirements.alignment` of an element of `create_infos` is not a power of
+///   two.
+/// - Panics if the `requirements.requires_dedicated_allocation` of an element of `create_infos`
+///   is `true`.
+/// - Panics if the `lifetime` of an element of `create_infos` is empty.
+/// - Panics if the elements of `create_infos` don't have any bit of
+///   `requirements.memory_type_bits` in common.
+/// - Panics if `allocator` does not return a [root allocation] when requested to always allocate
+///   `DeviceMemory`.
+///
+/// # Safety
+///
+/// - A resource that is bound to one of the returned allocations must only be accessed during its
+///   `lifetime`.
+/// - Before a resource is accessed at the start of its lifetime, all previous accesses to
+///   resources whose memory it aliases must have completed, and there must be a memory
+///   dependency between them.
+/// - The contents of a resource are undefined at the start of its lifetime. Images must be
+///   transitioned from [`ImageLayout::Undefined`] before being used.
+///
+/// In debug builds, you can use an [`AliasingTracker`] to check the first two requirements.
+///
+/// [`DeviceMemory`]: crate::memory::DeviceMemory
+/// [root allocation]: MemoryAlloc::is_root
+/// [`ImageLayout::Undefined`]: crate::image::ImageLayout::Undefined
+pub unsafe fn allocate_aliased(
+    allocator: &(impl MemoryAllocator + ?Sized),
+    usage: MemoryUsage,
+    create_infos: &[AliasedAllocationCreateInfo],
+) -> Result<Vec<MemoryAlloc>, AllocationCreationError> {
+    assert!(!create_infos.is_empty());
+
+    for create_info in create_infos {
+        assert!(create_info.requirements.size != 0);
+        assert!(create_info.requirements.alignment.is_power_of_two());
+        assert!(!create_info.requirements.requires_dedicated_allocation);
+        assert!(!create_info.lifetime.is_empty());
+    }
+
+    let memory_type_bits = create_infos.iter().fold(u32::MAX, |bits, create_info| {
+        bits & create_info.requirements.memory_type_bits
+    });
+    assert!(
+        memory_type_bits != 0,
+        "the elements of `create_infos` must have at least one memory type in common",
+    );
+
+    let buffer_image_granularity = allocator
+        .device()
+        .physical_device()
+        .properties()
+        .buffer_image_granularity;
+    let offsets = place_aliased(create_infos, buffer_image_granularity);
+
+    let size = create_infos
+        .iter()
+        .zip(&offsets)
+        .map(|(create_info, &offset)| offset + create_info.requirements.size)
+        .max()
+        .unwrap();
+    let alignment = create_infos
+        .iter()
+        .map(|create_info| create_info.requirements.alignment)
+        .max()
+        .unwrap();
+
+    let region = allocator.allocate(AllocationCreateInfo {
+        requirements: MemoryRequirements {
+            size,
+            alignment,
+            memory_type_bits,
+            prefers_dedicated_allocation: false,
+            requires_dedicated_allocation: false,
+        },
+        usage,
+        allocate_preference: MemoryAllocatePreference::AlwaysAllocate,
+        ..Default::default()
+    })?;
+
+    // The aliases only keep the `DeviceMemory` alive, so the region must not be a suballocation
+    // that could be handed out again once it's dropped.
+    assert!(region.is_root());
+
+    Ok(create_infos
+        .iter()
+        .zip(offsets)
+        .map(|(create_info, offset)| {
+            let mut alloc = region.alias().unwrap();
+            alloc.shift(offset);
+            alloc.shrink(create_info.requirements.size);
+            alloc.set_allocation_type(create_info.allocation_type);
+
+            alloc
+        })
+        .collect())
+}
+
+/// Returns the offset of each resource, such that resources whose lifetimes overlap don't overlap
+/// in memory.
+fn place_aliased(
+    create_infos: &[AliasedAllocationCreateInfo],
+    buffer_image_granularity: DeviceSize,
+) -> Vec<DeviceSize> {
+    // Placing the largest resources first results in less wasted space.
+    let mut order: Vec<_> = (0..create_infos.len()).collect();
+    order.sort_by_key(|&index| Reverse(create_infos[index].requirements.size));
+
+    let mut offsets = vec![0; create_infos.len()];
+    let mut placed: Vec<usize> = Vec::with_capacity(create_infos.len());
+
+    for index in order {
+        let create_info = &create_infos[index];
+        let alignment = create_info.requirements.alignment;
+
+        let conflicting: Vec<_> = placed
+            .iter()
+            .copied()
+            .filter(|&other| {
+                let other = &create_infos[other];
+                create_info.lifetime.start < other.lifetime.end
+                    && other.lifetime.start < create_info.lifetime.end
+            })
+            .collect();
+
+        // The resource can always be placed either at the start, or right after one of the
+        // resources that it can't share memory with. The candidate after the conflicting resource
+        // that ends last is past the end of all of them, and aligned up to the buffer-image
+        // granularity, so it never overlaps with anything and the search below can't fail.
+        let mut candidates = vec![0];
+        candidates.extend(conflicting.iter().flat_map(|&other| {
+            let end = offsets[other] + create_infos[other].requirements.size;
+
+            [
+                align_up(end, alignment),
+                align_up(align_up(end, buffer_image_granularity), alignment),
+            ]
+        }));
+        candidates.sort_unstable();
+
+        let offset = candidates
+            .into_iter()
+            .find(|&offset| {
+                conflicting.iter().all(|&other| {
+                    !overlaps(
+                        offset..offset + create_info.requirements.size,
+                        create_info.allocation_type,
+                        offsets[other]..offsets[other] + create_infos[other].requirements.size,
+                        create_infos[other].allocation_type,
+                        buffer_image_granularity,
+                    )
+                })
+            })
+            .unwrap();
+        debug_assert!(offset % alignment == 0);
+
+        offsets[index] = offset;
+        placed.push(index);
+    }
+
+    offsets
+}
+
+/// Checks that the allocations returned by [`allocate_aliased`] are used according to their
+/// lifetimes, and that there is a barrier between aliases whose memory overlaps.
+///
+/// You tell the tracker about every access to one of the resources and every barrier between
+/// two of them, in the order that they happen on the device. This is meant to be used in debug
+/// builds, to catch mistakes that would otherwise silently corrupt the contents of the resources.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use vulkano::memory::allocator::{AliasedAllocationCreateInfo, AliasingTracker};
+/// # let create_infos: [AliasedAllocationCreateInfo; 2] = unimplemented!();
+/// # let allocations: Vec<vulkano::memory::allocator::MemoryAlloc> = unimplemented!();
+/// let mut tracker = AliasingTracker::new(&create_infos, &allocations);
+///
+/// // The first resource is used during step 0.
+/// tracker.access(0, 0);
+/// // The second resource aliases the first, so it must wait for it.
+/// tracker.barrier(0, 1);
+/// tracker.access(1, 1);
+/// ```
+#[derive(Debug)]
+pub struct AliasingTracker {
+    aliases: Vec<TrackedAlias>,
+    // For each pair of aliases `(src, dst)`, whether `src` was accessed since the last barrier
+    // from `src` to `dst`.
+    unsynchronized: Vec<bool>,
+}
+
+#[derive(Debug)]
+struct TrackedAlias {
+    lifetime: Range<u32>,
+    // The aliases whose memory overlaps with this one.
+    overlapping: Vec<usize>,
+}
+
+impl AliasingTracker {
+    /// Creates a new `AliasingTracker` for `allocations`, which must have been returned by
+    /// [`allocate_aliased`] when it was called with `create_infos`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_infos` and `allocations` don't have the same length.
+    /// - Panics if the elements of `allocations` don't all come from the same [`DeviceMemory`].
+    ///
+    /// [`DeviceMemory`]: crate::memory::DeviceMemory
+    pub fn new(create_infos: &[AliasedAllocationCreateInfo], allocations: &[MemoryAlloc]) -> Self {
+        assert_eq!(create_infos.len(), allocations.len());
+
+        let buffer_image_granularity = allocations.first().map_or(1, |alloc| {
+            assert!(allocations
+                .iter()
+                .all(|other| ptr::eq(other.device_memory(), alloc.device_memory())));
+
+            alloc
+                .device()
+                .physical_device()
+                .properties()
+                .buffer_image_granularity
+        });
+        let ranges: Vec<_> = allocations
+            .iter()
+            .map(|alloc| {
+                (
+                    alloc.offset()..alloc.offset() + alloc.size(),
+                    alloc.allocation_type(),
+                )
+            })
+            .collect();
+
+        Self::from_ranges(create_infos, &ranges, buffer_image_granularity)
+    }
+
+    fn from_ranges(
+        create_infos: &[AliasedAllocationCreateInfo],
+        ranges: &[(Range<DeviceSize>, AllocationType)],
+        buffer_image_granularity: DeviceSize,
+    ) -> Self {
+        let aliases = create_infos
+            .iter()
+            .zip(ranges)
+            .enumerate()
+            .map(|(index, (create_info, (range, allocation_type)))| {
+                let overlapping = ranges
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other, (other_range, other_type))| {
+                        other != index
+                            && overlaps(
+                                range.clone(),
+                                *allocation_type,
+                                other_range.clone(),
+                                *other_type,
+                                buffer_image_granularity,
+                            )
+                    })
+                    .map(|(other, _)| other)
+                    .collect();
+
+                TrackedAlias {
+                    lifetime: create_info.lifetime.clone(),
+                    overlapping,
+                }
+            })
+            .collect();
+
+        AliasingTracker {
+            aliases,
+            unsynchronized: vec![false; create_infos.len() * create_infos.len()],
+        }
+    }
+
+    /// Records an access to the resource at `index` during `step`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `index` is out of bounds.
+    /// - Panics if `step` is not within the `lifetime` of the resource.
+    /// - Panics if a resource whose memory overlaps was accessed, and there was no
+    ///   [barrier](Self::barrier) from that resource to this one since.
+    pub fn access(&mut self, index: usize, step: u32) {
+        let len = self.aliases.len();
+        let alias = &self.aliases[index];
+
+        assert!(
+            alias.lifetime.contains(&step),
+            "resource {} was accessed during step {}, which is outside of its lifetime {:?}",
+            index,
+            step,
+            alias.lifetime,
+        );
+
+        for &other in &alias.overlapping {
+            assert!(
+                !self.unsynchronized[other * len + index],
+                "resource {} was accessed without a barrier after the last access to resource {}, \
+                which aliases its memory",
+                index,
+                other,
+            );
+        }
+
+        for &other in &alias.overlapping {
+            self.unsynchronized[index * len + other] = true;
+        }
+    }
+
+    /// Records a barrier that makes the previous accesses to the resource at `src` available to
+    /// subsequent accesses to the resource at `dst`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `src` or `dst` is out of bounds.
+    pub fn barrier(&mut self, src: usize, dst: usize) {
+        let len = self.aliases.len();
+        assert!(src < len && dst < len);
+
+        self.unsynchronized[src * len + dst] = false;
+    }
+}
+
+fn overlaps(
+    a: Range<DeviceSize>,
+    a_type: AllocationType,
+    b: Range<DeviceSize>,
+    b_type: AllocationType,
+    buffer_image_granularity: DeviceSize,
+) -> bool {
+    if matches!(
+        (a_type, b_type),
+        (AllocationType::Linear, AllocationType::Linear)
+            | (AllocationType::NonLinear, AllocationType::NonLinear)
+    ) {
+        a.start < b.end && b.start < a.end
+    } else {
+        // The resources must not share a page of buffer-image granularity.
+        align_down(a.start, buffer_image_granularity) < align_up(b.end, buffer_image_granularity)
+            && align_down(b.start, buffer_image_granularity)
+                < align_up(a.end, buffer_image_granularity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_info(
+        size: DeviceSize,
+        allocation_type: AllocationType,
+        lifetime: Range<u32>,
+    ) -> AliasedAllocationCreateInfo {
+        AliasedAllocationCreateInfo {
+            requirements: MemoryRequirements {
+                size,
+                alignment: 256,
+                memory_type_bits: 1,
+                prefers_dedicated_allocation: false,
+                requires_dedicated_allocation: false,
+            },
+            allocation_type,
+            lifetime,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn disjoint_lifetimes_alias() {
+        let create_infos = [
+            create_info(4096, AllocationType::NonLinear, 0..2),
+            create_info(4096, AllocationType::NonLinear, 2..4),
+            create_info(1024, AllocationType::NonLinear, 1..3),
+        ];
+        let offsets = place_aliased(&create_infos, 1);
+
+        assert_eq!(offsets[0], offsets[1]);
+        assert!(offsets[2] >= 4096);
+    }
+
+    #[test]
+    fn overlapping_lifetimes_dont_alias() {
+        let create_infos = [
+            create_info(1000, AllocationType::Linear, 0..3),
+            create_info(3000, AllocationType::NonLinear, 1..2),
+            create_info(2000, AllocationType::Unknown, 2..5),
+            create_info(500, AllocationType::Linear, 4..6),
+        ];
+        let offsets = place_aliased(&create_infos, 1024);
+
+        for (i, a) in create_infos.iter().enumerate() {
+            assert!(offsets[i] % a.requirements.alignment == 0);
+
+            for (j, b) in create_infos.iter().enumerate().skip(i + 1) {
+                if a.lifetime.start < b.lifetime.end && b.lifetime.start < a.lifetime.end {
+                    assert!(!overlaps(
+                        offsets[i]..offsets[i] + a.requirements.size,
+                        a.allocation_type,
+                        offsets[j]..offsets[j] + b.requirements.size,
+                        b.allocation_type,
+                        1024,
+                    ));
+                }
+            }
+        }
+    }
+
+    fn tracker(create_infos: &[AliasedAllocationCreateInfo]) -> AliasingTracker {
+        let ranges: Vec<_> = create_infos
+            .iter()
+            .zip(place_aliased(create_infos, 1))
+            .map(|(create_info, offset)| {
+                (
+                    offset..offset + create_info.requirements.size,
+                    create_info.allocation_type,
+                )
+            })
+            .collect();
+
+        AliasingTracker::from_ranges(create_infos, &ranges, 1)
+    }
+
+    #[test]
+    fn tracker_barrier_between_aliases() {
+        let create_infos = [
+            create_info(4096, AllocationType::NonLinear, 0..2),
+            create_info(4096, AllocationType::NonLinear, 2..4),
+        ];
+        let mut tracker = tracker(&create_infos);
+
+        tracker.access(0, 0);
+        tracker.access(0, 1);
+        tracker.barrier(0, 1);
+        tracker.access(1, 2);
+        tracker.access(1, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "without a barrier")]
+    fn tracker_missing_barrier() {
+        let create_infos = [
+            create_info(4096, AllocationType::NonLinear, 0..2),
+            create_info(4096, AllocationType::NonLinear, 2..4),
+        ];
+        let mut tracker = tracker(&create_infos);
+
+        tracker.access(0, 1);
+        tracker.barrier(0, 1);
+        // The barrier only covers the accesses that came before it.
+        tracker.access(0, 1);
+        tracker.access(1, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of its lifetime")]
+    fn tracker_access_outside_lifetime() {
+        let create_infos = [
+            create_info(4096, AllocationType::NonLinear, 0..2),
+            create_info(4096, AllocationType::NonLinear, 2..4),
+        ];
+        let mut tracker = tracker(&create_infos);
+
+        tracker.access(0, 2);
+    }
+}
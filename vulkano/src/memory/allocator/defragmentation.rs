@@ -0,0 +1,267 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::MemoryAlloc;
+use crate::DeviceSize;
+
+/// An allocation that [`GenericMemoryAllocator::defragment`] is allowed to move.
+///
+/// [`GenericMemoryAllocator::defragment`]: super::GenericMemoryAllocator::defragment
+#[derive(Clone, Copy, Debug)]
+pub struct MovableAllocation<'a> {
+    /// The allocation that the resource is currently bound to.
+    pub allocation: &'a MemoryAlloc,
+
+    /// The alignment that the resource requires, as returned in its [`MemoryRequirements`].
+    ///
+    /// [`MemoryRequirements`]: crate::memory::MemoryRequirements
+    pub alignment: DeviceSize,
+}
+
+/// A new allocation that was made by [`GenericMemoryAllocator::defragment`], to which one of the
+/// [movable allocations] should be moved.
+///
+/// [`GenericMemoryAllocator::defragment`]: super::GenericMemoryAllocator::defragment
+/// [movable allocations]: MovableAllocation
+#[derive(Debug)]
+pub struct DefragmentationMove {
+    /// The index of the allocation in the list of movable allocations that was passed to
+    /// `defragment`.
+    pub index: usize,
+
+    /// The new allocation. It has the same size, allocation type and memory type as the old one.
+    pub allocation: MemoryAlloc,
+}
+
+/// The usage of a block in a pool, as seen by the defragmentation planner.
+#[derive(Clone, Copy, Debug)]
+pub(super) struct BlockUsage {
+    pub(super) size: DeviceSize,
+    pub(super) free_size: DeviceSize,
+    // The total size of the movable allocations in this block.
+    pub(super) movable_size: DeviceSize,
+}
+
+/// Returns for each block whether its movable allocations should be moved out of it.
+///
+/// The least used blocks are evacuated first, for as long as the free space in the remaining
+/// blocks that are in use can take in their movable allocations. The moves are planned into the
+/// fullest blocks first, keeping track of the free space that is left in each of them, and a
+/// block that is planned to receive allocations is not evacuated itself. This is an estimate, the
+/// actual suballocation can still fail because of external fragmentation in the destination
+/// blocks.
+pub(super) fn select_source_blocks(blocks: &[BlockUsage]) -> Vec<bool> {
+    let mut order: Vec<_> = (0..blocks.len()).collect();
+    order.sort_by_key(|&index| blocks[index].size - blocks[index].free_size);
+
+    // Moving allocations into an empty block doesn't make anything more compact.
+    let mut destinations: Vec<_> = (0..blocks.len())
+        .filter(|&index| blocks[index].free_size < blocks[index].size)
+        .collect();
+    destinations.sort_by_key(|&index| blocks[index].free_size);
+
+    let mut is_source = vec![false; blocks.len()];
+    let mut is_destination = vec![false; blocks.len()];
+    let mut free_sizes: Vec<_> = blocks.iter().map(|block| block.free_size).collect();
+
+    for index in order {
+        if blocks[index].movable_size == 0 || is_destination[index] {
+            continue;
+        }
+
+        // The free space in the block itself, or in blocks that are evacuated, can't be used as a
+        // destination.
+        let available_size: DeviceSize = destinations
+            .iter()
+            .filter(|&&dst_index| dst_index != index && !is_source[dst_index])
+            .map(|&dst_index| free_sizes[dst_index])
+            .sum();
+
+        if available_size < blocks[index].movable_size {
+            break;
+        }
+
+        is_source[index] = true;
+        let mut remaining_size = blocks[index].movable_size;
+
+        for &dst_index in &destinations {
+            if remaining_size == 0 {
+                break;
+            }
+
+            if dst_index == index || is_source[dst_index] || free_sizes[dst_index] == 0 {
+                continue;
+            }
+
+            let size = remaining_size.min(free_sizes[dst_index]);
+            free_sizes[dst_index] -= size;
+            remaining_size -= size;
+            is_destination[dst_index] = true;
+        }
+    }
+
+    is_source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{
+        allocator::{
+            AllocationType, GenericMemoryAllocatorCreateInfo, MemoryAllocator,
+            StandardMemoryAllocator, SuballocationCreateInfo,
+        },
+        MemoryPropertyFlags,
+    };
+    use std::ptr;
+
+    fn block(size: DeviceSize, free_size: DeviceSize, movable_size: DeviceSize) -> BlockUsage {
+        BlockUsage {
+            size,
+            free_size,
+            movable_size,
+        }
+    }
+
+    #[test]
+    fn least_used_block_is_evacuated() {
+        let blocks = [
+            block(1024, 256, 768),
+            block(1024, 768, 256),
+            block(1024, 512, 512),
+        ];
+
+        assert_eq!(select_source_blocks(&blocks), [false, true, false]);
+    }
+
+    #[test]
+    fn not_enough_space() {
+        let blocks = [block(1024, 128, 896), block(1024, 768, 256)];
+
+        assert_eq!(select_source_blocks(&blocks), [false, false]);
+    }
+
+    #[test]
+    fn blocks_without_movable_allocations_stay() {
+        let blocks = [
+            block(1024, 1024, 0),
+            block(1024, 896, 0),
+            block(1024, 768, 256),
+            block(1024, 0, 1024),
+        ];
+
+        assert_eq!(select_source_blocks(&blocks), [false, false, true, false]);
+    }
+
+    #[test]
+    fn evacuated_block_space_is_not_reused() {
+        let blocks = [
+            block(1024, 900, 124),
+            block(1024, 800, 224),
+            block(1024, 100, 0),
+        ];
+
+        assert_eq!(select_source_blocks(&blocks), [true, false, false]);
+    }
+
+    #[test]
+    fn destination_block_is_not_evacuated() {
+        let blocks = [
+            block(1024, 100, 924),
+            block(1024, 700, 324),
+            block(1024, 900, 124),
+        ];
+
+        assert_eq!(select_source_blocks(&blocks), [false, false, true]);
+    }
+
+    #[test]
+    fn destination_free_space_is_tracked() {
+        let blocks = [
+            block(1024, 600, 424),
+            block(1024, 700, 324),
+            block(1024, 900, 124),
+        ];
+
+        // Both blocks fit into the first one, which then has to stay.
+        assert_eq!(select_source_blocks(&blocks), [false, true, true]);
+    }
+
+    #[test]
+    fn defragment_into_fuller_block() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // Avoid memory types where the allocations would need padding, or are always dedicated.
+        let memory_type_index = match device
+            .physical_device()
+            .memory_properties()
+            .memory_types
+            .iter()
+            .position(|memory_type| {
+                !memory_type.property_flags.intersects(
+                    MemoryPropertyFlags::LAZILY_ALLOCATED | MemoryPropertyFlags::HOST_VISIBLE,
+                )
+            }) {
+            Some(x) => x as u32,
+            None => return,
+        };
+
+        let allocator = StandardMemoryAllocator::new(
+            device,
+            GenericMemoryAllocatorCreateInfo {
+                block_sizes: &[(0, 1024)],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let create_info = SuballocationCreateInfo {
+            size: 256,
+            alignment: 1,
+            allocation_type: AllocationType::Linear,
+            ..Default::default()
+        };
+
+        // Fill up the first block, and put one more allocation in a second block.
+        let mut allocs: Vec<_> = (0..5)
+            .map(|_| {
+                allocator
+                    .allocate_from_type(memory_type_index, create_info.clone())
+                    .unwrap()
+            })
+            .collect();
+        let lonely = allocs.pop().unwrap();
+        assert!(allocs
+            .iter()
+            .all(|alloc| !ptr::eq(alloc.device_memory(), lonely.device_memory())));
+
+        // Make room in the first block.
+        allocs.pop();
+
+        let mut moves = Vec::new();
+        let moved_size = allocator.defragment(
+            &[MovableAllocation {
+                allocation: &lonely,
+                alignment: 1,
+            }],
+            |m| moves.push(m),
+        );
+
+        assert_eq!(moved_size, 256);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].index, 0);
+        assert!(ptr::eq(
+            moves[0].allocation.device_memory(),
+            allocs[0].device_memory(),
+        ));
+
+        // Once the old allocation is gone, the second block can be freed.
+        drop(lonely);
+        assert_eq!(allocator.free_unused_blocks(), 1024);
+    }
+}
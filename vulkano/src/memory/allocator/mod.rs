@@ -191,6 +191,18 @@
 //! +-----+---+-----+---------+-------+-----+-----+-- - - --+
 //! ```
 //!
+//! ## Defragmentation
+//!
+//! Vulkan doesn't allow the memory bound to a (non-sparse) buffer or image to be changed after the
+//! fact, so the only way to move an allocation is to create a new resource, copy the contents over
+//! and then replace every reference to the old resource with the new one. Vulkano can't do the last
+//! step for you, as buffers and images are shared using reference counting. What it can do is
+//! plan the moves: [`GenericMemoryAllocator::defragment`] picks the allocations to move out of the
+//! least used blocks and allocates their new places, and you recreate the resources. To keep the
+//! problem in check in the first place, use separate allocators for resources with different
+//! lifetimes, such as a [`FastMemoryAllocator`] for transient resources, and call
+//! [`GenericMemoryAllocator::free_unused_blocks`] once the memory usage goes down.
+//!
 //! # Leakage
 //!
 //! Memory leaks happen when allocations are kept alive past their shelf life. This most often
@@ -216,9 +228,14 @@
 //! [`mem::forget`]: std::mem::forget
 //! [region]: Suballocator#regions
 
+mod aliasing;
+mod defragmentation;
 pub mod suballocator;
 
+pub use self::aliasing::{allocate_aliased, AliasedAllocationCreateInfo, AliasingTracker};
 use self::array_vec::ArrayVec;
+use self::defragmentation::{select_source_blocks, BlockUsage};
+pub use self::defragmentation::{DefragmentationMove, MovableAllocation};
 pub use self::suballocator::{
     AllocationType, BuddyAllocator, BumpAllocator, FreeListAllocator, MemoryAlloc, PoolAllocator,
     SuballocationCreateInfo, SuballocationCreationError, Suballocator,
@@ -234,8 +251,10 @@ use crate::{
 use ash::vk::{MAX_MEMORY_HEAPS, MAX_MEMORY_TYPES};
 use parking_lot::RwLock;
 use std::{
+    cmp::Reverse,
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
+    mem, ptr,
     sync::Arc,
 };
 
@@ -673,15 +692,22 @@ impl FastMemoryAllocator {
 /// another block. No `DeviceMemory` is allocated when the allocator is created, the blocks are
 /// only allocated once they are needed.
 ///
+/// Blocks are kept in their pool for reuse after all of their suballocations have been freed. In
+/// long-running applications, where the memory usage can spike and then go back down, you can call
+/// [`free_unused_blocks`] to return such blocks to the implementation.
+///
 /// # Locking behavior
 ///
 /// The allocator never needs to lock while suballocating unless `S` needs to lock. The only time
-/// when a pool must be locked is when a new `DeviceMemory` block is allocated for the pool. This
-/// means that the allocator is suited to both locking and lock-free (sub)allocation algorithms.
+/// when a pool must be locked is when a new `DeviceMemory` block is allocated for the pool, or
+/// when [`free_unused_blocks`] or [`defragment`] is called. This means that the allocator is
+/// suited to both locking and lock-free (sub)allocation algorithms.
 ///
 /// [memory allocator]: MemoryAllocator
 /// [suballocate]: Suballocator
 /// [the `MemoryAllocator` implementation]: Self#impl-MemoryAllocator-for-GenericMemoryAllocator<S>
+/// [`free_unused_blocks`]: Self::free_unused_blocks
+/// [`defragment`]: Self::defragment
 #[derive(Debug)]
 pub struct GenericMemoryAllocator<S: Suballocator> {
     device: Arc<Device>,
@@ -697,6 +723,7 @@ pub struct GenericMemoryAllocator<S: Suballocator> {
     memory_type_bits: u32,
     // How many `DeviceMemory` allocations should be allowed before restricting them.
     max_allocations: u32,
+    priority: f32,
 }
 
 #[derive(Debug)]
@@ -745,6 +772,7 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             dedicated_allocation: _,
             export_handle_types,
             device_address: _,
+            priority,
             _ne: _,
         } = create_info;
 
@@ -788,6 +816,21 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             }
         }
 
+        if priority != 0.5 {
+            if !device.enabled_features().memory_priority {
+                return Err(GenericMemoryAllocatorCreationError::RequirementNotMet {
+                    required_for: "`create_info.priority` is not `0.5`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["memory_priority"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkMemoryPriorityAllocateInfoEXT-priority-02602
+            assert!((0.0..=1.0).contains(&priority));
+        }
+
         Ok(())
     }
 
@@ -802,6 +845,7 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             dedicated_allocation,
             export_handle_types,
             mut device_address,
+            priority,
             _ne: _,
         } = create_info;
 
@@ -851,16 +895,21 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
         device_address &=
             device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_device_group;
 
+        let mut excluded_property_flags = MemoryPropertyFlags::LAZILY_ALLOCATED
+            | MemoryPropertyFlags::DEVICE_COHERENT
+            | MemoryPropertyFlags::DEVICE_UNCACHED
+            | MemoryPropertyFlags::RDMA_CAPABLE;
+
+        // VUID-VkMemoryAllocateInfo-memoryTypeIndex-01872
+        // The memory requirements of a resource only contain protected memory types if the
+        // resource is protected, so these are never chosen for unprotected resources.
+        if !device.enabled_features().protected_memory {
+            excluded_property_flags |= MemoryPropertyFlags::PROTECTED;
+        }
+
         let mut memory_type_bits = u32::MAX;
         for (index, MemoryType { property_flags, .. }) in memory_types.iter().enumerate() {
-            if property_flags.intersects(
-                MemoryPropertyFlags::LAZILY_ALLOCATED
-                    | MemoryPropertyFlags::PROTECTED
-                    | MemoryPropertyFlags::DEVICE_COHERENT
-                    | MemoryPropertyFlags::DEVICE_UNCACHED
-                    | MemoryPropertyFlags::RDMA_CAPABLE,
-            ) {
-                // VUID-VkMemoryAllocateInfo-memoryTypeIndex-01872
+            if property_flags.intersects(excluded_property_flags) {
                 // VUID-vkAllocateMemory-deviceCoherentMemory-02790
                 // Lazily allocated memory would just cause problems for suballocation in general.
                 memory_type_bits &= !(1 << index);
@@ -887,9 +936,152 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
             },
             memory_type_bits,
             max_allocations,
+            priority,
         }
     }
 
+    /// Frees all `DeviceMemory` blocks in the pools that have no suballocations left, and returns
+    /// the total size of the memory that was freed.
+    ///
+    /// This takes an exclusive lock on each pool, and blocks that are still in use are left
+    /// untouched. Allocating concurrently is safe: an allocation that needs a new block searches
+    /// all the blocks of the pool again after taking the exclusive lock, so it never relies on
+    /// blocks that were freed in the meantime. Dedicated allocations are not part of any pool, and
+    /// are freed as soon as they are dropped.
+    pub fn free_unused_blocks(&self) -> DeviceSize {
+        let mut freed_size = 0;
+
+        for pool in self.pools.iter() {
+            let mut blocks = pool.blocks.write();
+
+            for block in mem::take(&mut *blocks) {
+                match block.try_into_region() {
+                    // Dropping the region frees the `DeviceMemory`.
+                    Ok(region) => freed_size += region.size(),
+                    Err(block) => blocks.push(block),
+                }
+            }
+        }
+
+        freed_size
+    }
+
+    /// Moves allocations out of the least used `DeviceMemory` blocks of the pools, so that those
+    /// blocks can be freed afterwards. Returns the total size of the allocations that are moved.
+    ///
+    /// Vulkan doesn't allow changing the memory that a buffer or image is bound to, so the
+    /// allocator can't move anything by itself. Instead, you pass in the allocations of the
+    /// resources that you are able to recreate. The allocator then plans which of them to move,
+    /// makes new allocations for them in the fuller blocks of the same pool, and calls `relocate`
+    /// for each new allocation. For each move, you must then:
+    ///
+    /// 1. Create a new resource and bind it to the new allocation.
+    /// 2. Record and execute a command that copies the contents of the old resource to the new
+    ///    one.
+    /// 3. Replace all uses of the old resource with the new one, and drop the old resource once
+    ///    the copy has completed.
+    ///
+    /// Once the old allocations have been dropped, [`free_unused_blocks`] will free the blocks
+    /// that were evacuated. A block can only become unused if all of its allocations are passed
+    /// in, but you don't need to pass in all allocations at once. To spread the work over several
+    /// frames, you can defragment a few of them at a time. Allocations that were not made from
+    /// the pools of this allocator, such as dedicated allocations, are never moved.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the `alignment` of an element of `allocations` is not a power of two.
+    ///
+    /// [`free_unused_blocks`]: Self::free_unused_blocks
+    pub fn defragment(
+        &self,
+        allocations: &[MovableAllocation<'_>],
+        relocate: impl FnMut(DefragmentationMove),
+    ) -> DeviceSize {
+        for movable in allocations {
+            assert!(movable.alignment.is_power_of_two());
+        }
+
+        let mut moves = Vec::new();
+
+        for pool in self.pools.iter() {
+            // Take an exclusive lock, so that the blocks don't change while planning.
+            let blocks = pool.blocks.write();
+
+            if blocks.is_empty() {
+                continue;
+            }
+
+            let block_indices: Vec<_> = allocations
+                .iter()
+                .map(|movable| {
+                    blocks.iter().position(|block| {
+                        ptr::eq(
+                            block.region().device_memory(),
+                            movable.allocation.device_memory(),
+                        )
+                    })
+                })
+                .collect();
+
+            let mut usages: Vec<_> = blocks
+                .iter()
+                .map(|block| BlockUsage {
+                    size: block.region().size(),
+                    free_size: block.free_size(),
+                    movable_size: 0,
+                })
+                .collect();
+
+            for (movable, &block_index) in allocations.iter().zip(&block_indices) {
+                if let Some(block_index) = block_index {
+                    usages[block_index].movable_size += movable.allocation.size();
+                }
+            }
+
+            let is_source = select_source_blocks(&usages);
+
+            // Fill up the fullest blocks first.
+            let mut destinations: Vec<_> = (0..blocks.len())
+                .filter(|&block_index| {
+                    !is_source[block_index]
+                        && usages[block_index].free_size < usages[block_index].size
+                })
+                .collect();
+            destinations.sort_by_key(|&block_index| usages[block_index].free_size);
+
+            // Moving the largest allocations first results in less wasted space.
+            let mut sources: Vec<_> = (0..allocations.len())
+                .filter(|&index| block_indices[index].map_or(false, |b| is_source[b]))
+                .collect();
+            sources.sort_by_key(|&index| Reverse(allocations[index].allocation.size()));
+
+            for index in sources {
+                let movable = &allocations[index];
+                let create_info = SuballocationCreateInfo {
+                    size: movable.allocation.size(),
+                    alignment: movable.alignment,
+                    allocation_type: movable.allocation.allocation_type(),
+                    ..Default::default()
+                };
+
+                // If none of the blocks have a large enough free range, the allocation stays
+                // where it is.
+                if let Some(allocation) = destinations.iter().find_map(|&block_index| unsafe {
+                    blocks[block_index]
+                        .allocate_unchecked(create_info.clone())
+                        .ok()
+                }) {
+                    moves.push(DefragmentationMove { index, allocation });
+                }
+            }
+        }
+
+        let moved_size = moves.iter().map(|m| m.allocation.size()).sum();
+        moves.into_iter().for_each(relocate);
+
+        moved_size
+    }
+
     fn validate_allocate_from_type(
         &self,
         memory_type_index: u32,
@@ -898,20 +1090,20 @@ impl<S: Suballocator> GenericMemoryAllocator<S> {
         let memory_type = &self.pools[memory_type_index as usize].memory_type;
         // VUID-VkMemoryAllocateInfo-memoryTypeIndex-01872
         assert!(
-            memory_type
+            !(memory_type
                 .property_flags
                 .contains(ash::vk::MemoryPropertyFlags::PROTECTED)
-                && !self.device.enabled_features().protected_memory,
+                && !self.device.enabled_features().protected_memory),
             "attempted to allocate from a protected memory type without the `protected_memory` \
             feature being enabled on the device",
         );
 
         // VUID-vkAllocateMemory-deviceCoherentMemory-02790
         assert!(
-            memory_type
+            !(memory_type
                 .property_flags
                 .contains(ash::vk::MemoryPropertyFlags::DEVICE_COHERENT_AMD)
-                && !self.device.enabled_features().device_coherent_memory,
+                && !self.device.enabled_features().device_coherent_memory),
             "attempted to allocate memory from a device-coherent memory type without the \
             `device_coherent_memory` feature being enabled on the device",
         );
@@ -1116,12 +1308,15 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                 }
             }
 
-            let len = blocks.len();
             drop(blocks);
             let blocks = pool.blocks.write();
-            if blocks.len() > len {
-                // Another thread beat us to it and inserted a fresh block, try to allocate from it.
-                match blocks[len].allocate_unchecked(create_info.clone()) {
+
+            // Another thread might have inserted a fresh block in the meantime, or freed space in
+            // one of the blocks. We can't only look at the blocks past the ones we already
+            // searched, because `free_unused_blocks` may have removed blocks, and the cleanup
+            // below reorders them, so search all of them again while holding the exclusive lock.
+            for block in blocks.iter().rev() {
+                match block.allocate_unchecked(create_info.clone()) {
                     Ok(alloc) => return Ok(alloc),
                     // This can happen if this is the first block that was inserted and when using
                     // the `PoolAllocator<BLOCK_SIZE>` if the allocation size is greater than
@@ -1168,6 +1363,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
                     export_handle_types,
                     dedicated_allocation: None,
                     flags: self.flags,
+                    priority: self.priority,
                     ..Default::default()
                 };
                 match DeviceMemory::allocate_unchecked(self.device.clone(), allocate_info, None) {
@@ -1403,6 +1599,7 @@ unsafe impl<S: Suballocator> MemoryAllocator for GenericMemoryAllocator<S> {
             dedicated_allocation,
             export_handle_types,
             flags: self.flags,
+            priority: self.priority,
             ..Default::default()
         };
         let mut alloc = MemoryAlloc::new(
@@ -1568,6 +1765,16 @@ pub struct GenericMemoryAllocatorCreateInfo<'b, 'e> {
     /// [`khr_device_group`]: crate::device::DeviceExtensions::khr_device_group
     pub device_address: bool,
 
+    /// The priority that the [`DeviceMemory`] blocks and dedicated allocations will be allocated
+    /// with, between `0.0` and `1.0`.
+    ///
+    /// If not `0.5`, the [`memory_priority`] feature must be enabled on the device.
+    ///
+    /// The default value is `0.5`.
+    ///
+    /// [`memory_priority`]: crate::device::Features::memory_priority
+    pub priority: f32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1584,6 +1791,7 @@ impl Default for GenericMemoryAllocatorCreateInfo<'_, '_> {
             dedicated_allocation: true,
             export_handle_types: &[],
             device_address: true,
+            priority: 0.5,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1669,3 +1877,77 @@ mod array_vec {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn free_unused_blocks_while_allocating() {
+        const THREADS: usize = 4;
+        const ITERATIONS: usize = 50;
+
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_type_index = match device
+            .physical_device()
+            .memory_properties()
+            .memory_types
+            .iter()
+            .position(|memory_type| {
+                !memory_type
+                    .property_flags
+                    .intersects(MemoryPropertyFlags::LAZILY_ALLOCATED)
+            }) {
+            Some(x) => x as u32,
+            None => return,
+        };
+
+        // The pool allocator is lock-free, so this exercises the path that searches the blocks
+        // without holding the exclusive lock.
+        let allocator = GenericMemoryAllocator::<Arc<PoolAllocator<256>>>::new(
+            device,
+            GenericMemoryAllocatorCreateInfo {
+                block_sizes: &[(0, 4 * K)],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let create_info = SuballocationCreateInfo {
+            size: 256,
+            alignment: 1,
+            ..Default::default()
+        };
+
+        thread::scope(|scope| {
+            for _ in 0..THREADS {
+                scope.spawn(|| {
+                    for _ in 0..ITERATIONS {
+                        // Enough allocations to need more than one block.
+                        let allocs: Vec<_> = (0..32)
+                            .map(|_| {
+                                allocator
+                                    .allocate_from_type(memory_type_index, create_info.clone())
+                                    .unwrap()
+                            })
+                            .collect();
+                        drop(allocs);
+                    }
+                });
+            }
+
+            scope.spawn(|| {
+                for _ in 0..THREADS * ITERATIONS {
+                    allocator.free_unused_blocks();
+                }
+            });
+        });
+
+        // All allocations have been dropped, so every block must be freed.
+        allocator.free_unused_blocks();
+        assert!(allocator
+            .pools
+            .iter()
+            .all(|pool| pool.blocks.read().is_empty()));
+    }
+}
@@ -469,6 +469,10 @@ impl MemoryAlloc {
 
         self.offset += amount;
         self.size -= amount;
+        // This is within the mapped range since the amount doesn't exceed the size.
+        self.mapped_ptr = self
+            .mapped_ptr
+            .and_then(|ptr| NonNull::new(unsafe { ptr.as_ptr().add(amount as usize) }));
     }
 
     /// Shrinks the size of the allocation to the specified `new_size`.
@@ -811,6 +815,8 @@ impl From<ImageTiling> for AllocationType {
         match tiling {
             ImageTiling::Optimal => AllocationType::NonLinear,
             ImageTiling::Linear => AllocationType::Linear,
+            // The modifier could describe either a linear or a non-linear layout.
+            ImageTiling::DrmFormatModifier => AllocationType::Unknown,
         }
     }
 }
@@ -74,6 +74,8 @@ impl DeviceMemory {
     /// - Panics if `allocate_info.allocation_size` is 0.
     /// - Panics if `allocate_info.dedicated_allocation` is `Some` and the contained buffer or
     ///   image does not belong to `device`.
+    /// - Panics if `allocate_info.flags` contains [`MemoryAllocateFlags::DEVICE_MASK`] and
+    ///   `allocate_info.device_mask` is zero or contains bits for nonexistent physical devices.
     #[inline]
     pub fn allocate(
         device: Arc<Device>,
@@ -102,9 +104,21 @@ impl DeviceMemory {
             dedicated_allocation,
             export_handle_types,
             flags,
+            device_mask: _,
+            priority: _,
             _ne: _,
         } = allocate_info;
 
+        let heap_index = device.physical_device().memory_properties().memory_types
+            [memory_type_index as usize]
+            .heap_index;
+        device.memory_tracker.record_allocate(
+            handle,
+            memory_type_index,
+            heap_index,
+            allocation_size,
+        );
+
         DeviceMemory {
             handle,
             device,
@@ -120,6 +134,10 @@ impl DeviceMemory {
 
     /// Imports a block of memory from an external source.
     ///
+    /// To use the imported memory as the backing of a buffer or image, wrap it with
+    /// [`MemoryAlloc::new`] and bind it with [`RawBuffer::bind_memory`] or
+    /// [`RawImage::bind_memory`].
+    ///
     /// # Safety
     ///
     /// - See the documentation of the variants of [`MemoryImportInfo`].
@@ -129,6 +147,10 @@ impl DeviceMemory {
     /// - Panics if `allocate_info.allocation_size` is 0.
     /// - Panics if `allocate_info.dedicated_allocation` is `Some` and the contained buffer or
     ///   image does not belong to `device`.
+    ///
+    /// [`MemoryAlloc::new`]: crate::memory::allocator::MemoryAlloc::new
+    /// [`RawBuffer::bind_memory`]: crate::buffer::sys::RawBuffer::bind_memory
+    /// [`RawImage::bind_memory`]: crate::image::sys::RawImage::bind_memory
     #[inline]
     pub unsafe fn import(
         device: Arc<Device>,
@@ -152,6 +174,8 @@ impl DeviceMemory {
             ref mut dedicated_allocation,
             export_handle_types,
             flags,
+            device_mask,
+            priority,
             _ne: _,
         } = allocate_info;
 
@@ -380,6 +404,85 @@ impl DeviceMemory {
                         // Can't validate, must be ensured by user
                     }
                 }
+                MemoryImportInfo::AndroidHardwareBuffer { buffer: _ } => {
+                    if !device
+                        .enabled_extensions()
+                        .android_external_memory_android_hardware_buffer
+                    {
+                        return Err(DeviceMemoryError::RequirementNotMet {
+                            required_for: "`allocate_info.import_info` is \
+                                `Some(MemoryImportInfo::AndroidHardwareBuffer)`",
+                            requires_one_of: RequiresOneOf {
+                                device_extensions: &[
+                                    "android_external_memory_android_hardware_buffer",
+                                ],
+                                ..Default::default()
+                            },
+                        });
+                    }
+
+                    // VUID-VkImportAndroidHardwareBufferInfoANDROID-buffer-01880
+                    // VUID-VkImportAndroidHardwareBufferInfoANDROID-buffer-01881
+                    // VUID-VkMemoryAllocateInfo-allocationSize-02383
+                    // VUID-VkMemoryAllocateInfo-pNext-02384
+                    // VUID-VkMemoryAllocateInfo-pNext-02385
+                    // VUID-VkMemoryAllocateInfo-pNext-02386
+                    // Can't validate, must be ensured by user
+                }
+                MemoryImportInfo::HostPointer {
+                    handle_type,
+                    host_pointer,
+                } => {
+                    if !device.enabled_extensions().ext_external_memory_host {
+                        return Err(DeviceMemoryError::RequirementNotMet {
+                            required_for: "`allocate_info.import_info` is \
+                                `Some(MemoryImportInfo::HostPointer)`",
+                            requires_one_of: RequiresOneOf {
+                                device_extensions: &["ext_external_memory_host"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+
+                    // VUID-VkImportMemoryHostPointerInfoEXT-handleType-parameter
+                    handle_type.validate_device(device)?;
+
+                    // VUID-VkImportMemoryHostPointerInfoEXT-handleType-01748
+                    if !matches!(
+                        handle_type,
+                        ExternalMemoryHandleType::HostAllocation
+                            | ExternalMemoryHandleType::HostMappedForeignMemory
+                    ) {
+                        return Err(DeviceMemoryError::ImportHostPointerHandleTypeNotSupported {
+                            handle_type,
+                        });
+                    }
+
+                    let required_alignment = device
+                        .physical_device()
+                        .properties()
+                        .min_imported_host_pointer_alignment
+                        .unwrap();
+
+                    // VUID-VkImportMemoryHostPointerInfoEXT-pHostPointer-01749
+                    // VUID-VkMemoryAllocateInfo-allocationSize-01745
+                    if host_pointer as DeviceSize % required_alignment != 0
+                        || allocation_size % required_alignment != 0
+                    {
+                        return Err(DeviceMemoryError::ImportHostPointerNotAligned {
+                            required_alignment,
+                        });
+                    }
+
+                    if dedicated_allocation.is_some() {
+                        return Err(DeviceMemoryError::ImportHostPointerDedicatedAllocation);
+                    }
+
+                    // VUID-VkImportMemoryHostPointerInfoEXT-pHostPointer-01750
+                    // VUID-VkImportMemoryHostPointerInfoEXT-pHostPointer-01751
+                    // VUID-VkMemoryAllocateInfo-memoryTypeIndex-01744
+                    // Can't validate, must be ensured by user
+                }
             }
         }
 
@@ -423,6 +526,31 @@ impl DeviceMemory {
             }
         }
 
+        if flags.intersects(MemoryAllocateFlags::DEVICE_MASK) {
+            let physical_device_count = device.physical_devices().len() as u32;
+
+            // VUID-VkMemoryAllocateFlagsInfo-deviceMask-00675
+            assert!(device_mask as u64 >> physical_device_count == 0);
+
+            // VUID-VkMemoryAllocateFlagsInfo-deviceMask-00676
+            assert!(device_mask != 0);
+        }
+
+        if priority != 0.5 {
+            if !device.enabled_features().memory_priority {
+                return Err(DeviceMemoryError::RequirementNotMet {
+                    required_for: "`allocate_info.priority` is not `0.5`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["memory_priority"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkMemoryPriorityAllocateInfoEXT-priority-02602
+            assert!((0.0..=1.0).contains(&priority));
+        }
+
         Ok(())
     }
 
@@ -439,6 +567,8 @@ impl DeviceMemory {
             dedicated_allocation,
             export_handle_types,
             flags,
+            device_mask,
+            priority,
             _ne: _,
         } = allocate_info;
 
@@ -479,8 +609,42 @@ impl DeviceMemory {
         let imported_handle_type = import_info.as_ref().map(|import_info| match import_info {
             MemoryImportInfo::Fd { handle_type, .. } => *handle_type,
             MemoryImportInfo::Win32 { handle_type, .. } => *handle_type,
+            MemoryImportInfo::AndroidHardwareBuffer { .. } => {
+                ExternalMemoryHandleType::AndroidHardwareBuffer
+            }
+            MemoryImportInfo::HostPointer { handle_type, .. } => *handle_type,
         });
 
+        let mut import_android_hardware_buffer_info = match import_info.as_ref() {
+            Some(&MemoryImportInfo::AndroidHardwareBuffer { buffer }) => {
+                Some(ash::vk::ImportAndroidHardwareBufferInfoANDROID {
+                    buffer,
+                    ..Default::default()
+                })
+            }
+            _ => None,
+        };
+
+        if let Some(info) = import_android_hardware_buffer_info.as_mut() {
+            allocate_info = allocate_info.push_next(info);
+        }
+
+        let mut import_host_pointer_info = match import_info.as_ref() {
+            Some(&MemoryImportInfo::HostPointer {
+                handle_type,
+                host_pointer,
+            }) => Some(ash::vk::ImportMemoryHostPointerInfoEXT {
+                handle_type: handle_type.into(),
+                p_host_pointer: host_pointer,
+                ..Default::default()
+            }),
+            _ => None,
+        };
+
+        if let Some(info) = import_host_pointer_info.as_mut() {
+            allocate_info = allocate_info.push_next(info);
+        }
+
         #[cfg(unix)]
         let mut import_fd_info = match import_info {
             Some(MemoryImportInfo::Fd { handle_type, file }) => {
@@ -520,6 +684,7 @@ impl DeviceMemory {
 
         let mut flags_info = ash::vk::MemoryAllocateFlagsInfo {
             flags: flags.into(),
+            device_mask,
             ..Default::default()
         };
 
@@ -527,6 +692,15 @@ impl DeviceMemory {
             allocate_info = allocate_info.push_next(&mut flags_info);
         }
 
+        let mut priority_info = ash::vk::MemoryPriorityAllocateInfoEXT {
+            priority,
+            ..Default::default()
+        };
+
+        if priority != 0.5 {
+            allocate_info = allocate_info.push_next(&mut priority_info);
+        }
+
         // VUID-vkAllocateMemory-maxMemoryAllocationCount-04101
         let max_allocations = device
             .physical_device()
@@ -557,6 +731,16 @@ impl DeviceMemory {
             output.assume_init()
         };
 
+        let heap_index = device.physical_device().memory_properties().memory_types
+            [memory_type_index as usize]
+            .heap_index;
+        device.memory_tracker.record_allocate(
+            handle,
+            memory_type_index,
+            heap_index,
+            allocation_size,
+        );
+
         Ok(DeviceMemory {
             handle,
             device,
@@ -658,6 +842,52 @@ impl DeviceMemory {
         output
     }
 
+    /// Changes the priority of the memory allocation, between `0.0` and `1.0`.
+    ///
+    /// This overrides the [`priority`](MemoryAllocateInfo::priority) that the memory was
+    /// allocated with.
+    ///
+    /// The [`pageable_device_local_memory`] feature must be enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `priority` is not between `0.0` and `1.0`.
+    ///
+    /// [`pageable_device_local_memory`]: crate::device::Features::pageable_device_local_memory
+    #[inline]
+    pub fn set_priority(&self, priority: f32) -> Result<(), DeviceMemoryError> {
+        self.validate_set_priority(priority)?;
+
+        unsafe { self.set_priority_unchecked(priority) };
+
+        Ok(())
+    }
+
+    fn validate_set_priority(&self, priority: f32) -> Result<(), DeviceMemoryError> {
+        if !self.device.enabled_features().pageable_device_local_memory {
+            return Err(DeviceMemoryError::RequirementNotMet {
+                required_for: "`DeviceMemory::set_priority`",
+                requires_one_of: RequiresOneOf {
+                    features: &["pageable_device_local_memory"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkSetDeviceMemoryPriorityEXT-priority-06258
+        assert!((0.0..=1.0).contains(&priority));
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn set_priority_unchecked(&self, priority: f32) {
+        let fns = self.device.fns();
+        (fns.ext_pageable_device_local_memory
+            .set_device_memory_priority_ext)(self.device.handle(), self.handle, priority);
+    }
+
     /// Exports the device memory into a Unix file descriptor. The caller owns the returned `File`.
     ///
     /// # Panics
@@ -719,12 +949,76 @@ impl DeviceMemory {
             Ok(file)
         }
     }
+
+    /// Exports the device memory into a Windows handle.
+    ///
+    /// If `handle_type` is [`ExternalMemoryHandleType::OpaqueWin32`],
+    /// [`ExternalMemoryHandleType::D3D11Texture`], [`ExternalMemoryHandleType::D3D12Heap`] or
+    /// [`ExternalMemoryHandleType::D3D12Resource`], the caller owns a reference to the underlying
+    /// resource and must eventually close the handle.
+    #[inline]
+    pub fn export_win32_handle(
+        &self,
+        handle_type: ExternalMemoryHandleType,
+    ) -> Result<*mut std::ffi::c_void, DeviceMemoryError> {
+        // VUID-VkMemoryGetWin32HandleInfoKHR-handleType-parameter
+        handle_type.validate_device(&self.device)?;
+
+        // VUID-VkMemoryGetWin32HandleInfoKHR-handleType-00664
+        if !matches!(
+            handle_type,
+            ExternalMemoryHandleType::OpaqueWin32
+                | ExternalMemoryHandleType::OpaqueWin32Kmt
+                | ExternalMemoryHandleType::D3D11Texture
+                | ExternalMemoryHandleType::D3D11TextureKmt
+                | ExternalMemoryHandleType::D3D12Heap
+                | ExternalMemoryHandleType::D3D12Resource
+        ) {
+            return Err(DeviceMemoryError::HandleTypeNotSupported { handle_type });
+        }
+
+        // VUID-VkMemoryGetWin32HandleInfoKHR-handleType-00662
+        if !ash::vk::ExternalMemoryHandleTypeFlags::from(self.export_handle_types)
+            .intersects(ash::vk::ExternalMemoryHandleTypeFlags::from(handle_type))
+        {
+            return Err(DeviceMemoryError::HandleTypeNotSupported { handle_type });
+        }
+
+        debug_assert!(self.device().enabled_extensions().khr_external_memory_win32);
+
+        #[cfg(not(windows))]
+        unreachable!("`khr_external_memory_win32` was somehow enabled on a non-Windows system");
+
+        #[cfg(windows)]
+        unsafe {
+            let fns = self.device.fns();
+            let info = ash::vk::MemoryGetWin32HandleInfoKHR {
+                memory: self.handle,
+                handle_type: handle_type.into(),
+                ..Default::default()
+            };
+
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_external_memory_win32.get_memory_win32_handle_khr)(
+                self.device.handle(),
+                &info,
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            Ok(output.assume_init())
+        }
+    }
 }
 
 impl Drop for DeviceMemory {
     #[inline]
     fn drop(&mut self) {
         unsafe {
+            // This must happen before freeing, as the handle can be reused right away.
+            self.device.memory_tracker.record_free(self.handle);
+
             let fns = self.device.fns();
             (fns.v1_0.free_memory)(self.device.handle(), self.handle, ptr::null());
             self.device.allocation_count.fetch_sub(1, Ordering::Release);
@@ -783,6 +1077,29 @@ pub struct MemoryAllocateInfo<'d> {
     /// The default value is [`MemoryAllocateFlags::empty()`].
     pub flags: MemoryAllocateFlags,
 
+    /// The physical devices of the device group to allocate the memory on, with one bit for each
+    /// element of [`Device::physical_devices`].
+    ///
+    /// This is only used if `flags` contains [`MemoryAllocateFlags::DEVICE_MASK`], in which case
+    /// it must not be zero, and must not contain bits for physical devices that are not part of
+    /// the device.
+    ///
+    /// The default value is `0`.
+    ///
+    /// [`Device::physical_devices`]: crate::device::Device::physical_devices
+    pub device_mask: u32,
+
+    /// The priority of the allocation relative to other allocations, between `0.0` and `1.0`.
+    ///
+    /// The implementation may use this to decide which allocations to move out of device-local
+    /// memory first when there is memory pressure. Higher values mean a higher priority.
+    ///
+    /// If not `0.5`, the [`memory_priority`](crate::device::Features::memory_priority) feature
+    /// must be enabled on the device.
+    ///
+    /// The default value is `0.5`.
+    pub priority: f32,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -795,6 +1112,8 @@ impl Default for MemoryAllocateInfo<'static> {
             dedicated_allocation: None,
             export_handle_types: ExternalMemoryHandleTypes::empty(),
             flags: MemoryAllocateFlags::empty(),
+            device_mask: 0,
+            priority: 0.5,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -810,6 +1129,8 @@ impl<'d> MemoryAllocateInfo<'d> {
             dedicated_allocation: Some(dedicated_allocation),
             export_handle_types: ExternalMemoryHandleTypes::empty(),
             flags: MemoryAllocateFlags::empty(),
+            device_mask: 0,
+            priority: 0.5,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -867,6 +1188,53 @@ pub enum MemoryImportInfo {
         handle_type: ExternalMemoryHandleType,
         handle: ash::vk::HANDLE,
     },
+
+    /// Import memory from an Android hardware buffer.
+    ///
+    /// The imported memory has the handle type
+    /// [`ExternalMemoryHandleType::AndroidHardwareBuffer`].
+    ///
+    /// # Safety
+    ///
+    /// - `buffer` must be a valid pointer to an `AHardwareBuffer`.
+    /// - Vulkan will acquire a reference to `buffer`, so you may release your own reference to it
+    ///   once the memory is imported.
+    /// - [`MemoryAllocateInfo::allocation_size`] must equal the `allocation_size` returned by
+    ///   [`Device::android_hardware_buffer_properties`], and
+    ///   [`MemoryAllocateInfo::memory_type_index`] must be one of the memory types returned by it.
+    /// - If the buffer is imported to be bound to an image, then
+    ///   [`MemoryAllocateInfo::dedicated_allocation`] must be that image, and the image must have
+    ///   been created with the same format, dimensions, mip levels and array layers as the
+    ///   buffer.
+    AndroidHardwareBuffer {
+        buffer: *mut ash::vk::AHardwareBuffer,
+    },
+
+    /// Import memory from a pointer to host memory.
+    ///
+    /// `handle_type` must be either [`ExternalMemoryHandleType::HostAllocation`] or
+    /// [`ExternalMemoryHandleType::HostMappedForeignMemory`]. `host_pointer` must be aligned to
+    /// the [`min_imported_host_pointer_alignment`] device property, and
+    /// [`MemoryAllocateInfo::allocation_size`] must be a multiple of it.
+    /// [`MemoryAllocateInfo::dedicated_allocation`] must be `None`.
+    ///
+    /// This allows the device to access data that already lives in host memory without first
+    /// copying it into a staging buffer. To use the memory with a buffer, the buffer must be
+    /// created with `handle_type` in its `external_memory_handle_types`.
+    ///
+    /// # Safety
+    ///
+    /// - `host_pointer` must point to `allocation_size` bytes of host memory, which must remain
+    ///   valid until the imported memory is dropped.
+    /// - Vulkan will not take ownership of the host memory.
+    /// - [`MemoryAllocateInfo::memory_type_index`] must be one of the memory types returned by
+    ///   [`Device::memory_host_pointer_properties`].
+    ///
+    /// [`min_imported_host_pointer_alignment`]: crate::device::Properties::min_imported_host_pointer_alignment
+    HostPointer {
+        handle_type: ExternalMemoryHandleType,
+        host_pointer: *mut c_void,
+    },
 }
 
 vulkan_bitflags_enum! {
@@ -938,8 +1306,10 @@ vulkan_bitflags! {
     /// A mask specifying flags for device memory allocation.
     MemoryAllocateFlags = MemoryAllocateFlags(u32);
 
-    /* TODO: enable
-    DEVICE_MASK = DEVICE_MASK,*/
+    /// Specifies that the memory is only allocated on the physical devices of the device group
+    /// that are included in [`MemoryAllocateInfo::device_mask`]. If this flag is not set, the
+    /// memory is allocated on all physical devices of the device group.
+    DEVICE_MASK = DEVICE_MASK,
 
     /// Specifies that the allocated device memory can be bound to a buffer created with the
     /// [`SHADER_DEVICE_ADDRESS`] usage. This requires that the [`buffer_device_address`] feature
@@ -955,6 +1325,26 @@ vulkan_bitflags! {
     DEVICE_ADDRESS_CAPTURE_REPLAY = DEVICE_ADDRESS_CAPTURE_REPLAY,*/
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// How memory on one physical device of a device group can be accessed by another physical
+    /// device of the group.
+    PeerMemoryFeatures = PeerMemoryFeatureFlags(u32);
+
+    /// The memory can be accessed as the source of a copy command.
+    COPY_SRC = COPY_SRC,
+
+    /// The memory can be accessed as the destination of a copy command.
+    COPY_DST = COPY_DST,
+
+    /// The memory can be read in any way, such as by a shader.
+    GENERIC_SRC = GENERIC_SRC,
+
+    /// The memory can be written in any way, such as by a shader.
+    GENERIC_DST = GENERIC_DST,
+}
+
 /// Error type returned by functions related to `DeviceMemory`.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DeviceMemoryError {
@@ -995,6 +1385,18 @@ pub enum DeviceMemoryError {
         handle_type: ExternalMemoryHandleType,
     },
 
+    /// `MemoryImportInfo::HostPointer` was provided, but `dedicated_allocation` was `Some`.
+    ImportHostPointerDedicatedAllocation,
+
+    /// The provided `MemoryImportInfo::HostPointer::handle_type` is not supported.
+    ImportHostPointerHandleTypeNotSupported {
+        handle_type: ExternalMemoryHandleType,
+    },
+
+    /// The provided `MemoryImportInfo::HostPointer::host_pointer` or `allocation_size` was not
+    /// a multiple of the `min_imported_host_pointer_alignment` device property.
+    ImportHostPointerNotAligned { required_alignment: DeviceSize },
+
     /// The provided `allocation_size` was greater than the memory type's heap size.
     MemoryTypeHeapSizeExceeded {
         allocation_size: DeviceSize,
@@ -1075,6 +1477,24 @@ impl Display for DeviceMemoryError {
                 "the provided `MemoryImportInfo::Win32::handle_type` ({:?}) is not supported",
                 handle_type,
             ),
+            Self::ImportHostPointerDedicatedAllocation => write!(
+                f,
+                "`MemoryImportInfo::HostPointer` was provided, but `dedicated_allocation` was \
+                `Some`",
+            ),
+            Self::ImportHostPointerHandleTypeNotSupported { handle_type } => write!(
+                f,
+                "the provided `MemoryImportInfo::HostPointer::handle_type` ({:?}) is not \
+                supported",
+                handle_type,
+            ),
+            Self::ImportHostPointerNotAligned { required_alignment } => write!(
+                f,
+                "the provided `MemoryImportInfo::HostPointer::host_pointer` or `allocation_size` \
+                was not a multiple of the `min_imported_host_pointer_alignment` device property \
+                ({})",
+                required_alignment,
+            ),
             Self::MemoryTypeHeapSizeExceeded {
                 allocation_size,
                 heap_size,
@@ -1581,10 +2001,10 @@ impl From<OomError> for MemoryMapError {
 
 #[cfg(test)]
 mod tests {
-    use super::MemoryAllocateInfo;
+    use super::{MemoryAllocateInfo, MemoryImportInfo};
     use crate::{
-        memory::{DeviceMemory, DeviceMemoryError, MemoryPropertyFlags},
-        OomError,
+        memory::{DeviceMemory, DeviceMemoryError, ExternalMemoryHandleType, MemoryPropertyFlags},
+        DeviceSize, OomError, VulkanObject,
     };
 
     #[test]
@@ -1601,6 +2021,68 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn memory_report() {
+        let (device, _) = gfx_dev_and_queue!();
+        let heap_index = device.physical_device().memory_properties().memory_types[0].heap_index;
+        let before = device.memory_report().heaps[heap_index as usize];
+
+        let memory = DeviceMemory::allocate(
+            device.clone(),
+            MemoryAllocateInfo {
+                allocation_size: 256,
+                memory_type_index: 0,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let report = device.memory_report();
+        let heap = report.heaps[heap_index as usize];
+        assert_eq!(heap.allocation_count, before.allocation_count + 1);
+        assert_eq!(heap.allocated_size, before.allocated_size + 256);
+        assert!(heap.peak_allocated_size >= heap.allocated_size);
+        assert!(report
+            .allocations
+            .iter()
+            .any(|allocation| allocation.handle == memory.handle()));
+
+        drop(memory);
+
+        let heap = device.memory_report().heaps[heap_index as usize];
+        assert_eq!(heap.allocation_count, before.allocation_count);
+        assert_eq!(heap.allocated_size, before.allocated_size);
+    }
+
+    #[test]
+    fn import_host_pointer_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.enabled_extensions().ext_external_memory_host {
+            return;
+        }
+
+        let mut data = vec![0u8; 4096];
+
+        match unsafe {
+            DeviceMemory::import(
+                device,
+                MemoryAllocateInfo {
+                    allocation_size: data.len() as DeviceSize,
+                    memory_type_index: 0,
+                    ..Default::default()
+                },
+                MemoryImportInfo::HostPointer {
+                    handle_type: ExternalMemoryHandleType::HostAllocation,
+                    host_pointer: data.as_mut_ptr().cast(),
+                },
+            )
+        } {
+            Err(DeviceMemoryError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn zero_size() {
         let (device, _) = gfx_dev_and_queue!();
@@ -94,6 +94,7 @@
 pub use self::device_memory::{
     DeviceMemory, DeviceMemoryError, ExternalMemoryHandleType, ExternalMemoryHandleTypes,
     MappedDeviceMemory, MemoryAllocateFlags, MemoryAllocateInfo, MemoryImportInfo, MemoryMapError,
+    PeerMemoryFeatures,
 };
 use crate::{
     buffer::{sys::RawBuffer, BufferAccess},
@@ -106,6 +107,7 @@ use std::{num::NonZeroU64, sync::Arc};
 
 pub mod allocator;
 mod device_memory;
+pub mod report;
 
 /// Properties of the memory in a physical device.
 #[derive(Clone, Debug)]
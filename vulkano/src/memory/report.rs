@@ -0,0 +1,280 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Statistics about the memory allocations of a device.
+//!
+//! Every [`DeviceMemory`] allocation is recorded by the device that it belongs to, together with
+//! the name that was given to it with [`Device::set_debug_utils_object_name`], if any. You can
+//! retrieve the totals of each memory heap, their high-water marks, and the list of allocations
+//! that are still alive with [`Device::memory_report`]. This is useful to find out where memory
+//! is going, and to find leaks.
+//!
+//! The [`ext_device_memory_report`] extension additionally lets the implementation report the
+//! memory that it allocates on its own, such as the memory that backs other Vulkan objects. See
+//! [`Device::with_device_memory_report_callback`].
+//!
+//! [`DeviceMemory`]: super::DeviceMemory
+//! [`Device::set_debug_utils_object_name`]: crate::device::Device::set_debug_utils_object_name
+//! [`Device::memory_report`]: crate::device::Device::memory_report
+//! [`ext_device_memory_report`]: crate::device::DeviceExtensions::ext_device_memory_report
+//! [`Device::with_device_memory_report_callback`]: crate::device::Device::with_device_memory_report_callback
+
+use crate::{instance::debug::ObjectType, macros::vulkan_enum, DeviceSize};
+use ahash::HashMap;
+use parking_lot::Mutex;
+use std::{
+    cmp::Reverse,
+    ffi::c_void,
+    fmt::{Display, Error as FmtError, Formatter},
+    panic::{catch_unwind, AssertUnwindSafe, RefUnwindSafe},
+    sync::Arc,
+};
+
+/// A snapshot of the memory allocations of a device, as returned by
+/// [`Device::memory_report`](crate::device::Device::memory_report).
+///
+/// The `Display` implementation formats the report as a human-readable summary.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct MemoryReport {
+    /// The statistics of each memory heap of the physical device, in the same order as
+    /// [`MemoryProperties::memory_heaps`](super::MemoryProperties::memory_heaps).
+    pub heaps: Vec<MemoryHeapReport>,
+
+    /// The `DeviceMemory` allocations that are currently alive, sorted by their size from largest
+    /// to smallest.
+    pub allocations: Vec<DeviceMemoryReport>,
+}
+
+impl Display for MemoryReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        for (heap_index, heap) in self.heaps.iter().enumerate() {
+            writeln!(
+                f,
+                "heap {}: {} allocations, {} bytes allocated (peak {} bytes)",
+                heap_index, heap.allocation_count, heap.allocated_size, heap.peak_allocated_size,
+            )?;
+        }
+
+        writeln!(f, "{} live allocations:", self.allocations.len())?;
+
+        for allocation in &self.allocations {
+            writeln!(
+                f,
+                "    {}: {} bytes from memory type {} (heap {})",
+                allocation.name.as_deref().unwrap_or("<unnamed>"),
+                allocation.allocation_size,
+                allocation.memory_type_index,
+                allocation.heap_index,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The statistics of a single memory heap in a [`MemoryReport`].
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct MemoryHeapReport {
+    /// The number of `DeviceMemory` allocations that are currently alive in the heap.
+    pub allocation_count: u32,
+
+    /// The total size in bytes of the `DeviceMemory` allocations that are currently alive in the
+    /// heap.
+    pub allocated_size: DeviceSize,
+
+    /// The highest value that `allocated_size` has reached since the device was created.
+    pub peak_allocated_size: DeviceSize,
+}
+
+/// A single live `DeviceMemory` allocation in a [`MemoryReport`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DeviceMemoryReport {
+    /// The handle of the `DeviceMemory`.
+    pub handle: ash::vk::DeviceMemory,
+
+    /// The index of the memory type that the memory was allocated from.
+    pub memory_type_index: u32,
+
+    /// The index of the memory heap that the memory type belongs to.
+    pub heap_index: u32,
+
+    /// The size of the allocation in bytes.
+    pub allocation_size: DeviceSize,
+
+    /// The name that was given to the memory with
+    /// [`set_debug_utils_object_name`](crate::device::Device::set_debug_utils_object_name), if
+    /// any.
+    pub name: Option<String>,
+}
+
+/// Keeps track of the `DeviceMemory` allocations of a device.
+#[derive(Debug)]
+pub(crate) struct MemoryTracker {
+    state: Mutex<MemoryTrackerState>,
+}
+
+#[derive(Debug)]
+struct MemoryTrackerState {
+    heaps: Vec<MemoryHeapReport>,
+    allocations: HashMap<ash::vk::DeviceMemory, DeviceMemoryReport>,
+}
+
+impl MemoryTracker {
+    pub(crate) fn new(memory_heap_count: usize) -> Self {
+        MemoryTracker {
+            state: Mutex::new(MemoryTrackerState {
+                heaps: vec![MemoryHeapReport::default(); memory_heap_count],
+                allocations: HashMap::default(),
+            }),
+        }
+    }
+
+    pub(crate) fn record_allocate(
+        &self,
+        handle: ash::vk::DeviceMemory,
+        memory_type_index: u32,
+        heap_index: u32,
+        allocation_size: DeviceSize,
+    ) {
+        let mut state = self.state.lock();
+
+        let heap = &mut state.heaps[heap_index as usize];
+        heap.allocation_count += 1;
+        heap.allocated_size += allocation_size;
+        heap.peak_allocated_size = heap.peak_allocated_size.max(heap.allocated_size);
+
+        state.allocations.insert(
+            handle,
+            DeviceMemoryReport {
+                handle,
+                memory_type_index,
+                heap_index,
+                allocation_size,
+                name: None,
+            },
+        );
+    }
+
+    pub(crate) fn record_free(&self, handle: ash::vk::DeviceMemory) {
+        let mut state = self.state.lock();
+
+        if let Some(allocation) = state.allocations.remove(&handle) {
+            let heap = &mut state.heaps[allocation.heap_index as usize];
+            heap.allocation_count -= 1;
+            heap.allocated_size -= allocation.allocation_size;
+        }
+    }
+
+    pub(crate) fn record_name(&self, handle: ash::vk::DeviceMemory, name: Option<&str>) {
+        if let Some(allocation) = self.state.lock().allocations.get_mut(&handle) {
+            allocation.name = name.map(ToOwned::to_owned);
+        }
+    }
+
+    pub(crate) fn report(&self) -> MemoryReport {
+        let state = self.state.lock();
+
+        let mut allocations: Vec<_> = state.allocations.values().cloned().collect();
+        allocations.sort_by_key(|allocation| Reverse(allocation.allocation_size));
+
+        MemoryReport {
+            heaps: state.heaps.clone(),
+            allocations,
+        }
+    }
+}
+
+/// A callback that the implementation calls to report device memory events.
+///
+/// The callback must not make any calls to the Vulkan API. If the callback panics, the panic is
+/// caught and ignored.
+pub type DeviceMemoryReportCallback =
+    Arc<dyn Fn(&DeviceMemoryReportCallbackData) + RefUnwindSafe + Send + Sync>;
+
+/// The data of a device memory event, passed to a [`DeviceMemoryReportCallback`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DeviceMemoryReportCallbackData {
+    /// The type of the event.
+    pub ty: DeviceMemoryReportEventType,
+
+    /// A unique identifier of the memory object that the event is about. The same memory object
+    /// can be allocated and freed multiple times, but only one memory object can have this
+    /// identifier at a time.
+    pub memory_object_id: u64,
+
+    /// The size of the memory object in bytes, or `0` for free and unimport events.
+    pub size: DeviceSize,
+
+    /// The type of the Vulkan object that the memory object is associated with.
+    pub object_type: ObjectType,
+
+    /// The raw handle of the Vulkan object that the memory object is associated with.
+    pub object_handle: u64,
+
+    /// The index of the memory heap that the memory object was allocated from, if applicable.
+    pub heap_index: u32,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type of a device memory event.
+    DeviceMemoryReportEventType = DeviceMemoryReportEventTypeEXT(i32);
+
+    /// Memory was allocated.
+    Allocate = ALLOCATE,
+
+    /// Memory was freed.
+    Free = FREE,
+
+    /// Memory was imported from an external source.
+    Import = IMPORT,
+
+    /// Imported memory was released.
+    Unimport = UNIMPORT,
+
+    /// An allocation of memory failed.
+    AllocationFailed = ALLOCATION_FAILED,
+}
+
+pub(crate) unsafe extern "system" fn device_memory_report_trampoline(
+    callback_data: *const ash::vk::DeviceMemoryReportCallbackDataEXT,
+    user_data: *mut c_void,
+) {
+    // Since we box the closure, the type system doesn't detect that the `UnwindSafe`
+    // bound is enforced. Therefore we enforce it manually.
+    let _ = catch_unwind(AssertUnwindSafe(move || {
+        let user_callback = user_data as *mut DeviceMemoryReportCallback as *const _;
+        let user_callback: &DeviceMemoryReportCallback = &*user_callback;
+
+        let callback_data = &*callback_data;
+
+        let ty = match callback_data.ty.try_into() {
+            Ok(ty) => ty,
+            Err(_) => return,
+        };
+        let object_type = callback_data
+            .object_type
+            .try_into()
+            .unwrap_or(ObjectType::Unknown);
+
+        user_callback(&DeviceMemoryReportCallbackData {
+            ty,
+            memory_object_id: callback_data.memory_object_id,
+            size: callback_data.size,
+            object_type,
+            object_handle: callback_data.object_handle,
+            heap_index: callback_data.heap_index,
+        });
+    }));
+}
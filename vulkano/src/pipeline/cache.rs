@@ -20,9 +20,27 @@
 //! Once that is done, you can extract the data from the cache and store it. See the documentation
 //! of [`get_data`](crate::pipeline::cache::PipelineCache::get_data) for example of how to store the data
 //! on the disk, and [`with_data`](crate::pipeline::cache::PipelineCache::with_data) for how to reload it.
-
-use crate::{device::Device, OomError, VulkanError, VulkanObject};
-use std::{mem::MaybeUninit, ptr, sync::Arc};
+//!
+//! The [`save_to_file`](crate::pipeline::cache::PipelineCache::save_to_file) and
+//! [`load_from_file`](crate::pipeline::cache::PipelineCache::load_from_file) helpers do this for
+//! you. When loading, they check the [header](PipelineCacheHeader) of the data first, so that data
+//! that was created by a different device or driver version is discarded instead of being passed
+//! to the implementation.
+
+use crate::{
+    device::{physical::PhysicalDevice, Device},
+    OomError, VulkanError, VulkanObject,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    mem::MaybeUninit,
+    path::Path,
+    ptr,
+    sync::Arc,
+};
 
 /// Opaque cache that contains pipeline objects.
 ///
@@ -45,7 +63,9 @@ impl PipelineCache {
     ///
     /// This example loads a cache from a file, if it exists.
     /// See [`get_data`](#method.get_data) for how to store the data in a file.
-    /// TODO: there's a header in the cached data that must be checked ; talk about this
+    /// [`PipelineCacheHeader`] can be used to check that the data was created by the same device
+    /// and driver version, or use [`load_from_file`](Self::load_from_file) which does this
+    /// automatically.
     ///
     /// ```
     /// # use std::sync::Arc;
@@ -138,39 +158,63 @@ impl PipelineCache {
     /// Merges other pipeline caches into this one.
     ///
     /// It is `self` that is modified here. The pipeline caches passed as parameter are untouched.
+    /// This can be used to combine caches that were filled by creating pipelines on several
+    /// threads.
     ///
     /// # Panics
     ///
-    /// - Panics if `self` is included in the list of other pipelines.
+    /// - Panics if `self` is included in `src_caches`.
+    /// - Panics if an element of `src_caches` does not belong to the same device as `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use vulkano::device::Device;
+    /// use vulkano::pipeline::cache::PipelineCache;
+    /// # let device: Arc<Device> = return;
+    ///
+    /// let main_cache = PipelineCache::empty(device.clone()).unwrap();
+    /// let worker_caches = [
+    ///     PipelineCache::empty(device.clone()).unwrap(),
+    ///     PipelineCache::empty(device.clone()).unwrap(),
+    /// ];
     ///
+    /// // ...create pipelines with each of the worker caches on a separate thread...
+    ///
+    /// main_cache.merge(&[&worker_caches[0], &worker_caches[1]]).unwrap();
+    /// ```
     // FIXME: vkMergePipelineCaches is not thread safe for the destination cache
-    // TODO: write example
     pub fn merge<'a>(
         &self,
-        pipelines: impl IntoIterator<Item = &'a &'a Arc<PipelineCache>>,
+        src_caches: impl IntoIterator<Item = &'a &'a Arc<PipelineCache>>,
     ) -> Result<(), OomError> {
-        unsafe {
-            let fns = self.device.fns();
+        let src_caches_vk: Vec<_> = src_caches
+            .into_iter()
+            .map(|src_cache| {
+                // VUID-vkMergePipelineCaches-dstCache-00770
+                assert!(!ptr::eq(&***src_cache, self));
+
+                // VUID-vkMergePipelineCaches-pSrcCaches-parent
+                assert_eq!(self.device, src_cache.device);
 
-            let pipelines = pipelines
-                .into_iter()
-                .map(|pipeline| {
-                    assert!(&***pipeline as *const _ != self as *const _);
-                    pipeline.cache
-                })
-                .collect::<Vec<_>>();
+                src_cache.cache
+            })
+            .collect();
 
+        unsafe {
+            let fns = self.device.fns();
             (fns.v1_0.merge_pipeline_caches)(
                 self.device.handle(),
                 self.cache,
-                pipelines.len() as u32,
-                pipelines.as_ptr(),
+                src_caches_vk.len() as u32,
+                src_caches_vk.as_ptr(),
             )
             .result()
             .map_err(VulkanError::from)?;
-
-            Ok(())
         }
+
+        Ok(())
     }
 
     /// Obtains the data from the cache.
@@ -238,6 +282,62 @@ impl PipelineCache {
 
         Ok(data)
     }
+
+    /// Writes the data of the cache to the file at `path`.
+    ///
+    /// The data is first written to a temporary file next to `path`, which then replaces `path`.
+    /// This ensures that a partially written file is never left behind if the application is
+    /// interrupted.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), PipelineCacheError> {
+        let path = path.as_ref();
+        let data = self.get_data()?;
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        if let Err(err) = fs::write(&tmp_path, data) {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(err.into());
+        }
+
+        fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+
+    /// Builds a new pipeline cache from the data in the file at `path`, that was previously
+    /// written by [`save_to_file`](Self::save_to_file).
+    ///
+    /// If the file does not exist, or if its [header](PipelineCacheHeader) shows that the data
+    /// was created by a different device or driver version, an empty cache is returned instead.
+    ///
+    /// # Safety
+    ///
+    /// - Apart from the header, the data is passed to the Vulkan implementation as is. The file
+    ///   must contain data that was obtained with [`get_data`](Self::get_data) or
+    ///   [`save_to_file`](Self::save_to_file), and must not have been modified since.
+    pub unsafe fn load_from_file(
+        device: Arc<Device>,
+        path: impl AsRef<Path>,
+    ) -> Result<Arc<PipelineCache>, PipelineCacheError> {
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                return Ok(PipelineCache::empty(device)?)
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let is_compatible = PipelineCacheHeader::from_data(&data).map_or(false, |header| {
+            header.is_compatible(device.physical_device())
+        });
+
+        if is_compatible {
+            Ok(PipelineCache::with_data(device, &data)?)
+        } else {
+            Ok(PipelineCache::empty(device)?)
+        }
+    }
 }
 
 unsafe impl VulkanObject for PipelineCache {
@@ -249,6 +349,107 @@ unsafe impl VulkanObject for PipelineCache {
     }
 }
 
+/// The header at the start of the data of a pipeline cache.
+///
+/// The header identifies the device and driver that created the data. An implementation will
+/// only make use of data that it created itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PipelineCacheHeader {
+    /// The vendor ID of the physical device that created the data.
+    pub vendor_id: u32,
+
+    /// The device ID of the physical device that created the data.
+    pub device_id: u32,
+
+    /// The pipeline cache UUID of the physical device that created the data.
+    pub pipeline_cache_uuid: [u8; 16],
+}
+
+impl PipelineCacheHeader {
+    // The size of a version one header.
+    const SIZE: usize = 32;
+
+    /// Reads the header from the start of `data`.
+    ///
+    /// Returns `None` if `data` does not start with a valid header of a version that vulkano
+    /// knows about.
+    pub fn from_data(data: &[u8]) -> Option<Self> {
+        if data.len() < Self::SIZE {
+            return None;
+        }
+
+        // All values are stored with the least significant byte first.
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+
+        let header_size = read_u32(0);
+        let header_version = read_u32(4);
+
+        if header_version != ash::vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+            || (header_size as usize) < Self::SIZE
+            || header_size as usize > data.len()
+        {
+            return None;
+        }
+
+        Some(PipelineCacheHeader {
+            vendor_id: read_u32(8),
+            device_id: read_u32(12),
+            pipeline_cache_uuid: data[16..32].try_into().unwrap(),
+        })
+    }
+
+    /// Returns whether the data was created by `physical_device` with its current driver.
+    pub fn is_compatible(&self, physical_device: &PhysicalDevice) -> bool {
+        let properties = physical_device.properties();
+
+        self.vendor_id == properties.vendor_id
+            && self.device_id == properties.device_id
+            && self.pipeline_cache_uuid == properties.pipeline_cache_uuid
+    }
+}
+
+/// Error that can happen when saving or loading a pipeline cache.
+#[derive(Debug)]
+pub enum PipelineCacheError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// An error occurred while reading or writing the file.
+    IoError(io::Error),
+}
+
+impl Error for PipelineCacheError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            Self::IoError(err) => Some(err),
+        }
+    }
+}
+
+impl Display for PipelineCacheError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::IoError(_) => write!(f, "an error occurred while reading or writing the file"),
+        }
+    }
+}
+
+impl From<OomError> for PipelineCacheError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<io::Error> for PipelineCacheError {
+    fn from(err: io::Error) -> Self {
+        Self::IoError(err)
+    }
+}
+
 impl Drop for PipelineCache {
     #[inline]
     fn drop(&mut self) {
@@ -262,10 +463,56 @@ impl Drop for PipelineCache {
 #[cfg(test)]
 mod tests {
     use crate::{
-        pipeline::{cache::PipelineCache, ComputePipeline},
+        pipeline::{
+            cache::{PipelineCache, PipelineCacheHeader},
+            ComputePipeline,
+        },
         shader::ShaderModule,
     };
 
+    #[test]
+    fn header_from_data() {
+        let mut data = vec![0u8; 40];
+        data[0..4].copy_from_slice(&32u32.to_le_bytes());
+        data[4..8].copy_from_slice(&1u32.to_le_bytes());
+        data[8..12].copy_from_slice(&0x10de_u32.to_le_bytes());
+        data[12..16].copy_from_slice(&0x1234_u32.to_le_bytes());
+        data[16..32].copy_from_slice(&[7; 16]);
+
+        let header = PipelineCacheHeader::from_data(&data).unwrap();
+        assert_eq!(header.vendor_id, 0x10de);
+        assert_eq!(header.device_id, 0x1234);
+        assert_eq!(header.pipeline_cache_uuid, [7; 16]);
+
+        assert!(PipelineCacheHeader::from_data(&data[..16]).is_none());
+
+        data[4..8].copy_from_slice(&2u32.to_le_bytes());
+        assert!(PipelineCacheHeader::from_data(&data).is_none());
+    }
+
+    #[test]
+    fn save_and_load_file() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let path = std::env::temp_dir().join(format!(
+            "vulkano_pipeline_cache_test_{}.bin",
+            std::process::id()
+        ));
+
+        let cache = PipelineCache::empty(device.clone()).unwrap();
+        cache.save_to_file(&path).unwrap();
+
+        let data = std::fs::read(&path).unwrap();
+        if let Some(header) = PipelineCacheHeader::from_data(&data) {
+            assert!(header.is_compatible(device.physical_device()));
+        }
+
+        let loaded = unsafe { PipelineCache::load_from_file(device, &path).unwrap() };
+        assert_eq!(cache.get_data().unwrap(), loaded.get_data().unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
     #[test]
     fn merge_self_forbidden() {
         let (device, _queue) = gfx_dev_and_queue!();
@@ -30,17 +30,23 @@ use crate::{
     device::{Device, DeviceOwned},
     pipeline::{
         cache::PipelineCache,
+        executable::{
+            self, PipelineExecutableError, PipelineExecutableInternalRepresentation,
+            PipelineExecutableProperties, PipelineExecutableStatistic,
+        },
         layout::{PipelineLayout, PipelineLayoutCreationError, PipelineLayoutSupersetError},
         Pipeline, PipelineBindPoint,
     },
-    shader::{DescriptorBindingRequirements, EntryPoint, SpecializationConstants},
-    DeviceSize, OomError, VulkanError, VulkanObject,
+    shader::{
+        specialization_constants_compatible, spirv::ExecutionModel, DescriptorBindingRequirements,
+        EntryPoint, ShaderCreationError, ShaderModule, ShaderStages, SpecializationConstants,
+    },
+    OomError, RequiresOneOf, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use std::{
     error::Error,
     fmt::{Debug, Display, Error as FmtError, Formatter},
-    mem,
     mem::MaybeUninit,
     num::NonZeroU64,
     ptr,
@@ -114,6 +120,43 @@ impl ComputePipeline {
         }
     }
 
+    /// Builds a new `ComputePipeline` from SPIR-V code that is only known at runtime.
+    ///
+    /// A shader module is created from `words`, and the compute entry point named
+    /// `entry_point_name` is reflected to create the descriptor set layouts and the pipeline
+    /// layout, in the same way as [`new`](Self::new). The reflected descriptor bindings can then be
+    /// retrieved with [`descriptor_binding_requirements`](Pipeline::descriptor_binding_requirements),
+    /// and the created layout with [`layout`](Pipeline::layout).
+    ///
+    /// Specialization constants that are not given a value keep the value that they have in the
+    /// shader, so `&()` can be passed if none need to be changed. To set them at runtime, use
+    /// [`SpecializationConstantMap`](crate::shader::SpecializationConstantMap).
+    ///
+    /// # Safety
+    ///
+    /// - `words` must be valid SPIR-V code, as for [`ShaderModule::from_words`].
+    pub unsafe fn from_spirv<Css>(
+        device: Arc<Device>,
+        words: &[u32],
+        entry_point_name: &str,
+        specialization_constants: &Css,
+        cache: Option<Arc<PipelineCache>>,
+    ) -> Result<Arc<ComputePipeline>, ComputePipelineCreationError>
+    where
+        Css: SpecializationConstants,
+    {
+        let module = ShaderModule::from_words(device.clone(), words)?;
+        let entry_point = module
+            .entry_point_with_execution(entry_point_name, ExecutionModel::GLCompute)
+            .ok_or(ComputePipelineCreationError::EntryPointNotFound)?;
+
+        if !specialization_constants_compatible(&entry_point, specialization_constants) {
+            return Err(ComputePipelineCreationError::IncompatibleSpecializationConstants);
+        }
+
+        Self::new(device, entry_point, specialization_constants, cache, |_| {})
+    }
+
     /// Builds a new `ComputePipeline` with a specific pipeline layout.
     ///
     /// An error will be returned if the pipeline layout isn't a superset of what the shader
@@ -128,17 +171,44 @@ impl ComputePipeline {
     where
         Css: SpecializationConstants,
     {
-        let spec_descriptors = Css::descriptors();
+        if !specialization_constants_compatible(&shader, specialization_constants) {
+            return Err(ComputePipelineCreationError::IncompatibleSpecializationConstants);
+        }
 
-        for (constant_id, reqs) in shader.specialization_constant_requirements() {
-            let map_entry = spec_descriptors
-                .iter()
-                .find(|desc| desc.constant_id == constant_id)
-                .ok_or(ComputePipelineCreationError::IncompatibleSpecializationConstants)?;
+        layout.ensure_compatible_with_shader(
+            shader.descriptor_binding_requirements(),
+            shader.push_constant_requirements(),
+        )?;
 
-            if map_entry.size as DeviceSize != reqs.size {
-                return Err(ComputePipelineCreationError::IncompatibleSpecializationConstants);
-            }
+        unsafe {
+            ComputePipeline::with_unchecked_pipeline_layout(
+                device,
+                shader,
+                specialization_constants,
+                layout,
+                cache,
+            )
+        }
+    }
+
+    /// Builds a new `ComputePipeline` with a specific pipeline layout, and control over the size
+    /// of the subgroups that the shader is executed with.
+    ///
+    /// An error will be returned if the pipeline layout isn't a superset of what the shader
+    /// uses, or if `subgroup_size_control` is not supported by the device.
+    pub fn with_subgroup_size_control<Css>(
+        device: Arc<Device>,
+        shader: EntryPoint<'_>,
+        specialization_constants: &Css,
+        layout: Arc<PipelineLayout>,
+        cache: Option<Arc<PipelineCache>>,
+        subgroup_size_control: SubgroupSizeControl,
+    ) -> Result<Arc<ComputePipeline>, ComputePipelineCreationError>
+    where
+        Css: SpecializationConstants,
+    {
+        if !specialization_constants_compatible(&shader, specialization_constants) {
+            return Err(ComputePipelineCreationError::IncompatibleSpecializationConstants);
         }
 
         layout.ensure_compatible_with_shader(
@@ -146,17 +216,124 @@ impl ComputePipeline {
             shader.push_constant_requirements(),
         )?;
 
+        Self::validate_subgroup_size_control(&device, &subgroup_size_control)?;
+
         unsafe {
-            ComputePipeline::with_unchecked_pipeline_layout(
+            Self::new_unchecked(
                 device,
                 shader,
                 specialization_constants,
                 layout,
                 cache,
+                &subgroup_size_control,
             )
         }
     }
 
+    fn validate_subgroup_size_control(
+        device: &Device,
+        subgroup_size_control: &SubgroupSizeControl,
+    ) -> Result<(), ComputePipelineCreationError> {
+        let &SubgroupSizeControl {
+            allow_varying_subgroup_size,
+            require_full_subgroups,
+            required_subgroup_size,
+            _ne: _,
+        } = subgroup_size_control;
+
+        let properties = device.physical_device().properties();
+
+        if allow_varying_subgroup_size {
+            // VUID-VkPipelineShaderStageCreateInfo-flags-02784
+            if !device.enabled_features().subgroup_size_control {
+                return Err(ComputePipelineCreationError::RequirementNotMet {
+                    required_for: "`subgroup_size_control.allow_varying_subgroup_size` is `true`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["subgroup_size_control"],
+                        ..Default::default()
+                    },
+                });
+            }
+        }
+
+        if require_full_subgroups {
+            // VUID-VkPipelineShaderStageCreateInfo-flags-02785
+            if !device.enabled_features().compute_full_subgroups {
+                return Err(ComputePipelineCreationError::RequirementNotMet {
+                    required_for: "`subgroup_size_control.require_full_subgroups` is `true`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["compute_full_subgroups"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // TODO: the local workgroup size is not reflected from the shader, so these can't be
+            // checked yet.
+            // VUID-VkPipelineShaderStageCreateInfo-flags-02758
+            // VUID-VkPipelineShaderStageCreateInfo-flags-02759
+        }
+
+        if let Some(required_subgroup_size) = required_subgroup_size {
+            // VUID-VkPipelineShaderStageCreateInfo-pNext-02755
+            if !device.enabled_features().subgroup_size_control {
+                return Err(ComputePipelineCreationError::RequirementNotMet {
+                    required_for: "`subgroup_size_control.required_subgroup_size` is `Some`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["subgroup_size_control"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkPipelineShaderStageCreateInfo-pNext-02754
+            if allow_varying_subgroup_size {
+                return Err(
+                    ComputePipelineCreationError::RequiredSubgroupSizeWithVaryingSubgroupSize,
+                );
+            }
+
+            // VUID-VkPipelineShaderStageCreateInfo-pNext-02756
+            if !properties
+                .required_subgroup_size_stages
+                .unwrap_or_default()
+                .intersects(ShaderStages::COMPUTE)
+            {
+                return Err(ComputePipelineCreationError::RequiredSubgroupSizeNotSupported);
+            }
+
+            // VUID-VkPipelineShaderStageRequiredSubgroupSizeCreateInfo-requiredSubgroupSize-02760
+            if !required_subgroup_size.is_power_of_two() {
+                return Err(
+                    ComputePipelineCreationError::RequiredSubgroupSizeNotPowerOfTwo {
+                        required_subgroup_size,
+                    },
+                );
+            }
+
+            let min_supported = properties.min_subgroup_size.unwrap_or(1);
+            let max_supported = properties.max_subgroup_size.unwrap_or(u32::MAX);
+
+            // VUID-VkPipelineShaderStageRequiredSubgroupSizeCreateInfo-requiredSubgroupSize-02761
+            // VUID-VkPipelineShaderStageRequiredSubgroupSizeCreateInfo-requiredSubgroupSize-02762
+            if !(min_supported..=max_supported).contains(&required_subgroup_size) {
+                return Err(
+                    ComputePipelineCreationError::RequiredSubgroupSizeOutOfRange {
+                        required_subgroup_size,
+                        min_supported,
+                        max_supported,
+                    },
+                );
+            }
+
+            // TODO: the local workgroup size is not reflected from the shader, so this can't be
+            // checked yet.
+            // VUID-VkPipelineShaderStageCreateInfo-pNext-02757
+        }
+
+        Ok(())
+    }
+
     /// Same as `with_pipeline_layout`, but doesn't check whether the pipeline layout is a
     /// superset of what the shader expects.
     pub unsafe fn with_unchecked_pipeline_layout<Css>(
@@ -169,19 +346,66 @@ impl ComputePipeline {
     where
         Css: SpecializationConstants,
     {
+        Self::new_unchecked(
+            device,
+            shader,
+            specialization_constants,
+            layout,
+            cache,
+            &SubgroupSizeControl::default(),
+        )
+    }
+
+    unsafe fn new_unchecked<Css>(
+        device: Arc<Device>,
+        shader: EntryPoint<'_>,
+        specialization_constants: &Css,
+        layout: Arc<PipelineLayout>,
+        cache: Option<Arc<PipelineCache>>,
+        subgroup_size_control: &SubgroupSizeControl,
+    ) -> Result<Arc<ComputePipeline>, ComputePipelineCreationError>
+    where
+        Css: SpecializationConstants,
+    {
+        let &SubgroupSizeControl {
+            allow_varying_subgroup_size,
+            require_full_subgroups,
+            required_subgroup_size,
+            _ne: _,
+        } = subgroup_size_control;
+
         let fns = device.fns();
 
         let handle = {
-            let spec_descriptors = Css::descriptors();
+            let spec_descriptors = specialization_constants.map_entries();
+            let spec_data = specialization_constants.data();
             let specialization = ash::vk::SpecializationInfo {
                 map_entry_count: spec_descriptors.len() as u32,
                 p_map_entries: spec_descriptors.as_ptr() as *const _,
-                data_size: mem::size_of_val(specialization_constants),
-                p_data: specialization_constants as *const Css as *const _,
+                data_size: spec_data.len(),
+                p_data: spec_data.as_ptr() as *const _,
             };
 
-            let stage = ash::vk::PipelineShaderStageCreateInfo {
-                flags: ash::vk::PipelineShaderStageCreateFlags::empty(),
+            let mut stage_flags = ash::vk::PipelineShaderStageCreateFlags::empty();
+
+            if allow_varying_subgroup_size {
+                stage_flags |= ash::vk::PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE;
+            }
+
+            if require_full_subgroups {
+                stage_flags |= ash::vk::PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS;
+            }
+
+            let mut required_subgroup_size_vk =
+                required_subgroup_size.map(|required_subgroup_size| {
+                    ash::vk::PipelineShaderStageRequiredSubgroupSizeCreateInfo {
+                        required_subgroup_size,
+                        ..Default::default()
+                    }
+                });
+
+            let mut stage = ash::vk::PipelineShaderStageCreateInfo {
+                flags: stage_flags,
                 stage: ash::vk::ShaderStageFlags::COMPUTE,
                 module: shader.module().handle(),
                 p_name: shader.name().as_ptr(),
@@ -193,8 +417,28 @@ impl ComputePipeline {
                 ..Default::default()
             };
 
+            if let Some(info) = required_subgroup_size_vk.as_mut() {
+                info.p_next = stage.p_next as *mut _;
+                stage.p_next = info as *const _ as *const _;
+            }
+
+            let mut flags = ash::vk::PipelineCreateFlags::empty();
+
+            if device.enabled_features().pipeline_executable_info {
+                flags |= ash::vk::PipelineCreateFlags::CAPTURE_STATISTICS_KHR
+                    | ash::vk::PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS_KHR;
+            }
+
+            if layout
+                .set_layouts()
+                .iter()
+                .any(|set_layout| set_layout.descriptor_buffer())
+            {
+                flags |= ash::vk::PipelineCreateFlags::DESCRIPTOR_BUFFER_EXT;
+            }
+
             let infos = ash::vk::ComputePipelineCreateInfo {
-                flags: ash::vk::PipelineCreateFlags::empty(),
+                flags,
                 stage,
                 layout: layout.handle(),
                 base_pipeline_handle: ash::vk::Pipeline::null(),
@@ -247,6 +491,80 @@ impl ComputePipeline {
     pub fn device(&self) -> &Arc<Device> {
         &self.device
     }
+
+    /// Returns the properties of the executables that the pipeline was compiled into.
+    ///
+    /// The [`pipeline_executable_info`] feature must be enabled on the device.
+    ///
+    /// [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutableError> {
+        executable::validate_executable_properties(&self.device)?;
+
+        unsafe { Ok(self.executable_properties_unchecked()?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_properties_unchecked(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, VulkanError> {
+        executable::executable_properties_unchecked(&self.device, self.handle)
+    }
+
+    /// Returns the statistics of the executable with index `executable_index`.
+    ///
+    /// The [`pipeline_executable_info`] feature must be enabled on the device.
+    ///
+    /// [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutableError> {
+        executable::validate_executable_index(&self.device, self.handle, executable_index)?;
+
+        unsafe { Ok(self.executable_statistics_unchecked(executable_index)?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_statistics_unchecked(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, VulkanError> {
+        executable::executable_statistics_unchecked(&self.device, self.handle, executable_index)
+    }
+
+    /// Returns the internal representations of the executable with index `executable_index`.
+    ///
+    /// The [`pipeline_executable_info`] feature must be enabled on the device.
+    ///
+    /// [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+        executable::validate_executable_index(&self.device, self.handle, executable_index)?;
+
+        unsafe { Ok(self.executable_internal_representations_unchecked(executable_index)?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_internal_representations_unchecked(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, VulkanError> {
+        executable::executable_internal_representations_unchecked(
+            &self.device,
+            self.handle,
+            executable_index,
+        )
+    }
 }
 
 impl Pipeline for ComputePipeline {
@@ -307,6 +625,65 @@ impl Drop for ComputePipeline {
     }
 }
 
+/// Parameters that control the size of the subgroups that a compute shader is executed with.
+///
+/// The subgroup sizes that the physical device supports are given by the
+/// [`min_subgroup_size`](crate::device::Properties::min_subgroup_size) and
+/// [`max_subgroup_size`](crate::device::Properties::max_subgroup_size) properties, and the size
+/// that is used by default by the [`subgroup_size`](crate::device::Properties::subgroup_size)
+/// property.
+#[derive(Clone, Debug)]
+pub struct SubgroupSizeControl {
+    /// Whether the subgroup size is allowed to vary in the shader, instead of being
+    /// [`subgroup_size`](crate::device::Properties::subgroup_size).
+    ///
+    /// If set to `true`, the
+    /// [`subgroup_size_control`](crate::device::Features::subgroup_size_control) feature must be
+    /// enabled on the device.
+    ///
+    /// The default value is `false`.
+    pub allow_varying_subgroup_size: bool,
+
+    /// Whether all subgroups in the local workgroup must be full, with all invocations of the
+    /// subgroup active.
+    ///
+    /// If set to `true`, the
+    /// [`compute_full_subgroups`](crate::device::Features::compute_full_subgroups) feature must
+    /// be enabled on the device, and the X dimension of the local workgroup size of the shader
+    /// must be a multiple of the subgroup size.
+    ///
+    /// The default value is `false`.
+    pub require_full_subgroups: bool,
+
+    /// The subgroup size that the shader must be executed with.
+    ///
+    /// If set to `Some`, the
+    /// [`subgroup_size_control`](crate::device::Features::subgroup_size_control) feature must be
+    /// enabled on the device, the
+    /// [`required_subgroup_size_stages`](crate::device::Properties::required_subgroup_size_stages)
+    /// property must contain [`ShaderStages::COMPUTE`], `allow_varying_subgroup_size` must be
+    /// `false`, and the value must be a power of two between the
+    /// [`min_subgroup_size`](crate::device::Properties::min_subgroup_size) and
+    /// [`max_subgroup_size`](crate::device::Properties::max_subgroup_size) properties.
+    ///
+    /// The default value is `None`.
+    pub required_subgroup_size: Option<u32>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for SubgroupSizeControl {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            allow_varying_subgroup_size: false,
+            require_full_subgroups: false,
+            required_subgroup_size: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
 /// Error that can happen when creating a compute pipeline.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ComputePipelineCreationError {
@@ -320,6 +697,32 @@ pub enum ComputePipelineCreationError {
     IncompatiblePipelineLayout(PipelineLayoutSupersetError),
     /// The provided specialization constants are not compatible with what the shader expects.
     IncompatibleSpecializationConstants,
+    /// Error while creating the shader module.
+    ShaderCreationError(ShaderCreationError),
+    /// The shader module does not contain a compute entry point with the given name.
+    EntryPointNotFound,
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// A required subgroup size was given, but varying subgroup sizes were also allowed.
+    RequiredSubgroupSizeWithVaryingSubgroupSize,
+
+    /// A required subgroup size was given, but the device does not support it for compute
+    /// shaders.
+    RequiredSubgroupSizeNotSupported,
+
+    /// The required subgroup size is not a power of two.
+    RequiredSubgroupSizeNotPowerOfTwo { required_subgroup_size: u32 },
+
+    /// The required subgroup size is outside the range supported by the device.
+    RequiredSubgroupSizeOutOfRange {
+        required_subgroup_size: u32,
+        min_supported: u32,
+        max_supported: u32,
+    },
 }
 
 impl Error for ComputePipelineCreationError {
@@ -329,33 +732,71 @@ impl Error for ComputePipelineCreationError {
             Self::DescriptorSetLayoutCreationError(err) => Some(err),
             Self::PipelineLayoutCreationError(err) => Some(err),
             Self::IncompatiblePipelineLayout(err) => Some(err),
-            Self::IncompatibleSpecializationConstants => None,
+            Self::ShaderCreationError(err) => Some(err),
+            _ => None,
         }
     }
 }
 
 impl Display for ComputePipelineCreationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
-        write!(
-            f,
-            "{}",
-            match self {
-                ComputePipelineCreationError::OomError(_) => "not enough memory available",
-                ComputePipelineCreationError::DescriptorSetLayoutCreationError(_) => {
-                    "error while creating a descriptor set layout object"
-                }
-                ComputePipelineCreationError::PipelineLayoutCreationError(_) => {
-                    "error while creating the pipeline layout object"
-                }
-                ComputePipelineCreationError::IncompatiblePipelineLayout(_) => {
-                    "the pipeline layout is not compatible with what the shader expects"
-                }
-                ComputePipelineCreationError::IncompatibleSpecializationConstants => {
-                    "the provided specialization constants are not compatible with what the shader \
-                    expects"
-                }
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::DescriptorSetLayoutCreationError(_) => {
+                write!(f, "error while creating a descriptor set layout object")
             }
-        )
+            Self::PipelineLayoutCreationError(_) => {
+                write!(f, "error while creating the pipeline layout object")
+            }
+            Self::IncompatiblePipelineLayout(_) => write!(
+                f,
+                "the pipeline layout is not compatible with what the shader expects",
+            ),
+            Self::IncompatibleSpecializationConstants => write!(
+                f,
+                "the provided specialization constants are not compatible with what the shader \
+                expects",
+            ),
+            Self::ShaderCreationError(_) => write!(f, "error while creating the shader module"),
+            Self::EntryPointNotFound => write!(
+                f,
+                "the shader module does not contain a compute entry point with the given name",
+            ),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::RequiredSubgroupSizeWithVaryingSubgroupSize => write!(
+                f,
+                "a required subgroup size was given, but varying subgroup sizes were also allowed",
+            ),
+            Self::RequiredSubgroupSizeNotSupported => write!(
+                f,
+                "a required subgroup size was given, but the device does not support it for \
+                compute shaders",
+            ),
+            Self::RequiredSubgroupSizeNotPowerOfTwo {
+                required_subgroup_size,
+            } => write!(
+                f,
+                "the required subgroup size ({}) is not a power of two",
+                required_subgroup_size,
+            ),
+            Self::RequiredSubgroupSizeOutOfRange {
+                required_subgroup_size,
+                min_supported,
+                max_supported,
+            } => write!(
+                f,
+                "the required subgroup size ({}) is outside the range supported by the device \
+                ({}..={})",
+                required_subgroup_size, min_supported, max_supported,
+            ),
+        }
     }
 }
 
@@ -383,6 +824,12 @@ impl From<PipelineLayoutSupersetError> for ComputePipelineCreationError {
     }
 }
 
+impl From<ShaderCreationError> for ComputePipelineCreationError {
+    fn from(err: ShaderCreationError) -> Self {
+        Self::ShaderCreationError(err)
+    }
+}
+
 impl From<VulkanError> for ComputePipelineCreationError {
     fn from(err: VulkanError) -> ComputePipelineCreationError {
         match err {
@@ -404,14 +851,56 @@ mod tests {
             allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
         },
         memory::allocator::StandardMemoryAllocator,
-        pipeline::{ComputePipeline, Pipeline, PipelineBindPoint},
-        shader::{ShaderModule, SpecializationConstants, SpecializationMapEntry},
+        pipeline::{
+            compute::{ComputePipelineCreationError, SubgroupSizeControl},
+            ComputePipeline, Pipeline, PipelineBindPoint,
+        },
+        shader::{
+            ShaderModule, SpecializationConstant, SpecializationConstantMap,
+            SpecializationConstants, SpecializationMapEntry,
+        },
         sync::{now, GpuFuture},
     };
 
     // TODO: test for basic creation
     // TODO: test for pipeline layout error
 
+    /*
+    #version 450
+
+    layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+    layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+    layout(set = 0, binding = 0) buffer Output {
+        int write;
+    } write;
+
+    void main() {
+        write.write = VALUE;
+    }
+    */
+    const SPECIALIZATION_MODULE: [u8; 480] = [
+        3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 14, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0, 0, 11,
+        0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0, 0, 0, 0, 14, 0,
+        3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 5, 0, 0, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0,
+        0, 0, 16, 0, 6, 0, 4, 0, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 3, 0, 3, 0,
+        2, 0, 0, 0, 194, 1, 0, 0, 5, 0, 4, 0, 4, 0, 0, 0, 109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 4,
+        0, 7, 0, 0, 0, 79, 117, 116, 112, 117, 116, 0, 0, 6, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 119,
+        114, 105, 116, 101, 0, 0, 0, 5, 0, 4, 0, 9, 0, 0, 0, 119, 114, 105, 116, 101, 0, 0, 0, 5,
+        0, 4, 0, 11, 0, 0, 0, 86, 65, 76, 85, 69, 0, 0, 0, 72, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 35,
+        0, 0, 0, 0, 0, 0, 0, 71, 0, 3, 0, 7, 0, 0, 0, 3, 0, 0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 34, 0,
+        0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 33, 0, 0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 11, 0, 0,
+        0, 1, 0, 0, 0, 83, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0, 0,
+        21, 0, 4, 0, 6, 0, 0, 0, 32, 0, 0, 0, 1, 0, 0, 0, 30, 0, 3, 0, 7, 0, 0, 0, 6, 0, 0, 0, 32,
+        0, 4, 0, 8, 0, 0, 0, 2, 0, 0, 0, 7, 0, 0, 0, 59, 0, 4, 0, 8, 0, 0, 0, 9, 0, 0, 0, 2, 0, 0,
+        0, 43, 0, 4, 0, 6, 0, 0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 50, 0, 4, 0, 6, 0, 0, 0, 11, 0, 0, 0,
+        239, 190, 173, 222, 32, 0, 4, 0, 12, 0, 0, 0, 2, 0, 0, 0, 6, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0,
+        0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2, 0, 5, 0, 0, 0, 65, 0, 5, 0, 12, 0, 0, 0,
+        13, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 62, 0, 3, 0, 13, 0, 0, 0, 11, 0, 0, 0, 253, 0, 1, 0,
+        56, 0, 1, 0,
+    ];
+
     #[test]
     fn specialization_constants() {
         // This test checks whether specialization constants work.
@@ -420,45 +909,8 @@ mod tests {
 
         let (device, queue) = gfx_dev_and_queue!();
 
-        let module = unsafe {
-            /*
-            #version 450
-
-            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
-
-            layout(constant_id = 83) const int VALUE = 0xdeadbeef;
-
-            layout(set = 0, binding = 0) buffer Output {
-                int write;
-            } write;
-
-            void main() {
-                write.write = VALUE;
-            }
-            */
-            const MODULE: [u8; 480] = [
-                3, 2, 35, 7, 0, 0, 1, 0, 1, 0, 8, 0, 14, 0, 0, 0, 0, 0, 0, 0, 17, 0, 2, 0, 1, 0, 0,
-                0, 11, 0, 6, 0, 1, 0, 0, 0, 71, 76, 83, 76, 46, 115, 116, 100, 46, 52, 53, 48, 0,
-                0, 0, 0, 14, 0, 3, 0, 0, 0, 0, 0, 1, 0, 0, 0, 15, 0, 5, 0, 5, 0, 0, 0, 4, 0, 0, 0,
-                109, 97, 105, 110, 0, 0, 0, 0, 16, 0, 6, 0, 4, 0, 0, 0, 17, 0, 0, 0, 1, 0, 0, 0, 1,
-                0, 0, 0, 1, 0, 0, 0, 3, 0, 3, 0, 2, 0, 0, 0, 194, 1, 0, 0, 5, 0, 4, 0, 4, 0, 0, 0,
-                109, 97, 105, 110, 0, 0, 0, 0, 5, 0, 4, 0, 7, 0, 0, 0, 79, 117, 116, 112, 117, 116,
-                0, 0, 6, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 119, 114, 105, 116, 101, 0, 0, 0, 5, 0,
-                4, 0, 9, 0, 0, 0, 119, 114, 105, 116, 101, 0, 0, 0, 5, 0, 4, 0, 11, 0, 0, 0, 86,
-                65, 76, 85, 69, 0, 0, 0, 72, 0, 5, 0, 7, 0, 0, 0, 0, 0, 0, 0, 35, 0, 0, 0, 0, 0, 0,
-                0, 71, 0, 3, 0, 7, 0, 0, 0, 3, 0, 0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 34, 0, 0, 0, 0, 0,
-                0, 0, 71, 0, 4, 0, 9, 0, 0, 0, 33, 0, 0, 0, 0, 0, 0, 0, 71, 0, 4, 0, 11, 0, 0, 0,
-                1, 0, 0, 0, 83, 0, 0, 0, 19, 0, 2, 0, 2, 0, 0, 0, 33, 0, 3, 0, 3, 0, 0, 0, 2, 0, 0,
-                0, 21, 0, 4, 0, 6, 0, 0, 0, 32, 0, 0, 0, 1, 0, 0, 0, 30, 0, 3, 0, 7, 0, 0, 0, 6, 0,
-                0, 0, 32, 0, 4, 0, 8, 0, 0, 0, 2, 0, 0, 0, 7, 0, 0, 0, 59, 0, 4, 0, 8, 0, 0, 0, 9,
-                0, 0, 0, 2, 0, 0, 0, 43, 0, 4, 0, 6, 0, 0, 0, 10, 0, 0, 0, 0, 0, 0, 0, 50, 0, 4, 0,
-                6, 0, 0, 0, 11, 0, 0, 0, 239, 190, 173, 222, 32, 0, 4, 0, 12, 0, 0, 0, 2, 0, 0, 0,
-                6, 0, 0, 0, 54, 0, 5, 0, 2, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 248, 0, 2,
-                0, 5, 0, 0, 0, 65, 0, 5, 0, 12, 0, 0, 0, 13, 0, 0, 0, 9, 0, 0, 0, 10, 0, 0, 0, 62,
-                0, 3, 0, 13, 0, 0, 0, 11, 0, 0, 0, 253, 0, 1, 0, 56, 0, 1, 0,
-            ];
-            ShaderModule::from_bytes(device.clone(), &MODULE).unwrap()
-        };
+        let module =
+            unsafe { ShaderModule::from_bytes(device.clone(), &SPECIALIZATION_MODULE).unwrap() };
 
         #[derive(Debug, Copy, Clone)]
         #[allow(non_snake_case)]
@@ -531,4 +983,87 @@ mod tests {
         let data_buffer_content = data_buffer.read().unwrap();
         assert_eq!(*data_buffer_content, 0x12345678);
     }
+
+    #[test]
+    fn specialization_constant_map() {
+        let (device, _queue) = gfx_dev_and_queue!();
+        let module =
+            unsafe { ShaderModule::from_bytes(device.clone(), &SPECIALIZATION_MODULE).unwrap() };
+        let entry_point = module.entry_point("main").unwrap();
+
+        let mut specialization_constants =
+            SpecializationConstantMap::from_default_values(&entry_point);
+        assert_eq!(
+            specialization_constants.get(83),
+            Some(SpecializationConstant::I32(0xdeadbeef_u32 as i32)),
+        );
+
+        // The constant is an `int`, so an `uint` of the same size is rejected.
+        specialization_constants.insert(83, 0x12345678u32);
+        assert!(matches!(
+            ComputePipeline::new(
+                device.clone(),
+                entry_point.clone(),
+                &specialization_constants,
+                None,
+                |_| {},
+            ),
+            Err(ComputePipelineCreationError::IncompatibleSpecializationConstants),
+        ));
+
+        specialization_constants.insert(83, 0x12345678i32);
+        ComputePipeline::new(device, entry_point, &specialization_constants, None, |_| {}).unwrap();
+    }
+
+    #[test]
+    fn from_spirv() {
+        let (device, _queue) = gfx_dev_and_queue!();
+        let words: Vec<u32> = SPECIALIZATION_MODULE
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+
+        let pipeline = unsafe {
+            ComputePipeline::from_spirv(device.clone(), &words, "main", &(), None).unwrap()
+        };
+        assert!(pipeline
+            .descriptor_binding_requirements()
+            .contains_key(&(0, 0)));
+        assert_eq!(pipeline.layout().set_layouts().len(), 1);
+
+        assert_eq!(
+            unsafe { ComputePipeline::from_spirv(device, &words, "foo", &(), None) }.unwrap_err(),
+            ComputePipelineCreationError::EntryPointNotFound,
+        );
+    }
+
+    #[test]
+    fn subgroup_size_control() {
+        let (device, _queue) = gfx_dev_and_queue!();
+        let module =
+            unsafe { ShaderModule::from_bytes(device.clone(), &SPECIALIZATION_MODULE).unwrap() };
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            module.entry_point("main").unwrap(),
+            &(),
+            None,
+            |_| {},
+        )
+        .unwrap();
+
+        match ComputePipeline::with_subgroup_size_control(
+            device,
+            module.entry_point("main").unwrap(),
+            &(),
+            pipeline.layout().clone(),
+            None,
+            SubgroupSizeControl {
+                required_subgroup_size: Some(32),
+                ..Default::default()
+            },
+        ) {
+            Err(ComputePipelineCreationError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
 }
@@ -0,0 +1,473 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Information about the executables that a pipeline was compiled into.
+//!
+//! When a pipeline is created, the implementation compiles its shaders into one or more
+//! *executables*, that run on the device. If the [`pipeline_executable_info`] feature is enabled
+//! on the device, it is possible to retrieve information about these executables from a
+//! [`GraphicsPipeline`] or [`ComputePipeline`]:
+//!
+//! - The [properties] of each executable, such as the shader stages that were compiled into it.
+//! - Implementation-specific [statistics], such as the number of registers that are used, or the
+//!   amount of memory that was spilled.
+//! - Implementation-specific [internal representations], such as the intermediate
+//!   representations or the disassembly of the final machine code.
+//!
+//! This information is mostly useful for performance tuning of shaders, and is not meant to be
+//! used in a shipping application.
+//!
+//! When the `pipeline_executable_info` feature is enabled, vulkano automatically requests the
+//! implementation to capture the statistics and internal representations of all graphics and
+//! compute pipelines that are created. This may make pipeline creation slower.
+//!
+//! [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+//! [`GraphicsPipeline`]: super::GraphicsPipeline
+//! [`ComputePipeline`]: super::ComputePipeline
+//! [properties]: super::GraphicsPipeline::executable_properties
+//! [statistics]: super::GraphicsPipeline::executable_statistics
+//! [internal representations]: super::GraphicsPipeline::executable_internal_representations
+
+use crate::{
+    device::Device, shader::ShaderStages, OomError, RequiresOneOf, VulkanError, VulkanObject,
+};
+use bytemuck::cast_slice;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    ptr,
+};
+
+/// Properties of a pipeline executable.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableProperties {
+    /// The shader stages that were compiled into the executable.
+    pub stages: ShaderStages,
+
+    /// A short, human-readable name of the executable.
+    pub name: String,
+
+    /// A human-readable description of the executable.
+    pub description: String,
+
+    /// The subgroup size that the executable is dispatched with.
+    pub subgroup_size: u32,
+}
+
+/// An implementation-specific statistic of a pipeline executable.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableStatistic {
+    /// A short, human-readable name of the statistic.
+    pub name: String,
+
+    /// A human-readable description of the statistic.
+    pub description: String,
+
+    /// The value of the statistic.
+    pub value: PipelineExecutableStatisticValue,
+}
+
+/// The value of a pipeline executable statistic.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PipelineExecutableStatisticValue {
+    /// A boolean value.
+    Bool(bool),
+
+    /// A signed integer value.
+    Int(i64),
+
+    /// An unsigned integer value.
+    Uint(u64),
+
+    /// A floating point value.
+    Float(f64),
+}
+
+/// An implementation-specific internal representation of a pipeline executable.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableInternalRepresentation {
+    /// A short, human-readable name of the internal representation.
+    pub name: String,
+
+    /// A human-readable description of the internal representation.
+    pub description: String,
+
+    /// Whether `data` contains text. If `true`, the text is null-terminated.
+    pub is_text: bool,
+
+    /// The data of the internal representation.
+    pub data: Vec<u8>,
+}
+
+pub(crate) fn validate_executable_properties(
+    device: &Device,
+) -> Result<(), PipelineExecutableError> {
+    // VUID-vkGetPipelineExecutablePropertiesKHR-pipelineExecutableInfo-03270
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(PipelineExecutableError::RequirementNotMet {
+            required_for: "retrieving the executable properties of a pipeline",
+            requires_one_of: RequiresOneOf {
+                features: &["pipeline_executable_info"],
+                ..Default::default()
+            },
+        });
+    }
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_properties_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+) -> Result<Vec<PipelineExecutableProperties>, VulkanError> {
+    let fns = device.fns();
+    let pipeline_info = ash::vk::PipelineInfoKHR {
+        pipeline,
+        ..Default::default()
+    };
+
+    loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let mut properties = vec![Default::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info,
+            &mut count,
+            properties.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                properties.truncate(count as usize);
+
+                return Ok(properties
+                    .into_iter()
+                    .map(|properties: ash::vk::PipelineExecutablePropertiesKHR| {
+                        PipelineExecutableProperties {
+                            stages: properties.stages.into(),
+                            name: string_from_array(&properties.name),
+                            description: string_from_array(&properties.description),
+                            subgroup_size: properties.subgroup_size,
+                        }
+                    })
+                    .collect());
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    }
+}
+
+pub(crate) fn validate_executable_index(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<(), PipelineExecutableError> {
+    validate_executable_properties(device)?;
+
+    let executable_count = unsafe {
+        let fns = device.fns();
+        let pipeline_info = ash::vk::PipelineInfoKHR {
+            pipeline,
+            ..Default::default()
+        };
+
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        count
+    };
+
+    // VUID-VkPipelineExecutableInfoKHR-executableIndex-03275
+    if executable_index >= executable_count {
+        return Err(PipelineExecutableError::ExecutableIndexOutOfRange {
+            executable_index,
+            executable_count,
+        });
+    }
+
+    // VUID-vkGetPipelineExecutableStatisticsKHR-pipeline-03274
+    // VUID-vkGetPipelineExecutableInternalRepresentationsKHR-pipeline-03278
+    // Ensured by vulkano when creating the pipeline.
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_statistics_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableStatistic>, VulkanError> {
+    let fns = device.fns();
+    let executable_info = ash::vk::PipelineExecutableInfoKHR {
+        pipeline,
+        executable_index,
+        ..Default::default()
+    };
+
+    loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_statistics_khr)(
+            device.handle(),
+            &executable_info,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let mut statistics = vec![Default::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_statistics_khr)(
+            device.handle(),
+            &executable_info,
+            &mut count,
+            statistics.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                statistics.truncate(count as usize);
+
+                return Ok(statistics
+                    .into_iter()
+                    .map(|statistic: ash::vk::PipelineExecutableStatisticKHR| {
+                        let value = match statistic.format {
+                            ash::vk::PipelineExecutableStatisticFormatKHR::BOOL32 => {
+                                PipelineExecutableStatisticValue::Bool(
+                                    statistic.value.b32 != ash::vk::FALSE,
+                                )
+                            }
+                            ash::vk::PipelineExecutableStatisticFormatKHR::INT64 => {
+                                PipelineExecutableStatisticValue::Int(statistic.value.i64)
+                            }
+                            ash::vk::PipelineExecutableStatisticFormatKHR::UINT64 => {
+                                PipelineExecutableStatisticValue::Uint(statistic.value.u64)
+                            }
+                            ash::vk::PipelineExecutableStatisticFormatKHR::FLOAT64 => {
+                                PipelineExecutableStatisticValue::Float(statistic.value.f64)
+                            }
+                            _ => unreachable!(),
+                        };
+
+                        PipelineExecutableStatistic {
+                            name: string_from_array(&statistic.name),
+                            description: string_from_array(&statistic.description),
+                            value,
+                        }
+                    })
+                    .collect());
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    }
+}
+
+pub(crate) unsafe fn executable_internal_representations_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+    executable_index: u32,
+) -> Result<Vec<PipelineExecutableInternalRepresentation>, VulkanError> {
+    let fns = device.fns();
+    let executable_info = ash::vk::PipelineExecutableInfoKHR {
+        pipeline,
+        executable_index,
+        ..Default::default()
+    };
+
+    loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        // First retrieve the size of the data of each internal representation.
+        let mut internal_representations: Vec<
+            ash::vk::PipelineExecutableInternalRepresentationKHR,
+        > = vec![Default::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info,
+            &mut count,
+            internal_representations.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => (),
+            ash::vk::Result::INCOMPLETE => continue,
+            err => return Err(VulkanError::from(err)),
+        }
+
+        internal_representations.truncate(count as usize);
+
+        // Then retrieve the data itself.
+        let mut data: Vec<Vec<u8>> = internal_representations
+            .iter()
+            .map(|internal_representation| vec![0; internal_representation.data_size])
+            .collect();
+
+        for (internal_representation, data) in internal_representations.iter_mut().zip(&mut data) {
+            internal_representation.p_data = data.as_mut_ptr() as *mut _;
+        }
+
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_internal_representations_khr)(
+            device.handle(),
+            &executable_info,
+            &mut count,
+            internal_representations.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                return Ok(internal_representations
+                    .into_iter()
+                    .zip(data)
+                    .map(|(internal_representation, mut data)| {
+                        data.truncate(internal_representation.data_size);
+
+                        PipelineExecutableInternalRepresentation {
+                            name: string_from_array(&internal_representation.name),
+                            description: string_from_array(&internal_representation.description),
+                            is_text: internal_representation.is_text != ash::vk::FALSE,
+                            data,
+                        }
+                    })
+                    .collect());
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    }
+}
+
+fn string_from_array(array: &[std::os::raw::c_char]) -> String {
+    let bytes = cast_slice(array);
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[0..end]).into()
+}
+
+/// Error that can happen when retrieving information about pipeline executables.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PipelineExecutableError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The provided `executable_index` was not less than the number of executables in the
+    /// pipeline.
+    ExecutableIndexOutOfRange {
+        executable_index: u32,
+        executable_count: u32,
+    },
+}
+
+impl Error for PipelineExecutableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for PipelineExecutableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::ExecutableIndexOutOfRange {
+                executable_index,
+                executable_count,
+            } => write!(
+                f,
+                "the provided `executable_index` ({}) was not less than the number of executables \
+                in the pipeline ({})",
+                executable_index, executable_count,
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for PipelineExecutableError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            err @ VulkanError::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ VulkanError::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for PipelineExecutableError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_executable_properties, PipelineExecutableError};
+
+    #[test]
+    fn requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(matches!(
+            validate_executable_properties(&device),
+            Err(PipelineExecutableError::RequirementNotMet { .. })
+        ));
+    }
+}
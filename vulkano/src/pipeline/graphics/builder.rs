@@ -17,8 +17,11 @@ use super::{
     },
     depth_stencil::{DepthStencilState, StencilOps},
     discard_rectangle::DiscardRectangleState,
+    fragment_shading_rate::{
+        FragmentShadingRate, FragmentShadingRateCombinerOp, FragmentShadingRateState,
+    },
     input_assembly::{InputAssemblyState, PrimitiveTopology, PrimitiveTopologyClass},
-    multisample::MultisampleState,
+    multisample::{MultisampleState, SampleLocationsInfo, SampleLocationsState},
     rasterization::{
         CullMode, DepthBiasState, FrontFace, LineRasterizationMode, PolygonMode, RasterizationState,
     },
@@ -29,7 +32,7 @@ use super::{
         VertexInputBindingDescription, VertexInputState,
     },
     viewport::{Scissor, Viewport, ViewportState},
-    GraphicsPipeline, GraphicsPipelineCreationError,
+    GraphicsPipeline, GraphicsPipelineCreationError, GraphicsPipelineLibraryFlags,
 };
 use crate::{
     descriptor_set::layout::{DescriptorSetLayout, DescriptorSetLayoutCreateInfo},
@@ -43,29 +46,28 @@ use crate::{
             depth_stencil::{DepthBoundsState, DepthState, StencilOpState, StencilState},
             vertex_input::VertexInputRate,
         },
-        layout::{PipelineLayoutCreateInfo, PushConstantRange},
-        DynamicState, PartialStateMode, PipelineLayout, StateMode,
+        layout::PipelineLayoutCreateInfo,
+        DynamicState, PartialStateMode, Pipeline, PipelineCreateFlags, PipelineLayout, StateMode,
     },
     shader::{
-        DescriptorBindingRequirements, EntryPoint, ShaderExecution, ShaderStage,
-        SpecializationConstants, SpecializationMapEntry,
+        reflect, specialization_constants_compatible, DescriptorBindingRequirements, EntryPoint,
+        ShaderExecution, ShaderInterface, ShaderStage, SpecializationConstants,
     },
     DeviceSize, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use smallvec::SmallVec;
-use std::{
-    collections::hash_map::Entry,
-    mem::{size_of_val, MaybeUninit},
-    ptr, slice,
-    sync::Arc,
-};
+use std::{collections::hash_map::Entry, mem::MaybeUninit, ptr, sync::Arc};
 
 /// Prototype for a `GraphicsPipeline`.
 #[derive(Debug)]
 pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss> {
     render_pass: Option<PipelineRenderPassType>,
     cache: Option<Arc<PipelineCache>>,
+    flags: PipelineCreateFlags,
+    base_pipeline: Option<Arc<GraphicsPipeline>>,
+    library_flags: GraphicsPipelineLibraryFlags,
+    libraries: Vec<Arc<GraphicsPipeline>>,
 
     vertex_shader: Option<(EntryPoint<'vs>, Vss)>,
     tessellation_shaders: Option<TessellationShaders<'tcs, 'tes, Tcss, Tess>>,
@@ -77,8 +79,10 @@ pub struct GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, T
     tessellation_state: TessellationState,
     viewport_state: ViewportState,
     discard_rectangle_state: DiscardRectangleState,
+    fragment_shading_rate_state: FragmentShadingRateState,
     rasterization_state: RasterizationState,
     multisample_state: MultisampleState,
+    sample_locations_state: SampleLocationsState,
     depth_stencil_state: DepthStencilState,
     color_blend_state: ColorBlendState,
 }
@@ -110,6 +114,10 @@ impl
         GraphicsPipelineBuilder {
             render_pass: None,
             cache: None,
+            flags: PipelineCreateFlags::empty(),
+            base_pipeline: None,
+            library_flags: GraphicsPipelineLibraryFlags::empty(),
+            libraries: Vec::new(),
 
             vertex_shader: None,
             tessellation_shaders: None,
@@ -121,8 +129,10 @@ impl
             tessellation_state: Default::default(),
             viewport_state: Default::default(),
             discard_rectangle_state: Default::default(),
+            fragment_shading_rate_state: Default::default(),
             rasterization_state: Default::default(),
             multisample_state: Default::default(),
+            sample_locations_state: Default::default(),
             depth_stencil_state: Default::default(),
             color_blend_state: Default::default(),
         }
@@ -185,28 +195,12 @@ where
 
             // Produce `DescriptorBindingRequirements` for each binding, by iterating over all
             // shaders and adding the requirements of each.
-            let mut descriptor_binding_requirements: HashMap<
-                (u32, u32),
-                DescriptorBindingRequirements,
-            > = HashMap::default();
-
-            for (loc, reqs) in stages
-                .iter()
-                .flat_map(|shader| shader.descriptor_binding_requirements())
-            {
-                match descriptor_binding_requirements.entry(loc) {
-                    Entry::Occupied(entry) => {
-                        // Previous shaders already added requirements, so we merge requirements of
-                        // the current shader into the requirements of the previous one.
-                        entry.into_mut().merge(reqs).expect("Could not produce an intersection of the shader descriptor requirements");
-                    }
-                    Entry::Vacant(entry) => {
-                        // No previous shader had this descriptor yet, so we just insert the
-                        // requirements.
-                        entry.insert(reqs.clone());
-                    }
-                }
-            }
+            let descriptor_binding_requirements = reflect::merge_descriptor_binding_requirements(
+                stages
+                    .iter()
+                    .flat_map(|shader| shader.descriptor_binding_requirements()),
+            )
+            .expect("Could not produce an intersection of the shader descriptor requirements");
 
             // Build a description of a descriptor set layout from the shader requirements, then
             // feed it to the user-provided closure to allow tweaking.
@@ -220,27 +214,11 @@ where
             // We want to union each push constant range into a set of ranges that do not have intersecting stage flags.
             // e.g. The range [0, 16) is either made available to Vertex | Fragment or we only make [0, 16) available to
             // Vertex and a subrange available to Fragment, like [0, 8)
-            let mut range_map = HashMap::default();
-            for stage in stages.iter() {
-                if let Some(range) = stage.push_constant_requirements() {
-                    match range_map.entry((range.offset, range.size)) {
-                        Entry::Vacant(entry) => {
-                            entry.insert(range.stages);
-                        }
-                        Entry::Occupied(mut entry) => {
-                            *entry.get_mut() = *entry.get() | range.stages;
-                        }
-                    }
-                }
-            }
-            let push_constant_ranges: Vec<_> = range_map
-                .iter()
-                .map(|((offset, size), stages)| PushConstantRange {
-                    stages: *stages,
-                    offset: *offset,
-                    size: *size,
-                })
-                .collect();
+            let push_constant_ranges = reflect::merge_push_constant_ranges(
+                stages
+                    .iter()
+                    .filter_map(|shader| shader.push_constant_requirements()),
+            );
 
             (set_layout_create_infos, push_constant_ranges)
         };
@@ -272,9 +250,56 @@ where
         device: Arc<Device>,
         pipeline_layout: Arc<PipelineLayout>,
     ) -> Result<Arc<GraphicsPipeline>, GraphicsPipelineCreationError> {
-        let vertex_input_state = self
-            .vertex_input_state
-            .definition(self.vertex_shader.as_ref().unwrap().0.input_interface())?;
+        // The state subsets that are provided by linked libraries, and the ones that must be
+        // provided by this pipeline itself.
+        let linked_flags = self
+            .libraries
+            .iter()
+            .fold(GraphicsPipelineLibraryFlags::empty(), |flags, library| {
+                flags | library.library_flags()
+            });
+        let own_flags = if self.library_flags.is_empty() {
+            GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE
+                | GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS
+                | GraphicsPipelineLibraryFlags::FRAGMENT_SHADER
+                | GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE
+        } else {
+            self.library_flags
+        } - linked_flags;
+
+        if self.render_pass.is_none() {
+            // Vertex input interface libraries don't use the render pass, so prefer a library
+            // that does. The render passes of the libraries are checked in `validate_create`.
+            self.render_pass = self
+                .libraries
+                .iter()
+                .find(|library| library_uses_render_pass(library.library_flags()))
+                .or_else(|| self.libraries.first())
+                .map(|library| library.render_pass().clone());
+        }
+
+        // The vertex shader may also be provided by a library.
+        let vertex_shader_input_interface = self
+            .vertex_shader
+            .as_ref()
+            .map(|(entry_point, _)| entry_point.input_interface())
+            .or_else(|| {
+                self.libraries
+                    .iter()
+                    .find_map(|library| library.vertex_shader_input_interface.as_ref())
+            })
+            .cloned();
+
+        let vertex_input_state = match vertex_shader_input_interface.as_ref() {
+            Some(interface) => self.vertex_input_state.definition(interface)?,
+            None if !own_flags
+                .intersects(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS) =>
+            {
+                self.vertex_input_state
+                    .definition(&ShaderInterface::empty())?
+            }
+            None => panic!("Missing vertex shader"),
+        };
 
         // If there is one element, duplicate it for all attachments.
         // TODO: this is undocumented and only exists for compatibility with some of the
@@ -299,8 +324,12 @@ where
             let Self {
                 render_pass,
                 cache: _,
+                flags: _,
+                base_pipeline: _,
+                library_flags: _,
+                libraries: _,
 
-                vertex_shader,
+                vertex_shader: _,
                 tessellation_shaders,
                 geometry_shader: _,
                 fragment_shader: _,
@@ -310,25 +339,33 @@ where
                 tessellation_state: _,
                 viewport_state: _,
                 discard_rectangle_state: _,
+                fragment_shading_rate_state: _,
                 rasterization_state,
                 multisample_state: _,
+                sample_locations_state: _,
                 depth_stencil_state: _,
                 color_blend_state: _,
             } = &self;
 
             let render_pass = render_pass.as_ref().expect("Missing render pass");
-
-            let has_pre_rasterization_shader_state = true;
-            let has_vertex_input_state = vertex_shader.is_some();
-            let has_fragment_shader_state =
-                rasterization_state.rasterizer_discard_enable != StateMode::Fixed(true);
-            let has_fragment_output_state =
-                rasterization_state.rasterizer_discard_enable != StateMode::Fixed(true);
+            let rasterizer_discard_enabled =
+                rasterization_state.rasterizer_discard_enable == StateMode::Fixed(true);
+
+            let has_pre_rasterization_shader_state =
+                own_flags.intersects(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS);
+            let has_vertex_input_state =
+                own_flags.intersects(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE);
+            let has_fragment_shader_state = own_flags
+                .intersects(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER)
+                && !rasterizer_discard_enabled;
+            let has_fragment_output_state = own_flags
+                .intersects(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE)
+                && !rasterizer_discard_enabled;
 
             let has_tessellation_state =
                 has_pre_rasterization_shader_state && tessellation_shaders.is_some();
             let has_viewport_state =
-                has_pre_rasterization_shader_state && has_fragment_shader_state;
+                has_pre_rasterization_shader_state && !rasterizer_discard_enabled;
             let has_depth_stencil_state = has_fragment_shader_state
                 && match render_pass {
                     PipelineRenderPassType::BeginRenderPass(subpass) => {
@@ -364,12 +401,16 @@ where
 
         self.validate_create(&device, &pipeline_layout, &vertex_input_state, has)?;
 
-        let (handle, descriptor_requirements, dynamic_state, shaders) =
+        let (handle, mut descriptor_requirements, mut dynamic_state, mut shaders) =
             unsafe { self.record_create(&device, &pipeline_layout, &vertex_input_state, has)? };
 
         let Self {
             mut render_pass,
             cache: _,
+            flags,
+            base_pipeline: _,
+            library_flags,
+            libraries,
             vertex_shader: _,
             tessellation_shaders: _,
             geometry_shader: _,
@@ -379,12 +420,79 @@ where
             tessellation_state,
             viewport_state,
             discard_rectangle_state,
+            fragment_shading_rate_state,
             rasterization_state,
             multisample_state,
+            sample_locations_state,
             depth_stencil_state,
             color_blend_state,
         } = self;
 
+        let mut vertex_input_state = vertex_input_state;
+        let mut input_assembly_state = input_assembly_state;
+        let mut tessellation_state = has.tessellation_state.then_some(tessellation_state);
+        let mut viewport_state = has.viewport_state.then_some(viewport_state);
+        let mut discard_rectangle_state = has
+            .pre_rasterization_shader_state
+            .then_some(discard_rectangle_state);
+        let mut fragment_shading_rate_state = (has.pre_rasterization_shader_state
+            || has.fragment_shader_state)
+            .then_some(fragment_shading_rate_state);
+        let mut rasterization_state = rasterization_state;
+        let mut multisample_state = has.fragment_output_state.then_some(multisample_state);
+        let mut sample_locations_state =
+            has.fragment_output_state.then_some(sample_locations_state);
+        let mut depth_stencil_state = has.depth_stencil_state.then_some(depth_stencil_state);
+        let mut color_blend_state = has.color_blend_state.then_some(color_blend_state);
+
+        // Take over the shaders and state of the parts that are provided by linked libraries.
+        for library in &libraries {
+            let flags = library.library_flags();
+
+            if flags.intersects(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE) {
+                vertex_input_state = library.vertex_input_state.clone();
+                input_assembly_state = library.input_assembly_state;
+            }
+
+            if flags.intersects(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS) {
+                tessellation_state = library.tessellation_state;
+                viewport_state = library.viewport_state.clone();
+                discard_rectangle_state = library.discard_rectangle_state.clone();
+                fragment_shading_rate_state = library.fragment_shading_rate_state.clone();
+                rasterization_state = library.rasterization_state.clone();
+            }
+
+            if flags.intersects(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER) {
+                depth_stencil_state = library.depth_stencil_state.clone();
+            }
+
+            if flags.intersects(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE) {
+                multisample_state = library.multisample_state;
+                sample_locations_state = library.sample_locations_state.clone();
+                color_blend_state = library.color_blend_state.clone();
+            }
+
+            shaders.extend(
+                library
+                    .shaders
+                    .iter()
+                    .map(|(&stage, &shader)| (stage, shader)),
+            );
+            dynamic_state.extend(library.dynamic_state.iter().map(|(&k, &v)| (k, v)));
+
+            for (&loc, reqs) in &library.descriptor_binding_requirements {
+                match descriptor_requirements.entry(loc) {
+                    Entry::Occupied(entry) => {
+                        // Checked in `validate_create`.
+                        entry.into_mut().merge(reqs).unwrap();
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(reqs.clone());
+                    }
+                }
+            }
+        }
+
         let num_used_descriptor_sets = descriptor_requirements
             .keys()
             .map(|loc| loc.0)
@@ -398,20 +506,23 @@ where
             id: GraphicsPipeline::next_id(),
             layout: pipeline_layout,
             render_pass: render_pass.take().expect("Missing render pass"),
+            flags,
+            library_flags,
             shaders,
+            vertex_shader_input_interface,
             descriptor_binding_requirements: descriptor_requirements,
             num_used_descriptor_sets,
             vertex_input_state, // Can be None if there's a mesh shader, but we don't support that yet
             input_assembly_state, // Can be None if there's a mesh shader, but we don't support that yet
-            tessellation_state: has.tessellation_state.then_some(tessellation_state),
-            viewport_state: has.viewport_state.then_some(viewport_state),
-            discard_rectangle_state: has
-                .pre_rasterization_shader_state
-                .then_some(discard_rectangle_state),
+            tessellation_state,
+            viewport_state,
+            discard_rectangle_state,
+            fragment_shading_rate_state,
             rasterization_state,
-            multisample_state: has.fragment_output_state.then_some(multisample_state),
-            depth_stencil_state: has.depth_stencil_state.then_some(depth_stencil_state),
-            color_blend_state: has.color_blend_state.then_some(color_blend_state),
+            multisample_state,
+            sample_locations_state,
+            depth_stencil_state,
+            color_blend_state,
             dynamic_state,
         }))
     }
@@ -429,6 +540,10 @@ where
         let Self {
             render_pass,
             cache: _,
+            flags,
+            base_pipeline,
+            library_flags,
+            libraries,
 
             vertex_shader,
             tessellation_shaders,
@@ -440,8 +555,10 @@ where
             tessellation_state,
             viewport_state,
             discard_rectangle_state,
+            fragment_shading_rate_state,
             rasterization_state,
             multisample_state,
+            sample_locations_state,
             depth_stencil_state,
             color_blend_state,
         } = self;
@@ -453,6 +570,181 @@ where
         // VUID-VkGraphicsPipelineCreateInfo-layout-01688
         // Checked at pipeline layout creation time.
 
+        /*
+            Flags
+        */
+
+        // VUID-VkGraphicsPipelineCreateInfo-flags-parameter
+        flags.validate_device(device)?;
+
+        if flags.intersects(PipelineCreateFlags::DERIVATIVE) {
+            // VUID-vkCreateGraphicsPipelines-flags-00720
+            let base_pipeline = base_pipeline
+                .as_ref()
+                .ok_or(GraphicsPipelineCreationError::BasePipelineMissing)?;
+
+            // VUID-VkGraphicsPipelineCreateInfo-commonparent
+            assert_eq!(device, base_pipeline.device().as_ref());
+
+            // VUID-vkCreateGraphicsPipelines-flags-00721
+            if !base_pipeline
+                .flags()
+                .intersects(PipelineCreateFlags::ALLOW_DERIVATIVES)
+            {
+                return Err(GraphicsPipelineCreationError::BasePipelineNotAllowDerivatives);
+            }
+        }
+
+        /*
+            Pipeline libraries
+        */
+
+        if !library_flags.is_empty() || !libraries.is_empty() {
+            if !device.enabled_features().graphics_pipeline_library {
+                return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                    required_for: "`library_flags` is not empty, or `libraries` is not empty",
+                    requires_one_of: RequiresOneOf {
+                        features: &["graphics_pipeline_library"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkGraphicsPipelineLibraryCreateInfoEXT-flags-parameter
+            library_flags.validate_device(device)?;
+
+            let mut linked_flags = GraphicsPipelineLibraryFlags::empty();
+
+            // The render pass that the libraries are checked against. If the pipeline being
+            // created does not use the render pass itself, the first library that does is used.
+            let mut reference_render_pass = (has.pre_rasterization_shader_state
+                || has.fragment_shader_state
+                || has.fragment_output_state)
+                .then_some(render_pass);
+            let has_shaders = has.pre_rasterization_shader_state || has.fragment_shader_state;
+            let mut reference_layout = has_shaders.then_some(pipeline_layout);
+
+            // The descriptor requirements of the shaders of the pipeline itself, which the
+            // requirements of the libraries are merged into.
+            let mut descriptor_binding_requirements =
+                reflect::merge_descriptor_binding_requirements(
+                    [
+                        vertex_shader
+                            .as_ref()
+                            .filter(|_| has.pre_rasterization_shader_state)
+                            .map(|s| &s.0),
+                        tessellation_shaders
+                            .as_ref()
+                            .filter(|_| has.pre_rasterization_shader_state)
+                            .map(|s| &s.control.0),
+                        tessellation_shaders
+                            .as_ref()
+                            .filter(|_| has.pre_rasterization_shader_state)
+                            .map(|s| &s.evaluation.0),
+                        geometry_shader
+                            .as_ref()
+                            .filter(|_| has.pre_rasterization_shader_state)
+                            .map(|s| &s.0),
+                        fragment_shader
+                            .as_ref()
+                            .filter(|_| has.fragment_shader_state)
+                            .map(|s| &s.0),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|entry_point| entry_point.descriptor_binding_requirements()),
+                )
+                .map_err(GraphicsPipelineCreationError::DescriptorRequirementsIncompatible)?;
+
+            for (library_index, library) in libraries.iter().enumerate() {
+                // VUID-VkGraphicsPipelineCreateInfo-commonparent
+                assert_eq!(device, library.device().as_ref());
+
+                // VUID-VkPipelineLibraryCreateInfoKHR-pLibraries-03381
+                if library.library_flags().is_empty() {
+                    return Err(GraphicsPipelineCreationError::NotALibrary {
+                        library_index: library_index as u32,
+                    });
+                }
+
+                // VUID?
+                if library
+                    .library_flags()
+                    .intersects(linked_flags | *library_flags)
+                {
+                    return Err(GraphicsPipelineCreationError::LibraryStateOverlap {
+                        library_index: library_index as u32,
+                    });
+                }
+
+                linked_flags |= library.library_flags();
+
+                // VUID?
+                if library_uses_render_pass(library.library_flags()) {
+                    match reference_render_pass {
+                        Some(reference_render_pass) => {
+                            if !render_pass_is_compatible_with(
+                                reference_render_pass,
+                                library.render_pass(),
+                            ) {
+                                return Err(
+                                    GraphicsPipelineCreationError::LibraryRenderPassIncompatible {
+                                        library_index: library_index as u32,
+                                    },
+                                );
+                            }
+                        }
+                        None => reference_render_pass = Some(library.render_pass()),
+                    }
+                }
+
+                // VUID-VkGraphicsPipelineCreateInfo-flags-06621
+                // VUID-VkGraphicsPipelineCreateInfo-flags-06622
+                if library.library_flags().intersects(
+                    GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS
+                        | GraphicsPipelineLibraryFlags::FRAGMENT_SHADER,
+                ) {
+                    match reference_layout {
+                        Some(reference_layout) => {
+                            let library_layout = library.layout();
+
+                            if reference_layout.set_layouts().len()
+                                != library_layout.set_layouts().len()
+                                || !reference_layout.is_compatible_with(
+                                    library_layout,
+                                    reference_layout.set_layouts().len() as u32,
+                                )
+                            {
+                                return Err(
+                                    GraphicsPipelineCreationError::LibraryPipelineLayoutIncompatible {
+                                        library_index: library_index as u32,
+                                    },
+                                );
+                            }
+                        }
+                        None => reference_layout = Some(library.layout().as_ref()),
+                    }
+                }
+
+                // VUID?
+                for (&loc, reqs) in &library.descriptor_binding_requirements {
+                    match descriptor_binding_requirements.entry(loc) {
+                        Entry::Occupied(entry) => {
+                            entry.into_mut().merge(reqs).map_err(|error| {
+                                GraphicsPipelineCreationError::LibraryDescriptorRequirementsIncompatible {
+                                    library_index: library_index as u32,
+                                    error,
+                                }
+                            })?;
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(reqs.clone());
+                        }
+                    }
+                }
+            }
+        }
+
         /*
             Render pass
         */
@@ -953,13 +1245,10 @@ where
             if let Some((entry_point, specialization_data)) = vertex_shader {
                 shader_stages.push(ShaderStageInfo {
                     entry_point,
-                    specialization_map_entries: Vss::descriptors(),
-                    _specialization_data: unsafe {
-                        std::slice::from_raw_parts(
-                            specialization_data as *const _ as *const u8,
-                            size_of_val(specialization_data),
-                        )
-                    },
+                    specialization_constants_compatible: specialization_constants_compatible(
+                        entry_point,
+                        specialization_data,
+                    ),
                 });
 
                 match entry_point.execution() {
@@ -967,62 +1256,6 @@ where
                     _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                 }
 
-                // VUID?
-                // Check that the vertex input state contains attributes for all the shader's input
-                // variables.
-                for element in entry_point.input_interface().elements() {
-                    assert!(!element.ty.is_64bit); // TODO: implement
-                    let location_range =
-                        element.location..element.location + element.ty.num_locations();
-
-                    for location in location_range {
-                        let attribute_desc =
-                            match vertex_input_state.attributes.get(&location) {
-                                Some(attribute_desc) => attribute_desc,
-                                None => return Err(
-                                    GraphicsPipelineCreationError::VertexInputAttributeMissing {
-                                        location,
-                                    },
-                                ),
-                            };
-
-                        // TODO: Check component assignments too. Multiple variables can occupy the same
-                        // location but in different components.
-
-                        let shader_type = element.ty.to_format().type_color().unwrap();
-                        let attribute_type = attribute_desc.format.type_color().unwrap();
-
-                        if !matches!(
-                            (shader_type, attribute_type),
-                            (
-                                NumericType::SFLOAT
-                                    | NumericType::UFLOAT
-                                    | NumericType::SNORM
-                                    | NumericType::UNORM
-                                    | NumericType::SSCALED
-                                    | NumericType::USCALED
-                                    | NumericType::SRGB,
-                                NumericType::SFLOAT
-                                    | NumericType::UFLOAT
-                                    | NumericType::SNORM
-                                    | NumericType::UNORM
-                                    | NumericType::SSCALED
-                                    | NumericType::USCALED
-                                    | NumericType::SRGB,
-                            ) | (NumericType::SINT, NumericType::SINT)
-                                | (NumericType::UINT, NumericType::UINT)
-                        ) {
-                            return Err(
-                                GraphicsPipelineCreationError::VertexInputAttributeIncompatibleFormat {
-                                    location,
-                                    shader_type,
-                                    attribute_type,
-                                },
-                            );
-                        }
-                    }
-                }
-
                 // TODO:
                 // VUID-VkPipelineShaderStageCreateInfo-stage-00712
             } else {
@@ -1036,8 +1269,6 @@ where
                 // VUID-VkGraphicsPipelineCreateInfo-pStages-00730
                 // Ensured by the definition of TessellationShaders.
 
-                // FIXME: must check that the control shader and evaluation shader are compatible
-
                 // VUID-VkPipelineShaderStageCreateInfo-stage-00705
                 if !device.enabled_features().tessellation_shader {
                     return Err(GraphicsPipelineCreationError::RequirementNotMet {
@@ -1054,17 +1285,14 @@ where
 
                     shader_stages.push(ShaderStageInfo {
                         entry_point,
-                        specialization_map_entries: Tcss::descriptors(),
-                        _specialization_data: unsafe {
-                            std::slice::from_raw_parts(
-                                specialization_data as *const _ as *const u8,
-                                size_of_val(specialization_data),
-                            )
-                        },
+                        specialization_constants_compatible: specialization_constants_compatible(
+                            entry_point,
+                            specialization_data,
+                        ),
                     });
 
                     match entry_point.execution() {
-                        ShaderExecution::TessellationControl => (),
+                        ShaderExecution::TessellationControl(_) => (),
                         _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                     }
                 }
@@ -1074,21 +1302,87 @@ where
 
                     shader_stages.push(ShaderStageInfo {
                         entry_point,
-                        specialization_map_entries: Tess::descriptors(),
-                        _specialization_data: unsafe {
-                            std::slice::from_raw_parts(
-                                specialization_data as *const _ as *const u8,
-                                size_of_val(specialization_data),
-                            )
-                        },
+                        specialization_constants_compatible: specialization_constants_compatible(
+                            entry_point,
+                            specialization_data,
+                        ),
                     });
 
                     match entry_point.execution() {
-                        ShaderExecution::TessellationEvaluation => (),
+                        ShaderExecution::TessellationEvaluation(_) => (),
                         _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                     }
                 }
 
+                let (control_execution, evaluation_execution) = match (
+                    tessellation_shaders.control.0.execution(),
+                    tessellation_shaders.evaluation.0.execution(),
+                ) {
+                    (
+                        &ShaderExecution::TessellationControl(control_execution),
+                        &ShaderExecution::TessellationEvaluation(evaluation_execution),
+                    ) => (control_execution, evaluation_execution),
+                    _ => unreachable!(),
+                };
+
+                for output_vertices in [
+                    control_execution.output_vertices,
+                    evaluation_execution.output_vertices,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    // VUID-VkPipelineShaderStageCreateInfo-stage-00713
+                    if output_vertices == 0
+                        || output_vertices > properties.max_tessellation_patch_size
+                    {
+                        return Err(
+                            GraphicsPipelineCreationError::MaxTessellationPatchSizeExceeded {
+                                output_vertices,
+                                max: properties.max_tessellation_patch_size,
+                            },
+                        );
+                    }
+                }
+
+                match (
+                    control_execution.subdivision,
+                    evaluation_execution.subdivision,
+                ) {
+                    // VUID-VkGraphicsPipelineCreateInfo-pStages-00732
+                    (None, None) => {
+                        return Err(
+                            GraphicsPipelineCreationError::TessellationShadersSubdivisionMissing,
+                        );
+                    }
+                    // VUID-VkGraphicsPipelineCreateInfo-pStages-00733
+                    (Some(control), Some(evaluation)) if control != evaluation => {
+                        return Err(
+                            GraphicsPipelineCreationError::TessellationShadersSubdivisionMismatch,
+                        );
+                    }
+                    _ => (),
+                }
+
+                match (
+                    control_execution.output_vertices,
+                    evaluation_execution.output_vertices,
+                ) {
+                    // VUID-VkGraphicsPipelineCreateInfo-pStages-00734
+                    (None, None) => {
+                        return Err(
+                            GraphicsPipelineCreationError::TessellationShadersOutputVerticesMissing,
+                        );
+                    }
+                    // VUID-VkGraphicsPipelineCreateInfo-pStages-00735
+                    (Some(control), Some(evaluation)) if control != evaluation => {
+                        return Err(
+                            GraphicsPipelineCreationError::TessellationShadersOutputVerticesMismatch,
+                        );
+                    }
+                    _ => (),
+                }
+
                 if !device.enabled_features().multiview_tessellation_shader {
                     let view_mask = match render_pass {
                         PipelineRenderPassType::BeginRenderPass(subpass) => {
@@ -1112,26 +1406,16 @@ where
                         });
                     }
                 }
-
-                // TODO:
-                // VUID-VkPipelineShaderStageCreateInfo-stage-00713
-                // VUID-VkGraphicsPipelineCreateInfo-pStages-00732
-                // VUID-VkGraphicsPipelineCreateInfo-pStages-00733
-                // VUID-VkGraphicsPipelineCreateInfo-pStages-00734
-                // VUID-VkGraphicsPipelineCreateInfo-pStages-00735
             }
 
             // Geometry shader
             if let Some((entry_point, specialization_data)) = geometry_shader {
                 shader_stages.push(ShaderStageInfo {
                     entry_point,
-                    specialization_map_entries: Gss::descriptors(),
-                    _specialization_data: unsafe {
-                        std::slice::from_raw_parts(
-                            specialization_data as *const _ as *const u8,
-                            size_of_val(specialization_data),
-                        )
-                    },
+                    specialization_constants_compatible: specialization_constants_compatible(
+                        entry_point,
+                        specialization_data,
+                    ),
                 });
 
                 // VUID-VkPipelineShaderStageCreateInfo-stage-00704
@@ -1202,20 +1486,40 @@ where
                     line_width,
                     line_rasterization_mode,
                     line_stipple,
+                    rasterization_stream,
                 } = rasterization_state;
 
-                // VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-parameter
-                polygon_mode.validate_device(device)?;
-
-                // VUID-VkPipelineRasterizationStateCreateInfo-depthClampEnable-00782
-                if depth_clamp_enable && !device.enabled_features().depth_clamp {
-                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                        required_for: "`rasterization_state.depth_clamp_enable` is set",
-                        requires_one_of: RequiresOneOf {
-                            features: &["depth_clamp"],
-                            ..Default::default()
-                        },
-                    });
+                match depth_clamp_enable {
+                    StateMode::Fixed(false) => (),
+                    StateMode::Fixed(true) => {
+                        // VUID-VkPipelineRasterizationStateCreateInfo-depthClampEnable-00782
+                        if !device.enabled_features().depth_clamp {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.depth_clamp_enable` is \
+                                    `StateMode::Fixed(true)`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["depth_clamp"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                    StateMode::Dynamic => {
+                        // VUID?
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_depth_clamp_enable
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.depth_clamp_enable` is \
+                                    `StateMode::Dynamic`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_depth_clamp_enable"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
                 }
 
                 match rasterizer_discard_enable {
@@ -1239,7 +1543,7 @@ where
                         // VUID-VkPipelineRasterizationStateCreateInfo-pointPolygons-04458
                         if device.enabled_extensions().khr_portability_subset
                             && !device.enabled_features().point_polygons
-                            && polygon_mode == PolygonMode::Point
+                            && polygon_mode == StateMode::Fixed(PolygonMode::Point)
                         {
                             return Err(GraphicsPipelineCreationError::RequirementNotMet {
                                 required_for: "this device is a portability subset device, \
@@ -1256,18 +1560,41 @@ where
                     _ => (),
                 }
 
-                // VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-01507
-                if polygon_mode != PolygonMode::Fill
-                    && !device.enabled_features().fill_mode_non_solid
-                {
-                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                        required_for: "`rasterization_state.polygon_mode` is not \
-                            `PolygonMode::Fill`",
-                        requires_one_of: RequiresOneOf {
-                            features: &["fill_mode_non_solid"],
-                            ..Default::default()
-                        },
-                    });
+                match polygon_mode {
+                    StateMode::Fixed(polygon_mode) => {
+                        // VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-parameter
+                        polygon_mode.validate_device(device)?;
+
+                        // VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-01507
+                        if polygon_mode != PolygonMode::Fill
+                            && !device.enabled_features().fill_mode_non_solid
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.polygon_mode` is not \
+                                    `StateMode::Fixed(PolygonMode::Fill)`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["fill_mode_non_solid"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                    StateMode::Dynamic => {
+                        // VUID?
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_polygon_mode
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`rasterization_state.polygon_mode` is \
+                                    `StateMode::Dynamic`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_polygon_mode"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
                 }
 
                 match cull_mode {
@@ -1515,6 +1842,51 @@ where
                         });
                     }
                 }
+
+                if rasterization_stream != 0 {
+                    if !device.enabled_extensions().ext_transform_feedback {
+                        return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                            required_for: "`rasterization_state.rasterization_stream` is not `0`",
+                            requires_one_of: RequiresOneOf {
+                                device_extensions: &["ext_transform_feedback"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+
+                    // VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-geometryStreams-02324
+                    if !device.enabled_features().geometry_streams {
+                        return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                            required_for: "`rasterization_state.rasterization_stream` is not `0`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["geometry_streams"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+
+                    let properties = device.physical_device().properties();
+
+                    // VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-rasterizationStream-02325
+                    let max = properties.max_transform_feedback_streams.unwrap_or(0);
+
+                    if rasterization_stream >= max {
+                        return Err(
+                            GraphicsPipelineCreationError::MaxTransformFeedbackStreamsExceeded {
+                                rasterization_stream,
+                                max,
+                            },
+                        );
+                    }
+
+                    // VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-rasterizationStream-02326
+                    if !properties
+                        .transform_feedback_rasterization_stream_select
+                        .unwrap_or(false)
+                    {
+                        return Err(GraphicsPipelineCreationError::TransformFeedbackRasterizationStreamSelectNotSupported);
+                    }
+                }
             }
 
             // Discard rectangle state
@@ -1558,6 +1930,135 @@ where
                 }
             }
 
+            // Fragment shading rate state
+            {
+                let FragmentShadingRateState {
+                    fragment_shading_rate,
+                } = fragment_shading_rate_state;
+
+                match fragment_shading_rate {
+                    StateMode::Fixed(fragment_shading_rate) => {
+                        let &FragmentShadingRate {
+                            fragment_size,
+                            combiner_ops,
+                        } = fragment_shading_rate;
+
+                        if device.enabled_extensions().khr_fragment_shading_rate {
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04569
+                            for combiner_op in combiner_ops {
+                                combiner_op.validate_device(device)?;
+                            }
+
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04494
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04495
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04496
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04497
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04498
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04499
+                            if !fragment_size
+                                .iter()
+                                .all(|&size| size.is_power_of_two() && size <= 4)
+                            {
+                                return Err(
+                                    GraphicsPipelineCreationError::FragmentShadingRateFragmentSizeInvalid {
+                                        fragment_size,
+                                    },
+                                );
+                            }
+
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04500
+                            if fragment_size != [1, 1]
+                                && !device.enabled_features().pipeline_fragment_shading_rate
+                            {
+                                return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                    required_for:
+                                        "`fragment_shading_rate_state.fragment_shading_rate` \
+                                        is `StateMode::Fixed(fragment_shading_rate)`, where \
+                                        `fragment_shading_rate.fragment_size` is not `[1, 1]`",
+                                    requires_one_of: RequiresOneOf {
+                                        features: &["pipeline_fragment_shading_rate"],
+                                        ..Default::default()
+                                    },
+                                });
+                            }
+
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04501
+                            if combiner_ops[0] != FragmentShadingRateCombinerOp::Keep
+                                && !device.enabled_features().primitive_fragment_shading_rate
+                            {
+                                return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                    required_for:
+                                        "`fragment_shading_rate_state.fragment_shading_rate` \
+                                        is `StateMode::Fixed(fragment_shading_rate)`, where \
+                                        `fragment_shading_rate.combiner_ops[0]` is not \
+                                        `FragmentShadingRateCombinerOp::Keep`",
+                                    requires_one_of: RequiresOneOf {
+                                        features: &["primitive_fragment_shading_rate"],
+                                        ..Default::default()
+                                    },
+                                });
+                            }
+
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicState-04502
+                            if combiner_ops[1] != FragmentShadingRateCombinerOp::Keep
+                                && !device.enabled_features().attachment_fragment_shading_rate
+                            {
+                                return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                    required_for:
+                                        "`fragment_shading_rate_state.fragment_shading_rate` \
+                                        is `StateMode::Fixed(fragment_shading_rate)`, where \
+                                        `fragment_shading_rate.combiner_ops[1]` is not \
+                                        `FragmentShadingRateCombinerOp::Keep`",
+                                    requires_one_of: RequiresOneOf {
+                                        features: &["attachment_fragment_shading_rate"],
+                                        ..Default::default()
+                                    },
+                                });
+                            }
+
+                            // VUID-VkGraphicsPipelineCreateInfo-fragmentShadingRateNonTrivialCombinerOps-04506
+                            if !properties
+                                .fragment_shading_rate_non_trivial_combiner_ops
+                                .unwrap_or(false)
+                                && combiner_ops.iter().any(|combiner_op| {
+                                    !matches!(
+                                        combiner_op,
+                                        FragmentShadingRateCombinerOp::Keep
+                                            | FragmentShadingRateCombinerOp::Replace
+                                    )
+                                })
+                            {
+                                return Err(GraphicsPipelineCreationError::FragmentShadingRateNonTrivialCombinerOpsNotSupported);
+                            }
+                        } else if *fragment_shading_rate != FragmentShadingRate::default() {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for:
+                                    "`fragment_shading_rate_state.fragment_shading_rate` \
+                                    is `StateMode::Fixed(fragment_shading_rate)`, where \
+                                    `fragment_shading_rate` is not the default value",
+                                requires_one_of: RequiresOneOf {
+                                    device_extensions: &["khr_fragment_shading_rate"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                    StateMode::Dynamic => {
+                        if !device.enabled_extensions().khr_fragment_shading_rate {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for:
+                                    "`fragment_shading_rate_state.fragment_shading_rate` \
+                                    is dynamic",
+                                requires_one_of: RequiresOneOf {
+                                    device_extensions: &["khr_fragment_shading_rate"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                }
+            }
+
             // TODO:
             // VUID-VkPipelineShaderStageCreateInfo-stage-02596
             // VUID-VkPipelineShaderStageCreateInfo-stage-02597
@@ -1835,17 +2336,14 @@ where
             if let Some((entry_point, specialization_data)) = fragment_shader {
                 shader_stages.push(ShaderStageInfo {
                     entry_point,
-                    specialization_map_entries: Fss::descriptors(),
-                    _specialization_data: unsafe {
-                        std::slice::from_raw_parts(
-                            specialization_data as *const _ as *const u8,
-                            size_of_val(specialization_data),
-                        )
-                    },
+                    specialization_constants_compatible: specialization_constants_compatible(
+                        entry_point,
+                        specialization_data,
+                    ),
                 });
 
                 match entry_point.execution() {
-                    ShaderExecution::Fragment => (),
+                    ShaderExecution::Fragment(_) => (),
                     _ => return Err(GraphicsPipelineCreationError::WrongShaderType),
                 }
 
@@ -2175,9 +2673,10 @@ where
                     rasterization_samples,
                     sample_shading,
                     sample_mask: _,
-                    alpha_to_coverage_enable: _,
+                    alpha_to_coverage_enable,
                     alpha_to_one_enable,
                 } = multisample_state;
+                let SampleLocationsState { sample_locations } = sample_locations_state;
 
                 // VUID-VkPipelineMultisampleStateCreateInfo-rasterizationSamples-parameter
                 rasterization_samples.validate_device(device)?;
@@ -2203,32 +2702,133 @@ where
                     }
                 }
 
-                if let Some(min_sample_shading) = sample_shading {
-                    // VUID-VkPipelineMultisampleStateCreateInfo-sampleShadingEnable-00784
-                    if !device.enabled_features().sample_rate_shading {
+                if let Some(min_sample_shading) = sample_shading {
+                    // VUID-VkPipelineMultisampleStateCreateInfo-sampleShadingEnable-00784
+                    if !device.enabled_features().sample_rate_shading {
+                        return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                            required_for: "`multisample_state.sample_shading` is `Some`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["sample_rate_shading"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+
+                    // VUID-VkPipelineMultisampleStateCreateInfo-minSampleShading-00786
+                    // TODO: return error?
+                    assert!((0.0..=1.0).contains(&min_sample_shading));
+                }
+
+                if let StateMode::Dynamic = alpha_to_coverage_enable {
+                    // VUID?
+                    if !device
+                        .enabled_features()
+                        .extended_dynamic_state3_alpha_to_coverage_enable
+                    {
+                        return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                            required_for: "`multisample_state.alpha_to_coverage_enable` is \
+                                `StateMode::Dynamic`",
+                            requires_one_of: RequiresOneOf {
+                                features: &["extended_dynamic_state3_alpha_to_coverage_enable"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+
+                match alpha_to_one_enable {
+                    StateMode::Fixed(false) => (),
+                    StateMode::Fixed(true) => {
+                        // VUID-VkPipelineMultisampleStateCreateInfo-alphaToOneEnable-00785
+                        if !device.enabled_features().alpha_to_one {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`multisample_state.alpha_to_one_enable` is \
+                                    `StateMode::Fixed(true)`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["alpha_to_one"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                    StateMode::Dynamic => {
+                        // VUID?
+                        if !device
+                            .enabled_features()
+                            .extended_dynamic_state3_alpha_to_one_enable
+                        {
+                            return Err(GraphicsPipelineCreationError::RequirementNotMet {
+                                required_for: "`multisample_state.alpha_to_one_enable` is \
+                                    `StateMode::Dynamic`",
+                                requires_one_of: RequiresOneOf {
+                                    features: &["extended_dynamic_state3_alpha_to_one_enable"],
+                                    ..Default::default()
+                                },
+                            });
+                        }
+                    }
+                }
+
+                if let Some(sample_locations) = sample_locations {
+                    if !device.enabled_extensions().ext_sample_locations {
                         return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                            required_for: "`multisample_state.sample_shading` is `Some`",
+                            required_for: "`sample_locations_state.sample_locations` is `Some`",
                             requires_one_of: RequiresOneOf {
-                                features: &["sample_rate_shading"],
+                                device_extensions: &["ext_sample_locations"],
                                 ..Default::default()
                             },
                         });
                     }
 
-                    // VUID-VkPipelineMultisampleStateCreateInfo-minSampleShading-00786
-                    // TODO: return error?
-                    assert!((0.0..=1.0).contains(&min_sample_shading));
-                }
+                    match sample_locations {
+                        StateMode::Fixed(sample_locations) => {
+                            let &SampleLocationsInfo {
+                                sample_locations_per_pixel,
+                                sample_location_grid_size,
+                                sample_locations: ref sample_locations_vec,
+                            } = sample_locations;
+
+                            // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-parameter
+                            sample_locations_per_pixel.validate_device(device)?;
+
+                            // VUID-VkSampleLocationsInfoEXT-sampleLocationsPerPixel-01526
+                            if !properties
+                                .sample_location_sample_counts
+                                .unwrap_or_default()
+                                .contains_enum(sample_locations_per_pixel)
+                            {
+                                return Err(
+                                    GraphicsPipelineCreationError::SampleLocationsPerPixelNotSupported,
+                                );
+                            }
 
-                // VUID-VkPipelineMultisampleStateCreateInfo-alphaToOneEnable-00785
-                if alpha_to_one_enable && !device.enabled_features().alpha_to_one {
-                    return Err(GraphicsPipelineCreationError::RequirementNotMet {
-                        required_for: "`multisample_state.alpha_to_one_enable` is set",
-                        requires_one_of: RequiresOneOf {
-                            features: &["alpha_to_one"],
-                            ..Default::default()
-                        },
-                    });
+                            // VUID-VkSampleLocationsInfoEXT-sampleLocationsCount-01527
+                            let required = sample_locations_per_pixel as u32
+                                * sample_location_grid_size[0]
+                                * sample_location_grid_size[1];
+
+                            if sample_locations_vec.len() as u32 != required {
+                                return Err(
+                                    GraphicsPipelineCreationError::SampleLocationsCountMismatch {
+                                        provided: sample_locations_vec.len() as u32,
+                                        required,
+                                    },
+                                );
+                            }
+
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-01523
+                            if sample_locations_per_pixel != rasterization_samples {
+                                return Err(
+                                    GraphicsPipelineCreationError::SampleLocationsPerPixelMismatch,
+                                );
+                            }
+
+                            // TODO:
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-01521
+                            // VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-01522
+                        }
+                        StateMode::Dynamic => (),
+                    }
                 }
 
                 // TODO:
@@ -2470,6 +3070,93 @@ where
             }
         }
 
+        /*
+            Vertex input interface
+        */
+
+        // VUID?
+        // Check that the vertex input state contains attributes for all the shader's input
+        // variables. Either of them may be provided by a library.
+        let vertex_shader_input_interface = if has.pre_rasterization_shader_state {
+            vertex_shader
+                .as_ref()
+                .map(|(entry_point, _)| entry_point.input_interface())
+        } else {
+            libraries
+                .iter()
+                .find_map(|library| library.vertex_shader_input_interface.as_ref())
+        };
+        let vertex_input_state = if has.vertex_input_state {
+            Some(vertex_input_state)
+        } else {
+            libraries
+                .iter()
+                .find(|library| {
+                    library
+                        .library_flags()
+                        .intersects(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE)
+                })
+                .map(|library| library.vertex_input_state())
+        };
+
+        if let (Some(vertex_shader_input_interface), Some(vertex_input_state)) =
+            (vertex_shader_input_interface, vertex_input_state)
+        {
+            for element in vertex_shader_input_interface.elements() {
+                assert!(!element.ty.is_64bit); // TODO: implement
+                let location_range =
+                    element.location..element.location + element.ty.num_locations();
+
+                for location in location_range {
+                    let attribute_desc = match vertex_input_state.attributes.get(&location) {
+                        Some(attribute_desc) => attribute_desc,
+                        None => {
+                            return Err(
+                                GraphicsPipelineCreationError::VertexInputAttributeMissing {
+                                    location,
+                                },
+                            )
+                        }
+                    };
+
+                    // TODO: Check component assignments too. Multiple variables can occupy the same
+                    // location but in different components.
+
+                    let shader_type = element.ty.to_format().type_color().unwrap();
+                    let attribute_type = attribute_desc.format.type_color().unwrap();
+
+                    if !matches!(
+                        (shader_type, attribute_type),
+                        (
+                            NumericType::SFLOAT
+                                | NumericType::UFLOAT
+                                | NumericType::SNORM
+                                | NumericType::UNORM
+                                | NumericType::SSCALED
+                                | NumericType::USCALED
+                                | NumericType::SRGB,
+                            NumericType::SFLOAT
+                                | NumericType::UFLOAT
+                                | NumericType::SNORM
+                                | NumericType::UNORM
+                                | NumericType::SSCALED
+                                | NumericType::USCALED
+                                | NumericType::SRGB,
+                        ) | (NumericType::SINT, NumericType::SINT)
+                            | (NumericType::UINT, NumericType::UINT)
+                    ) {
+                        return Err(
+                            GraphicsPipelineCreationError::VertexInputAttributeIncompatibleFormat {
+                                location,
+                                shader_type,
+                                attribute_type,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
         /*
             Generic shader checks
         */
@@ -2481,19 +3168,8 @@ where
                 stage_info.entry_point.push_constant_requirements(),
             )?;
 
-            for (constant_id, reqs) in stage_info
-                .entry_point
-                .specialization_constant_requirements()
-            {
-                let map_entry = stage_info
-                    .specialization_map_entries
-                    .iter()
-                    .find(|desc| desc.constant_id == constant_id)
-                    .ok_or(GraphicsPipelineCreationError::IncompatibleSpecializationConstants)?;
-
-                if map_entry.size as DeviceSize != reqs.size {
-                    return Err(GraphicsPipelineCreationError::IncompatibleSpecializationConstants);
-                }
+            if !stage_info.specialization_constants_compatible {
+                return Err(GraphicsPipelineCreationError::IncompatibleSpecializationConstants);
             }
         }
 
@@ -2519,11 +3195,9 @@ where
 
         // Dynamic states not handled yet:
         // - ViewportWScaling (VkPipelineViewportWScalingStateCreateInfoNV)
-        // - SampleLocations (VkPipelineSampleLocationsStateCreateInfoEXT)
         // - ViewportShadingRatePalette (VkPipelineViewportShadingRateImageStateCreateInfoNV)
         // - ViewportCoarseSampleOrder (VkPipelineViewportCoarseSampleOrderStateCreateInfoNV)
         // - ExclusiveScissor (VkPipelineViewportExclusiveScissorStateCreateInfoNV)
-        // - FragmentShadingRate (VkPipelineFragmentShadingRateStateCreateInfoKHR)
 
         Ok(())
     }
@@ -2546,6 +3220,10 @@ where
         let Self {
             render_pass,
             cache,
+            flags,
+            base_pipeline,
+            library_flags,
+            libraries,
 
             vertex_shader,
             tessellation_shaders,
@@ -2557,8 +3235,10 @@ where
             tessellation_state,
             viewport_state,
             discard_rectangle_state,
+            fragment_shading_rate_state,
             rasterization_state,
             multisample_state,
+            sample_locations_state,
             depth_stencil_state,
             color_blend_state,
         } = self;
@@ -2751,18 +3431,17 @@ where
         let mut scissors_vk: SmallVec<[_; 2]> = SmallVec::new();
         let mut viewport_state_vk = None;
         let mut rasterization_line_state_vk = None;
+        let mut rasterization_stream_state_vk = None;
         let mut rasterization_state_vk = None;
         let mut discard_rectangles: SmallVec<[_; 2]> = SmallVec::new();
         let mut discard_rectangle_state_vk = None;
+        let mut fragment_shading_rate_state_vk = None;
 
         if has.pre_rasterization_shader_state {
             // Vertex shader
             if let Some((entry_point, specialization_data)) = vertex_shader {
-                let specialization_map_entries = Vss::descriptors();
-                let specialization_data = slice::from_raw_parts(
-                    specialization_data as *const _ as *const u8,
-                    size_of_val(specialization_data),
-                );
+                let specialization_map_entries = specialization_data.map_entries();
+                let specialization_data = specialization_data.data();
 
                 let specialization_info_vk =
                     vertex_shader_specialization_vk.insert(ash::vk::SpecializationInfo {
@@ -2798,11 +3477,8 @@ where
             if let Some(tessellation_shaders) = tessellation_shaders {
                 {
                     let (entry_point, specialization_data) = &tessellation_shaders.control;
-                    let specialization_map_entries = Tcss::descriptors();
-                    let specialization_data = slice::from_raw_parts(
-                        specialization_data as *const _ as *const u8,
-                        size_of_val(specialization_data),
-                    );
+                    let specialization_map_entries = specialization_data.map_entries();
+                    let specialization_data = specialization_data.data();
 
                     let specialization_info_vk = tessellation_control_shader_specialization_vk
                         .insert(ash::vk::SpecializationInfo {
@@ -2836,11 +3512,8 @@ where
 
                 {
                     let (entry_point, specialization_data) = &tessellation_shaders.evaluation;
-                    let specialization_map_entries = Tess::descriptors();
-                    let specialization_data = slice::from_raw_parts(
-                        specialization_data as *const _ as *const u8,
-                        size_of_val(specialization_data),
-                    );
+                    let specialization_map_entries = specialization_data.map_entries();
+                    let specialization_data = specialization_data.data();
 
                     let specialization_info_vk = tessellation_evaluation_shader_specialization_vk
                         .insert(ash::vk::SpecializationInfo {
@@ -2875,11 +3548,8 @@ where
 
             // Geometry shader
             if let Some((entry_point, specialization_data)) = geometry_shader {
-                let specialization_map_entries = Gss::descriptors();
-                let specialization_data = slice::from_raw_parts(
-                    specialization_data as *const _ as *const u8,
-                    size_of_val(specialization_data),
-                );
+                let specialization_map_entries = specialization_data.map_entries();
+                let specialization_data = specialization_data.data();
 
                 let specialization_info_vk =
                     geometry_shader_specialization_vk.insert(ash::vk::SpecializationInfo {
@@ -2923,8 +3593,20 @@ where
                     line_width,
                     line_rasterization_mode,
                     line_stipple,
+                    rasterization_stream,
                 } = rasterization_state;
 
+                let depth_clamp_enable = match depth_clamp_enable {
+                    StateMode::Fixed(depth_clamp_enable) => {
+                        dynamic_state.insert(DynamicState::DepthClampEnable, false);
+                        depth_clamp_enable as ash::vk::Bool32
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::DepthClampEnable, true);
+                        ash::vk::FALSE
+                    }
+                };
+
                 let rasterizer_discard_enable = match rasterizer_discard_enable {
                     StateMode::Fixed(rasterizer_discard_enable) => {
                         dynamic_state.insert(DynamicState::RasterizerDiscardEnable, false);
@@ -2936,6 +3618,17 @@ where
                     }
                 };
 
+                let polygon_mode = match polygon_mode {
+                    StateMode::Fixed(polygon_mode) => {
+                        dynamic_state.insert(DynamicState::PolygonMode, false);
+                        polygon_mode.into()
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::PolygonMode, true);
+                        PolygonMode::default().into()
+                    }
+                };
+
                 let cull_mode = match cull_mode {
                     StateMode::Fixed(cull_mode) => {
                         dynamic_state.insert(DynamicState::CullMode, false);
@@ -3000,9 +3693,9 @@ where
                 let rasterization_state =
                     rasterization_state_vk.insert(ash::vk::PipelineRasterizationStateCreateInfo {
                         flags: ash::vk::PipelineRasterizationStateCreateFlags::empty(),
-                        depth_clamp_enable: depth_clamp_enable as ash::vk::Bool32,
+                        depth_clamp_enable,
                         rasterizer_discard_enable,
-                        polygon_mode: polygon_mode.into(),
+                        polygon_mode,
                         cull_mode,
                         front_face,
                         depth_bias_enable,
@@ -3042,6 +3735,17 @@ where
                         },
                     ) as *const _ as *const _;
                 }
+
+                if rasterization_stream != 0 {
+                    let rasterization_stream_state = rasterization_stream_state_vk.insert(
+                        ash::vk::PipelineRasterizationStateStreamCreateInfoEXT {
+                            rasterization_stream,
+                            ..Default::default()
+                        },
+                    );
+                    rasterization_stream_state.p_next = rasterization_state.p_next;
+                    rasterization_state.p_next = rasterization_stream_state as *const _ as *const _;
+                }
             }
 
             // Discard rectangle state
@@ -3072,6 +3776,38 @@ where
                     },
                 );
             }
+
+            // Fragment shading rate state
+            if device.enabled_extensions().khr_fragment_shading_rate {
+                let FragmentShadingRateState {
+                    fragment_shading_rate,
+                } = fragment_shading_rate_state;
+
+                let FragmentShadingRate {
+                    fragment_size,
+                    combiner_ops,
+                } = match fragment_shading_rate {
+                    StateMode::Fixed(fragment_shading_rate) => {
+                        dynamic_state.insert(DynamicState::FragmentShadingRate, false);
+                        *fragment_shading_rate
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::FragmentShadingRate, true);
+                        Default::default()
+                    }
+                };
+
+                let _ = fragment_shading_rate_state_vk.insert(
+                    ash::vk::PipelineFragmentShadingRateStateCreateInfoKHR {
+                        fragment_size: ash::vk::Extent2D {
+                            width: fragment_size[0],
+                            height: fragment_size[1],
+                        },
+                        combiner_ops: combiner_ops.map(Into::into),
+                        ..Default::default()
+                    },
+                );
+            }
         }
 
         // Tessellation state
@@ -3215,11 +3951,8 @@ where
         if has.fragment_shader_state {
             // Fragment shader
             if let Some((entry_point, specialization_data)) = fragment_shader {
-                let specialization_map_entries = Fss::descriptors();
-                let specialization_data = slice::from_raw_parts(
-                    specialization_data as *const _ as *const u8,
-                    size_of_val(specialization_data),
-                );
+                let specialization_map_entries = specialization_data.map_entries();
+                let specialization_data = specialization_data.data();
 
                 let specialization_info_vk =
                     fragment_shader_specialization_vk.insert(ash::vk::SpecializationInfo {
@@ -3444,6 +4177,8 @@ where
         */
 
         let mut multisample_state_vk = None;
+        let mut sample_locations_vk: SmallVec<[_; 4]> = SmallVec::new();
+        let mut sample_locations_state_vk = None;
         let mut color_blend_attachments_vk: SmallVec<[_; 4]> = SmallVec::new();
         let mut color_write_enables_vk: SmallVec<[_; 4]> = SmallVec::new();
         let mut color_write_vk = None;
@@ -3459,6 +4194,7 @@ where
                     alpha_to_coverage_enable,
                     alpha_to_one_enable,
                 } = multisample_state;
+                let SampleLocationsState { sample_locations } = sample_locations_state;
 
                 let (sample_shading_enable, min_sample_shading) =
                     if let Some(min_sample_shading) = sample_shading {
@@ -3467,16 +4203,84 @@ where
                         (ash::vk::FALSE, 0.0)
                     };
 
-                let _ = multisample_state_vk.insert(ash::vk::PipelineMultisampleStateCreateInfo {
-                    flags: ash::vk::PipelineMultisampleStateCreateFlags::empty(),
-                    rasterization_samples: rasterization_samples.into(),
-                    sample_shading_enable,
-                    min_sample_shading,
-                    p_sample_mask: sample_mask as _,
-                    alpha_to_coverage_enable: alpha_to_coverage_enable as ash::vk::Bool32,
-                    alpha_to_one_enable: alpha_to_one_enable as ash::vk::Bool32,
-                    ..Default::default()
-                });
+                let alpha_to_coverage_enable = match alpha_to_coverage_enable {
+                    StateMode::Fixed(alpha_to_coverage_enable) => {
+                        dynamic_state.insert(DynamicState::AlphaToCoverageEnable, false);
+                        alpha_to_coverage_enable as ash::vk::Bool32
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::AlphaToCoverageEnable, true);
+                        ash::vk::FALSE
+                    }
+                };
+
+                let alpha_to_one_enable = match alpha_to_one_enable {
+                    StateMode::Fixed(alpha_to_one_enable) => {
+                        dynamic_state.insert(DynamicState::AlphaToOneEnable, false);
+                        alpha_to_one_enable as ash::vk::Bool32
+                    }
+                    StateMode::Dynamic => {
+                        dynamic_state.insert(DynamicState::AlphaToOneEnable, true);
+                        ash::vk::FALSE
+                    }
+                };
+
+                let multisample_state =
+                    multisample_state_vk.insert(ash::vk::PipelineMultisampleStateCreateInfo {
+                        flags: ash::vk::PipelineMultisampleStateCreateFlags::empty(),
+                        rasterization_samples: rasterization_samples.into(),
+                        sample_shading_enable,
+                        min_sample_shading,
+                        p_sample_mask: sample_mask as _,
+                        alpha_to_coverage_enable,
+                        alpha_to_one_enable,
+                        ..Default::default()
+                    });
+
+                if let Some(sample_locations) = sample_locations {
+                    let sample_locations_info = match sample_locations {
+                        StateMode::Fixed(sample_locations) => {
+                            dynamic_state.insert(DynamicState::SampleLocations, false);
+
+                            let &SampleLocationsInfo {
+                                sample_locations_per_pixel,
+                                sample_location_grid_size,
+                                sample_locations: ref sample_locations_vec,
+                            } = sample_locations;
+
+                            sample_locations_vk.extend(
+                                sample_locations_vec
+                                    .iter()
+                                    .map(|&[x, y]| ash::vk::SampleLocationEXT { x, y }),
+                            );
+
+                            ash::vk::SampleLocationsInfoEXT {
+                                sample_locations_per_pixel: sample_locations_per_pixel.into(),
+                                sample_location_grid_size: ash::vk::Extent2D {
+                                    width: sample_location_grid_size[0],
+                                    height: sample_location_grid_size[1],
+                                },
+                                sample_locations_count: sample_locations_vk.len() as u32,
+                                p_sample_locations: sample_locations_vk.as_ptr(),
+                                ..Default::default()
+                            }
+                        }
+                        StateMode::Dynamic => {
+                            dynamic_state.insert(DynamicState::SampleLocations, true);
+                            Default::default()
+                        }
+                    };
+
+                    let sample_locations_state = sample_locations_state_vk.insert(
+                        ash::vk::PipelineSampleLocationsStateCreateInfoEXT {
+                            sample_locations_enable: ash::vk::TRUE,
+                            sample_locations_info,
+                            ..Default::default()
+                        },
+                    );
+                    sample_locations_state.p_next = multisample_state.p_next;
+                    multisample_state.p_next = sample_locations_state as *const _ as *const _;
+                }
             }
         }
 
@@ -3604,12 +4408,58 @@ where
             }
         }
 
+        /*
+            Flags
+        */
+
+        let mut flags_vk: ash::vk::PipelineCreateFlags = (*flags).into();
+
+        if device.enabled_features().pipeline_executable_info {
+            flags_vk |= ash::vk::PipelineCreateFlags::CAPTURE_STATISTICS_KHR
+                | ash::vk::PipelineCreateFlags::CAPTURE_INTERNAL_REPRESENTATIONS_KHR;
+        }
+
+        if pipeline_layout
+            .set_layouts()
+            .iter()
+            .any(|set_layout| set_layout.descriptor_buffer())
+        {
+            flags_vk |= ash::vk::PipelineCreateFlags::DESCRIPTOR_BUFFER_EXT;
+        }
+
+        /*
+            Pipeline libraries
+        */
+
+        let mut library_create_info_vk = None;
+        let mut libraries_vk: SmallVec<[_; 4]> = SmallVec::new();
+        let mut library_info_vk = None;
+
+        if !library_flags.is_empty() {
+            flags_vk |= ash::vk::PipelineCreateFlags::LIBRARY_KHR;
+
+            let _ = library_create_info_vk.insert(ash::vk::GraphicsPipelineLibraryCreateInfoEXT {
+                flags: (*library_flags).into(),
+                ..Default::default()
+            });
+        }
+
+        if !libraries.is_empty() {
+            libraries_vk.extend(libraries.iter().map(|library| library.handle()));
+
+            let _ = library_info_vk.insert(ash::vk::PipelineLibraryCreateInfoKHR {
+                library_count: libraries_vk.len() as u32,
+                p_libraries: libraries_vk.as_ptr(),
+                ..Default::default()
+            });
+        }
+
         /*
             Create
         */
 
         let mut create_info = ash::vk::GraphicsPipelineCreateInfo {
-            flags: ash::vk::PipelineCreateFlags::empty(), // TODO: some flags are available but none are critical
+            flags: flags_vk,
             stage_count: stages_vk.len() as u32,
             p_stages: stages_vk.as_ptr(),
             p_vertex_input_state: vertex_input_state_vk
@@ -3651,8 +4501,13 @@ where
             layout: pipeline_layout.handle(),
             render_pass: render_pass_vk,
             subpass: subpass_vk,
-            base_pipeline_handle: ash::vk::Pipeline::null(), // TODO:
-            base_pipeline_index: -1,                         // TODO:
+            base_pipeline_handle: base_pipeline
+                .as_ref()
+                .filter(|_| flags.intersects(PipelineCreateFlags::DERIVATIVE))
+                .map_or(ash::vk::Pipeline::null(), |base_pipeline| {
+                    base_pipeline.handle()
+                }),
+            base_pipeline_index: -1,
             ..Default::default()
         };
 
@@ -3661,11 +4516,26 @@ where
             create_info.p_next = info as *const _ as *const _;
         }
 
+        if let Some(info) = fragment_shading_rate_state_vk.as_mut() {
+            info.p_next = create_info.p_next;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
         if let Some(info) = rendering_create_info_vk.as_mut() {
             info.p_next = create_info.p_next;
             create_info.p_next = info as *const _ as *const _;
         }
 
+        if let Some(info) = library_create_info_vk.as_mut() {
+            info.p_next = create_info.p_next as *mut _;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
+        if let Some(info) = library_info_vk.as_mut() {
+            info.p_next = create_info.p_next;
+            create_info.p_next = info as *const _ as *const _;
+        }
+
         let cache_handle = match cache.as_ref() {
             Some(cache) => cache.handle(),
             None => ash::vk::PipelineCache::null(),
@@ -3708,15 +4578,49 @@ where
 
 struct ShaderStageInfo<'a> {
     entry_point: &'a EntryPoint<'a>,
-    specialization_map_entries: &'a [SpecializationMapEntry],
-    _specialization_data: &'a [u8],
+    specialization_constants_compatible: bool,
+}
+
+/// Returns whether a pipeline library with the given flags makes use of the render pass.
+fn library_uses_render_pass(library_flags: GraphicsPipelineLibraryFlags) -> bool {
+    library_flags.intersects(
+        GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS
+            | GraphicsPipelineLibraryFlags::FRAGMENT_SHADER
+            | GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE,
+    )
+}
+
+/// Returns whether two pipeline libraries that use the given render passes can be linked.
+fn render_pass_is_compatible_with(
+    render_pass: &PipelineRenderPassType,
+    other: &PipelineRenderPassType,
+) -> bool {
+    match (render_pass, other) {
+        (
+            PipelineRenderPassType::BeginRenderPass(subpass),
+            PipelineRenderPassType::BeginRenderPass(other),
+        ) => {
+            subpass.index() == other.index()
+                && subpass
+                    .render_pass()
+                    .is_compatible_with(other.render_pass())
+        }
+        (
+            PipelineRenderPassType::BeginRendering(rendering_info),
+            PipelineRenderPassType::BeginRendering(other),
+        ) => {
+            rendering_info.view_mask == other.view_mask
+                && rendering_info.color_attachment_formats == other.color_attachment_formats
+                && rendering_info.depth_attachment_format == other.depth_attachment_format
+                && rendering_info.stencil_attachment_format == other.stencil_attachment_format
+        }
+        _ => false,
+    }
 }
 
 impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     GraphicsPipelineBuilder<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
 {
-    // TODO: add pipeline derivate system
-
     /// Sets the vertex shader to use.
     // TODO: correct specialization constants
     pub fn vertex_shader<'vs2, Vss2>(
@@ -3730,6 +4634,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
+            library_flags: self.library_flags,
+            libraries: self.libraries,
 
             vertex_shader: Some((shader, specialization_constants)),
             tessellation_shaders: self.tessellation_shaders,
@@ -3741,8 +4649,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
+            sample_locations_state: self.sample_locations_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
         }
@@ -3764,6 +4674,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
+            library_flags: self.library_flags,
+            libraries: self.libraries,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: Some(TessellationShaders {
@@ -3778,8 +4692,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
+            sample_locations_state: self.sample_locations_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
         }
@@ -3798,6 +4714,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
+            library_flags: self.library_flags,
+            libraries: self.libraries,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -3809,8 +4729,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
+            sample_locations_state: self.sample_locations_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
         }
@@ -3831,6 +4753,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
+            library_flags: self.library_flags,
+            libraries: self.libraries,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -3842,8 +4768,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
+            sample_locations_state: self.sample_locations_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
         }
@@ -3862,6 +4790,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         GraphicsPipelineBuilder {
             render_pass: self.render_pass,
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
+            library_flags: self.library_flags,
+            libraries: self.libraries,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -3873,8 +4805,10 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             tessellation_state: self.tessellation_state,
             viewport_state: self.viewport_state,
             discard_rectangle_state: self.discard_rectangle_state,
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
+            sample_locations_state: self.sample_locations_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
         }
@@ -3920,6 +4854,18 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
+    /// Sets the fragment shading rate state.
+    ///
+    /// The default value is [`FragmentShadingRateState::default()`].
+    #[inline]
+    pub fn fragment_shading_rate_state(
+        mut self,
+        fragment_shading_rate_state: FragmentShadingRateState,
+    ) -> Self {
+        self.fragment_shading_rate_state = fragment_shading_rate_state;
+        self
+    }
+
     /// Sets the rasterization state.
     ///
     /// The default value is [`RasterizationState::default()`].
@@ -3938,6 +4884,15 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
+    /// Sets the custom sample locations state.
+    ///
+    /// The default value is [`SampleLocationsState::default()`].
+    #[inline]
+    pub fn sample_locations_state(mut self, sample_locations_state: SampleLocationsState) -> Self {
+        self.sample_locations_state = sample_locations_state;
+        self
+    }
+
     /// Sets the depth/stencil state.
     ///
     /// The default value is [`DepthStencilState::default()`].
@@ -4203,7 +5158,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `rasterization_state` instead")]
     #[inline]
     pub fn depth_clamp(mut self, clamp: bool) -> Self {
-        self.rasterization_state.depth_clamp_enable = clamp;
+        self.rasterization_state.depth_clamp_enable = StateMode::Fixed(clamp);
         self
     }
 
@@ -4270,7 +5225,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `rasterization_state` instead")]
     #[inline]
     pub fn polygon_mode_fill(mut self) -> Self {
-        self.rasterization_state.polygon_mode = PolygonMode::Fill;
+        self.rasterization_state.polygon_mode = StateMode::Fixed(PolygonMode::Fill);
         self
     }
 
@@ -4278,7 +5233,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `rasterization_state` instead")]
     #[inline]
     pub fn polygon_mode_line(mut self) -> Self {
-        self.rasterization_state.polygon_mode = PolygonMode::Line;
+        self.rasterization_state.polygon_mode = StateMode::Fixed(PolygonMode::Line);
         self
     }
 
@@ -4286,7 +5241,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `rasterization_state` instead")]
     #[inline]
     pub fn polygon_mode_point(mut self) -> Self {
-        self.rasterization_state.polygon_mode = PolygonMode::Point;
+        self.rasterization_state.polygon_mode = StateMode::Fixed(PolygonMode::Point);
         self
     }
 
@@ -4345,14 +5300,14 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     // TODO: doc
     #[deprecated(since = "0.27.0", note = "Use `multisample_state` instead")]
     pub fn alpha_to_coverage_disabled(mut self) -> Self {
-        self.multisample_state.alpha_to_coverage_enable = false;
+        self.multisample_state.alpha_to_coverage_enable = StateMode::Fixed(false);
         self
     }
 
     // TODO: doc
     #[deprecated(since = "0.27.0", note = "Use `multisample_state` instead")]
     pub fn alpha_to_coverage_enabled(mut self) -> Self {
-        self.multisample_state.alpha_to_coverage_enable = true;
+        self.multisample_state.alpha_to_coverage_enable = StateMode::Fixed(true);
         self
     }
 
@@ -4362,7 +5317,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `multisample_state` instead")]
     #[inline]
     pub fn alpha_to_one_disabled(mut self) -> Self {
-        self.multisample_state.alpha_to_one_enable = false;
+        self.multisample_state.alpha_to_one_enable = StateMode::Fixed(false);
         self
     }
 
@@ -4375,7 +5330,7 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
     #[deprecated(since = "0.27.0", note = "Use `multisample_state` instead")]
     #[inline]
     pub fn alpha_to_one_enabled(mut self) -> Self {
-        self.multisample_state.alpha_to_one_enable = true;
+        self.multisample_state.alpha_to_one_enable = StateMode::Fixed(true);
         self
     }
 
@@ -4512,11 +5467,69 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
         self
     }
 
+    /// Sets the flags to create the pipeline with. The default is empty.
+    #[inline]
+    pub fn flags(mut self, flags: PipelineCreateFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Sets the pipeline that this pipeline is a derivative of. The default is `None`.
+    ///
+    /// This is only used if `flags` contains [`PipelineCreateFlags::DERIVATIVE`]. The base
+    /// pipeline must have been created with [`PipelineCreateFlags::ALLOW_DERIVATIVES`].
+    #[inline]
+    pub fn base_pipeline(mut self, base_pipeline: Arc<GraphicsPipeline>) -> Self {
+        self.base_pipeline = Some(base_pipeline);
+        self
+    }
+
+    /// Creates the pipeline as a pipeline library, that contains only the given parts of a
+    /// graphics pipeline. Only the shaders and state belonging to these parts are used, the rest
+    /// is ignored. The default is empty, which creates a complete pipeline.
+    ///
+    /// A render pass must be provided for all libraries, even if it is only used by some of the
+    /// parts.
+    ///
+    /// The [`graphics_pipeline_library`] feature must be enabled on the device.
+    ///
+    /// [`graphics_pipeline_library`]: crate::device::Features::graphics_pipeline_library
+    #[inline]
+    pub fn library(mut self, library_flags: GraphicsPipelineLibraryFlags) -> Self {
+        self.library_flags = library_flags;
+        self
+    }
+
+    /// Links the given pipeline libraries into the pipeline. The parts of the pipeline that are
+    /// contained in the libraries are taken from them, and the shaders and state belonging to
+    /// these parts are ignored on the builder.
+    ///
+    /// If no render pass is set on the builder, the render pass of the first library that uses
+    /// one is used. The render passes of all libraries, except vertex input interface libraries,
+    /// must be compatible with each other. The pipeline layout must be compatible with the ones
+    /// that the libraries containing shaders were created with.
+    ///
+    /// The [`graphics_pipeline_library`] feature must be enabled on the device.
+    ///
+    /// [`graphics_pipeline_library`]: crate::device::Features::graphics_pipeline_library
+    #[inline]
+    pub fn link_libraries(
+        mut self,
+        libraries: impl IntoIterator<Item = Arc<GraphicsPipeline>>,
+    ) -> Self {
+        self.libraries.extend(libraries);
+        self
+    }
+
     /// Sets the render pass subpass to use.
     pub fn render_pass(self, render_pass: impl Into<PipelineRenderPassType>) -> Self {
         GraphicsPipelineBuilder {
             render_pass: Some(render_pass.into()),
             cache: self.cache,
+            flags: self.flags,
+            base_pipeline: self.base_pipeline,
+            library_flags: self.library_flags,
+            libraries: self.libraries,
 
             vertex_shader: self.vertex_shader,
             tessellation_shaders: self.tessellation_shaders,
@@ -4529,10 +5542,13 @@ impl<'vs, 'tcs, 'tes, 'gs, 'fs, Vdef, Vss, Tcss, Tess, Gss, Fss>
             viewport_state: self.viewport_state,
             rasterization_state: self.rasterization_state,
             multisample_state: self.multisample_state,
+            sample_locations_state: self.sample_locations_state,
             depth_stencil_state: self.depth_stencil_state,
             color_blend_state: self.color_blend_state,
 
             discard_rectangle_state: self.discard_rectangle_state,
+
+            fragment_shading_rate_state: self.fragment_shading_rate_state,
         }
     }
 
@@ -4562,6 +5578,10 @@ where
         GraphicsPipelineBuilder {
             render_pass: self.render_pass.clone(),
             cache: self.cache.clone(),
+            flags: self.flags,
+            base_pipeline: self.base_pipeline.clone(),
+            library_flags: self.library_flags,
+            libraries: self.libraries.clone(),
 
             vertex_shader: self.vertex_shader.clone(),
             tessellation_shaders: self.tessellation_shaders.clone(),
@@ -4574,10 +5594,161 @@ where
             viewport_state: self.viewport_state.clone(),
             rasterization_state: self.rasterization_state.clone(),
             multisample_state: self.multisample_state,
+            sample_locations_state: self.sample_locations_state.clone(),
             depth_stencil_state: self.depth_stencil_state.clone(),
             color_blend_state: self.color_blend_state.clone(),
 
             discard_rectangle_state: self.discard_rectangle_state.clone(),
+
+            fragment_shading_rate_state: self.fragment_shading_rate_state.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        format::Format,
+        pipeline::{
+            graphics::{
+                multisample::MultisampleState, GraphicsPipeline, GraphicsPipelineCreationError,
+                GraphicsPipelineLibraryFlags,
+            },
+            StateMode,
+        },
+        render_pass::Subpass,
+        RequiresOneOf,
+    };
+
+    #[test]
+    fn link_incompatible_render_pass() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            features: [graphics_pipeline_library]
+        );
+
+        let render_pass = single_pass_renderpass!(device.clone(),
+            attachments: {},
+            pass: {
+                color: [],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+        let other_render_pass = single_pass_renderpass!(device.clone(),
+            attachments: {
+                depth: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth}
+            }
+        )
+        .unwrap();
+
+        let library = GraphicsPipeline::start()
+            .library(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE)
+            .render_pass(Subpass::from(render_pass, 0).unwrap())
+            .build(device.clone())
+            .unwrap();
+
+        match GraphicsPipeline::start()
+            .library(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER)
+            .render_pass(Subpass::from(other_render_pass, 0).unwrap())
+            .link_libraries([library])
+            .build(device)
+        {
+            Err(GraphicsPipelineCreationError::LibraryRenderPassIncompatible {
+                library_index: 0,
+            }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn dynamic_alpha_to_coverage_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            features: [graphics_pipeline_library]
+        );
+
+        let render_pass = single_pass_renderpass!(device.clone(),
+            attachments: {},
+            pass: {
+                color: [],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        match GraphicsPipeline::start()
+            .library(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE)
+            .render_pass(Subpass::from(render_pass, 0).unwrap())
+            .multisample_state(MultisampleState {
+                alpha_to_coverage_enable: StateMode::Dynamic,
+                ..Default::default()
+            })
+            .build(device)
+        {
+            Err(GraphicsPipelineCreationError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"extended_dynamic_state3_alpha_to_coverage_enable") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn alpha_to_one_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            features: [graphics_pipeline_library]
+        );
+
+        let render_pass = single_pass_renderpass!(device.clone(),
+            attachments: {},
+            pass: {
+                color: [],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        match GraphicsPipeline::start()
+            .library(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE)
+            .render_pass(Subpass::from(render_pass.clone(), 0).unwrap())
+            .multisample_state(MultisampleState {
+                alpha_to_one_enable: StateMode::Fixed(true),
+                ..Default::default()
+            })
+            .build(device.clone())
+        {
+            Err(GraphicsPipelineCreationError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"alpha_to_one") => (),
+            _ => panic!(),
+        }
+
+        match GraphicsPipeline::start()
+            .library(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE)
+            .render_pass(Subpass::from(render_pass, 0).unwrap())
+            .multisample_state(MultisampleState {
+                alpha_to_one_enable: StateMode::Dynamic,
+                ..Default::default()
+            })
+            .build(device)
+        {
+            Err(GraphicsPipelineCreationError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"extended_dynamic_state3_alpha_to_one_enable") => (),
+            _ => panic!(),
         }
     }
 }
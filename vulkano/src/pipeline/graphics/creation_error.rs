@@ -12,7 +12,7 @@ use crate::{
     descriptor_set::layout::DescriptorSetLayoutCreationError,
     format::{Format, NumericType},
     pipeline::layout::{PipelineLayoutCreationError, PipelineLayoutSupersetError},
-    shader::ShaderInterfaceMismatchError,
+    shader::{DescriptorBindingRequirementsIncompatible, ShaderInterfaceMismatchError},
     OomError, RequirementNotMet, RequiresOneOf, VulkanError,
 };
 use std::{
@@ -28,6 +28,12 @@ pub enum GraphicsPipelineCreationError {
         requires_one_of: RequiresOneOf,
     },
 
+    /// `flags` contains `PipelineCreateFlags::DERIVATIVE`, but no base pipeline was provided.
+    BasePipelineMissing,
+
+    /// The base pipeline was not created with `PipelineCreateFlags::ALLOW_DERIVATIVES`.
+    BasePipelineNotAllowDerivatives,
+
     /// A color attachment has a format that does not support blending.
     ColorAttachmentFormatBlendNotSupported { attachment_index: u32 },
 
@@ -40,10 +46,22 @@ pub enum GraphicsPipelineCreationError {
     /// The depth and stencil attachments have different formats.
     DepthStencilAttachmentFormatMismatch,
 
+    /// The descriptor requirements of the shaders are not compatible with each other.
+    DescriptorRequirementsIncompatible(DescriptorBindingRequirementsIncompatible),
+
     /// The output of the fragment shader is not compatible with what the render pass subpass
     /// expects.
     FragmentShaderRenderPassIncompatible,
 
+    /// The fragment size of the fragment shading rate state does not have elements that are 1, 2
+    /// or 4.
+    FragmentShadingRateFragmentSizeInvalid { fragment_size: [u32; 2] },
+
+    /// A combiner operation of the fragment shading rate state is not `Keep` or `Replace`, but
+    /// the [`fragment_shading_rate_non_trivial_combiner_ops`](crate::device::Properties::fragment_shading_rate_non_trivial_combiner_ops)
+    /// device property is not `true`.
+    FragmentShadingRateNonTrivialCombinerOpsNotSupported,
+
     /// The pipeline layout is not compatible with what the shaders expect.
     IncompatiblePipelineLayout(PipelineLayoutSupersetError),
 
@@ -60,6 +78,25 @@ pub enum GraphicsPipelineCreationError {
     /// `patch_control_points` was not greater than 0 and less than or equal to the `max_tessellation_patch_size` limit.
     InvalidNumPatchControlPoints,
 
+    /// The descriptor requirements of the shaders in a pipeline in `libraries` are not compatible
+    /// with those of the other libraries, or of the pipeline being created.
+    LibraryDescriptorRequirementsIncompatible {
+        library_index: u32,
+        error: DescriptorBindingRequirementsIncompatible,
+    },
+
+    /// The pipeline layout of a pipeline in `libraries` is not compatible with the pipeline
+    /// layout of the pipeline being created.
+    LibraryPipelineLayoutIncompatible { library_index: u32 },
+
+    /// The render pass or rendering info of a pipeline in `libraries` is not compatible with
+    /// that of the pipeline being created, or of the other libraries.
+    LibraryRenderPassIncompatible { library_index: u32 },
+
+    /// A pipeline in `libraries` contains a part of the pipeline that is already contained in
+    /// another library, or in the pipeline being created.
+    LibraryStateOverlap { library_index: u32 },
+
     /// The maximum number of discard rectangles has been exceeded.
     MaxDiscardRectanglesExceeded {
         /// Maximum allowed value.
@@ -71,6 +108,13 @@ pub enum GraphicsPipelineCreationError {
     /// The `max_multiview_view_count` limit has been exceeded.
     MaxMultiviewViewCountExceeded { view_count: u32, max: u32 },
 
+    /// The number of output vertices declared by a tessellation shader was not greater than 0 and
+    /// less than or equal to the `max_tessellation_patch_size` limit.
+    MaxTessellationPatchSizeExceeded { output_vertices: u32, max: u32 },
+
+    /// The `max_transform_feedback_streams` limit has been exceeded.
+    MaxTransformFeedbackStreamsExceeded { rasterization_stream: u32, max: u32 },
+
     /// The maximum value for the instance rate divisor has been exceeded.
     MaxVertexAttribDivisorExceeded {
         /// Index of the faulty binding.
@@ -154,6 +198,9 @@ pub enum GraphicsPipelineCreationError {
     /// stencil writing is enabled and the stencil attachment is read-only.
     NoStencilAttachment,
 
+    /// A pipeline in `libraries` was not created as a pipeline library.
+    NotALibrary { library_index: u32 },
+
     /// Not enough memory.
     OomError(OomError),
 
@@ -163,6 +210,19 @@ pub enum GraphicsPipelineCreationError {
     /// Error while creating the pipeline layout object.
     PipelineLayoutCreationError(PipelineLayoutCreationError),
 
+    /// The number of elements in `sample_locations` of the sample locations state does not equal
+    /// the number required by the sample count and grid size.
+    SampleLocationsCountMismatch { provided: u32, required: u32 },
+
+    /// The `sample_locations_per_pixel` of the sample locations state does not equal
+    /// `rasterization_samples`.
+    SampleLocationsPerPixelMismatch,
+
+    /// The `sample_locations_per_pixel` of the sample locations state is not included in the
+    /// [`sample_location_sample_counts`](crate::device::Properties::sample_location_sample_counts)
+    /// device property.
+    SampleLocationsPerPixelNotSupported,
+
     /// The output interface of one shader and the input interface of the next shader do not match.
     ShaderStagesMismatch(ShaderInterfaceMismatchError),
 
@@ -172,9 +232,30 @@ pub enum GraphicsPipelineCreationError {
     /// The [`strict_lines`](crate::device::Properties::strict_lines) device property was `false`.
     StrictLinesNotSupported,
 
+    /// The tessellation control and evaluation shaders both declare the number of output
+    /// vertices, but the numbers are different.
+    TessellationShadersOutputVerticesMismatch,
+
+    /// Neither the tessellation control nor the evaluation shader declares the number of output
+    /// vertices.
+    TessellationShadersOutputVerticesMissing,
+
+    /// The tessellation control and evaluation shaders both declare the type of subdivision, but
+    /// the types are different.
+    TessellationShadersSubdivisionMismatch,
+
+    /// Neither the tessellation control nor the evaluation shader declares the type of
+    /// subdivision.
+    TessellationShadersSubdivisionMissing,
+
     /// The primitives topology does not match what the geometry shader expects.
     TopologyNotMatchingGeometryShader,
 
+    /// The rasterization stream is not `0`, but the
+    /// [`transform_feedback_rasterization_stream_select`](crate::device::Properties::transform_feedback_rasterization_stream_select)
+    /// device property was `false`.
+    TransformFeedbackRasterizationStreamSelectNotSupported,
+
     /// The type of the shader input variable at the given location is not compatible with the
     /// format of the corresponding vertex input attribute.
     VertexInputAttributeIncompatibleFormat {
@@ -214,6 +295,8 @@ impl Error for GraphicsPipelineCreationError {
             Self::IncompatiblePipelineLayout(err) => Some(err),
             Self::ShaderStagesMismatch(err) => Some(err),
             Self::IncompatibleVertexDefinition(err) => Some(err),
+            Self::DescriptorRequirementsIncompatible(err) => Some(err),
+            Self::LibraryDescriptorRequirementsIncompatible { error, .. } => Some(error),
             _ => None,
         }
     }
@@ -230,6 +313,15 @@ impl Display for GraphicsPipelineCreationError {
                 "a requirement was not met for: {}; requires one of: {}",
                 required_for, requires_one_of,
             ),
+            Self::BasePipelineMissing => write!(
+                f,
+                "`flags` contains `PipelineCreateFlags::DERIVATIVE`, but no base pipeline was \
+                provided",
+            ),
+            Self::BasePipelineNotAllowDerivatives => write!(
+                f,
+                "the base pipeline was not created with `PipelineCreateFlags::ALLOW_DERIVATIVES`",
+            ),
             Self::ColorAttachmentFormatBlendNotSupported { attachment_index } => write!(
                 f,
                 "color attachment {} has a format that does not support blending",
@@ -248,11 +340,27 @@ impl Display for GraphicsPipelineCreationError {
                 f,
                 "the depth and stencil attachments have different formats",
             ),
+            Self::DescriptorRequirementsIncompatible(_) => write!(
+                f,
+                "the descriptor requirements of the shaders are not compatible with each other",
+            ),
             Self::FragmentShaderRenderPassIncompatible => write!(
                 f,
                 "the output of the fragment shader is not compatible with what the render pass \
                 subpass expects",
             ),
+            Self::FragmentShadingRateFragmentSizeInvalid { fragment_size } => write!(
+                f,
+                "the fragment size of the fragment shading rate state ({:?}) does not have \
+                elements that are 1, 2 or 4",
+                fragment_size,
+            ),
+            Self::FragmentShadingRateNonTrivialCombinerOpsNotSupported => write!(
+                f,
+                "a combiner operation of the fragment shading rate state is not `Keep` or \
+                `Replace`, but the `fragment_shading_rate_non_trivial_combiner_ops` device \
+                property is not `true`",
+            ),
             Self::IncompatiblePipelineLayout(_) => write!(
                 f,
                 "the pipeline layout is not compatible with what the shaders expect",
@@ -276,6 +384,30 @@ impl Display for GraphicsPipelineCreationError {
                 "patch_control_points was not greater than 0 and less than or equal to the \
                 max_tessellation_patch_size limit",
             ),
+            Self::LibraryDescriptorRequirementsIncompatible { library_index, .. } => write!(
+                f,
+                "the descriptor requirements of the shaders in library {} are not compatible with \
+                those of the other libraries or of the pipeline being created",
+                library_index,
+            ),
+            Self::LibraryPipelineLayoutIncompatible { library_index } => write!(
+                f,
+                "the pipeline layout of library {} is not compatible with the pipeline layout of \
+                the pipeline being created",
+                library_index,
+            ),
+            Self::LibraryRenderPassIncompatible { library_index } => write!(
+                f,
+                "the render pass of library {} is not compatible with the render pass of the \
+                pipeline being created or of the other libraries",
+                library_index,
+            ),
+            Self::LibraryStateOverlap { library_index } => write!(
+                f,
+                "library {} contains a part of the pipeline that is already contained in another \
+                library or in the pipeline being created",
+                library_index,
+            ),
             Self::MaxDiscardRectanglesExceeded { .. } => write!(
                 f,
                 "the maximum number of discard rectangles has been exceeded",
@@ -283,6 +415,20 @@ impl Display for GraphicsPipelineCreationError {
             Self::MaxMultiviewViewCountExceeded { .. } => {
                 write!(f, "the `max_multiview_view_count` limit has been exceeded")
             }
+            Self::MaxTessellationPatchSizeExceeded {
+                output_vertices,
+                max,
+            } => write!(
+                f,
+                "the number of output vertices declared by a tessellation shader ({}) was not \
+                greater than 0 and less than or equal to the max_tessellation_patch_size limit \
+                ({})",
+                output_vertices, max,
+            ),
+            Self::MaxTransformFeedbackStreamsExceeded { .. } => write!(
+                f,
+                "the `max_transform_feedback_streams` limit has been exceeded",
+            ),
             Self::MaxVertexAttribDivisorExceeded { .. } => write!(
                 f,
                 "the maximum value for the instance rate divisor has been exceeded",
@@ -331,6 +477,11 @@ impl Display for GraphicsPipelineCreationError {
                 f,
                 "the stencil attachment of the render pass does not match the stencil test",
             ),
+            Self::NotALibrary { library_index } => write!(
+                f,
+                "library {} was not created as a pipeline library",
+                library_index,
+            ),
             Self::OomError(_) => write!(f, "not enough memory available"),
             Self::DescriptorSetLayoutCreationError(_) => {
                 write!(f, "error while creating a descriptor set layout object")
@@ -338,6 +489,22 @@ impl Display for GraphicsPipelineCreationError {
             Self::PipelineLayoutCreationError(_) => {
                 write!(f, "error while creating the pipeline layout object")
             }
+            Self::SampleLocationsCountMismatch { provided, required } => write!(
+                f,
+                "the number of elements in `sample_locations` of the sample locations state ({}) \
+                does not equal the number required by the sample count and grid size ({})",
+                provided, required,
+            ),
+            Self::SampleLocationsPerPixelMismatch => write!(
+                f,
+                "the `sample_locations_per_pixel` of the sample locations state does not equal \
+                `rasterization_samples`",
+            ),
+            Self::SampleLocationsPerPixelNotSupported => write!(
+                f,
+                "the `sample_locations_per_pixel` of the sample locations state is not included in \
+                the `sample_location_sample_counts` device property",
+            ),
             Self::ShaderStagesMismatch(_) => write!(
                 f,
                 "the output interface of one shader and the input interface of the next shader do \
@@ -350,10 +517,35 @@ impl Display for GraphicsPipelineCreationError {
             Self::StrictLinesNotSupported => {
                 write!(f, "the strict_lines device property was false")
             }
+            Self::TessellationShadersOutputVerticesMismatch => write!(
+                f,
+                "the tessellation control and evaluation shaders both declare the number of \
+                output vertices, but the numbers are different",
+            ),
+            Self::TessellationShadersOutputVerticesMissing => write!(
+                f,
+                "neither the tessellation control nor the evaluation shader declares the number \
+                of output vertices",
+            ),
+            Self::TessellationShadersSubdivisionMismatch => write!(
+                f,
+                "the tessellation control and evaluation shaders both declare the type of \
+                subdivision, but the types are different",
+            ),
+            Self::TessellationShadersSubdivisionMissing => write!(
+                f,
+                "neither the tessellation control nor the evaluation shader declares the type of \
+                subdivision",
+            ),
             Self::TopologyNotMatchingGeometryShader => write!(
                 f,
                 "the primitives topology does not match what the geometry shader expects",
             ),
+            Self::TransformFeedbackRasterizationStreamSelectNotSupported => write!(
+                f,
+                "the rasterization stream is not 0, but the \
+                `transform_feedback_rasterization_stream_select` device property was false",
+            ),
             Self::VertexInputAttributeIncompatibleFormat {
                 location,
                 shader_type,
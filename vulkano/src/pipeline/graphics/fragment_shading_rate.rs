@@ -0,0 +1,130 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Controls the number of pixels that are covered by each fragment shader invocation.
+//!
+//! Normally, the fragment shader is invoked once for every pixel (or sample) that is covered by a
+//! primitive. With a fragment shading rate, a single invocation can instead cover a larger area,
+//! such as 2x2 or 4x4 pixels, which reduces the shading cost in areas where less detail is needed.
+//!
+//! There are three sources that contribute to the final shading rate:
+//! - The pipeline shading rate, which is set in the [`FragmentShadingRateState`] of the pipeline
+//!   or dynamically with the `set_fragment_shading_rate` command.
+//! - The primitive shading rate, which is written by the last pre-rasterization shader stage
+//!   using the `PrimitiveShadingRateKHR` built-in.
+//! - The attachment shading rate, which is read from a fragment shading rate attachment of the
+//!   subpass.
+//!
+//! These are combined into the final rate by the two
+//! [`combiner_ops`](FragmentShadingRate::combiner_ops).
+
+use crate::{macros::vulkan_enum, pipeline::StateMode};
+
+/// The state in a graphics pipeline describing the fragment shading rate.
+#[derive(Clone, Debug)]
+pub struct FragmentShadingRateState {
+    /// The pipeline fragment size and the combiner operations.
+    ///
+    /// If set to anything other than `Fixed` with the default value of [`FragmentShadingRate`],
+    /// the [`khr_fragment_shading_rate`](crate::device::DeviceExtensions::khr_fragment_shading_rate)
+    /// extension must be enabled on the device.
+    pub fragment_shading_rate: StateMode<FragmentShadingRate>,
+}
+
+impl FragmentShadingRateState {
+    /// Creates a `FragmentShadingRateState` with a fixed fragment size of 1x1, that keeps the
+    /// pipeline fragment size.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            fragment_shading_rate: StateMode::Fixed(FragmentShadingRate::default()),
+        }
+    }
+
+    /// Creates a `FragmentShadingRateState` with a dynamic fragment size and combiner operations.
+    #[inline]
+    pub fn dynamic() -> Self {
+        Self {
+            fragment_shading_rate: StateMode::Dynamic,
+        }
+    }
+}
+
+impl Default for FragmentShadingRateState {
+    /// Returns [`FragmentShadingRateState::new`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The pipeline fragment size, and how it is combined with the other sources of the fragment
+/// shading rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FragmentShadingRate {
+    /// The width and height of the area covered by each fragment.
+    ///
+    /// Both values must be 1, 2 or 4. If either value is not 1, the
+    /// [`pipeline_fragment_shading_rate`](crate::device::Features::pipeline_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `[1, 1]`.
+    pub fragment_size: [u32; 2],
+
+    /// The operations that are used to combine the shading rates. The first operation combines
+    /// the pipeline shading rate with the primitive shading rate, the second operation combines
+    /// the result of that with the attachment shading rate.
+    ///
+    /// If the first operation is not [`FragmentShadingRateCombinerOp::Keep`], the
+    /// [`primitive_fragment_shading_rate`](crate::device::Features::primitive_fragment_shading_rate)
+    /// feature must be enabled on the device. If the second operation is not
+    /// [`FragmentShadingRateCombinerOp::Keep`], the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `[FragmentShadingRateCombinerOp::Keep; 2]`.
+    pub combiner_ops: [FragmentShadingRateCombinerOp; 2],
+}
+
+impl Default for FragmentShadingRate {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            fragment_size: [1, 1],
+            combiner_ops: [FragmentShadingRateCombinerOp::Keep; 2],
+        }
+    }
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// How two fragment shading rates are combined into one.
+    FragmentShadingRateCombinerOp = FragmentShadingRateCombinerOpKHR(i32);
+
+    /// The first shading rate is kept, and the second is ignored.
+    Keep = KEEP,
+
+    /// The second shading rate replaces the first.
+    Replace = REPLACE,
+
+    /// The smallest of the two shading rates is used, for each dimension separately.
+    Min = MIN,
+
+    /// The largest of the two shading rates is used, for each dimension separately.
+    Max = MAX,
+
+    /// The two shading rates are multiplied together, for each dimension separately.
+    ///
+    /// If the [`fragment_shading_rate_strict_multiply_combiner`] property is not `true`, then
+    /// combining two values of 1 in the same dimension produces 2 instead.
+    ///
+    /// [`fragment_shading_rate_strict_multiply_combiner`]: crate::device::Properties::fragment_shading_rate_strict_multiply_combiner
+    Mul = MUL,
+}
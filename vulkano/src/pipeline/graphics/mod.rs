@@ -55,20 +55,52 @@
 //! buffer, binding the necessary vertex buffers, binding any descriptor sets, setting push
 //! constants, and setting any dynamic state that the pipeline may need. Then you issue a `draw`
 //! command.
+//!
+//! # Pipeline libraries
+//!
+//! If the [`graphics_pipeline_library`] feature is enabled on the device, a graphics pipeline can
+//! be split into up to four parts, that are compiled separately as *pipeline libraries*:
+//!
+//! - The vertex input interface: the vertex input and input assembly state.
+//! - The pre-rasterization shaders: the vertex, tessellation and geometry shaders, and the
+//!   tessellation, viewport, discard rectangle and rasterization state.
+//! - The fragment shader: the fragment shader, and the depth/stencil state.
+//! - The fragment output interface: the multisample and color blend state.
+//!
+//! A library is created by calling [`GraphicsPipelineBuilder::library`] with the parts that it
+//! should contain. Libraries can then be linked into a complete pipeline with
+//! [`GraphicsPipelineBuilder::link_libraries`]. Linking is much faster than creating a complete
+//! pipeline from scratch, so this can be used to avoid stalls when a new combination of shaders
+//! and state is needed while rendering.
+//!
+//! [`graphics_pipeline_library`]: crate::device::Features::graphics_pipeline_library
 
 pub use self::{builder::GraphicsPipelineBuilder, creation_error::GraphicsPipelineCreationError};
 use self::{
-    color_blend::ColorBlendState, depth_stencil::DepthStencilState,
-    discard_rectangle::DiscardRectangleState, input_assembly::InputAssemblyState,
-    multisample::MultisampleState, rasterization::RasterizationState,
-    render_pass::PipelineRenderPassType, tessellation::TessellationState,
-    vertex_input::VertexInputState, viewport::ViewportState,
+    color_blend::ColorBlendState,
+    depth_stencil::DepthStencilState,
+    discard_rectangle::DiscardRectangleState,
+    fragment_shading_rate::FragmentShadingRateState,
+    input_assembly::InputAssemblyState,
+    multisample::{MultisampleState, SampleLocationsState},
+    rasterization::RasterizationState,
+    render_pass::PipelineRenderPassType,
+    tessellation::TessellationState,
+    vertex_input::VertexInputState,
+    viewport::ViewportState,
+};
+use super::{
+    executable::{
+        self, PipelineExecutableError, PipelineExecutableInternalRepresentation,
+        PipelineExecutableProperties, PipelineExecutableStatistic,
+    },
+    DynamicState, Pipeline, PipelineBindPoint, PipelineCreateFlags, PipelineLayout,
 };
-use super::{DynamicState, Pipeline, PipelineBindPoint, PipelineLayout};
 use crate::{
     device::{Device, DeviceOwned},
-    shader::{DescriptorBindingRequirements, ShaderStage},
-    VulkanObject,
+    macros::vulkan_bitflags,
+    shader::{DescriptorBindingRequirements, ShaderInterface, ShaderStage},
+    VulkanError, VulkanObject,
 };
 use ahash::HashMap;
 use std::{
@@ -83,6 +115,7 @@ pub mod color_blend;
 mod creation_error;
 pub mod depth_stencil;
 pub mod discard_rectangle;
+pub mod fragment_shading_rate;
 pub mod input_assembly;
 pub mod multisample;
 pub mod rasterization;
@@ -103,9 +136,12 @@ pub struct GraphicsPipeline {
     id: NonZeroU64,
     layout: Arc<PipelineLayout>,
     render_pass: PipelineRenderPassType,
+    flags: PipelineCreateFlags,
+    library_flags: GraphicsPipelineLibraryFlags,
 
     // TODO: replace () with an object that describes the shaders in some way.
     shaders: HashMap<ShaderStage, ()>,
+    vertex_shader_input_interface: Option<ShaderInterface>,
     descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
     num_used_descriptor_sets: u32,
 
@@ -114,8 +150,10 @@ pub struct GraphicsPipeline {
     tessellation_state: Option<TessellationState>,
     viewport_state: Option<ViewportState>,
     discard_rectangle_state: Option<DiscardRectangleState>,
+    fragment_shading_rate_state: Option<FragmentShadingRateState>,
     rasterization_state: RasterizationState,
     multisample_state: Option<MultisampleState>,
+    sample_locations_state: Option<SampleLocationsState>,
     depth_stencil_state: Option<DepthStencilState>,
     color_blend_state: Option<ColorBlendState>,
     dynamic_state: HashMap<DynamicState, bool>,
@@ -153,6 +191,21 @@ impl GraphicsPipeline {
         &self.render_pass
     }
 
+    /// Returns the flags that the pipeline was created with.
+    #[inline]
+    pub fn flags(&self) -> PipelineCreateFlags {
+        self.flags
+    }
+
+    /// Returns the parts of a graphics pipeline that this pipeline contains, if it is a
+    /// pipeline library.
+    ///
+    /// If the pipeline is not a library, this returns an empty value.
+    #[inline]
+    pub fn library_flags(&self) -> GraphicsPipelineLibraryFlags {
+        self.library_flags
+    }
+
     /// Returns information about a particular shader.
     ///
     /// `None` is returned if the pipeline does not contain this shader.
@@ -195,6 +248,12 @@ impl GraphicsPipeline {
         self.discard_rectangle_state.as_ref()
     }
 
+    /// Returns the fragment shading rate state used to create this pipeline.
+    #[inline]
+    pub fn fragment_shading_rate_state(&self) -> Option<&FragmentShadingRateState> {
+        self.fragment_shading_rate_state.as_ref()
+    }
+
     /// Returns the rasterization state used to create this pipeline.
     #[inline]
     pub fn rasterization_state(&self) -> &RasterizationState {
@@ -207,6 +266,12 @@ impl GraphicsPipeline {
         self.multisample_state.as_ref()
     }
 
+    /// Returns the sample locations state used to create this pipeline.
+    #[inline]
+    pub fn sample_locations_state(&self) -> Option<&SampleLocationsState> {
+        self.sample_locations_state.as_ref()
+    }
+
     /// Returns the depth/stencil state used to create this pipeline.
     #[inline]
     pub fn depth_stencil_state(&self) -> Option<&DepthStencilState> {
@@ -233,6 +298,80 @@ impl GraphicsPipeline {
     pub fn dynamic_states(&self) -> impl ExactSizeIterator<Item = (DynamicState, bool)> + '_ {
         self.dynamic_state.iter().map(|(k, v)| (*k, *v))
     }
+
+    /// Returns the properties of the executables that the pipeline was compiled into.
+    ///
+    /// The [`pipeline_executable_info`] feature must be enabled on the device.
+    ///
+    /// [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+    #[inline]
+    pub fn executable_properties(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, PipelineExecutableError> {
+        executable::validate_executable_properties(&self.device)?;
+
+        unsafe { Ok(self.executable_properties_unchecked()?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_properties_unchecked(
+        &self,
+    ) -> Result<Vec<PipelineExecutableProperties>, VulkanError> {
+        executable::executable_properties_unchecked(&self.device, self.handle)
+    }
+
+    /// Returns the statistics of the executable with index `executable_index`.
+    ///
+    /// The [`pipeline_executable_info`] feature must be enabled on the device.
+    ///
+    /// [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, PipelineExecutableError> {
+        executable::validate_executable_index(&self.device, self.handle, executable_index)?;
+
+        unsafe { Ok(self.executable_statistics_unchecked(executable_index)?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_statistics_unchecked(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableStatistic>, VulkanError> {
+        executable::executable_statistics_unchecked(&self.device, self.handle, executable_index)
+    }
+
+    /// Returns the internal representations of the executable with index `executable_index`.
+    ///
+    /// The [`pipeline_executable_info`] feature must be enabled on the device.
+    ///
+    /// [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+    #[inline]
+    pub fn executable_internal_representations(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, PipelineExecutableError> {
+        executable::validate_executable_index(&self.device, self.handle, executable_index)?;
+
+        unsafe { Ok(self.executable_internal_representations_unchecked(executable_index)?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_internal_representations_unchecked(
+        &self,
+        executable_index: u32,
+    ) -> Result<Vec<PipelineExecutableInternalRepresentation>, VulkanError> {
+        executable::executable_internal_representations_unchecked(
+            &self.device,
+            self.handle,
+            executable_index,
+        )
+    }
 }
 
 impl Pipeline for GraphicsPipeline {
@@ -292,3 +431,31 @@ impl Drop for GraphicsPipeline {
 }
 
 crate::impl_id_counter!(GraphicsPipeline);
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// The parts of a graphics pipeline that are contained in a pipeline library.
+    GraphicsPipelineLibraryFlags = GraphicsPipelineLibraryFlagsEXT(u32);
+
+    /// The library contains the vertex input and input assembly state.
+    VERTEX_INPUT_INTERFACE = VERTEX_INPUT_INTERFACE {
+        device_extensions: [ext_graphics_pipeline_library],
+    },
+
+    /// The library contains the vertex, tessellation and geometry shaders, and the tessellation,
+    /// viewport, discard rectangle and rasterization state.
+    PRE_RASTERIZATION_SHADERS = PRE_RASTERIZATION_SHADERS {
+        device_extensions: [ext_graphics_pipeline_library],
+    },
+
+    /// The library contains the fragment shader and the depth/stencil state.
+    FRAGMENT_SHADER = FRAGMENT_SHADER {
+        device_extensions: [ext_graphics_pipeline_library],
+    },
+
+    /// The library contains the multisample and color blend state.
+    FRAGMENT_OUTPUT_INTERFACE = FRAGMENT_OUTPUT_INTERFACE {
+        device_extensions: [ext_graphics_pipeline_library],
+    },
+}
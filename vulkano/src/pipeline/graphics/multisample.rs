@@ -10,7 +10,7 @@
 //! Generates multiple fragments per framebuffer pixel when rasterizing. This can be used for
 //! anti-aliasing.
 
-use crate::image::SampleCount;
+use crate::{image::SampleCount, pipeline::StateMode};
 
 // TODO: handle some weird behaviors with non-floating-point targets
 
@@ -47,14 +47,22 @@ pub struct MultisampleState {
     /// then about half of the samples will be discarded. If you render to a multisample image, this
     /// means that the color will end up being mixed with whatever color was underneath, which gives
     /// the same effect as alpha blending.
-    pub alpha_to_coverage_enable: bool,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_alpha_to_coverage_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_coverage_enable)
+    /// feature must be enabled on the device.
+    pub alpha_to_coverage_enable: StateMode<bool>,
 
     /// Controls whether the alpha value of all the samples will be forced to 1.0 (or the
     /// maximum possible value) after the effects of `alpha_to_coverage` have been applied.
     ///
     /// If set to `true`, the [`alpha_to_one`](crate::device::Features::alpha_to_one)
     /// feature must be enabled on the device.
-    pub alpha_to_one_enable: bool,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_alpha_to_one_enable`](crate::device::Features::extended_dynamic_state3_alpha_to_one_enable)
+    /// feature must be enabled on the device.
+    pub alpha_to_one_enable: StateMode<bool>,
 }
 
 impl MultisampleState {
@@ -65,8 +73,8 @@ impl MultisampleState {
             rasterization_samples: SampleCount::Sample1,
             sample_shading: None,
             sample_mask: [0xFFFFFFFF; 2],
-            alpha_to_coverage_enable: false,
-            alpha_to_one_enable: false,
+            alpha_to_coverage_enable: StateMode::Fixed(false),
+            alpha_to_one_enable: StateMode::Fixed(false),
         }
     }
 }
@@ -78,3 +86,76 @@ impl Default for MultisampleState {
         Self::new()
     }
 }
+
+/// State of the custom sample locations.
+#[derive(Clone, Debug)]
+pub struct SampleLocationsState {
+    /// Custom locations of the samples within each pixel, to use instead of the standard sample
+    /// locations.
+    ///
+    /// If set to `Some`, the
+    /// [`ext_sample_locations`](crate::device::DeviceExtensions::ext_sample_locations)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub sample_locations: Option<StateMode<SampleLocationsInfo>>,
+}
+
+impl SampleLocationsState {
+    /// Creates a `SampleLocationsState` that uses the standard sample locations.
+    #[inline]
+    pub fn new() -> SampleLocationsState {
+        SampleLocationsState {
+            sample_locations: None,
+        }
+    }
+}
+
+impl Default for SampleLocationsState {
+    /// Returns [`SampleLocationsState::new()`].
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A set of custom sample locations, that is repeated over a grid of pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SampleLocationsInfo {
+    /// The number of samples per pixel that the sample locations are provided for.
+    ///
+    /// The value must be included in the
+    /// [`sample_location_sample_counts`](crate::device::Properties::sample_location_sample_counts)
+    /// device property.
+    ///
+    /// The default value is [`SampleCount::Sample1`].
+    pub sample_locations_per_pixel: SampleCount,
+
+    /// The width and height, in pixels, of the grid over which the sample locations are repeated.
+    ///
+    /// The default value is `[1, 1]`.
+    pub sample_location_grid_size: [u32; 2],
+
+    /// The location of each sample, relative to the top left corner of its pixel. The locations
+    /// are ordered first by sample, then by the pixel's horizontal position in the grid, and then
+    /// by its vertical position. Values outside the
+    /// [`sample_location_coordinate_range`](crate::device::Properties::sample_location_coordinate_range)
+    /// device property are clamped to that range.
+    ///
+    /// The number of elements must equal `sample_locations_per_pixel` multiplied by the width and
+    /// height of `sample_location_grid_size`.
+    ///
+    /// The default value is a single location in the center of the pixel.
+    pub sample_locations: Vec<[f32; 2]>,
+}
+
+impl Default for SampleLocationsInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            sample_locations_per_pixel: SampleCount::Sample1,
+            sample_location_grid_size: [1, 1],
+            sample_locations: vec![[0.5, 0.5]],
+        }
+    }
+}
@@ -19,7 +19,11 @@ pub struct RasterizationState {
     ///
     /// If enabled, the [`depth_clamp`](crate::device::Features::depth_clamp) feature must be
     /// enabled on the device.
-    pub depth_clamp_enable: bool,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_depth_clamp_enable`](crate::device::Features::extended_dynamic_state3_depth_clamp_enable)
+    /// feature must be enabled on the device.
+    pub depth_clamp_enable: StateMode<bool>,
 
     /// If true, all the fragments will be discarded, and the fragment shader will not be run. This
     /// is usually used when your vertex shader has some side effects and you don't need to run the
@@ -36,7 +40,11 @@ pub struct RasterizationState {
     /// If set to a value other than `Fill`, the
     /// [`fill_mode_non_solid`](crate::device::Features::fill_mode_non_solid) feature must be
     /// enabled on the device.
-    pub polygon_mode: PolygonMode,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_polygon_mode`](crate::device::Features::extended_dynamic_state3_polygon_mode)
+    /// feature must be enabled on the device.
+    pub polygon_mode: StateMode<PolygonMode>,
 
     /// Specifies whether front faces or back faces should be discarded, or none, or both.
     ///
@@ -78,6 +86,19 @@ pub struct RasterizationState {
     /// [`ext_line_rasterization`](crate::device::DeviceExtensions::ext_line_rasterization)
     /// extension and an additional feature must be enabled on the device.
     pub line_stipple: Option<StateMode<LineStipple>>,
+
+    /// The vertex stream that is used for rasterization, when transform feedback is used to
+    /// output multiple vertex streams.
+    ///
+    /// If this is not set to `0`, the
+    /// [`ext_transform_feedback`](crate::device::DeviceExtensions::ext_transform_feedback)
+    /// extension and the [`geometry_streams`](crate::device::Features::geometry_streams) feature
+    /// must be enabled on the device, and the
+    /// [`transform_feedback_rasterization_stream_select`](crate::device::Properties::transform_feedback_rasterization_stream_select)
+    /// device property must be `true`.
+    ///
+    /// The default value is `0`.
+    pub rasterization_stream: u32,
 }
 
 impl RasterizationState {
@@ -87,22 +108,30 @@ impl RasterizationState {
     #[inline]
     pub fn new() -> Self {
         Self {
-            depth_clamp_enable: false,
+            depth_clamp_enable: StateMode::Fixed(false),
             rasterizer_discard_enable: StateMode::Fixed(false),
-            polygon_mode: Default::default(),
+            polygon_mode: StateMode::Fixed(Default::default()),
             cull_mode: StateMode::Fixed(Default::default()),
             front_face: StateMode::Fixed(Default::default()),
             depth_bias: None,
             line_width: StateMode::Fixed(1.0),
             line_rasterization_mode: Default::default(),
             line_stipple: None,
+            rasterization_stream: 0,
         }
     }
 
     /// Sets the polygon mode.
     #[inline]
     pub fn polygon_mode(mut self, polygon_mode: PolygonMode) -> Self {
-        self.polygon_mode = polygon_mode;
+        self.polygon_mode = StateMode::Fixed(polygon_mode);
+        self
+    }
+
+    /// Sets the polygon mode to dynamic.
+    #[inline]
+    pub fn polygon_mode_dynamic(mut self) -> Self {
+        self.polygon_mode = StateMode::Dynamic;
         self
     }
 
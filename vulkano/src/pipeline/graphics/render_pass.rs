@@ -7,7 +7,7 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use crate::{format::Format, render_pass::Subpass};
+use crate::{command_buffer::RenderingInfo, format::Format, render_pass::Subpass};
 
 /// Selects the type of render pass that a graphics pipeline is created for.
 #[derive(Clone, Debug)]
@@ -79,3 +79,33 @@ impl Default for PipelineRenderingCreateInfo {
         }
     }
 }
+
+impl From<&RenderingInfo> for PipelineRenderingCreateInfo {
+    /// Returns a `PipelineRenderingCreateInfo` with the view mask and attachment formats that
+    /// are used in `rendering_info`, so that a pipeline can be created that is compatible with
+    /// it.
+    #[inline]
+    fn from(rendering_info: &RenderingInfo) -> Self {
+        Self {
+            view_mask: rendering_info.view_mask,
+            color_attachment_formats: rendering_info
+                .color_attachments
+                .iter()
+                .map(|attachment_info| {
+                    attachment_info
+                        .as_ref()
+                        .and_then(|attachment_info| attachment_info.image_view.format())
+                })
+                .collect(),
+            depth_attachment_format: rendering_info
+                .depth_attachment
+                .as_ref()
+                .and_then(|attachment_info| attachment_info.image_view.format()),
+            stencil_attachment_format: rendering_info
+                .stencil_attachment
+                .as_ref()
+                .and_then(|attachment_info| attachment_info.image_view.format()),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
@@ -62,6 +62,68 @@
 //!
 //! A pipeline layout is a Vulkan object type, represented in Vulkano with the `PipelineLayout`
 //! type. Each pipeline that you create holds a pipeline layout object.
+//!
+//! When creating a pipeline, the pipeline layout can be derived automatically from the
+//! reflection data of its shaders. It's also possible to describe the layout entirely at runtime,
+//! without any shaders, by creating the descriptor set layouts and the pipeline layout from their
+//! create info structs. The same layouts can then be shared by several pipelines, as long as
+//! their shaders are compatible with them.
+//!
+//! ```
+//! # use std::sync::Arc;
+//! # use vulkano::device::Device;
+//! use vulkano::{
+//!     descriptor_set::layout::{
+//!         DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+//!         DescriptorType,
+//!     },
+//!     pipeline::layout::{PipelineLayout, PipelineLayoutCreateInfo, PushConstantRange},
+//!     shader::ShaderStages,
+//! };
+//!
+//! # let device: Arc<Device> = return;
+//! let set_layout = DescriptorSetLayout::new(
+//!     device.clone(),
+//!     DescriptorSetLayoutCreateInfo {
+//!         bindings: [
+//!             (
+//!                 0,
+//!                 DescriptorSetLayoutBinding {
+//!                     stages: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+//!                     ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::UniformBuffer)
+//!                 },
+//!             ),
+//!             (
+//!                 1,
+//!                 DescriptorSetLayoutBinding {
+//!                     descriptor_count: 16,
+//!                     stages: ShaderStages::FRAGMENT,
+//!                     ..DescriptorSetLayoutBinding::descriptor_type(
+//!                         DescriptorType::CombinedImageSampler,
+//!                     )
+//!                 },
+//!             ),
+//!         ]
+//!         .into(),
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//!
+//! let pipeline_layout = PipelineLayout::new(
+//!     device.clone(),
+//!     PipelineLayoutCreateInfo {
+//!         set_layouts: vec![set_layout],
+//!         push_constant_ranges: vec![PushConstantRange {
+//!             stages: ShaderStages::VERTEX,
+//!             offset: 0,
+//!             size: 64,
+//!         }],
+//!         ..Default::default()
+//!     },
+//! )
+//! .unwrap();
+//! ```
 
 use crate::{
     descriptor_set::layout::{DescriptorRequirementsNotMet, DescriptorSetLayout, DescriptorType},
@@ -212,8 +274,21 @@ impl PipelineLayout {
             let mut num_sampled_images = Counter::default();
             let mut num_storage_images = Counter::default();
             let mut num_input_attachments = Counter::default();
+            let mut num_acceleration_structures = Counter::default();
+            let mut num_inline_uniform_blocks = Counter::default();
             let mut push_descriptor_set = None;
 
+            // VUID-VkPipelineLayoutCreateInfo-pSetLayouts-08008
+            if set_layouts
+                .iter()
+                .any(|set_layout| set_layout.descriptor_buffer())
+                && !set_layouts
+                    .iter()
+                    .all(|set_layout| set_layout.descriptor_buffer())
+            {
+                return Err(PipelineLayoutCreationError::SetLayoutsDescriptorBufferMixed);
+            }
+
             for (set_num, set_layout) in set_layouts.iter().enumerate() {
                 let set_num = set_num as u32;
 
@@ -227,7 +302,12 @@ impl PipelineLayout {
                 }
 
                 for layout_binding in set_layout.bindings().values() {
-                    num_resources.increment(layout_binding.descriptor_count, layout_binding.stages);
+                    // For inline uniform blocks, `descriptor_count` is a size in bytes, and they
+                    // don't count as resources.
+                    if layout_binding.descriptor_type != DescriptorType::InlineUniformBlock {
+                        num_resources
+                            .increment(layout_binding.descriptor_count, layout_binding.stages);
+                    }
 
                     match layout_binding.descriptor_type {
                         DescriptorType::Sampler => {
@@ -270,6 +350,13 @@ impl PipelineLayout {
                             num_input_attachments
                                 .increment(layout_binding.descriptor_count, layout_binding.stages);
                         }
+                        DescriptorType::AccelerationStructure => {
+                            num_acceleration_structures
+                                .increment(layout_binding.descriptor_count, layout_binding.stages);
+                        }
+                        DescriptorType::InlineUniformBlock => {
+                            num_inline_uniform_blocks.increment(1, layout_binding.stages);
+                        }
                     }
                 }
             }
@@ -351,6 +438,38 @@ impl PipelineLayout {
                 );
             }
 
+            // VUID-VkPipelineLayoutCreateInfo-descriptorType-03571
+            let max_per_stage_descriptor_acceleration_structures = properties
+                .max_per_stage_descriptor_acceleration_structures
+                .unwrap_or(0);
+
+            if num_acceleration_structures.max_per_stage()
+                > max_per_stage_descriptor_acceleration_structures
+            {
+                return Err(
+                    PipelineLayoutCreationError::MaxPerStageDescriptorAccelerationStructuresExceeded {
+                        provided: num_acceleration_structures.max_per_stage(),
+                        max_supported: max_per_stage_descriptor_acceleration_structures,
+                    },
+                );
+            }
+
+            // VUID-VkPipelineLayoutCreateInfo-descriptorType-02214
+            let max_per_stage_descriptor_inline_uniform_blocks = properties
+                .max_per_stage_descriptor_inline_uniform_blocks
+                .unwrap_or(0);
+
+            if num_inline_uniform_blocks.max_per_stage()
+                > max_per_stage_descriptor_inline_uniform_blocks
+            {
+                return Err(
+                    PipelineLayoutCreationError::MaxPerStageDescriptorInlineUniformBlocksExceeded {
+                        provided: num_inline_uniform_blocks.max_per_stage(),
+                        max_supported: max_per_stage_descriptor_inline_uniform_blocks,
+                    },
+                );
+            }
+
             // VUID-VkPipelineLayoutCreateInfo-descriptorType-03028
             if num_samplers.total > properties.max_descriptor_set_samplers {
                 return Err(
@@ -430,6 +549,34 @@ impl PipelineLayout {
                     },
                 );
             }
+
+            // VUID-VkPipelineLayoutCreateInfo-descriptorType-03573
+            let max_descriptor_set_acceleration_structures = properties
+                .max_descriptor_set_acceleration_structures
+                .unwrap_or(0);
+
+            if num_acceleration_structures.total > max_descriptor_set_acceleration_structures {
+                return Err(
+                    PipelineLayoutCreationError::MaxDescriptorSetAccelerationStructuresExceeded {
+                        provided: num_acceleration_structures.total,
+                        max_supported: max_descriptor_set_acceleration_structures,
+                    },
+                );
+            }
+
+            // VUID-VkPipelineLayoutCreateInfo-descriptorType-02216
+            let max_descriptor_set_inline_uniform_blocks = properties
+                .max_descriptor_set_inline_uniform_blocks
+                .unwrap_or(0);
+
+            if num_inline_uniform_blocks.total > max_descriptor_set_inline_uniform_blocks {
+                return Err(
+                    PipelineLayoutCreationError::MaxDescriptorSetInlineUniformBlocksExceeded {
+                        provided: num_inline_uniform_blocks.total,
+                        max_supported: max_descriptor_set_inline_uniform_blocks,
+                    },
+                );
+            }
         }
 
         /* Check push constant ranges */
@@ -755,6 +902,17 @@ pub enum PipelineLayoutCreationError {
     /// limit.
     MaxDescriptorSetInputAttachmentsExceeded { provided: u32, max_supported: u32 },
 
+    /// The `set_layouts` contain more [`DescriptorType::AccelerationStructure`] descriptors than
+    /// the
+    /// [`max_descriptor_set_acceleration_structures`](crate::device::Properties::max_descriptor_set_acceleration_structures)
+    /// limit.
+    MaxDescriptorSetAccelerationStructuresExceeded { provided: u32, max_supported: u32 },
+
+    /// The `set_layouts` contain more [`DescriptorType::InlineUniformBlock`] bindings than the
+    /// [`max_descriptor_set_inline_uniform_blocks`](crate::device::Properties::max_descriptor_set_inline_uniform_blocks)
+    /// limit.
+    MaxDescriptorSetInlineUniformBlocksExceeded { provided: u32, max_supported: u32 },
+
     /// The `set_layouts` contain more bound resources in a single stage than the
     /// [`max_per_stage_resources`](crate::device::Properties::max_per_stage_resources)
     /// limit.
@@ -797,6 +955,18 @@ pub enum PipelineLayoutCreationError {
     /// limit.
     MaxPerStageDescriptorInputAttachmentsExceeded { provided: u32, max_supported: u32 },
 
+    /// The `set_layouts` contain more [`DescriptorType::AccelerationStructure`] descriptors in a
+    /// single stage than the
+    /// [`max_per_stage_descriptor_acceleration_structures`](crate::device::Properties::max_per_stage_descriptor_acceleration_structures)
+    /// limit.
+    MaxPerStageDescriptorAccelerationStructuresExceeded { provided: u32, max_supported: u32 },
+
+    /// The `set_layouts` contain more [`DescriptorType::InlineUniformBlock`] bindings in a
+    /// single stage than the
+    /// [`max_per_stage_descriptor_inline_uniform_blocks`](crate::device::Properties::max_per_stage_descriptor_inline_uniform_blocks)
+    /// limit.
+    MaxPerStageDescriptorInlineUniformBlocksExceeded { provided: u32, max_supported: u32 },
+
     /// An element in `push_constant_ranges` has an `offset + size` greater than the
     /// [`max_push_constants_size`](crate::device::Properties::max_push_constants_size) limit.
     MaxPushConstantsSizeExceeded { provided: u32, max_supported: u32 },
@@ -804,6 +974,9 @@ pub enum PipelineLayoutCreationError {
     /// A shader stage appears in multiple elements of `push_constant_ranges`.
     PushConstantRangesStageMultiple,
 
+    /// Some elements of `set_layouts` have `descriptor_buffer` enabled, but not all of them.
+    SetLayoutsDescriptorBufferMixed,
+
     /// Multiple elements of `set_layouts` have `push_descriptor` enabled.
     SetLayoutsPushDescriptorMultiple,
 }
@@ -913,6 +1086,24 @@ impl Display for PipelineLayoutCreationError {
                 than the `max_descriptor_set_input_attachments` limit ({})",
                 provided, max_supported,
             ),
+            Self::MaxDescriptorSetAccelerationStructuresExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more `DescriptorType::AccelerationStructure` \
+                descriptors ({}) than the `max_descriptor_set_acceleration_structures` limit ({})",
+                provided, max_supported,
+            ),
+            Self::MaxDescriptorSetInlineUniformBlocksExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more `DescriptorType::InlineUniformBlock` bindings ({}) \
+                than the `max_descriptor_set_inline_uniform_blocks` limit ({})",
+                provided, max_supported,
+            ),
             Self::MaxPerStageResourcesExceeded {
                 provided,
                 max_supported,
@@ -983,6 +1174,26 @@ impl Display for PipelineLayoutCreationError {
                 ({})",
                 provided, max_supported,
             ),
+            Self::MaxPerStageDescriptorAccelerationStructuresExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more `DescriptorType::AccelerationStructure` \
+                descriptors ({}) in a single stage than the \
+                `max_per_stage_descriptor_acceleration_structures` limit ({})",
+                provided, max_supported,
+            ),
+            Self::MaxPerStageDescriptorInlineUniformBlocksExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the `set_layouts` contain more `DescriptorType::InlineUniformBlock` bindings ({}) \
+                in a single stage than the `max_per_stage_descriptor_inline_uniform_blocks` limit \
+                ({})",
+                provided, max_supported,
+            ),
             Self::MaxPushConstantsSizeExceeded {
                 provided,
                 max_supported,
@@ -996,6 +1207,11 @@ impl Display for PipelineLayoutCreationError {
                 f,
                 "a shader stage appears in multiple elements of `push_constant_ranges`",
             ),
+            Self::SetLayoutsDescriptorBufferMixed => write!(
+                f,
+                "some elements of `set_layouts` have `descriptor_buffer` enabled, but not all of \
+                them",
+            ),
             Self::SetLayoutsPushDescriptorMultiple => write!(
                 f,
                 "multiple elements of `set_layouts` have `push_descriptor` enabled",
@@ -17,15 +17,24 @@
 //! the CPU). Consequently it is a CPU-intensive operation that should be performed at
 //! initialization or during a loading screen.
 
-pub use self::{compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout};
-use crate::{device::DeviceOwned, macros::vulkan_enum, shader::DescriptorBindingRequirements};
+pub use self::{
+    compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout,
+    ray_tracing::RayTracingPipeline,
+};
+use crate::{
+    device::DeviceOwned,
+    macros::{vulkan_bitflags, vulkan_enum},
+    shader::DescriptorBindingRequirements,
+};
 use ahash::HashMap;
 use std::sync::Arc;
 
 pub mod cache;
 pub mod compute;
+pub mod executable;
 pub mod graphics;
 pub mod layout;
+pub mod ray_tracing;
 
 /// A trait for operations shared between pipeline types.
 pub trait Pipeline: DeviceOwned {
@@ -62,11 +71,10 @@ vulkan_enum! {
     // TODO: document
     Graphics = GRAPHICS,
 
-    /* TODO: enable
-    // TODO: document
+    /// The bind point of [`RayTracingPipeline`]s.
     RayTracing = RAY_TRACING_KHR {
         device_extensions: [khr_ray_tracing_pipeline, nv_ray_tracing],
-    },*/
+    },
 
     /* TODO: enable
     // TODO: document
@@ -75,6 +83,45 @@ vulkan_enum! {
     },*/
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control how a pipeline is created.
+    PipelineCreateFlags = PipelineCreateFlags(u32);
+
+    /// The pipeline will not be optimized. This may make creating the pipeline faster.
+    DISABLE_OPTIMIZATION = DISABLE_OPTIMIZATION,
+
+    /// Other pipelines can be created as derivatives of this pipeline.
+    ALLOW_DERIVATIVES = ALLOW_DERIVATIVES,
+
+    /// The pipeline is created as a derivative of a base pipeline, which must have been created
+    /// with the `ALLOW_DERIVATIVES` flag. An implementation may be able to create the pipeline
+    /// faster, if it is similar to the base pipeline.
+    DERIVATIVE = DERIVATIVE,
+
+    /* TODO: enable
+    // TODO: document
+    VIEW_INDEX_FROM_DEVICE_INDEX = VIEW_INDEX_FROM_DEVICE_INDEX {
+        api_version: V1_1,
+        device_extensions: [khr_device_group],
+    },*/
+
+    /* TODO: enable
+    // TODO: document
+    FAIL_ON_PIPELINE_COMPILE_REQUIRED = FAIL_ON_PIPELINE_COMPILE_REQUIRED {
+        api_version: V1_3,
+        device_extensions: [ext_pipeline_creation_cache_control],
+    },*/
+
+    /* TODO: enable
+    // TODO: document
+    EARLY_RETURN_ON_FAILURE = EARLY_RETURN_ON_FAILURE {
+        api_version: V1_3,
+        device_extensions: [ext_pipeline_creation_cache_control],
+    },*/
+}
+
 vulkan_enum! {
     #[non_exhaustive]
 
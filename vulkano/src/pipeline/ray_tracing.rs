@@ -0,0 +1,1307 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A pipeline that traces rays through a scene of acceleration structures.
+//!
+//! A ray tracing pipeline consists of a number of shader stages, which are combined into
+//! *shader groups*:
+//! - A *general* group contains a single ray generation, miss or callable shader.
+//! - A *triangles hit* group contains an optional closest hit shader and an optional any hit
+//!   shader, and is invoked when a ray hits triangle geometry.
+//! - A *procedural hit* group additionally contains an intersection shader, and is invoked when a
+//!   ray hits AABB geometry.
+//!
+//! To trace rays, the shader groups that are used must be arranged in a
+//! [`ShaderBindingTable`], which is then passed to the
+//! [`trace_rays`](crate::command_buffer::AutoCommandBufferBuilder::trace_rays) command after the
+//! pipeline has been bound.
+
+use super::layout::PipelineLayoutCreateInfo;
+use crate::{
+    buffer::{BufferAccess, BufferDeviceAddressError, BufferUsage, CpuAccessibleBuffer},
+    descriptor_set::layout::{
+        DescriptorSetLayout, DescriptorSetLayoutCreateInfo, DescriptorSetLayoutCreationError,
+    },
+    device::{Device, DeviceOwned},
+    memory::allocator::{AllocationCreationError, MemoryAllocator},
+    pipeline::{
+        cache::PipelineCache,
+        layout::{
+            PipelineLayout, PipelineLayoutCreationError, PipelineLayoutSupersetError,
+            PushConstantRange,
+        },
+        Pipeline, PipelineBindPoint,
+    },
+    shader::{
+        specialization_constants_compatible, DescriptorBindingRequirements, EntryPoint,
+        ShaderStage, SpecializationConstants, SpecializationMapEntry,
+    },
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
+};
+use ahash::HashMap;
+use smallvec::SmallVec;
+use std::{
+    collections::hash_map::Entry,
+    error::Error,
+    fmt::{Debug, Display, Error as FmtError, Formatter},
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::Arc,
+};
+
+/// A pipeline object that describes to the Vulkan implementation how it should perform ray
+/// tracing operations.
+pub struct RayTracingPipeline {
+    handle: ash::vk::Pipeline,
+    device: Arc<Device>,
+    id: NonZeroU64,
+    layout: Arc<PipelineLayout>,
+
+    descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
+    num_used_descriptor_sets: u32,
+    stages: SmallVec<[ShaderStage; 8]>,
+    groups: Vec<RayTracingShaderGroup>,
+    max_pipeline_ray_recursion_depth: u32,
+    group_handles: Vec<u8>,
+}
+
+impl RayTracingPipeline {
+    /// Starts the building process of a ray tracing pipeline. Returns a builder object that you
+    /// can fill with the various parameters.
+    #[inline]
+    pub fn start() -> RayTracingPipelineBuilder<'static> {
+        RayTracingPipelineBuilder {
+            stages: Vec::new(),
+            groups: Vec::new(),
+            max_pipeline_ray_recursion_depth: 1,
+            cache: None,
+        }
+    }
+
+    /// Returns the `Device` this ray tracing pipeline was created with.
+    #[inline]
+    pub fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+
+    /// Returns the shader stage of each shader in the pipeline, in the order they were added.
+    #[inline]
+    pub fn stages(&self) -> &[ShaderStage] {
+        &self.stages
+    }
+
+    /// Returns the shader groups of the pipeline.
+    #[inline]
+    pub fn groups(&self) -> &[RayTracingShaderGroup] {
+        &self.groups
+    }
+
+    /// Returns the maximum recursion depth of rays traced by the pipeline.
+    #[inline]
+    pub fn max_pipeline_ray_recursion_depth(&self) -> u32 {
+        self.max_pipeline_ray_recursion_depth
+    }
+
+    /// Returns the opaque handle of the shader group with index `group_index`, as it must be
+    /// written into a shader binding table.
+    ///
+    /// Returns `None` if `group_index` is out of range.
+    #[inline]
+    pub fn group_handle(&self, group_index: u32) -> Option<&[u8]> {
+        let handle_size = self.group_handles.len() / self.groups.len().max(1);
+        let start = group_index as usize * handle_size;
+
+        self.group_handles.get(start..start + handle_size)
+    }
+}
+
+impl Pipeline for RayTracingPipeline {
+    #[inline]
+    fn bind_point(&self) -> PipelineBindPoint {
+        PipelineBindPoint::RayTracing
+    }
+
+    #[inline]
+    fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+
+    #[inline]
+    fn num_used_descriptor_sets(&self) -> u32 {
+        self.num_used_descriptor_sets
+    }
+
+    #[inline]
+    fn descriptor_binding_requirements(
+        &self,
+    ) -> &HashMap<(u32, u32), DescriptorBindingRequirements> {
+        &self.descriptor_binding_requirements
+    }
+}
+
+impl Debug for RayTracingPipeline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "<Vulkan ray tracing pipeline {:?}>", self.handle)
+    }
+}
+
+crate::impl_id_counter!(RayTracingPipeline);
+
+unsafe impl VulkanObject for RayTracingPipeline {
+    type Handle = ash::vk::Pipeline;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for RayTracingPipeline {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.device()
+    }
+}
+
+impl Drop for RayTracingPipeline {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.v1_0.destroy_pipeline)(self.device.handle(), self.handle, ptr::null());
+        }
+    }
+}
+
+/// A shader group of a ray tracing pipeline. The shaders are referred to by their index in the
+/// order they were added to the pipeline with [`RayTracingPipelineBuilder::stage`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RayTracingShaderGroup {
+    /// A group with a single ray generation, miss or callable shader.
+    General { general_shader: u32 },
+
+    /// A hit group for triangle geometry.
+    TrianglesHit {
+        closest_hit_shader: Option<u32>,
+        any_hit_shader: Option<u32>,
+    },
+
+    /// A hit group for AABB geometry, with an intersection shader that determines whether the
+    /// ray hits the procedural geometry.
+    ProceduralHit {
+        closest_hit_shader: Option<u32>,
+        any_hit_shader: Option<u32>,
+        intersection_shader: u32,
+    },
+}
+
+impl From<&RayTracingShaderGroup> for ash::vk::RayTracingShaderGroupCreateInfoKHR {
+    #[inline]
+    fn from(val: &RayTracingShaderGroup) -> Self {
+        let unused = ash::vk::SHADER_UNUSED_KHR;
+
+        match *val {
+            RayTracingShaderGroup::General { general_shader } => Self {
+                ty: ash::vk::RayTracingShaderGroupTypeKHR::GENERAL,
+                general_shader,
+                closest_hit_shader: unused,
+                any_hit_shader: unused,
+                intersection_shader: unused,
+                ..Default::default()
+            },
+            RayTracingShaderGroup::TrianglesHit {
+                closest_hit_shader,
+                any_hit_shader,
+            } => Self {
+                ty: ash::vk::RayTracingShaderGroupTypeKHR::TRIANGLES_HIT_GROUP,
+                general_shader: unused,
+                closest_hit_shader: closest_hit_shader.unwrap_or(unused),
+                any_hit_shader: any_hit_shader.unwrap_or(unused),
+                intersection_shader: unused,
+                ..Default::default()
+            },
+            RayTracingShaderGroup::ProceduralHit {
+                closest_hit_shader,
+                any_hit_shader,
+                intersection_shader,
+            } => Self {
+                ty: ash::vk::RayTracingShaderGroupTypeKHR::PROCEDURAL_HIT_GROUP,
+                general_shader: unused,
+                closest_hit_shader: closest_hit_shader.unwrap_or(unused),
+                any_hit_shader: any_hit_shader.unwrap_or(unused),
+                intersection_shader,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+struct RayTracingShaderStage<'a> {
+    entry_point: EntryPoint<'a>,
+    specialization_map_entries: Vec<SpecializationMapEntry>,
+    specialization_data: Vec<u8>,
+    specialization_constants_compatible: bool,
+}
+
+/// Prototype for a `RayTracingPipeline`.
+pub struct RayTracingPipelineBuilder<'a> {
+    stages: Vec<RayTracingShaderStage<'a>>,
+    groups: Vec<RayTracingShaderGroup>,
+    max_pipeline_ray_recursion_depth: u32,
+    cache: Option<Arc<PipelineCache>>,
+}
+
+impl<'a> RayTracingPipelineBuilder<'a> {
+    /// Adds a shader stage to the pipeline. The shader can then be referred to in a shader group
+    /// by its index, which is the number of stages that were added before it.
+    ///
+    /// The execution model of `entry_point` must be one of the ray tracing shader types.
+    #[inline]
+    pub fn stage<'b, Css>(
+        self,
+        entry_point: EntryPoint<'b>,
+        specialization_constants: Css,
+    ) -> RayTracingPipelineBuilder<'b>
+    where
+        'a: 'b,
+        Css: SpecializationConstants,
+    {
+        let specialization_constants_compatible =
+            specialization_constants_compatible(&entry_point, &specialization_constants);
+
+        let mut stages: Vec<RayTracingShaderStage<'b>> = self.stages;
+        stages.push(RayTracingShaderStage {
+            entry_point,
+            specialization_map_entries: specialization_constants.map_entries().to_vec(),
+            specialization_data: specialization_constants.data().to_vec(),
+            specialization_constants_compatible,
+        });
+
+        RayTracingPipelineBuilder {
+            stages,
+            groups: self.groups,
+            max_pipeline_ray_recursion_depth: self.max_pipeline_ray_recursion_depth,
+            cache: self.cache,
+        }
+    }
+
+    /// Adds a shader group to the pipeline. Shader groups are referred to by their index in a
+    /// shader binding table, which is the number of groups that were added before it.
+    #[inline]
+    pub fn group(mut self, group: RayTracingShaderGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Sets the maximum recursion depth of rays that are traced by the pipeline. This must not
+    /// be greater than the [`max_ray_recursion_depth`] device property.
+    ///
+    /// The default value is 1.
+    ///
+    /// [`max_ray_recursion_depth`]: crate::device::Properties::max_ray_recursion_depth
+    #[inline]
+    pub fn max_pipeline_ray_recursion_depth(mut self, depth: u32) -> Self {
+        self.max_pipeline_ray_recursion_depth = depth;
+        self
+    }
+
+    /// Enable caching of this pipeline via a PipelineCache object.
+    ///
+    /// If this pipeline already exists in the cache it will be used, if this is a new
+    /// pipeline it will be inserted into the cache. The implementation handles the
+    /// PipelineCache.
+    #[inline]
+    pub fn build_with_cache(mut self, pipeline_cache: Arc<PipelineCache>) -> Self {
+        self.cache = Some(pipeline_cache);
+        self
+    }
+
+    /// Builds the ray tracing pipeline, using an inferred pipeline layout.
+    #[inline]
+    pub fn build(
+        self,
+        device: Arc<Device>,
+    ) -> Result<Arc<RayTracingPipeline>, RayTracingPipelineCreationError> {
+        self.with_auto_layout(device, |_| {})
+    }
+
+    /// The same as `build`, but allows you to provide a closure that is given a mutable reference
+    /// to the inferred descriptor set definitions. This can be used to make changes to the layout
+    /// before it's created, for example to add dynamic buffers or immutable samplers.
+    pub fn with_auto_layout<F>(
+        self,
+        device: Arc<Device>,
+        func: F,
+    ) -> Result<Arc<RayTracingPipeline>, RayTracingPipelineCreationError>
+    where
+        F: FnOnce(&mut [DescriptorSetLayoutCreateInfo]),
+    {
+        let descriptor_binding_requirements = self.descriptor_binding_requirements();
+
+        let mut set_layout_create_infos = DescriptorSetLayoutCreateInfo::from_requirements(
+            descriptor_binding_requirements
+                .iter()
+                .map(|(&loc, reqs)| (loc, reqs)),
+        );
+        func(&mut set_layout_create_infos);
+
+        // Union the push constant ranges of the stages, in the same way as for graphics
+        // pipelines.
+        let mut range_map = HashMap::default();
+
+        for stage in &self.stages {
+            if let Some(range) = stage.entry_point.push_constant_requirements() {
+                match range_map.entry((range.offset, range.size)) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(range.stages);
+                    }
+                    Entry::Occupied(mut entry) => {
+                        *entry.get_mut() = *entry.get() | range.stages;
+                    }
+                }
+            }
+        }
+
+        let push_constant_ranges: Vec<_> = range_map
+            .into_iter()
+            .map(|((offset, size), stages)| PushConstantRange {
+                stages,
+                offset,
+                size,
+            })
+            .collect();
+
+        let set_layouts = set_layout_create_infos
+            .into_iter()
+            .map(|desc| DescriptorSetLayout::new(device.clone(), desc))
+            .collect::<Result<Vec<_>, _>>()?;
+        let pipeline_layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts,
+                push_constant_ranges,
+                ..Default::default()
+            },
+        )?;
+
+        self.with_pipeline_layout(device, pipeline_layout)
+    }
+
+    /// Builds the ray tracing pipeline with a specific pipeline layout.
+    ///
+    /// An error will be returned if the pipeline layout isn't a superset of what the shaders
+    /// use.
+    pub fn with_pipeline_layout(
+        self,
+        device: Arc<Device>,
+        pipeline_layout: Arc<PipelineLayout>,
+    ) -> Result<Arc<RayTracingPipeline>, RayTracingPipelineCreationError> {
+        self.validate(&device, &pipeline_layout)?;
+
+        unsafe { Ok(self.record_create(device, pipeline_layout)?) }
+    }
+
+    // Produces `DescriptorBindingRequirements` for each binding, by merging the requirements of
+    // all shaders.
+    fn descriptor_binding_requirements(
+        &self,
+    ) -> HashMap<(u32, u32), DescriptorBindingRequirements> {
+        let mut descriptor_binding_requirements: HashMap<
+            (u32, u32),
+            DescriptorBindingRequirements,
+        > = HashMap::default();
+
+        for (loc, reqs) in self
+            .stages
+            .iter()
+            .flat_map(|stage| stage.entry_point.descriptor_binding_requirements())
+        {
+            match descriptor_binding_requirements.entry(loc) {
+                Entry::Occupied(entry) => {
+                    entry.into_mut().merge(reqs).expect(
+                        "Could not produce an intersection of the shader descriptor requirements",
+                    );
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(reqs.clone());
+                }
+            }
+        }
+
+        descriptor_binding_requirements
+    }
+
+    fn validate(
+        &self,
+        device: &Device,
+        pipeline_layout: &PipelineLayout,
+    ) -> Result<(), RayTracingPipelineCreationError> {
+        // VUID-vkCreateRayTracingPipelinesKHR-rayTracingPipeline-03586
+        if !device.enabled_features().ray_tracing_pipeline {
+            return Err(RayTracingPipelineCreationError::RequirementNotMet {
+                required_for: "`RayTracingPipeline::start`",
+                requires_one_of: RequiresOneOf {
+                    features: &["ray_tracing_pipeline"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkRayTracingPipelineCreateInfoKHR-commonparent
+        assert_eq!(device, pipeline_layout.device().as_ref());
+
+        let stages: SmallVec<[ShaderStage; 8]> = self
+            .stages
+            .iter()
+            .map(|stage| ShaderStage::from(*stage.entry_point.execution()))
+            .collect();
+
+        for (stage_index, (stage, &shader_stage)) in self.stages.iter().zip(&stages).enumerate() {
+            // VUID-VkPipelineShaderStageCreateInfo-module-parameter
+            assert_eq!(device, stage.entry_point.module().device().as_ref());
+
+            // VUID-VkRayTracingPipelineCreateInfoKHR-stage-06899
+            if !matches!(
+                shader_stage,
+                ShaderStage::Raygen
+                    | ShaderStage::AnyHit
+                    | ShaderStage::ClosestHit
+                    | ShaderStage::Miss
+                    | ShaderStage::Intersection
+                    | ShaderStage::Callable
+            ) {
+                return Err(RayTracingPipelineCreationError::ShaderStageInvalid {
+                    stage_index: stage_index as u32,
+                });
+            }
+
+            if !stage.specialization_constants_compatible {
+                return Err(RayTracingPipelineCreationError::IncompatibleSpecializationConstants);
+            }
+
+            // VUID-VkRayTracingPipelineCreateInfoKHR-layout-03427
+            pipeline_layout.ensure_compatible_with_shader(
+                stage.entry_point.descriptor_binding_requirements(),
+                stage.entry_point.push_constant_requirements(),
+            )?;
+        }
+
+        // VUID-VkRayTracingPipelineCreateInfoKHR-stage-03425
+        if !stages.contains(&ShaderStage::Raygen) {
+            return Err(RayTracingPipelineCreationError::RaygenShaderMissing);
+        }
+
+        let check_shader = |group_index: usize,
+                            shader: u32,
+                            allowed: &[ShaderStage]|
+         -> Result<(), RayTracingPipelineCreationError> {
+            match stages.get(shader as usize) {
+                Some(stage) if allowed.contains(stage) => Ok(()),
+                _ => Err(RayTracingPipelineCreationError::ShaderGroupInvalid {
+                    group_index: group_index as u32,
+                }),
+            }
+        };
+
+        for (group_index, group) in self.groups.iter().enumerate() {
+            match *group {
+                RayTracingShaderGroup::General { general_shader } => {
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-type-03474
+                    check_shader(
+                        group_index,
+                        general_shader,
+                        &[
+                            ShaderStage::Raygen,
+                            ShaderStage::Miss,
+                            ShaderStage::Callable,
+                        ],
+                    )?;
+                }
+                RayTracingShaderGroup::TrianglesHit {
+                    closest_hit_shader,
+                    any_hit_shader,
+                } => {
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-closestHitShader-03478
+                    if let Some(closest_hit_shader) = closest_hit_shader {
+                        check_shader(group_index, closest_hit_shader, &[ShaderStage::ClosestHit])?;
+                    }
+
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-anyHitShader-03479
+                    if let Some(any_hit_shader) = any_hit_shader {
+                        check_shader(group_index, any_hit_shader, &[ShaderStage::AnyHit])?;
+                    }
+                }
+                RayTracingShaderGroup::ProceduralHit {
+                    closest_hit_shader,
+                    any_hit_shader,
+                    intersection_shader,
+                } => {
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-closestHitShader-03478
+                    if let Some(closest_hit_shader) = closest_hit_shader {
+                        check_shader(group_index, closest_hit_shader, &[ShaderStage::ClosestHit])?;
+                    }
+
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-anyHitShader-03479
+                    if let Some(any_hit_shader) = any_hit_shader {
+                        check_shader(group_index, any_hit_shader, &[ShaderStage::AnyHit])?;
+                    }
+
+                    // VUID-VkRayTracingShaderGroupCreateInfoKHR-type-03476
+                    check_shader(
+                        group_index,
+                        intersection_shader,
+                        &[ShaderStage::Intersection],
+                    )?;
+                }
+            }
+        }
+
+        // VUID-VkRayTracingPipelineCreateInfoKHR-maxPipelineRayRecursionDepth-03589
+        let max_ray_recursion_depth = device
+            .physical_device()
+            .properties()
+            .max_ray_recursion_depth
+            .unwrap_or(0);
+
+        if self.max_pipeline_ray_recursion_depth > max_ray_recursion_depth {
+            return Err(
+                RayTracingPipelineCreationError::MaxRayRecursionDepthExceeded {
+                    depth: self.max_pipeline_ray_recursion_depth,
+                    max: max_ray_recursion_depth,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    unsafe fn record_create(
+        self,
+        device: Arc<Device>,
+        pipeline_layout: Arc<PipelineLayout>,
+    ) -> Result<Arc<RayTracingPipeline>, VulkanError> {
+        let descriptor_binding_requirements = self.descriptor_binding_requirements();
+
+        let Self {
+            stages,
+            groups,
+            max_pipeline_ray_recursion_depth,
+            cache,
+        } = self;
+
+        let specialization_infos_vk: SmallVec<[_; 8]> = stages
+            .iter()
+            .map(|stage| ash::vk::SpecializationInfo {
+                map_entry_count: stage.specialization_map_entries.len() as u32,
+                p_map_entries: stage.specialization_map_entries.as_ptr() as *const _,
+                data_size: stage.specialization_data.len(),
+                p_data: stage.specialization_data.as_ptr() as *const _,
+            })
+            .collect();
+
+        let stages_vk: SmallVec<[_; 8]> = stages
+            .iter()
+            .zip(&specialization_infos_vk)
+            .map(
+                |(stage, specialization_info_vk)| ash::vk::PipelineShaderStageCreateInfo {
+                    flags: ash::vk::PipelineShaderStageCreateFlags::empty(),
+                    stage: ShaderStage::from(*stage.entry_point.execution()).into(),
+                    module: stage.entry_point.module().handle(),
+                    p_name: stage.entry_point.name().as_ptr(),
+                    p_specialization_info: if specialization_info_vk.data_size == 0 {
+                        ptr::null()
+                    } else {
+                        specialization_info_vk as *const _
+                    },
+                    ..Default::default()
+                },
+            )
+            .collect();
+
+        let groups_vk: SmallVec<[_; 8]> = groups
+            .iter()
+            .map(ash::vk::RayTracingShaderGroupCreateInfoKHR::from)
+            .collect();
+
+        let mut flags_vk = ash::vk::PipelineCreateFlags::empty();
+
+        if pipeline_layout
+            .set_layouts()
+            .iter()
+            .any(|set_layout| set_layout.descriptor_buffer())
+        {
+            flags_vk |= ash::vk::PipelineCreateFlags::DESCRIPTOR_BUFFER_EXT;
+        }
+
+        let create_info_vk = ash::vk::RayTracingPipelineCreateInfoKHR {
+            flags: flags_vk,
+            stage_count: stages_vk.len() as u32,
+            p_stages: stages_vk.as_ptr(),
+            group_count: groups_vk.len() as u32,
+            p_groups: groups_vk.as_ptr(),
+            max_pipeline_ray_recursion_depth,
+            layout: pipeline_layout.handle(),
+            base_pipeline_handle: ash::vk::Pipeline::null(),
+            base_pipeline_index: 0,
+            ..Default::default()
+        };
+
+        let cache_handle = match cache {
+            Some(ref cache) => cache.handle(),
+            None => ash::vk::PipelineCache::null(),
+        };
+
+        let fns = device.fns();
+
+        let handle = {
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_ray_tracing_pipeline
+                .create_ray_tracing_pipelines_khr)(
+                device.handle(),
+                ash::vk::DeferredOperationKHR::null(),
+                cache_handle,
+                1,
+                &create_info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        let handle_size = device
+            .physical_device()
+            .properties()
+            .shader_group_handle_size
+            .unwrap_or(0) as usize;
+        let mut group_handles = vec![0u8; handle_size * groups.len()];
+
+        if !group_handles.is_empty() {
+            let result = (fns
+                .khr_ray_tracing_pipeline
+                .get_ray_tracing_shader_group_handles_khr)(
+                device.handle(),
+                handle,
+                0,
+                groups.len() as u32,
+                group_handles.len(),
+                group_handles.as_mut_ptr() as *mut _,
+            )
+            .result()
+            .map_err(VulkanError::from);
+
+            if let Err(err) = result {
+                (fns.v1_0.destroy_pipeline)(device.handle(), handle, ptr::null());
+                return Err(err);
+            }
+        }
+
+        let num_used_descriptor_sets = descriptor_binding_requirements
+            .keys()
+            .map(|loc| loc.0)
+            .max()
+            .map(|x| x + 1)
+            .unwrap_or(0);
+
+        Ok(Arc::new(RayTracingPipeline {
+            handle,
+            device: device.clone(),
+            id: RayTracingPipeline::next_id(),
+            layout: pipeline_layout,
+            descriptor_binding_requirements,
+            num_used_descriptor_sets,
+            stages: stages
+                .iter()
+                .map(|stage| ShaderStage::from(*stage.entry_point.execution()))
+                .collect(),
+            groups,
+            max_pipeline_ray_recursion_depth,
+            group_handles,
+        }))
+    }
+}
+
+/// Error that can happen when creating a ray tracing pipeline.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RayTracingPipelineCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// Error while creating a descriptor set layout object.
+    DescriptorSetLayoutCreationError(DescriptorSetLayoutCreationError),
+
+    /// Error while creating the pipeline layout object.
+    PipelineLayoutCreationError(PipelineLayoutCreationError),
+
+    /// The pipeline layout is not compatible with what the shaders expect.
+    IncompatiblePipelineLayout(PipelineLayoutSupersetError),
+
+    /// The provided specialization constants are not compatible with what the shader expects.
+    IncompatibleSpecializationConstants,
+
+    /// The maximum ray recursion depth exceeds the `max_ray_recursion_depth` limit.
+    MaxRayRecursionDepthExceeded { depth: u32, max: u32 },
+
+    /// The pipeline does not contain a ray generation shader.
+    RaygenShaderMissing,
+
+    /// A shader group refers to a shader that does not exist, or that has a stage that is not
+    /// allowed for its place in the group.
+    ShaderGroupInvalid { group_index: u32 },
+
+    /// The stage of a shader is not a ray tracing stage.
+    ShaderStageInvalid { stage_index: u32 },
+}
+
+impl Error for RayTracingPipelineCreationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            Self::DescriptorSetLayoutCreationError(err) => Some(err),
+            Self::PipelineLayoutCreationError(err) => Some(err),
+            Self::IncompatiblePipelineLayout(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RayTracingPipelineCreationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::DescriptorSetLayoutCreationError(_) => {
+                write!(f, "error while creating a descriptor set layout object")
+            }
+            Self::PipelineLayoutCreationError(_) => {
+                write!(f, "error while creating the pipeline layout object")
+            }
+            Self::IncompatiblePipelineLayout(_) => write!(
+                f,
+                "the pipeline layout is not compatible with what the shaders expect",
+            ),
+            Self::IncompatibleSpecializationConstants => write!(
+                f,
+                "the provided specialization constants are not compatible with what the shader \
+                expects",
+            ),
+            Self::MaxRayRecursionDepthExceeded { depth, max } => write!(
+                f,
+                "the maximum ray recursion depth ({}) exceeds the `max_ray_recursion_depth` \
+                limit ({})",
+                depth, max,
+            ),
+            Self::RaygenShaderMissing => {
+                write!(f, "the pipeline does not contain a ray generation shader")
+            }
+            Self::ShaderGroupInvalid { group_index } => write!(
+                f,
+                "shader group {} refers to a shader that does not exist, or that has a stage \
+                that is not allowed for its place in the group",
+                group_index,
+            ),
+            Self::ShaderStageInvalid { stage_index } => write!(
+                f,
+                "the stage of shader {} is not a ray tracing stage",
+                stage_index,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for RayTracingPipelineCreationError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<DescriptorSetLayoutCreationError> for RayTracingPipelineCreationError {
+    fn from(err: DescriptorSetLayoutCreationError) -> Self {
+        Self::DescriptorSetLayoutCreationError(err)
+    }
+}
+
+impl From<PipelineLayoutCreationError> for RayTracingPipelineCreationError {
+    fn from(err: PipelineLayoutCreationError) -> Self {
+        Self::PipelineLayoutCreationError(err)
+    }
+}
+
+impl From<PipelineLayoutSupersetError> for RayTracingPipelineCreationError {
+    fn from(err: PipelineLayoutSupersetError) -> Self {
+        Self::IncompatiblePipelineLayout(err)
+    }
+}
+
+impl From<VulkanError> for RayTracingPipelineCreationError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            err @ VulkanError::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ VulkanError::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for RayTracingPipelineCreationError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+/// A buffer containing the handles of the shader groups of a ray tracing pipeline, arranged in
+/// the way that is needed for tracing rays.
+///
+/// The table consists of four regions: a single ray generation group, followed by the miss,
+/// hit and callable groups. Each region starts at a multiple of the
+/// [`shader_group_base_alignment`] device property, and each handle in a region is placed at a
+/// multiple of the [`shader_group_handle_alignment`] device property.
+///
+/// [`shader_group_base_alignment`]: crate::device::Properties::shader_group_base_alignment
+/// [`shader_group_handle_alignment`]: crate::device::Properties::shader_group_handle_alignment
+#[derive(Debug)]
+pub struct ShaderBindingTable {
+    buffer: Arc<CpuAccessibleBuffer<[u8]>>,
+    pipeline: Arc<RayTracingPipeline>,
+    raygen: StridedDeviceAddressRegion,
+    miss: StridedDeviceAddressRegion,
+    hit: StridedDeviceAddressRegion,
+    callable: StridedDeviceAddressRegion,
+}
+
+impl ShaderBindingTable {
+    /// Creates a new `ShaderBindingTable` for `pipeline`, with the groups given in
+    /// `create_info`.
+    pub fn new(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        pipeline: Arc<RayTracingPipeline>,
+        create_info: ShaderBindingTableCreateInfo,
+    ) -> Result<Self, ShaderBindingTableError> {
+        let ShaderBindingTableCreateInfo {
+            raygen_group,
+            miss_groups,
+            hit_groups,
+            callable_groups,
+            _ne: _,
+        } = create_info;
+
+        // Checks that the group exists, and that it is a general group with a shader of
+        // `general_stage`, or a hit group if `general_stage` is `None`.
+        let check_group = |group_index: u32,
+                           general_stage: Option<ShaderStage>|
+         -> Result<(), ShaderBindingTableError> {
+            let valid = match pipeline.groups().get(group_index as usize) {
+                Some(&RayTracingShaderGroup::General { general_shader }) => {
+                    general_stage == Some(pipeline.stages()[general_shader as usize])
+                }
+                Some(_) => general_stage.is_none(),
+                None => {
+                    return Err(ShaderBindingTableError::GroupIndexOutOfRange {
+                        group_index,
+                        group_count: pipeline.groups().len() as u32,
+                    })
+                }
+            };
+
+            if valid {
+                Ok(())
+            } else {
+                Err(ShaderBindingTableError::GroupTypeMismatch { group_index })
+            }
+        };
+
+        check_group(raygen_group, Some(ShaderStage::Raygen))?;
+
+        for &group_index in &miss_groups {
+            check_group(group_index, Some(ShaderStage::Miss))?;
+        }
+
+        for &group_index in &hit_groups {
+            check_group(group_index, None)?;
+        }
+
+        for &group_index in &callable_groups {
+            check_group(group_index, Some(ShaderStage::Callable))?;
+        }
+
+        let properties = pipeline.device().physical_device().properties();
+        let handle_size = properties.shader_group_handle_size.unwrap_or(0) as DeviceSize;
+        let handle_alignment = properties.shader_group_handle_alignment.unwrap_or(1) as DeviceSize;
+        let base_alignment = properties.shader_group_base_alignment.unwrap_or(1) as DeviceSize;
+
+        let ShaderBindingTableLayout {
+            handle_stride,
+            raygen_stride,
+            miss_offset,
+            miss_size,
+            hit_offset,
+            hit_size,
+            callable_offset,
+            callable_size,
+            table_size,
+        } = ShaderBindingTableLayout::new(
+            handle_size,
+            handle_alignment,
+            base_alignment,
+            miss_groups.len() as DeviceSize,
+            hit_groups.len() as DeviceSize,
+            callable_groups.len() as DeviceSize,
+        );
+
+        // The buffer may not be allocated at a multiple of the base alignment, so allocate some
+        // extra space to move the start of the table forward if needed.
+        let buffer = CpuAccessibleBuffer::from_iter(
+            allocator,
+            BufferUsage::SHADER_BINDING_TABLE | BufferUsage::SHADER_DEVICE_ADDRESS,
+            false,
+            vec![0u8; (table_size + base_alignment - 1) as usize],
+        )?;
+
+        let buffer_address = buffer.raw_device_address()?.get();
+        let table_offset = align_up(buffer_address, base_alignment) - buffer_address;
+        let table_address = buffer_address + table_offset;
+
+        {
+            let mut content = buffer.write().unwrap();
+            let table = &mut content[table_offset as usize..];
+
+            let mut write_handles = |offset: DeviceSize, groups: &[u32]| {
+                for (index, &group_index) in groups.iter().enumerate() {
+                    let start = (offset + index as DeviceSize * handle_stride) as usize;
+                    let handle = pipeline.group_handle(group_index).unwrap();
+                    table[start..start + handle.len()].copy_from_slice(handle);
+                }
+            };
+
+            write_handles(0, &[raygen_group]);
+            write_handles(miss_offset, &miss_groups);
+            write_handles(hit_offset, &hit_groups);
+            write_handles(callable_offset, &callable_groups);
+        }
+
+        let region = |offset: DeviceSize, size: DeviceSize, stride: DeviceSize| {
+            if size == 0 {
+                StridedDeviceAddressRegion::default()
+            } else {
+                StridedDeviceAddressRegion {
+                    device_address: table_address + offset,
+                    stride,
+                    size,
+                }
+            }
+        };
+
+        Ok(ShaderBindingTable {
+            buffer,
+            raygen: region(0, raygen_stride, raygen_stride),
+            miss: region(miss_offset, miss_size, handle_stride),
+            hit: region(hit_offset, hit_size, handle_stride),
+            callable: region(callable_offset, callable_size, handle_stride),
+            pipeline,
+        })
+    }
+
+    /// Returns the buffer that contains the table.
+    #[inline]
+    pub fn buffer(&self) -> &Arc<CpuAccessibleBuffer<[u8]>> {
+        &self.buffer
+    }
+
+    /// Returns the pipeline that the table was created for.
+    #[inline]
+    pub fn pipeline(&self) -> &Arc<RayTracingPipeline> {
+        &self.pipeline
+    }
+
+    /// Returns the region of the ray generation group.
+    #[inline]
+    pub fn raygen(&self) -> &StridedDeviceAddressRegion {
+        &self.raygen
+    }
+
+    /// Returns the region of the miss groups.
+    #[inline]
+    pub fn miss(&self) -> &StridedDeviceAddressRegion {
+        &self.miss
+    }
+
+    /// Returns the region of the hit groups.
+    #[inline]
+    pub fn hit(&self) -> &StridedDeviceAddressRegion {
+        &self.hit
+    }
+
+    /// Returns the region of the callable groups.
+    #[inline]
+    pub fn callable(&self) -> &StridedDeviceAddressRegion {
+        &self.callable
+    }
+}
+
+fn align_up(value: DeviceSize, alignment: DeviceSize) -> DeviceSize {
+    (value + alignment - 1) / alignment * alignment
+}
+
+/// The offsets and sizes of the regions in a shader binding table, relative to the start of the
+/// table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct ShaderBindingTableLayout {
+    handle_stride: DeviceSize,
+    raygen_stride: DeviceSize,
+    miss_offset: DeviceSize,
+    miss_size: DeviceSize,
+    hit_offset: DeviceSize,
+    hit_size: DeviceSize,
+    callable_offset: DeviceSize,
+    callable_size: DeviceSize,
+    table_size: DeviceSize,
+}
+
+impl ShaderBindingTableLayout {
+    fn new(
+        handle_size: DeviceSize,
+        handle_alignment: DeviceSize,
+        base_alignment: DeviceSize,
+        miss_count: DeviceSize,
+        hit_count: DeviceSize,
+        callable_count: DeviceSize,
+    ) -> Self {
+        let handle_stride = align_up(handle_size, handle_alignment);
+
+        // The ray generation region must have a size equal to its stride.
+        let raygen_stride = align_up(handle_size, base_alignment);
+        let miss_offset = raygen_stride;
+        let miss_size = miss_count * handle_stride;
+        let hit_offset = align_up(miss_offset + miss_size, base_alignment);
+        let hit_size = hit_count * handle_stride;
+        let callable_offset = align_up(hit_offset + hit_size, base_alignment);
+        let callable_size = callable_count * handle_stride;
+        let table_size = callable_offset + callable_size;
+
+        Self {
+            handle_stride,
+            raygen_stride,
+            miss_offset,
+            miss_size,
+            hit_offset,
+            hit_size,
+            callable_offset,
+            callable_size,
+            table_size,
+        }
+    }
+}
+
+/// Parameters to create a new `ShaderBindingTable`.
+///
+/// The groups are referred to by their index in the pipeline.
+#[derive(Clone, Debug)]
+pub struct ShaderBindingTableCreateInfo {
+    /// The group containing the ray generation shader.
+    ///
+    /// There is no default value.
+    pub raygen_group: u32,
+
+    /// The groups containing miss shaders.
+    ///
+    /// The default value is empty.
+    pub miss_groups: Vec<u32>,
+
+    /// The hit groups.
+    ///
+    /// The default value is empty.
+    pub hit_groups: Vec<u32>,
+
+    /// The groups containing callable shaders.
+    ///
+    /// The default value is empty.
+    pub callable_groups: Vec<u32>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl ShaderBindingTableCreateInfo {
+    /// Returns a `ShaderBindingTableCreateInfo` with the specified `raygen_group`.
+    #[inline]
+    pub fn raygen_group(raygen_group: u32) -> Self {
+        Self {
+            raygen_group,
+            miss_groups: Vec::new(),
+            hit_groups: Vec::new(),
+            callable_groups: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A region of a shader binding table, consisting of a number of handles that are placed at a
+/// regular stride.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct StridedDeviceAddressRegion {
+    /// The device address of the start of the region, or 0 if the region is empty.
+    pub device_address: DeviceSize,
+
+    /// The number of bytes between the start of successive handles.
+    pub stride: DeviceSize,
+
+    /// The size of the region in bytes.
+    pub size: DeviceSize,
+}
+
+impl From<&StridedDeviceAddressRegion> for ash::vk::StridedDeviceAddressRegionKHR {
+    #[inline]
+    fn from(val: &StridedDeviceAddressRegion) -> Self {
+        Self {
+            device_address: val.device_address,
+            stride: val.stride,
+            size: val.size,
+        }
+    }
+}
+
+/// Error that can happen when creating a shader binding table.
+#[derive(Clone, Debug)]
+pub enum ShaderBindingTableError {
+    /// Allocating memory for the table failed.
+    AllocationCreationError(AllocationCreationError),
+
+    /// Retrieving the device address of the table failed.
+    BufferDeviceAddressError(BufferDeviceAddressError),
+
+    /// A group index is not less than the number of groups in the pipeline.
+    GroupIndexOutOfRange { group_index: u32, group_count: u32 },
+
+    /// The type of a group does not match the region of the table it was placed in.
+    GroupTypeMismatch { group_index: u32 },
+}
+
+impl Error for ShaderBindingTableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AllocationCreationError(err) => Some(err),
+            Self::BufferDeviceAddressError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ShaderBindingTableError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::AllocationCreationError(_) => {
+                write!(f, "allocating memory for the table failed")
+            }
+            Self::BufferDeviceAddressError(_) => {
+                write!(f, "retrieving the device address of the table failed")
+            }
+            Self::GroupIndexOutOfRange {
+                group_index,
+                group_count,
+            } => write!(
+                f,
+                "the group index {} is not less than the number of groups in the pipeline ({})",
+                group_index, group_count,
+            ),
+            Self::GroupTypeMismatch { group_index } => write!(
+                f,
+                "the type of group {} does not match the region of the table it was placed in",
+                group_index,
+            ),
+        }
+    }
+}
+
+impl From<AllocationCreationError> for ShaderBindingTableError {
+    fn from(err: AllocationCreationError) -> Self {
+        Self::AllocationCreationError(err)
+    }
+}
+
+impl From<BufferDeviceAddressError> for ShaderBindingTableError {
+    fn from(err: BufferDeviceAddressError) -> Self {
+        Self::BufferDeviceAddressError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RayTracingPipelineCreationError, ShaderBindingTableLayout};
+    use crate::pipeline::{layout::PipelineLayoutCreateInfo, PipelineLayout, RayTracingPipeline};
+
+    #[test]
+    fn sbt_layout() {
+        // Handles of 32 bytes, aligned to 32 bytes, with regions aligned to 64 bytes.
+        let layout = ShaderBindingTableLayout::new(32, 32, 64, 3, 1, 0);
+
+        assert_eq!(layout.handle_stride, 32);
+        assert_eq!(layout.raygen_stride, 64);
+        assert_eq!(layout.miss_offset, 64);
+        assert_eq!(layout.miss_size, 96);
+        // 64 + 96 = 160, rounded up to 192.
+        assert_eq!(layout.hit_offset, 192);
+        assert_eq!(layout.hit_size, 32);
+        assert_eq!(layout.callable_offset, 256);
+        assert_eq!(layout.callable_size, 0);
+        assert_eq!(layout.table_size, 256);
+    }
+
+    #[test]
+    fn sbt_layout_handle_alignment() {
+        // The handle stride is rounded up to the handle alignment.
+        let layout = ShaderBindingTableLayout::new(20, 16, 32, 2, 2, 1);
+
+        assert_eq!(layout.handle_stride, 32);
+        assert_eq!(layout.raygen_stride, 32);
+        assert_eq!(layout.miss_offset, 32);
+        assert_eq!(layout.miss_size, 64);
+        assert_eq!(layout.hit_offset, 96);
+        assert_eq!(layout.hit_size, 64);
+        assert_eq!(layout.callable_offset, 160);
+        assert_eq!(layout.callable_size, 32);
+        assert_eq!(layout.table_size, 192);
+
+        for offset in [
+            layout.miss_offset,
+            layout.hit_offset,
+            layout.callable_offset,
+        ] {
+            assert_eq!(offset % 32, 0);
+        }
+    }
+
+    #[test]
+    fn requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let pipeline_layout =
+            PipelineLayout::new(device.clone(), PipelineLayoutCreateInfo::default()).unwrap();
+
+        match RayTracingPipeline::start().with_pipeline_layout(device, pipeline_layout) {
+            Err(RayTracingPipelineCreationError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -12,17 +12,37 @@
 //! In Vulkan, queries are not created individually. Instead you manipulate **query pools**, which
 //! represent a collection of queries. Whenever you use a query, you have to specify both the query
 //! pool and the slot id within that query pool.
+//!
+//! # Performance queries
+//!
+//! With the [`khr_performance_query`] extension, a query pool can read hardware performance
+//! counters, such as memory bandwidth or shader occupancy. The counters that are available for a
+//! queue family are listed by [`PhysicalDevice::queue_family_performance_counters`], and the
+//! counters to read are chosen when creating the pool with [`QueryType::Performance`].
+//!
+//! Before recording a command buffer that uses a performance query, the profiling lock must be
+//! acquired with [`Device::acquire_profiling_lock`]. Reading all of the chosen counters may
+//! require executing the queries more than once. The number of passes is given by
+//! [`PhysicalDevice::queue_family_performance_query_passes`], and each pass is selected with
+//! [`SubmitInfo::performance_counter_pass_index`]. The results are read with
+//! [`QueriesRange::get_performance_results`].
+//!
+//! [`khr_performance_query`]: crate::device::DeviceExtensions::khr_performance_query
+//! [`PhysicalDevice::queue_family_performance_counters`]: crate::device::physical::PhysicalDevice::queue_family_performance_counters
+//! [`Device::acquire_profiling_lock`]: crate::device::Device::acquire_profiling_lock
+//! [`PhysicalDevice::queue_family_performance_query_passes`]: crate::device::physical::PhysicalDevice::queue_family_performance_query_passes
+//! [`SubmitInfo::performance_counter_pass_index`]: crate::command_buffer::SubmitInfo::performance_counter_pass_index
 
 use crate::{
     device::{Device, DeviceOwned},
-    macros::vulkan_bitflags,
-    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
+    macros::{vulkan_bitflags, vulkan_enum},
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use std::{
     error::Error,
     ffi::c_void,
     fmt::{Display, Error as FmtError, Formatter},
-    mem::{size_of_val, MaybeUninit},
+    mem::{size_of, size_of_val, MaybeUninit},
     num::NonZeroU64,
     ops::Range,
     ptr,
@@ -38,6 +58,8 @@ pub struct QueryPool {
 
     query_type: QueryType,
     query_count: u32,
+    performance_queue_family_index: u32,
+    performance_counter_indices: Vec<u32>,
 }
 
 impl QueryPool {
@@ -46,6 +68,8 @@ impl QueryPool {
     /// # Panics
     ///
     /// - Panics if `create_info.query_count` is `0`.
+    /// - Panics if `create_info.query_type` is [`QueryType::Performance`] and
+    ///   `create_info.performance_counter_indices` is empty.
     pub fn new(
         device: Arc<Device>,
         create_info: QueryPoolCreateInfo,
@@ -53,6 +77,8 @@ impl QueryPool {
         let QueryPoolCreateInfo {
             query_type,
             query_count,
+            performance_queue_family_index,
+            performance_counter_indices,
             _ne: _,
         } = create_info;
 
@@ -72,15 +98,121 @@ impl QueryPool {
             QueryType::Occlusion | QueryType::Timestamp => {
                 ash::vk::QueryPipelineStatisticFlags::empty()
             }
+            QueryType::AccelerationStructureCompactedSize => {
+                // VUID-VkQueryPoolCreateInfo-queryType-parameter
+                if !device.enabled_extensions().khr_acceleration_structure {
+                    return Err(QueryPoolCreationError::AccelerationStructureExtensionNotEnabled);
+                }
+
+                ash::vk::QueryPipelineStatisticFlags::empty()
+            }
+            QueryType::TransformFeedbackStream => {
+                // VUID-VkQueryPoolCreateInfo-queryType-parameter
+                if !device.enabled_extensions().ext_transform_feedback {
+                    return Err(QueryPoolCreationError::RequirementNotMet {
+                        required_for: "`create_info.query_type` is \
+                            `QueryType::TransformFeedbackStream`",
+                        requires_one_of: RequiresOneOf {
+                            device_extensions: &["ext_transform_feedback"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                ash::vk::QueryPipelineStatisticFlags::empty()
+            }
+            QueryType::PrimitivesGenerated => {
+                // VUID-VkQueryPoolCreateInfo-queryType-parameter
+                if !device.enabled_extensions().ext_primitives_generated_query {
+                    return Err(QueryPoolCreationError::RequirementNotMet {
+                        required_for: "`create_info.query_type` is \
+                            `QueryType::PrimitivesGenerated`",
+                        requires_one_of: RequiresOneOf {
+                            device_extensions: &["ext_primitives_generated_query"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                ash::vk::QueryPipelineStatisticFlags::empty()
+            }
+            QueryType::Performance => {
+                if !device.enabled_extensions().khr_performance_query {
+                    return Err(QueryPoolCreationError::RequirementNotMet {
+                        required_for: "`create_info.query_type` is `QueryType::Performance`",
+                        requires_one_of: RequiresOneOf {
+                            device_extensions: &["khr_performance_query"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                // VUID-VkQueryPoolPerformanceCreateInfoKHR-performanceCounterQueryPools-03237
+                if !device.enabled_features().performance_counter_query_pools {
+                    return Err(QueryPoolCreationError::RequirementNotMet {
+                        required_for: "`create_info.query_type` is `QueryType::Performance`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["performance_counter_query_pools"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                let queue_family_count =
+                    device.physical_device().queue_family_properties().len() as u32;
+
+                // VUID-VkQueryPoolPerformanceCreateInfoKHR-queueFamilyIndex-03236
+                if performance_queue_family_index >= queue_family_count {
+                    return Err(QueryPoolCreationError::QueueFamilyIndexOutOfRange {
+                        queue_family_index: performance_queue_family_index,
+                        queue_family_count,
+                    });
+                }
+
+                // VUID-VkQueryPoolPerformanceCreateInfoKHR-counterIndexCount-arraylength
+                assert!(!performance_counter_indices.is_empty());
+
+                let counter_count = unsafe {
+                    device
+                        .physical_device()
+                        .queue_family_performance_counters_unchecked(
+                            performance_queue_family_index,
+                        )?
+                        .len() as u32
+                };
+
+                for &counter_index in &performance_counter_indices {
+                    // VUID-VkQueryPoolPerformanceCreateInfoKHR-pCounterIndices-03321
+                    if counter_index >= counter_count {
+                        return Err(QueryPoolCreationError::PerformanceCounterIndexOutOfRange {
+                            counter_index,
+                            counter_count,
+                        });
+                    }
+                }
+
+                ash::vk::QueryPipelineStatisticFlags::empty()
+            }
         };
 
-        let create_info = ash::vk::QueryPoolCreateInfo {
+        let mut create_info = ash::vk::QueryPoolCreateInfo {
             flags: ash::vk::QueryPoolCreateFlags::empty(),
             query_type: query_type.into(),
             query_count,
             pipeline_statistics,
             ..Default::default()
         };
+        let performance_create_info;
+
+        if let QueryType::Performance = query_type {
+            performance_create_info = ash::vk::QueryPoolPerformanceCreateInfoKHR {
+                queue_family_index: performance_queue_family_index,
+                counter_index_count: performance_counter_indices.len() as u32,
+                p_counter_indices: performance_counter_indices.as_ptr(),
+                ..Default::default()
+            };
+            create_info.p_next = &performance_create_info as *const _ as *const _;
+        }
 
         let handle = unsafe {
             let fns = device.fns();
@@ -102,6 +234,8 @@ impl QueryPool {
             id: Self::next_id(),
             query_type,
             query_count,
+            performance_queue_family_index,
+            performance_counter_indices,
         }))
     }
 
@@ -120,6 +254,8 @@ impl QueryPool {
         let QueryPoolCreateInfo {
             query_type,
             query_count,
+            performance_queue_family_index,
+            performance_counter_indices,
             _ne: _,
         } = create_info;
 
@@ -129,6 +265,8 @@ impl QueryPool {
             id: Self::next_id(),
             query_type,
             query_count,
+            performance_queue_family_index,
+            performance_counter_indices,
         })
     }
 
@@ -144,6 +282,19 @@ impl QueryPool {
         self.query_count
     }
 
+    /// For performance query pools, returns the queue family that the counters are for.
+    #[inline]
+    pub fn performance_queue_family_index(&self) -> u32 {
+        self.performance_queue_family_index
+    }
+
+    /// For performance query pools, returns the indices of the counters that are read by each
+    /// query.
+    #[inline]
+    pub fn performance_counter_indices(&self) -> &[u32] {
+        &self.performance_counter_indices
+    }
+
     /// Returns a reference to a single query slot, or `None` if the index is out of range.
     #[inline]
     pub fn query(&self, index: u32) -> Option<Query<'_>> {
@@ -212,6 +363,27 @@ pub struct QueryPoolCreateInfo {
     /// The default value is `0`, which must be overridden.
     pub query_count: u32,
 
+    /// If `query_type` is [`QueryType::Performance`], the queue family that the performance
+    /// counters are for. Queries of the pool can only be used in command buffers for this queue
+    /// family.
+    ///
+    /// This is ignored for other query types.
+    ///
+    /// The default value is `0`.
+    pub performance_queue_family_index: u32,
+
+    /// If `query_type` is [`QueryType::Performance`], the performance counters to read, as
+    /// indices into the list returned by
+    /// [`PhysicalDevice::queue_family_performance_counters`] for
+    /// `performance_queue_family_index`.
+    ///
+    /// This must not be empty for performance queries, and is ignored for other query types.
+    ///
+    /// The default value is empty.
+    ///
+    /// [`PhysicalDevice::queue_family_performance_counters`]: crate::device::physical::PhysicalDevice::queue_family_performance_counters
+    pub performance_counter_indices: Vec<u32>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -222,6 +394,8 @@ impl QueryPoolCreateInfo {
         Self {
             query_type,
             query_count: 0,
+            performance_queue_family_index: 0,
+            performance_counter_indices: Vec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -232,8 +406,27 @@ impl QueryPoolCreateInfo {
 pub enum QueryPoolCreationError {
     /// Not enough memory.
     OomError(OomError),
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
     /// A pipeline statistics pool was requested but the corresponding feature wasn't enabled.
     PipelineStatisticsQueryFeatureNotEnabled,
+    /// An acceleration structure compacted size pool was requested but the corresponding
+    /// extension wasn't enabled.
+    AccelerationStructureExtensionNotEnabled,
+    /// A performance counter index was not less than the number of performance counters of the
+    /// queue family.
+    PerformanceCounterIndexOutOfRange {
+        counter_index: u32,
+        counter_count: u32,
+    },
+    /// The performance queue family index was not less than the number of queue families in the
+    /// physical device.
+    QueueFamilyIndexOutOfRange {
+        queue_family_index: u32,
+        queue_family_count: u32,
+    },
 }
 
 impl Error for QueryPoolCreationError {
@@ -247,17 +440,45 @@ impl Error for QueryPoolCreationError {
 
 impl Display for QueryPoolCreationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
-        write!(
-            f,
-            "{}",
-            match self {
-                QueryPoolCreationError::OomError(_) => "not enough memory available",
-                QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled => {
-                    "a pipeline statistics pool was requested but the corresponding feature \
-                    wasn't enabled"
-                }
-            }
-        )
+        match self {
+            QueryPoolCreationError::OomError(_) => write!(f, "not enough memory available"),
+            QueryPoolCreationError::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            QueryPoolCreationError::PipelineStatisticsQueryFeatureNotEnabled => write!(
+                f,
+                "a pipeline statistics pool was requested but the corresponding feature wasn't \
+                enabled",
+            ),
+            QueryPoolCreationError::AccelerationStructureExtensionNotEnabled => write!(
+                f,
+                "an acceleration structure compacted size pool was requested but the \
+                corresponding extension wasn't enabled",
+            ),
+            QueryPoolCreationError::PerformanceCounterIndexOutOfRange {
+                counter_index,
+                counter_count,
+            } => write!(
+                f,
+                "the performance counter index {} was not less than the number of performance \
+                counters of the queue family ({})",
+                counter_index, counter_count,
+            ),
+            QueryPoolCreationError::QueueFamilyIndexOutOfRange {
+                queue_family_index,
+                queue_family_count,
+            } => write!(
+                f,
+                "the performance queue family index ({}) was not less than the number of queue \
+                families in the physical device ({})",
+                queue_family_index, queue_family_count,
+            ),
+        }
     }
 }
 
@@ -281,6 +502,15 @@ impl From<VulkanError> for QueryPoolCreationError {
     }
 }
 
+impl From<RequirementNotMet> for QueryPoolCreationError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 /// A reference to a single query slot.
 ///
 /// This is created through [`QueryPool::query`].
@@ -376,6 +606,219 @@ impl<'a> QueriesRange<'a> {
         }
     }
 
+    /// Copies the results of this range of pipeline statistics queries to `destination`, as
+    /// [`PipelineStatistics`] values.
+    ///
+    /// One element is written for each query in the range, so `destination` must be at least as
+    /// long as the range. As with [`get_results`](Self::get_results), `true` is returned if every
+    /// result was available, and results that are not yet available are not written, unless
+    /// [`PARTIAL`] is enabled.
+    ///
+    /// [`PARTIAL`]: QueryResultFlags::PARTIAL
+    pub fn get_pipeline_statistics(
+        &self,
+        destination: &mut [PipelineStatistics],
+        flags: QueryResultFlags,
+    ) -> Result<bool, GetResultsError> {
+        let statistic_flags = match self.pool.query_type {
+            QueryType::PipelineStatistics(statistic_flags) => statistic_flags,
+            _ => return Err(GetResultsError::QueryTypeNotSupported),
+        };
+
+        let count = (self.range.end - self.range.start) as usize;
+
+        if destination.len() < count {
+            return Err(GetResultsError::BufferTooSmall {
+                required_len: count as DeviceSize,
+                actual_len: destination.len() as DeviceSize,
+            });
+        }
+
+        // Availability is always retrieved, so that only the available results are written to
+        // `destination`.
+        let per_query_len = statistic_flags.count() as usize + 1;
+        let mut results = vec![0u64; per_query_len * count];
+        let all_available =
+            self.get_results(&mut results, flags | QueryResultFlags::WITH_AVAILABILITY)?;
+
+        for (results, destination) in results.chunks_exact(per_query_len).zip(destination) {
+            let (&availability, results) = results.split_last().unwrap();
+
+            if availability != 0 || flags.intersects(QueryResultFlags::PARTIAL) {
+                *destination = PipelineStatistics::from_results(statistic_flags, results);
+            }
+        }
+
+        Ok(all_available)
+    }
+
+    /// Copies the results of this range of performance queries to `destination`.
+    ///
+    /// For each query in the range, one [`PerformanceCounterResult`] is written for each element
+    /// of [`performance_counter_indices`], in the same order. `destination` must be large enough
+    /// to hold them. As with [`get_results`](Self::get_results), `true` is returned if every
+    /// result was available, and results that are not yet available are not written.
+    ///
+    /// The [`WITH_AVAILABILITY`] and [`PARTIAL`] flags are not allowed for performance queries.
+    ///
+    /// [`performance_counter_indices`]: QueryPool::performance_counter_indices
+    /// [`WITH_AVAILABILITY`]: QueryResultFlags::WITH_AVAILABILITY
+    /// [`PARTIAL`]: QueryResultFlags::PARTIAL
+    pub fn get_performance_results(
+        &self,
+        destination: &mut [PerformanceCounterResult],
+        flags: QueryResultFlags,
+    ) -> Result<bool, GetResultsError> {
+        // VUID-vkGetQueryPoolResults-flags-parameter
+        flags.validate_device(&self.pool.device)?;
+
+        if !matches!(self.pool.query_type, QueryType::Performance) {
+            return Err(GetResultsError::QueryTypeNotSupported);
+        }
+
+        // VUID-vkGetQueryPoolResults-queryType-03230
+        if flags.intersects(QueryResultFlags::WITH_AVAILABILITY | QueryResultFlags::PARTIAL) {
+            return Err(GetResultsError::InvalidFlags);
+        }
+
+        let count = self.range.end - self.range.start;
+        let per_query_len = self.pool.performance_counter_indices.len();
+        let required_len = per_query_len * count as usize;
+
+        // VUID-vkGetQueryPoolResults-dataSize-00817
+        if destination.len() < required_len {
+            return Err(GetResultsError::BufferTooSmall {
+                required_len: required_len as DeviceSize,
+                actual_len: destination.len() as DeviceSize,
+            });
+        }
+
+        let counters = unsafe {
+            self.pool
+                .device
+                .physical_device()
+                .queue_family_performance_counters_unchecked(
+                    self.pool.performance_queue_family_index,
+                )?
+        };
+
+        let mut results_vk = vec![ash::vk::PerformanceCounterResultKHR::default(); required_len];
+
+        let result = unsafe {
+            let fns = self.pool.device.fns();
+            (fns.v1_0.get_query_pool_results)(
+                self.pool.device.handle(),
+                self.pool.handle(),
+                self.range.start,
+                count,
+                size_of_val(results_vk.as_slice()),
+                results_vk.as_mut_ptr() as *mut c_void,
+                (per_query_len * size_of::<ash::vk::PerformanceCounterResultKHR>()) as DeviceSize,
+                flags.into(),
+            )
+        };
+
+        let all_available = match result {
+            ash::vk::Result::SUCCESS => true,
+            ash::vk::Result::NOT_READY => false,
+            err => return Err(VulkanError::from(err).into()),
+        };
+
+        // Without availability, it's not known which results were written, so they are only
+        // copied if they all are.
+        if all_available {
+            for (results_vk, destination) in results_vk
+                .chunks_exact(per_query_len)
+                .zip(destination.chunks_exact_mut(per_query_len))
+            {
+                for ((&result_vk, &counter_index), destination) in results_vk
+                    .iter()
+                    .zip(&self.pool.performance_counter_indices)
+                    .zip(destination)
+                {
+                    *destination = unsafe {
+                        PerformanceCounterResult::from_vulkan(
+                            counters[counter_index as usize].storage,
+                            result_vk,
+                        )
+                    };
+                }
+            }
+        }
+
+        Ok(all_available)
+    }
+
+    /// Resets this range of queries from the host, making them unavailable so that they can be
+    /// used again.
+    ///
+    /// This is an alternative to recording [`reset_query_pool`] in a command buffer.
+    ///
+    /// The [`host_query_reset`] feature must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - Submitted commands that use any of the queries in this range must have completed
+    ///   execution.
+    /// - The queries must not be concurrently used by [`get_results`](Self::get_results) or
+    ///   `reset` in another thread.
+    ///
+    /// [`reset_query_pool`]: crate::command_buffer::AutoCommandBufferBuilder::reset_query_pool
+    /// [`host_query_reset`]: crate::device::Features::host_query_reset
+    #[inline]
+    pub unsafe fn reset(&self) -> Result<(), QueryResetError> {
+        self.validate_reset()?;
+
+        self.reset_unchecked();
+        Ok(())
+    }
+
+    fn validate_reset(&self) -> Result<(), QueryResetError> {
+        // VUID-vkResetQueryPool-None-02665
+        if !self.pool.device.enabled_features().host_query_reset {
+            return Err(QueryResetError::RequirementNotMet {
+                required_for: "`QueriesRange::reset`",
+                requires_one_of: RequiresOneOf {
+                    features: &["host_query_reset"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-vkResetQueryPool-firstQuery-02666
+        // VUID-vkResetQueryPool-firstQuery-02667
+        // Ensured by `QueryPool::queries_range`.
+
+        // VUID-vkResetQueryPool-firstQuery-02741
+        // VUID-vkResetQueryPool-firstQuery-02742
+        // Ensured by the safety requirements.
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn reset_unchecked(&self) {
+        let fns = self.pool.device.fns();
+
+        if self.pool.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.reset_query_pool)(
+                self.pool.device.handle(),
+                self.pool.handle(),
+                self.range.start,
+                self.range.end - self.range.start,
+            );
+        } else {
+            debug_assert!(self.pool.device.enabled_extensions().ext_host_query_reset);
+            (fns.ext_host_query_reset.reset_query_pool_ext)(
+                self.pool.device.handle(),
+                self.pool.handle(),
+                self.range.start,
+                self.range.end - self.range.start,
+            );
+        }
+    }
+
     pub(crate) fn check_query_pool_results<T>(
         &self,
         buffer_start: DeviceSize,
@@ -389,6 +832,11 @@ impl<'a> QueriesRange<'a> {
         // VUID-vkCmdCopyQueryPoolResults-flags-parameter
         flags.validate_device(&self.pool.device)?;
 
+        // Performance query results are not made of `QueryResultElement`s.
+        if let QueryType::Performance = self.pool.query_type {
+            return Err(GetResultsError::QueryTypeNotSupported);
+        }
+
         assert!(buffer_len > 0);
 
         // VUID-vkGetQueryPoolResults-flags-02828
@@ -411,6 +859,10 @@ impl<'a> QueriesRange<'a> {
         match self.pool.query_type {
             QueryType::Occlusion => (),
             QueryType::PipelineStatistics(_) => (),
+            QueryType::AccelerationStructureCompactedSize => (),
+            QueryType::TransformFeedbackStream => (),
+            QueryType::PrimitivesGenerated => (),
+            QueryType::Performance => unreachable!(),
             QueryType::Timestamp => {
                 // VUID-vkGetQueryPoolResults-queryType-00818
                 if flags.intersects(QueryResultFlags::PARTIAL) {
@@ -447,6 +899,9 @@ pub enum GetResultsError {
 
     /// The provided flags are not allowed for this type of query.
     InvalidFlags,
+
+    /// The type of the query pool is not supported by the operation.
+    QueryTypeNotSupported,
 }
 
 impl Error for GetResultsError {
@@ -476,6 +931,10 @@ impl Display for GetResultsError {
                 f,
                 "the provided flags are not allowed for this type of query"
             ),
+            Self::QueryTypeNotSupported => write!(
+                f,
+                "the type of the query pool is not supported by the operation",
+            ),
         }
     }
 }
@@ -507,6 +966,41 @@ impl From<RequirementNotMet> for GetResultsError {
     }
 }
 
+/// Error that can happen when calling [`QueriesRange::reset`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryResetError {
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for QueryResetError {}
+
+impl Display for QueryResetError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for QueryResetError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 /// A trait for elements of buffers that can be used as a destination for query results.
 ///
 /// # Safety
@@ -532,15 +1026,68 @@ pub enum QueryType {
     /// Tracks statistics on pipeline invocations and their input data.
     PipelineStatistics(QueryPipelineStatisticFlags),
     /// Writes timestamps at chosen points in a command buffer.
+    ///
+    /// The timestamps are in the [`TimeDomain::Device`] time domain. They can be correlated with
+    /// the clocks of the host with [`Device::calibrate_timestamps`].
+    ///
+    /// [`TimeDomain::Device`]: crate::device::physical::TimeDomain::Device
+    /// [`Device::calibrate_timestamps`]: crate::device::Device::calibrate_timestamps
     Timestamp,
+    /// Receives the size that an acceleration structure would have after compaction, written
+    /// with [`write_acceleration_structures_properties`].
+    ///
+    /// The [`khr_acceleration_structure`] extension must be enabled on the device.
+    ///
+    /// [`write_acceleration_structures_properties`]: crate::command_buffer::AutoCommandBufferBuilder::write_acceleration_structures_properties
+    /// [`khr_acceleration_structure`]: crate::device::DeviceExtensions::khr_acceleration_structure
+    AccelerationStructureCompactedSize,
+    /// Tracks the number of primitives that were written to the transform feedback buffers, and
+    /// the number of primitives that would have been written if the buffers were large enough.
+    ///
+    /// The query measures vertex stream 0. Transform feedback must be active for the primitives
+    /// to be counted, see [`begin_transform_feedback`].
+    ///
+    /// The [`ext_transform_feedback`] extension must be enabled on the device, and the
+    /// [`transform_feedback_queries`] device property must be `true` to begin the query.
+    ///
+    /// [`begin_transform_feedback`]: crate::command_buffer::AutoCommandBufferBuilder::begin_transform_feedback
+    /// [`ext_transform_feedback`]: crate::device::DeviceExtensions::ext_transform_feedback
+    /// [`transform_feedback_queries`]: crate::device::Properties::transform_feedback_queries
+    TransformFeedbackStream,
+    /// Tracks the number of primitives that were generated by the last vertex processing shader
+    /// stage, whether or not transform feedback is active.
+    ///
+    /// The query measures vertex stream 0.
+    ///
+    /// The [`ext_primitives_generated_query`] extension must be enabled on the device, and the
+    /// [`primitives_generated_query`] feature must be enabled to begin the query.
+    ///
+    /// [`ext_primitives_generated_query`]: crate::device::DeviceExtensions::ext_primitives_generated_query
+    /// [`primitives_generated_query`]: crate::device::Features::primitives_generated_query
+    PrimitivesGenerated,
+    /// Reads hardware performance counters. The counters are chosen with
+    /// [`QueryPoolCreateInfo::performance_counter_indices`].
+    ///
+    /// The [`khr_performance_query`] extension must be enabled on the device, and the
+    /// [`performance_counter_query_pools`] feature must be enabled.
+    ///
+    /// [`khr_performance_query`]: crate::device::DeviceExtensions::khr_performance_query
+    /// [`performance_counter_query_pools`]: crate::device::Features::performance_counter_query_pools
+    Performance,
 }
 
 impl QueryType {
     /// Returns the number of [`QueryResultElement`]s that are needed to hold the result of a
     /// single query of this type.
     ///
-    /// - For [`Occlusion`] and [`Timestamp`] queries, this returns 1.
+    /// - For [`Occlusion`], [`Timestamp`], [`AccelerationStructureCompactedSize`] and
+    ///   [`PrimitivesGenerated`] queries, this returns 1.
     /// - For [`PipelineStatistics`] queries, this returns the number of statistics flags enabled.
+    /// - For [`TransformFeedbackStream`] queries, this returns 2: the number of primitives
+    ///   written, followed by the number of primitives needed.
+    /// - For [`Performance`] queries, this returns 0, as their results are not made of
+    ///   `QueryResultElement`s. They are retrieved with [`QueriesRange::get_performance_results`]
+    ///   instead.
     ///
     /// If the results are retrieved with [`WITH_AVAILABILITY`] enabled, then an additional element
     /// is required per query.
@@ -548,12 +1095,21 @@ impl QueryType {
     /// [`Occlusion`]: QueryType::Occlusion
     /// [`Timestamp`]: QueryType::Timestamp
     /// [`PipelineStatistics`]: QueryType::PipelineStatistics
+    /// [`AccelerationStructureCompactedSize`]: QueryType::AccelerationStructureCompactedSize
+    /// [`PrimitivesGenerated`]: QueryType::PrimitivesGenerated
+    /// [`TransformFeedbackStream`]: QueryType::TransformFeedbackStream
+    /// [`Performance`]: QueryType::Performance
     /// [`WITH_AVAILABILITY`]: QueryResultFlags::WITH_AVAILABILITY
     #[inline]
     pub const fn result_len(self) -> DeviceSize {
         match self {
-            Self::Occlusion | Self::Timestamp => 1,
+            Self::Occlusion
+            | Self::Timestamp
+            | Self::AccelerationStructureCompactedSize
+            | Self::PrimitivesGenerated => 1,
             Self::PipelineStatistics(flags) => flags.count() as DeviceSize,
+            Self::TransformFeedbackStream => 2,
+            Self::Performance => 0,
         }
     }
 }
@@ -565,6 +1121,12 @@ impl From<QueryType> for ash::vk::QueryType {
             QueryType::Occlusion => ash::vk::QueryType::OCCLUSION,
             QueryType::PipelineStatistics(_) => ash::vk::QueryType::PIPELINE_STATISTICS,
             QueryType::Timestamp => ash::vk::QueryType::TIMESTAMP,
+            QueryType::AccelerationStructureCompactedSize => {
+                ash::vk::QueryType::ACCELERATION_STRUCTURE_COMPACTED_SIZE_KHR
+            }
+            QueryType::TransformFeedbackStream => ash::vk::QueryType::TRANSFORM_FEEDBACK_STREAM_EXT,
+            QueryType::PrimitivesGenerated => ash::vk::QueryType::PRIMITIVES_GENERATED_EXT,
+            QueryType::Performance => ash::vk::QueryType::PERFORMANCE_QUERY_KHR,
         }
     }
 }
@@ -657,6 +1219,282 @@ vulkan_bitflags! {
     },*/
 }
 
+/// The results of a single pipeline statistics query.
+///
+/// Each statistic is `Some` if it was gathered by the query pool, and `None` otherwise.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PipelineStatistics {
+    /// The number of vertices processed by the input assembly.
+    pub input_assembly_vertices: Option<u64>,
+
+    /// The number of primitives processed by the input assembly.
+    pub input_assembly_primitives: Option<u64>,
+
+    /// The number of times a vertex shader was invoked.
+    pub vertex_shader_invocations: Option<u64>,
+
+    /// The number of times a geometry shader was invoked.
+    pub geometry_shader_invocations: Option<u64>,
+
+    /// The number of primitives generated by geometry shaders.
+    pub geometry_shader_primitives: Option<u64>,
+
+    /// The number of times the clipping stage was invoked on a primitive.
+    pub clipping_invocations: Option<u64>,
+
+    /// The number of primitives that were output by the clipping stage.
+    pub clipping_primitives: Option<u64>,
+
+    /// The number of times a fragment shader was invoked.
+    pub fragment_shader_invocations: Option<u64>,
+
+    /// The number of patches processed by a tessellation control shader.
+    pub tessellation_control_shader_patches: Option<u64>,
+
+    /// The number of times a tessellation evaluation shader was invoked.
+    pub tessellation_evaluation_shader_invocations: Option<u64>,
+
+    /// The number of times a compute shader was invoked.
+    pub compute_shader_invocations: Option<u64>,
+}
+
+impl PipelineStatistics {
+    /// Returns the `PipelineStatistics` of a single query, given the query's raw `results` as
+    /// written by [`QueriesRange::get_results`] or
+    /// [`copy_query_pool_results`], and the `statistic_flags` of the query pool.
+    ///
+    /// `results` must not include the availability element.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the length of `results` is not equal to the number of flags in
+    ///   `statistic_flags`.
+    ///
+    /// [`copy_query_pool_results`]: crate::command_buffer::AutoCommandBufferBuilder::copy_query_pool_results
+    pub fn from_results<T>(statistic_flags: QueryPipelineStatisticFlags, results: &[T]) -> Self
+    where
+        T: QueryResultElement + Copy + Into<u64>,
+    {
+        assert_eq!(results.len(), statistic_flags.count() as usize);
+
+        // The results are written in the order of the bits of the flags.
+        let mut results = results.iter().map(|&result| result.into());
+        let mut next = |flag: QueryPipelineStatisticFlags| {
+            statistic_flags
+                .intersects(flag)
+                .then(|| results.next().unwrap())
+        };
+
+        Self {
+            input_assembly_vertices: next(QueryPipelineStatisticFlags::INPUT_ASSEMBLY_VERTICES),
+            input_assembly_primitives: next(QueryPipelineStatisticFlags::INPUT_ASSEMBLY_PRIMITIVES),
+            vertex_shader_invocations: next(QueryPipelineStatisticFlags::VERTEX_SHADER_INVOCATIONS),
+            geometry_shader_invocations: next(
+                QueryPipelineStatisticFlags::GEOMETRY_SHADER_INVOCATIONS,
+            ),
+            geometry_shader_primitives: next(
+                QueryPipelineStatisticFlags::GEOMETRY_SHADER_PRIMITIVES,
+            ),
+            clipping_invocations: next(QueryPipelineStatisticFlags::CLIPPING_INVOCATIONS),
+            clipping_primitives: next(QueryPipelineStatisticFlags::CLIPPING_PRIMITIVES),
+            fragment_shader_invocations: next(
+                QueryPipelineStatisticFlags::FRAGMENT_SHADER_INVOCATIONS,
+            ),
+            tessellation_control_shader_patches: next(
+                QueryPipelineStatisticFlags::TESSELLATION_CONTROL_SHADER_PATCHES,
+            ),
+            tessellation_evaluation_shader_invocations: next(
+                QueryPipelineStatisticFlags::TESSELLATION_EVALUATION_SHADER_INVOCATIONS,
+            ),
+            compute_shader_invocations: next(
+                QueryPipelineStatisticFlags::COMPUTE_SHADER_INVOCATIONS,
+            ),
+        }
+    }
+}
+
+/// A hardware performance counter that can be read by a performance query, as returned by
+/// [`PhysicalDevice::queue_family_performance_counters`].
+///
+/// [`PhysicalDevice::queue_family_performance_counters`]: crate::device::physical::PhysicalDevice::queue_family_performance_counters
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PerformanceCounter {
+    /// The unit that the value of the counter is in.
+    pub unit: PerformanceCounterUnit,
+
+    /// The scope that the counter is measured over.
+    pub scope: PerformanceCounterScope,
+
+    /// The type that the value of the counter is stored as.
+    pub storage: PerformanceCounterStorage,
+
+    /// An identifier of the counter, that stays the same across driver versions and processes.
+    pub uuid: [u8; 16],
+
+    /// Additional properties of the counter.
+    pub flags: PerformanceCounterDescriptionFlags,
+
+    /// The name of the counter.
+    pub name: String,
+
+    /// The category of the counter.
+    pub category: String,
+
+    /// A description of the counter.
+    pub description: String,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The unit that the value of a performance counter is in.
+    PerformanceCounterUnit = PerformanceCounterUnitKHR(i32);
+
+    /// A generic count, without a unit.
+    Generic = GENERIC,
+
+    /// A percentage.
+    Percentage = PERCENTAGE,
+
+    /// Nanoseconds.
+    Nanoseconds = NANOSECONDS,
+
+    /// Bytes.
+    Bytes = BYTES,
+
+    /// Bytes per second.
+    BytesPerSecond = BYTES_PER_SECOND,
+
+    /// Kelvin.
+    Kelvin = KELVIN,
+
+    /// Watts.
+    Watts = WATTS,
+
+    /// Volts.
+    Volts = VOLTS,
+
+    /// Amperes.
+    Amps = AMPS,
+
+    /// Hertz.
+    Hertz = HERTZ,
+
+    /// Clock cycles.
+    Cycles = CYCLES,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The scope that a performance counter is measured over.
+    PerformanceCounterScope = PerformanceCounterScopeKHR(i32);
+
+    /// The counter is measured over a whole command buffer. The query must be the first command
+    /// recorded in the command buffer, and must be ended as the last command.
+    CommandBuffer = COMMAND_BUFFER,
+
+    /// The counter is measured over a whole render pass. The query must begin and end outside of
+    /// a render pass.
+    RenderPass = RENDER_PASS,
+
+    /// The counter is measured over any sequence of commands.
+    Command = COMMAND,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type that the value of a performance counter is stored as.
+    PerformanceCounterStorage = PerformanceCounterStorageKHR(i32);
+
+    /// A 32-bit signed integer.
+    Int32 = INT32,
+
+    /// A 64-bit signed integer.
+    Int64 = INT64,
+
+    /// A 32-bit unsigned integer.
+    Uint32 = UINT32,
+
+    /// A 64-bit unsigned integer.
+    Uint64 = UINT64,
+
+    /// A 32-bit floating point number.
+    Float32 = FLOAT32,
+
+    /// A 64-bit floating point number.
+    Float64 = FLOAT64,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Additional properties of a performance counter.
+    PerformanceCounterDescriptionFlags = PerformanceCounterDescriptionFlagsKHR(u32);
+
+    /// Reading the counter has a performance impact on the commands that are measured.
+    PERFORMANCE_IMPACTING = PERFORMANCE_IMPACTING,
+
+    /// Reading the counter in multiple queries at the same time affects the accuracy of the
+    /// results.
+    CONCURRENTLY_IMPACTED = CONCURRENTLY_IMPACTED,
+}
+
+/// The value of a performance counter, as read by a performance query.
+///
+/// The variant matches the [`storage`](PerformanceCounter::storage) of the counter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PerformanceCounterResult {
+    Int32(i32),
+    Int64(i64),
+    Uint32(u32),
+    Uint64(u64),
+    Float32(f32),
+    Float64(f64),
+}
+
+impl PerformanceCounterResult {
+    /// Returns the value as an `f64`, converting it if necessary.
+    #[inline]
+    pub fn as_f64(self) -> f64 {
+        match self {
+            Self::Int32(value) => value as f64,
+            Self::Int64(value) => value as f64,
+            Self::Uint32(value) => value as f64,
+            Self::Uint64(value) => value as f64,
+            Self::Float32(value) => value as f64,
+            Self::Float64(value) => value,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// - `result_vk` must have been written for a counter with the given `storage`.
+    unsafe fn from_vulkan(
+        storage: PerformanceCounterStorage,
+        result_vk: ash::vk::PerformanceCounterResultKHR,
+    ) -> Self {
+        match storage {
+            PerformanceCounterStorage::Int32 => Self::Int32(result_vk.int32),
+            PerformanceCounterStorage::Int64 => Self::Int64(result_vk.int64),
+            PerformanceCounterStorage::Uint32 => Self::Uint32(result_vk.uint32),
+            PerformanceCounterStorage::Uint64 => Self::Uint64(result_vk.uint64),
+            PerformanceCounterStorage::Float32 => Self::Float32(result_vk.float32),
+            PerformanceCounterStorage::Float64 => Self::Float64(result_vk.float64),
+        }
+    }
+}
+
+impl Default for PerformanceCounterResult {
+    #[inline]
+    fn default() -> Self {
+        Self::Uint64(0)
+    }
+}
+
 vulkan_bitflags! {
     #[non_exhaustive]
 
@@ -689,7 +1527,10 @@ vulkan_bitflags! {
 #[cfg(test)]
 mod tests {
     use super::QueryPoolCreateInfo;
-    use crate::query::{QueryPipelineStatisticFlags, QueryPool, QueryPoolCreationError, QueryType};
+    use crate::query::{
+        PipelineStatistics, QueryPipelineStatisticFlags, QueryPool, QueryPoolCreationError,
+        QueryType,
+    };
 
     #[test]
     fn pipeline_statistics_feature() {
@@ -706,4 +1547,54 @@ mod tests {
             _ => panic!(),
         };
     }
+
+    #[test]
+    fn pipeline_statistics_from_results() {
+        let statistic_flags = QueryPipelineStatisticFlags::VERTEX_SHADER_INVOCATIONS
+            | QueryPipelineStatisticFlags::CLIPPING_PRIMITIVES
+            | QueryPipelineStatisticFlags::COMPUTE_SHADER_INVOCATIONS;
+        let statistics = PipelineStatistics::from_results(statistic_flags, &[3u32, 5, 7]);
+
+        assert_eq!(
+            statistics,
+            PipelineStatistics {
+                vertex_shader_invocations: Some(3),
+                clipping_primitives: Some(5),
+                compute_shader_invocations: Some(7),
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn acceleration_structure_compacted_size_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        let query_type = QueryType::AccelerationStructureCompactedSize;
+        match QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 16,
+                ..QueryPoolCreateInfo::query_type(query_type)
+            },
+        ) {
+            Err(QueryPoolCreationError::AccelerationStructureExtensionNotEnabled) => (),
+            _ => panic!(),
+        };
+    }
+
+    #[test]
+    fn performance_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        match QueryPool::new(
+            device,
+            QueryPoolCreateInfo {
+                query_count: 1,
+                performance_counter_indices: vec![0],
+                ..QueryPoolCreateInfo::query_type(QueryType::Performance)
+            },
+        ) {
+            Err(QueryPoolCreationError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        };
+    }
 }
@@ -8,8 +8,8 @@
 // according to those terms.
 
 use super::{
-    AttachmentDescription, AttachmentReference, LoadOp, RenderPass, RenderPassCreateInfo,
-    SubpassDependency, SubpassDescription,
+    AttachmentDescription, AttachmentReference, FragmentShadingRateAttachmentInfo, LoadOp,
+    RenderPass, RenderPassCreateInfo, ResolveMode, StoreOp, SubpassDependency, SubpassDescription,
 };
 use crate::{
     device::Device,
@@ -21,6 +21,7 @@ use crate::{
 use smallvec::SmallVec;
 use std::{
     error::Error,
+    ffi::c_void,
     fmt::{Display, Error as FmtError, Formatter},
     mem::MaybeUninit,
     ptr,
@@ -38,6 +39,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
             _ne: _,
         } = create_info;
 
@@ -164,7 +166,11 @@ impl RenderPass {
                 ref color_attachments,
                 ref resolve_attachments,
                 ref depth_stencil_attachment,
+                ref depth_stencil_resolve_attachment,
+                depth_resolve_mode,
+                stencil_resolve_mode,
                 ref preserve_attachments,
+                ref fragment_shading_rate_attachment,
                 _ne: _,
             } = subpass;
             let subpass_num = subpass_num as u32;
@@ -596,6 +602,293 @@ impl RenderPass {
                 }
             }
 
+            /*
+                Check depth/stencil resolve attachment
+            */
+
+            if let Some(atch_ref) = depth_stencil_resolve_attachment.as_ref() {
+                if !(device.api_version() >= Version::V1_2
+                    || device.enabled_extensions().khr_depth_stencil_resolve)
+                {
+                    return Err(RenderPassCreationError::RequirementNotMet {
+                        required_for: "`create_info.subpasses` has an element where \
+                            `depth_stencil_resolve_attachment` is `Some`",
+                        requires_one_of: RequiresOneOf {
+                            api_version: Some(Version::V1_2),
+                            device_extensions: &["khr_depth_stencil_resolve"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                let (atch, features, _first_use) = check_attachment(atch_ref)?;
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-02651
+                if !features.intersects(FormatFeatures::DEPTH_STENCIL_ATTACHMENT) {
+                    return Err(
+                        RenderPassCreationError::SubpassAttachmentFormatUsageNotSupported {
+                            subpass: subpass_num,
+                            attachment: atch_ref.attachment,
+                            usage: "depth/stencil resolve",
+                        },
+                    );
+                }
+
+                // VUID-VkAttachmentReference2-layout-03077
+                if matches!(
+                    atch_ref.layout,
+                    ImageLayout::Undefined
+                        | ImageLayout::Preinitialized
+                        | ImageLayout::PresentSrc
+                        | ImageLayout::ColorAttachmentOptimal
+                        | ImageLayout::ShaderReadOnlyOptimal
+                        | ImageLayout::DepthStencilReadOnlyOptimal
+                ) {
+                    return Err(RenderPassCreationError::SubpassAttachmentLayoutInvalid {
+                        subpass: subpass_num,
+                        attachment: atch_ref.attachment,
+                        usage: "depth/stencil resolve",
+                    });
+                }
+
+                // Not required by spec, but enforced by Vulkano for sanity.
+                if !atch_ref.aspects.is_empty() {
+                    return Err(RenderPassCreationError::SubpassAttachmentAspectsNotEmpty {
+                        subpass: subpass_num,
+                        attachment: atch_ref.attachment,
+                    });
+                }
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03177
+                let ds_atch_ref = depth_stencil_attachment.as_ref().ok_or(
+                    RenderPassCreationError::SubpassDepthStencilResolveAttachmentWithoutDepthStencilAttachment {
+                        subpass: subpass_num,
+                    },
+                )?;
+                let ds_atch = &attachments[ds_atch_ref.attachment as usize];
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03178
+                if depth_resolve_mode.is_none() && stencil_resolve_mode.is_none() {
+                    return Err(
+                        RenderPassCreationError::SubpassDepthStencilResolveModesNone {
+                            subpass: subpass_num,
+                        },
+                    );
+                }
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03179
+                if ds_atch.samples == SampleCount::Sample1 {
+                    return Err(
+                        RenderPassCreationError::SubpassDepthStencilAttachmentWithResolveNotMultisampled {
+                            subpass: subpass_num,
+                            attachment: ds_atch_ref.attachment,
+                        },
+                    );
+                }
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03180
+                if atch.samples != SampleCount::Sample1 {
+                    return Err(
+                        RenderPassCreationError::SubpassDepthStencilResolveAttachmentMultisampled {
+                            subpass: subpass_num,
+                            attachment: atch_ref.attachment,
+                        },
+                    );
+                }
+
+                let format = atch.format.unwrap();
+                let ds_format = ds_atch.format.unwrap();
+
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03181
+                // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03182
+                if format.type_depth().is_some()
+                    && (format.type_depth() != ds_format.type_depth()
+                        || format.components()[0] != ds_format.components()[0])
+                    || format.type_stencil().is_some()
+                        && (format.type_stencil() != ds_format.type_stencil()
+                            || format.components()[1] != ds_format.components()[1])
+                {
+                    return Err(
+                        RenderPassCreationError::SubpassDepthStencilResolveAttachmentFormatMismatch {
+                            subpass: subpass_num,
+                            resolve_attachment: atch_ref.attachment,
+                            depth_stencil_attachment: ds_atch_ref.attachment,
+                        },
+                    );
+                }
+
+                if let Some(depth_resolve_mode) = depth_resolve_mode {
+                    // VUID-VkSubpassDescriptionDepthStencilResolve-depthResolveMode-parameter
+                    depth_resolve_mode.validate_device(device)?;
+
+                    // VUID-VkSubpassDescriptionDepthStencilResolve-depthResolveMode-03183
+                    if !properties
+                        .supported_depth_resolve_modes
+                        .map_or(false, |modes| modes.contains_enum(depth_resolve_mode))
+                    {
+                        return Err(
+                            RenderPassCreationError::SubpassDepthResolveModeNotSupported {
+                                subpass: subpass_num,
+                                depth_resolve_mode,
+                            },
+                        );
+                    }
+                }
+
+                if let Some(stencil_resolve_mode) = stencil_resolve_mode {
+                    // VUID-VkSubpassDescriptionDepthStencilResolve-stencilResolveMode-parameter
+                    stencil_resolve_mode.validate_device(device)?;
+
+                    // VUID-VkSubpassDescriptionDepthStencilResolve-stencilResolveMode-03184
+                    if !properties
+                        .supported_stencil_resolve_modes
+                        .map_or(false, |modes| modes.contains_enum(stencil_resolve_mode))
+                    {
+                        return Err(
+                            RenderPassCreationError::SubpassStencilResolveModeNotSupported {
+                                subpass: subpass_num,
+                                stencil_resolve_mode,
+                            },
+                        );
+                    }
+                }
+
+                if format.type_depth().is_some() && format.type_stencil().is_some() {
+                    match (depth_resolve_mode, stencil_resolve_mode) {
+                        (None, None) => (),
+                        (None, Some(_)) | (Some(_), None) => {
+                            // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03185
+                            // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03186
+                            if !properties.independent_resolve_none.unwrap_or(false) {
+                                return Err(
+                                    RenderPassCreationError::SubpassDepthStencilResolveModesNotSupported {
+                                        subpass: subpass_num,
+                                    },
+                                );
+                            }
+                        }
+                        (Some(depth_resolve_mode), Some(stencil_resolve_mode)) => {
+                            // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03185
+                            // VUID-VkSubpassDescriptionDepthStencilResolve-pDepthStencilResolveAttachment-03186
+                            if !properties.independent_resolve.unwrap_or(false)
+                                && depth_resolve_mode != stencil_resolve_mode
+                            {
+                                return Err(
+                                    RenderPassCreationError::SubpassDepthStencilResolveModesNotSupported {
+                                        subpass: subpass_num,
+                                    },
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+
+            /*
+                Check fragment shading rate attachment
+            */
+
+            if let Some(info) = fragment_shading_rate_attachment.as_ref() {
+                let FragmentShadingRateAttachmentInfo {
+                    attachment: ref atch_ref,
+                    texel_size,
+                } = *info;
+
+                if !device.enabled_features().attachment_fragment_shading_rate {
+                    return Err(RenderPassCreationError::RequirementNotMet {
+                        required_for: "`create_info.subpasses` has an element where \
+                            `fragment_shading_rate_attachment` is `Some`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["attachment_fragment_shading_rate"],
+                            ..Default::default()
+                        },
+                    });
+                }
+
+                let (_atch, features, _first_use) = check_attachment(atch_ref)?;
+
+                // VUID-VkRenderPassCreateInfo2-pAttachments-04586
+                if !features.intersects(FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT) {
+                    return Err(
+                        RenderPassCreationError::SubpassAttachmentFormatUsageNotSupported {
+                            subpass: subpass_num,
+                            attachment: atch_ref.attachment,
+                            usage: "fragment shading rate",
+                        },
+                    );
+                }
+
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04524
+                if !matches!(
+                    atch_ref.layout,
+                    ImageLayout::General | ImageLayout::FragmentShadingRateAttachmentOptimal
+                ) {
+                    return Err(RenderPassCreationError::SubpassAttachmentLayoutInvalid {
+                        subpass: subpass_num,
+                        attachment: atch_ref.attachment,
+                        usage: "fragment shading rate",
+                    });
+                }
+
+                // Not required by spec, but enforced by Vulkano for sanity.
+                if !atch_ref.aspects.is_empty() {
+                    return Err(RenderPassCreationError::SubpassAttachmentAspectsNotEmpty {
+                        subpass: subpass_num,
+                        attachment: atch_ref.attachment,
+                    });
+                }
+
+                // VUID-VkSubpassDescription2-pFragmentShadingRateAttachment-04595
+                if (input_attachments.iter())
+                    .chain(color_attachments)
+                    .chain(resolve_attachments)
+                    .chain([depth_stencil_attachment, depth_stencil_resolve_attachment])
+                    .flatten()
+                    .any(|other_atch_ref| other_atch_ref.attachment == atch_ref.attachment)
+                {
+                    return Err(
+                        RenderPassCreationError::SubpassFragmentShadingRateAttachmentUsedElsewhere {
+                            subpass: subpass_num,
+                            attachment: atch_ref.attachment,
+                        },
+                    );
+                }
+
+                let min_texel_size = properties
+                    .min_fragment_shading_rate_attachment_texel_size
+                    .unwrap_or_default();
+                let max_texel_size = properties
+                    .max_fragment_shading_rate_attachment_texel_size
+                    .unwrap_or_default();
+                let max_aspect_ratio = properties
+                    .max_fragment_shading_rate_attachment_texel_size_aspect_ratio
+                    .unwrap_or_default();
+
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04525
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04526
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04527
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04528
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04529
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04530
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04531
+                // VUID-VkFragmentShadingRateAttachmentInfoKHR-pFragmentShadingRateAttachment-04532
+                if !(texel_size.iter())
+                    .zip(min_texel_size.iter().zip(max_texel_size.iter()))
+                    .all(|(&size, (&min, &max))| {
+                        size.is_power_of_two() && size >= min && size <= max
+                    })
+                    || texel_size[0] / texel_size[1] > max_aspect_ratio
+                    || texel_size[1] / texel_size[0] > max_aspect_ratio
+                {
+                    return Err(
+                        RenderPassCreationError::SubpassFragmentShadingRateAttachmentTexelSizeInvalid {
+                            subpass: subpass_num,
+                            texel_size,
+                        },
+                    );
+                }
+            }
+
             /*
                 Check preserve attachments
             */
@@ -1041,6 +1334,101 @@ impl RenderPass {
             })?;
         }
 
+        /*
+            Fragment density map attachment
+        */
+
+        if let Some(atch_ref) = fragment_density_map_attachment {
+            if !device.enabled_features().fragment_density_map {
+                return Err(RenderPassCreationError::RequirementNotMet {
+                    required_for: "`create_info.fragment_density_map_attachment` is `Some`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["fragment_density_map"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkAttachmentReference-layout-parameter
+            atch_ref.layout.validate_device(device)?;
+
+            // VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02547
+            let atch = attachments.get(atch_ref.attachment as usize).ok_or(
+                RenderPassCreationError::FragmentDensityMapAttachmentOutOfRange {
+                    attachment: atch_ref.attachment,
+                },
+            )?;
+
+            // VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02548
+            if let Some(subpass_num) = subpasses.iter().position(|subpass| {
+                (subpass.input_attachments.iter())
+                    .chain(&subpass.color_attachments)
+                    .chain(&subpass.resolve_attachments)
+                    .chain([
+                        &subpass.depth_stencil_attachment,
+                        &subpass.depth_stencil_resolve_attachment,
+                    ])
+                    .flatten()
+                    .chain(
+                        subpass
+                            .fragment_shading_rate_attachment
+                            .as_ref()
+                            .map(|info| &info.attachment),
+                    )
+                    .any(|other_atch_ref| other_atch_ref.attachment == atch_ref.attachment)
+                    || subpass.preserve_attachments.contains(&atch_ref.attachment)
+            }) {
+                return Err(
+                    RenderPassCreationError::FragmentDensityMapAttachmentUsedInSubpass {
+                        attachment: atch_ref.attachment,
+                        subpass: subpass_num as u32,
+                    },
+                );
+            }
+
+            // VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02549
+            if !matches!(
+                atch_ref.layout,
+                ImageLayout::FragmentDensityMapOptimal | ImageLayout::ShaderReadOnlyOptimal
+            ) {
+                return Err(
+                    RenderPassCreationError::FragmentDensityMapAttachmentLayoutInvalid {
+                        attachment: atch_ref.attachment,
+                    },
+                );
+            }
+
+            // VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02550
+            // VUID-VkRenderPassFragmentDensityMapCreateInfoEXT-fragmentDensityMapAttachment-02551
+            if atch.load_op == LoadOp::Clear || atch.store_op != StoreOp::DontCare {
+                return Err(
+                    RenderPassCreationError::FragmentDensityMapAttachmentLoadStoreOpInvalid {
+                        attachment: atch_ref.attachment,
+                    },
+                );
+            }
+
+            // VUID-VkRenderPassCreateInfo-fragmentDensityMapAttachment-06471
+            if !attachment_potential_format_features[atch_ref.attachment as usize]
+                .intersects(FormatFeatures::FRAGMENT_DENSITY_MAP)
+            {
+                return Err(
+                    RenderPassCreationError::FragmentDensityMapAttachmentFormatUsageNotSupported {
+                        attachment: atch_ref.attachment,
+                    },
+                );
+            }
+
+            // Not required by spec, but enforced by Vulkano for sanity.
+            if !atch_ref.aspects.is_empty() {
+                return Err(
+                    RenderPassCreationError::FragmentDensityMapAttachmentAspectsNotEmpty {
+                        attachment: atch_ref.attachment,
+                    },
+                );
+            }
+        }
+
         Ok(views_used)
     }
 
@@ -1053,6 +1441,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
             _ne: _,
         } = create_info;
 
@@ -1100,13 +1489,106 @@ impl RenderPass {
             })
             .collect::<SmallVec<[_; 8]>>();
 
+        let fragment_shading_rate_attachment_references_vk = subpasses
+            .iter()
+            .map(|subpass| {
+                subpass
+                    .fragment_shading_rate_attachment
+                    .as_ref()
+                    .map(|info| ash::vk::AttachmentReference2 {
+                        attachment: info.attachment.attachment,
+                        layout: info.attachment.layout.into(),
+                        aspect_mask: info.attachment.aspects.into(),
+                        ..Default::default()
+                    })
+            })
+            .collect::<SmallVec<[_; 4]>>();
+
+        let fragment_shading_rate_attachment_infos_vk = subpasses
+            .iter()
+            .zip(&fragment_shading_rate_attachment_references_vk)
+            .map(|(subpass, atch_ref_vk)| {
+                subpass
+                    .fragment_shading_rate_attachment
+                    .as_ref()
+                    .zip(atch_ref_vk.as_ref())
+                    .map(
+                        |(info, atch_ref_vk)| ash::vk::FragmentShadingRateAttachmentInfoKHR {
+                            p_fragment_shading_rate_attachment: atch_ref_vk,
+                            shading_rate_attachment_texel_size: ash::vk::Extent2D {
+                                width: info.texel_size[0],
+                                height: info.texel_size[1],
+                            },
+                            ..Default::default()
+                        },
+                    )
+            })
+            .collect::<SmallVec<[_; 4]>>();
+
+        let depth_stencil_resolve_attachment_references_vk = subpasses
+            .iter()
+            .map(|subpass| {
+                subpass
+                    .depth_stencil_resolve_attachment
+                    .as_ref()
+                    .map(|atch_ref| ash::vk::AttachmentReference2 {
+                        attachment: atch_ref.attachment,
+                        layout: atch_ref.layout.into(),
+                        aspect_mask: atch_ref.aspects.into(),
+                        ..Default::default()
+                    })
+            })
+            .collect::<SmallVec<[_; 4]>>();
+
+        let depth_stencil_resolves_vk = subpasses
+            .iter()
+            .zip(&depth_stencil_resolve_attachment_references_vk)
+            .zip(&fragment_shading_rate_attachment_infos_vk)
+            .map(
+                |((subpass, atch_ref_vk), fragment_shading_rate_attachment_info_vk)| {
+                    atch_ref_vk.as_ref().map(|atch_ref_vk| {
+                        ash::vk::SubpassDescriptionDepthStencilResolve {
+                            p_next: fragment_shading_rate_attachment_info_vk
+                                .as_ref()
+                                .map_or(ptr::null(), |info_vk| info_vk as *const _ as *const _),
+                            depth_resolve_mode: subpass
+                                .depth_resolve_mode
+                                .map_or(ash::vk::ResolveModeFlags::NONE, Into::into),
+                            stencil_resolve_mode: subpass
+                                .stencil_resolve_mode
+                                .map_or(ash::vk::ResolveModeFlags::NONE, Into::into),
+                            p_depth_stencil_resolve_attachment: atch_ref_vk,
+                            ..Default::default()
+                        }
+                    })
+                },
+            )
+            .collect::<SmallVec<[_; 4]>>();
+
+        let subpass_p_nexts_vk = depth_stencil_resolves_vk
+            .iter()
+            .zip(&fragment_shading_rate_attachment_infos_vk)
+            .map(
+                |(depth_stencil_resolve_vk, fragment_shading_rate_attachment_info_vk)| {
+                    if let Some(info_vk) = depth_stencil_resolve_vk {
+                        info_vk as *const _ as *const c_void
+                    } else if let Some(info_vk) = fragment_shading_rate_attachment_info_vk {
+                        info_vk as *const _ as *const c_void
+                    } else {
+                        ptr::null()
+                    }
+                },
+            )
+            .collect::<SmallVec<[_; 4]>>();
+
         let subpasses_vk = {
             // `ref_index` is increased during the loop and points to the next element to use
             // in `attachment_references_vk`.
             let mut ref_index = 0usize;
             let out: SmallVec<[_; 4]> = subpasses
                 .iter()
-                .map(|subpass| {
+                .zip(&subpass_p_nexts_vk)
+                .map(|(subpass, &p_next)| {
                     let input_attachments = attachment_references_vk.as_ptr().add(ref_index);
                     ref_index += subpass.input_attachments.len();
                     let color_attachments = attachment_references_vk.as_ptr().add(ref_index);
@@ -1122,6 +1604,7 @@ impl RenderPass {
                     };
 
                     ash::vk::SubpassDescription2 {
+                        p_next,
                         flags: ash::vk::SubpassDescriptionFlags::empty(),
                         pipeline_bind_point: ash::vk::PipelineBindPoint::GRAPHICS, // TODO: any need to make this user-specifiable?
                         view_mask: subpass.view_mask,
@@ -1201,7 +1684,18 @@ impl RenderPass {
             })
             .collect::<SmallVec<[_; 4]>>();
 
-        let create_info = ash::vk::RenderPassCreateInfo2 {
+        let mut fragment_density_map_create_info_vk =
+            fragment_density_map_attachment.as_ref().map(|atch_ref| {
+                ash::vk::RenderPassFragmentDensityMapCreateInfoEXT {
+                    fragment_density_map_attachment: ash::vk::AttachmentReference {
+                        attachment: atch_ref.attachment,
+                        layout: atch_ref.layout.into(),
+                    },
+                    ..Default::default()
+                }
+            });
+
+        let mut create_info = ash::vk::RenderPassCreateInfo2 {
             flags: ash::vk::RenderPassCreateFlags::empty(),
             attachment_count: attachments_vk.len() as u32,
             p_attachments: if attachments_vk.is_empty() {
@@ -1226,6 +1720,13 @@ impl RenderPass {
             ..Default::default()
         };
 
+        if let Some(fragment_density_map_create_info_vk) =
+            fragment_density_map_create_info_vk.as_mut()
+        {
+            fragment_density_map_create_info_vk.p_next = create_info.p_next;
+            create_info.p_next = fragment_density_map_create_info_vk as *const _ as *const _;
+        }
+
         Ok({
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -1261,6 +1762,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
             _ne: _,
         } = create_info;
 
@@ -1448,6 +1950,19 @@ impl RenderPass {
             None
         };
 
+        /* Fragment density map */
+
+        let mut fragment_density_map_create_info_vk =
+            fragment_density_map_attachment.as_ref().map(|atch_ref| {
+                ash::vk::RenderPassFragmentDensityMapCreateInfoEXT {
+                    fragment_density_map_attachment: ash::vk::AttachmentReference {
+                        attachment: atch_ref.attachment,
+                        layout: atch_ref.layout.into(),
+                    },
+                    ..Default::default()
+                }
+            });
+
         /* Create */
 
         let mut create_info = ash::vk::RenderPassCreateInfo {
@@ -1485,6 +2000,13 @@ impl RenderPass {
             create_info.p_next = multiview_create_info as *const _ as *const _;
         }
 
+        if let Some(fragment_density_map_create_info_vk) =
+            fragment_density_map_create_info_vk.as_mut()
+        {
+            fragment_density_map_create_info_vk.p_next = create_info.p_next;
+            create_info.p_next = fragment_density_map_create_info_vk as *const _ as *const _;
+        }
+
         Ok({
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -1581,6 +2103,26 @@ pub enum RenderPassCreationError {
     /// [`VIEW_LOCAL`]: crate::sync::DependencyFlags::VIEW_LOCAL
     DependencyViewOffzetNonzeroWithoutViewLocal { dependency: u32 },
 
+    /// The reference to the fragment density map attachment has one or more aspects selected.
+    FragmentDensityMapAttachmentAspectsNotEmpty { attachment: u32 },
+
+    /// The fragment density map attachment has a format that does not support that usage.
+    FragmentDensityMapAttachmentFormatUsageNotSupported { attachment: u32 },
+
+    /// The fragment density map attachment has a layout that is not supported for that usage.
+    FragmentDensityMapAttachmentLayoutInvalid { attachment: u32 },
+
+    /// The fragment density map attachment has a `load_op` of [`LoadOp::Clear`], or a
+    /// `store_op` that is not [`StoreOp::DontCare`].
+    FragmentDensityMapAttachmentLoadStoreOpInvalid { attachment: u32 },
+
+    /// The fragment density map attachment index is not less than the number of attachments in
+    /// the render pass.
+    FragmentDensityMapAttachmentOutOfRange { attachment: u32 },
+
+    /// The fragment density map attachment is also used in a subpass.
+    FragmentDensityMapAttachmentUsedInSubpass { attachment: u32, subpass: u32 },
+
     /// A reference to an attachment used other than as an input attachment in a subpass has
     /// one or more aspects selected.
     SubpassAttachmentAspectsNotEmpty { subpass: u32, attachment: u32 },
@@ -1625,6 +2167,49 @@ pub enum RenderPassCreationError {
         first_samples: SampleCount,
     },
 
+    /// The `depth_resolve_mode` of a subpass is not supported by the
+    /// `supported_depth_resolve_modes` device property.
+    SubpassDepthResolveModeNotSupported {
+        subpass: u32,
+        depth_resolve_mode: ResolveMode,
+    },
+
+    /// An attachment used as a depth/stencil attachment in a subpass with a depth/stencil resolve
+    /// attachment has a `samples` value of [`SampleCount::Sample1`].
+    SubpassDepthStencilAttachmentWithResolveNotMultisampled { subpass: u32, attachment: u32 },
+
+    /// The attachment used as the depth/stencil resolve attachment in a subpass has a depth or
+    /// stencil component that does not match the depth/stencil attachment.
+    SubpassDepthStencilResolveAttachmentFormatMismatch {
+        subpass: u32,
+        resolve_attachment: u32,
+        depth_stencil_attachment: u32,
+    },
+
+    /// The attachment used as the depth/stencil resolve attachment in a subpass has a `samples`
+    /// value other than [`SampleCount::Sample1`].
+    SubpassDepthStencilResolveAttachmentMultisampled { subpass: u32, attachment: u32 },
+
+    /// The depth/stencil resolve attachment in a subpass is `Some`, but the depth/stencil
+    /// attachment is `None`.
+    SubpassDepthStencilResolveAttachmentWithoutDepthStencilAttachment { subpass: u32 },
+
+    /// The depth/stencil resolve attachment in a subpass is `Some`, but `depth_resolve_mode` and
+    /// `stencil_resolve_mode` are both `None`.
+    SubpassDepthStencilResolveModesNone { subpass: u32 },
+
+    /// The combination of `depth_resolve_mode` and `stencil_resolve_mode` in a subpass is not
+    /// supported by the `independent_resolve` and `independent_resolve_none` device properties.
+    SubpassDepthStencilResolveModesNotSupported { subpass: u32 },
+
+    /// The attachment used as the fragment shading rate attachment in a subpass is also used as
+    /// another attachment type in that subpass.
+    SubpassFragmentShadingRateAttachmentUsedElsewhere { subpass: u32, attachment: u32 },
+
+    /// The texel size of the fragment shading rate attachment in a subpass is not a power of two,
+    /// is outside the range allowed by the device, or exceeds the maximum aspect ratio.
+    SubpassFragmentShadingRateAttachmentTexelSizeInvalid { subpass: u32, texel_size: [u32; 2] },
+
     /// A reference to an attachment used as an input attachment in a subpass selects aspects that
     /// are not present in the format of the attachment.
     SubpassInputAttachmentAspectsNotCompatible { subpass: u32, attachment: u32 },
@@ -1674,6 +2259,13 @@ pub enum RenderPassCreationError {
     /// A resolve attachment in a subpass is `Some`, but the corresponding color attachment is
     /// `None`.
     SubpassResolveAttachmentWithoutColorAttachment { subpass: u32 },
+
+    /// The `stencil_resolve_mode` of a subpass is not supported by the
+    /// `supported_stencil_resolve_modes` device property.
+    SubpassStencilResolveModeNotSupported {
+        subpass: u32,
+        stencil_resolve_mode: ResolveMode,
+    },
 }
 
 impl Error for RenderPassCreationError {
@@ -1808,6 +2400,44 @@ impl Display for RenderPassCreationError {
                 not contain `VIEW_LOCAL`",
                 dependency,
             ),
+            Self::FragmentDensityMapAttachmentAspectsNotEmpty { attachment } => write!(
+                f,
+                "the reference to the fragment density map attachment {} has one or more aspects \
+                selected",
+                attachment,
+            ),
+            Self::FragmentDensityMapAttachmentFormatUsageNotSupported { attachment } => write!(
+                f,
+                "the fragment density map attachment {} has a format that does not support that \
+                usage",
+                attachment,
+            ),
+            Self::FragmentDensityMapAttachmentLayoutInvalid { attachment } => write!(
+                f,
+                "the fragment density map attachment {} has a layout that is not supported for \
+                that usage",
+                attachment,
+            ),
+            Self::FragmentDensityMapAttachmentLoadStoreOpInvalid { attachment } => write!(
+                f,
+                "the fragment density map attachment {} has a `load_op` of `LoadOp::Clear`, or a \
+                `store_op` that is not `StoreOp::DontCare`",
+                attachment,
+            ),
+            Self::FragmentDensityMapAttachmentOutOfRange { attachment } => write!(
+                f,
+                "the fragment density map attachment index {} is not less than the number of \
+                attachments in the render pass",
+                attachment,
+            ),
+            Self::FragmentDensityMapAttachmentUsedInSubpass {
+                attachment,
+                subpass,
+            } => write!(
+                f,
+                "the fragment density map attachment {} is also used in subpass {}",
+                attachment, subpass,
+            ),
             Self::SubpassAttachmentAspectsNotEmpty {
                 subpass,
                 attachment,
@@ -1883,6 +2513,83 @@ impl Display for RenderPassCreationError {
                 `samples` value {:?} that is different from the first color attachment ({:?})",
                 attachment, subpass, samples, first_samples,
             ),
+            Self::SubpassDepthResolveModeNotSupported {
+                subpass,
+                depth_resolve_mode,
+            } => write!(
+                f,
+                "the depth resolve mode {:?} of subpass {} is not supported by the \
+                `supported_depth_resolve_modes` device property",
+                depth_resolve_mode, subpass,
+            ),
+            Self::SubpassDepthStencilAttachmentWithResolveNotMultisampled {
+                subpass,
+                attachment,
+            } => write!(
+                f,
+                "attachment {} used as the depth/stencil attachment in subpass {} with a \
+                depth/stencil resolve attachment has a `samples` value of `SampleCount::Sample1`",
+                attachment, subpass,
+            ),
+            Self::SubpassDepthStencilResolveAttachmentFormatMismatch {
+                subpass,
+                resolve_attachment,
+                depth_stencil_attachment,
+            } => write!(
+                f,
+                "attachment {} used as the depth/stencil resolve attachment in subpass {} has a \
+                depth or stencil component that does not match the depth/stencil attachment {}",
+                resolve_attachment, subpass, depth_stencil_attachment,
+            ),
+            Self::SubpassDepthStencilResolveAttachmentMultisampled {
+                subpass,
+                attachment,
+            } => write!(
+                f,
+                "attachment {} used as the depth/stencil resolve attachment in subpass {} has a \
+                `samples` value other than `SampleCount::Sample1`",
+                attachment, subpass,
+            ),
+            Self::SubpassDepthStencilResolveAttachmentWithoutDepthStencilAttachment { subpass } => {
+                write!(
+                    f,
+                    "the depth/stencil resolve attachment in subpass {} is `Some`, but the \
+                depth/stencil attachment is `None`",
+                    subpass,
+                )
+            }
+            Self::SubpassDepthStencilResolveModesNone { subpass } => write!(
+                f,
+                "the depth/stencil resolve attachment in subpass {} is `Some`, but \
+                `depth_resolve_mode` and `stencil_resolve_mode` are both `None`",
+                subpass,
+            ),
+            Self::SubpassDepthStencilResolveModesNotSupported { subpass } => write!(
+                f,
+                "the combination of `depth_resolve_mode` and `stencil_resolve_mode` in subpass {} \
+                is not supported by the `independent_resolve` and `independent_resolve_none` \
+                device properties",
+                subpass,
+            ),
+            Self::SubpassFragmentShadingRateAttachmentUsedElsewhere {
+                subpass,
+                attachment,
+            } => write!(
+                f,
+                "attachment {} used as the fragment shading rate attachment in subpass {} is also \
+                used as another attachment type in that subpass",
+                attachment, subpass,
+            ),
+            Self::SubpassFragmentShadingRateAttachmentTexelSizeInvalid {
+                subpass,
+                texel_size,
+            } => write!(
+                f,
+                "the texel size {:?} of the fragment shading rate attachment in subpass {} is not \
+                a power of two, is outside the range allowed by the device, or exceeds the \
+                maximum aspect ratio",
+                texel_size, subpass,
+            ),
             Self::SubpassInputAttachmentAspectsNotCompatible {
                 subpass,
                 attachment,
@@ -1949,6 +2656,15 @@ impl Display for RenderPassCreationError {
                 attachment is `None`",
                 subpass,
             ),
+            Self::SubpassStencilResolveModeNotSupported {
+                subpass,
+                stencil_resolve_mode,
+            } => write!(
+                f,
+                "the stencil resolve mode {:?} of subpass {} is not supported by the \
+                `supported_stencil_resolve_modes` device property",
+                stencil_resolve_mode, subpass,
+            ),
         }
     }
 }
@@ -11,8 +11,12 @@ use super::RenderPass;
 use crate::{
     device::{Device, DeviceOwned},
     format::Format,
-    image::{view::ImageViewType, ImageDimensions, ImageUsage, ImageViewAbstract, SampleCount},
-    OomError, VulkanError, VulkanObject,
+    image::{
+        view::ImageViewType, ImageCreateFlags, ImageDimensions, ImageUsage, ImageViewAbstract,
+        SampleCount,
+    },
+    macros::vulkan_bitflags,
+    OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
 };
 use smallvec::SmallVec;
 use std::{
@@ -49,13 +53,27 @@ use std::{
 ///     },
 /// ).unwrap();
 /// ```
+///
+/// # Imageless framebuffers
+///
+/// If the framebuffer is created with the [`IMAGELESS`] flag, then no image views are provided
+/// when creating it. Instead, the framebuffer is created from a description of the attachment
+/// images, and the image views are provided each time the framebuffer is used, in
+/// [`RenderPassBeginInfo::attachments`]. This allows a single framebuffer to be used with
+/// different images, for example with each image of a swapchain, as long as they match the
+/// description.
+///
+/// [`IMAGELESS`]: FramebufferCreateFlags::IMAGELESS
+/// [`RenderPassBeginInfo::attachments`]: crate::command_buffer::RenderPassBeginInfo::attachments
 #[derive(Debug)]
 pub struct Framebuffer {
     handle: ash::vk::Framebuffer,
     render_pass: Arc<RenderPass>,
     id: NonZeroU64,
 
+    flags: FramebufferCreateFlags,
     attachments: Vec<Arc<dyn ImageViewAbstract>>,
+    attachment_image_infos: Vec<FramebufferAttachmentImageInfo>,
     extent: [u32; 2],
     layers: u32,
 }
@@ -67,7 +85,9 @@ impl Framebuffer {
         create_info: FramebufferCreateInfo,
     ) -> Result<Arc<Framebuffer>, FramebufferCreationError> {
         let FramebufferCreateInfo {
+            flags,
             attachments,
+            attachment_image_infos,
             mut extent,
             mut layers,
             _ne: _,
@@ -75,12 +95,47 @@ impl Framebuffer {
 
         let device = render_pass.device();
 
-        // VUID-VkFramebufferCreateInfo-attachmentCount-00876
-        if attachments.len() != render_pass.attachments().len() {
-            return Err(FramebufferCreationError::AttachmentCountMismatch {
-                provided: attachments.len() as u32,
-                required: render_pass.attachments().len() as u32,
-            });
+        // VUID-VkFramebufferCreateInfo-flags-parameter
+        flags.validate_device(device)?;
+
+        let imageless = flags.intersects(FramebufferCreateFlags::IMAGELESS);
+
+        if imageless {
+            // VUID-VkFramebufferCreateInfo-flags-03189
+            if !device.enabled_features().imageless_framebuffer {
+                return Err(FramebufferCreationError::RequirementNotMet {
+                    required_for:
+                        "`create_info.flags` contains `FramebufferCreateFlags::IMAGELESS`",
+                    requires_one_of: RequiresOneOf {
+                        features: &["imageless_framebuffer"],
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // The image views are provided when beginning the render pass instead.
+            if !attachments.is_empty() {
+                return Err(FramebufferCreationError::AttachmentCountMismatch {
+                    provided: attachments.len() as u32,
+                    required: 0,
+                });
+            }
+
+            // VUID-VkFramebufferCreateInfo-flags-03191
+            if attachment_image_infos.len() != render_pass.attachments().len() {
+                return Err(FramebufferCreationError::AttachmentCountMismatch {
+                    provided: attachment_image_infos.len() as u32,
+                    required: render_pass.attachments().len() as u32,
+                });
+            }
+        } else {
+            // VUID-VkFramebufferCreateInfo-attachmentCount-00876
+            if attachments.len() != render_pass.attachments().len() {
+                return Err(FramebufferCreationError::AttachmentCountMismatch {
+                    provided: attachments.len() as u32,
+                    required: render_pass.attachments().len() as u32,
+                });
+            }
         }
 
         let auto_extent = extent[0] == 0 || extent[1] == 0;
@@ -89,7 +144,7 @@ impl Framebuffer {
         // VUID-VkFramebufferCreateInfo-width-00885
         // VUID-VkFramebufferCreateInfo-height-00887
         if auto_extent {
-            if attachments.is_empty() {
+            if render_pass.attachments().is_empty() {
                 return Err(FramebufferCreationError::AutoExtentAttachmentsEmpty);
             }
 
@@ -98,7 +153,7 @@ impl Framebuffer {
 
         // VUID-VkFramebufferCreateInfo-layers-00889
         if auto_layers {
-            if attachments.is_empty() {
+            if render_pass.attachments().is_empty() {
                 return Err(FramebufferCreationError::AutoLayersAttachmentsEmpty);
             }
 
@@ -115,6 +170,184 @@ impl Framebuffer {
             }
         }
 
+        // Fragment shading rate and fragment density map attachments have texels that each cover an
+        // area of the framebuffer. They are exempt from the usual extent requirements, and are
+        // checked separately once the extent of the framebuffer is known.
+        let mut texel_sizes = vec![None; render_pass.attachments().len()];
+        let mut texel_extents = Vec::new();
+        let mut fragment_shading_rate_attachments = vec![false; render_pass.attachments().len()];
+
+        for info in render_pass
+            .subpasses()
+            .iter()
+            .filter_map(|subpass| subpass.fragment_shading_rate_attachment.as_ref())
+        {
+            fragment_shading_rate_attachments[info.attachment.attachment as usize] = true;
+
+            let texel_size: &mut Option<[u32; 2]> =
+                &mut texel_sizes[info.attachment.attachment as usize];
+            *texel_size = Some(texel_size.map_or(info.texel_size, |texel_size| {
+                [
+                    texel_size[0].min(info.texel_size[0]),
+                    texel_size[1].min(info.texel_size[1]),
+                ]
+            }));
+        }
+
+        let fragment_density_map_attachment = render_pass
+            .fragment_density_map_attachment()
+            .map(|atch_ref| atch_ref.attachment);
+
+        if let Some(attachment) = fragment_density_map_attachment {
+            texel_sizes[attachment as usize] = Some(
+                device
+                    .physical_device()
+                    .properties()
+                    .max_fragment_density_texel_size
+                    .unwrap_or([1, 1]),
+            );
+        }
+
+        if imageless {
+            for (attachment_num, (image_info, attachment_desc)) in attachment_image_infos
+                .iter()
+                .zip(render_pass.attachments())
+                .enumerate()
+            {
+                let attachment_num = attachment_num as u32;
+                let &FramebufferAttachmentImageInfo {
+                    flags: image_flags,
+                    usage,
+                    extent: image_extent,
+                    array_layers,
+                    ref view_formats,
+                    _ne: _,
+                } = image_info;
+
+                // VUID-VkFramebufferAttachmentImageInfo-flags-parameter
+                image_flags.validate_device(device)?;
+
+                // VUID-VkFramebufferAttachmentImageInfo-usage-parameter
+                // VUID-VkFramebufferAttachmentImageInfo-usage-requiredbitmask
+                usage.validate_device(device)?;
+
+                for subpass in render_pass.subpasses() {
+                    // VUID-VkFramebufferCreateInfo-flags-03201
+                    if subpass
+                        .color_attachments
+                        .iter()
+                        .chain(&subpass.resolve_attachments)
+                        .flatten()
+                        .any(|atch_ref| atch_ref.attachment == attachment_num)
+                        && !usage.intersects(ImageUsage::COLOR_ATTACHMENT)
+                    {
+                        return Err(FramebufferCreationError::AttachmentMissingUsage {
+                            attachment: attachment_num,
+                            usage: "color_attachment",
+                        });
+                    }
+
+                    // VUID-VkFramebufferCreateInfo-flags-03202
+                    if (subpass.depth_stencil_attachment.iter())
+                        .chain(&subpass.depth_stencil_resolve_attachment)
+                        .any(|atch_ref| atch_ref.attachment == attachment_num)
+                        && !usage.intersects(ImageUsage::DEPTH_STENCIL_ATTACHMENT)
+                    {
+                        return Err(FramebufferCreationError::AttachmentMissingUsage {
+                            attachment: attachment_num,
+                            usage: "depth_stencil",
+                        });
+                    }
+
+                    // VUID-VkFramebufferCreateInfo-flags-03204
+                    if subpass
+                        .input_attachments
+                        .iter()
+                        .flatten()
+                        .any(|atch_ref| atch_ref.attachment == attachment_num)
+                        && !usage.intersects(ImageUsage::INPUT_ATTACHMENT)
+                    {
+                        return Err(FramebufferCreationError::AttachmentMissingUsage {
+                            attachment: attachment_num,
+                            usage: "input_attachment",
+                        });
+                    }
+                }
+
+                // VUID-VkFramebufferCreateInfo-flags-03203
+                if fragment_density_map_attachment == Some(attachment_num)
+                    && !usage.intersects(ImageUsage::FRAGMENT_DENSITY_MAP)
+                {
+                    return Err(FramebufferCreationError::AttachmentMissingUsage {
+                        attachment: attachment_num,
+                        usage: "fragment_density_map",
+                    });
+                }
+
+                // VUID-VkFramebufferCreateInfo-flags-04549
+                if fragment_shading_rate_attachments[attachment_num as usize]
+                    && !usage.intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                {
+                    return Err(FramebufferCreationError::AttachmentMissingUsage {
+                        attachment: attachment_num,
+                        usage: "fragment_shading_rate_attachment",
+                    });
+                }
+
+                // VUID-VkFramebufferCreateInfo-flags-03205
+                if !attachment_desc
+                    .format
+                    .map_or(false, |format| view_formats.contains(&format))
+                {
+                    return Err(FramebufferCreationError::AttachmentImageInfoFormatMissing {
+                        attachment: attachment_num,
+                        required: attachment_desc.format,
+                    });
+                }
+
+                // VUID-VkFramebufferCreateInfo-renderPass-03198
+                if array_layers < render_pass.views_used() {
+                    return Err(
+                        FramebufferCreationError::MultiviewAttachmentNotEnoughLayers {
+                            attachment: attachment_num,
+                            provided: array_layers,
+                            min: render_pass.views_used(),
+                        },
+                    );
+                }
+
+                // VUID-VkFramebufferCreateInfo-flags-04541
+                // VUID-VkFramebufferCreateInfo-flags-04542
+                if let Some(texel_size) = texel_sizes[attachment_num as usize] {
+                    // Checked below, once the extent of the framebuffer is known.
+                    texel_extents.push((attachment_num, image_extent, texel_size));
+                } else if auto_extent {
+                    extent[0] = extent[0].min(image_extent[0]);
+                    extent[1] = extent[1].min(image_extent[1]);
+                } else if image_extent[0] < extent[0] || image_extent[1] < extent[1] {
+                    return Err(FramebufferCreationError::AttachmentExtentTooSmall {
+                        attachment: attachment_num,
+                        provided: image_extent,
+                        min: extent,
+                    });
+                }
+
+                // VUID-VkFramebufferCreateInfo-flags-04545
+                // VUID-VkFramebufferCreateInfo-flags-04546
+                if texel_sizes[attachment_num as usize].is_some() && array_layers == 1 {
+                    // A single layer is used for all layers of the framebuffer.
+                } else if auto_layers {
+                    layers = layers.min(array_layers);
+                } else if array_layers < layers {
+                    return Err(FramebufferCreationError::AttachmentNotEnoughLayers {
+                        attachment: attachment_num,
+                        provided: array_layers,
+                        min: layers,
+                    });
+                }
+            }
+        }
+
         let attachments_vk = attachments
             .iter()
             .zip(render_pass.attachments())
@@ -140,17 +373,19 @@ impl Framebuffer {
                     }
 
                     // VUID-VkFramebufferCreateInfo-pAttachments-02633
-                    if let Some(atch_ref) = &subpass.depth_stencil_attachment {
-                        if atch_ref.attachment == attachment_num {
-                            if !image_view
-                                .usage()
-                                .intersects(ImageUsage::DEPTH_STENCIL_ATTACHMENT)
-                            {
-                                return Err(FramebufferCreationError::AttachmentMissingUsage {
-                                    attachment: attachment_num,
-                                    usage: "depth_stencil",
-                                });
-                            }
+                    // VUID-VkFramebufferCreateInfo-pAttachments-02634
+                    if (subpass.depth_stencil_attachment.iter())
+                        .chain(&subpass.depth_stencil_resolve_attachment)
+                        .any(|atch_ref| atch_ref.attachment == attachment_num)
+                    {
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::DEPTH_STENCIL_ATTACHMENT)
+                        {
+                            return Err(FramebufferCreationError::AttachmentMissingUsage {
+                                attachment: attachment_num,
+                                usage: "depth_stencil",
+                            });
                         }
                     }
 
@@ -170,6 +405,30 @@ impl Framebuffer {
                     }
                 }
 
+                // VUID-VkFramebufferCreateInfo-pAttachments-02552
+                if fragment_density_map_attachment == Some(attachment_num)
+                    && !image_view
+                        .usage()
+                        .intersects(ImageUsage::FRAGMENT_DENSITY_MAP)
+                {
+                    return Err(FramebufferCreationError::AttachmentMissingUsage {
+                        attachment: attachment_num,
+                        usage: "fragment_density_map",
+                    });
+                }
+
+                // VUID-VkFramebufferCreateInfo-flags-04548
+                if fragment_shading_rate_attachments[attachment_num as usize]
+                    && !image_view
+                        .usage()
+                        .intersects(ImageUsage::FRAGMENT_SHADING_RATE_ATTACHMENT)
+                {
+                    return Err(FramebufferCreationError::AttachmentMissingUsage {
+                        attachment: attachment_num,
+                        usage: "fragment_shading_rate_attachment",
+                    });
+                }
+
                 // VUID-VkFramebufferCreateInfo-pAttachments-00880
                 if image_view.format() != attachment_desc.format {
                     return Err(FramebufferCreationError::AttachmentFormatMismatch {
@@ -205,7 +464,10 @@ impl Framebuffer {
 
                 // VUID-VkFramebufferCreateInfo-flags-04533
                 // VUID-VkFramebufferCreateInfo-flags-04534
-                if auto_extent {
+                if let Some(texel_size) = texel_sizes[attachment_num as usize] {
+                    // Checked below, once the extent of the framebuffer is known.
+                    texel_extents.push((attachment_num, image_view_extent, texel_size));
+                } else if auto_extent {
                     extent[0] = extent[0].min(image_view_extent[0]);
                     extent[1] = extent[1].min(image_view_extent[1]);
                 } else if image_view_extent[0] < extent[0] || image_view_extent[1] < extent[1] {
@@ -217,7 +479,10 @@ impl Framebuffer {
                 }
 
                 // VUID-VkFramebufferCreateInfo-flags-04535
-                if auto_layers {
+                // VUID-VkFramebufferCreateInfo-flags-04538
+                if texel_sizes[attachment_num as usize].is_some() && image_view_array_layers == 1 {
+                    // A single layer is used for all layers of the framebuffer.
+                } else if auto_layers {
                     layers = layers.min(image_view_array_layers);
                 } else if image_view_array_layers < layers {
                     return Err(FramebufferCreationError::AttachmentNotEnoughLayers {
@@ -273,6 +538,35 @@ impl Framebuffer {
             })
             .collect::<Result<SmallVec<[_; 4]>, _>>()?;
 
+        // If all attachments have a texel size, then the extent is derived from those instead.
+        if auto_extent && extent == [u32::MAX, u32::MAX] {
+            for &(_, image_extent, texel_size) in &texel_extents {
+                extent[0] = extent[0].min(image_extent[0].saturating_mul(texel_size[0]));
+                extent[1] = extent[1].min(image_extent[1].saturating_mul(texel_size[1]));
+            }
+        }
+
+        // VUID-VkFramebufferCreateInfo-pAttachments-02555
+        // VUID-VkFramebufferCreateInfo-pAttachments-02556
+        // VUID-VkFramebufferCreateInfo-flags-04539
+        // VUID-VkFramebufferCreateInfo-flags-04540
+        // VUID-VkFramebufferCreateInfo-flags-04543
+        // VUID-VkFramebufferCreateInfo-flags-04544
+        for &(attachment_num, image_extent, texel_size) in &texel_extents {
+            let min = [
+                (extent[0] + texel_size[0] - 1) / texel_size[0],
+                (extent[1] + texel_size[1] - 1) / texel_size[1],
+            ];
+
+            if image_extent[0] < min[0] || image_extent[1] < min[1] {
+                return Err(FramebufferCreationError::AttachmentExtentTooSmall {
+                    attachment: attachment_num,
+                    provided: image_extent,
+                    min,
+                });
+            }
+        }
+
         {
             let properties = device.physical_device().properties();
 
@@ -299,8 +593,8 @@ impl Framebuffer {
             }
         }
 
-        let create_info = ash::vk::FramebufferCreateInfo {
-            flags: ash::vk::FramebufferCreateFlags::empty(),
+        let mut create_info = ash::vk::FramebufferCreateInfo {
+            flags: flags.into(),
             render_pass: render_pass.handle(),
             attachment_count: attachments_vk.len() as u32,
             p_attachments: attachments_vk.as_ptr(),
@@ -310,6 +604,50 @@ impl Framebuffer {
             ..Default::default()
         };
 
+        let view_formats_vk: SmallVec<[SmallVec<[_; 2]>; 4]> = attachment_image_infos
+            .iter()
+            .filter(|_| imageless)
+            .map(|image_info| {
+                image_info
+                    .view_formats
+                    .iter()
+                    .copied()
+                    .map(ash::vk::Format::from)
+                    .collect()
+            })
+            .collect();
+        let attachment_image_infos_vk: SmallVec<[_; 4]> = attachment_image_infos
+            .iter()
+            .zip(&view_formats_vk)
+            .map(
+                |(image_info, view_formats_vk)| ash::vk::FramebufferAttachmentImageInfo {
+                    flags: image_info.flags.into(),
+                    usage: image_info.usage.into(),
+                    width: image_info.extent[0],
+                    height: image_info.extent[1],
+                    layer_count: image_info.array_layers,
+                    view_format_count: view_formats_vk.len() as u32,
+                    p_view_formats: view_formats_vk.as_ptr(),
+                    ..Default::default()
+                },
+            )
+            .collect();
+        let mut attachments_create_info_vk = None;
+
+        if imageless {
+            let next =
+                attachments_create_info_vk.insert(ash::vk::FramebufferAttachmentsCreateInfo {
+                    attachment_image_info_count: attachment_image_infos_vk.len() as u32,
+                    p_attachment_image_infos: attachment_image_infos_vk.as_ptr(),
+                    ..Default::default()
+                });
+
+            // The image views are ignored, but the count must still match the render pass.
+            create_info.attachment_count = attachment_image_infos_vk.len() as u32;
+            create_info.p_attachments = ptr::null();
+            create_info.p_next = next as *const _ as *const _;
+        }
+
         let handle = unsafe {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -328,7 +666,9 @@ impl Framebuffer {
             handle,
             render_pass,
             id: Self::next_id(),
+            flags,
             attachments,
+            attachment_image_infos,
             extent,
             layers,
         }))
@@ -347,7 +687,9 @@ impl Framebuffer {
         create_info: FramebufferCreateInfo,
     ) -> Arc<Framebuffer> {
         let FramebufferCreateInfo {
+            flags,
             attachments,
+            attachment_image_infos,
             extent,
             layers,
             _ne: _,
@@ -357,23 +699,41 @@ impl Framebuffer {
             handle,
             render_pass,
             id: Self::next_id(),
+            flags,
             attachments,
+            attachment_image_infos,
             extent,
             layers,
         })
     }
+
     /// Returns the renderpass that was used to create this framebuffer.
     #[inline]
     pub fn render_pass(&self) -> &Arc<RenderPass> {
         &self.render_pass
     }
 
+    /// Returns the flags that the framebuffer was created with.
+    #[inline]
+    pub fn flags(&self) -> FramebufferCreateFlags {
+        self.flags
+    }
+
     /// Returns the attachments of the framebuffer.
+    ///
+    /// This is empty if the framebuffer was created with [`FramebufferCreateFlags::IMAGELESS`].
     #[inline]
     pub fn attachments(&self) -> &[Arc<dyn ImageViewAbstract>] {
         &self.attachments
     }
 
+    /// Returns the descriptions of the attachment images, if the framebuffer was created with
+    /// [`FramebufferCreateFlags::IMAGELESS`].
+    #[inline]
+    pub fn attachment_image_infos(&self) -> &[FramebufferAttachmentImageInfo] {
+        &self.attachment_image_infos
+    }
+
     /// Returns the extent (width and height) of the framebuffer.
     #[inline]
     pub fn extent(&self) -> [u32; 2] {
@@ -387,6 +747,8 @@ impl Framebuffer {
     }
 
     /// Returns the layer ranges for all attachments.
+    ///
+    /// This is empty if the framebuffer was created with [`FramebufferCreateFlags::IMAGELESS`].
     #[inline]
     pub fn attached_layers_ranges(&self) -> SmallVec<[Range<u32>; 4]> {
         self.attachments
@@ -427,6 +789,11 @@ crate::impl_id_counter!(Framebuffer);
 /// Parameters to create a new `Framebuffer`.
 #[derive(Clone, Debug)]
 pub struct FramebufferCreateInfo {
+    /// Specifies how to create the framebuffer.
+    ///
+    /// The default value is empty.
+    pub flags: FramebufferCreateFlags,
+
     /// The attachment images that are to be used in the framebuffer.
     ///
     /// Attachments are specified in the same order as they are defined in the render pass, and
@@ -441,9 +808,21 @@ pub struct FramebufferCreateInfo {
     /// If the render pass has multiview enabled (`views_used` does not return 0), then each
     /// image must have at least `views_used` array layers.
     ///
+    /// If `flags` contains [`FramebufferCreateFlags::IMAGELESS`], this must be empty.
+    ///
     /// The default value is empty.
     pub attachments: Vec<Arc<dyn ImageViewAbstract>>,
 
+    /// If `flags` contains [`FramebufferCreateFlags::IMAGELESS`], the descriptions of the
+    /// attachment images that will be used with the framebuffer, in place of `attachments`.
+    ///
+    /// There must be exactly as many elements as there are attachments in the render pass, and
+    /// the same requirements apply to them as to the images in `attachments`. If `flags` does not
+    /// contain `IMAGELESS`, this is ignored.
+    ///
+    /// The default value is empty.
+    pub attachment_image_infos: Vec<FramebufferAttachmentImageInfo>,
+
     /// The extent (width and height) of the framebuffer.
     ///
     /// This must be no larger than the smallest width and height of the images in `attachments`.
@@ -482,7 +861,9 @@ impl Default for FramebufferCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            flags: FramebufferCreateFlags::empty(),
             attachments: Vec::new(),
+            attachment_image_infos: Vec::new(),
             extent: [0, 0],
             layers: 0,
             _ne: crate::NonExhaustive(()),
@@ -490,6 +871,91 @@ impl Default for FramebufferCreateInfo {
     }
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control how a framebuffer is created.
+    FramebufferCreateFlags = FramebufferCreateFlags(u32);
+
+    /// The framebuffer is created without image views. Instead, the attachment images are
+    /// described in [`FramebufferCreateInfo::attachment_image_infos`], and the image views are
+    /// provided when beginning a render pass.
+    ///
+    /// The [`imageless_framebuffer`] feature must be enabled on the device.
+    ///
+    /// [`imageless_framebuffer`]: crate::device::Features::imageless_framebuffer
+    IMAGELESS = IMAGELESS {
+        api_version: V1_2,
+        device_extensions: [khr_imageless_framebuffer],
+    },
+}
+
+/// Describes an attachment image of an imageless framebuffer.
+#[derive(Clone, Debug)]
+pub struct FramebufferAttachmentImageInfo {
+    /// The flags that the image must have been created with.
+    ///
+    /// The default value is empty.
+    pub flags: ImageCreateFlags,
+
+    /// The usage that the image view must have.
+    ///
+    /// The default value is empty, which must be overridden.
+    pub usage: ImageUsage,
+
+    /// The width and height of the image.
+    ///
+    /// The default value is `[0, 0]`, which must be overridden.
+    pub extent: [u32; 2],
+
+    /// The number of array layers of the image view.
+    ///
+    /// The default value is `1`.
+    pub array_layers: u32,
+
+    /// The formats that image views can be created with, for the image that the image view
+    /// belongs to. This must include the format of the attachment in the render pass.
+    ///
+    /// The default value is empty, which must be overridden.
+    pub view_formats: Vec<Format>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for FramebufferAttachmentImageInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: ImageCreateFlags::empty(),
+            usage: ImageUsage::empty(),
+            extent: [0, 0],
+            array_layers: 1,
+            view_formats: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl FramebufferAttachmentImageInfo {
+    /// Returns a `FramebufferAttachmentImageInfo` that describes `image_view`.
+    ///
+    /// Image views that are compatible with the returned info can then be used with the
+    /// framebuffer, such as the other images of the same swapchain.
+    #[inline]
+    pub fn image_view(image_view: &dyn ImageViewAbstract) -> Self {
+        let subresource_range = image_view.subresource_range();
+
+        Self {
+            flags: image_view.image().inner().image.flags(),
+            usage: image_view.usage(),
+            extent: image_view.image().dimensions().width_height(),
+            array_layers: subresource_range.array_layers.end - subresource_range.array_layers.start,
+            view_formats: image_view.format().into_iter().collect(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
 /// Error that can happen when creating a `Framebuffer`.
 #[derive(Copy, Clone, Debug)]
 pub enum FramebufferCreationError {
@@ -500,6 +966,11 @@ pub enum FramebufferCreationError {
     /// format.
     Attachment2dArrayCompatibleDepthStencil { attachment: u32 },
 
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
     /// An attachment image has a non-identity component mapping.
     AttachmentComponentMappingNotIdentity { attachment: u32 },
 
@@ -520,6 +991,13 @@ pub enum FramebufferCreationError {
         required: Option<Format>,
     },
 
+    /// The `view_formats` of an attachment image info does not contain the format that the
+    /// render pass requires.
+    AttachmentImageInfoFormatMissing {
+        attachment: u32,
+        required: Option<Format>,
+    },
+
     /// An attachment image is missing a usage that the render pass requires it to have.
     AttachmentMissingUsage {
         attachment: u32,
@@ -592,6 +1070,14 @@ impl Display for FramebufferCreationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
             Self::OomError(_) => write!(f, "no memory available",),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
             Self::Attachment2dArrayCompatibleDepthStencil { attachment } => write!(
                 f,
                 "attachment image {} is a 2D image view created from a 3D image, and has a \
@@ -627,6 +1113,15 @@ impl Display for FramebufferCreationError {
                 requires ({:?})",
                 attachment, provided, required,
             ),
+            Self::AttachmentImageInfoFormatMissing {
+                attachment,
+                required,
+            } => write!(
+                f,
+                "the `view_formats` of attachment image info {} does not contain the format \
+                that the render pass requires ({:?})",
+                attachment, required,
+            ),
             Self::AttachmentMissingUsage { attachment, usage } => write!(
                 f,
                 "attachment image {} is missing usage `{}` that the render pass requires it to \
@@ -705,13 +1200,25 @@ impl From<VulkanError> for FramebufferCreationError {
     }
 }
 
+impl From<RequirementNotMet> for FramebufferCreationError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
         format::Format,
-        image::{attachment::AttachmentImage, view::ImageView},
+        image::{attachment::AttachmentImage, view::ImageView, ImageUsage},
         memory::allocator::StandardMemoryAllocator,
-        render_pass::{Framebuffer, FramebufferCreateInfo, FramebufferCreationError, RenderPass},
+        render_pass::{
+            Framebuffer, FramebufferAttachmentImageInfo, FramebufferCreateFlags,
+            FramebufferCreateInfo, FramebufferCreationError, RenderPass,
+        },
     };
 
     #[test]
@@ -1074,4 +1581,42 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn imageless_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = single_pass_renderpass!(device,
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        )
+        .unwrap();
+
+        match Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                flags: FramebufferCreateFlags::IMAGELESS,
+                attachment_image_infos: vec![FramebufferAttachmentImageInfo {
+                    usage: ImageUsage::COLOR_ATTACHMENT,
+                    extent: [1024, 768],
+                    view_formats: vec![Format::R8G8B8A8_UNORM],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ) {
+            Err(FramebufferCreationError::RequirementNotMet { .. }) => (),
+            _ => panic!(),
+        }
+    }
 }
@@ -15,8 +15,12 @@ macro_rules! single_pass_renderpass {
         attachments: { $($a:tt)* },
         pass: {
             color: [$($color_atch:ident),*],
-            depth_stencil: {$($depth_atch:ident)*}$(,)*
-            $(resolve: [$($resolve_atch:ident),*])*$(,)*
+            depth_stencil: {$($depth_atch:ident)*}$(,)?
+            $(resolve: [$($resolve_atch:ident),*]$(,)?)*
+            $(depth_stencil_resolve: {$($depth_resolve_atch:ident)*}$(,)?)?
+            $(depth_resolve_mode: $depth_resolve_mode:ident$(,)?)?
+            $(stencil_resolve_mode: $stencil_resolve_mode:ident$(,)?)?
+            $(view_mask: $view_mask:expr$(,)?)?
         }
     ) => (
         $crate::ordered_passes_renderpass!(
@@ -27,7 +31,11 @@ macro_rules! single_pass_renderpass {
                     color: [$($color_atch),*],
                     depth_stencil: {$($depth_atch)*},
                     input: [],
-                    resolve: [$($($resolve_atch),*)*]
+                    resolve: [$($($resolve_atch),*)*],
+                    $(depth_stencil_resolve: {$($depth_resolve_atch)*},)?
+                    $(depth_resolve_mode: $depth_resolve_mode,)?
+                    $(stencil_resolve_mode: $stencil_resolve_mode,)?
+                    $(view_mask: $view_mask,)?
                 }
             ]
         )
@@ -56,11 +64,16 @@ macro_rules! ordered_passes_renderpass {
                 {
                     color: [$($color_atch:ident),*],
                     depth_stencil: {$($depth_atch:ident)*},
-                    input: [$($input_atch:ident),*]$(,)*
-                    $(resolve: [$($resolve_atch:ident),*])*$(,)*
+                    input: [$($input_atch:ident),*]$(,)?
+                    $(resolve: [$($resolve_atch:ident),*]$(,)?)*
+                    $(depth_stencil_resolve: {$($depth_resolve_atch:ident)*}$(,)?)?
+                    $(depth_resolve_mode: $depth_resolve_mode:ident$(,)?)?
+                    $(stencil_resolve_mode: $stencil_resolve_mode:ident$(,)?)?
+                    $(view_mask: $view_mask:expr$(,)?)?
                 }
             ),*
-        ]
+        ]$(,)?
+        $(correlated_view_masks: [$($correlated_view_mask:expr),*]$(,)?)?
     ) => ({
         use $crate::render_pass::RenderPass;
 
@@ -80,7 +93,8 @@ macro_rules! ordered_passes_renderpass {
 
             let subpasses = vec![
                 $({
-                    let desc = $crate::render_pass::SubpassDescription {
+                    #[allow(unused_mut)]
+                    let mut desc = $crate::render_pass::SubpassDescription {
                         color_attachments: vec![
                             $({
                                 let layout = &mut layouts[$color_atch as usize];
@@ -135,18 +149,61 @@ macro_rules! ordered_passes_renderpass {
                                 })
                             }),*)*
                         ],
+                        depth_stencil_resolve_attachment: {
+                            let resolve: Option<$crate::render_pass::AttachmentReference> = None;
+                            $($(
+                                let layout = &mut layouts[$depth_resolve_atch as usize];
+                                layout.1 = Some($crate::image::ImageLayout::DepthStencilAttachmentOptimal);
+                                layout.0 = layout.0.or(layout.1);
+
+                                let resolve = Some($crate::render_pass::AttachmentReference {
+                                    attachment: $depth_resolve_atch,
+                                    layout: $crate::image::ImageLayout::DepthStencilAttachmentOptimal,
+                                    ..Default::default()
+                                });
+                            )*)?
+                            resolve
+                        },
+                        depth_resolve_mode: {
+                            let mode: Option<$crate::render_pass::ResolveMode> = None;
+                            $(let mode = Some($crate::render_pass::ResolveMode::$depth_resolve_mode);)?
+                            mode
+                        },
+                        stencil_resolve_mode: {
+                            let mode: Option<$crate::render_pass::ResolveMode> = None;
+                            $(let mode = Some($crate::render_pass::ResolveMode::$stencil_resolve_mode);)?
+                            mode
+                        },
                         preserve_attachments: (0 .. attachment_num).filter(|&a| {
                             $(if a == $color_atch { return false; })*
                             $(if a == $depth_atch { return false; })*
                             $(if a == $input_atch { return false; })*
                             $($(if a == $resolve_atch { return false; })*)*
+                            $($(if a == $depth_resolve_atch { return false; })*)?
                             true
                         }).collect(),
+                        view_mask: {
+                            #[allow(unused_mut)]
+                            let mut view_mask = 0;
+                            $(view_mask = $view_mask;)?
+                            view_mask
+                        },
                         ..Default::default()
                     };
 
                     assert!(desc.resolve_attachments.is_empty() ||
                             desc.resolve_attachments.len() == desc.color_attachments.len());
+
+                    // Resolve both aspects from sample zero if no modes were given, which every
+                    // implementation supports.
+                    if desc.depth_stencil_resolve_attachment.is_some()
+                        && desc.depth_resolve_mode.is_none()
+                        && desc.stencil_resolve_mode.is_none()
+                    {
+                        desc.depth_resolve_mode = Some($crate::render_pass::ResolveMode::SampleZero);
+                        desc.stencil_resolve_mode = Some($crate::render_pass::ResolveMode::SampleZero);
+                    }
+
                     desc
                 }),*
             ];
@@ -211,10 +268,15 @@ macro_rules! ordered_passes_renderpass {
                 }),*
             ];
 
+            #[allow(unused_mut)]
+            let mut correlated_view_masks: Vec<u32> = Vec::new();
+            $(correlated_view_masks.extend([$($correlated_view_mask),*]);)?
+
             $crate::render_pass::RenderPassCreateInfo {
                 attachments,
                 subpasses,
                 dependencies,
+                correlated_view_masks,
                 ..Default::default()
             }
         };
@@ -225,7 +287,7 @@ macro_rules! ordered_passes_renderpass {
 
 #[cfg(test)]
 mod tests {
-    use crate::format::Format;
+    use crate::{format::Format, render_pass::ResolveMode};
 
     #[test]
     fn single_pass_resolve() {
@@ -253,4 +315,131 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn single_pass_depth_stencil_resolve() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.api_version() < crate::Version::V1_2 {
+            return; // test ignored
+        }
+
+        let render_pass = single_pass_renderpass!(device,
+            attachments: {
+                a: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D16_UNORM,
+                    samples: 4,
+                },
+                b: {
+                    load: DontCare,
+                    store: Store,
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [],
+                depth_stencil: {a},
+                depth_stencil_resolve: {b},
+            }
+        )
+        .unwrap();
+
+        let subpass = &render_pass.subpasses()[0];
+        assert_eq!(
+            subpass
+                .depth_stencil_resolve_attachment
+                .as_ref()
+                .map(|atch_ref| atch_ref.attachment),
+            Some(1),
+        );
+        assert_eq!(subpass.depth_resolve_mode, Some(ResolveMode::SampleZero));
+    }
+
+    #[test]
+    fn single_pass_depth_stencil_resolve_modes() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.api_version() < crate::Version::V1_2 {
+            return; // test ignored
+        }
+
+        let render_pass = single_pass_renderpass!(device,
+            attachments: {
+                a: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D16_UNORM,
+                    samples: 4,
+                },
+                b: {
+                    load: DontCare,
+                    store: Store,
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [],
+                depth_stencil: {a},
+                depth_stencil_resolve: {b},
+                depth_resolve_mode: SampleZero,
+                stencil_resolve_mode: SampleZero
+            }
+        )
+        .unwrap();
+
+        let subpass = &render_pass.subpasses()[0];
+        assert_eq!(subpass.depth_resolve_mode, Some(ResolveMode::SampleZero));
+        assert_eq!(subpass.stencil_resolve_mode, Some(ResolveMode::SampleZero));
+    }
+
+    #[test]
+    fn multiview() {
+        let (device, _) = gfx_dev_and_queue!(multiview);
+        let render_pass = ordered_passes_renderpass!(device,
+            attachments: {
+                a: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            passes: [
+                {
+                    color: [a],
+                    depth_stencil: {},
+                    input: [],
+                    view_mask: 0b11,
+                }
+            ],
+            correlated_view_masks: [0b11],
+        )
+        .unwrap();
+
+        assert_eq!(render_pass.subpasses()[0].view_mask, 0b11);
+        assert_eq!(render_pass.correlated_view_masks(), &[0b11]);
+    }
+
+    #[test]
+    fn single_pass_multiview() {
+        let (device, _) = gfx_dev_and_queue!(multiview);
+        let render_pass = single_pass_renderpass!(device,
+            attachments: {
+                a: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                }
+            },
+            pass: { color: [a], depth_stencil: {}, view_mask: 0b11 }
+        )
+        .unwrap();
+
+        assert_eq!(render_pass.subpasses()[0].view_mask, 0b11);
+    }
 }
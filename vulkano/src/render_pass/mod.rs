@@ -27,7 +27,10 @@
 
 pub use self::{
     create::RenderPassCreationError,
-    framebuffer::{Framebuffer, FramebufferCreateInfo, FramebufferCreationError},
+    framebuffer::{
+        Framebuffer, FramebufferAttachmentImageInfo, FramebufferCreateFlags, FramebufferCreateInfo,
+        FramebufferCreationError,
+    },
 };
 use crate::{
     device::{Device, DeviceOwned},
@@ -108,6 +111,7 @@ pub struct RenderPass {
     subpasses: Vec<SubpassDescription>,
     dependencies: Vec<SubpassDependency>,
     correlated_view_masks: Vec<u32>,
+    fragment_density_map_attachment: Option<AttachmentReference>,
 
     granularity: [u32; 2],
     views_used: u32,
@@ -141,6 +145,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
             _ne: _,
         } = create_info;
 
@@ -154,6 +159,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
             granularity,
             views_used,
         }))
@@ -211,6 +217,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
             _ne: _,
         } = create_info;
 
@@ -222,6 +229,7 @@ impl RenderPass {
             subpasses,
             dependencies,
             correlated_view_masks,
+            fragment_density_map_attachment,
             granularity,
             views_used,
         }))
@@ -251,6 +259,12 @@ impl RenderPass {
         &self.correlated_view_masks
     }
 
+    /// Returns the fragment density map attachment of the render pass.
+    #[inline]
+    pub fn fragment_density_map_attachment(&self) -> Option<&AttachmentReference> {
+        self.fragment_density_map_attachment.as_ref()
+    }
+
     /// If the render pass has multiview enabled, returns the number of views used by the render
     /// pass. Returns 0 if multiview is not enabled.
     #[inline]
@@ -292,6 +306,7 @@ impl RenderPass {
             subpasses: subpasses1,
             dependencies: dependencies1,
             correlated_view_masks: correlated_view_masks1,
+            fragment_density_map_attachment: fragment_density_map_attachment1,
             granularity: _,
             views_used: _,
         } = self;
@@ -303,6 +318,7 @@ impl RenderPass {
             subpasses: subpasses2,
             dependencies: dependencies2,
             correlated_view_masks: correlated_view_masks2,
+            fragment_density_map_attachment: fragment_density_map_attachment2,
             granularity: _,
             views_used: _,
         } = other;
@@ -401,7 +417,11 @@ impl RenderPass {
                     color_attachments: color_attachments1,
                     resolve_attachments: resolve_attachments1,
                     depth_stencil_attachment: depth_stencil_attachment1,
+                    depth_stencil_resolve_attachment: depth_stencil_resolve_attachment1,
+                    depth_resolve_mode: depth_resolve_mode1,
+                    stencil_resolve_mode: stencil_resolve_mode1,
                     preserve_attachments: _,
+                    fragment_shading_rate_attachment: fragment_shading_rate_attachment1,
                     _ne: _,
                 } = subpass1;
                 let SubpassDescription {
@@ -410,7 +430,11 @@ impl RenderPass {
                     color_attachments: color_attachments2,
                     resolve_attachments: resolve_attachments2,
                     depth_stencil_attachment: depth_stencil_attachment2,
+                    depth_stencil_resolve_attachment: depth_stencil_resolve_attachment2,
+                    depth_resolve_mode: depth_resolve_mode2,
+                    stencil_resolve_mode: stencil_resolve_mode2,
                     preserve_attachments: _,
+                    fragment_shading_rate_attachment: fragment_shading_rate_attachment2,
                     _ne: _,
                 } = subpass2;
 
@@ -450,6 +474,27 @@ impl RenderPass {
                     return false;
                 }
 
+                if subpasses1.len() > 1
+                    && (!are_atch_refs_compatible(
+                        depth_stencil_resolve_attachment1.as_ref(),
+                        depth_stencil_resolve_attachment2.as_ref(),
+                    ) || depth_resolve_mode1 != depth_resolve_mode2
+                        || stencil_resolve_mode1 != stencil_resolve_mode2)
+                {
+                    return false;
+                }
+
+                if !are_atch_refs_compatible(
+                    fragment_shading_rate_attachment1
+                        .as_ref()
+                        .map(|info| &info.attachment),
+                    fragment_shading_rate_attachment2
+                        .as_ref()
+                        .map(|info| &info.attachment),
+                ) {
+                    return false;
+                }
+
                 if view_mask1 != view_mask2 {
                     return false;
                 }
@@ -468,6 +513,13 @@ impl RenderPass {
             return false;
         }
 
+        if !are_atch_refs_compatible(
+            fragment_density_map_attachment1.as_ref(),
+            fragment_density_map_attachment2.as_ref(),
+        ) {
+            return false;
+        }
+
         true
     }
 
@@ -756,6 +808,21 @@ pub struct RenderPassCreateInfo {
     /// The default value is empty.
     pub correlated_view_masks: Vec<u32>,
 
+    /// The attachment that is used as a fragment density map for the whole render pass.
+    ///
+    /// A fragment density map controls the size of the area of the framebuffer that is covered
+    /// by each fragment, which can be used to reduce the rendering resolution towards the edges
+    /// of the view.
+    ///
+    /// If set to `Some`, the
+    /// [`fragment_density_map`](crate::device::Features::fragment_density_map) feature must be
+    /// enabled on the device. The attachment must not be used as any other attachment type in a
+    /// subpass, its layout must be [`ImageLayout::FragmentDensityMapOptimal`] or
+    /// [`ImageLayout::ShaderReadOnlyOptimal`], and its `load_op` must not be [`LoadOp::Clear`].
+    ///
+    /// The default value is `None`.
+    pub fragment_density_map_attachment: Option<AttachmentReference>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -767,6 +834,7 @@ impl Default for RenderPassCreateInfo {
             subpasses: Vec::new(),
             dependencies: Vec::new(),
             correlated_view_masks: Vec::new(),
+            fragment_density_map_attachment: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -905,6 +973,48 @@ pub struct SubpassDescription {
     /// The default value is `None`.
     pub depth_stencil_attachment: Option<AttachmentReference>,
 
+    /// The attachment of the render pass that is to be used as the resolve attachment for the
+    /// depth/stencil attachment in this subpass.
+    ///
+    /// If set to `Some`, then `depth_stencil_attachment` must also be `Some`, and must have a
+    /// `samples` value other than [`SampleCount::Sample1`], while the resolve attachment itself
+    /// must have a `samples` value of [`SampleCount::Sample1`]. The depth and stencil components
+    /// of the resolve attachment's format must match those of the depth/stencil attachment.
+    /// At least one of `depth_resolve_mode` and `stencil_resolve_mode` must also be `Some`.
+    ///
+    /// If set to `Some`, the device API version must be at least 1.2, or the
+    /// [`khr_depth_stencil_resolve`](crate::device::DeviceExtensions::khr_depth_stencil_resolve)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub depth_stencil_resolve_attachment: Option<AttachmentReference>,
+
+    /// How the depth component of `depth_stencil_attachment` is resolved into
+    /// `depth_stencil_resolve_attachment`. If set to `None`, the depth component is not resolved.
+    ///
+    /// If set to `Some`, the mode must be supported by the
+    /// [`supported_depth_resolve_modes`](crate::device::Properties::supported_depth_resolve_modes)
+    /// device property. If both `depth_resolve_mode` and `stencil_resolve_mode` are `Some` and
+    /// they differ, the
+    /// [`independent_resolve`](crate::device::Properties::independent_resolve) device property
+    /// must be `true`. If only one of them is `Some`, the
+    /// [`independent_resolve_none`](crate::device::Properties::independent_resolve_none) device
+    /// property must be `true`.
+    ///
+    /// The default value is `None`.
+    pub depth_resolve_mode: Option<ResolveMode>,
+
+    /// How the stencil component of `depth_stencil_attachment` is resolved into
+    /// `depth_stencil_resolve_attachment`. If set to `None`, the stencil component is not
+    /// resolved.
+    ///
+    /// If set to `Some`, the mode must be supported by the
+    /// [`supported_stencil_resolve_modes`](crate::device::Properties::supported_stencil_resolve_modes)
+    /// device property.
+    ///
+    /// The default value is `None`.
+    pub stencil_resolve_mode: Option<ResolveMode>,
+
     /// The indices of attachments of the render pass that will be preserved during this subpass.
     ///
     /// The referenced attachments must not be used as any other attachment type in the subpass.
@@ -912,6 +1022,16 @@ pub struct SubpassDescription {
     /// The default value is empty.
     pub preserve_attachments: Vec<u32>,
 
+    /// The attachment of the render pass that is to be used as a fragment shading rate attachment
+    /// in this subpass.
+    ///
+    /// If set to `Some`, the
+    /// [`attachment_fragment_shading_rate`](crate::device::Features::attachment_fragment_shading_rate)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub fragment_shading_rate_attachment: Option<FragmentShadingRateAttachmentInfo>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -922,14 +1042,40 @@ impl Default for SubpassDescription {
             view_mask: 0,
             color_attachments: Vec::new(),
             depth_stencil_attachment: None,
+            depth_stencil_resolve_attachment: None,
+            depth_resolve_mode: None,
+            stencil_resolve_mode: None,
             input_attachments: Vec::new(),
             resolve_attachments: Vec::new(),
             preserve_attachments: Vec::new(),
+            fragment_shading_rate_attachment: None,
             _ne: crate::NonExhaustive(()),
         }
     }
 }
 
+/// A fragment shading rate attachment of a subpass.
+#[derive(Clone, Debug)]
+pub struct FragmentShadingRateAttachmentInfo {
+    /// The attachment of the render pass that is used as the fragment shading rate attachment.
+    ///
+    /// The layout must be [`ImageLayout::FragmentShadingRateAttachmentOptimal`] or
+    /// [`ImageLayout::General`], and the format must support the
+    /// [`FRAGMENT_SHADING_RATE_ATTACHMENT`](crate::format::FormatFeatures::FRAGMENT_SHADING_RATE_ATTACHMENT)
+    /// format feature.
+    pub attachment: AttachmentReference,
+
+    /// The width and height of the area of the framebuffer that each texel of the attachment
+    /// corresponds to.
+    ///
+    /// Both values must be powers of two, and must be between the
+    /// [`min_fragment_shading_rate_attachment_texel_size`](crate::device::Properties::min_fragment_shading_rate_attachment_texel_size)
+    /// and
+    /// [`max_fragment_shading_rate_attachment_texel_size`](crate::device::Properties::max_fragment_shading_rate_attachment_texel_size)
+    /// limits of the physical device.
+    pub texel_size: [u32; 2],
+}
+
 /// A reference in a subpass description to a particular attachment of the render pass.
 #[derive(Clone, Debug)]
 pub struct AttachmentReference {
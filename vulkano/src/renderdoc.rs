@@ -0,0 +1,260 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Triggering captures from the application when running under [RenderDoc].
+//!
+//! When an application is launched from RenderDoc, or RenderDoc is injected into it, the
+//! RenderDoc library is loaded into the process. [`RenderDoc::new`] connects to it through the
+//! in-application API, which makes it possible to capture exactly the commands of interest:
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::instance::Instance;
+//! # let instance: Arc<Instance> = return;
+//! use vulkano::renderdoc::RenderDoc;
+//!
+//! if let Ok(renderdoc) = RenderDoc::new() {
+//!     renderdoc.set_capture_file_path_template("captures/my_test");
+//!     renderdoc.start_frame_capture(&instance);
+//!
+//!     // Record and submit the commands to capture...
+//!
+//!     renderdoc.end_frame_capture(&instance);
+//! }
+//! ```
+//!
+//! RenderDoc is never loaded by this module; if the application is not running under RenderDoc,
+//! [`RenderDoc::new`] returns [`RenderDocError::NotAttached`].
+//!
+//! This module is only available with the `renderdoc` feature.
+//!
+//! [RenderDoc]: https://renderdoc.org
+
+use crate::{instance::Instance, VulkanObject};
+use ash::vk::Handle;
+use libloading::Library;
+use std::{
+    error::Error,
+    ffi::{c_void, CString},
+    fmt::{Debug, Display, Error as FmtError, Formatter},
+    os::raw::{c_char, c_int},
+    ptr,
+};
+
+/// A connection to the in-application API of RenderDoc.
+pub struct RenderDoc {
+    // Keeps the library loaded for as long as `api` is used.
+    _library: Library,
+    api: &'static RenderDocApi,
+}
+
+// The RenderDoc API is thread-safe.
+unsafe impl Send for RenderDoc {}
+unsafe impl Sync for RenderDoc {}
+
+impl RenderDoc {
+    /// Connects to RenderDoc, if it is loaded in the current process.
+    pub fn new() -> Result<RenderDoc, RenderDocError> {
+        let library = unsafe { open_renderdoc_library() }.ok_or(RenderDocError::NotAttached)?;
+
+        let api = unsafe {
+            let get_api = library
+                .get::<GetApiFn>(b"RENDERDOC_GetAPI\0")
+                .map_err(|_| RenderDocError::NotAttached)?;
+
+            let mut api: *mut c_void = ptr::null_mut();
+
+            if get_api(API_VERSION_1_1_2, &mut api) != 1 || api.is_null() {
+                return Err(RenderDocError::UnsupportedApiVersion);
+            }
+
+            &*(api as *const RenderDocApi)
+        };
+
+        Ok(RenderDoc {
+            _library: library,
+            api,
+        })
+    }
+
+    /// Returns the version of the RenderDoc API, as `(major, minor, patch)`.
+    #[inline]
+    pub fn api_version(&self) -> (u32, u32, u32) {
+        let (mut major, mut minor, mut patch) = (0, 0, 0);
+
+        unsafe {
+            (self.api.get_api_version)(&mut major, &mut minor, &mut patch);
+        }
+
+        (major as u32, minor as u32, patch as u32)
+    }
+
+    /// Sets the template for the paths of capture files. Captures are written to this path, with
+    /// a frame number and the `.rdc` extension appended.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `path_template` contains a nul byte.
+    #[inline]
+    pub fn set_capture_file_path_template(&self, path_template: &str) {
+        let path_template = CString::new(path_template).unwrap();
+
+        unsafe {
+            (self.api.set_capture_file_path_template)(path_template.as_ptr());
+        }
+    }
+
+    /// Returns the number of captures that have been made so far.
+    #[inline]
+    pub fn num_captures(&self) -> u32 {
+        unsafe { (self.api.get_num_captures)() }
+    }
+
+    /// Captures the next frame that is presented, as if the capture key was pressed.
+    #[inline]
+    pub fn trigger_capture(&self) {
+        unsafe {
+            (self.api.trigger_capture)();
+        }
+    }
+
+    /// Starts capturing all Vulkan commands of `instance`, until
+    /// [`end_frame_capture`](Self::end_frame_capture) is called.
+    ///
+    /// Unlike [`trigger_capture`](Self::trigger_capture), this does not need a swapchain, so it
+    /// can also be used to capture headless or compute work.
+    #[inline]
+    pub fn start_frame_capture(&self, instance: &Instance) {
+        unsafe {
+            (self.api.start_frame_capture)(device_pointer(instance), ptr::null_mut());
+        }
+    }
+
+    /// Returns whether a capture that was started with
+    /// [`start_frame_capture`](Self::start_frame_capture) is in progress.
+    #[inline]
+    pub fn is_frame_capturing(&self) -> bool {
+        unsafe { (self.api.is_frame_capturing)() == 1 }
+    }
+
+    /// Ends the capture of `instance` that was started with
+    /// [`start_frame_capture`](Self::start_frame_capture), and writes it to a capture file.
+    ///
+    /// Returns `false` if the capture failed.
+    #[inline]
+    pub fn end_frame_capture(&self, instance: &Instance) -> bool {
+        unsafe { (self.api.end_frame_capture)(device_pointer(instance), ptr::null_mut()) == 1 }
+    }
+}
+
+impl Debug for RenderDoc {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("RenderDoc")
+            .field("api_version", &self.api_version())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error that can happen when connecting to RenderDoc.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderDocError {
+    /// RenderDoc is not loaded in the current process.
+    NotAttached,
+
+    /// The loaded version of RenderDoc does not support the required version of its API.
+    UnsupportedApiVersion,
+}
+
+impl Error for RenderDocError {}
+
+impl Display for RenderDocError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::NotAttached => write!(f, "RenderDoc is not loaded in the current process"),
+            Self::UnsupportedApiVersion => write!(
+                f,
+                "the loaded version of RenderDoc does not support the required version of its API",
+            ),
+        }
+    }
+}
+
+/// Opens the RenderDoc library, only if it is already loaded.
+unsafe fn open_renderdoc_library() -> Option<Library> {
+    #[cfg(windows)]
+    {
+        libloading::os::windows::Library::open_already_loaded("renderdoc.dll")
+            .ok()
+            .map(Library::from)
+    }
+
+    #[cfg(unix)]
+    {
+        #[cfg(target_os = "android")]
+        const NAME: &str = "libVkLayer_GLES_RenderDoc.so";
+        #[cfg(not(target_os = "android"))]
+        const NAME: &str = "librenderdoc.so";
+
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        const RTLD_NOLOAD: c_int = 0x10;
+        #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+        const RTLD_NOLOAD: c_int = 0x4;
+
+        libloading::os::unix::Library::open(
+            Some(NAME),
+            libloading::os::unix::RTLD_NOW | RTLD_NOLOAD,
+        )
+        .ok()
+        .map(Library::from)
+    }
+
+    #[cfg(not(any(windows, unix)))]
+    {
+        None
+    }
+}
+
+/// Returns the value that RenderDoc uses to identify the Vulkan instance, which is the dispatch
+/// table pointer at the start of the instance handle.
+unsafe fn device_pointer(instance: &Instance) -> *mut c_void {
+    *(instance.handle().as_raw() as *const *mut c_void)
+}
+
+type GetApiFn = unsafe extern "C" fn(version: c_int, out_api_pointers: *mut *mut c_void) -> c_int;
+
+const API_VERSION_1_1_2: c_int = 10102;
+
+type Unused = *const c_void;
+
+/// The function table of version 1.1.2 of the RenderDoc API. Later versions only append to it.
+#[repr(C)]
+struct RenderDocApi {
+    get_api_version: unsafe extern "C" fn(major: *mut c_int, minor: *mut c_int, patch: *mut c_int),
+    set_capture_option_u32: Unused,
+    set_capture_option_f32: Unused,
+    get_capture_option_u32: Unused,
+    get_capture_option_f32: Unused,
+    set_focus_toggle_keys: Unused,
+    set_capture_keys: Unused,
+    get_overlay_bits: Unused,
+    mask_overlay_bits: Unused,
+    remove_hooks: Unused,
+    unload_crash_handler: Unused,
+    set_capture_file_path_template: unsafe extern "C" fn(path_template: *const c_char),
+    get_capture_file_path_template: Unused,
+    get_num_captures: unsafe extern "C" fn() -> u32,
+    get_capture: Unused,
+    trigger_capture: unsafe extern "C" fn(),
+    is_target_control_connected: Unused,
+    launch_replay_ui: Unused,
+    set_active_window: Unused,
+    start_frame_capture: unsafe extern "C" fn(device: *mut c_void, window: *mut c_void),
+    is_frame_capturing: unsafe extern "C" fn() -> u32,
+    end_frame_capture: unsafe extern "C" fn(device: *mut c_void, window: *mut c_void) -> u32,
+}
@@ -51,7 +51,7 @@ use crate::{
     device::{Device, DeviceOwned},
     format::FormatFeatures,
     image::{view::ImageViewType, ImageAspects, ImageViewAbstract},
-    macros::vulkan_enum,
+    macros::{vulkan_bitflags, vulkan_enum},
     pipeline::graphics::depth_stencil::CompareOp,
     shader::ShaderScalarType,
     OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
@@ -101,6 +101,7 @@ pub struct Sampler {
     device: Arc<Device>,
     id: NonZeroU64,
 
+    flags: SamplerCreateFlags,
     address_mode: [SamplerAddressMode; 3],
     anisotropy: Option<f32>,
     border_color: Option<BorderColor>,
@@ -127,6 +128,7 @@ impl Sampler {
         create_info: SamplerCreateInfo,
     ) -> Result<Arc<Sampler>, SamplerCreationError> {
         let SamplerCreateInfo {
+            flags,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -142,6 +144,9 @@ impl Sampler {
             _ne: _,
         } = create_info;
 
+        // VUID-VkSamplerCreateInfo-flags-parameter
+        flags.validate_device(&device)?;
+
         for filter in [mag_filter, min_filter] {
             // VUID-VkSamplerCreateInfo-magFilter-parameter
             // VUID-VkSamplerCreateInfo-minFilter-parameter
@@ -296,6 +301,53 @@ impl Sampler {
             }
         }
 
+        if flags.intersects(SamplerCreateFlags::SUBSAMPLED) {
+            // VUID-VkSamplerCreateInfo-flags-02574
+            if min_filter != mag_filter {
+                return Err(SamplerCreationError::SubsampledFiltersNotEqual {
+                    mag_filter,
+                    min_filter,
+                });
+            }
+
+            // VUID-VkSamplerCreateInfo-flags-02575
+            if mipmap_mode != SamplerMipmapMode::Nearest {
+                return Err(SamplerCreationError::SubsampledInvalidMipmapMode { mipmap_mode });
+            }
+
+            // VUID-VkSamplerCreateInfo-flags-02576
+            if lod != (0.0..=0.0) {
+                return Err(SamplerCreationError::SubsampledNonzeroLod { lod });
+            }
+
+            // VUID-VkSamplerCreateInfo-flags-02577
+            if address_mode[0..2].iter().any(|mode| {
+                !matches!(
+                    mode,
+                    SamplerAddressMode::ClampToEdge | SamplerAddressMode::ClampToBorder
+                )
+            }) {
+                return Err(SamplerCreationError::SubsampledInvalidAddressMode {
+                    address_mode: [address_mode[0], address_mode[1]],
+                });
+            }
+
+            // VUID-VkSamplerCreateInfo-flags-02578
+            if anisotropy.is_some() {
+                return Err(SamplerCreationError::SubsampledAnisotropyEnabled);
+            }
+
+            // VUID-VkSamplerCreateInfo-flags-02579
+            if compare.is_some() {
+                return Err(SamplerCreationError::SubsampledCompareEnabled);
+            }
+
+            // VUID-VkSamplerCreateInfo-flags-02580
+            if unnormalized_coordinates {
+                return Err(SamplerCreationError::SubsampledUnnormalizedCoordinatesEnabled);
+            }
+        }
+
         let mut sampler_reduction_mode_create_info =
             if reduction_mode != SamplerReductionMode::WeightedAverage {
                 if !(device.enabled_features().sampler_filter_minmax
@@ -393,7 +445,7 @@ impl Sampler {
         };
 
         let mut create_info = ash::vk::SamplerCreateInfo {
-            flags: ash::vk::SamplerCreateFlags::empty(),
+            flags: flags.into(),
             mag_filter: mag_filter.into(),
             min_filter: min_filter.into(),
             mipmap_mode: mipmap_mode.into(),
@@ -442,6 +494,7 @@ impl Sampler {
             handle,
             device,
             id: Self::next_id(),
+            flags,
             address_mode,
             anisotropy,
             border_color: address_mode
@@ -473,6 +526,7 @@ impl Sampler {
         create_info: SamplerCreateInfo,
     ) -> Arc<Sampler> {
         let SamplerCreateInfo {
+            flags,
             mag_filter,
             min_filter,
             mipmap_mode,
@@ -492,6 +546,7 @@ impl Sampler {
             handle,
             device,
             id: Self::next_id(),
+            flags,
             address_mode,
             anisotropy,
             border_color: address_mode
@@ -680,6 +735,12 @@ impl Sampler {
         Ok(())
     }
 
+    /// Returns the flags that the sampler was created with.
+    #[inline]
+    pub fn flags(&self) -> SamplerCreateFlags {
+        self.flags
+    }
+
     /// Returns the address modes for the u, v and w coordinates.
     #[inline]
     pub fn address_mode(&self) -> [SamplerAddressMode; 3] {
@@ -850,6 +911,33 @@ pub enum SamplerCreationError {
     /// Sampler YCbCr conversion was enabled together with unnormalized coordinates.
     SamplerYcbcrConversionUnnormalizedCoordinatesEnabled,
 
+    /// The `subsampled` flag was enabled together with anisotropy.
+    SubsampledAnisotropyEnabled,
+
+    /// The `subsampled` flag was enabled together with depth comparison.
+    SubsampledCompareEnabled,
+
+    /// The `subsampled` flag was enabled, but the min and mag filters were not equal.
+    SubsampledFiltersNotEqual {
+        mag_filter: Filter,
+        min_filter: Filter,
+    },
+
+    /// The `subsampled` flag was enabled, but the address mode for `u` or `v` was something other
+    /// than `ClampToEdge` or `ClampToBorder`.
+    SubsampledInvalidAddressMode {
+        address_mode: [SamplerAddressMode; 2],
+    },
+
+    /// The `subsampled` flag was enabled, but the mipmap mode was not `Nearest`.
+    SubsampledInvalidMipmapMode { mipmap_mode: SamplerMipmapMode },
+
+    /// The `subsampled` flag was enabled, but the LOD range was not zero.
+    SubsampledNonzeroLod { lod: RangeInclusive<f32> },
+
+    /// The `subsampled` flag was enabled together with unnormalized coordinates.
+    SubsampledUnnormalizedCoordinatesEnabled,
+
     /// Unnormalized coordinates were enabled together with anisotropy.
     UnnormalizedCoordinatesAnisotropyEnabled,
 
@@ -932,6 +1020,34 @@ impl Display for SamplerCreationError {
                 f,
                 "sampler YCbCr conversion was enabled together with unnormalized coordinates",
             ),
+            Self::SubsampledAnisotropyEnabled => write!(
+                f,
+                "the `subsampled` flag was enabled together with anisotropy",
+            ),
+            Self::SubsampledCompareEnabled => write!(
+                f,
+                "the `subsampled` flag was enabled together with depth comparison",
+            ),
+            Self::SubsampledFiltersNotEqual { .. } => write!(
+                f,
+                "the `subsampled` flag was enabled, but the min and mag filters were not equal",
+            ),
+            Self::SubsampledInvalidAddressMode { .. } => write!(
+                f,
+                "the `subsampled` flag was enabled, but the address mode for u or v was something                 other than `ClampToEdge` or `ClampToBorder`",
+            ),
+            Self::SubsampledInvalidMipmapMode { .. } => write!(
+                f,
+                "the `subsampled` flag was enabled, but the mipmap mode was not `Nearest`",
+            ),
+            Self::SubsampledNonzeroLod { .. } => write!(
+                f,
+                "the `subsampled` flag was enabled, but the LOD range was not zero",
+            ),
+            Self::SubsampledUnnormalizedCoordinatesEnabled => write!(
+                f,
+                "the `subsampled` flag was enabled together with unnormalized coordinates",
+            ),
             Self::UnnormalizedCoordinatesAnisotropyEnabled => write!(
                 f,
                 "unnormalized coordinates were enabled together with anisotropy",
@@ -990,6 +1106,11 @@ impl From<RequirementNotMet> for SamplerCreationError {
 /// Parameters to create a new `Sampler`.
 #[derive(Clone, Debug)]
 pub struct SamplerCreateInfo {
+    /// Specifies how the sampler will be used.
+    ///
+    /// The default value is empty.
+    pub flags: SamplerCreateFlags,
+
     /// How the sampled value of a single mipmap should be calculated,
     /// when magnification is applied (LOD <= 0.0).
     ///
@@ -1132,6 +1253,7 @@ impl Default for SamplerCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            flags: SamplerCreateFlags::empty(),
             mag_filter: Filter::Nearest,
             min_filter: Filter::Nearest,
             mipmap_mode: SamplerMipmapMode::Nearest,
@@ -1178,6 +1300,47 @@ impl SamplerCreateInfo {
     }
 }
 
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that can be set when creating a new sampler.
+    SamplerCreateFlags = SamplerCreateFlags(u32);
+
+    /// The sampler will be used to sample subsampled images, that were rendered to using a
+    /// fragment density map.
+    ///
+    /// The sampler must use equal `mag_filter` and `min_filter`, a `mipmap_mode` of `Nearest`,
+    /// a zero LOD range, and an address mode of `ClampToEdge` or `ClampToBorder` for u and v.
+    /// Anisotropy, depth comparison and unnormalized coordinates must not be enabled.
+    SUBSAMPLED = SUBSAMPLED_EXT {
+        device_extensions: [ext_fragment_density_map],
+    },
+
+    /// The implementation may use approximations when reconstructing a full color value from a
+    /// subsampled image.
+    SUBSAMPLED_COARSE_RECONSTRUCTION = SUBSAMPLED_COARSE_RECONSTRUCTION_EXT {
+        device_extensions: [ext_fragment_density_map],
+    },
+
+    /* TODO: enable
+    // TODO: document
+    DESCRIPTOR_BUFFER_CAPTURE_REPLAY = DESCRIPTOR_BUFFER_CAPTURE_REPLAY_EXT {
+        device_extensions: [ext_descriptor_buffer],
+    },*/
+
+    /* TODO: enable
+    // TODO: document
+    NON_SEAMLESS_CUBE_MAP = NON_SEAMLESS_CUBE_MAP_EXT {
+        device_extensions: [ext_non_seamless_cube_map],
+    },*/
+
+    /* TODO: enable
+    // TODO: document
+    IMAGE_PROCESSING = IMAGE_PROCESSING_QCOM {
+        device_extensions: [qcom_image_processing],
+    },*/
+}
+
 /// A special value to indicate that the maximum LOD should not be clamped.
 pub const LOD_CLAMP_NONE: f32 = ash::vk::LOD_CLAMP_NONE;
 
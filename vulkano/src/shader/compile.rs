@@ -0,0 +1,312 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Compiling GLSL shaders to SPIR-V at runtime.
+//!
+//! The `vulkano-shaders` crate compiles shaders when the application is built, which is not
+//! possible when the source code of the shaders is only known at runtime, for example in an
+//! editor or when shaders are loaded from files. This module uses [shaderc] to compile GLSL
+//! source code at runtime, and creates a [`ShaderModule`] from the result. The entry points of the
+//! module are reflected in the same way as with [`ShaderModule::from_words`].
+//!
+//! ```no_run
+//! # use std::sync::Arc;
+//! # use vulkano::device::Device;
+//! # let device: Arc<Device> = return;
+//! use vulkano::shader::compile::{compile, ShaderCompileInfo, ShaderKind};
+//!
+//! let source = "
+//!     #version 450
+//!
+//!     layout(local_size_x = 64) in;
+//!
+//!     layout(set = 0, binding = 0) buffer Data {
+//!         uint data[];
+//!     };
+//!
+//!     void main() {
+//!         data[gl_GlobalInvocationID.x] *= 12;
+//!     }
+//! ";
+//!
+//! let compiled_shader = compile(
+//!     device.clone(),
+//!     source,
+//!     ShaderCompileInfo::shader_kind(ShaderKind::Compute),
+//! )
+//! .unwrap();
+//! let entry_point = compiled_shader.entry_point().unwrap();
+//! ```
+//!
+//! ESSL shaders are compiled with the ES profile if the `#version` directive of the source code
+//! specifies it, for example `#version 310 es`.
+//!
+//! This module is only available with the `shaderc` feature.
+//!
+//! [shaderc]: https://github.com/google/shaderc
+
+use super::{EntryPoint, ShaderCreationError, ShaderModule};
+use crate::{device::Device, Version};
+use shaderc::{CompileOptions, Compiler, EnvVersion, TargetEnv};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+};
+
+pub use shaderc::{OptimizationLevel, ShaderKind};
+
+/// Compiles `source` to SPIR-V, and creates a shader module from it on `device`.
+///
+/// If [`target_vulkan_version`](ShaderCompileInfo::target_vulkan_version) is `None`, the API
+/// version of `device` is targeted.
+pub fn compile(
+    device: Arc<Device>,
+    source: &str,
+    mut compile_info: ShaderCompileInfo,
+) -> Result<CompiledShader, ShaderCompileError> {
+    compile_info
+        .target_vulkan_version
+        .get_or_insert(device.api_version());
+
+    let CompiledSpirv { words, warnings } = compile_to_spirv(source, &compile_info)?;
+
+    // Safety: the code was generated by shaderc, so it is valid SPIR-V.
+    let module = unsafe { ShaderModule::from_words(device, &words)? };
+
+    Ok(CompiledShader {
+        module,
+        entry_point_name: compile_info.entry_point_name,
+        warnings,
+    })
+}
+
+/// Compiles `source` to SPIR-V, without creating a shader module.
+///
+/// If [`target_vulkan_version`](ShaderCompileInfo::target_vulkan_version) is `None`, Vulkan 1.0
+/// is targeted.
+pub fn compile_to_spirv(
+    source: &str,
+    compile_info: &ShaderCompileInfo,
+) -> Result<CompiledSpirv, ShaderCompileError> {
+    let &ShaderCompileInfo {
+        shader_kind,
+        ref entry_point_name,
+        ref file_name,
+        ref macro_definitions,
+        target_vulkan_version,
+        optimization_level,
+        generate_debug_info,
+        _ne: _,
+    } = compile_info;
+
+    let compiler = Compiler::new().ok_or(ShaderCompileError::CompilerCreationFailed)?;
+    let mut compile_options =
+        CompileOptions::new().ok_or(ShaderCompileError::CompilerCreationFailed)?;
+
+    // Newer versions are not known by the bundled version of shaderc, but SPIR-V for Vulkan 1.2
+    // is still valid for them.
+    let env_version = match target_vulkan_version.unwrap_or(Version::V1_0) {
+        version if version >= Version::V1_2 => EnvVersion::Vulkan1_2,
+        version if version >= Version::V1_1 => EnvVersion::Vulkan1_1,
+        _ => EnvVersion::Vulkan1_0,
+    };
+    compile_options.set_target_env(TargetEnv::Vulkan, env_version as u32);
+
+    for (name, value) in macro_definitions {
+        compile_options.add_macro_definition(name, value.as_deref());
+    }
+
+    compile_options.set_optimization_level(optimization_level);
+
+    if generate_debug_info {
+        compile_options.set_generate_debug_info();
+    }
+
+    let artifact = compiler.compile_into_spirv(
+        source,
+        shader_kind,
+        file_name,
+        entry_point_name,
+        Some(&compile_options),
+    )?;
+
+    Ok(CompiledSpirv {
+        words: artifact.as_binary().to_vec(),
+        warnings: artifact.get_warning_messages(),
+    })
+}
+
+/// Parameters to compile a shader.
+#[derive(Clone, Debug)]
+pub struct ShaderCompileInfo {
+    /// The stage of the shader.
+    ///
+    /// If this is [`ShaderKind::InferFromSource`], the stage must be specified in the source code
+    /// with `#pragma shader_stage(...)`.
+    ///
+    /// The default value is [`ShaderKind::InferFromSource`].
+    pub shader_kind: ShaderKind,
+
+    /// The name that the `main` function of the source code is given in the SPIR-V code. This is
+    /// the name to use when looking up the entry point in the shader module.
+    ///
+    /// The default value is `"main"`.
+    pub entry_point_name: String,
+
+    /// The name of the source file, which is used in error messages.
+    ///
+    /// The default value is `"shader.glsl"`.
+    pub file_name: String,
+
+    /// Preprocessor macros to define, as if with `#define name value`.
+    ///
+    /// The default value is empty.
+    pub macro_definitions: Vec<(String, Option<String>)>,
+
+    /// The version of Vulkan that the SPIR-V code targets.
+    ///
+    /// The default value is `None`.
+    pub target_vulkan_version: Option<Version>,
+
+    /// How the SPIR-V code is optimized.
+    ///
+    /// [`OptimizationLevel::Size`] and [`OptimizationLevel::Performance`] also remove dead code,
+    /// which makes the code smaller and reduces the time it takes for the driver to compile it.
+    ///
+    /// The default value is [`OptimizationLevel::Zero`].
+    pub optimization_level: OptimizationLevel,
+
+    /// Whether to include debug information, such as variable names, in the SPIR-V code.
+    ///
+    /// The default value is `false`.
+    pub generate_debug_info: bool,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for ShaderCompileInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            shader_kind: ShaderKind::InferFromSource,
+            entry_point_name: "main".to_owned(),
+            file_name: "shader.glsl".to_owned(),
+            macro_definitions: Vec::new(),
+            target_vulkan_version: None,
+            optimization_level: OptimizationLevel::Zero,
+            generate_debug_info: false,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+impl ShaderCompileInfo {
+    /// Returns a `ShaderCompileInfo` with the specified `shader_kind`.
+    #[inline]
+    pub fn shader_kind(shader_kind: ShaderKind) -> Self {
+        Self {
+            shader_kind,
+            ..Default::default()
+        }
+    }
+}
+
+/// SPIR-V code that was produced by [`compile_to_spirv`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct CompiledSpirv {
+    /// The SPIR-V code.
+    pub words: Vec<u32>,
+
+    /// The warnings that were produced by the compiler, or an empty string if there were none.
+    pub warnings: String,
+}
+
+/// A shader module that was produced by [`compile`].
+#[derive(Debug)]
+pub struct CompiledShader {
+    module: Arc<ShaderModule>,
+    entry_point_name: String,
+    warnings: String,
+}
+
+impl CompiledShader {
+    /// Returns the shader module.
+    #[inline]
+    pub fn module(&self) -> &Arc<ShaderModule> {
+        &self.module
+    }
+
+    /// Returns the entry point that was compiled from the `main` function of the source code.
+    ///
+    /// Returns `None` if the source code does not have an unambiguous entry point, which can
+    /// happen if the source code was not a GLSL shader.
+    #[inline]
+    pub fn entry_point(&self) -> Option<EntryPoint<'_>> {
+        self.module.entry_point(&self.entry_point_name)
+    }
+
+    /// Returns the warnings that were produced by the compiler, or an empty string if there were
+    /// none.
+    #[inline]
+    pub fn warnings(&self) -> &str {
+        &self.warnings
+    }
+}
+
+/// Error that can happen when compiling a shader.
+#[derive(Debug)]
+pub enum ShaderCompileError {
+    /// The shaderc compiler could not be initialized.
+    CompilerCreationFailed,
+
+    /// Compiling the source code failed.
+    CompilationFailed(shaderc::Error),
+
+    /// Creating the shader module from the compiled code failed.
+    ShaderCreationError(ShaderCreationError),
+}
+
+impl Error for ShaderCompileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::CompilationFailed(err) => Some(err),
+            Self::ShaderCreationError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ShaderCompileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::CompilerCreationFailed => {
+                write!(f, "the shaderc compiler could not be initialized")
+            }
+            Self::CompilationFailed(_) => write!(f, "compiling the source code failed"),
+            Self::ShaderCreationError(_) => write!(
+                f,
+                "creating the shader module from the compiled code failed",
+            ),
+        }
+    }
+}
+
+impl From<shaderc::Error> for ShaderCompileError {
+    fn from(err: shaderc::Error) -> Self {
+        Self::CompilationFailed(err)
+    }
+}
+
+impl From<ShaderCreationError> for ShaderCompileError {
+    fn from(err: ShaderCreationError) -> Self {
+        Self::ShaderCreationError(err)
+    }
+}
@@ -24,26 +24,33 @@ use crate::{
     image::view::ImageViewType,
     macros::vulkan_bitflags_enum,
     pipeline::{graphics::input_assembly::PrimitiveTopology, layout::PushConstantRange},
-    shader::spirv::{Capability, Spirv, SpirvError},
+    shader::{
+        spirv::{Capability, Instruction, Spirv, SpirvError},
+        validation_cache::ValidationCache,
+    },
     sync::PipelineStages,
-    DeviceSize, OomError, Version, VulkanError, VulkanObject,
+    DeviceSize, OomError, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use ahash::{HashMap, HashSet};
+use half::f16;
 use std::{
     borrow::Cow,
-    collections::hash_map::Entry,
+    collections::{hash_map::Entry, BTreeMap},
     error::Error,
     ffi::{CStr, CString},
     fmt::{Display, Error as FmtError, Formatter},
     mem,
     mem::MaybeUninit,
     num::NonZeroU64,
-    ptr,
+    ptr, slice,
     sync::Arc,
 };
 
+#[cfg(feature = "shaderc")]
+pub mod compile;
 pub mod reflect;
 pub mod spirv;
+pub mod validation_cache;
 
 use spirv::ExecutionModel;
 
@@ -63,6 +70,9 @@ impl ShaderModule {
     /// Builds a new shader module from SPIR-V 32-bit words. The shader code is parsed and the
     /// necessary information is extracted from it.
     ///
+    /// If the `validate_spirv` feature is enabled, the code is also checked with
+    /// [`Spirv::validate`], and an error is returned if it is malformed.
+    ///
     /// # Safety
     ///
     /// - The SPIR-V code is not validated beyond the minimum needed to extract the information.
@@ -72,6 +82,8 @@ impl ShaderModule {
         words: &[u32],
     ) -> Result<Arc<ShaderModule>, ShaderCreationError> {
         let spirv = Spirv::new(words)?;
+        #[cfg(feature = "validate_spirv")]
+        spirv.validate()?;
 
         Self::from_words_with_data(
             device,
@@ -83,6 +95,38 @@ impl ShaderModule {
         )
     }
 
+    /// As `from_words`, but uses `validation_cache` to store the results of validating the shader
+    /// in the validation layers, or to skip the validation if the results are already in the
+    /// cache.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `validation_cache` does not belong to `device`.
+    ///
+    /// # Safety
+    ///
+    /// - The SPIR-V code is not validated beyond the minimum needed to extract the information.
+    #[inline]
+    pub unsafe fn from_words_with_validation_cache(
+        device: Arc<Device>,
+        words: &[u32],
+        validation_cache: &ValidationCache,
+    ) -> Result<Arc<ShaderModule>, ShaderCreationError> {
+        let spirv = Spirv::new(words)?;
+        #[cfg(feature = "validate_spirv")]
+        spirv.validate()?;
+
+        Self::from_words_with_data_impl(
+            device,
+            words,
+            spirv.version(),
+            reflect::spirv_capabilities(&spirv),
+            reflect::spirv_extensions(&spirv),
+            reflect::entry_points(&spirv),
+            Some(validation_cache),
+        )
+    }
+
     /// As `from_words`, but takes a slice of bytes.
     ///
     /// # Panics
@@ -120,6 +164,31 @@ impl ShaderModule {
         spirv_extensions: impl IntoIterator<Item = &'a str>,
         entry_points: impl IntoIterator<Item = (String, ExecutionModel, EntryPointInfo)>,
     ) -> Result<Arc<ShaderModule>, ShaderCreationError> {
+        Self::from_words_with_data_impl(
+            device,
+            words,
+            spirv_version,
+            spirv_capabilities,
+            spirv_extensions,
+            entry_points,
+            None,
+        )
+    }
+
+    unsafe fn from_words_with_data_impl<'a>(
+        device: Arc<Device>,
+        words: &[u32],
+        spirv_version: Version,
+        spirv_capabilities: impl IntoIterator<Item = &'a Capability>,
+        spirv_extensions: impl IntoIterator<Item = &'a str>,
+        entry_points: impl IntoIterator<Item = (String, ExecutionModel, EntryPointInfo)>,
+        validation_cache: Option<&ValidationCache>,
+    ) -> Result<Arc<ShaderModule>, ShaderCreationError> {
+        if let Some(validation_cache) = validation_cache {
+            // VUID-VkShaderModuleValidationCacheCreateInfoEXT-validationCache-parameter
+            assert_eq!(&device, validation_cache.device());
+        }
+
         if let Err(reason) = check_spirv_version(&device, spirv_version) {
             return Err(ShaderCreationError::SpirvVersionNotSupported {
                 version: spirv_version,
@@ -127,6 +196,8 @@ impl ShaderModule {
             });
         }
 
+        let mut uses_shader_clock = false;
+
         for &capability in spirv_capabilities {
             if let Err(reason) = check_spirv_capability(&device, capability) {
                 return Err(ShaderCreationError::SpirvCapabilityNotSupported {
@@ -134,6 +205,8 @@ impl ShaderModule {
                     reason,
                 });
             }
+
+            uses_shader_clock |= capability == Capability::ShaderClockKHR;
         }
 
         for extension in spirv_extensions {
@@ -145,13 +218,29 @@ impl ShaderModule {
             }
         }
 
+        if uses_shader_clock {
+            Self::validate_read_clock(&device, &Spirv::new(words)?)?;
+        }
+
         let handle = {
-            let infos = ash::vk::ShaderModuleCreateInfo {
+            let mut infos = ash::vk::ShaderModuleCreateInfo {
                 flags: ash::vk::ShaderModuleCreateFlags::empty(),
                 code_size: words.len() * mem::size_of::<u32>(),
                 p_code: words.as_ptr(),
                 ..Default::default()
             };
+            let mut validation_cache_info_vk = None;
+
+            if let Some(validation_cache) = validation_cache {
+                let next = validation_cache_info_vk.insert(
+                    ash::vk::ShaderModuleValidationCacheCreateInfoEXT {
+                        validation_cache: validation_cache.handle(),
+                        ..Default::default()
+                    },
+                );
+
+                infos.p_next = next as *const _ as *const _;
+            }
 
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -197,6 +286,57 @@ impl ShaderModule {
         }))
     }
 
+    /// Checks that the device supports reading the shader clock with the scopes that the shader
+    /// uses.
+    fn validate_read_clock(device: &Device, spirv: &Spirv) -> Result<(), ShaderCreationError> {
+        // Values of the SPIR-V `Scope` enum.
+        const SCOPE_DEVICE: u32 = 1;
+        const SCOPE_SUBGROUP: u32 = 3;
+
+        for instruction in spirv.instructions() {
+            let scope = match *instruction {
+                Instruction::ReadClockKHR { scope, .. } => scope,
+                _ => continue,
+            };
+
+            // The scope can also be a specialization constant, which can't be checked here.
+            let scope = match *spirv.id(scope).instruction() {
+                Instruction::Constant { ref value, .. } => value[0],
+                _ => continue,
+            };
+
+            match scope {
+                SCOPE_SUBGROUP => {
+                    // VUID-RuntimeSpirv-shaderSubgroupClock-06267
+                    if !device.enabled_features().shader_subgroup_clock {
+                        return Err(ShaderCreationError::RequirementNotMet {
+                            required_for: "the shader reads the clock with the `Subgroup` scope",
+                            requires_one_of: RequiresOneOf {
+                                features: &["shader_subgroup_clock"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+                SCOPE_DEVICE => {
+                    // VUID-RuntimeSpirv-shaderDeviceClock-06268
+                    if !device.enabled_features().shader_device_clock {
+                        return Err(ShaderCreationError::RequirementNotMet {
+                            required_for: "the shader reads the clock with the `Device` scope",
+                            requires_one_of: RequiresOneOf {
+                                features: &["shader_device_clock"],
+                                ..Default::default()
+                            },
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
     /// As `from_words_with_data`, but takes a slice of bytes.
     ///
     /// # Panics
@@ -228,6 +368,10 @@ impl ShaderModule {
     /// Returns information about the entry point with the provided name. Returns `None` if no entry
     /// point with that name exists in the shader module or if multiple entry points with the same
     /// name exist.
+    ///
+    /// If the module contains entry points with the same name for different shader stages, use
+    /// [`entry_point_with_stage`](Self::entry_point_with_stage) or
+    /// [`entry_point_with_execution`](Self::entry_point_with_execution) instead.
     #[inline]
     pub fn entry_point<'a>(&'a self, name: &str) -> Option<EntryPoint<'a>> {
         self.entry_points.get(name).and_then(|infos| {
@@ -259,6 +403,41 @@ impl ShaderModule {
             })
         })
     }
+
+    /// Returns information about the entry point with the provided name, that is executed in
+    /// `stage`. Returns `None` if no such entry point exists in the shader module.
+    #[inline]
+    pub fn entry_point_with_stage<'a>(
+        &'a self,
+        name: &str,
+        stage: ShaderStage,
+    ) -> Option<EntryPoint<'a>> {
+        self.entry_points.get(name).and_then(|infos| {
+            infos
+                .values()
+                .find(|info| ShaderStage::from(info.execution) == stage)
+                .map(|info| EntryPoint {
+                    module: self,
+                    name: CString::new(name).unwrap(),
+                    info,
+                })
+        })
+    }
+
+    /// Returns an iterator over all the entry points in the shader module.
+    ///
+    /// A module can contain several entry points, possibly for different shader stages. This is
+    /// common for modules produced by toolchains such as DXC or rust-gpu.
+    #[inline]
+    pub fn entry_points(&self) -> impl Iterator<Item = EntryPoint<'_>> {
+        self.entry_points.iter().flat_map(move |(name, infos)| {
+            infos.values().map(move |info| EntryPoint {
+                module: self,
+                name: CString::new(name.as_str()).unwrap(),
+                info,
+            })
+        })
+    }
 }
 
 impl Drop for ShaderModule {
@@ -290,9 +469,13 @@ unsafe impl DeviceOwned for ShaderModule {
 crate::impl_id_counter!(ShaderModule);
 
 /// Error that can happen when creating a new shader module.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ShaderCreationError {
     OomError(OomError),
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
     SpirvCapabilityNotSupported {
         capability: Capability,
         reason: ShaderSupportError,
@@ -312,6 +495,7 @@ impl Error for ShaderCreationError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             Self::OomError(err) => Some(err),
+            Self::RequirementNotMet { .. } => None,
             Self::SpirvCapabilityNotSupported { reason, .. } => Some(reason),
             Self::SpirvError(err) => Some(err),
             Self::SpirvExtensionNotSupported { reason, .. } => Some(reason),
@@ -324,6 +508,14 @@ impl Display for ShaderCreationError {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
         match self {
             Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
             Self::SpirvCapabilityNotSupported { capability, .. } => write!(
                 f,
                 "the SPIR-V capability {:?} enabled by the shader is not supported by the device",
@@ -357,7 +549,7 @@ impl From<SpirvError> for ShaderCreationError {
 }
 
 /// Error that can happen when checking whether a shader is supported by a device.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ShaderSupportError {
     NotSupportedByVulkan,
     RequirementsNotMet(&'static [&'static str]),
@@ -464,10 +656,10 @@ impl<'a> EntryPoint<'a> {
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum ShaderExecution {
     Vertex,
-    TessellationControl,
-    TessellationEvaluation,
+    TessellationControl(TessellationShaderExecution),
+    TessellationEvaluation(TessellationShaderExecution),
     Geometry(GeometryShaderExecution),
-    Fragment,
+    Fragment(FragmentShaderExecution),
     Compute,
     RayGeneration,
     AnyHit,
@@ -480,19 +672,28 @@ pub enum ShaderExecution {
     SubpassShading,
 }
 
-/*#[derive(Clone, Copy, Debug)]
+/// The mode in which a tessellation control or evaluation shader executes.
+///
+/// The execution modes can be declared in either of the two tessellation shaders, or in both if
+/// they agree. The modes of the control and evaluation shader together must specify both the
+/// number of output vertices and the subdivision.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct TessellationShaderExecution {
-    pub num_output_vertices: u32,
-    pub point_mode: bool,
-    pub subdivision: TessellationShaderSubdivision,
+    /// The number of vertices in the output patch of the tessellation control shader, if declared
+    /// by this shader.
+    pub output_vertices: Option<u32>,
+
+    /// The type of subdivision that the tessellator performs, if declared by this shader.
+    pub subdivision: Option<TessellationShaderSubdivision>,
 }
 
+/// The type of subdivision that is performed by the tessellator.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum TessellationShaderSubdivision {
     Triangles,
     Quads,
     Isolines,
-}*/
+}
 
 /// The mode in which a geometry shader executes.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -543,6 +744,51 @@ impl GeometryShaderInput {
     }
 }
 
+/// The mode in which a fragment shader executes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FragmentShaderExecution {
+    /// The kind of critical section that the shader declares with an interlock execution mode,
+    /// if any.
+    pub interlock: Option<FragmentShaderInterlock>,
+}
+
+/// The kind of critical section that a fragment shader declares, between the
+/// `beginInvocationInterlockARB` and `endInvocationInterlockARB` calls in GLSL.
+///
+/// Within the critical section, accesses to memory are guaranteed not to overlap with the
+/// critical sections of other fragment shader invocations that cover the same pixel, sample or
+/// shading rate region. For the ordered variants, the critical sections are also executed in
+/// primitive order. This can be used for order-dependent techniques such as programmable
+/// blending and order-independent transparency.
+///
+/// Using these requires the
+/// [`fragment_shader_pixel_interlock`](crate::device::Features::fragment_shader_pixel_interlock),
+/// [`fragment_shader_sample_interlock`](crate::device::Features::fragment_shader_sample_interlock)
+/// or
+/// [`fragment_shader_shading_rate_interlock`](crate::device::Features::fragment_shader_shading_rate_interlock)
+/// feature respectively.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FragmentShaderInterlock {
+    /// Invocations that cover the same pixel are interlocked, in primitive order.
+    PixelOrdered,
+
+    /// Invocations that cover the same pixel are interlocked, in no particular order.
+    PixelUnordered,
+
+    /// Invocations that cover the same sample are interlocked, in primitive order.
+    SampleOrdered,
+
+    /// Invocations that cover the same sample are interlocked, in no particular order.
+    SampleUnordered,
+
+    /// Invocations that cover the same shading rate region are interlocked, in primitive order.
+    ShadingRateOrdered,
+
+    /// Invocations that cover the same shading rate region are interlocked, in no particular
+    /// order.
+    ShadingRateUnordered,
+}
+
 /*#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum GeometryShaderOutput {
     Points,
@@ -769,6 +1015,95 @@ impl Display for DescriptorBindingRequirementsIncompatible {
 #[derive(Clone, Copy, Debug)]
 pub struct SpecializationConstantRequirements {
     pub size: DeviceSize,
+
+    /// The value of the constant in the shader, which is used if it is not specialized. The type
+    /// of a value given at runtime must match the type of this value.
+    ///
+    /// This is `None` if the type of the constant is not supported by [`SpecializationConstant`].
+    pub default_value: Option<SpecializationConstant>,
+}
+
+/// The value of a single specialization constant.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpecializationConstant {
+    Bool(bool),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    F16(f16),
+    F32(f32),
+    F64(f64),
+}
+
+impl SpecializationConstant {
+    /// Returns whether `self` and `other` have the same type.
+    #[inline]
+    pub fn has_same_type(&self, other: &Self) -> bool {
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+
+    /// Returns the size of the value as it is passed to Vulkan. Booleans are passed as 32-bit
+    /// integers.
+    #[inline]
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Bool(_) => mem::size_of::<ash::vk::Bool32>(),
+            Self::I8(_) | Self::U8(_) => 1,
+            Self::I16(_) | Self::U16(_) | Self::F16(_) => 2,
+            Self::I32(_) | Self::U32(_) | Self::F32(_) => 4,
+            Self::I64(_) | Self::U64(_) | Self::F64(_) => 8,
+        }
+    }
+
+    fn extend_bytes(&self, data: &mut Vec<u8>) {
+        match *self {
+            Self::Bool(value) => data.extend_from_slice(&(value as ash::vk::Bool32).to_ne_bytes()),
+            Self::I8(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::I16(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::I32(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::I64(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::U8(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::U16(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::U32(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::U64(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::F16(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::F32(value) => data.extend_from_slice(&value.to_ne_bytes()),
+            Self::F64(value) => data.extend_from_slice(&value.to_ne_bytes()),
+        }
+    }
+}
+
+macro_rules! specialization_constant_from {
+    ($($ty:ty => $variant:ident,)+) => {
+        $(
+            impl From<$ty> for SpecializationConstant {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    SpecializationConstant::$variant(value)
+                }
+            }
+        )+
+    };
+}
+
+specialization_constant_from! {
+    bool => Bool,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    f16 => F16,
+    f32 => F32,
+    f64 => F64,
 }
 
 /// Trait for types that contain specialization data for shaders.
@@ -783,7 +1118,8 @@ pub struct SpecializationConstantRequirements {
 /// any non-zero value means `true`. Integer and floating-point specialization constants are
 /// stored as their Rust equivalent.
 ///
-/// This trait is implemented on `()` for shaders that don't have any specialization constant.
+/// This trait is implemented on `()` for shaders that don't have any specialization constant,
+/// and on [`SpecializationConstantMap`] for constants whose values are chosen at runtime.
 ///
 /// # Examples
 ///
@@ -831,6 +1167,34 @@ pub struct SpecializationConstantRequirements {
 pub unsafe trait SpecializationConstants {
     /// Returns descriptors of the struct's layout.
     fn descriptors() -> &'static [SpecializationMapEntry];
+
+    /// Returns the map entries of `self`, which describe where each constant is found in
+    /// [`data`](Self::data).
+    ///
+    /// The default implementation returns [`descriptors`](Self::descriptors). It only needs to be
+    /// overridden by types whose layout is not known at compile time.
+    #[inline]
+    fn map_entries(&self) -> &[SpecializationMapEntry] {
+        Self::descriptors()
+    }
+
+    /// Returns the data that the map entries refer to.
+    ///
+    /// The default implementation returns the memory of `self`.
+    #[inline]
+    fn data(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self as *const Self as *const u8, mem::size_of_val(self)) }
+    }
+
+    /// Returns the value of the constant with the given `constant_id`, if it is known at runtime.
+    ///
+    /// This is used to check that the value has the same type as the constant in the shader. The
+    /// default implementation returns `None`, in which case only the size of the value is
+    /// checked.
+    #[inline]
+    fn value(&self, _constant_id: u32) -> Option<SpecializationConstant> {
+        None
+    }
 }
 
 unsafe impl SpecializationConstants for () {
@@ -840,6 +1204,176 @@ unsafe impl SpecializationConstants for () {
     }
 }
 
+/// Specialization constants whose values are chosen at runtime.
+///
+/// The struct generated by `vulkano-shaders` fixes the set of constants and their types when the
+/// application is compiled. A `SpecializationConstantMap` instead holds any number of constants,
+/// which is useful when the values come from runtime settings or when the shader is only loaded at
+/// runtime. When a pipeline is created, the type of each value is checked against the type of the
+/// constant in the shader.
+///
+/// Constants that are not in the map keep the value that they have in the shader.
+/// [`from_default_values`](Self::from_default_values) returns a map with these values, which is
+/// useful to see which constants a shader has and what their types are.
+///
+/// ```
+/// # use vulkano::shader::EntryPoint;
+/// # let entry_point: EntryPoint<'_> = return;
+/// use vulkano::shader::SpecializationConstantMap;
+///
+/// let mut specialization_constants = SpecializationConstantMap::from_default_values(&entry_point);
+/// specialization_constants.insert(0, 8u32);
+/// specialization_constants.insert(1, true);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SpecializationConstantMap {
+    values: BTreeMap<u32, SpecializationConstant>,
+    map_entries: Vec<SpecializationMapEntry>,
+    data: Vec<u8>,
+}
+
+impl SpecializationConstantMap {
+    /// Returns an empty `SpecializationConstantMap`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a `SpecializationConstantMap` with the default value of each specialization
+    /// constant of `entry_point`, as it is given in the shader.
+    ///
+    /// Constants with a type that is not supported by [`SpecializationConstant`] are skipped.
+    pub fn from_default_values(entry_point: &EntryPoint<'_>) -> Self {
+        entry_point
+            .specialization_constant_requirements()
+            .filter_map(|(constant_id, reqs)| Some((constant_id, reqs.default_value?)))
+            .collect()
+    }
+
+    /// Sets the value of the constant with the given `constant_id`, and returns the previous
+    /// value.
+    pub fn insert(
+        &mut self,
+        constant_id: u32,
+        value: impl Into<SpecializationConstant>,
+    ) -> Option<SpecializationConstant> {
+        let previous = self.values.insert(constant_id, value.into());
+        self.update_data();
+        previous
+    }
+
+    /// Removes the constant with the given `constant_id`, and returns its value.
+    pub fn remove(&mut self, constant_id: u32) -> Option<SpecializationConstant> {
+        let previous = self.values.remove(&constant_id);
+        self.update_data();
+        previous
+    }
+
+    /// Returns the value of the constant with the given `constant_id`.
+    #[inline]
+    pub fn get(&self, constant_id: u32) -> Option<SpecializationConstant> {
+        self.values.get(&constant_id).copied()
+    }
+
+    /// Returns an iterator over the constants, ordered by `constant_id`.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (u32, SpecializationConstant)> + '_ {
+        self.values
+            .iter()
+            .map(|(&constant_id, &value)| (constant_id, value))
+    }
+
+    fn update_data(&mut self) {
+        self.map_entries.clear();
+        self.data.clear();
+
+        for (&constant_id, value) in &self.values {
+            self.map_entries.push(SpecializationMapEntry {
+                constant_id,
+                offset: self.data.len() as u32,
+                size: value.size(),
+            });
+            value.extend_bytes(&mut self.data);
+        }
+    }
+}
+
+impl<V> FromIterator<(u32, V)> for SpecializationConstantMap
+where
+    V: Into<SpecializationConstant>,
+{
+    fn from_iter<T: IntoIterator<Item = (u32, V)>>(iter: T) -> Self {
+        let mut map = Self {
+            values: iter
+                .into_iter()
+                .map(|(constant_id, value)| (constant_id, value.into()))
+                .collect(),
+            map_entries: Vec::new(),
+            data: Vec::new(),
+        };
+        map.update_data();
+        map
+    }
+}
+
+unsafe impl SpecializationConstants for SpecializationConstantMap {
+    /// Returns an empty slice, because the constants are only known at runtime.
+    #[inline]
+    fn descriptors() -> &'static [SpecializationMapEntry] {
+        &[]
+    }
+
+    #[inline]
+    fn map_entries(&self) -> &[SpecializationMapEntry] {
+        &self.map_entries
+    }
+
+    #[inline]
+    fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    #[inline]
+    fn value(&self, constant_id: u32) -> Option<SpecializationConstant> {
+        self.get(constant_id)
+    }
+}
+
+/// Returns whether the values in `specialization_constants` have the correct size for the
+/// specialization constants of `entry_point`, and the correct type if the value is known.
+///
+/// Constants that are not given a value keep the value that they have in the shader.
+pub(crate) fn specialization_constants_compatible(
+    entry_point: &EntryPoint<'_>,
+    specialization_constants: &(impl SpecializationConstants + ?Sized),
+) -> bool {
+    let map_entries = specialization_constants.map_entries();
+
+    entry_point
+        .specialization_constant_requirements()
+        .all(|(constant_id, reqs)| {
+            let map_entry = match map_entries
+                .iter()
+                .find(|map_entry| map_entry.constant_id == constant_id)
+            {
+                Some(map_entry) => map_entry,
+                None => return true,
+            };
+
+            if map_entry.size as DeviceSize != reqs.size {
+                return false;
+            }
+
+            match (
+                specialization_constants.value(constant_id),
+                reqs.default_value,
+            ) {
+                (Some(value), Some(default_value)) => value.has_same_type(&default_value),
+                _ => true,
+            }
+        })
+}
+
 /// Describes an individual constant to set in the shader. Also a field in the struct.
 // Implementation note: has the same memory representation as a `VkSpecializationMapEntry`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -1186,10 +1720,10 @@ impl From<ShaderExecution> for ShaderStage {
     fn from(val: ShaderExecution) -> Self {
         match val {
             ShaderExecution::Vertex => Self::Vertex,
-            ShaderExecution::TessellationControl => Self::TessellationControl,
-            ShaderExecution::TessellationEvaluation => Self::TessellationEvaluation,
+            ShaderExecution::TessellationControl(_) => Self::TessellationControl,
+            ShaderExecution::TessellationEvaluation(_) => Self::TessellationEvaluation,
             ShaderExecution::Geometry(_) => Self::Geometry,
-            ShaderExecution::Fragment => Self::Fragment,
+            ShaderExecution::Fragment(_) => Self::Fragment,
             ShaderExecution::Compute => Self::Compute,
             ShaderExecution::RayGeneration => Self::Raygen,
             ShaderExecution::AnyHit => Self::AnyHit,
@@ -8,6 +8,48 @@
 // according to those terms.
 
 //! Extraction of information from SPIR-V modules, that is needed by the rest of Vulkano.
+//!
+//! The functions of this module can also be used directly, for example to build descriptor set
+//! layouts and pipeline layouts for shaders that are loaded at runtime. [`ShaderReflection`]
+//! gathers all the information about a SPIR-V module at once:
+//!
+//! ```
+//! # let words: Vec<u32> = return;
+//! use vulkano::{
+//!     descriptor_set::layout::DescriptorSetLayoutCreateInfo,
+//!     shader::reflect::{self, ShaderReflection},
+//! };
+//!
+//! let reflection = ShaderReflection::from_words(&words).unwrap();
+//!
+//! for entry_point in &reflection.entry_points {
+//!     println!("{} ({:?})", entry_point.name, entry_point.execution_model);
+//!
+//!     for (&(set, binding), reqs) in &entry_point.info.descriptor_binding_requirements {
+//!         println!(
+//!             "set {} binding {}: {:?} x {:?}, used by {:?}",
+//!             set, binding, reqs.descriptor_types, reqs.descriptor_count, reqs.stages,
+//!         );
+//!     }
+//! }
+//!
+//! // Combine the requirements of all entry points to create the layouts of a pipeline.
+//! let descriptor_binding_requirements = reflect::merge_descriptor_binding_requirements(
+//!     reflection.entry_points.iter().flat_map(|entry_point| {
+//!         entry_point.info.descriptor_binding_requirements.iter().map(|(&k, v)| (k, v))
+//!     }),
+//! )
+//! .unwrap();
+//! let set_layout_create_infos = DescriptorSetLayoutCreateInfo::from_requirements(
+//!     descriptor_binding_requirements.iter().map(|(&k, v)| (k, v)),
+//! );
+//! let push_constant_ranges = reflect::merge_push_constant_ranges(
+//!     reflection
+//!         .entry_points
+//!         .iter()
+//!         .filter_map(|entry_point| entry_point.info.push_constant_requirements.as_ref()),
+//! );
+//! ```
 
 use super::DescriptorBindingRequirements;
 use crate::{
@@ -17,17 +59,155 @@ use crate::{
     shader::{
         spirv::{
             Capability, Decoration, Dim, ExecutionMode, ExecutionModel, Id, Instruction, Spirv,
-            StorageClass,
+            SpirvError, StorageClass,
         },
-        DescriptorIdentifier, DescriptorRequirements, EntryPointInfo, GeometryShaderExecution,
+        DescriptorBindingRequirementsIncompatible, DescriptorIdentifier, DescriptorRequirements,
+        EntryPointInfo, FragmentShaderExecution, FragmentShaderInterlock, GeometryShaderExecution,
         GeometryShaderInput, ShaderExecution, ShaderInterface, ShaderInterfaceEntry,
-        ShaderInterfaceEntryType, ShaderScalarType, ShaderStage,
-        SpecializationConstantRequirements,
+        ShaderInterfaceEntryType, ShaderScalarType, ShaderStage, SpecializationConstant,
+        SpecializationConstantRequirements, TessellationShaderExecution,
+        TessellationShaderSubdivision,
     },
-    DeviceSize,
+    DeviceSize, Version,
 };
 use ahash::{HashMap, HashSet};
-use std::borrow::Cow;
+use half::f16;
+use std::{borrow::Cow, collections::hash_map::Entry};
+
+/// Information about a SPIR-V module and all of its entry points.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ShaderReflection {
+    /// The version of SPIR-V that the module uses.
+    pub version: Version,
+
+    /// The SPIR-V capabilities that the module enables.
+    pub capabilities: Vec<Capability>,
+
+    /// The SPIR-V extensions that the module enables.
+    pub extensions: Vec<String>,
+
+    /// The entry points of the module.
+    pub entry_points: Vec<ReflectedEntryPoint>,
+}
+
+impl ShaderReflection {
+    /// Parses `words` as a SPIR-V module, and extracts information about it.
+    pub fn from_words(words: &[u32]) -> Result<Self, SpirvError> {
+        let spirv = Spirv::new(words)?;
+
+        Ok(Self::from_spirv(&spirv))
+    }
+
+    /// Extracts information about an already parsed SPIR-V module.
+    pub fn from_spirv(spirv: &Spirv) -> Self {
+        Self {
+            version: spirv.version(),
+            capabilities: spirv_capabilities(spirv).cloned().collect(),
+            extensions: spirv_extensions(spirv).map(ToOwned::to_owned).collect(),
+            entry_points: entry_points(spirv)
+                .map(|(name, execution_model, info)| ReflectedEntryPoint {
+                    name,
+                    execution_model,
+                    info,
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the entry point with the provided name. Returns `None` if no entry point with that
+    /// name exists, or if multiple entry points with the same name exist.
+    pub fn entry_point(&self, name: &str) -> Option<&ReflectedEntryPoint> {
+        let mut iter = self
+            .entry_points
+            .iter()
+            .filter(|entry_point| entry_point.name == name);
+
+        match (iter.next(), iter.next()) {
+            (Some(entry_point), None) => Some(entry_point),
+            _ => None,
+        }
+    }
+}
+
+/// Information about an entry point of a SPIR-V module.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct ReflectedEntryPoint {
+    /// The name of the entry point.
+    pub name: String,
+
+    /// The execution model of the entry point, which determines the shader stage.
+    pub execution_model: ExecutionModel,
+
+    /// The requirements and interfaces of the entry point.
+    pub info: EntryPointInfo,
+}
+
+/// Merges the descriptor binding requirements of several shaders, such as the shaders of all
+/// stages of a pipeline, so that the result satisfies all of them.
+///
+/// The result can be passed to
+/// [`DescriptorSetLayoutCreateInfo::from_requirements`](crate::descriptor_set::layout::DescriptorSetLayoutCreateInfo::from_requirements).
+/// An error is returned if two shaders have conflicting requirements for the same binding.
+pub fn merge_descriptor_binding_requirements<'a>(
+    descriptor_binding_requirements: impl IntoIterator<
+        Item = ((u32, u32), &'a DescriptorBindingRequirements),
+    >,
+) -> Result<
+    HashMap<(u32, u32), DescriptorBindingRequirements>,
+    DescriptorBindingRequirementsIncompatible,
+> {
+    let mut merged: HashMap<(u32, u32), DescriptorBindingRequirements> = HashMap::default();
+
+    for (loc, reqs) in descriptor_binding_requirements {
+        match merged.entry(loc) {
+            Entry::Occupied(entry) => {
+                // Previous shaders already added requirements, so we merge requirements of
+                // the current shader into the requirements of the previous one.
+                entry.into_mut().merge(reqs)?;
+            }
+            Entry::Vacant(entry) => {
+                // No previous shader had this descriptor yet, so we just insert the
+                // requirements.
+                entry.insert(reqs.clone());
+            }
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Merges the push constant ranges of several shaders, such as the shaders of all stages of a
+/// pipeline, into a list of ranges for a pipeline layout.
+///
+/// Ranges with the same offset and size are combined into one range that is available to the
+/// stages of all of them.
+pub fn merge_push_constant_ranges<'a>(
+    push_constant_ranges: impl IntoIterator<Item = &'a PushConstantRange>,
+) -> Vec<PushConstantRange> {
+    let mut range_map = HashMap::default();
+
+    for range in push_constant_ranges {
+        match range_map.entry((range.offset, range.size)) {
+            Entry::Vacant(entry) => {
+                entry.insert(range.stages);
+            }
+            Entry::Occupied(mut entry) => {
+                *entry.get_mut() = *entry.get() | range.stages;
+            }
+        }
+    }
+
+    range_map
+        .into_iter()
+        .map(|((offset, size), stages)| PushConstantRange {
+            stages,
+            offset,
+            size,
+        })
+        .collect()
+}
 
 /// Returns an iterator of the capabilities used by `spirv`.
 #[inline]
@@ -79,7 +259,7 @@ pub fn entry_points(
             stage,
             function_id,
         );
-        let push_constant_requirements = push_constant_requirements(spirv, stage);
+        let push_constant_requirements = push_constant_requirements(spirv, stage, interface);
         let specialization_constant_requirements = specialization_constant_requirements(spirv);
         let input_interface = shader_interface(
             spirv,
@@ -114,7 +294,39 @@ pub fn entry_points(
     })
 }
 
-/// Extracts the `ShaderExecution` for the entry point `function_id` from `spirv`.
+/// Extracts the `TessellationShaderExecution` for the entry point `function_id` from `spirv`.
+fn tessellation_shader_execution(spirv: &Spirv, function_id: Id) -> TessellationShaderExecution {
+    let mut execution = TessellationShaderExecution {
+        output_vertices: None,
+        subdivision: None,
+    };
+
+    for instruction in spirv.iter_execution_mode() {
+        match instruction {
+            Instruction::ExecutionMode {
+                entry_point, mode, ..
+            } if *entry_point == function_id => match *mode {
+                ExecutionMode::OutputVertices { vertex_count } => {
+                    execution.output_vertices = Some(vertex_count);
+                }
+                ExecutionMode::Triangles => {
+                    execution.subdivision = Some(TessellationShaderSubdivision::Triangles);
+                }
+                ExecutionMode::Quads => {
+                    execution.subdivision = Some(TessellationShaderSubdivision::Quads);
+                }
+                ExecutionMode::Isolines => {
+                    execution.subdivision = Some(TessellationShaderSubdivision::Isolines);
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+
+    execution
+}
+
 fn shader_execution(
     spirv: &Spirv,
     execution_model: ExecutionModel,
@@ -123,9 +335,13 @@ fn shader_execution(
     match execution_model {
         ExecutionModel::Vertex => ShaderExecution::Vertex,
 
-        ExecutionModel::TessellationControl => ShaderExecution::TessellationControl,
+        ExecutionModel::TessellationControl => {
+            ShaderExecution::TessellationControl(tessellation_shader_execution(spirv, function_id))
+        }
 
-        ExecutionModel::TessellationEvaluation => ShaderExecution::TessellationEvaluation,
+        ExecutionModel::TessellationEvaluation => ShaderExecution::TessellationEvaluation(
+            tessellation_shader_execution(spirv, function_id),
+        ),
 
         ExecutionModel::Geometry => {
             let input = spirv
@@ -153,7 +369,39 @@ fn shader_execution(
             ShaderExecution::Geometry(GeometryShaderExecution { input })
         }
 
-        ExecutionModel::Fragment => ShaderExecution::Fragment,
+        ExecutionModel::Fragment => {
+            let interlock = spirv
+                .iter_execution_mode()
+                .into_iter()
+                .find_map(|instruction| match instruction {
+                    Instruction::ExecutionMode {
+                        entry_point, mode, ..
+                    } if *entry_point == function_id => match mode {
+                        ExecutionMode::PixelInterlockOrderedEXT => {
+                            Some(FragmentShaderInterlock::PixelOrdered)
+                        }
+                        ExecutionMode::PixelInterlockUnorderedEXT => {
+                            Some(FragmentShaderInterlock::PixelUnordered)
+                        }
+                        ExecutionMode::SampleInterlockOrderedEXT => {
+                            Some(FragmentShaderInterlock::SampleOrdered)
+                        }
+                        ExecutionMode::SampleInterlockUnorderedEXT => {
+                            Some(FragmentShaderInterlock::SampleUnordered)
+                        }
+                        ExecutionMode::ShadingRateInterlockOrderedEXT => {
+                            Some(FragmentShaderInterlock::ShadingRateOrdered)
+                        }
+                        ExecutionMode::ShadingRateInterlockUnorderedEXT => {
+                            Some(FragmentShaderInterlock::ShadingRateUnordered)
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                });
+
+            ShaderExecution::Fragment(FragmentShaderExecution { interlock })
+        }
 
         ExecutionModel::GLCompute => ShaderExecution::Compute,
 
@@ -648,6 +896,15 @@ fn inspect_entry_point(
                             }
                         }
 
+                        Instruction::RayQueryInitializeKHR { accel, .. }
+                        | Instruction::TraceRayKHR { accel, .. } => {
+                            if let Some(desc_reqs) =
+                                desc_reqs(self.instruction_chain([inst_load], accel))
+                            {
+                                desc_reqs.memory_read = stage.into();
+                            }
+                        }
+
                         Instruction::Load { pointer, .. } => {
                             if let Some((binding_variable, index)) =
                                 self.instruction_chain([], pointer)
@@ -789,6 +1046,7 @@ fn descriptor_binding_requirements_of(spirv: &Spirv, variable_id: Id) -> Descrip
                     reqs.descriptor_types = vec![
                         DescriptorType::UniformBuffer,
                         DescriptorType::UniformBufferDynamic,
+                        DescriptorType::InlineUniformBlock,
                     ];
                 };
 
@@ -915,7 +1173,10 @@ fn descriptor_binding_requirements_of(spirv: &Spirv, variable_id: Id) -> Descrip
                 Some(element_type)
             }
 
-            Instruction::TypeAccelerationStructureKHR { .. } => None, // FIXME temporary workaround
+            Instruction::TypeAccelerationStructureKHR { .. } => {
+                reqs.descriptor_types = vec![DescriptorType::AccelerationStructure];
+                None
+            }
 
             _ => {
                 let name = variable_id_info
@@ -961,15 +1222,30 @@ fn descriptor_binding_requirements_of(spirv: &Spirv, variable_id: Id) -> Descrip
 }
 
 /// Extracts the `PushConstantRange` from `spirv`.
-fn push_constant_requirements(spirv: &Spirv, stage: ShaderStage) -> Option<PushConstantRange> {
+fn push_constant_requirements(
+    spirv: &Spirv,
+    stage: ShaderStage,
+    interface: &[Id],
+) -> Option<PushConstantRange> {
+    // Starting with SPIR-V 1.4, the interface of an entry point lists all the global variables
+    // that it uses, so a module with several entry points can give each one its own push
+    // constants. Earlier versions only list inputs and outputs, so the push constants of the
+    // module are assumed to be used by every entry point.
+    let uses_interface = spirv.version() >= Version::V1_4;
+
     spirv
         .iter_global()
         .find_map(|instruction| match *instruction {
-            Instruction::TypePointer {
-                ty,
+            Instruction::Variable {
+                result_type_id,
+                result_id,
                 storage_class: StorageClass::PushConstant,
                 ..
-            } => {
+            } if !uses_interface || interface.contains(&result_id) => {
+                let ty = match *spirv.id(result_type_id).instruction() {
+                    Instruction::TypePointer { ty, .. } => ty,
+                    _ => unreachable!(),
+                };
                 let id_info = spirv.id(ty);
                 assert!(matches!(
                     id_info.instruction(),
@@ -1036,7 +1312,15 @@ fn specialization_constant_requirements(
                             _ => size_of_type(spirv, result_type_id)
                                 .expect("Found runtime-sized specialization constant"),
                         };
-                        (constant_id, SpecializationConstantRequirements { size })
+                        let default_value =
+                            specialization_constant_default_value(spirv, instruction);
+                        (
+                            constant_id,
+                            SpecializationConstantRequirements {
+                                size,
+                                default_value,
+                            },
+                        )
                     }),
                 _ => None,
             }
@@ -1044,6 +1328,57 @@ fn specialization_constant_requirements(
         .collect()
 }
 
+/// Returns the value of a scalar specialization constant, as it is given in the SPIR-V code.
+fn specialization_constant_default_value(
+    spirv: &Spirv,
+    instruction: &Instruction,
+) -> Option<SpecializationConstant> {
+    match *instruction {
+        Instruction::SpecConstantTrue { .. } => Some(SpecializationConstant::Bool(true)),
+        Instruction::SpecConstantFalse { .. } => Some(SpecializationConstant::Bool(false)),
+        Instruction::SpecConstant {
+            result_type_id,
+            ref value,
+            ..
+        } => {
+            // 64-bit values are given as two words, with the low-order word first.
+            let value64 = || Some(*value.first()? as u64 | (*value.get(1)? as u64) << 32);
+
+            match *spirv.id(result_type_id).instruction() {
+                Instruction::TypeInt {
+                    width, signedness, ..
+                } => {
+                    let value32 = *value.first()?;
+
+                    Some(match (width, signedness) {
+                        (8, 0) => SpecializationConstant::U8(value32 as u8),
+                        (8, _) => SpecializationConstant::I8(value32 as i8),
+                        (16, 0) => SpecializationConstant::U16(value32 as u16),
+                        (16, _) => SpecializationConstant::I16(value32 as i16),
+                        (32, 0) => SpecializationConstant::U32(value32),
+                        (32, _) => SpecializationConstant::I32(value32 as i32),
+                        (64, 0) => SpecializationConstant::U64(value64()?),
+                        (64, _) => SpecializationConstant::I64(value64()? as i64),
+                        _ => return None,
+                    })
+                }
+                Instruction::TypeFloat { width, .. } => {
+                    let value32 = *value.first()?;
+
+                    Some(match width {
+                        16 => SpecializationConstant::F16(f16::from_bits(value32 as u16)),
+                        32 => SpecializationConstant::F32(f32::from_bits(value32)),
+                        64 => SpecializationConstant::F64(f64::from_bits(value64()?)),
+                        _ => return None,
+                    })
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Extracts the `ShaderInterface` with the given storage class from `spirv`.
 fn shader_interface(
     spirv: &Spirv,
@@ -20,6 +20,7 @@ use ahash::{HashMap, HashMapExt};
 use std::{
     error::Error,
     fmt::{Display, Error as FmtError, Formatter},
+    mem,
     ops::Range,
     string::FromUtf8Error,
 };
@@ -71,7 +72,16 @@ impl Spirv {
             let mut rest = &words[5..];
             while !rest.is_empty() {
                 let word_count = (rest[0] >> 16) as usize;
-                assert!(word_count >= 1);
+
+                if word_count == 0 {
+                    return Err(ParseError {
+                        instruction: ret.len(),
+                        word: 0,
+                        error: ParseErrors::MissingOperands,
+                        words: rest[0..1].to_owned(),
+                    }
+                    .into());
+                }
 
                 if rest.len() < word_count {
                     return Err(ParseError {
@@ -276,10 +286,12 @@ impl Spirv {
         for index in spirv.range_name.clone() {
             match &spirv.instructions[index] {
                 Instruction::Name { target, .. } => {
-                    spirv.ids.get_mut(target).unwrap().names.push(index);
+                    id_data_mut(&mut spirv.ids, *target, index)?
+                        .names
+                        .push(index);
                 }
                 Instruction::MemberName { ty, member, .. } => {
-                    spirv.ids.get_mut(ty).unwrap().members[*member as usize]
+                    member_data_mut(&mut spirv.ids, *ty, *member, index)?
                         .names
                         .push(index);
                 }
@@ -293,7 +305,9 @@ impl Spirv {
                 Instruction::Decorate { target, .. }
                 | Instruction::DecorateId { target, .. }
                 | Instruction::DecorateString { target, .. } => {
-                    spirv.ids.get_mut(target).unwrap().decorations.push(index);
+                    id_data_mut(&mut spirv.ids, *target, index)?
+                        .decorations
+                        .push(index);
                 }
                 Instruction::MemberDecorate {
                     structure_type: target,
@@ -305,7 +319,7 @@ impl Spirv {
                     member,
                     ..
                 } => {
-                    spirv.ids.get_mut(target).unwrap().members[*member as usize]
+                    member_data_mut(&mut spirv.ids, *target, *member, index)?
                         .decorations
                         .push(index);
                 }
@@ -321,22 +335,23 @@ impl Spirv {
                     targets,
                     ..
                 } => {
-                    let indices = {
-                        let data = &spirv.ids[decoration_group];
-                        if !matches!(
-                            spirv.instructions[data.index],
-                            Instruction::DecorationGroup { .. }
-                        ) {
-                            return Err(SpirvError::GroupDecorateNotGroup { index });
-                        };
-                        data.decorations.clone()
+                    let data = spirv
+                        .ids
+                        .get(decoration_group)
+                        .ok_or(SpirvError::IdNotDefined {
+                            id: *decoration_group,
+                            index,
+                        })?;
+                    if !matches!(
+                        spirv.instructions[data.index],
+                        Instruction::DecorationGroup { .. }
+                    ) {
+                        return Err(SpirvError::GroupDecorateNotGroup { index });
                     };
+                    let indices = data.decorations.clone();
 
                     for target in targets {
-                        spirv
-                            .ids
-                            .get_mut(target)
-                            .unwrap()
+                        id_data_mut(&mut spirv.ids, *target, index)?
                             .decorations
                             .extend(&indices);
                     }
@@ -346,19 +361,23 @@ impl Spirv {
                     targets,
                     ..
                 } => {
-                    let indices = {
-                        let data = &spirv.ids[decoration_group];
-                        if !matches!(
-                            spirv.instructions[data.index],
-                            Instruction::DecorationGroup { .. }
-                        ) {
-                            return Err(SpirvError::GroupDecorateNotGroup { index });
-                        };
-                        data.decorations.clone()
+                    let data = spirv
+                        .ids
+                        .get(decoration_group)
+                        .ok_or(SpirvError::IdNotDefined {
+                            id: *decoration_group,
+                            index,
+                        })?;
+                    if !matches!(
+                        spirv.instructions[data.index],
+                        Instruction::DecorationGroup { .. }
+                    ) {
+                        return Err(SpirvError::GroupDecorateNotGroup { index });
                     };
+                    let indices = data.decorations.clone();
 
                     for (target, member) in targets {
-                        spirv.ids.get_mut(target).unwrap().members[*member as usize]
+                        member_data_mut(&mut spirv.ids, *target, *member, index)?
                             .decorations
                             .extend(&indices);
                     }
@@ -370,6 +389,133 @@ impl Spirv {
         Ok(spirv)
     }
 
+    /// Performs additional validation of the module, beyond what is done by [`Spirv::new`].
+    ///
+    /// This checks that the instructions that Vulkano relies on to extract information from the
+    /// module refer to `Id`s that exist and are of the right kind. For example, every entry
+    /// point must refer to a function, and every variable must have a pointer type. It also checks
+    /// that decorations such as `Location` or `Binding` are not applied twice. This does not
+    /// fully validate the module according to the SPIR-V specification; for that, an external
+    /// tool such as `spirv-val` is needed.
+    ///
+    /// If the `validate_spirv` feature is enabled, this is called when a
+    /// [`ShaderModule`](super::ShaderModule) is created from SPIR-V words.
+    pub fn validate(&self) -> Result<(), SpirvError> {
+        let instruction_of = |id: Id, index: usize| {
+            self.ids
+                .get(&id)
+                .map(|data| &self.instructions[data.index])
+                .ok_or(SpirvError::IdNotDefined { id, index })
+        };
+        let require_defined = |id: Id, index: usize| instruction_of(id, index).map(|_| ());
+
+        let mut in_function = false;
+
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            match instruction {
+                Instruction::EntryPoint {
+                    entry_point,
+                    interface,
+                    ..
+                } => {
+                    if !matches!(
+                        instruction_of(*entry_point, index)?,
+                        Instruction::Function { .. }
+                    ) {
+                        return Err(SpirvError::IdWrongKind {
+                            id: *entry_point,
+                            index,
+                            expected: "an OpFunction",
+                        });
+                    }
+
+                    for &id in interface {
+                        if !matches!(instruction_of(id, index)?, Instruction::Variable { .. }) {
+                            return Err(SpirvError::IdWrongKind {
+                                id,
+                                index,
+                                expected: "an OpVariable",
+                            });
+                        }
+                    }
+                }
+                Instruction::Variable { result_type_id, .. } => {
+                    if !matches!(
+                        instruction_of(*result_type_id, index)?,
+                        Instruction::TypePointer { .. }
+                    ) {
+                        return Err(SpirvError::IdWrongKind {
+                            id: *result_type_id,
+                            index,
+                            expected: "an OpTypePointer",
+                        });
+                    }
+                }
+                Instruction::TypePointer { ty, .. } => require_defined(*ty, index)?,
+                Instruction::TypeVector { component_type, .. } => {
+                    require_defined(*component_type, index)?
+                }
+                Instruction::TypeMatrix { column_type, .. } => {
+                    require_defined(*column_type, index)?
+                }
+                Instruction::TypeArray {
+                    element_type,
+                    length,
+                    ..
+                } => {
+                    require_defined(*element_type, index)?;
+                    require_defined(*length, index)?;
+                }
+                Instruction::TypeRuntimeArray { element_type, .. } => {
+                    require_defined(*element_type, index)?
+                }
+                Instruction::TypeStruct { member_types, .. } => {
+                    for &member_type in member_types {
+                        require_defined(member_type, index)?;
+                    }
+                }
+                Instruction::Function { .. } => {
+                    if in_function {
+                        return Err(SpirvError::BadLayout { index });
+                    }
+
+                    in_function = true;
+                }
+                Instruction::FunctionEnd => {
+                    if !in_function {
+                        return Err(SpirvError::BadLayout { index });
+                    }
+
+                    in_function = false;
+                }
+                _ => (),
+            }
+        }
+
+        if in_function {
+            return Err(SpirvError::BadLayout {
+                index: self.instructions.len(),
+            });
+        }
+
+        // Vulkano only looks at the first of these decorations on a target, so any others would be
+        // silently ignored.
+        for (&id, data) in &self.ids {
+            check_unique_decorations(&self.instructions, &data.decorations, id, None)?;
+
+            for (member, member_data) in data.members.iter().enumerate() {
+                check_unique_decorations(
+                    &self.instructions,
+                    &member_data.decorations,
+                    id,
+                    Some(member as u32),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns a reference to the instructions in the module.
     #[inline]
     pub fn instructions(&self) -> &[Instruction] {
@@ -475,6 +621,73 @@ struct StructMemberDataIndices {
     decorations: Vec<usize>,
 }
 
+fn id_data_mut(
+    ids: &mut HashMap<Id, IdDataIndices>,
+    id: Id,
+    index: usize,
+) -> Result<&mut IdDataIndices, SpirvError> {
+    ids.get_mut(&id)
+        .ok_or(SpirvError::IdNotDefined { id, index })
+}
+
+fn member_data_mut(
+    ids: &mut HashMap<Id, IdDataIndices>,
+    id: Id,
+    member: u32,
+    index: usize,
+) -> Result<&mut StructMemberDataIndices, SpirvError> {
+    id_data_mut(ids, id, index)?
+        .members
+        .get_mut(member as usize)
+        .ok_or(SpirvError::MemberOutOfBounds { id, member, index })
+}
+
+fn check_unique_decorations(
+    instructions: &[Instruction],
+    indices: &[usize],
+    id: Id,
+    member: Option<u32>,
+) -> Result<(), SpirvError> {
+    fn unique_decoration(instruction: &Instruction) -> Option<mem::Discriminant<Decoration>> {
+        match instruction {
+            Instruction::Decorate { decoration, .. }
+            | Instruction::MemberDecorate { decoration, .. } => match decoration {
+                Decoration::SpecId { .. }
+                | Decoration::ArrayStride { .. }
+                | Decoration::BuiltIn { .. }
+                | Decoration::Location { .. }
+                | Decoration::Component { .. }
+                | Decoration::Binding { .. }
+                | Decoration::DescriptorSet { .. }
+                | Decoration::Offset { .. } => Some(mem::discriminant(decoration)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    for (i, &first_index) in indices.iter().enumerate() {
+        let kind = match unique_decoration(&instructions[first_index]) {
+            Some(kind) => kind,
+            None => continue,
+        };
+
+        if let Some(&second_index) = indices[i + 1..]
+            .iter()
+            .find(|&&index| unique_decoration(&instructions[index]) == Some(kind))
+        {
+            return Err(SpirvError::DuplicateDecoration {
+                id,
+                member,
+                first_index,
+                second_index,
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// Information associated with an `Id`.
 #[derive(Clone, Debug)]
 pub struct IdInfo<'a> {
@@ -659,11 +872,17 @@ impl<'a> InstructionReader<'a> {
 }
 
 /// Error that can happen when reading a SPIR-V module.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SpirvError {
     BadLayout {
         index: usize,
     },
+    DuplicateDecoration {
+        id: Id,
+        member: Option<u32>,
+        first_index: usize,
+        second_index: usize,
+    },
     DuplicateId {
         id: Id,
         first_index: usize,
@@ -672,12 +891,26 @@ pub enum SpirvError {
     GroupDecorateNotGroup {
         index: usize,
     },
+    IdNotDefined {
+        id: Id,
+        index: usize,
+    },
     IdOutOfBounds {
         id: Id,
         index: usize,
         bound: u32,
     },
+    IdWrongKind {
+        id: Id,
+        index: usize,
+        expected: &'static str,
+    },
     InvalidHeader,
+    MemberOutOfBounds {
+        id: Id,
+        member: u32,
+        index: usize,
+    },
     MemoryModelInvalid,
     ParseError(ParseError),
 }
@@ -690,6 +923,25 @@ impl Display for SpirvError {
                 "the instruction at index {} does not follow the logical layout of a module",
                 index,
             ),
+            Self::DuplicateDecoration {
+                id,
+                member,
+                first_index,
+                second_index,
+            } => {
+                if let Some(member) = member {
+                    write!(f, "member {} of struct {}", member, id)?;
+                } else {
+                    write!(f, "id {}", id)?;
+                }
+
+                write!(
+                    f,
+                    " is decorated more than once with the same decoration, by instructions {} and \
+                    {}",
+                    first_index, second_index,
+                )
+            }
             Self::DuplicateId {
                 id,
                 first_index,
@@ -705,12 +957,31 @@ impl Display for SpirvError {
                 that was not a DecorationGroup",
                 index,
             ),
+            Self::IdNotDefined { id, index } => write!(
+                f,
+                "id {}, referred to by instruction {}, is not defined",
+                id, index,
+            ),
             Self::IdOutOfBounds { id, bound, index } => write!(
                 f,
                 "id {}, assigned at instruction {}, is not below the maximum bound {}",
                 id, index, bound,
             ),
+            Self::IdWrongKind {
+                id,
+                index,
+                expected,
+            } => write!(
+                f,
+                "id {}, referred to by instruction {}, is not {}",
+                id, index, expected,
+            ),
             Self::InvalidHeader => write!(f, "the SPIR-V module header is invalid"),
+            Self::MemberOutOfBounds { id, member, index } => write!(
+                f,
+                "instruction {} refers to member {} of struct {}, which does not exist",
+                index, member, id,
+            ),
             Self::MemoryModelInvalid => {
                 write!(f, "the MemoryModel instruction is not present exactly once")
             }
@@ -735,7 +1006,7 @@ impl From<ParseError> for SpirvError {
 }
 
 /// Error that can happen when parsing SPIR-V instructions into Rust data structures.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ParseError {
     /// The instruction number the error happened at, starting from 0.
     pub instruction: usize,
@@ -760,7 +1031,7 @@ impl Display for ParseError {
 impl Error for ParseError {}
 
 /// Individual types of parse error that can happen.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ParseErrors {
     FromUtf8Error(FromUtf8Error),
     LeftoverOperands,
@@ -792,3 +1063,148 @@ impl Display for ParseErrors {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Builds a module from instructions that are given as an opcode followed by the operands.
+    fn module(bound: u32, instructions: &[&[u32]]) -> Vec<u32> {
+        let mut words = vec![0x07230203, 0x00010000, 0, bound, 0];
+
+        for instruction in instructions {
+            words.push((instruction.len() as u32) << 16 | instruction[0]);
+            words.extend(&instruction[1..]);
+        }
+
+        words
+    }
+
+    const OP_MEMORY_MODEL: &[u32] = &[14, 0, 1];
+
+    #[test]
+    fn bad_header() {
+        assert_eq!(
+            Spirv::new(&[0x07230203, 0x00010000, 0]).unwrap_err(),
+            SpirvError::InvalidHeader,
+        );
+
+        let mut words = module(1, &[OP_MEMORY_MODEL]);
+        words[0] = 0x03022307;
+        assert_eq!(Spirv::new(&words).unwrap_err(), SpirvError::InvalidHeader);
+    }
+
+    #[test]
+    fn id_out_of_bounds() {
+        // %5 = OpTypeVoid
+        let words = module(2, &[OP_MEMORY_MODEL, &[19, 5]]);
+
+        assert_eq!(
+            Spirv::new(&words).unwrap_err(),
+            SpirvError::IdOutOfBounds {
+                id: Id(5),
+                index: 1,
+                bound: 2,
+            },
+        );
+    }
+
+    #[test]
+    fn truncated_instruction() {
+        // %1 = OpTypeInt 32, with the signedness operand cut off at the end of the module.
+        let mut words = module(2, &[OP_MEMORY_MODEL, &[21, 1, 32, 0]]);
+        words.pop();
+
+        assert!(matches!(
+            Spirv::new(&words).unwrap_err(),
+            SpirvError::ParseError(ParseError {
+                instruction: 1,
+                error: ParseErrors::UnexpectedEOF,
+                ..
+            }),
+        ));
+    }
+
+    #[test]
+    fn decoration_target_not_defined() {
+        // OpDecorate %1 Location 0
+        let words = module(2, &[OP_MEMORY_MODEL, &[71, 1, 30, 0]]);
+
+        assert_eq!(
+            Spirv::new(&words).unwrap_err(),
+            SpirvError::IdNotDefined {
+                id: Id(1),
+                index: 1
+            },
+        );
+    }
+
+    #[test]
+    fn member_decoration_out_of_bounds() {
+        // OpMemberDecorate %2 1 Offset 0
+        // %1 = OpTypeFloat 32
+        // %2 = OpTypeStruct %1
+        let words = module(
+            3,
+            &[
+                OP_MEMORY_MODEL,
+                &[72, 2, 1, 35, 0],
+                &[22, 1, 32],
+                &[30, 2, 1],
+            ],
+        );
+
+        assert_eq!(
+            Spirv::new(&words).unwrap_err(),
+            SpirvError::MemberOutOfBounds {
+                id: Id(2),
+                member: 1,
+                index: 1,
+            },
+        );
+    }
+
+    #[test]
+    fn duplicate_decorations() {
+        // OpDecorate %1 Location 0
+        // OpDecorate %1 Location 1
+        // %1 = OpTypeFloat 32
+        let words = module(
+            2,
+            &[
+                OP_MEMORY_MODEL,
+                &[71, 1, 30, 0],
+                &[71, 1, 30, 1],
+                &[22, 1, 32],
+            ],
+        );
+        let spirv = Spirv::new(&words).unwrap();
+
+        assert_eq!(
+            spirv.validate().unwrap_err(),
+            SpirvError::DuplicateDecoration {
+                id: Id(1),
+                member: None,
+                first_index: 1,
+                second_index: 2,
+            },
+        );
+    }
+
+    #[test]
+    fn variable_type_not_pointer() {
+        // %1 = OpTypeFloat 32
+        // %2 = OpVariable %1 Input
+        let words = module(3, &[OP_MEMORY_MODEL, &[22, 1, 32], &[59, 1, 2, 1]]);
+        let spirv = Spirv::new(&words).unwrap();
+
+        assert_eq!(
+            spirv.validate().unwrap_err(),
+            SpirvError::IdWrongKind {
+                id: Id(1),
+                index: 2,
+                expected: "an OpTypePointer",
+            },
+        );
+    }
+}
@@ -0,0 +1,368 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Cache the results of shader validation to disk for faster reloads.
+//!
+//! When the validation layers are enabled, every shader module that is created is validated,
+//! which can take a long time for projects with many shaders. A validation cache stores the
+//! results of this validation, so that shaders that were already validated don't need to be
+//! validated again.
+//!
+//! A validation cache is used by creating shader modules with
+//! [`ShaderModule::from_words_with_validation_cache`]. Once that is done, the data of the cache can
+//! be stored with [`save_to_file`](ValidationCache::save_to_file) and reloaded the next time with
+//! [`load_from_file`](ValidationCache::load_from_file).
+//!
+//! The [`ext_validation_cache`] extension, which is provided by the validation layers, must be
+//! enabled on the device.
+//!
+//! [`ShaderModule::from_words_with_validation_cache`]: super::ShaderModule::from_words_with_validation_cache
+//! [`ext_validation_cache`]: crate::device::DeviceExtensions::ext_validation_cache
+
+use crate::{
+    device::{Device, DeviceOwned},
+    OomError, RequiresOneOf, VulkanError, VulkanObject,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    fs,
+    io::{self, ErrorKind},
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    path::Path,
+    ptr,
+    sync::Arc,
+};
+
+/// Opaque cache that contains the results of shader validation.
+///
+/// See [the documentation of the module](crate::shader::validation_cache) for more info.
+#[derive(Debug)]
+pub struct ValidationCache {
+    handle: ash::vk::ValidationCacheEXT,
+    device: Arc<Device>,
+    id: NonZeroU64,
+}
+
+impl ValidationCache {
+    /// Builds a new empty validation cache.
+    ///
+    /// The [`ext_validation_cache`] extension must be enabled on the device.
+    ///
+    /// [`ext_validation_cache`]: crate::device::DeviceExtensions::ext_validation_cache
+    #[inline]
+    pub fn empty(device: Arc<Device>) -> Result<Arc<ValidationCache>, ValidationCacheError> {
+        Self::validate_new(&device)?;
+
+        unsafe { Ok(Self::new_unchecked(device, None)?) }
+    }
+
+    /// Builds a new validation cache from existing data. The data must have been previously
+    /// obtained with [`get_data`](Self::get_data).
+    ///
+    /// The [`ext_validation_cache`] extension must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - `initial_data` must have been obtained from a validation cache with
+    ///   [`get_data`](Self::get_data), and must not have been modified since.
+    ///
+    /// [`ext_validation_cache`]: crate::device::DeviceExtensions::ext_validation_cache
+    #[inline]
+    pub unsafe fn with_data(
+        device: Arc<Device>,
+        initial_data: &[u8],
+    ) -> Result<Arc<ValidationCache>, ValidationCacheError> {
+        Self::validate_new(&device)?;
+
+        Ok(Self::new_unchecked(device, Some(initial_data))?)
+    }
+
+    fn validate_new(device: &Device) -> Result<(), ValidationCacheError> {
+        if !device.enabled_extensions().ext_validation_cache {
+            return Err(ValidationCacheError::RequirementNotMet {
+                required_for: "`ValidationCache::new`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_validation_cache"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        initial_data: Option<&[u8]>,
+    ) -> Result<Arc<ValidationCache>, VulkanError> {
+        let create_info = ash::vk::ValidationCacheCreateInfoEXT {
+            flags: ash::vk::ValidationCacheCreateFlagsEXT::empty(),
+            initial_data_size: initial_data.map_or(0, |data| data.len()),
+            p_initial_data: initial_data.map_or(ptr::null(), |data| data.as_ptr() as *const _),
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.ext_validation_cache.create_validation_cache_ext)(
+                device.handle(),
+                &create_info,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(ValidationCache {
+            handle,
+            device,
+            id: Self::next_id(),
+        }))
+    }
+
+    /// Loads a validation cache from the data in the file at `path`, that was previously written
+    /// by [`save_to_file`](Self::save_to_file). If the file does not exist, an empty cache is
+    /// returned instead.
+    ///
+    /// The [`ext_validation_cache`] extension must be enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// - The file must contain data that was obtained with [`get_data`](Self::get_data) or
+    ///   [`save_to_file`](Self::save_to_file), and must not have been modified since.
+    ///
+    /// [`ext_validation_cache`]: crate::device::DeviceExtensions::ext_validation_cache
+    pub unsafe fn load_from_file(
+        device: Arc<Device>,
+        path: impl AsRef<Path>,
+    ) -> Result<Arc<ValidationCache>, ValidationCacheError> {
+        match fs::read(path) {
+            Ok(data) => Self::with_data(device, &data),
+            Err(err) if err.kind() == ErrorKind::NotFound => Self::empty(device),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Merges other validation caches into this one.
+    ///
+    /// It is `self` that is modified here. The validation caches passed as parameter are
+    /// untouched.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `self` is included in `src_caches`.
+    /// - Panics if an element of `src_caches` does not belong to the same device as `self`.
+    // FIXME: vkMergeValidationCachesEXT is not thread safe for the destination cache
+    pub fn merge(&self, src_caches: &[&ValidationCache]) -> Result<(), OomError> {
+        let src_caches_vk: Vec<_> = src_caches
+            .iter()
+            .map(|src_cache| {
+                // VUID-vkMergeValidationCachesEXT-dstCache-01536
+                assert!(!ptr::eq(*src_cache, self));
+
+                // VUID-vkMergeValidationCachesEXT-pSrcCaches-parent
+                assert_eq!(self.device, src_cache.device);
+
+                src_cache.handle
+            })
+            .collect();
+
+        unsafe {
+            let fns = self.device.fns();
+            (fns.ext_validation_cache.merge_validation_caches_ext)(
+                self.device.handle(),
+                self.handle,
+                src_caches_vk.len() as u32,
+                src_caches_vk.as_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+        }
+
+        Ok(())
+    }
+
+    /// Obtains the data from the cache.
+    ///
+    /// This data can be stored and then reloaded and passed to
+    /// [`with_data`](Self::with_data).
+    pub fn get_data(&self) -> Result<Vec<u8>, OomError> {
+        let fns = self.device.fns();
+
+        let data = unsafe {
+            loop {
+                let mut count = 0;
+                (fns.ext_validation_cache.get_validation_cache_data_ext)(
+                    self.device.handle(),
+                    self.handle,
+                    &mut count,
+                    ptr::null_mut(),
+                )
+                .result()
+                .map_err(VulkanError::from)?;
+
+                let mut data: Vec<u8> = Vec::with_capacity(count);
+                let result = (fns.ext_validation_cache.get_validation_cache_data_ext)(
+                    self.device.handle(),
+                    self.handle,
+                    &mut count,
+                    data.as_mut_ptr() as *mut _,
+                );
+
+                match result {
+                    ash::vk::Result::SUCCESS => {
+                        data.set_len(count);
+                        break data;
+                    }
+                    ash::vk::Result::INCOMPLETE => (),
+                    err => return Err(VulkanError::from(err).into()),
+                }
+            }
+        };
+
+        Ok(data)
+    }
+
+    /// Writes the data of the cache to the file at `path`.
+    ///
+    /// The data is first written to a temporary file next to `path`, which then replaces `path`.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), ValidationCacheError> {
+        let path = path.as_ref();
+        let data = self.get_data()?;
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        if let Err(err) = fs::write(&tmp_path, data) {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(err.into());
+        }
+
+        fs::rename(&tmp_path, path)?;
+
+        Ok(())
+    }
+}
+
+impl Drop for ValidationCache {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.ext_validation_cache.destroy_validation_cache_ext)(
+                self.device.handle(),
+                self.handle,
+                ptr::null(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for ValidationCache {
+    type Handle = ash::vk::ValidationCacheEXT;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for ValidationCache {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+crate::impl_id_counter!(ValidationCache);
+
+/// Error that can happen when creating, saving or loading a validation cache.
+#[derive(Debug)]
+pub enum ValidationCacheError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// An error occurred while reading or writing the file.
+    IoError(io::Error),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for ValidationCacheError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            Self::IoError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ValidationCacheError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::IoError(_) => write!(f, "an error occurred while reading or writing the file"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<OomError> for ValidationCacheError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<VulkanError> for ValidationCacheError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            err @ VulkanError::OutOfHostMemory => Self::OomError(OomError::from(err)),
+            err @ VulkanError::OutOfDeviceMemory => Self::OomError(OomError::from(err)),
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<io::Error> for ValidationCacheError {
+    fn from(err: io::Error) -> Self {
+        Self::IoError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ValidationCache, ValidationCacheError};
+
+    #[test]
+    fn requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(matches!(
+            ValidationCache::empty(device),
+            Err(ValidationCacheError::RequirementNotMet { .. })
+        ));
+    }
+}
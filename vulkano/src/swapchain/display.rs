@@ -9,19 +9,20 @@
 
 //! Allows you to create surfaces that fill a whole display, outside of the windowing system.
 //!
-//! **As far as the author knows, no existing device supports these features. Therefore the code
-//! here is mostly a draft and needs rework in both the API and the implementation.**
+//! This requires the `khr_display` instance extension. It is mostly useful for embedded systems,
+//! kiosks and VR runtimes, that have no window system or need to bypass it.
 //!
 //! The purpose of the objects in this module is to let you create a `Surface` object that
 //! represents a location on the screen. This is done in four steps:
 //!
 //! - Choose a `Display` where the surface will be located. A `Display` represents a display
 //!   display, usually a monitor. The available displays can be enumerated with
-//!   `Display::enumerate`.
+//!   [`PhysicalDevice::displays`].
 //! - Choose a `DisplayMode`, which is the combination of a display, a resolution and a refresh
 //!   rate. You can enumerate the modes available on a display with `Display::display_modes`, or
-//!   attempt to create your own mode with `TODO`.
-//! - Choose a `DisplayPlane`. A display can show multiple planes in a stacking fashion.
+//!   attempt to create your own mode with [`DisplayMode::new`].
+//! - Choose a `DisplayPlane`. A display can show multiple planes in a stacking fashion. The
+//!   available planes can be enumerated with [`PhysicalDevice::display_planes`].
 //! - Create a `Surface` object with `Surface::from_display_plane` and pass the chosen `DisplayMode`
 //!   and `DisplayPlane`.
 
@@ -29,12 +30,14 @@
 #![allow(unused_variables)] // TODO: this module isn't finished
 
 use crate::{
-    device::physical::PhysicalDevice, swapchain::SurfaceTransforms, OomError, VulkanError,
-    VulkanObject,
+    device::physical::PhysicalDevice, swapchain::SurfaceTransforms, OomError, RequirementNotMet,
+    RequiresOneOf, VulkanError, VulkanObject,
 };
 use std::{
+    error::Error,
     ffi::CStr,
     fmt::{Display as FmtDisplay, Error as FmtError, Formatter},
+    mem::MaybeUninit,
     ptr,
     sync::Arc,
     vec::IntoIter,
@@ -56,10 +59,18 @@ impl DisplayPlane {
     /// See the docs of enumerate().
     pub fn enumerate_raw(
         physical_device: Arc<PhysicalDevice>,
-    ) -> Result<IntoIter<DisplayPlane>, OomError> {
+    ) -> Result<IntoIter<DisplayPlane>, DisplayError> {
         let fns = physical_device.instance().fns();
 
-        assert!(physical_device.instance().enabled_extensions().khr_display); // TODO: return error instead
+        if !physical_device.instance().enabled_extensions().khr_display {
+            return Err(DisplayError::RequirementNotMet {
+                required_for: "`DisplayPlane::enumerate_raw`",
+                requires_one_of: RequiresOneOf {
+                    instance_extensions: &["khr_display"],
+                    ..Default::default()
+                },
+            });
+        }
 
         let display_plane_properties = unsafe {
             loop {
@@ -107,8 +118,7 @@ impl DisplayPlane {
                             ptr::null_mut(),
                         )
                         .result()
-                        .map_err(VulkanError::from)
-                        .unwrap(); // TODO: shouldn't unwrap
+                        .map_err(VulkanError::from)?;
 
                         let mut displays = Vec::with_capacity(count as usize);
                         let result = (fns.khr_display.get_display_plane_supported_displays_khr)(
@@ -124,19 +134,19 @@ impl DisplayPlane {
                                 break displays;
                             }
                             ash::vk::Result::INCOMPLETE => (),
-                            err => todo!(), // TODO: shouldn't panic
+                            err => return Err(VulkanError::from(err).into()),
                         }
                     }
                 };
 
-                DisplayPlane {
+                Ok(DisplayPlane {
                     physical_device: physical_device.clone(),
                     index: index as u32,
                     properties: prop,
                     supported_displays,
-                }
+                })
             })
-            .collect::<Vec<_>>()
+            .collect::<Result<Vec<_>, DisplayError>>()?
             .into_iter())
     }
 
@@ -163,6 +173,12 @@ impl DisplayPlane {
         self.index
     }
 
+    /// Returns the current position of the plane in the stack of planes of its display.
+    #[inline]
+    pub fn current_stack_index(&self) -> u32 {
+        self.properties.current_stack_index
+    }
+
     /// Returns true if this plane supports the given display.
     #[inline]
     pub fn supports(&self, display: &Display) -> bool {
@@ -189,9 +205,18 @@ impl Display {
     /// See the docs of enumerate().
     pub fn enumerate_raw(
         physical_device: Arc<PhysicalDevice>,
-    ) -> Result<IntoIter<Display>, OomError> {
+    ) -> Result<IntoIter<Display>, DisplayError> {
         let fns = physical_device.instance().fns();
-        assert!(physical_device.instance().enabled_extensions().khr_display); // TODO: return error instead
+
+        if !physical_device.instance().enabled_extensions().khr_display {
+            return Err(DisplayError::RequirementNotMet {
+                required_for: "`Display::enumerate_raw`",
+                requires_one_of: RequiresOneOf {
+                    instance_extensions: &["khr_display"],
+                    ..Default::default()
+                },
+            });
+        }
 
         let display_properties = unsafe {
             loop {
@@ -282,20 +307,22 @@ impl Display {
         self.properties.supported_transforms.into()
     }
 
-    /// Returns true if TODO.
+    /// Returns true if the planes on this display can have their stack order changed. If so, a
+    /// surface can be created on any stack index of a plane.
     #[inline]
     pub fn plane_reorder_possible(&self) -> bool {
         self.properties.plane_reorder_possible != 0
     }
 
-    /// Returns true if TODO.
+    /// Returns true if the display supports self-refresh or internal buffering, in which case
+    /// presenting can be limited to the regions that have changed.
     #[inline]
     pub fn persistent_content(&self) -> bool {
         self.properties.persistent_content != 0
     }
 
     /// See the docs of display_modes().
-    pub fn display_modes_raw(&self) -> Result<IntoIter<DisplayMode>, OomError> {
+    pub fn display_modes_raw(&self) -> Result<IntoIter<DisplayMode>, DisplayError> {
         let fns = self.physical_device.instance().fns();
 
         let mode_properties = unsafe {
@@ -371,35 +398,77 @@ pub struct DisplayMode {
 }
 
 impl DisplayMode {
-    /*pub fn new(display: &Display) -> Result<Arc<DisplayMode>, OomError> {
-        let fns = instance.fns();
-        assert!(device.instance().enabled_extensions().khr_display);     // TODO: return error instead
+    /// Creates a new display mode on `display`, with the given visible region and refresh rate.
+    ///
+    /// The refresh rate is in millihertz, as returned by [`refresh_rate`](Self::refresh_rate).
+    /// Whether the mode is supported by the display is determined by the implementation, which
+    /// returns [`DisplayError::InitializationFailed`] if it is not.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if any of the values of `visible_region` is zero, or if `refresh_rate` is zero.
+    pub fn new(
+        display: &Display,
+        visible_region: [u32; 2],
+        refresh_rate: u32,
+    ) -> Result<DisplayMode, DisplayError> {
+        if !display
+            .physical_device()
+            .instance()
+            .enabled_extensions()
+            .khr_display
+        {
+            return Err(DisplayError::RequirementNotMet {
+                required_for: "`DisplayMode::new`",
+                requires_one_of: RequiresOneOf {
+                    instance_extensions: &["khr_display"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkDisplayModeParametersKHR-width-01990
+        // VUID-VkDisplayModeParametersKHR-height-01991
+        assert!(visible_region[0] != 0 && visible_region[1] != 0);
+
+        // VUID-VkDisplayModeParametersKHR-refreshRate-01992
+        assert!(refresh_rate != 0);
 
         let parameters = ash::vk::DisplayModeParametersKHR {
-            visibleRegion: ash::vk::Extent2D { width: , height:  },
-            refreshRate: ,
+            visible_region: ash::vk::Extent2D {
+                width: visible_region[0],
+                height: visible_region[1],
+            },
+            refresh_rate,
         };
 
-        let display_mode = {
-            let infos = ash::vk::DisplayModeCreateInfoKHR {
-                flags: ash::vk::DisplayModeCreateFlags::empty(),
-                parameters: parameters,
+        let display_mode = unsafe {
+            let create_info = ash::vk::DisplayModeCreateInfoKHR {
+                flags: ash::vk::DisplayModeCreateFlagsKHR::empty(),
+                parameters,
                 ..Default::default()
             };
 
-            let mut output = mem::uninitialized();
-            (fns.v1_0.CreateDisplayModeKHR)(display.device.handle(),
-                                                      display.display, &infos, ptr::null(),
-                                                      &mut output).result().map_err(VulkanError::from)?;
-            output
+            let fns = display.physical_device().instance().fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_display.create_display_mode_khr)(
+                display.physical_device().handle(),
+                display.handle(),
+                &create_info,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
         };
 
-        Ok(Arc::new(DisplayMode {
-            instance: display.device.instance().clone(),
-            display_mode: display_mode,
-            parameters: ,
-        }))
-    }*/
+        Ok(DisplayMode {
+            display: display.clone(),
+            display_mode,
+            parameters,
+        })
+    }
 
     /// Returns the display corresponding to this mode.
     #[inline]
@@ -448,3 +517,74 @@ unsafe impl VulkanObject for DisplayMode {
         self.display_mode
     }
 }
+
+/// Error that can happen when enumerating displays or creating display modes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisplayError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The implementation does not support the requested display mode.
+    InitializationFailed,
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for DisplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::OomError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl FmtDisplay for DisplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OomError(_) => write!(f, "not enough memory available"),
+            Self::InitializationFailed => write!(
+                f,
+                "the implementation does not support the requested display mode",
+            ),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for DisplayError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            err @ VulkanError::OutOfHostMemory | err @ VulkanError::OutOfDeviceMemory => {
+                Self::OomError(err.into())
+            }
+            VulkanError::InitializationFailed => Self::InitializationFailed,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<OomError> for DisplayError {
+    fn from(err: OomError) -> Self {
+        Self::OomError(err)
+    }
+}
+
+impl From<RequirementNotMet> for DisplayError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
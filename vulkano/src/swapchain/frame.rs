@@ -0,0 +1,350 @@
+// Copyright (c) 2022 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{acquire_next_image, AcquireError, Swapchain, SwapchainPresentInfo};
+use crate::{
+    device::{Device, DeviceOwned, Queue},
+    sync::future::{FenceSignalFuture, FlushError, GpuFuture},
+};
+use std::{any::Any, mem::take, sync::Arc, time::Duration};
+
+/// Manages a fixed number of frames in flight.
+///
+/// Rendering a frame consists of acquiring a swapchain image, submitting work that draws to it,
+/// and presenting it. To keep the GPU busy, the CPU should be able to prepare the next frame
+/// while the previous ones are still being processed, but not get arbitrarily far ahead of the
+/// GPU. A `FrameManager` takes care of this:
+///
+/// - [`acquire`] waits until the frame that last used the current slot has finished executing,
+///   then acquires the next swapchain image. The returned future is joined with the previous
+///   frame, so that resources shared between frames are correctly synchronized.
+/// - [`present`] presents the image, signals a fence to know when the frame is finished, and
+///   moves on to the next slot.
+/// - [`defer_drop`] keeps a value alive until the GPU has finished using it, which is
+///   useful for per-frame resources that must be replaced, for example when the swapchain is
+///   recreated.
+///
+/// # Examples
+///
+/// ```no_run
+/// use vulkano::{
+///     swapchain::{AcquireError, FrameManager, SwapchainPresentInfo},
+///     sync::{FlushError, GpuFuture},
+/// };
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+/// # let queue: std::sync::Arc<vulkano::device::Queue> = return;
+/// # let swapchain: std::sync::Arc<vulkano::swapchain::Swapchain> = return;
+///
+/// let mut frame_manager = FrameManager::new(device.clone(), 2);
+///
+/// loop {
+///     let (image_index, suboptimal, acquire_future) =
+///         match frame_manager.acquire(swapchain.clone(), None) {
+///             Ok(r) => r,
+///             Err(AcquireError::OutOfDate) => {
+///                 // Recreate the swapchain...
+///                 continue;
+///             }
+///             Err(err) => panic!("{:?}", err),
+///         };
+///
+///     // let future = acquire_future.then_execute(queue.clone(), command_buffer).unwrap();
+///     let future = acquire_future;
+///
+///     match frame_manager.present(
+///         future,
+///         queue.clone(),
+///         SwapchainPresentInfo::swapchain_image_index(swapchain.clone(), image_index),
+///     ) {
+///         Ok(()) => (),
+///         Err(FlushError::OutOfDate) => {
+///             // Recreate the swapchain...
+///         }
+///         Err(err) => panic!("{:?}", err),
+///     }
+/// }
+/// ```
+///
+/// [`acquire`]: Self::acquire
+/// [`present`]: Self::present
+/// [`defer_drop`]: Self::defer_drop
+pub struct FrameManager {
+    device: Arc<Device>,
+    frames: Vec<Frame>,
+    current_frame: usize,
+    acquired: bool,
+}
+
+// The state of one frame in flight.
+#[derive(Default)]
+struct Frame {
+    // The future of the last submission that used this frame, if it may still be executing.
+    future: Option<Arc<FenceSignalFuture<Box<dyn GpuFuture + Send + Sync>>>>,
+    // Values that must be kept alive until `future` has finished.
+    deferred: Vec<Box<dyn Any + Send + Sync>>,
+}
+
+impl FrameManager {
+    /// Creates a new `FrameManager` with `frames_in_flight` frames.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `frames_in_flight` is 0.
+    pub fn new(device: Arc<Device>, frames_in_flight: usize) -> Self {
+        assert!(frames_in_flight != 0);
+
+        FrameManager {
+            device,
+            frames: (0..frames_in_flight).map(|_| Frame::default()).collect(),
+            current_frame: 0,
+            acquired: false,
+        }
+    }
+
+    /// Returns the number of frames in flight.
+    #[inline]
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the index of the current frame, which is less than
+    /// [`frames_in_flight`](Self::frames_in_flight).
+    ///
+    /// This can be used to index per-frame resources. Once [`acquire`](Self::acquire) has
+    /// returned, the resources of the current frame are no longer in use by the GPU.
+    #[inline]
+    pub fn current_frame(&self) -> usize {
+        self.current_frame
+    }
+
+    /// Waits for the current frame to be no longer in use by the GPU, then acquires the next
+    /// image of `swapchain`.
+    ///
+    /// The return value is the same as for [`acquire_next_image`], except that the future also
+    /// includes the end of the previous frame.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if an image was already acquired and not presented with
+    ///   [`present`](Self::present).
+    /// - Panics if `swapchain` does not belong to the same device as `self`.
+    pub fn acquire(
+        &mut self,
+        swapchain: Arc<Swapchain>,
+        timeout: Option<Duration>,
+    ) -> Result<(u32, bool, Box<dyn GpuFuture + Send + Sync>), AcquireError> {
+        assert!(!self.acquired);
+        assert_eq!(&self.device, swapchain.device());
+
+        // The future was already flushed, so waiting for it can only fail if the device was lost
+        // or ran out of memory.
+        self.wait_frame(self.current_frame)
+            .map_err(|err| match err {
+                FlushError::OomError(err) => AcquireError::OomError(err),
+                _ => AcquireError::DeviceLost,
+            })?;
+
+        let (image_index, suboptimal, acquire_future) = acquire_next_image(swapchain, timeout)?;
+        let future = self.join_previous_frame(acquire_future.boxed_send_sync());
+
+        self.acquired = true;
+
+        Ok((image_index, suboptimal, future))
+    }
+
+    /// Presents the image that was returned by [`acquire`](Self::acquire), after `future`, and
+    /// moves on to the next frame.
+    ///
+    /// If an error is returned, the swapchain most likely needs to be recreated. The manager
+    /// moves on to the next frame regardless.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no image was acquired with [`acquire`](Self::acquire).
+    pub fn present<F>(
+        &mut self,
+        future: F,
+        queue: Arc<Queue>,
+        swapchain_info: SwapchainPresentInfo,
+    ) -> Result<(), FlushError>
+    where
+        F: GpuFuture + Send + Sync + 'static,
+    {
+        assert!(self.acquired);
+        self.acquired = false;
+
+        let result = future
+            .then_swapchain_present(queue, swapchain_info)
+            .boxed_send_sync()
+            .then_signal_fence_and_flush();
+
+        self.end_frame(result)
+    }
+
+    /// Keeps `value` alive until the GPU has finished executing the current frame.
+    ///
+    /// The value is dropped during a later call to [`acquire`](Self::acquire) or
+    /// [`wait_idle`](Self::wait_idle), once the current frame is no longer in use.
+    pub fn defer_drop<T>(&mut self, value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        self.frames[self.current_frame]
+            .deferred
+            .push(Box::new(value));
+    }
+
+    /// Waits until all frames are no longer in use by the GPU, and drops all deferred values.
+    ///
+    /// This is useful before recreating the swapchain or destroying resources that are used by
+    /// all frames.
+    pub fn wait_idle(&mut self) -> Result<(), FlushError> {
+        for index in 0..self.frames.len() {
+            self.wait_frame(index)?;
+        }
+
+        Ok(())
+    }
+
+    // Waits for the frame at `index` to finish, and drops its deferred values.
+    fn wait_frame(&mut self, index: usize) -> Result<(), FlushError> {
+        let frame = &mut self.frames[index];
+
+        if let Some(future) = frame.future.take() {
+            future.wait(None)?;
+        }
+
+        drop(take(&mut frame.deferred));
+
+        Ok(())
+    }
+
+    // Makes `future` wait for the end of the previous frame.
+    fn join_previous_frame(
+        &mut self,
+        future: Box<dyn GpuFuture + Send + Sync>,
+    ) -> Box<dyn GpuFuture + Send + Sync> {
+        let previous_frame = (self.current_frame + self.frames.len() - 1) % self.frames.len();
+
+        match self.frames[previous_frame].future.clone() {
+            Some(mut previous_future) => {
+                previous_future.cleanup_finished();
+                previous_future.join(future).boxed_send_sync()
+            }
+            None => future,
+        }
+    }
+
+    // Stores the future of the submission of the current frame, and moves on to the next frame.
+    fn end_frame(
+        &mut self,
+        result: Result<FenceSignalFuture<Box<dyn GpuFuture + Send + Sync>>, FlushError>,
+    ) -> Result<(), FlushError> {
+        let frame = &mut self.frames[self.current_frame];
+
+        let result = match result {
+            Ok(future) => {
+                frame.future = Some(Arc::new(future));
+                Ok(())
+            }
+            Err(err) => {
+                // The failed future already blocked until any submitted work finished, when
+                // it was dropped.
+                frame.future = None;
+                Err(err)
+            }
+        };
+
+        self.current_frame = (self.current_frame + 1) % self.frames.len();
+
+        result
+    }
+}
+
+unsafe impl DeviceOwned for FrameManager {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl Drop for FrameManager {
+    fn drop(&mut self) {
+        // Make sure that deferred values aren't dropped while the GPU could still be using them.
+        let _ = self.wait_idle();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FrameManager;
+    use crate::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        sync::{self, GpuFuture},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn frame_manager_cycles_frames() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+
+        let mut frame_manager = FrameManager::new(device.clone(), 3);
+        assert_eq!(frame_manager.frames_in_flight(), 3);
+
+        // One resource per frame in flight, and one value deferred by each submission.
+        let per_frame: Vec<_> = (0..3).map(|_| Arc::new(())).collect();
+        let deferred: Vec<_> = (0..4).map(|_| Arc::new(())).collect();
+
+        for (frame, deferred_value) in deferred.iter().enumerate() {
+            let index = frame_manager.current_frame();
+            assert_eq!(index, frame % 3);
+
+            frame_manager.wait_frame(index).unwrap();
+
+            // Coming back to the first frame, the value that was deferred by its previous
+            // submission has been dropped, but those of the other frames are still alive.
+            if frame == 3 {
+                assert_eq!(Arc::strong_count(&deferred[0]), 1);
+                assert_eq!(Arc::strong_count(&deferred[1]), 2);
+                assert_eq!(Arc::strong_count(&deferred[2]), 2);
+            }
+
+            let resource = per_frame[index].clone();
+            frame_manager.defer_drop(resource);
+            frame_manager.defer_drop(deferred_value.clone());
+
+            let command_buffer = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+            .build()
+            .unwrap();
+            let future = frame_manager
+                .join_previous_frame(sync::now(device.clone()).boxed_send_sync())
+                .then_execute(queue.clone(), command_buffer)
+                .unwrap()
+                .boxed_send_sync()
+                .then_signal_fence_and_flush();
+            frame_manager.end_frame(future).unwrap();
+        }
+
+        // The fourth frame reused the resource of the first one.
+        assert_eq!(frame_manager.current_frame(), 1);
+        assert_eq!(Arc::strong_count(&per_frame[0]), 2);
+
+        frame_manager.wait_idle().unwrap();
+        assert!(deferred.iter().all(|value| Arc::strong_count(value) == 1));
+        assert!(per_frame.iter().all(|value| Arc::strong_count(value) == 1));
+    }
+}
@@ -264,6 +264,10 @@
 //! }
 //! ```
 //!
+//! When rendering to several windows, the images of all their swapchains can be presented with a
+//! single present operation by using `then_swapchain_present_multi` instead. The result for each
+//! individual swapchain is then available from `PresentFuture::results`.
+//!
 //! ## Recreating a swapchain
 //!
 //! In some situations, the swapchain will become invalid by itself. This includes for example when
@@ -322,15 +326,17 @@
 //! ```
 
 pub use self::{
+    frame::FrameManager,
     surface::{
         ColorSpace, CompositeAlpha, CompositeAlphas, PresentMode, Surface, SurfaceApi,
         SurfaceCapabilities, SurfaceCreationError, SurfaceInfo, SurfaceTransform,
         SurfaceTransforms,
     },
     swapchain::{
-        acquire_next_image, acquire_next_image_raw, present, wait_for_present, AcquireError,
-        AcquiredImage, FullScreenExclusive, FullScreenExclusiveError, PresentFuture,
-        PresentWaitError, Swapchain, SwapchainAcquireFuture, SwapchainCreateInfo,
+        acquire_next_image, acquire_next_image_raw, present, present_multi, wait_for_present,
+        AcquireError, AcquiredImage, DisplayTimingError, FullScreenExclusive,
+        FullScreenExclusiveError, HdrMetadata, HdrMetadataError, PastPresentationTiming,
+        PresentFuture, PresentWaitError, Swapchain, SwapchainAcquireFuture, SwapchainCreateInfo,
         SwapchainCreationError, Win32Monitor,
     },
 };
@@ -344,6 +350,7 @@ use std::{
 };
 
 pub mod display;
+mod frame;
 mod surface;
 mod swapchain;
 
@@ -393,27 +400,46 @@ pub struct SwapchainPresentInfo {
     /// An id used to identify this present operation.
     ///
     /// If `present_id` is `Some`, the [`present_id`](crate::device::Features::present_id) feature
-    /// must be enabled on the device. The id must be greater than any id previously used for
-    /// `swapchain`. If a swapchain is recreated, this resets.
+    /// must be enabled on the device; when presenting through a [`GpuFuture`], the id is ignored
+    /// otherwise. The id must be greater than any id previously used for `swapchain`. If a
+    /// swapchain is recreated, this resets.
+    ///
+    /// With the [`present_wait`](crate::device::Features::present_wait) feature, you can wait
+    /// until the present operation has been displayed with [`wait_for_present`].
+    ///
+    /// [`GpuFuture`]: crate::sync::GpuFuture
     ///
     /// The default value is `None`.
     pub present_id: Option<NonZeroU64>,
 
-    /// Am optimization hint to the implementation, that only some parts of the swapchain image are
-    /// going to be updated by the present operation.
+    /// An optimization hint to the implementation, that only some parts of the swapchain image
+    /// have changed since the last time that it was presented, also known as damage regions.
     ///
-    /// If `present_regions` is not empty, the
+    /// If the
     /// [`khr_incremental_present`](crate::device::DeviceExtensions::khr_incremental_present)
-    /// extension must be enabled on the device. The implementation will update the provided
-    /// regions of the swapchain image, and _may_ ignore the other areas. However, as this is just
-    /// a hint, the Vulkan implementation is free to ignore the regions altogether and update
-    /// everything.
+    /// extension is enabled on the device, the implementation will update the provided regions of
+    /// the swapchain image, and _may_ ignore the other areas. However, as this is just a hint, the
+    /// Vulkan implementation is free to ignore the regions altogether and update everything. If
+    /// the extension is not enabled, the regions are ignored.
+    ///
+    /// Each region must be [compatible](RectangleLayer::is_compatible_with) with `swapchain`.
     ///
     /// If `present_regions` is empty, that means that all of the swapchain image must be updated.
     ///
     /// The default value is empty.
     pub present_regions: Vec<RectangleLayer>,
 
+    /// The time at which the image should be presented to the user, and an id to identify the
+    /// present operation with when retrieving its timing afterwards with
+    /// [`Swapchain::past_presentation_timing`].
+    ///
+    /// If `present_time` is `Some`, the
+    /// [`google_display_timing`](crate::device::DeviceExtensions::google_display_timing)
+    /// extension must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub present_time: Option<PresentTime>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -426,6 +452,7 @@ impl SwapchainPresentInfo {
             image_index,
             present_id: None,
             present_regions: Vec::new(),
+            present_time: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -446,10 +473,30 @@ pub struct RectangleLayer {
 
 impl RectangleLayer {
     /// Returns true if this rectangle layer is compatible with swapchain.
+    ///
+    /// The rectangle must lie within the images of the swapchain, after being transformed
+    /// according to the [`pre_transform`](Swapchain::pre_transform) of the swapchain, and `layer`
+    /// must be less than the number of array layers of the images.
     #[inline]
     pub fn is_compatible_with(&self, swapchain: &Swapchain) -> bool {
-        self.offset[0] + self.extent[0] <= swapchain.image_extent()[0]
-            && self.offset[1] + self.extent[1] <= swapchain.image_extent()[1]
+        let [width, height] = swapchain.image_extent();
+
+        // VUID-VkRectLayerKHR-offset-04864
+        let image_extent = if swapchain.pre_transform().swaps_extent() {
+            [height, width]
+        } else {
+            [width, height]
+        };
+
+        (self.offset.iter())
+            .zip(&self.extent)
+            .zip(image_extent)
+            .all(|((&offset, &extent), image_extent)| {
+                offset
+                    .checked_add(extent)
+                    .map_or(false, |end| end <= image_extent)
+            })
+            // VUID-VkRectLayerKHR-layer-01262
             && self.layer < swapchain.image_array_layers()
     }
 }
@@ -471,6 +518,31 @@ impl From<&RectangleLayer> for ash::vk::RectLayerKHR {
     }
 }
 
+/// The desired time of a present operation, for the `google_display_timing` extension.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PresentTime {
+    /// An application-provided id, that is returned in the [`PastPresentationTiming`] of the
+    /// present operation. It does not need to be unique.
+    pub present_id: u32,
+
+    /// The earliest time at which the image should be presented to the user, in nanoseconds.
+    ///
+    /// This is compared against the presentation times that are returned by
+    /// [`Swapchain::past_presentation_timing`]. A value of 0 means that the image may be
+    /// presented at any time.
+    pub desired_present_time: u64,
+}
+
+impl From<&PresentTime> for ash::vk::PresentTimeGOOGLE {
+    #[inline]
+    fn from(val: &PresentTime) -> Self {
+        ash::vk::PresentTimeGOOGLE {
+            present_id: val.present_id,
+            desired_present_time: val.desired_present_time,
+        }
+    }
+}
+
 /// Internal trait so that creating/destroying a swapchain can access the surface's "has_swapchain"
 /// flag.
 // TODO: use pub(crate) maybe?
@@ -12,7 +12,7 @@ use crate::{
     cache::OnceCache,
     format::Format,
     image::ImageUsage,
-    instance::Instance,
+    instance::{Instance, InstanceExtensions},
     macros::{vulkan_bitflags_enum, vulkan_enum},
     swapchain::{
         display::{DisplayMode, DisplayPlane},
@@ -21,9 +21,11 @@ use crate::{
     OomError, RequiresOneOf, VulkanError, VulkanObject,
 };
 
-#[cfg(target_os = "ios")]
+#[cfg(any(target_os = "macos", target_os = "ios"))]
 use objc::{class, msg_send, runtime::Object, sel, sel_impl};
-
+use raw_window_handle::{
+    HasRawDisplayHandle, HasRawWindowHandle, RawDisplayHandle, RawWindowHandle,
+};
 use std::{
     any::Any,
     error::Error,
@@ -92,6 +94,15 @@ impl Surface {
     ///
     /// Presenting to a headless surface does nothing, so this is mostly useless in itself. However,
     /// it may be useful for testing, and it is available for future extensions to layer on top of.
+    /// Because it doesn't require a window system, it allows running the full swapchain code path,
+    /// including acquiring and presenting images, on machines without a display.
+    ///
+    /// A headless surface has no current extent, so the
+    /// [`image_extent`](crate::swapchain::SwapchainCreateInfo::image_extent) must be given
+    /// explicitly when creating a swapchain for it.
+    ///
+    /// The [`ext_headless_surface`](crate::instance::InstanceExtensions::ext_headless_surface)
+    /// extension must be enabled on the instance.
     pub fn headless(
         instance: Arc<Instance>,
         object: Option<Arc<dyn Any + Send + Sync>>,
@@ -147,6 +158,135 @@ impl Surface {
         )))
     }
 
+    /// Returns the instance extensions required to create a surface from a window of the given
+    /// event loop.
+    ///
+    /// The returned extensions should be enabled when creating the instance, in order to use
+    /// [`from_window`](Self::from_window). Only the extensions for the windowing API that
+    /// `event_loop` uses are returned.
+    pub fn required_extensions(event_loop: &impl HasRawDisplayHandle) -> InstanceExtensions {
+        let mut extensions = InstanceExtensions {
+            khr_surface: true,
+            ..InstanceExtensions::empty()
+        };
+
+        match event_loop.raw_display_handle() {
+            RawDisplayHandle::Android(_) => extensions.khr_android_surface = true,
+            // `mvk_macos_surface` and `mvk_ios_surface` are deprecated in favor of this one.
+            RawDisplayHandle::AppKit(_) | RawDisplayHandle::UiKit(_) => {
+                extensions.ext_metal_surface = true
+            }
+            RawDisplayHandle::Wayland(_) => extensions.khr_wayland_surface = true,
+            RawDisplayHandle::Windows(_) => extensions.khr_win32_surface = true,
+            RawDisplayHandle::Xcb(_) => extensions.khr_xcb_surface = true,
+            RawDisplayHandle::Xlib(_) => extensions.khr_xlib_surface = true,
+            _ => (),
+        }
+
+        extensions
+    }
+
+    /// Creates a `Surface` from a window, using the handles that it exposes through the
+    /// `raw-window-handle` crate.
+    ///
+    /// This works with any windowing library that implements the `raw-window-handle` traits, such
+    /// as `winit` or `sdl2`. The Win32, Xlib, XCB, Wayland, AppKit, UIKit and Android NDK
+    /// windowing APIs are supported. On macOS and iOS, a `CAMetalLayer` is attached to the view of
+    /// the window if it does not have one already, and the surface is created with
+    /// [`ext_metal_surface`](crate::instance::InstanceExtensions::ext_metal_surface) if it is
+    /// enabled, or with the older MoltenVK extensions otherwise.
+    ///
+    /// The surface keeps `window` alive, so that the window is never destroyed before the
+    /// surface. The extensions returned by [`required_extensions`](Self::required_extensions)
+    /// must be enabled on the instance.
+    pub fn from_window(
+        instance: Arc<Instance>,
+        window: Arc<impl HasRawWindowHandle + HasRawDisplayHandle + Any + Send + Sync>,
+    ) -> Result<Arc<Self>, SurfaceCreationError> {
+        unsafe {
+            Self::from_raw_handles(
+                instance,
+                window.raw_window_handle(),
+                window.raw_display_handle(),
+                Some(window),
+            )
+        }
+    }
+
+    /// Creates a `Surface` from a reference to a window, using the handles that it exposes
+    /// through the `raw-window-handle` crate.
+    ///
+    /// This is the same as [`from_window`](Self::from_window), except that the surface does not
+    /// keep the window alive.
+    ///
+    /// # Safety
+    ///
+    /// - `window` must outlive the created `Surface`.
+    pub unsafe fn from_window_ref(
+        instance: Arc<Instance>,
+        window: &(impl HasRawWindowHandle + HasRawDisplayHandle),
+    ) -> Result<Arc<Self>, SurfaceCreationError> {
+        Self::from_raw_handles(
+            instance,
+            window.raw_window_handle(),
+            window.raw_display_handle(),
+            None,
+        )
+    }
+
+    unsafe fn from_raw_handles(
+        instance: Arc<Instance>,
+        window_handle: RawWindowHandle,
+        display_handle: RawDisplayHandle,
+        object: Option<Arc<dyn Any + Send + Sync>>,
+    ) -> Result<Arc<Self>, SurfaceCreationError> {
+        match (window_handle, display_handle) {
+            (RawWindowHandle::AndroidNdk(window), RawDisplayHandle::Android(_)) => {
+                Self::from_android(instance, window.a_native_window, object)
+            }
+            #[cfg(target_os = "macos")]
+            (RawWindowHandle::AppKit(window), RawDisplayHandle::AppKit(_)) => {
+                let layer = get_metal_layer_macos(window.ns_view);
+
+                if instance.enabled_extensions().ext_metal_surface {
+                    Self::from_metal(instance, layer as *const (), object)
+                } else {
+                    Self::from_mac_os(instance, layer as *const (), object)
+                }
+            }
+            #[cfg(target_os = "ios")]
+            (RawWindowHandle::UiKit(window), RawDisplayHandle::UiKit(_)) => {
+                let metal_layer = get_metal_layer_ios(window.ui_view);
+
+                if instance.enabled_extensions().ext_metal_surface {
+                    let mut surface = Self::from_metal(
+                        instance,
+                        metal_layer.render_layer.0 as *const (),
+                        object,
+                    )?;
+                    Arc::get_mut(&mut surface).unwrap().metal_layer = metal_layer;
+
+                    Ok(surface)
+                } else {
+                    Self::from_ios(instance, metal_layer, object)
+                }
+            }
+            (RawWindowHandle::Wayland(window), RawDisplayHandle::Wayland(display)) => {
+                Self::from_wayland(instance, display.display, window.surface, object)
+            }
+            (RawWindowHandle::Win32(window), RawDisplayHandle::Windows(_)) => {
+                Self::from_win32(instance, window.hinstance, window.hwnd, object)
+            }
+            (RawWindowHandle::Xcb(window), RawDisplayHandle::Xcb(display)) => {
+                Self::from_xcb(instance, display.connection, window.window, object)
+            }
+            (RawWindowHandle::Xlib(window), RawDisplayHandle::Xlib(display)) => {
+                Self::from_xlib(instance, display.display, window.window, object)
+            }
+            _ => Err(SurfaceCreationError::WindowHandleNotSupported),
+        }
+    }
+
     /// Creates a `Surface` from a `DisplayPlane`.
     ///
     /// # Panics
@@ -202,9 +342,9 @@ impl Surface {
             flags: ash::vk::DisplaySurfaceCreateFlagsKHR::empty(),
             display_mode: display_mode.handle(),
             plane_index: plane.index(),
-            plane_stack_index: 0, // FIXME: plane.properties.currentStackIndex,
+            plane_stack_index: plane.current_stack_index(),
             transform: ash::vk::SurfaceTransformFlagsKHR::IDENTITY, // TODO: let user choose
-            global_alpha: 0.0,    // TODO: let user choose
+            global_alpha: 0.0,                                      // TODO: let user choose
             alpha_mode: ash::vk::DisplayPlaneAlphaFlagsKHR::OPAQUE, // TODO: let user choose
             image_extent: ash::vk::Extent2D {
                 // TODO: let user choose
@@ -1345,6 +1485,9 @@ pub enum SurfaceCreationError {
         required_for: &'static str,
         requires_one_of: RequiresOneOf,
     },
+
+    /// The window uses a windowing API that is not supported on the current platform.
+    WindowHandleNotSupported,
 }
 
 impl Error for SurfaceCreationError {
@@ -1368,6 +1511,10 @@ impl Display for SurfaceCreationError {
                 "a requirement was not met for: {}; requires one of: {}",
                 required_for, requires_one_of,
             ),
+            Self::WindowHandleNotSupported => write!(
+                f,
+                "the window uses a windowing API that is not supported on the current platform",
+            ),
         }
     }
 }
@@ -1490,17 +1637,25 @@ vulkan_enum! {
     /// This is the equivalent of OpenGL's `SwapInterval` with a value of -1.
     FifoRelaxed = FIFO_RELAXED,
 
-    /* TODO: enable
-    // TODO: document
-    SharedDemandRefresh = SHARED_DEMAND_REFRESH_KHR {
+    /// The swapchain has a single image, which is shared between the application and the
+    /// presentation engine. The presentation engine only updates the displayed image after the
+    /// application presents it, but the application can keep rendering to the image afterwards
+    /// without acquiring it again.
+    ///
+    /// The swapchain must be created with a `min_image_count` of 1.
+    SharedDemandRefresh = SHARED_DEMAND_REFRESH {
         device_extensions: [khr_shared_presentable_image],
-    },*/
+    },
 
-    /* TODO: enable
-    // TODO: document
-    SharedContinuousRefresh = SHARED_CONTINUOUS_REFRESH_KHR {
+    /// The swapchain has a single image, which is shared between the application and the
+    /// presentation engine. The presentation engine periodically updates the displayed image on
+    /// its regular refresh cycle, so presenting is only needed once, after which any changes the
+    /// application makes to the image may become visible at any time. Tearing may be visible.
+    ///
+    /// The swapchain must be created with a `min_image_count` of 1.
+    SharedContinuousRefresh = SHARED_CONTINUOUS_REFRESH {
         device_extensions: [khr_shared_presentable_image],
-    },*/
+    },
 }
 
 vulkan_bitflags_enum! {
@@ -1542,6 +1697,60 @@ vulkan_bitflags_enum! {
     INHERIT, Inherit = INHERIT,
 }
 
+impl SurfaceTransform {
+    /// Returns whether the transform rotates the image by 90 or 270 degrees, which means that the
+    /// width and height of the image are swapped when it is presented.
+    #[inline]
+    pub fn swaps_extent(self) -> bool {
+        matches!(
+            self,
+            Self::Rotate90
+                | Self::Rotate270
+                | Self::HorizontalMirrorRotate90
+                | Self::HorizontalMirrorRotate270
+        )
+    }
+
+    /// Returns the matrix that must be applied to clip-space positions in order to pre-rotate the
+    /// rendering for this transform.
+    ///
+    /// When a swapchain is created with a [`pre_transform`] that matches the
+    /// [`current_transform`] of the surface, the presentation engine doesn't need to transform
+    /// the images itself, which on mobile devices avoids a costly composition pass. The
+    /// application must then render the images already transformed, by multiplying this matrix
+    /// with its projection matrix, and use an image extent with the width and height swapped if
+    /// [`swaps_extent`](Self::swaps_extent) returns `true`.
+    ///
+    /// The matrix is in column-major order, so each inner array is a column, as expected by
+    /// GLSL's `mat4`. It accounts for the Vulkan clip space, where the Y axis points down. For
+    /// example, the matrix for `Rotate90` rotates clip-space positions by 90 degrees clockwise,
+    /// so that the position `(1, 0)` ends up at `(0, 1)`.
+    ///
+    /// [`pre_transform`]: crate::swapchain::SwapchainCreateInfo::pre_transform
+    /// [`current_transform`]: crate::swapchain::SurfaceCapabilities::current_transform
+    pub fn pre_rotation_matrix(self) -> [[f32; 4]; 4] {
+        // The clip-space position `(x, y)` to render at, for the position `(u, v)` that the
+        // application intended. The mirrored transforms mirror horizontally first, then rotate.
+        let [[xu, xv], [yu, yv]]: [[f32; 2]; 2] = match self {
+            Self::Identity | Self::Inherit => [[1.0, 0.0], [0.0, 1.0]],
+            Self::Rotate90 => [[0.0, -1.0], [1.0, 0.0]],
+            Self::Rotate180 => [[-1.0, 0.0], [0.0, -1.0]],
+            Self::Rotate270 => [[0.0, 1.0], [-1.0, 0.0]],
+            Self::HorizontalMirror => [[-1.0, 0.0], [0.0, 1.0]],
+            Self::HorizontalMirrorRotate90 => [[0.0, -1.0], [-1.0, 0.0]],
+            Self::HorizontalMirrorRotate180 => [[1.0, 0.0], [0.0, -1.0]],
+            Self::HorizontalMirrorRotate270 => [[0.0, 1.0], [1.0, 0.0]],
+        };
+
+        [
+            [xu, yu, 0.0, 0.0],
+            [xv, yv, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]
+    }
+}
+
 impl Default for SurfaceTransform {
     #[inline]
     fn default() -> SurfaceTransform {
@@ -1668,80 +1877,88 @@ vulkan_enum! {
     /// space and perform a manual conversion to that color space from inside your shader.
     ColorSpace = ColorSpaceKHR(i32);
 
-    // TODO: document
+    /// The sRGB color space, with the sRGB transfer function.
     SrgbNonLinear = SRGB_NONLINEAR,
 
-    // TODO: document
+    /// The Display-P3 color space, with the sRGB transfer function.
     DisplayP3NonLinear = DISPLAY_P3_NONLINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The extended sRGB color space (also known as scRGB), with a linear transfer function.
+    ///
+    /// Component values outside the `[0.0, 1.0]` range are allowed, to represent colors outside of
+    /// the sRGB gamut and luminances above the SDR white level. This is usually combined with a
+    /// floating-point format, such as `R16G16B16A16_SFLOAT`.
     ExtendedSrgbLinear = EXTENDED_SRGB_LINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The extended sRGB color space, with the extended sRGB transfer function.
     ExtendedSrgbNonLinear = EXTENDED_SRGB_NONLINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The Display-P3 color space, with a linear transfer function.
     DisplayP3Linear = DISPLAY_P3_LINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The DCI-P3 color space, with the DCI-P3 transfer function.
     DciP3NonLinear = DCI_P3_NONLINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The BT.709 color space, with a linear transfer function.
     Bt709Linear = BT709_LINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The BT.709 color space, with the BT.709 transfer function.
     Bt709NonLinear = BT709_NONLINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The BT.2020 color space, with a linear transfer function.
     Bt2020Linear = BT2020_LINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The HDR10 color space: the BT.2020 color space, with the SMPTE ST 2084 perceptual
+    /// quantizer (PQ) transfer function.
+    ///
+    /// The HDR metadata of the swapchain can be set with
+    /// [`Swapchain::set_hdr_metadata`](crate::swapchain::Swapchain::set_hdr_metadata).
     Hdr10St2084 = HDR10_ST2084_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The Dolby Vision color space, with a proprietary encoding.
     DolbyVision = DOLBYVISION_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The BT.2020 color space, with the hybrid log-gamma (HLG) transfer function.
     Hdr10Hlg = HDR10_HLG_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The Adobe RGB color space, with a linear transfer function.
     AdobeRgbLinear = ADOBERGB_LINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The Adobe RGB color space, with a gamma 2.2 transfer function.
     AdobeRgbNonLinear = ADOBERGB_NONLINEAR_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The color components are passed to the display as they are, without any interpretation.
     PassThrough = PASS_THROUGH_EXT {
         instance_extensions: [ext_swapchain_colorspace],
     },
 
-    // TODO: document
+    /// The native color space of the display.
     DisplayNative = DISPLAY_NATIVE_AMD {
         device_extensions: [amd_display_native_hdr],
     },
@@ -1802,6 +2019,57 @@ unsafe impl Send for IOSMetalLayer {}
 #[cfg(target_os = "ios")]
 unsafe impl Sync for IOSMetalLayer {}
 
+/// Returns the `CAMetalLayer` of a macOS `NSView`, first attaching a new one if the view is not
+/// backed by one yet.
+#[cfg(target_os = "macos")]
+unsafe fn get_metal_layer_macos(view: *mut std::ffi::c_void) -> *mut Object {
+    use core_graphics_types::base::CGFloat;
+    use objc::runtime::{BOOL, NO, YES};
+
+    let view: *mut Object = view.cast();
+    let main_layer: *mut Object = msg_send![view, layer];
+    let class = class!(CAMetalLayer);
+    let is_valid_layer: BOOL = msg_send![main_layer, isKindOfClass: class];
+
+    if is_valid_layer == NO {
+        let new_layer: *mut Object = msg_send![class, new];
+        let () = msg_send![new_layer, setEdgeAntialiasingMask: 0];
+        let () = msg_send![new_layer, setPresentsWithTransaction: false];
+        let () = msg_send![new_layer, removeAllAnimations];
+        let () = msg_send![view, setLayer: new_layer];
+        let () = msg_send![view, setWantsLayer: YES];
+        let window: *mut Object = msg_send![view, window];
+
+        if !window.is_null() {
+            let scale_factor: CGFloat = msg_send![window, backingScaleFactor];
+            let () = msg_send![new_layer, setContentsScale: scale_factor];
+        }
+
+        new_layer
+    } else {
+        main_layer
+    }
+}
+
+/// Adds a new `CAMetalLayer` as a sublayer of the layer of an iOS `UIView`.
+#[cfg(target_os = "ios")]
+unsafe fn get_metal_layer_ios(view: *mut std::ffi::c_void) -> IOSMetalLayer {
+    use core_graphics_types::{base::CGFloat, geometry::CGRect};
+
+    let view: *mut Object = view.cast();
+    let main_layer: *mut Object = msg_send![view, layer];
+    let class = class!(CAMetalLayer);
+    let new_layer: *mut Object = msg_send![class, new];
+    let frame: CGRect = msg_send![main_layer, bounds];
+    let () = msg_send![new_layer, setFrame: frame];
+    let () = msg_send![main_layer, addSublayer: new_layer];
+    let screen: *mut Object = msg_send![class!(UIScreen), mainScreen];
+    let scale_factor: CGFloat = msg_send![screen, nativeScale];
+    let () = msg_send![view, setContentScaleFactor: scale_factor];
+
+    IOSMetalLayer::new(view, new_layer)
+}
+
 /// The capabilities of a surface when used by a physical device.
 ///
 /// You have to match these capabilities when you create a swapchain.
@@ -1843,20 +2111,51 @@ pub struct SurfaceCapabilities {
     pub supported_usage_flags: ImageUsage,
 
     /// Whether creating a protected swapchain is supported.
+    ///
+    /// This is always `false` if the
+    /// [`khr_surface_protected_capabilities`](crate::instance::InstanceExtensions::khr_surface_protected_capabilities)
+    /// extension is not enabled on the instance.
     pub supports_protected: bool,
 
     /// Whether full-screen exclusivity is supported.
     pub full_screen_exclusive_supported: bool,
+
+    /// List of image usages that are supported for images of a swapchain that uses the
+    /// [`SharedDemandRefresh`](PresentMode::SharedDemandRefresh) or
+    /// [`SharedContinuousRefresh`](PresentMode::SharedContinuousRefresh) present modes.
+    ///
+    /// This is empty if the
+    /// [`khr_get_surface_capabilities2`](crate::instance::InstanceExtensions::khr_get_surface_capabilities2)
+    /// extension is not enabled on the instance, or if the physical device does not support the
+    /// [`khr_shared_presentable_image`](crate::device::DeviceExtensions::khr_shared_presentable_image)
+    /// extension.
+    pub shared_present_supported_usage_flags: ImageUsage,
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        swapchain::{Surface, SurfaceCreationError},
+        swapchain::{Surface, SurfaceCreationError, SurfaceTransform},
         RequiresOneOf,
     };
     use std::ptr;
 
+    #[test]
+    fn ext_headless_surface_ext_missing() {
+        let instance = instance!();
+        match Surface::headless(instance, None) {
+            Err(SurfaceCreationError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        instance_extensions,
+                        ..
+                    },
+                ..
+            }) if instance_extensions.contains(&"ext_headless_surface") => (),
+            _ => panic!(),
+        }
+    }
+
     #[test]
     fn khr_win32_surface_ext_missing() {
         let instance = instance!();
@@ -1937,4 +2236,115 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    fn apply_matrix(matrix: [[f32; 4]; 4], [x, y]: [f32; 2]) -> [f32; 2] {
+        let position = [x, y, 0.0, 1.0];
+        let mut result = [0.0; 4];
+
+        for (column, &value) in matrix.iter().zip(&position) {
+            for (result, &element) in result.iter_mut().zip(column) {
+                *result += element * value;
+            }
+        }
+
+        [result[0], result[1]]
+    }
+
+    #[test]
+    fn pre_rotation_matrix() {
+        const TOP_RIGHT: [f32; 2] = [1.0, -1.0];
+        const BOTTOM_RIGHT: [f32; 2] = [1.0, 1.0];
+
+        // The expected positions of the top right and bottom right corners, in clip space where
+        // the Y axis points down.
+        let expected = [
+            (SurfaceTransform::Identity, [1.0, -1.0], [1.0, 1.0]),
+            (SurfaceTransform::Rotate90, [1.0, 1.0], [-1.0, 1.0]),
+            (SurfaceTransform::Rotate180, [-1.0, 1.0], [-1.0, -1.0]),
+            (SurfaceTransform::Rotate270, [-1.0, -1.0], [1.0, -1.0]),
+            (
+                SurfaceTransform::HorizontalMirror,
+                [-1.0, -1.0],
+                [-1.0, 1.0],
+            ),
+            (
+                SurfaceTransform::HorizontalMirrorRotate90,
+                [1.0, -1.0],
+                [-1.0, -1.0],
+            ),
+            (
+                SurfaceTransform::HorizontalMirrorRotate180,
+                [1.0, 1.0],
+                [1.0, -1.0],
+            ),
+            (
+                SurfaceTransform::HorizontalMirrorRotate270,
+                [-1.0, 1.0],
+                [1.0, 1.0],
+            ),
+            (SurfaceTransform::Inherit, [1.0, -1.0], [1.0, 1.0]),
+        ];
+
+        for (transform, top_right, bottom_right) in expected {
+            let matrix = transform.pre_rotation_matrix();
+            assert_eq!(
+                apply_matrix(matrix, TOP_RIGHT),
+                top_right,
+                "{:?}",
+                transform
+            );
+            assert_eq!(
+                apply_matrix(matrix, BOTTOM_RIGHT),
+                bottom_right,
+                "{:?}",
+                transform,
+            );
+        }
+
+        // The mirrored transforms mirror horizontally first, then rotate.
+        for (mirrored, rotated) in [
+            (
+                SurfaceTransform::HorizontalMirrorRotate90,
+                SurfaceTransform::Rotate90,
+            ),
+            (
+                SurfaceTransform::HorizontalMirrorRotate180,
+                SurfaceTransform::Rotate180,
+            ),
+            (
+                SurfaceTransform::HorizontalMirrorRotate270,
+                SurfaceTransform::Rotate270,
+            ),
+        ] {
+            for corner in [TOP_RIGHT, BOTTOM_RIGHT] {
+                let mirrored_corner = apply_matrix(
+                    SurfaceTransform::HorizontalMirror.pre_rotation_matrix(),
+                    corner,
+                );
+                assert_eq!(
+                    apply_matrix(mirrored.pre_rotation_matrix(), corner),
+                    apply_matrix(rotated.pre_rotation_matrix(), mirrored_corner),
+                    "{:?}",
+                    mirrored,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn swaps_extent() {
+        for (transform, swaps_extent) in [
+            (SurfaceTransform::Identity, false),
+            (SurfaceTransform::Rotate90, true),
+            (SurfaceTransform::Rotate180, false),
+            (SurfaceTransform::Rotate270, true),
+            (SurfaceTransform::HorizontalMirror, false),
+            (SurfaceTransform::HorizontalMirrorRotate90, true),
+            (SurfaceTransform::HorizontalMirrorRotate180, false),
+            (SurfaceTransform::HorizontalMirrorRotate270, true),
+            (SurfaceTransform::Inherit, false),
+        ] {
+            assert_eq!(transform.swaps_extent(), swaps_extent, "{:?}", transform);
+        }
+    }
 }
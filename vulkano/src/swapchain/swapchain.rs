@@ -27,7 +27,7 @@ use crate::{
         semaphore::{Semaphore, SemaphoreError},
         Sharing,
     },
-    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
+    DeviceSize, OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use parking_lot::Mutex;
 use smallvec::{smallvec, SmallVec};
@@ -63,6 +63,7 @@ pub struct Swapchain {
     composite_alpha: CompositeAlpha,
     present_mode: PresentMode,
     clipped: bool,
+    protected: bool,
     full_screen_exclusive: FullScreenExclusive,
     win32_monitor: Option<Win32Monitor>,
     prev_present_id: AtomicU64,
@@ -70,6 +71,9 @@ pub struct Swapchain {
     // Whether full-screen exclusive is currently held.
     full_screen_exclusive_held: AtomicBool,
 
+    // Whether an acquire or present operation has reported the swapchain as suboptimal.
+    suboptimal: AtomicBool,
+
     // The images of this swapchain.
     images: Vec<ImageEntry>,
 
@@ -128,6 +132,7 @@ impl Swapchain {
             composite_alpha,
             present_mode,
             clipped,
+            protected,
             full_screen_exclusive,
             win32_monitor,
             _ne: _,
@@ -149,10 +154,12 @@ impl Swapchain {
             composite_alpha,
             present_mode,
             clipped,
+            protected,
             full_screen_exclusive,
             win32_monitor,
             prev_present_id: Default::default(),
             full_screen_exclusive_held: AtomicBool::new(false),
+            suboptimal: AtomicBool::new(false),
             images: image_handles
                 .iter()
                 .map(|&handle| ImageEntry {
@@ -227,6 +234,7 @@ impl Swapchain {
             composite_alpha,
             present_mode,
             clipped,
+            protected,
             full_screen_exclusive,
             win32_monitor,
             _ne: _,
@@ -248,10 +256,12 @@ impl Swapchain {
             composite_alpha,
             present_mode,
             clipped,
+            protected,
             full_screen_exclusive,
             win32_monitor,
             prev_present_id: Default::default(),
             full_screen_exclusive_held: AtomicBool::new(full_screen_exclusive_held),
+            suboptimal: AtomicBool::new(false),
             images: image_handles
                 .iter()
                 .map(|&handle| ImageEntry {
@@ -273,6 +283,102 @@ impl Swapchain {
         Ok((swapchain, swapchain_images))
     }
 
+    /// Creates a new swapchain from this one, with the image extent adjusted to the current
+    /// surface.
+    ///
+    /// This re-queries the capabilities of the surface and uses them to pick the new image
+    /// extent and minimum image count. If the surface dictates an extent, that extent is used and
+    /// `image_extent` is ignored; otherwise `image_extent`, which is usually the current size of
+    /// the window, is clamped to the range supported by the surface. All other parameters are
+    /// carried over from this swapchain.
+    ///
+    /// If the window is minimized, the surface may report an extent of zero. In that case,
+    /// [`SwapchainCreationError::ImageExtentZeroLengthDimensions`] is returned, and the caller
+    /// should try again later.
+    pub fn recreate_with_extent(
+        self: &Arc<Self>,
+        image_extent: [u32; 2],
+    ) -> Result<(Arc<Swapchain>, Vec<Arc<SwapchainImage>>), SwapchainCreationError> {
+        self.recreate_for_surface(image_extent, self.present_mode)
+    }
+
+    /// Creates a new swapchain from this one, with a different present mode.
+    ///
+    /// This can be used to switch between present modes at runtime, for example from
+    /// [`PresentMode::Fifo`] to [`PresentMode::Mailbox`] or [`PresentMode::Immediate`] when the
+    /// user disables vertical synchronization. The image extent and minimum image count are
+    /// adjusted to the current surface in the same way as
+    /// [`recreate_with_extent`](Self::recreate_with_extent), with the current image extent as the
+    /// preferred extent. All other parameters are carried over from this swapchain.
+    ///
+    /// If the surface does not support `present_mode`,
+    /// [`SwapchainCreationError::PresentModeNotSupported`] is returned and this swapchain remains
+    /// usable. The supported present modes can be queried with
+    /// [`PhysicalDevice::surface_present_modes`](crate::device::physical::PhysicalDevice::surface_present_modes).
+    ///
+    /// The new swapchain has new images, so any image views and framebuffers that were created
+    /// from the images of this swapchain must be recreated as well.
+    pub fn recreate_with_present_mode(
+        self: &Arc<Self>,
+        present_mode: PresentMode,
+    ) -> Result<(Arc<Swapchain>, Vec<Arc<SwapchainImage>>), SwapchainCreationError> {
+        self.recreate_for_surface(self.image_extent, present_mode)
+    }
+
+    fn recreate_for_surface(
+        self: &Arc<Self>,
+        image_extent: [u32; 2],
+        present_mode: PresentMode,
+    ) -> Result<(Arc<Swapchain>, Vec<Arc<SwapchainImage>>), SwapchainCreationError> {
+        let surface_capabilities = unsafe {
+            self.device
+                .physical_device()
+                .surface_capabilities_unchecked(
+                    &self.surface,
+                    SurfaceInfo {
+                        full_screen_exclusive: self.full_screen_exclusive,
+                        win32_monitor: self.win32_monitor,
+                        ..Default::default()
+                    },
+                )
+                .map_err(|err| self.device.check_lost(err))?
+        };
+
+        let image_extent = surface_capabilities.current_extent.unwrap_or([
+            image_extent[0].clamp(
+                surface_capabilities.min_image_extent[0],
+                surface_capabilities.max_image_extent[0],
+            ),
+            image_extent[1].clamp(
+                surface_capabilities.min_image_extent[1],
+                surface_capabilities.max_image_extent[1],
+            ),
+        ]);
+
+        let min_image_count = if matches!(
+            present_mode,
+            PresentMode::SharedDemandRefresh | PresentMode::SharedContinuousRefresh
+        ) {
+            1
+        } else {
+            let min_image_count = self
+                .min_image_count
+                .max(surface_capabilities.min_image_count);
+            surface_capabilities
+                .max_image_count
+                .map_or(min_image_count, |max_image_count| {
+                    min_image_count.min(max_image_count)
+                })
+        };
+
+        self.recreate(SwapchainCreateInfo {
+            min_image_count,
+            image_extent,
+            present_mode,
+            ..self.create_info()
+        })
+    }
+
     fn validate(
         device: &Device,
         surface: &Surface,
@@ -290,6 +396,7 @@ impl Swapchain {
             composite_alpha,
             present_mode,
             clipped: _,
+            protected,
             full_screen_exclusive,
             win32_monitor,
             _ne: _,
@@ -395,12 +502,12 @@ impl Swapchain {
                 }
                 *format
             } else {
+                let candidates = default_image_formats(image_color_space);
+
                 surface_formats
                     .into_iter()
                     .find_map(|(f, c)| {
-                        (c == image_color_space
-                            && [Format::R8G8B8A8_UNORM, Format::B8G8R8A8_UNORM].contains(&f))
-                        .then_some(f)
+                        (c == image_color_space && candidates.contains(&f)).then_some(f)
                     })
                     .ok_or(SwapchainCreationError::FormatColorSpaceNotSupported)?
             }
@@ -418,22 +525,40 @@ impl Swapchain {
             )?
         };
 
-        // VUID-VkSwapchainCreateInfoKHR-minImageCount-01272
-        // VUID-VkSwapchainCreateInfoKHR-presentMode-02839
-        if min_image_count < surface_capabilities.min_image_count
-            || surface_capabilities
-                .max_image_count
-                .map_or(false, |c| min_image_count > c)
-        {
-            return Err(SwapchainCreationError::MinImageCountNotSupported {
-                provided: min_image_count,
-                min_supported: surface_capabilities.min_image_count,
-                max_supported: surface_capabilities.max_image_count,
-            });
+        if matches!(
+            present_mode,
+            PresentMode::SharedDemandRefresh | PresentMode::SharedContinuousRefresh
+        ) {
+            // VUID-VkSwapchainCreateInfoKHR-minImageCount-01383
+            if min_image_count != 1 {
+                return Err(SwapchainCreationError::MinImageCountNotSupported {
+                    provided: min_image_count,
+                    min_supported: 1,
+                    max_supported: Some(1),
+                });
+            }
+        } else {
+            // VUID-VkSwapchainCreateInfoKHR-minImageCount-01272
+            // VUID-VkSwapchainCreateInfoKHR-presentMode-02839
+            if min_image_count < surface_capabilities.min_image_count
+                || surface_capabilities
+                    .max_image_count
+                    .map_or(false, |c| min_image_count > c)
+            {
+                return Err(SwapchainCreationError::MinImageCountNotSupported {
+                    provided: min_image_count,
+                    min_supported: surface_capabilities.min_image_count,
+                    max_supported: surface_capabilities.max_image_count,
+                });
+            }
         }
 
         if image_extent[0] == 0 || image_extent[1] == 0 {
-            *image_extent = surface_capabilities.current_extent.unwrap();
+            // Some surfaces, such as headless surfaces, have no current extent. Then the
+            // zero-length extent is rejected below.
+            if let Some(current_extent) = surface_capabilities.current_extent {
+                *image_extent = current_extent;
+            }
         }
 
         // VUID-VkSwapchainCreateInfoKHR-imageExtent-01274
@@ -465,14 +590,38 @@ impl Swapchain {
             });
         }
 
-        // VUID-VkSwapchainCreateInfoKHR-presentMode-01427
-        if (ash::vk::ImageUsageFlags::from(image_usage)
-            & ash::vk::ImageUsageFlags::from(surface_capabilities.supported_usage_flags))
-            != ash::vk::ImageUsageFlags::from(image_usage)
-        {
+        if protected {
+            if device.api_version() < Version::V1_1 {
+                return Err(SwapchainCreationError::RequirementNotMet {
+                    required_for: "`create_info.protected` is `true`",
+                    requires_one_of: RequiresOneOf {
+                        api_version: Some(Version::V1_1),
+                        ..Default::default()
+                    },
+                });
+            }
+
+            // VUID-VkSwapchainCreateInfoKHR-flags-03187
+            if !surface_capabilities.supports_protected {
+                return Err(SwapchainCreationError::ProtectedNotSupported);
+            }
+        }
+
+        let supported_usage_flags = if matches!(
+            present_mode,
+            PresentMode::SharedDemandRefresh | PresentMode::SharedContinuousRefresh
+        ) {
+            // VUID-VkSwapchainCreateInfoKHR-imageUsage-01384
+            surface_capabilities.shared_present_supported_usage_flags
+        } else {
+            // VUID-VkSwapchainCreateInfoKHR-presentMode-01427
+            surface_capabilities.supported_usage_flags
+        };
+
+        if !supported_usage_flags.contains(image_usage) {
             return Err(SwapchainCreationError::ImageUsageNotSupported {
                 provided: image_usage,
-                supported: surface_capabilities.supported_usage_flags,
+                supported: supported_usage_flags,
             });
         }
 
@@ -578,6 +727,7 @@ impl Swapchain {
             composite_alpha,
             present_mode,
             clipped,
+            protected,
             full_screen_exclusive,
             win32_monitor,
             _ne: _,
@@ -594,7 +744,11 @@ impl Swapchain {
             };
 
         let mut info_vk = ash::vk::SwapchainCreateInfoKHR {
-            flags: ash::vk::SwapchainCreateFlagsKHR::empty(),
+            flags: if protected {
+                ash::vk::SwapchainCreateFlagsKHR::PROTECTED
+            } else {
+                ash::vk::SwapchainCreateFlagsKHR::empty()
+            },
             surface: surface.handle(),
             min_image_count,
             image_format: image_format.unwrap().into(),
@@ -704,6 +858,7 @@ impl Swapchain {
             composite_alpha: self.composite_alpha,
             present_mode: self.present_mode,
             clipped: self.clipped,
+            protected: self.protected,
             full_screen_exclusive: self.full_screen_exclusive,
             win32_monitor: self.win32_monitor,
             _ne: crate::NonExhaustive(()),
@@ -767,11 +922,28 @@ impl Swapchain {
         &self.image_sharing
     }
 
+    /// Returns whether an acquire or present operation on this swapchain has reported it as
+    /// suboptimal.
+    ///
+    /// A suboptimal swapchain can still be used, but it no longer matches the surface properties
+    /// exactly, for example after a window resize. It should be recreated, for example with
+    /// [`recreate_with_extent`](Self::recreate_with_extent), when convenient. The flag is never
+    /// reset; the new swapchain starts out as not suboptimal.
+    #[inline]
+    pub fn is_suboptimal(&self) -> bool {
+        self.suboptimal.load(Ordering::SeqCst)
+    }
+
     #[inline]
     pub(crate) unsafe fn full_screen_exclusive_held(&self) -> &AtomicBool {
         &self.full_screen_exclusive_held
     }
 
+    #[inline]
+    pub(crate) unsafe fn suboptimal(&self) -> &AtomicBool {
+        &self.suboptimal
+    }
+
     #[inline]
     pub(crate) unsafe fn try_claim_present_id(&self, present_id: NonZeroU64) -> bool {
         let present_id = u64::from(present_id);
@@ -802,6 +974,12 @@ impl Swapchain {
         self.clipped
     }
 
+    /// Returns the value of `protected` that was passed when creating the swapchain.
+    #[inline]
+    pub fn protected(&self) -> bool {
+        self.protected
+    }
+
     /// Returns the value of 'full_screen_exclusive` that was passed when creating the swapchain.
     #[inline]
     pub fn full_screen_exclusive(&self) -> FullScreenExclusive {
@@ -814,12 +992,22 @@ impl Swapchain {
     /// and must not already hold full-screen exclusivity. Full-screen exclusivity is held until
     /// either the `release_full_screen_exclusive` is called, or if any of the the other `Swapchain`
     /// functions return `FullScreenExclusiveLost`.
+    ///
+    /// On Win32, the swapchain must also have been created with a
+    /// [`win32_monitor`](SwapchainCreateInfo::win32_monitor), which is the monitor that
+    /// exclusivity is acquired on.
     #[inline]
     pub fn acquire_full_screen_exclusive(&self) -> Result<(), FullScreenExclusiveError> {
         if self.full_screen_exclusive != FullScreenExclusive::ApplicationControlled {
             return Err(FullScreenExclusiveError::NotApplicationControlled);
         }
 
+        // VUID-vkAcquireFullScreenExclusiveModeEXT-swapchain-02674
+        let retired = self.retired.lock();
+        if *retired {
+            return Err(FullScreenExclusiveError::Retired);
+        }
+
         if self.full_screen_exclusive_held.swap(true, Ordering::SeqCst) {
             return Err(FullScreenExclusiveError::DoubleAcquire);
         }
@@ -831,7 +1019,12 @@ impl Swapchain {
                 self.device.handle(), self.handle
             )
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|err| {
+                // Exclusivity was not acquired, so allow trying again.
+                self.full_screen_exclusive_held
+                    .store(false, Ordering::SeqCst);
+                VulkanError::from(err)
+            })?;
         }
 
         Ok(())
@@ -847,6 +1040,12 @@ impl Swapchain {
             return Err(FullScreenExclusiveError::NotApplicationControlled);
         }
 
+        // VUID-vkReleaseFullScreenExclusiveModeEXT-swapchain-02677
+        let retired = self.retired.lock();
+        if *retired {
+            return Err(FullScreenExclusiveError::Retired);
+        }
+
         if !self
             .full_screen_exclusive_held
             .swap(false, Ordering::SeqCst)
@@ -867,10 +1066,12 @@ impl Swapchain {
         Ok(())
     }
 
-    /// `FullScreenExclusive::AppControlled` is not the active full-screen exclusivity mode,
-    /// then this function will always return false. If true is returned the swapchain
-    /// is in `FullScreenExclusive::AppControlled` full-screen exclusivity mode and exclusivity
-    /// is currently acquired.
+    /// Returns whether the swapchain currently holds full-screen exclusivity.
+    ///
+    /// If [`FullScreenExclusive::ApplicationControlled`] is not the active full-screen
+    /// exclusivity mode, then this function will always return false. If true is returned, the
+    /// swapchain is in `FullScreenExclusive::ApplicationControlled` full-screen exclusivity mode
+    /// and exclusivity is currently acquired.
     #[inline]
     pub fn is_full_screen_exclusive(&self) -> bool {
         if self.full_screen_exclusive != FullScreenExclusive::ApplicationControlled {
@@ -880,6 +1081,197 @@ impl Swapchain {
         }
     }
 
+    /// Sets the HDR metadata of the swapchain, which describes the display that the content was
+    /// mastered on and the light levels of the content to the presentation engine.
+    ///
+    /// The metadata is used for the images that are presented after this call, until it is set
+    /// again. It is only meaningful for HDR color spaces, such as [`ColorSpace::Hdr10St2084`].
+    /// The presentation engine may ignore it.
+    #[inline]
+    pub fn set_hdr_metadata(&self, metadata: &HdrMetadata) -> Result<(), HdrMetadataError> {
+        self.validate_set_hdr_metadata(metadata)?;
+
+        unsafe {
+            self.set_hdr_metadata_unchecked(metadata);
+        }
+
+        Ok(())
+    }
+
+    fn validate_set_hdr_metadata(&self, _metadata: &HdrMetadata) -> Result<(), HdrMetadataError> {
+        if !self.device.enabled_extensions().ext_hdr_metadata {
+            return Err(HdrMetadataError::RequirementNotMet {
+                required_for: "`Swapchain::set_hdr_metadata`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["ext_hdr_metadata"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn set_hdr_metadata_unchecked(&self, metadata: &HdrMetadata) {
+        let &HdrMetadata {
+            display_primary_red,
+            display_primary_green,
+            display_primary_blue,
+            white_point,
+            max_luminance,
+            min_luminance,
+            max_content_light_level,
+            max_frame_average_light_level,
+            _ne: _,
+        } = metadata;
+
+        let xy_color = |[x, y]: [f32; 2]| ash::vk::XYColorEXT { x, y };
+        let metadata_vk = ash::vk::HdrMetadataEXT {
+            display_primary_red: xy_color(display_primary_red),
+            display_primary_green: xy_color(display_primary_green),
+            display_primary_blue: xy_color(display_primary_blue),
+            white_point: xy_color(white_point),
+            max_luminance,
+            min_luminance,
+            max_content_light_level,
+            max_frame_average_light_level,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+        (fns.ext_hdr_metadata.set_hdr_metadata_ext)(
+            self.device.handle(),
+            1,
+            &self.handle,
+            &metadata_vk,
+        );
+    }
+
+    /// Returns the duration of a refresh cycle of the display that the swapchain is presented
+    /// on.
+    ///
+    /// The [`google_display_timing`](crate::device::DeviceExtensions::google_display_timing)
+    /// extension must be enabled on the device.
+    #[inline]
+    pub fn refresh_cycle_duration(&self) -> Result<Duration, DisplayTimingError> {
+        self.validate_refresh_cycle_duration()?;
+
+        unsafe { Ok(self.refresh_cycle_duration_unchecked()?) }
+    }
+
+    fn validate_refresh_cycle_duration(&self) -> Result<(), DisplayTimingError> {
+        if !self.device.enabled_extensions().google_display_timing {
+            return Err(DisplayTimingError::RequirementNotMet {
+                required_for: "`Swapchain::refresh_cycle_duration`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["google_display_timing"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn refresh_cycle_duration_unchecked(&self) -> Result<Duration, VulkanError> {
+        let fns = self.device.fns();
+        let mut properties = ash::vk::RefreshCycleDurationGOOGLE::default();
+        (fns.google_display_timing.get_refresh_cycle_duration_google)(
+            self.device.handle(),
+            self.handle,
+            &mut properties,
+        )
+        .result()
+        .map_err(|err| self.device.check_lost(err.into()))?;
+
+        Ok(Duration::from_nanos(properties.refresh_duration))
+    }
+
+    /// Returns the timing of the present operations on the swapchain that were given a
+    /// [`present_time`](crate::swapchain::SwapchainPresentInfo::present_time), and that have
+    /// completed since the last call to this function.
+    ///
+    /// The [`google_display_timing`](crate::device::DeviceExtensions::google_display_timing)
+    /// extension must be enabled on the device.
+    #[inline]
+    pub fn past_presentation_timing(
+        &self,
+    ) -> Result<Vec<PastPresentationTiming>, DisplayTimingError> {
+        self.validate_past_presentation_timing()?;
+
+        unsafe { Ok(self.past_presentation_timing_unchecked()?) }
+    }
+
+    fn validate_past_presentation_timing(&self) -> Result<(), DisplayTimingError> {
+        if !self.device.enabled_extensions().google_display_timing {
+            return Err(DisplayTimingError::RequirementNotMet {
+                required_for: "`Swapchain::past_presentation_timing`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["google_display_timing"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn past_presentation_timing_unchecked(
+        &self,
+    ) -> Result<Vec<PastPresentationTiming>, VulkanError> {
+        let fns = self.device.fns();
+
+        loop {
+            let mut count = 0;
+            (fns.google_display_timing
+                .get_past_presentation_timing_google)(
+                self.device.handle(),
+                self.handle,
+                &mut count,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(|err| self.device.check_lost(err.into()))?;
+
+            let mut timings = Vec::with_capacity(count as usize);
+            let result = (fns
+                .google_display_timing
+                .get_past_presentation_timing_google)(
+                self.device.handle(),
+                self.handle,
+                &mut count,
+                timings.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => {
+                    timings.set_len(count as usize);
+
+                    return Ok(timings
+                        .into_iter()
+                        .map(|timing: ash::vk::PastPresentationTimingGOOGLE| {
+                            PastPresentationTiming {
+                                present_id: timing.present_id,
+                                desired_present_time: timing.desired_present_time,
+                                actual_present_time: timing.actual_present_time,
+                                earliest_present_time: timing.earliest_present_time,
+                                present_margin: Duration::from_nanos(timing.present_margin),
+                            }
+                        })
+                        .collect());
+                }
+                ash::vk::Result::INCOMPLETE => (),
+                err => return Err(self.device.check_lost(err.into())),
+            }
+        }
+    }
+
     // This method is necessary to allow `SwapchainImage`s to signal when they have been
     // transitioned out of their initial `undefined` image layout.
     //
@@ -954,10 +1346,12 @@ impl Debug for Swapchain {
             composite_alpha,
             present_mode,
             clipped,
+            protected,
             full_screen_exclusive,
             win32_monitor,
             prev_present_id,
             full_screen_exclusive_held,
+            suboptimal,
             images,
             retired,
         } = self;
@@ -977,10 +1371,12 @@ impl Debug for Swapchain {
             .field("composite_alpha", &composite_alpha)
             .field("present_mode", &present_mode)
             .field("clipped", &clipped)
+            .field("protected", &protected)
             .field("full_screen_exclusive", &full_screen_exclusive)
             .field("win32_monitor", &win32_monitor)
             .field("prev_present_id", &prev_present_id)
             .field("full_screen_exclusive_held", &full_screen_exclusive_held)
+            .field("suboptimal", &suboptimal)
             .field("images", &images)
             .field("retired", &retired)
             .finish()
@@ -1003,8 +1399,14 @@ pub struct SwapchainCreateInfo {
 
     /// The format of the created images.
     ///
-    /// If set to `None`, [`Format::R8G8B8A8_UNORM`] or [`Format::B8G8R8A8_UNORM`] will be selected,
-    /// based on which is supported by the surface.
+    /// If set to `None`, a format that is supported by the surface in combination with
+    /// `image_color_space` will be selected:
+    /// - For [`ColorSpace::ExtendedSrgbLinear`] and [`ColorSpace::ExtendedSrgbNonLinear`],
+    ///   [`Format::R16G16B16A16_SFLOAT`].
+    /// - For [`ColorSpace::Hdr10St2084`], [`ColorSpace::Hdr10Hlg`] and
+    ///   [`ColorSpace::Bt2020Linear`], [`Format::A2B10G10R10_UNORM_PACK32`] or
+    ///   [`Format::A2R10G10B10_UNORM_PACK32`].
+    /// - For all other color spaces, [`Format::R8G8B8A8_UNORM`] or [`Format::B8G8R8A8_UNORM`].
     ///
     /// The default value is `None`.
     pub image_format: Option<Format>,
@@ -1018,6 +1420,8 @@ pub struct SwapchainCreateInfo {
     ///
     /// If any of the values is 0, the value of
     /// [`SurfaceCapabilities::current_extent`](crate::swapchain::SurfaceCapabilities) will be used.
+    /// If the surface has no current extent, as is the case for headless surfaces, the extent
+    /// must be specified.
     ///
     /// The default value is `[0, 0]`.
     pub image_extent: [u32; 2],
@@ -1040,6 +1444,12 @@ pub struct SwapchainCreateInfo {
 
     /// The transform that should be applied to an image before it is presented.
     ///
+    /// If this is not the same as the
+    /// [`current_transform`](crate::swapchain::SurfaceCapabilities::current_transform) of the
+    /// surface, the presentation engine may need an extra pass to transform the images, which
+    /// can be costly on mobile devices. See [`SurfaceTransform::pre_rotation_matrix`] for how to
+    /// render for a rotated pre-transform instead.
+    ///
     /// The default value is [`SurfaceTransform::Identity`].
     pub pre_transform: SurfaceTransform,
 
@@ -1061,6 +1471,17 @@ pub struct SwapchainCreateInfo {
     /// The default value is `true`.
     pub clipped: bool,
 
+    /// Whether the created images are protected.
+    ///
+    /// If set to `true`, the device API version must be at least 1.1, and the surface must
+    /// [support protected swapchains]. Protected swapchain images can only be rendered to with
+    /// protected command buffers.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [support protected swapchains]: crate::swapchain::SurfaceCapabilities::supports_protected
+    pub protected: bool,
+
     /// How full-screen exclusivity is to be handled.
     ///
     /// If set to anything other than [`FullScreenExclusive::Default`], then the
@@ -1097,6 +1518,7 @@ impl Default for SwapchainCreateInfo {
             composite_alpha: CompositeAlpha::Opaque,
             present_mode: PresentMode::Fifo,
             clipped: true,
+            protected: false,
             full_screen_exclusive: FullScreenExclusive::Default,
             win32_monitor: None,
             _ne: crate::NonExhaustive(()),
@@ -1104,6 +1526,21 @@ impl Default for SwapchainCreateInfo {
     }
 }
 
+/// Returns the formats that are selected from, if no format is given when creating a swapchain
+/// with `color_space`.
+fn default_image_formats(color_space: ColorSpace) -> &'static [Format] {
+    match color_space {
+        ColorSpace::ExtendedSrgbLinear | ColorSpace::ExtendedSrgbNonLinear => {
+            &[Format::R16G16B16A16_SFLOAT]
+        }
+        ColorSpace::Hdr10St2084 | ColorSpace::Hdr10Hlg | ColorSpace::Bt2020Linear => &[
+            Format::A2B10G10R10_UNORM_PACK32,
+            Format::A2R10G10B10_UNORM_PACK32,
+        ],
+        _ => &[Format::R8G8B8A8_UNORM, Format::B8G8R8A8_UNORM],
+    }
+}
+
 /// Error that can happen when creating a `Swapchain`.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SwapchainCreationError {
@@ -1184,6 +1621,10 @@ pub enum SwapchainCreationError {
     /// The provided `present_mode` is not supported by the surface for this device.
     PresentModeNotSupported,
 
+    /// The provided `protected` is `true`, but protected swapchains are not supported by the
+    /// surface for this device.
+    ProtectedNotSupported,
+
     /// The provided `pre_transform` is not supported by the surface for this device.
     PreTransformNotSupported {
         provided: SurfaceTransform,
@@ -1294,6 +1735,11 @@ impl Display for SwapchainCreationError {
                 f,
                 "the provided `present_mode` is not supported by the surface for this device",
             ),
+            Self::ProtectedNotSupported => write!(
+                f,
+                "the provided `protected` is `true`, but protected swapchains are not supported \
+                by the surface for this device",
+            ),
             Self::PreTransformNotSupported { .. } => write!(
                 f,
                 "the provided `pre_transform` is not supported by the surface for this device",
@@ -1354,7 +1800,7 @@ vulkan_enum! {
     /// Indicates that the driver may use full-screen exclusive mechanisms when available.
     /// Such mechanisms may result in better performance and/or the availability of
     /// different presentation capabilities, but may require a more disruptive transition
-    // during swapchain initialization, first presentation and/or destruction.
+    /// during swapchain initialization, first presentation and/or destruction.
     Allowed = ALLOWED,
 
     /// Indicates that the driver should avoid using full-screen mechanisms which rely
@@ -1367,6 +1813,197 @@ vulkan_enum! {
     ApplicationControlled = APPLICATION_CONTROLLED,
 }
 
+/// HDR metadata of a swapchain, for [`Swapchain::set_hdr_metadata`].
+///
+/// The chromaticity coordinates are `[x, y]` values in the CIE 1931 color space. The luminance
+/// values are in nits (candela per square meter).
+#[derive(Clone, Debug, PartialEq)]
+pub struct HdrMetadata {
+    /// The chromaticity of the red primary of the mastering display.
+    ///
+    /// The default value is `[0.708, 0.292]`, the red primary of BT.2020.
+    pub display_primary_red: [f32; 2],
+
+    /// The chromaticity of the green primary of the mastering display.
+    ///
+    /// The default value is `[0.170, 0.797]`, the green primary of BT.2020.
+    pub display_primary_green: [f32; 2],
+
+    /// The chromaticity of the blue primary of the mastering display.
+    ///
+    /// The default value is `[0.131, 0.046]`, the blue primary of BT.2020.
+    pub display_primary_blue: [f32; 2],
+
+    /// The chromaticity of the white point of the mastering display.
+    ///
+    /// The default value is `[0.3127, 0.3290]`, the D65 white point.
+    pub white_point: [f32; 2],
+
+    /// The maximum luminance of the mastering display.
+    ///
+    /// The default value is `1000.0`.
+    pub max_luminance: f32,
+
+    /// The minimum luminance of the mastering display.
+    ///
+    /// The default value is `0.0`.
+    pub min_luminance: f32,
+
+    /// The maximum luminance of any single pixel of the content, or `0.0` if unknown.
+    ///
+    /// The default value is `0.0`.
+    pub max_content_light_level: f32,
+
+    /// The maximum average luminance of any single frame of the content, or `0.0` if unknown.
+    ///
+    /// The default value is `0.0`.
+    pub max_frame_average_light_level: f32,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for HdrMetadata {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            display_primary_red: [0.708, 0.292],
+            display_primary_green: [0.170, 0.797],
+            display_primary_blue: [0.131, 0.046],
+            white_point: [0.3127, 0.3290],
+            max_luminance: 1000.0,
+            min_luminance: 0.0,
+            max_content_light_level: 0.0,
+            max_frame_average_light_level: 0.0,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Error that can happen when calling [`Swapchain::set_hdr_metadata`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HdrMetadataError {
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for HdrMetadataError {}
+
+impl Display for HdrMetadataError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for HdrMetadataError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+/// The timing of a past present operation, as returned by
+/// [`Swapchain::past_presentation_timing`].
+///
+/// All times are in nanoseconds, in the same time domain as
+/// [`PresentTime::desired_present_time`](crate::swapchain::PresentTime::desired_present_time).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PastPresentationTiming {
+    /// The id that was provided in the `PresentTime` of the present operation.
+    pub present_id: u32,
+
+    /// The desired present time that was provided in the `PresentTime` of the present operation.
+    pub desired_present_time: u64,
+
+    /// The time at which the image was actually presented to the user.
+    pub actual_present_time: u64,
+
+    /// The earliest time at which the image could have been presented to the user. This may
+    /// differ from `actual_present_time` if the image was presented later than it could have been,
+    /// for example because of `desired_present_time`.
+    pub earliest_present_time: u64,
+
+    /// How early the processing of the present operation completed compared to
+    /// `earliest_present_time`.
+    pub present_margin: Duration,
+}
+
+/// Error that can happen when calling [`Swapchain::refresh_cycle_duration`] or
+/// [`Swapchain::past_presentation_timing`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisplayTimingError {
+    /// The connection to the device has been lost.
+    DeviceLost,
+
+    /// The surface has changed in a way that makes the swapchain unusable. You must query the
+    /// surface's new properties and recreate a new swapchain if you want to continue drawing.
+    OutOfDate,
+
+    /// The surface is no longer accessible and must be recreated.
+    SurfaceLost,
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+}
+
+impl Error for DisplayTimingError {}
+
+impl Display for DisplayTimingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::DeviceLost => write!(f, "the connection to the device has been lost"),
+            Self::OutOfDate => write!(
+                f,
+                "the surface has changed and the swapchain is out of date"
+            ),
+            Self::SurfaceLost => write!(f, "the surface of this swapchain is no longer valid"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+        }
+    }
+}
+
+impl From<VulkanError> for DisplayTimingError {
+    fn from(err: VulkanError) -> Self {
+        match err {
+            VulkanError::DeviceLost => Self::DeviceLost,
+            VulkanError::OutOfDate => Self::OutOfDate,
+            VulkanError::SurfaceLost => Self::SurfaceLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+impl From<RequirementNotMet> for DisplayTimingError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
 /// A wrapper around a Win32 monitor handle.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Win32Monitor(pub(crate) ash::vk::HMONITOR);
@@ -1407,6 +2044,10 @@ pub enum FullScreenExclusiveError {
 
     /// The swapchain is not in full-screen exclusive application controlled mode.
     NotApplicationControlled,
+
+    /// The swapchain has been retired, by being passed as the old swapchain when creating a new
+    /// swapchain.
+    Retired,
 }
 
 impl Error for FullScreenExclusiveError {
@@ -1438,6 +2079,7 @@ impl Display for FullScreenExclusiveError {
                 FullScreenExclusiveError::NotApplicationControlled => {
                     "the swapchain is not in full-screen exclusive application controlled mode"
                 }
+                FullScreenExclusiveError::Retired => "the swapchain has been retired",
             }
         )
     }
@@ -1532,7 +2174,42 @@ pub fn present<F>(
 where
     F: GpuFuture,
 {
-    assert!(swapchain_info.image_index < swapchain_info.swapchain.image_count());
+    present_multi(before, queue, [swapchain_info])
+}
+
+/// Presents images on the screens of several swapchains, with a single present operation.
+///
+/// This is more efficient than presenting to each swapchain separately, as only one semaphore
+/// wait and one call to `vkQueuePresentKHR` are needed. The result of presenting to each
+/// swapchain can be retrieved with [`PresentFuture::results`] after the future is flushed.
+///
+/// # Panics
+///
+/// - Panics if `swapchain_infos` is empty.
+/// - Panics if `swapchain_infos` contains the same swapchain more than once.
+/// - Panics if an image index is not less than the number of images of its swapchain.
+pub fn present_multi<F>(
+    before: F,
+    queue: Arc<Queue>,
+    swapchain_infos: impl IntoIterator<Item = SwapchainPresentInfo>,
+) -> PresentFuture<F>
+where
+    F: GpuFuture,
+{
+    let swapchain_infos: SmallVec<[_; 1]> = swapchain_infos.into_iter().collect();
+    assert!(!swapchain_infos.is_empty());
+
+    for (index, swapchain_info) in swapchain_infos.iter().enumerate() {
+        // VUID-VkPresentInfoKHR-commonparent
+        assert_eq!(swapchain_info.swapchain.device(), queue.device());
+
+        // VUID?
+        assert!(swapchain_infos[..index]
+            .iter()
+            .all(|other| other.swapchain != swapchain_info.swapchain));
+
+        assert!(swapchain_info.image_index < swapchain_info.swapchain.image_count());
+    }
 
     // TODO: restore this check with a dummy ImageAccess implementation
     /*let swapchain_image = me.images.lock().unwrap().get(index).unwrap().0.upgrade().unwrap();       // TODO: return error instead
@@ -1544,18 +2221,31 @@ where
     PresentFuture {
         previous: before,
         queue,
-        swapchain_info,
+        swapchain_infos,
+        results: Mutex::new(None),
         flushed: AtomicBool::new(false),
         finished: AtomicBool::new(false),
     }
 }
 
-/// Wait for an image to be presented to the user. Must be used with a `present_id` given to
-/// `present_with_id`.
+/// Wait for an image to be presented to the user. Must be used with a `present_id` that was given
+/// in the [`SwapchainPresentInfo`] of a present operation on `swapchain`, or a greater one that
+/// has been presented since.
+///
+/// If `timeout` is `None`, then the wait is infinite. Otherwise the function returns
+/// [`PresentWaitError::Timeout`] once the timeout has elapsed.
 ///
 /// Returns a bool to represent if the presentation was suboptimal. In this case the swapchain is
 /// still usable, but the swapchain should be recreated as the Surface's properties no longer match
 /// the swapchain.
+///
+/// This can be used to limit the latency between rendering and display: by waiting for the
+/// present of frame `n - 1` before recording frame `n + 1`, the CPU never runs more than one
+/// frame ahead of the display.
+///
+/// The [`present_id`](crate::device::Features::present_id) and
+/// [`present_wait`](crate::device::Features::present_wait) features must be enabled on the
+/// device.
 pub fn wait_for_present(
     swapchain: Arc<Swapchain>,
     present_id: u64,
@@ -1583,7 +2273,12 @@ pub fn wait_for_present(
         });
     }
 
-    let timeout_ns = timeout.map(|dur| dur.as_nanos() as u64).unwrap_or(0);
+    let timeout_ns = timeout.map_or(u64::MAX, |timeout| {
+        timeout
+            .as_secs()
+            .saturating_mul(1_000_000_000)
+            .saturating_add(timeout.subsec_nanos() as u64)
+    });
 
     let result = unsafe {
         (swapchain.device.fns().khr_present_wait.wait_for_present_khr)(
@@ -1599,7 +2294,7 @@ pub fn wait_for_present(
         ash::vk::Result::SUBOPTIMAL_KHR => Ok(true),
         ash::vk::Result::TIMEOUT => Err(PresentWaitError::Timeout),
         err => {
-            let err = VulkanError::from(err).into();
+            let err = swapchain.device.check_lost(VulkanError::from(err)).into();
 
             if let PresentWaitError::FullScreenExclusiveModeLost = &err {
                 swapchain
@@ -1744,7 +2439,10 @@ unsafe impl GpuFuture for SwapchainAcquireFuture {
 impl Drop for SwapchainAcquireFuture {
     fn drop(&mut self) {
         if let Some(ref fence) = self.fence {
-            fence.wait(None).unwrap(); // TODO: handle error?
+            if let Err(err) = fence.wait(None) {
+                assert!(fence.device().is_lost(), "{:?}", err);
+            }
+
             self.semaphore = None;
         }
 
@@ -1947,7 +2645,7 @@ impl From<VulkanError> for PresentWaitError {
     }
 }
 
-/// Represents a swapchain image being presented on the screen.
+/// Represents one or more swapchain images being presented on the screen.
 #[must_use = "Dropping this object will immediately block the thread until the GPU has finished processing the submission"]
 pub struct PresentFuture<P>
 where
@@ -1955,7 +2653,9 @@ where
 {
     previous: P,
     queue: Arc<Queue>,
-    swapchain_info: SwapchainPresentInfo,
+    swapchain_infos: SmallVec<[SwapchainPresentInfo; 1]>,
+    // The result of presenting to each swapchain in `swapchain_infos`, once flushed.
+    results: Mutex<Option<SmallVec<[Result<bool, FlushError>; 1]>>>,
     // True if `flush()` has been called on the future, which means that the present command has
     // been submitted.
     flushed: AtomicBool,
@@ -1969,13 +2669,32 @@ where
     P: GpuFuture,
 {
     /// Returns the index of the image in the list of images returned when creating the swapchain.
+    ///
+    /// If several swapchains are presented, this returns the image index of the first one.
     pub fn image_id(&self) -> u32 {
-        self.swapchain_info.image_index
+        self.swapchain_infos[0].image_index
     }
 
     /// Returns the corresponding swapchain.
+    ///
+    /// If several swapchains are presented, this returns the first one.
     pub fn swapchain(&self) -> &Arc<Swapchain> {
-        &self.swapchain_info.swapchain
+        &self.swapchain_infos[0].swapchain
+    }
+
+    /// Returns the present operations that this future performs.
+    pub fn swapchain_infos(&self) -> &[SwapchainPresentInfo] {
+        &self.swapchain_infos
+    }
+
+    /// Returns the result of presenting to each swapchain, in the same order as
+    /// [`swapchain_infos`](Self::swapchain_infos). `Ok(true)` means that the present succeeded,
+    /// but the swapchain is suboptimal.
+    ///
+    /// Returns `None` if the future has not been flushed yet, or if the present operation as a
+    /// whole failed before reaching the individual swapchains.
+    pub fn results(&self) -> Option<SmallVec<[Result<bool, FlushError>; 1]>> {
+        self.results.lock().clone()
     }
 }
 
@@ -1992,20 +2711,32 @@ where
             return Ok(SubmitAnyBuilder::Empty);
         }
 
-        let mut swapchain_info = self.swapchain_info.clone();
-        debug_assert!((swapchain_info.image_index as u32) < swapchain_info.swapchain.image_count());
-        let device = swapchain_info.swapchain.device();
+        let device = self.queue.device();
+        let mut swapchain_infos = Vec::with_capacity(self.swapchain_infos.len());
 
-        if !device.enabled_features().present_id {
-            swapchain_info.present_id = None;
-        }
+        for swapchain_info in &self.swapchain_infos {
+            let mut swapchain_info = swapchain_info.clone();
+            debug_assert!(swapchain_info.image_index < swapchain_info.swapchain.image_count());
 
-        if device.enabled_extensions().khr_incremental_present {
-            for rectangle in &swapchain_info.present_regions {
-                assert!(rectangle.is_compatible_with(swapchain_info.swapchain.as_ref()));
+            if !device.enabled_features().present_id {
+                swapchain_info.present_id = None;
             }
-        } else {
-            swapchain_info.present_regions = Default::default();
+
+            if device.enabled_extensions().khr_incremental_present {
+                for rectangle in &swapchain_info.present_regions {
+                    if !rectangle.is_compatible_with(swapchain_info.swapchain.as_ref()) {
+                        return Err(FlushError::PresentRegionOutOfBounds);
+                    }
+                }
+            } else {
+                swapchain_info.present_regions = Default::default();
+            }
+
+            if !device.enabled_extensions().google_display_timing {
+                swapchain_info.present_time = None;
+            }
+
+            swapchain_infos.push(swapchain_info);
         }
 
         let _queue = self.previous.queue();
@@ -2015,13 +2746,13 @@ where
 
         Ok(match self.previous.build_submission()? {
             SubmitAnyBuilder::Empty => SubmitAnyBuilder::QueuePresent(PresentInfo {
-                swapchain_infos: vec![self.swapchain_info.clone()],
+                swapchain_infos,
                 ..Default::default()
             }),
             SubmitAnyBuilder::SemaphoresWait(semaphores) => {
                 SubmitAnyBuilder::QueuePresent(PresentInfo {
                     wait_semaphores: semaphores.into_iter().collect(),
-                    swapchain_infos: vec![self.swapchain_info.clone()],
+                    swapchain_infos,
                     ..Default::default()
                 })
             }
@@ -2031,7 +2762,7 @@ where
                 self.previous.flush()?;
 
                 SubmitAnyBuilder::QueuePresent(PresentInfo {
-                    swapchain_infos: vec![self.swapchain_info.clone()],
+                    swapchain_infos,
                     ..Default::default()
                 })
             }
@@ -2041,14 +2772,12 @@ where
                 self.previous.flush()?;
 
                 SubmitAnyBuilder::QueuePresent(PresentInfo {
-                    swapchain_infos: vec![self.swapchain_info.clone()],
+                    swapchain_infos,
                     ..Default::default()
                 })
             }
             SubmitAnyBuilder::QueuePresent(mut present_info) => {
-                present_info
-                    .swapchain_infos
-                    .push(self.swapchain_info.clone());
+                present_info.swapchain_infos.extend(swapchain_infos);
 
                 SubmitAnyBuilder::QueuePresent(present_info)
             }
@@ -2074,23 +2803,37 @@ where
                         }
                     }
 
-                    match self.previous.check_swapchain_image_acquired(
-                        &self.swapchain_info.swapchain,
-                        self.swapchain_info.image_index,
-                        true,
-                    ) {
-                        Ok(_) => (),
-                        Err(AccessCheckError::Unknown) => {
-                            return Err(AccessError::SwapchainImageNotAcquired.into())
+                    for swapchain_info in &self.swapchain_infos {
+                        match self.previous.check_swapchain_image_acquired(
+                            &swapchain_info.swapchain,
+                            swapchain_info.image_index,
+                            true,
+                        ) {
+                            Ok(_) => (),
+                            Err(AccessCheckError::Unknown) => {
+                                return Err(AccessError::SwapchainImageNotAcquired.into())
+                            }
+                            Err(AccessCheckError::Denied(e)) => return Err(e.into()),
                         }
-                        Err(AccessCheckError::Denied(e)) => return Err(e.into()),
                     }
 
-                    Ok(self
+                    // If previous present futures were merged into this present operation,
+                    // their results come first.
+                    let skip = present_info.swapchain_infos.len() - self.swapchain_infos.len();
+                    let results: SmallVec<[_; 1]> = self
                         .queue
                         .with(|mut q| q.present_unchecked(present_info))?
-                        .map(|r| r.map(|_| ()))
-                        .fold(Ok(()), Result::and)?)
+                        .skip(skip)
+                        .map(|result| result.map_err(FlushError::from))
+                        .collect();
+                    let result = results
+                        .iter()
+                        .cloned()
+                        .map(|result| result.map(|_| ()))
+                        .fold(Ok(()), Result::and);
+                    *self.results.lock() = Some(results);
+
+                    result
                 }
                 _ => unreachable!(),
             }
@@ -2135,9 +2878,9 @@ where
         expected_layout: ImageLayout,
         queue: &Queue,
     ) -> Result<(), AccessCheckError> {
-        if self.swapchain_info.swapchain.index_of_image(image)
-            == Some(self.swapchain_info.image_index)
-        {
+        if self.swapchain_infos.iter().any(|swapchain_info| {
+            swapchain_info.swapchain.index_of_image(image) == Some(swapchain_info.image_index)
+        }) {
             // This future presents the swapchain image, which "unlocks" it. Therefore any attempt
             // to use this swapchain image afterwards shouldn't get granted automatic access.
             // Instead any attempt to access the image afterwards should get an authorization from
@@ -2159,9 +2902,10 @@ where
         if before {
             self.previous
                 .check_swapchain_image_acquired(swapchain, image_index, false)
-        } else if swapchain == self.swapchain_info.swapchain.as_ref()
-            && image_index == self.swapchain_info.image_index
-        {
+        } else if self.swapchain_infos.iter().any(|swapchain_info| {
+            swapchain == swapchain_info.swapchain.as_ref()
+                && image_index == swapchain_info.image_index
+        }) {
             Err(AccessError::SwapchainImageNotAcquired.into())
         } else {
             self.previous
@@ -2192,8 +2936,12 @@ where
             }
 
             if !*self.finished.get_mut() {
-                // Block until the queue finished.
-                self.queue().unwrap().with(|mut q| q.wait_idle()).unwrap();
+                // Block until the queue finished. If the device was lost, nothing more will be
+                // executed on it, so the resources can be released anyway.
+                if let Err(err) = self.queue().unwrap().with(|mut q| q.wait_idle()) {
+                    assert!(self.device().is_lost(), "{:?}", err);
+                }
+
                 self.previous.signal_finished();
             }
         }
@@ -2246,9 +2994,13 @@ pub unsafe fn acquire_next_image_raw(
         ash::vk::Result::SUBOPTIMAL_KHR => true,
         ash::vk::Result::NOT_READY => return Err(AcquireError::Timeout),
         ash::vk::Result::TIMEOUT => return Err(AcquireError::Timeout),
-        err => return Err(VulkanError::from(err).into()),
+        err => return Err(swapchain.device.check_lost(VulkanError::from(err)).into()),
     };
 
+    if suboptimal {
+        swapchain.suboptimal.store(true, Ordering::SeqCst);
+    }
+
     if let Some(semaphore) = semaphore {
         let mut state = semaphore.state();
         state.swapchain_acquire();
@@ -19,10 +19,14 @@
 //! An event can also be signaled from the host, by calling the [`set`] method directly on the
 //! [`Event`].
 //!
+//! [`AutoCommandBufferBuilder`] also provides these commands, with validation that each
+//! `wait_events` matches an earlier `set_event` in the same command buffer.
+//!
 //! [`set_event`]: crate::command_buffer::CommandBufferBuilder::set_event
 //! [pipeline barrier]: crate::command_buffer::CommandBufferBuilder::pipeline_barrier
 //! [`wait_events`]: crate::command_buffer::CommandBufferBuilder::wait_events
 //! [`set`]: Event::set
+//! [`AutoCommandBufferBuilder`]: crate::command_buffer::AutoCommandBufferBuilder
 
 use crate::{
     device::{Device, DeviceOwned},
@@ -262,7 +262,7 @@ impl Fence {
 
     /// Returns true if the fence is signaled.
     #[inline]
-    pub fn is_signaled(&self) -> Result<bool, OomError> {
+    pub fn is_signaled(&self) -> Result<bool, FenceError> {
         let queue_to_signal = {
             let mut state = self.state();
 
@@ -281,7 +281,7 @@ impl Fence {
             match result {
                 ash::vk::Result::SUCCESS => unsafe { state.set_signaled() },
                 ash::vk::Result::NOT_READY => return Ok(false),
-                err => return Err(VulkanError::from(err).into()),
+                err => return Err(self.device.check_lost(VulkanError::from(err)).into()),
             }
         };
 
@@ -331,7 +331,7 @@ impl Fence {
             match result {
                 ash::vk::Result::SUCCESS => unsafe { state.set_signaled() },
                 ash::vk::Result::TIMEOUT => return Err(FenceError::Timeout),
-                err => return Err(VulkanError::from(err).into()),
+                err => return Err(self.device.check_lost(VulkanError::from(err)).into()),
             }
         };
 
@@ -348,21 +348,30 @@ impl Fence {
 
     /// Waits for multiple fences at once.
     ///
+    /// If `wait_all` is `true`, waits until all of the fences are signaled. Otherwise, waits until
+    /// at least one of them is signaled. Use [`is_signaled`](Self::is_signaled) afterwards to find
+    /// out which ones.
+    ///
+    /// Returns `Err` if the timeout was reached before the wait condition was satisfied. If you
+    /// pass a duration of 0, then the function will return without blocking.
+    ///
     /// # Panics
     ///
     /// - Panics if not all fences belong to the same device.
     pub fn multi_wait<'a>(
         fences: impl IntoIterator<Item = &'a Fence>,
+        wait_all: bool,
         timeout: Option<Duration>,
     ) -> Result<(), FenceError> {
         let fences: SmallVec<[_; 8]> = fences.into_iter().collect();
-        Self::validate_multi_wait(&fences, timeout)?;
+        Self::validate_multi_wait(&fences, wait_all, timeout)?;
 
-        unsafe { Self::multi_wait_unchecked(fences, timeout) }
+        unsafe { Self::multi_wait_unchecked(fences, wait_all, timeout) }
     }
 
     fn validate_multi_wait(
         fences: &[&Fence],
+        _wait_all: bool,
         _timeout: Option<Duration>,
     ) -> Result<(), FenceError> {
         if fences.is_empty() {
@@ -382,6 +391,7 @@ impl Fence {
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     pub unsafe fn multi_wait_unchecked<'a>(
         fences: impl IntoIterator<Item = &'a Fence>,
+        wait_all: bool,
         timeout: Option<Duration>,
     ) -> Result<(), FenceError> {
         let queues_to_signal: SmallVec<[_; 8]> = {
@@ -393,8 +403,13 @@ impl Fence {
             for fence in iter {
                 let state = fence.state.lock();
 
-                // Skip the fences that are already signaled.
-                if !state.is_signaled().unwrap_or(false) {
+                // Skip the fences that are already signaled. If we only need one of them, then
+                // we're done.
+                if state.is_signaled().unwrap_or(false) {
+                    if !wait_all {
+                        return Ok(());
+                    }
+                } else {
                     fences_vk.push(fence.handle);
                     fences.push(fence);
                     states.push(state);
@@ -421,19 +436,38 @@ impl Fence {
                     device.handle(),
                     fences_vk.len() as u32,
                     fences_vk.as_ptr(),
-                    ash::vk::TRUE, // TODO: let the user choose false here?
+                    wait_all as ash::vk::Bool32,
                     timeout_ns,
                 )
             };
 
             match result {
-                ash::vk::Result::SUCCESS => fences
+                ash::vk::Result::SUCCESS if wait_all => fences
                     .into_iter()
                     .zip(&mut states)
                     .filter_map(|(fence, state)| state.set_signaled().map(|state| (state, fence)))
                     .collect(),
+                ash::vk::Result::SUCCESS => {
+                    // Only some of the fences may be signaled, so ask Vulkan which ones.
+                    let fns = device.fns();
+                    let mut queues_to_signal = SmallVec::new();
+
+                    for (fence, state) in fences.into_iter().zip(&mut states) {
+                        match (fns.v1_0.get_fence_status)(device.handle(), fence.handle) {
+                            ash::vk::Result::SUCCESS => {
+                                if let Some(queue) = state.set_signaled() {
+                                    queues_to_signal.push((queue, fence));
+                                }
+                            }
+                            ash::vk::Result::NOT_READY => (),
+                            err => return Err(device.check_lost(VulkanError::from(err)).into()),
+                        }
+                    }
+
+                    queues_to_signal
+                }
                 ash::vk::Result::TIMEOUT => return Err(FenceError::Timeout),
-                err => return Err(VulkanError::from(err).into()),
+                err => return Err(device.check_lost(VulkanError::from(err)).into()),
             }
         };
 
@@ -568,11 +602,19 @@ impl Fence {
 
     /// Exports the fence into a POSIX file descriptor. The caller owns the returned `File`.
     ///
+    /// If `handle_type` is [`ExternalFenceHandleType::SyncFd`] and the fence is already
+    /// signaled, the implementation may return `None` instead of a file descriptor. This
+    /// represents a fence that is always signaled, and can be imported again by setting
+    /// [`ImportFenceFdInfo::file`] to `None`.
+    ///
     /// The [`khr_external_fence_fd`](crate::device::DeviceExtensions::khr_external_fence_fd)
     /// extension must be enabled on the device.
     #[cfg(unix)]
     #[inline]
-    pub fn export_fd(&self, handle_type: ExternalFenceHandleType) -> Result<File, FenceError> {
+    pub fn export_fd(
+        &self,
+        handle_type: ExternalFenceHandleType,
+    ) -> Result<Option<File>, FenceError> {
         let mut state = self.state.lock();
         self.validate_export_fd(handle_type, &state)?;
 
@@ -654,7 +696,7 @@ impl Fence {
     pub unsafe fn export_fd_unchecked(
         &self,
         handle_type: ExternalFenceHandleType,
-    ) -> Result<File, VulkanError> {
+    ) -> Result<Option<File>, VulkanError> {
         let mut state = self.state.lock();
         self.export_fd_unchecked_locked(handle_type, &mut state)
     }
@@ -664,7 +706,7 @@ impl Fence {
         &self,
         handle_type: ExternalFenceHandleType,
         state: &mut FenceState,
-    ) -> Result<File, VulkanError> {
+    ) -> Result<Option<File>, VulkanError> {
         use std::os::unix::io::FromRawFd;
 
         let info_vk = ash::vk::FenceGetFdInfoKHR {
@@ -685,7 +727,14 @@ impl Fence {
 
         state.export(handle_type);
 
-        Ok(File::from_raw_fd(output.assume_init()))
+        // For a sync fd, -1 may be returned if the fence is already signaled.
+        let fd = output.assume_init();
+
+        if fd == -1 {
+            Ok(None)
+        } else {
+            Ok(Some(File::from_raw_fd(fd)))
+        }
     }
 
     /// Exports the fence into a Win32 handle.
@@ -1063,7 +1112,7 @@ impl Fence {
     }
 
     // Shared by Fence and FenceSignalFuture
-    pub(crate) fn poll_impl(&self, cx: &mut Context<'_>) -> Poll<Result<(), OomError>> {
+    pub(crate) fn poll_impl(&self, cx: &mut Context<'_>) -> Poll<Result<(), FenceError>> {
         // Vulkan only allows polling of the fence status, so we have to use a spin future.
         // This is still better than blocking in async applications, since a smart-enough async engine
         // can choose to run some other tasks between probing this one.
@@ -1100,7 +1149,7 @@ impl Drop for Fence {
 }
 
 impl Future for Fence {
-    type Output = Result<(), OomError>;
+    type Output = Result<(), FenceError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         self.poll_impl(cx)
@@ -1139,7 +1188,7 @@ pub(crate) struct FenceState {
 impl FenceState {
     /// If the fence is not in a queue and has no external references, returns the current status.
     #[inline]
-    fn is_signaled(&self) -> Option<bool> {
+    pub(crate) fn is_signaled(&self) -> Option<bool> {
         // If either of these is true, we can't be certain of the status.
         if self.is_in_queue() || self.has_external_reference() {
             None
@@ -1149,7 +1198,7 @@ impl FenceState {
     }
 
     #[inline]
-    fn is_in_queue(&self) -> bool {
+    pub(crate) fn is_in_queue(&self) -> bool {
         self.pending_signal.is_some()
     }
 
@@ -1611,7 +1660,7 @@ impl From<RequirementNotMet> for FenceError {
 #[cfg(test)]
 mod tests {
     use crate::{
-        sync::fence::{Fence, FenceCreateInfo},
+        sync::fence::{Fence, FenceCreateInfo, FenceError},
         VulkanObject,
     };
     use std::time::Duration;
@@ -1670,6 +1719,20 @@ mod tests {
         assert!(!fence.is_signaled().unwrap());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn fence_export_fd_requires_extension() {
+        use crate::sync::fence::ExternalFenceHandleType;
+
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence = Fence::new(device, Default::default()).unwrap();
+        assert!(matches!(
+            fence.export_fd(ExternalFenceHandleType::SyncFd),
+            Err(FenceError::RequirementNotMet { .. })
+        ));
+    }
+
     #[test]
     fn multiwait_different_devices() {
         let (device1, _) = gfx_dev_and_queue!();
@@ -1695,11 +1758,35 @@ mod tests {
 
             let _ = Fence::multi_wait(
                 [&fence1, &fence2].iter().cloned(),
+                true,
                 Some(Duration::new(0, 10)),
             );
         });
     }
 
+    #[test]
+    fn multiwait_any() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence1 = Fence::new(device.clone(), Default::default()).unwrap();
+        let fence2 = Fence::new(
+            device,
+            FenceCreateInfo {
+                signaled: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        Fence::multi_wait([&fence1, &fence2], false, Some(Duration::new(0, 10))).unwrap();
+        assert!(matches!(
+            Fence::multi_wait([&fence1, &fence2], true, Some(Duration::new(0, 10))),
+            Err(FenceError::Timeout)
+        ));
+        assert!(!fence1.is_signaled().unwrap());
+        assert!(fence2.is_signaled().unwrap());
+    }
+
     #[test]
     fn multireset_different_devices() {
         let (device1, _) = gfx_dev_and_queue!();
@@ -0,0 +1,252 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{AccessCheckError, FlushError, GpuFuture, SubmitAnyBuilder};
+use crate::{
+    buffer::sys::Buffer,
+    command_buffer::SemaphoreSubmitInfo,
+    device::{BindSparseError, Device, DeviceOwned, Queue},
+    image::{sys::Image, ImageLayout},
+    memory::BindSparseInfo,
+    swapchain::Swapchain,
+    sync::semaphore::Semaphore,
+    DeviceSize,
+};
+use parking_lot::Mutex;
+use smallvec::SmallVec;
+use std::{
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Builds a new sparse binding future.
+pub(crate) unsafe fn then_bind_sparse<F>(
+    future: F,
+    queue: Arc<Queue>,
+    bind_infos: impl IntoIterator<Item = BindSparseInfo>,
+) -> Result<BindSparseFuture<F>, BindSparseError>
+where
+    F: GpuFuture,
+{
+    let bind_infos: SmallVec<[_; 1]> = bind_infos.into_iter().collect();
+    assert!(!bind_infos.is_empty());
+
+    // VUID-vkQueueBindSparse-commonparent
+    assert_eq!(future.device(), queue.device());
+
+    if !future.queue_change_allowed() {
+        assert!(future.queue().unwrap() == queue);
+    }
+
+    queue.with(|q| q.validate_bind_sparse(&bind_infos, None))?;
+
+    Ok(BindSparseFuture {
+        semaphore: Arc::new(Semaphore::from_pool(queue.device().clone()).unwrap()),
+        previous: future,
+        bind_infos,
+        queue,
+        submitted: Mutex::new(false),
+        finished: AtomicBool::new(false),
+    })
+}
+
+/// Represents sparse bind operations being executed by the GPU and the moment when they finish.
+#[derive(Debug)]
+#[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
+              processing the submission"]
+pub struct BindSparseFuture<F>
+where
+    F: GpuFuture,
+{
+    previous: F,
+    bind_infos: SmallVec<[BindSparseInfo; 1]>,
+    queue: Arc<Queue>,
+    // Used to order the bind operations with a previous submission, if needed.
+    semaphore: Arc<Semaphore>,
+    // True if the bind operations have already been submitted.
+    // If flush is called multiple times, we want to block so that only one flushing is executed.
+    // Therefore we use a `Mutex<bool>` and not an `AtomicBool`.
+    submitted: Mutex<bool>,
+    finished: AtomicBool,
+}
+
+impl<F> BindSparseFuture<F>
+where
+    F: GpuFuture,
+{
+    // Implementation of `build_submission`. Doesn't check whenever the future was already flushed.
+    // You must make sure to not submit the same bind operations multiple times.
+    unsafe fn build_submission_impl(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        let mut bind_infos = self.bind_infos.clone();
+
+        Ok(match self.previous.build_submission()? {
+            SubmitAnyBuilder::Empty => SubmitAnyBuilder::BindSparse(bind_infos, None),
+            SubmitAnyBuilder::SemaphoresWait(semaphores) => {
+                bind_infos[0].wait_semaphores.splice(0..0, semaphores);
+                SubmitAnyBuilder::BindSparse(bind_infos, None)
+            }
+            SubmitAnyBuilder::CommandBuffer(mut submit_info, fence) => {
+                // Sparse binding operations are not ordered with command buffer submissions, so
+                // a semaphore is needed between them.
+                submit_info
+                    .signal_semaphores
+                    .push(SemaphoreSubmitInfo::semaphore(self.semaphore.clone()));
+
+                self.queue.with(|mut q| {
+                    q.submit_with_future(submit_info, fence, &self.previous, &self.queue)
+                })?;
+
+                bind_infos[0]
+                    .wait_semaphores
+                    .insert(0, self.semaphore.clone());
+                SubmitAnyBuilder::BindSparse(bind_infos, None)
+            }
+            SubmitAnyBuilder::QueuePresent(_) => {
+                self.previous.flush()?;
+                SubmitAnyBuilder::BindSparse(bind_infos, None)
+            }
+            SubmitAnyBuilder::BindSparse(mut previous_bind_infos, fence) => {
+                // Batches of sparse binding operations may complete out of order, so a semaphore
+                // is needed to make sure that the previous batches complete first.
+                previous_bind_infos
+                    .last_mut()
+                    .unwrap()
+                    .signal_semaphores
+                    .push(self.semaphore.clone());
+                bind_infos[0]
+                    .wait_semaphores
+                    .insert(0, self.semaphore.clone());
+
+                previous_bind_infos.extend(bind_infos);
+                SubmitAnyBuilder::BindSparse(previous_bind_infos, fence)
+            }
+        })
+    }
+}
+
+unsafe impl<F> GpuFuture for BindSparseFuture<F>
+where
+    F: GpuFuture,
+{
+    fn cleanup_finished(&mut self) {
+        self.previous.cleanup_finished();
+    }
+
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        if *self.submitted.lock() {
+            return Ok(SubmitAnyBuilder::Empty);
+        }
+
+        self.build_submission_impl()
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        unsafe {
+            let mut submitted = self.submitted.lock();
+            if *submitted {
+                return Ok(());
+            }
+
+            match self.build_submission_impl()? {
+                SubmitAnyBuilder::BindSparse(bind_infos, fence) => {
+                    self.queue
+                        .with(|mut q| q.bind_sparse_unchecked(bind_infos, fence))?;
+                }
+                _ => unreachable!(),
+            };
+
+            // Only write `true` here in order to try again next time if we failed to submit.
+            *submitted = true;
+            Ok(())
+        }
+    }
+
+    unsafe fn signal_finished(&self) {
+        self.finished.store(true, Ordering::SeqCst);
+        self.previous.signal_finished();
+    }
+
+    fn queue_change_allowed(&self) -> bool {
+        false
+    }
+
+    fn queue(&self) -> Option<Arc<Queue>> {
+        Some(self.queue.clone())
+    }
+
+    fn check_buffer_access(
+        &self,
+        buffer: &Buffer,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_buffer_access(buffer, range, exclusive, queue)
+    }
+
+    fn check_image_access(
+        &self,
+        image: &Image,
+        range: Range<DeviceSize>,
+        exclusive: bool,
+        expected_layout: ImageLayout,
+        queue: &Queue,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_image_access(image, range, exclusive, expected_layout, queue)
+    }
+
+    #[inline]
+    fn check_swapchain_image_acquired(
+        &self,
+        swapchain: &Swapchain,
+        image_index: u32,
+        _before: bool,
+    ) -> Result<(), AccessCheckError> {
+        self.previous
+            .check_swapchain_image_acquired(swapchain, image_index, false)
+    }
+}
+
+unsafe impl<F> DeviceOwned for BindSparseFuture<F>
+where
+    F: GpuFuture,
+{
+    fn device(&self) -> &Arc<Device> {
+        self.queue.device()
+    }
+}
+
+impl<F> Drop for BindSparseFuture<F>
+where
+    F: GpuFuture,
+{
+    fn drop(&mut self) {
+        unsafe {
+            if !*self.finished.get_mut() {
+                // If the device was lost, nothing more will be executed on it, so the resources
+                // can be released anyway.
+                if let Err(err) = self.flush() {
+                    assert!(self.device().is_lost(), "{:?}", err);
+                }
+
+                // Block until the queue finished.
+                if let Err(err) = self.queue.with(|mut q| q.wait_idle()) {
+                    assert!(self.device().is_lost(), "{:?}", err);
+                }
+
+                self.previous.signal_finished();
+            }
+        }
+    }
+}
@@ -15,11 +15,11 @@ use crate::{
     image::{sys::Image, ImageLayout},
     swapchain::Swapchain,
     sync::{
-        fence::Fence,
+        fence::{Fence, FenceError},
         future::{AccessError, SubmitAnyBuilder},
         PipelineStages,
     },
-    DeviceSize, OomError,
+    DeviceSize,
 };
 use parking_lot::{Mutex, MutexGuard};
 use std::{
@@ -139,7 +139,7 @@ where
     F: GpuFuture,
 {
     /// Returns true if the fence is signaled by the GPU.
-    pub fn is_signaled(&self) -> Result<bool, OomError> {
+    pub fn is_signaled(&self) -> Result<bool, FenceError> {
         let state = self.state.lock();
 
         match &*state {
@@ -166,7 +166,9 @@ where
 
         match replace(&mut *state, FenceSignalFutureState::Cleaned) {
             FenceSignalFutureState::Flushed(previous, fence) => {
-                fence.wait(timeout)?;
+                fence
+                    .wait(timeout)
+                    .map_err(|err| FlushError::from(err).with_device_fault(fence.device()))?;
                 unsafe {
                     previous.signal_finished();
                 }
@@ -358,11 +360,11 @@ where
                 }
                 Err(OutcomeErr::Partial(err)) => {
                     **state = FenceSignalFutureState::PartiallyFlushed(previous, new_fence);
-                    Err(err)
+                    Err(err.with_device_fault(queue.device()))
                 }
                 Err(OutcomeErr::Full(err)) => {
                     **state = FenceSignalFutureState::Pending(previous, new_fence);
-                    Err(err)
+                    Err(err.with_device_fault(queue.device()))
                 }
             }
         }
@@ -373,7 +375,7 @@ impl<F> Future for FenceSignalFuture<F>
 where
     F: GpuFuture,
 {
-    type Output = Result<(), OomError>;
+    type Output = Result<(), FenceError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         // Implement through fence
@@ -416,7 +418,9 @@ where
         match &*state {
             FenceSignalFutureState::Flushed(_, fence) => match self.behavior {
                 FenceSignalFutureBehavior::Block { timeout } => {
-                    fence.wait(timeout)?;
+                    fence
+                        .wait(timeout)
+                        .map_err(|err| FlushError::from(err).with_device_fault(fence.device()))?;
                 }
                 FenceSignalFutureBehavior::Continue => (),
             },
@@ -531,8 +535,12 @@ where
         match replace(&mut *state, FenceSignalFutureState::Cleaned) {
             FenceSignalFutureState::Flushed(previous, fence) => {
                 // This is a normal situation. Submitting worked.
-                // TODO: handle errors?
-                fence.wait(None).unwrap();
+                // If the device was lost, the GPU will never touch the resources again, so it's
+                // safe to release them anyway.
+                match fence.wait(None) {
+                    Ok(()) | Err(FenceError::DeviceLost) => (),
+                    Err(err) => panic!("{:?}", err),
+                }
                 unsafe {
                     previous.signal_finished();
                 }
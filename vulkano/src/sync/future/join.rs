@@ -10,9 +10,11 @@
 use super::{AccessCheckError, FlushError, GpuFuture, SubmitAnyBuilder};
 use crate::{
     buffer::sys::Buffer,
+    command_buffer::SemaphoreSubmitInfo,
     device::{Device, DeviceOwned, Queue},
     image::{sys::Image, ImageLayout},
     swapchain::Swapchain,
+    sync::PipelineStages,
     DeviceSize, VulkanObject,
 };
 use std::{ops::Range, sync::Arc};
@@ -33,6 +35,43 @@ where
     JoinFuture { first, second }
 }
 
+/// Joins any number of futures together, representing the moment when all of them have
+/// happened.
+///
+/// Unlike repeated calls to [`join`](GpuFuture::join), which produce a deeply nested type, this
+/// returns a single boxed future. The futures are joined pairwise, so that the depth of the
+/// resulting tree of futures only grows logarithmically with their number.
+///
+/// # Panics
+///
+/// - Panics if `futures` is empty.
+/// - Panics if the futures don't all belong to the same device.
+/// - Panics if two of the futures are bound to different queues and neither of them allows
+///   changing the queue.
+pub fn join_all<I>(futures: I) -> Box<dyn GpuFuture>
+where
+    I: IntoIterator<Item = Box<dyn GpuFuture>>,
+{
+    let mut futures: Vec<_> = futures.into_iter().collect();
+    assert!(!futures.is_empty(), "`futures` must not be empty");
+
+    while futures.len() > 1 {
+        let mut joined = Vec::with_capacity((futures.len() + 1) / 2);
+        let mut futures_iter = futures.into_iter();
+
+        while let Some(first) = futures_iter.next() {
+            joined.push(match futures_iter.next() {
+                Some(second) => join(first, second).boxed(),
+                None => first,
+            });
+        }
+
+        futures = joined;
+    }
+
+    futures.pop().unwrap()
+}
+
 /// Two futures joined into one.
 #[must_use]
 pub struct JoinFuture<A, B> {
@@ -85,13 +124,27 @@ where
                 a.extend(b);
                 SubmitAnyBuilder::SemaphoresWait(a)
             }
-            (SubmitAnyBuilder::SemaphoresWait(a), SubmitAnyBuilder::CommandBuffer(_, _)) => {
-                self.second.flush()?;
-                SubmitAnyBuilder::SemaphoresWait(a)
-            }
-            (SubmitAnyBuilder::CommandBuffer(_, _), SubmitAnyBuilder::SemaphoresWait(b)) => {
-                self.first.flush()?;
-                SubmitAnyBuilder::SemaphoresWait(b)
+            // Add the semaphores to the command buffer submission, so that both can be submitted
+            // in a single batch.
+            (
+                SubmitAnyBuilder::SemaphoresWait(semaphores),
+                SubmitAnyBuilder::CommandBuffer(mut submit_info, fence),
+            )
+            | (
+                SubmitAnyBuilder::CommandBuffer(mut submit_info, fence),
+                SubmitAnyBuilder::SemaphoresWait(semaphores),
+            ) => {
+                submit_info
+                    .wait_semaphores
+                    .extend(semaphores.into_iter().map(|semaphore| {
+                        SemaphoreSubmitInfo {
+                            // TODO: correct stages ; hard
+                            stages: PipelineStages::ALL_COMMANDS,
+                            ..SemaphoreSubmitInfo::semaphore(semaphore)
+                        }
+                    }));
+
+                SubmitAnyBuilder::CommandBuffer(submit_info, fence)
             }
             (SubmitAnyBuilder::SemaphoresWait(a), SubmitAnyBuilder::QueuePresent(_)) => {
                 self.second.flush()?;
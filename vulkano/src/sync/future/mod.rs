@@ -91,8 +91,9 @@
 //! Signalling a fence is kind of a "terminator" to a chain of futures
 
 pub use self::{
+    bind_sparse::BindSparseFuture,
     fence_signal::{FenceSignalFuture, FenceSignalFutureBehavior},
-    join::JoinFuture,
+    join::{join_all, JoinFuture},
     now::{now, NowFuture},
     semaphore_signal::SemaphoreSignalFuture,
 };
@@ -103,10 +104,10 @@ use super::{
 use crate::{
     buffer::sys::Buffer,
     command_buffer::{
-        CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract,
+        self, CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract,
         ResourceUseRef, SubmitInfo,
     },
-    device::{DeviceOwned, Queue},
+    device::{BindSparseError, Device, DeviceFaultInfo, DeviceOwned, Queue},
     image::{sys::Image, ImageLayout},
     memory::BindSparseInfo,
     swapchain::{self, PresentFuture, PresentInfo, Swapchain, SwapchainPresentInfo},
@@ -120,6 +121,7 @@ use std::{
     sync::Arc,
 };
 
+mod bind_sparse;
 mod fence_signal;
 mod join;
 mod now;
@@ -244,6 +246,15 @@ pub unsafe trait GpuFuture: DeviceOwned {
         join::join(self, other)
     }
 
+    /// Joins this future with any number of other futures. See [`join_all`] for details.
+    fn join_all<I>(self, others: I) -> Box<dyn GpuFuture>
+    where
+        Self: Sized + 'static,
+        I: IntoIterator<Item = Box<dyn GpuFuture>>,
+    {
+        join::join_all(std::iter::once(self.boxed()).chain(others))
+    }
+
     /// Executes a command buffer after this future.
     ///
     /// > **Note**: This is just a shortcut function. The actual implementation is in the
@@ -276,6 +287,58 @@ pub unsafe trait GpuFuture: DeviceOwned {
         command_buffer.execute_after(self, queue)
     }
 
+    /// Executes several command buffers after this future, in a single queue submission.
+    ///
+    /// This has the same effect as chaining calls to [`then_execute`](Self::then_execute) with
+    /// the same queue, but the resulting future has the same type regardless of the number of
+    /// command buffers. The command buffers are executed in the order that they are provided.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `command_buffers` is empty.
+    /// - Panics if the device of one of the command buffers is not the same as the device of the
+    ///   future.
+    fn then_execute_batch<I>(
+        self,
+        queue: Arc<Queue>,
+        command_buffers: I,
+    ) -> Result<CommandBufferExecFuture<Self>, CommandBufferExecError>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = Arc<dyn PrimaryCommandBufferAbstract>>,
+    {
+        command_buffer::execute_batch_after(self, queue, command_buffers)
+    }
+
+    /// Performs sparse bind operations after this future.
+    ///
+    /// The bind operations are ordered after the previous operations, and operations that are
+    /// chained after the returned future are ordered after the bind operations, using semaphores
+    /// where needed.
+    ///
+    /// # Safety
+    ///
+    /// - The safety requirements of [`QueueGuard::bind_sparse`] apply.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `bind_infos` is empty.
+    /// - Panics if `queue` does not belong to the same device as `self`.
+    /// - Panics if the queue of `self` can't be changed, and `queue` is a different queue.
+    ///
+    /// [`QueueGuard::bind_sparse`]: crate::device::QueueGuard::bind_sparse
+    unsafe fn then_bind_sparse<I>(
+        self,
+        queue: Arc<Queue>,
+        bind_infos: I,
+    ) -> Result<BindSparseFuture<Self>, BindSparseError>
+    where
+        Self: Sized,
+        I: IntoIterator<Item = BindSparseInfo>,
+    {
+        bind_sparse::then_bind_sparse(self, queue, bind_infos)
+    }
+
     /// Signals a semaphore after this future. Returns another future that represents the signal.
     ///
     /// Call this function when you want to execute some operations on a queue and want to see the
@@ -312,6 +375,31 @@ pub unsafe trait GpuFuture: DeviceOwned {
         Ok(f)
     }
 
+    /// Signals a timeline semaphore after this future, setting its counter to `value`. Returns
+    /// another future that represents the signal.
+    ///
+    /// Operations that are chained after the returned future will wait for the counter of
+    /// `semaphore` to reach `value`. Other submissions and the host can also wait for this value,
+    /// for example with [`Semaphore::wait`].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `semaphore` is not a timeline semaphore.
+    /// - Panics if `semaphore` does not belong to the same device as `self`.
+    /// - Panics if `self` is not associated with a queue, such as a future returned by
+    ///   [`now`].
+    #[inline]
+    fn then_signal_timeline_semaphore(
+        self,
+        semaphore: Arc<Semaphore>,
+        value: u64,
+    ) -> SemaphoreSignalFuture<Self>
+    where
+        Self: Sized,
+    {
+        semaphore_signal::then_signal_timeline_semaphore(self, semaphore, value)
+    }
+
     /// Signals a fence after this future. Returns another future that represents the signal.
     ///
     /// > **Note**: More often than not you want to immediately flush the future after calling this
@@ -356,6 +444,24 @@ pub unsafe trait GpuFuture: DeviceOwned {
         swapchain::present(self, queue, swapchain_info)
     }
 
+    /// Presents images of several swapchains after this future, with a single present operation.
+    ///
+    /// You should only ever do this indirectly after a `SwapchainAcquireFuture` of each image,
+    /// otherwise an error will occur when flushing.
+    ///
+    /// > **Note**: This is just a shortcut for the `swapchain::present_multi()` function.
+    #[inline]
+    fn then_swapchain_present_multi(
+        self,
+        queue: Arc<Queue>,
+        swapchain_infos: impl IntoIterator<Item = SwapchainPresentInfo>,
+    ) -> PresentFuture<Self>
+    where
+        Self: Sized,
+    {
+        swapchain::present_multi(self, queue, swapchain_infos)
+    }
+
     /// Turn the current future into a `Box<dyn GpuFuture>`.
     ///
     /// This is a helper function that calls `Box::new(yourFuture) as Box<dyn GpuFuture>`.
@@ -579,8 +685,15 @@ pub enum FlushError {
     OomError(OomError),
 
     /// The connection to the device has been lost.
+    ///
+    /// If the [`device_fault`](crate::device::Features::device_fault) feature is enabled,
+    /// `DeviceFault` is returned instead.
     DeviceLost,
 
+    /// The connection to the device has been lost, and the device reported information about
+    /// the fault that caused it.
+    DeviceFault(Arc<DeviceFaultInfo>),
+
     /// The surface is no longer accessible and must be recreated.
     SurfaceLost,
 
@@ -599,6 +712,9 @@ pub enum FlushError {
     /// for the same swapchain.
     PresentIdLessThanOrEqual,
 
+    /// A present region is not compatible with the swapchain that it is presented to.
+    PresentRegionOutOfBounds,
+
     /// Access to a resource has been denied.
     ResourceAccessError {
         error: AccessError,
@@ -634,6 +750,9 @@ impl Display for FlushError {
                 FlushError::AccessError(_) => "access to a resource has been denied",
                 FlushError::OomError(_) => "not enough memory",
                 FlushError::DeviceLost => "the connection to the device has been lost",
+                FlushError::DeviceFault(_) => {
+                    "the connection to the device has been lost because of a device fault"
+                }
                 FlushError::SurfaceLost => "the surface of this swapchain is no longer valid",
                 FlushError::OutOfDate => "the swapchain needs to be recreated",
                 FlushError::FullScreenExclusiveModeLost => {
@@ -645,6 +764,9 @@ impl Display for FlushError {
                 FlushError::PresentIdLessThanOrEqual => {
                     "present id is less than or equal to previous"
                 }
+                FlushError::PresentRegionOutOfBounds => {
+                    "a present region is not compatible with the swapchain"
+                }
                 FlushError::ResourceAccessError { .. } => "access to a resource has been denied",
                 FlushError::OneTimeSubmitAlreadySubmitted => {
                     "the command buffer or one of the secondary command buffers it executes was \
@@ -660,6 +782,22 @@ impl Display for FlushError {
     }
 }
 
+impl FlushError {
+    /// If `self` is `DeviceLost` and the device can report the fault that caused it, returns
+    /// `DeviceFault` with the reported information instead.
+    pub(crate) fn with_device_fault(self, device: &Device) -> Self {
+        match self {
+            Self::DeviceLost if device.enabled_features().device_fault => {
+                match unsafe { device.fault_info_unchecked() } {
+                    Ok(fault_info) => Self::DeviceFault(Arc::new(fault_info)),
+                    Err(_) => Self::DeviceLost,
+                }
+            }
+            err => err,
+        }
+    }
+}
+
 impl From<AccessError> for FlushError {
     fn from(err: AccessError) -> FlushError {
         FlushError::AccessError(err)
@@ -14,7 +14,11 @@ use crate::{
     device::{Device, DeviceOwned, Queue},
     image::{sys::Image, ImageLayout},
     swapchain::Swapchain,
-    sync::{future::AccessError, semaphore::Semaphore, PipelineStages},
+    sync::{
+        future::AccessError,
+        semaphore::{Semaphore, SemaphoreType},
+        PipelineStages,
+    },
     DeviceSize,
 };
 use parking_lot::Mutex;
@@ -39,6 +43,30 @@ where
     SemaphoreSignalFuture {
         previous: future,
         semaphore: Arc::new(Semaphore::from_pool(device).unwrap()),
+        value: 0,
+        wait_submitted: Mutex::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Builds a new timeline semaphore signal future.
+pub fn then_signal_timeline_semaphore<F>(
+    future: F,
+    semaphore: Arc<Semaphore>,
+    value: u64,
+) -> SemaphoreSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    assert_eq!(future.device(), semaphore.device());
+    assert_eq!(semaphore.semaphore_type(), SemaphoreType::Timeline);
+    // The signal operation is part of a queue submission, so the queue must be known.
+    assert!(future.queue().is_some());
+
+    SemaphoreSignalFuture {
+        previous: future,
+        semaphore,
+        value,
         wait_submitted: Mutex::new(false),
         finished: AtomicBool::new(false),
     }
@@ -53,6 +81,8 @@ where
 {
     previous: F,
     semaphore: Arc<Semaphore>,
+    // The value to signal, if `semaphore` is a timeline semaphore.
+    value: u64,
     // True if the signaling command has already been submitted.
     // If flush is called multiple times, we want to block so that only one flushing is executed.
     // Therefore we use a `Mutex<bool>` and not an `AtomicBool`.
@@ -60,6 +90,18 @@ where
     finished: AtomicBool,
 }
 
+impl<F> SemaphoreSignalFuture<F>
+where
+    F: GpuFuture,
+{
+    fn signal_info(&self) -> SemaphoreSubmitInfo {
+        SemaphoreSubmitInfo {
+            value: self.value,
+            ..SemaphoreSubmitInfo::semaphore(self.semaphore.clone())
+        }
+    }
+}
+
 unsafe impl<F> GpuFuture for SemaphoreSignalFuture<F>
 where
     F: GpuFuture,
@@ -71,9 +113,21 @@ where
     unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
         // Flushing the signaling part, since it must always be submitted before the waiting part.
         self.flush()?;
-        let sem = smallvec![self.semaphore.clone()];
 
-        Ok(SubmitAnyBuilder::SemaphoresWait(sem))
+        match self.semaphore.semaphore_type() {
+            SemaphoreType::Binary => {
+                let sem = smallvec![self.semaphore.clone()];
+                Ok(SubmitAnyBuilder::SemaphoresWait(sem))
+            }
+            // `SemaphoresWait` can't carry a value, so wait using an empty submission instead.
+            SemaphoreType::Timeline => Ok(SubmitAnyBuilder::CommandBuffer(
+                SubmitInfo {
+                    wait_semaphores: vec![self.signal_info()],
+                    ..Default::default()
+                },
+                None,
+            )),
+        }
     }
 
     fn flush(&self) -> Result<(), FlushError> {
@@ -91,9 +145,7 @@ where
                     queue.with(|mut q| {
                         q.submit_unchecked(
                             [SubmitInfo {
-                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
-                                    self.semaphore.clone(),
-                                )],
+                                signal_semaphores: vec![self.signal_info()],
                                 ..Default::default()
                             }],
                             None,
@@ -114,9 +166,7 @@ where
                                         }
                                     })
                                     .collect(),
-                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
-                                    self.semaphore.clone(),
-                                )],
+                                signal_semaphores: vec![self.signal_info()],
                                 ..Default::default()
                             }],
                             None,
@@ -126,19 +176,50 @@ where
                 SubmitAnyBuilder::CommandBuffer(mut submit_info, fence) => {
                     debug_assert!(submit_info.signal_semaphores.is_empty());
 
-                    submit_info
-                        .signal_semaphores
-                        .push(SemaphoreSubmitInfo::semaphore(self.semaphore.clone()));
+                    submit_info.signal_semaphores.push(self.signal_info());
 
                     queue.with(|mut q| {
                         q.submit_with_future(submit_info, fence, &self.previous, &queue)
                     })?;
                 }
-                SubmitAnyBuilder::BindSparse(_, _) => {
-                    unimplemented!() // TODO: how to do that?
-                                     /*debug_assert_eq!(builder.num_signal_semaphores(), 0);
-                                     builder.add_signal_semaphore(&self.semaphore);
-                                     builder.submit(&queue)?;*/
+                SubmitAnyBuilder::BindSparse(mut bind_infos, fence) => {
+                    match self.semaphore.semaphore_type() {
+                        SemaphoreType::Binary => {
+                            bind_infos
+                                .last_mut()
+                                .unwrap()
+                                .signal_semaphores
+                                .push(self.semaphore.clone());
+
+                            queue.with(|mut q| q.bind_sparse_unchecked(bind_infos, fence))?;
+                        }
+                        // Sparse binding can't signal a timeline semaphore with a value, so signal
+                        // an intermediate binary semaphore and use an empty submission instead.
+                        SemaphoreType::Timeline => {
+                            let semaphore =
+                                Arc::new(Semaphore::from_pool(self.device().clone()).unwrap());
+                            bind_infos
+                                .last_mut()
+                                .unwrap()
+                                .signal_semaphores
+                                .push(semaphore.clone());
+
+                            queue.with(|mut q| {
+                                q.bind_sparse_unchecked(bind_infos, fence)?;
+                                q.submit_unchecked(
+                                    [SubmitInfo {
+                                        wait_semaphores: vec![SemaphoreSubmitInfo {
+                                            stages: PipelineStages::ALL_COMMANDS,
+                                            ..SemaphoreSubmitInfo::semaphore(semaphore)
+                                        }],
+                                        signal_semaphores: vec![self.signal_info()],
+                                        ..Default::default()
+                                    }],
+                                    None,
+                                )
+                            })?;
+                        }
+                    }
                 }
                 SubmitAnyBuilder::QueuePresent(present_info) => {
                     // VUID-VkPresentIdKHR-presentIds-04999
@@ -169,9 +250,7 @@ where
                         // FIXME: problematic because if we return an error and flush() is called again, then we'll submit the present twice
                         q.submit_unchecked(
                             [SubmitInfo {
-                                signal_semaphores: vec![SemaphoreSubmitInfo::semaphore(
-                                    self.semaphore.clone(),
-                                )],
+                                signal_semaphores: vec![self.signal_info()],
                                 ..Default::default()
                             }],
                             None,
@@ -252,10 +331,17 @@ where
     fn drop(&mut self) {
         unsafe {
             if !*self.finished.get_mut() {
-                // TODO: handle errors?
-                self.flush().unwrap();
+                // If the device was lost, nothing more will be executed on it, so the resources
+                // can be released anyway.
+                if let Err(err) = self.flush() {
+                    assert!(self.device().is_lost(), "{:?}", err);
+                }
+
                 // Block until the queue finished.
-                self.queue().unwrap().with(|mut q| q.wait_idle()).unwrap();
+                if let Err(err) = self.queue().unwrap().with(|mut q| q.wait_idle()) {
+                    assert!(self.device().is_lost(), "{:?}", err);
+                }
+
                 self.previous.signal_finished();
             }
         }
@@ -17,7 +17,7 @@
 //! knowledge if you want to avoid errors.
 
 pub use self::{
-    future::{now, FlushError, GpuFuture},
+    future::{join_all, now, FlushError, GpuFuture},
     pipeline::{
         AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
         MemoryBarrier, PipelineMemoryAccess, PipelineStage, PipelineStages,
@@ -67,7 +67,7 @@ impl<'a> From<&'a [&'a Arc<Queue>]> for SharingMode {
 }
 
 /// Declares in which queue(s) a resource can be used.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Sharing<I>
 where
     I: IntoIterator<Item = u32>,
@@ -95,6 +95,47 @@ vulkan_bitflags_enum! {
 
             self
         }
+
+        /// Replaces flags that are only available in `VkPipelineStageFlagBits2` with equivalent
+        /// flags from `VkPipelineStageFlagBits`, for use when the `synchronization2` feature is
+        /// not enabled.
+        ///
+        /// Stages that have no narrower equivalent turn the whole value into `all_commands`.
+        pub(crate) fn into_legacy(mut self) -> Self {
+            if self.intersects(
+                PipelineStages::COPY
+                    | PipelineStages::RESOLVE
+                    | PipelineStages::BLIT
+                    | PipelineStages::CLEAR
+                    | PipelineStages::ACCELERATION_STRUCTURE_COPY,
+            ) {
+                self -= PipelineStages::COPY
+                    | PipelineStages::RESOLVE
+                    | PipelineStages::BLIT
+                    | PipelineStages::CLEAR
+                    | PipelineStages::ACCELERATION_STRUCTURE_COPY;
+                self |= PipelineStages::ALL_TRANSFER;
+            }
+
+            if self.intersects(
+                PipelineStages::INDEX_INPUT | PipelineStages::VERTEX_ATTRIBUTE_INPUT,
+            ) {
+                self -= PipelineStages::INDEX_INPUT | PipelineStages::VERTEX_ATTRIBUTE_INPUT;
+                self |= PipelineStages::VERTEX_INPUT;
+            }
+
+            // The individual shader stages may need features that are not enabled.
+            if self.intersects(PipelineStages::PRE_RASTERIZATION_SHADERS) {
+                self -= PipelineStages::PRE_RASTERIZATION_SHADERS;
+                self |= PipelineStages::ALL_GRAPHICS;
+            }
+
+            if self.is_2() {
+                PipelineStages::ALL_COMMANDS
+            } else {
+                self
+            }
+        }
     },
 
     /// A single stage in the device's processing pipeline.
@@ -491,6 +532,47 @@ vulkan_bitflags! {
 
             self
         }
+
+        /// Replaces flags that are only available in `VkAccessFlagBits2` with equivalent flags
+        /// from `VkAccessFlagBits`, for use when the `synchronization2` feature is not enabled.
+        ///
+        /// Accesses that have no narrower equivalent are replaced with `memory_read` or
+        /// `memory_write`.
+        pub(crate) fn into_legacy(mut self) -> Self {
+            const READS_2: AccessFlags = AccessFlags::VIDEO_DECODE_READ
+                .union(AccessFlags::VIDEO_ENCODE_READ)
+                .union(AccessFlags::DESCRIPTOR_BUFFER_READ)
+                .union(AccessFlags::INVOCATION_MASK_READ)
+                .union(AccessFlags::SHADER_BINDING_TABLE_READ)
+                .union(AccessFlags::MICROMAP_READ)
+                .union(AccessFlags::OPTICAL_FLOW_READ);
+            const WRITES_2: AccessFlags = AccessFlags::VIDEO_DECODE_WRITE
+                .union(AccessFlags::VIDEO_ENCODE_WRITE)
+                .union(AccessFlags::MICROMAP_WRITE)
+                .union(AccessFlags::OPTICAL_FLOW_WRITE);
+
+            if self.intersects(AccessFlags::SHADER_SAMPLED_READ | AccessFlags::SHADER_STORAGE_READ) {
+                self -= AccessFlags::SHADER_SAMPLED_READ | AccessFlags::SHADER_STORAGE_READ;
+                self |= AccessFlags::SHADER_READ;
+            }
+
+            if self.intersects(AccessFlags::SHADER_STORAGE_WRITE) {
+                self -= AccessFlags::SHADER_STORAGE_WRITE;
+                self |= AccessFlags::SHADER_WRITE;
+            }
+
+            if self.intersects(READS_2) {
+                self -= READS_2;
+                self |= AccessFlags::MEMORY_READ;
+            }
+
+            if self.intersects(WRITES_2) {
+                self -= WRITES_2;
+                self |= AccessFlags::MEMORY_WRITE;
+            }
+
+            self
+        }
     }
     = AccessFlags2(u64);
 
@@ -660,6 +742,11 @@ vulkan_bitflags! {
         device_extensions: [ext_blend_operation_advanced],
     },
 
+    /// Read access to a descriptor buffer.
+    DESCRIPTOR_BUFFER_READ = DESCRIPTOR_BUFFER_READ_EXT {
+        device_extensions: [ext_descriptor_buffer],
+    },
+
     /// Read access to an invocation mask image.
     INVOCATION_MASK_READ = INVOCATION_MASK_READ_HUAWEI {
         device_extensions: [huawei_invocation_mask],
@@ -727,7 +814,8 @@ impl From<PipelineStages> for AccessFlags {
                 | AccessFlags::SHADER_WRITE
                 | AccessFlags::SHADER_STORAGE_WRITE
                 | AccessFlags::ACCELERATION_STRUCTURE_READ
-                | AccessFlags::SHADER_BINDING_TABLE_READ;
+                | AccessFlags::SHADER_BINDING_TABLE_READ
+                | AccessFlags::DESCRIPTOR_BUFFER_READ;
         }
 
         if val.intersects(PipelineStages::FRAGMENT_SHADER | PipelineStages::SUBPASS_SHADING) {
@@ -1386,3 +1474,48 @@ impl From<QueueFamilyOwnershipTransfer> for (u32, u32) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stages_into_legacy() {
+        assert_eq!(
+            (PipelineStages::COPY | PipelineStages::FRAGMENT_SHADER).into_legacy(),
+            PipelineStages::ALL_TRANSFER | PipelineStages::FRAGMENT_SHADER,
+        );
+        assert_eq!(
+            PipelineStages::INDEX_INPUT.into_legacy(),
+            PipelineStages::VERTEX_INPUT,
+        );
+        assert_eq!(
+            PipelineStages::PRE_RASTERIZATION_SHADERS.into_legacy(),
+            PipelineStages::ALL_GRAPHICS,
+        );
+        assert_eq!(
+            (PipelineStages::VIDEO_DECODE | PipelineStages::COMPUTE_SHADER).into_legacy(),
+            PipelineStages::ALL_COMMANDS,
+        );
+        assert_eq!(
+            PipelineStages::TOP_OF_PIPE.into_legacy(),
+            PipelineStages::TOP_OF_PIPE,
+        );
+    }
+
+    #[test]
+    fn access_into_legacy() {
+        assert_eq!(
+            (AccessFlags::SHADER_SAMPLED_READ | AccessFlags::SHADER_STORAGE_WRITE).into_legacy(),
+            AccessFlags::SHADER_READ | AccessFlags::SHADER_WRITE,
+        );
+        assert_eq!(
+            (AccessFlags::VIDEO_DECODE_READ | AccessFlags::VIDEO_DECODE_WRITE).into_legacy(),
+            AccessFlags::MEMORY_READ | AccessFlags::MEMORY_WRITE,
+        );
+        assert_eq!(
+            AccessFlags::TRANSFER_WRITE.into_legacy(),
+            AccessFlags::TRANSFER_WRITE,
+        );
+    }
+}
@@ -12,10 +12,11 @@
 
 use crate::{
     device::{Device, DeviceOwned, Queue},
-    macros::{vulkan_bitflags, vulkan_bitflags_enum},
+    macros::{vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
     OomError, RequirementNotMet, RequiresOneOf, Version, VulkanError, VulkanObject,
 };
 use parking_lot::{Mutex, MutexGuard};
+use smallvec::SmallVec;
 #[cfg(unix)]
 use std::fs::File;
 use std::{
@@ -25,12 +26,17 @@ use std::{
     num::NonZeroU64,
     ptr,
     sync::{Arc, Weak},
+    time::Duration,
 };
 
 /// Used to provide synchronization between command buffers during their execution.
 ///
 /// It is similar to a fence, except that it is purely on the GPU side. The CPU can't query a
-/// semaphore's status or wait for it to be signaled.
+/// binary semaphore's status or wait for it to be signaled.
+///
+/// A semaphore created with [`SemaphoreType::Timeline`] instead holds a 64-bit counter that only
+/// increases. Queue operations can wait for the counter to reach a value or signal it to a new
+/// value, and the host can query, signal and wait on the counter as well.
 #[derive(Debug)]
 pub struct Semaphore {
     handle: ash::vk::Semaphore,
@@ -38,6 +44,8 @@ pub struct Semaphore {
     id: NonZeroU64,
     must_put_in_pool: bool,
 
+    semaphore_type: SemaphoreType,
+
     export_handle_types: ExternalSemaphoreHandleTypes,
 
     state: Mutex<SemaphoreState>,
@@ -60,10 +68,34 @@ impl Semaphore {
         create_info: &SemaphoreCreateInfo,
     ) -> Result<(), SemaphoreError> {
         let &SemaphoreCreateInfo {
+            semaphore_type,
+            initial_value,
             export_handle_types,
             _ne: _,
         } = create_info;
 
+        match semaphore_type {
+            SemaphoreType::Binary => {
+                // VUID-VkSemaphoreTypeCreateInfo-semaphoreType-03279
+                if initial_value != 0 {
+                    return Err(SemaphoreError::BinaryInitialValueNotZero);
+                }
+            }
+            SemaphoreType::Timeline => {
+                // VUID-VkSemaphoreTypeCreateInfo-timelineSemaphore-03252
+                if !device.enabled_features().timeline_semaphore {
+                    return Err(SemaphoreError::RequirementNotMet {
+                        required_for: "`create_info.semaphore_type` is \
+                            `SemaphoreType::Timeline`",
+                        requires_one_of: RequiresOneOf {
+                            features: &["timeline_semaphore"],
+                            ..Default::default()
+                        },
+                    });
+                }
+            }
+        }
+
         if !export_handle_types.is_empty() {
             if !(device.api_version() >= Version::V1_1
                 || device.enabled_extensions().khr_external_semaphore)
@@ -114,6 +146,8 @@ impl Semaphore {
         create_info: SemaphoreCreateInfo,
     ) -> Result<Semaphore, VulkanError> {
         let SemaphoreCreateInfo {
+            semaphore_type,
+            initial_value,
             export_handle_types,
             _ne: _,
         } = create_info;
@@ -122,8 +156,20 @@ impl Semaphore {
             flags: ash::vk::SemaphoreCreateFlags::empty(),
             ..Default::default()
         };
+        let mut semaphore_type_create_info_vk = None;
         let mut export_semaphore_create_info_vk = None;
 
+        if semaphore_type != SemaphoreType::Binary {
+            let next = semaphore_type_create_info_vk.insert(ash::vk::SemaphoreTypeCreateInfo {
+                semaphore_type: semaphore_type.into(),
+                initial_value,
+                ..Default::default()
+            });
+
+            next.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
         if !export_handle_types.is_empty() {
             let _ = export_semaphore_create_info_vk.insert(ash::vk::ExportSemaphoreCreateInfo {
                 handle_types: export_handle_types.into(),
@@ -155,8 +201,9 @@ impl Semaphore {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            semaphore_type,
             export_handle_types,
-            state: Mutex::new(Default::default()),
+            state: Mutex::new(SemaphoreState::new(semaphore_type)),
         })
     }
 
@@ -175,8 +222,9 @@ impl Semaphore {
                 device,
                 id: Self::next_id(),
                 must_put_in_pool: true,
+                semaphore_type: SemaphoreType::Binary,
                 export_handle_types: ExternalSemaphoreHandleTypes::empty(),
-                state: Mutex::new(Default::default()),
+                state: Mutex::new(SemaphoreState::new(SemaphoreType::Binary)),
             },
             None => {
                 // Pool is empty, alloc new semaphore
@@ -202,6 +250,8 @@ impl Semaphore {
         create_info: SemaphoreCreateInfo,
     ) -> Semaphore {
         let SemaphoreCreateInfo {
+            semaphore_type,
+            initial_value: _,
             export_handle_types,
             _ne: _,
         } = create_info;
@@ -211,8 +261,210 @@ impl Semaphore {
             device,
             id: Self::next_id(),
             must_put_in_pool: false,
+            semaphore_type,
             export_handle_types,
-            state: Mutex::new(Default::default()),
+            state: Mutex::new(SemaphoreState::new(semaphore_type)),
+        }
+    }
+
+    /// Returns the type of the semaphore.
+    #[inline]
+    pub fn semaphore_type(&self) -> SemaphoreType {
+        self.semaphore_type
+    }
+
+    /// Returns the current value of the counter of a timeline semaphore.
+    #[inline]
+    pub fn counter_value(&self) -> Result<u64, SemaphoreError> {
+        self.validate_counter_value()?;
+
+        unsafe { Ok(self.counter_value_unchecked()?) }
+    }
+
+    fn validate_counter_value(&self) -> Result<(), SemaphoreError> {
+        // VUID-vkGetSemaphoreCounterValue-semaphore-03255
+        if self.semaphore_type != SemaphoreType::Timeline {
+            return Err(SemaphoreError::NotTimeline);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn counter_value_unchecked(&self) -> Result<u64, VulkanError> {
+        let fns = self.device.fns();
+        let mut output = MaybeUninit::uninit();
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.get_semaphore_counter_value)(
+                self.device.handle(),
+                self.handle,
+                output.as_mut_ptr(),
+            )
+        } else {
+            (fns.khr_timeline_semaphore.get_semaphore_counter_value_khr)(
+                self.device.handle(),
+                self.handle,
+                output.as_mut_ptr(),
+            )
+        }
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(output.assume_init())
+    }
+
+    /// Signals a timeline semaphore from the host, setting its counter to `value`.
+    ///
+    /// # Safety
+    ///
+    /// - `value` must be less than the value of any signal operation on the semaphore that is
+    ///   currently pending in a queue. This is checked for operations that were submitted through
+    ///   Vulkano, but not for operations on an imported payload.
+    #[inline]
+    pub unsafe fn signal(&self, value: u64) -> Result<(), SemaphoreError> {
+        self.validate_signal(value)?;
+
+        Ok(self.signal_unchecked(value)?)
+    }
+
+    fn validate_signal(&self, value: u64) -> Result<(), SemaphoreError> {
+        // VUID-VkSemaphoreSignalInfo-semaphore-03257
+        if self.semaphore_type != SemaphoreType::Timeline {
+            return Err(SemaphoreError::NotTimeline);
+        }
+
+        let state = self.state.lock();
+        let (pending_signals, pending_waits) = state.pending_values().unwrap();
+        let current_value = unsafe { self.counter_value_unchecked()? };
+
+        // VUID-VkSemaphoreSignalInfo-value-03258
+        if value <= current_value {
+            return Err(SemaphoreError::SignalValueNotGreater {
+                value,
+                current_value,
+            });
+        }
+
+        // VUID-VkSemaphoreSignalInfo-value-03259
+        if let Some(pending_value) = pending_signals.iter().copied().min() {
+            if value >= pending_value {
+                return Err(SemaphoreError::SignalValueNotLessThanPending {
+                    value,
+                    pending_value,
+                });
+            }
+        }
+
+        // VUID-VkSemaphoreSignalInfo-value-03260
+        if let Some(max) = self
+            .device
+            .physical_device()
+            .properties()
+            .max_timeline_semaphore_value_difference
+        {
+            let difference = [current_value]
+                .iter()
+                .chain(pending_signals)
+                .chain(pending_waits)
+                .map(|&other| value.max(other) - value.min(other))
+                .max()
+                .unwrap();
+
+            if difference > max {
+                return Err(
+                    SemaphoreError::MaxTimelineSemaphoreValueDifferenceExceeded { difference, max },
+                );
+            }
+        }
+
+        // A signal operation that was submitted to a queue outside of Vulkano's tracking, or to
+        // an imported payload, can't be checked here.
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn signal_unchecked(&self, value: u64) -> Result<(), VulkanError> {
+        let signal_info_vk = ash::vk::SemaphoreSignalInfo {
+            semaphore: self.handle,
+            value,
+            ..Default::default()
+        };
+
+        let fns = self.device.fns();
+
+        if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.signal_semaphore)(self.device.handle(), &signal_info_vk)
+        } else {
+            (fns.khr_timeline_semaphore.signal_semaphore_khr)(self.device.handle(), &signal_info_vk)
+        }
+        .result()
+        .map_err(VulkanError::from)?;
+
+        Ok(())
+    }
+
+    /// Waits until the counter of a timeline semaphore has reached at least `value`, or the
+    /// timeout has elapsed.
+    ///
+    /// Returns `Ok` if the counter has reached `value`.
+    /// If `timeout` is `None`, then this will potentially block forever.
+    #[inline]
+    pub fn wait(&self, value: u64, timeout: Option<Duration>) -> Result<(), SemaphoreError> {
+        self.validate_wait()?;
+
+        unsafe { self.wait_unchecked(value, timeout) }
+    }
+
+    fn validate_wait(&self) -> Result<(), SemaphoreError> {
+        // VUID-VkSemaphoreWaitInfo-pSemaphores-03256
+        if self.semaphore_type != SemaphoreType::Timeline {
+            return Err(SemaphoreError::NotTimeline);
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn wait_unchecked(
+        &self,
+        value: u64,
+        timeout: Option<Duration>,
+    ) -> Result<(), SemaphoreError> {
+        let wait_info_vk = ash::vk::SemaphoreWaitInfo {
+            flags: ash::vk::SemaphoreWaitFlags::empty(),
+            semaphore_count: 1,
+            p_semaphores: &self.handle,
+            p_values: &value,
+            ..Default::default()
+        };
+
+        let timeout_ns = timeout.map_or(u64::MAX, |timeout| {
+            timeout
+                .as_secs()
+                .saturating_mul(1_000_000_000)
+                .saturating_add(timeout.subsec_nanos() as u64)
+        });
+
+        let fns = self.device.fns();
+        let result = if self.device.api_version() >= Version::V1_2 {
+            (fns.v1_2.wait_semaphores)(self.device.handle(), &wait_info_vk, timeout_ns)
+        } else {
+            (fns.khr_timeline_semaphore.wait_semaphores_khr)(
+                self.device.handle(),
+                &wait_info_vk,
+                timeout_ns,
+            )
+        };
+
+        match result {
+            ash::vk::Result::SUCCESS => Ok(()),
+            ash::vk::Result::TIMEOUT => Err(SemaphoreError::Timeout),
+            err => Err(VulkanError::from(err).into()),
         }
     }
 
@@ -1021,11 +1273,9 @@ unsafe impl DeviceOwned for Semaphore {
 
 crate::impl_id_counter!(Semaphore);
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub(crate) struct SemaphoreState {
-    is_signaled: bool,
-    pending_signal: Option<SignalType>,
-    pending_wait: Option<Weak<Queue>>,
+    payload: PayloadState,
 
     reference_exported: bool,
     exported_handle_types: ExternalSemaphoreHandleTypes,
@@ -1034,34 +1284,78 @@ pub(crate) struct SemaphoreState {
 }
 
 impl SemaphoreState {
-    /// If the semaphore does not have a pending operation and has no external references,
-    /// returns the current status.
+    fn new(semaphore_type: SemaphoreType) -> Self {
+        Self {
+            payload: match semaphore_type {
+                SemaphoreType::Binary => PayloadState::Binary(Default::default()),
+                SemaphoreType::Timeline => PayloadState::Timeline(Default::default()),
+            },
+            reference_exported: false,
+            exported_handle_types: ExternalSemaphoreHandleTypes::empty(),
+            current_import: None,
+            permanent_import: None,
+        }
+    }
+
+    /// If the semaphore is a binary semaphore, does not have a pending operation and has no
+    /// external references, returns the current status.
     #[inline]
     fn is_signaled(&self) -> Option<bool> {
-        // If any of these is true, we can't be certain of the status.
-        if self.pending_signal.is_some()
-            || self.pending_wait.is_some()
-            || self.has_external_reference()
-        {
-            None
-        } else {
-            Some(self.is_signaled)
+        match &self.payload {
+            PayloadState::Binary(state) => {
+                // If any of these is true, we can't be certain of the status.
+                if state.pending_signal.is_some()
+                    || state.pending_wait.is_some()
+                    || self.has_external_reference()
+                {
+                    None
+                } else {
+                    Some(state.is_signaled)
+                }
+            }
+            PayloadState::Timeline(_) => None,
         }
     }
 
     #[inline]
     fn is_signal_pending(&self) -> bool {
-        self.pending_signal.is_some()
+        match &self.payload {
+            PayloadState::Binary(state) => state.pending_signal.is_some(),
+            PayloadState::Timeline(state) => !state.pending_signals.is_empty(),
+        }
     }
 
     #[inline]
     fn is_wait_pending(&self) -> bool {
-        self.pending_wait.is_some()
+        match &self.payload {
+            PayloadState::Binary(state) => state.pending_wait.is_some(),
+            PayloadState::Timeline(state) => !state.pending_waits.is_empty(),
+        }
     }
 
     #[inline]
     fn is_in_queue(&self) -> bool {
-        matches!(self.pending_signal, Some(SignalType::Queue(_))) || self.pending_wait.is_some()
+        match &self.payload {
+            PayloadState::Binary(state) => {
+                matches!(state.pending_signal, Some(SignalType::Queue(_)))
+                    || state.pending_wait.is_some()
+            }
+            PayloadState::Timeline(state) => {
+                !state.pending_signals.is_empty() || !state.pending_waits.is_empty()
+            }
+        }
+    }
+
+    /// If the semaphore is a timeline semaphore, returns the values of the signal and wait
+    /// operations that are pending in a queue.
+    #[inline]
+    fn pending_values(&self) -> Option<(&[u64], &[u64])> {
+        match &self.payload {
+            PayloadState::Binary(_) => None,
+            PayloadState::Timeline(state) => {
+                Some((&state.pending_signals[..], &state.pending_waits[..]))
+            }
+        }
     }
 
     /// Returns whether there are any potential external references to the semaphore payload.
@@ -1077,29 +1371,49 @@ impl SemaphoreState {
         self.exported_handle_types.intersects(handle_type.into())
     }
 
+    /// `value` is ignored for binary semaphores.
     #[inline]
-    pub(crate) unsafe fn add_queue_signal(&mut self, queue: &Arc<Queue>) {
-        self.pending_signal = Some(SignalType::Queue(Arc::downgrade(queue)));
+    pub(crate) unsafe fn add_queue_signal(&mut self, queue: &Arc<Queue>, value: u64) {
+        match &mut self.payload {
+            PayloadState::Binary(state) => {
+                state.pending_signal = Some(SignalType::Queue(Arc::downgrade(queue)));
+            }
+            PayloadState::Timeline(state) => state.pending_signals.push(value),
+        }
     }
 
+    /// `value` is ignored for binary semaphores.
     #[inline]
-    pub(crate) unsafe fn add_queue_wait(&mut self, queue: &Arc<Queue>) {
-        self.pending_wait = Some(Arc::downgrade(queue));
+    pub(crate) unsafe fn add_queue_wait(&mut self, queue: &Arc<Queue>, value: u64) {
+        match &mut self.payload {
+            PayloadState::Binary(state) => state.pending_wait = Some(Arc::downgrade(queue)),
+            PayloadState::Timeline(state) => state.pending_waits.push(value),
+        }
     }
 
     /// Called when a queue is unlocking resources.
     #[inline]
-    pub(crate) unsafe fn set_signal_finished(&mut self) {
-        self.pending_signal = None;
-        self.is_signaled = true;
+    pub(crate) unsafe fn set_signal_finished(&mut self, value: u64) {
+        match &mut self.payload {
+            PayloadState::Binary(state) => {
+                state.pending_signal = None;
+                state.is_signaled = true;
+            }
+            PayloadState::Timeline(state) => remove_value(&mut state.pending_signals, value),
+        }
     }
 
     /// Called when a queue is unlocking resources.
     #[inline]
-    pub(crate) unsafe fn set_wait_finished(&mut self) {
-        self.pending_wait = None;
-        self.current_import = self.permanent_import.map(Into::into);
-        self.is_signaled = false;
+    pub(crate) unsafe fn set_wait_finished(&mut self, value: u64) {
+        match &mut self.payload {
+            PayloadState::Binary(state) => {
+                state.pending_wait = None;
+                state.is_signaled = false;
+                self.current_import = self.permanent_import.map(Into::into);
+            }
+            PayloadState::Timeline(state) => remove_value(&mut state.pending_waits, value),
+        }
     }
 
     #[allow(dead_code)]
@@ -1109,7 +1423,10 @@ impl SemaphoreState {
 
         if handle_type.has_copy_transference() {
             self.current_import = self.permanent_import.map(Into::into);
-            self.is_signaled = false;
+
+            if let PayloadState::Binary(state) = &mut self.payload {
+                state.is_signaled = false;
+            }
         } else {
             self.reference_exported = true;
         }
@@ -1127,11 +1444,45 @@ impl SemaphoreState {
 
     #[inline]
     pub(crate) unsafe fn swapchain_acquire(&mut self) {
-        self.pending_signal = Some(SignalType::SwapchainAcquire);
+        // Only binary semaphores can be signaled by an acquire operation.
+        if let PayloadState::Binary(state) = &mut self.payload {
+            state.pending_signal = Some(SignalType::SwapchainAcquire);
+        }
+
         self.current_import = Some(ImportType::SwapchainAcquire);
     }
 }
 
+#[derive(Debug)]
+enum PayloadState {
+    Binary(BinaryState),
+    Timeline(TimelineState),
+}
+
+/// The state of the payload of a binary semaphore, which is either signaled or unsignaled.
+#[derive(Debug, Default)]
+struct BinaryState {
+    is_signaled: bool,
+    pending_signal: Option<SignalType>,
+    pending_wait: Option<Weak<Queue>>,
+}
+
+/// The state of the payload of a timeline semaphore, which is a counter. Unlike a binary
+/// semaphore, any number of signal and wait operations can be pending at the same time.
+#[derive(Debug, Default)]
+struct TimelineState {
+    // The values of the signal operations that are pending in a queue.
+    pending_signals: SmallVec<[u64; 4]>,
+    // The values of the wait operations that are pending in a queue.
+    pending_waits: SmallVec<[u64; 4]>,
+}
+
+fn remove_value(values: &mut SmallVec<[u64; 4]>, value: u64) {
+    if let Some(index) = values.iter().position(|&v| v == value) {
+        values.swap_remove(index);
+    }
+}
+
 #[derive(Clone, Debug)]
 enum SignalType {
     Queue(Weak<Queue>),
@@ -1154,6 +1505,22 @@ impl From<ExternalSemaphoreHandleType> for ImportType {
 /// Parameters to create a new `Semaphore`.
 #[derive(Clone, Debug)]
 pub struct SemaphoreCreateInfo {
+    /// The type of semaphore to create.
+    ///
+    /// If set to [`SemaphoreType::Timeline`], the
+    /// [`timeline_semaphore`](crate::device::Features::timeline_semaphore) feature must be
+    /// enabled on the device.
+    ///
+    /// The default value is [`SemaphoreType::Binary`].
+    pub semaphore_type: SemaphoreType,
+
+    /// The initial value of the counter of a timeline semaphore.
+    ///
+    /// If `semaphore_type` is [`SemaphoreType::Binary`], this must be 0.
+    ///
+    /// The default value is 0.
+    pub initial_value: u64,
+
     /// The handle types that can be exported from the semaphore.
     ///
     /// The default value is [`ExternalSemaphoreHandleTypes::empty()`].
@@ -1166,12 +1533,30 @@ impl Default for SemaphoreCreateInfo {
     #[inline]
     fn default() -> Self {
         Self {
+            semaphore_type: SemaphoreType::Binary,
+            initial_value: 0,
             export_handle_types: ExternalSemaphoreHandleTypes::empty(),
             _ne: crate::NonExhaustive(()),
         }
     }
 }
 
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The type that a semaphore can have.
+    SemaphoreType = SemaphoreType(i32);
+
+    /// A semaphore that can only have a signaled or unsignaled state. Each signal operation must
+    /// be followed by exactly one wait operation.
+    Binary = BINARY,
+
+    /// A semaphore that has a 64-bit counter value, which only increases. Wait operations wait
+    /// for the counter to reach at least a given value, and signal operations set it to a new,
+    /// higher value.
+    Timeline = TIMELINE,
+}
+
 vulkan_bitflags_enum! {
     #[non_exhaustive]
 
@@ -1440,6 +1825,9 @@ pub enum SemaphoreError {
     /// semaphore.
     HandleTypeNotEnabled,
 
+    /// The semaphore type is `Binary`, but the initial value is not 0.
+    BinaryInitialValueNotZero,
+
     /// Exporting is not supported for the provided handle type.
     HandleTypeNotExportable {
         handle_type: ExternalSemaphoreHandleType,
@@ -1460,8 +1848,27 @@ pub enum SemaphoreError {
     /// The semaphore is currently in use by a queue.
     InQueue,
 
+    /// The difference between the provided value and the current value of the semaphore exceeds
+    /// the [`max_timeline_semaphore_value_difference`] limit.
+    ///
+    /// [`max_timeline_semaphore_value_difference`]: crate::device::Properties::max_timeline_semaphore_value_difference
+    MaxTimelineSemaphoreValueDifferenceExceeded { difference: u64, max: u64 },
+
+    /// The operation requires a timeline semaphore, but the semaphore is a binary semaphore.
+    NotTimeline,
+
     /// A queue is currently waiting on the semaphore.
     QueueIsWaiting,
+
+    /// The value to signal is not greater than the current value of the semaphore.
+    SignalValueNotGreater { value: u64, current_value: u64 },
+
+    /// The value to signal is not less than the value of a signal operation on the semaphore that
+    /// is pending in a queue.
+    SignalValueNotLessThanPending { value: u64, pending_value: u64 },
+
+    /// The wait operation timed out.
+    Timeout,
 }
 
 impl Error for SemaphoreError {
@@ -1518,6 +1925,10 @@ impl Display for SemaphoreError {
                 "the provided export handle type was not set in `export_handle_types` when \
                 creating the semaphore",
             ),
+            Self::BinaryInitialValueNotZero => write!(
+                f,
+                "the semaphore type is `Binary`, but the initial value is not 0",
+            ),
             Self::HandleTypeNotExportable { handle_type } => write!(
                 f,
                 "exporting is not supported for handles of type {:?}",
@@ -1538,7 +1949,37 @@ impl Display for SemaphoreError {
                 "the semaphore currently has a temporary import for a swapchain acquire operation",
             ),
             Self::InQueue => write!(f, "the semaphore is currently in use by a queue"),
+            Self::MaxTimelineSemaphoreValueDifferenceExceeded { difference, max } => write!(
+                f,
+                "the difference between the provided value and the current value of the \
+                semaphore ({}) exceeds the `max_timeline_semaphore_value_difference` limit ({})",
+                difference, max,
+            ),
+            Self::NotTimeline => write!(
+                f,
+                "the operation requires a timeline semaphore, but the semaphore is a binary \
+                semaphore",
+            ),
             Self::QueueIsWaiting => write!(f, "a queue is currently waiting on the semaphore"),
+            Self::SignalValueNotGreater {
+                value,
+                current_value,
+            } => write!(
+                f,
+                "the value to signal ({}) is not greater than the current value of the semaphore \
+                ({})",
+                value, current_value,
+            ),
+            Self::SignalValueNotLessThanPending {
+                value,
+                pending_value,
+            } => write!(
+                f,
+                "the value to signal ({}) is not less than the value of a pending signal \
+                operation on the semaphore ({})",
+                value, pending_value,
+            ),
+            Self::Timeout => write!(f, "the wait operation timed out"),
         }
     }
 }
@@ -1575,12 +2016,14 @@ mod tests {
     use crate::{
         device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo},
         instance::{Instance, InstanceCreateInfo, InstanceExtensions},
-        sync::semaphore::{
-            ExternalSemaphoreHandleType, ExternalSemaphoreHandleTypes, SemaphoreCreateInfo,
-        },
+        sync::semaphore::{ExternalSemaphoreHandleType, ExternalSemaphoreHandleTypes},
         VulkanLibrary,
     };
-    use crate::{sync::semaphore::Semaphore, VulkanObject};
+    use crate::{
+        sync::semaphore::{Semaphore, SemaphoreCreateInfo, SemaphoreError, SemaphoreType},
+        VulkanObject,
+    };
+    use std::time::Duration;
 
     #[test]
     fn semaphore_create() {
@@ -1588,6 +2031,104 @@ mod tests {
         let _ = Semaphore::new(device, Default::default());
     }
 
+    #[test]
+    fn semaphore_binary_initial_value() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        assert!(matches!(
+            Semaphore::new(
+                device,
+                SemaphoreCreateInfo {
+                    initial_value: 1,
+                    ..Default::default()
+                },
+            ),
+            Err(SemaphoreError::BinaryInitialValueNotZero),
+        ));
+    }
+
+    #[test]
+    fn semaphore_binary_not_timeline() {
+        let (device, _) = gfx_dev_and_queue!();
+        let sem = Semaphore::new(device, Default::default()).unwrap();
+
+        assert!(matches!(
+            sem.counter_value(),
+            Err(SemaphoreError::NotTimeline),
+        ));
+        assert!(matches!(
+            sem.wait(1, Some(Duration::from_secs(0))),
+            Err(SemaphoreError::NotTimeline),
+        ));
+    }
+
+    #[test]
+    fn semaphore_timeline_host() {
+        let (device, _) = gfx_dev_and_queue!(timeline_semaphore);
+        let sem = Semaphore::new(
+            device,
+            SemaphoreCreateInfo {
+                semaphore_type: SemaphoreType::Timeline,
+                initial_value: 5,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(sem.counter_value().unwrap(), 5);
+
+        assert!(matches!(
+            sem.wait(6, Some(Duration::from_secs(0))),
+            Err(SemaphoreError::Timeout),
+        ));
+        assert!(matches!(
+            unsafe { sem.signal(5) },
+            Err(SemaphoreError::SignalValueNotGreater { .. }),
+        ));
+
+        unsafe { sem.signal(7).unwrap() };
+        assert_eq!(sem.counter_value().unwrap(), 7);
+        sem.wait(6, Some(Duration::from_secs(0))).unwrap();
+    }
+
+    #[test]
+    fn semaphore_timeline_pending_values() {
+        let (device, queue) = gfx_dev_and_queue!(timeline_semaphore);
+        let sem = Semaphore::new(
+            device,
+            SemaphoreCreateInfo {
+                semaphore_type: SemaphoreType::Timeline,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        unsafe {
+            let mut state = sem.state();
+            state.add_queue_signal(&queue, 5);
+            state.add_queue_wait(&queue, 3);
+            assert!(state.is_in_queue());
+            assert_eq!(state.pending_values(), Some((&[5][..], &[3][..])));
+        }
+
+        assert!(matches!(
+            unsafe { sem.signal(6) },
+            Err(SemaphoreError::SignalValueNotLessThanPending {
+                pending_value: 5,
+                ..
+            }),
+        ));
+
+        unsafe {
+            let mut state = sem.state();
+            state.set_signal_finished(5);
+            state.set_wait_finished(3);
+            assert!(!state.is_in_queue());
+        }
+
+        unsafe { sem.signal(6).unwrap() };
+        assert_eq!(sem.counter_value().unwrap(), 6);
+    }
+
     #[test]
     fn semaphore_pool() {
         let (device, _) = gfx_dev_and_queue!();
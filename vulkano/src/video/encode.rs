@@ -0,0 +1,744 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    null_ptr_if_empty, std_video, VideoCodecOperation, VideoPictureResourceInfo,
+    VideoReferenceSlotInfo,
+};
+use crate::{buffer::BufferAccess, macros::vulkan_enum, DeviceSize};
+use smallvec::SmallVec;
+use std::{ptr, sync::Arc};
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The rate control mode of an encode video session.
+    VideoEncodeRateControlMode = VideoEncodeRateControlModeFlagsKHR(u32);
+
+    /// No rate control. The bitrate is determined by the quantization parameters that are given
+    /// in the codec-specific picture information.
+    None = NONE,
+
+    /// Constant bitrate.
+    Cbr = CBR,
+
+    /// Variable bitrate.
+    Vbr = VBR,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The structure of the group of pictures of an H.264 stream, as a hint for rate control.
+    VideoEncodeH264RateControlStructure = VideoEncodeH264RateControlStructureEXT(i32);
+
+    /// The structure is not known.
+    Unknown = UNKNOWN,
+
+    /// All pictures are at the same temporal layer.
+    Flat = FLAT,
+
+    /// The pictures are arranged in a dyadic hierarchy of temporal layers.
+    Dyadic = DYADIC,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The structure of the group of pictures of an H.265 stream, as a hint for rate control.
+    VideoEncodeH265RateControlStructure = VideoEncodeH265RateControlStructureEXT(i32);
+
+    /// The structure is not known.
+    Unknown = UNKNOWN,
+
+    /// All pictures are at the same temporal sub-layer.
+    Flat = FLAT,
+
+    /// The pictures are arranged in a dyadic hierarchy of temporal sub-layers.
+    Dyadic = DYADIC,
+}
+
+/// The rate control state of an encode video session, as set by
+/// [`AutoCommandBufferBuilder::control_video_coding`].
+///
+/// [`AutoCommandBufferBuilder::control_video_coding`]: crate::command_buffer::AutoCommandBufferBuilder::control_video_coding
+#[derive(Clone, Debug)]
+pub struct VideoEncodeRateControlInfo {
+    /// The rate control mode.
+    ///
+    /// If this is not [`VideoEncodeRateControlMode::None`], it must be supported as given by
+    /// [`VideoCapabilities::encode_rate_control_modes`].
+    ///
+    /// The default value is [`VideoEncodeRateControlMode::None`].
+    ///
+    /// [`VideoCapabilities::encode_rate_control_modes`]: super::VideoCapabilities::encode_rate_control_modes
+    pub rate_control_mode: VideoEncodeRateControlMode,
+
+    /// The rate control layers.
+    ///
+    /// This must be empty if `rate_control_mode` is [`VideoEncodeRateControlMode::None`], and
+    /// must otherwise not contain more elements than
+    /// [`VideoCapabilities::encode_rate_control_layer_count`].
+    ///
+    /// The default value is empty.
+    ///
+    /// [`VideoCapabilities::encode_rate_control_layer_count`]: super::VideoCapabilities::encode_rate_control_layer_count
+    pub layers: Vec<VideoEncodeRateControlLayerInfo>,
+
+    /// The codec-specific rate control state.
+    ///
+    /// If `Some`, this must match the codec of the video session.
+    ///
+    /// The default value is `None`.
+    pub codec_rate_control_info: Option<VideoEncodeCodecRateControlInfo>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for VideoEncodeRateControlInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            rate_control_mode: VideoEncodeRateControlMode::None,
+            layers: Vec::new(),
+            codec_rate_control_info: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A rate control layer of an encode video session.
+#[derive(Clone, Debug)]
+pub struct VideoEncodeRateControlLayerInfo {
+    /// The target average bitrate, in bits per second.
+    ///
+    /// The default value is `0`.
+    pub average_bitrate: u32,
+
+    /// The peak bitrate, in bits per second. For [`VideoEncodeRateControlMode::Cbr`], this must
+    /// be equal to `average_bitrate`.
+    ///
+    /// The default value is `0`.
+    pub max_bitrate: u32,
+
+    /// The frame rate, as a numerator and a denominator.
+    ///
+    /// The default value is `[0, 1]`.
+    pub frame_rate: [u32; 2],
+
+    /// The size of the leaky bucket model of the virtual buffer, in milliseconds.
+    ///
+    /// The default value is `0`.
+    pub virtual_buffer_size_in_ms: u32,
+
+    /// The initial occupancy of the virtual buffer, in milliseconds.
+    ///
+    /// The default value is `0`.
+    pub initial_virtual_buffer_size_in_ms: u32,
+
+    /// The codec-specific parameters of the layer.
+    ///
+    /// If `Some`, this must match the codec of the video session.
+    ///
+    /// The default value is `None`.
+    pub codec_layer_info: Option<VideoEncodeCodecRateControlLayerInfo>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for VideoEncodeRateControlLayerInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            average_bitrate: 0,
+            max_bitrate: 0,
+            frame_rate: [0, 1],
+            virtual_buffer_size_in_ms: 0,
+            initial_virtual_buffer_size_in_ms: 0,
+            codec_layer_info: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The codec-specific rate control state of an encode video session.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum VideoEncodeCodecRateControlInfo {
+    /// H.264 rate control state.
+    H264 {
+        /// The number of pictures in a group of pictures, or `0` if not known.
+        gop_frame_count: u32,
+
+        /// The distance between IDR pictures, or `0` if not known.
+        idr_period: u32,
+
+        /// The number of consecutive B pictures between I and P pictures.
+        consecutive_b_frame_count: u32,
+
+        /// The structure of the group of pictures.
+        rate_control_structure: VideoEncodeH264RateControlStructure,
+
+        /// The number of temporal layers.
+        temporal_layer_count: u32,
+    },
+
+    /// H.265 rate control state.
+    H265 {
+        /// The number of pictures in a group of pictures, or `0` if not known.
+        gop_frame_count: u32,
+
+        /// The distance between IDR pictures, or `0` if not known.
+        idr_period: u32,
+
+        /// The number of consecutive B pictures between I and P pictures.
+        consecutive_b_frame_count: u32,
+
+        /// The structure of the group of pictures.
+        rate_control_structure: VideoEncodeH265RateControlStructure,
+
+        /// The number of temporal sub-layers.
+        sub_layer_count: u32,
+    },
+}
+
+impl VideoEncodeCodecRateControlInfo {
+    /// Returns the video coding operation that the rate control state is for.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        match self {
+            Self::H264 { .. } => VideoCodecOperation::EncodeH264,
+            Self::H265 { .. } => VideoCodecOperation::EncodeH265,
+        }
+    }
+}
+
+/// The codec-specific parameters of a rate control layer.
+///
+/// The quantization parameters and frame sizes are given for I, P and B pictures, in that
+/// order. A value of `None` leaves the choice to the implementation.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum VideoEncodeCodecRateControlLayerInfo {
+    /// H.264 rate control layer parameters.
+    H264 {
+        /// The ID of the temporal layer.
+        temporal_layer_id: u32,
+
+        /// The initial quantization parameters.
+        initial_rc_qp: Option<[i32; 3]>,
+
+        /// The minimum quantization parameters.
+        min_qp: Option<[i32; 3]>,
+
+        /// The maximum quantization parameters.
+        max_qp: Option<[i32; 3]>,
+
+        /// The maximum sizes of the encoded pictures, in bytes.
+        max_frame_size: Option<[u32; 3]>,
+    },
+
+    /// H.265 rate control layer parameters.
+    H265 {
+        /// The ID of the temporal sub-layer.
+        temporal_id: u32,
+
+        /// The initial quantization parameters.
+        initial_rc_qp: Option<[i32; 3]>,
+
+        /// The minimum quantization parameters.
+        min_qp: Option<[i32; 3]>,
+
+        /// The maximum quantization parameters.
+        max_qp: Option<[i32; 3]>,
+
+        /// The maximum sizes of the encoded pictures, in bytes.
+        max_frame_size: Option<[u32; 3]>,
+    },
+}
+
+impl VideoEncodeCodecRateControlLayerInfo {
+    /// Returns the video coding operation that the layer parameters are for.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        match self {
+            Self::H264 { .. } => VideoCodecOperation::EncodeH264,
+            Self::H265 { .. } => VideoCodecOperation::EncodeH265,
+        }
+    }
+}
+
+impl VideoEncodeRateControlInfo {
+    pub(crate) fn to_vulkan(&self) -> Box<VideoEncodeRateControlInfoVk> {
+        let &Self {
+            rate_control_mode,
+            ref layers,
+            codec_rate_control_info,
+            _ne: _,
+        } = self;
+
+        // The structures are boxed, so that the pointers in the chain stay valid when the result
+        // is moved.
+        let mut rate_control_info_vk = Box::new(VideoEncodeRateControlInfoVk {
+            rate_control_info: ash::vk::VideoEncodeRateControlInfoKHR {
+                flags: ash::vk::VideoEncodeRateControlFlagsKHR::empty(),
+                rate_control_mode: rate_control_mode.into(),
+                layer_count: layers.len() as u8,
+                ..Default::default()
+            },
+            layers_vk: SmallVec::new(),
+            h264_layers_vk: SmallVec::new(),
+            h265_layers_vk: SmallVec::new(),
+            h264_rate_control_info: Default::default(),
+            h265_rate_control_info: Default::default(),
+        });
+        let VideoEncodeRateControlInfoVk {
+            rate_control_info,
+            layers_vk,
+            h264_layers_vk,
+            h265_layers_vk,
+            h264_rate_control_info,
+            h265_rate_control_info,
+        } = &mut *rate_control_info_vk;
+
+        // The codec-specific layer structures must be fully collected before pointers to them are
+        // taken.
+        for layer in layers {
+            h264_layers_vk.push(match layer.codec_layer_info {
+                Some(VideoEncodeCodecRateControlLayerInfo::H264 {
+                    temporal_layer_id,
+                    initial_rc_qp,
+                    min_qp,
+                    max_qp,
+                    max_frame_size,
+                }) => Some(ash::vk::VideoEncodeH264RateControlLayerInfoEXT {
+                    temporal_layer_id: temporal_layer_id as u8,
+                    use_initial_rc_qp: initial_rc_qp.is_some() as ash::vk::Bool32,
+                    initial_rc_qp: h264_qp(initial_rc_qp),
+                    use_min_qp: min_qp.is_some() as ash::vk::Bool32,
+                    min_qp: h264_qp(min_qp),
+                    use_max_qp: max_qp.is_some() as ash::vk::Bool32,
+                    max_qp: h264_qp(max_qp),
+                    use_max_frame_size: max_frame_size.is_some() as ash::vk::Bool32,
+                    max_frame_size: h264_frame_size(max_frame_size),
+                    ..Default::default()
+                }),
+                _ => None,
+            });
+            h265_layers_vk.push(match layer.codec_layer_info {
+                Some(VideoEncodeCodecRateControlLayerInfo::H265 {
+                    temporal_id,
+                    initial_rc_qp,
+                    min_qp,
+                    max_qp,
+                    max_frame_size,
+                }) => Some(ash::vk::VideoEncodeH265RateControlLayerInfoEXT {
+                    temporal_id: temporal_id as u8,
+                    use_initial_rc_qp: initial_rc_qp.is_some() as ash::vk::Bool32,
+                    initial_rc_qp: h265_qp(initial_rc_qp),
+                    use_min_qp: min_qp.is_some() as ash::vk::Bool32,
+                    min_qp: h265_qp(min_qp),
+                    use_max_qp: max_qp.is_some() as ash::vk::Bool32,
+                    max_qp: h265_qp(max_qp),
+                    use_max_frame_size: max_frame_size.is_some() as ash::vk::Bool32,
+                    max_frame_size: h265_frame_size(max_frame_size),
+                    ..Default::default()
+                }),
+                _ => None,
+            });
+        }
+
+        for ((layer, h264_layer_vk), h265_layer_vk) in layers
+            .iter()
+            .zip(h264_layers_vk.iter())
+            .zip(h265_layers_vk.iter())
+        {
+            let &VideoEncodeRateControlLayerInfo {
+                average_bitrate,
+                max_bitrate,
+                frame_rate,
+                virtual_buffer_size_in_ms,
+                initial_virtual_buffer_size_in_ms,
+                codec_layer_info: _,
+                _ne: _,
+            } = layer;
+
+            layers_vk.push(ash::vk::VideoEncodeRateControlLayerInfoKHR {
+                p_next: match (h264_layer_vk, h265_layer_vk) {
+                    (Some(next), _) => next as *const _ as *const _,
+                    (_, Some(next)) => next as *const _ as *const _,
+                    (None, None) => ptr::null(),
+                },
+                average_bitrate,
+                max_bitrate,
+                frame_rate_numerator: frame_rate[0],
+                frame_rate_denominator: frame_rate[1],
+                virtual_buffer_size_in_ms,
+                initial_virtual_buffer_size_in_ms,
+                ..Default::default()
+            });
+        }
+
+        rate_control_info.p_layer_configs = null_ptr_if_empty(layers_vk);
+
+        match codec_rate_control_info {
+            Some(VideoEncodeCodecRateControlInfo::H264 {
+                gop_frame_count,
+                idr_period,
+                consecutive_b_frame_count,
+                rate_control_structure,
+                temporal_layer_count,
+            }) => {
+                *h264_rate_control_info = ash::vk::VideoEncodeH264RateControlInfoEXT {
+                    gop_frame_count,
+                    idr_period,
+                    consecutive_b_frame_count,
+                    rate_control_structure: rate_control_structure.into(),
+                    temporal_layer_count: temporal_layer_count as u8,
+                    ..Default::default()
+                };
+
+                h264_rate_control_info.p_next = rate_control_info.p_next;
+                rate_control_info.p_next = h264_rate_control_info as *const _ as *const _;
+            }
+            Some(VideoEncodeCodecRateControlInfo::H265 {
+                gop_frame_count,
+                idr_period,
+                consecutive_b_frame_count,
+                rate_control_structure,
+                sub_layer_count,
+            }) => {
+                *h265_rate_control_info = ash::vk::VideoEncodeH265RateControlInfoEXT {
+                    gop_frame_count,
+                    idr_period,
+                    consecutive_b_frame_count,
+                    rate_control_structure: rate_control_structure.into(),
+                    sub_layer_count: sub_layer_count as u8,
+                    ..Default::default()
+                };
+
+                h265_rate_control_info.p_next = rate_control_info.p_next;
+                rate_control_info.p_next = h265_rate_control_info as *const _ as *const _;
+            }
+            None => (),
+        }
+
+        rate_control_info_vk
+    }
+}
+
+pub(crate) struct VideoEncodeRateControlInfoVk {
+    pub(crate) rate_control_info: ash::vk::VideoEncodeRateControlInfoKHR,
+    layers_vk: SmallVec<[ash::vk::VideoEncodeRateControlLayerInfoKHR; 4]>,
+    h264_layers_vk: SmallVec<[Option<ash::vk::VideoEncodeH264RateControlLayerInfoEXT>; 4]>,
+    h265_layers_vk: SmallVec<[Option<ash::vk::VideoEncodeH265RateControlLayerInfoEXT>; 4]>,
+    h264_rate_control_info: ash::vk::VideoEncodeH264RateControlInfoEXT,
+    h265_rate_control_info: ash::vk::VideoEncodeH265RateControlInfoEXT,
+}
+
+fn h264_qp(qp: Option<[i32; 3]>) -> ash::vk::VideoEncodeH264QpEXT {
+    let [qp_i, qp_p, qp_b] = qp.unwrap_or_default();
+
+    ash::vk::VideoEncodeH264QpEXT { qp_i, qp_p, qp_b }
+}
+
+fn h265_qp(qp: Option<[i32; 3]>) -> ash::vk::VideoEncodeH265QpEXT {
+    let [qp_i, qp_p, qp_b] = qp.unwrap_or_default();
+
+    ash::vk::VideoEncodeH265QpEXT { qp_i, qp_p, qp_b }
+}
+
+fn h264_frame_size(frame_size: Option<[u32; 3]>) -> ash::vk::VideoEncodeH264FrameSizeEXT {
+    let [frame_i_size, frame_p_size, frame_b_size] = frame_size.unwrap_or_default();
+
+    ash::vk::VideoEncodeH264FrameSizeEXT {
+        frame_i_size,
+        frame_p_size,
+        frame_b_size,
+    }
+}
+
+fn h265_frame_size(frame_size: Option<[u32; 3]>) -> ash::vk::VideoEncodeH265FrameSizeEXT {
+    let [frame_i_size, frame_p_size, frame_b_size] = frame_size.unwrap_or_default();
+
+    ash::vk::VideoEncodeH265FrameSizeEXT {
+        frame_i_size,
+        frame_p_size,
+        frame_b_size,
+    }
+}
+
+/// Parameters to encode a picture, with [`AutoCommandBufferBuilder::encode_video`].
+///
+/// [`AutoCommandBufferBuilder::encode_video`]: crate::command_buffer::AutoCommandBufferBuilder::encode_video
+#[derive(Clone, Debug)]
+pub struct VideoEncodeInfo {
+    /// The buffer that the bitstream of the encoded picture is written to.
+    ///
+    /// The buffer must have been created with the [`VIDEO_ENCODE_DST`] usage.
+    ///
+    /// There is no default value.
+    ///
+    /// [`VIDEO_ENCODE_DST`]: crate::buffer::BufferUsage::VIDEO_ENCODE_DST
+    pub dst_buffer: Arc<dyn BufferAccess>,
+
+    /// The offset within `dst_buffer` that the bitstream is written to.
+    ///
+    /// This must be a multiple of the
+    /// [`min_bitstream_buffer_offset_alignment`](super::VideoCapabilities::min_bitstream_buffer_offset_alignment)
+    /// of the video profile.
+    ///
+    /// The default value is `0`.
+    pub dst_buffer_offset: DeviceSize,
+
+    /// The maximum size of the bitstream that can be written to `dst_buffer`.
+    ///
+    /// This must be a multiple of the
+    /// [`min_bitstream_buffer_size_alignment`](super::VideoCapabilities::min_bitstream_buffer_size_alignment)
+    /// of the video profile.
+    ///
+    /// The default value is the size of `dst_buffer`.
+    pub dst_buffer_max_range: DeviceSize,
+
+    /// The picture resource that the picture to encode is read from.
+    ///
+    /// The image must have been created with the [`VIDEO_ENCODE_SRC`] usage.
+    ///
+    /// There is no default value.
+    ///
+    /// [`VIDEO_ENCODE_SRC`]: crate::image::ImageUsage::VIDEO_ENCODE_SRC
+    pub src_picture_resource: VideoPictureResourceInfo,
+
+    /// The DPB slot that the reconstructed picture is written to, if it is going to be used as a
+    /// reference picture by later encode operations.
+    ///
+    /// The slot must have been given to `begin_video_coding`, and must have a slot index and a
+    /// picture resource.
+    ///
+    /// The default value is `None`.
+    pub setup_reference_slot: Option<VideoReferenceSlotInfo>,
+
+    /// The DPB slots that hold the reference pictures that the picture is predicted from.
+    ///
+    /// The slots must have been given to `begin_video_coding`, and must have a slot index and a
+    /// picture resource.
+    ///
+    /// The default value is empty.
+    pub reference_slots: Vec<VideoReferenceSlotInfo>,
+
+    /// The quality level to encode with.
+    ///
+    /// This must be less than
+    /// [`encode_quality_level_count`](super::VideoCapabilities::encode_quality_level_count).
+    ///
+    /// The default value is `0`.
+    pub quality_level: u32,
+
+    /// The number of bytes that the application has encoded itself and has inserted before the
+    /// bitstream of the picture, so that the rate control can account for them.
+    ///
+    /// If this is not `0`, then the
+    /// [`PRECEDING_EXTERNALLY_ENCODED_BYTES`](super::VideoEncodeCapabilityFlags::PRECEDING_EXTERNALLY_ENCODED_BYTES)
+    /// capability must be supported.
+    ///
+    /// The default value is `0`.
+    pub preceding_externally_encoded_bytes: u32,
+
+    /// The codec-specific information about the picture.
+    ///
+    /// There is no default value.
+    pub codec_picture_info: VideoEncodeCodecPictureInfo,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl VideoEncodeInfo {
+    /// Returns a `VideoEncodeInfo` that encodes `src_picture_resource` into the whole of
+    /// `dst_buffer`, without reference pictures.
+    #[inline]
+    pub fn new(
+        dst_buffer: Arc<dyn BufferAccess>,
+        src_picture_resource: VideoPictureResourceInfo,
+        codec_picture_info: VideoEncodeCodecPictureInfo,
+    ) -> Self {
+        let dst_buffer_max_range = dst_buffer.size();
+
+        Self {
+            dst_buffer,
+            dst_buffer_offset: 0,
+            dst_buffer_max_range,
+            src_picture_resource,
+            setup_reference_slot: None,
+            reference_slots: Vec::new(),
+            quality_level: 0,
+            preceding_externally_encoded_bytes: 0,
+            codec_picture_info,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The codec-specific information about the picture of an encode operation.
+///
+/// Some of the codec Std structures contain pointers to further structures, such as the weight
+/// tables of a slice header. The structures that they point to are not owned by vulkano, see the
+/// safety requirements of [`AutoCommandBufferBuilder::encode_video`].
+///
+/// [`AutoCommandBufferBuilder::encode_video`]: crate::command_buffer::AutoCommandBufferBuilder::encode_video
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum VideoEncodeCodecPictureInfo {
+    /// An H.264 picture.
+    H264 {
+        std_picture_info: std_video::StdVideoEncodeH264PictureInfo,
+
+        /// The reference lists of the picture, after any reordering.
+        reference_final_lists: Option<VideoEncodeH264ReferenceListsInfo>,
+
+        /// The slices that the picture is divided into.
+        ///
+        /// This must not be empty.
+        nalu_slices: Vec<VideoEncodeH264NaluSliceInfo>,
+
+        /// The parameter sets to insert into the bitstream before the picture.
+        emit_picture_parameters: Option<VideoEncodeH264EmitPictureParametersInfo>,
+    },
+
+    /// An H.265 picture.
+    H265 {
+        std_picture_info: std_video::StdVideoEncodeH265PictureInfo,
+
+        /// The reference lists of the picture, after any reordering.
+        reference_final_lists: Option<VideoEncodeH265ReferenceListsInfo>,
+
+        /// The slice segments that the picture is divided into.
+        ///
+        /// This must not be empty.
+        nalu_slice_segments: Vec<VideoEncodeH265NaluSliceSegmentInfo>,
+
+        /// The parameter sets to insert into the bitstream before the picture.
+        emit_picture_parameters: Option<VideoEncodeH265EmitPictureParametersInfo>,
+    },
+}
+
+// The codec Std structures can contain raw pointers, which makes them neither `Send` nor `Sync`.
+// They are only dereferenced by the implementation when the command is recorded.
+unsafe impl Send for VideoEncodeCodecPictureInfo {}
+unsafe impl Sync for VideoEncodeCodecPictureInfo {}
+
+impl VideoEncodeCodecPictureInfo {
+    /// Returns the video coding operation that the picture info is for.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        match self {
+            Self::H264 { .. } => VideoCodecOperation::EncodeH264,
+            Self::H265 { .. } => VideoCodecOperation::EncodeH265,
+        }
+    }
+}
+
+/// The reference lists of an H.264 picture or slice.
+#[derive(Clone, Debug, Default)]
+pub struct VideoEncodeH264ReferenceListsInfo {
+    /// The entries of reference list 0.
+    pub reference_list0: Vec<VideoEncodeH264DpbSlotInfo>,
+
+    /// The entries of reference list 1.
+    pub reference_list1: Vec<VideoEncodeH264DpbSlotInfo>,
+
+    /// The memory management control operations of the picture.
+    pub std_mem_mgmt_ctrl_operations: Option<std_video::StdVideoEncodeH264RefMemMgmtCtrlOperations>,
+}
+
+/// An entry of an H.264 reference list.
+#[derive(Clone, Copy, Debug)]
+pub struct VideoEncodeH264DpbSlotInfo {
+    /// The DPB slot that holds the reference picture.
+    pub slot_index: u32,
+
+    pub std_reference_info: std_video::StdVideoEncodeH264ReferenceInfo,
+}
+
+/// A slice of an H.264 picture.
+#[derive(Clone, Debug)]
+pub struct VideoEncodeH264NaluSliceInfo {
+    /// The number of macroblocks in the slice.
+    pub mb_count: u32,
+
+    /// The reference lists of the slice, if they differ from those of the picture.
+    pub reference_final_lists: Option<VideoEncodeH264ReferenceListsInfo>,
+
+    pub std_slice_header: std_video::StdVideoEncodeH264SliceHeader,
+}
+
+/// The H.264 parameter sets to insert into the bitstream before a picture.
+#[derive(Clone, Debug, Default)]
+pub struct VideoEncodeH264EmitPictureParametersInfo {
+    /// The ID of the SPS.
+    pub sps_id: u8,
+
+    /// Whether to insert the SPS.
+    pub emit_sps: bool,
+
+    /// The IDs of the PPSs to insert.
+    pub pps_ids: Vec<u8>,
+}
+
+/// The reference lists of an H.265 picture or slice segment.
+#[derive(Clone, Debug, Default)]
+pub struct VideoEncodeH265ReferenceListsInfo {
+    /// The entries of reference list 0.
+    pub reference_list0: Vec<VideoEncodeH265DpbSlotInfo>,
+
+    /// The entries of reference list 1.
+    pub reference_list1: Vec<VideoEncodeH265DpbSlotInfo>,
+
+    /// The modifications of the reference lists.
+    pub std_reference_modifications: Option<std_video::StdVideoEncodeH265ReferenceModifications>,
+}
+
+/// An entry of an H.265 reference list.
+#[derive(Clone, Copy, Debug)]
+pub struct VideoEncodeH265DpbSlotInfo {
+    /// The DPB slot that holds the reference picture.
+    pub slot_index: u32,
+
+    pub std_reference_info: std_video::StdVideoEncodeH265ReferenceInfo,
+}
+
+/// A slice segment of an H.265 picture.
+#[derive(Clone, Debug)]
+pub struct VideoEncodeH265NaluSliceSegmentInfo {
+    /// The number of coding tree blocks in the slice segment.
+    pub ctb_count: u32,
+
+    /// The reference lists of the slice segment, if they differ from those of the picture.
+    pub reference_final_lists: Option<VideoEncodeH265ReferenceListsInfo>,
+
+    pub std_slice_segment_header: std_video::StdVideoEncodeH265SliceSegmentHeader,
+}
+
+/// The H.265 parameter sets to insert into the bitstream before a picture.
+#[derive(Clone, Debug, Default)]
+pub struct VideoEncodeH265EmitPictureParametersInfo {
+    /// The ID of the VPS.
+    pub vps_id: u8,
+
+    /// The ID of the SPS.
+    pub sps_id: u8,
+
+    /// Whether to insert the VPS.
+    pub emit_vps: bool,
+
+    /// Whether to insert the SPS.
+    pub emit_sps: bool,
+
+    /// The IDs of the PPSs to insert.
+    pub pps_ids: Vec<u8>,
+}
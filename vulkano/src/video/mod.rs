@@ -0,0 +1,1526 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Decoding and encoding of compressed video streams on the device.
+//!
+//! Video decoding is performed by queues that have the [`VIDEO_DECODE`] queue flag, and video
+//! encoding by queues that have the [`VIDEO_ENCODE`] queue flag. Which codecs a queue family can
+//! decode or encode is given by [`QueueFamilyProperties::video_codec_operations`]. The
+//! [`khr_video_queue`] extension must be enabled on the device, together with
+//! [`khr_video_decode_queue`] for decoding or [`khr_video_encode_queue`] for encoding, as well as
+//! the extension of each codec that is used, such as [`ext_video_decode_h264`] or
+//! [`ext_video_encode_h264`].
+//!
+//! # Setting up a decoder
+//!
+//! 1. Describe the video stream with a [`VideoProfileInfo`], and query what the implementation
+//!    supports for it with [`PhysicalDevice::video_capabilities`] and
+//!    [`PhysicalDevice::video_format_properties`].
+//! 2. Create a [`VideoSession`], which holds the decoder state and the device memory that the
+//!    implementation needs for it.
+//! 3. Create a [`VideoSessionParameters`] object from the parameter sets of the stream (for
+//!    example the SPS and PPS of an H.264 stream).
+//! 4. Create the images that the decoded pictures are written to, and the images of the decoded
+//!    picture buffer (DPB) that hold the reference pictures. These are regular vulkano images,
+//!    created with the [`VIDEO_DECODE_DST`] and [`VIDEO_DECODE_DPB`] usages, and with the
+//!    video profile in their [`video_profiles`]. If the output images are also created with the
+//!    [`SAMPLED`] usage, the decoded pictures can be sampled from directly.
+//!
+//! # Decoding
+//!
+//! Pictures are decoded by recording commands into a command buffer, in a video coding scope:
+//!
+//! 1. [`begin_video_coding`] begins the scope, binding the video session, its parameters, and
+//!    the DPB slots that are used by the following commands.
+//! 2. The first time a video session is used, it must be reset with [`control_video_coding`].
+//! 3. Each [`decode_video`] command decodes one picture from a bitstream buffer that was created
+//!    with the [`VIDEO_DECODE_SRC`] buffer usage, and optionally writes the reconstructed picture
+//!    into a DPB slot so that it can be used as a reference by later pictures.
+//! 4. [`end_video_coding`] ends the scope.
+//!
+//! The codec-specific parts of the commands are given with the structures of the Vulkan Video
+//! codec headers, which are re-exported in [`std_video`]. Parsing the bitstream and filling in
+//! these structures is the responsibility of the application.
+//!
+//! # Encoding
+//!
+//! Encoding works the same way as decoding, with an encode video profile, and with images that
+//! are created with the [`VIDEO_ENCODE_SRC`] and [`VIDEO_ENCODE_DPB`] usages. Because the source
+//! pictures are regular vulkano images, frames that were rendered or computed on the device can
+//! be encoded without leaving it.
+//!
+//! In a video coding scope, the rate control of the video session can be set with
+//! [`control_video_coding`] and [`VideoCodingControlInfo::encode_rate_control`]. Each
+//! [`encode_video`] command then encodes one picture, and writes the resulting bitstream into a
+//! buffer that was created with the [`VIDEO_ENCODE_DST`] buffer usage. Generating the codec
+//! parameter sets and slice headers is the responsibility of the application.
+//!
+//! [`VIDEO_DECODE`]: crate::device::QueueFlags::VIDEO_DECODE
+//! [`VIDEO_ENCODE`]: crate::device::QueueFlags::VIDEO_ENCODE
+//! [`QueueFamilyProperties::video_codec_operations`]: crate::device::QueueFamilyProperties::video_codec_operations
+//! [`khr_video_queue`]: crate::device::DeviceExtensions::khr_video_queue
+//! [`khr_video_decode_queue`]: crate::device::DeviceExtensions::khr_video_decode_queue
+//! [`khr_video_encode_queue`]: crate::device::DeviceExtensions::khr_video_encode_queue
+//! [`ext_video_decode_h264`]: crate::device::DeviceExtensions::ext_video_decode_h264
+//! [`ext_video_encode_h264`]: crate::device::DeviceExtensions::ext_video_encode_h264
+//! [`PhysicalDevice::video_capabilities`]: crate::device::physical::PhysicalDevice::video_capabilities
+//! [`PhysicalDevice::video_format_properties`]: crate::device::physical::PhysicalDevice::video_format_properties
+//! [`VIDEO_DECODE_DST`]: crate::image::ImageUsage::VIDEO_DECODE_DST
+//! [`VIDEO_DECODE_DPB`]: crate::image::ImageUsage::VIDEO_DECODE_DPB
+//! [`video_profiles`]: crate::image::sys::ImageCreateInfo::video_profiles
+//! [`SAMPLED`]: crate::image::ImageUsage::SAMPLED
+//! [`begin_video_coding`]: crate::command_buffer::AutoCommandBufferBuilder::begin_video_coding
+//! [`control_video_coding`]: crate::command_buffer::AutoCommandBufferBuilder::control_video_coding
+//! [`decode_video`]: crate::command_buffer::AutoCommandBufferBuilder::decode_video
+//! [`VIDEO_DECODE_SRC`]: crate::buffer::BufferUsage::VIDEO_DECODE_SRC
+//! [`end_video_coding`]: crate::command_buffer::AutoCommandBufferBuilder::end_video_coding
+//! [`VIDEO_ENCODE_SRC`]: crate::image::ImageUsage::VIDEO_ENCODE_SRC
+//! [`VIDEO_ENCODE_DPB`]: crate::image::ImageUsage::VIDEO_ENCODE_DPB
+//! [`encode_video`]: crate::command_buffer::AutoCommandBufferBuilder::encode_video
+//! [`VIDEO_ENCODE_DST`]: crate::buffer::BufferUsage::VIDEO_ENCODE_DST
+
+pub use self::encode::{
+    VideoEncodeCodecPictureInfo, VideoEncodeCodecRateControlInfo,
+    VideoEncodeCodecRateControlLayerInfo, VideoEncodeH264DpbSlotInfo,
+    VideoEncodeH264EmitPictureParametersInfo, VideoEncodeH264NaluSliceInfo,
+    VideoEncodeH264RateControlStructure, VideoEncodeH264ReferenceListsInfo,
+    VideoEncodeH265DpbSlotInfo, VideoEncodeH265EmitPictureParametersInfo,
+    VideoEncodeH265NaluSliceSegmentInfo, VideoEncodeH265RateControlStructure,
+    VideoEncodeH265ReferenceListsInfo, VideoEncodeInfo, VideoEncodeRateControlInfo,
+    VideoEncodeRateControlLayerInfo, VideoEncodeRateControlMode,
+};
+pub use self::session::{
+    VideoSession, VideoSessionCreateFlags, VideoSessionCreateInfo, VideoSessionError,
+    VideoSessionParameters, VideoSessionParametersCodecCreateInfo,
+    VideoSessionParametersCreateInfo,
+};
+/// The codec-specific structures of the Vulkan Video codec headers.
+pub use ash::vk::native as std_video;
+
+use crate::{
+    buffer::BufferAccess,
+    device::physical::PhysicalDevice,
+    format::Format,
+    image::{ImageCreateFlags, ImageTiling, ImageType, ImageUsage, ImageViewAbstract},
+    macros::{vulkan_bitflags, vulkan_enum},
+    sampler::ComponentMapping,
+    DeviceSize, ExtensionProperties, RequirementNotMet,
+};
+use smallvec::SmallVec;
+use std::{ffi::c_char, ptr, sync::Arc};
+
+mod encode;
+mod session;
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// A video coding operation, which identifies the codec that is used.
+    VideoCodecOperation = VideoCodecOperationFlagsKHR(u32);
+
+    /// Decoding of H.264 video streams.
+    DecodeH264 = DECODE_H264_EXT {
+        device_extensions: [ext_video_decode_h264],
+    },
+
+    /// Decoding of H.265 video streams.
+    DecodeH265 = DECODE_H265_EXT {
+        device_extensions: [ext_video_decode_h265],
+    },
+
+    /// Encoding of H.264 video streams.
+    EncodeH264 = ENCODE_H264_EXT {
+        device_extensions: [ext_video_encode_h264],
+    },
+
+    /// Encoding of H.265 video streams.
+    EncodeH265 = ENCODE_H265_EXT {
+        device_extensions: [ext_video_encode_h265],
+    },
+}
+
+impl VideoCodecOperation {
+    /// Returns whether the operation is a decode operation.
+    #[inline]
+    pub fn is_decode(self) -> bool {
+        matches!(self, Self::DecodeH264 | Self::DecodeH265)
+    }
+
+    /// Returns whether the operation is an encode operation.
+    #[inline]
+    pub fn is_encode(self) -> bool {
+        matches!(self, Self::EncodeH264 | Self::EncodeH265)
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// A set of video coding operations.
+    VideoCodecOperations = VideoCodecOperationFlagsKHR(u32);
+
+    /// Decoding of H.264 video streams.
+    DECODE_H264 = DECODE_H264_EXT {
+        device_extensions: [ext_video_decode_h264],
+    },
+
+    /// Decoding of H.265 video streams.
+    DECODE_H265 = DECODE_H265_EXT {
+        device_extensions: [ext_video_decode_h265],
+    },
+
+    /// Encoding of H.264 video streams.
+    ENCODE_H264 = ENCODE_H264_EXT {
+        device_extensions: [ext_video_encode_h264],
+    },
+
+    /// Encoding of H.265 video streams.
+    ENCODE_H265 = ENCODE_H265_EXT {
+        device_extensions: [ext_video_encode_h265],
+    },
+}
+
+impl From<VideoCodecOperation> for VideoCodecOperations {
+    #[inline]
+    fn from(val: VideoCodecOperation) -> Self {
+        Self::from(ash::vk::VideoCodecOperationFlagsKHR::from(val))
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// The chroma subsampling of a video stream.
+    VideoChromaSubsampling = VideoChromaSubsamplingFlagsKHR(u32);
+
+    /// There are no chroma components.
+    MONOCHROME = MONOCHROME,
+
+    /// The chroma components have half the width and half the height of the luma component.
+    TYPE_420 = TYPE_420,
+
+    /// The chroma components have half the width and the same height as the luma component.
+    TYPE_422 = TYPE_422,
+
+    /// The chroma components have the same size as the luma component.
+    TYPE_444 = TYPE_444,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// The bit depth of a component of a video stream.
+    VideoComponentBitDepth = VideoComponentBitDepthFlagsKHR(u32);
+
+    /// 8 bits per component.
+    TYPE_8 = TYPE_8,
+
+    /// 10 bits per component.
+    TYPE_10 = TYPE_10,
+
+    /// 12 bits per component.
+    TYPE_12 = TYPE_12,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Hints about how a video decode profile is going to be used.
+    VideoDecodeUsage = VideoDecodeUsageFlagsKHR(u32);
+
+    /// The decoded video is going to be transcoded to another format.
+    TRANSCODING = TRANSCODING,
+
+    /// The video is decoded offline, rather than for real-time playback.
+    OFFLINE = OFFLINE,
+
+    /// The video is streamed, rather than read from local storage.
+    STREAMING = STREAMING,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Hints about how a video encode profile is going to be used.
+    VideoEncodeUsage = VideoEncodeUsageFlagsKHR(u32);
+
+    /// The encoded video is transcoded from another format.
+    TRANSCODING = TRANSCODING,
+
+    /// The encoded video is streamed.
+    STREAMING = STREAMING,
+
+    /// The encoded video is recorded for later playback.
+    RECORDING = RECORDING,
+
+    /// The encoded video is used for video conferencing.
+    CONFERENCING = CONFERENCING,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Hints about the content of the pictures that are encoded with a video encode profile.
+    VideoEncodeContent = VideoEncodeContentFlagsKHR(u32);
+
+    /// The pictures are captured by a camera.
+    CAMERA = CAMERA,
+
+    /// The pictures are captures of a desktop.
+    DESKTOP = DESKTOP,
+
+    /// The pictures are rendered.
+    RENDERED = RENDERED,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// What an encoder should optimize the encoded video for.
+    VideoEncodeTuningMode = VideoEncodeTuningModeKHR(i32);
+
+    /// The default tuning of the implementation.
+    Default = DEFAULT,
+
+    /// Optimize for quality.
+    HighQuality = HIGH_QUALITY,
+
+    /// Optimize for low latency.
+    LowLatency = LOW_LATENCY,
+
+    /// Optimize for the lowest possible latency.
+    UltraLowLatency = ULTRA_LOW_LATENCY,
+
+    /// Encode without loss.
+    Lossless = LOSSLESS,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// How the fields of an interlaced H.264 video stream are laid out in the decoded pictures.
+    VideoDecodeH264PictureLayout = VideoDecodeH264PictureLayoutFlagsEXT(u32);
+
+    /// The video stream is progressive, it contains no fields.
+    Progressive = PROGRESSIVE,
+
+    /// The two fields of a frame are stored in alternating lines of the same picture.
+    InterlacedInterleavedLines = INTERLACED_INTERLEAVED_LINES,
+
+    /// The two fields of a frame are stored in separate planes.
+    InterlacedSeparatePlanes = INTERLACED_SEPARATE_PLANES,
+}
+
+/// An H.264 profile, as given by the `profile_idc` syntax element of the bitstream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum H264ProfileIdc {
+    Baseline = 66,
+    Main = 77,
+    High = 100,
+    High444Predictive = 244,
+}
+
+/// An H.265 profile, as given by the `general_profile_idc` syntax element of the bitstream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u32)]
+pub enum H265ProfileIdc {
+    Main = 1,
+    Main10 = 2,
+    MainStillPicture = 3,
+    FormatRangeExtensions = 4,
+    SccExtensions = 9,
+}
+
+/// The codec-specific part of a [`VideoProfileInfo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VideoCodecProfileInfo {
+    /// An H.264 decode profile.
+    DecodeH264 {
+        std_profile_idc: H264ProfileIdc,
+        picture_layout: VideoDecodeH264PictureLayout,
+    },
+
+    /// An H.265 decode profile.
+    DecodeH265 { std_profile_idc: H265ProfileIdc },
+
+    /// An H.264 encode profile.
+    EncodeH264 { std_profile_idc: H264ProfileIdc },
+
+    /// An H.265 encode profile.
+    EncodeH265 { std_profile_idc: H265ProfileIdc },
+}
+
+impl VideoCodecProfileInfo {
+    /// Returns the video coding operation of the profile.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        match self {
+            Self::DecodeH264 { .. } => VideoCodecOperation::DecodeH264,
+            Self::DecodeH265 { .. } => VideoCodecOperation::DecodeH265,
+            Self::EncodeH264 { .. } => VideoCodecOperation::EncodeH264,
+            Self::EncodeH265 { .. } => VideoCodecOperation::EncodeH265,
+        }
+    }
+}
+
+/// Describes the video stream that a video session, image or buffer is going to be used with.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VideoProfileInfo {
+    /// The codec and the codec-specific profile parameters.
+    ///
+    /// There is no default value.
+    pub codec_profile_info: VideoCodecProfileInfo,
+
+    /// The chroma subsampling of the video stream.
+    ///
+    /// Exactly one flag must be set.
+    ///
+    /// The default value is [`VideoChromaSubsampling::TYPE_420`].
+    pub chroma_subsampling: VideoChromaSubsampling,
+
+    /// The bit depth of the luma component.
+    ///
+    /// Exactly one flag must be set.
+    ///
+    /// The default value is [`VideoComponentBitDepth::TYPE_8`].
+    pub luma_bit_depth: VideoComponentBitDepth,
+
+    /// The bit depth of the chroma components.
+    ///
+    /// This must be `None` if `chroma_subsampling` is [`VideoChromaSubsampling::MONOCHROME`], and
+    /// `Some` with exactly one flag set otherwise.
+    ///
+    /// The default value is `Some(VideoComponentBitDepth::TYPE_8)`.
+    pub chroma_bit_depth: Option<VideoComponentBitDepth>,
+
+    /// Hints about how the decoded video is going to be used.
+    ///
+    /// This must be empty for encode profiles.
+    ///
+    /// The default value is empty.
+    pub decode_usage_hints: VideoDecodeUsage,
+
+    /// Hints about how the encoded video is going to be used.
+    ///
+    /// This must be empty for decode profiles.
+    ///
+    /// The default value is empty.
+    pub encode_usage_hints: VideoEncodeUsage,
+
+    /// Hints about the content of the pictures that are encoded.
+    ///
+    /// This must be empty for decode profiles.
+    ///
+    /// The default value is empty.
+    pub encode_content_hints: VideoEncodeContent,
+
+    /// What the encoder should optimize the encoded video for.
+    ///
+    /// This must be [`VideoEncodeTuningMode::Default`] for decode profiles.
+    ///
+    /// The default value is [`VideoEncodeTuningMode::Default`].
+    pub encode_tuning_mode: VideoEncodeTuningMode,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl VideoProfileInfo {
+    /// Returns a `VideoProfileInfo` with the specified `codec_profile_info`, for an 8-bit video
+    /// stream with 4:2:0 chroma subsampling.
+    #[inline]
+    pub fn new(codec_profile_info: VideoCodecProfileInfo) -> Self {
+        Self {
+            codec_profile_info,
+            chroma_subsampling: VideoChromaSubsampling::TYPE_420,
+            luma_bit_depth: VideoComponentBitDepth::TYPE_8,
+            chroma_bit_depth: Some(VideoComponentBitDepth::TYPE_8),
+            decode_usage_hints: VideoDecodeUsage::empty(),
+            encode_usage_hints: VideoEncodeUsage::empty(),
+            encode_content_hints: VideoEncodeContent::empty(),
+            encode_tuning_mode: VideoEncodeTuningMode::Default,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    /// Returns the video coding operation of the profile.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        self.codec_profile_info.video_codec_operation()
+    }
+
+    pub(crate) fn validate(
+        &self,
+        physical_device: &PhysicalDevice,
+    ) -> Result<(), RequirementNotMet> {
+        let &Self {
+            codec_profile_info,
+            chroma_subsampling,
+            luma_bit_depth,
+            chroma_bit_depth,
+            decode_usage_hints,
+            encode_usage_hints,
+            encode_content_hints,
+            encode_tuning_mode,
+            _ne: _,
+        } = self;
+
+        // VUID-VkVideoProfileInfoKHR-videoCodecOperation-parameter
+        codec_profile_info
+            .video_codec_operation()
+            .validate_physical_device(physical_device)?;
+
+        // VUID-VkVideoProfileInfoKHR-chromaSubsampling-parameter
+        chroma_subsampling.validate_physical_device(physical_device)?;
+
+        // VUID-VkVideoProfileInfoKHR-lumaBitDepth-parameter
+        luma_bit_depth.validate_physical_device(physical_device)?;
+
+        // VUID-VkVideoDecodeUsageInfoKHR-videoUsageHints-parameter
+        decode_usage_hints.validate_physical_device(physical_device)?;
+
+        // VUID-VkVideoEncodeUsageInfoKHR-videoUsageHints-parameter
+        encode_usage_hints.validate_physical_device(physical_device)?;
+
+        // VUID-VkVideoEncodeUsageInfoKHR-videoContentHints-parameter
+        encode_content_hints.validate_physical_device(physical_device)?;
+
+        // VUID-VkVideoEncodeUsageInfoKHR-tuningMode-parameter
+        encode_tuning_mode.validate_physical_device(physical_device)?;
+
+        if codec_profile_info.video_codec_operation().is_encode() {
+            assert!(
+                decode_usage_hints.is_empty(),
+                "`decode_usage_hints` must be empty for encode profiles",
+            );
+        } else {
+            assert!(
+                encode_usage_hints.is_empty()
+                    && encode_content_hints.is_empty()
+                    && encode_tuning_mode == VideoEncodeTuningMode::Default,
+                "the encode hints must be left at their defaults for decode profiles",
+            );
+        }
+
+        // VUID-VkVideoProfileInfoKHR-chromaSubsampling-requiredbitmask
+        assert!(
+            chroma_subsampling.count() == 1,
+            "`chroma_subsampling` must have exactly one flag set",
+        );
+
+        // VUID-VkVideoProfileInfoKHR-lumaBitDepth-requiredbitmask
+        assert!(
+            luma_bit_depth.count() == 1,
+            "`luma_bit_depth` must have exactly one flag set",
+        );
+
+        // VUID-VkVideoProfileInfoKHR-chromaSubsampling-07015
+        if chroma_subsampling == VideoChromaSubsampling::MONOCHROME {
+            assert!(
+                chroma_bit_depth.is_none(),
+                "`chroma_bit_depth` must be `None` for monochrome video profiles",
+            );
+        } else {
+            let chroma_bit_depth = chroma_bit_depth
+                .expect("`chroma_bit_depth` must be `Some` for video profiles with chroma");
+
+            // VUID-VkVideoProfileInfoKHR-chromaBitDepth-parameter
+            chroma_bit_depth.validate_physical_device(physical_device)?;
+
+            assert!(
+                chroma_bit_depth.count() == 1,
+                "`chroma_bit_depth` must have exactly one flag set",
+            );
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn to_vulkan(&self) -> Box<VideoProfileInfoVk> {
+        let &Self {
+            codec_profile_info,
+            chroma_subsampling,
+            luma_bit_depth,
+            chroma_bit_depth,
+            decode_usage_hints,
+            encode_usage_hints,
+            encode_content_hints,
+            encode_tuning_mode,
+            _ne: _,
+        } = self;
+
+        // The structures are boxed, so that the pointers in the chain stay valid when the result
+        // is moved.
+        let mut profile_info_vk = Box::new(VideoProfileInfoVk {
+            profile_info: ash::vk::VideoProfileInfoKHR {
+                video_codec_operation: codec_profile_info.video_codec_operation().into(),
+                chroma_subsampling: chroma_subsampling.into(),
+                luma_bit_depth: luma_bit_depth.into(),
+                chroma_bit_depth: chroma_bit_depth.map_or_else(Default::default, Into::into),
+                ..Default::default()
+            },
+            decode_usage_info: ash::vk::VideoDecodeUsageInfoKHR {
+                video_usage_hints: decode_usage_hints.into(),
+                ..Default::default()
+            },
+            encode_usage_info: ash::vk::VideoEncodeUsageInfoKHR {
+                video_usage_hints: encode_usage_hints.into(),
+                video_content_hints: encode_content_hints.into(),
+                tuning_mode: encode_tuning_mode.into(),
+                ..Default::default()
+            },
+            decode_h264_profile_info: Default::default(),
+            decode_h265_profile_info: Default::default(),
+            encode_h264_profile_info: Default::default(),
+            encode_h265_profile_info: Default::default(),
+        });
+        let VideoProfileInfoVk {
+            profile_info,
+            decode_usage_info,
+            encode_usage_info,
+            decode_h264_profile_info,
+            decode_h265_profile_info,
+            encode_h264_profile_info,
+            encode_h265_profile_info,
+        } = &mut *profile_info_vk;
+
+        match codec_profile_info {
+            VideoCodecProfileInfo::DecodeH264 {
+                std_profile_idc,
+                picture_layout,
+            } => {
+                *decode_h264_profile_info = ash::vk::VideoDecodeH264ProfileInfoEXT {
+                    std_profile_idc: std_profile_idc as u32 as _,
+                    picture_layout: picture_layout.into(),
+                    ..Default::default()
+                };
+
+                decode_h264_profile_info.p_next = profile_info.p_next;
+                profile_info.p_next = decode_h264_profile_info as *const _ as *const _;
+            }
+            VideoCodecProfileInfo::DecodeH265 { std_profile_idc } => {
+                *decode_h265_profile_info = ash::vk::VideoDecodeH265ProfileInfoEXT {
+                    std_profile_idc: std_profile_idc as u32 as _,
+                    ..Default::default()
+                };
+
+                decode_h265_profile_info.p_next = profile_info.p_next;
+                profile_info.p_next = decode_h265_profile_info as *const _ as *const _;
+            }
+            VideoCodecProfileInfo::EncodeH264 { std_profile_idc } => {
+                *encode_h264_profile_info = ash::vk::VideoEncodeH264ProfileInfoEXT {
+                    std_profile_idc: std_profile_idc as u32 as _,
+                    ..Default::default()
+                };
+
+                encode_h264_profile_info.p_next = profile_info.p_next;
+                profile_info.p_next = encode_h264_profile_info as *const _ as *const _;
+            }
+            VideoCodecProfileInfo::EncodeH265 { std_profile_idc } => {
+                *encode_h265_profile_info = ash::vk::VideoEncodeH265ProfileInfoEXT {
+                    std_profile_idc: std_profile_idc as u32 as _,
+                    ..Default::default()
+                };
+
+                encode_h265_profile_info.p_next = profile_info.p_next;
+                profile_info.p_next = encode_h265_profile_info as *const _ as *const _;
+            }
+        }
+
+        if !decode_usage_hints.is_empty() {
+            decode_usage_info.p_next = profile_info.p_next;
+            profile_info.p_next = decode_usage_info as *const _ as *const _;
+        }
+
+        if !(encode_usage_hints.is_empty()
+            && encode_content_hints.is_empty()
+            && encode_tuning_mode == VideoEncodeTuningMode::Default)
+        {
+            encode_usage_info.p_next = profile_info.p_next;
+            profile_info.p_next = encode_usage_info as *const _ as *const _;
+        }
+
+        profile_info_vk
+    }
+}
+
+pub(crate) struct VideoProfileInfoVk {
+    pub(crate) profile_info: ash::vk::VideoProfileInfoKHR,
+    decode_usage_info: ash::vk::VideoDecodeUsageInfoKHR,
+    encode_usage_info: ash::vk::VideoEncodeUsageInfoKHR,
+    decode_h264_profile_info: ash::vk::VideoDecodeH264ProfileInfoEXT,
+    decode_h265_profile_info: ash::vk::VideoDecodeH265ProfileInfoEXT,
+    encode_h264_profile_info: ash::vk::VideoEncodeH264ProfileInfoEXT,
+    encode_h265_profile_info: ash::vk::VideoEncodeH265ProfileInfoEXT,
+}
+
+/// A list of video profiles, converted for chaining into the `p_next` of another structure.
+pub(crate) struct VideoProfileListInfoVk {
+    _profile_infos_vk: SmallVec<[Box<VideoProfileInfoVk>; 2]>,
+    _profiles_vk: SmallVec<[ash::vk::VideoProfileInfoKHR; 2]>,
+    pub(crate) profile_list_info: ash::vk::VideoProfileListInfoKHR,
+}
+
+impl VideoProfileListInfoVk {
+    pub(crate) fn new<'a>(profiles: impl IntoIterator<Item = &'a VideoProfileInfo>) -> Box<Self> {
+        let profile_infos_vk: SmallVec<[_; 2]> = profiles
+            .into_iter()
+            .map(|profile| profile.to_vulkan())
+            .collect();
+        let profiles_vk: SmallVec<[_; 2]> = profile_infos_vk
+            .iter()
+            .map(|profile_info_vk| profile_info_vk.profile_info)
+            .collect();
+
+        let mut profile_list_info_vk = Box::new(Self {
+            _profile_infos_vk: profile_infos_vk,
+            _profiles_vk: profiles_vk,
+            profile_list_info: Default::default(),
+        });
+        profile_list_info_vk.profile_list_info = ash::vk::VideoProfileListInfoKHR {
+            profile_count: profile_list_info_vk._profiles_vk.len() as u32,
+            p_profiles: profile_list_info_vk._profiles_vk.as_ptr(),
+            ..Default::default()
+        };
+
+        profile_list_info_vk
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// General capabilities of a video profile.
+    VideoCapabilityFlags = VideoCapabilityFlagsKHR(u32);
+
+    /// Video sessions can be created with the
+    /// [`PROTECTED_CONTENT`](VideoSessionCreateFlags::PROTECTED_CONTENT) flag, to decode
+    /// protected content.
+    PROTECTED_CONTENT = PROTECTED_CONTENT,
+
+    /// The reference pictures in the DPB can be stored in separate images. If not set, all
+    /// reference pictures must be stored in the array layers of a single image.
+    SEPARATE_REFERENCE_IMAGES = SEPARATE_REFERENCE_IMAGES,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Capabilities of a video decode profile.
+    VideoDecodeCapabilityFlags = VideoDecodeCapabilityFlagsKHR(u32);
+
+    /// The output picture of a decode operation can be the same image subresource as the DPB
+    /// slot that the reconstructed picture is written to.
+    DPB_AND_OUTPUT_COINCIDE = DPB_AND_OUTPUT_COINCIDE,
+
+    /// The output picture of a decode operation can be a different image subresource than the
+    /// DPB slot that the reconstructed picture is written to.
+    DPB_AND_OUTPUT_DISTINCT = DPB_AND_OUTPUT_DISTINCT,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Capabilities of a video encode profile.
+    VideoEncodeCapabilityFlags = VideoEncodeCapabilityFlagsKHR(u32);
+
+    /// The [`preceding_externally_encoded_bytes`] of an encode operation can be nonzero.
+    ///
+    /// [`preceding_externally_encoded_bytes`]: VideoEncodeInfo::preceding_externally_encoded_bytes
+    PRECEDING_EXTERNALLY_ENCODED_BYTES = PRECEDING_EXTERNALLY_ENCODED_BYTES,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// A set of rate control modes of a video encode profile.
+    ///
+    /// [`VideoEncodeRateControlMode::None`] is always supported, and has no flag.
+    VideoEncodeRateControlModes = VideoEncodeRateControlModeFlagsKHR(u32);
+
+    /// Constant bitrate.
+    CBR = CBR,
+
+    /// Variable bitrate.
+    VBR = VBR,
+}
+
+/// The capabilities of a video profile, as returned by
+/// [`PhysicalDevice::video_capabilities`].
+///
+/// [`PhysicalDevice::video_capabilities`]: crate::device::physical::PhysicalDevice::video_capabilities
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct VideoCapabilities {
+    /// General capabilities of the video profile.
+    pub flags: VideoCapabilityFlags,
+
+    /// The required alignment of the offset of the bitstream within its buffer.
+    pub min_bitstream_buffer_offset_alignment: DeviceSize,
+
+    /// The required alignment of the size of the bitstream within its buffer.
+    pub min_bitstream_buffer_size_alignment: DeviceSize,
+
+    /// The granularity of the coded offsets and extents of the picture resources.
+    pub picture_access_granularity: [u32; 2],
+
+    /// The minimum coded extent of the pictures.
+    pub min_coded_extent: [u32; 2],
+
+    /// The maximum coded extent of the pictures.
+    pub max_coded_extent: [u32; 2],
+
+    /// The maximum number of DPB slots of a video session.
+    pub max_dpb_slots: u32,
+
+    /// The maximum number of reference pictures that a single video coding operation can use.
+    pub max_active_reference_pictures: u32,
+
+    /// The name and the latest supported version of the codec Std header.
+    pub std_header_version: ExtensionProperties,
+
+    /// The capabilities of the video profile that are specific to decoding.
+    ///
+    /// This is empty for encode profiles.
+    pub decode_capability_flags: VideoDecodeCapabilityFlags,
+
+    /// The capabilities of the video profile that are specific to encoding.
+    ///
+    /// This is empty for decode profiles.
+    pub encode_capability_flags: VideoEncodeCapabilityFlags,
+
+    /// The rate control modes that are supported, in addition to
+    /// [`VideoEncodeRateControlMode::None`].
+    ///
+    /// This is empty for decode profiles.
+    pub encode_rate_control_modes: VideoEncodeRateControlModes,
+
+    /// The maximum number of rate control layers.
+    ///
+    /// This is `0` for decode profiles.
+    pub encode_rate_control_layer_count: u32,
+
+    /// The number of quality levels. The [`quality_level`] of an encode operation must be less
+    /// than this.
+    ///
+    /// This is `0` for decode profiles.
+    ///
+    /// [`quality_level`]: VideoEncodeInfo::quality_level
+    pub encode_quality_level_count: u32,
+
+    /// The granularity that the implementation may read source picture data at, beyond the
+    /// coded extent.
+    ///
+    /// This is `[0; 2]` for decode profiles.
+    pub encode_input_image_data_fill_alignment: [u32; 2],
+
+    /// The capabilities of the video profile that are specific to the codec.
+    pub codec_capabilities: VideoCodecCapabilities,
+}
+
+/// The codec-specific capabilities of a video profile.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum VideoCodecCapabilities {
+    /// The capabilities of an H.264 decode profile.
+    DecodeH264 {
+        /// The highest H.264 level that is supported, as a `StdVideoH264LevelIdc` value.
+        max_level_idc: std_video::StdVideoH264LevelIdc,
+
+        /// The granularity of the offset of the bottom field of an interlaced picture, if the
+        /// picture layout is [`VideoDecodeH264PictureLayout::InterlacedSeparatePlanes`].
+        field_offset_granularity: [i32; 2],
+    },
+
+    /// The capabilities of an H.265 decode profile.
+    DecodeH265 {
+        /// The highest H.265 level that is supported, as a `StdVideoH265LevelIdc` value.
+        max_level_idc: std_video::StdVideoH265LevelIdc,
+    },
+
+    /// The capabilities of an H.264 encode profile.
+    EncodeH264 {
+        /// The maximum number of entries in reference list 0 of a P picture.
+        max_p_picture_l0_reference_count: u32,
+
+        /// The maximum number of entries in reference list 0 of a B picture.
+        max_b_picture_l0_reference_count: u32,
+
+        /// The maximum number of entries in reference list 1.
+        max_l1_reference_count: u32,
+
+        /// Whether motion vectors may point outside the picture boundaries.
+        motion_vectors_over_pic_boundaries: bool,
+    },
+
+    /// The capabilities of an H.265 encode profile.
+    EncodeH265 {
+        /// The maximum number of entries in reference list 0 of a P picture.
+        max_p_picture_l0_reference_count: u32,
+
+        /// The maximum number of entries in reference list 0 of a B picture.
+        max_b_picture_l0_reference_count: u32,
+
+        /// The maximum number of entries in reference list 1.
+        max_l1_reference_count: u32,
+
+        /// The maximum number of temporal sub-layers.
+        max_sub_layers_count: u32,
+    },
+}
+
+/// Parameters to query the formats that can be used for images with a video profile, with
+/// [`PhysicalDevice::video_format_properties`].
+///
+/// [`PhysicalDevice::video_format_properties`]: crate::device::physical::PhysicalDevice::video_format_properties
+#[derive(Clone, Debug)]
+pub struct VideoFormatInfo {
+    /// The usage that the images will have.
+    ///
+    /// This must include one of [`ImageUsage::VIDEO_DECODE_DST`],
+    /// [`ImageUsage::VIDEO_DECODE_DPB`], [`ImageUsage::VIDEO_ENCODE_SRC`] or
+    /// [`ImageUsage::VIDEO_ENCODE_DPB`].
+    ///
+    /// The default value is [`ImageUsage::empty()`], which must be overridden.
+    pub image_usage: ImageUsage,
+
+    /// The video profiles that the images will be used with.
+    ///
+    /// The default value is empty, which must be overridden.
+    pub video_profiles: Vec<VideoProfileInfo>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for VideoFormatInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            image_usage: ImageUsage::empty(),
+            video_profiles: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The properties of a format that can be used for images with a video profile, as returned by
+/// [`PhysicalDevice::video_format_properties`].
+///
+/// [`PhysicalDevice::video_format_properties`]: crate::device::physical::PhysicalDevice::video_format_properties
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct VideoFormatProperties {
+    /// The format of the images.
+    pub format: Format,
+
+    /// The component mapping that must be used to interpret the components of the format.
+    pub component_mapping: ComponentMapping,
+
+    /// The image creation flags that are supported with the format.
+    pub image_create_flags: ImageCreateFlags,
+
+    /// The image type that is supported with the format.
+    pub image_type: ImageType,
+
+    /// The image tiling that is supported with the format.
+    pub image_tiling: ImageTiling,
+
+    /// The image usages that are supported with the format.
+    pub image_usage_flags: ImageUsage,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control a video coding control operation.
+    VideoCodingControlFlags = VideoCodingControlFlagsKHR(u32);
+
+    /// Resets the state of the video session, and deactivates all of its DPB slots. This must be
+    /// done before a newly created video session is used.
+    RESET = RESET,
+
+    /// Sets the rate control state of an encode video session to
+    /// [`VideoCodingControlInfo::encode_rate_control`].
+    ENCODE_RATE_CONTROL = ENCODE_RATE_CONTROL {
+        device_extensions: [khr_video_encode_queue],
+    },
+}
+
+/// Parameters to control the state of a video session, with
+/// [`AutoCommandBufferBuilder::control_video_coding`].
+///
+/// [`AutoCommandBufferBuilder::control_video_coding`]: crate::command_buffer::AutoCommandBufferBuilder::control_video_coding
+#[derive(Clone, Debug)]
+pub struct VideoCodingControlInfo {
+    /// The operations to perform.
+    ///
+    /// This must not be empty.
+    ///
+    /// The default value is [`VideoCodingControlFlags::RESET`].
+    pub flags: VideoCodingControlFlags,
+
+    /// The rate control state to set for an encode video session.
+    ///
+    /// This must be `Some` if and only if `flags` contains
+    /// [`VideoCodingControlFlags::ENCODE_RATE_CONTROL`].
+    ///
+    /// The default value is `None`.
+    pub encode_rate_control: Option<VideoEncodeRateControlInfo>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for VideoCodingControlInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            flags: VideoCodingControlFlags::RESET,
+            encode_rate_control: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// Parameters to begin a video coding scope, with
+/// [`AutoCommandBufferBuilder::begin_video_coding`].
+///
+/// [`AutoCommandBufferBuilder::begin_video_coding`]: crate::command_buffer::AutoCommandBufferBuilder::begin_video_coding
+#[derive(Clone, Debug)]
+pub struct VideoBeginCodingInfo {
+    /// The video session to use.
+    ///
+    /// There is no default value.
+    pub video_session: Arc<VideoSession>,
+
+    /// The parameters to use with the video session.
+    ///
+    /// This must be `Some` for the H.264 and H.265 decode and encode operations.
+    ///
+    /// The default value is `None`.
+    pub video_session_parameters: Option<Arc<VideoSessionParameters>>,
+
+    /// The DPB slots, and the picture resources that are bound to them, that can be used by the
+    /// video coding operations in the scope.
+    ///
+    /// A slot with a `slot_index` of `None` is not active yet, and is activated by a decode or
+    /// encode operation that uses its picture resource as its setup reference slot.
+    ///
+    /// The default value is empty.
+    pub reference_slots: Vec<VideoReferenceSlotInfo>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl VideoBeginCodingInfo {
+    /// Returns a `VideoBeginCodingInfo` with the specified `video_session`.
+    #[inline]
+    pub fn video_session(video_session: Arc<VideoSession>) -> Self {
+        Self {
+            video_session,
+            video_session_parameters: None,
+            reference_slots: Vec::new(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A picture resource that is used by a video coding operation.
+#[derive(Clone, Debug)]
+pub struct VideoPictureResourceInfo {
+    /// The image view that the picture is stored in.
+    ///
+    /// The view must be a 2D or 2D array view.
+    ///
+    /// There is no default value.
+    pub image_view: Arc<dyn ImageViewAbstract>,
+
+    /// The array layer of `image_view` that the picture is stored in, relative to the view.
+    ///
+    /// The default value is `0`.
+    pub base_array_layer: u32,
+
+    /// The offset of the picture within the image.
+    ///
+    /// The default value is `[0; 2]`.
+    pub coded_offset: [i32; 2],
+
+    /// The extent of the picture.
+    ///
+    /// The default value is the extent of `image_view`.
+    pub coded_extent: [u32; 2],
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl VideoPictureResourceInfo {
+    /// Returns a `VideoPictureResourceInfo` with the specified `image_view`, covering the whole
+    /// extent of its first array layer.
+    #[inline]
+    pub fn image_view(image_view: Arc<dyn ImageViewAbstract>) -> Self {
+        let [width, height, _] = image_view.dimensions().width_height_depth();
+
+        Self {
+            image_view,
+            base_array_layer: 0,
+            coded_offset: [0; 2],
+            coded_extent: [width, height],
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn to_vulkan(&self) -> ash::vk::VideoPictureResourceInfoKHR {
+        let &Self {
+            ref image_view,
+            base_array_layer,
+            coded_offset,
+            coded_extent,
+            _ne: _,
+        } = self;
+
+        ash::vk::VideoPictureResourceInfoKHR {
+            coded_offset: ash::vk::Offset2D {
+                x: coded_offset[0],
+                y: coded_offset[1],
+            },
+            coded_extent: ash::vk::Extent2D {
+                width: coded_extent[0],
+                height: coded_extent[1],
+            },
+            base_array_layer,
+            image_view_binding: image_view.handle(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A DPB slot that is used by a video coding operation.
+#[derive(Clone, Debug)]
+pub struct VideoReferenceSlotInfo {
+    /// The index of the DPB slot, or `None` if the slot is not active yet.
+    ///
+    /// The default value is `None`.
+    pub slot_index: Option<u32>,
+
+    /// The picture resource that is bound to the DPB slot.
+    ///
+    /// The default value is `None`.
+    pub picture_resource: Option<VideoPictureResourceInfo>,
+
+    /// The codec-specific information about the reference picture in the slot.
+    ///
+    /// This is ignored by [`begin_video_coding`] and [`encode_video`], but must be `Some` for the
+    /// slots that are used by [`decode_video`]. The reference pictures of an encode operation
+    /// are described in the codec-specific reference lists of [`VideoEncodeCodecPictureInfo`]
+    /// instead.
+    ///
+    /// The default value is `None`.
+    ///
+    /// [`begin_video_coding`]: crate::command_buffer::AutoCommandBufferBuilder::begin_video_coding
+    /// [`encode_video`]: crate::command_buffer::AutoCommandBufferBuilder::encode_video
+    /// [`decode_video`]: crate::command_buffer::AutoCommandBufferBuilder::decode_video
+    pub codec_reference_info: Option<VideoDecodeCodecReferenceInfo>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for VideoReferenceSlotInfo {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            slot_index: None,
+            picture_resource: None,
+            codec_reference_info: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The codec-specific information about a reference picture of a decode operation.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum VideoDecodeCodecReferenceInfo {
+    H264(std_video::StdVideoDecodeH264ReferenceInfo),
+    H265(std_video::StdVideoDecodeH265ReferenceInfo),
+}
+
+/// Parameters to decode a picture, with [`AutoCommandBufferBuilder::decode_video`].
+///
+/// [`AutoCommandBufferBuilder::decode_video`]: crate::command_buffer::AutoCommandBufferBuilder::decode_video
+#[derive(Clone, Debug)]
+pub struct VideoDecodeInfo {
+    /// The buffer that the bitstream of the picture is read from.
+    ///
+    /// The buffer must have been created with the [`VIDEO_DECODE_SRC`] usage.
+    ///
+    /// There is no default value.
+    ///
+    /// [`VIDEO_DECODE_SRC`]: crate::buffer::BufferUsage::VIDEO_DECODE_SRC
+    pub src_buffer: Arc<dyn BufferAccess>,
+
+    /// The offset of the bitstream within `src_buffer`.
+    ///
+    /// This must be a multiple of the
+    /// [`min_bitstream_buffer_offset_alignment`](VideoCapabilities::min_bitstream_buffer_offset_alignment)
+    /// of the video profile.
+    ///
+    /// The default value is `0`.
+    pub src_buffer_offset: DeviceSize,
+
+    /// The size of the bitstream within `src_buffer`.
+    ///
+    /// This must be a multiple of the
+    /// [`min_bitstream_buffer_size_alignment`](VideoCapabilities::min_bitstream_buffer_size_alignment)
+    /// of the video profile.
+    ///
+    /// The default value is the size of `src_buffer`.
+    pub src_buffer_range: DeviceSize,
+
+    /// The picture resource that the decoded picture is written to.
+    ///
+    /// The image must have been created with the [`VIDEO_DECODE_DST`] usage.
+    ///
+    /// There is no default value.
+    ///
+    /// [`VIDEO_DECODE_DST`]: ImageUsage::VIDEO_DECODE_DST
+    pub dst_picture_resource: VideoPictureResourceInfo,
+
+    /// The DPB slot that the reconstructed picture is written to, if it is going to be used as a
+    /// reference picture by later decode operations.
+    ///
+    /// The slot must have been given to `begin_video_coding`, and must have a picture resource.
+    ///
+    /// The default value is `None`.
+    pub setup_reference_slot: Option<VideoReferenceSlotInfo>,
+
+    /// The DPB slots that hold the reference pictures that the picture is predicted from.
+    ///
+    /// The slots must have been given to `begin_video_coding`, and must have a picture resource.
+    ///
+    /// The default value is empty.
+    pub reference_slots: Vec<VideoReferenceSlotInfo>,
+
+    /// The codec-specific information about the picture.
+    ///
+    /// There is no default value.
+    pub codec_picture_info: VideoDecodeCodecPictureInfo,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl VideoDecodeInfo {
+    /// Returns a `VideoDecodeInfo` that decodes the whole of `src_buffer` into
+    /// `dst_picture_resource`, without reference pictures.
+    #[inline]
+    pub fn new(
+        src_buffer: Arc<dyn BufferAccess>,
+        dst_picture_resource: VideoPictureResourceInfo,
+        codec_picture_info: VideoDecodeCodecPictureInfo,
+    ) -> Self {
+        let src_buffer_range = src_buffer.size();
+
+        Self {
+            src_buffer,
+            src_buffer_offset: 0,
+            src_buffer_range,
+            dst_picture_resource,
+            setup_reference_slot: None,
+            reference_slots: Vec::new(),
+            codec_picture_info,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The codec-specific information about the picture of a decode operation.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum VideoDecodeCodecPictureInfo {
+    /// An H.264 picture.
+    H264 {
+        std_picture_info: std_video::StdVideoDecodeH264PictureInfo,
+
+        /// The offsets of the slices of the picture within the bitstream range.
+        slice_offsets: Vec<u32>,
+    },
+
+    /// An H.265 picture.
+    H265 {
+        std_picture_info: std_video::StdVideoDecodeH265PictureInfo,
+
+        /// The offsets of the slice segments of the picture within the bitstream range.
+        slice_offsets: Vec<u32>,
+    },
+}
+
+impl VideoDecodeCodecPictureInfo {
+    /// Returns the video coding operation that the picture info is for.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        match self {
+            Self::H264 { .. } => VideoCodecOperation::DecodeH264,
+            Self::H265 { .. } => VideoCodecOperation::DecodeH265,
+        }
+    }
+}
+
+impl VideoDecodeCodecReferenceInfo {
+    /// Returns the video coding operation that the reference info is for.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        match self {
+            Self::H264(_) => VideoCodecOperation::DecodeH264,
+            Self::H265(_) => VideoCodecOperation::DecodeH265,
+        }
+    }
+}
+
+pub(crate) fn extension_properties_to_vulkan(
+    extension_properties: &ExtensionProperties,
+) -> ash::vk::ExtensionProperties {
+    let mut extension_properties_vk = ash::vk::ExtensionProperties {
+        extension_name: [0; 256],
+        spec_version: extension_properties.spec_version,
+    };
+
+    for (dst, &src) in extension_properties_vk
+        .extension_name
+        .iter_mut()
+        .zip(extension_properties.extension_name.as_bytes())
+        .take(ash::vk::MAX_EXTENSION_NAME_SIZE - 1)
+    {
+        *dst = src as c_char;
+    }
+
+    extension_properties_vk
+}
+
+pub(crate) fn null_ptr_if_empty<T>(slice: &[T]) -> *const T {
+    if slice.is_empty() {
+        ptr::null()
+    } else {
+        slice.as_ptr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{device::physical::PhysicalDeviceError, RequiresOneOf};
+
+    fn decode_h264_profile() -> VideoProfileInfo {
+        VideoProfileInfo::new(VideoCodecProfileInfo::DecodeH264 {
+            std_profile_idc: H264ProfileIdc::Main,
+            picture_layout: VideoDecodeH264PictureLayout::Progressive,
+        })
+    }
+
+    #[test]
+    fn video_capabilities_requires_extension() {
+        let instance = instance!();
+
+        let physical_device = match instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .find(|p| !p.supported_extensions().khr_video_queue)
+        {
+            Some(x) => x,
+            None => return,
+        };
+
+        match physical_device.video_capabilities(&decode_h264_profile()) {
+            Err(PhysicalDeviceError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"khr_video_queue") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn video_format_properties_requires_extension() {
+        let instance = instance!();
+
+        let physical_device = match instance
+            .enumerate_physical_devices()
+            .unwrap()
+            .find(|p| !p.supported_extensions().khr_video_queue)
+        {
+            Some(x) => x,
+            None => return,
+        };
+
+        match physical_device.video_format_properties(VideoFormatInfo {
+            image_usage: ImageUsage::VIDEO_DECODE_DST,
+            video_profiles: vec![decode_h264_profile()],
+            ..Default::default()
+        }) {
+            Err(PhysicalDeviceError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"khr_video_queue") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn video_capabilities_decode_h264() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+
+        let video_capabilities = match device
+            .physical_device()
+            .video_capabilities(&decode_h264_profile())
+        {
+            Ok(x) => x,
+            Err(PhysicalDeviceError::VulkanError(_)) => return,
+            Err(_) => panic!(),
+        };
+
+        assert!(video_capabilities.min_coded_extent[0] <= video_capabilities.max_coded_extent[0]);
+        assert!(video_capabilities.min_coded_extent[1] <= video_capabilities.max_coded_extent[1]);
+        assert!(matches!(
+            video_capabilities.codec_capabilities,
+            VideoCodecCapabilities::DecodeH264 { .. },
+        ));
+    }
+
+    #[test]
+    fn video_capabilities_decode_profile_with_encode_hints() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+
+        assert_should_panic!(
+            "the encode hints must be left at their defaults for decode profiles",
+            {
+                let _ = device
+                    .physical_device()
+                    .video_capabilities(&VideoProfileInfo {
+                        encode_usage_hints: VideoEncodeUsage::STREAMING,
+                        ..decode_h264_profile()
+                    });
+            }
+        );
+    }
+
+    #[test]
+    fn video_capabilities_multiple_luma_bit_depths() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+
+        assert_should_panic!("`luma_bit_depth` must have exactly one flag set", {
+            let _ = device
+                .physical_device()
+                .video_capabilities(&VideoProfileInfo {
+                    luma_bit_depth: VideoComponentBitDepth::TYPE_8
+                        | VideoComponentBitDepth::TYPE_10,
+                    ..decode_h264_profile()
+                });
+        });
+    }
+
+    #[test]
+    fn video_capabilities_monochrome_with_chroma_bit_depth() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+
+        assert_should_panic!(
+            "`chroma_bit_depth` must be `None` for monochrome video profiles",
+            {
+                let _ = device
+                    .physical_device()
+                    .video_capabilities(&VideoProfileInfo {
+                        chroma_subsampling: VideoChromaSubsampling::MONOCHROME,
+                        ..decode_h264_profile()
+                    });
+            }
+        );
+    }
+
+    #[test]
+    fn video_format_properties_no_video_profiles() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+
+        assert_should_panic!({
+            let _ = device
+                .physical_device()
+                .video_format_properties(VideoFormatInfo {
+                    image_usage: ImageUsage::VIDEO_DECODE_DST,
+                    ..Default::default()
+                });
+        });
+    }
+
+    #[test]
+    fn video_format_properties_no_video_usage() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+
+        assert_should_panic!({
+            let _ = device
+                .physical_device()
+                .video_format_properties(VideoFormatInfo {
+                    image_usage: ImageUsage::SAMPLED,
+                    video_profiles: vec![decode_h264_profile()],
+                    ..Default::default()
+                });
+        });
+    }
+}
@@ -0,0 +1,1457 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use super::{
+    extension_properties_to_vulkan, null_ptr_if_empty, std_video, VideoCapabilities,
+    VideoCapabilityFlags, VideoCodecOperation, VideoCodecOperations, VideoProfileInfo,
+};
+use crate::{
+    device::{Device, DeviceOwned},
+    format::Format,
+    macros::vulkan_bitflags,
+    memory::{
+        allocator::{
+            AllocationCreateInfo, AllocationCreationError, AllocationType, MemoryAlloc,
+            MemoryAllocator, MemoryUsage,
+        },
+        MemoryRequirements,
+    },
+    ExtensionProperties, RequirementNotMet, RequiresOneOf, VulkanError, VulkanObject,
+};
+use smallvec::SmallVec;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ptr,
+    sync::Arc,
+};
+
+/// An object that holds the state of a video decoder or encoder, and the device memory that the
+/// implementation needs for it.
+#[derive(Debug)]
+pub struct VideoSession {
+    handle: ash::vk::VideoSessionKHR,
+    device: Arc<Device>,
+    id: NonZeroU64,
+
+    queue_family_index: u32,
+    flags: VideoSessionCreateFlags,
+    video_profile: VideoProfileInfo,
+    picture_format: Option<Format>,
+    max_coded_extent: [u32; 2],
+    reference_picture_format: Option<Format>,
+    max_dpb_slots: u32,
+    max_active_reference_pictures: u32,
+    std_header_version: ExtensionProperties,
+    video_capabilities: VideoCapabilities,
+
+    _memory: Vec<MemoryAlloc>,
+}
+
+impl VideoSession {
+    /// Creates a new `VideoSession`, and binds memory allocated from `allocator` to it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.video_profile` is not a valid video profile.
+    pub fn new(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        create_info: VideoSessionCreateInfo,
+    ) -> Result<Arc<VideoSession>, VideoSessionError> {
+        Self::validate_new(allocator.device(), &create_info)?;
+
+        unsafe { Self::new_unchecked(allocator, create_info) }
+    }
+
+    fn validate_new(
+        device: &Device,
+        create_info: &VideoSessionCreateInfo,
+    ) -> Result<(), VideoSessionError> {
+        let &VideoSessionCreateInfo {
+            queue_family_index,
+            flags,
+            ref video_profile,
+            picture_format,
+            max_coded_extent,
+            reference_picture_format,
+            max_dpb_slots,
+            max_active_reference_pictures,
+            ref std_header_version,
+            _ne: _,
+        } = create_info;
+
+        if !device.enabled_extensions().khr_video_queue {
+            return Err(VideoSessionError::RequirementNotMet {
+                required_for: "`VideoSession::new`",
+                requires_one_of: RequiresOneOf {
+                    device_extensions: &["khr_video_queue"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        let physical_device = device.physical_device();
+
+        // VUID-VkVideoSessionCreateInfoKHR-flags-parameter
+        flags.validate_device(device)?;
+
+        // VUID-VkVideoSessionCreateInfoKHR-protectedMemory-07189
+        if flags.intersects(VideoSessionCreateFlags::PROTECTED_CONTENT)
+            && !device.enabled_features().protected_memory
+        {
+            return Err(VideoSessionError::RequirementNotMet {
+                required_for: "`create_info.flags` contains \
+                    `VideoSessionCreateFlags::PROTECTED_CONTENT`",
+                requires_one_of: RequiresOneOf {
+                    features: &["protected_memory"],
+                    ..Default::default()
+                },
+            });
+        }
+
+        // VUID-VkVideoSessionCreateInfoKHR-pVideoProfile-parameter
+        video_profile.validate(physical_device)?;
+
+        let video_codec_operation = video_profile.video_codec_operation();
+
+        // VUID?
+        // The extension of the codec must be enabled, not only supported.
+        video_codec_operation.validate_device(device)?;
+
+        let queue_family_properties = physical_device
+            .queue_family_properties()
+            .get(queue_family_index as usize)
+            .ok_or(VideoSessionError::QueueFamilyIndexOutOfRange {
+                queue_family_index,
+                queue_family_count: physical_device.queue_family_properties().len() as u32,
+            })?;
+
+        // VUID-vkCreateVideoSessionKHR-queueFamilyIndex-04876
+        if !queue_family_properties
+            .video_codec_operations
+            .intersects(VideoCodecOperations::from(video_codec_operation))
+        {
+            return Err(VideoSessionError::QueueFamilyNotSupported {
+                queue_family_index,
+                video_codec_operation,
+            });
+        }
+
+        // VUID-VkVideoSessionCreateInfoKHR-pVideoProfile-04845
+        let video_capabilities = unsafe {
+            physical_device
+                .video_capabilities_unchecked(video_profile)
+                .map_err(|err| match err {
+                    VulkanError::VideoProfileOperationNotSupported
+                    | VulkanError::VideoProfileFormatNotSupported
+                    | VulkanError::VideoProfileCodecNotSupported
+                    | VulkanError::VideoPictureLayoutNotSupported => {
+                        VideoSessionError::VideoProfileNotSupported
+                    }
+                    err => err.into(),
+                })?
+        };
+
+        // VUID-VkVideoSessionCreateInfoKHR-protectedMemory-07189
+        if flags.intersects(VideoSessionCreateFlags::PROTECTED_CONTENT)
+            && !video_capabilities
+                .flags
+                .intersects(VideoCapabilityFlags::PROTECTED_CONTENT)
+        {
+            return Err(VideoSessionError::ProtectedContentNotSupported);
+        }
+
+        // VUID-VkVideoSessionCreateInfoKHR-maxDpbSlots-04847
+        if max_dpb_slots > video_capabilities.max_dpb_slots {
+            return Err(VideoSessionError::MaxDpbSlotsExceeded {
+                provided: max_dpb_slots,
+                max_supported: video_capabilities.max_dpb_slots,
+            });
+        }
+
+        // VUID-VkVideoSessionCreateInfoKHR-maxActiveReferencePictures-04849
+        if max_active_reference_pictures > video_capabilities.max_active_reference_pictures {
+            return Err(VideoSessionError::MaxActiveReferencePicturesExceeded {
+                provided: max_active_reference_pictures,
+                max_supported: video_capabilities.max_active_reference_pictures,
+            });
+        }
+
+        // VUID-VkVideoSessionCreateInfoKHR-maxDpbSlots-04850
+        if (max_dpb_slots == 0) != (max_active_reference_pictures == 0) {
+            return Err(VideoSessionError::ReferencePicturesWithoutDpbSlots);
+        }
+
+        // VUID-VkVideoSessionCreateInfoKHR-maxCodedExtent-04851
+        if max_coded_extent[0] < video_capabilities.min_coded_extent[0]
+            || max_coded_extent[1] < video_capabilities.min_coded_extent[1]
+            || max_coded_extent[0] > video_capabilities.max_coded_extent[0]
+            || max_coded_extent[1] > video_capabilities.max_coded_extent[1]
+        {
+            return Err(VideoSessionError::MaxCodedExtentNotSupported {
+                provided: max_coded_extent,
+                min_supported: video_capabilities.min_coded_extent,
+                max_supported: video_capabilities.max_coded_extent,
+            });
+        }
+
+        if let Some(picture_format) = picture_format {
+            // VUID-VkVideoSessionCreateInfoKHR-pictureFormat-parameter
+            picture_format.validate_device(device)?;
+        }
+
+        if let Some(reference_picture_format) = reference_picture_format {
+            // VUID-VkVideoSessionCreateInfoKHR-referencePictureFormat-parameter
+            reference_picture_format.validate_device(device)?;
+        }
+
+        if let Some(std_header_version) = std_header_version {
+            // VUID-VkVideoSessionCreateInfoKHR-pStdHeaderVersion-07190
+            if std_header_version.extension_name
+                != video_capabilities.std_header_version.extension_name
+            {
+                return Err(VideoSessionError::StdHeaderNameMismatch);
+            }
+
+            // VUID-VkVideoSessionCreateInfoKHR-pStdHeaderVersion-07191
+            if std_header_version.spec_version > video_capabilities.std_header_version.spec_version
+            {
+                return Err(VideoSessionError::StdHeaderVersionNotSupported {
+                    provided: std_header_version.spec_version,
+                    max_supported: video_capabilities.std_header_version.spec_version,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        allocator: &(impl MemoryAllocator + ?Sized),
+        create_info: VideoSessionCreateInfo,
+    ) -> Result<Arc<VideoSession>, VideoSessionError> {
+        let VideoSessionCreateInfo {
+            queue_family_index,
+            flags,
+            video_profile,
+            picture_format,
+            max_coded_extent,
+            reference_picture_format,
+            max_dpb_slots,
+            max_active_reference_pictures,
+            std_header_version,
+            _ne: _,
+        } = create_info;
+
+        let device = allocator.device();
+        let video_capabilities = device
+            .physical_device()
+            .video_capabilities_unchecked(&video_profile)?;
+
+        let std_header_version =
+            std_header_version.unwrap_or_else(|| video_capabilities.std_header_version.clone());
+        let std_header_version_vk = extension_properties_to_vulkan(&std_header_version);
+        let video_profile_vk = video_profile.to_vulkan();
+
+        let create_info_vk = ash::vk::VideoSessionCreateInfoKHR {
+            queue_family_index,
+            flags: flags.into(),
+            p_video_profile: &video_profile_vk.profile_info,
+            picture_format: picture_format.map_or(ash::vk::Format::UNDEFINED, Into::into),
+            max_coded_extent: ash::vk::Extent2D {
+                width: max_coded_extent[0],
+                height: max_coded_extent[1],
+            },
+            reference_picture_format: reference_picture_format
+                .map_or(ash::vk::Format::UNDEFINED, Into::into),
+            max_dpb_slots,
+            max_active_reference_pictures,
+            p_std_header_version: &std_header_version_vk,
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_video_queue.create_video_session_khr)(
+                device.handle(),
+                &create_info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        // From here on, the session is destroyed on drop if anything fails.
+        let mut video_session = VideoSession {
+            handle,
+            device: device.clone(),
+            id: Self::next_id(),
+            queue_family_index,
+            flags,
+            video_profile,
+            picture_format,
+            max_coded_extent,
+            reference_picture_format,
+            max_dpb_slots,
+            max_active_reference_pictures,
+            std_header_version,
+            video_capabilities,
+            _memory: Vec::new(),
+        };
+
+        let memory_requirements = video_session.memory_requirements()?;
+        let mut memory = Vec::with_capacity(memory_requirements.len());
+
+        for (_, requirements) in &memory_requirements {
+            memory.push(allocator.allocate(AllocationCreateInfo {
+                requirements: *requirements,
+                allocation_type: AllocationType::Unknown,
+                usage: MemoryUsage::GpuOnly,
+                ..Default::default()
+            })?);
+        }
+
+        if !memory.is_empty() {
+            let bind_infos_vk: SmallVec<[_; 8]> = memory_requirements
+                .iter()
+                .zip(&memory)
+                .map(
+                    |(&(memory_bind_index, _), alloc)| ash::vk::BindVideoSessionMemoryInfoKHR {
+                        memory_bind_index,
+                        memory: alloc.device_memory().handle(),
+                        memory_offset: alloc.offset(),
+                        memory_size: alloc.size(),
+                        ..Default::default()
+                    },
+                )
+                .collect();
+
+            let fns = device.fns();
+            (fns.khr_video_queue.bind_video_session_memory_khr)(
+                device.handle(),
+                handle,
+                bind_infos_vk.len() as u32,
+                bind_infos_vk.as_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+        }
+
+        video_session._memory = memory;
+
+        Ok(Arc::new(video_session))
+    }
+
+    unsafe fn memory_requirements(&self) -> Result<Vec<(u32, MemoryRequirements)>, VulkanError> {
+        let fns = self.device.fns();
+
+        loop {
+            let mut count = 0;
+            (fns.khr_video_queue
+                .get_video_session_memory_requirements_khr)(
+                self.device.handle(),
+                self.handle,
+                &mut count,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            let mut memory_requirements =
+                vec![ash::vk::VideoSessionMemoryRequirementsKHR::default(); count as usize];
+            let result = (fns
+                .khr_video_queue
+                .get_video_session_memory_requirements_khr)(
+                self.device.handle(),
+                self.handle,
+                &mut count,
+                memory_requirements.as_mut_ptr(),
+            );
+
+            match result {
+                ash::vk::Result::INCOMPLETE => (),
+                ash::vk::Result::SUCCESS => {
+                    memory_requirements.truncate(count as usize);
+
+                    return Ok(memory_requirements
+                        .into_iter()
+                        .map(|requirements| {
+                            (
+                                requirements.memory_bind_index,
+                                MemoryRequirements {
+                                    size: requirements.memory_requirements.size,
+                                    alignment: requirements.memory_requirements.alignment,
+                                    memory_type_bits: requirements
+                                        .memory_requirements
+                                        .memory_type_bits,
+                                    prefers_dedicated_allocation: false,
+                                    requires_dedicated_allocation: false,
+                                },
+                            )
+                        })
+                        .collect());
+                }
+                err => return Err(VulkanError::from(err)),
+            }
+        }
+    }
+
+    /// Returns the index of the queue family that the video session can be used with.
+    #[inline]
+    pub fn queue_family_index(&self) -> u32 {
+        self.queue_family_index
+    }
+
+    /// Returns the flags that the video session was created with.
+    #[inline]
+    pub fn flags(&self) -> VideoSessionCreateFlags {
+        self.flags
+    }
+
+    /// Returns the video profile that the video session was created with.
+    #[inline]
+    pub fn video_profile(&self) -> &VideoProfileInfo {
+        &self.video_profile
+    }
+
+    /// Returns the format of the decoded pictures that the video session was created with.
+    #[inline]
+    pub fn picture_format(&self) -> Option<Format> {
+        self.picture_format
+    }
+
+    /// Returns the maximum coded extent that the video session was created with.
+    #[inline]
+    pub fn max_coded_extent(&self) -> [u32; 2] {
+        self.max_coded_extent
+    }
+
+    /// Returns the format of the reference pictures that the video session was created with.
+    #[inline]
+    pub fn reference_picture_format(&self) -> Option<Format> {
+        self.reference_picture_format
+    }
+
+    /// Returns the number of DPB slots of the video session.
+    #[inline]
+    pub fn max_dpb_slots(&self) -> u32 {
+        self.max_dpb_slots
+    }
+
+    /// Returns the maximum number of reference pictures that a single decode operation can use.
+    #[inline]
+    pub fn max_active_reference_pictures(&self) -> u32 {
+        self.max_active_reference_pictures
+    }
+
+    /// Returns the version of the codec Std header that the video session was created with.
+    #[inline]
+    pub fn std_header_version(&self) -> &ExtensionProperties {
+        &self.std_header_version
+    }
+
+    /// Returns the capabilities of the physical device for the video profile of the video
+    /// session.
+    #[inline]
+    pub fn video_capabilities(&self) -> &VideoCapabilities {
+        &self.video_capabilities
+    }
+}
+
+impl Drop for VideoSession {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.khr_video_queue.destroy_video_session_khr)(
+                self.device.handle(),
+                self.handle,
+                ptr::null(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for VideoSession {
+    type Handle = ash::vk::VideoSessionKHR;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for VideoSession {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+crate::impl_id_counter!(VideoSession);
+
+/// Parameters to create a new `VideoSession`.
+#[derive(Clone, Debug)]
+pub struct VideoSessionCreateInfo {
+    /// The index of the queue family that the video session will be used with.
+    ///
+    /// The queue family must support the video coding operation of `video_profile`.
+    ///
+    /// The default value is `0`.
+    pub queue_family_index: u32,
+
+    /// Additional properties of the video session.
+    ///
+    /// The default value is empty.
+    pub flags: VideoSessionCreateFlags,
+
+    /// The video profile that the video session will decode or encode.
+    ///
+    /// There is no default value.
+    pub video_profile: VideoProfileInfo,
+
+    /// The format of the images that the decoded pictures are written to, or that the pictures
+    /// to encode are read from.
+    ///
+    /// The default value is `None`.
+    pub picture_format: Option<Format>,
+
+    /// The largest coded extent of the pictures that the video session will decode or encode.
+    ///
+    /// The default value is `[0; 2]`, which must be overridden.
+    pub max_coded_extent: [u32; 2],
+
+    /// The format of the images that the reference pictures are stored in.
+    ///
+    /// The default value is `None`.
+    pub reference_picture_format: Option<Format>,
+
+    /// The number of DPB slots of the video session.
+    ///
+    /// The default value is `0`.
+    pub max_dpb_slots: u32,
+
+    /// The maximum number of reference pictures that a single video coding operation can use.
+    ///
+    /// This must be `0` if and only if `max_dpb_slots` is `0`.
+    ///
+    /// The default value is `0`.
+    pub max_active_reference_pictures: u32,
+
+    /// The name and version of the codec Std header that the video session is used with.
+    ///
+    /// If set to `None`, the latest version supported by the implementation is used, as given by
+    /// [`VideoCapabilities::std_header_version`].
+    ///
+    /// The default value is `None`.
+    pub std_header_version: Option<ExtensionProperties>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl VideoSessionCreateInfo {
+    /// Returns a `VideoSessionCreateInfo` with the specified `video_profile`.
+    #[inline]
+    pub fn video_profile(video_profile: VideoProfileInfo) -> Self {
+        Self {
+            queue_family_index: 0,
+            flags: VideoSessionCreateFlags::empty(),
+            video_profile,
+            picture_format: None,
+            max_coded_extent: [0; 2],
+            reference_picture_format: None,
+            max_dpb_slots: 0,
+            max_active_reference_pictures: 0,
+            std_header_version: None,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control the creation of a video session.
+    VideoSessionCreateFlags = VideoSessionCreateFlagsKHR(u32);
+
+    /// The video session decodes or encodes protected content.
+    ///
+    /// The [`PROTECTED_CONTENT`](VideoCapabilityFlags::PROTECTED_CONTENT) capability flag must
+    /// be supported for the video profile.
+    PROTECTED_CONTENT = PROTECTED_CONTENT,
+}
+
+/// An object that holds the codec parameter sets of a video stream, such as the SPS and PPS of an
+/// H.264 stream, for use with a video session.
+#[derive(Debug)]
+pub struct VideoSessionParameters {
+    handle: ash::vk::VideoSessionParametersKHR,
+    video_session: Arc<VideoSession>,
+    id: NonZeroU64,
+
+    video_codec_operation: VideoCodecOperation,
+}
+
+impl VideoSessionParameters {
+    /// Creates a new `VideoSessionParameters` for `video_session`.
+    ///
+    /// # Safety
+    ///
+    /// - The codec parameter sets in `create_info` must be valid, and any pointers that they
+    ///   contain must be valid for the duration of the call.
+    pub unsafe fn new(
+        video_session: Arc<VideoSession>,
+        create_info: VideoSessionParametersCreateInfo,
+    ) -> Result<Arc<VideoSessionParameters>, VideoSessionError> {
+        Self::validate_new(&video_session, &create_info)?;
+
+        Self::new_unchecked(video_session, create_info)
+    }
+
+    fn validate_new(
+        video_session: &VideoSession,
+        create_info: &VideoSessionParametersCreateInfo,
+    ) -> Result<(), VideoSessionError> {
+        let VideoSessionParametersCreateInfo {
+            template,
+            codec,
+            _ne: _,
+        } = create_info;
+
+        if let Some(template) = template {
+            // VUID-VkVideoSessionParametersCreateInfoKHR-videoSessionParametersTemplate-04855
+            if template.video_session.handle() != video_session.handle() {
+                return Err(VideoSessionError::TemplateVideoSessionMismatch);
+            }
+        }
+
+        // VUID-VkVideoSessionParametersCreateInfoKHR-videoSession-04858
+        // VUID-VkVideoSessionParametersCreateInfoKHR-videoSession-04859
+        if codec.video_codec_operation() != video_session.video_profile.video_codec_operation() {
+            return Err(VideoSessionError::CodecMismatch);
+        }
+
+        match codec {
+            VideoSessionParametersCodecCreateInfo::DecodeH264 {
+                max_std_sps_count,
+                max_std_pps_count,
+                std_spss,
+                std_ppss,
+            }
+            | VideoSessionParametersCodecCreateInfo::EncodeH264 {
+                max_std_sps_count,
+                max_std_pps_count,
+                std_spss,
+                std_ppss,
+            } => {
+                // VUID-VkVideoSessionParametersCreateInfoKHR-videoSession-04859
+                // VUID-VkVideoSessionParametersCreateInfoKHR-videoSession-04861
+                if std_spss.len() as u32 > *max_std_sps_count
+                    || std_ppss.len() as u32 > *max_std_pps_count
+                {
+                    return Err(VideoSessionError::TooManyParameterSets);
+                }
+            }
+            VideoSessionParametersCodecCreateInfo::DecodeH265 {
+                max_std_vps_count,
+                max_std_sps_count,
+                max_std_pps_count,
+                std_vpss,
+                std_spss,
+                std_ppss,
+            }
+            | VideoSessionParametersCodecCreateInfo::EncodeH265 {
+                max_std_vps_count,
+                max_std_sps_count,
+                max_std_pps_count,
+                std_vpss,
+                std_spss,
+                std_ppss,
+            } => {
+                // VUID-VkVideoSessionParametersCreateInfoKHR-videoSession-04860
+                // VUID-VkVideoSessionParametersCreateInfoKHR-videoSession-04862
+                if std_vpss.len() as u32 > *max_std_vps_count
+                    || std_spss.len() as u32 > *max_std_sps_count
+                    || std_ppss.len() as u32 > *max_std_pps_count
+                {
+                    return Err(VideoSessionError::TooManyParameterSets);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        video_session: Arc<VideoSession>,
+        create_info: VideoSessionParametersCreateInfo,
+    ) -> Result<Arc<VideoSessionParameters>, VideoSessionError> {
+        let VideoSessionParametersCreateInfo {
+            template,
+            codec,
+            _ne: _,
+        } = create_info;
+
+        let mut create_info_vk = ash::vk::VideoSessionParametersCreateInfoKHR {
+            flags: ash::vk::VideoSessionParametersCreateFlagsKHR::empty(),
+            video_session_parameters_template: template
+                .as_ref()
+                .map_or_else(Default::default, |template| template.handle),
+            video_session: video_session.handle,
+            ..Default::default()
+        };
+        let mut h264_add_info_vk = None;
+        let mut h264_create_info_vk = None;
+        let mut h265_add_info_vk = None;
+        let mut h265_create_info_vk = None;
+        let mut encode_h264_add_info_vk = None;
+        let mut encode_h264_create_info_vk = None;
+        let mut encode_h265_add_info_vk = None;
+        let mut encode_h265_create_info_vk = None;
+
+        match &codec {
+            VideoSessionParametersCodecCreateInfo::DecodeH264 {
+                max_std_sps_count,
+                max_std_pps_count,
+                std_spss,
+                std_ppss,
+            } => {
+                let add_info_vk =
+                    h264_add_info_vk.insert(ash::vk::VideoDecodeH264SessionParametersAddInfoEXT {
+                        std_sps_count: std_spss.len() as u32,
+                        p_std_sp_ss: null_ptr_if_empty(std_spss),
+                        std_pps_count: std_ppss.len() as u32,
+                        p_std_pp_ss: null_ptr_if_empty(std_ppss),
+                        ..Default::default()
+                    });
+                let next = h264_create_info_vk.insert(
+                    ash::vk::VideoDecodeH264SessionParametersCreateInfoEXT {
+                        max_std_sps_count: *max_std_sps_count,
+                        max_std_pps_count: *max_std_pps_count,
+                        p_parameters_add_info: add_info_vk,
+                        ..Default::default()
+                    },
+                );
+
+                next.p_next = create_info_vk.p_next;
+                create_info_vk.p_next = next as *const _ as *const _;
+            }
+            VideoSessionParametersCodecCreateInfo::DecodeH265 {
+                max_std_vps_count,
+                max_std_sps_count,
+                max_std_pps_count,
+                std_vpss,
+                std_spss,
+                std_ppss,
+            } => {
+                let add_info_vk =
+                    h265_add_info_vk.insert(ash::vk::VideoDecodeH265SessionParametersAddInfoEXT {
+                        std_vps_count: std_vpss.len() as u32,
+                        p_std_vp_ss: null_ptr_if_empty(std_vpss),
+                        std_sps_count: std_spss.len() as u32,
+                        p_std_sp_ss: null_ptr_if_empty(std_spss),
+                        std_pps_count: std_ppss.len() as u32,
+                        p_std_pp_ss: null_ptr_if_empty(std_ppss),
+                        ..Default::default()
+                    });
+                let next = h265_create_info_vk.insert(
+                    ash::vk::VideoDecodeH265SessionParametersCreateInfoEXT {
+                        max_std_vps_count: *max_std_vps_count,
+                        max_std_sps_count: *max_std_sps_count,
+                        max_std_pps_count: *max_std_pps_count,
+                        p_parameters_add_info: add_info_vk,
+                        ..Default::default()
+                    },
+                );
+
+                next.p_next = create_info_vk.p_next;
+                create_info_vk.p_next = next as *const _ as *const _;
+            }
+            VideoSessionParametersCodecCreateInfo::EncodeH264 {
+                max_std_sps_count,
+                max_std_pps_count,
+                std_spss,
+                std_ppss,
+            } => {
+                let add_info_vk = encode_h264_add_info_vk.insert(
+                    ash::vk::VideoEncodeH264SessionParametersAddInfoEXT {
+                        std_sps_count: std_spss.len() as u32,
+                        p_std_sp_ss: null_ptr_if_empty(std_spss),
+                        std_pps_count: std_ppss.len() as u32,
+                        p_std_pp_ss: null_ptr_if_empty(std_ppss),
+                        ..Default::default()
+                    },
+                );
+                let next = encode_h264_create_info_vk.insert(
+                    ash::vk::VideoEncodeH264SessionParametersCreateInfoEXT {
+                        max_std_sps_count: *max_std_sps_count,
+                        max_std_pps_count: *max_std_pps_count,
+                        p_parameters_add_info: add_info_vk,
+                        ..Default::default()
+                    },
+                );
+
+                next.p_next = create_info_vk.p_next;
+                create_info_vk.p_next = next as *const _ as *const _;
+            }
+            VideoSessionParametersCodecCreateInfo::EncodeH265 {
+                max_std_vps_count,
+                max_std_sps_count,
+                max_std_pps_count,
+                std_vpss,
+                std_spss,
+                std_ppss,
+            } => {
+                let add_info_vk = encode_h265_add_info_vk.insert(
+                    ash::vk::VideoEncodeH265SessionParametersAddInfoEXT {
+                        std_vps_count: std_vpss.len() as u32,
+                        p_std_vp_ss: null_ptr_if_empty(std_vpss),
+                        std_sps_count: std_spss.len() as u32,
+                        p_std_sp_ss: null_ptr_if_empty(std_spss),
+                        std_pps_count: std_ppss.len() as u32,
+                        p_std_pp_ss: null_ptr_if_empty(std_ppss),
+                        ..Default::default()
+                    },
+                );
+                let next = encode_h265_create_info_vk.insert(
+                    ash::vk::VideoEncodeH265SessionParametersCreateInfoEXT {
+                        max_std_vps_count: *max_std_vps_count,
+                        max_std_sps_count: *max_std_sps_count,
+                        max_std_pps_count: *max_std_pps_count,
+                        p_parameters_add_info: add_info_vk,
+                        ..Default::default()
+                    },
+                );
+
+                next.p_next = create_info_vk.p_next;
+                create_info_vk.p_next = next as *const _ as *const _;
+            }
+        }
+
+        let handle = {
+            let device = video_session.device();
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.khr_video_queue.create_video_session_parameters_khr)(
+                device.handle(),
+                &create_info_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(VideoSessionParameters {
+            handle,
+            video_session,
+            id: Self::next_id(),
+            video_codec_operation: codec.video_codec_operation(),
+        }))
+    }
+
+    /// Returns the video session that the parameters were created for.
+    #[inline]
+    pub fn video_session(&self) -> &Arc<VideoSession> {
+        &self.video_session
+    }
+
+    /// Returns the video coding operation that the parameters are for.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        self.video_codec_operation
+    }
+}
+
+impl Drop for VideoSessionParameters {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let device = self.video_session.device();
+            let fns = device.fns();
+            (fns.khr_video_queue.destroy_video_session_parameters_khr)(
+                device.handle(),
+                self.handle,
+                ptr::null(),
+            );
+        }
+    }
+}
+
+unsafe impl VulkanObject for VideoSessionParameters {
+    type Handle = ash::vk::VideoSessionParametersKHR;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for VideoSessionParameters {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.video_session.device()
+    }
+}
+
+crate::impl_id_counter!(VideoSessionParameters);
+
+/// Parameters to create a new `VideoSessionParameters`.
+#[derive(Clone, Debug)]
+pub struct VideoSessionParametersCreateInfo {
+    /// An existing `VideoSessionParameters` object, for the same video session, to copy the
+    /// parameter sets from. Parameter sets in `codec` replace those with the same ID in the
+    /// template.
+    ///
+    /// The default value is `None`.
+    pub template: Option<Arc<VideoSessionParameters>>,
+
+    /// The codec-specific parameter sets.
+    ///
+    /// There is no default value.
+    pub codec: VideoSessionParametersCodecCreateInfo,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl VideoSessionParametersCreateInfo {
+    /// Returns a `VideoSessionParametersCreateInfo` with the specified `codec`.
+    #[inline]
+    pub fn codec(codec: VideoSessionParametersCodecCreateInfo) -> Self {
+        Self {
+            template: None,
+            codec,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// The codec-specific parameter sets of a `VideoSessionParameters`.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum VideoSessionParametersCodecCreateInfo {
+    /// H.264 sequence and picture parameter sets, for decoding.
+    DecodeH264 {
+        /// The maximum number of SPSs that the object can hold.
+        max_std_sps_count: u32,
+
+        /// The maximum number of PPSs that the object can hold.
+        max_std_pps_count: u32,
+
+        /// The SPSs to add.
+        std_spss: Vec<std_video::StdVideoH264SequenceParameterSet>,
+
+        /// The PPSs to add.
+        std_ppss: Vec<std_video::StdVideoH264PictureParameterSet>,
+    },
+
+    /// H.265 video, sequence and picture parameter sets, for decoding.
+    DecodeH265 {
+        /// The maximum number of VPSs that the object can hold.
+        max_std_vps_count: u32,
+
+        /// The maximum number of SPSs that the object can hold.
+        max_std_sps_count: u32,
+
+        /// The maximum number of PPSs that the object can hold.
+        max_std_pps_count: u32,
+
+        /// The VPSs to add.
+        std_vpss: Vec<std_video::StdVideoH265VideoParameterSet>,
+
+        /// The SPSs to add.
+        std_spss: Vec<std_video::StdVideoH265SequenceParameterSet>,
+
+        /// The PPSs to add.
+        std_ppss: Vec<std_video::StdVideoH265PictureParameterSet>,
+    },
+
+    /// H.264 sequence and picture parameter sets, for encoding.
+    EncodeH264 {
+        /// The maximum number of SPSs that the object can hold.
+        max_std_sps_count: u32,
+
+        /// The maximum number of PPSs that the object can hold.
+        max_std_pps_count: u32,
+
+        /// The SPSs to add.
+        std_spss: Vec<std_video::StdVideoH264SequenceParameterSet>,
+
+        /// The PPSs to add.
+        std_ppss: Vec<std_video::StdVideoH264PictureParameterSet>,
+    },
+
+    /// H.265 video, sequence and picture parameter sets, for encoding.
+    EncodeH265 {
+        /// The maximum number of VPSs that the object can hold.
+        max_std_vps_count: u32,
+
+        /// The maximum number of SPSs that the object can hold.
+        max_std_sps_count: u32,
+
+        /// The maximum number of PPSs that the object can hold.
+        max_std_pps_count: u32,
+
+        /// The VPSs to add.
+        std_vpss: Vec<std_video::StdVideoH265VideoParameterSet>,
+
+        /// The SPSs to add.
+        std_spss: Vec<std_video::StdVideoH265SequenceParameterSet>,
+
+        /// The PPSs to add.
+        std_ppss: Vec<std_video::StdVideoH265PictureParameterSet>,
+    },
+}
+
+impl VideoSessionParametersCodecCreateInfo {
+    /// Returns the video coding operation that the parameter sets are for.
+    #[inline]
+    pub fn video_codec_operation(&self) -> VideoCodecOperation {
+        match self {
+            Self::DecodeH264 { .. } => VideoCodecOperation::DecodeH264,
+            Self::DecodeH265 { .. } => VideoCodecOperation::DecodeH265,
+            Self::EncodeH264 { .. } => VideoCodecOperation::EncodeH264,
+            Self::EncodeH265 { .. } => VideoCodecOperation::EncodeH265,
+        }
+    }
+}
+
+/// Error that can happen when creating a video session or video session parameters.
+#[derive(Clone, Debug)]
+pub enum VideoSessionError {
+    VulkanError(VulkanError),
+
+    /// Allocating memory for the video session failed.
+    AllocError(AllocationCreationError),
+
+    RequirementNotMet {
+        required_for: &'static str,
+        requires_one_of: RequiresOneOf,
+    },
+
+    /// The codec of the parameter sets does not match the codec of the video session.
+    CodecMismatch,
+
+    /// The requested maximum coded extent is not within the range supported for the video
+    /// profile.
+    MaxCodedExtentNotSupported {
+        provided: [u32; 2],
+        min_supported: [u32; 2],
+        max_supported: [u32; 2],
+    },
+
+    /// The requested number of active reference pictures exceeds the maximum supported for the
+    /// video profile.
+    MaxActiveReferencePicturesExceeded {
+        provided: u32,
+        max_supported: u32,
+    },
+
+    /// The requested number of DPB slots exceeds the maximum supported for the video profile.
+    MaxDpbSlotsExceeded {
+        provided: u32,
+        max_supported: u32,
+    },
+
+    /// The `PROTECTED_CONTENT` flag was set, but the video profile does not support protected
+    /// content.
+    ProtectedContentNotSupported,
+
+    /// The queue family index is not less than the number of queue families of the physical
+    /// device.
+    QueueFamilyIndexOutOfRange {
+        queue_family_index: u32,
+        queue_family_count: u32,
+    },
+
+    /// The queue family does not support the video coding operation of the video profile.
+    QueueFamilyNotSupported {
+        queue_family_index: u32,
+        video_codec_operation: VideoCodecOperation,
+    },
+
+    /// Only one of `max_dpb_slots` and `max_active_reference_pictures` was zero.
+    ReferencePicturesWithoutDpbSlots,
+
+    /// The name of the provided codec Std header does not match the one of the video profile.
+    StdHeaderNameMismatch,
+
+    /// The version of the provided codec Std header is newer than the supported version.
+    StdHeaderVersionNotSupported {
+        provided: u32,
+        max_supported: u32,
+    },
+
+    /// The template belongs to a different video session.
+    TemplateVideoSessionMismatch,
+
+    /// More parameter sets were provided than the maximum number that the object can hold.
+    TooManyParameterSets,
+
+    /// The video profile is not supported by the physical device.
+    VideoProfileNotSupported,
+}
+
+impl Error for VideoSessionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::VulkanError(err) => Some(err),
+            Self::AllocError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for VideoSessionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::VulkanError(_) => write!(f, "a runtime error occurred"),
+            Self::AllocError(_) => write!(f, "allocating memory failed"),
+            Self::RequirementNotMet {
+                required_for,
+                requires_one_of,
+            } => write!(
+                f,
+                "a requirement was not met for: {}; requires one of: {}",
+                required_for, requires_one_of,
+            ),
+            Self::CodecMismatch => write!(
+                f,
+                "the codec of the parameter sets does not match the codec of the video session",
+            ),
+            Self::MaxCodedExtentNotSupported {
+                provided,
+                min_supported,
+                max_supported,
+            } => write!(
+                f,
+                "the requested maximum coded extent ({:?}) is not between the minimum ({:?}) and \
+                maximum ({:?}) supported for the video profile",
+                provided, min_supported, max_supported,
+            ),
+            Self::MaxActiveReferencePicturesExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the requested number of active reference pictures ({}) exceeds the maximum \
+                supported for the video profile ({})",
+                provided, max_supported,
+            ),
+            Self::MaxDpbSlotsExceeded {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the requested number of DPB slots ({}) exceeds the maximum supported for the \
+                video profile ({})",
+                provided, max_supported,
+            ),
+            Self::ProtectedContentNotSupported => write!(
+                f,
+                "the `PROTECTED_CONTENT` flag was set, but the video profile does not support \
+                protected content",
+            ),
+            Self::QueueFamilyIndexOutOfRange {
+                queue_family_index,
+                queue_family_count,
+            } => write!(
+                f,
+                "the queue family index ({}) is not less than the number of queue families ({})",
+                queue_family_index, queue_family_count,
+            ),
+            Self::QueueFamilyNotSupported {
+                queue_family_index,
+                video_codec_operation,
+            } => write!(
+                f,
+                "the queue family {} does not support the video coding operation {:?}",
+                queue_family_index, video_codec_operation,
+            ),
+            Self::ReferencePicturesWithoutDpbSlots => write!(
+                f,
+                "only one of `max_dpb_slots` and `max_active_reference_pictures` was zero",
+            ),
+            Self::StdHeaderNameMismatch => write!(
+                f,
+                "the name of the provided codec Std header does not match the one of the video \
+                profile",
+            ),
+            Self::StdHeaderVersionNotSupported {
+                provided,
+                max_supported,
+            } => write!(
+                f,
+                "the version of the provided codec Std header ({}) is newer than the supported \
+                version ({})",
+                provided, max_supported,
+            ),
+            Self::TemplateVideoSessionMismatch => {
+                write!(f, "the template belongs to a different video session")
+            }
+            Self::TooManyParameterSets => write!(
+                f,
+                "more parameter sets were provided than the maximum number that the object can \
+                hold",
+            ),
+            Self::VideoProfileNotSupported => {
+                write!(
+                    f,
+                    "the video profile is not supported by the physical device"
+                )
+            }
+        }
+    }
+}
+
+impl From<VulkanError> for VideoSessionError {
+    fn from(err: VulkanError) -> Self {
+        Self::VulkanError(err)
+    }
+}
+
+impl From<AllocationCreationError> for VideoSessionError {
+    fn from(err: AllocationCreationError) -> Self {
+        Self::AllocError(err)
+    }
+}
+
+impl From<RequirementNotMet> for VideoSessionError {
+    fn from(err: RequirementNotMet) -> Self {
+        Self::RequirementNotMet {
+            required_for: err.required_for,
+            requires_one_of: err.requires_one_of,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        memory::allocator::StandardMemoryAllocator,
+        video::{
+            H264ProfileIdc, VideoChromaSubsampling, VideoCodecProfileInfo, VideoComponentBitDepth,
+            VideoDecodeH264PictureLayout, VideoDecodeUsage,
+        },
+    };
+
+    fn decode_h264_profile() -> VideoProfileInfo {
+        VideoProfileInfo::new(VideoCodecProfileInfo::DecodeH264 {
+            std_profile_idc: H264ProfileIdc::Main,
+            picture_layout: VideoDecodeH264PictureLayout::Progressive,
+        })
+    }
+
+    #[test]
+    fn create_requires_video_queue() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        match VideoSession::new(
+            &memory_allocator,
+            VideoSessionCreateInfo {
+                queue_family_index: queue.queue_family_index(),
+                max_coded_extent: [64, 64],
+                ..VideoSessionCreateInfo::video_profile(decode_h264_profile())
+            },
+        ) {
+            Err(VideoSessionError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"khr_video_queue") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn create_requires_codec_extension() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [khr_synchronization2, khr_video_queue],
+            features: []
+        );
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        match VideoSession::new(
+            &memory_allocator,
+            VideoSessionCreateInfo {
+                queue_family_index: queue.queue_family_index(),
+                max_coded_extent: [64, 64],
+                ..VideoSessionCreateInfo::video_profile(decode_h264_profile())
+            },
+        ) {
+            Err(VideoSessionError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"ext_video_decode_h264") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn create_encode_requires_codec_extension() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [khr_synchronization2, khr_video_queue, khr_video_encode_queue],
+            features: []
+        );
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        match VideoSession::new(
+            &memory_allocator,
+            VideoSessionCreateInfo {
+                queue_family_index: queue.queue_family_index(),
+                max_coded_extent: [64, 64],
+                ..VideoSessionCreateInfo::video_profile(VideoProfileInfo::new(
+                    VideoCodecProfileInfo::EncodeH264 {
+                        std_profile_idc: H264ProfileIdc::Main,
+                    },
+                ))
+            },
+        ) {
+            Err(VideoSessionError::RequirementNotMet {
+                requires_one_of:
+                    RequiresOneOf {
+                        device_extensions, ..
+                    },
+                ..
+            }) if device_extensions.contains(&"ext_video_encode_h264") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn create_encode_with_decode_hints() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_encode_queue,
+                ext_video_encode_h264
+            ],
+            features: []
+        );
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        assert_should_panic!("`decode_usage_hints` must be empty for encode profiles", {
+            let _ = VideoSession::new(
+                &memory_allocator,
+                VideoSessionCreateInfo {
+                    queue_family_index: queue.queue_family_index(),
+                    max_coded_extent: [64, 64],
+                    ..VideoSessionCreateInfo::video_profile(VideoProfileInfo {
+                        decode_usage_hints: VideoDecodeUsage::TRANSCODING,
+                        ..VideoProfileInfo::new(VideoCodecProfileInfo::EncodeH264 {
+                            std_profile_idc: H264ProfileIdc::Main,
+                        })
+                    })
+                },
+            );
+        });
+    }
+
+    #[test]
+    fn create_protected_requires_feature() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+        let memory_allocator = StandardMemoryAllocator::new_default(device);
+
+        match VideoSession::new(
+            &memory_allocator,
+            VideoSessionCreateInfo {
+                queue_family_index: queue.queue_family_index(),
+                flags: VideoSessionCreateFlags::PROTECTED_CONTENT,
+                max_coded_extent: [64, 64],
+                ..VideoSessionCreateInfo::video_profile(decode_h264_profile())
+            },
+        ) {
+            Err(VideoSessionError::RequirementNotMet {
+                requires_one_of: RequiresOneOf { features, .. },
+                ..
+            }) if features.contains(&"protected_memory") => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn create_unsupported_profile() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                khr_synchronization2,
+                khr_video_queue,
+                khr_video_decode_queue,
+                ext_video_decode_h264
+            ],
+            features: []
+        );
+        let physical_device = device.physical_device();
+
+        let queue_family_index =
+            match physical_device
+                .queue_family_properties()
+                .iter()
+                .position(|properties| {
+                    properties
+                        .video_codec_operations
+                        .intersects(VideoCodecOperations::DECODE_H264)
+                }) {
+                Some(x) => x as u32,
+                None => return,
+            };
+
+        // 12-bit 4:4:4 is not something that H.264 hardware decoders usually support.
+        let video_profile = VideoProfileInfo {
+            chroma_subsampling: VideoChromaSubsampling::TYPE_444,
+            luma_bit_depth: VideoComponentBitDepth::TYPE_12,
+            chroma_bit_depth: Some(VideoComponentBitDepth::TYPE_12),
+            ..VideoProfileInfo::new(VideoCodecProfileInfo::DecodeH264 {
+                std_profile_idc: H264ProfileIdc::High444Predictive,
+                picture_layout: VideoDecodeH264PictureLayout::Progressive,
+            })
+        };
+
+        if physical_device.video_capabilities(&video_profile).is_ok() {
+            return;
+        }
+
+        let memory_allocator = StandardMemoryAllocator::new_default(device.clone());
+
+        match VideoSession::new(
+            &memory_allocator,
+            VideoSessionCreateInfo {
+                queue_family_index,
+                max_coded_extent: [64, 64],
+                ..VideoSessionCreateInfo::video_profile(video_profile)
+            },
+        ) {
+            Err(VideoSessionError::VideoProfileNotSupported) => (),
+            _ => panic!(),
+        }
+    }
+}